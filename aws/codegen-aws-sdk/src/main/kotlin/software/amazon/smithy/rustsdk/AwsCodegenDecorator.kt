@@ -27,14 +27,24 @@ import software.amazon.smithy.rustsdk.customize.onlyApplyTo
 import software.amazon.smithy.rustsdk.customize.onlyApplyToList
 import software.amazon.smithy.rustsdk.customize.rds.RdsDecorator
 import software.amazon.smithy.rustsdk.customize.route53.Route53Decorator
+import software.amazon.smithy.rustsdk.customize.s3.S3AccessGrantsDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3AddressingStyleDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3BatchDeleteDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3CrossRegionRedirectDecorator
 import software.amazon.smithy.rustsdk.customize.s3.S3Decorator
+import software.amazon.smithy.rustsdk.customize.s3.S3DownloadDecorator
 import software.amazon.smithy.rustsdk.customize.s3.S3ExpiresDecorator
 import software.amazon.smithy.rustsdk.customize.s3.S3ExpressDecorator
 import software.amazon.smithy.rustsdk.customize.s3.S3ExtendedRequestIdDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3ObjectLambdaDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3RequestValidationDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3SeekableReaderDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3ServerSideEncryptionDefaultsDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3UploadDecorator
+import software.amazon.smithy.rustsdk.customize.s3.S3VpcEndpointDecorator
 import software.amazon.smithy.rustsdk.customize.s3control.S3ControlDecorator
 import software.amazon.smithy.rustsdk.customize.sso.SSODecorator
 import software.amazon.smithy.rustsdk.customize.sts.STSDecorator
-import software.amazon.smithy.rustsdk.customize.timestream.TimestreamDecorator
 import software.amazon.smithy.rustsdk.endpoints.AwsEndpointsStdLib
 import software.amazon.smithy.rustsdk.endpoints.OperationInputTestDecorator
 import software.amazon.smithy.rustsdk.endpoints.RequireEndpointRules
@@ -63,6 +73,7 @@ val DECORATORS: List<ClientCodegenDecorator> =
             GenericSmithySdkConfigSettings(),
             OperationInputTestDecorator(),
             AwsRequestIdDecorator(),
+            SigningPropertiesDecorator(),
             DisabledAuthDecorator(),
             RecursionDetectionDecorator(),
             InvocationIdDecorator(),
@@ -76,6 +87,7 @@ val DECORATORS: List<ClientCodegenDecorator> =
             Sigv4aAuthTraitBackfillDecorator(),
             EndpointBasedAuthSchemeDecorator(),
             SpanDecorator(),
+            EndpointDiscoveryDecorator(),
             // TODO(https://github.com/smithy-lang/smithy-rs/issues/3863): Comment in once the issue has been resolved
             // SmokeTestsDecorator(),
         ),
@@ -84,6 +96,8 @@ val DECORATORS: List<ClientCodegenDecorator> =
         // Therefore, that decorator is applied to all but S3, and S3 customizes the creation of `AwsErrorCodeClassifier`
         // accordingly (see https://github.com/smithy-lang/smithy-rs/pull/3699).
         RetryClassifierDecorator().applyExceptFor("com.amazonaws.s3#AmazonS3"),
+        // S3 removes the bucket from the path of requests, so passthrough needs to add it back
+        EndpointUrlPassthroughDecorator().applyExceptFor("com.amazonaws.s3#AmazonS3"),
         // Service specific decorators
         ApiGatewayDecorator().onlyApplyTo("com.amazonaws.apigateway#BackplaneControlService"),
         DsqlDecorator().onlyApplyTo("com.amazonaws.dsql#DSQL"),
@@ -97,12 +111,22 @@ val DECORATORS: List<ClientCodegenDecorator> =
             S3ExtendedRequestIdDecorator(),
             IsTruncatedPaginatorDecorator(),
             S3ExpiresDecorator(),
+            S3VpcEndpointDecorator(),
+            EndpointUrlPassthroughDecorator(bucketInPath = true),
+            S3CrossRegionRedirectDecorator(),
+            S3AddressingStyleDecorator(),
+            S3SeekableReaderDecorator(),
+            S3UploadDecorator(),
+            S3DownloadDecorator(),
+            S3AccessGrantsDecorator(),
+            S3ObjectLambdaDecorator(),
+            S3ServerSideEncryptionDefaultsDecorator(),
+            S3BatchDeleteDecorator(),
+            S3RequestValidationDecorator(),
         ),
         S3ControlDecorator().onlyApplyTo("com.amazonaws.s3control#AWSS3ControlServiceV20180820"),
         STSDecorator().onlyApplyTo("com.amazonaws.sts#AWSSecurityTokenServiceV20110615"),
         SSODecorator().onlyApplyTo("com.amazonaws.sso#SWBPortalService"),
-        TimestreamDecorator().onlyApplyTo("com.amazonaws.timestreamwrite#Timestream_20181101"),
-        TimestreamDecorator().onlyApplyTo("com.amazonaws.timestreamquery#Timestream_20181101"),
         listOf("bedrock").map { EnvironmentTokenProviderDecorator(it) },
         // Only build docs-rs for linux to reduce load on docs.rs
         listOf(
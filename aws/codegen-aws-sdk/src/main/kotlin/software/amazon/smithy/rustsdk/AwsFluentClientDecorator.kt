@@ -31,6 +31,8 @@ import software.amazon.smithy.rust.codegen.core.smithy.generators.LibRsSection
 import software.amazon.smithy.rust.codegen.core.smithy.generators.protocol.ProtocolTestGenerator
 import software.amazon.smithy.rust.codegen.core.util.letIf
 import software.amazon.smithy.rust.codegen.core.util.serviceNameOrDefault
+import software.amazon.smithy.rustsdk.customize.s3.S3AccessGrantsFluentClientCustomization
+import software.amazon.smithy.rustsdk.customize.s3.S3CrossRegionRedirectFluentClientCustomization
 import software.amazon.smithy.rustsdk.customize.s3.S3ExpressFluentClientCustomization
 
 private class Types(runtimeConfig: RuntimeConfig) {
@@ -61,7 +63,9 @@ class AwsFluentClientDecorator : ClientCodegenDecorator {
                     AwsFluentClientDocs(codegenContext),
                     AwsFluentClientRetryPartition(codegenContext),
                 ).letIf(codegenContext.serviceShape.id == ShapeId.from("com.amazonaws.s3#AmazonS3")) {
-                    it + S3ExpressFluentClientCustomization(codegenContext)
+                    it + S3ExpressFluentClientCustomization(codegenContext) +
+                        S3AccessGrantsFluentClientCustomization(codegenContext) +
+                        S3CrossRegionRedirectFluentClientCustomization(codegenContext)
                 },
         ).render(rustCrate)
         rustCrate.withModule(ClientRustModule.client) {
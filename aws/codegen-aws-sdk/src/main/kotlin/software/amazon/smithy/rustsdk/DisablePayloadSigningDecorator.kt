@@ -46,9 +46,16 @@ class DisablePayloadSigningDecorator : ClientCodegenDecorator {
                                         """
                                         /// Disable payload signing for this request.
                                         ///
+                                        /// The request is signed with `UNSIGNED-PAYLOAD` instead of
+                                        /// the SHA-256 of its body, which removes the cost of hashing
+                                        /// large bodies. Unless request checksums are only calculated
+                                        /// when required, a checksum of the body is still sent: as a
+                                        /// signed header for in-memory bodies, and as an `aws-chunked`
+                                        /// trailer for streaming bodies.
+                                        ///
                                         /// **WARNING:** This is an advanced feature that removes
-                                        /// the cost of signing a request payload by removing a data
-                                        /// integrity check. Not all services/operations support
+                                        /// a data integrity check. Only use it on connections that
+                                        /// are protected by TLS. Not all services/operations support
                                         /// this feature.
                                         pub fn disable_payload_signing(self) -> Self {
                                             self.runtime_plugin(#{PayloadSigningOverrideRuntimePlugin}::unsigned())
@@ -5,6 +5,7 @@
 
 package software.amazon.smithy.rustsdk
 
+import software.amazon.smithy.aws.traits.ServiceTrait
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.node.BooleanNode
 import software.amazon.smithy.model.node.Node
@@ -41,6 +42,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.mapRustType
 import software.amazon.smithy.rust.codegen.core.util.PANIC
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.extendIf
+import software.amazon.smithy.rust.codegen.core.util.getTrait
 import software.amazon.smithy.rust.codegen.core.util.orNull
 import software.amazon.smithy.rust.codegen.core.util.sdkId
 import software.amazon.smithy.rust.codegen.core.util.toPascalCase
@@ -174,13 +176,17 @@ fun decoratorForBuiltIn(
                     ): Writable? =
                         when (parameter.builtIn) {
                             builtIn.builtIn ->
-                                writable {
-                                    val newtype = configParamNewtype(parameter, name, codegenContext.runtimeConfig)
-                                    val symbol = parameter.symbol().mapRustType { t -> t.stripOuter<RustType.Option>() }
-                                    rustTemplate(
-                                        """$configRef.#{load_from_service_config_layer}""",
-                                        "load_from_service_config_layer" to loadFromConfigBag(symbol.name, newtype),
-                                    )
+                                if (builtIn.builtIn == BuiltIns.SDK_ENDPOINT.builtIn) {
+                                    endpointUrlFromServiceConfig(codegenContext, configRef)
+                                } else {
+                                    writable {
+                                        val newtype = configParamNewtype(parameter, name, codegenContext.runtimeConfig)
+                                        val symbol = parameter.symbol().mapRustType { t -> t.stripOuter<RustType.Option>() }
+                                        rustTemplate(
+                                            """$configRef.#{load_from_service_config_layer}""",
+                                            "load_from_service_config_layer" to loadFromConfigBag(symbol.name, newtype),
+                                        )
+                                    }
                                 }
 
                             else -> null
@@ -206,6 +212,32 @@ fun decoratorForBuiltIn(
     }
 }
 
+/**
+ * Loads the endpoint URL from the service config, expanding its `{service}` and `{region}` placeholders
+ *
+ * This is rendered in the endpoint params interceptor, so errors are returned with `?`.
+ */
+private fun endpointUrlFromServiceConfig(
+    codegenContext: ClientCodegenContext,
+    configRef: String,
+): Writable =
+    writable {
+        rustTemplate(
+            """
+            $configRef.load::<#{EndpointUrl}>()
+                .map(|url| url.expand(${endpointUrlServiceName(codegenContext).dq()}, $configRef.load::<#{Region}>().map(|r| r.as_ref())))
+                .transpose()?
+            """,
+            "EndpointUrl" to AwsRuntimeType.awsTypes(codegenContext.runtimeConfig).resolve("endpoint_config::EndpointUrl"),
+            "Region" to region(codegenContext.runtimeConfig).resolve("Region"),
+        )
+    }
+
+/** The name substituted for the `{service}` placeholder of endpoint URLs, e.g. `s3` */
+internal fun endpointUrlServiceName(codegenContext: ClientCodegenContext): String =
+    codegenContext.serviceShape.getTrait<ServiceTrait>()?.endpointPrefix
+        ?: codegenContext.serviceShape.sdkId().lowercase().replace(" ", "-")
+
 private val endpointUrlDocs =
     writable {
         rust(
@@ -215,6 +247,10 @@ private val endpointUrlDocs =
             /// Note: this is used in combination with other endpoint rules, e.g. an API that applies a host-label prefix
             /// will be prefixed onto this URL. To fully override the endpoint resolver, use
             /// [`Builder::endpoint_resolver`].
+            ///
+            /// The URL may contain `{service}` and `{region}` placeholders, which are replaced with this service's
+            /// endpoint prefix and the configured region when a request is made, e.g.
+            /// `https://{service}.{region}.mycorp.internal`.
             """.trimIndent(),
         )
     }
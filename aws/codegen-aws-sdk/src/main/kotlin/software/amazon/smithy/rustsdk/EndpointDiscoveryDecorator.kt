@@ -0,0 +1,272 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import software.amazon.smithy.aws.traits.clientendpointdiscovery.ClientDiscoveredEndpointTrait
+import software.amazon.smithy.aws.traits.clientendpointdiscovery.ClientEndpointDiscoveryIdTrait
+import software.amazon.smithy.aws.traits.clientendpointdiscovery.ClientEndpointDiscoveryTrait
+import software.amazon.smithy.model.knowledge.TopDownIndex
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientGenerator
+import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.isOptional
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.getTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.inputShape
+
+/**
+ * Enables endpoint discovery for services modeled with `@clientEndpointDiscovery`
+ *
+ * This does three things:
+ * 1. Registers the `EndpointDiscoveryInterceptor` on operations with `@clientDiscoveredEndpoint`. Operations with
+ *    `@clientEndpointDiscoveryId` input members discover and cache endpoints per operation and identifier values.
+ * 2. Adds a `with_endpoint_discovery_enabled` method on client that returns a client with endpoint discovery enabled
+ * 3. Re-exports `ReloadEndpoint` from a public `endpoint_discovery` module
+ */
+class EndpointDiscoveryDecorator : ClientCodegenDecorator {
+    override val name: String = "EndpointDiscovery"
+    override val order: Byte = 0
+
+    private fun usesEndpointDiscovery(codegenContext: ClientCodegenContext) =
+        codegenContext.serviceShape.hasTrait<ClientEndpointDiscoveryTrait>()
+
+    private fun discoveryIsRequired(codegenContext: ClientCodegenContext) =
+        TopDownIndex.of(codegenContext.model).getContainedOperations(codegenContext.serviceShape).any {
+            it.getTrait<ClientDiscoveredEndpointTrait>()?.isRequired == true
+        }
+
+    override fun operationCustomizations(
+        codegenContext: ClientCodegenContext,
+        operation: OperationShape,
+        baseCustomizations: List<OperationCustomization>,
+    ): List<OperationCustomization> {
+        val discoveredEndpoint = operation.getTrait<ClientDiscoveredEndpointTrait>()
+        return if (usesEndpointDiscovery(codegenContext) && discoveredEndpoint != null) {
+            baseCustomizations +
+                EndpointDiscoveryOperationCustomization(codegenContext, operation, discoveredEndpoint.isRequired)
+        } else {
+            baseCustomizations
+        }
+    }
+
+    override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> =
+        if (usesEndpointDiscovery(codegenContext) && discoveryIsRequired(codegenContext)) {
+            listOf(
+                adhocCustomization<DocSection.CreateClient> {
+                    addDependency(AwsCargoDependency.awsConfig(codegenContext.runtimeConfig).toDevDependency())
+                    rustTemplate(
+                        """
+                        let config = aws_config::load_from_env().await;
+                        // You MUST call `with_endpoint_discovery_enabled` to produce a working client for this service.
+                        let ${it.clientName} = ${it.crateName}::Client::new(&config).with_endpoint_discovery_enabled().await;
+                        """.replaceIndent(it.indent),
+                    )
+                },
+            )
+        } else {
+            emptyList()
+        }
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        val discoveryTrait = codegenContext.serviceShape.getTrait<ClientEndpointDiscoveryTrait>() ?: return
+        val runtimeConfig = codegenContext.runtimeConfig
+        val endpointDiscovery = AwsRuntimeType.awsRuntime(runtimeConfig).resolve("endpoint_discovery")
+        val discoveryOperation = codegenContext.model.expectShape(discoveryTrait.operation, OperationShape::class.java)
+        val discoveryOperationFn =
+            FluentClientGenerator.clientOperationFnName(discoveryOperation, codegenContext.symbolProvider)
+        val required = discoveryIsRequired(codegenContext)
+        // The endpoint discovery operation can take the operation and identifiers endpoints are discovered for
+        val discoveryInput = discoveryOperation.inputShape(codegenContext.model)
+        val operationMember = discoveryInput.getMember("Operation").orElse(null)
+        val identifiersMember = discoveryInput.getMember("Identifiers").orElse(null)
+        val usesKey = operationMember != null || identifiersMember != null
+
+        rustCrate.withModule(
+            RustModule.public("endpoint_discovery", documentationOverride = "Endpoint discovery for this service."),
+        ) {
+            rustTemplate("pub use #{endpoint_discovery}::ReloadEndpoint;", "endpoint_discovery" to endpointDiscovery)
+        }
+        rustCrate.withModule(ClientRustModule.client) {
+            rustTemplate(
+                """
+                impl Client {
+                    /// Enable endpoint discovery for this client
+                    ///
+                    #{required_docs}
+                    /// The returned [`ReloadEndpoint`](crate::endpoint_discovery::ReloadEndpoint) refreshes discovered
+                    /// endpoints before they expire when its `reload_task` is spawned. Operations that don't use endpoint
+                    /// discovery are sent to the regional endpoint.
+                    pub async fn with_endpoint_discovery_enabled(self) -> #{Result}<(Self, crate::endpoint_discovery::ReloadEndpoint), #{BoxError}> {
+                        let handle = self.handle.clone();
+
+                        // The original client without endpoint discovery gets moved into the endpoint discovery
+                        // resolver since calls to $discoveryOperationFn without discovery need to be made.
+                        let client_without_discovery = self;
+                        let resolver = #{endpoint_discovery}::EndpointDiscoveryResolver::builder()
+                            .discover(move |${if (usesKey) "key" else "_key"}| {
+                                let client = client_without_discovery.clone();
+                                async move {
+                                    let ${if (usesKey) "mut " else ""}request = client.$discoveryOperationFn();
+                                    #{discovery_key_input}
+                                    let output = request.send().await?;
+                                    let endpoint = output
+                                        .endpoints()
+                                        .first()
+                                        .ok_or("the endpoint discovery response didn't contain any endpoints")?;
+                                    #{Ok}::<_, #{BoxError}>(#{endpoint_discovery}::DiscoveredEndpoint::from_address(
+                                        endpoint.address(),
+                                        endpoint.cache_period_in_minutes(),
+                                    ))
+                                }
+                            })
+                            .fallback_resolver(handle.conf.endpoint_resolver())
+                            .time_source(handle.conf.time_source()
+                                .expect("endpoint discovery requires the client config to have a time source"))
+                            .build();
+                        #{initial_discovery}
+                        let reloader = resolver.reloader(handle.conf.sleep_impl()
+                            .expect("endpoint discovery requires the client config to have a sleep impl"));
+
+                        use #{IntoShared};
+                        let mut conf = handle.conf.to_builder();
+                        conf.set_endpoint_resolver(#{Some}(resolver.into_shared()));
+
+                        let client_with_discovery = crate::Client::from_conf(conf.build());
+                        #{Ok}((client_with_discovery, reloader))
+                    }
+                }
+                """,
+                *RuntimeType.preludeScope,
+                "BoxError" to RuntimeType.boxError(runtimeConfig),
+                "IntoShared" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("shared::IntoShared"),
+                "endpoint_discovery" to endpointDiscovery,
+                "discovery_key_input" to
+                    writable {
+                        operationMember?.also { member ->
+                            val setter = codegenContext.symbolProvider.toMemberName(member)
+                            rustTemplate(
+                                """
+                                if let #{Some}(operation) = key.operation() {
+                                    request = request.$setter(operation);
+                                }
+                                """,
+                                *RuntimeType.preludeScope,
+                            )
+                        }
+                        identifiersMember?.also { member ->
+                            val setter = codegenContext.symbolProvider.toMemberName(member)
+                            rust(
+                                """
+                                for (name, value) in key.identifiers() {
+                                    request = request.$setter(name, value);
+                                }
+                                """,
+                            )
+                        }
+                    },
+                "required_docs" to
+                    writable {
+                        if (required) {
+                            rust("/// This method MUST be called to construct a working client.")
+                            rust("///")
+                        }
+                    },
+                "initial_discovery" to
+                    writable {
+                        if (required) {
+                            rustTemplate(
+                                """
+                                // if we can't discover an endpoint, bail out so the caller knows configuration failed to work
+                                resolver.discover(&#{endpoint_discovery}::DiscoveryKey::default()).await?;
+                                """,
+                                "endpoint_discovery" to endpointDiscovery,
+                            )
+                        }
+                    },
+            )
+        }
+    }
+}
+
+private class EndpointDiscoveryOperationCustomization(
+    private val codegenContext: ClientCodegenContext,
+    private val operation: OperationShape,
+    private val required: Boolean,
+) : OperationCustomization() {
+    private val endpointDiscovery = AwsRuntimeType.awsRuntime(codegenContext.runtimeConfig).resolve("endpoint_discovery")
+    private val inputShape = operation.inputShape(codegenContext.model)
+    private val discoveryIdMembers = inputShape.members().filter { it.hasTrait<ClientEndpointDiscoveryIdTrait>() }
+
+    override fun section(section: OperationSection): Writable =
+        writable {
+            when (section) {
+                is OperationSection.AdditionalInterceptors -> {
+                    section.registerInterceptor(codegenContext.runtimeConfig, this) {
+                        if (discoveryIdMembers.isEmpty()) {
+                            rustTemplate(
+                                "#{endpoint_discovery}::EndpointDiscoveryInterceptor::new(#{endpoint_discovery}::DiscoveryKey::default(), $required)",
+                                "endpoint_discovery" to endpointDiscovery,
+                            )
+                        } else {
+                            rustTemplate(
+                                """
+                                #{endpoint_discovery}::EndpointDiscoveryInterceptor::from_input(
+                                    |input: &#{Input}| {
+                                        let mut key = #{endpoint_discovery}::DiscoveryKey::for_operation(${operation.id.name.dq()});
+                                        #{identifiers}
+                                        key
+                                    },
+                                    $required,
+                                )
+                                """,
+                                "endpoint_discovery" to endpointDiscovery,
+                                "Input" to codegenContext.symbolProvider.toSymbol(inputShape),
+                                "identifiers" to discoveryIdentifiers(),
+                            )
+                        }
+                    }
+                }
+
+                else -> {}
+            }
+        }
+
+    private fun discoveryIdentifiers(): Writable =
+        writable {
+            discoveryIdMembers.forEach { member ->
+                val memberName = codegenContext.symbolProvider.toMemberName(member)
+                if (codegenContext.symbolProvider.toSymbol(member).isOptional()) {
+                    rustTemplate(
+                        """
+                        if let #{Some}(value) = &input.$memberName {
+                            key = key.with_identifier(${member.memberName.dq()}, value.as_str());
+                        }
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                } else {
+                    rust("key = key.with_identifier(${member.memberName.dq()}, input.$memberName.as_str());")
+                }
+            }
+        }
+}
@@ -0,0 +1,182 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointTypesGenerator
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.serviceSpecificEndpointResolver
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.util.dq
+
+/**
+ * Adds `endpoint_url_passthrough` to the config builder to send requests to the configured endpoint URL verbatim
+ *
+ * When [bucketInPath] is set, the `Bucket` endpoint parameter is appended to the path of the endpoint URL. S3 needs
+ * this since it removes the bucket from the path of requests.
+ */
+class EndpointUrlPassthroughDecorator(private val bucketInPath: Boolean = false) : ClientCodegenDecorator {
+    override val name: String = "EndpointUrlPassthrough"
+    override val order: Byte = 0
+
+    private fun applies(codegenContext: ClientCodegenContext): Boolean =
+        EndpointTypesGenerator.fromContext(codegenContext).defaultResolver() != null
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> =
+        if (applies(codegenContext)) {
+            baseCustomizations + EndpointUrlPassthroughConfig(codegenContext, bucketInPath)
+        } else {
+            baseCustomizations
+        }
+
+    override fun serviceRuntimePluginCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ServiceRuntimePluginCustomization>,
+    ): List<ServiceRuntimePluginCustomization> =
+        if (applies(codegenContext)) {
+            baseCustomizations +
+                object : ServiceRuntimePluginCustomization() {
+                    override fun section(section: ServiceRuntimePluginSection): Writable =
+                        writable {
+                            if (section is ServiceRuntimePluginSection.RegisterRuntimeComponents) {
+                                section.registerInterceptor(this) {
+                                    rust(
+                                        "#T",
+                                        passthroughModule(codegenContext.runtimeConfig)
+                                            .resolve("EndpointUrlPassthroughInterceptor"),
+                                    )
+                                }
+                            }
+                        }
+                }
+        } else {
+            baseCustomizations
+        }
+}
+
+private fun passthroughModule(runtimeConfig: RuntimeConfig) =
+    RuntimeType.forInlineDependency(
+        InlineAwsDependency.forRustFile(
+            "endpoint_url_passthrough", visibility = Visibility.PUBCRATE,
+            AwsCargoDependency.awsTypes(runtimeConfig),
+            CargoDependency.smithyHttp(runtimeConfig),
+            CargoDependency.smithyRuntimeApiClient(runtimeConfig),
+            CargoDependency.smithyTypes(runtimeConfig),
+        ),
+    )
+
+private class EndpointUrlPassthroughConfig(
+    private val codegenContext: ClientCodegenContext,
+    private val bucketInPath: Boolean,
+) : ConfigCustomization() {
+    private val passthroughModule = passthroughModule(codegenContext.runtimeConfig)
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "DefaultResolver" to EndpointTypesGenerator.fromContext(codegenContext).defaultResolver()!!,
+            "EndpointUrlPassthrough" to passthroughModule.resolve("EndpointUrlPassthrough"),
+            "EndpointUrlPassthroughFallback" to passthroughModule.resolve("EndpointUrlPassthroughFallback"),
+            "EndpointUrlPassthroughResolver" to passthroughModule.resolve("EndpointUrlPassthroughResolver"),
+            "Params" to EndpointTypesGenerator.fromContext(codegenContext).paramsStruct(),
+            "ServiceSpecificResolver" to codegenContext.serviceSpecificEndpointResolver(),
+            "SharedEndpointResolver" to
+                RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+                    .resolve("client::endpoint::SharedEndpointResolver"),
+        )
+
+    private val pathDocs =
+        writable {
+            if (bucketInPath) {
+                rust(
+                    """
+                    /// The bucket is appended to the path of the endpoint URL, e.g. `https://objects.mycorp.internal/my-bucket`,
+                    /// since it is never added to the host.
+                    """,
+                )
+            }
+        }
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sends requests to the configured endpoint URL verbatim
+                        ///
+                        /// By default, the [endpoint URL](Self::endpoint_url) is an input to the endpoint rules of
+                        /// this service, which may change it, e.g. by adding a host prefix for the operation. When
+                        /// passthrough is enabled, the endpoint rules are skipped and every request is sent to the
+                        /// endpoint URL as configured in code, the environment, or a profile. Only its `{service}` and
+                        /// `{region}` placeholders are expanded. This is useful for compatible services and proxies that
+                        /// don't support the hosts produced by the endpoint rules.
+                        #{path_docs:W}///
+                        /// Requests fail if passthrough is enabled and no endpoint URL has been configured. Enabling
+                        /// passthrough wraps the endpoint resolver that has been set, which is used again when
+                        /// passthrough is disabled. Enabling it again keeps wrapping that same resolver. An endpoint
+                        /// resolver set afterwards replaces the passthrough, so set a custom endpoint resolver before
+                        /// enabling passthrough.
+                        pub fn endpoint_url_passthrough(mut self, enabled: bool) -> Self {
+                            self.set_endpoint_url_passthrough(#{Some}(enabled));
+                            self
+                        }
+
+                        /// Sends requests to the configured endpoint URL verbatim
+                        ///
+                        /// See [`Self::endpoint_url_passthrough`] for more information.
+                        pub fn set_endpoint_url_passthrough(&mut self, enabled: #{Option}<bool>) -> &mut Self {
+                            if enabled == #{Some}(true) {
+                                // Replace a passthrough resolver set by a previous call rather than wrapping it again
+                                let fallback = match self.config.load::<#{EndpointUrlPassthroughFallback}>() {
+                                    #{Some}(fallback) => fallback.0.clone(),
+                                    #{None} => self.runtime_components.endpoint_resolver().unwrap_or_else(|| {
+                                        use #{ServiceSpecificResolver};
+                                        #{DefaultResolver}::new().into_shared_resolver()
+                                    }),
+                                };
+                                self.config.store_put(#{EndpointUrlPassthroughFallback}(fallback.clone()));
+                                let resolver = #{EndpointUrlPassthroughResolver}::new(${endpointUrlServiceName(codegenContext).dq()}, fallback)#{bucket_in_path:W};
+                                self.set_endpoint_resolver(#{Some}(#{SharedEndpointResolver}::new(resolver)));
+                            }
+                            self.config.store_or_unset(enabled.map(#{EndpointUrlPassthrough}));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                        "path_docs" to pathDocs,
+                        "bucket_in_path" to
+                            writable {
+                                if (bucketInPath) {
+                                    rustTemplate(
+                                        """
+                                        .with_bucket_in_path(|params| params.get::<#{Params}>().and_then(|params| params.bucket()))
+                                        """,
+                                        *codegenScope,
+                                    )
+                                }
+                            },
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
@@ -5,6 +5,7 @@
 
 package software.amazon.smithy.rustsdk
 
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.InlineDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
@@ -22,9 +23,17 @@ object InlineAwsDependency {
         moduleName: String,
         visibility: Visibility = Visibility.PRIVATE,
         vararg additionalDependency: RustDependency,
+    ): InlineDependency = forRustFileAs(file, moduleName, visibility, listOf(), *additionalDependency)
+
+    fun forRustFileAs(
+        file: String,
+        moduleName: String,
+        visibility: Visibility,
+        additionalAttributes: List<Attribute>,
+        vararg additionalDependency: RustDependency,
     ): InlineDependency =
         InlineDependency.Companion.forRustFile(
-            RustModule.new(moduleName, visibility, documentationOverride = ""),
+            RustModule.new(moduleName, visibility, additionalAttributes = additionalAttributes, documentationOverride = ""),
             "/aws-inlineable/src/$file.rs",
             *additionalDependency,
         )
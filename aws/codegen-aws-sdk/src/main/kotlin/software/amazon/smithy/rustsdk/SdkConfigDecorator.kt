@@ -73,7 +73,9 @@ object SdkConfigCustomization {
 
         rustTemplate(
             """
-            if ${section.sdkConfig}.get_origin("endpoint_url").is_client_config() {
+            if let Some(service_endpoint_url) = ${section.sdkConfig}.endpoint_url_for(${serviceId.dq()}) {
+                ${section.serviceConfigBuilder}.set_$fieldName(Some(service_endpoint_url)#{map});
+            } else if ${section.sdkConfig}.get_origin("endpoint_url").is_client_config() {
                 ${section.serviceConfigBuilder}.set_$fieldName(${section.sdkConfig}.$fieldName()#{map});
             } else {
                 ${section.serviceConfigBuilder}.set_$fieldName(
@@ -110,6 +112,7 @@ class GenericSmithySdkConfigSettings : ClientCodegenDecorator {
                     ${section.serviceConfigBuilder}.set_time_source(${section.sdkConfig}.time_source());
                     ${section.serviceConfigBuilder}.set_behavior_version(${section.sdkConfig}.behavior_version());
                     ${section.serviceConfigBuilder}.set_auth_scheme_preference(${section.sdkConfig}.auth_scheme_preference().cloned());
+                    ${section.serviceConfigBuilder}.set_disabled_auth_schemes(${section.sdkConfig}.disabled_auth_schemes().cloned());
                     // setting `None` here removes the default
                     if let Some(config) = ${section.sdkConfig}.stalled_stream_protection() {
                         ${section.serviceConfigBuilder}.set_stalled_stream_protection(Some(config));
@@ -37,6 +37,18 @@ class ServiceEnvConfigDecorator : ClientCodegenDecorator {
                         .build()
                         .expect("all field sets explicitly, can't fail")
                 }
+
+                /// Key for a setting that can only be set in a profile, since it has no environment variable.
+                fn profile_service_config_key<'a>(
+                    service_id: &'a str,
+                    profile: &'a str,
+                ) -> aws_types::service_config::ServiceConfigKey<'a> {
+                    #{ServiceConfigKey}::builder()
+                        .service_id(service_id)
+                        .profile(profile)
+                        .build()
+                        .expect("all field sets explicitly, can't fail")
+                }
                 """,
                 "ServiceConfigKey" to AwsRuntimeType.awsTypes(rc).resolve("service_config::ServiceConfigKey"),
             )
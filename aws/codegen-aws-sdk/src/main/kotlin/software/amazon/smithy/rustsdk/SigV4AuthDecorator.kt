@@ -30,7 +30,10 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.getTrait
 import software.amazon.smithy.rust.codegen.core.util.hasEventStreamOperations
@@ -77,7 +80,19 @@ class SigV4AuthDecorator : ConditionalDecorator(
                 codegenContext: ClientCodegenContext,
                 baseCustomizations: List<ConfigCustomization>,
             ): List<ConfigCustomization> =
-                baseCustomizations + SigV4SigningConfig(codegenContext.runtimeConfig, codegenContext.serviceShape.getTrait())
+                (baseCustomizations + SigV4SigningConfig(codegenContext.runtimeConfig, codegenContext.serviceShape.getTrait()))
+                    .letIf(codegenContext.usesSigV4a()) {
+                        it + SigV4aSigningRegionSetConfig(codegenContext.runtimeConfig)
+                    }
+
+            override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> =
+                listOfNotNull(
+                    adhocCustomization<SdkConfigSection.CopySdkConfigToClientConfig> { section ->
+                        rust(
+                            "${section.serviceConfigBuilder}.set_sigv4a_signing_region_set(${section.sdkConfig}.sigv4a_signing_region_set().cloned());",
+                        )
+                    }.takeIf { codegenContext.usesSigV4a() },
+                )
 
             override fun extras(
                 codegenContext: ClientCodegenContext,
@@ -232,6 +247,53 @@ private class SigV4SigningConfig(
         }
 }
 
+private class SigV4aSigningRegionSetConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "SigningRegionSet" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("region::SigningRegionSet"),
+        )
+
+    override fun section(section: ServiceConfig): Writable =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets the region set to use when signing requests with SigV4a.
+                        ///
+                        /// This takes precedence over the region set returned by the endpoint, e.g. `*` for
+                        /// S3 Multi-Region Access Points.
+                        pub fn sigv4a_signing_region_set(mut self, sigv4a_signing_region_set: impl #{Into}<#{SigningRegionSet}>) -> Self {
+                            self.set_sigv4a_signing_region_set(#{Some}(sigv4a_signing_region_set.into()));
+                            self
+                        }
+
+                        /// Sets the region set to use when signing requests with SigV4a.
+                        ///
+                        /// This takes precedence over the region set returned by the endpoint, e.g. `*` for
+                        /// S3 Multi-Region Access Points.
+                        pub fn set_sigv4a_signing_region_set(&mut self, sigv4a_signing_region_set: #{Option}<#{SigningRegionSet}>) -> &mut Self {
+                            self.config.store_or_unset(sigv4a_signing_region_set);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.BuilderFromConfigBag -> {
+                    rustTemplate(
+                        "${section.builder}.set_sigv4a_signing_region_set(${section.configBag}.load::<#{SigningRegionSet}>().cloned());",
+                        *codegenScope,
+                    )
+                }
+
+                else -> {}
+            }
+        }
+}
+
 private class AuthServiceRuntimePluginCustomization(private val codegenContext: ClientCodegenContext) :
     ServiceRuntimePluginCustomization() {
     private val runtimeConfig = codegenContext.runtimeConfig
@@ -0,0 +1,147 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderSection
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureSection
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.letIf
+
+private fun signingPropertiesModule(runtimeConfig: RuntimeConfig) =
+    AwsRuntimeType.awsRuntime(runtimeConfig).resolve("auth::signing_properties")
+
+/**
+ * Adds the signing properties a request was signed with to the outputs of SigV4 and SigV4a signed services.
+ *
+ * The properties are only collected when the `ResolvedSigningPropertiesInterceptor` was added to the client, so the
+ * outputs don't carry them by default.
+ */
+class SigningPropertiesDecorator : ClientCodegenDecorator {
+    override val name: String = "SigningPropertiesDecorator"
+    override val order: Byte = 0
+
+    override fun operationCustomizations(
+        codegenContext: ClientCodegenContext,
+        operation: OperationShape,
+        baseCustomizations: List<OperationCustomization>,
+    ): List<OperationCustomization> =
+        baseCustomizations.letIf(codegenContext.usesSigAuth()) {
+            it + SigningPropertiesOperationCustomization(codegenContext)
+        }
+
+    override fun structureCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<StructureCustomization>,
+    ): List<StructureCustomization> =
+        baseCustomizations.letIf(codegenContext.usesSigAuth()) {
+            it + SigningPropertiesStructureCustomization(codegenContext)
+        }
+
+    override fun builderCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<BuilderCustomization>,
+    ): List<BuilderCustomization> =
+        baseCustomizations.letIf(codegenContext.usesSigAuth()) { it + SigningPropertiesBuilderCustomization() }
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        if (!codegenContext.usesSigAuth()) {
+            return
+        }
+        val module = signingPropertiesModule(codegenContext.runtimeConfig)
+        rustCrate.withModule(ClientRustModule.Operation) {
+            rust("pub use #T;", module.resolve("SigningProperties"))
+        }
+        rustCrate.withModule(ClientRustModule.Config.interceptors) {
+            rust("pub use #T;", module.resolve("ResolvedSigningPropertiesInterceptor"))
+        }
+    }
+}
+
+private class SigningPropertiesOperationCustomization(private val codegenContext: ClientCodegenContext) :
+    OperationCustomization() {
+    override fun section(section: OperationSection): Writable =
+        writable {
+            if (section is OperationSection.MutateOutputFromResponse) {
+                rust(
+                    "${section.outputName}._set_signing_properties(#T::signing_properties(${section.responseName}).cloned());",
+                    signingPropertiesModule(codegenContext.runtimeConfig).resolve("SigningProperties"),
+                )
+            }
+        }
+}
+
+private class SigningPropertiesStructureCustomization(private val codegenContext: ClientCodegenContext) :
+    StructureCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "ResolvedSigningProperties" to
+                signingPropertiesModule(codegenContext.runtimeConfig).resolve("ResolvedSigningProperties"),
+            "SigningProperties" to signingPropertiesModule(codegenContext.runtimeConfig).resolve("SigningProperties"),
+        )
+
+    override fun section(section: StructureSection): Writable =
+        writable {
+            if (section.shape.hasTrait<SyntheticOutputTrait>()) {
+                when (section) {
+                    is StructureSection.AdditionalFields -> {
+                        rustTemplate("_signing_properties: #{Option}<#{ResolvedSigningProperties}>,", *codegenScope)
+                    }
+
+                    is StructureSection.AdditionalTraitImpls -> {
+                        rustTemplate(
+                            """
+                            impl ${section.structName} {
+                                pub(crate) fn _set_signing_properties(&mut self, signing_properties: #{Option}<#{ResolvedSigningProperties}>) {
+                                    self._signing_properties = signing_properties;
+                                }
+                            }
+
+                            impl #{SigningProperties} for ${section.structName} {
+                                fn signing_properties(&self) -> #{Option}<&#{ResolvedSigningProperties}> {
+                                    self._signing_properties.as_ref()
+                                }
+                            }
+                            """,
+                            *codegenScope,
+                        )
+                    }
+
+                    is StructureSection.AdditionalDebugFields -> {
+                        rust("""${section.formatterName}.field("_signing_properties", &self._signing_properties);""")
+                    }
+                }
+            }
+        }
+}
+
+private class SigningPropertiesBuilderCustomization : BuilderCustomization() {
+    override fun section(section: BuilderSection): Writable =
+        writable {
+            if (section.shape.hasTrait<SyntheticOutputTrait>() && section is BuilderSection.AdditionalFieldsInBuild) {
+                rust("_signing_properties: None,")
+            }
+        }
+}
@@ -10,9 +10,12 @@ import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.configReexport
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ConditionalDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginSection
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
@@ -37,6 +40,12 @@ class TokenProvidersDecorator : ConditionalDecorator(
                 baseCustomizations: List<ConfigCustomization>,
             ): List<ConfigCustomization> = baseCustomizations + TokenProviderConfig(codegenContext)
 
+            override fun serviceRuntimePluginCustomizations(
+                codegenContext: ClientCodegenContext,
+                baseCustomizations: List<ServiceRuntimePluginCustomization>,
+            ): List<ServiceRuntimePluginCustomization> =
+                baseCustomizations + BearerAuthServiceRuntimePluginCustomization(codegenContext)
+
             override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> =
                 listOf(
                     adhocCustomization<SdkConfigSection.CopySdkConfigToClientConfig> { section ->
@@ -50,6 +59,30 @@ class TokenProvidersDecorator : ConditionalDecorator(
     }
 }
 
+/**
+ * Registers the AWS bearer auth scheme, replacing the generic one registered for `@httpBearerAuth`
+ */
+private class BearerAuthServiceRuntimePluginCustomization(codegenContext: ClientCodegenContext) :
+    ServiceRuntimePluginCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val codegenScope =
+        arrayOf(
+            "BearerAuthScheme" to AwsRuntimeType.awsRuntime(runtimeConfig).resolve("auth::bearer::BearerAuthScheme"),
+            "SharedAuthScheme" to
+                RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                    .resolve("client::auth::SharedAuthScheme"),
+        )
+
+    override fun section(section: ServiceRuntimePluginSection): Writable =
+        writable {
+            if (section is ServiceRuntimePluginSection.RegisterRuntimeComponents) {
+                section.registerAuthScheme(this) {
+                    rustTemplate("#{SharedAuthScheme}::new(#{BearerAuthScheme}::new())", *codegenScope)
+                }
+            }
+        }
+}
+
 /**
  * Add a `.token_provider` field and builder to the `Config` for a given service
  */
@@ -71,6 +104,7 @@ class TokenProviderConfig(private val codegenContext: ClientCodegenContext) : Co
                         .resolve("provider::token::SharedTokenProvider"),
                 ),
             "TestToken" to AwsRuntimeType.awsCredentialTypesTestUtil(runtimeConfig).resolve("Token"),
+            "TokenResolver" to AwsRuntimeType.awsRuntime(runtimeConfig).resolve("auth::bearer::TokenResolver"),
             "HTTP_BEARER_AUTH_SCHEME_ID" to
                 CargoDependency.smithyRuntimeApiClient(runtimeConfig)
                     .withFeature("http-auth").toType().resolve("client::auth::http")
@@ -85,6 +119,9 @@ class TokenProviderConfig(private val codegenContext: ClientCodegenContext) : Co
                         """
                         /// Sets the access token provider for this service
                         ///
+                        /// Tokens are cached, and refreshed before they expire. If refreshing a token fails,
+                        /// the cached token is used until it expires.
+                        ///
                         /// Note: the [`Self::bearer_token`] and [`Self::bearer_token_resolver`] methods are
                         /// equivalent to this method, but take the [`Token`] and [`ResolveIdentity`] types
                         /// respectively.
@@ -106,7 +143,10 @@ class TokenProviderConfig(private val codegenContext: ClientCodegenContext) : Co
                         /// [`ResolveIdentity`]: crate::config::ResolveIdentity
                         pub fn set_token_provider(&mut self, token_provider: #{Option}<#{SharedTokenProvider}>) -> &mut Self {
                             if let Some(token_provider) = token_provider {
-                                self.runtime_components.set_identity_resolver(#{HTTP_BEARER_AUTH_SCHEME_ID}, token_provider);
+                                self.runtime_components.set_identity_resolver(
+                                    #{HTTP_BEARER_AUTH_SCHEME_ID},
+                                    #{TokenResolver}::new(token_provider),
+                                );
                             }
                             self
                         }
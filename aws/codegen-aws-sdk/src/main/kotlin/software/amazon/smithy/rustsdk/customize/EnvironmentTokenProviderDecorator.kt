@@ -27,16 +27,22 @@ import software.amazon.smithy.rustsdk.TokenProvidersDecorator
 /** Environment variable prefix for AWS bearer tokens */
 const val AWS_BEARER_TOKEN = "AWS_BEARER_TOKEN"
 
+/** Profile key for the bearer token of the service with the given signing name, e.g. `aws_bearer_token_bedrock` */
+fun bearerTokenProfileKey(signingName: String): String =
+    "${AWS_BEARER_TOKEN.lowercase()}_${signingName.replace("-", "_").lowercase()}"
+
 /**
- * A code generation decorator that adds support for service-specific environment variable-based
- * bearer token authentication.
+ * A code generation decorator that adds support for service-specific bearer token authentication
+ * configured through environment variables or the shared config file.
  *
  * This decorator is relevant to AWS services whose SigV4 service signing name matches the input
  * to the decorator. It generates code that allows these services to automatically configure bearer
- * token authentication from service-specific environment variables.
+ * token authentication from service-specific environment variables or profile keys. The environment
+ * variable takes precedence over the profile key.
  *
  * @param signingName The AWS service signing name used to match against SigV4 traits and construct
- *                    the environment variable name (e.g., "bedrock" -> AWS_BEARER_TOKEN_BEDROCK)
+ *                    the environment variable name and profile key (e.g., "bedrock" -> AWS_BEARER_TOKEN_BEDROCK
+ *                    and aws_bearer_token_bedrock)
  */
 class EnvironmentTokenProviderDecorator(signingName: String) : ConditionalDecorator(
     predicate = { codegenContext, _ ->
@@ -86,11 +92,13 @@ class EnvironmentTokenProviderDecorator(signingName: String) : ConditionalDecora
                         rustTemplate(
                             """
                             if let #{Some}(val) = input.service_config().and_then(|conf| {
-                                // Passing an empty string for the last argument of `service_config_key`,
-                                // since shared config/profile for environment token provider is not supported.
                                 #{LoadServiceConfig}::load_config(
                                     conf,
-                                    service_config_key(${signingName.replace("-", " ").dq()}, ${AWS_BEARER_TOKEN.dq()}, "")
+                                    service_config_key(
+                                        ${signingName.replace("-", " ").dq()},
+                                        ${AWS_BEARER_TOKEN.dq()},
+                                        ${bearerTokenProfileKey(signingName).dq()},
+                                    )
                                 )
                                 .and_then(|it| it.parse::<#{String}>().ok())
                             }) {
@@ -0,0 +1,117 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rustsdk.AwsCargoDependency
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds `access_grants` to the S3 config builder to sign object requests with credentials vended by S3 Access Grants
+ */
+class S3AccessGrantsDecorator : ClientCodegenDecorator {
+    override val name: String = "S3AccessGrantsDecorator"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + S3AccessGrantsConfig(codegenContext.runtimeConfig)
+}
+
+private fun accessGrantsModule(runtimeConfig: RuntimeConfig) =
+    RuntimeType.forInlineDependency(
+        InlineAwsDependency.forRustFileAs(
+            "s3_access_grants",
+            "access_grants",
+            Visibility.PUBLIC,
+            AwsCargoDependency.awsCredentialTypes(runtimeConfig),
+            AwsCargoDependency.awsRuntime(runtimeConfig),
+            CargoDependency.smithyAsync(runtimeConfig),
+            CargoDependency.smithyRuntimeApiClient(runtimeConfig),
+            CargoDependency.smithyTypes(runtimeConfig),
+            CargoDependency.Tracing,
+        ),
+    )
+
+private class S3AccessGrantsConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "AccessGrantsConfig" to configReexport(accessGrantsModule(runtimeConfig).resolve("AccessGrantsConfig")),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Signs requests for objects with credentials vended by S3 Access Grants
+                        ///
+                        /// Requests for objects are signed with credentials for the grant that covers the object,
+                        /// which are requested with `GetDataAccess` and cached by the scope of the grant. See
+                        /// [`#{AccessGrantsConfig}`] for the requests that this applies to.
+                        pub fn access_grants(mut self, access_grants: #{AccessGrantsConfig}) -> Self {
+                            self.set_access_grants(#{Some}(access_grants));
+                            self
+                        }
+
+                        /// Signs requests for objects with credentials vended by S3 Access Grants
+                        ///
+                        /// See [`Self::access_grants`] for more information.
+                        pub fn set_access_grants(&mut self, access_grants: #{Option}<#{AccessGrantsConfig}>) -> &mut Self {
+                            self.config.store_or_unset(access_grants);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+class S3AccessGrantsFluentClientCustomization(
+    codegenContext: ClientCodegenContext,
+) : FluentClientCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+
+    override fun section(section: FluentClientSection): Writable =
+        writable {
+            when (section) {
+                is FluentClientSection.AdditionalBaseClientPlugins -> {
+                    rustTemplate(
+                        """
+                        ${section.plugins} = ${section.plugins}.with_client_plugin(
+                            #{AccessGrantsRuntimePlugin}::new(${section.config}.clone())
+                        );
+                        """,
+                        "AccessGrantsRuntimePlugin" to
+                            accessGrantsModule(runtimeConfig).resolve("AccessGrantsRuntimePlugin"),
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
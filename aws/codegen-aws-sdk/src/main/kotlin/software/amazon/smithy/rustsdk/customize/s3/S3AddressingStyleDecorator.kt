@@ -0,0 +1,125 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointTypesGenerator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds `addressing_style_overrides` to the S3 config builder to choose path-style or virtual-hosted addressing per bucket
+ */
+class S3AddressingStyleDecorator : ClientCodegenDecorator {
+    override val name: String = "S3AddressingStyleDecorator"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + S3AddressingStyleConfig(codegenContext.runtimeConfig)
+
+    override fun serviceRuntimePluginCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ServiceRuntimePluginCustomization>,
+    ): List<ServiceRuntimePluginCustomization> =
+        baseCustomizations + S3AddressingStyleRuntimePluginCustomization(codegenContext)
+}
+
+private fun addressingStyleModule(runtimeConfig: RuntimeConfig) =
+    RuntimeType.forInlineDependency(
+        InlineAwsDependency.forRustFile(
+            "s3_addressing_style", visibility = Visibility.PUBCRATE,
+            CargoDependency.smithyRuntimeApiClient(runtimeConfig),
+            CargoDependency.smithyTypes(runtimeConfig),
+        ),
+    )
+
+private class S3AddressingStyleConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "AddressingStyle" to configReexport(addressingStyleModule(runtimeConfig).resolve("AddressingStyle")),
+            "AddressingStyleOverrides" to
+                configReexport(addressingStyleModule(runtimeConfig).resolve("AddressingStyleOverrides")),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Overrides the addressing style of buckets whose names match a pattern
+                        ///
+                        /// Buckets are addressed with virtual-hosted style requests, or path-style requests if
+                        /// [`force_path_style`](Self::force_path_style) is set. The overrides choose the
+                        /// [`#{AddressingStyle}`] of buckets by name instead, e.g. to talk to both AWS and an
+                        /// S3-compatible store that only supports path-style requests from one client. By default,
+                        /// the overrides also address buckets whose names aren't valid DNS labels with path-style
+                        /// requests. See [`#{AddressingStyleOverrides}`] for how patterns are matched.
+                        pub fn addressing_style_overrides(mut self, overrides: #{AddressingStyleOverrides}) -> Self {
+                            self.set_addressing_style_overrides(#{Some}(overrides));
+                            self
+                        }
+
+                        /// Overrides the addressing style of buckets whose names match a pattern
+                        ///
+                        /// See [`Self::addressing_style_overrides`] for more information.
+                        pub fn set_addressing_style_overrides(&mut self, overrides: #{Option}<#{AddressingStyleOverrides}>) -> &mut Self {
+                            self.config.store_or_unset(overrides);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+private class S3AddressingStyleRuntimePluginCustomization(
+    private val codegenContext: ClientCodegenContext,
+) : ServiceRuntimePluginCustomization() {
+    override fun section(section: ServiceRuntimePluginSection): Writable =
+        writable {
+            if (section is ServiceRuntimePluginSection.RegisterRuntimeComponents) {
+                section.registerInterceptor(this) {
+                    rustTemplate(
+                        """
+                        #{AddressingStyleInterceptor}::new(
+                            |params| params.get::<#{Params}>().and_then(|params| params.bucket()),
+                            |params, force_path_style| {
+                                if let #{Some}(params) = params.get_mut::<#{Params}>() {
+                                    params.force_path_style = force_path_style;
+                                }
+                            },
+                        )
+                        """,
+                        *preludeScope,
+                        "AddressingStyleInterceptor" to
+                            addressingStyleModule(codegenContext.runtimeConfig).resolve("AddressingStyleInterceptor"),
+                        "Params" to EndpointTypesGenerator.fromContext(codegenContext).paramsStruct(),
+                    )
+                }
+            }
+        }
+}
@@ -0,0 +1,47 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds the `batch_delete` module to S3 for deleting any number of objects with concurrent `DeleteObjects` batches
+ */
+class S3BatchDeleteDecorator : ClientCodegenDecorator {
+    override val name: String = "S3BatchDeleteDecorator"
+    override val order: Byte = 0
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        val rc = codegenContext.runtimeConfig
+        rustCrate.lib {
+            // We should have a better way of including an inline dependency.
+            rust(
+                "// include #T;",
+                RuntimeType.forInlineDependency(
+                    InlineAwsDependency.forRustFileAs(
+                        "s3_batch_delete",
+                        "batch_delete",
+                        Visibility.PUBLIC,
+                        listOfNotNull(codegenContext.operationsFeatureGate(ShapeId.from("com.amazonaws.s3#DeleteObjects"))),
+                        CargoDependency.FuturesUtil,
+                        CargoDependency.smithyRuntimeApiClient(rc),
+                    ),
+                ),
+            )
+        }
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointTypesGenerator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds `cross_region_redirects` to the S3 config builder to follow redirects to the region of a bucket
+ *
+ * The redirect is sent by wrapping the retry strategy of the client, see [S3CrossRegionRedirectFluentClientCustomization].
+ */
+class S3CrossRegionRedirectDecorator : ClientCodegenDecorator {
+    override val name: String = "S3CrossRegionRedirectDecorator"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + S3CrossRegionRedirectConfig(codegenContext.runtimeConfig)
+
+    override fun serviceRuntimePluginCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ServiceRuntimePluginCustomization>,
+    ): List<ServiceRuntimePluginCustomization> =
+        baseCustomizations + S3CrossRegionRedirectRuntimePluginCustomization(codegenContext)
+}
+
+private fun redirectModule(runtimeConfig: RuntimeConfig) =
+    RuntimeType.forInlineDependency(
+        InlineAwsDependency.forRustFile(
+            "s3_cross_region_redirect", visibility = Visibility.PUBCRATE,
+            CargoDependency.smithyRuntimeApiClient(runtimeConfig),
+            CargoDependency.smithyTypes(runtimeConfig),
+            CargoDependency.Tracing,
+        ),
+    )
+
+private class S3CrossRegionRedirectConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "BucketRegionCache" to redirectModule(runtimeConfig).resolve("BucketRegionCache"),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Retries requests for buckets in other regions in the region of the bucket
+                        ///
+                        /// S3 rejects requests that are sent to a different region than the region of the bucket, e.g.
+                        /// with a `301 PermanentRedirect` error, and includes the region of the bucket in the
+                        /// `x-amz-bucket-region` header of the response. When enabled, such requests are retried in the
+                        /// region of the bucket: the endpoint is resolved again for that region, and the request is
+                        /// signed again. The region of each bucket is cached, so later requests for the bucket are sent
+                        /// to its region directly. The cache is shared by all clients created from this config.
+                        ///
+                        /// Redirects are disabled by default. A request is redirected at most once. The redirected
+                        /// request is sent regardless of the [retry config](Self::retry_config), and doesn't count
+                        /// towards its max attempts or take retry quota.
+                        pub fn cross_region_redirects(mut self, enabled: bool) -> Self {
+                            self.set_cross_region_redirects(#{Some}(enabled));
+                            self
+                        }
+
+                        /// Retries requests for buckets in other regions in the region of the bucket
+                        ///
+                        /// See [`Self::cross_region_redirects`] for more information.
+                        pub fn set_cross_region_redirects(&mut self, enabled: #{Option}<bool>) -> &mut Self {
+                            self.config.store_or_unset(enabled.filter(|enabled| *enabled).map(|_| #{BucketRegionCache}::default()));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+private class S3CrossRegionRedirectRuntimePluginCustomization(
+    private val codegenContext: ClientCodegenContext,
+) : ServiceRuntimePluginCustomization() {
+    private val redirectModule = redirectModule(codegenContext.runtimeConfig)
+
+    override fun section(section: ServiceRuntimePluginSection): Writable =
+        writable {
+            if (section is ServiceRuntimePluginSection.RegisterRuntimeComponents) {
+                section.registerInterceptor(this) {
+                    rustTemplate(
+                        """
+                        #{CrossRegionRedirectInterceptor}::new(
+                            |params| params.get::<#{Params}>().and_then(|params| params.bucket()),
+                            |params| params.get::<#{Params}>().and_then(|params| params.region()),
+                            |params, region| {
+                                if let #{Some}(params) = params.get_mut::<#{Params}>() {
+                                    params.region = #{Some}(region);
+                                }
+                            },
+                        )
+                        """,
+                        *preludeScope,
+                        "CrossRegionRedirectInterceptor" to redirectModule.resolve("CrossRegionRedirectInterceptor"),
+                        "Params" to EndpointTypesGenerator.fromContext(codegenContext).paramsStruct(),
+                    )
+                }
+            }
+        }
+}
+
+class S3CrossRegionRedirectFluentClientCustomization(
+    codegenContext: ClientCodegenContext,
+) : FluentClientCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+
+    override fun section(section: FluentClientSection): Writable =
+        writable {
+            when (section) {
+                is FluentClientSection.AdditionalBaseClientPlugins -> {
+                    rustTemplate(
+                        """
+                        ${section.plugins} = ${section.plugins}.with_client_plugin(#{CrossRegionRedirectRuntimePlugin});
+                        """,
+                        "CrossRegionRedirectRuntimePlugin" to
+                            redirectModule(runtimeConfig).resolve("CrossRegionRedirectRuntimePlugin"),
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
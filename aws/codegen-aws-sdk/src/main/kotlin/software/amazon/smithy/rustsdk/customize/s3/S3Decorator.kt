@@ -33,12 +33,18 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.ProtocolFunctions
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.ProtocolMap
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.RestXml
 import software.amazon.smithy.rust.codegen.core.smithy.traits.AllowInvalidXmlRoot
+import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.letIf
+import software.amazon.smithy.rust.codegen.core.util.sdkId
 import software.amazon.smithy.rustsdk.AwsRuntimeType
+import software.amazon.smithy.rustsdk.SdkConfigSection
 import software.amazon.smithy.rustsdk.getBuiltIn
 import software.amazon.smithy.rustsdk.toWritable
 import java.util.logging.Logger
@@ -125,6 +131,39 @@ class S3Decorator : ClientCodegenDecorator {
         )
     }
 
+    override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> {
+        val serviceId = codegenContext.serviceShape.sdkId().dq()
+        return listOf(
+            // S3 specific settings from the `services` section of the profile file. Like other SDKs, these have no
+            // environment variables.
+            adhocCustomization<SdkConfigSection.CopySdkConfigToClientConfig> { section ->
+                rustTemplate(
+                    """
+                    if let #{Some}(conf) = ${section.sdkConfig}.service_config() {
+                        match conf.load_parsed::<#{String}>(profile_service_config_key($serviceId, "addressing_style")) {
+                            #{Ok}(#{Some}(style)) => match style.to_ascii_lowercase().as_str() {
+                                "path" => { ${section.serviceConfigBuilder}.set_force_path_style(#{Some}(true)); }
+                                "virtual" => { ${section.serviceConfigBuilder}.set_force_path_style(#{Some}(false)); }
+                                "auto" => {}
+                                _ => #{tracing}::warn!(addressing_style = %style, "ignoring unknown S3 addressing style; expected one of `auto`, `path`, or `virtual`"),
+                            },
+                            #{Ok}(#{None}) => {}
+                            #{Err}(err) => #{tracing}::warn!(err = %err, "ignoring invalid S3 addressing style"),
+                        }
+                        match conf.load_bool(profile_service_config_key($serviceId, "use_accelerate_endpoint")) {
+                            #{Ok}(#{Some}(accelerate)) => { ${section.serviceConfigBuilder}.set_accelerate(#{Some}(accelerate)); }
+                            #{Ok}(#{None}) => {}
+                            #{Err}(err) => #{tracing}::warn!(err = %err, "ignoring invalid S3 accelerate endpoint setting"),
+                        }
+                    }
+                    """,
+                    *preludeScope,
+                    "tracing" to RuntimeType.Tracing,
+                )
+            },
+        )
+    }
+
     override fun operationCustomizations(
         codegenContext: ClientCodegenContext,
         operation: OperationShape,
@@ -0,0 +1,48 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds the `download` module to S3 for downloads that validate the composite checksums of multipart objects
+ */
+class S3DownloadDecorator : ClientCodegenDecorator {
+    override val name: String = "S3DownloadDecorator"
+    override val order: Byte = 0
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        val rc = codegenContext.runtimeConfig
+        rustCrate.lib {
+            // We should have a better way of including an inline dependency.
+            rust(
+                "// include #T;",
+                RuntimeType.forInlineDependency(
+                    InlineAwsDependency.forRustFileAs(
+                        "s3_download",
+                        "download",
+                        Visibility.PUBLIC,
+                        CargoDependency.Bytes,
+                        CargoDependency.FuturesUtil,
+                        CargoDependency.smithyChecksums(rc),
+                        CargoDependency.smithyRuntimeApiClient(rc),
+                        CargoDependency.smithyTypes(rc),
+                    ),
+                ),
+            )
+        }
+    }
+}
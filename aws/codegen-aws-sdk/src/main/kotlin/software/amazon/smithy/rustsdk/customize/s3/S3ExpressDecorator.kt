@@ -7,6 +7,7 @@ package software.amazon.smithy.rustsdk.customize.s3
 
 import software.amazon.smithy.aws.traits.HttpChecksumTrait
 import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.ShapeId
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.configReexport
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
@@ -19,6 +20,7 @@ import software.amazon.smithy.rust.codegen.client.smithy.generators.client.Fluen
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
@@ -26,6 +28,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
 import software.amazon.smithy.rust.codegen.core.util.dq
@@ -60,7 +63,36 @@ class S3ExpressDecorator : ClientCodegenDecorator {
         baseCustomizations +
             S3ExpressRequestChecksumCustomization(
                 codegenContext, operation,
+            ) +
+            S3ExpressSessionRefreshCustomization(codegenContext, operation)
+
+    // The S3 Express identity provider creates sessions for every request to a directory bucket
+    override fun requiredOperations(codegenContext: ClientCodegenContext): List<ShapeId> =
+        listOf(ShapeId.from("com.amazonaws.s3#CreateSession"))
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        val rc = codegenContext.runtimeConfig
+        rustCrate.lib {
+            // We should have a better way of including an inline dependency.
+            rust(
+                "// include #T;",
+                RuntimeType.forInlineDependency(
+                    InlineAwsDependency.forRustFileAs(
+                        "s3_express_pagination",
+                        "express_pagination",
+                        Visibility.PUBLIC,
+                        listOfNotNull(codegenContext.operationsFeatureGate(ShapeId.from("com.amazonaws.s3#ListObjectsV2"))),
+                        CargoDependency.smithyAsync(rc),
+                        CargoDependency.smithyRuntimeApiClient(rc),
+                        CargoDependency.Tracing,
+                    ),
+                ),
             )
+        }
+    }
 
     override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> {
         val serviceId = codegenContext.serviceShape.sdkId()
@@ -153,6 +185,14 @@ private class S3ExpressIdentityProviderConfig(codegenContext: ClientCodegenConte
                     .resolve("client::identity::SharedIdentityResolver"),
             "S3_EXPRESS_SCHEME_ID" to
                 s3ExpressModule(runtimeConfig).resolve("auth::SCHEME_ID"),
+            "S3ExpressSessionCacheConfig" to
+                configReexport(
+                    s3ExpressModule(runtimeConfig).resolve("identity_cache::S3ExpressSessionCacheConfig"),
+                ),
+            "S3ExpressSessionEvictionPolicy" to
+                configReexport(
+                    s3ExpressModule(runtimeConfig).resolve("identity_cache::S3ExpressSessionEvictionPolicy"),
+                ),
         )
 
     override fun section(section: ServiceConfig) =
@@ -182,6 +222,30 @@ private class S3ExpressIdentityProviderConfig(codegenContext: ClientCodegenConte
                         """,
                         *codegenScope,
                     )
+
+                    rustTemplate(
+                        """
+                        /// Configures the cache of S3 Express session credentials used by the default S3 Express credentials provider
+                        ///
+                        /// Sets the maximum number of cached sessions, how sessions are evicted when the cache is full
+                        /// ([`#{S3ExpressSessionEvictionPolicy}`]), and how long before their expiration sessions are refreshed. See [`#{S3ExpressSessionCacheConfig}`]
+                        /// for the defaults and the metrics recorded by the cache. This has no effect if an
+                        /// [`express_credentials_provider`](Self::express_credentials_provider) is set.
+                        pub fn s3_express_session_cache_config(mut self, cache_config: #{S3ExpressSessionCacheConfig}) -> Self {
+                            self.set_s3_express_session_cache_config(#{Some}(cache_config));
+                            self
+                        }
+
+                        /// Configures the cache of S3 Express session credentials used by the default S3 Express credentials provider
+                        ///
+                        /// See [`Self::s3_express_session_cache_config`] for more information.
+                        pub fn set_s3_express_session_cache_config(&mut self, cache_config: #{Option}<#{S3ExpressSessionCacheConfig}>) -> &mut Self {
+                            self.config.store_or_unset(cache_config);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
                 }
 
                 else -> emptySection
@@ -259,6 +323,32 @@ class S3ExpressRequestChecksumCustomization(
         }
 }
 
+/**
+ * Registers an interceptor that removes the cached S3 Express session of a bucket once S3 rejected it, so that
+ * the next request to the bucket creates a new session
+ */
+class S3ExpressSessionRefreshCustomization(
+    codegenContext: ClientCodegenContext,
+    private val operationShape: OperationShape,
+) : OperationCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val symbolProvider = codegenContext.symbolProvider
+
+    override fun section(section: OperationSection): Writable =
+        writable {
+            if (section is OperationSection.AdditionalInterceptors) {
+                section.registerInterceptor(runtimeConfig, this) {
+                    rustTemplate(
+                        "#{Interceptor}::<#{OperationError}>::new()",
+                        "Interceptor" to
+                            s3ExpressModule(runtimeConfig).resolve("session_refresh::S3ExpressSessionRefreshInterceptor"),
+                        "OperationError" to symbolProvider.symbolForOperationError(operationShape),
+                    )
+                }
+            }
+        }
+}
+
 private fun s3ExpressModule(runtimeConfig: RuntimeConfig) =
     RuntimeType.forInlineDependency(
         InlineAwsDependency.forRustFile(
@@ -279,6 +369,7 @@ private fun s3ExpressDependencies(runtimeConfig: RuntimeConfig) =
         CargoDependency.Sha2,
         CargoDependency.smithyAsync(runtimeConfig),
         CargoDependency.smithyChecksums(runtimeConfig),
+        CargoDependency.smithyObservability(runtimeConfig),
         CargoDependency.smithyRuntimeApiClient(runtimeConfig),
         CargoDependency.smithyTypes(runtimeConfig),
     )
@@ -0,0 +1,109 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds `request_validation` to the S3 config builder to validate bucket names, keys, and metadata before requests are sent
+ */
+class S3RequestValidationDecorator : ClientCodegenDecorator {
+    override val name: String = "S3RequestValidationDecorator"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + S3RequestValidationConfig(codegenContext.runtimeConfig)
+
+    override fun serviceRuntimePluginCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ServiceRuntimePluginCustomization>,
+    ): List<ServiceRuntimePluginCustomization> =
+        baseCustomizations + S3RequestValidationRuntimePluginCustomization(codegenContext.runtimeConfig)
+}
+
+private fun requestValidationModule(runtimeConfig: RuntimeConfig) =
+    RuntimeType.forInlineDependency(
+        InlineAwsDependency.forRustFileAs(
+            "s3_request_validation",
+            "request_validation",
+            Visibility.PUBLIC,
+            CargoDependency.smithyRuntimeApiClient(runtimeConfig),
+            CargoDependency.smithyTypes(runtimeConfig),
+        ),
+    )
+
+private class S3RequestValidationConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "RequestValidation" to requestValidationModule(runtimeConfig).resolve("RequestValidation"),
+            "RequestValidationError" to requestValidationModule(runtimeConfig).resolve("RequestValidationError"),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Validates bucket names, object keys, and user-defined metadata of requests before they are sent
+                        ///
+                        /// Requests that fail a check of the validation fail with a [`#{RequestValidationError}`]
+                        /// instead of being sent. Requests aren't validated by default.
+                        pub fn request_validation(mut self, validation: #{RequestValidation}) -> Self {
+                            self.set_request_validation(#{Some}(validation));
+                            self
+                        }
+
+                        /// Validates bucket names, object keys, and user-defined metadata of requests before they are sent
+                        ///
+                        /// See [`Self::request_validation`] for more information.
+                        pub fn set_request_validation(&mut self, validation: #{Option}<#{RequestValidation}>) -> &mut Self {
+                            self.config.store_or_unset(validation);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+private class S3RequestValidationRuntimePluginCustomization(
+    private val runtimeConfig: RuntimeConfig,
+) : ServiceRuntimePluginCustomization() {
+    override fun section(section: ServiceRuntimePluginSection): Writable =
+        writable {
+            if (section is ServiceRuntimePluginSection.RegisterRuntimeComponents) {
+                section.registerInterceptor(this) {
+                    rustTemplate(
+                        "#{RequestValidationInterceptor}::new()",
+                        "RequestValidationInterceptor" to
+                            requestValidationModule(runtimeConfig).resolve("RequestValidationInterceptor"),
+                    )
+                }
+            }
+        }
+}
@@ -0,0 +1,67 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.DependencyScope
+import software.amazon.smithy.rust.codegen.core.rustlang.Feature
+import software.amazon.smithy.rust.codegen.core.rustlang.InlineDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+
+/**
+ * Adds the `seekable_reader` module to S3 for random access to objects through ranged `GetObject` requests
+ *
+ * The reader implements Tokio's `AsyncRead` and `AsyncSeek`, so the module is only available with the `rt-tokio` feature.
+ */
+class S3SeekableReaderDecorator : ClientCodegenDecorator {
+    override val name: String = "S3SeekableReaderDecorator"
+    override val order: Byte = 0
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        rustCrate.mergeFeature(Feature("rt-tokio", true, listOf("dep:tokio")))
+        rustCrate.lib {
+            // We should have a better way of including an inline dependency.
+            rust(
+                "// include #T;",
+                RuntimeType.forInlineDependency(
+                    InlineDependency.forRustFile(
+                        RustModule.new(
+                            "seekable_reader",
+                            Visibility.PUBLIC,
+                            additionalAttributes =
+                                listOfNotNull(
+                                    Attribute.featureGate("rt-tokio"),
+                                    codegenContext.operationsFeatureGate(
+                                        ShapeId.from("com.amazonaws.s3#HeadObject"),
+                                        ShapeId.from("com.amazonaws.s3#GetObject"),
+                                    ),
+                                ),
+                            documentationOverride = "",
+                        ),
+                        "/aws-inlineable/src/s3_seekable_reader.rs",
+                        CargoDependency.Bytes,
+                        CargoDependency.Tokio.copy(
+                            scope = DependencyScope.Compile,
+                            optional = true,
+                            features = emptySet(),
+                        ),
+                    ),
+                ),
+            )
+        }
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds `server_side_encryption_defaults` to the S3 config builder to encrypt new objects with SSE-KMS by default
+ */
+class S3ServerSideEncryptionDefaultsDecorator : ClientCodegenDecorator {
+    override val name: String = "S3ServerSideEncryptionDefaultsDecorator"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> =
+        baseCustomizations + S3ServerSideEncryptionDefaultsConfig(codegenContext.runtimeConfig)
+
+    override fun serviceRuntimePluginCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ServiceRuntimePluginCustomization>,
+    ): List<ServiceRuntimePluginCustomization> =
+        baseCustomizations + S3ServerSideEncryptionDefaultsRuntimePluginCustomization(codegenContext.runtimeConfig)
+}
+
+private fun sseDefaultsModule(runtimeConfig: RuntimeConfig) =
+    RuntimeType.forInlineDependency(
+        InlineAwsDependency.forRustFile(
+            "s3_sse_defaults", visibility = Visibility.PUBCRATE,
+            CargoDependency.smithyJson(runtimeConfig),
+            CargoDependency.smithyRuntimeApiClient(runtimeConfig),
+            CargoDependency.smithyTypes(runtimeConfig),
+        ),
+    )
+
+private class S3ServerSideEncryptionDefaultsConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "ServerSideEncryptionDefaults" to
+                configReexport(sseDefaultsModule(runtimeConfig).resolve("ServerSideEncryptionDefaults")),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets the SSE-KMS settings of objects written by `PutObject`, `CreateMultipartUpload`, and `CopyObject`
+                        ///
+                        /// The settings apply to requests that don't choose a server-side encryption on their own.
+                        /// See [`#{ServerSideEncryptionDefaults}`] for how requests override them.
+                        pub fn server_side_encryption_defaults(mut self, defaults: #{ServerSideEncryptionDefaults}) -> Self {
+                            self.set_server_side_encryption_defaults(#{Some}(defaults));
+                            self
+                        }
+
+                        /// Sets the SSE-KMS settings of objects written by `PutObject`, `CreateMultipartUpload`, and `CopyObject`
+                        ///
+                        /// See [`Self::server_side_encryption_defaults`] for more information.
+                        pub fn set_server_side_encryption_defaults(&mut self, defaults: #{Option}<#{ServerSideEncryptionDefaults}>) -> &mut Self {
+                            self.config.store_or_unset(defaults);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+private class S3ServerSideEncryptionDefaultsRuntimePluginCustomization(
+    private val runtimeConfig: RuntimeConfig,
+) : ServiceRuntimePluginCustomization() {
+    override fun section(section: ServiceRuntimePluginSection): Writable =
+        writable {
+            if (section is ServiceRuntimePluginSection.RegisterRuntimeComponents) {
+                section.registerInterceptor(this) {
+                    rustTemplate(
+                        "#{ServerSideEncryptionDefaultsInterceptor}::new()",
+                        "ServerSideEncryptionDefaultsInterceptor" to
+                            sseDefaultsModule(runtimeConfig).resolve("ServerSideEncryptionDefaultsInterceptor"),
+                    )
+                }
+            }
+        }
+}
@@ -0,0 +1,89 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rustsdk.InlineAwsDependency
+
+/**
+ * Adds `vpc_endpoint` to the S3 config builder to send requests to an interface VPC endpoint (AWS PrivateLink)
+ */
+class S3VpcEndpointDecorator : ClientCodegenDecorator {
+    override val name: String = "S3VpcEndpointDecorator"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + S3VpcEndpointConfig()
+}
+
+private class S3VpcEndpointConfig : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "vpc_endpoint_url" to
+                RuntimeType.forInlineDependency(InlineAwsDependency.forRustFile("s3_vpc_endpoint"))
+                    .resolve("vpc_endpoint_url"),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sends requests to an S3 interface VPC endpoint (AWS PrivateLink)
+                        ///
+                        /// `dns_name` is a DNS name of the VPC endpoint as listed in the VPC console, e.g.
+                        /// `*.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com`. The leading wildcard is optional.
+                        ///
+                        /// Buckets are addressed with virtual-hosted style requests, e.g.
+                        /// `https://my-bucket.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com`, unless
+                        /// [`force_path_style`](Self::force_path_style) is set, in which case requests are sent to
+                        /// `https://vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com/my-bucket`. Presigned requests
+                        /// are made against the VPC endpoint as well.
+                        ///
+                        /// The region of the client must match the region of the VPC endpoint. This replaces any
+                        /// endpoint URL that has been set.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use aws_sdk_s3::config::{Config, Region};
+                        ///
+                        /// let config = Config::builder()
+                        ///     .region(Region::new("us-east-1"))
+                        ///     .vpc_endpoint("*.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com")
+                        ///     .build();
+                        /// ```
+                        pub fn vpc_endpoint(mut self, dns_name: impl #{AsRef}<str>) -> Self {
+                            self.set_vpc_endpoint(#{Some}(dns_name.as_ref()));
+                            self
+                        }
+
+                        /// Sends requests to an S3 interface VPC endpoint (AWS PrivateLink)
+                        ///
+                        /// See [`Self::vpc_endpoint`] for more information.
+                        pub fn set_vpc_endpoint(&mut self, dns_name: #{Option}<&str>) -> &mut Self {
+                            self.set_endpoint_url(dns_name.map(#{vpc_endpoint_url}));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
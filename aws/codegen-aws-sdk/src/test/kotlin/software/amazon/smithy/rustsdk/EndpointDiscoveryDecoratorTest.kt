@@ -0,0 +1,156 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+class EndpointDiscoveryDecoratorTest {
+    private val model =
+        """
+        namespace test
+
+        use aws.api#clientDiscoveredEndpoint
+        use aws.api#clientEndpointDiscovery
+        use aws.api#clientEndpointDiscoveryId
+        use aws.api#service
+        use aws.auth#sigv4
+        use aws.protocols#awsJson1_0
+        use smithy.rules#endpointRuleSet
+
+        @service(sdkId: "dontcare")
+        @awsJson1_0
+        @sigv4(name: "dontcare")
+        @auth([sigv4])
+        @clientEndpointDiscovery(operation: DescribeEndpoints)
+        @endpointRuleSet({
+            "version": "1.0"
+            "parameters": {
+                "Region": { "required": false, "type": "String", "builtIn": "AWS::Region" },
+            }
+            "rules": [
+                {
+                    "type": "endpoint"
+                    "conditions": []
+                    "endpoint": { "url": "https://regional.example.com" }
+                }
+            ]
+        })
+        service TestService {
+            version: "2023-01-01",
+            operations: [DescribeEndpoints, GetItem]
+        }
+
+        @optionalAuth
+        operation DescribeEndpoints {
+            input := {
+                Operation: String
+                Identifiers: Identifiers
+            }
+            output := {
+                @required
+                Endpoints: Endpoints
+            }
+        }
+
+        map Identifiers {
+            key: String
+            value: String
+        }
+
+        list Endpoints {
+            member: Endpoint
+        }
+
+        structure Endpoint {
+            @required
+            Address: String
+
+            @required
+            CachePeriodInMinutes: Long
+        }
+
+        @optionalAuth
+        @clientDiscoveredEndpoint(required: false)
+        operation GetItem {
+            input := {
+                @required
+                @clientEndpointDiscoveryId
+                TableName: String
+            }
+        }
+        """.asSmithyModel(smithyVersion = "2.0")
+
+    @Test
+    fun `endpoints are discovered for the discovery identifiers of the input`() {
+        awsSdkIntegrationTest(model) { ctx, rustCrate ->
+            rustCrate.integrationTest("endpoint_discovery") {
+                tokioTest("endpoints_are_discovered_for_the_discovery_identifiers_of_the_input") {
+                    val module = ctx.moduleUseName()
+                    rustTemplate(
+                        """
+                        use $module::{Config, Client, config::Region};
+
+                        let http_client = #{StaticReplayClient}::new(vec![
+                            #{ReplayEvent}::new(
+                                http::Request::builder()
+                                    .uri("https://regional.example.com/")
+                                    .body(#{SdkBody}::empty())
+                                    .unwrap(),
+                                http::Response::builder()
+                                    .status(200)
+                                    .body(#{SdkBody}::from(r##"{"Endpoints":[{"Address":"table-a.example.com","CachePeriodInMinutes":10}]}"##))
+                                    .unwrap(),
+                            ),
+                            #{ReplayEvent}::new(
+                                http::Request::builder()
+                                    .uri("https://table-a.example.com/")
+                                    .body(#{SdkBody}::empty())
+                                    .unwrap(),
+                                http::Response::builder().status(200).body(#{SdkBody}::from("{}")).unwrap(),
+                            ),
+                        ]);
+                        let config = Config::builder()
+                            .http_client(http_client.clone())
+                            .region(Region::new("us-east-1"))
+                            .sleep_impl(#{SharedAsyncSleep}::new(#{TokioSleep}::new()))
+                            .time_source(#{SharedTimeSource}::default())
+                            .build();
+                        let (client, _reloader) = Client::from_conf(config)
+                            .with_endpoint_discovery_enabled()
+                            .await
+                            .expect("discovery is optional");
+
+                        client.get_item().table_name("a").send().await.expect("success");
+
+                        let requests: Vec<_> = http_client.actual_requests().collect();
+                        let discovery_request =
+                            std::str::from_utf8(requests[0].body().bytes().unwrap()).unwrap();
+                        assert!(discovery_request.contains(r##""Operation":"GetItem""##), "{discovery_request}");
+                        assert!(discovery_request.contains(r##""Identifiers":{"TableName":"a"}"##), "{discovery_request}");
+                        http_client.assert_requests_match(&[]);
+                        """,
+                        "SdkBody" to RuntimeType.sdkBody(ctx.runtimeConfig),
+                        "SharedAsyncSleep" to RuntimeType.smithyAsync(ctx.runtimeConfig).resolve("rt::sleep::SharedAsyncSleep"),
+                        "SharedTimeSource" to RuntimeType.smithyAsync(ctx.runtimeConfig).resolve("time::SharedTimeSource"),
+                        "StaticReplayClient" to
+                            CargoDependency.smithyHttpClientTestUtil(ctx.runtimeConfig).toType()
+                                .resolve("test_util::StaticReplayClient"),
+                        "ReplayEvent" to
+                            CargoDependency.smithyHttpClientTestUtil(ctx.runtimeConfig).toType()
+                                .resolve("test_util::ReplayEvent"),
+                        "TokioSleep" to RuntimeType.smithyAsync(ctx.runtimeConfig).resolve("rt::sleep::TokioSleep"),
+                    )
+                }
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ package software.amazon.smithy.rustsdk
 
 import org.junit.jupiter.api.Test
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
 import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
@@ -72,4 +73,40 @@ class TokenProvidersDecoratorTest {
             }
         }
     }
+
+    @Test
+    fun `it signs requests with tokens from the token provider`() {
+        awsSdkIntegrationTest(model) { ctx, rustCrate ->
+            rustCrate.integrationTest("bearer_auth") {
+                tokioTest("sends_token_in_authorization_header") {
+                    val moduleName = ctx.moduleUseName()
+                    rustTemplate(
+                        """
+                        let (http_client, rx) = #{capture_request}(#{None});
+                        let config = $moduleName::Config::builder()
+                            .with_test_defaults()
+                            .http_client(http_client)
+                            .token_provider(#{provide_token_fn}(|| async {
+                                #{Ok}(#{Token}::new("token-from-callback", #{None}))
+                            }))
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+                        let _ = client.some_operation().send().await;
+                        let request = rx.expect_request();
+                        assert_eq!(
+                            #{Some}("Bearer token-from-callback"),
+                            request.headers().get("authorization"),
+                        );
+                        """,
+                        *RuntimeType.preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(ctx.runtimeConfig),
+                        "provide_token_fn" to
+                            AwsRuntimeType.awsCredentialTypes(ctx.runtimeConfig)
+                                .resolve("token_fn::provide_token_fn"),
+                        "Token" to AwsRuntimeType.awsCredentialTypes(ctx.runtimeConfig).resolve("Token"),
+                    )
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,119 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk.customize.s3
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+import software.amazon.smithy.rustsdk.AwsRuntimeType
+import software.amazon.smithy.rustsdk.awsSdkIntegrationTest
+
+internal class S3DecoratorTest {
+    private val model =
+        """
+        namespace test
+
+        use aws.api#service
+        use aws.auth#sigv4
+        use aws.protocols#restXml
+        use smithy.rules#clientContextParams
+        use smithy.rules#endpointRuleSet
+
+        @service(sdkId: "S3")
+        @restXml
+        @sigv4(name: "s3")
+        @auth([sigv4])
+        @clientContextParams(
+            ForcePathStyle: { documentation: "Forces path style addressing", type: "boolean" }
+            Accelerate: { documentation: "Enables S3 Transfer Acceleration", type: "boolean" }
+        )
+        @endpointRuleSet({
+            "version": "1.0"
+            "parameters": {
+                "Region": { "required": false, "type": "String", "builtIn": "AWS::Region" },
+            }
+            "rules": [
+                {
+                    "type": "endpoint"
+                    "conditions": []
+                    "endpoint": { "url": "https://s3.amazonaws.com" }
+                }
+            ]
+        })
+        service S3 {
+            version: "2006-03-01",
+            operations: [GetFoo]
+        }
+
+        @http(uri: "/foo", method: "GET")
+        operation GetFoo {}
+        """.asSmithyModel(smithyVersion = "2.0")
+
+    // Only the profile settings are under test; the rest of the S3 customizations need the real S3 model
+    private val profileSettingsDecorator =
+        object : ClientCodegenDecorator {
+            override val name: String = "S3ProfileSettings"
+            override val order: Byte = 0
+
+            override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> =
+                S3Decorator().extraSections(codegenContext)
+        }
+
+    @Test
+    fun `s3 settings are loaded from the service config`() {
+        awsSdkIntegrationTest(model, additionalDecorators = listOf(profileSettingsDecorator)) { ctx, crate ->
+            val rc = ctx.runtimeConfig
+            crate.unitTest("s3_settings_are_loaded_from_the_service_config") {
+                rustTemplate(
+                    """
+                    ##[derive(Debug)]
+                    struct TestServiceConfig(&'static [(&'static str, &'static str)]);
+
+                    impl #{LoadServiceConfig} for TestServiceConfig {
+                        fn load_config(&self, key: #{ServiceConfigKey}<'_>) -> Option<String> {
+                            assert_eq!("S3", key.service_id());
+                            // These settings can only be set in a profile
+                            assert_eq!(None, key.env());
+                            self.0
+                                .iter()
+                                .find(|(profile_key, _)| *profile_key == key.profile())
+                                .map(|(_, value)| value.to_string())
+                        }
+                    }
+
+                    let load = |settings: &'static [(&'static str, &'static str)]| {
+                        let sdk_config = #{SdkConfig}::builder()
+                            .service_config(TestServiceConfig(settings))
+                            .build();
+                        let conf = crate::config::Builder::from(&sdk_config).build();
+                        (
+                            conf.config.load::<crate::config::ForcePathStyle>().map(|it| it.0),
+                            conf.config.load::<crate::config::Accelerate>().map(|it| it.0),
+                        )
+                    };
+
+                    assert_eq!(
+                        (Some(true), Some(true)),
+                        load(&[("addressing_style", "PATH"), ("use_accelerate_endpoint", "true")])
+                    );
+                    assert_eq!((Some(false), None), load(&[("addressing_style", "virtual")]));
+                    assert_eq!((None, Some(false)), load(&[("addressing_style", "auto"), ("use_accelerate_endpoint", "false")]));
+                    // Invalid values are ignored
+                    assert_eq!((None, None), load(&[("addressing_style", "sideways"), ("use_accelerate_endpoint", "maybe")]));
+                    assert_eq!((None, None), load(&[]));
+                    """,
+                    "LoadServiceConfig" to AwsRuntimeType.awsTypes(rc).resolve("service_config::LoadServiceConfig"),
+                    "SdkConfig" to AwsRuntimeType.awsTypes(rc).resolve("SdkConfig"),
+                    "ServiceConfigKey" to AwsRuntimeType.awsTypes(rc).resolve("service_config::ServiceConfigKey"),
+                )
+            }
+        }
+    }
+}
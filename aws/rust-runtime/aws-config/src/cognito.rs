@@ -0,0 +1,669 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Load credentials from an Amazon Cognito identity pool.
+//!
+//! The [`CognitoIdentityCredentialsProvider`] exchanges the logins of a Cognito identity pool for
+//! AWS credentials using the Cognito Identity `GetId` and `GetCredentialsForIdentity` APIs. Both APIs
+//! are unsigned, so no AWS credentials are required to use this provider.
+//!
+//! The identity ID returned by `GetId` is cached by the provider and reused every time credentials
+//! are refreshed. The credentials returned by Cognito expire, and will be refreshed automatically by
+//! the identity cache of the SDK client they are used with.
+
+use crate::json_credentials::{json_parse_loop, InvalidJsonCredentials};
+use crate::provider_config::ProviderConfig;
+use aws_credential_types::provider::token::{ProvideToken, SharedTokenProvider};
+use aws_credential_types::provider::{self, error::CredentialsError, future, ProvideCredentials};
+use aws_credential_types::Credentials;
+use aws_smithy_json::deserialize::token::{
+    expect_start_object, expect_string_or_null, expect_timestamp_or_null, skip_value,
+};
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_runtime::client::orchestrator::operation::{Operation, OperationBuilder};
+use aws_smithy_runtime::client::retries::classifiers::{
+    HttpStatusCodeClassifier, TransientErrorClassifier,
+};
+use aws_smithy_runtime_api::client::interceptors::context::Error;
+use aws_smithy_runtime_api::client::orchestrator::{
+    HttpRequest, HttpResponse, OrchestratorError, SensitiveOutput,
+};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::Layer;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use aws_types::region::Region;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+const PROVIDER_NAME: &str = "CognitoIdentity";
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Credentials provider that exchanges Amazon Cognito identity pool logins for AWS credentials.
+///
+/// Without any logins, the provider retrieves credentials for an unauthenticated (guest) identity,
+/// which requires unauthenticated access to be enabled on the identity pool. Logins map an identity
+/// provider name (such as `accounts.google.com`, `cognito-idp.us-east-1.amazonaws.com/<user pool ID>`,
+/// or the name of an OIDC provider) to a token from that provider. Since these tokens often expire,
+/// they are given as a [`ProvideToken`] implementation so that they can be refreshed.
+///
+/// Identity pools only exist in the region of their endpoint, so the region of the Cognito Identity
+/// endpoint is taken from the region prefix of the identity pool ID, and only falls back to the
+/// region of the [`ProviderConfig`] when the ID has no prefix.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_config::cognito::CognitoIdentityCredentialsProvider;
+/// use aws_credential_types::Token;
+///
+/// # fn id_token_from_my_login_flow() -> String { unimplemented!() }
+/// let provider = CognitoIdentityCredentialsProvider::builder()
+///     .identity_pool_id("us-east-1:00000000-0000-0000-0000-000000000000")
+///     .login("accounts.google.com", Token::new(id_token_from_my_login_flow(), None))
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct CognitoIdentityCredentialsProvider {
+    identity_pool_id: String,
+    account_id: Option<String>,
+    logins: Vec<(String, SharedTokenProvider)>,
+    identity_id: Mutex<Option<String>>,
+    get_id: Operation<GetIdInput, String, CredentialsError>,
+    get_credentials: Operation<GetCredentialsInput, Credentials, CredentialsError>,
+}
+
+impl CognitoIdentityCredentialsProvider {
+    /// Builder for this credentials provider
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    async fn credentials(&self) -> provider::Result {
+        let mut logins = Vec::with_capacity(self.logins.len());
+        for (provider_name, token_provider) in &self.logins {
+            let token = token_provider.provide_token().await.map_err(|err| {
+                CredentialsError::provider_error(format!(
+                    "failed to load the login token for `{provider_name}`: {err}"
+                ))
+            })?;
+            logins.push((provider_name.clone(), token.token().to_owned()));
+        }
+
+        let cached_identity_id = self.identity_id.lock().unwrap().clone();
+        let identity_id = match cached_identity_id {
+            Some(identity_id) => identity_id,
+            None => {
+                let identity_id = invoke(
+                    &self.get_id,
+                    GetIdInput {
+                        identity_pool_id: self.identity_pool_id.clone(),
+                        account_id: self.account_id.clone(),
+                        logins: logins.clone(),
+                    },
+                )
+                .await?;
+                tracing::debug!(identity_id = %identity_id, "loaded Cognito identity ID");
+                *self.identity_id.lock().unwrap() = Some(identity_id.clone());
+                identity_id
+            }
+        };
+
+        invoke(
+            &self.get_credentials,
+            GetCredentialsInput {
+                identity_id,
+                logins,
+            },
+        )
+        .await
+    }
+}
+
+impl ProvideCredentials for CognitoIdentityCredentialsProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+async fn invoke<I, O>(
+    operation: &Operation<I, O, CredentialsError>,
+    input: I,
+) -> Result<O, CredentialsError>
+where
+    I: fmt::Debug + Send + Sync + 'static,
+    O: fmt::Debug + Send + Sync + 'static,
+{
+    match operation.invoke(input).await {
+        Ok(output) => Ok(output),
+        Err(SdkError::ServiceError(context)) => Err(context.into_err()),
+        Err(other) => Err(CredentialsError::provider_error(other)),
+    }
+}
+
+/// Builder for [`CognitoIdentityCredentialsProvider`]
+#[derive(Debug, Default)]
+pub struct Builder {
+    provider_config: Option<ProviderConfig>,
+    identity_pool_id: Option<String>,
+    identity_id: Option<String>,
+    account_id: Option<String>,
+    logins: Vec<(String, SharedTokenProvider)>,
+    region: Option<Region>,
+    endpoint_url: Option<String>,
+}
+
+impl Builder {
+    /// Override the configuration used for this provider
+    pub fn configure(mut self, provider_config: &ProviderConfig) -> Self {
+        self.provider_config = Some(provider_config.clone());
+        self
+    }
+
+    /// The ID of the identity pool, e.g. `us-east-1:00000000-0000-0000-0000-000000000000`
+    ///
+    /// This is required.
+    pub fn identity_pool_id(mut self, identity_pool_id: impl Into<String>) -> Self {
+        self.identity_pool_id = Some(identity_pool_id.into());
+        self
+    }
+
+    /// A previously obtained identity ID
+    ///
+    /// When set, the `GetId` call is skipped and credentials are retrieved for this identity
+    /// directly. This allows an app to keep the same identity across restarts.
+    pub fn identity_id(mut self, identity_id: impl Into<String>) -> Self {
+        self.identity_id = Some(identity_id.into());
+        self
+    }
+
+    /// The ID of the AWS account that owns the identity pool
+    pub fn account_id(mut self, account_id: impl Into<String>) -> Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    /// Add a login for an identity provider
+    ///
+    /// `provider_name` is the identity provider name as configured on the identity pool, and
+    /// `token` provides the ID token (or access token) issued by that provider. The token is
+    /// requested again every time credentials are refreshed.
+    pub fn login(
+        mut self,
+        provider_name: impl Into<String>,
+        token: impl ProvideToken + 'static,
+    ) -> Self {
+        self.logins
+            .push((provider_name.into(), SharedTokenProvider::new(token)));
+        self
+    }
+
+    /// Override the region of the Cognito Identity endpoint
+    ///
+    /// By default, the region is taken from the identity pool ID.
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Override the Cognito Identity endpoint
+    pub fn endpoint_url(mut self, endpoint_url: impl Into<String>) -> Self {
+        self.endpoint_url = Some(endpoint_url.into());
+        self
+    }
+
+    /// Build a [`CognitoIdentityCredentialsProvider`] from this builder
+    ///
+    /// # Panics
+    ///
+    /// This will panic if no identity pool ID was given, or if the region can't be determined from
+    /// the identity pool ID or the configuration.
+    pub fn build(self) -> CognitoIdentityCredentialsProvider {
+        let provider_config = self.provider_config.unwrap_or_default();
+        let identity_pool_id = self
+            .identity_pool_id
+            .expect("an identity pool ID is required");
+        let endpoint_url = self.endpoint_url.unwrap_or_else(|| {
+            let region = self
+                .region
+                .or_else(|| {
+                    identity_pool_id
+                        .split_once(':')
+                        .map(|(region, _)| Region::new(region.to_owned()))
+                })
+                .or_else(|| provider_config.region())
+                .expect("the region must be part of the identity pool ID or be configured");
+            default_endpoint(&region)
+        });
+
+        CognitoIdentityCredentialsProvider {
+            identity_pool_id,
+            account_id: self.account_id,
+            logins: self.logins,
+            identity_id: Mutex::new(self.identity_id),
+            get_id: operation(&provider_config, &endpoint_url, "GetId")
+                .serializer(|input: GetIdInput| Ok(request("GetId", input.to_json())))
+                .deserializer(|response| parse_response(response, parse_get_id))
+                .build(),
+            get_credentials: operation(
+                &provider_config,
+                &endpoint_url,
+                "GetCredentialsForIdentity",
+            )
+            .serializer(|input: GetCredentialsInput| {
+                Ok(request("GetCredentialsForIdentity", input.to_json()))
+            })
+            .deserializer(|response| parse_response(response, parse_get_credentials))
+            .build(),
+        }
+    }
+}
+
+fn default_endpoint(region: &Region) -> String {
+    let suffix = if region.as_ref().starts_with("cn-") {
+        "amazonaws.com.cn"
+    } else {
+        "amazonaws.com"
+    };
+    format!("https://cognito-identity.{region}.{suffix}")
+}
+
+fn operation(
+    provider_config: &ProviderConfig,
+    endpoint_url: &str,
+    operation_name: &'static str,
+) -> OperationBuilder {
+    let mut builder = Operation::builder()
+        .service_name("CognitoIdentity")
+        .operation_name(operation_name)
+        .with_connection_poisoning()
+        .endpoint_url(endpoint_url)
+        .no_auth()
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+                .read_timeout(DEFAULT_READ_TIMEOUT)
+                .build(),
+        )
+        .runtime_plugin(StaticRuntimePlugin::new().with_config({
+            let mut layer = Layer::new("SensitiveOutput");
+            layer.store_put(SensitiveOutput);
+            layer.freeze()
+        }));
+    if let Some(http_client) = provider_config.http_client() {
+        builder = builder.http_client(http_client);
+    }
+    if let Some(sleep_impl) = provider_config.sleep_impl() {
+        builder = builder
+            .standard_retry(&RetryConfig::standard())
+            // Socket errors and network timeouts
+            .retry_classifier(TransientErrorClassifier::<Error>::new())
+            // 5xx errors
+            .retry_classifier(HttpStatusCodeClassifier::default())
+            .sleep_impl(sleep_impl);
+    } else {
+        builder = builder.no_retry();
+    }
+    builder
+}
+
+fn request(operation_name: &str, body: String) -> HttpRequest {
+    http::Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("content-type", "application/x-amz-json-1.1")
+        .header(
+            "x-amz-target",
+            format!("AWSCognitoIdentityService.{operation_name}"),
+        )
+        .body(SdkBody::from(body))
+        .expect("valid request")
+        .try_into()
+        .unwrap()
+}
+
+#[derive(Debug)]
+struct GetIdInput {
+    identity_pool_id: String,
+    account_id: Option<String>,
+    logins: Vec<(String, String)>,
+}
+
+impl GetIdInput {
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        let mut object = JsonObjectWriter::new(&mut out);
+        if let Some(account_id) = &self.account_id {
+            object.key("AccountId").string(account_id);
+        }
+        object.key("IdentityPoolId").string(&self.identity_pool_id);
+        write_logins(&mut object, &self.logins);
+        object.finish();
+        out
+    }
+}
+
+#[derive(Debug)]
+struct GetCredentialsInput {
+    identity_id: String,
+    logins: Vec<(String, String)>,
+}
+
+impl GetCredentialsInput {
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        let mut object = JsonObjectWriter::new(&mut out);
+        object.key("IdentityId").string(&self.identity_id);
+        write_logins(&mut object, &self.logins);
+        object.finish();
+        out
+    }
+}
+
+fn write_logins(object: &mut JsonObjectWriter<'_>, logins: &[(String, String)]) {
+    if !logins.is_empty() {
+        let mut logins_object = object.key("Logins").start_object();
+        for (provider_name, token) in logins {
+            logins_object.key(provider_name).string(token);
+        }
+        logins_object.finish();
+    }
+}
+
+fn parse_response<T>(
+    response: &HttpResponse,
+    parse: impl Fn(&[u8]) -> Result<T, InvalidJsonCredentials>,
+) -> Result<T, OrchestratorError<CredentialsError>> {
+    let body = response.body().bytes().expect("non-streaming deserializer");
+    if !response.status().is_success() {
+        let (code, message) = parse_error(body);
+        return Err(OrchestratorError::operation(
+            CredentialsError::provider_error(format!(
+                "Cognito Identity returned an error ({:?}) [{}]: {}",
+                response.status(),
+                code.as_deref().unwrap_or("unknown"),
+                message.as_deref().unwrap_or("no message"),
+            )),
+        ));
+    }
+    parse(body).map_err(|err| OrchestratorError::operation(CredentialsError::unhandled(err)))
+}
+
+/// Extracts the error code and message from a JSON error response
+fn parse_error(body: &[u8]) -> (Option<String>, Option<String>) {
+    let (mut code, mut message) = (None, None);
+    let _ = json_parse_loop(body, |key, value| {
+        if let Token::ValueString { value, .. } = value {
+            if key.eq_ignore_ascii_case("__type") || key.eq_ignore_ascii_case("code") {
+                // The code may be prefixed with a namespace, e.g. `com.amazonaws#NotAuthorizedException`
+                let value = value.to_unescaped()?;
+                code = Some(value.rsplit('#').next().unwrap_or_default().to_owned());
+            } else if key.eq_ignore_ascii_case("message") {
+                message = Some(value.to_unescaped()?.into_owned());
+            }
+        }
+        Ok(())
+    });
+    (code, message)
+}
+
+fn parse_get_id(body: &[u8]) -> Result<String, InvalidJsonCredentials> {
+    let mut identity_id = None;
+    json_parse_loop(body, |key, value| {
+        if let (key, Token::ValueString { value, .. }) = (key, value) {
+            if key == "IdentityId" {
+                identity_id = Some(value.to_unescaped()?.into_owned());
+            }
+        }
+        Ok(())
+    })?;
+    identity_id.ok_or(InvalidJsonCredentials::MissingField("IdentityId"))
+}
+
+fn parse_get_credentials(body: &[u8]) -> Result<Credentials, InvalidJsonCredentials> {
+    let mut tokens = json_token_iter(body).peekable();
+    expect_start_object(tokens.next())?;
+    let (mut access_key_id, mut secret_key, mut session_token, mut expiration) =
+        (None, None, None, None);
+    loop {
+        match tokens.next().transpose()? {
+            Some(Token::EndObject { .. }) => break,
+            Some(Token::ObjectKey { key, .. }) if key.to_unescaped()? == "Credentials" => {
+                expect_start_object(tokens.next())?;
+                loop {
+                    match tokens.next().transpose()? {
+                        Some(Token::EndObject { .. }) => break,
+                        Some(Token::ObjectKey { key, .. }) => match key.to_unescaped()?.as_ref() {
+                            "AccessKeyId" => {
+                                access_key_id = expect_string_or_null(tokens.next())?
+                                    .map(|v| v.to_unescaped().map(|v| v.into_owned()))
+                                    .transpose()?
+                            }
+                            "SecretKey" => {
+                                secret_key = expect_string_or_null(tokens.next())?
+                                    .map(|v| v.to_unescaped().map(|v| v.into_owned()))
+                                    .transpose()?
+                            }
+                            "SessionToken" => {
+                                session_token = expect_string_or_null(tokens.next())?
+                                    .map(|v| v.to_unescaped().map(|v| v.into_owned()))
+                                    .transpose()?
+                            }
+                            "Expiration" => {
+                                expiration =
+                                    expect_timestamp_or_null(tokens.next(), Format::EpochSeconds)?
+                            }
+                            _ => skip_value(&mut tokens)?,
+                        },
+                        other => {
+                            return Err(InvalidJsonCredentials::Other(
+                                format!("expected object key, found: {other:?}").into(),
+                            ))
+                        }
+                    }
+                }
+            }
+            Some(Token::ObjectKey { .. }) => skip_value(&mut tokens)?,
+            other => {
+                return Err(InvalidJsonCredentials::Other(
+                    format!("expected object key, found: {other:?}").into(),
+                ))
+            }
+        }
+    }
+
+    let expiration = expiration.ok_or(InvalidJsonCredentials::MissingField("Expiration"))?;
+    let expiration = SystemTime::try_from(expiration).map_err(|_| {
+        InvalidJsonCredentials::Other(
+            "credential expiration time cannot be represented by a SystemTime".into(),
+        )
+    })?;
+    Ok(Credentials::new(
+        access_key_id.ok_or(InvalidJsonCredentials::MissingField("AccessKeyId"))?,
+        secret_key.ok_or(InvalidJsonCredentials::MissingField("SecretKey"))?,
+        Some(session_token.ok_or(InvalidJsonCredentials::MissingField("SessionToken"))?),
+        Some(expiration),
+        PROVIDER_NAME,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_credential_types::Token as LoginToken;
+    use aws_smithy_http_client::test_util::{ReplayEvent, StaticReplayClient};
+    use http::{Request, Response};
+
+    const IDENTITY_POOL_ID: &str = "us-east-1:00000000-0000-0000-0000-000000000000";
+    const IDENTITY_ID: &str = "us-east-1:11111111-1111-1111-1111-111111111111";
+
+    fn event(target: &str, request_body: &str, status: u16, response_body: &str) -> ReplayEvent {
+        ReplayEvent::new(
+            Request::builder()
+                .uri("https://cognito-identity.us-east-1.amazonaws.com/")
+                .header("content-type", "application/x-amz-json-1.1")
+                .header(
+                    "x-amz-target",
+                    format!("AWSCognitoIdentityService.{target}"),
+                )
+                .body(SdkBody::from(request_body.to_owned()))
+                .unwrap(),
+            Response::builder()
+                .status(status)
+                .body(SdkBody::from(response_body.to_owned()))
+                .unwrap(),
+        )
+    }
+
+    fn credentials_event(logins: &str) -> ReplayEvent {
+        event(
+            "GetCredentialsForIdentity",
+            &format!(r#"{{"IdentityId":"{IDENTITY_ID}"{logins}}}"#),
+            200,
+            &format!(
+                r#"{{
+                    "IdentityId": "{IDENTITY_ID}",
+                    "Credentials": {{
+                        "AccessKeyId": "ASIARTEST",
+                        "SecretKey": "secret",
+                        "SessionToken": "session-token",
+                        "Expiration": 1631935916
+                    }}
+                }}"#
+            ),
+        )
+    }
+
+    fn provider(http_client: &StaticReplayClient) -> Builder {
+        CognitoIdentityCredentialsProvider::builder()
+            .configure(&ProviderConfig::no_configuration().with_http_client(http_client.clone()))
+            .identity_pool_id(IDENTITY_POOL_ID)
+    }
+
+    #[tokio::test]
+    async fn unauthenticated_identity() {
+        let http_client = StaticReplayClient::new(vec![
+            event(
+                "GetId",
+                &format!(r#"{{"IdentityPoolId":"{IDENTITY_POOL_ID}"}}"#),
+                200,
+                &format!(r#"{{"IdentityId":"{IDENTITY_ID}"}}"#),
+            ),
+            credentials_event(""),
+            credentials_event(""),
+        ]);
+        let provider = provider(&http_client).build();
+
+        let creds = provider.provide_credentials().await.expect("success");
+        assert_eq!("ASIARTEST", creds.access_key_id());
+        assert_eq!("secret", creds.secret_access_key());
+        assert_eq!(Some("session-token"), creds.session_token());
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1631935916)),
+            creds.expiry()
+        );
+
+        // The identity ID is cached, so only `GetCredentialsForIdentity` is called again
+        provider.provide_credentials().await.expect("success");
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn authenticated_identity() {
+        let logins = r#","Logins":{"accounts.google.com":"id-token"}"#;
+        let http_client = StaticReplayClient::new(vec![
+            event(
+                "GetId",
+                &format!(
+                    r#"{{"AccountId":"123456789012","IdentityPoolId":"{IDENTITY_POOL_ID}"{logins}}}"#
+                ),
+                200,
+                &format!(r#"{{"IdentityId":"{IDENTITY_ID}"}}"#),
+            ),
+            credentials_event(logins),
+        ]);
+        let provider = provider(&http_client)
+            .account_id("123456789012")
+            .login("accounts.google.com", LoginToken::new("id-token", None))
+            .build();
+
+        let creds = provider.provide_credentials().await.expect("success");
+        assert_eq!("ASIARTEST", creds.access_key_id());
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn known_identity_id_skips_get_id() {
+        let http_client = StaticReplayClient::new(vec![credentials_event("")]);
+        let provider = provider(&http_client).identity_id(IDENTITY_ID).build();
+
+        provider.provide_credentials().await.expect("success");
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn error_response() {
+        let http_client = StaticReplayClient::new(vec![event(
+            "GetId",
+            &format!(r#"{{"IdentityPoolId":"{IDENTITY_POOL_ID}"}}"#),
+            400,
+            r#"{"__type":"NotAuthorizedException","message":"Unauthenticated access is not supported for this identity pool."}"#,
+        )]);
+        let err = provider(&http_client)
+            .build()
+            .provide_credentials()
+            .await
+            .expect_err("it should fail");
+        assert!(
+            matches!(err, CredentialsError::ProviderError { .. }),
+            "should be CredentialsError::ProviderError: {err}",
+        );
+        let message = format!(
+            "{}",
+            aws_smithy_types::error::display::DisplayErrorContext(&err)
+        );
+        assert!(message.contains("NotAuthorizedException"), "{message}");
+        assert!(message.contains("Unauthenticated access"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn identity_pool_region_takes_precedence_over_configured_region() {
+        let http_client = StaticReplayClient::new(vec![credentials_event("")]);
+        let provider = CognitoIdentityCredentialsProvider::builder()
+            .configure(
+                &ProviderConfig::no_configuration()
+                    .with_http_client(http_client.clone())
+                    .with_region(Some(Region::from_static("us-west-2"))),
+            )
+            .identity_pool_id(IDENTITY_POOL_ID)
+            .identity_id(IDENTITY_ID)
+            .build();
+
+        // The request is sent to the `us-east-1` endpoint of the identity pool
+        provider.provide_credentials().await.expect("success");
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[test]
+    fn endpoint_for_region() {
+        assert_eq!(
+            "https://cognito-identity.us-west-2.amazonaws.com",
+            default_endpoint(&Region::from_static("us-west-2"))
+        );
+        assert_eq!(
+            "https://cognito-identity.cn-north-1.amazonaws.com.cn",
+            default_endpoint(&Region::from_static("cn-north-1"))
+        );
+    }
+}
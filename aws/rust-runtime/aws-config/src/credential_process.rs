@@ -15,10 +15,20 @@ use aws_credential_types::provider::{self, error::CredentialsError, future, Prov
 use aws_credential_types::Credentials;
 use aws_smithy_json::deserialize::Token;
 use std::borrow::Cow;
-use std::process::Command;
-use std::time::SystemTime;
+use std::io;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+
+/// Request written to the stdin of a persistent helper process each time credentials are needed.
+const HELPER_REQUEST: &[u8] = b"{\"Version\": 1, \"Action\": \"GetCredentials\"}\n";
+
+/// How long a persistent helper process has to respond to a request before it's restarted.
+const HELPER_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// External process credentials provider
 ///
@@ -53,10 +63,26 @@ use time::OffsetDateTime;
 /// [profile example]
 /// credential_process = /path/to/my/process --some --arguments
 /// ```
+///
+/// # Persistent helper processes
+///
+/// By default, a new process is spawned every time credentials are loaded. A provider created with
+/// [`CredentialProcessProvider::new_persistent`] instead keeps a single helper process alive and
+/// requests refreshed credentials from it over `stdin` and `stdout`. Each request is a single line:
+///
+/// ```json
+/// {"Version": 1, "Action": "GetCredentials"}
+/// ```
+///
+/// and the helper must answer with the JSON document described above, written on a single line.
+/// If the helper exits, doesn't respond within 10 seconds, or its response can't be read, it is
+/// restarted on the next request. The helper's `stderr` is discarded.
 #[derive(Debug)]
 pub struct CredentialProcessProvider {
     command: CommandWithSensitiveArgs<String>,
     profile_account_id: Option<AccountId>,
+    helper: Option<Mutex<Option<HelperProcess>>>,
+    helper_response_timeout: Duration,
 }
 
 impl ProvideCredentials for CredentialProcessProvider {
@@ -74,6 +100,20 @@ impl CredentialProcessProvider {
         Self {
             command: CommandWithSensitiveArgs::new(command),
             profile_account_id: None,
+            helper: None,
+            helper_response_timeout: HELPER_RESPONSE_TIMEOUT,
+        }
+    }
+
+    /// Create new [`CredentialProcessProvider`] that keeps the external process running and
+    /// requests refreshed credentials from it rather than spawning a new process per refresh.
+    ///
+    /// See [persistent helper processes](CredentialProcessProvider#persistent-helper-processes)
+    /// for the protocol the process must implement.
+    pub fn new_persistent(command: String) -> Self {
+        Self {
+            helper: Some(Mutex::new(None)),
+            ..Self::new(command)
         }
     }
 
@@ -81,11 +121,8 @@ impl CredentialProcessProvider {
         Builder::default()
     }
 
-    async fn credentials(&self) -> provider::Result {
-        // Security: command arguments must be redacted at debug level
-        tracing::debug!(command = %self.command, "loading credentials from external process");
-
-        let command = if cfg!(windows) {
+    fn shell_command(&self) -> Command {
+        if cfg!(windows) {
             let mut command = Command::new("cmd.exe");
             command.args(["/C", self.command.unredacted()]);
             command
@@ -93,8 +130,82 @@ impl CredentialProcessProvider {
             let mut command = Command::new("sh");
             command.args(["-c", self.command.unredacted()]);
             command
+        }
+    }
+
+    async fn credentials(&self) -> provider::Result {
+        let output = match &self.helper {
+            Some(helper) => self.output_from_helper(helper).await?,
+            None => self.output_from_process().await?,
         };
-        let output = tokio::process::Command::from(command)
+
+        parse_credential_process_json_credentials(&output, self.profile_account_id.as_ref())
+            .map(|mut creds| {
+                creds
+                    .get_property_mut_or_default::<Vec<AwsCredentialFeature>>()
+                    .push(AwsCredentialFeature::CredentialsProcess);
+                creds
+            })
+            .map_err(|invalid| {
+                CredentialsError::provider_error(format!(
+                "Error retrieving credentials from external process, could not parse response: {invalid}",
+            ))
+            })
+    }
+
+    async fn output_from_helper(
+        &self,
+        helper: &Mutex<Option<HelperProcess>>,
+    ) -> Result<String, CredentialsError> {
+        // The helper is taken out of the slot while a request is in flight, so the lock is never
+        // held across an await. A cancelled request drops (and kills) the process rather than
+        // leaving an unread response behind, and a concurrent request starts its own process.
+        let idle = helper.lock().unwrap().take();
+        if let Some(mut process) = idle {
+            match process.request(self.helper_response_timeout).await {
+                Ok(output) => {
+                    Self::release_helper(helper, process);
+                    return Ok(output);
+                }
+                Err(err) => {
+                    tracing::debug!(error = %err, "credential helper process failed, restarting it");
+                }
+            }
+        }
+
+        // Security: command arguments must be redacted at debug level
+        tracing::debug!(command = %self.command, "starting credential helper process");
+        let mut process = HelperProcess::spawn(self.shell_command()).map_err(|e| {
+            CredentialsError::provider_error(format!(
+                "Error starting credential helper process: {e}",
+            ))
+        })?;
+        let output = process
+            .request(self.helper_response_timeout)
+            .await
+            .map_err(|e| {
+                CredentialsError::provider_error(format!(
+                    "Error retrieving credentials from credential helper process: {e}",
+                ))
+            })?;
+        Self::release_helper(helper, process);
+        Ok(output)
+    }
+
+    /// Puts a helper process back into the slot, unless a concurrent request already did. The
+    /// surplus process is killed when it's dropped.
+    fn release_helper(helper: &Mutex<Option<HelperProcess>>, process: HelperProcess) {
+        let mut helper = helper.lock().unwrap();
+        if helper.is_none() {
+            *helper = Some(process);
+        }
+    }
+
+    async fn output_from_process(&self) -> Result<String, CredentialsError> {
+        // Security: command arguments must be redacted at debug level
+        tracing::debug!(command = %self.command, "loading credentials from external process");
+
+        let output = tokio::process::Command::from(self.shell_command())
             .output()
             .await
             .map_err(|e| {
@@ -115,24 +226,59 @@ impl CredentialProcessProvider {
             )));
         }
 
-        let output = std::str::from_utf8(&output.stdout).map_err(|e| {
+        String::from_utf8(output.stdout).map_err(|e| {
             CredentialsError::provider_error(format!(
                 "Error retrieving credentials from external process: could not decode output as UTF-8: {e}",
             ))
-        })?;
+        })
+    }
+}
 
-        parse_credential_process_json_credentials(output, self.profile_account_id.as_ref())
-            .map(|mut creds| {
-                creds
-                    .get_property_mut_or_default::<Vec<AwsCredentialFeature>>()
-                    .push(AwsCredentialFeature::CredentialsProcess);
-                creds
-            })
-            .map_err(|invalid| {
-                CredentialsError::provider_error(format!(
-                "Error retrieving credentials from external process, could not parse response: {invalid}",
-            ))
-            })
+/// A long-running credential helper process and the pipes used to talk to it.
+#[derive(Debug)]
+struct HelperProcess {
+    // Held so that the process is killed when the helper is dropped.
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl HelperProcess {
+    fn spawn(command: Command) -> io::Result<Self> {
+        let mut child = tokio::process::Command::from(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        Ok(Self {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    async fn request(&mut self, timeout: Duration) -> io::Result<String> {
+        self.stdin.write_all(HELPER_REQUEST).await?;
+        self.stdin.flush().await?;
+        let mut response = String::new();
+        let read = tokio::time::timeout(timeout, self.stdout.read_line(&mut response))
+            .await
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("credential helper process didn't respond within {timeout:?}"),
+                )
+            })??;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "credential helper process exited without responding",
+            ));
+        }
+        Ok(response)
     }
 }
 
@@ -162,6 +308,8 @@ impl Builder {
         CredentialProcessProvider {
             command: self.command.expect("should be set"),
             profile_account_id: self.profile_account_id,
+            helper: None,
+            helper_response_timeout: HELPER_RESPONSE_TIMEOUT,
         }
     }
 }
@@ -269,6 +417,7 @@ mod test {
     use crate::sensitive_command::CommandWithSensitiveArgs;
     use aws_credential_types::credential_feature::AwsCredentialFeature;
     use aws_credential_types::provider::ProvideCredentials;
+    use aws_smithy_types::error::display::DisplayErrorContext;
     use std::time::{Duration, SystemTime};
     use time::format_description::well_known::Rfc3339;
     use time::OffsetDateTime;
@@ -358,4 +507,92 @@ mod test {
             creds.get_property::<Vec<AwsCredentialFeature>>().unwrap()
         );
     }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn persistent_helper_is_reused() {
+        let provider = CredentialProcessProvider::new_persistent(String::from(
+            r#"n=0; while read -r line; do n=$((n+1)); echo "{ \"Version\": 1, \"AccessKeyId\": \"ASIARTESTID$n\", \"SecretAccessKey\": \"TESTSECRETKEY\" }"; done"#,
+        ));
+        let first = provider.provide_credentials().await.expect("valid creds");
+        assert_eq!("ASIARTESTID1", first.access_key_id());
+        let second = provider.provide_credentials().await.expect("valid creds");
+        assert_eq!("ASIARTESTID2", second.access_key_id());
+        assert_eq!(
+            &vec![AwsCredentialFeature::CredentialsProcess],
+            second.get_property::<Vec<AwsCredentialFeature>>().unwrap()
+        );
+    }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn persistent_helper_is_restarted_after_exit() {
+        let provider = CredentialProcessProvider::new_persistent(String::from(
+            r#"read -r line; echo '{ "Version": 1, "AccessKeyId": "ASIARTESTID", "SecretAccessKey": "TESTSECRETKEY" }'"#,
+        ));
+        for _ in 0..3 {
+            let creds = provider.provide_credentials().await.expect("valid creds");
+            assert_eq!("ASIARTESTID", creds.access_key_id());
+        }
+    }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn persistent_helper_exits_without_responding() {
+        let provider =
+            CredentialProcessProvider::new_persistent(String::from("read -r line; exit 0"));
+        let err = provider
+            .provide_credentials()
+            .await
+            .expect_err("no response from helper");
+        assert!(
+            format!("{}", DisplayErrorContext(&err)).contains("exited without responding"),
+            "{}",
+            DisplayErrorContext(&err)
+        );
+    }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn persistent_helper_that_does_not_respond_times_out() {
+        let mut provider = CredentialProcessProvider::new_persistent(String::from("sleep 60"));
+        provider.helper_response_timeout = Duration::from_millis(100);
+        let err = provider
+            .provide_credentials()
+            .await
+            .expect_err("no response from helper");
+        assert!(
+            format!("{}", DisplayErrorContext(&err)).contains("didn't respond within"),
+            "{}",
+            DisplayErrorContext(&err)
+        );
+    }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn concurrent_requests_to_persistent_helper_do_not_wait_for_each_other() {
+        // The first helper that is started never responds, while the ones started after it do
+        let marker = std::env::temp_dir().join(format!("credential-helper-{}", std::process::id()));
+        let mut provider = CredentialProcessProvider::new_persistent(format!(
+            r#"if [ -s {marker} ]; then while read -r line; do echo '{{ "Version": 1, "AccessKeyId": "ASIARTESTID", "SecretAccessKey": "TESTSECRETKEY" }}'; done; else echo started > {marker}; sleep 60; fi"#,
+            marker = marker.display()
+        ));
+        provider.helper_response_timeout = Duration::from_secs(5);
+        let stuck = provider.provide_credentials();
+        let fast = async {
+            // give the first request time to start its helper
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            timeout(Duration::from_secs(2), provider.provide_credentials()).await
+        };
+        let (stuck, fast) = tokio::join!(stuck, fast);
+        let _ = std::fs::remove_file(&marker);
+        let fast = fast.expect("not blocked by the request in flight");
+        assert_eq!("ASIARTESTID", fast.expect("valid creds").access_key_id());
+        stuck.expect_err("no response from the first helper");
+    }
 }
@@ -72,3 +72,13 @@ pub mod account_id_endpoint_mode;
 
 /// Default provider chain for auth scheme preference list
 pub mod auth_scheme_preference;
+
+/// Default provider chain for the defaults mode
+pub(crate) mod defaults_mode;
+
+/// Default provider chain for the SigV4a signing region set
+pub mod sigv4a_signing_region_set;
+
+/// Default provider chain for proxy settings
+#[cfg(feature = "default-https-client")]
+pub(crate) mod proxy_config;
@@ -38,12 +38,12 @@ pub(crate) async fn auth_scheme_preference_provider(
     EnvConfigValue::new()
         .env(env::AUTH_SCHEME_PREFERENCE)
         .profile(profile_key::AUTH_SCHEME_PREFERENCE)
-        .validate(&env, profiles, parse_auth_scheme_names)
+        .validate(&env, profiles, |csv| parse_auth_scheme_names(csv).map(AuthSchemePreference::from))
         .map_err(|err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for `AuthSchemePreference`"))
         .unwrap_or(None)
 }
 
-fn parse_auth_scheme_names(csv: &str) -> Result<AuthSchemePreference, InvalidAuthSchemeNamesCsv> {
+fn parse_auth_scheme_names(csv: &str) -> Result<Vec<AuthSchemeId>, InvalidAuthSchemeNamesCsv> {
     csv.split(',')
         .map(|s| {
             let trimmed = s.trim().replace([' ', '\t'], "");
@@ -56,7 +56,6 @@ fn parse_auth_scheme_names(csv: &str) -> Result<AuthSchemePreference, InvalidAut
             Ok(AuthSchemeId::from(Cow::Owned(scheme_name.to_owned())))
         })
         .collect::<Result<Vec<_>, _>>()
-        .map(AuthSchemePreference::from)
 }
 
 #[derive(Debug)]
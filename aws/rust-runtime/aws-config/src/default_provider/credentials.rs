@@ -10,8 +10,9 @@ use aws_credential_types::Credentials;
 use tracing::Instrument;
 
 use crate::environment::credentials::EnvironmentVariableCredentialsProvider;
-use crate::meta::credentials::CredentialsProviderChain;
+use crate::meta::credentials::{ChainHealthReport, CredentialsProviderChain};
 use crate::meta::region::ProvideRegion;
+use crate::offline::SkippedCredentialsProvider;
 use crate::provider_config::ProviderConfig;
 
 #[cfg(any(feature = "default-https-client", feature = "rustls"))]
@@ -69,6 +70,16 @@ impl DefaultCredentialsChain {
         Builder::default()
     }
 
+    /// Validates every provider in the default chain and reports the outcome for each of them
+    ///
+    /// See [`CredentialsProviderChain::health_report`] for details.
+    pub async fn health_report(&self) -> ChainHealthReport {
+        self.provider_chain
+            .health_report()
+            .instrument(tracing::debug_span!("default_credentials_chain"))
+            .await
+    }
+
     async fn credentials(&self) -> provider::Result {
         self.provider_chain
             .provide_credentials()
@@ -88,6 +99,13 @@ impl ProvideCredentials for DefaultCredentialsChain {
     fn fallback_on_interrupt(&self) -> Option<Credentials> {
         self.provider_chain.fallback_on_interrupt()
     }
+
+    fn validate<'a>(&'a self) -> future::Validate<'a>
+    where
+        Self: 'a,
+    {
+        self.provider_chain.validate()
+    }
 }
 
 /// Builder for [`DefaultCredentialsChain`].
@@ -182,15 +200,35 @@ impl Builder {
 
         let env_provider = EnvironmentVariableCredentialsProvider::new_with_env(conf.env());
         let profile_provider = self.profile_file_builder.configure(&conf).build();
-        let web_identity_token_provider = self.web_identity_builder.configure(&conf).build();
-        let imds_provider = self.imds_builder.configure(&conf).build();
-        let ecs_provider = self.ecs_builder.configure(&conf).build();
 
         let provider_chain = CredentialsProviderChain::first_try("Environment", env_provider)
-            .or_else("Profile", profile_provider)
-            .or_else("WebIdentityToken", web_identity_token_provider)
-            .or_else("EcsContainer", ecs_provider)
-            .or_else("Ec2InstanceMetadata", imds_provider);
+            .or_else("Profile", profile_provider);
+        let provider_chain = if conf.offline() {
+            provider_chain
+                .or_else(
+                    "WebIdentityToken",
+                    SkippedCredentialsProvider::new("WebIdentityToken"),
+                )
+                .or_else(
+                    "EcsContainer",
+                    SkippedCredentialsProvider::new("EcsContainer"),
+                )
+                .or_else(
+                    "Ec2InstanceMetadata",
+                    SkippedCredentialsProvider::new("Ec2InstanceMetadata"),
+                )
+        } else {
+            provider_chain
+                .or_else(
+                    "WebIdentityToken",
+                    self.web_identity_builder.configure(&conf).build(),
+                )
+                .or_else("EcsContainer", self.ecs_builder.configure(&conf).build())
+                .or_else(
+                    "Ec2InstanceMetadata",
+                    self.imds_builder.configure(&conf).build(),
+                )
+        };
 
         DefaultCredentialsChain { provider_chain }
     }
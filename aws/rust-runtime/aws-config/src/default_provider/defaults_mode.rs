@@ -0,0 +1,209 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::imds;
+use crate::provider_config::ProviderConfig;
+use aws_runtime::env_config::EnvConfigValue;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::timeout::TimeoutConfig;
+use aws_types::defaults_mode::DefaultsMode;
+use aws_types::region::Region;
+use std::str::FromStr;
+use std::time::Duration;
+
+mod env {
+    pub(super) const DEFAULTS_MODE: &str = "AWS_DEFAULTS_MODE";
+    pub(super) const EXECUTION_ENV: &str = "AWS_EXECUTION_ENV";
+    pub(super) const REGION: &str = "AWS_REGION";
+    pub(super) const DEFAULT_REGION: &str = "AWS_DEFAULT_REGION";
+}
+
+mod profile_key {
+    pub(super) const DEFAULTS_MODE: &str = "defaults_mode";
+}
+
+const IMDS_REGION_PATH: &str = "/latest/meta-data/placement/region";
+const IMDS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Load the value for the defaults mode
+///
+/// This checks the following sources:
+/// 1. The environment variable `AWS_DEFAULTS_MODE=legacy/standard/in-region/cross-region/mobile/auto`
+/// 2. The profile key `defaults_mode=legacy/standard/in-region/cross-region/mobile/auto`
+///
+/// If invalid values are found, the provider will return `None` and an error will be logged.
+pub(crate) async fn defaults_mode_provider(
+    provider_config: &ProviderConfig,
+) -> Option<DefaultsMode> {
+    let env = provider_config.env();
+    let profiles = provider_config.profile().await;
+
+    EnvConfigValue::new()
+        .env(env::DEFAULTS_MODE)
+        .profile(profile_key::DEFAULTS_MODE)
+        .validate(&env, profiles, DefaultsMode::from_str)
+        .map_err(|err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for `DefaultsMode`"))
+        .unwrap_or(None)
+}
+
+/// Resolve [`DefaultsMode::Auto`] to the mode matching the execution environment
+///
+/// Mobile platforms use [`DefaultsMode::Mobile`]. Otherwise, the region the application runs in
+/// is compared to the configured `region`, resulting in [`DefaultsMode::InRegion`] or
+/// [`DefaultsMode::CrossRegion`]. The region the application runs in is read from `AWS_REGION`
+/// in managed environments that set `AWS_EXECUTION_ENV` (e.g. Lambda), and from the EC2 instance
+/// metadata service otherwise. If it can't be determined, [`DefaultsMode::Standard`] is used.
+pub(crate) async fn resolve_auto_mode(
+    provider_config: &ProviderConfig,
+    region: Option<&Region>,
+) -> DefaultsMode {
+    if cfg!(any(target_os = "android", target_os = "ios")) {
+        return DefaultsMode::Mobile;
+    }
+    let Some(region) = region else {
+        tracing::debug!("no region configured, using the `standard` defaults mode");
+        return DefaultsMode::Standard;
+    };
+    let mode = match current_region(provider_config).await {
+        Some(current_region) if &current_region == region => DefaultsMode::InRegion,
+        Some(_) => DefaultsMode::CrossRegion,
+        None => DefaultsMode::Standard,
+    };
+    tracing::debug!(defaults_mode = %mode, "resolved the `auto` defaults mode");
+    mode
+}
+
+async fn current_region(provider_config: &ProviderConfig) -> Option<Region> {
+    let env = provider_config.env();
+    if env.get(env::EXECUTION_ENV).is_ok() {
+        if let Ok(region) = env
+            .get(env::REGION)
+            .or_else(|_| env.get(env::DEFAULT_REGION))
+        {
+            return Some(Region::new(region));
+        }
+    }
+    if env
+        .get(imds::env::EC2_METADATA_DISABLED)
+        .map(|value| value.eq_ignore_ascii_case("true"))
+        .unwrap_or_default()
+    {
+        return None;
+    }
+    if provider_config.offline() {
+        tracing::debug!("network access is disabled, not loading the current region from IMDS");
+        return None;
+    }
+    // Outside of EC2 this request is expected to fail, so give up quickly
+    let client = imds::Client::builder()
+        .configure(provider_config)
+        .max_attempts(1)
+        .connect_timeout(IMDS_TIMEOUT)
+        .read_timeout(IMDS_TIMEOUT)
+        .build();
+    match client.get(IMDS_REGION_PATH).await {
+        Ok(region) => Some(Region::new(String::from(region))),
+        Err(err) => {
+            tracing::debug!(err = %DisplayErrorContext(&err), "couldn't load the current region from IMDS");
+            None
+        }
+    }
+}
+
+/// Default timeouts of a defaults mode
+///
+/// The `legacy` mode doesn't change any defaults. The standard retry mode that the other modes
+/// select is already the default, so only timeouts are returned.
+pub(crate) fn timeout_config(mode: DefaultsMode) -> Option<TimeoutConfig> {
+    let connect_timeout = match mode {
+        DefaultsMode::InRegion => Duration::from_millis(1100),
+        DefaultsMode::Standard | DefaultsMode::CrossRegion => Duration::from_millis(3100),
+        DefaultsMode::Mobile => Duration::from_millis(30000),
+        _ => return None,
+    };
+    Some(
+        TimeoutConfig::builder()
+            .connect_timeout(connect_timeout)
+            .build(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{defaults_mode_provider, env, resolve_auto_mode, timeout_config};
+    use crate::provider_config::ProviderConfig;
+    use aws_types::defaults_mode::DefaultsMode;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use aws_types::region::Region;
+    use std::time::Duration;
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_value() {
+        let conf =
+            ProviderConfig::empty().with_env(Env::from_slice(&[(env::DEFAULTS_MODE, "invalid")]));
+        assert_eq!(None, defaults_mode_provider(&conf).await);
+        assert!(logs_contain("invalid value for `DefaultsMode`"));
+    }
+
+    #[tokio::test]
+    async fn environment_priority() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[
+                (env::DEFAULTS_MODE, "in-region"),
+                ("AWS_CONFIG_FILE", "conf"),
+            ]))
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\ndefaults_mode = cross-region",
+            )]));
+        assert_eq!(
+            Some(DefaultsMode::InRegion),
+            defaults_mode_provider(&conf).await
+        );
+    }
+
+    #[tokio::test]
+    async fn auto_mode_in_execution_environment() {
+        let conf = ProviderConfig::empty().with_env(Env::from_slice(&[
+            (env::EXECUTION_ENV, "AWS_Lambda_rust"),
+            (env::REGION, "us-west-2"),
+        ]));
+        assert_eq!(
+            DefaultsMode::InRegion,
+            resolve_auto_mode(&conf, Some(&Region::new("us-west-2"))).await
+        );
+        assert_eq!(
+            DefaultsMode::CrossRegion,
+            resolve_auto_mode(&conf, Some(&Region::new("eu-west-1"))).await
+        );
+        assert_eq!(DefaultsMode::Standard, resolve_auto_mode(&conf, None).await);
+
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[("AWS_EC2_METADATA_DISABLED", "true")]));
+        assert_eq!(
+            DefaultsMode::Standard,
+            resolve_auto_mode(&conf, Some(&Region::new("us-west-2"))).await
+        );
+    }
+
+    #[test]
+    fn mode_timeouts() {
+        assert!(timeout_config(DefaultsMode::Legacy).is_none());
+        assert_eq!(
+            Some(Duration::from_millis(1100)),
+            timeout_config(DefaultsMode::InRegion)
+                .unwrap()
+                .connect_timeout()
+        );
+        assert_eq!(
+            Some(Duration::from_secs(30)),
+            timeout_config(DefaultsMode::Mobile)
+                .unwrap()
+                .connect_timeout()
+        );
+    }
+}
@@ -23,6 +23,9 @@ mod profile_key {
 /// 1. The environment variable `AWS_ENDPOINT_URL=http://localhost`
 /// 2. The profile key `endpoint_url=http://localhost`
 ///
+/// The URL may contain `{service}` and `{region}` placeholders, e.g.
+/// `https://{service}.{region}.mycorp.internal`, which are expanded for each request.
+///
 /// If invalid values are found, the provider will return None and an error will be logged.
 pub async fn endpoint_url_provider(provider_config: &ProviderConfig) -> Option<String> {
     let env = provider_config.env();
@@ -81,6 +84,18 @@ mod test {
         assert!(logs_contain(env::ENDPOINT_URL));
     }
 
+    #[tokio::test]
+    async fn url_with_placeholders() {
+        let conf = ProviderConfig::empty().with_env(Env::from_slice(&[(
+            env::ENDPOINT_URL,
+            "https://{service}.{region}.mycorp.internal",
+        )]));
+        assert_eq!(
+            Some("https://{service}.{region}.mycorp.internal".to_owned()),
+            endpoint_url_provider(&conf).await,
+        );
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn environment_priority() {
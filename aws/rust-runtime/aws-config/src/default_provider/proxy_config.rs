@@ -0,0 +1,130 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::provider_config::ProviderConfig;
+use aws_smithy_http_client::proxy::ProxyConfig;
+use aws_smithy_runtime::client::defaults::default_http_client_with_proxy;
+use aws_smithy_runtime_api::client::behavior_version::BehaviorVersion;
+use aws_smithy_runtime_api::client::http::SharedHttpClient;
+use aws_smithy_types::error::display::DisplayErrorContext;
+
+mod profile_key {
+    pub(super) const PROXY_URL: &str = "proxy_url";
+    pub(super) const PROXY_USERNAME: &str = "proxy_username";
+    pub(super) const PROXY_PASSWORD: &str = "proxy_password";
+    pub(super) const NO_PROXY: &str = "no_proxy";
+}
+
+/// Load the proxy configuration from the selected profile
+///
+/// This reads the following profile keys:
+/// - `proxy_url`: the proxy used for all HTTP and HTTPS traffic
+/// - `proxy_username` and `proxy_password`: basic authentication credentials for the proxy
+/// - `no_proxy`: comma-separated hosts that bypass the proxy, e.g. `localhost,*.internal`
+///
+/// Returns `None` if `proxy_url` isn't set. If it is invalid, the provider will return `None`
+/// and an error will be logged.
+pub(crate) async fn proxy_config_provider(provider_config: &ProviderConfig) -> Option<ProxyConfig> {
+    let profiles = provider_config.profile().await?;
+    let proxy_url = profiles.get(profile_key::PROXY_URL)?;
+    let proxy_config = match ProxyConfig::all(proxy_url.trim()) {
+        Ok(proxy_config) => proxy_config,
+        Err(err) => {
+            tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for `proxy_url`");
+            return None;
+        }
+    };
+    let proxy_config = match (
+        profiles.get(profile_key::PROXY_USERNAME),
+        profiles.get(profile_key::PROXY_PASSWORD),
+    ) {
+        (Some(username), Some(password)) => proxy_config.with_basic_auth(username, password),
+        (None, None) => proxy_config,
+        _ => {
+            tracing::warn!(
+                "`proxy_username` and `proxy_password` must be set together, connecting to the proxy without credentials"
+            );
+            proxy_config
+        }
+    };
+    Some(match profiles.get(profile_key::NO_PROXY) {
+        Some(rules) => proxy_config.no_proxy(rules),
+        None => proxy_config,
+    })
+}
+
+/// Creates the default HTTPS client for `behavior_version`, routing requests through `proxy_config`
+///
+/// Returns `None` and logs a warning if the default client for `behavior_version` doesn't support
+/// proxies.
+pub(crate) fn http_client_with_proxy(
+    behavior_version: BehaviorVersion,
+    proxy_config: ProxyConfig,
+) -> Option<SharedHttpClient> {
+    let http_client = default_http_client_with_proxy(behavior_version, proxy_config);
+    if http_client.is_none() {
+        tracing::warn!(
+            "the default HTTP client of this behavior version doesn't support proxies, ignoring the proxy settings from the profile"
+        );
+    }
+    http_client
+}
+
+#[cfg(test)]
+mod test {
+    use super::proxy_config_provider;
+    use crate::provider_config::ProviderConfig;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use tracing_test::traced_test;
+
+    fn provider_config(profile: &str) -> ProviderConfig {
+        ProviderConfig::empty()
+            .with_env(Env::from_slice(&[("AWS_CONFIG_FILE", "conf")]))
+            .with_fs(Fs::from_slice(&[("conf", profile)]))
+    }
+
+    #[tokio::test]
+    async fn no_proxy_configured() {
+        let conf = provider_config("[default]\nregion = us-east-1");
+        assert!(proxy_config_provider(&conf).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn load_from_profile() {
+        let conf = provider_config(
+            "[default]\n\
+             proxy_url = http://proxy.example.com:8080\n\
+             proxy_username = user\n\
+             proxy_password = pass\n\
+             no_proxy = localhost,*.internal",
+        );
+        let proxy_config = proxy_config_provider(&conf)
+            .await
+            .expect("proxy configured");
+        assert!(!proxy_config.is_disabled());
+        assert!(!proxy_config.is_from_env());
+        let debug = format!("{proxy_config:?}");
+        assert!(debug.contains("proxy.example.com:8080"), "{debug}");
+        assert!(debug.contains("localhost,*.internal"), "{debug}");
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_url() {
+        let conf = provider_config("[default]\nproxy_url = not a url");
+        assert!(proxy_config_provider(&conf).await.is_none());
+        assert!(logs_contain("invalid value for `proxy_url`"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn incomplete_credentials_are_ignored() {
+        let conf = provider_config(
+            "[default]\nproxy_url = http://proxy.example.com:8080\nproxy_username = user",
+        );
+        assert!(proxy_config_provider(&conf).await.is_some());
+        assert!(logs_contain("must be set together"));
+    }
+}
@@ -34,6 +34,14 @@ impl DefaultRegionChain {
     pub fn builder() -> Builder {
         Builder::default()
     }
+
+    /// Convert this chain into a [`RegionProviderChain`] that can be customized
+    ///
+    /// The providers are named `Environment`, `Profile`, and `Ec2InstanceMetadata`. When network
+    /// access is disabled, the `Ec2InstanceMetadata` provider is omitted.
+    pub fn into_chain(self) -> RegionProviderChain {
+        self.0
+    }
 }
 
 /// Builder for [DefaultRegionChain]
@@ -42,6 +50,7 @@ pub struct Builder {
     env_provider: EnvironmentVariableRegionProvider,
     profile_file: profile::region::Builder,
     imds: imds::region::Builder,
+    offline: bool,
 }
 
 impl Builder {
@@ -52,6 +61,7 @@ impl Builder {
         self.env_provider = EnvironmentVariableRegionProvider::new_with_env(configuration.env());
         self.profile_file = self.profile_file.configure(configuration);
         self.imds = self.imds.configure(configuration);
+        self.offline = configuration.offline();
         self
     }
 
@@ -63,11 +73,12 @@ impl Builder {
 
     /// Build a [DefaultRegionChain]
     pub fn build(self) -> DefaultRegionChain {
-        DefaultRegionChain(
-            RegionProviderChain::first_try(self.env_provider)
-                .or_else(self.profile_file.build())
-                .or_else(self.imds.build()),
-        )
+        let chain = RegionProviderChain::first_try_named("Environment", self.env_provider)
+            .or_else_named("Profile", self.profile_file.build());
+        if self.offline {
+            return DefaultRegionChain(chain);
+        }
+        DefaultRegionChain(chain.or_else_named("Ec2InstanceMetadata", self.imds.build()))
     }
 }
 
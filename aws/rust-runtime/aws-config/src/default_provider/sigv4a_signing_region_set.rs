@@ -0,0 +1,114 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::provider_config::ProviderConfig;
+use aws_runtime::env_config::EnvConfigValue;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_types::region::SigningRegionSet;
+use std::error::Error;
+use std::fmt;
+
+mod env {
+    pub(super) const SIGV4A_SIGNING_REGION_SET: &str = "AWS_SIGV4A_SIGNING_REGION_SET";
+}
+
+mod profile_key {
+    pub(super) const SIGV4A_SIGNING_REGION_SET: &str = "sigv4a_signing_region_set";
+}
+
+/// Load the value for the SigV4a signing region set
+///
+/// This checks the following sources:
+/// 1. The environment variable `AWS_SIGV4A_SIGNING_REGION_SET=us-east-1,us-west-2`
+/// 2. The profile key `sigv4a_signing_region_set=us-east-1,us-west-2`
+///
+/// Whitespace around regions is ignored. If invalid values are found, the provider will return
+/// `None` and an error will be logged.
+pub async fn sigv4a_signing_region_set_provider(
+    provider_config: &ProviderConfig,
+) -> Option<SigningRegionSet> {
+    let env = provider_config.env();
+    let profiles = provider_config.profile().await;
+
+    EnvConfigValue::new()
+        .env(env::SIGV4A_SIGNING_REGION_SET)
+        .profile(profile_key::SIGV4A_SIGNING_REGION_SET)
+        .validate(&env, profiles, parse_region_set)
+        .map_err(|err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for SigV4a signing region set"))
+        .unwrap_or(None)
+}
+
+fn parse_region_set(value: &str) -> Result<SigningRegionSet, InvalidSigningRegionSet> {
+    let regions: Vec<_> = value.split(',').map(str::trim).collect();
+    if regions.iter().any(|region| region.is_empty()) {
+        return Err(InvalidSigningRegionSet);
+    }
+    Ok(regions.into_iter().collect())
+}
+
+#[derive(Debug)]
+struct InvalidSigningRegionSet;
+
+impl fmt::Display for InvalidSigningRegionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a comma-separated list of regions, e.g. `us-east-1,us-west-2` or `*`"
+        )
+    }
+}
+
+impl Error for InvalidSigningRegionSet {}
+
+#[cfg(test)]
+mod test {
+    use super::sigv4a_signing_region_set_provider;
+    use crate::provider_config::ProviderConfig;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use aws_types::region::SigningRegionSet;
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    async fn environment_priority() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[
+                ("AWS_SIGV4A_SIGNING_REGION_SET", "us-east-1, us-west-2"),
+                ("AWS_CONFIG_FILE", "conf"),
+            ]))
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\nsigv4a_signing_region_set = *",
+            )]));
+        assert_eq!(
+            Some(SigningRegionSet::from("us-east-1,us-west-2")),
+            sigv4a_signing_region_set_provider(&conf).await
+        );
+    }
+
+    #[tokio::test]
+    async fn load_from_profile() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[("AWS_CONFIG_FILE", "conf")]))
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\nsigv4a_signing_region_set = *",
+            )]));
+        assert_eq!(
+            Some(SigningRegionSet::from("*")),
+            sigv4a_signing_region_set_provider(&conf).await
+        );
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_value() {
+        let conf = ProviderConfig::empty().with_env(Env::from_slice(&[(
+            "AWS_SIGV4A_SIGNING_REGION_SET",
+            "us-east-1,,us-west-2",
+        )]));
+        assert_eq!(None, sigv4a_signing_region_set_provider(&conf).await);
+        assert!(logs_contain("invalid value for SigV4a signing region set"));
+    }
+}
@@ -0,0 +1,465 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Load configuration from a structured document
+//!
+//! Applications that centralize their configuration outside of environment variables and profile
+//! files (for example, in a Kubernetes ConfigMap) can describe the shared config as a JSON object
+//! or an [`aws_smithy_types::Document`] and apply it to a [`ConfigLoader`](crate::ConfigLoader)
+//! with [`from_json`](crate::ConfigLoader::from_json) or
+//! [`from_document`](crate::ConfigLoader::from_document). Documents in other formats (such as
+//! TOML) can be converted into a [`Document`] by the application first.
+//!
+//! Keys use the same names as the equivalent profile file keys where one exists:
+//!
+//! | Key                            | Value                                                       |
+//! |--------------------------------|-------------------------------------------------------------|
+//! | `region`                       | region name                                                 |
+//! | `profile`                      | name of the profile to load the remaining config from       |
+//! | `endpoint_url`                 | endpoint URL for all services                               |
+//! | `use_fips_endpoint`            | boolean                                                     |
+//! | `use_dualstack_endpoint`       | boolean                                                     |
+//! | `sdk_ua_app_id`                | app name added to the user agent                            |
+//! | `aws_access_key_id`            | static access key ID                                        |
+//! | `aws_secret_access_key`        | static secret access key                                    |
+//! | `aws_session_token`            | static session token                                        |
+//! | `credential_source`            | `Environment`, `Ec2InstanceMetadata`, or `EcsContainer`     |
+//! | `retry_mode`                   | `standard` or `adaptive`                                    |
+//! | `max_attempts`                 | maximum number of attempts, including the initial attempt   |
+//! | `connect_timeout_ms`           | connect timeout in milliseconds                             |
+//! | `read_timeout_ms`              | read timeout in milliseconds                                |
+//! | `operation_timeout_ms`         | operation timeout in milliseconds                           |
+//! | `operation_attempt_timeout_ms` | operation attempt timeout in milliseconds                   |
+//!
+//! Booleans and numbers may also be given as strings, since many configuration systems only
+//! support string values. Unknown keys are ignored so that the document can be shared with other
+//! application settings. Settings that aren't in the document are resolved the usual way.
+//!
+//! # Examples
+//! ```no_run
+//! # async fn example() -> Result<(), aws_config::document::DocumentConfigError> {
+//! use aws_config::BehaviorVersion;
+//!
+//! let config = aws_config::defaults(BehaviorVersion::latest())
+//!     .from_json(r#"{ "region": "us-west-2", "max_attempts": 5, "connect_timeout_ms": 3100 }"#)?
+//!     .load()
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::provider_config::ProviderConfig;
+use aws_credential_types::provider::SharedCredentialsProvider;
+use aws_credential_types::Credentials;
+use aws_smithy_json::deserialize::error::DeserializeError;
+use aws_smithy_json::deserialize::json_token_iter;
+use aws_smithy_json::deserialize::token::expect_document;
+use aws_smithy_types::retry::{RetryConfig, RetryMode};
+use aws_smithy_types::timeout::TimeoutConfig;
+use aws_smithy_types::Document;
+use aws_types::app_name::AppName;
+use aws_types::region::Region;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+const PROVIDER_NAME: &str = "DocumentConfig";
+
+#[derive(Debug)]
+enum ErrorKind {
+    InvalidJson(DeserializeError),
+    NotAnObject,
+    InvalidValue {
+        key: &'static str,
+        expected: &'static str,
+    },
+    IncompleteCredentials,
+}
+
+/// Error returned when a configuration document can't be applied
+#[derive(Debug)]
+pub struct DocumentConfigError {
+    kind: ErrorKind,
+}
+
+impl DocumentConfigError {
+    fn invalid_value(key: &'static str, expected: &'static str) -> Self {
+        Self {
+            kind: ErrorKind::InvalidValue { key, expected },
+        }
+    }
+}
+
+impl fmt::Display for DocumentConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::InvalidJson(_) => write!(f, "configuration document is not valid JSON"),
+            ErrorKind::NotAnObject => write!(f, "configuration document must be an object"),
+            ErrorKind::InvalidValue { key, expected } => {
+                write!(f, "invalid value for `{key}`: expected {expected}")
+            }
+            ErrorKind::IncompleteCredentials => write!(
+                f,
+                "`aws_access_key_id` and `aws_secret_access_key` must be set together"
+            ),
+        }
+    }
+}
+
+impl Error for DocumentConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ErrorKind::InvalidJson(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Named credentials provider selected with `credential_source`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CredentialSource {
+    Environment,
+    Ec2InstanceMetadata,
+    EcsContainer,
+}
+
+impl CredentialSource {
+    fn parse(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("Environment") {
+            Some(Self::Environment)
+        } else if value.eq_ignore_ascii_case("Ec2InstanceMetadata") {
+            Some(Self::Ec2InstanceMetadata)
+        } else if value.eq_ignore_ascii_case("EcsContainer") {
+            Some(Self::EcsContainer)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn provider(self, conf: &ProviderConfig) -> SharedCredentialsProvider {
+        match self {
+            Self::Environment => SharedCredentialsProvider::new(
+                crate::environment::credentials::EnvironmentVariableCredentialsProvider::new_with_env(
+                    conf.env(),
+                ),
+            ),
+            Self::Ec2InstanceMetadata => SharedCredentialsProvider::new(
+                crate::imds::credentials::ImdsCredentialsProvider::builder()
+                    .configure(conf)
+                    .build(),
+            ),
+            Self::EcsContainer => SharedCredentialsProvider::new(
+                crate::ecs::EcsCredentialsProvider::builder()
+                    .configure(conf)
+                    .build(),
+            ),
+        }
+    }
+}
+
+/// Settings read from a configuration document
+#[derive(Debug, Default)]
+pub(crate) struct DocumentConfig {
+    pub(crate) region: Option<Region>,
+    pub(crate) profile_name: Option<String>,
+    pub(crate) endpoint_url: Option<String>,
+    pub(crate) use_fips: Option<bool>,
+    pub(crate) use_dual_stack: Option<bool>,
+    pub(crate) app_name: Option<AppName>,
+    pub(crate) credentials: Option<Credentials>,
+    pub(crate) credential_source: Option<CredentialSource>,
+    pub(crate) retry_config: Option<RetryConfig>,
+    pub(crate) timeout_config: Option<TimeoutConfig>,
+}
+
+impl DocumentConfig {
+    pub(crate) fn from_json(json: &str) -> Result<Self, DocumentConfigError> {
+        let mut tokens = json_token_iter(json.as_bytes()).peekable();
+        let document = expect_document(&mut tokens).map_err(|err| DocumentConfigError {
+            kind: ErrorKind::InvalidJson(err),
+        })?;
+        if tokens.next().is_some() {
+            return Err(DocumentConfigError {
+                kind: ErrorKind::InvalidJson(DeserializeError::custom(
+                    "found more JSON tokens after completing parsing",
+                )),
+            });
+        }
+        Self::from_document(&document)
+    }
+
+    pub(crate) fn from_document(document: &Document) -> Result<Self, DocumentConfigError> {
+        let values = Values(document.as_object().ok_or(DocumentConfigError {
+            kind: ErrorKind::NotAnObject,
+        })?);
+
+        let credentials = match (
+            values.string("aws_access_key_id")?,
+            values.string("aws_secret_access_key")?,
+        ) {
+            (Some(access_key_id), Some(secret_access_key)) => Some(Credentials::new(
+                access_key_id,
+                secret_access_key,
+                values.string("aws_session_token")?.map(str::to_owned),
+                None,
+                PROVIDER_NAME,
+            )),
+            (None, None) => None,
+            _ => {
+                return Err(DocumentConfigError {
+                    kind: ErrorKind::IncompleteCredentials,
+                })
+            }
+        };
+        let credential_source = values
+            .string("credential_source")?
+            .map(|source| {
+                CredentialSource::parse(source).ok_or(DocumentConfigError::invalid_value(
+                    "credential_source",
+                    "`Environment`, `Ec2InstanceMetadata`, or `EcsContainer`",
+                ))
+            })
+            .transpose()?;
+
+        let retry_mode = values
+            .string("retry_mode")?
+            .map(|mode| {
+                mode.parse::<RetryMode>().map_err(|_| {
+                    DocumentConfigError::invalid_value("retry_mode", "`standard` or `adaptive`")
+                })
+            })
+            .transpose()?;
+        let max_attempts = values.u32("max_attempts")?;
+        if max_attempts == Some(0) {
+            return Err(DocumentConfigError::invalid_value(
+                "max_attempts",
+                "a number greater than zero",
+            ));
+        }
+        let retry_config = if retry_mode.is_some() || max_attempts.is_some() {
+            let mut retry_config = RetryConfig::standard();
+            if let Some(retry_mode) = retry_mode {
+                retry_config = retry_config.with_retry_mode(retry_mode);
+            }
+            if let Some(max_attempts) = max_attempts {
+                retry_config = retry_config.with_max_attempts(max_attempts);
+            }
+            Some(retry_config)
+        } else {
+            None
+        };
+
+        let mut timeouts = TimeoutConfig::builder();
+        let mut has_timeouts = false;
+        if let Some(timeout) = values.millis("connect_timeout_ms")? {
+            timeouts = timeouts.connect_timeout(timeout);
+            has_timeouts = true;
+        }
+        if let Some(timeout) = values.millis("read_timeout_ms")? {
+            timeouts = timeouts.read_timeout(timeout);
+            has_timeouts = true;
+        }
+        if let Some(timeout) = values.millis("operation_timeout_ms")? {
+            timeouts = timeouts.operation_timeout(timeout);
+            has_timeouts = true;
+        }
+        if let Some(timeout) = values.millis("operation_attempt_timeout_ms")? {
+            timeouts = timeouts.operation_attempt_timeout(timeout);
+            has_timeouts = true;
+        }
+
+        Ok(Self {
+            region: values
+                .string("region")?
+                .map(|region| Region::new(region.to_owned())),
+            profile_name: values.string("profile")?.map(str::to_owned),
+            endpoint_url: values.string("endpoint_url")?.map(str::to_owned),
+            use_fips: values.bool("use_fips_endpoint")?,
+            use_dual_stack: values.bool("use_dualstack_endpoint")?,
+            app_name: values
+                .string("sdk_ua_app_id")?
+                .map(|app_name| {
+                    AppName::new(app_name.to_owned()).map_err(|_| {
+                        DocumentConfigError::invalid_value("sdk_ua_app_id", "a valid app name")
+                    })
+                })
+                .transpose()?,
+            credentials,
+            credential_source,
+            retry_config,
+            timeout_config: has_timeouts.then(|| timeouts.build()),
+        })
+    }
+}
+
+/// Typed accessors for the top-level values of a configuration document
+struct Values<'a>(&'a HashMap<String, Document>);
+
+impl<'a> Values<'a> {
+    fn get(&self, key: &str) -> Option<&'a Document> {
+        self.0.get(key).filter(|value| value.as_null().is_none())
+    }
+
+    fn string(&self, key: &'static str) -> Result<Option<&'a str>, DocumentConfigError> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Document::String(value)) => Ok(Some(value.as_str())),
+            Some(_) => Err(DocumentConfigError::invalid_value(key, "a string")),
+        }
+    }
+
+    fn bool(&self, key: &'static str) -> Result<Option<bool>, DocumentConfigError> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Document::Bool(value)) => Ok(Some(*value)),
+            Some(Document::String(value)) if value.eq_ignore_ascii_case("true") => Ok(Some(true)),
+            Some(Document::String(value)) if value.eq_ignore_ascii_case("false") => Ok(Some(false)),
+            Some(_) => Err(DocumentConfigError::invalid_value(key, "a boolean")),
+        }
+    }
+
+    fn u64(
+        &self,
+        key: &'static str,
+        expected: &'static str,
+    ) -> Result<Option<u64>, DocumentConfigError> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Document::Number(value)) => u64::try_from(*value)
+                .map(Some)
+                .map_err(|_| DocumentConfigError::invalid_value(key, expected)),
+            Some(Document::String(value)) => value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| DocumentConfigError::invalid_value(key, expected)),
+            Some(_) => Err(DocumentConfigError::invalid_value(key, expected)),
+        }
+    }
+
+    fn u32(&self, key: &'static str) -> Result<Option<u32>, DocumentConfigError> {
+        const EXPECTED: &str = "a non-negative 32-bit integer";
+        self.u64(key, EXPECTED)?
+            .map(|value| {
+                u32::try_from(value).map_err(|_| DocumentConfigError::invalid_value(key, EXPECTED))
+            })
+            .transpose()
+    }
+
+    fn millis(&self, key: &'static str) -> Result<Option<Duration>, DocumentConfigError> {
+        Ok(self
+            .u64(key, "a non-negative number of milliseconds")?
+            .map(Duration::from_millis))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CredentialSource, DocumentConfig};
+    use aws_smithy_types::retry::RetryMode;
+    use aws_smithy_types::Document;
+    use std::time::Duration;
+
+    #[test]
+    fn parse_json_document() {
+        let config = DocumentConfig::from_json(
+            r#"{
+                "region": "us-west-2",
+                "use_fips_endpoint": "TRUE",
+                "use_dualstack_endpoint": false,
+                "aws_access_key_id": "akid",
+                "aws_secret_access_key": "secret",
+                "retry_mode": "adaptive",
+                "max_attempts": "5",
+                "connect_timeout_ms": 3100,
+                "unrelated": { "nested": [1, 2, 3] }
+            }"#,
+        )
+        .expect("valid document");
+
+        assert_eq!(
+            Some("us-west-2"),
+            config.region.as_ref().map(|r| r.as_ref())
+        );
+        assert_eq!(Some(true), config.use_fips);
+        assert_eq!(Some(false), config.use_dual_stack);
+        let credentials = config.credentials.expect("static credentials");
+        assert_eq!("akid", credentials.access_key_id());
+        assert_eq!(None, credentials.session_token());
+        let retry_config = config.retry_config.expect("retry config");
+        assert_eq!(RetryMode::Adaptive, retry_config.mode());
+        assert_eq!(5, retry_config.max_attempts());
+        let timeout_config = config.timeout_config.expect("timeout config");
+        assert_eq!(
+            Some(Duration::from_millis(3100)),
+            timeout_config.connect_timeout()
+        );
+        assert_eq!(None, timeout_config.read_timeout());
+        assert!(config.endpoint_url.is_none());
+        assert!(config.credential_source.is_none());
+    }
+
+    #[test]
+    fn parse_document() {
+        let document = Document::Object(
+            [
+                ("profile".to_owned(), Document::String("production".into())),
+                (
+                    "credential_source".to_owned(),
+                    Document::String("ecscontainer".into()),
+                ),
+                ("region".to_owned(), Document::Null),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let config = DocumentConfig::from_document(&document).expect("valid document");
+        assert_eq!(Some("production"), config.profile_name.as_deref());
+        assert_eq!(
+            Some(CredentialSource::EcsContainer),
+            config.credential_source
+        );
+        assert!(config.region.is_none());
+        assert!(config.retry_config.is_none());
+        assert!(config.timeout_config.is_none());
+    }
+
+    #[test]
+    fn invalid_documents() {
+        for (json, message) in [
+            ("[]", "configuration document must be an object"),
+            ("{", "configuration document is not valid JSON"),
+            (r#"{} {}"#, "configuration document is not valid JSON"),
+            (
+                r#"{"region": 1}"#,
+                "invalid value for `region`: expected a string",
+            ),
+            (
+                r#"{"max_attempts": -1}"#,
+                "invalid value for `max_attempts`: expected a non-negative 32-bit integer",
+            ),
+            (
+                r#"{"max_attempts": 0}"#,
+                "invalid value for `max_attempts`: expected a number greater than zero",
+            ),
+            (
+                r#"{"retry_mode": "legacy"}"#,
+                "invalid value for `retry_mode`: expected `standard` or `adaptive`",
+            ),
+            (
+                r#"{"credential_source": "Unknown"}"#,
+                "invalid value for `credential_source`: expected `Environment`, `Ec2InstanceMetadata`, or `EcsContainer`",
+            ),
+            (
+                r#"{"aws_access_key_id": "akid"}"#,
+                "`aws_access_key_id` and `aws_secret_access_key` must be set together",
+            ),
+        ] {
+            let err = DocumentConfig::from_json(json).expect_err(json);
+            assert_eq!(message, err.to_string(), "{json}");
+        }
+    }
+}
@@ -31,7 +31,8 @@
 //!
 //! **Next**: It will check the value of `$AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`. If this is set,
 //! the filename specified will be read, and the value passed in the `Authorization` header. If the file
-//! cannot be read, an error is returned.
+//! cannot be read, an error is returned. The file is re-read every time credentials are loaded so
+//! that rotated tokens are picked up, and surrounding whitespace (such as a trailing newline) is ignored.
 //!
 //! **Finally**: It will check the value of `$AWS_CONTAINER_AUTHORIZATION_TOKEN`. If this is set, the
 //! value will be passed in the `Authorization` header.
@@ -63,6 +64,8 @@ use aws_smithy_runtime_api::client::dns::{ResolveDns, ResolveDnsError, SharedDns
 use aws_smithy_runtime_api::client::http::HttpConnectorSettings;
 use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
 use aws_types::os_shim_internal::{Env, Fs};
 use http::header::InvalidHeaderValue;
 use http::uri::{InvalidUri, PathAndQuery, Scheme};
@@ -112,8 +115,9 @@ impl EcsCredentialsProvider {
                 .read_to_end(auth_token_file)
                 .await
                 .map_err(CredentialsError::provider_error)?;
-            Some(HeaderValue::from_bytes(auth.as_slice()).map_err(|err| {
-                let auth_token = String::from_utf8_lossy(auth.as_slice()).to_string();
+            let auth = auth.trim_ascii();
+            Some(HeaderValue::from_bytes(auth).map_err(|err| {
+                let auth_token = String::from_utf8_lossy(auth).to_string();
                 tracing::warn!(token = %auth_token, "invalid auth token");
                 CredentialsError::invalid_configuration(EcsConfigurationError::InvalidAuthToken {
                     err,
@@ -203,14 +207,34 @@ impl Provider {
         .expect("parts will be valid")
         .to_string();
 
+        // Settings of this builder take precedence over the provider config
+        let provider_timeouts = provider_config
+            .timeout_config()
+            .unwrap_or_else(|| TimeoutConfig::builder().build());
         let http_provider = HttpCredentialProvider::builder()
             .configure(&provider_config)
             .http_connector_settings(
                 HttpConnectorSettings::builder()
-                    .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
-                    .read_timeout(DEFAULT_READ_TIMEOUT)
+                    .connect_timeout(
+                        builder
+                            .connect_timeout
+                            .or(provider_timeouts.connect_timeout())
+                            .unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+                    )
+                    .read_timeout(
+                        builder
+                            .read_timeout
+                            .or(provider_timeouts.read_timeout())
+                            .unwrap_or(DEFAULT_READ_TIMEOUT),
+                    )
                     .build(),
             )
+            .retry_config(
+                builder
+                    .retry_config
+                    .or_else(|| provider_config.retry_config())
+                    .unwrap_or_else(RetryConfig::standard),
+            )
             .build("EcsContainer", &endpoint, path_and_query);
         Provider::Configured(http_provider)
     }
@@ -289,6 +313,7 @@ pub struct Builder {
     dns: Option<SharedDnsResolver>,
     connect_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
+    retry_config: Option<RetryConfig>,
 }
 
 impl Builder {
@@ -323,6 +348,16 @@ impl Builder {
         self
     }
 
+    /// Override the retry configuration used when loading credentials
+    ///
+    /// By default, [`RetryConfig::standard`] is used: up to 3 attempts with exponential backoff.
+    /// Socket errors, timeouts, and 5xx responses are retried. Retries require a sleep
+    /// implementation to be configured.
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
     /// Create an [`EcsCredentialsProvider`] from this builder
     pub fn build(self) -> EcsCredentialsProvider {
         let env = self
@@ -913,6 +948,100 @@ mod test {
         assert_correct(creds);
     }
 
+    #[tokio::test]
+    async fn retry_disabled() {
+        let env = Env::from_slice(&[("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI", "/credentials")]);
+        let http_client = StaticReplayClient::new(vec![ReplayEvent::new(
+            creds_request("http://169.254.170.2/credentials", None),
+            http::Response::builder()
+                .status(500)
+                .body(SdkBody::empty())
+                .unwrap(),
+        )]);
+        tokio::time::pause();
+        let provider_config = ProviderConfig::empty()
+            .with_env(env)
+            .with_http_client(http_client.clone())
+            .with_sleep_impl(TokioSleep::new());
+        let provider = Builder::default()
+            .configure(&provider_config)
+            .retry_config(RetryConfig::disabled())
+            .build();
+        provider
+            .provide_credentials()
+            .await
+            .expect_err("500 should not be retried");
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn auth_file_trailing_newline() {
+        let env = Env::from_slice(&[
+            (
+                "AWS_CONTAINER_CREDENTIALS_FULL_URI",
+                "http://169.254.170.23/v1/credentials",
+            ),
+            ("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE", "/token"),
+        ]);
+        let fs = Fs::from_raw_map(HashMap::from([(
+            OsString::from("/token"),
+            "Basic password\n".into(),
+        )]));
+        let http_client = StaticReplayClient::new(vec![ReplayEvent::new(
+            creds_request(
+                "http://169.254.170.23/v1/credentials",
+                Some("Basic password"),
+            ),
+            ok_creds_response(),
+        )]);
+        let provider = provider(env, fs, http_client.clone());
+        let creds = provider
+            .provide_credentials()
+            .await
+            .expect("valid credentials");
+        assert_correct(creds);
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn auth_file_reread_on_each_request() {
+        let token_file =
+            std::env::temp_dir().join(format!("ecs-auth-token-{}", std::process::id()));
+        std::fs::write(&token_file, "first-token").unwrap();
+        let env = Env::from_slice(&[
+            (
+                "AWS_CONTAINER_CREDENTIALS_FULL_URI",
+                "http://169.254.170.23/v1/credentials",
+            ),
+            (
+                "AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE",
+                token_file.to_str().unwrap(),
+            ),
+        ]);
+        let http_client = StaticReplayClient::new(vec![
+            ReplayEvent::new(
+                creds_request("http://169.254.170.23/v1/credentials", Some("first-token")),
+                ok_creds_response(),
+            ),
+            ReplayEvent::new(
+                creds_request("http://169.254.170.23/v1/credentials", Some("second-token")),
+                ok_creds_response(),
+            ),
+        ]);
+        let provider = provider(env, Fs::real(), http_client.clone());
+        provider
+            .provide_credentials()
+            .await
+            .expect("valid credentials");
+        std::fs::write(&token_file, "second-token").unwrap();
+        provider
+            .provide_credentials()
+            .await
+            .expect("valid credentials");
+        std::fs::remove_file(&token_file).unwrap();
+        http_client.assert_requests_match(&[]);
+    }
+
     #[tokio::test]
     async fn load_valid_creds_no_auth() {
         let env = Env::from_slice(&[("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI", "/credentials")]);
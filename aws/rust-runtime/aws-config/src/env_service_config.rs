@@ -16,11 +16,13 @@ pub(crate) struct EnvServiceConfig {
 
 impl LoadServiceConfig for EnvServiceConfig {
     fn load_config(&self, key: ServiceConfigKey<'_>) -> Option<String> {
-        let (value, _source) = EnvConfigValue::new()
-            .env(key.env())
+        let mut value = EnvConfigValue::new()
             .profile(key.profile())
-            .service_id(key.service_id())
-            .load(&self.env, Some(&self.env_config_sections))?;
+            .service_id(key.service_id());
+        if let Some(env) = key.env() {
+            value = value.env(env);
+        }
+        let (value, _source) = value.load(&self.env, Some(&self.env_config_sections))?;
 
         Some(value.to_string())
     }
@@ -16,6 +16,10 @@ pub use credentials::EnvironmentVariableCredentialsProvider;
 pub mod region;
 pub use region::EnvironmentVariableRegionProvider;
 
+/// Load bearer tokens from the environment
+pub mod token;
+pub use token::EnvironmentVariableTokenProvider;
+
 #[derive(Debug)]
 pub(crate) struct InvalidBooleanValue {
     value: String,
@@ -74,7 +78,12 @@ impl fmt::Display for InvalidUrlValue {
 impl Error for InvalidUrlValue {}
 
 pub(crate) fn parse_url(value: &str) -> Result<String, InvalidUrlValue> {
-    match url::Url::parse(value) {
+    // Endpoint URLs may contain `{service}` and `{region}` placeholders that are expanded when a
+    // request is made, so validate the URL with placeholder values in their place.
+    let expanded = value
+        .replace("{service}", "service")
+        .replace("{region}", "region");
+    match url::Url::parse(&expanded) {
         // We discard the parse result because it includes a trailing slash
         Ok(_) => Ok(value.to_string()),
         Err(_) => Err(InvalidUrlValue {
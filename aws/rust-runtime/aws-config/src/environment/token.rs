@@ -0,0 +1,166 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_credential_types::provider::{error::TokenError, future, token::ProvideToken};
+use aws_credential_types::Token;
+use aws_types::os_shim_internal::Env;
+use std::borrow::Cow;
+use std::env::VarError;
+
+const AWS_BEARER_TOKEN_PREFIX: &str = "AWS_BEARER_TOKEN_";
+
+/// Load a bearer token from an environment variable
+///
+/// The token is read from the environment every time it is requested. When the variable is unset
+/// or blank, [`TokenError::TokenNotLoaded`] is returned so that this provider can be used as a
+/// link in a [`TokenProviderChain`](crate::meta::token::TokenProviderChain).
+///
+/// This provider isn't part of the default token provider chain, so it must be set explicitly,
+/// e.g. with [`ConfigLoader::token_provider`](crate::ConfigLoader::token_provider). Clients of
+/// services that use `@httpBearerAuth` cache the tokens it returns with
+/// `aws_runtime::auth::bearer::TokenResolver`, and send them in the `Authorization: Bearer` header.
+///
+/// # Examples
+///
+/// Load a token from `AWS_BEARER_TOKEN_BEDROCK`, falling back to a user provided callback:
+/// ```no_run
+/// use aws_config::environment::EnvironmentVariableTokenProvider;
+/// use aws_config::meta::token::TokenProviderChain;
+/// use aws_credential_types::token_fn::provide_token_fn;
+/// use aws_credential_types::Token;
+///
+/// let provider = TokenProviderChain::first_try(
+///     "Environment",
+///     EnvironmentVariableTokenProvider::for_service("bedrock"),
+/// )
+/// .or_else(
+///     "Callback",
+///     provide_token_fn(|| async { Ok(Token::new("token-from-my-vault", None)) }),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvironmentVariableTokenProvider {
+    env: Env,
+    var_name: Cow<'static, str>,
+}
+
+impl EnvironmentVariableTokenProvider {
+    /// Create an `EnvironmentVariableTokenProvider` that reads the token from `var_name`
+    pub fn new(var_name: impl Into<Cow<'static, str>>) -> Self {
+        Self::new_with_env(Env::real(), var_name)
+    }
+
+    /// Create an `EnvironmentVariableTokenProvider` that reads the token from the service specific
+    /// `AWS_BEARER_TOKEN_<SERVICE>` environment variable
+    ///
+    /// The service name is upper-cased and any spaces or dashes are replaced with underscores,
+    /// e.g. `bedrock` resolves to `AWS_BEARER_TOKEN_BEDROCK`.
+    pub fn for_service(service_name: &str) -> Self {
+        Self::new(service_env_var_name(service_name))
+    }
+
+    /// Create a new `EnvironmentVariableTokenProvider` with `Env` overridden
+    ///
+    /// This function is intended for tests that mock out the process environment.
+    pub(crate) fn new_with_env(env: Env, var_name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            env,
+            var_name: var_name.into(),
+        }
+    }
+
+    /// The name of the environment variable the token is loaded from
+    pub fn var_name(&self) -> &str {
+        &self.var_name
+    }
+
+    fn token(&self) -> Result<Token, TokenError> {
+        match self.env.get(&self.var_name) {
+            Ok(token) if !token.trim().is_empty() => Ok(Token::new(token.trim(), None)),
+            Ok(_) | Err(VarError::NotPresent) => Err(TokenError::not_loaded(format!(
+                "environment variable `{}` not set",
+                self.var_name
+            ))),
+            Err(err @ VarError::NotUnicode(_)) => Err(TokenError::invalid_configuration(err)),
+        }
+    }
+}
+
+impl ProvideToken for EnvironmentVariableTokenProvider {
+    fn provide_token<'a>(&'a self) -> future::ProvideToken<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideToken::ready(self.token())
+    }
+}
+
+pub(crate) fn service_env_var_name(service_name: &str) -> String {
+    let suffix: String = service_name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_uppercase(),
+        })
+        .collect();
+    format!("{AWS_BEARER_TOKEN_PREFIX}{suffix}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{service_env_var_name, EnvironmentVariableTokenProvider};
+    use aws_credential_types::provider::{error::TokenError, token::ProvideToken};
+    use aws_types::os_shim_internal::Env;
+    use futures_util::FutureExt;
+
+    fn make_provider(
+        vars: &[(&str, &str)],
+        var_name: &'static str,
+    ) -> EnvironmentVariableTokenProvider {
+        EnvironmentVariableTokenProvider::new_with_env(Env::from_slice(vars), var_name)
+    }
+
+    #[test]
+    fn loads_token() {
+        let provider = make_provider(&[("MY_TOKEN", "some-token")], "MY_TOKEN");
+        let token = provider
+            .provide_token()
+            .now_or_never()
+            .unwrap()
+            .expect("valid token");
+        assert_eq!("some-token", token.token());
+        assert_eq!(None, token.expiration());
+    }
+
+    #[test]
+    fn missing_or_blank_token_is_not_loaded() {
+        for vars in [&[][..], &[("MY_TOKEN", "")][..], &[("MY_TOKEN", "  ")][..]] {
+            let err = make_provider(vars, "MY_TOKEN")
+                .provide_token()
+                .now_or_never()
+                .unwrap()
+                .expect_err("no token");
+            assert!(matches!(err, TokenError::TokenNotLoaded(_)), "{err:?}");
+        }
+    }
+
+    #[test]
+    fn service_specific_var_names() {
+        assert_eq!("AWS_BEARER_TOKEN_BEDROCK", service_env_var_name("bedrock"));
+        assert_eq!(
+            "AWS_BEARER_TOKEN_BEDROCK_RUNTIME",
+            service_env_var_name("Bedrock Runtime")
+        );
+        assert_eq!(
+            "AWS_BEARER_TOKEN_CODE_CATALYST",
+            service_env_var_name("code-catalyst")
+        );
+        assert_eq!(
+            "AWS_BEARER_TOKEN_BEDROCK",
+            EnvironmentVariableTokenProvider::for_service("bedrock").var_name()
+        );
+    }
+}
@@ -77,6 +77,7 @@ impl HttpCredentialProvider {
 pub(crate) struct Builder {
     provider_config: Option<ProviderConfig>,
     http_connector_settings: Option<HttpConnectorSettings>,
+    retry_config: Option<RetryConfig>,
 }
 
 impl Builder {
@@ -93,6 +94,11 @@ impl Builder {
         self
     }
 
+    pub(crate) fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
     pub(crate) fn build(
         self,
         provider_name: &'static str,
@@ -101,6 +107,13 @@ impl Builder {
     ) -> HttpCredentialProvider {
         let provider_config = self.provider_config.unwrap_or_default();
         let path = path.into();
+        let connector_settings = self.http_connector_settings.as_ref();
+        let connect_timeout = connector_settings
+            .and_then(HttpConnectorSettings::connect_timeout)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT);
+        let read_timeout = connector_settings
+            .and_then(HttpConnectorSettings::read_timeout)
+            .unwrap_or(DEFAULT_READ_TIMEOUT);
 
         let mut builder = Operation::builder()
             .service_name("HttpCredentialProvider")
@@ -110,8 +123,8 @@ impl Builder {
             .no_auth()
             .timeout_config(
                 TimeoutConfig::builder()
-                    .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
-                    .read_timeout(DEFAULT_READ_TIMEOUT)
+                    .connect_timeout(connect_timeout)
+                    .read_timeout(read_timeout)
                     .build(),
             )
             .runtime_plugin(StaticRuntimePlugin::new().with_config({
@@ -132,7 +145,7 @@ impl Builder {
         }
         if let Some(sleep_impl) = provider_config.sleep_impl() {
             builder = builder
-                .standard_retry(&RetryConfig::standard())
+                .standard_retry(&self.retry_config.unwrap_or_else(RetryConfig::standard))
                 // The following errors are retryable:
                 //   - Socket errors
                 //   - Networking timeouts
@@ -7,6 +7,7 @@
 //!
 //! Client for direct access to IMDSv2.
 
+use crate::imds::client::cache::ResponseCache;
 use crate::imds::client::error::{BuildError, ImdsError, InnerImdsError, InvalidEndpointMode};
 use crate::imds::client::token::TokenRuntimePlugin;
 use crate::provider_config::ProviderConfig;
@@ -45,6 +46,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
+mod cache;
 pub mod error;
 mod token;
 
@@ -128,9 +130,26 @@ fn user_agent() -> AwsUserAgent {
 ///
 /// 7. The default value of `http://169.254.169.254` will be used.
 ///
+/// # Response caching
+/// By default, every call to [`Client::get`] makes a request to IMDS. Responses can be cached by
+/// configuring a TTL with [`Builder::cache_ttl`] and/or [`Builder::path_cache_ttl`]. At most 256
+/// responses are cached at once; when the cache is full, the response closest to expiring is
+/// evicted.
+/// ```no_run
+/// use aws_config::imds::client::Client;
+/// use std::time::Duration;
+/// # async fn docs() {
+/// let client = Client::builder()
+///   .cache_ttl(Duration::from_secs(60))
+///   .path_cache_ttl("/latest/meta-data/spot", Duration::ZERO)
+///   .build();
+/// # }
+/// ```
+///
 #[derive(Clone, Debug)]
 pub struct Client {
     operation: Operation<String, SensitiveString, InnerImdsError>,
+    cache: Option<Arc<ResponseCache>>,
 }
 
 impl Client {
@@ -159,41 +178,70 @@ impl Client {
     ///   .expect("failure communicating with IMDS");
     /// # }
     /// ```
+    ///
+    /// If response caching is enabled, a cached response for `path` is returned when one exists.
     pub async fn get(&self, path: impl Into<String>) -> Result<SensitiveString, ImdsError> {
-        self.operation
-            .invoke(path.into())
-            .await
-            .map_err(|err| match err {
-                SdkError::ConstructionFailure(_) if err.source().is_some() => {
-                    match err.into_source().map(|e| e.downcast::<ImdsError>()) {
-                        Ok(Ok(token_failure)) => *token_failure,
-                        Ok(Err(err)) => ImdsError::unexpected(err),
-                        Err(err) => ImdsError::unexpected(err),
-                    }
+        let path = path.into();
+        if let Some(cached) = self.cache.as_ref().and_then(|cache| cache.get(&path)) {
+            tracing::trace!(path = %path, "using cached IMDS response");
+            return Ok(cached);
+        }
+        self.get_uncached(path).await
+    }
+
+    /// Retrieve information from IMDS, bypassing the response cache
+    ///
+    /// The response is still stored in the cache (if caching is enabled for `path`) so that
+    /// subsequent calls to [`Client::get`] observe the refreshed value.
+    pub async fn get_uncached(
+        &self,
+        path: impl Into<String>,
+    ) -> Result<SensitiveString, ImdsError> {
+        let path = path.into();
+        let response = self.invoke(path.clone()).await?;
+        if let Some(cache) = &self.cache {
+            cache.insert(&path, &response);
+        }
+        Ok(response)
+    }
+
+    /// Discard all cached IMDS responses
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    async fn invoke(&self, path: String) -> Result<SensitiveString, ImdsError> {
+        self.operation.invoke(path).await.map_err(|err| match err {
+            SdkError::ConstructionFailure(_) if err.source().is_some() => {
+                match err.into_source().map(|e| e.downcast::<ImdsError>()) {
+                    Ok(Ok(token_failure)) => *token_failure,
+                    Ok(Err(err)) => ImdsError::unexpected(err),
+                    Err(err) => ImdsError::unexpected(err),
                 }
-                SdkError::ConstructionFailure(_) => ImdsError::unexpected(err),
-                SdkError::ServiceError(context) => match context.err() {
-                    InnerImdsError::InvalidUtf8 => {
-                        ImdsError::unexpected("IMDS returned invalid UTF-8")
-                    }
-                    InnerImdsError::BadStatus => ImdsError::error_response(context.into_raw()),
-                },
-                // If the error source is an ImdsError, then we need to directly return that source.
-                // That way, the IMDS token provider's errors can become the top-level ImdsError.
-                // There is a unit test that checks the correct error is being extracted.
-                err @ SdkError::DispatchFailure(_) => match err.into_source() {
-                    Ok(source) => match source.downcast::<ConnectorError>() {
-                        Ok(source) => match source.into_source().downcast::<ImdsError>() {
-                            Ok(source) => *source,
-                            Err(err) => ImdsError::unexpected(err),
-                        },
+            }
+            SdkError::ConstructionFailure(_) => ImdsError::unexpected(err),
+            SdkError::ServiceError(context) => match context.err() {
+                InnerImdsError::InvalidUtf8 => ImdsError::unexpected("IMDS returned invalid UTF-8"),
+                InnerImdsError::BadStatus => ImdsError::error_response(context.into_raw()),
+            },
+            // If the error source is an ImdsError, then we need to directly return that source.
+            // That way, the IMDS token provider's errors can become the top-level ImdsError.
+            // There is a unit test that checks the correct error is being extracted.
+            err @ SdkError::DispatchFailure(_) => match err.into_source() {
+                Ok(source) => match source.downcast::<ConnectorError>() {
+                    Ok(source) => match source.into_source().downcast::<ImdsError>() {
+                        Ok(source) => *source,
                         Err(err) => ImdsError::unexpected(err),
                     },
                     Err(err) => ImdsError::unexpected(err),
                 },
-                SdkError::TimeoutError(_) | SdkError::ResponseError(_) => ImdsError::io_error(err),
-                _ => ImdsError::unexpected(err),
-            })
+                Err(err) => ImdsError::unexpected(err),
+            },
+            SdkError::TimeoutError(_) | SdkError::ResponseError(_) => ImdsError::io_error(err),
+            _ => ImdsError::unexpected(err),
+        })
     }
 }
 
@@ -330,6 +378,8 @@ pub struct Builder {
     operation_attempt_timeout: Option<Duration>,
     config: Option<ProviderConfig>,
     retry_classifier: Option<SharedRetryClassifier>,
+    cache_ttl: Option<Duration>,
+    path_cache_ttls: Vec<(String, Duration)>,
 }
 
 impl Builder {
@@ -431,6 +481,25 @@ impl Builder {
         self
     }
 
+    /// Cache IMDS responses for `ttl`
+    ///
+    /// By default, responses are not cached. Instance credentials (under
+    /// `/latest/meta-data/iam/security-credentials`) are never cached by this TTL; they can only be
+    /// cached by setting a [`path_cache_ttl`](Builder::path_cache_ttl) for them explicitly.
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Cache IMDS responses for paths starting with `path_prefix` for `ttl`
+    ///
+    /// This overrides [`cache_ttl`](Builder::cache_ttl) for matching paths. When several prefixes
+    /// match a path, the longest one is used. A `ttl` of zero disables caching for matching paths.
+    pub fn path_cache_ttl(mut self, path_prefix: impl Into<String>, ttl: Duration) -> Self {
+        self.path_cache_ttls.push((path_prefix.into(), ttl));
+        self
+    }
+
     /* TODO(https://github.com/awslabs/aws-sdk-rust/issues/339): Support customizing the port explicitly */
     /*
     pub fn port(mut self, port: u32) -> Self {
@@ -441,14 +510,31 @@ impl Builder {
     /// Build an IMDSv2 Client
     pub fn build(self) -> Client {
         let config = self.config.unwrap_or_default();
+        // Settings of this builder take precedence over the provider config
+        let provider_timeouts = config
+            .timeout_config()
+            .unwrap_or_else(|| TimeoutConfig::builder().build());
         let timeout_config = TimeoutConfig::builder()
-            .connect_timeout(self.connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT))
-            .read_timeout(self.read_timeout.unwrap_or(DEFAULT_READ_TIMEOUT))
+            .connect_timeout(
+                self.connect_timeout
+                    .or(provider_timeouts.connect_timeout())
+                    .unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+            )
+            .read_timeout(
+                self.read_timeout
+                    .or(provider_timeouts.read_timeout())
+                    .unwrap_or(DEFAULT_READ_TIMEOUT),
+            )
             .operation_attempt_timeout(
                 self.operation_attempt_timeout
+                    .or(provider_timeouts.operation_attempt_timeout())
                     .unwrap_or(DEFAULT_OPERATION_ATTEMPT_TIMEOUT),
             )
-            .operation_timeout(self.operation_timeout.unwrap_or(DEFAULT_OPERATION_TIMEOUT))
+            .operation_timeout(
+                self.operation_timeout
+                    .or(provider_timeouts.operation_timeout())
+                    .unwrap_or(DEFAULT_OPERATION_TIMEOUT),
+            )
             .build();
         let endpoint_source = self
             .endpoint
@@ -457,8 +543,11 @@ impl Builder {
             endpoint_source: Arc::new(endpoint_source),
             mode_override: self.mode_override,
         };
-        let retry_config = RetryConfig::standard()
-            .with_max_attempts(self.max_attempts.unwrap_or(DEFAULT_ATTEMPTS));
+        let retry_config = RetryConfig::standard().with_max_attempts(
+            self.max_attempts
+                .or_else(|| config.retry_config().map(|conf| conf.max_attempts()))
+                .unwrap_or(DEFAULT_ATTEMPTS),
+        );
         let retry_classifier = self.retry_classifier.unwrap_or(SharedRetryClassifier::new(
             ImdsResponseRetryClassifier::default(),
         ));
@@ -505,7 +594,14 @@ impl Builder {
                 }
             })
             .build();
-        Client { operation }
+        let cache = (self.cache_ttl.is_some() || !self.path_cache_ttls.is_empty()).then(|| {
+            Arc::new(ResponseCache::new(
+                self.cache_ttl,
+                self.path_cache_ttls,
+                config.time_source(),
+            ))
+        });
+        Client { operation, cache }
     }
 }
 
@@ -660,6 +756,7 @@ pub(crate) mod test {
     };
     use aws_smithy_types::body::SdkBody;
     use aws_smithy_types::error::display::DisplayErrorContext;
+    use aws_smithy_types::retry::RetryConfig;
     use aws_types::os_shim_internal::{Env, Fs};
     use http::header::USER_AGENT;
     use http::Uri;
@@ -754,6 +851,86 @@ pub(crate) mod test {
         (client, http_client)
     }
 
+    #[tokio::test]
+    async fn client_caches_responses() {
+        let http_client = StaticReplayClient::new(vec![
+            ReplayEvent::new(
+                token_request("http://169.254.169.254", 21600),
+                token_response(21600, TOKEN_A),
+            ),
+            ReplayEvent::new(
+                imds_request("http://169.254.169.254/latest/metadata", TOKEN_A),
+                imds_response("first"),
+            ),
+            ReplayEvent::new(
+                imds_request("http://169.254.169.254/latest/uncached", TOKEN_A),
+                imds_response("uncached-1"),
+            ),
+            ReplayEvent::new(
+                imds_request("http://169.254.169.254/latest/uncached", TOKEN_A),
+                imds_response("uncached-2"),
+            ),
+            ReplayEvent::new(
+                imds_request("http://169.254.169.254/latest/metadata", TOKEN_A),
+                imds_response("second"),
+            ),
+            ReplayEvent::new(
+                imds_request("http://169.254.169.254/latest/metadata", TOKEN_A),
+                imds_response("third"),
+            ),
+        ]);
+        let (time_source, sleep) = instant_time_and_sleep(UNIX_EPOCH);
+        let client = super::Client::builder()
+            .configure(
+                &ProviderConfig::no_configuration()
+                    .with_sleep_impl(sleep)
+                    .with_time_source(time_source.clone())
+                    .with_http_client(http_client.clone()),
+            )
+            .cache_ttl(Duration::from_secs(60))
+            .path_cache_ttl("/latest/uncached", Duration::ZERO)
+            .build();
+
+        assert_eq!(
+            "first",
+            client.get("/latest/metadata").await.unwrap().as_ref()
+        );
+        // served from the cache
+        assert_eq!(
+            "first",
+            client.get("/latest/metadata").await.unwrap().as_ref()
+        );
+        // caching is disabled for this path
+        assert_eq!(
+            "uncached-1",
+            client.get("/latest/uncached").await.unwrap().as_ref()
+        );
+        assert_eq!(
+            "uncached-2",
+            client.get("/latest/uncached").await.unwrap().as_ref()
+        );
+        // the cached response expires after the TTL
+        time_source.advance(Duration::from_secs(61));
+        assert_eq!(
+            "second",
+            client.get("/latest/metadata").await.unwrap().as_ref()
+        );
+        // the cache can be bypassed explicitly, which also refreshes the cached value
+        assert_eq!(
+            "third",
+            client
+                .get_uncached("/latest/metadata")
+                .await
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            "third",
+            client.get("/latest/metadata").await.unwrap().as_ref()
+        );
+        http_client.assert_requests_match(&[]);
+    }
+
     #[tokio::test]
     async fn client_caches_token() {
         let (client, http_client) = mock_imds_client(vec![
@@ -913,6 +1090,39 @@ pub(crate) mod test {
         }
     }
 
+    /// The retry config of the provider config applies when the builder doesn't set max attempts
+    #[tokio::test]
+    #[traced_test]
+    async fn provider_config_retry_config() {
+        tokio::time::pause();
+        let http_client = StaticReplayClient::new(vec![
+            ReplayEvent::new(
+                token_request("http://169.254.169.254", 21600),
+                token_response(21600, TOKEN_A),
+            ),
+            ReplayEvent::new(
+                imds_request("http://169.254.169.254/latest/metadata", TOKEN_A),
+                http::Response::builder()
+                    .status(500)
+                    .body(SdkBody::empty())
+                    .unwrap(),
+            ),
+        ]);
+        let client = super::Client::builder()
+            .configure(
+                &ProviderConfig::no_configuration()
+                    .with_sleep_impl(InstantSleep::unlogged())
+                    .with_http_client(http_client.clone())
+                    .with_retry_config(RetryConfig::standard().with_max_attempts(1)),
+            )
+            .build();
+        client
+            .get("/latest/metadata")
+            .await
+            .expect_err("no retries");
+        http_client.assert_requests_match(&[]);
+    }
+
     /// 500 error during token acquisition should be retried
     #[tokio::test]
     #[traced_test]
@@ -0,0 +1,187 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! IMDS Response Cache
+//!
+//! Caches successful IMDS responses by path so that frequently read metadata (e.g. the region or
+//! instance tags) is not reloaded from IMDS on every call. Each path is cached according to the
+//! TTL of the longest matching configured path prefix, falling back to the default TTL. At most
+//! `MAX_ENTRIES` responses are cached; when the cache is full, expired responses are removed
+//! first, then the response closest to expiring.
+
+use crate::imds::client::SensitiveString;
+use aws_smithy_async::time::SharedTimeSource;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Instance credentials are never cached by the default TTL, since they have their own expiration.
+const SECURITY_CREDENTIALS_PREFIX: &str = "/latest/meta-data/iam/security-credentials";
+
+/// The maximum number of responses that are cached at once.
+const MAX_ENTRIES: usize = 256;
+
+#[derive(Debug)]
+pub(super) struct ResponseCache {
+    default_ttl: Option<Duration>,
+    path_ttls: Vec<(String, Duration)>,
+    time_source: SharedTimeSource,
+    entries: Mutex<HashMap<String, (SensitiveString, SystemTime)>>,
+}
+
+impl ResponseCache {
+    pub(super) fn new(
+        default_ttl: Option<Duration>,
+        path_ttls: Vec<(String, Duration)>,
+        time_source: SharedTimeSource,
+    ) -> Self {
+        Self {
+            default_ttl,
+            path_ttls,
+            time_source,
+            entries: Default::default(),
+        }
+    }
+
+    /// Returns how long the response for `path` may be cached, or `None` if it must not be cached.
+    fn ttl_for(&self, path: &str) -> Option<Duration> {
+        let ttl = self
+            .path_ttls
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| *ttl)
+            .or_else(|| {
+                self.default_ttl
+                    .filter(|_| !path.starts_with(SECURITY_CREDENTIALS_PREFIX))
+            });
+        ttl.filter(|ttl| !ttl.is_zero())
+    }
+
+    /// Returns the cached response for `path` if one exists and hasn't expired.
+    pub(super) fn get(&self, path: &str) -> Option<SensitiveString> {
+        let now = self.time_source.now();
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(path) {
+            Some((value, expires_at)) if now < *expires_at => Some(value.clone()),
+            Some(_) => {
+                entries.remove(path);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches `value` as the response for `path` if `path` is cacheable.
+    ///
+    /// If the cache is full, expired responses are removed. If none have expired, the response
+    /// closest to expiring is removed to make room.
+    pub(super) fn insert(&self, path: &str, value: &SensitiveString) {
+        let Some(ttl) = self.ttl_for(path) else {
+            return;
+        };
+        let now = self.time_source.now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(path) {
+            entries.retain(|_, (_, expires_at)| now < *expires_at);
+            if entries.len() >= MAX_ENTRIES {
+                let soonest = entries
+                    .iter()
+                    .min_by_key(|(_, (_, expires_at))| *expires_at)
+                    .map(|(path, _)| path.clone());
+                if let Some(soonest) = soonest {
+                    entries.remove(&soonest);
+                }
+            }
+        }
+        entries.insert(path.to_string(), (value.clone(), now + ttl));
+    }
+
+    pub(super) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ResponseCache, MAX_ENTRIES};
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_async::time::SharedTimeSource;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn ttl_for_path() {
+        let cache = ResponseCache::new(
+            Some(Duration::from_secs(60)),
+            vec![
+                (
+                    "/latest/meta-data/tags".to_string(),
+                    Duration::from_secs(600),
+                ),
+                (
+                    "/latest/meta-data/tags/instance/volatile".to_string(),
+                    Duration::ZERO,
+                ),
+            ],
+            SharedTimeSource::new(ManualTimeSource::new(UNIX_EPOCH)),
+        );
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            cache.ttl_for("/latest/meta-data/placement/region")
+        );
+        assert_eq!(
+            Some(Duration::from_secs(600)),
+            cache.ttl_for("/latest/meta-data/tags/instance/Name")
+        );
+        assert_eq!(
+            None,
+            cache.ttl_for("/latest/meta-data/tags/instance/volatile")
+        );
+        assert_eq!(
+            None,
+            cache.ttl_for("/latest/meta-data/iam/security-credentials/my-role")
+        );
+    }
+
+    #[test]
+    fn entries_expire() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let cache = ResponseCache::new(
+            Some(Duration::from_secs(60)),
+            vec![],
+            SharedTimeSource::new(time_source.clone()),
+        );
+        cache.insert("/path", &String::from("value").into());
+        assert_eq!("value", cache.get("/path").unwrap().as_ref());
+        time_source.advance(Duration::from_secs(60));
+        assert!(cache.get("/path").is_none());
+    }
+
+    #[test]
+    fn cache_size_is_bounded() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let cache = ResponseCache::new(
+            Some(Duration::from_secs(60)),
+            vec![("/short".to_string(), Duration::from_secs(10))],
+            SharedTimeSource::new(time_source.clone()),
+        );
+        cache.insert("/short", &String::from("short").into());
+        for i in 1..MAX_ENTRIES {
+            cache.insert(&format!("/path/{i}"), &String::from("value").into());
+        }
+        assert_eq!(MAX_ENTRIES, cache.entries.lock().unwrap().len());
+
+        // the response closest to expiring is evicted when the cache is full
+        cache.insert("/new", &String::from("new").into());
+        assert_eq!(MAX_ENTRIES, cache.entries.lock().unwrap().len());
+        assert!(cache.get("/short").is_none());
+        assert_eq!("new", cache.get("/new").unwrap().as_ref());
+
+        // expired responses are evicted first
+        time_source.advance(Duration::from_secs(60));
+        cache.insert("/newer", &String::from("newer").into());
+        assert_eq!(1, cache.entries.lock().unwrap().len());
+    }
+}
@@ -0,0 +1,768 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typed accessors for commonly used instance metadata
+//!
+//! [`Client::get`] returns the raw value stored at a metadata path. [`InstanceMetadata`] builds on
+//! top of it to fetch and parse structured metadata such as the instance identity document.
+
+use crate::imds::client::error::ImdsError;
+use crate::imds::client::SensitiveString;
+use crate::imds::Client;
+use crate::json_credentials::{json_parse_loop, InvalidJsonCredentials};
+use aws_smithy_json::deserialize::Token;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::DateTime;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+const IDENTITY_DOCUMENT_PATH: &str = "/latest/dynamic/instance-identity/document";
+const TAGS_PATH: &str = "/latest/meta-data/tags/instance";
+const MACS_PATH: &str = "/latest/meta-data/network/interfaces/macs";
+const SPOT_INSTANCE_ACTION_PATH: &str = "/latest/meta-data/spot/instance-action";
+const IAM_INFO_PATH: &str = "/latest/meta-data/iam/info";
+
+/// Typed access to instance metadata
+///
+/// Metadata that can't change for the lifetime of an instance, like the
+/// [instance identity document](InstanceMetadata::identity_document), is cached after it is
+/// first loaded. [Instance tags](InstanceMetadata::instance_tags) are also cached since
+/// they're typically read far more often than they change; use
+/// [`clear_cache`](InstanceMetadata::clear_cache) to force them to be reloaded. All other
+/// metadata is fetched from IMDS on every call.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_config::imds::client::Client;
+/// use aws_config::imds::metadata::InstanceMetadata;
+/// # async fn docs() {
+/// let metadata = InstanceMetadata::new(Client::builder().build());
+/// let document = metadata
+///     .identity_document()
+///     .await
+///     .expect("failure communicating with IMDS");
+/// println!("running in {}", document.availability_zone());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct InstanceMetadata {
+    client: Client,
+    cache: Arc<Mutex<Cache>>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    identity_document: Option<InstanceIdentityDocument>,
+    instance_tags: Option<HashMap<String, String>>,
+}
+
+impl InstanceMetadata {
+    /// Creates typed metadata accessors that load metadata with the given IMDS `client`.
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            cache: Default::default(),
+        }
+    }
+
+    /// Discards cached metadata so that it is reloaded from IMDS on next access.
+    pub fn clear_cache(&self) {
+        *self.cache.lock().unwrap() = Cache::default();
+    }
+
+    /// Loads the [instance identity document](https://docs.aws.amazon.com/AWSEC2/latest/UserGuide/instance-identity-documents.html)
+    ///
+    /// The document is cached after it is first loaded.
+    pub async fn identity_document(&self) -> Result<InstanceIdentityDocument, ImdsError> {
+        if let Some(document) = &self.cache.lock().unwrap().identity_document {
+            return Ok(document.clone());
+        }
+        let document = self.client.get(IDENTITY_DOCUMENT_PATH).await?;
+        let document = parse_identity_document(document.as_ref()).map_err(ImdsError::unexpected)?;
+        self.cache.lock().unwrap().identity_document = Some(document.clone());
+        Ok(document)
+    }
+
+    /// Loads the tags of this instance
+    ///
+    /// Access to tags in instance metadata must be enabled for the instance. If it isn't, IMDS
+    /// responds with a 404 and an [`ImdsError::ErrorResponse`] is returned.
+    ///
+    /// Tags are cached after they are first loaded.
+    pub async fn instance_tags(&self) -> Result<HashMap<String, String>, ImdsError> {
+        if let Some(tags) = &self.cache.lock().unwrap().instance_tags {
+            return Ok(tags.clone());
+        }
+        let keys = self.client.get(TAGS_PATH).await?;
+        let mut tags = HashMap::new();
+        for key in lines(&keys) {
+            let value = self.client.get(format!("{TAGS_PATH}/{key}")).await?;
+            tags.insert(key.to_string(), String::from(value));
+        }
+        self.cache.lock().unwrap().instance_tags = Some(tags.clone());
+        Ok(tags)
+    }
+
+    /// Loads the network interfaces attached to this instance
+    pub async fn network_interfaces(&self) -> Result<Vec<NetworkInterface>, ImdsError> {
+        let macs = self.client.get(format!("{MACS_PATH}/")).await?;
+        let mut interfaces = Vec::new();
+        for mac in lines(&macs) {
+            let mac = mac.trim_end_matches('/');
+            let path = format!("{MACS_PATH}/{mac}");
+            let device_number = self
+                .get_optional(format!("{path}/device-number"))
+                .await?
+                .map(|number| {
+                    number.as_ref().trim().parse::<u32>().map_err(|err| {
+                        ImdsError::unexpected(format!("invalid device number for {mac}: {err}"))
+                    })
+                })
+                .transpose()?;
+            interfaces.push(NetworkInterface {
+                mac: mac.to_string(),
+                device_number,
+                interface_id: self
+                    .get_optional_string(format!("{path}/interface-id"))
+                    .await?,
+                local_ipv4s: self.get_list(format!("{path}/local-ipv4s")).await?,
+                public_ipv4s: self.get_list(format!("{path}/public-ipv4s")).await?,
+                subnet_id: self
+                    .get_optional_string(format!("{path}/subnet-id"))
+                    .await?,
+                vpc_id: self.get_optional_string(format!("{path}/vpc-id")).await?,
+            });
+        }
+        Ok(interfaces)
+    }
+
+    /// Loads the pending [spot instance interruption](https://docs.aws.amazon.com/AWSEC2/latest/UserGuide/spot-instance-termination-notices.html), if any
+    ///
+    /// Returns `None` when this instance isn't scheduled to be interrupted or isn't a spot instance.
+    /// This is never cached.
+    pub async fn spot_interruption(&self) -> Result<Option<SpotInterruption>, ImdsError> {
+        match self.get_optional(SPOT_INSTANCE_ACTION_PATH).await? {
+            Some(action) => parse_spot_interruption(action.as_ref())
+                .map(Some)
+                .map_err(ImdsError::unexpected),
+            None => Ok(None),
+        }
+    }
+
+    /// Loads information about the IAM instance profile associated with this instance
+    ///
+    /// Returns `None` when no instance profile is associated with the instance.
+    pub async fn iam_info(&self) -> Result<Option<IamInfo>, ImdsError> {
+        match self.get_optional(IAM_INFO_PATH).await? {
+            Some(info) => parse_iam_info(info.as_ref())
+                .map(Some)
+                .map_err(ImdsError::unexpected),
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the value at `path`, returning `None` if IMDS responds with a 404.
+    async fn get_optional(
+        &self,
+        path: impl Into<String>,
+    ) -> Result<Option<SensitiveString>, ImdsError> {
+        match self.client.get(path).await {
+            Ok(value) => Ok(Some(value)),
+            Err(ImdsError::ErrorResponse(err)) if err.response().status().as_u16() == 404 => {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn get_optional_string(
+        &self,
+        path: impl Into<String>,
+    ) -> Result<Option<String>, ImdsError> {
+        Ok(self
+            .get_optional(path)
+            .await?
+            .map(|value| value.as_ref().trim().to_string()))
+    }
+
+    async fn get_list(&self, path: impl Into<String>) -> Result<Vec<String>, ImdsError> {
+        Ok(self
+            .get_optional(path)
+            .await?
+            .map(|value| lines(&value).map(str::to_string).collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Splits a newline-delimited IMDS listing into its non-empty entries.
+fn lines(value: &SensitiveString) -> impl Iterator<Item = &str> {
+    value
+        .as_ref()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+}
+
+/// The instance identity document, describing attributes of the running instance
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstanceIdentityDocument {
+    account_id: String,
+    architecture: String,
+    availability_zone: String,
+    image_id: String,
+    instance_id: String,
+    instance_type: String,
+    private_ip: Option<String>,
+    region: String,
+    pending_time: Option<SystemTime>,
+}
+
+impl InstanceIdentityDocument {
+    /// The ID of the AWS account that launched the instance
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    /// The architecture of the AMI used to launch the instance, e.g. `x86_64` or `arm64`
+    pub fn architecture(&self) -> &str {
+        &self.architecture
+    }
+
+    /// The Availability Zone in which the instance is running
+    pub fn availability_zone(&self) -> &str {
+        &self.availability_zone
+    }
+
+    /// The ID of the AMI used to launch the instance
+    pub fn image_id(&self) -> &str {
+        &self.image_id
+    }
+
+    /// The ID of the instance
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// The instance type, e.g. `m5.large`
+    pub fn instance_type(&self) -> &str {
+        &self.instance_type
+    }
+
+    /// The private IPv4 address of the instance
+    pub fn private_ip(&self) -> Option<&str> {
+        self.private_ip.as_deref()
+    }
+
+    /// The Region in which the instance is running
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// The time at which the instance was launched
+    pub fn pending_time(&self) -> Option<SystemTime> {
+        self.pending_time
+    }
+}
+
+/// A network interface attached to the instance
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetworkInterface {
+    mac: String,
+    device_number: Option<u32>,
+    interface_id: Option<String>,
+    local_ipv4s: Vec<String>,
+    public_ipv4s: Vec<String>,
+    subnet_id: Option<String>,
+    vpc_id: Option<String>,
+}
+
+impl NetworkInterface {
+    /// The MAC address of the interface
+    pub fn mac(&self) -> &str {
+        &self.mac
+    }
+
+    /// The device number of the interface. The primary interface has device number 0.
+    pub fn device_number(&self) -> Option<u32> {
+        self.device_number
+    }
+
+    /// The ID of the interface
+    pub fn interface_id(&self) -> Option<&str> {
+        self.interface_id.as_deref()
+    }
+
+    /// The private IPv4 addresses associated with the interface
+    pub fn local_ipv4s(&self) -> &[String] {
+        &self.local_ipv4s
+    }
+
+    /// The public IPv4 addresses associated with the interface
+    pub fn public_ipv4s(&self) -> &[String] {
+        &self.public_ipv4s
+    }
+
+    /// The ID of the subnet in which the interface resides
+    pub fn subnet_id(&self) -> Option<&str> {
+        self.subnet_id.as_deref()
+    }
+
+    /// The ID of the VPC in which the interface resides
+    pub fn vpc_id(&self) -> Option<&str> {
+        self.vpc_id.as_deref()
+    }
+}
+
+/// A pending interruption of a spot instance
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpotInterruption {
+    action: String,
+    time: SystemTime,
+}
+
+impl SpotInterruption {
+    /// The action that will be taken: `hibernate`, `stop`, or `terminate`
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// The approximate time at which the action will be taken
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+}
+
+/// Information about the IAM instance profile associated with the instance
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IamInfo {
+    instance_profile_arn: String,
+    instance_profile_id: String,
+    last_updated: Option<SystemTime>,
+}
+
+impl IamInfo {
+    /// The ARN of the instance profile
+    pub fn instance_profile_arn(&self) -> &str {
+        &self.instance_profile_arn
+    }
+
+    /// The ID of the instance profile
+    pub fn instance_profile_id(&self) -> &str {
+        &self.instance_profile_id
+    }
+
+    /// The last time the instance profile information was updated
+    pub fn last_updated(&self) -> Option<SystemTime> {
+        self.last_updated
+    }
+}
+
+fn parse_identity_document(
+    document: &str,
+) -> Result<InstanceIdentityDocument, InvalidJsonCredentials> {
+    let mut account_id = None;
+    let mut architecture = None;
+    let mut availability_zone = None;
+    let mut image_id = None;
+    let mut instance_id = None;
+    let mut instance_type = None;
+    let mut private_ip = None;
+    let mut region = None;
+    let mut pending_time = None;
+    json_parse_loop(document.as_bytes(), |key, value| {
+        if let Token::ValueString { value, .. } = value {
+            let value = value.to_unescaped()?;
+            match key.as_ref() {
+                "accountId" => account_id = Some(value),
+                "architecture" => architecture = Some(value),
+                "availabilityZone" => availability_zone = Some(value),
+                "imageId" => image_id = Some(value),
+                "instanceId" => instance_id = Some(value),
+                "instanceType" => instance_type = Some(value),
+                "privateIp" => private_ip = Some(value),
+                "region" => region = Some(value),
+                "pendingTime" => pending_time = Some(parse_time("pendingTime", &value)?),
+                _ => {}
+            }
+        }
+        Ok(())
+    })?;
+    Ok(InstanceIdentityDocument {
+        account_id: required(account_id, "accountId")?,
+        architecture: required(architecture, "architecture")?,
+        availability_zone: required(availability_zone, "availabilityZone")?,
+        image_id: required(image_id, "imageId")?,
+        instance_id: required(instance_id, "instanceId")?,
+        instance_type: required(instance_type, "instanceType")?,
+        private_ip: private_ip.map(String::from),
+        region: required(region, "region")?,
+        pending_time,
+    })
+}
+
+fn parse_spot_interruption(action: &str) -> Result<SpotInterruption, InvalidJsonCredentials> {
+    let mut kind = None;
+    let mut time = None;
+    json_parse_loop(action.as_bytes(), |key, value| {
+        match (key.as_ref(), value) {
+            ("action", Token::ValueString { value, .. }) => {
+                kind = Some(value.to_unescaped()?.into_owned())
+            }
+            ("time", Token::ValueString { value, .. }) => {
+                time = Some(parse_time("time", &value.to_unescaped()?)?)
+            }
+            _ => {}
+        }
+        Ok(())
+    })?;
+    Ok(SpotInterruption {
+        action: kind.ok_or(InvalidJsonCredentials::MissingField("action"))?,
+        time: time.ok_or(InvalidJsonCredentials::MissingField("time"))?,
+    })
+}
+
+fn parse_iam_info(info: &str) -> Result<IamInfo, InvalidJsonCredentials> {
+    let mut code = None;
+    let mut instance_profile_arn = None;
+    let mut instance_profile_id = None;
+    let mut last_updated = None;
+    json_parse_loop(info.as_bytes(), |key, value| {
+        if let Token::ValueString { value, .. } = value {
+            let value = value.to_unescaped()?;
+            match key.as_ref() {
+                "Code" => code = Some(value.into_owned()),
+                "InstanceProfileArn" => instance_profile_arn = Some(value.into_owned()),
+                "InstanceProfileId" => instance_profile_id = Some(value.into_owned()),
+                "LastUpdated" => last_updated = Some(parse_time("LastUpdated", &value)?),
+                _ => {}
+            }
+        }
+        Ok(())
+    })?;
+    if let Some(code) = code.filter(|code| code != "Success") {
+        return Err(InvalidJsonCredentials::Other(
+            format!("IMDS returned an unsuccessful IAM info code: {code}").into(),
+        ));
+    }
+    Ok(IamInfo {
+        instance_profile_arn: instance_profile_arn
+            .ok_or(InvalidJsonCredentials::MissingField("InstanceProfileArn"))?,
+        instance_profile_id: instance_profile_id
+            .ok_or(InvalidJsonCredentials::MissingField("InstanceProfileId"))?,
+        last_updated,
+    })
+}
+
+fn parse_time(field: &'static str, value: &str) -> Result<SystemTime, InvalidJsonCredentials> {
+    SystemTime::try_from(DateTime::from_str(value, Format::DateTime).map_err(|err| {
+        InvalidJsonCredentials::InvalidField {
+            field,
+            err: err.into(),
+        }
+    })?)
+    .map_err(|_| {
+        InvalidJsonCredentials::Other(
+            format!("{field} cannot be represented by a SystemTime").into(),
+        )
+    })
+}
+
+fn required(
+    value: Option<Cow<'_, str>>,
+    field: &'static str,
+) -> Result<String, InvalidJsonCredentials> {
+    value
+        .map(String::from)
+        .ok_or(InvalidJsonCredentials::MissingField(field))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::imds::client::error::ImdsError;
+    use crate::imds::client::test::{
+        imds_request, imds_response, make_imds_client, token_request, token_response,
+    };
+    use crate::imds::metadata::InstanceMetadata;
+    use aws_smithy_http_client::test_util::{ReplayEvent, StaticReplayClient};
+    use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_types::body::SdkBody;
+    use std::collections::HashMap;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const TOKEN_A: &str = "token_a";
+
+    fn not_found() -> HttpResponse {
+        HttpResponse::try_from(
+            http::Response::builder()
+                .status(404)
+                .body(SdkBody::empty())
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn token_event() -> ReplayEvent {
+        ReplayEvent::new(
+            token_request("http://169.254.169.254", 21600),
+            token_response(21600, TOKEN_A),
+        )
+    }
+
+    #[tokio::test]
+    async fn identity_document_is_cached() {
+        let http_client = StaticReplayClient::new(vec![
+            token_event(),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/dynamic/instance-identity/document",
+                    TOKEN_A,
+                ),
+                imds_response(
+                    r#"{
+                        "accountId" : "123456789012",
+                        "architecture" : "x86_64",
+                        "availabilityZone" : "us-west-2b",
+                        "billingProducts" : null,
+                        "devpayProductCodes" : null,
+                        "marketplaceProductCodes" : [ "1abc2defghijklm3nopqrs4tu" ],
+                        "imageId" : "ami-5fb8c835",
+                        "instanceId" : "i-1234567890abcdef0",
+                        "instanceType" : "t2.micro",
+                        "kernelId" : null,
+                        "pendingTime" : "2016-11-19T16:32:11Z",
+                        "privateIp" : "10.158.112.84",
+                        "ramdiskId" : null,
+                        "region" : "us-west-2",
+                        "version" : "2017-09-30"
+                    }"#,
+                ),
+            ),
+        ]);
+        let metadata = InstanceMetadata::new(make_imds_client(&http_client));
+        let document = metadata.identity_document().await.expect("valid document");
+        assert_eq!("123456789012", document.account_id());
+        assert_eq!("us-west-2b", document.availability_zone());
+        assert_eq!("i-1234567890abcdef0", document.instance_id());
+        assert_eq!("t2.micro", document.instance_type());
+        assert_eq!(Some("10.158.112.84"), document.private_ip());
+        assert_eq!("us-west-2", document.region());
+        assert_eq!(
+            Some(UNIX_EPOCH + Duration::from_secs(1479573131)),
+            document.pending_time()
+        );
+
+        // the second load is served from the cache
+        assert_eq!(
+            document,
+            metadata.identity_document().await.expect("cached")
+        );
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn instance_tags() {
+        let http_client = StaticReplayClient::new(vec![
+            token_event(),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/tags/instance",
+                    TOKEN_A,
+                ),
+                imds_response("Name\nteam"),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/tags/instance/Name",
+                    TOKEN_A,
+                ),
+                imds_response("my-instance"),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/tags/instance/team",
+                    TOKEN_A,
+                ),
+                imds_response("storage"),
+            ),
+        ]);
+        let metadata = InstanceMetadata::new(make_imds_client(&http_client));
+        let expected = HashMap::from([
+            ("Name".to_string(), "my-instance".to_string()),
+            ("team".to_string(), "storage".to_string()),
+        ]);
+        assert_eq!(
+            expected,
+            metadata.instance_tags().await.expect("valid tags")
+        );
+        assert_eq!(expected, metadata.instance_tags().await.expect("cached"));
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn network_interfaces() {
+        let http_client = StaticReplayClient::new(vec![
+            token_event(),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/network/interfaces/macs/",
+                    TOKEN_A,
+                ),
+                imds_response("0e:49:61:0f:c3:11/"),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/network/interfaces/macs/0e:49:61:0f:c3:11/device-number",
+                    TOKEN_A,
+                ),
+                imds_response("0"),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/network/interfaces/macs/0e:49:61:0f:c3:11/interface-id",
+                    TOKEN_A,
+                ),
+                imds_response("eni-0f95d3625f5c521cc"),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/network/interfaces/macs/0e:49:61:0f:c3:11/local-ipv4s",
+                    TOKEN_A,
+                ),
+                imds_response("10.0.0.12\n10.0.0.13"),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/network/interfaces/macs/0e:49:61:0f:c3:11/public-ipv4s",
+                    TOKEN_A,
+                ),
+                not_found(),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/network/interfaces/macs/0e:49:61:0f:c3:11/subnet-id",
+                    TOKEN_A,
+                ),
+                imds_response("subnet-0ac62554"),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/network/interfaces/macs/0e:49:61:0f:c3:11/vpc-id",
+                    TOKEN_A,
+                ),
+                imds_response("vpc-d295a6a7"),
+            ),
+        ]);
+        let metadata = InstanceMetadata::new(make_imds_client(&http_client));
+        let interfaces = metadata
+            .network_interfaces()
+            .await
+            .expect("valid interfaces");
+        assert_eq!(1, interfaces.len());
+        let interface = &interfaces[0];
+        assert_eq!("0e:49:61:0f:c3:11", interface.mac());
+        assert_eq!(Some(0), interface.device_number());
+        assert_eq!(Some("eni-0f95d3625f5c521cc"), interface.interface_id());
+        assert_eq!(&["10.0.0.12", "10.0.0.13"], interface.local_ipv4s());
+        assert!(interface.public_ipv4s().is_empty());
+        assert_eq!(Some("subnet-0ac62554"), interface.subnet_id());
+        assert_eq!(Some("vpc-d295a6a7"), interface.vpc_id());
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn spot_interruption() {
+        let http_client = StaticReplayClient::new(vec![
+            token_event(),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/spot/instance-action",
+                    TOKEN_A,
+                ),
+                not_found(),
+            ),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/spot/instance-action",
+                    TOKEN_A,
+                ),
+                imds_response(r#"{"action": "terminate", "time": "2017-09-18T08:22:00Z"}"#),
+            ),
+        ]);
+        let metadata = InstanceMetadata::new(make_imds_client(&http_client));
+        assert_eq!(None, metadata.spot_interruption().await.expect("no action"));
+        let interruption = metadata
+            .spot_interruption()
+            .await
+            .expect("valid response")
+            .expect("interruption scheduled");
+        assert_eq!("terminate", interruption.action());
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(1505722920),
+            interruption.time()
+        );
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn iam_info() {
+        let http_client = StaticReplayClient::new(vec![
+            token_event(),
+            ReplayEvent::new(
+                imds_request("http://169.254.169.254/latest/meta-data/iam/info", TOKEN_A),
+                imds_response(
+                    r#"{
+                        "Code" : "Success",
+                        "LastUpdated" : "2021-09-20T21:42:26Z",
+                        "InstanceProfileArn" : "arn:aws:iam::123456789012:instance-profile/my-profile",
+                        "InstanceProfileId" : "AIPAABCDEFGHIJKLMN123"
+                    }"#,
+                ),
+            ),
+        ]);
+        let metadata = InstanceMetadata::new(make_imds_client(&http_client));
+        let info = metadata
+            .iam_info()
+            .await
+            .expect("valid response")
+            .expect("instance profile attached");
+        assert_eq!(
+            "arn:aws:iam::123456789012:instance-profile/my-profile",
+            info.instance_profile_arn()
+        );
+        assert_eq!("AIPAABCDEFGHIJKLMN123", info.instance_profile_id());
+        assert_eq!(
+            Some(UNIX_EPOCH + Duration::from_secs(1632174146)),
+            info.last_updated()
+        );
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn invalid_identity_document() {
+        let http_client = StaticReplayClient::new(vec![
+            token_event(),
+            ReplayEvent::new(
+                imds_request(
+                    "http://169.254.169.254/latest/dynamic/instance-identity/document",
+                    TOKEN_A,
+                ),
+                imds_response(r#"{"accountId": "123456789012"}"#),
+            ),
+        ]);
+        let metadata = InstanceMetadata::new(make_imds_client(&http_client));
+        let err = metadata
+            .identity_document()
+            .await
+            .expect_err("missing fields");
+        assert!(matches!(err, ImdsError::Unexpected(_)), "{err:?}");
+    }
+}
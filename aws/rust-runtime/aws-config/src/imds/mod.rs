@@ -3,15 +3,16 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-//! IMDSv2 Client, credential, and region provider
+//! IMDSv2 Client, credential, and region provider, and typed metadata accessors
 //!
 //! See [`client`] for more information.
 pub mod client;
 
 pub mod credentials;
+pub mod metadata;
 pub mod region;
 
-mod env {
+pub(crate) mod env {
     pub(crate) const EC2_METADATA_DISABLED: &str = "AWS_EC2_METADATA_DISABLED";
 }
 
@@ -0,0 +1,463 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Load credentials from the AWS IoT Core credentials provider.
+//!
+//! Devices registered with AWS IoT Core can exchange their X.509 device certificate for temporary
+//! AWS credentials by calling the IoT credentials endpoint over mutual TLS (mTLS). The credentials are
+//! issued for the IAM role that the given role alias points to. The endpoint of an account can be
+//! found with `aws iot describe-endpoint --endpoint-type iot:CredentialProvider`.
+//!
+//! The device certificate is presented by the HTTP client, which must be configured with the
+//! certificate and its private key. See [`Builder::http_client`] for an example.
+
+use crate::json_credentials::InvalidJsonCredentials;
+use crate::provider_config::ProviderConfig;
+use aws_credential_types::provider::{self, error::CredentialsError, future, ProvideCredentials};
+use aws_credential_types::Credentials;
+use aws_smithy_http::label::{fmt_string, EncodingStrategy};
+use aws_smithy_json::deserialize::token::{expect_start_object, expect_string_or_null, skip_value};
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::{
+    HttpStatusCodeClassifier, TransientErrorClassifier,
+};
+use aws_smithy_runtime_api::client::http::{HttpClient, SharedHttpClient};
+use aws_smithy_runtime_api::client::interceptors::context::Error;
+use aws_smithy_runtime_api::client::orchestrator::{
+    HttpResponse, OrchestratorError, SensitiveOutput,
+};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::Layer;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use aws_smithy_types::DateTime;
+use std::time::{Duration, SystemTime};
+
+const PROVIDER_NAME: &str = "IotCredentials";
+const THING_NAME_HEADER: &str = "x-amzn-iot-thingname";
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Credentials provider that exchanges an AWS IoT device certificate for AWS credentials.
+///
+/// The credentials are loaded from `https://<endpoint>/role-aliases/<role alias>/credentials`.
+/// They expire, and will be refreshed automatically by the identity cache of the SDK client they
+/// are used with.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_config::iot::IotCredentialsProvider;
+/// use aws_smithy_http_client::tls::{self, rustls_provider::CryptoMode, ClientIdentity, TlsContext};
+/// use aws_smithy_http_client::Builder;
+///
+/// # fn docs(cert_chain_pem: Vec<u8>, private_key_pem: Vec<u8>) {
+/// let http_client = Builder::new()
+///     .tls_provider(tls::Provider::Rustls(CryptoMode::AwsLc))
+///     .tls_context(
+///         TlsContext::builder()
+///             .with_client_identity(ClientIdentity::from_pem(cert_chain_pem, private_key_pem))
+///             .build()
+///             .expect("valid TLS config"),
+///     )
+///     .build_https();
+///
+/// let provider = IotCredentialsProvider::builder()
+///     .endpoint("c2example.credentials.iot.us-east-1.amazonaws.com")
+///     .role_alias("my-device-role-alias")
+///     .thing_name("my-device")
+///     .http_client(http_client)
+///     .build();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct IotCredentialsProvider {
+    operation: Operation<(), Credentials, CredentialsError>,
+}
+
+impl IotCredentialsProvider {
+    /// Builder for this credentials provider
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    async fn credentials(&self) -> provider::Result {
+        match self.operation.invoke(()).await {
+            Ok(credentials) => Ok(credentials),
+            Err(SdkError::ServiceError(context)) => Err(context.into_err()),
+            Err(other) => Err(CredentialsError::provider_error(other)),
+        }
+    }
+}
+
+impl ProvideCredentials for IotCredentialsProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+/// Builder for [`IotCredentialsProvider`]
+#[derive(Debug, Default)]
+pub struct Builder {
+    provider_config: Option<ProviderConfig>,
+    endpoint: Option<String>,
+    role_alias: Option<String>,
+    thing_name: Option<String>,
+    http_client: Option<SharedHttpClient>,
+}
+
+impl Builder {
+    /// Override the configuration used for this provider
+    pub fn configure(mut self, provider_config: &ProviderConfig) -> Self {
+        self.provider_config = Some(provider_config.clone());
+        self
+    }
+
+    /// The IoT credentials endpoint of the account, e.g. `c2example.credentials.iot.us-east-1.amazonaws.com`
+    ///
+    /// A URL with a scheme may also be given. This is required.
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// The role alias that points to the IAM role to get credentials for
+    ///
+    /// This is required.
+    pub fn role_alias(mut self, role_alias: impl Into<String>) -> Self {
+        self.role_alias = Some(role_alias.into());
+        self
+    }
+
+    /// The name of the IoT thing the device certificate is attached to
+    ///
+    /// This is required when the role's policies use the `credentials-iot:ThingName` policy variable.
+    pub fn thing_name(mut self, thing_name: impl Into<String>) -> Self {
+        self.thing_name = Some(thing_name.into());
+        self
+    }
+
+    /// The HTTP client that presents the device certificate to the credentials endpoint
+    ///
+    /// The client must be configured for mutual TLS with the device certificate and its private
+    /// key. When not set, the HTTP client of the [`ProviderConfig`] is used.
+    pub fn http_client(mut self, http_client: impl HttpClient + 'static) -> Self {
+        self.http_client = Some(http_client.into_shared());
+        self
+    }
+
+    /// Build an [`IotCredentialsProvider`] from this builder
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the endpoint or role alias are not set.
+    pub fn build(self) -> IotCredentialsProvider {
+        let provider_config = self.provider_config.unwrap_or_default();
+        let endpoint = self
+            .endpoint
+            .expect("an IoT credentials endpoint is required");
+        let endpoint = if endpoint.contains("://") {
+            endpoint
+        } else {
+            format!("https://{endpoint}")
+        };
+        let role_alias = self.role_alias.expect("a role alias is required");
+        let path = format!(
+            "/role-aliases/{}/credentials",
+            fmt_string(role_alias, EncodingStrategy::Default)
+        );
+        let thing_name = self.thing_name;
+
+        let mut builder = Operation::builder()
+            .service_name("IotCredentialsProvider")
+            .operation_name("AssumeRoleWithCertificate")
+            .with_connection_poisoning()
+            .endpoint_url(&endpoint)
+            .no_auth()
+            .timeout_config(
+                TimeoutConfig::builder()
+                    .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+                    .read_timeout(DEFAULT_READ_TIMEOUT)
+                    .build(),
+            )
+            .runtime_plugin(StaticRuntimePlugin::new().with_config({
+                let mut layer = Layer::new("SensitiveOutput");
+                layer.store_put(SensitiveOutput);
+                layer.freeze()
+            }));
+        if let Some(http_client) = self.http_client.or_else(|| provider_config.http_client()) {
+            builder = builder.http_client(http_client);
+        }
+        if let Some(sleep_impl) = provider_config.sleep_impl() {
+            builder = builder
+                .standard_retry(&RetryConfig::standard())
+                // Socket errors and network timeouts
+                .retry_classifier(TransientErrorClassifier::<Error>::new())
+                // 5xx errors
+                .retry_classifier(HttpStatusCodeClassifier::default())
+                .sleep_impl(sleep_impl);
+        } else {
+            builder = builder.no_retry();
+        }
+        let operation = builder
+            .serializer(move |_input: ()| {
+                let mut request = http::Request::builder().uri(path.clone());
+                if let Some(thing_name) = &thing_name {
+                    request = request.header(THING_NAME_HEADER, thing_name);
+                }
+                Ok(request
+                    .body(SdkBody::empty())
+                    .expect("valid request")
+                    .try_into()
+                    .unwrap())
+            })
+            .deserializer(parse_response)
+            .build();
+        IotCredentialsProvider { operation }
+    }
+}
+
+fn parse_response(
+    response: &HttpResponse,
+) -> Result<Credentials, OrchestratorError<CredentialsError>> {
+    let body = response.body().bytes().expect("non-streaming deserializer");
+    if !response.status().is_success() {
+        let message = parse_error_message(body);
+        return Err(OrchestratorError::operation(
+            CredentialsError::provider_error(format!(
+                "IoT credentials endpoint returned an error ({:?}): {}",
+                response.status(),
+                message.as_deref().unwrap_or("no message"),
+            )),
+        ));
+    }
+    parse_credentials(body)
+        .map_err(|err| OrchestratorError::operation(CredentialsError::unhandled(err)))
+}
+
+fn parse_error_message(body: &[u8]) -> Option<String> {
+    let mut message = None;
+    let _ = crate::json_credentials::json_parse_loop(body, |key, value| {
+        if let Token::ValueString { value, .. } = value {
+            if key.eq_ignore_ascii_case("message") {
+                message = Some(value.to_unescaped()?.into_owned());
+            }
+        }
+        Ok(())
+    });
+    message
+}
+
+/// Parses a response of the form:
+/// ```json
+/// {
+///   "credentials": {
+///     "accessKeyId": "...",
+///     "secretAccessKey": "...",
+///     "sessionToken": "...",
+///     "expiration": "2018-01-18T09:18:06Z"
+///   }
+/// }
+/// ```
+fn parse_credentials(body: &[u8]) -> Result<Credentials, InvalidJsonCredentials> {
+    let mut tokens = json_token_iter(body).peekable();
+    expect_start_object(tokens.next())?;
+    let (mut access_key_id, mut secret_access_key, mut session_token, mut expiration) =
+        (None, None, None, None);
+    loop {
+        match tokens.next().transpose()? {
+            Some(Token::EndObject { .. }) => break,
+            Some(Token::ObjectKey { key, .. }) if key.to_unescaped()? == "credentials" => {
+                expect_start_object(tokens.next())?;
+                loop {
+                    match tokens.next().transpose()? {
+                        Some(Token::EndObject { .. }) => break,
+                        Some(Token::ObjectKey { key, .. }) => {
+                            let field = match key.to_unescaped()?.as_ref() {
+                                "accessKeyId" => &mut access_key_id,
+                                "secretAccessKey" => &mut secret_access_key,
+                                "sessionToken" => &mut session_token,
+                                "expiration" => &mut expiration,
+                                _ => {
+                                    skip_value(&mut tokens)?;
+                                    continue;
+                                }
+                            };
+                            *field = expect_string_or_null(tokens.next())?
+                                .map(|v| v.to_unescaped().map(|v| v.into_owned()))
+                                .transpose()?;
+                        }
+                        other => {
+                            return Err(InvalidJsonCredentials::Other(
+                                format!("expected object key, found: {other:?}").into(),
+                            ))
+                        }
+                    }
+                }
+            }
+            Some(Token::ObjectKey { .. }) => skip_value(&mut tokens)?,
+            other => {
+                return Err(InvalidJsonCredentials::Other(
+                    format!("expected object key, found: {other:?}").into(),
+                ))
+            }
+        }
+    }
+
+    let expiration = expiration.ok_or(InvalidJsonCredentials::MissingField("expiration"))?;
+    let expiration = SystemTime::try_from(
+        DateTime::from_str(&expiration, Format::DateTime).map_err(|err| {
+            InvalidJsonCredentials::InvalidField {
+                field: "expiration",
+                err: err.into(),
+            }
+        })?,
+    )
+    .map_err(|_| {
+        InvalidJsonCredentials::Other(
+            "credential expiration time cannot be represented by a SystemTime".into(),
+        )
+    })?;
+    Ok(Credentials::new(
+        access_key_id.ok_or(InvalidJsonCredentials::MissingField("accessKeyId"))?,
+        secret_access_key.ok_or(InvalidJsonCredentials::MissingField("secretAccessKey"))?,
+        Some(session_token.ok_or(InvalidJsonCredentials::MissingField("sessionToken"))?),
+        Some(expiration),
+        PROVIDER_NAME,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_smithy_http_client::test_util::{ReplayEvent, StaticReplayClient};
+    use http::{Request, Response};
+
+    fn event(status: u16, response_body: &str) -> ReplayEvent {
+        event_for_alias("my-alias", status, response_body)
+    }
+
+    fn event_for_alias(encoded_alias: &str, status: u16, response_body: &str) -> ReplayEvent {
+        ReplayEvent::new(
+            Request::builder()
+                .uri(format!("https://c2example.credentials.iot.us-east-1.amazonaws.com/role-aliases/{encoded_alias}/credentials"))
+                .header(THING_NAME_HEADER, "my-device")
+                .body(SdkBody::empty())
+                .unwrap(),
+            Response::builder()
+                .status(status)
+                .body(SdkBody::from(response_body.to_owned()))
+                .unwrap(),
+        )
+    }
+
+    fn provider(http_client: &StaticReplayClient) -> IotCredentialsProvider {
+        provider_for_alias("my-alias", http_client)
+    }
+
+    fn provider_for_alias(
+        role_alias: &str,
+        http_client: &StaticReplayClient,
+    ) -> IotCredentialsProvider {
+        IotCredentialsProvider::builder()
+            .configure(&ProviderConfig::no_configuration())
+            .endpoint("c2example.credentials.iot.us-east-1.amazonaws.com")
+            .role_alias(role_alias)
+            .thing_name("my-device")
+            .http_client(http_client.clone())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn loads_credentials() {
+        let http_client = StaticReplayClient::new(vec![event(
+            200,
+            r#"{
+                "credentials": {
+                    "accessKeyId": "ASIARTEST",
+                    "secretAccessKey": "secret",
+                    "sessionToken": "session-token",
+                    "expiration": "2021-09-18T03:31:56Z"
+                }
+            }"#,
+        )]);
+        let creds = provider(&http_client)
+            .provide_credentials()
+            .await
+            .expect("success");
+        assert_eq!("ASIARTEST", creds.access_key_id());
+        assert_eq!("secret", creds.secret_access_key());
+        assert_eq!(Some("session-token"), creds.session_token());
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1631935916)),
+            creds.expiry()
+        );
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn role_alias_is_percent_encoded() {
+        let http_client = StaticReplayClient::new(vec![event_for_alias(
+            "my%20alias%2Fwith%3Fspecial%23chars",
+            200,
+            r#"{
+                "credentials": {
+                    "accessKeyId": "ASIARTEST",
+                    "secretAccessKey": "secret",
+                    "sessionToken": "session-token",
+                    "expiration": "2021-09-18T03:31:56Z"
+                }
+            }"#,
+        )]);
+        provider_for_alias("my alias/with?special#chars", &http_client)
+            .provide_credentials()
+            .await
+            .expect("success");
+        http_client.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn error_response() {
+        let http_client =
+            StaticReplayClient::new(vec![event(403, r#"{"message":"Access Denied"}"#)]);
+        let err = provider(&http_client)
+            .provide_credentials()
+            .await
+            .expect_err("it should fail");
+        assert!(
+            matches!(err, CredentialsError::ProviderError { .. }),
+            "should be CredentialsError::ProviderError: {err}",
+        );
+        let message = format!(
+            "{}",
+            aws_smithy_types::error::display::DisplayErrorContext(&err)
+        );
+        assert!(message.contains("Access Denied"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn missing_field() {
+        let http_client = StaticReplayClient::new(vec![event(
+            200,
+            r#"{"credentials": {"accessKeyId": "ASIARTEST"}}"#,
+        )]);
+        let err = provider(&http_client)
+            .provide_credentials()
+            .await
+            .expect_err("it should fail");
+        assert!(
+            matches!(err, CredentialsError::Unhandled { .. }),
+            "should be CredentialsError::Unhandled: {err}",
+        );
+    }
+}
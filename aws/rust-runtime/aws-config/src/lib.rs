@@ -123,13 +123,17 @@ mod json_credentials;
 #[cfg(test)]
 mod test_case;
 
+pub mod cognito;
 pub mod credential_process;
 pub mod default_provider;
+pub mod document;
 pub mod ecs;
 mod env_service_config;
 pub mod environment;
 pub mod imds;
+pub mod iot;
 pub mod meta;
+pub mod offline;
 pub mod profile;
 pub mod provider_config;
 pub mod retry;
@@ -138,6 +142,8 @@ mod sensitive_command;
 pub mod sso;
 pub mod stalled_stream_protection;
 pub mod sts;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 pub mod timeout;
 pub mod web_identity_token;
 
@@ -222,8 +228,9 @@ mod loader {
     use aws_credential_types::Credentials;
     use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
     use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+    use aws_smithy_runtime::client::defaults::default_http_client;
     use aws_smithy_runtime::client::identity::IdentityCache;
-    use aws_smithy_runtime_api::client::auth::AuthSchemePreference;
+    use aws_smithy_runtime_api::client::auth::{AuthSchemePreference, DisabledAuthSchemes};
     use aws_smithy_runtime_api::client::behavior_version::BehaviorVersion;
     use aws_smithy_runtime_api::client::http::HttpClient;
     use aws_smithy_runtime_api::client::identity::{ResolveCachedIdentity, SharedIdentityCache};
@@ -234,24 +241,31 @@ mod loader {
     };
     use aws_smithy_types::retry::RetryConfig;
     use aws_smithy_types::timeout::TimeoutConfig;
+    use aws_smithy_types::Document;
     use aws_types::app_name::AppName;
+    use aws_types::defaults_mode::DefaultsMode;
     use aws_types::docs_for;
     use aws_types::endpoint_config::AccountIdEndpointMode;
     use aws_types::origin::Origin;
     use aws_types::os_shim_internal::{Env, Fs};
+    use aws_types::region::SigningRegionSet;
     use aws_types::sdk_config::SharedHttpClient;
     use aws_types::SdkConfig;
 
+    #[cfg(feature = "default-https-client")]
+    use crate::default_provider::proxy_config;
     use crate::default_provider::{
         account_id_endpoint_mode, app_name, auth_scheme_preference, checksums, credentials,
-        disable_request_compression, endpoint_url, ignore_configured_endpoint_urls as ignore_ep,
-        region, request_min_compression_size_bytes, retry_config, timeout_config, use_dual_stack,
-        use_fips,
+        defaults_mode, disable_request_compression, endpoint_url,
+        ignore_configured_endpoint_urls as ignore_ep, region, request_min_compression_size_bytes,
+        retry_config, sigv4a_signing_region_set, timeout_config, use_dual_stack, use_fips,
     };
+    use crate::document::{CredentialSource, DocumentConfig, DocumentConfigError};
     use crate::meta::region::ProvideRegion;
     #[allow(deprecated)]
     use crate::profile::profile_file::ProfileFiles;
     use crate::provider_config::ProviderConfig;
+    use crate::sts::StsClientConfig;
 
     #[derive(Default, Debug)]
     enum TriStateOption<T> {
@@ -274,27 +288,37 @@ mod loader {
     pub struct ConfigLoader {
         app_name: Option<AppName>,
         auth_scheme_preference: Option<AuthSchemePreference>,
+        disabled_auth_schemes: Option<DisabledAuthSchemes>,
         identity_cache: Option<SharedIdentityCache>,
         credentials_provider: TriStateOption<SharedCredentialsProvider>,
+        credential_source: Option<CredentialSource>,
         token_provider: Option<SharedTokenProvider>,
         account_id_endpoint_mode: Option<AccountIdEndpointMode>,
         endpoint_url: Option<String>,
+        service_endpoint_urls: Vec<(String, String)>,
         region: Option<Box<dyn ProvideRegion>>,
         retry_config: Option<RetryConfig>,
+        provider_retry_config: Option<RetryConfig>,
+        provider_timeout_config: Option<TimeoutConfig>,
         sleep: Option<SharedAsyncSleep>,
         timeout_config: Option<TimeoutConfig>,
         provider_config: Option<ProviderConfig>,
+        sts_client_config: Option<StsClientConfig>,
         http_client: Option<SharedHttpClient>,
         profile_name_override: Option<String>,
         #[allow(deprecated)]
         profile_files_override: Option<ProfileFiles>,
         use_fips: Option<bool>,
         use_dual_stack: Option<bool>,
+        defaults_mode: Option<DefaultsMode>,
         time_source: Option<SharedTimeSource>,
         disable_request_compression: Option<bool>,
         request_min_compression_size_bytes: Option<u32>,
+        sigv4a_signing_region_set: Option<SigningRegionSet>,
         stalled_stream_protection_config: Option<StalledStreamProtectionConfig>,
         env: Option<Env>,
+        env_prefix: Option<String>,
+        offline: bool,
         fs: Option<Fs>,
         behavior_version: Option<BehaviorVersion>,
         request_checksum_calculation: Option<RequestChecksumCalculation>,
@@ -374,6 +398,57 @@ mod loader {
             self
         }
 
+        /// Override the retry config of the requests made while loading credentials and config
+        ///
+        /// This applies to the clients aws-config creates internally to call IMDS, the ECS
+        /// credentials endpoint, SSO, SSO OIDC, and STS. It doesn't affect service clients, which
+        /// use [`retry_config`](Self::retry_config).
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// use aws_config::retry::RetryConfig;
+        ///
+        /// let config = aws_config::from_env()
+        ///     .provider_retry_config(RetryConfig::standard().with_max_attempts(1))
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn provider_retry_config(mut self, retry_config: RetryConfig) -> Self {
+            self.provider_retry_config = Some(retry_config);
+            self
+        }
+
+        /// Override the timeouts of the requests made while loading credentials and config
+        ///
+        /// Like [`provider_retry_config`](Self::provider_retry_config), this only applies to the
+        /// clients aws-config creates internally, and can be used to bound the worst-case time
+        /// spent loading config. Timeouts that aren't set keep each provider's defaults. Service
+        /// clients use [`timeout_config`](Self::timeout_config).
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # use std::time::Duration;
+        /// # async fn create_config() {
+        /// use aws_config::timeout::TimeoutConfig;
+        ///
+        /// let config = aws_config::from_env()
+        ///     .provider_timeout_config(
+        ///         TimeoutConfig::builder()
+        ///             .connect_timeout(Duration::from_millis(500))
+        ///             .operation_timeout(Duration::from_secs(2))
+        ///             .build(),
+        ///     )
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn provider_timeout_config(mut self, timeout_config: TimeoutConfig) -> Self {
+            self.provider_timeout_config = Some(timeout_config);
+            self
+        }
+
         /// Override the sleep implementation for this [`ConfigLoader`].
         ///
         /// The sleep implementation is used to create timeout futures.
@@ -397,7 +472,16 @@ mod loader {
 
         /// Override the [`HttpClient`] for this [`ConfigLoader`].
         ///
-        /// The HTTP client will be used for both AWS services and credentials providers.
+        /// The HTTP client will be used for both AWS services and credentials providers. This
+        /// includes the clients aws-config creates internally to call IMDS, the ECS credentials
+        /// endpoint, SSO, SSO OIDC, and STS, so they share one connection pool, proxy, and TLS
+        /// configuration with the service clients.
+        ///
+        /// When no HTTP client is set, a default HTTPS client is created once and shared in the
+        /// same way. It's available from [`SdkConfig::http_client`]. If the selected profile sets
+        /// `proxy_url` (and optionally `proxy_username`, `proxy_password`, and `no_proxy`), the
+        /// default client sends requests through that proxy. Proxy settings from the profile
+        /// don't apply to an HTTP client set with this function.
         ///
         /// If you wish to use a separate HTTP client for credentials providers when creating clients,
         /// then override the HTTP client set with this function on the client-specific `Config`s.
@@ -428,6 +512,27 @@ mod loader {
             self
         }
 
+        #[doc = docs_for!(disabled_auth_schemes)]
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # use aws_smithy_runtime_api::client::auth::AuthSchemeId;
+        /// # async fn create_config() {
+        /// let config = aws_config::from_env()
+        ///     // Never sign requests with a bearer token, even if the service supports it.
+        ///     .disabled_auth_schemes([AuthSchemeId::from("httpBearerAuth")])
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn disabled_auth_schemes(
+            mut self,
+            disabled_auth_schemes: impl Into<DisabledAuthSchemes>,
+        ) -> Self {
+            self.disabled_auth_schemes = Some(disabled_auth_schemes.into());
+            self
+        }
+
         /// Override the identity cache used to build [`SdkConfig`].
         ///
         /// The identity cache caches AWS credentials and SSO tokens. By default, a lazy cache is used
@@ -653,6 +758,83 @@ mod loader {
             self
         }
 
+        #[doc = docs_for!(defaults_mode)]
+        ///
+        /// When not set, the mode is loaded from the `AWS_DEFAULTS_MODE` environment variable or the
+        /// `defaults_mode` profile key. If no mode is configured, [`DefaultsMode::Legacy`] is used.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// use aws_config::BehaviorVersion;
+        /// use aws_types::defaults_mode::DefaultsMode;
+        ///
+        /// let config = aws_config::defaults(BehaviorVersion::latest())
+        ///     .defaults_mode(DefaultsMode::Auto)
+        ///     .load()
+        ///     .await;
+        /// println!("detected defaults mode: {:?}", config.defaults_mode());
+        /// # }
+        /// ```
+        pub fn defaults_mode(mut self, defaults_mode: DefaultsMode) -> Self {
+            self.defaults_mode = Some(defaults_mode);
+            self
+        }
+
+        /// Look up environment variables with an additional prefix before the standard `AWS_*` names
+        ///
+        /// With a prefix of `MYAPP_`, `MYAPP_AWS_REGION` takes precedence over `AWS_REGION`,
+        /// `MYAPP_AWS_PROFILE` over `AWS_PROFILE`, and so on. Variables without the prefix are still
+        /// used when no prefixed variable is set. This allows scoping SDK configuration to one
+        /// component of a process without changing the `AWS_*` variables other components see.
+        ///
+        /// Static credentials are resolved as a set: when `MYAPP_AWS_ACCESS_KEY_ID` or
+        /// `MYAPP_AWS_SECRET_ACCESS_KEY` is set, the unprefixed credential variables are ignored, so
+        /// a prefixed access key is never combined with an unprefixed session token.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// use aws_config::BehaviorVersion;
+        ///
+        /// let config = aws_config::defaults(BehaviorVersion::latest())
+        ///     .env_prefix("MYAPP_")
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+            self.env_prefix = Some(prefix.into());
+            self
+        }
+
+        /// Resolve configuration and credentials without making any network calls
+        ///
+        /// When `offline` is true, the IMDS, ECS, and web identity token providers of the default
+        /// chains are skipped, and profiles that need SSO or STS fail instead of making a request.
+        /// Skipped steps fail with an error whose source is
+        /// [`NetworkAccessDisabled`](crate::offline::NetworkAccessDisabled), describing what was
+        /// skipped. This applies to configuration resolution only; it doesn't affect the HTTP
+        /// client of service clients created from the resulting [`SdkConfig`].
+        ///
+        /// This is useful for build sandboxes and unit tests that must not touch the network.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// use aws_config::BehaviorVersion;
+        ///
+        /// let config = aws_config::defaults(BehaviorVersion::latest())
+        ///     .offline(true)
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn offline(mut self, offline: bool) -> Self {
+            self.offline = offline;
+            self
+        }
+
         #[doc = docs_for!(account_id_endpoint_mode)]
         pub fn account_id_endpoint_mode(
             mut self,
@@ -681,11 +863,72 @@ mod loader {
         ///     .load()
         ///     .await;
         /// # }
+        /// ```
+        ///
+        /// Route requests through an internal gateway. The `{service}` and `{region}` placeholders
+        /// are replaced with the endpoint prefix of each service and the configured region.
+        /// ```no_run
+        /// # async fn create_config() {
+        /// let sdk_config = aws_config::from_env()
+        ///     .endpoint_url("https://{service}.{region}.mycorp.internal")
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
         pub fn endpoint_url(mut self, endpoint_url: impl Into<String>) -> Self {
             self.endpoint_url = Some(endpoint_url.into());
             self
         }
 
+        /// Override the endpoint URL used for the service with the given service ID
+        ///
+        /// This is the programmatic equivalent of `AWS_ENDPOINT_URL_<SERVICE>`, and takes
+        /// precedence over it and over [`endpoint_url`](Self::endpoint_url). See
+        /// [`SdkConfig::endpoint_url_for`] for how service IDs are matched.
+        ///
+        /// # Examples
+        ///
+        /// Send S3 requests to MinIO and DynamoDB requests to DynamoDB Local:
+        /// ```no_run
+        /// # async fn create_config() {
+        /// let sdk_config = aws_config::from_env()
+        ///     .endpoint_url_for("s3", "http://localhost:9000")
+        ///     .endpoint_url_for("dynamodb", "http://localhost:8000")
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn endpoint_url_for(
+            mut self,
+            service_id: impl Into<String>,
+            endpoint_url: impl Into<String>,
+        ) -> Self {
+            self.service_endpoint_urls
+                .push((service_id.into(), endpoint_url.into()));
+            self
+        }
+
+        /// Override the STS client used by credentials providers that assume roles through STS
+        ///
+        /// This only affects the STS calls made while loading credentials, e.g. for profile role
+        /// chains and web identity tokens. It does not change the configuration of service clients.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// use aws_config::sts::{StsClientConfig, StsEndpointMode};
+        ///
+        /// let sdk_config = aws_config::from_env()
+        ///     .sts_client_config(StsClientConfig::new().endpoint_mode(StsEndpointMode::Global))
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn sts_client_config(mut self, sts_client_config: StsClientConfig) -> Self {
+            self.sts_client_config = Some(sts_client_config);
+            self
+        }
+
         #[doc = docs_for!(use_fips)]
         pub fn use_fips(mut self, use_fips: bool) -> Self {
             self.use_fips = Some(use_fips);
@@ -704,6 +947,15 @@ mod loader {
             self
         }
 
+        #[doc = docs_for!(sigv4a_signing_region_set)]
+        pub fn sigv4a_signing_region_set(
+            mut self,
+            sigv4a_signing_region_set: impl Into<SigningRegionSet>,
+        ) -> Self {
+            self.sigv4a_signing_region_set = Some(sigv4a_signing_region_set.into());
+            self
+        }
+
         #[doc = docs_for!(request_min_compression_size_bytes)]
         pub fn request_min_compression_size_bytes(mut self, size: u32) -> Self {
             self.request_min_compression_size_bytes = Some(size);
@@ -773,6 +1025,98 @@ mod loader {
             self
         }
 
+        /// Apply the settings described by a configuration [`Document`]
+        ///
+        /// Settings in the document override the corresponding settings made on this loader so far,
+        /// and can be overridden again by calling the individual setters afterward. Settings that
+        /// aren't in the document are resolved from the environment as usual. Timeouts in the
+        /// document are merged into the [`TimeoutConfig`] set on this loader, so timeouts the
+        /// document doesn't mention keep their values. See the [`document`](crate::document) module
+        /// for the supported keys.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() -> Result<(), aws_config::document::DocumentConfigError> {
+        /// use aws_config::BehaviorVersion;
+        /// use aws_smithy_types::Document;
+        ///
+        /// let document = Document::Object(
+        ///     [("region".to_string(), Document::String("us-west-2".to_string()))]
+        ///         .into_iter()
+        ///         .collect(),
+        /// );
+        /// let config = aws_config::defaults(BehaviorVersion::latest())
+        ///     .from_document(&document)?
+        ///     .load()
+        ///     .await;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn from_document(self, document: &Document) -> Result<Self, DocumentConfigError> {
+            Ok(self.apply_document_config(DocumentConfig::from_document(document)?))
+        }
+
+        /// Apply the settings described by a JSON configuration document
+        ///
+        /// The document must be a JSON object. See [`from_document`](Self::from_document) and the
+        /// [`document`](crate::document) module for details.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() -> Result<(), aws_config::document::DocumentConfigError> {
+        /// use aws_config::BehaviorVersion;
+        ///
+        /// let json = std::fs::read_to_string("/etc/config/aws.json").unwrap();
+        /// let config = aws_config::defaults(BehaviorVersion::latest())
+        ///     .from_json(&json)?
+        ///     .load()
+        ///     .await;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn from_json(self, json: &str) -> Result<Self, DocumentConfigError> {
+            Ok(self.apply_document_config(DocumentConfig::from_json(json)?))
+        }
+
+        fn apply_document_config(mut self, config: DocumentConfig) -> Self {
+            if let Some(region) = config.region {
+                self = self.region(region);
+            }
+            if let Some(profile_name) = config.profile_name {
+                self = self.profile_name(profile_name);
+            }
+            if let Some(endpoint_url) = config.endpoint_url {
+                self = self.endpoint_url(endpoint_url);
+            }
+            if let Some(use_fips) = config.use_fips {
+                self = self.use_fips(use_fips);
+            }
+            if let Some(use_dual_stack) = config.use_dual_stack {
+                self = self.use_dual_stack(use_dual_stack);
+            }
+            if let Some(app_name) = config.app_name {
+                self = self.app_name(app_name);
+            }
+            if let Some(credentials) = config.credentials {
+                self = self.credentials_provider(credentials);
+                self.credential_source = None;
+            } else if let Some(credential_source) = config.credential_source {
+                self.credentials_provider = TriStateOption::NotSet;
+                self.credential_source = Some(credential_source);
+            }
+            if let Some(retry_config) = config.retry_config {
+                self = self.retry_config(retry_config);
+            }
+            if let Some(mut timeout_config) = config.timeout_config {
+                // Timeouts that aren't in the document keep the values already set on this loader
+                if let Some(loader_timeout_config) = &self.timeout_config {
+                    timeout_config.take_defaults_from(loader_timeout_config);
+                }
+                self = self.timeout_config(timeout_config);
+            }
+            self
+        }
+
         /// Load the default configuration chain
         ///
         /// If fields have been overridden during builder construction, the override values will be used.
@@ -800,18 +1144,64 @@ mod loader {
                 default_async_sleep()
             };
 
-            let conf = self
-                .provider_config
-                .unwrap_or_else(|| {
-                    let mut config = ProviderConfig::init(time_source.clone(), sleep_impl.clone())
-                        .with_fs(self.fs.unwrap_or_default())
-                        .with_env(self.env.unwrap_or_default());
-                    if let Some(http_client) = self.http_client.clone() {
-                        config = config.with_http_client(http_client);
-                    }
-                    config
-                })
-                .with_profile_config(self.profile_files_override, self.profile_name_override);
+            #[cfg(feature = "default-https-client")]
+            let http_client_overridden = self.http_client.is_some();
+            let http_client = self.http_client.or_else(|| {
+                default_http_client(self.behavior_version.unwrap_or(BehaviorVersion::latest()))
+            });
+            let conf = self.provider_config.unwrap_or_else(|| {
+                let mut config = ProviderConfig::init(time_source.clone(), sleep_impl.clone())
+                    .with_fs(self.fs.unwrap_or_default())
+                    .with_env(self.env.unwrap_or_default());
+                if let Some(http_client) = http_client.clone() {
+                    config = config.with_http_client(http_client);
+                }
+                config
+            });
+            let conf = match self.provider_retry_config {
+                Some(retry_config) => conf.with_retry_config(retry_config),
+                None => conf,
+            };
+            let conf = match self.provider_timeout_config {
+                Some(timeout_config) => conf.with_timeout_config(timeout_config),
+                None => conf,
+            };
+            let conf = match self.env_prefix {
+                Some(prefix) => {
+                    let env = conf.env().with_prefix(prefix);
+                    conf.with_env(env)
+                }
+                None => conf,
+            };
+            let conf = if self.offline {
+                conf.with_offline(true)
+            } else {
+                conf
+            }
+            .with_profile_config(self.profile_files_override, self.profile_name_override);
+            let conf = match self.sts_client_config {
+                Some(sts_client_config) => conf.with_sts_client_config(sts_client_config),
+                None => conf,
+            };
+
+            // Proxy settings from the profile only apply to the default HTTP client
+            #[cfg(feature = "default-https-client")]
+            let (conf, http_client) = if http_client_overridden {
+                (conf, http_client)
+            } else {
+                let behavior_version = self.behavior_version.unwrap_or(BehaviorVersion::latest());
+                match proxy_config::proxy_config_provider(&conf)
+                    .await
+                    .and_then(|proxy_config| {
+                        proxy_config::http_client_with_proxy(behavior_version, proxy_config)
+                    }) {
+                    Some(http_client) => (
+                        conf.with_http_client(http_client.clone()),
+                        Some(http_client),
+                    ),
+                    None => (conf, http_client),
+                }
+            };
 
             let use_fips = if let Some(use_fips) = self.use_fips {
                 Some(use_fips)
@@ -840,6 +1230,17 @@ mod loader {
             };
             let conf = conf.with_region(region.clone());
 
+            let defaults_mode = match self.defaults_mode {
+                Some(mode) => Some(mode),
+                None => defaults_mode::defaults_mode_provider(&conf).await,
+            };
+            let defaults_mode = match defaults_mode {
+                Some(DefaultsMode::Auto) => {
+                    Some(defaults_mode::resolve_auto_mode(&conf, region.as_ref()).await)
+                }
+                mode => mode,
+            };
+
             let retry_config = if let Some(retry_config) = self.retry_config {
                 retry_config
             } else {
@@ -874,6 +1275,12 @@ mod loader {
                     .await
                 };
 
+            let sigv4a_signing_region_set = if self.sigv4a_signing_region_set.is_some() {
+                self.sigv4a_signing_region_set
+            } else {
+                sigv4a_signing_region_set::sigv4a_signing_region_set_provider(&conf).await
+            };
+
             let base_config = timeout_config::default_provider()
                 .configure(&conf)
                 .timeout_config()
@@ -881,10 +1288,17 @@ mod loader {
             let mut timeout_config = self
                 .timeout_config
                 .unwrap_or_else(|| TimeoutConfig::builder().build());
+            // The defaults mode takes precedence over the SDK defaults
+            if let Some(mode_timeouts) = defaults_mode.and_then(defaults_mode::timeout_config) {
+                timeout_config.take_defaults_from(&mode_timeouts);
+            }
             timeout_config.take_defaults_from(&base_config);
 
             let credentials_provider = match self.credentials_provider {
                 TriStateOption::Set(provider) => Some(provider),
+                TriStateOption::NotSet if self.credential_source.is_some() => self
+                    .credential_source
+                    .map(|credential_source| credential_source.provider(&conf)),
                 TriStateOption::NotSet => {
                     let mut builder =
                         credentials::DefaultCredentialsChain::builder().configure(conf.clone());
@@ -957,8 +1371,11 @@ mod loader {
             };
 
             builder.set_endpoint_url(endpoint_url);
+            for (service_id, endpoint_url) in self.service_endpoint_urls {
+                builder.set_endpoint_url_for(service_id, Some(endpoint_url));
+            }
             builder.set_behavior_version(self.behavior_version);
-            builder.set_http_client(self.http_client);
+            builder.set_http_client(http_client);
             builder.set_app_name(app_name);
 
             let identity_cache = match self.identity_cache {
@@ -1013,21 +1430,30 @@ mod loader {
             builder.set_use_dual_stack(use_dual_stack);
             builder.set_disable_request_compression(disable_request_compression);
             builder.set_request_min_compression_size_bytes(request_min_compression_size_bytes);
+            builder.set_sigv4a_signing_region_set(sigv4a_signing_region_set);
             builder.set_stalled_stream_protection(self.stalled_stream_protection_config);
             builder.set_account_id_endpoint_mode(account_id_endpoint_mode);
+            builder.set_defaults_mode(defaults_mode);
             builder.set_auth_scheme_preference(auth_scheme_preference);
+            builder.set_disabled_auth_schemes(self.disabled_auth_schemes);
             builder.build()
         }
     }
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-util"))]
     impl ConfigLoader {
-        pub(crate) fn env(mut self, env: Env) -> Self {
+        /// Read environment variables from `env` instead of the process environment.
+        ///
+        /// This is intended for tests. See [`test_util`](crate::test_util) for more details.
+        pub fn env(mut self, env: Env) -> Self {
             self.env = Some(env);
             self
         }
 
-        pub(crate) fn fs(mut self, fs: Fs) -> Self {
+        /// Read config and credentials files from `fs` instead of the file system.
+        ///
+        /// This is intended for tests. See [`test_util`](crate::test_util) for more details.
+        pub fn fs(mut self, fs: Fs) -> Self {
             self.fs = Some(fs);
             self
         }
@@ -1044,13 +1470,86 @@ mod loader {
         use aws_smithy_async::rt::sleep::TokioSleep;
         use aws_smithy_http_client::test_util::{infallible_client_fn, NeverClient};
         use aws_smithy_runtime::test_util::capture_test_logs::capture_test_logs;
+        use aws_smithy_types::error::display::DisplayErrorContext;
+        use aws_smithy_types::timeout::TimeoutConfig;
         use aws_types::app_name::AppName;
+        use aws_types::defaults_mode::DefaultsMode;
         use aws_types::origin::Origin;
         use aws_types::os_shim_internal::{Env, Fs};
+        use aws_types::region::Region;
         use aws_types::sdk_config::{RequestChecksumCalculation, ResponseChecksumValidation};
         use std::sync::atomic::{AtomicUsize, Ordering};
         use std::sync::Arc;
 
+        #[tokio::test]
+        async fn offline_makes_no_network_calls() {
+            let requests = Arc::new(AtomicUsize::new(0));
+            let http_client = infallible_client_fn({
+                let requests = requests.clone();
+                move |_req| {
+                    requests.fetch_add(1, Ordering::Relaxed);
+                    http::Response::new("")
+                }
+            });
+            let fs = Fs::from_slice(&[(
+                "config",
+                "[default]\nrole_arn = arn:aws:iam::123456789012:role/test\nsource_profile = base\n\
+                 [profile base]\naws_access_key_id = akid\naws_secret_access_key = secret",
+            )]);
+            let conf = defaults(BehaviorVersion::latest())
+                .sleep_impl(InstantSleep)
+                .env(Env::from_slice(&[
+                    ("AWS_CONFIG_FILE", "config"),
+                    ("AWS_DEFAULTS_MODE", "auto"),
+                ]))
+                .fs(fs)
+                .http_client(http_client)
+                .region(Region::new("us-west-2"))
+                .offline(true)
+                .load()
+                .await;
+            let err = conf
+                .credentials_provider()
+                .unwrap()
+                .provide_credentials()
+                .await
+                .expect_err("assuming a role needs STS");
+            assert!(
+                format!("{}", DisplayErrorContext(&err)).contains("network access is disabled"),
+                "{}",
+                DisplayErrorContext(&err)
+            );
+            assert_eq!(0, requests.load(Ordering::Relaxed));
+        }
+
+        #[tokio::test]
+        async fn env_prefix_overrides_aws_variables() {
+            let env = Env::from_slice(&[
+                ("AWS_REGION", "us-west-2"),
+                ("MYAPP_AWS_REGION", "eu-west-1"),
+                ("AWS_ACCESS_KEY_ID", "akid"),
+                ("AWS_SECRET_ACCESS_KEY", "secret"),
+                ("MYAPP_AWS_ACCESS_KEY_ID", "myapp-akid"),
+                ("MYAPP_AWS_SECRET_ACCESS_KEY", "myapp-secret"),
+                ("AWS_MAX_ATTEMPTS", "5"),
+            ]);
+            let conf = base_conf()
+                .env(env)
+                .env_prefix("MYAPP_")
+                .fs(Fs::from_slice(&[]))
+                .load()
+                .await;
+            assert_eq!(Some(&Region::new("eu-west-1")), conf.region());
+            let credentials = conf
+                .credentials_provider()
+                .unwrap()
+                .provide_credentials()
+                .await
+                .unwrap();
+            assert_eq!("myapp-akid", credentials.access_key_id());
+            assert_eq!(5, conf.retry_config().unwrap().max_attempts());
+        }
+
         #[tokio::test]
         async fn provider_config_used() {
             let (_guard, logs_rx) = capture_test_logs();
@@ -1209,6 +1708,33 @@ mod loader {
             assert_eq!(None, conf.disable_request_compression());
         }
 
+        #[tokio::test]
+        async fn load_sigv4a_signing_region_set() {
+            let conf = base_conf()
+                .sigv4a_signing_region_set("us-west-2,us-east-1")
+                .load()
+                .await;
+            assert_eq!(
+                Some("us-west-2,us-east-1"),
+                conf.sigv4a_signing_region_set().map(|set| set.as_ref())
+            );
+
+            let conf = base_conf()
+                .env(Env::from_slice(&[(
+                    "AWS_SIGV4A_SIGNING_REGION_SET",
+                    "eu-*",
+                )]))
+                .load()
+                .await;
+            assert_eq!(
+                Some("eu-*"),
+                conf.sigv4a_signing_region_set().map(|set| set.as_ref())
+            );
+
+            let conf = base_conf().load().await;
+            assert_eq!(None, conf.sigv4a_signing_region_set());
+        }
+
         #[tokio::test]
         async fn load_request_min_compression_size_bytes() {
             let conf = base_conf()
@@ -1252,6 +1778,107 @@ mod loader {
             );
         }
 
+        #[tokio::test]
+        async fn defaults_mode() {
+            let conf = base_conf().load().await;
+            assert_eq!(None, conf.defaults_mode());
+
+            let conf = base_conf()
+                .defaults_mode(DefaultsMode::InRegion)
+                .load()
+                .await;
+            assert_eq!(Some(DefaultsMode::InRegion), conf.defaults_mode());
+            assert_eq!(
+                Some(std::time::Duration::from_millis(1100)),
+                conf.timeout_config().unwrap().connect_timeout()
+            );
+
+            let env = Env::from_slice(&[
+                ("AWS_DEFAULTS_MODE", "auto"),
+                ("AWS_EXECUTION_ENV", "AWS_Lambda_rust"),
+                ("AWS_REGION", "us-west-2"),
+            ]);
+            let conf = base_conf()
+                .env(env)
+                .region(Region::new("eu-west-1"))
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(std::time::Duration::from_secs(1))
+                        .build(),
+                )
+                .load()
+                .await;
+            assert_eq!(Some(DefaultsMode::CrossRegion), conf.defaults_mode());
+            assert_eq!(
+                Some(std::time::Duration::from_secs(1)),
+                conf.timeout_config().unwrap().connect_timeout()
+            );
+        }
+
+        #[tokio::test]
+        async fn load_from_json_document() {
+            let env = Env::from_slice(&[
+                ("AWS_REGION", "us-east-1"),
+                ("AWS_ACCESS_KEY_ID", "env-akid"),
+                ("AWS_SECRET_ACCESS_KEY", "env-secret"),
+            ]);
+            let conf = base_conf()
+                .env(env)
+                .use_fips(true)
+                .from_json(
+                    r#"{
+                        "region": "eu-west-1",
+                        "use_fips_endpoint": false,
+                        "credential_source": "Environment",
+                        "max_attempts": 7,
+                        "operation_timeout_ms": 5000
+                    }"#,
+                )
+                .expect("valid document")
+                .use_dual_stack(true)
+                .load()
+                .await;
+            assert_eq!(Some("eu-west-1"), conf.region().map(|r| r.as_ref()));
+            assert_eq!(Some(false), conf.use_fips());
+            assert_eq!(Some(true), conf.use_dual_stack());
+            assert_eq!(7, conf.retry_config().unwrap().max_attempts());
+            assert_eq!(
+                Some(std::time::Duration::from_secs(5)),
+                conf.timeout_config().unwrap().operation_timeout()
+            );
+            let credentials = conf
+                .credentials_provider()
+                .unwrap()
+                .provide_credentials()
+                .await
+                .expect("credentials from the environment");
+            assert_eq!("env-akid", credentials.access_key_id());
+        }
+
+        #[tokio::test]
+        async fn document_timeouts_are_merged_into_the_loader_timeouts() {
+            let conf = base_conf()
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(std::time::Duration::from_secs(1))
+                        .operation_timeout(std::time::Duration::from_secs(30))
+                        .build(),
+                )
+                .from_json(r#"{ "operation_timeout_ms": 5000 }"#)
+                .expect("valid document")
+                .load()
+                .await;
+            let timeout_config = conf.timeout_config().unwrap();
+            assert_eq!(
+                Some(std::time::Duration::from_secs(1)),
+                timeout_config.connect_timeout()
+            );
+            assert_eq!(
+                Some(std::time::Duration::from_secs(5)),
+                timeout_config.operation_timeout()
+            );
+        }
+
         #[cfg(feature = "default-https-client")]
         #[tokio::test]
         async fn disable_default_credentials() {
@@ -1303,6 +1930,47 @@ mod loader {
             assert!(num_requests > 0, "{}", num_requests);
         }
 
+        #[cfg(feature = "default-https-client")]
+        #[tokio::test]
+        async fn default_http_client_is_shared() {
+            use aws_smithy_runtime::client::defaults::default_http_client;
+            use aws_smithy_runtime_api::client::http::HttpClient;
+
+            let conf = defaults(BehaviorVersion::latest())
+                .sleep_impl(InstantSleep)
+                .empty_test_environment()
+                .fs(Fs::from_slice(&[]))
+                .region(Region::new("us-east-1"))
+                .no_credentials()
+                .load()
+                .await;
+            let http_client = conf.http_client().expect("a default HTTP client is set");
+            assert_eq!(
+                default_http_client(BehaviorVersion::latest())
+                    .unwrap()
+                    .connector_metadata(),
+                http_client.connector_metadata()
+            );
+            assert_eq!("hyper", http_client.connector_metadata().unwrap().name());
+        }
+
+        #[tokio::test]
+        async fn service_specific_endpoint_urls() {
+            let conf = base_conf()
+                .endpoint_url("http://localhost:1234")
+                .endpoint_url_for("S3", "http://localhost:9000")
+                .endpoint_url_for("dynamodb", "http://localhost:8000")
+                .load()
+                .await;
+            assert_eq!(Some("http://localhost:1234"), conf.endpoint_url());
+            assert_eq!(Some("http://localhost:9000"), conf.endpoint_url_for("s3"));
+            assert_eq!(
+                Some("http://localhost:8000"),
+                conf.endpoint_url_for("DynamoDB")
+            );
+            assert_eq!(None, conf.endpoint_url_for("sts"));
+        }
+
         #[tokio::test]
         async fn endpoint_urls_may_be_ignored_from_env() {
             let fs = Fs::from_slice(&[(
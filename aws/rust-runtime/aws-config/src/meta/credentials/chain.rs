@@ -9,7 +9,8 @@ use aws_credential_types::{
 };
 use aws_smithy_types::error::display::DisplayErrorContext;
 use std::borrow::Cow;
-use std::fmt::Debug;
+use std::error::Error;
+use std::fmt::{self, Debug};
 use tracing::Instrument;
 
 /// Credentials provider that checks a series of inner providers
@@ -111,6 +112,137 @@ impl CredentialsProviderChain {
             "no providers in chain provided credentials",
         ))
     }
+
+    /// Validates every provider in the chain and reports the outcome for each of them
+    ///
+    /// Unlike [`validate`](ProvideCredentials::validate), this doesn't stop at the first provider
+    /// that is able to provide credentials, so that misconfigured providers further down the chain
+    /// are reported as well.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() {
+    /// use aws_config::meta::credentials::CredentialsProviderChain;
+    /// use aws_config::environment::credentials::EnvironmentVariableCredentialsProvider;
+    /// use aws_config::profile::ProfileFileCredentialsProvider;
+    ///
+    /// let provider = CredentialsProviderChain::first_try("Environment", EnvironmentVariableCredentialsProvider::new())
+    ///     .or_else("Profile", ProfileFileCredentialsProvider::builder().build());
+    /// let report = provider.health_report().await;
+    /// if !report.is_healthy() {
+    ///     panic!("credentials are misconfigured:\n{report}");
+    /// }
+    /// # }
+    /// ```
+    pub async fn health_report(&self) -> ChainHealthReport {
+        let mut providers = Vec::with_capacity(self.providers.len());
+        for (name, provider) in &self.providers {
+            let span = tracing::debug_span!("credentials_provider_chain", provider = %name);
+            let result = provider.validate().instrument(span).await;
+            providers.push((name.clone(), result));
+        }
+        ChainHealthReport { providers }
+    }
+
+    async fn validate_chain(&self) -> Result<(), CredentialsError> {
+        for (name, provider) in &self.providers {
+            let span = tracing::debug_span!("credentials_provider_chain", provider = %name);
+            match provider.validate().instrument(span).await {
+                Ok(()) => return Ok(()),
+                Err(CredentialsError::CredentialsNotLoaded(_)) => {}
+                Err(err) => {
+                    let is_invalid_configuration =
+                        matches!(err, CredentialsError::InvalidConfiguration(_));
+                    let err = ProviderFailed {
+                        name: name.clone(),
+                        source: err,
+                    };
+                    return Err(if is_invalid_configuration {
+                        CredentialsError::invalid_configuration(err)
+                    } else {
+                        CredentialsError::provider_error(err)
+                    });
+                }
+            }
+        }
+        Err(CredentialsError::not_loaded(
+            "no providers in chain provided credentials",
+        ))
+    }
+}
+
+/// The outcome of validating each provider in a [`CredentialsProviderChain`]
+///
+/// This is returned by [`CredentialsProviderChain::health_report`]. Its `Display` implementation
+/// lists every provider along with the reason it failed, if any.
+#[derive(Debug)]
+pub struct ChainHealthReport {
+    providers: Vec<(Cow<'static, str>, Result<(), CredentialsError>)>,
+}
+
+impl ChainHealthReport {
+    /// Returns the name and validation result of every provider, in chain order
+    pub fn providers(&self) -> impl Iterator<Item = (&str, Result<(), &CredentialsError>)> {
+        self.providers
+            .iter()
+            .map(|(name, result)| (name.as_ref(), result.as_ref().copied()))
+    }
+
+    /// Returns the name of the provider the chain would load credentials from, if any
+    pub fn active_provider(&self) -> Option<&str> {
+        for (name, result) in self.providers() {
+            match result {
+                Ok(()) => return Some(name),
+                Err(CredentialsError::CredentialsNotLoaded(_)) => {}
+                Err(_) => return None,
+            }
+        }
+        None
+    }
+
+    /// Returns true if the chain is able to provide credentials
+    ///
+    /// A chain is healthy if the first provider that doesn't return
+    /// [`CredentialsError::CredentialsNotLoaded`] is able to provide credentials. Providers after
+    /// that one are never consulted by the chain, so their failures don't affect the result.
+    pub fn is_healthy(&self) -> bool {
+        self.active_provider().is_some()
+    }
+}
+
+impl fmt::Display for ChainHealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, result) in self.providers() {
+            match result {
+                Ok(()) => writeln!(f, "{name}: ok")?,
+                Err(err @ CredentialsError::CredentialsNotLoaded(_)) => {
+                    writeln!(f, "{name}: not loaded ({})", DisplayErrorContext(err))?
+                }
+                Err(err) => writeln!(f, "{name}: failed ({})", DisplayErrorContext(err))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A provider in the chain failed to validate
+#[derive(Debug)]
+struct ProviderFailed {
+    name: Cow<'static, str>,
+    source: CredentialsError,
+}
+
+impl fmt::Display for ProviderFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "credentials provider `{}` failed validation", self.name)
+    }
+}
+
+impl Error for ProviderFailed {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
 }
 
 impl ProvideCredentials for CredentialsProviderChain {
@@ -121,6 +253,13 @@ impl ProvideCredentials for CredentialsProviderChain {
         future::ProvideCredentials::new(self.credentials())
     }
 
+    fn validate<'a>(&'a self) -> future::Validate<'a>
+    where
+        Self: 'a,
+    {
+        future::Validate::new(self.validate_chain())
+    }
+
     fn fallback_on_interrupt(&self) -> Option<Credentials> {
         for (_, provider) in &self.providers {
             if let creds @ Some(_) = provider.fallback_on_interrupt() {
@@ -141,6 +280,7 @@ mod tests {
         Credentials,
     };
     use aws_smithy_async::future::timeout::Timeout;
+    use aws_smithy_types::error::display::DisplayErrorContext;
 
     use crate::meta::credentials::CredentialsProviderChain;
 
@@ -228,4 +368,69 @@ mod tests {
             },
         };
     }
+
+    #[tokio::test]
+    async fn health_report_names_misconfigured_provider() {
+        let chain = CredentialsProviderChain::first_try(
+            "provider1",
+            provide_credentials_fn(|| async {
+                Err(CredentialsError::not_loaded("not configured"))
+            }),
+        )
+        .or_else(
+            "provider2",
+            provide_credentials_fn(|| async {
+                Err(CredentialsError::invalid_configuration("missing role_arn"))
+            }),
+        )
+        .or_else("provider3", Credentials::for_tests());
+
+        let report = chain.health_report().await;
+        assert!(!report.is_healthy());
+        assert_eq!(None, report.active_provider());
+        let statuses: Vec<_> = report
+            .providers()
+            .map(|(name, result)| (name, result.is_ok()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("provider1", false),
+                ("provider2", false),
+                ("provider3", true)
+            ],
+            statuses
+        );
+        let report = report.to_string();
+        assert!(report.contains("provider1: not loaded"), "{report}");
+        assert!(report.contains("provider2: failed"), "{report}");
+        assert!(report.contains("missing role_arn"), "{report}");
+        assert!(report.contains("provider3: ok"), "{report}");
+
+        let err = chain
+            .validate()
+            .await
+            .expect_err("provider2 is misconfigured");
+        assert!(matches!(err, CredentialsError::InvalidConfiguration(_)));
+        assert!(
+            format!("{}", DisplayErrorContext(&err)).contains("`provider2`"),
+            "{}",
+            DisplayErrorContext(&err)
+        );
+    }
+
+    #[tokio::test]
+    async fn health_report_skips_providers_that_did_not_load() {
+        let chain = CredentialsProviderChain::first_try(
+            "provider1",
+            provide_credentials_fn(|| async {
+                Err(CredentialsError::not_loaded("not configured"))
+            }),
+        )
+        .or_else("provider2", Credentials::for_tests());
+
+        let report = chain.health_report().await;
+        assert!(report.is_healthy());
+        assert_eq!(Some("provider2"), report.active_provider());
+        chain.validate().await.expect("chain is healthy");
+    }
 }
@@ -7,11 +7,17 @@
 
 use aws_types::region::Region;
 use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
 use std::fmt::Debug;
 use tracing::Instrument;
 
 /// Load a region by selecting the first from a series of region providers.
 ///
+/// Each provider in the chain has a name, which is used to reorder or remove providers and to
+/// report which providers were consulted when no region could be resolved. Providers added
+/// without a name are named after their type.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -27,9 +33,42 @@ use tracing::Instrument;
 ///     .or_else(Region::new("us-east-2"));
 /// # }
 /// ```
+///
+/// Customize the default chain by inserting a provider between the profile and IMDS providers:
+/// ```no_run
+/// # fn example() {
+/// use aws_config::default_provider::region::DefaultRegionChain;
+/// use aws_types::region::Region;
+///
+/// let chain = DefaultRegionChain::builder()
+///     .build()
+///     .into_chain()
+///     .insert_before("Ec2InstanceMetadata", "Custom", std::env::var("CUSTOM_REGION").ok().map(Region::new))
+///     .or_fallback_region(Region::from_static("us-east-1"));
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct RegionProviderChain {
-    providers: Vec<Box<dyn ProvideRegion>>,
+    providers: Vec<NamedProvider>,
+}
+
+#[derive(Debug)]
+struct NamedProvider {
+    name: Cow<'static, str>,
+    provider: Box<dyn ProvideRegion>,
+}
+
+impl NamedProvider {
+    fn new(name: impl Into<Cow<'static, str>>, provider: impl ProvideRegion + 'static) -> Self {
+        Self {
+            name: name.into(),
+            provider: Box::new(provider),
+        }
+    }
+
+    fn unnamed<P: ProvideRegion + 'static>(provider: P) -> Self {
+        Self::new(std::any::type_name::<P>(), provider)
+    }
 }
 
 impl RegionProviderChain {
@@ -37,44 +76,173 @@ impl RegionProviderChain {
     ///
     /// The first provider to return a non-optional region will be selected
     pub async fn region(&self) -> Option<Region> {
-        for provider in &self.providers {
+        self.try_region().await.ok()
+    }
+
+    /// Load a region from the provider chain, returning an error listing the providers that were
+    /// consulted if none of them returned a region
+    pub async fn try_region(&self) -> Result<Region, RegionResolutionError> {
+        for NamedProvider { name, provider } in &self.providers {
             if let Some(region) = provider
                 .region()
-                .instrument(tracing::debug_span!("region_provider_chain", provider = ?provider))
+                .instrument(tracing::debug_span!("region_provider_chain", provider = %name))
                 .await
             {
-                return Some(region);
+                return Ok(region);
             }
         }
-        None
+        Err(RegionResolutionError {
+            providers: self.providers.iter().map(|p| p.name.clone()).collect(),
+        })
     }
 
     /// Create a default provider chain that starts by checking this provider.
     pub fn first_try(provider: impl ProvideRegion + 'static) -> Self {
         RegionProviderChain {
-            providers: vec![Box::new(provider)],
+            providers: vec![NamedProvider::unnamed(provider)],
+        }
+    }
+
+    /// Create a provider chain that starts by checking this provider, identified by `name`.
+    pub fn first_try_named(
+        name: impl Into<Cow<'static, str>>,
+        provider: impl ProvideRegion + 'static,
+    ) -> Self {
+        RegionProviderChain {
+            providers: vec![NamedProvider::new(name, provider)],
         }
     }
 
     /// Add a fallback provider to the region provider chain.
     pub fn or_else(mut self, fallback: impl ProvideRegion + 'static) -> Self {
-        self.providers.push(Box::new(fallback));
+        self.providers.push(NamedProvider::unnamed(fallback));
         self
     }
 
+    /// Add a fallback provider, identified by `name`, to the region provider chain.
+    pub fn or_else_named(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        fallback: impl ProvideRegion + 'static,
+    ) -> Self {
+        self.providers.push(NamedProvider::new(name, fallback));
+        self
+    }
+
+    /// Fall back to a fixed region if no other provider returns a region.
+    pub fn or_fallback_region(self, region: Region) -> Self {
+        self.or_else_named("FallbackRegion", region)
+    }
+
+    /// Insert a provider, identified by `name`, at position `index` in the chain.
+    ///
+    /// If `index` is greater than the number of providers, the provider is added to the end of the
+    /// chain.
+    pub fn insert(
+        mut self,
+        index: usize,
+        name: impl Into<Cow<'static, str>>,
+        provider: impl ProvideRegion + 'static,
+    ) -> Self {
+        let index = index.min(self.providers.len());
+        self.providers
+            .insert(index, NamedProvider::new(name, provider));
+        self
+    }
+
+    /// Insert a provider, identified by `name`, in front of the provider named `existing`.
+    ///
+    /// If there is no provider named `existing`, the provider is added to the end of the chain.
+    pub fn insert_before(
+        self,
+        existing: &str,
+        name: impl Into<Cow<'static, str>>,
+        provider: impl ProvideRegion + 'static,
+    ) -> Self {
+        let index = self.position(existing).unwrap_or(self.providers.len());
+        self.insert(index, name, provider)
+    }
+
+    /// Move the provider named `name` to position `index` in the chain.
+    ///
+    /// If `index` is greater than the index of the last provider, the provider is moved to the end
+    /// of the chain. If there is no provider named `name`, the chain is unchanged.
+    pub fn move_to(mut self, name: &str, index: usize) -> Self {
+        if let Some(current) = self.position(name) {
+            let provider = self.providers.remove(current);
+            let index = index.min(self.providers.len());
+            self.providers.insert(index, provider);
+        }
+        self
+    }
+
+    /// Remove the provider named `name` from the chain.
+    pub fn remove(mut self, name: &str) -> Self {
+        self.providers.retain(|provider| provider.name != name);
+        self
+    }
+
+    /// Returns the names of the providers in this chain, in the order they're checked.
+    pub fn provider_names(&self) -> impl Iterator<Item = &str> {
+        self.providers.iter().map(|provider| provider.name.as_ref())
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.providers
+            .iter()
+            .position(|provider| provider.name == name)
+    }
+
     /// Create a region provider chain that starts by checking the default provider.
     pub fn default_provider() -> Self {
-        Self::first_try(crate::default_provider::region::default_provider())
+        Self::first_try_named(
+            DEFAULT_PROVIDER_NAME,
+            crate::default_provider::region::default_provider(),
+        )
     }
 
     /// Fallback to the default provider
-    pub fn or_default_provider(mut self) -> Self {
-        self.providers
-            .push(Box::new(crate::default_provider::region::default_provider()));
-        self
+    pub fn or_default_provider(self) -> Self {
+        self.or_else_named(
+            DEFAULT_PROVIDER_NAME,
+            crate::default_provider::region::default_provider(),
+        )
     }
 }
 
+const DEFAULT_PROVIDER_NAME: &str = "DefaultRegionChain";
+
+/// Error returned when no provider in a [`RegionProviderChain`] returned a region
+#[derive(Debug)]
+pub struct RegionResolutionError {
+    providers: Vec<Cow<'static, str>>,
+}
+
+impl RegionResolutionError {
+    /// Returns the names of the providers that were consulted, in the order they were checked.
+    pub fn providers(&self) -> impl Iterator<Item = &str> {
+        self.providers.iter().map(|name| name.as_ref())
+    }
+}
+
+impl fmt::Display for RegionResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no region could be resolved. Providers consulted: ")?;
+        if self.providers.is_empty() {
+            return write!(f, "none");
+        }
+        for (i, name) in self.providers.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RegionResolutionError {}
+
 impl ProvideRegion for Option<Region> {
     fn region(&self) -> future::ProvideRegion<'_> {
         future::ProvideRegion::ready(self.clone())
@@ -182,4 +350,56 @@ mod test {
         let chain = RegionProviderChain::first_try(None).or_else(None);
         assert_eq!(chain.region().now_or_never().expect("ready"), None);
     }
+
+    #[test]
+    fn compose_chain() {
+        let chain = RegionProviderChain::first_try_named("A", None)
+            .or_else_named("B", Some(Region::new("us-east-1")))
+            .insert_before("B", "C", Region::new("us-west-2"))
+            .insert(0, "D", None);
+        assert_eq!(
+            vec!["D", "A", "C", "B"],
+            chain.provider_names().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            chain.region().now_or_never().expect("ready"),
+            Some(Region::new("us-west-2"))
+        );
+
+        let chain = chain.move_to("B", 0).remove("D").move_to("A", 10);
+        assert_eq!(
+            vec!["B", "C", "A"],
+            chain.provider_names().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            chain.region().now_or_never().expect("ready"),
+            Some(Region::new("us-east-1"))
+        );
+    }
+
+    #[test]
+    fn error_lists_consulted_providers() {
+        let chain = RegionProviderChain::first_try_named("Environment", None)
+            .or_else_named("Profile", None);
+        let err = chain
+            .try_region()
+            .now_or_never()
+            .expect("ready")
+            .expect_err("no region");
+        assert_eq!(
+            vec!["Environment", "Profile"],
+            err.providers().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "no region could be resolved. Providers consulted: Environment, Profile",
+            err.to_string()
+        );
+
+        let chain =
+            RegionProviderChain::first_try(None).or_fallback_region(Region::new("eu-west-1"));
+        assert_eq!(
+            chain.try_region().now_or_never().expect("ready").unwrap(),
+            Region::new("eu-west-1")
+        );
+    }
 }
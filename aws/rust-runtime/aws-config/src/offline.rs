@@ -0,0 +1,147 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Resolve configuration without network access
+//!
+//! When [`ConfigLoader::offline`](crate::ConfigLoader::offline) is enabled, providers that need
+//! the network (IMDS, ECS, web identity tokens, SSO, and STS) are skipped. Instead of making
+//! a request, they fail with an error whose source is [`NetworkAccessDisabled`].
+
+use aws_credential_types::provider::error::CredentialsError;
+use aws_credential_types::provider::{future, ProvideCredentials};
+use aws_smithy_runtime_api::client::connector_metadata::ConnectorMetadata;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// A step of configuration resolution was skipped because network access is disabled
+#[derive(Debug)]
+pub struct NetworkAccessDisabled {
+    skipped: Cow<'static, str>,
+}
+
+impl NetworkAccessDisabled {
+    pub(crate) fn new(skipped: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            skipped: skipped.into(),
+        }
+    }
+
+    /// Describes what was skipped, e.g. `loading credentials from Ec2InstanceMetadata`
+    pub fn skipped(&self) -> &str {
+        &self.skipped
+    }
+}
+
+impl fmt::Display for NetworkAccessDisabled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} was skipped because network access is disabled",
+            self.skipped
+        )
+    }
+}
+
+impl Error for NetworkAccessDisabled {}
+
+/// HTTP client that fails every request with [`NetworkAccessDisabled`]
+#[derive(Clone, Debug)]
+pub(crate) struct OfflineHttpClient;
+
+impl HttpConnector for OfflineHttpClient {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let err = NetworkAccessDisabled::new(format!("HTTP request to `{}`", request.uri()));
+        HttpConnectorFuture::ready(Err(ConnectorError::user(err.into())))
+    }
+}
+
+impl HttpClient for OfflineHttpClient {
+    fn http_connector(
+        &self,
+        _: &HttpConnectorSettings,
+        _: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        self.clone().into_shared()
+    }
+
+    fn connector_metadata(&self) -> Option<ConnectorMetadata> {
+        Some(ConnectorMetadata::new("offline", None))
+    }
+}
+
+/// Credentials provider that stands in for a provider that needs network access
+#[derive(Debug)]
+pub(crate) struct SkippedCredentialsProvider {
+    name: &'static str,
+}
+
+impl SkippedCredentialsProvider {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+impl ProvideCredentials for SkippedCredentialsProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::ready(Err(CredentialsError::not_loaded(
+            NetworkAccessDisabled::new(format!("loading credentials from {}", self.name)),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NetworkAccessDisabled, OfflineHttpClient, SkippedCredentialsProvider};
+    use aws_credential_types::provider::ProvideCredentials;
+    use aws_smithy_runtime_api::client::http::HttpConnector;
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_types::body::SdkBody;
+    use std::error::Error;
+
+    #[tokio::test]
+    async fn http_requests_fail() {
+        let request = HttpRequest::try_from(
+            http::Request::builder()
+                .uri("http://169.254.169.254/latest/api/token")
+                .body(SdkBody::empty())
+                .unwrap(),
+        )
+        .unwrap();
+        let err = OfflineHttpClient.call(request).await.expect_err("offline");
+        assert!(err.is_user());
+        let source = err
+            .source()
+            .and_then(|source| source.downcast_ref::<NetworkAccessDisabled>())
+            .expect("source is NetworkAccessDisabled");
+        assert_eq!(
+            "HTTP request to `http://169.254.169.254/latest/api/token`",
+            source.skipped()
+        );
+    }
+
+    #[tokio::test]
+    async fn skipped_provider_explains_what_was_skipped() {
+        let err = SkippedCredentialsProvider::new("EcsContainer")
+            .provide_credentials()
+            .await
+            .expect_err("skipped");
+        let source = err
+            .source()
+            .and_then(|source| source.downcast_ref::<NetworkAccessDisabled>())
+            .expect("source is NetworkAccessDisabled");
+        assert_eq!("loading credentials from EcsContainer", source.skipped());
+    }
+}
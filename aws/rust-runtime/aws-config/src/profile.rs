@@ -10,9 +10,11 @@
 
 pub mod parser;
 
+pub mod bearer_token;
 pub mod credentials;
 pub mod profile_file;
 pub mod region;
+pub mod sso_config;
 
 #[cfg(feature = "sso")]
 pub mod token;
@@ -20,6 +22,11 @@ pub mod token;
 #[doc(inline)]
 pub use token::ProfileFileTokenProvider;
 
+#[doc(inline)]
+pub use aws_runtime::env_config::edit::{
+    EditableSection, EnvConfigEditError as ProfileFileEditError,
+    EnvConfigFileEditor as ProfileFileEditor,
+};
 #[doc(inline)]
 pub use aws_runtime::env_config::error::EnvConfigFileLoadError as ProfileFileLoadError;
 #[doc(inline)]
@@ -29,6 +36,8 @@ pub use aws_runtime::env_config::property::Property;
 #[doc(inline)]
 pub use aws_runtime::env_config::section::{EnvConfigSections as ProfileSet, Profile};
 #[doc(inline)]
+pub use bearer_token::ProfileFileBearerTokenProvider;
+#[doc(inline)]
 pub use credentials::ProfileFileCredentialsProvider;
 #[doc(inline)]
 pub use parser::load;
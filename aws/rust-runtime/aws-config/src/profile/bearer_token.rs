@@ -0,0 +1,177 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Load a bearer token from an AWS profile
+
+use crate::provider_config::ProviderConfig;
+use aws_credential_types::provider::{error::TokenError, future, token::ProvideToken};
+use aws_credential_types::Token;
+use std::borrow::Cow;
+
+const AWS_BEARER_TOKEN_PREFIX: &str = "aws_bearer_token_";
+
+/// Load a bearer token from a key in the active profile
+///
+/// This is the profile counterpart of
+/// [`EnvironmentVariableTokenProvider`](crate::environment::EnvironmentVariableTokenProvider),
+/// for services that authenticate with API keys (e.g. Bedrock API keys). When the key is unset or
+/// blank, [`TokenError::TokenNotLoaded`] is returned so that this provider can be used as a link in
+/// a [`TokenProviderChain`](crate::meta::token::TokenProviderChain).
+///
+#[doc = include_str!("location_of_profile_files.md")]
+///
+/// # Examples
+///
+/// **Loads a token for Bedrock from the `aws_bearer_token_bedrock` key**
+/// ```ini
+/// [default]
+/// aws_bearer_token_bedrock = my-api-key
+/// ```
+///
+/// Load a token from the environment, falling back to the profile:
+/// ```no_run
+/// use aws_config::environment::EnvironmentVariableTokenProvider;
+/// use aws_config::meta::token::TokenProviderChain;
+/// use aws_config::profile::ProfileFileBearerTokenProvider;
+///
+/// let provider = TokenProviderChain::first_try(
+///     "Environment",
+///     EnvironmentVariableTokenProvider::for_service("bedrock"),
+/// )
+/// .or_else("Profile", ProfileFileBearerTokenProvider::for_service("bedrock"));
+/// ```
+#[derive(Debug)]
+pub struct ProfileFileBearerTokenProvider {
+    provider_config: ProviderConfig,
+    profile_key: Cow<'static, str>,
+}
+
+impl ProfileFileBearerTokenProvider {
+    /// Create a `ProfileFileBearerTokenProvider` that reads the token from `profile_key`
+    ///
+    /// To override the selected profile, set the `AWS_PROFILE` environment variable.
+    pub fn new(profile_key: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            provider_config: ProviderConfig::default(),
+            profile_key: profile_key.into(),
+        }
+    }
+
+    /// Create a `ProfileFileBearerTokenProvider` that reads the token from the service specific
+    /// `aws_bearer_token_<service>` profile key
+    ///
+    /// The service name is lower-cased and any spaces or dashes are replaced with underscores,
+    /// e.g. `Bedrock` resolves to `aws_bearer_token_bedrock`.
+    pub fn for_service(service_name: &str) -> Self {
+        Self::new(service_profile_key_name(service_name))
+    }
+
+    /// Override the configuration for this provider
+    ///
+    /// This controls the environment and file system the profile is loaded from.
+    pub fn configure(mut self, config: &ProviderConfig) -> Self {
+        self.provider_config = config.clone();
+        self
+    }
+
+    /// The profile key the token is loaded from
+    pub fn profile_key(&self) -> &str {
+        &self.profile_key
+    }
+
+    async fn token(&self) -> Result<Token, TokenError> {
+        let token = self
+            .provider_config
+            .profile()
+            .await
+            .and_then(|profile_set| profile_set.get(&self.profile_key))
+            .map(str::trim)
+            .filter(|token| !token.is_empty());
+        match token {
+            Some(token) => Ok(Token::new(token, None)),
+            None => Err(TokenError::not_loaded(format!(
+                "profile key `{}` not set",
+                self.profile_key
+            ))),
+        }
+    }
+}
+
+impl ProvideToken for ProfileFileBearerTokenProvider {
+    fn provide_token<'a>(&'a self) -> future::ProvideToken<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideToken::new(self.token())
+    }
+}
+
+pub(crate) fn service_profile_key_name(service_name: &str) -> String {
+    let suffix: String = service_name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect();
+    format!("{AWS_BEARER_TOKEN_PREFIX}{suffix}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{service_profile_key_name, ProfileFileBearerTokenProvider};
+    use crate::provider_config::ProviderConfig;
+    use aws_credential_types::provider::{error::TokenError, token::ProvideToken};
+    use aws_types::os_shim_internal::{Env, Fs};
+
+    fn make_provider(config_file: &str) -> ProfileFileBearerTokenProvider {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[("AWS_CONFIG_FILE", "config")]))
+            .with_fs(Fs::from_slice(&[("config", config_file)]));
+        ProfileFileBearerTokenProvider::for_service("bedrock").configure(&conf)
+    }
+
+    #[tokio::test]
+    async fn loads_token() {
+        let token = make_provider("[default]\naws_bearer_token_bedrock = some-token\n")
+            .provide_token()
+            .await
+            .expect("valid token");
+        assert_eq!("some-token", token.token());
+        assert_eq!(None, token.expiration());
+    }
+
+    #[tokio::test]
+    async fn missing_or_blank_token_is_not_loaded() {
+        for config_file in [
+            "[default]\nregion = us-east-1\n",
+            "[default]\naws_bearer_token_bedrock =\n",
+            "[default]\naws_bearer_token_runtime = other-token\n",
+        ] {
+            let err = make_provider(config_file)
+                .provide_token()
+                .await
+                .expect_err("no token");
+            assert!(matches!(err, TokenError::TokenNotLoaded(_)), "{err:?}");
+        }
+    }
+
+    #[test]
+    fn service_specific_key_names() {
+        assert_eq!(
+            "aws_bearer_token_bedrock",
+            service_profile_key_name("bedrock")
+        );
+        assert_eq!(
+            "aws_bearer_token_bedrock_runtime",
+            service_profile_key_name("Bedrock Runtime")
+        );
+        assert_eq!(
+            "aws_bearer_token_code_catalyst",
+            service_profile_key_name("code-catalyst")
+        );
+    }
+}
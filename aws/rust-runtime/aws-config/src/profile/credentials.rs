@@ -30,7 +30,13 @@ use crate::profile::ProfileFileLoadError;
 use crate::provider_config::ProviderConfig;
 use aws_credential_types::credential_feature::AwsCredentialFeature;
 use aws_credential_types::{
-    provider::{self, error::CredentialsError, future, ProvideCredentials},
+    provider::{
+        self,
+        error::CredentialsError,
+        future,
+        token::{ProvideToken, SharedTokenProvider},
+        ProvideCredentials,
+    },
     Credentials,
 };
 use aws_smithy_types::error::display::DisplayErrorContext;
@@ -143,6 +149,7 @@ pub struct ProfileFileCredentialsProvider {
 struct Config {
     factory: exec::named::NamedProviderFactory,
     provider_config: ProviderConfig,
+    mfa_token_provider: Option<SharedTokenProvider>,
 }
 
 impl ProfileFileCredentialsProvider {
@@ -291,6 +298,15 @@ pub enum ProfileFileError {
         message: Cow<'static, str>,
     },
 
+    /// Invalid `[sso-session]` section
+    #[non_exhaustive]
+    InvalidSsoSession {
+        /// The name of the SSO session
+        sso_session: String,
+        /// Error message
+        message: Cow<'static, str>,
+    },
+
     /// Profile is intended to be used in the token provider chain rather
     /// than in the credentials chain.
     #[non_exhaustive]
@@ -360,6 +376,12 @@ impl Display for ProfileFileError {
             ProfileFileError::InvalidSsoConfig { profile, message } => {
                 write!(f, "profile `{profile}` has invalid SSO config: {message}")
             }
+            ProfileFileError::InvalidSsoSession {
+                sso_session,
+                message,
+            } => {
+                write!(f, "sso-session `{sso_session}` is invalid: {message}")
+            }
             ProfileFileError::TokenProviderConfig { .. } => {
                 write!(
                     f,
@@ -380,6 +402,7 @@ pub struct Builder {
     #[allow(deprecated)]
     profile_files: Option<ProfileFiles>,
     custom_providers: HashMap<Cow<'static, str>, Arc<dyn ProvideCredentials>>,
+    mfa_token_provider: Option<SharedTokenProvider>,
 }
 
 impl Builder {
@@ -444,6 +467,30 @@ impl Builder {
         self
     }
 
+    /// Set the provider used to obtain MFA token codes for profiles that set `mfa_serial`
+    ///
+    /// The provider is invoked every time a role requiring MFA is assumed, so it may prompt
+    /// the user for a fresh code from their MFA device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use aws_config::profile::ProfileFileCredentialsProvider;
+    /// use aws_credential_types::token_fn::provide_token_fn;
+    /// use aws_credential_types::Token;
+    /// # async fn prompt_for_mfa_code() -> String { todo!() }
+    ///
+    /// let provider = ProfileFileCredentialsProvider::builder()
+    ///     .mfa_token_provider(provide_token_fn(|| async {
+    ///         Ok(Token::new(prompt_for_mfa_code().await, None))
+    ///     }))
+    ///     .build();
+    /// ```
+    pub fn mfa_token_provider(mut self, provider: impl ProvideToken + 'static) -> Self {
+        self.mfa_token_provider = Some(SharedTokenProvider::new(provider));
+        self
+    }
+
     /// Set the profile file that should be used by the [`ProfileFileCredentialsProvider`]
     #[allow(deprecated)]
     pub fn profile_files(mut self, profile_files: ProfileFiles) -> Self {
@@ -493,6 +540,7 @@ impl Builder {
             config: Arc::new(Config {
                 factory,
                 provider_config: conf,
+                mfa_token_provider: self.mfa_token_provider,
             }),
             inner_provider: ErrorTakingOnceCell::new(),
         }
@@ -509,7 +557,12 @@ async fn build_provider_chain(
         .map_err(|parse_err| ProfileFileError::InvalidProfile(parse_err.clone()))?;
     let repr = repr::resolve_chain(profile_set)?;
     tracing::info!(chain = ?repr, "constructed abstract provider from config file");
-    exec::ProviderChain::from_repr(&config.provider_config, repr, &config.factory)
+    exec::ProviderChain::from_repr(
+        &config.provider_config,
+        repr,
+        &config.factory,
+        config.mfa_token_provider.as_ref(),
+    )
 }
 
 #[derive(Debug)]
@@ -543,7 +596,7 @@ impl ChainProvider {
 
             // we want to create `SdkConfig` _after_ we have resolved the profile or else
             // we won't get things like `service_config()` set appropriately.
-            let sdk_config = config.provider_config.client_config();
+            let sdk_config = config.provider_config.sts_client_config();
             for provider in chain.chain().iter() {
                 let next_creds = provider
                     .credentials(creds, &sdk_config)
@@ -10,6 +10,7 @@ use crate::profile::credentials::ProfileFileError;
 use crate::provider_config::ProviderConfig;
 use crate::sts;
 use crate::web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider};
+use aws_credential_types::provider::token::{ProvideToken, SharedTokenProvider};
 use aws_credential_types::provider::{
     self, error::CredentialsError, ProvideCredentials, SharedCredentialsProvider,
 };
@@ -25,6 +26,8 @@ pub(super) struct AssumeRoleProvider {
     role_arn: String,
     external_id: Option<String>,
     session_name: Option<String>,
+    mfa_serial: Option<String>,
+    mfa_token_provider: Option<SharedTokenProvider>,
     time_source: SharedTimeSource,
 }
 
@@ -42,11 +45,30 @@ impl AssumeRoleProvider {
         let session_name = &self.session_name.as_ref().cloned().unwrap_or_else(|| {
             sts::util::default_session_name("assume-role-from-profile", self.time_source.now())
         });
-        let assume_role_output = client
+        let mut assume_role = client
             .assume_role()
             .role_arn(&self.role_arn)
             .set_external_id(self.external_id.clone())
-            .role_session_name(session_name)
+            .role_session_name(session_name);
+        match (&self.mfa_serial, &self.mfa_token_provider) {
+            (Some(mfa_serial), Some(mfa_token_provider)) => {
+                let token = mfa_token_provider
+                    .provide_token()
+                    .await
+                    .map_err(CredentialsError::provider_error)?;
+                assume_role = assume_role
+                    .serial_number(mfa_serial)
+                    .token_code(token.token());
+            }
+            // Profiles with `mfa_serial` were previously loaded without MFA, so keep doing that
+            // rather than failing for users that haven't configured an MFA token provider.
+            (Some(_), None) => tracing::warn!(
+                role_arn = %self.role_arn,
+                "the profile sets `mfa_serial` but no MFA token provider was configured, so the role is assumed without MFA"
+            ),
+            (None, _) => {}
+        }
+        let assume_role_output = assume_role
             .send()
             .await
             .map_err(CredentialsError::provider_error)?;
@@ -79,6 +101,7 @@ impl ProviderChain {
         provider_config: &ProviderConfig,
         repr: repr::ProfileChain<'_>,
         factory: &named::NamedProviderFactory,
+        mfa_token_provider: Option<&SharedTokenProvider>,
     ) -> Result<Self, ProfileFileError> {
         let base = match repr.base() {
             BaseProvider::NamedSource(name) => {
@@ -184,6 +207,8 @@ impl ProviderChain {
                     role_arn: role_arn.role_arn.into(),
                     external_id: role_arn.external_id.map(Into::into),
                     session_name: role_arn.session_name.map(Into::into),
+                    mfa_serial: role_arn.mfa_serial.map(Into::into),
+                    mfa_token_provider: mfa_token_provider.cloned(),
                     time_source: provider_config.time_source(),
                 }
             })
@@ -264,6 +289,7 @@ mod test {
                 chain: vec![],
             },
             &factory,
+            None,
         );
         let err = chain.expect_err("no source by that name");
         assert!(
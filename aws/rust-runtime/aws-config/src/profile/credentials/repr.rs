@@ -17,6 +17,7 @@ use crate::profile::{Profile, ProfileSet};
 use crate::sensitive_command::CommandWithSensitiveArgs;
 use aws_credential_types::attributes::AccountId;
 use aws_credential_types::Credentials;
+use aws_runtime::env_config::section::SsoSession;
 
 /// Chain of Profile Providers
 ///
@@ -114,6 +115,9 @@ pub(crate) struct RoleArn<'a> {
 
     /// session name parameter to pass to the assume role provider
     pub(crate) session_name: Option<&'a str>,
+
+    /// MFA device serial number to pass to the assume role provider
+    pub(crate) mfa_serial: Option<&'a str>,
 }
 
 /// Resolve a ProfileChain from a ProfileSet or return an error
@@ -215,17 +219,19 @@ mod role {
     pub(super) const ROLE_ARN: &str = "role_arn";
     pub(super) const EXTERNAL_ID: &str = "external_id";
     pub(super) const SESSION_NAME: &str = "role_session_name";
+    pub(super) const MFA_SERIAL: &str = "mfa_serial";
 
     pub(super) const CREDENTIAL_SOURCE: &str = "credential_source";
     pub(super) const SOURCE_PROFILE: &str = "source_profile";
 }
 
-mod sso {
-    pub(super) const ACCOUNT_ID: &str = "sso_account_id";
-    pub(super) const REGION: &str = "sso_region";
-    pub(super) const ROLE_NAME: &str = "sso_role_name";
-    pub(super) const START_URL: &str = "sso_start_url";
-    pub(super) const SESSION_NAME: &str = "sso_session";
+pub(crate) mod sso {
+    pub(crate) const ACCOUNT_ID: &str = "sso_account_id";
+    pub(crate) const REGION: &str = "sso_region";
+    pub(crate) const ROLE_NAME: &str = "sso_role_name";
+    pub(crate) const START_URL: &str = "sso_start_url";
+    pub(crate) const SESSION_NAME: &str = "sso_session";
+    pub(crate) const REGISTRATION_SCOPES: &str = "sso_registration_scopes";
 }
 
 mod web_identity_token {
@@ -299,10 +305,12 @@ fn role_arn_from_profile(profile: &Profile) -> Option<RoleArn<'_>> {
     let role_arn = profile.get(role::ROLE_ARN)?;
     let session_name = profile.get(role::SESSION_NAME);
     let external_id = profile.get(role::EXTERNAL_ID);
+    let mfa_serial = profile.get(role::MFA_SERIAL);
     Some(RoleArn {
         role_arn,
         external_id,
         session_name,
+        mfa_serial,
     })
 }
 
@@ -355,6 +363,9 @@ fn sso_from_profile<'a>(
         )
         .into(),
     };
+    if let Some(warning) = lint_sso_profile(profile) {
+        tracing::warn!("{warning}");
+    }
     if let Some(sso_session_name) = sso_session_name {
         if sso_start_url.is_some() {
             return Err(invalid_sso_config(sso::START_URL));
@@ -363,6 +374,10 @@ fn sso_from_profile<'a>(
             return Err(invalid_sso_config(sso::REGION));
         }
         if let Some(session) = profile_set.sso_session(sso_session_name) {
+            validate_required_sso_session_fields(sso_session_name, session)?;
+            if let Some(warning) = lint_sso_session(sso_session_name, session) {
+                tracing::warn!("{warning}");
+            }
             sso_start_url = session.get(sso::START_URL);
             sso_region = session.get(sso::REGION);
         } else {
@@ -395,6 +410,101 @@ fn sso_from_profile<'a>(
     }))
 }
 
+/// Validate the SSO configuration of `profile`, if it has any
+///
+/// Unlike loading credentials, this also fails for problems that are only logged as warnings when
+/// credentials are loaded.
+pub(crate) fn validate_sso_profile(
+    profile_set: &ProfileSet,
+    profile: &Profile,
+) -> Result<(), ProfileFileError> {
+    sso_from_profile(profile_set, profile)?;
+    match lint_sso_profile(profile) {
+        Some(warning) => Err(warning),
+        None => Ok(()),
+    }
+}
+
+/// Validate the settings of the `[sso-session <name>]` section
+///
+/// Unlike loading credentials, this also fails for problems that are only logged as warnings when
+/// credentials are loaded.
+pub(crate) fn validate_sso_session(
+    name: &str,
+    session: &SsoSession,
+) -> Result<(), ProfileFileError> {
+    validate_required_sso_session_fields(name, session)?;
+    match lint_sso_session(name, session) {
+        Some(warning) => Err(warning),
+        None => Ok(()),
+    }
+}
+
+fn invalid_sso_session(name: &str, message: String) -> ProfileFileError {
+    ProfileFileError::InvalidSsoSession {
+        sso_session: name.into(),
+        message: message.into(),
+    }
+}
+
+fn validate_required_sso_session_fields(
+    name: &str,
+    session: &SsoSession,
+) -> Result<(), ProfileFileError> {
+    for field in [sso::REGION, sso::START_URL] {
+        if session
+            .get(field)
+            .map(str::trim)
+            .unwrap_or_default()
+            .is_empty()
+        {
+            return Err(invalid_sso_session(
+                name,
+                format!(
+                    "`{field}` is required. Add `{field} = <value>` to the [sso-session {name}] section"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns a problem with the SSO profile that doesn't prevent it from being used
+///
+/// Registration scopes in a legacy SSO profile aren't used, so they're ignored.
+fn lint_sso_profile(profile: &Profile) -> Option<ProfileFileError> {
+    profile
+        .get(sso::REGISTRATION_SCOPES)
+        .map(|_| ProfileFileError::InvalidSsoConfig {
+            profile: profile.name().into(),
+            message: format!(
+                "`{}` can only be specified in an [sso-session] section, so it is ignored. Move it \
+                 to the sso-session referenced by `{}`",
+                sso::REGISTRATION_SCOPES,
+                sso::SESSION_NAME
+            )
+            .into(),
+        })
+}
+
+/// Returns a problem with the `[sso-session <name>]` section that doesn't prevent it from being used
+///
+/// Registration scopes aren't used to load credentials, so malformed scopes don't prevent it.
+fn lint_sso_session(name: &str, session: &SsoSession) -> Option<ProfileFileError> {
+    session
+        .get(sso::REGISTRATION_SCOPES)
+        .filter(|scopes| scopes.split(',').any(|scope| scope.trim().is_empty()))
+        .map(|scopes| {
+            invalid_sso_session(
+                name,
+                format!(
+                    "`{}` should be a comma-separated list of scopes without empty entries, but was `{scopes}`",
+                    sso::REGISTRATION_SCOPES
+                ),
+            )
+        })
+}
+
 fn web_identity_token_from_profile(
     profile: &Profile,
 ) -> Option<Result<BaseProvider<'_>, ProfileFileError>> {
@@ -594,6 +704,7 @@ mod tests {
                 role_arn: role.role_arn.into(),
                 external_id: role.external_id.map(ToString::to_string),
                 role_session_name: role.session_name.map(ToString::to_string),
+                mfa_serial: role.mfa_serial.map(ToString::to_string),
             })
         }
         output
@@ -611,6 +722,7 @@ mod tests {
             role_arn: String,
             external_id: Option<String>,
             role_session_name: Option<String>,
+            mfa_serial: Option<String>,
         },
         AccessKey {
             access_key_id: String,
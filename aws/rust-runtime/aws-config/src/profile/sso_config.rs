@@ -0,0 +1,267 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Inspect and validate the SSO configuration of a profile set
+//!
+//! This is useful for tools that list the SSO sessions and profiles a user can log in with, or
+//! that check a config file for mistakes before it is used.
+//!
+//! # Examples
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use aws_config::profile::sso_config::SsoConfig;
+//! use aws_types::os_shim_internal::{Env, Fs};
+//!
+//! let profile_set = aws_config::profile::load(
+//!     &Fs::real(),
+//!     &Env::real(),
+//!     &Default::default(),
+//!     None,
+//! )
+//! .await?;
+//! let sso_config = SsoConfig::new(&profile_set);
+//! for session in sso_config.sessions() {
+//!     println!("sso-session {}: {:?}", session.name(), session.sso_start_url());
+//! }
+//! for error in sso_config.validate() {
+//!     eprintln!("{error}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::profile::credentials::repr::{sso, validate_sso_profile, validate_sso_session};
+use crate::profile::credentials::ProfileFileError;
+use crate::profile::{Profile, ProfileSet};
+use aws_runtime::env_config::section::SsoSession;
+
+/// The SSO sessions and SSO profiles defined in a [`ProfileSet`]
+#[derive(Debug)]
+pub struct SsoConfig<'a> {
+    profile_set: &'a ProfileSet,
+}
+
+impl<'a> SsoConfig<'a> {
+    /// Create an `SsoConfig` for `profile_set`
+    pub fn new(profile_set: &'a ProfileSet) -> Self {
+        Self { profile_set }
+    }
+
+    /// Returns the `[sso-session]` sections, sorted by name
+    pub fn sessions(&self) -> Vec<SsoSessionConfig<'a>> {
+        let profile_set = self.profile_set;
+        let mut names: Vec<_> = profile_set.sso_sessions().collect();
+        names.sort_unstable();
+        names
+            .into_iter()
+            .filter_map(|name| {
+                profile_set
+                    .sso_session(name)
+                    .map(|session| SsoSessionConfig { name, session })
+            })
+            .collect()
+    }
+
+    /// Returns the profiles that use SSO, sorted by name
+    pub fn profiles(&self) -> Vec<SsoProfileConfig<'a>> {
+        let profile_set = self.profile_set;
+        let mut names: Vec<_> = profile_set.profiles().collect();
+        names.sort_unstable();
+        names
+            .into_iter()
+            .filter_map(|name| profile_set.get_profile(name))
+            .filter(|profile| {
+                [
+                    sso::SESSION_NAME,
+                    sso::START_URL,
+                    sso::ACCOUNT_ID,
+                    sso::ROLE_NAME,
+                ]
+                .iter()
+                .any(|key| profile.get(key).is_some())
+            })
+            .map(|profile| SsoProfileConfig { profile })
+            .collect()
+    }
+
+    /// Validate every SSO session and SSO profile, returning the problems that were found
+    ///
+    /// This includes problems that are only logged as warnings when credentials are loaded, like
+    /// `sso_registration_scopes` in a legacy SSO profile. Sessions that are referenced by a
+    /// profile are reported once, by the profile referencing them.
+    pub fn validate(&self) -> Vec<ProfileFileError> {
+        let profiles = self.profiles();
+        let mut errors: Vec<_> = self
+            .sessions()
+            .into_iter()
+            .filter(|session| {
+                !profiles
+                    .iter()
+                    .any(|profile| profile.sso_session() == Some(session.name()))
+            })
+            .filter_map(|session| validate_sso_session(session.name, session.session).err())
+            .collect();
+        errors.extend(
+            profiles.iter().filter_map(|profile| {
+                validate_sso_profile(self.profile_set, profile.profile).err()
+            }),
+        );
+        errors
+    }
+}
+
+/// An `[sso-session]` section
+#[derive(Debug, Clone, Copy)]
+pub struct SsoSessionConfig<'a> {
+    name: &'a str,
+    session: &'a SsoSession,
+}
+
+impl<'a> SsoSessionConfig<'a> {
+    /// The name of the SSO session
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The region of the IAM Identity Center instance
+    pub fn sso_region(&self) -> Option<&'a str> {
+        self.session.get(sso::REGION)
+    }
+
+    /// The start URL of the IAM Identity Center instance
+    pub fn sso_start_url(&self) -> Option<&'a str> {
+        self.session.get(sso::START_URL)
+    }
+
+    /// The scopes requested when registering a client for this session
+    pub fn sso_registration_scopes(&self) -> Vec<&'a str> {
+        self.session
+            .get(sso::REGISTRATION_SCOPES)
+            .map(|scopes| {
+                scopes
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|scope| !scope.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// A profile that uses SSO
+#[derive(Debug, Clone, Copy)]
+pub struct SsoProfileConfig<'a> {
+    profile: &'a Profile,
+}
+
+impl<'a> SsoProfileConfig<'a> {
+    /// The name of the profile
+    pub fn name(&self) -> &'a str {
+        self.profile.name()
+    }
+
+    /// The SSO session this profile references, if it uses session-based configuration
+    pub fn sso_session(&self) -> Option<&'a str> {
+        self.profile.get(sso::SESSION_NAME)
+    }
+
+    /// Returns true if this profile uses the legacy configuration, where the SSO start URL and
+    /// region are set in the profile rather than in an `[sso-session]` section
+    pub fn is_legacy(&self) -> bool {
+        self.sso_session().is_none()
+    }
+
+    /// The account ID credentials are resolved for
+    pub fn sso_account_id(&self) -> Option<&'a str> {
+        self.profile.get(sso::ACCOUNT_ID)
+    }
+
+    /// The role credentials are resolved for
+    pub fn sso_role_name(&self) -> Option<&'a str> {
+        self.profile.get(sso::ROLE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SsoConfig;
+    use aws_types::os_shim_internal::{Env, Fs};
+
+    const CONFIG: &str = r#"
+[default]
+region = us-east-1
+
+[profile dev]
+sso_session = dev
+sso_account_id = 012345678901
+sso_role_name = Developer
+
+[profile legacy]
+sso_start_url = https://legacy.awsapps.com/start
+sso_region = us-east-1
+sso_account_id = 012345678901
+
+[profile legacy-scopes]
+sso_start_url = https://legacy.awsapps.com/start
+sso_region = us-east-1
+sso_account_id = 012345678901
+sso_role_name = Developer
+sso_registration_scopes = sso:account:access
+
+[sso-session dev]
+sso_start_url = https://dev.awsapps.com/start
+sso_region = us-west-2
+sso_registration_scopes = sso:account:access, codecatalyst:read_write
+
+[sso-session unused]
+sso_start_url = https://unused.awsapps.com/start
+"#;
+
+    #[tokio::test]
+    async fn list_and_validate() {
+        let profile_set = crate::profile::load(
+            &Fs::from_slice(&[("config", CONFIG)]),
+            &Env::from_slice(&[("AWS_CONFIG_FILE", "config")]),
+            &Default::default(),
+            None,
+        )
+        .await
+        .expect("valid config");
+        let sso_config = SsoConfig::new(&profile_set);
+
+        let sessions = sso_config.sessions();
+        assert_eq!(
+            vec!["dev", "unused"],
+            sessions.iter().map(|s| s.name()).collect::<Vec<_>>()
+        );
+        assert_eq!(Some("us-west-2"), sessions[0].sso_region());
+        assert_eq!(
+            vec!["sso:account:access", "codecatalyst:read_write"],
+            sessions[0].sso_registration_scopes()
+        );
+
+        let profiles = sso_config.profiles();
+        assert_eq!(
+            vec!["dev", "legacy", "legacy-scopes"],
+            profiles.iter().map(|p| p.name()).collect::<Vec<_>>()
+        );
+        assert_eq!(Some("dev"), profiles[0].sso_session());
+        assert!(profiles[1].is_legacy());
+
+        let errors: Vec<_> = sso_config
+            .validate()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "sso-session `unused` is invalid: `sso_region` is required. Add `sso_region = <value>` to the [sso-session unused] section",
+                "profile `legacy` has invalid SSO config: if `sso_account_id` is set, then `sso_role_name` must also be set",
+                "profile `legacy-scopes` has invalid SSO config: `sso_registration_scopes` can only be specified in an [sso-session] section, so it is ignored. Move it to the sso-session referenced by `sso_session`",
+            ],
+            errors
+        );
+    }
+}
@@ -6,16 +6,19 @@
 //! Configuration Options for Credential Providers
 
 use crate::env_service_config::EnvServiceConfig;
+use crate::offline::OfflineHttpClient;
 use crate::profile;
 #[allow(deprecated)]
 use crate::profile::profile_file::ProfileFiles;
 use crate::profile::{ProfileFileLoadError, ProfileSet};
+use crate::sts::StsClientConfig;
 use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
 use aws_smithy_async::time::{SharedTimeSource, TimeSource};
 use aws_smithy_runtime_api::client::http::HttpClient;
 use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::error::display::DisplayErrorContext;
 use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
 use aws_types::os_shim_internal::{Env, Fs};
 use aws_types::region::Region;
 use aws_types::sdk_config::SharedHttpClient;
@@ -40,6 +43,7 @@ pub struct ProviderConfig {
     time_source: SharedTimeSource,
     http_client: Option<SharedHttpClient>,
     retry_config: Option<RetryConfig>,
+    timeout_config: Option<TimeoutConfig>,
     sleep_impl: Option<SharedAsyncSleep>,
     region: Option<Region>,
     use_fips: Option<bool>,
@@ -51,6 +55,8 @@ pub struct ProviderConfig {
     profile_files: ProfileFiles,
     /// An override to use when constructing a `ProfileSet`
     profile_name_override: Option<Cow<'static, str>>,
+    sts_client_config: Option<StsClientConfig>,
+    offline: bool,
 }
 
 impl Debug for ProviderConfig {
@@ -61,11 +67,14 @@ impl Debug for ProviderConfig {
             .field("time_source", &self.time_source)
             .field("http_client", &self.http_client)
             .field("retry_config", &self.retry_config)
+            .field("timeout_config", &self.timeout_config)
             .field("sleep_impl", &self.sleep_impl)
             .field("region", &self.region)
             .field("use_fips", &self.use_fips)
             .field("use_dual_stack", &self.use_dual_stack)
             .field("profile_name_override", &self.profile_name_override)
+            .field("sts_client_config", &self.sts_client_config)
+            .field("offline", &self.offline)
             .finish()
     }
 }
@@ -78,6 +87,7 @@ impl Default for ProviderConfig {
             time_source: SharedTimeSource::default(),
             http_client: None,
             retry_config: None,
+            timeout_config: None,
             sleep_impl: default_async_sleep(),
             region: None,
             use_fips: None,
@@ -86,6 +96,8 @@ impl Default for ProviderConfig {
             #[allow(deprecated)]
             profile_files: ProfileFiles::default(),
             profile_name_override: None,
+            sts_client_config: None,
+            offline: false,
         }
     }
 }
@@ -111,11 +123,14 @@ impl ProviderConfig {
             time_source: SharedTimeSource::new(StaticTimeSource::new(UNIX_EPOCH)),
             http_client: None,
             retry_config: None,
+            timeout_config: None,
             sleep_impl: None,
             region: None,
             use_fips: None,
             use_dual_stack: None,
             profile_name_override: None,
+            sts_client_config: None,
+            offline: false,
         }
     }
 }
@@ -154,6 +169,7 @@ impl ProviderConfig {
             time_source: SharedTimeSource::default(),
             http_client: None,
             retry_config: None,
+            timeout_config: None,
             sleep_impl: None,
             region: None,
             use_fips: None,
@@ -162,6 +178,8 @@ impl ProviderConfig {
             #[allow(deprecated)]
             profile_files: ProfileFiles::default(),
             profile_name_override: None,
+            sts_client_config: None,
+            offline: false,
         }
     }
 
@@ -179,11 +197,14 @@ impl ProviderConfig {
             time_source,
             http_client: None,
             retry_config: None,
+            timeout_config: None,
             sleep_impl,
             region: None,
             use_fips: None,
             use_dual_stack: None,
             profile_name_override: None,
+            sts_client_config: None,
+            offline: false,
         }
     }
 
@@ -228,11 +249,29 @@ impl ProviderConfig {
             .use_dual_stack(self.use_dual_stack().unwrap_or_default())
             .service_config(service_config)
             .behavior_version(crate::BehaviorVersion::latest());
-        builder.set_http_client(self.http_client.clone());
+        builder.set_timeout_config(self.timeout_config.clone());
+        builder.set_http_client(self.http_client());
         builder.set_sleep_impl(self.sleep_impl.clone());
         builder.build()
     }
 
+    /// Like [`ProviderConfig::client_config`], with any [`StsClientConfig`] overrides applied.
+    ///
+    /// Credentials providers that call STS should build their client from this config.
+    pub(crate) fn sts_client_config(&self) -> SdkConfig {
+        let conf = self.client_config();
+        match &self.sts_client_config {
+            // An HTTP client set on the STS client config must not bypass offline mode
+            Some(sts_client_config) if self.offline => {
+                let mut builder = sts_client_config.apply(conf).into_builder();
+                builder.set_http_client(self.http_client());
+                builder.build()
+            }
+            Some(sts_client_config) => sts_client_config.apply(conf),
+            None => conf,
+        }
+    }
+
     // When all crate features are disabled, these accessors are unused
 
     #[allow(dead_code)]
@@ -250,16 +289,32 @@ impl ProviderConfig {
         self.time_source.clone()
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn http_client(&self) -> Option<SharedHttpClient> {
+    /// The HTTP client used by providers configured with this configuration
+    ///
+    /// IMDS, ECS, SSO, SSO OIDC, and STS requests made while loading credentials and config are
+    /// all sent with this client. When network access is disabled with
+    /// [`with_offline`](Self::with_offline), this returns a client that fails every request.
+    pub fn http_client(&self) -> Option<SharedHttpClient> {
+        if self.offline {
+            return Some(OfflineHttpClient.into_shared());
+        }
         self.http_client.clone()
     }
 
+    pub(crate) fn offline(&self) -> bool {
+        self.offline
+    }
+
     #[allow(dead_code)]
     pub(crate) fn retry_config(&self) -> Option<RetryConfig> {
         self.retry_config.clone()
     }
 
+    #[allow(dead_code)]
+    pub(crate) fn timeout_config(&self) -> Option<TimeoutConfig> {
+        self.timeout_config.clone()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn sleep_impl(&self) -> Option<SharedAsyncSleep> {
         self.sleep_impl.clone()
@@ -391,6 +446,15 @@ impl ProviderConfig {
         }
     }
 
+    /// Disable network access for providers configured with this configuration
+    ///
+    /// When `offline` is true, providers that need the network are skipped and HTTP requests
+    /// fail with [`NetworkAccessDisabled`](crate::offline::NetworkAccessDisabled), even if an
+    /// HTTP client was set with [`with_http_client`](Self::with_http_client).
+    pub fn with_offline(self, offline: bool) -> Self {
+        ProviderConfig { offline, ..self }
+    }
+
     /// Override the sleep implementation for this configuration
     pub fn with_sleep_impl(self, sleep_impl: impl AsyncSleep + 'static) -> Self {
         ProviderConfig {
@@ -400,10 +464,35 @@ impl ProviderConfig {
     }
 
     /// Override the retry config for this configuration
+    ///
+    /// This applies to the requests providers make to IMDS, the ECS credentials endpoint, SSO,
+    /// SSO OIDC, and STS. Retry settings configured directly on a provider, e.g. with
+    /// [`imds::Client::builder().max_attempts(..)`](crate::imds::client::Builder::max_attempts),
+    /// take precedence.
     pub fn with_retry_config(self, retry_config: RetryConfig) -> Self {
         ProviderConfig {
             retry_config: Some(retry_config),
             ..self
         }
     }
+
+    /// Override the timeout config for this configuration
+    ///
+    /// Like [`with_retry_config`](Self::with_retry_config), this applies to the requests
+    /// providers make while loading credentials and config, and timeouts configured directly on a
+    /// provider take precedence. Timeouts that aren't set keep the provider's defaults.
+    pub fn with_timeout_config(self, timeout_config: TimeoutConfig) -> Self {
+        ProviderConfig {
+            timeout_config: Some(timeout_config),
+            ..self
+        }
+    }
+
+    /// Override the configuration of the STS client used by credentials providers that call STS
+    pub fn with_sts_client_config(self, sts_client_config: StsClientConfig) -> Self {
+        ProviderConfig {
+            sts_client_config: Some(sts_client_config),
+            ..self
+        }
+    }
 }
@@ -13,4 +13,4 @@ pub mod token;
 
 pub use token::SsoTokenProvider;
 
-mod cache;
+pub mod cache;
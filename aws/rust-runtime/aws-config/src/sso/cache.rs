@@ -3,11 +3,20 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+//! Storage for SSO tokens
+//!
+//! By default, SSO tokens are cached in JSON files in `~/.aws/sso/cache`, where they are shared
+//! with the AWS CLI and other SDKs. Implement [`SsoTokenCache`] to store them elsewhere, e.g. in
+//! memory, in the OS keyring, or in a remote secret store, and configure it with
+//! [`SsoTokenProvider::builder`](crate::sso::SsoTokenProvider::builder).
+
 use aws_runtime::fs_util::{home_dir, Os};
+use aws_smithy_async::future::BoxFuture;
 use aws_smithy_json::deserialize::token::skip_value;
 use aws_smithy_json::deserialize::Token;
 use aws_smithy_json::deserialize::{json_token_iter, EscapeError};
 use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_types::date_time::{DateTimeFormatError, Format};
 use aws_smithy_types::DateTime;
 use aws_types::os_shim_internal::{Env, Fs};
@@ -19,6 +28,97 @@ use std::path::PathBuf;
 use std::time::SystemTime;
 use zeroize::Zeroizing;
 
+/// Storage for cached SSO tokens
+///
+/// Tokens are stored as JSON documents in the format of the files in `~/.aws/sso/cache`. They
+/// contain secrets, so implementations should protect them accordingly.
+///
+/// The `identifier` is the SSO session name for token providers, and the SSO start URL for
+/// credentials providers that use the legacy SSO configuration.
+pub trait SsoTokenCache: fmt::Debug + Send + Sync {
+    /// Loads the token cached for `identifier`
+    ///
+    /// Returns an error if no token is cached for `identifier`.
+    fn load<'a>(&'a self, identifier: &'a str) -> BoxFuture<'a, Vec<u8>, BoxError>;
+
+    /// Stores `token` for `identifier`, replacing any previously cached token
+    fn store<'a>(&'a self, identifier: &'a str, token: &'a [u8]) -> BoxFuture<'a, (), BoxError>;
+}
+
+/// SSO token cache that stores tokens in JSON files
+///
+/// By default, tokens are stored in `~/.aws/sso/cache/<hash>.json`, where `<hash>` is the
+/// SHA-1 hash of the identifier.
+#[derive(Clone, Debug, Default)]
+pub struct FileTokenCache {
+    env: Env,
+    fs: Fs,
+    directory: Option<PathBuf>,
+}
+
+impl FileTokenCache {
+    /// Creates a cache that stores tokens in `~/.aws/sso/cache`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store tokens in `directory` instead of `~/.aws/sso/cache`
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    pub(crate) fn with_env_fs(env: Env, fs: Fs) -> Self {
+        Self {
+            env,
+            fs,
+            directory: None,
+        }
+    }
+
+    fn path(&self, identifier: &str) -> Result<PathBuf, CachedSsoTokenError> {
+        match &self.directory {
+            Some(directory) => Ok(directory.join(cached_token_file_name(identifier))),
+            None => {
+                let home =
+                    home_dir(&self.env, Os::real()).ok_or(CachedSsoTokenError::NoHomeDirectory)?;
+                Ok(cached_token_path(identifier, &home))
+            }
+        }
+    }
+}
+
+impl SsoTokenCache for FileTokenCache {
+    fn load<'a>(&'a self, identifier: &'a str) -> BoxFuture<'a, Vec<u8>, BoxError> {
+        Box::pin(async move {
+            let path = self.path(identifier)?;
+            let data = self.fs.read_to_end(&path).await.map_err(|source| {
+                CachedSsoTokenError::IoError {
+                    what: "read",
+                    path,
+                    source,
+                }
+            })?;
+            Ok(data)
+        })
+    }
+
+    fn store<'a>(&'a self, identifier: &'a str, token: &'a [u8]) -> BoxFuture<'a, (), BoxError> {
+        Box::pin(async move {
+            let path = self.path(identifier)?;
+            self.fs
+                .write(&path, token)
+                .await
+                .map_err(|source| CachedSsoTokenError::IoError {
+                    what: "write",
+                    path,
+                    source,
+                })?;
+            Ok(())
+        })
+    }
+}
+
 #[cfg_attr(test, derive(Eq, PartialEq))]
 #[derive(Clone)]
 pub(super) struct CachedSsoToken {
@@ -61,6 +161,7 @@ impl fmt::Debug for CachedSsoToken {
 
 #[derive(Debug)]
 pub(super) enum CachedSsoTokenError {
+    Cache(BoxError),
     FailedToFormatDateTime {
         source: Box<dyn StdError + Send + Sync>,
     },
@@ -82,6 +183,7 @@ pub(super) enum CachedSsoTokenError {
 impl fmt::Display for CachedSsoTokenError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::Cache(_) => write!(f, "the SSO token cache failed"),
             Self::FailedToFormatDateTime { .. } => write!(f, "failed to format date time"),
             Self::InvalidField { field, .. } => write!(
                 f,
@@ -101,6 +203,7 @@ impl fmt::Display for CachedSsoTokenError {
 impl StdError for CachedSsoTokenError {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
+            Self::Cache(source) => Some(source.as_ref()),
             Self::FailedToFormatDateTime { source } => Some(source.as_ref()),
             Self::InvalidField { source, .. } => Some(source.as_ref()),
             Self::IoError { source, .. } => Some(source),
@@ -112,6 +215,16 @@ impl StdError for CachedSsoTokenError {
     }
 }
 
+impl From<BoxError> for CachedSsoTokenError {
+    fn from(err: BoxError) -> Self {
+        // Errors of the file cache are returned as-is
+        match err.downcast::<CachedSsoTokenError>() {
+            Ok(err) => *err,
+            Err(err) => Self::Cache(err),
+        }
+    }
+}
+
 impl From<EscapeError> for CachedSsoTokenError {
     fn from(err: EscapeError) -> Self {
         Self::JsonError(err.into())
@@ -132,39 +245,36 @@ impl From<DateTimeFormatError> for CachedSsoTokenError {
     }
 }
 
+/// Determine the SSO cached token file name for a given identifier.
+fn cached_token_file_name(identifier: &str) -> String {
+    // hex::encode returns a lowercase string
+    let mut out = hex::encode(digest::digest(
+        &digest::SHA1_FOR_LEGACY_USE_ONLY,
+        identifier.as_bytes(),
+    ));
+    out.push_str(".json");
+    out
+}
+
 /// Determine the SSO cached token path for a given identifier.
 ///
 /// The `identifier` is the `sso_start_url` for credentials providers, and `sso_session_name` for token providers.
 fn cached_token_path(identifier: &str, home: &str) -> PathBuf {
-    // hex::encode returns a lowercase string
     let mut out = PathBuf::with_capacity(home.len() + "/.aws/sso/cache".len() + ".json".len() + 40);
     out.push(home);
     out.push(".aws/sso/cache");
-    out.push(hex::encode(digest::digest(
-        &digest::SHA1_FOR_LEGACY_USE_ONLY,
-        identifier.as_bytes(),
-    )));
-    out.set_extension("json");
+    out.push(cached_token_file_name(identifier));
     out
 }
 
-/// Load the token for `identifier` from `~/.aws/sso/cache/<hashofidentifier>.json`
+/// Load the token for `identifier` from the `cache`
 ///
 /// The `identifier` is the `sso_start_url` for credentials providers, and `sso_session_name` for token providers.
 pub(super) async fn load_cached_token(
-    env: &Env,
-    fs: &Fs,
+    cache: &dyn SsoTokenCache,
     identifier: &str,
 ) -> Result<CachedSsoToken, CachedSsoTokenError> {
-    let home = home_dir(env, Os::real()).ok_or(CachedSsoTokenError::NoHomeDirectory)?;
-    let path = cached_token_path(identifier, &home);
-    let data = Zeroizing::new(fs.read_to_end(&path).await.map_err(|source| {
-        CachedSsoTokenError::IoError {
-            what: "read",
-            path,
-            source,
-        }
-    })?);
+    let data = Zeroizing::new(cache.load(identifier).await?);
     parse_cached_token(&data)
 }
 
@@ -308,8 +418,7 @@ fn json_parse_loop<'a>(
 }
 
 pub(super) async fn save_cached_token(
-    env: &Env,
-    fs: &Fs,
+    cache: &dyn SsoTokenCache,
     identifier: &str,
     token: &CachedSsoToken,
 ) -> Result<(), CachedSsoTokenError> {
@@ -345,15 +454,7 @@ pub(super) async fn save_cached_token(
     }
     writer.finish();
 
-    let home = home_dir(env, Os::real()).ok_or(CachedSsoTokenError::NoHomeDirectory)?;
-    let path = cached_token_path(identifier, &home);
-    fs.write(&path, out.as_bytes())
-        .await
-        .map_err(|err| CachedSsoTokenError::IoError {
-            what: "write",
-            path,
-            source: err,
-        })?;
+    cache.store(identifier, out.as_bytes()).await?;
     Ok(())
 }
 
@@ -501,8 +602,10 @@ mod tests {
     #[tokio::test]
     async fn gracefully_handle_missing_files() {
         let err = load_cached_token(
-            &Env::from_slice(&[("HOME", "/home")]),
-            &Fs::from_slice(&[]),
+            &FileTokenCache::with_env_fs(
+                Env::from_slice(&[("HOME", "/home")]),
+                Fs::from_slice(&[]),
+            ),
             "asdf",
         )
         .await
@@ -555,9 +658,13 @@ mod tests {
 
         let env = Env::from_slice(&[("HOME", "/home/user")]);
         let fs = Fs::from_map(HashMap::<_, Vec<u8>>::new());
-        super::save_cached_token(&env, &fs, "test", &token)
-            .await
-            .expect("success");
+        super::save_cached_token(
+            &FileTokenCache::with_env_fs(env, fs.clone()),
+            "test",
+            &token,
+        )
+        .await
+        .expect("success");
 
         let contents = fs
             .read_to_end("/home/user/.aws/sso/cache/a94a8fe5ccb19ba61c4c0873d391e987982fbbd3.json")
@@ -588,11 +695,78 @@ mod tests {
         let env = Env::from_slice(&[("HOME", "/home/user")]);
         let fs = Fs::from_map(HashMap::<_, Vec<u8>>::new());
 
-        super::save_cached_token(&env, &fs, "test", &original)
+        let cache = FileTokenCache::with_env_fs(env, fs);
+        super::save_cached_token(&cache, "test", &original)
             .await
             .unwrap();
 
-        let roundtripped = load_cached_token(&env, &fs, "test").await.unwrap();
+        let roundtripped = load_cached_token(&cache, "test").await.unwrap();
         assert_eq!(original, roundtripped)
     }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[cfg_attr(windows, ignore)]
+    #[tokio::test]
+    async fn override_cache_directory() {
+        let fs = Fs::from_map(HashMap::<_, Vec<u8>>::new());
+        let cache = FileTokenCache::with_env_fs(Env::from_slice(&[]), fs.clone())
+            .directory("/var/cache/sso");
+        cache.store("test", b"token").await.unwrap();
+        assert_eq!(
+            b"token".to_vec(),
+            fs.read_to_end("/var/cache/sso/a94a8fe5ccb19ba61c4c0873d391e987982fbbd3.json")
+                .await
+                .unwrap()
+        );
+        assert_eq!(b"token".to_vec(), cache.load("test").await.unwrap());
+    }
+
+    #[derive(Debug, Default)]
+    struct InMemoryCache(std::sync::Mutex<HashMap<String, Vec<u8>>>);
+
+    impl SsoTokenCache for InMemoryCache {
+        fn load<'a>(&'a self, identifier: &'a str) -> BoxFuture<'a, Vec<u8>, BoxError> {
+            let token = self.0.lock().unwrap().get(identifier).cloned();
+            Box::pin(async move { token.ok_or_else(|| "no token".into()) })
+        }
+
+        fn store<'a>(
+            &'a self,
+            identifier: &'a str,
+            token: &'a [u8],
+        ) -> BoxFuture<'a, (), BoxError> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(identifier.to_string(), token.to_vec());
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_cache() {
+        let cache = InMemoryCache::default();
+        let err = load_cached_token(&cache, "test")
+            .await
+            .expect_err("no token cached");
+        assert!(
+            matches!(err, CachedSsoTokenError::Cache(_)),
+            "incorrect error: {err:?}"
+        );
+
+        let token = CachedSsoToken {
+            access_token: Zeroizing::new("access-token".into()),
+            client_id: None,
+            client_secret: None,
+            expires_at: SystemTime::UNIX_EPOCH + Duration::from_secs(50_000_000),
+            refresh_token: None,
+            region: None,
+            registration_expires_at: None,
+            start_url: None,
+        };
+        super::save_cached_token(&cache, "test", &token)
+            .await
+            .unwrap();
+        assert_eq!(token, load_cached_token(&cache, "test").await.unwrap());
+    }
 }
@@ -10,7 +10,7 @@
 //!
 //! This provider is included automatically when profiles are loaded.
 
-use super::cache::load_cached_token;
+use super::cache::{load_cached_token, FileTokenCache, SsoTokenCache};
 use crate::identity::IdentityCache;
 use crate::provider_config::ProviderConfig;
 use crate::sso::SsoTokenProvider;
@@ -21,9 +21,10 @@ use aws_sdk_sso::types::RoleCredentials;
 use aws_sdk_sso::Client as SsoClient;
 use aws_smithy_async::time::SharedTimeSource;
 use aws_smithy_types::DateTime;
-use aws_types::os_shim_internal::{Env, Fs};
 use aws_types::region::Region;
 use aws_types::SdkConfig;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// SSO Credentials Provider
 ///
@@ -33,10 +34,11 @@ use aws_types::SdkConfig;
 /// Two different values will be tried for `<hash>` in order:
 /// 1. The configured [`session_name`](Builder::session_name).
 /// 2. The configured [`start_url`](Builder::start_url).
+///
+/// Tokens can be stored elsewhere with [`Builder::token_cache`] or [`Builder::cache_directory`].
 #[derive(Debug)]
 pub struct SsoCredentialsProvider {
-    fs: Fs,
-    env: Env,
+    token_cache: Arc<dyn SsoTokenCache>,
     sso_provider_config: SsoProviderConfig,
     sdk_config: SdkConfig,
     token_provider: Option<SsoTokenProvider>,
@@ -53,9 +55,18 @@ impl SsoCredentialsProvider {
         provider_config: &ProviderConfig,
         sso_provider_config: SsoProviderConfig,
     ) -> Self {
-        let fs = provider_config.fs();
-        let env = provider_config.env();
+        let token_cache = Arc::new(FileTokenCache::with_env_fs(
+            provider_config.env(),
+            provider_config.fs(),
+        ));
+        Self::with_token_cache(provider_config, sso_provider_config, token_cache)
+    }
 
+    fn with_token_cache(
+        provider_config: &ProviderConfig,
+        sso_provider_config: SsoProviderConfig,
+        token_cache: Arc<dyn SsoTokenCache>,
+    ) -> Self {
         let token_provider = if let Some(session_name) = &sso_provider_config.session_name {
             Some(
                 SsoTokenProvider::builder()
@@ -63,15 +74,15 @@ impl SsoCredentialsProvider {
                     .start_url(&sso_provider_config.start_url)
                     .session_name(session_name)
                     .region(sso_provider_config.region.clone())
-                    .build_with(env.clone(), fs.clone()),
+                    .shared_token_cache(token_cache.clone())
+                    .build_with(provider_config.env(), provider_config.fs()),
             )
         } else {
             None
         };
 
         SsoCredentialsProvider {
-            fs,
-            env,
+            token_cache,
             sso_provider_config,
             sdk_config: provider_config.client_config(),
             token_provider,
@@ -84,8 +95,7 @@ impl SsoCredentialsProvider {
             &self.sso_provider_config,
             &self.sdk_config,
             self.token_provider.as_ref(),
-            &self.env,
-            &self.fs,
+            self.token_cache.as_ref(),
             self.time_source.clone(),
         )
         .await
@@ -116,6 +126,8 @@ pub struct Builder {
     role_name: Option<String>,
     start_url: Option<String>,
     session_name: Option<String>,
+    token_cache: Option<Arc<dyn SsoTokenCache>>,
+    cache_directory: Option<PathBuf>,
 }
 
 impl Builder {
@@ -206,6 +218,20 @@ impl Builder {
         self
     }
 
+    /// Load cached tokens from `token_cache` instead of `~/.aws/sso/cache`
+    ///
+    /// This takes precedence over [`cache_directory`](Self::cache_directory).
+    pub fn token_cache(mut self, token_cache: impl SsoTokenCache + 'static) -> Self {
+        self.token_cache = Some(Arc::new(token_cache));
+        self
+    }
+
+    /// Load cached tokens from `directory` instead of `~/.aws/sso/cache`
+    pub fn cache_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.cache_directory = Some(directory.into());
+        self
+    }
+
     /// Construct an SsoCredentialsProvider from the builder
     ///
     /// # Panics
@@ -223,7 +249,14 @@ impl Builder {
             start_url: self.start_url.expect("start_url must be set"),
             session_name: self.session_name,
         };
-        SsoCredentialsProvider::new(&provider_config, sso_config)
+        let token_cache = self.token_cache.unwrap_or_else(|| {
+            let cache = FileTokenCache::with_env_fs(provider_config.env(), provider_config.fs());
+            Arc::new(match self.cache_directory {
+                Some(directory) => cache.directory(directory),
+                None => cache,
+            })
+        });
+        SsoCredentialsProvider::with_token_cache(&provider_config, sso_config, token_cache)
     }
 }
 
@@ -240,8 +273,7 @@ async fn load_sso_credentials(
     sso_provider_config: &SsoProviderConfig,
     sdk_config: &SdkConfig,
     token_provider: Option<&SsoTokenProvider>,
-    env: &Env,
-    fs: &Fs,
+    token_cache: &dyn SsoTokenCache,
     time_source: SharedTimeSource,
 ) -> provider::Result {
     let token = if let Some(token_provider) = token_provider {
@@ -251,7 +283,7 @@ async fn load_sso_credentials(
             .map_err(CredentialsError::provider_error)?
     } else {
         // Backwards compatible token loading that uses `start_url` instead of `session_name`
-        load_cached_token(env, fs, &sso_provider_config.start_url)
+        load_cached_token(token_cache, &sso_provider_config.start_url)
             .await
             .map_err(CredentialsError::provider_error)?
     };
@@ -9,10 +9,15 @@
 //! see [AWS Builder ID for developers](https://docs.aws.amazon.com/toolkit-for-vscode/latest/userguide/builder-id.html).
 //!
 //! This provider is included automatically when profiles are loaded.
+//!
+//! When constructed directly, the provider can optionally obtain a new token by running the
+//! [OAuth 2.0 device authorization grant](https://datatracker.ietf.org/doc/html/rfc8628) when
+//! there is no cached token, and can keep the cached token refreshed in the background.
 
 use crate::identity::IdentityCache;
 use crate::sso::cache::{
-    load_cached_token, save_cached_token, CachedSsoToken, CachedSsoTokenError,
+    load_cached_token, save_cached_token, CachedSsoToken, CachedSsoTokenError, FileTokenCache,
+    SsoTokenCache,
 };
 use aws_credential_types::provider::token::ProvideToken;
 use aws_credential_types::provider::{
@@ -21,6 +26,7 @@ use aws_credential_types::provider::{
 use aws_sdk_ssooidc::error::DisplayErrorContext;
 use aws_sdk_ssooidc::operation::create_token::CreateTokenOutput;
 use aws_sdk_ssooidc::Client as SsoOidcClient;
+use aws_smithy_async::rt::sleep::AsyncSleep;
 use aws_smithy_async::time::SharedTimeSource;
 use aws_smithy_runtime::expiring_cache::ExpiringCache;
 use aws_smithy_runtime_api::client::identity::http::Token;
@@ -32,6 +38,7 @@ use aws_types::region::Region;
 use aws_types::SdkConfig;
 use std::error::Error as StdError;
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use zeroize::Zeroizing;
@@ -39,10 +46,17 @@ use zeroize::Zeroizing;
 const REFRESH_BUFFER_TIME: Duration = Duration::from_secs(5 * 60 /* 5 minutes */);
 const MIN_TIME_BETWEEN_REFRESH: Duration = Duration::from_secs(30);
 
+const DEVICE_CLIENT_NAME: &str = "aws-sdk-rust";
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const DEFAULT_DEVICE_SCOPE: &str = "sso:account:access";
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const SLOW_DOWN_INTERVAL_INCREASE: Duration = Duration::from_secs(5);
+
 /// SSO Token Provider
 ///
 /// This token provider will use cached SSO tokens stored in `~/.aws/sso/cache/<hash>.json`.
 /// `<hash>` is computed based on the configured [`session_name`](Builder::session_name).
+/// Tokens can be stored elsewhere with [`Builder::token_cache`] or [`Builder::cache_directory`].
 ///
 /// If possible, the cached token will be refreshed when it gets close to expiring.
 #[derive(Debug)]
@@ -53,13 +67,70 @@ pub struct SsoTokenProvider {
 
 #[derive(Debug)]
 struct Inner {
-    env: Env,
-    fs: Fs,
+    cache: Arc<dyn SsoTokenCache>,
     region: Region,
     session_name: String,
     start_url: String,
     sdk_config: SdkConfig,
     last_refresh_attempt: Mutex<Option<SystemTime>>,
+    device_authorization: Option<DeviceAuthorizationCallback>,
+}
+
+impl Inner {
+    fn oidc_client(&self) -> SsoOidcClient {
+        // TODO(enableNewSmithyRuntimeCleanup): Use `customize().config_override()` to set the region instead of creating a new client once middleware is removed
+        let config = self
+            .sdk_config
+            .to_builder()
+            .region(Some(self.region.clone()))
+            .identity_cache(IdentityCache::no_cache())
+            .build();
+        SsoOidcClient::new(&config)
+    }
+}
+
+/// Instructions for the user to approve a device authorization request
+///
+/// The user must visit [`verification_uri_complete`](Self::verification_uri_complete), or visit
+/// [`verification_uri`](Self::verification_uri) and enter the [`user_code`](Self::user_code),
+/// before the request [`expires_in`](Self::expires_in).
+#[derive(Clone, Debug)]
+pub struct DeviceAuthorization {
+    verification_uri: String,
+    verification_uri_complete: Option<String>,
+    user_code: String,
+    expires_in: Duration,
+}
+
+impl DeviceAuthorization {
+    /// The URL the user needs to visit to approve the request
+    pub fn verification_uri(&self) -> &str {
+        &self.verification_uri
+    }
+
+    /// The URL the user needs to visit to approve the request, with the user code pre-filled
+    pub fn verification_uri_complete(&self) -> Option<&str> {
+        self.verification_uri_complete.as_deref()
+    }
+
+    /// The code the user needs to enter at the verification URL
+    pub fn user_code(&self) -> &str {
+        &self.user_code
+    }
+
+    /// How long the user has to approve the request
+    pub fn expires_in(&self) -> Duration {
+        self.expires_in
+    }
+}
+
+#[derive(Clone)]
+struct DeviceAuthorizationCallback(Arc<dyn Fn(&DeviceAuthorization) + Send + Sync>);
+
+impl fmt::Debug for DeviceAuthorizationCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DeviceAuthorizationCallback")
+    }
 }
 
 impl SsoTokenProvider {
@@ -74,14 +145,7 @@ impl SsoTokenProvider {
         identifier: &str,
         now: SystemTime,
     ) -> Result<Option<CachedSsoToken>, SsoTokenProviderError> {
-        // TODO(enableNewSmithyRuntimeCleanup): Use `customize().config_override()` to set the region instead of creating a new client once middleware is removed
-        let config = inner
-            .sdk_config
-            .to_builder()
-            .region(Some(inner.region.clone()))
-            .identity_cache(IdentityCache::no_cache())
-            .build();
-        let client = SsoOidcClient::new(&config);
+        let client = inner.oidc_client();
         let resp = client
             .create_token()
             .grant_type("refresh_token")
@@ -131,7 +195,7 @@ impl SsoTokenProvider {
                     registration_expires_at: cached_token.registration_expires_at,
                     start_url: Some(inner.start_url.clone()),
                 };
-                save_cached_token(&inner.env, &inner.fs, identifier, &refreshed_token).await?;
+                save_cached_token(inner.cache.as_ref(), identifier, &refreshed_token).await?;
                 tracing::debug!("saved refreshed SSO token");
                 Ok(Some(refreshed_token))
             }
@@ -149,13 +213,143 @@ impl SsoTokenProvider {
         }
     }
 
+    /// Obtains a new token with the device authorization grant, and saves it to the cache.
+    async fn authorize_device(
+        inner: &Inner,
+        callback: &DeviceAuthorizationCallback,
+        time_source: &SharedTimeSource,
+    ) -> Result<CachedSsoToken, SsoTokenProviderError> {
+        let client = inner.oidc_client();
+        let registration = client
+            .register_client()
+            .client_name(DEVICE_CLIENT_NAME)
+            .client_type("public")
+            .scopes(DEFAULT_DEVICE_SCOPE)
+            .send()
+            .await
+            .map_err(SsoTokenProviderError::device_authorization_failed)?;
+        let (client_id, client_secret) = registration
+            .client_id
+            .zip(registration.client_secret)
+            .ok_or_else(|| {
+                SsoTokenProviderError::device_authorization_failed(
+                    "SSO OIDC RegisterClient responded without client credentials",
+                )
+            })?;
+        let registration_expires_at = u64::try_from(registration.client_secret_expires_at)
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .ok();
+
+        let authorization = client
+            .start_device_authorization()
+            .client_id(&client_id)
+            .client_secret(&client_secret)
+            .start_url(&inner.start_url)
+            .send()
+            .await
+            .map_err(SsoTokenProviderError::device_authorization_failed)?;
+        let (device_code, user_code, verification_uri) = match (
+            authorization.device_code,
+            authorization.user_code,
+            authorization.verification_uri,
+        ) {
+            (Some(device_code), Some(user_code), Some(verification_uri)) => {
+                (device_code, user_code, verification_uri)
+            }
+            _ => {
+                return Err(SsoTokenProviderError::device_authorization_failed(
+                    "SSO OIDC StartDeviceAuthorization responded without a device code, user code, or verification URI",
+                ))
+            }
+        };
+        let expires_in = Duration::from_secs(u64::try_from(authorization.expires_in).unwrap_or(0));
+        (callback.0)(&DeviceAuthorization {
+            verification_uri,
+            verification_uri_complete: authorization.verification_uri_complete,
+            user_code,
+            expires_in,
+        });
+
+        let sleep_impl = inner.sdk_config.sleep_impl().ok_or_else(|| {
+            SsoTokenProviderError::device_authorization_failed(
+                "an async sleep implementation is required to poll for the device authorization",
+            )
+        })?;
+        let mut interval = u64::try_from(authorization.interval)
+            .ok()
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_POLL_INTERVAL);
+        let deadline = time_source.now() + expires_in;
+        loop {
+            sleep_impl.sleep(interval).await;
+            let now = time_source.now();
+            let resp = client
+                .create_token()
+                .grant_type(DEVICE_CODE_GRANT_TYPE)
+                .client_id(&client_id)
+                .client_secret(&client_secret)
+                .device_code(&device_code)
+                .send()
+                .await;
+            match resp {
+                Ok(CreateTokenOutput {
+                    access_token: Some(access_token),
+                    refresh_token,
+                    expires_in,
+                    ..
+                }) => {
+                    let token = CachedSsoToken {
+                        access_token: Zeroizing::new(access_token),
+                        client_id: Some(client_id),
+                        client_secret: Some(Zeroizing::new(client_secret)),
+                        expires_at: now
+                            + Duration::from_secs(u64::try_from(expires_in).map_err(|_| {
+                                SsoTokenProviderError::BadExpirationTimeFromSsoOidc
+                            })?),
+                        refresh_token: refresh_token.map(Zeroizing::new),
+                        region: Some(inner.region.to_string()),
+                        registration_expires_at,
+                        start_url: Some(inner.start_url.clone()),
+                    };
+                    save_cached_token(inner.cache.as_ref(), &inner.session_name, &token).await?;
+                    tracing::debug!("saved SSO token from device authorization");
+                    return Ok(token);
+                }
+                Ok(_) => {
+                    return Err(SsoTokenProviderError::device_authorization_failed(
+                        "SSO OIDC CreateToken responded without an access token",
+                    ))
+                }
+                Err(err) => {
+                    let service_err = err.as_service_error();
+                    if service_err.is_some_and(|e| e.is_authorization_pending_exception()) {
+                        tracing::debug!("waiting for the device authorization to be approved");
+                    } else if service_err.is_some_and(|e| e.is_slow_down_exception()) {
+                        interval += SLOW_DOWN_INTERVAL_INCREASE;
+                    } else {
+                        return Err(SsoTokenProviderError::device_authorization_failed(err));
+                    }
+                }
+            }
+            if time_source.now() >= deadline {
+                return Err(SsoTokenProviderError::DeviceAuthorizationExpired);
+            }
+        }
+    }
+
     pub(super) fn resolve_token(
         &self,
         time_source: SharedTimeSource,
     ) -> impl std::future::Future<Output = Result<CachedSsoToken, TokenError>> + 'static {
-        let token_cache = self.token_cache.clone();
-        let inner = self.inner.clone();
+        Self::resolve(self.inner.clone(), self.token_cache.clone(), time_source)
+    }
 
+    fn resolve(
+        inner: Arc<Inner>,
+        token_cache: ExpiringCache<CachedSsoToken, SsoTokenProviderError>,
+        time_source: SharedTimeSource,
+    ) -> impl std::future::Future<Output = Result<CachedSsoToken, TokenError>> + 'static {
         async move {
             if let Some(token) = token_cache
                 .yield_or_clear_if_expired(time_source.now())
@@ -168,7 +362,21 @@ impl SsoTokenProvider {
                 .get_or_load(|| async move {
                     tracing::debug!("expiring cache asked for an updated SSO token");
                     let mut token =
-                        load_cached_token(&inner.env, &inner.fs, &inner.session_name).await?;
+                        match load_cached_token(inner.cache.as_ref(), &inner.session_name).await
+                        {
+                            Ok(token) => token,
+                            Err(err) => match &inner.device_authorization {
+                                Some(callback) => {
+                                    tracing::debug!(error = %DisplayErrorContext(&err), "failed to load cached SSO token; starting device authorization");
+                                    let token =
+                                        Self::authorize_device(&inner, callback, &time_source)
+                                            .await?;
+                                    let expires_at = token.expires_at;
+                                    return Ok((token, expires_at));
+                                }
+                                None => return Err(err.into()),
+                            },
+                        };
                     tracing::debug!("loaded cached SSO token");
 
                     let now = time_source.now();
@@ -201,6 +409,12 @@ impl SsoTokenProvider {
                     // Fail fast if the token has expired and we can't refresh it
                     if expired && !refreshable {
                         tracing::debug!("cached SSO token is expired and cannot be refreshed");
+                        if let Some(callback) = &inner.device_authorization {
+                            let token =
+                                Self::authorize_device(&inner, callback, &time_source).await?;
+                            let expires_at = token.expires_at;
+                            return Ok((token, expires_at));
+                        }
                         return Err(SsoTokenProviderError::ExpiredToken);
                     }
 
@@ -225,6 +439,45 @@ impl SsoTokenProvider {
             Ok(token)
         }
     }
+
+    /// Refreshes the token shortly before it expires for as long as the provider exists.
+    #[cfg(feature = "rt-tokio")]
+    fn spawn_background_refresh(&self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(
+                "SSO token background refresh requires a Tokio runtime; it will not be enabled"
+            );
+            return;
+        };
+        let (Some(time_source), Some(sleep_impl)) = (
+            self.inner.sdk_config.time_source(),
+            self.inner.sdk_config.sleep_impl(),
+        ) else {
+            tracing::warn!("SSO token background refresh requires a time source and sleep implementation; it will not be enabled");
+            return;
+        };
+        let inner = Arc::downgrade(&self.inner);
+        let token_cache = self.token_cache.clone();
+        handle.spawn(async move {
+            // Stop once the provider has been dropped
+            while let Some(strong_inner) = inner.upgrade() {
+                let result =
+                    Self::resolve(strong_inner, token_cache.clone(), time_source.clone()).await;
+                let next_refresh = match result {
+                    Ok(token) => (token.expires_at - REFRESH_BUFFER_TIME)
+                        .duration_since(time_source.now())
+                        .unwrap_or_default(),
+                    Err(err) => {
+                        tracing::debug!(error = %DisplayErrorContext(&err), "background SSO token refresh failed");
+                        Duration::ZERO
+                    }
+                };
+                sleep_impl
+                    .sleep(next_refresh.max(MIN_TIME_BETWEEN_REFRESH))
+                    .await;
+            }
+        });
+    }
 }
 
 impl ProvideToken for SsoTokenProvider {
@@ -270,6 +523,11 @@ pub struct Builder {
     region: Option<Region>,
     session_name: Option<String>,
     start_url: Option<String>,
+    device_authorization: Option<DeviceAuthorizationCallback>,
+    #[cfg(feature = "rt-tokio")]
+    background_refresh: bool,
+    token_cache: Option<Arc<dyn SsoTokenCache>>,
+    cache_directory: Option<PathBuf>,
 }
 
 impl Builder {
@@ -332,6 +590,55 @@ impl Builder {
         self
     }
 
+    /// Obtain a new token with the device authorization grant when there is no usable cached token.
+    ///
+    /// The `callback` is given the verification URL and user code that the user must use to
+    /// approve the request, e.g. by printing them or opening a browser. The provider then polls
+    /// until the request is approved, and saves the new token to the SSO token cache so that it
+    /// is refreshed like any other cached token.
+    ///
+    /// Without this, the provider fails when there is no cached token, or when the cached token
+    /// has expired and can't be refreshed.
+    pub fn device_authorization(
+        mut self,
+        callback: impl Fn(&DeviceAuthorization) + Send + Sync + 'static,
+    ) -> Self {
+        self.device_authorization = Some(DeviceAuthorizationCallback(Arc::new(callback)));
+        self
+    }
+
+    /// Keep the token refreshed in the background.
+    ///
+    /// By default, the token is only refreshed when it is requested. With background refresh
+    /// enabled, a task refreshes the token shortly before it expires for as long as the provider
+    /// exists, so that long-running applications always have a valid token.
+    ///
+    /// This requires the provider to be built within a Tokio runtime.
+    #[cfg(feature = "rt-tokio")]
+    pub fn background_refresh(mut self, background_refresh: bool) -> Self {
+        self.background_refresh = background_refresh;
+        self
+    }
+
+    /// Store cached tokens in `token_cache` instead of `~/.aws/sso/cache`.
+    ///
+    /// This takes precedence over [`cache_directory`](Self::cache_directory).
+    pub fn token_cache(mut self, token_cache: impl SsoTokenCache + 'static) -> Self {
+        self.token_cache = Some(Arc::new(token_cache));
+        self
+    }
+
+    /// Store cached tokens in `directory` instead of `~/.aws/sso/cache`.
+    pub fn cache_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.cache_directory = Some(directory.into());
+        self
+    }
+
+    pub(crate) fn shared_token_cache(mut self, token_cache: Arc<dyn SsoTokenCache>) -> Self {
+        self.token_cache = Some(token_cache);
+        self
+    }
+
     /// Builds the [`SsoTokenProvider`].
     ///
     /// # Panics
@@ -345,18 +652,32 @@ impl Builder {
     }
 
     pub(crate) fn build_with(self, env: Env, fs: Fs) -> SsoTokenProvider {
-        SsoTokenProvider {
+        #[cfg(feature = "rt-tokio")]
+        let background_refresh = self.background_refresh;
+        let cache = self.token_cache.unwrap_or_else(|| {
+            let cache = FileTokenCache::with_env_fs(env, fs);
+            Arc::new(match self.cache_directory {
+                Some(directory) => cache.directory(directory),
+                None => cache,
+            })
+        });
+        let provider = SsoTokenProvider {
             inner: Arc::new(Inner {
-                env,
-                fs,
+                cache,
                 region: self.region.expect("region is required"),
                 session_name: self.session_name.expect("session_name is required"),
                 start_url: self.start_url.expect("start_url is required"),
                 sdk_config: self.sdk_config.expect("sdk_config is required"),
                 last_refresh_attempt: Mutex::new(None),
+                device_authorization: self.device_authorization,
             }),
             token_cache: ExpiringCache::new(REFRESH_BUFFER_TIME),
+        };
+        #[cfg(feature = "rt-tokio")]
+        if background_refresh {
+            provider.spawn_background_refresh();
         }
+        provider
     }
 }
 
@@ -367,6 +688,18 @@ pub(super) enum SsoTokenProviderError {
         source: Box<dyn StdError + Send + Sync>,
     },
     ExpiredToken,
+    DeviceAuthorizationFailed {
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    DeviceAuthorizationExpired,
+}
+
+impl SsoTokenProviderError {
+    fn device_authorization_failed(source: impl Into<Box<dyn StdError + Send + Sync>>) -> Self {
+        Self::DeviceAuthorizationFailed {
+            source: source.into(),
+        }
+    }
 }
 
 impl fmt::Display for SsoTokenProviderError {
@@ -377,6 +710,12 @@ impl fmt::Display for SsoTokenProviderError {
             }
             Self::ExpiredToken => f.write_str("the SSO token has expired and cannot be refreshed"),
             Self::FailedToLoadToken { .. } => f.write_str("failed to load the cached SSO token"),
+            Self::DeviceAuthorizationFailed { .. } => {
+                f.write_str("failed to obtain an SSO token with device authorization")
+            }
+            Self::DeviceAuthorizationExpired => {
+                f.write_str("the device authorization request expired before it was approved")
+            }
         }
     }
 }
@@ -387,6 +726,8 @@ impl StdError for SsoTokenProviderError {
             Self::BadExpirationTimeFromSsoOidc => None,
             Self::ExpiredToken => None,
             Self::FailedToLoadToken { source } => Some(source.as_ref()),
+            Self::DeviceAuthorizationFailed { source } => Some(source.as_ref()),
+            Self::DeviceAuthorizationExpired => None,
         }
     }
 }
@@ -404,7 +745,7 @@ mod tests {
     use super::*;
     use aws_sdk_sso::config::{AsyncSleep, SharedAsyncSleep};
     use aws_smithy_async::rt::sleep::TokioSleep;
-    use aws_smithy_async::test_util::instant_time_and_sleep;
+    use aws_smithy_async::test_util::{controlled_time_and_sleep, instant_time_and_sleep};
     use aws_smithy_async::time::{StaticTimeSource, TimeSource};
     use aws_smithy_http_client::test_util::{capture_request, ReplayEvent, StaticReplayClient};
     use aws_smithy_runtime::{
@@ -537,6 +878,38 @@ mod tests {
         req_rx.expect_no_request();
     }
 
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[cfg_attr(windows, ignore)]
+    #[tokio::test]
+    async fn use_token_from_cache_directory() {
+        let fs = Fs::from_slice(&[(
+            "/var/cache/sso/a94a8fe5ccb19ba61c4c0873d391e987982fbbd3.json",
+            r#"
+            { "accessToken": "some-token",
+              "expiresAt": "1975-01-01T00:00:00Z" }
+            "#,
+        )]);
+        let time_source =
+            SharedTimeSource::new(StaticTimeSource::new(time("1974-12-25T00:00:00Z")));
+        let (conn, req_rx) = capture_request(None);
+        let config = SdkConfig::builder()
+            .http_client(conn)
+            .time_source(time_source.clone())
+            .behavior_version(crate::BehaviorVersion::latest())
+            .build();
+        let token_provider = SsoTokenProvider::builder()
+            .configure(&config)
+            .session_name("test")
+            .region(Region::new("us-west-2"))
+            .start_url("https://d-123.awsapps.com/start")
+            .cache_directory("/var/cache/sso")
+            .build_with(Env::from_slice(&[("HOME", "/home/user")]), fs);
+
+        let token = token_provider.resolve_token(time_source).await.unwrap();
+        assert_eq!("some-token", token.access_token.as_str());
+        req_rx.expect_no_request();
+    }
+
     // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
     #[cfg_attr(windows, ignore)]
     #[tokio::test]
@@ -628,9 +1001,12 @@ mod tests {
         let returned_token = harness
             .expect_sso_token("newtoken", "2021-12-25T21:30:00Z")
             .await;
-        let cached_token = load_cached_token(&harness.env, &harness.fs, "test")
-            .await
-            .unwrap();
+        let cached_token = load_cached_token(
+            &FileTokenCache::with_env_fs(harness.env.clone(), harness.fs.clone()),
+            "test",
+        )
+        .await
+        .unwrap();
         assert_eq!(returned_token, cached_token);
         assert_eq!(
             "newrefreshtoken",
@@ -706,9 +1082,12 @@ mod tests {
         let returned_token = harness
             .expect_sso_token("cachedtoken", "2021-12-25T13:00:00Z")
             .await;
-        let cached_token = load_cached_token(&harness.env, &harness.fs, "test")
-            .await
-            .unwrap();
+        let cached_token = load_cached_token(
+            &FileTokenCache::with_env_fs(harness.env.clone(), harness.fs.clone()),
+            "test",
+        )
+        .await
+        .unwrap();
         assert_eq!(returned_token, cached_token);
 
         let _ = req_rx.expect_request();
@@ -753,9 +1132,12 @@ mod tests {
         let returned_token = harness
             .expect_sso_token("newtoken", "2021-12-25T21:30:00Z")
             .await;
-        let cached_token = load_cached_token(&harness.env, &harness.fs, "test")
-            .await
-            .unwrap();
+        let cached_token = load_cached_token(
+            &FileTokenCache::with_env_fs(harness.env.clone(), harness.fs.clone()),
+            "test",
+        )
+        .await
+        .unwrap();
         assert_eq!(returned_token, cached_token);
         assert_eq!(
             "cachedrefreshtoken",
@@ -894,4 +1276,188 @@ mod tests {
             .expect_token("second_token", "2023-01-01T08:06:00Z")
             .await;
     }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[cfg_attr(windows, ignore)]
+    #[tokio::test]
+    async fn device_authorization_without_cached_token() {
+        let start_time = DateTime::from_str("2023-01-01T00:00:00Z", Format::DateTime).unwrap();
+        let (time_source, sleep_impl) = instant_time_and_sleep(start_time.try_into().unwrap());
+        let time_source = SharedTimeSource::new(time_source);
+        let fs = Fs::from_slice(&[]);
+        let env = Env::from_slice(&[("HOME", "/home/user")]);
+
+        let response = |status: u16, error_type: Option<&str>, body: &'static str| {
+            let mut response = http::Response::builder().status(status);
+            if let Some(error_type) = error_type {
+                response = response.header("x-amzn-errortype", error_type);
+            }
+            response.body(SdkBody::from(body)).unwrap()
+        };
+        let http_client = StaticReplayClient::new(vec![
+            ReplayEvent::new(
+                http::Request::new(SdkBody::from("")),
+                response(
+                    200,
+                    None,
+                    r#"{ "clientId": "clientid",
+                         "clientSecret": "YSBzZWNyZXQ=",
+                         "clientIdIssuedAt": 1672531200,
+                         "clientSecretExpiresAt": 1680307200 }"#,
+                ),
+            ),
+            ReplayEvent::new(
+                http::Request::new(SdkBody::from("")),
+                response(
+                    200,
+                    None,
+                    r#"{ "deviceCode": "devicecode",
+                         "userCode": "ABCD-EFGH",
+                         "verificationUri": "https://device.sso.us-west-2.amazonaws.com/",
+                         "verificationUriComplete": "https://device.sso.us-west-2.amazonaws.com/?user_code=ABCD-EFGH",
+                         "expiresIn": 600,
+                         "interval": 1 }"#,
+                ),
+            ),
+            ReplayEvent::new(
+                http::Request::new(SdkBody::from("")),
+                response(
+                    400,
+                    Some("AuthorizationPendingException"),
+                    r#"{ "error": "authorization_pending" }"#,
+                ),
+            ),
+            ReplayEvent::new(
+                http::Request::new(SdkBody::from("")),
+                response(
+                    200,
+                    None,
+                    r#"{ "tokenType": "Bearer",
+                         "accessToken": "devicetoken",
+                         "expiresIn": 28800,
+                         "refreshToken": "devicerefreshtoken" }"#,
+                ),
+            ),
+        ]);
+        let config = SdkConfig::builder()
+            .http_client(http_client.clone())
+            .time_source(time_source.clone())
+            .sleep_impl(SharedAsyncSleep::new(sleep_impl))
+            .retry_config(RetryConfig::disabled())
+            .behavior_version(crate::BehaviorVersion::latest())
+            .build();
+        let authorizations = Arc::new(Mutex::new(Vec::new()));
+        let token_provider = SsoTokenProvider::builder()
+            .configure(&config)
+            .session_name("test")
+            .region(Region::new("us-west-2"))
+            .start_url("https://d-123.awsapps.com/start")
+            .device_authorization({
+                let authorizations = authorizations.clone();
+                move |authorization| authorizations.lock().unwrap().push(authorization.clone())
+            })
+            .build_with(env.clone(), fs.clone());
+
+        let token = token_provider
+            .resolve_token(time_source.clone())
+            .await
+            .unwrap();
+        assert_eq!("devicetoken", token.access_token.as_str());
+        // Two poll intervals passed before the token was issued: the first poll was still pending
+        assert_eq!(time("2023-01-01T08:00:02Z"), token.expires_at);
+
+        let authorizations = authorizations.lock().unwrap();
+        assert_eq!(1, authorizations.len());
+        assert_eq!("ABCD-EFGH", authorizations[0].user_code());
+        assert_eq!(
+            Some("https://device.sso.us-west-2.amazonaws.com/?user_code=ABCD-EFGH"),
+            authorizations[0].verification_uri_complete()
+        );
+        assert_eq!(Duration::from_secs(600), authorizations[0].expires_in());
+
+        // The token is saved with everything needed to refresh it later
+        let cached_token = load_cached_token(&FileTokenCache::with_env_fs(env, fs), "test")
+            .await
+            .unwrap();
+        assert_eq!(token, cached_token);
+        assert!(cached_token.refreshable());
+        assert_eq!(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_680_307_200),
+            cached_token.registration_expires_at.unwrap()
+        );
+    }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[cfg_attr(windows, ignore)]
+    #[tokio::test]
+    async fn background_refresh() {
+        let (time_source, sleep_impl, mut gate) =
+            controlled_time_and_sleep(time("2023-01-01T00:00:00Z"));
+        let time_source = SharedTimeSource::new(time_source);
+        let env = Env::from_slice(&[("HOME", "/home/user")]);
+        let fs = Fs::from_slice(&[(
+            "/home/user/.aws/sso/cache/a94a8fe5ccb19ba61c4c0873d391e987982fbbd3.json",
+            r#"
+            { "startUrl": "https://d-123.awsapps.com/start",
+              "region": "us-west-2",
+              "accessToken": "first_token",
+              "expiresAt": "2023-01-01T00:10:00Z",
+              "clientId": "clientid",
+              "clientSecret": "YSBzZWNyZXQ=",
+              "registrationExpiresAt": "2023-01-02T12:00:00Z",
+              "refreshToken": "cachedrefreshtoken" }
+            "#,
+        )]);
+        let http_client = StaticReplayClient::new(vec![ReplayEvent::new(
+            http::Request::new(SdkBody::from("")),
+            http::Response::builder()
+                .status(200)
+                .body(SdkBody::from(
+                    r#"
+                    { "tokenType": "Bearer",
+                      "accessToken": "second_token",
+                      "expiresIn": 28800 }
+                    "#,
+                ))
+                .unwrap(),
+        )]);
+        let config = SdkConfig::builder()
+            .http_client(http_client.clone())
+            .time_source(time_source.clone())
+            .sleep_impl(SharedAsyncSleep::new(sleep_impl))
+            .retry_config(RetryConfig::disabled())
+            .behavior_version(crate::BehaviorVersion::latest())
+            .build();
+        let token_provider = SsoTokenProvider::builder()
+            .configure(&config)
+            .session_name("test")
+            .region(Region::new("us-west-2"))
+            .start_url("https://d-123.awsapps.com/start")
+            .background_refresh(true)
+            .build_with(env.clone(), fs.clone());
+
+        // The cached token is loaded, and the next refresh waits until it is about to expire
+        let sleep = gate.expect_sleep().await;
+        assert_eq!(Duration::from_secs(5 * 60), sleep.duration());
+        assert_eq!(0, http_client.actual_requests().count());
+        sleep.allow_progress();
+
+        // The token is refreshed without being requested
+        let sleep = gate.expect_sleep().await;
+        assert_eq!(
+            Duration::from_secs(8 * 60 * 60) - REFRESH_BUFFER_TIME,
+            sleep.duration()
+        );
+        assert_eq!(1, http_client.actual_requests().count());
+        let cached_token = load_cached_token(&FileTokenCache::with_env_fs(env, fs), "test")
+            .await
+            .unwrap();
+        assert_eq!("second_token", cached_token.access_token.as_str());
+        assert_eq!(time("2023-01-01T08:05:00Z"), cached_token.expires_at);
+
+        // The refresh stops once the provider is dropped
+        drop(token_provider);
+        sleep.allow_progress();
+        assert_eq!(None, gate.skip_sleep().await);
+    }
 }
@@ -6,6 +6,8 @@
 //! Credential provider augmentation through the AWS Security Token Service (STS).
 
 pub use assume_role::{AssumeRoleProvider, AssumeRoleProviderBuilder};
+pub use client_config::{StsClientConfig, StsEndpointMode};
 
 mod assume_role;
+mod client_config;
 pub(crate) mod util;
@@ -5,7 +5,9 @@
 
 //! Assume credentials for a role through the AWS Security Token Service (STS).
 
+use super::StsClientConfig;
 use aws_credential_types::credential_feature::AwsCredentialFeature;
+use aws_credential_types::provider::token::{ProvideToken, SharedTokenProvider};
 use aws_credential_types::provider::{
     self, error::CredentialsError, future, ProvideCredentials, SharedCredentialsProvider,
 };
@@ -75,6 +77,7 @@ pub struct AssumeRoleProvider {
 #[derive(Debug)]
 struct Inner {
     fluent_builder: AssumeRoleFluentBuilder,
+    mfa_token_provider: Option<SharedTokenProvider>,
 }
 
 impl AssumeRoleProvider {
@@ -104,6 +107,9 @@ pub struct AssumeRoleProviderBuilder {
     region_override: Option<Region>,
     sdk_config: Option<SdkConfig>,
     tags: Option<Vec<Tag>>,
+    serial_number: Option<String>,
+    mfa_token_provider: Option<SharedTokenProvider>,
+    sts_client_config: Option<StsClientConfig>,
 }
 
 impl AssumeRoleProviderBuilder {
@@ -125,6 +131,9 @@ impl AssumeRoleProviderBuilder {
             sdk_config: None,
             region_override: None,
             tags: None,
+            serial_number: None,
+            mfa_token_provider: None,
+            sts_client_config: None,
         }
     }
 
@@ -225,6 +234,50 @@ impl AssumeRoleProviderBuilder {
         self
     }
 
+    /// Set the identification number of the MFA device associated with the user making the call.
+    ///
+    /// This is either the serial number of a hardware device (such as `GAHT12345678`) or the
+    /// Amazon Resource Name (ARN) of a virtual device (such as `arn:aws:iam::123456789012:mfa/user`).
+    /// When set, an [`mfa_token_provider`](Self::mfa_token_provider) must also be configured.
+    pub fn serial_number(mut self, serial_number: impl Into<String>) -> Self {
+        self.serial_number = Some(serial_number.into());
+        self
+    }
+
+    /// Set the provider used to obtain a fresh MFA token code on every call to STS.
+    ///
+    /// Codes from an MFA device are typically only valid for a short window of time, so the
+    /// provider is invoked each time the role is assumed rather than once at construction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use aws_config::sts::AssumeRoleProvider;
+    /// use aws_credential_types::token_fn::provide_token_fn;
+    /// use aws_credential_types::Token;
+    /// # async fn read_code_from_user() -> String { todo!() }
+    /// # async fn docs() {
+    /// let provider = AssumeRoleProvider::builder("arn:aws:iam::123456789012:role/demo")
+    ///   .serial_number("arn:aws:iam::123456789012:mfa/user")
+    ///   .mfa_token_provider(provide_token_fn(|| async {
+    ///       Ok(Token::new(read_code_from_user().await, None))
+    ///   }))
+    ///   .build().await;
+    /// # }
+    /// ```
+    pub fn mfa_token_provider(mut self, provider: impl ProvideToken + 'static) -> Self {
+        self.mfa_token_provider = Some(SharedTokenProvider::new(provider));
+        self
+    }
+
+    /// Override the configuration of the STS client used to assume the role.
+    ///
+    /// Settings in `sts_client_config` take precedence over both the configuration set via
+    /// [`configure`](Self::configure) and the region set via [`region`](Self::region).
+    pub fn sts_client_config(mut self, sts_client_config: StsClientConfig) -> Self {
+        self.sts_client_config = Some(sts_client_config);
+        self
+    }
+
     /// Sets the configuration used for this provider
     ///
     /// This enables overriding the connection used to communicate with STS in addition to other internal
@@ -267,6 +320,10 @@ impl AssumeRoleProviderBuilder {
             conf = conf.into_builder().region(region).build()
         }
 
+        if let Some(sts_client_config) = &self.sts_client_config {
+            conf = sts_client_config.apply(conf);
+        }
+
         let config = aws_sdk_sts::config::Builder::from(&conf);
 
         let time_source = conf.time_source().expect("A time source must be provided.");
@@ -284,10 +341,14 @@ impl AssumeRoleProviderBuilder {
             .set_policy(self.policy)
             .set_policy_arns(self.policy_arns)
             .set_duration_seconds(self.session_length.map(|dur| dur.as_secs() as i32))
-            .set_tags(self.tags);
+            .set_tags(self.tags)
+            .set_serial_number(self.serial_number);
 
         AssumeRoleProvider {
-            inner: Inner { fluent_builder },
+            inner: Inner {
+                fluent_builder,
+                mfa_token_provider: self.mfa_token_provider,
+            },
         }
     }
 
@@ -313,7 +374,21 @@ impl Inner {
     async fn credentials(&self) -> provider::Result {
         tracing::debug!("retrieving assumed credentials");
 
-        let assumed = self.fluent_builder.clone().send().in_current_span().await;
+        let mut fluent_builder = self.fluent_builder.clone();
+        if fluent_builder.get_serial_number().is_some() {
+            let Some(mfa_token_provider) = &self.mfa_token_provider else {
+                return Err(CredentialsError::invalid_configuration(
+                    "an MFA serial number was set but no MFA token provider was configured",
+                ));
+            };
+            let token = mfa_token_provider
+                .provide_token()
+                .await
+                .map_err(CredentialsError::provider_error)?;
+            fluent_builder = fluent_builder.token_code(token.token());
+        }
+
+        let assumed = fluent_builder.send().in_current_span().await;
         let assumed = match assumed {
             Ok(assumed) => {
                 tracing::debug!(
@@ -368,11 +443,13 @@ impl ProvideCredentials for AssumeRoleProvider {
 
 #[cfg(test)]
 mod test {
-    use crate::sts::AssumeRoleProvider;
+    use crate::sts::{AssumeRoleProvider, StsClientConfig, StsEndpointMode};
     use aws_credential_types::credential_feature::AwsCredentialFeature;
     use aws_credential_types::credential_fn::provide_credentials_fn;
+    use aws_credential_types::provider::error::CredentialsError;
     use aws_credential_types::provider::{ProvideCredentials, SharedCredentialsProvider};
-    use aws_credential_types::Credentials;
+    use aws_credential_types::token_fn::provide_token_fn;
+    use aws_credential_types::{Credentials, Token};
     use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
     use aws_smithy_async::test_util::instant_time_and_sleep;
     use aws_smithy_async::time::StaticTimeSource;
@@ -384,6 +461,8 @@ mod test {
     use aws_types::region::Region;
     use aws_types::SdkConfig;
     use http::header::AUTHORIZATION;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
     use std::time::{Duration, UNIX_EPOCH};
 
     #[tokio::test]
@@ -413,6 +492,31 @@ mod test {
         assert_eq!(req.uri(), "https://sts.us-east-1.amazonaws.com/");
     }
 
+    #[tokio::test]
+    async fn sts_client_config_overrides_endpoint() {
+        let (http_client, request) = capture_request(None);
+        let sdk_config = SdkConfig::builder()
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .time_source(StaticTimeSource::from_secs(1234567890))
+            .http_client(http_client)
+            .region(Region::from_static("us-west-2"))
+            .behavior_version(crate::BehaviorVersion::latest())
+            .build();
+        let provider = AssumeRoleProvider::builder("myrole")
+            .configure(&sdk_config)
+            .sts_client_config(StsClientConfig::new().endpoint_mode(StsEndpointMode::Global))
+            .build_from_provider(Credentials::for_tests())
+            .await;
+        let _ = provider.provide_credentials().await;
+        let req = request.expect_request();
+        assert_eq!(req.uri(), "https://sts.amazonaws.com/");
+        let auth_header = req.headers().get(AUTHORIZATION).unwrap().to_string();
+        assert!(
+            auth_header.contains("/us-west-2/sts/aws4_request"),
+            "{auth_header}"
+        );
+    }
+
     #[tokio::test]
     async fn loads_region_from_sdk_config() {
         let (http_client, request) = capture_request(None);
@@ -591,4 +695,81 @@ mod test {
             creds.get_property::<Vec<AwsCredentialFeature>>().unwrap()
         )
     }
+
+    #[tokio::test]
+    async fn mfa_token_provider_invoked_on_each_call() {
+        let http_client = create_test_http_client();
+        let (time_source, sleep) =
+            instant_time_and_sleep(UNIX_EPOCH + Duration::from_secs(1234567890 - 120));
+        let sdk_config = SdkConfig::builder()
+            .sleep_impl(SharedAsyncSleep::new(sleep))
+            .time_source(time_source)
+            .http_client(http_client.clone())
+            .behavior_version(crate::BehaviorVersion::latest())
+            .build();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = AssumeRoleProvider::builder("myrole")
+            .configure(&sdk_config)
+            .region(Region::new("us-east-1"))
+            .serial_number("GAHT12345678")
+            .mfa_token_provider(provide_token_fn({
+                let calls = calls.clone();
+                move || {
+                    let code = 123456 + calls.fetch_add(1, Ordering::SeqCst);
+                    async move { Ok(Token::new(code.to_string(), None)) }
+                }
+            }))
+            .build_from_provider(Credentials::for_tests())
+            .await;
+
+        provider
+            .provide_credentials()
+            .await
+            .expect("first call succeeds");
+        provider
+            .provide_credentials()
+            .await
+            .expect("second call succeeds");
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+
+        let bodies: Vec<String> = http_client
+            .actual_requests()
+            .map(|req| {
+                std::str::from_utf8(req.body().bytes().unwrap())
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(2, bodies.len());
+        for (body, code) in bodies.iter().zip(["123456", "123457"]) {
+            assert!(body.contains("SerialNumber=GAHT12345678"), "{body}");
+            assert!(body.contains(&format!("TokenCode={code}")), "{body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn serial_number_without_mfa_token_provider() {
+        let (http_client, request) = capture_request(None);
+        let sdk_config = SdkConfig::builder()
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .time_source(StaticTimeSource::from_secs(1234567890))
+            .http_client(http_client)
+            .region(Region::from_static("us-east-1"))
+            .behavior_version(crate::BehaviorVersion::latest())
+            .build();
+        let provider = AssumeRoleProvider::builder("myrole")
+            .configure(&sdk_config)
+            .serial_number("GAHT12345678")
+            .build_from_provider(Credentials::for_tests())
+            .await;
+        let err = provider
+            .provide_credentials()
+            .await
+            .expect_err("no MFA token provider was configured");
+        assert!(
+            matches!(err, CredentialsError::InvalidConfiguration(_)),
+            "{err:?}"
+        );
+        request.expect_no_request();
+    }
 }
@@ -0,0 +1,208 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Configuration for the STS client used by credentials providers.
+
+use aws_smithy_runtime_api::client::http::HttpClient;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::retry::RetryConfig;
+use aws_types::region::Region;
+use aws_types::sdk_config::SharedHttpClient;
+use aws_types::SdkConfig;
+
+const GLOBAL_STS_ENDPOINT: &str = "https://sts.amazonaws.com";
+const GLOBAL_STS_REGION: Region = Region::from_static("us-east-1");
+
+/// Which STS endpoint credentials providers should call.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum StsEndpointMode {
+    /// Use the STS endpoint for the resolved region, e.g. `https://sts.us-west-2.amazonaws.com`.
+    ///
+    /// This is the default.
+    #[default]
+    Regional,
+
+    /// Use the global STS endpoint, `https://sts.amazonaws.com`.
+    ///
+    /// Requests are still signed for the configured region, or for `us-east-1` if no region is
+    /// configured. This takes precedence over an endpoint URL configured for all services, but
+    /// not over [`StsClientConfig::endpoint_url`].
+    ///
+    /// The global endpoint does not support FIPS or dual-stack. Prefer regional endpoints unless
+    /// you need to stay compatible with tooling that only allows the global endpoint.
+    Global,
+}
+
+/// Overrides for the STS client used internally by credentials providers that derive credentials
+/// from STS, such as [`AssumeRoleProvider`](crate::sts::AssumeRoleProvider),
+/// [`WebIdentityTokenCredentialsProvider`](crate::web_identity_token::WebIdentityTokenCredentialsProvider),
+/// and role chains in the [profile provider](crate::profile::ProfileFileCredentialsProvider).
+///
+/// By default, these providers build their STS client from the same configuration as the service
+/// clients. Any field set here takes precedence over that configuration for STS calls only.
+///
+/// # Examples
+/// ```no_run
+/// use aws_config::sts::{StsClientConfig, StsEndpointMode};
+/// use aws_config::retry::RetryConfig;
+/// use aws_types::region::Region;
+///
+/// let sts_config = StsClientConfig::new()
+///     .fallback_region(Region::from_static("us-east-1"))
+///     .retry_config(RetryConfig::standard().with_max_attempts(5))
+///     .endpoint_mode(StsEndpointMode::Regional);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StsClientConfig {
+    endpoint_url: Option<String>,
+    region: Option<Region>,
+    fallback_region: Option<Region>,
+    retry_config: Option<RetryConfig>,
+    http_client: Option<SharedHttpClient>,
+    endpoint_mode: StsEndpointMode,
+}
+
+impl StsClientConfig {
+    /// Creates an `StsClientConfig` that doesn't override anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the endpoint URL used for STS calls.
+    ///
+    /// This takes precedence over [`StsEndpointMode`].
+    pub fn endpoint_url(mut self, endpoint_url: impl Into<String>) -> Self {
+        self.endpoint_url = Some(endpoint_url.into());
+        self
+    }
+
+    /// Always uses the given region for STS calls, regardless of the region otherwise configured.
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Uses the given region for STS calls only when no region was otherwise configured.
+    pub fn fallback_region(mut self, region: Region) -> Self {
+        self.fallback_region = Some(region);
+        self
+    }
+
+    /// Sets the retry configuration used for STS calls.
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Sets the HTTP client used for STS calls.
+    pub fn http_client(mut self, http_client: impl HttpClient + 'static) -> Self {
+        self.http_client = Some(http_client.into_shared());
+        self
+    }
+
+    /// Sets whether the regional or global STS endpoint is used.
+    pub fn endpoint_mode(mut self, endpoint_mode: StsEndpointMode) -> Self {
+        self.endpoint_mode = endpoint_mode;
+        self
+    }
+
+    /// Applies these overrides on top of `conf`, producing the config the STS client is built from.
+    pub(crate) fn apply(&self, conf: SdkConfig) -> SdkConfig {
+        let region = self
+            .region
+            .clone()
+            .or_else(|| conf.region().cloned())
+            .or_else(|| self.fallback_region.clone())
+            .or_else(|| {
+                (self.endpoint_mode == StsEndpointMode::Global).then_some(GLOBAL_STS_REGION)
+            });
+        let endpoint_url = match (&self.endpoint_url, &self.endpoint_mode) {
+            (Some(url), _) => Some(url.clone()),
+            (None, StsEndpointMode::Global) => Some(GLOBAL_STS_ENDPOINT.to_string()),
+            (None, _) => conf.endpoint_url().map(str::to_string),
+        };
+
+        let mut builder = conf.into_builder();
+        builder.set_region(region);
+        builder.set_endpoint_url(endpoint_url);
+        if let Some(retry_config) = &self.retry_config {
+            builder.set_retry_config(Some(retry_config.clone()));
+        }
+        if let Some(http_client) = &self.http_client {
+            builder.set_http_client(Some(http_client.clone()));
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StsClientConfig, StsEndpointMode};
+    use aws_smithy_types::retry::RetryConfig;
+    use aws_types::region::Region;
+    use aws_types::SdkConfig;
+
+    fn base_config(region: Option<&'static str>) -> SdkConfig {
+        let mut builder = SdkConfig::builder().retry_config(RetryConfig::standard());
+        builder.set_region(region.map(Region::from_static));
+        builder.build()
+    }
+
+    #[test]
+    fn no_overrides_preserves_config() {
+        let conf = StsClientConfig::new().apply(base_config(Some("us-west-2")));
+        assert_eq!(Some(&Region::from_static("us-west-2")), conf.region());
+        assert_eq!(None, conf.endpoint_url());
+        assert_eq!(Some(&RetryConfig::standard()), conf.retry_config());
+    }
+
+    #[test]
+    fn region_policy() {
+        let fallback = StsClientConfig::new().fallback_region(Region::from_static("eu-west-1"));
+        assert_eq!(
+            Some(&Region::from_static("us-west-2")),
+            fallback.apply(base_config(Some("us-west-2"))).region()
+        );
+        assert_eq!(
+            Some(&Region::from_static("eu-west-1")),
+            fallback.apply(base_config(None)).region()
+        );
+
+        let fixed = StsClientConfig::new().region(Region::from_static("eu-west-1"));
+        assert_eq!(
+            Some(&Region::from_static("eu-west-1")),
+            fixed.apply(base_config(Some("us-west-2"))).region()
+        );
+    }
+
+    #[test]
+    fn global_endpoint() {
+        let conf = StsClientConfig::new()
+            .endpoint_mode(StsEndpointMode::Global)
+            .apply(base_config(Some("us-west-2")));
+        assert_eq!(Some(&Region::from_static("us-west-2")), conf.region());
+        assert_eq!(Some("https://sts.amazonaws.com"), conf.endpoint_url());
+
+        let conf = StsClientConfig::new()
+            .endpoint_mode(StsEndpointMode::Global)
+            .apply(base_config(None));
+        assert_eq!(Some(&Region::from_static("us-east-1")), conf.region());
+
+        let conf = StsClientConfig::new()
+            .endpoint_mode(StsEndpointMode::Global)
+            .endpoint_url("http://localhost:4566")
+            .apply(base_config(Some("us-west-2")));
+        assert_eq!(Some("http://localhost:4566"), conf.endpoint_url());
+    }
+
+    #[test]
+    fn overrides_retry_config() {
+        let conf = StsClientConfig::new()
+            .retry_config(RetryConfig::disabled())
+            .apply(base_config(Some("us-west-2")));
+        assert_eq!(Some(&RetryConfig::disabled()), conf.retry_config());
+    }
+}
@@ -0,0 +1,200 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Utilities for testing code that loads configuration with aws-config
+//!
+//! These make it possible to unit test configuration logic without touching the real process
+//! environment, the file system, or the network:
+//! - [`Env`] provides fake environment variables
+//! - [`Fs`] provides in-memory config and credentials files
+//! - [`FakeImds`] answers requests to the EC2 instance metadata service
+//!
+//! # Examples
+//! ```no_run
+//! # async fn example() {
+//! use aws_config::test_util::{Env, FakeImds, Fs};
+//! use aws_config::BehaviorVersion;
+//!
+//! let config = aws_config::defaults(BehaviorVersion::latest())
+//!     .env(Env::from_slice(&[("HOME", "/home/user"), ("AWS_PROFILE", "dev")]))
+//!     .fs(Fs::from_slice(&[(
+//!         "/home/user/.aws/config",
+//!         "[profile dev]\nretry_mode = adaptive",
+//!     )]))
+//!     .http_client(FakeImds::new().region("us-west-2"))
+//!     .load()
+//!     .await;
+//! assert_eq!("us-west-2", config.region().unwrap().as_ref());
+//! # }
+//! ```
+
+pub use aws_types::os_shim_internal::{Env, Fs};
+
+use aws_smithy_runtime_api::client::connector_metadata::ConnectorMetadata;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const TOKEN_PATH: &str = "/latest/api/token";
+const TOKEN_TTL_HEADER: &str = "x-aws-ec2-metadata-token-ttl-seconds";
+const FAKE_TOKEN: &str = "fake-imds-token";
+const TOKEN_TTL_SECONDS: &str = "21600";
+const REGION_PATH: &str = "/latest/meta-data/placement/region";
+const CREDENTIALS_PATH: &str = "/latest/meta-data/iam/security-credentials/";
+
+/// HTTP client that acts as the EC2 instance metadata service (IMDS)
+///
+/// Session tokens are always granted, and metadata is served from the paths set with
+/// [`FakeImds::response`]. Requests to other paths receive a `404 Not Found` response. The
+/// host of requests isn't checked, so this should only be used where all HTTP traffic is
+/// expected to go to IMDS.
+#[derive(Clone, Debug, Default)]
+pub struct FakeImds {
+    responses: Arc<HashMap<String, String>>,
+}
+
+impl FakeImds {
+    /// Creates a fake IMDS that doesn't have any metadata
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Respond to requests for `path`, e.g. `/latest/meta-data/instance-id`, with `body`
+    pub fn response(mut self, path: impl Into<String>, body: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.responses).insert(path.into(), body.into());
+        self
+    }
+
+    /// Respond with `region` as the region the instance runs in
+    pub fn region(self, region: impl Into<String>) -> Self {
+        self.response(REGION_PATH, region)
+    }
+
+    /// Respond with instance profile credentials for the role `role_name`
+    ///
+    /// `expiration` is an RFC 3339 timestamp, e.g. `2021-09-21T04:16:53Z`.
+    pub fn credentials(
+        self,
+        role_name: &str,
+        access_key_id: &str,
+        secret_access_key: &str,
+        session_token: &str,
+        expiration: &str,
+    ) -> Self {
+        let credentials = format!(
+            r#"{{
+  "Code" : "Success",
+  "LastUpdated" : "2021-09-20T21:42:26Z",
+  "Type" : "AWS-HMAC",
+  "AccessKeyId" : "{access_key_id}",
+  "SecretAccessKey" : "{secret_access_key}",
+  "Token" : "{session_token}",
+  "Expiration" : "{expiration}"
+}}"#
+        );
+        self.response(CREDENTIALS_PATH, role_name)
+            .response(format!("{CREDENTIALS_PATH}{role_name}"), credentials)
+    }
+
+    fn respond(&self, request: &HttpRequest) -> HttpResponse {
+        let path = request
+            .uri()
+            .parse::<http::Uri>()
+            .map(|uri| uri.path().to_string())
+            .unwrap_or_default();
+        if request.method() == "PUT" && path == TOKEN_PATH {
+            let mut response =
+                HttpResponse::new(StatusCode::try_from(200).unwrap(), FAKE_TOKEN.into());
+            response
+                .headers_mut()
+                .insert(TOKEN_TTL_HEADER, TOKEN_TTL_SECONDS);
+            return response;
+        }
+        match self.responses.get(&path) {
+            Some(body) => HttpResponse::new(
+                StatusCode::try_from(200).unwrap(),
+                SdkBody::from(body.as_str()),
+            ),
+            None => HttpResponse::new(StatusCode::try_from(404).unwrap(), SdkBody::empty()),
+        }
+    }
+}
+
+impl HttpConnector for FakeImds {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        HttpConnectorFuture::ready(Ok(self.respond(&request)))
+    }
+}
+
+impl HttpClient for FakeImds {
+    fn http_connector(
+        &self,
+        _: &HttpConnectorSettings,
+        _: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        self.clone().into_shared()
+    }
+
+    fn connector_metadata(&self) -> Option<ConnectorMetadata> {
+        Some(ConnectorMetadata::new("fake-imds", None))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Env, FakeImds, Fs};
+    use crate::imds::credentials::ImdsCredentialsProvider;
+    use crate::provider_config::ProviderConfig;
+    use crate::test_case::InstantSleep;
+    use aws_credential_types::provider::ProvideCredentials;
+    use aws_smithy_runtime_api::client::behavior_version::BehaviorVersion;
+
+    #[tokio::test]
+    async fn load_config_from_fakes() {
+        let config = crate::defaults(BehaviorVersion::latest())
+            .sleep_impl(InstantSleep)
+            .env(Env::from_slice(&[
+                ("HOME", "/home/user"),
+                ("AWS_PROFILE", "dev"),
+            ]))
+            .fs(Fs::from_slice(&[(
+                "/home/user/.aws/config",
+                "[profile dev]\nuse_fips_endpoint = true",
+            )]))
+            .http_client(FakeImds::new().region("us-west-2"))
+            .load()
+            .await;
+        assert_eq!("us-west-2", config.region().unwrap().as_ref());
+        assert_eq!(Some(true), config.use_fips());
+    }
+
+    #[tokio::test]
+    async fn instance_profile_credentials() {
+        let imds = FakeImds::new().credentials(
+            "my-role",
+            "ASIARTEST",
+            "secret",
+            "token",
+            "2100-01-01T00:00:00Z",
+        );
+        let provider = ImdsCredentialsProvider::builder()
+            .configure(
+                &ProviderConfig::no_configuration()
+                    .with_http_client(imds)
+                    .with_sleep_impl(InstantSleep),
+            )
+            .build();
+        let credentials = provider.provide_credentials().await.expect("valid");
+        assert_eq!("ASIARTEST", credentials.access_key_id());
+        assert_eq!(Some("token"), credentials.session_token());
+    }
+}
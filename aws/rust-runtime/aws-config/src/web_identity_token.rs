@@ -243,7 +243,7 @@ impl Builder {
         WebIdentityTokenCredentialsProvider {
             source,
             fs: conf.fs(),
-            sts_client: StsClient::new(&conf.client_config()),
+            sts_client: StsClient::new(&conf.sts_client_config()),
             time_source: conf.time_source(),
             policy: self.policy,
             policy_arns: self.policy_arns,
@@ -0,0 +1,166 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Serialization of [`Credentials`] for handing them off to another process
+//!
+//! Credentials are serialized using the same field names as the
+//! [`credential_process` output format](https://docs.aws.amazon.com/sdkref/latest/guide/feature-process-credentials.html),
+//! so that a serialized credential is also valid `credential_process` output:
+//!
+//! ```json
+//! {
+//!     "Version": 1,
+//!     "AccessKeyId": "AKIDEXAMPLE",
+//!     "SecretAccessKey": "secret",
+//!     "SessionToken": "token",
+//!     "Expiration": "2009-02-13T23:31:30Z",
+//!     "AccountId": "123456789012"
+//! }
+//! ```
+//!
+//! The provider name and any feature properties are not serialized. Deserialized credentials
+//! always report `Serialized` as their provider name.
+
+use crate::attributes::AccountId;
+use crate::Credentials;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::DateTime;
+use serde::de::Error as _;
+use serde::ser::{Error as _, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::SystemTime;
+
+const PROVIDER_NAME: &str = "Serialized";
+const FORMAT_VERSION: u32 = 1;
+
+impl Serialize for Credentials {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Credentials", 6)?;
+        state.serialize_field("Version", &FORMAT_VERSION)?;
+        state.serialize_field("AccessKeyId", self.access_key_id())?;
+        state.serialize_field("SecretAccessKey", self.secret_access_key())?;
+        if let Some(session_token) = self.session_token() {
+            state.serialize_field("SessionToken", session_token)?;
+        } else {
+            state.skip_field("SessionToken")?;
+        }
+        if let Some(expiry) = self.expiry() {
+            let expiry = DateTime::from(expiry)
+                .fmt(Format::DateTime)
+                .map_err(S::Error::custom)?;
+            state.serialize_field("Expiration", &expiry)?;
+        } else {
+            state.skip_field("Expiration")?;
+        }
+        if let Some(account_id) = self.account_id() {
+            state.serialize_field("AccountId", account_id.as_str())?;
+        } else {
+            state.skip_field("AccountId")?;
+        }
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SerializedCredentials {
+    version: u32,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    expiration: Option<String>,
+    account_id: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for Credentials {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let serialized = SerializedCredentials::deserialize(deserializer)?;
+        if serialized.version != FORMAT_VERSION {
+            return Err(D::Error::custom(format!(
+                "unsupported credentials version: {}",
+                serialized.version
+            )));
+        }
+        let expiry = serialized
+            .expiration
+            .map(|expiration| {
+                DateTime::from_str(&expiration, Format::DateTime)
+                    .map_err(D::Error::custom)
+                    .and_then(|date_time| SystemTime::try_from(date_time).map_err(D::Error::custom))
+            })
+            .transpose()?;
+        let mut builder = Credentials::builder()
+            .access_key_id(serialized.access_key_id)
+            .secret_access_key(serialized.secret_access_key)
+            .provider_name(PROVIDER_NAME);
+        builder.set_session_token(serialized.session_token);
+        builder.set_expiry(expiry);
+        builder.set_account_id(serialized.account_id.map(AccountId::from));
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Credentials;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn round_trip() {
+        let creds = Credentials::builder()
+            .access_key_id("akid")
+            .secret_access_key("secret")
+            .session_token("token")
+            .expiry(UNIX_EPOCH + Duration::from_secs(1234567890))
+            .account_id("123456789012")
+            .provider_name("test")
+            .build();
+        let serialized = serde_json::to_string(&creds).unwrap();
+        assert_eq!(
+            r#"{"Version":1,"AccessKeyId":"akid","SecretAccessKey":"secret","SessionToken":"token","Expiration":"2009-02-13T23:31:30Z","AccountId":"123456789012"}"#,
+            serialized
+        );
+        let deserialized: Credentials = serde_json::from_str(&serialized).unwrap();
+        assert_eq!("akid", deserialized.access_key_id());
+        assert_eq!("secret", deserialized.secret_access_key());
+        assert_eq!(Some("token"), deserialized.session_token());
+        assert_eq!(creds.expiry(), deserialized.expiry());
+        assert_eq!(creds.account_id(), deserialized.account_id());
+    }
+
+    #[test]
+    fn optional_fields() {
+        let creds = Credentials::new("akid", "secret", None, None, "test");
+        let serialized = serde_json::to_string(&creds).unwrap();
+        assert_eq!(
+            r#"{"Version":1,"AccessKeyId":"akid","SecretAccessKey":"secret"}"#,
+            serialized
+        );
+        let deserialized: Credentials = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(None, deserialized.session_token());
+        assert_eq!(None, deserialized.expiry());
+        assert_eq!(None, deserialized.account_id());
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        for invalid in [
+            r#"{"Version":2,"AccessKeyId":"akid","SecretAccessKey":"secret"}"#,
+            r#"{"Version":1,"AccessKeyId":"akid"}"#,
+            r#"{"Version":1,"AccessKeyId":"akid","SecretAccessKey":"secret","Expiration":"tomorrow"}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Credentials>(invalid).is_err(),
+                "{invalid} should fail to deserialize"
+            );
+        }
+    }
+}
@@ -10,6 +10,8 @@
 //! * Traits for credentials providers and for credentials caching
 //! * An opaque struct representing credentials
 //! * Concrete implementations of credentials caching
+//! * An encrypted, on-disk cache for sharing temporary credentials across processes
+//! * Serialization of credentials for handing them to another process (requires the `serde` feature)
 
 #![allow(clippy::derive_partial_eq_without_eq)]
 #![warn(
@@ -25,6 +27,10 @@ pub mod attributes;
 pub mod credential_feature;
 pub mod credential_fn;
 mod credentials_impl;
+#[cfg(feature = "serde")]
+mod credentials_serde;
+#[cfg(feature = "persistent-cache")]
+pub mod persistent_cache;
 pub mod provider;
 pub mod token_fn;
 
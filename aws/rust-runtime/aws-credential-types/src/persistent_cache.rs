@@ -0,0 +1,772 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An encrypted, on-disk cache for temporary credentials
+//!
+//! Short-lived processes, such as CLI tools, resolve credentials from scratch every time they run.
+//! When those credentials come from STS or SSO, this costs a network round trip (or even a new
+//! browser login) per invocation. [`PersistentCredentialsCache`] wraps another credentials
+//! provider and stores the temporary credentials it returns in a file, encrypted with a
+//! [`CredentialsCipher`], so that subsequent processes can reuse them until they are close to
+//! expiring. Credentials are never written to disk unencrypted: a cipher must be set to build
+//! the cache.
+//!
+//! [`Aes256GcmCipher`] encrypts the cache file with AES-256-GCM. This crate intentionally doesn't
+//! pick a key storage mechanism, so its key should be loaded from the OS keyring or another secret
+//! store. To use a different encryption algorithm, implement [`CredentialsCipher`] on top of your
+//! preferred AEAD implementation.
+//!
+//! This module requires the `persistent-cache` feature.
+
+use crate::provider::{self, future, ProvideCredentials};
+use crate::{attributes::AccountId, Credentials};
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_types::error::operation::BuildError;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::error::Error;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zeroize::Zeroizing;
+
+const DEFAULT_BUFFER_TIME: Duration = Duration::from_secs(5 * 60);
+const PROVIDER_NAME: &str = "PersistentCredentialsCache";
+const FORMAT_VERSION: &str = "1";
+
+/// Encrypts and decrypts the credentials stored by [`PersistentCredentialsCache`]
+///
+/// Implementations should use authenticated encryption so that a tampered or corrupted cache file
+/// fails to decrypt rather than producing garbage credentials. When decryption fails, the cached
+/// entry is ignored and credentials are loaded from the wrapped provider.
+pub trait CredentialsCipher: fmt::Debug + Send + Sync {
+    /// Encrypts `plaintext`, returning the bytes to write to disk
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+
+    /// Decrypts `ciphertext` previously produced by [`CredentialsCipher::encrypt`]
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+}
+
+/// A [`CredentialsCipher`] that encrypts the cache file with AES-256-GCM
+///
+/// Every write is encrypted with a new random nonce, which is stored in front of the ciphertext.
+pub struct Aes256GcmCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl Aes256GcmCipher {
+    /// Creates a cipher that encrypts with the given 256-bit key
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            key: LessSafeKey::new(
+                UnboundKey::new(&AES_256_GCM, key)
+                    .expect("the key has the length of the algorithm"),
+            ),
+            rng: SystemRandom::new(),
+        }
+    }
+}
+
+impl fmt::Debug for Aes256GcmCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Aes256GcmCipher")
+            .field("key", &"** redacted **")
+            .finish()
+    }
+}
+
+impl CredentialsCipher for Aes256GcmCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let mut nonce = [0; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| "failed to generate a nonce")?;
+        // Size the buffer up front so that the plaintext is never left behind by a reallocation
+        let tag_len = AES_256_GCM.tag_len();
+        let mut out = Vec::with_capacity(NONCE_LEN + plaintext.len() + tag_len);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(plaintext);
+        let tag = self
+            .key
+            .seal_in_place_separate_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut out[NONCE_LEN..],
+            )
+            .map_err(|_| "failed to encrypt")?;
+        out.extend_from_slice(tag.as_ref());
+        Ok(out)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        if ciphertext.len() < NONCE_LEN {
+            return Err("ciphertext is too short".into());
+        }
+        let (nonce, sealed) = ciphertext.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| "invalid nonce")?;
+        let mut out = sealed.to_vec();
+        let len = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut out)
+            .map_err(|_| "failed to decrypt")?
+            .len();
+        out.truncate(len);
+        Ok(out)
+    }
+}
+
+/// Credentials provider that persists temporary credentials to an encrypted file
+///
+/// Credentials are loaded from the cache file if it holds credentials that are valid for longer
+/// than the configured buffer time. Otherwise, they are loaded from the wrapped provider and, if
+/// they have an expiry, written back to the cache file. Credentials without an expiry are never
+/// written to disk.
+///
+/// Failing to read or write the cache file is not an error: the wrapped provider is used instead.
+/// When called from within a Tokio runtime, the cache file is read and written on its blocking
+/// thread pool so that the async runtime isn't blocked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_credential_types::persistent_cache::{Aes256GcmCipher, PersistentCredentialsCache};
+/// use aws_credential_types::credential_fn::provide_credentials_fn;
+/// use aws_credential_types::Credentials;
+///
+/// fn load_key_from_keyring() -> [u8; 32] {
+///     todo!()
+/// }
+///
+/// async fn load_from_sts() -> Credentials {
+///     todo!()
+/// }
+///
+/// let provider = PersistentCredentialsCache::builder()
+///     .path("/home/user/.cache/my-cli/credentials")
+///     .cipher(Aes256GcmCipher::new(&load_key_from_keyring()))
+///     .build(provide_credentials_fn(|| async { Ok(load_from_sts().await) }))
+///     .expect("path and cipher are set");
+/// ```
+#[derive(Debug)]
+pub struct PersistentCredentialsCache {
+    provider: Arc<dyn ProvideCredentials>,
+    file: CacheFile,
+    time_source: SharedTimeSource,
+    buffer_time: Duration,
+}
+
+impl PersistentCredentialsCache {
+    /// Returns a builder for `PersistentCredentialsCache`
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// The path of the cache file
+    pub fn path(&self) -> &Path {
+        &self.file.path
+    }
+
+    async fn credentials(&self) -> provider::Result {
+        let now = self.time_source.now();
+        let file = self.file.clone();
+        match run_blocking(move || file.load()).await {
+            Ok(Some(credentials)) if self.is_fresh(&credentials, now) => {
+                tracing::debug!(path = ?self.file.path, "loaded credentials from the persistent cache");
+                return Ok(credentials);
+            }
+            Ok(_) => {}
+            Err(err) => {
+                tracing::warn!(path = ?self.file.path, err = %err, "failed to load credentials from the persistent cache");
+            }
+        }
+
+        let credentials = self.provider.provide_credentials().await?;
+        if credentials.expiry().is_some() {
+            let (file, to_store) = (self.file.clone(), credentials.clone());
+            if let Err(err) = run_blocking(move || file.store(&to_store)).await {
+                tracing::warn!(path = ?self.file.path, err = %err, "failed to store credentials in the persistent cache");
+            }
+        }
+        Ok(credentials)
+    }
+
+    fn is_fresh(&self, credentials: &Credentials, now: SystemTime) -> bool {
+        credentials
+            .expiry()
+            .is_some_and(|expiry| expiry > now + self.buffer_time)
+    }
+}
+
+impl ProvideCredentials for PersistentCredentialsCache {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+/// Runs blocking file system calls on Tokio's blocking thread pool, or inline outside of a runtime
+async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, CacheError> + Send + 'static,
+) -> Result<T, CacheError> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => handle
+            .spawn_blocking(f)
+            .await
+            .map_err(|err| CacheError::Io(io::Error::other(err)))?,
+        Err(_) => f(),
+    }
+}
+
+/// The cache file and the cipher that its contents are encrypted with
+#[derive(Clone, Debug)]
+struct CacheFile {
+    path: PathBuf,
+    cipher: Arc<dyn CredentialsCipher>,
+}
+
+impl CacheFile {
+    fn load(&self) -> Result<Option<Credentials>, CacheError> {
+        let ciphertext = match fs::read(&self.path) {
+            Ok(ciphertext) => ciphertext,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(CacheError::Io(err)),
+        };
+        let plaintext = Zeroizing::new(
+            self.cipher
+                .decrypt(&ciphertext)
+                .map_err(CacheError::Cipher)?,
+        );
+        let plaintext = std::str::from_utf8(&plaintext).map_err(|_| CacheError::Malformed)?;
+        decode(plaintext).map(Some)
+    }
+
+    fn store(&self, credentials: &Credentials) -> Result<(), CacheError> {
+        let plaintext = encode(credentials);
+        let ciphertext = self
+            .cipher
+            .encrypt(plaintext.as_bytes())
+            .map_err(CacheError::Cipher)?;
+
+        let parent = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        fs::create_dir_all(parent).map_err(CacheError::Io)?;
+        // Write to a uniquely named temporary file in the same directory first, so that concurrent
+        // readers never observe a partial write and concurrent writers don't clobber each other's
+        // temporary files. The temporary file is only readable by its owner.
+        let mut file = tempfile::NamedTempFile::new_in(parent).map_err(CacheError::Io)?;
+        file.write_all(&ciphertext).map_err(CacheError::Io)?;
+        file.as_file().sync_all().map_err(CacheError::Io)?;
+        file.persist(&self.path)
+            .map_err(|err| CacheError::Io(err.error))?;
+        Ok(())
+    }
+}
+
+/// Builder for [`PersistentCredentialsCache`]
+#[derive(Debug, Default)]
+pub struct Builder {
+    path: Option<PathBuf>,
+    cipher: Option<Arc<dyn CredentialsCipher>>,
+    time_source: Option<SharedTimeSource>,
+    buffer_time: Option<Duration>,
+}
+
+impl Builder {
+    /// Set the path of the cache file
+    ///
+    /// The file and any missing parent directories are created when credentials are first stored.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Set the cipher used to encrypt and decrypt the cache file
+    ///
+    /// This is required: credentials are never persisted without encryption.
+    /// [`Aes256GcmCipher`] can be used unless a different algorithm is needed.
+    pub fn cipher(mut self, cipher: impl CredentialsCipher + 'static) -> Self {
+        self.cipher = Some(Arc::new(cipher));
+        self
+    }
+
+    /// Set the time source used to determine whether cached credentials are expired
+    pub fn time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = Some(SharedTimeSource::new(time_source));
+        self
+    }
+
+    /// Set the amount of time before expiry at which cached credentials are no longer used
+    ///
+    /// Defaults to 5 minutes, which leaves enough time for the credentials to be used by the
+    /// process that loaded them.
+    pub fn buffer_time(mut self, buffer_time: Duration) -> Self {
+        self.buffer_time = Some(buffer_time);
+        self
+    }
+
+    /// Create a `PersistentCredentialsCache` that loads credentials from `provider` when the
+    /// cache file doesn't hold fresh credentials
+    ///
+    /// Returns an error if `path` or `cipher` was not set.
+    pub fn build(
+        self,
+        provider: impl ProvideCredentials + 'static,
+    ) -> Result<PersistentCredentialsCache, BuildError> {
+        Ok(PersistentCredentialsCache {
+            provider: Arc::new(provider),
+            file: CacheFile {
+                path: self.path.ok_or_else(|| {
+                    BuildError::missing_field("path", "the path of the cache file is required")
+                })?,
+                cipher: self.cipher.ok_or_else(|| {
+                    BuildError::missing_field(
+                        "cipher",
+                        "credentials are never persisted without a cipher to encrypt them",
+                    )
+                })?,
+            },
+            time_source: self.time_source.unwrap_or_default(),
+            buffer_time: self.buffer_time.unwrap_or(DEFAULT_BUFFER_TIME),
+        })
+    }
+}
+
+#[derive(Debug)]
+enum CacheError {
+    Io(io::Error),
+    Cipher(Box<dyn Error + Send + Sync>),
+    Malformed,
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Cipher(err) => write!(f, "cipher error: {err}"),
+            Self::Malformed => f.write_str("cache file is malformed"),
+        }
+    }
+}
+
+impl Error for CacheError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Cipher(err) => Some(err.as_ref()),
+            Self::Malformed => None,
+        }
+    }
+}
+
+const VERSION_KEY: &str = "version=";
+const ACCESS_KEY_ID_KEY: &str = "access_key_id=";
+const SECRET_ACCESS_KEY_KEY: &str = "secret_access_key=";
+const SESSION_TOKEN_KEY: &str = "session_token=";
+const EXPIRY_KEY: &str = "expiry=";
+const ACCOUNT_ID_KEY: &str = "account_id=";
+// `u64::MAX` seconds, a dot, and 9 digits of nanoseconds
+const MAX_EXPIRY_LEN: usize = 20 + 1 + 9;
+
+// The cache file holds one `key=value` pair per line. None of the values can contain newlines.
+//
+// The output is written into a buffer that is sized up front, because growing it would leave
+// copies of the secrets behind in memory that is never zeroized.
+fn encode(credentials: &Credentials) -> Zeroizing<String> {
+    let line_len = |key: &str, value: &str| key.len() + value.len() + 1;
+    let capacity = line_len(VERSION_KEY, FORMAT_VERSION)
+        + line_len(ACCESS_KEY_ID_KEY, credentials.access_key_id())
+        + line_len(SECRET_ACCESS_KEY_KEY, credentials.secret_access_key())
+        + credentials
+            .session_token()
+            .map_or(0, |token| line_len(SESSION_TOKEN_KEY, token))
+        + line_len(EXPIRY_KEY, "")
+        + MAX_EXPIRY_LEN
+        + credentials.account_id().map_or(0, |account_id| {
+            line_len(ACCOUNT_ID_KEY, account_id.as_str())
+        });
+    let mut out = Zeroizing::new(String::with_capacity(capacity));
+    let allocated = out.capacity();
+    let mut push_line = |key: &str, value: &str| {
+        out.push_str(key);
+        out.push_str(value);
+        out.push('\n');
+    };
+    push_line(VERSION_KEY, FORMAT_VERSION);
+    push_line(ACCESS_KEY_ID_KEY, credentials.access_key_id());
+    push_line(SECRET_ACCESS_KEY_KEY, credentials.secret_access_key());
+    if let Some(session_token) = credentials.session_token() {
+        push_line(SESSION_TOKEN_KEY, session_token);
+    }
+    if let Some(account_id) = credentials.account_id() {
+        push_line(ACCOUNT_ID_KEY, account_id.as_str());
+    }
+    if let Some(expiry) = credentials.expiry() {
+        let since_epoch = expiry.duration_since(UNIX_EPOCH).unwrap_or_default();
+        // The expiry isn't secret, and writing it doesn't allocate
+        writeln!(
+            out,
+            "{EXPIRY_KEY}{}.{:09}",
+            since_epoch.as_secs(),
+            since_epoch.subsec_nanos()
+        )
+        .expect("writing to a String can't fail");
+    }
+    debug_assert_eq!(allocated, out.capacity(), "the buffer was reallocated");
+    out
+}
+
+fn decode(plaintext: &str) -> Result<Credentials, CacheError> {
+    let mut builder = Credentials::builder().provider_name(PROVIDER_NAME);
+    let (mut version, mut access_key_id, mut secret_access_key) = (None, None, None);
+    for line in plaintext.lines().filter(|line| !line.is_empty()) {
+        let (key, value) = line.split_once('=').ok_or(CacheError::Malformed)?;
+        match key {
+            "version" => version = Some(value),
+            "access_key_id" => access_key_id = Some(value),
+            "secret_access_key" => secret_access_key = Some(value),
+            "session_token" => builder.set_session_token(Some(value.to_owned())),
+            "expiry" => builder.set_expiry(Some(parse_expiry(value)?)),
+            "account_id" => builder.set_account_id(Some(AccountId::from(value))),
+            // Ignore unknown keys so that newer versions can add fields
+            _ => {}
+        }
+    }
+    match (version, access_key_id, secret_access_key) {
+        (Some(FORMAT_VERSION), Some(access_key_id), Some(secret_access_key)) => Ok(builder
+            .access_key_id(access_key_id)
+            .secret_access_key(secret_access_key)
+            .build()),
+        _ => Err(CacheError::Malformed),
+    }
+}
+
+fn parse_expiry(value: &str) -> Result<SystemTime, CacheError> {
+    let (secs, nanos) = value.split_once('.').ok_or(CacheError::Malformed)?;
+    let secs = secs.parse::<u64>().map_err(|_| CacheError::Malformed)?;
+    let nanos = nanos.parse::<u32>().map_err(|_| CacheError::Malformed)?;
+    UNIX_EPOCH
+        .checked_add(Duration::new(secs, nanos))
+        .ok_or(CacheError::Malformed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::credential_fn::provide_credentials_fn;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Not encryption, but enough to verify that the cipher is applied in both directions
+    #[derive(Debug)]
+    struct XorCipher(u8);
+
+    impl CredentialsCipher for XorCipher {
+        fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+            Ok(plaintext.iter().map(|b| b ^ self.0).collect())
+        }
+
+        fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+            self.encrypt(ciphertext)
+        }
+    }
+
+    fn epoch_secs(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "aws-credential-types-persistent-cache-{}-{name}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn counting_provider(
+        calls: Arc<AtomicUsize>,
+        expiry: Option<SystemTime>,
+    ) -> impl ProvideCredentials {
+        provide_credentials_fn(move || {
+            let calls = calls.clone();
+            async move {
+                let n = calls.fetch_add(1, Ordering::SeqCst);
+                let mut builder = Credentials::builder()
+                    .access_key_id(format!("akid-{n}"))
+                    .secret_access_key("secret")
+                    .session_token("token")
+                    .account_id("123456789012")
+                    .provider_name("test");
+                builder.set_expiry(expiry);
+                Ok(builder.build())
+            }
+        })
+    }
+
+    fn cache(
+        path: &Path,
+        time_source: &ManualTimeSource,
+        provider: impl ProvideCredentials + 'static,
+    ) -> PersistentCredentialsCache {
+        PersistentCredentialsCache::builder()
+            .path(path)
+            .cipher(XorCipher(0x5a))
+            .time_source(time_source.clone())
+            .build(provider)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reuses_credentials_across_instances() {
+        let path = test_dir("reuse").join("nested").join("credentials");
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let expiry = Some(epoch_secs(1000 + 3600));
+
+        let first = cache(
+            &path,
+            &time_source,
+            counting_provider(calls.clone(), expiry),
+        );
+        let loaded = first.provide_credentials().await.unwrap();
+        assert_eq!("akid-0", loaded.access_key_id());
+
+        // The file is encrypted with the cipher
+        let on_disk = fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&on_disk).contains("akid-0"));
+
+        // A new instance (e.g. in the next process) reuses the cached credentials
+        let second = cache(
+            &path,
+            &time_source,
+            counting_provider(calls.clone(), expiry),
+        );
+        let cached = second.provide_credentials().await.unwrap();
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+        assert_eq!(loaded.access_key_id(), cached.access_key_id());
+        assert_eq!(loaded.secret_access_key(), cached.secret_access_key());
+        assert_eq!(loaded.session_token(), cached.session_token());
+        assert_eq!(loaded.expiry(), cached.expiry());
+        assert_eq!(
+            Some("123456789012"),
+            cached.account_id().map(AccountId::as_str)
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn refreshes_credentials_within_buffer_time() {
+        let dir = test_dir("refresh");
+        let path = dir.join("credentials");
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = cache(
+            &path,
+            &time_source,
+            counting_provider(calls.clone(), Some(epoch_secs(1000 + 600))),
+        );
+
+        assert_eq!(
+            "akid-0",
+            cache.provide_credentials().await.unwrap().access_key_id()
+        );
+        time_source.advance(Duration::from_secs(200));
+        assert_eq!(
+            "akid-0",
+            cache.provide_credentials().await.unwrap().access_key_id()
+        );
+        // Now within the default 5 minute buffer
+        time_source.advance(Duration::from_secs(200));
+        assert_eq!(
+            "akid-1",
+            cache.provide_credentials().await.unwrap().access_key_id()
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn does_not_persist_long_term_credentials() {
+        let dir = test_dir("long-term");
+        let path = dir.join("credentials");
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = cache(&path, &time_source, counting_provider(calls.clone(), None));
+
+        cache.provide_credentials().await.unwrap();
+        cache.provide_credentials().await.unwrap();
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+        assert!(!path.exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn ignores_undecryptable_cache_file() {
+        let dir = test_dir("corrupt");
+        let path = dir.join("credentials");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, b"definitely not credentials").unwrap();
+
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = cache(
+            &path,
+            &time_source,
+            counting_provider(calls.clone(), Some(epoch_secs(1000 + 3600))),
+        );
+        assert_eq!(
+            "akid-0",
+            cache.provide_credentials().await.unwrap().access_key_id()
+        );
+        // The corrupt file was replaced
+        assert_eq!(
+            "akid-0",
+            cache.provide_credentials().await.unwrap().access_key_id()
+        );
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn concurrent_writers_use_separate_temporary_files() {
+        let dir = test_dir("concurrent");
+        let path = dir.join("credentials");
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let cache = Arc::new(cache(
+            &path,
+            &time_source,
+            counting_provider(Arc::new(AtomicUsize::new(0)), None),
+        ));
+        let writers: Vec<_> = (0..8)
+            .map(|n| {
+                let cache = cache.clone();
+                std::thread::spawn(move || {
+                    let credentials = Credentials::builder()
+                        .access_key_id(format!("akid-{n}"))
+                        .secret_access_key("secret")
+                        .expiry(epoch_secs(1000 + 3600))
+                        .provider_name(PROVIDER_NAME)
+                        .build();
+                    for _ in 0..20 {
+                        cache.file.store(&credentials).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert!(cache.file.load().unwrap().is_some());
+        // No temporary files are left behind
+        let files: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(vec![std::ffi::OsString::from("credentials")], files);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(0o600, mode & 0o777);
+        }
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn aes_256_gcm_cipher_round_trips_and_detects_tampering() {
+        let cipher = Aes256GcmCipher::new(&[7; 32]);
+        let first = cipher.encrypt(b"secret_access_key=secret").unwrap();
+        let second = cipher.encrypt(b"secret_access_key=secret").unwrap();
+        // Every write uses a new nonce
+        assert_ne!(first, second);
+        assert!(!String::from_utf8_lossy(&first).contains("secret"));
+        assert_eq!(
+            b"secret_access_key=secret".to_vec(),
+            cipher.decrypt(&first).unwrap()
+        );
+
+        let mut tampered = first.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(cipher.decrypt(&tampered).is_err());
+        assert!(Aes256GcmCipher::new(&[8; 32]).decrypt(&first).is_err());
+        assert!(cipher.decrypt(&first[..4]).is_err());
+        assert!(!format!("{cipher:?}").contains('7'));
+    }
+
+    #[tokio::test]
+    async fn persists_with_aes_256_gcm_cipher() {
+        let dir = test_dir("aes-256-gcm");
+        let path = dir.join("credentials");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let build = || {
+            PersistentCredentialsCache::builder()
+                .path(&path)
+                .cipher(Aes256GcmCipher::new(&[7; 32]))
+                .time_source(ManualTimeSource::new(epoch_secs(1000)))
+                .build(counting_provider(
+                    calls.clone(),
+                    Some(epoch_secs(1000 + 3600)),
+                ))
+                .unwrap()
+        };
+        build().provide_credentials().await.unwrap();
+        assert!(!String::from_utf8_lossy(&fs::read(&path).unwrap()).contains("secret"));
+        assert_eq!(
+            "akid-0",
+            build().provide_credentials().await.unwrap().access_key_id()
+        );
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn build_requires_path_and_cipher() {
+        let provider = || counting_provider(Arc::new(AtomicUsize::new(0)), None);
+        let err = PersistentCredentialsCache::builder()
+            .cipher(XorCipher(0x5a))
+            .build(provider())
+            .unwrap_err();
+        assert!(err.to_string().contains("path"), "{err}");
+        let err = PersistentCredentialsCache::builder()
+            .path("credentials")
+            .build(provider())
+            .unwrap_err();
+        assert!(err.to_string().contains("cipher"), "{err}");
+    }
+
+    #[test]
+    fn decode_rejects_unknown_versions() {
+        let credentials = Credentials::builder()
+            .access_key_id("akid")
+            .secret_access_key("secret")
+            .expiry(UNIX_EPOCH + Duration::new(u64::MAX / 2, 999_999_999))
+            .provider_name(PROVIDER_NAME)
+            .build();
+        let encoded = encode(&credentials);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(credentials, decoded);
+        assert_eq!(None, decoded.session_token());
+
+        let future_version = encoded.replace("version=1", "version=2");
+        assert!(matches!(
+            decode(&future_version),
+            Err(CacheError::Malformed)
+        ));
+    }
+}
@@ -76,4 +76,6 @@ pub mod error;
 pub mod future;
 pub mod token;
 
-pub use credentials::{ProvideCredentials, Result, SharedCredentialsProvider};
+pub use credentials::{
+    ProvideCredentials, Result, SharedCredentialsProvider, StaticCredentialsProvider,
+};
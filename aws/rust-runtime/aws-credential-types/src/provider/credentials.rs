@@ -101,6 +101,20 @@ pub trait ProvideCredentials: Send + Sync + std::fmt::Debug {
     fn fallback_on_interrupt(&self) -> Option<Credentials> {
         None
     }
+
+    /// Checks that this provider is able to provide credentials.
+    ///
+    /// This is intended to be called when an application starts, so that a misconfigured provider
+    /// is reported before the first request is made. The default implementation loads credentials
+    /// with [`provide_credentials`](ProvideCredentials::provide_credentials) and discards them.
+    /// Providers may override it with a check that is cheaper, or more thorough (e.g. one that
+    /// verifies the credentials with their issuer).
+    fn validate<'a>(&'a self) -> super::future::Validate<'a>
+    where
+        Self: 'a,
+    {
+        super::future::Validate::new(async move { self.provide_credentials().await.map(|_| ()) })
+    }
 }
 
 impl ProvideCredentials for Credentials {
@@ -112,6 +126,55 @@ impl ProvideCredentials for Credentials {
     }
 }
 
+/// Credentials provider that always returns the same credentials
+///
+/// This is useful for passing credentials minted by one process to another, short-lived process.
+/// With the `serde` feature enabled, the receiving process can load the serialized credentials
+/// with [`StaticCredentialsProvider::from_serialized`]. The credentials are never refreshed, so
+/// the receiving process must finish its work before they expire.
+#[derive(Clone, Debug)]
+pub struct StaticCredentialsProvider {
+    credentials: Credentials,
+}
+
+impl StaticCredentialsProvider {
+    /// Creates a provider that always returns `credentials`
+    pub fn new(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+
+    /// Creates a provider from credentials serialized as JSON
+    ///
+    /// The expected format is the one produced by serializing [`Credentials`] with `serde_json`.
+    /// This function requires the `serde` feature to be enabled.
+    ///
+    /// ```rust
+    /// use aws_credential_types::provider::StaticCredentialsProvider;
+    ///
+    /// let provider = StaticCredentialsProvider::from_serialized(
+    ///     r#"{"Version":1,"AccessKeyId":"akid","SecretAccessKey":"secret"}"#,
+    /// )
+    /// .expect("valid credentials");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_serialized(
+        serialized: &str,
+    ) -> std::result::Result<Self, super::error::CredentialsError> {
+        serde_json::from_str(serialized)
+            .map(Self::new)
+            .map_err(super::error::CredentialsError::invalid_configuration)
+    }
+}
+
+impl ProvideCredentials for StaticCredentialsProvider {
+    fn provide_credentials<'a>(&'a self) -> super::future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        super::future::ProvideCredentials::ready(Ok(self.credentials.clone()))
+    }
+}
+
 impl ProvideCredentials for Arc<dyn ProvideCredentials> {
     fn provide_credentials<'a>(&'a self) -> super::future::ProvideCredentials<'a>
     where
@@ -119,6 +182,13 @@ impl ProvideCredentials for Arc<dyn ProvideCredentials> {
     {
         self.as_ref().provide_credentials()
     }
+
+    fn validate<'a>(&'a self) -> super::future::Validate<'a>
+    where
+        Self: 'a,
+    {
+        self.as_ref().validate()
+    }
 }
 
 /// Credentials Provider wrapper that may be shared
@@ -157,6 +227,13 @@ impl ProvideCredentials for SharedCredentialsProvider {
     {
         self.0.provide_credentials()
     }
+
+    fn validate<'a>(&'a self) -> super::future::Validate<'a>
+    where
+        Self: 'a,
+    {
+        self.0.validate()
+    }
 }
 
 impl Storable for SharedCredentialsProvider {
@@ -204,6 +281,25 @@ mod tests {
         assert!(partition.unwrap() == identity_partition);
     }
 
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn static_provider_from_serialized() {
+        let provider = StaticCredentialsProvider::from_serialized(
+            r#"{"Version":1,"AccessKeyId":"AKID","SecretAccessKey":"SECRET","AccountId":"012345678901"}"#,
+        )
+        .expect("valid credentials");
+        let creds = provider.provide_credentials().await.unwrap();
+        assert_eq!("AKID", creds.access_key_id());
+        assert_eq!("SECRET", creds.secret_access_key());
+        assert_eq!(Some(&AccountId::from("012345678901")), creds.account_id());
+
+        let err = StaticCredentialsProvider::from_serialized("{}").expect_err("missing keys");
+        assert!(matches!(
+            err,
+            crate::provider::error::CredentialsError::InvalidConfiguration(_)
+        ));
+    }
+
     #[tokio::test]
     async fn account_id_can_be_retrieved_from_identity() {
         let expected_account_id = "012345678901";
@@ -5,6 +5,7 @@
 
 //! Convenience `ProvideCredentials` struct that implements the `ProvideCredentials` trait.
 
+use crate::provider::error::CredentialsError;
 use crate::provider::token::Result as TokenResult;
 use crate::provider::Result as CredsResult;
 use aws_smithy_async::future::now_or_later::NowOrLater;
@@ -38,6 +39,32 @@ impl Future for ProvideCredentials<'_> {
     }
 }
 
+type ValidateResult = Result<(), CredentialsError>;
+
+/// Future new-type that `ProvideCredentials::validate` must return.
+#[derive(Debug)]
+pub struct Validate<'a>(NowOrLater<ValidateResult, BoxFuture<'a, ValidateResult>>);
+
+impl<'a> Validate<'a> {
+    /// Creates a `Validate` struct from a future.
+    pub fn new(future: impl Future<Output = ValidateResult> + Send + 'a) -> Self {
+        Validate(NowOrLater::new(Box::pin(future)))
+    }
+
+    /// Creates a `Validate` struct from a resolved validation result.
+    pub fn ready(result: ValidateResult) -> Self {
+        Validate(NowOrLater::ready(result))
+    }
+}
+
+impl Future for Validate<'_> {
+    type Output = ValidateResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
 /// Future new-type that `ProvideToken::provide_token` must return.
 #[derive(Debug)]
 pub struct ProvideToken<'a>(NowOrLater<TokenResult, BoxFuture<'a, TokenResult>>);
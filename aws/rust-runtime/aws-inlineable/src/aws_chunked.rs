@@ -9,7 +9,9 @@ use std::fmt;
 
 use aws_runtime::{
     auth::PayloadSigningOverride,
-    content_encoding::{header_value::AWS_CHUNKED, AwsChunkedBody, AwsChunkedBodyOptions},
+    content_encoding::{
+        header_value::AWS_CHUNKED, AwsChunkedBody, AwsChunkedBodyOptions, AwsChunkedTrailers,
+    },
 };
 use aws_smithy_runtime_api::{
     box_error::BoxError,
@@ -24,11 +26,13 @@ use http::{header, HeaderValue};
 use http_body::Body;
 
 const X_AMZ_DECODED_CONTENT_LENGTH: &str = "x-amz-decoded-content-length";
+const X_AMZ_TRAILER: &str = "x-amz-trailer";
 
 /// Errors related to constructing aws-chunked encoded HTTP requests.
 #[derive(Debug)]
 enum Error {
     UnsizedRequestBody,
+    TrailersWithoutChunkedEncoding,
 }
 
 impl fmt::Display for Error {
@@ -38,6 +42,10 @@ impl fmt::Display for Error {
                 f,
                 "Only request bodies with a known size can be aws-chunked encoded."
             ),
+            Self::TrailersWithoutChunkedEncoding => write!(
+                f,
+                "Trailers were provided, but they can only be sent with aws-chunked encoded request bodies."
+            ),
         }
     }
 }
@@ -59,6 +67,9 @@ impl Intercept for AwsChunkedContentEncodingInterceptor {
         cfg: &mut ConfigBag,
     ) -> Result<(), BoxError> {
         if must_not_use_chunked_encoding(context.request(), cfg) {
+            if cfg.load::<AwsChunkedTrailers>().is_some() {
+                return Err(BuildError::other(Error::TrailersWithoutChunkedEncoding))?;
+            }
             tracing::debug!(
                 "short-circuiting modify_before_signing because chunked encoding must not be used"
             );
@@ -77,7 +88,7 @@ impl Intercept for AwsChunkedContentEncodingInterceptor {
             return Err(BuildError::other(Error::UnsizedRequestBody))?;
         };
 
-        let chunked_body_options = if let Some(chunked_body_options) =
+        let mut chunked_body_options = if let Some(chunked_body_options) =
             cfg.get_mut_from_interceptor_state::<AwsChunkedBodyOptions>()
         {
             let chunked_body_options = std::mem::take(chunked_body_options);
@@ -87,6 +98,16 @@ impl Intercept for AwsChunkedContentEncodingInterceptor {
         };
 
         let request = context.request_mut();
+        if let Some(trailers) = cfg.load::<AwsChunkedTrailers>() {
+            // Declare the additional trailers alongside any trailer (e.g. a checksum) that was
+            // already declared, so that they're covered by the request signature.
+            let mut trailer_names: Vec<&str> =
+                request.headers().get(X_AMZ_TRAILER).into_iter().collect();
+            trailer_names.extend(trailers.trailers().keys().map(|name| name.as_str()));
+            let trailer_names = trailer_names.join(",");
+            request.headers_mut().insert(X_AMZ_TRAILER, trailer_names);
+            chunked_body_options = chunked_body_options.with_trailers(trailers.trailers().clone());
+        }
         // For for aws-chunked encoding, `x-amz-decoded-content-length` must be set to the original body size.
         request.headers_mut().insert(
             header::HeaderName::from_static(X_AMZ_DECODED_CONTENT_LENGTH),
@@ -170,6 +191,7 @@ mod tests {
     use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
     use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
     use aws_smithy_types::byte_stream::ByteStream;
+    use aws_smithy_types::error::display::DisplayErrorContext;
     use bytes::BytesMut;
     use http_body::Body;
     use tempfile::NamedTempFile;
@@ -256,6 +278,68 @@ mod tests {
             .is_none());
     }
 
+    #[tokio::test]
+    async fn test_trailers_require_chunked_encoding() {
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let _ = ctx.take_input();
+        let request = HttpRequest::new(SdkBody::from(
+            "in-memory body, must not use chunked encoding",
+        ));
+        ctx.set_request(request);
+        ctx.enter_before_transmit_phase();
+        let mut ctx: BeforeTransmitInterceptorContextMut<'_> = (&mut ctx).into();
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("x-amz-meta-a", HeaderValue::from_static("1"));
+        let mut cfg = ConfigBag::base();
+        cfg.interceptor_state()
+            .store_put(AwsChunkedTrailers::new(trailers));
+
+        let interceptor = AwsChunkedContentEncodingInterceptor;
+        let err = interceptor
+            .modify_before_signing(&mut ctx, &runtime_components, &mut cfg)
+            .unwrap_err();
+        assert!(format!("{}", DisplayErrorContext(err.as_ref())).contains("Trailers were provided"));
+    }
+
+    #[tokio::test]
+    async fn test_trailers_are_declared_after_existing_trailers() {
+        let file = NamedTempFile::new().unwrap();
+        let mut request = HttpRequest::new(streaming_body(&file).await);
+        request
+            .headers_mut()
+            .insert(X_AMZ_TRAILER, "x-amz-checksum-crc32");
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let _ = ctx.take_input();
+        ctx.set_request(request);
+        ctx.enter_before_transmit_phase();
+        let mut ctx: BeforeTransmitInterceptorContextMut<'_> = (&mut ctx).into();
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("x-amz-meta-a", HeaderValue::from_static("1"));
+        let mut cfg = ConfigBag::base();
+        cfg.interceptor_state()
+            .store_put(AwsChunkedBodyOptions::default());
+        cfg.interceptor_state()
+            .store_put(AwsChunkedTrailers::new(trailers));
+
+        let interceptor = AwsChunkedContentEncodingInterceptor;
+        interceptor
+            .modify_before_signing(&mut ctx, &runtime_components, &mut cfg)
+            .unwrap();
+
+        assert_eq!(
+            Some("x-amz-checksum-crc32,x-amz-meta-a"),
+            ctx.request().headers().get(X_AMZ_TRAILER)
+        );
+    }
+
     #[tokio::test]
     async fn test_short_circuit_modify_before_transmit() {
         let mut ctx = InterceptorContext::new(Input::doesnt_matter());
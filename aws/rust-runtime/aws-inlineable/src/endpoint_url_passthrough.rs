@@ -0,0 +1,216 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_http::label::{fmt_string, EncodingStrategy};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::{
+    EndpointFuture, EndpointPrefix, EndpointResolverParams, ResolveEndpoint, SharedEndpointResolver,
+};
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::endpoint::Endpoint;
+use aws_types::endpoint_config::EndpointUrl;
+use aws_types::region::Region;
+
+/// Whether requests are sent to the configured endpoint URL verbatim
+#[derive(Clone, Debug)]
+pub(crate) struct EndpointUrlPassthrough(pub(crate) bool);
+
+impl Storable for EndpointUrlPassthrough {
+    type Storer = StoreReplace<Self>;
+}
+
+/// The endpoint resolver wrapped by the [`EndpointUrlPassthroughResolver`] set on a config builder
+///
+/// Enabling passthrough again wraps this resolver instead of the passthrough resolver, so the
+/// passthrough resolvers don't nest.
+#[derive(Clone, Debug)]
+pub(crate) struct EndpointUrlPassthroughFallback(pub(crate) SharedEndpointResolver);
+
+impl Storable for EndpointUrlPassthroughFallback {
+    type Storer = StoreReplace<Self>;
+}
+
+fn passthrough_enabled(cfg: &ConfigBag) -> bool {
+    cfg.load::<EndpointUrlPassthrough>()
+        .map(|passthrough| passthrough.0)
+        .unwrap_or_default()
+}
+
+/// Endpoint resolver that resolves to the configured endpoint URL without evaluating the endpoint rules
+///
+/// When passthrough is disabled, endpoint resolution is delegated to `fallback`.
+#[derive(Debug)]
+pub(crate) struct EndpointUrlPassthroughResolver {
+    service: &'static str,
+    fallback: SharedEndpointResolver,
+    bucket: Option<fn(&EndpointResolverParams) -> Option<&str>>,
+}
+
+impl EndpointUrlPassthroughResolver {
+    /// Creates a resolver for `service`, the name substituted for the `{service}` placeholder of the endpoint URL
+    pub(crate) fn new(service: &'static str, fallback: SharedEndpointResolver) -> Self {
+        Self {
+            service,
+            fallback,
+            bucket: None,
+        }
+    }
+
+    /// Appends the bucket returned by `bucket` to the path of the endpoint URL
+    ///
+    /// S3 removes the bucket from the path of requests, since the endpoint rules either add it to
+    /// the host or back to the path.
+    pub(crate) fn with_bucket_in_path(
+        mut self,
+        bucket: fn(&EndpointResolverParams) -> Option<&str>,
+    ) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    fn resolve_passthrough(
+        &self,
+        params: &EndpointResolverParams,
+        cfg: &ConfigBag,
+    ) -> Result<Endpoint, BoxError> {
+        let endpoint_url = cfg
+            .load::<EndpointUrl>()
+            .ok_or("endpoint URL passthrough is enabled, but no endpoint URL is configured")?;
+        let mut url =
+            endpoint_url.expand(self.service, cfg.load::<Region>().map(|r| r.as_ref()))?;
+        if let Some(bucket) = self.bucket.and_then(|bucket| bucket(params)) {
+            url = format!(
+                "{}/{}",
+                url.trim_end_matches('/'),
+                fmt_string(bucket, EncodingStrategy::Default)
+            );
+        }
+        Ok(Endpoint::builder().url(url).build())
+    }
+}
+
+impl ResolveEndpoint for EndpointUrlPassthroughResolver {
+    fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+        self.fallback.resolve_endpoint(params)
+    }
+
+    fn resolve_endpoint_with_config<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        cfg: &'a ConfigBag,
+    ) -> EndpointFuture<'a> {
+        if passthrough_enabled(cfg) {
+            EndpointFuture::ready(self.resolve_passthrough(params, cfg))
+        } else {
+            self.fallback.resolve_endpoint_with_config(params, cfg)
+        }
+    }
+
+    fn finalize_params<'a>(
+        &'a self,
+        params: &'a mut EndpointResolverParams,
+    ) -> Result<(), BoxError> {
+        self.fallback.finalize_params(params)
+    }
+}
+
+/// Interceptor that removes the host prefix of operations when endpoint URL passthrough is enabled
+#[derive(Debug, Default)]
+pub(crate) struct EndpointUrlPassthroughInterceptor;
+
+impl Intercept for EndpointUrlPassthroughInterceptor {
+    fn name(&self) -> &'static str {
+        "EndpointUrlPassthroughInterceptor"
+    }
+
+    fn modify_before_retry_loop(
+        &self,
+        _context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if passthrough_enabled(cfg) {
+            cfg.interceptor_state().unset::<EndpointPrefix>();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EndpointUrlPassthrough, EndpointUrlPassthroughResolver};
+    use aws_smithy_runtime_api::client::endpoint::{
+        EndpointFuture, EndpointResolverParams, ResolveEndpoint, SharedEndpointResolver,
+    };
+    use aws_smithy_types::config_bag::{ConfigBag, Layer};
+    use aws_smithy_types::endpoint::Endpoint;
+    use aws_types::endpoint_config::EndpointUrl;
+    use aws_types::region::Region;
+
+    #[derive(Debug)]
+    struct RulesResolver;
+
+    impl ResolveEndpoint for RulesResolver {
+        fn resolve_endpoint<'a>(&'a self, _: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+            EndpointFuture::ready(Ok(Endpoint::builder()
+                .url("https://bucket.s3.us-east-1.amazonaws.com")
+                .build()))
+        }
+    }
+
+    #[derive(Debug)]
+    struct Bucket(&'static str);
+
+    fn resolver() -> EndpointUrlPassthroughResolver {
+        EndpointUrlPassthroughResolver::new("s3", SharedEndpointResolver::new(RulesResolver))
+            .with_bucket_in_path(|params| params.get::<Bucket>().map(|bucket| bucket.0))
+    }
+
+    fn config(passthrough: bool) -> ConfigBag {
+        let mut layer = Layer::new("test");
+        layer.store_put(EndpointUrl("https://{region}.objects.example.com/".into()));
+        layer.store_put(Region::new("us-east-1"));
+        layer.store_put(EndpointUrlPassthrough(passthrough));
+        ConfigBag::of_layers(vec![layer])
+    }
+
+    #[tokio::test]
+    async fn resolve_to_endpoint_url_with_bucket_in_path() {
+        let params = EndpointResolverParams::new(Bucket("my bucket"));
+        let endpoint = resolver()
+            .resolve_endpoint_with_config(&params, &config(true))
+            .await
+            .unwrap();
+        assert_eq!(
+            "https://us-east-1.objects.example.com/my%20bucket",
+            endpoint.url()
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_with_fallback_when_disabled() {
+        let params = EndpointResolverParams::new(Bucket("bucket"));
+        let endpoint = resolver()
+            .resolve_endpoint_with_config(&params, &config(false))
+            .await
+            .unwrap();
+        assert_eq!("https://bucket.s3.us-east-1.amazonaws.com", endpoint.url());
+    }
+
+    #[tokio::test]
+    async fn passthrough_requires_endpoint_url() {
+        let params = EndpointResolverParams::new(Bucket("bucket"));
+        let mut layer = Layer::new("test");
+        layer.store_put(EndpointUrlPassthrough(true));
+        let err = resolver()
+            .resolve_endpoint_with_config(&params, &ConfigBag::of_layers(vec![layer]))
+            .await
+            .expect_err("no endpoint URL");
+        assert!(err.to_string().contains("no endpoint URL"), "{err}");
+    }
+}
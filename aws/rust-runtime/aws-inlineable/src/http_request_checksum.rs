@@ -511,4 +511,15 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn when_required_is_honored_when_payload_signing_is_disabled() {
+        let state = RequestChecksumInterceptorState::default();
+        let mut cfg = ConfigBag::base();
+        cfg.interceptor_state()
+            .store_put(RequestChecksumCalculation::WhenRequired);
+        cfg.interceptor_state()
+            .store_put(aws_runtime::auth::PayloadSigningOverride::UnsignedPayload);
+        assert!(!calculate_checksum(&mut cfg, &state));
+    }
 }
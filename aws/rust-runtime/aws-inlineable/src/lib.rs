@@ -33,6 +33,10 @@ pub mod aws_chunked;
 #[allow(dead_code)]
 pub mod endpoint_auth;
 
+/// Support for sending requests to the configured endpoint URL verbatim.
+#[allow(dead_code)]
+pub mod endpoint_url_passthrough;
+
 /// Interceptors for API Gateway
 pub mod apigateway_interceptors;
 
@@ -47,6 +51,29 @@ pub mod presigning_interceptors;
 // fail to compile.
 // pub mod s3_express;
 
+// Like `s3_express`, these modules use `crate::Client` of the S3 crate they are copied to.
+// pub mod s3_access_grants;
+// pub mod s3_batch_delete;
+// pub mod s3_download;
+// pub mod s3_express_pagination;
+// pub mod s3_object_lambda;
+// pub mod s3_request_validation;
+// pub mod s3_seekable_reader;
+// pub mod s3_sse_defaults;
+// pub mod s3_upload;
+
+/// Support for overriding the addressing style of S3 buckets.
+#[allow(dead_code)]
+pub mod s3_addressing_style;
+
+/// Support for following S3 redirects to the region of a bucket.
+#[allow(dead_code)]
+pub mod s3_cross_region_redirect;
+
+/// Support for sending S3 requests to interface VPC endpoints.
+#[allow(dead_code)]
+pub mod s3_vpc_endpoint;
+
 /// Special logic for extracting request IDs from S3's responses.
 #[allow(dead_code)]
 pub mod s3_request_id;
@@ -60,9 +87,6 @@ pub mod route53_resource_id_preprocessor;
 pub mod http_request_checksum;
 pub mod http_response_checksum;
 
-#[allow(dead_code)]
-pub mod endpoint_discovery;
-
 // This module is symlinked in from the smithy-rs rust-runtime inlineables so that
 // the `presigning_interceptors` module can refer to it.
 mod serialization_settings;
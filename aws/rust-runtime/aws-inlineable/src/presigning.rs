@@ -11,6 +11,10 @@
 //! [`PresigningConfig`](crate::presigning::PresigningConfig) to provide an expiration time.
 //!
 //! Only operations that support presigning have the `presigned()` method on them.
+//!
+//! Requests are presigned with the auth scheme selected for the operation. For example,
+//! requests to S3 Multi-Region Access Points are presigned with SigV4a, and the resulting
+//! URL is valid in every region the access point routes to.
 
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
@@ -168,7 +172,8 @@ impl PresigningConfigBuilder {
     }
 }
 
-/// Represents a presigned request. This only includes the HTTP request method, URI, and headers.
+/// Represents a presigned request. This includes the HTTP request method, URI, and headers, along
+/// with the serialized body of operations whose body is modeled, such as `CompleteMultipartUpload`.
 ///
 /// **This struct has conversion convenience functions:**
 ///
@@ -186,7 +191,7 @@ impl Clone for PresignedRequest {
                 Some(body) => body,
                 None => {
                     unreachable!(
-                        "during construction, we replaced the body with `SdkBody::empty()`"
+                        "during construction, we replaced streaming bodies with `SdkBody::empty()`"
                     )
                 }
             },
@@ -197,13 +202,17 @@ impl Clone for PresignedRequest {
 impl PresignedRequest {
     #[allow(dead_code)]
     pub(crate) fn new(inner: HttpRequest) -> Result<Self, BoxError> {
-        // throw out the body so we're sure it's cloneable
-        let http_request = inner.map(|_body| SdkBody::empty());
+        // keep serialized bodies, which must be sent along with the presigned request, but throw
+        // out streaming bodies so we're sure the request is cloneable
+        let http_request = inner.map(|body| match body.bytes() {
+            Some(bytes) => SdkBody::from(bytes.to_vec()),
+            None => SdkBody::empty(),
+        });
         // this should never fail, a presigned request should always be convertible, but better to
         // protect against this potential panic
         let _ = http_request
             .try_clone()
-            .expect("must be cloneable, body is empty")
+            .expect("must be cloneable, body is in memory")
             .try_into_http02x()?;
         Ok(Self { http_request })
     }
@@ -225,6 +234,15 @@ impl PresignedRequest {
         self.http_request.headers().iter()
     }
 
+    /// Returns the serialized body that needs to be sent with the request, such as the XML
+    /// listing the parts of a `CompleteMultipartUpload` request.
+    ///
+    /// This is empty for operations without a modeled body, and for operations with streaming
+    /// bodies, such as `PutObject`, whose body is provided by the caller.
+    pub fn body(&self) -> &[u8] {
+        self.http_request.body().bytes().unwrap_or_default()
+    }
+
     /// Given a body, produce an `http::Request` from this `PresignedRequest`
     #[deprecated = "Prefer the `make_http_1x_request()` instead by enabling the `http-1x` feature."]
     #[allow(deprecated)]
@@ -243,6 +261,8 @@ impl PresignedRequest {
 
     #[cfg(feature = "http-1x")]
     /// Given a body, produce an `http_1x::Request` from this `PresignedRequest`
+    ///
+    /// For operations with a modeled body, the body should be [`PresignedRequest::body`].
     pub fn make_http_1x_request<B>(&self, body: B) -> http_1x::Request<B> {
         self.clone().into_http_1x_request(body)
     }
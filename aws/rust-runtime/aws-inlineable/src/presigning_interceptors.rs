@@ -29,7 +29,7 @@ use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
 use aws_smithy_types::config_bag::{ConfigBag, FrozenLayer, Layer};
 use std::borrow::Cow;
 
-/// Interceptor that tells the SigV4 signer to add the signature to query params,
+/// Interceptor that tells the SigV4 (or SigV4a) signer to add the signature to query params,
 /// and sets the request expiration time from the presigning config.
 #[derive(Debug)]
 pub(crate) struct SigV4PresigningInterceptor {
@@ -0,0 +1,641 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Credentials vended by S3 Access Grants.
+//!
+//! With an [`AccessGrantsConfig`] set on the client, requests for objects are signed with
+//! credentials that are scoped to the grant that covers the object instead of the credentials of
+//! the client. Credentials are requested with the S3 Control `GetDataAccess` operation through a
+//! [`ProvideDataAccess`] implementation and cached by the scope of the matched grant, so that
+//! requests for other objects covered by the same grant reuse them.
+
+use aws_credential_types::Credentials;
+use aws_smithy_async::future::BoxFuture;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+use aws_smithy_runtime_api::client::identity::{
+    Identity, IdentityCacheLocation, IdentityFuture, ResolveCachedIdentity, ResolveIdentity,
+    SharedIdentityResolver,
+};
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeSerializationInterceptorContextRef, Input,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_MAX_CACHE_ENTRIES: usize = 1000;
+// Credentials are requested again once they expire within this time
+const REFRESH_BUFFER: Duration = Duration::from_secs(60);
+
+/// Level of access requested from S3 Access Grants
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Permission {
+    /// Access to read objects
+    Read,
+    /// Access to write and delete objects
+    Write,
+    /// Access to read, write, and delete objects
+    ReadWrite,
+}
+
+impl Permission {
+    /// Returns the name of the permission as used by the `GetDataAccess` operation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "READ",
+            Self::Write => "WRITE",
+            Self::ReadWrite => "READWRITE",
+        }
+    }
+
+    fn covers(self, other: Permission) -> bool {
+        self == other || self == Permission::ReadWrite
+    }
+}
+
+/// Request for credentials to access a target
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataAccessRequest {
+    target: String,
+    permission: Permission,
+}
+
+impl DataAccessRequest {
+    /// Returns the target of the request, e.g. `s3://my-bucket/my-object` or `s3://my-bucket/prefix*`
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns the requested permission
+    pub fn permission(&self) -> Permission {
+        self.permission
+    }
+}
+
+/// Credentials vended for a grant
+#[derive(Clone, Debug)]
+pub struct DataAccess {
+    credentials: Credentials,
+    matched_grant_target: String,
+}
+
+impl DataAccess {
+    /// Creates data access from the credentials and matched grant target of a `GetDataAccess` response
+    ///
+    /// The credentials must have an expiration time, and are cached for all targets matched by
+    /// `matched_grant_target`, e.g. `s3://my-bucket/prefix/*`.
+    pub fn new(credentials: Credentials, matched_grant_target: impl Into<String>) -> Self {
+        Self {
+            credentials,
+            matched_grant_target: matched_grant_target.into(),
+        }
+    }
+
+    /// Returns the vended credentials
+    pub fn credentials(&self) -> &Credentials {
+        &self.credentials
+    }
+
+    /// Returns the target of the grant that matched the request
+    pub fn matched_grant_target(&self) -> &str {
+        &self.matched_grant_target
+    }
+}
+
+/// Provider of credentials from S3 Access Grants
+///
+/// Implementations usually call `GetDataAccess` with an S3 Control client. See [`data_access_fn`]
+/// to implement this trait with a closure.
+pub trait ProvideDataAccess: fmt::Debug + Send + Sync {
+    /// Returns credentials for the target and permission of `request`
+    fn data_access(&self, request: DataAccessRequest) -> BoxFuture<'_, DataAccess, BoxError>;
+}
+
+/// Provider of credentials from S3 Access Grants that calls a closure
+#[derive(Clone)]
+pub struct DataAccessFn<F> {
+    f: F,
+}
+
+impl<F> fmt::Debug for DataAccessFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataAccessFn")
+    }
+}
+
+impl<F, Fut> ProvideDataAccess for DataAccessFn<F>
+where
+    F: Fn(DataAccessRequest) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<DataAccess, BoxError>> + Send + 'static,
+{
+    fn data_access(&self, request: DataAccessRequest) -> BoxFuture<'_, DataAccess, BoxError> {
+        Box::pin((self.f)(request))
+    }
+}
+
+/// Returns a [`ProvideDataAccess`] implementation that calls `f` for each request
+pub fn data_access_fn<F, Fut>(f: F) -> DataAccessFn<F>
+where
+    F: Fn(DataAccessRequest) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<DataAccess, BoxError>> + Send + 'static,
+{
+    DataAccessFn { f }
+}
+
+/// Configuration for signing object requests with credentials from S3 Access Grants
+///
+/// Requests for objects, e.g. `GetObject` and `PutObject`, and listings with `ListObjectsV2` are
+/// signed with credentials for the grant that covers the object or prefix. All other requests,
+/// requests for directory buckets, and requests of operations whose credentials are overridden
+/// are signed with the credentials of the client.
+///
+/// # Examples
+/// ```ignore
+/// use aws_sdk_s3::access_grants::{data_access_fn, AccessGrantsConfig, DataAccess};
+/// use aws_sdk_s3::config::Credentials;
+/// use std::time::SystemTime;
+///
+/// let sdk_config = aws_config::load_from_env().await;
+/// let s3control = aws_sdk_s3control::Client::new(&sdk_config);
+/// let provider = data_access_fn(move |request| {
+///     let s3control = s3control.clone();
+///     async move {
+///         let output = s3control
+///             .get_data_access()
+///             .account_id("123456789012")
+///             .target(request.target())
+///             .permission(request.permission().as_str().into())
+///             .send()
+///             .await?;
+///         let credentials = output.credentials().ok_or("no credentials in response")?;
+///         Ok(DataAccess::new(
+///             Credentials::new(
+///                 credentials.access_key_id().unwrap_or_default(),
+///                 credentials.secret_access_key().unwrap_or_default(),
+///                 credentials.session_token().map(str::to_owned),
+///                 credentials.expiration().and_then(|t| SystemTime::try_from(*t).ok()),
+///                 "S3AccessGrants",
+///             ),
+///             output.matched_grant_target().unwrap_or_default(),
+///         ))
+///     }
+/// });
+/// let config = aws_sdk_s3::config::Builder::from(&sdk_config)
+///     .access_grants(AccessGrantsConfig::new(provider).fallback_to_default_credentials(true))
+///     .build();
+/// let client = aws_sdk_s3::Client::from_conf(config);
+/// ```
+#[derive(Clone)]
+pub struct AccessGrantsConfig {
+    provider: Arc<dyn ProvideDataAccess>,
+    fallback_to_default_credentials: bool,
+    max_cache_entries: usize,
+}
+
+impl fmt::Debug for AccessGrantsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessGrantsConfig")
+            .field("provider", &self.provider)
+            .field(
+                "fallback_to_default_credentials",
+                &self.fallback_to_default_credentials,
+            )
+            .field("max_cache_entries", &self.max_cache_entries)
+            .finish()
+    }
+}
+
+impl AccessGrantsConfig {
+    /// Creates a configuration that requests credentials from `provider`
+    pub fn new(provider: impl ProvideDataAccess + 'static) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            fallback_to_default_credentials: false,
+            max_cache_entries: DEFAULT_MAX_CACHE_ENTRIES,
+        }
+    }
+
+    /// Whether requests are signed with the credentials of the client when no credentials can be vended
+    ///
+    /// Defaults to `false`, which fails requests for objects that aren't covered by a grant.
+    pub fn fallback_to_default_credentials(mut self, enabled: bool) -> Self {
+        self.fallback_to_default_credentials = enabled;
+        self
+    }
+
+    /// Sets the maximum number of grants whose credentials are cached
+    ///
+    /// Defaults to 1000. The credentials that were cached first are evicted once the cache is full.
+    /// The cache always holds at least one grant, so a `max_cache_entries` of zero is treated as one.
+    pub fn max_cache_entries(mut self, max_cache_entries: usize) -> Self {
+        self.max_cache_entries = max_cache_entries.max(1);
+        self
+    }
+}
+
+impl Storable for AccessGrantsConfig {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Target and permission of the current request, stored by [`AccessGrantsInterceptor`]
+#[derive(Clone, Debug)]
+struct AccessGrantsTarget(DataAccessRequest);
+
+impl Storable for AccessGrantsTarget {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Returns the target and permission of S3 operations that are covered by access grants
+fn access_grants_target(input: &Input) -> Option<DataAccessRequest> {
+    use crate::operation::*;
+
+    macro_rules! object_target {
+        ($($input:ty => $permission:expr),+ $(,)?) => {
+            $(
+                if let Some(input) = input.downcast_ref::<$input>() {
+                    return Some(DataAccessRequest {
+                        target: format!("s3://{}/{}", input.bucket.as_deref()?, input.key.as_deref()?),
+                        permission: $permission,
+                    });
+                }
+            )+
+        };
+    }
+
+    object_target!(
+        get_object::GetObjectInput => Permission::Read,
+        head_object::HeadObjectInput => Permission::Read,
+        get_object_attributes::GetObjectAttributesInput => Permission::Read,
+        list_parts::ListPartsInput => Permission::Read,
+        put_object::PutObjectInput => Permission::Write,
+        delete_object::DeleteObjectInput => Permission::Write,
+        create_multipart_upload::CreateMultipartUploadInput => Permission::Write,
+        upload_part::UploadPartInput => Permission::Write,
+        complete_multipart_upload::CompleteMultipartUploadInput => Permission::Write,
+        abort_multipart_upload::AbortMultipartUploadInput => Permission::Write,
+    );
+    if let Some(input) = input.downcast_ref::<list_objects_v2::ListObjectsV2Input>() {
+        return Some(DataAccessRequest {
+            target: format!(
+                "s3://{}/{}*",
+                input.bucket.as_deref()?,
+                input.prefix.as_deref().unwrap_or_default()
+            ),
+            permission: Permission::Read,
+        });
+    }
+    None
+}
+
+/// Whether `target` is covered by a grant for `grant_target`, e.g. `s3://my-bucket/prefix/*`
+fn grant_covers(grant_target: &str, target: &str) -> bool {
+    match grant_target.strip_suffix('*') {
+        Some(prefix) => target
+            .strip_suffix('*')
+            .unwrap_or(target)
+            .starts_with(prefix),
+        None => grant_target == target,
+    }
+}
+
+#[derive(Debug)]
+struct CachedGrant {
+    grant_target: String,
+    permission: Permission,
+    credentials: Credentials,
+}
+
+/// Cache of vended credentials by the scope of their grant
+#[derive(Debug)]
+struct GrantCache {
+    max_entries: usize,
+    grants: Mutex<VecDeque<CachedGrant>>,
+}
+
+impl GrantCache {
+    fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            grants: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn get(&self, request: &DataAccessRequest, now: SystemTime) -> Option<Credentials> {
+        let mut grants = self.grants.lock().unwrap();
+        grants.retain(|grant| !is_expired(&grant.credentials, now));
+        grants
+            .iter()
+            .find(|grant| {
+                grant.permission.covers(request.permission)
+                    && grant_covers(&grant.grant_target, &request.target)
+            })
+            .map(|grant| grant.credentials.clone())
+    }
+
+    fn insert(&self, request: &DataAccessRequest, data_access: &DataAccess) {
+        let mut grants = self.grants.lock().unwrap();
+        grants.retain(|grant| {
+            grant.grant_target != data_access.matched_grant_target
+                || grant.permission != request.permission
+        });
+        if grants.len() >= self.max_entries {
+            grants.pop_front();
+        }
+        grants.push_back(CachedGrant {
+            grant_target: data_access.matched_grant_target.clone(),
+            permission: request.permission,
+            credentials: data_access.credentials.clone(),
+        });
+    }
+}
+
+fn is_expired(credentials: &Credentials, now: SystemTime) -> bool {
+    credentials
+        .expiry()
+        .map_or(true, |expiry| expiry <= now + REFRESH_BUFFER)
+}
+
+/// Interceptor that stores the access grants target of object requests in the config bag
+#[derive(Debug)]
+struct AccessGrantsInterceptor;
+
+impl Intercept for AccessGrantsInterceptor {
+    fn name(&self) -> &'static str {
+        "AccessGrantsInterceptor"
+    }
+
+    fn read_before_serialization(
+        &self,
+        context: &BeforeSerializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if let Some(target) = access_grants_target(context.input()) {
+            cfg.interceptor_state()
+                .store_put(AccessGrantsTarget(target));
+        }
+        Ok(())
+    }
+}
+
+/// Identity resolver that resolves credentials from S3 Access Grants for object requests
+#[derive(Debug)]
+struct AccessGrantsIdentityResolver {
+    default_resolver: SharedIdentityResolver,
+    config: AccessGrantsConfig,
+    cache: GrantCache,
+}
+
+impl AccessGrantsIdentityResolver {
+    async fn identity(
+        &self,
+        runtime_components: &RuntimeComponents,
+        config_bag: &ConfigBag,
+    ) -> Result<Identity, BoxError> {
+        let target = config_bag
+            .load::<AccessGrantsTarget>()
+            .map(|target| &target.0)
+            .filter(|_| !is_directory_bucket_request(config_bag));
+        let Some(request) = target else {
+            return self.default_identity(runtime_components, config_bag).await;
+        };
+
+        let now = runtime_components
+            .time_source()
+            .ok_or("a time source is required to cache credentials from S3 Access Grants")?
+            .now();
+        if let Some(credentials) = self.cache.get(request, now) {
+            return Ok(credentials_identity(credentials));
+        }
+        match self.config.provider.data_access(request.clone()).await {
+            Ok(data_access) => {
+                if data_access.credentials.expiry().is_none() {
+                    return Err("credentials from S3 Access Grants must expire".into());
+                }
+                self.cache.insert(request, &data_access);
+                Ok(credentials_identity(data_access.credentials))
+            }
+            Err(err) if self.config.fallback_to_default_credentials => {
+                tracing::debug!(
+                    target = request.target(),
+                    error = %aws_smithy_types::error::display::DisplayErrorContext(&*err),
+                    "no credentials from S3 Access Grants, falling back to the credentials of the client"
+                );
+                self.default_identity(runtime_components, config_bag).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn default_identity(
+        &self,
+        runtime_components: &RuntimeComponents,
+        config_bag: &ConfigBag,
+    ) -> Result<Identity, BoxError> {
+        runtime_components
+            .identity_cache()
+            .resolve_cached_identity(
+                self.default_resolver.clone(),
+                runtime_components,
+                config_bag,
+            )
+            .await
+    }
+}
+
+fn credentials_identity(credentials: Credentials) -> Identity {
+    let expiry = credentials.expiry();
+    Identity::new(credentials, expiry)
+}
+
+fn is_directory_bucket_request(config_bag: &ConfigBag) -> bool {
+    config_bag
+        .load::<EndpointResolverParams>()
+        .and_then(|params| params.get::<crate::config::endpoint::Params>())
+        .and_then(|params| params.bucket())
+        .is_some_and(|bucket| bucket.ends_with("--x-s3"))
+}
+
+impl ResolveIdentity for AccessGrantsIdentityResolver {
+    fn resolve_identity<'a>(
+        &'a self,
+        runtime_components: &'a RuntimeComponents,
+        config_bag: &'a ConfigBag,
+    ) -> IdentityFuture<'a> {
+        IdentityFuture::new(async move { self.identity(runtime_components, config_bag).await })
+    }
+
+    fn cache_location(&self) -> IdentityCacheLocation {
+        IdentityCacheLocation::IdentityResolver
+    }
+}
+
+/// Runtime plugin that signs object requests with credentials from S3 Access Grants
+#[derive(Debug)]
+pub(crate) struct AccessGrantsRuntimePlugin {
+    runtime_components: RuntimeComponentsBuilder,
+}
+
+impl AccessGrantsRuntimePlugin {
+    // Like the S3 Express runtime plugin, this is called with the fully constructed service config
+    // so that the SigV4 identity resolver of the client can be wrapped.
+    pub(crate) fn new(service_config: crate::config::Config) -> Self {
+        let mut runtime_components = RuntimeComponentsBuilder::new("AccessGrantsRuntimePlugin");
+        let access_grants = service_config.config.load::<AccessGrantsConfig>();
+        let default_resolver = service_config
+            .runtime_components
+            .identity_resolver(&aws_runtime::auth::sigv4::SCHEME_ID);
+        if let (Some(config), Some(default_resolver)) = (access_grants, default_resolver) {
+            runtime_components.push_interceptor(AccessGrantsInterceptor);
+            runtime_components.set_identity_resolver(
+                aws_runtime::auth::sigv4::SCHEME_ID,
+                AccessGrantsIdentityResolver {
+                    default_resolver,
+                    cache: GrantCache::new(config.max_cache_entries),
+                    config: config.clone(),
+                },
+            );
+        }
+        Self { runtime_components }
+    }
+}
+
+impl RuntimePlugin for AccessGrantsRuntimePlugin {
+    fn runtime_components(
+        &self,
+        _current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        Cow::Borrowed(&self.runtime_components)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        data_access_fn, grant_covers, AccessGrantsConfig, DataAccess, DataAccessRequest,
+        GrantCache, Permission,
+    };
+    use aws_credential_types::Credentials;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn credentials(name: &str, expiry: SystemTime) -> Credentials {
+        Credentials::new(name, "secret", None, Some(expiry), "test")
+    }
+
+    fn request(target: &str, permission: Permission) -> DataAccessRequest {
+        DataAccessRequest {
+            target: target.into(),
+            permission,
+        }
+    }
+
+    #[test]
+    fn zero_max_cache_entries_is_clamped() {
+        let config = AccessGrantsConfig::new(data_access_fn(|_| async { Err("unused".into()) }))
+            .max_cache_entries(0);
+        assert_eq!(1, config.max_cache_entries);
+    }
+
+    #[test]
+    fn grant_scopes() {
+        assert!(grant_covers("s3://bucket/*", "s3://bucket/a/b"));
+        assert!(grant_covers("s3://bucket/a/*", "s3://bucket/a/b"));
+        assert!(grant_covers("s3://bucket/a/*", "s3://bucket/a/*"));
+        assert!(grant_covers("s3://bucket/a/*", "s3://bucket/a/b*"));
+        assert!(!grant_covers("s3://bucket/a/*", "s3://bucket/b"));
+        assert!(!grant_covers("s3://bucket/a/*", "s3://bucket/*"));
+        assert!(grant_covers("s3://bucket/a", "s3://bucket/a"));
+        assert!(!grant_covers("s3://bucket/a", "s3://bucket/ab"));
+    }
+
+    #[test]
+    fn cached_credentials_are_reused_within_the_grant_scope() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let cache = GrantCache::new(10);
+        let creds = credentials("grant", now + Duration::from_secs(3600));
+        cache.insert(
+            &request("s3://bucket/a/b", Permission::ReadWrite),
+            &DataAccess::new(creds.clone(), "s3://bucket/a/*"),
+        );
+
+        assert_eq!(
+            Some(creds.clone()),
+            cache.get(&request("s3://bucket/a/c", Permission::Read), now)
+        );
+        assert_eq!(
+            Some(creds),
+            cache.get(&request("s3://bucket/a/d", Permission::Write), now)
+        );
+        assert_eq!(
+            None,
+            cache.get(&request("s3://bucket/b", Permission::Read), now)
+        );
+    }
+
+    #[test]
+    fn narrower_permissions_are_not_reused() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let cache = GrantCache::new(10);
+        cache.insert(
+            &request("s3://bucket/a", Permission::Read),
+            &DataAccess::new(
+                credentials("grant", now + Duration::from_secs(3600)),
+                "s3://bucket/*",
+            ),
+        );
+        assert_eq!(
+            None,
+            cache.get(&request("s3://bucket/a", Permission::Write), now)
+        );
+    }
+
+    #[test]
+    fn expiring_credentials_are_evicted() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let cache = GrantCache::new(10);
+        cache.insert(
+            &request("s3://bucket/a", Permission::Read),
+            &DataAccess::new(
+                credentials("grant", now + Duration::from_secs(30)),
+                "s3://bucket/*",
+            ),
+        );
+        assert_eq!(
+            None,
+            cache.get(&request("s3://bucket/a", Permission::Read), now)
+        );
+    }
+
+    #[test]
+    fn oldest_grants_are_evicted_when_the_cache_is_full() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let expiry = now + Duration::from_secs(3600);
+        let cache = GrantCache::new(2);
+        for grant in ["a", "b", "c"] {
+            cache.insert(
+                &request(&format!("s3://bucket/{grant}"), Permission::Read),
+                &DataAccess::new(credentials(grant, expiry), format!("s3://bucket/{grant}")),
+            );
+        }
+        assert_eq!(
+            None,
+            cache.get(&request("s3://bucket/a", Permission::Read), now)
+        );
+        assert!(cache
+            .get(&request("s3://bucket/c", Permission::Read), now)
+            .is_some());
+    }
+}
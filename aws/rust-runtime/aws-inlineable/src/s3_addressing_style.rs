@@ -0,0 +1,342 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+
+/// How a bucket is addressed in requests
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AddressingStyle {
+    /// The bucket is part of the path, e.g. `https://s3.us-east-1.amazonaws.com/my-bucket`
+    Path,
+    /// The bucket is part of the host, e.g. `https://my-bucket.s3.us-east-1.amazonaws.com`
+    VirtualHosted,
+}
+
+/// Addressing styles of buckets that override the `force_path_style` setting of the client
+///
+/// Overrides are matched against bucket names in the order they were added, and the first matching
+/// override wins. Patterns may contain `*`, which matches any sequence of characters, and `?`, which
+/// matches any single character.
+///
+/// Buckets that match no override and whose names can't be used as a DNS label, e.g. because they
+/// contain dots, underscores, or uppercase letters, are addressed with path-style requests unless
+/// [`path_style_for_dns_incompatible_buckets`](Self::path_style_for_dns_incompatible_buckets) is
+/// disabled. Overrides don't apply to access point and Outposts ARNs.
+///
+/// A bucket that is addressed with virtual-hosted style but can't be used in a host name is still
+/// addressed with path-style requests, or rejected if path-style requests aren't supported, e.g.
+/// with S3 Transfer Acceleration.
+///
+/// # Examples
+/// ```ignore
+/// use aws_sdk_s3::config::{AddressingStyle, AddressingStyleOverrides};
+///
+/// // Talk to a path-style-only store for `local-*` buckets and to AWS for all other buckets
+/// let overrides = AddressingStyleOverrides::new().with_override("local-*", AddressingStyle::Path);
+/// let config = aws_sdk_s3::Config::builder()
+///     .addressing_style_overrides(overrides)
+///     .build();
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressingStyleOverrides {
+    overrides: Vec<(String, AddressingStyle)>,
+    path_style_for_dns_incompatible_buckets: bool,
+}
+
+impl Default for AddressingStyleOverrides {
+    fn default() -> Self {
+        Self {
+            overrides: Vec::new(),
+            path_style_for_dns_incompatible_buckets: true,
+        }
+    }
+}
+
+impl AddressingStyleOverrides {
+    /// Creates overrides that only address DNS-incompatible buckets with path-style requests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Addresses buckets whose names match `pattern` with `style`
+    pub fn with_override(mut self, pattern: impl Into<String>, style: AddressingStyle) -> Self {
+        self.overrides.push((pattern.into(), style));
+        self
+    }
+
+    /// Whether buckets that match no override and whose names aren't valid DNS labels are addressed with path-style requests
+    ///
+    /// Defaults to `true`.
+    pub fn path_style_for_dns_incompatible_buckets(mut self, enabled: bool) -> Self {
+        self.path_style_for_dns_incompatible_buckets = enabled;
+        self
+    }
+
+    /// Returns the addressing style of `bucket`, or `None` if the client's addressing style applies
+    pub fn addressing_style(&self, bucket: &str) -> Option<AddressingStyle> {
+        if bucket.starts_with("arn:") {
+            return None;
+        }
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| matches_pattern(pattern, bucket))
+            .map(|(_, style)| *style)
+            .or_else(|| {
+                (self.path_style_for_dns_incompatible_buckets && !is_dns_compatible(bucket))
+                    .then_some(AddressingStyle::Path)
+            })
+    }
+}
+
+impl Storable for AddressingStyleOverrides {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Matches `name` against a pattern where `*` matches any sequence of characters and `?` any single character
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let (pattern, name) = (pattern.as_bytes(), name.as_bytes());
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the position in the name it was matched at
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` match one more character
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Whether `bucket` can be used as a single DNS label of a host name
+///
+/// Names with dots are excluded since they don't match the wildcard TLS certificates of S3.
+fn is_dns_compatible(bucket: &str) -> bool {
+    (3..=63).contains(&bucket.len())
+        && bucket
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        && !bucket.starts_with('-')
+        && !bucket.ends_with('-')
+}
+
+/// Interceptor that sets the `ForcePathStyle` endpoint parameter according to [`AddressingStyleOverrides`]
+#[derive(Debug)]
+pub(crate) struct AddressingStyleInterceptor {
+    bucket: fn(&EndpointResolverParams) -> Option<&str>,
+    set_force_path_style: fn(&mut EndpointResolverParams, bool),
+}
+
+impl AddressingStyleInterceptor {
+    pub(crate) fn new(
+        bucket: fn(&EndpointResolverParams) -> Option<&str>,
+        set_force_path_style: fn(&mut EndpointResolverParams, bool),
+    ) -> Self {
+        Self {
+            bucket,
+            set_force_path_style,
+        }
+    }
+}
+
+impl Intercept for AddressingStyleInterceptor {
+    fn name(&self) -> &'static str {
+        "AddressingStyleInterceptor"
+    }
+
+    fn modify_before_retry_loop(
+        &self,
+        _context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(overrides) = cfg.load::<AddressingStyleOverrides>() else {
+            return Ok(());
+        };
+        let style = cfg
+            .load::<EndpointResolverParams>()
+            .and_then(|params| (self.bucket)(params))
+            .and_then(|bucket| overrides.addressing_style(bucket));
+        if let Some(style) = style {
+            if let Some(params) = cfg.get_mut_from_interceptor_state::<EndpointResolverParams>() {
+                (self.set_force_path_style)(params, style == AddressingStyle::Path);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        is_dns_compatible, matches_pattern, AddressingStyle, AddressingStyleInterceptor,
+        AddressingStyleOverrides,
+    };
+    use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+    use aws_smithy_runtime_api::client::interceptors::context::{
+        BeforeTransmitInterceptorContextMut, Input, InterceptorContext,
+    };
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::{ConfigBag, Layer};
+
+    #[test]
+    fn pattern_matching() {
+        assert!(matches_pattern("bucket", "bucket"));
+        assert!(!matches_pattern("bucket", "bucket-2"));
+        assert!(matches_pattern("local-*", "local-"));
+        assert!(matches_pattern("local-*", "local-bucket"));
+        assert!(!matches_pattern("local-*", "bucket-local-"));
+        assert!(matches_pattern("*-logs", "app-logs"));
+        assert!(matches_pattern("*-logs-*", "app-logs-logs-2024"));
+        assert!(!matches_pattern("*-logs-*", "app-logs"));
+        assert!(matches_pattern("bucket-?", "bucket-1"));
+        assert!(!matches_pattern("bucket-?", "bucket-10"));
+        assert!(matches_pattern("*", ""));
+    }
+
+    #[test]
+    fn dns_compatibility() {
+        assert!(is_dns_compatible("my-bucket-1"));
+        assert!(!is_dns_compatible("my.bucket"));
+        assert!(!is_dns_compatible("My_Bucket"));
+        assert!(!is_dns_compatible("-bucket"));
+        assert!(!is_dns_compatible("ab"));
+        assert!(!is_dns_compatible(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn first_matching_override_wins() {
+        let overrides = AddressingStyleOverrides::new()
+            .with_override("local-aws-*", AddressingStyle::VirtualHosted)
+            .with_override("local-*", AddressingStyle::Path);
+        assert_eq!(
+            Some(AddressingStyle::VirtualHosted),
+            overrides.addressing_style("local-aws-bucket")
+        );
+        assert_eq!(
+            Some(AddressingStyle::Path),
+            overrides.addressing_style("local-bucket")
+        );
+        assert_eq!(None, overrides.addressing_style("bucket"));
+    }
+
+    #[test]
+    fn path_style_for_dns_incompatible_buckets() {
+        let overrides =
+            AddressingStyleOverrides::new().with_override("*.vh", AddressingStyle::VirtualHosted);
+        assert_eq!(
+            Some(AddressingStyle::Path),
+            overrides.addressing_style("my.bucket")
+        );
+        assert_eq!(
+            Some(AddressingStyle::VirtualHosted),
+            overrides.addressing_style("my.bucket.vh")
+        );
+        assert_eq!(
+            None,
+            overrides
+                .path_style_for_dns_incompatible_buckets(false)
+                .addressing_style("my.bucket")
+        );
+    }
+
+    #[test]
+    fn arns_are_not_overridden() {
+        let overrides = AddressingStyleOverrides::new().with_override("*", AddressingStyle::Path);
+        assert_eq!(
+            None,
+            overrides.addressing_style("arn:aws:s3:us-west-2:123456789012:accesspoint:myendpoint")
+        );
+    }
+
+    #[derive(Debug)]
+    struct Params {
+        bucket: &'static str,
+        force_path_style: bool,
+    }
+
+    fn force_path_style(overrides: AddressingStyleOverrides, params: Params) -> bool {
+        let interceptor = AddressingStyleInterceptor::new(
+            |params| params.get::<Params>().map(|params| params.bucket),
+            |params, force_path_style| {
+                if let Some(params) = params.get_mut::<Params>() {
+                    params.force_path_style = force_path_style;
+                }
+            },
+        );
+        let mut layer = Layer::new("test");
+        layer.store_put(overrides);
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        cfg.interceptor_state()
+            .store_put(EndpointResolverParams::new(params));
+
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.set_request(HttpRequest::empty());
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        interceptor
+            .modify_before_retry_loop(
+                &mut BeforeTransmitInterceptorContextMut::from(&mut context),
+                &rc,
+                &mut cfg,
+            )
+            .unwrap();
+
+        cfg.load::<EndpointResolverParams>()
+            .unwrap()
+            .get::<Params>()
+            .unwrap()
+            .force_path_style
+    }
+
+    #[test]
+    fn interceptor_sets_force_path_style() {
+        let overrides = AddressingStyleOverrides::new()
+            .with_override("local-*", AddressingStyle::Path)
+            .with_override("aws-*", AddressingStyle::VirtualHosted);
+        assert!(force_path_style(
+            overrides.clone(),
+            Params {
+                bucket: "local-bucket",
+                force_path_style: false,
+            }
+        ));
+        assert!(!force_path_style(
+            overrides.clone(),
+            Params {
+                bucket: "aws-bucket",
+                force_path_style: true,
+            }
+        ));
+        assert!(force_path_style(
+            overrides,
+            Params {
+                bucket: "other-bucket",
+                force_path_style: true,
+            }
+        ));
+    }
+}
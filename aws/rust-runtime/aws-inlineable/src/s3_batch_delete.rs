@@ -0,0 +1,233 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Deletion of any number of objects with `DeleteObjects` requests.
+//!
+//! A `DeleteObjects` request deletes at most 1000 objects and reports failures per object instead
+//! of failing the request. [`BatchDelete`] splits any number of keys into batches of 1000 keys,
+//! sends the batches concurrently, and collects the failures of all batches into one
+//! [`BatchDeleteOutput`].
+
+use crate::operation::delete_objects::DeleteObjectsError;
+use crate::types::{Delete, ObjectIdentifier};
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use futures_util::stream::{self, StreamExt};
+use std::fmt;
+
+/// Maximum number of objects that a `DeleteObjects` request deletes
+const MAX_BATCH_SIZE: usize = 1000;
+const DEFAULT_CONCURRENCY: usize = 4;
+
+type Keys = Box<dyn Iterator<Item = String> + Send + Sync>;
+
+/// Builder for a [`BatchDelete`]
+pub struct BatchDeleteBuilder {
+    client: crate::Client,
+    bucket: String,
+    keys: Keys,
+    concurrency: usize,
+    expected_bucket_owner: Option<String>,
+}
+
+impl fmt::Debug for BatchDeleteBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchDeleteBuilder")
+            .field("client", &self.client)
+            .field("bucket", &self.bucket)
+            .field("concurrency", &self.concurrency)
+            .field("expected_bucket_owner", &self.expected_bucket_owner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BatchDeleteBuilder {
+    /// Sets the maximum number of `DeleteObjects` requests that are sent at the same time
+    ///
+    /// Defaults to 4. At least one batch is always sent at a time, so a `concurrency` of zero is
+    /// treated as one.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the account ID that is expected to own the bucket
+    pub fn expected_bucket_owner(mut self, expected_bucket_owner: impl Into<String>) -> Self {
+        self.expected_bucket_owner = Some(expected_bucket_owner.into());
+        self
+    }
+
+    /// Creates the batch delete
+    pub fn build(self) -> BatchDelete {
+        BatchDelete { inner: self }
+    }
+}
+
+/// Deletion of any number of objects of a bucket
+///
+/// Keys are read from the iterator as batches are sent, so the keys of a large listing don't have
+/// to be collected first. Batches are sent in quiet mode, in which S3 only reports the keys that
+/// it failed to delete. A batch whose request fails doesn't stop the other batches; its keys are
+/// reported in the output as well.
+///
+/// # Examples
+/// ```no_run
+/// # async fn example(client: aws_sdk_s3::Client) {
+/// use aws_sdk_s3::batch_delete::BatchDelete;
+///
+/// let keys = (0..5000).map(|i| format!("logs/{i}.txt"));
+/// let output = BatchDelete::builder(client, "my-bucket", keys)
+///     .build()
+///     .send()
+///     .await;
+/// for error in output.errors() {
+///     println!("failed to delete {:?}: {:?}", error.key(), error.code());
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BatchDelete {
+    inner: BatchDeleteBuilder,
+}
+
+impl BatchDelete {
+    /// Creates a builder for the deletion of `keys` in `bucket`
+    pub fn builder<I>(
+        client: crate::Client,
+        bucket: impl Into<String>,
+        keys: I,
+    ) -> BatchDeleteBuilder
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        I::IntoIter: Send + Sync + 'static,
+    {
+        BatchDeleteBuilder {
+            client,
+            bucket: bucket.into(),
+            keys: Box::new(keys.into_iter().map(Into::into)),
+            concurrency: DEFAULT_CONCURRENCY,
+            expected_bucket_owner: None,
+        }
+    }
+
+    /// Deletes the objects and collects the failures of all batches
+    pub async fn send(self) -> BatchDeleteOutput {
+        let BatchDeleteBuilder {
+            client,
+            bucket,
+            mut keys,
+            concurrency,
+            expected_bucket_owner,
+        } = self.inner;
+
+        let batches = std::iter::from_fn(move || {
+            let batch: Vec<String> = keys.by_ref().take(MAX_BATCH_SIZE).collect();
+            (!batch.is_empty()).then_some(batch)
+        });
+        let client = &client;
+        let bucket = &bucket;
+        let expected_bucket_owner = &expected_bucket_owner;
+        let results = stream::iter(batches)
+            .map(|batch| async move {
+                let objects = batch
+                    .iter()
+                    .map(|key| {
+                        ObjectIdentifier::builder()
+                            .key(key)
+                            .build()
+                            .expect("the key is set")
+                    })
+                    .collect();
+                let delete = Delete::builder()
+                    .set_objects(Some(objects))
+                    .quiet(true)
+                    .build()
+                    .expect("the objects are set");
+                let result = client
+                    .delete_objects()
+                    .bucket(bucket)
+                    .delete(delete)
+                    .set_expected_bucket_owner(expected_bucket_owner.clone())
+                    .send()
+                    .await;
+                (batch, result)
+            })
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut output = BatchDeleteOutput::default();
+        for (keys, result) in results {
+            match result {
+                Ok(response) => {
+                    let errors = response.errors();
+                    // S3 may report errors for the same key more than once, so don't let the
+                    // error count exceed the size of the batch
+                    output.deleted_count += keys.len().saturating_sub(errors.len()) as u64;
+                    output.errors.extend_from_slice(errors);
+                }
+                Err(error) => output.failed_batches.push(FailedBatch { keys, error }),
+            }
+        }
+        output
+    }
+}
+
+/// Batch of keys whose `DeleteObjects` request failed
+#[derive(Debug)]
+pub struct FailedBatch {
+    keys: Vec<String>,
+    error: SdkError<DeleteObjectsError, HttpResponse>,
+}
+
+impl FailedBatch {
+    /// Returns the keys of the batch, none of which were deleted by the batch
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Returns the error of the `DeleteObjects` request
+    pub fn error(&self) -> &SdkError<DeleteObjectsError, HttpResponse> {
+        &self.error
+    }
+
+    /// Consumes the batch and returns its keys and the error of its request
+    pub fn into_parts(self) -> (Vec<String>, SdkError<DeleteObjectsError, HttpResponse>) {
+        (self.keys, self.error)
+    }
+}
+
+/// Output of a [`BatchDelete`]
+#[derive(Debug, Default)]
+pub struct BatchDeleteOutput {
+    deleted_count: u64,
+    errors: Vec<crate::types::Error>,
+    failed_batches: Vec<FailedBatch>,
+}
+
+impl BatchDeleteOutput {
+    /// Returns the number of keys that S3 deleted
+    ///
+    /// Deleting a key that doesn't exist succeeds, so these keys are counted as well.
+    pub fn deleted_count(&self) -> u64 {
+        self.deleted_count
+    }
+
+    /// Returns the keys that S3 failed to delete, with the error code and message of each key
+    pub fn errors(&self) -> &[crate::types::Error] {
+        &self.errors
+    }
+
+    /// Returns the batches whose `DeleteObjects` request failed
+    pub fn failed_batches(&self) -> &[FailedBatch] {
+        &self.failed_batches
+    }
+
+    /// Returns true if every key was deleted
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty() && self.failed_batches.is_empty()
+    }
+}
@@ -0,0 +1,424 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeTransmitInterceptorContextMut, BeforeTransmitInterceptorContextRef,
+    FinalizerInterceptorContextMut, InterceptorContext,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::retries::{
+    RequestAttempts, RetryStrategy, SharedRetryStrategy, ShouldAttempt,
+};
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::{Order, RuntimePlugin};
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const BUCKET_REGION_HEADER: &str = "x-amz-bucket-region";
+
+/// Status codes of S3 responses that indicate that a bucket is in a different region
+///
+/// S3 responds with `301 PermanentRedirect` or `307 TemporaryRedirect` to most requests, with
+/// `400 AuthorizationHeaderMalformed` when the signing region is wrong, and with `403` to `HEAD`
+/// requests, which have no body to explain the error.
+const REDIRECT_STATUS_CODES: &[u16] = &[301, 307, 400, 403];
+
+const MAX_CACHED_BUCKETS: usize = 1000;
+
+/// Cache of the regions of buckets that S3 redirected requests to
+///
+/// Its presence in the config enables cross-region redirects. Clones share the same entries.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BucketRegionCache {
+    regions: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl BucketRegionCache {
+    fn get(&self, bucket: &str) -> Option<String> {
+        self.regions.lock().unwrap().get(bucket).cloned()
+    }
+
+    fn insert(&self, bucket: &str, region: &str) {
+        let mut regions = self.regions.lock().unwrap();
+        if regions.len() >= MAX_CACHED_BUCKETS && !regions.contains_key(bucket) {
+            regions.clear();
+        }
+        regions.insert(bucket.to_owned(), region.to_owned());
+    }
+}
+
+impl Storable for BucketRegionCache {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Marks a response that redirected the request to another region
+#[derive(Clone, Debug)]
+struct RedirectedToRegion;
+
+/// Marks an operation whose request was redirected to another region
+///
+/// A request is redirected at most once.
+#[derive(Clone, Debug)]
+struct Redirected;
+
+impl Storable for Redirected {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Interceptor that sends requests for buckets in other regions to the region indicated by S3
+///
+/// Known bucket regions are used before the first attempt. When S3 responds with the region of
+/// the bucket, the region endpoint parameter is changed so that the next attempt re-resolves the
+/// endpoint and re-signs the request for that region, and the response is marked for
+/// [`CrossRegionRedirectRetryStrategy`] to send the request again.
+///
+/// The redirected attempt doesn't count towards the max attempts of the retry config.
+#[derive(Debug)]
+pub(crate) struct CrossRegionRedirectInterceptor {
+    bucket: fn(&EndpointResolverParams) -> Option<&str>,
+    region: fn(&EndpointResolverParams) -> Option<&str>,
+    set_region: fn(&mut EndpointResolverParams, String),
+}
+
+impl CrossRegionRedirectInterceptor {
+    pub(crate) fn new(
+        bucket: fn(&EndpointResolverParams) -> Option<&str>,
+        region: fn(&EndpointResolverParams) -> Option<&str>,
+        set_region: fn(&mut EndpointResolverParams, String),
+    ) -> Self {
+        Self {
+            bucket,
+            region,
+            set_region,
+        }
+    }
+
+    /// Sets the region endpoint parameter to `region`, returning false if it already was `region`
+    fn redirect(&self, cfg: &mut ConfigBag, region: String) -> bool {
+        match cfg.get_mut_from_interceptor_state::<EndpointResolverParams>() {
+            Some(params) if (self.region)(params) != Some(region.as_str()) => {
+                (self.set_region)(params, region);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Intercept for CrossRegionRedirectInterceptor {
+    fn name(&self) -> &'static str {
+        "CrossRegionRedirectInterceptor"
+    }
+
+    fn modify_before_retry_loop(
+        &self,
+        _context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(cache) = cfg.load::<BucketRegionCache>() else {
+            return Ok(());
+        };
+        let cached_region = cfg
+            .load::<EndpointResolverParams>()
+            .and_then(|params| (self.bucket)(params))
+            .and_then(|bucket| cache.get(bucket));
+        if let Some(region) = cached_region {
+            self.redirect(cfg, region);
+        }
+        Ok(())
+    }
+
+    fn read_before_attempt(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if cfg.load::<Redirected>().is_some() {
+            if let Some(attempts) = cfg.load::<RequestAttempts>().map(|a| a.attempts()) {
+                cfg.interceptor_state()
+                    .store_put(RequestAttempts::new(attempts.saturating_sub(1)));
+            }
+        }
+        Ok(())
+    }
+
+    fn modify_before_attempt_completion(
+        &self,
+        context: &mut FinalizerInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(cache) = cfg.load::<BucketRegionCache>().cloned() else {
+            return Ok(());
+        };
+        if cfg.load::<Redirected>().is_some() {
+            return Ok(());
+        }
+        let Some(response) = context.response_mut() else {
+            return Ok(());
+        };
+        if !REDIRECT_STATUS_CODES.contains(&response.status().as_u16()) {
+            return Ok(());
+        }
+        let Some(region) = response.headers().get(BUCKET_REGION_HEADER) else {
+            return Ok(());
+        };
+        let region = region.to_owned();
+        let Some(bucket) = cfg
+            .load::<EndpointResolverParams>()
+            .and_then(|params| (self.bucket)(params))
+            .map(str::to_owned)
+        else {
+            return Ok(());
+        };
+
+        cache.insert(&bucket, &region);
+        if self.redirect(cfg, region.clone()) {
+            tracing::debug!(bucket = %bucket, region = %region, "redirecting request to the region of the bucket");
+            response.add_extension(RedirectedToRegion);
+            cfg.interceptor_state().store_put(Redirected);
+        }
+        Ok(())
+    }
+}
+
+/// Retry strategy that sends requests that [`CrossRegionRedirectInterceptor`] redirected to another
+/// region again, and defers to the wrapped retry strategy otherwise
+///
+/// The redirected request is sent immediately, without running the retry classifiers or taking
+/// retry quota, since the request failed because it was sent to the wrong region.
+#[derive(Debug)]
+struct CrossRegionRedirectRetryStrategy {
+    inner: SharedRetryStrategy,
+}
+
+impl RetryStrategy for CrossRegionRedirectRetryStrategy {
+    fn should_attempt_initial_request(
+        &self,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<ShouldAttempt, BoxError> {
+        self.inner
+            .should_attempt_initial_request(runtime_components, cfg)
+    }
+
+    fn should_attempt_retry(
+        &self,
+        context: &InterceptorContext,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<ShouldAttempt, BoxError> {
+        let redirected = context
+            .response()
+            .and_then(|response| response.extension::<RedirectedToRegion>())
+            .is_some();
+        if redirected {
+            Ok(ShouldAttempt::Yes)
+        } else {
+            self.inner
+                .should_attempt_retry(context, runtime_components, cfg)
+        }
+    }
+}
+
+/// Runtime plugin that wraps the configured retry strategy in [`CrossRegionRedirectRetryStrategy`]
+#[derive(Debug, Default)]
+pub(crate) struct CrossRegionRedirectRuntimePlugin;
+
+impl RuntimePlugin for CrossRegionRedirectRuntimePlugin {
+    fn order(&self) -> Order {
+        Order::NestedComponents
+    }
+
+    fn runtime_components(
+        &self,
+        current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        let retry_strategy = current_components
+            .retry_strategy()
+            .map(|inner| CrossRegionRedirectRetryStrategy { inner });
+        Cow::Owned(
+            RuntimeComponentsBuilder::new("CrossRegionRedirectRuntimePlugin")
+                .with_retry_strategy(retry_strategy),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        BucketRegionCache, CrossRegionRedirectInterceptor, CrossRegionRedirectRetryStrategy,
+    };
+    use aws_smithy_runtime::client::retries::strategy::NeverRetryStrategy;
+    use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+    use aws_smithy_runtime_api::client::interceptors::context::{
+        BeforeTransmitInterceptorContextRef, FinalizerInterceptorContextMut, Input,
+        InterceptorContext,
+    };
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+    use aws_smithy_runtime_api::client::retries::{
+        RequestAttempts, RetryStrategy, SharedRetryStrategy, ShouldAttempt,
+    };
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_runtime_api::http::StatusCode;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::{ConfigBag, Layer};
+
+    #[derive(Debug)]
+    struct Params {
+        bucket: &'static str,
+        region: String,
+    }
+
+    fn interceptor() -> CrossRegionRedirectInterceptor {
+        CrossRegionRedirectInterceptor::new(
+            |params| params.get::<Params>().map(|params| params.bucket),
+            |params| params.get::<Params>().map(|params| params.region.as_str()),
+            |params, region| {
+                if let Some(params) = params.get_mut::<Params>() {
+                    params.region = region;
+                }
+            },
+        )
+    }
+
+    fn config(cache: &BucketRegionCache) -> ConfigBag {
+        let mut layer = Layer::new("test");
+        layer.store_put(cache.clone());
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        cfg.interceptor_state()
+            .store_put(EndpointResolverParams::new(Params {
+                bucket: "bucket",
+                region: "us-east-1".into(),
+            }));
+        cfg
+    }
+
+    fn region(cfg: &ConfigBag) -> &str {
+        &cfg.load::<EndpointResolverParams>()
+            .unwrap()
+            .get::<Params>()
+            .unwrap()
+            .region
+    }
+
+    fn complete_attempt(
+        cfg: &mut ConfigBag,
+        status: u16,
+        bucket_region: Option<&str>,
+    ) -> InterceptorContext {
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        let mut response =
+            HttpResponse::new(StatusCode::try_from(status).unwrap(), SdkBody::empty());
+        if let Some(region) = bucket_region {
+            response
+                .headers_mut()
+                .insert("x-amz-bucket-region", region.to_owned());
+        }
+        context.set_response(response);
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        interceptor()
+            .modify_before_attempt_completion(
+                &mut FinalizerInterceptorContextMut::from(&mut context),
+                &rc,
+                cfg,
+            )
+            .unwrap();
+        context
+    }
+
+    /// Decides if the attempt in `context` is sent again, when the wrapped strategy never retries
+    fn should_attempt_retry(context: &InterceptorContext, cfg: &ConfigBag) -> ShouldAttempt {
+        let strategy = CrossRegionRedirectRetryStrategy {
+            inner: SharedRetryStrategy::new(NeverRetryStrategy::new()),
+        };
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        strategy.should_attempt_retry(context, &rc, cfg).unwrap()
+    }
+
+    #[test]
+    fn redirect_to_bucket_region() {
+        let cache = BucketRegionCache::default();
+        let mut cfg = config(&cache);
+        let context = complete_attempt(&mut cfg, 301, Some("eu-west-1"));
+
+        assert_eq!("eu-west-1", region(&cfg));
+        assert_eq!(Some("eu-west-1".to_owned()), cache.get("bucket"));
+        assert_eq!(ShouldAttempt::Yes, should_attempt_retry(&context, &cfg));
+    }
+
+    #[test]
+    fn dont_retry_when_already_in_bucket_region() {
+        let cache = BucketRegionCache::default();
+        let mut cfg = config(&cache);
+        let context = complete_attempt(&mut cfg, 301, Some("us-east-1"));
+
+        assert_eq!("us-east-1", region(&cfg));
+        assert_eq!(ShouldAttempt::No, should_attempt_retry(&context, &cfg));
+    }
+
+    #[test]
+    fn redirect_at_most_once() {
+        let cache = BucketRegionCache::default();
+        let mut cfg = config(&cache);
+        complete_attempt(&mut cfg, 301, Some("eu-west-1"));
+        let context = complete_attempt(&mut cfg, 301, Some("ap-south-1"));
+
+        assert_eq!("eu-west-1", region(&cfg));
+        assert_eq!(ShouldAttempt::No, should_attempt_retry(&context, &cfg));
+    }
+
+    #[test]
+    fn redirected_attempt_does_not_count_towards_max_attempts() {
+        let cache = BucketRegionCache::default();
+        let mut cfg = config(&cache);
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.set_request(HttpRequest::empty());
+
+        cfg.interceptor_state().store_put(RequestAttempts::new(1));
+        complete_attempt(&mut cfg, 301, Some("eu-west-1"));
+        cfg.interceptor_state().store_put(RequestAttempts::new(2));
+        interceptor()
+            .read_before_attempt(
+                &BeforeTransmitInterceptorContextRef::from(&context),
+                &rc,
+                &mut cfg,
+            )
+            .unwrap();
+
+        assert_eq!(1, cfg.load::<RequestAttempts>().unwrap().attempts());
+    }
+
+    #[test]
+    fn ignore_responses_without_redirect() {
+        let cache = BucketRegionCache::default();
+        let mut cfg = config(&cache);
+        complete_attempt(&mut cfg, 200, Some("eu-west-1"));
+        complete_attempt(&mut cfg, 301, None);
+
+        assert_eq!("us-east-1", region(&cfg));
+        assert_eq!(None, cache.get("bucket"));
+    }
+
+    #[test]
+    fn disabled_without_cache() {
+        let mut cfg = config(&BucketRegionCache::default());
+        cfg.interceptor_state().unset::<BucketRegionCache>();
+        let context = complete_attempt(&mut cfg, 301, Some("eu-west-1"));
+
+        assert_eq!("us-east-1", region(&cfg));
+        assert_eq!(ShouldAttempt::No, should_attempt_retry(&context, &cfg));
+    }
+}
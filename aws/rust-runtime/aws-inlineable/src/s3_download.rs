@@ -0,0 +1,544 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Downloads that validate the checksums of objects uploaded with multipart uploads.
+//!
+//! Objects uploaded with a multipart upload often have a composite checksum, a checksum of the
+//! checksums of their parts with a `-N` suffix for the number of parts. Such checksums can't be
+//! validated against the body of a single `GetObject` response, so [`Download`] downloads these
+//! objects part by part, validates the checksum of each part, and validates the composite checksum
+//! against the checksums of the parts.
+
+use crate::types::{ChecksumAlgorithm, ChecksumMode};
+use aws_smithy_checksums::{Checksum, ChecksumAlgorithm as ChecksumCalculator};
+use aws_smithy_runtime_api::box_error::BoxError;
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use std::error::Error;
+use std::fmt;
+
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Builder for a [`Download`]
+#[derive(Debug)]
+pub struct DownloadBuilder {
+    client: crate::Client,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    concurrency: usize,
+}
+
+impl DownloadBuilder {
+    /// Downloads the given version of the object instead of its current version
+    pub fn version_id(mut self, version_id: impl Into<String>) -> Self {
+        self.version_id = Some(version_id.into());
+        self
+    }
+
+    /// Sets the maximum number of parts that are downloaded at the same time
+    ///
+    /// Defaults to 8.
+    ///
+    /// # Panics
+    /// Panics if `concurrency` is zero.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        assert!(
+            concurrency > 0,
+            "at least one part must be downloaded at a time"
+        );
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Creates the download
+    pub fn build(self) -> Download {
+        Download { inner: self }
+    }
+}
+
+/// Download of an object that validates the checksums of its parts
+///
+/// The object is downloaded into memory. Objects uploaded with a multipart upload are downloaded
+/// with a `GetObject` request per part, and other objects with a single request. Every checksum
+/// that S3 stored for the object is validated, and the [`ChecksumValidationReport`] of the output
+/// describes which checksums were available. A checksum that doesn't match fails the download.
+///
+/// # Examples
+/// ```no_run
+/// # async fn example(client: aws_sdk_s3::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use aws_sdk_s3::download::{Download, ValidationStatus};
+///
+/// let output = Download::builder(client, "my-bucket", "large-file.bin")
+///     .build()
+///     .send()
+///     .await?;
+/// let report = output.checksum_validation();
+/// if report.full_object() == ValidationStatus::NotAvailable {
+///     println!("the object has no checksum");
+/// }
+/// println!("downloaded {} bytes", output.body().len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Download {
+    inner: DownloadBuilder,
+}
+
+impl Download {
+    /// Creates a builder for a download of the object `key` in `bucket`
+    pub fn builder(
+        client: crate::Client,
+        bucket: impl Into<String>,
+        key: impl Into<String>,
+    ) -> DownloadBuilder {
+        DownloadBuilder {
+            client,
+            bucket: bucket.into(),
+            key: key.into(),
+            version_id: None,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Downloads the object and validates its checksums
+    pub async fn send(self) -> Result<DownloadOutput, DownloadError> {
+        let DownloadBuilder {
+            client,
+            bucket,
+            key,
+            version_id,
+            concurrency,
+        } = self.inner;
+
+        let head = client
+            .head_object()
+            .bucket(&bucket)
+            .key(&key)
+            .set_version_id(version_id.clone())
+            .checksum_mode(ChecksumMode::Enabled)
+            .send()
+            .await
+            .map_err(|err| DownloadError::new(ErrorKind::HeadObject, err))?;
+        let object_checksum = Checksums {
+            crc64nvme: head.checksum_crc64_nvme(),
+            crc32c: head.checksum_crc32_c(),
+            crc32: head.checksum_crc32(),
+            sha1: head.checksum_sha1(),
+            sha256: head.checksum_sha256(),
+        }
+        .preferred();
+        // Parts only have their own checksums if the object has a composite checksum
+        let composite = object_checksum
+            .as_ref()
+            .and_then(|(_, value)| split_composite(value).1)
+            .is_some();
+        let part_count = head.e_tag().and_then(multipart_part_count);
+        // Pin the object to the version that was looked up
+        let version_id = version_id.or_else(|| head.version_id().map(str::to_owned));
+
+        let object_checksum_ref = &object_checksum;
+        let part_numbers: Vec<Option<i32>> = match part_count {
+            Some(count) => (1..=count).map(Some).collect(),
+            None => vec![None],
+        };
+        let parts = stream::iter(part_numbers)
+            .map(|part_number| {
+                let request = client
+                    .get_object()
+                    .bucket(&bucket)
+                    .key(&key)
+                    .set_version_id(version_id.clone())
+                    .set_if_match(head.e_tag().map(str::to_owned))
+                    .set_part_number(part_number)
+                    .set_checksum_mode(composite.then_some(ChecksumMode::Enabled));
+                async move {
+                    let error_kind = || ErrorKind::GetObject { part_number };
+                    let output = request
+                        .send()
+                        .await
+                        .map_err(|err| DownloadError::new(error_kind(), err))?;
+                    let checksum = Checksums {
+                        crc64nvme: output.checksum_crc64_nvme(),
+                        crc32c: output.checksum_crc32_c(),
+                        crc32: output.checksum_crc32(),
+                        sha1: output.checksum_sha1(),
+                        sha256: output.checksum_sha256(),
+                    };
+                    let checksum = object_checksum_ref
+                        .as_ref()
+                        .and_then(|(algorithm, _)| checksum.value(*algorithm))
+                        .map(str::to_owned);
+                    let body = output
+                        .body
+                        .collect()
+                        .await
+                        .map_err(|err| DownloadError::new(error_kind(), err))?
+                        .into_bytes();
+                    Ok::<_, DownloadError>((part_number, body, checksum))
+                }
+            })
+            .buffered(concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let Some((algorithm, expected)) = object_checksum else {
+            let report = ChecksumValidationReport {
+                algorithm: None,
+                full_object: ValidationStatus::NotAvailable,
+                parts: parts
+                    .iter()
+                    .filter_map(|(part_number, body, _)| {
+                        part_number.map(|part_number| PartValidation {
+                            part_number,
+                            size: body.len() as u64,
+                            status: ValidationStatus::NotAvailable,
+                        })
+                    })
+                    .collect(),
+            };
+            return Ok(DownloadOutput::new(head.e_tag, version_id, parts, report));
+        };
+
+        let mut part_validations = Vec::new();
+        let mut part_checksums = BytesMut::new();
+        let mut object_calculator = algorithm.into_impl();
+        for (part_number, body, part_checksum) in &parts {
+            let mut calculator = algorithm.into_impl();
+            calculator.update(body);
+            let calculated = calculator.finalize();
+            object_calculator.update(body);
+            part_checksums.extend_from_slice(&calculated);
+
+            let Some(part_number) = *part_number else {
+                continue;
+            };
+            let status = match part_checksum
+                .as_deref()
+                .filter(|_| composite)
+                .filter(|value| split_composite(value).1.is_none())
+            {
+                Some(expected) => {
+                    validate(Some(part_number), expected, &calculated)?;
+                    ValidationStatus::Validated
+                }
+                None => ValidationStatus::NotAvailable,
+            };
+            part_validations.push(PartValidation {
+                part_number,
+                size: body.len() as u64,
+                status,
+            });
+        }
+
+        let (expected, composite_part_count) = split_composite(&expected);
+        match composite_part_count {
+            Some(count) => {
+                if count != parts.len() as u64 || part_count.is_none() {
+                    return Err(DownloadError::new(
+                        ErrorKind::ChecksumMismatch { part_number: None },
+                        format!(
+                            "the composite checksum covers {count} parts, but {} parts were downloaded",
+                            parts.len()
+                        ),
+                    ));
+                }
+                let mut calculator = algorithm.into_impl();
+                calculator.update(&part_checksums);
+                validate(None, expected, &calculator.finalize())?;
+            }
+            None => validate(None, expected, &object_calculator.finalize())?,
+        }
+
+        let report = ChecksumValidationReport {
+            algorithm: Some(algorithm_name(algorithm)),
+            full_object: ValidationStatus::Validated,
+            parts: part_validations,
+        };
+        Ok(DownloadOutput::new(head.e_tag, version_id, parts, report))
+    }
+}
+
+/// Checksums of an object or part as returned by S3
+struct Checksums<'a> {
+    crc64nvme: Option<&'a str>,
+    crc32c: Option<&'a str>,
+    crc32: Option<&'a str>,
+    sha1: Option<&'a str>,
+    sha256: Option<&'a str>,
+}
+
+impl Checksums<'_> {
+    fn value(&self, algorithm: ChecksumCalculator) -> Option<&str> {
+        match algorithm {
+            ChecksumCalculator::Crc64Nvme => self.crc64nvme,
+            ChecksumCalculator::Crc32c => self.crc32c,
+            ChecksumCalculator::Crc32 => self.crc32,
+            ChecksumCalculator::Sha1 => self.sha1,
+            ChecksumCalculator::Sha256 => self.sha256,
+            _ => None,
+        }
+    }
+
+    /// Returns the checksum that is fastest to calculate
+    fn preferred(&self) -> Option<(ChecksumCalculator, String)> {
+        [
+            ChecksumCalculator::Crc64Nvme,
+            ChecksumCalculator::Crc32c,
+            ChecksumCalculator::Crc32,
+            ChecksumCalculator::Sha1,
+            ChecksumCalculator::Sha256,
+        ]
+        .into_iter()
+        .find_map(|algorithm| {
+            self.value(algorithm)
+                .map(|value| (algorithm, value.to_owned()))
+        })
+    }
+}
+
+fn algorithm_name(algorithm: ChecksumCalculator) -> ChecksumAlgorithm {
+    ChecksumAlgorithm::from(algorithm.as_str().to_ascii_uppercase().as_str())
+}
+
+/// Splits a checksum into its base64-encoded value and, for composite checksums, the number of parts
+fn split_composite(checksum: &str) -> (&str, Option<u64>) {
+    match checksum.rsplit_once('-') {
+        Some((value, count)) if !count.is_empty() && count.bytes().all(|b| b.is_ascii_digit()) => {
+            (value, count.parse().ok())
+        }
+        _ => (checksum, None),
+    }
+}
+
+/// Returns the number of parts of an object from the `-N` suffix of its entity tag
+fn multipart_part_count(e_tag: &str) -> Option<i32> {
+    let (_, count) = split_composite(e_tag.trim_matches('"'));
+    count
+        .and_then(|count| i32::try_from(count).ok())
+        .filter(|count| *count > 0)
+}
+
+fn validate(
+    part_number: Option<i32>,
+    expected: &str,
+    calculated: &[u8],
+) -> Result<(), DownloadError> {
+    let calculated = aws_smithy_types::base64::encode(calculated);
+    if expected == calculated {
+        Ok(())
+    } else {
+        Err(DownloadError::new(
+            ErrorKind::ChecksumMismatch { part_number },
+            format!("expected checksum `{expected}`, but calculated `{calculated}`"),
+        ))
+    }
+}
+
+/// Whether a checksum was validated
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationStatus {
+    /// The checksum stored by S3 matched the downloaded data
+    Validated,
+    /// S3 didn't return a checksum to validate
+    NotAvailable,
+}
+
+/// Validation of the checksum of one part of an object
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartValidation {
+    part_number: i32,
+    size: u64,
+    status: ValidationStatus,
+}
+
+impl PartValidation {
+    /// Returns the number of the part
+    pub fn part_number(&self) -> i32 {
+        self.part_number
+    }
+
+    /// Returns the size of the part in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns whether the checksum of the part was validated
+    pub fn status(&self) -> ValidationStatus {
+        self.status
+    }
+}
+
+/// Checksums that were validated by a [`Download`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChecksumValidationReport {
+    algorithm: Option<ChecksumAlgorithm>,
+    full_object: ValidationStatus,
+    parts: Vec<PartValidation>,
+}
+
+impl ChecksumValidationReport {
+    /// Returns the algorithm of the validated checksums, or `None` if the object has no checksum
+    pub fn algorithm(&self) -> Option<&ChecksumAlgorithm> {
+        self.algorithm.as_ref()
+    }
+
+    /// Returns whether the checksum of the whole object was validated
+    ///
+    /// For objects with a composite checksum, this is the checksum of the checksums of the parts.
+    pub fn full_object(&self) -> ValidationStatus {
+        self.full_object
+    }
+
+    /// Returns the validations of the parts of objects uploaded with a multipart upload
+    ///
+    /// Empty for objects that weren't uploaded with a multipart upload. Parts only have checksums
+    /// of their own if the object has a composite checksum.
+    pub fn parts(&self) -> &[PartValidation] {
+        &self.parts
+    }
+}
+
+/// Output of a [`Download`]
+#[derive(Clone, Debug)]
+pub struct DownloadOutput {
+    body: Bytes,
+    e_tag: Option<String>,
+    version_id: Option<String>,
+    checksum_validation: ChecksumValidationReport,
+}
+
+impl DownloadOutput {
+    fn new(
+        e_tag: Option<String>,
+        version_id: Option<String>,
+        parts: Vec<(Option<i32>, Bytes, Option<String>)>,
+        checksum_validation: ChecksumValidationReport,
+    ) -> Self {
+        let body = match <[_; 1]>::try_from(parts) {
+            Ok([(_, body, _)]) => body,
+            Err(parts) => {
+                let mut body = BytesMut::new();
+                for (_, part, _) in parts {
+                    body.extend_from_slice(&part);
+                }
+                body.freeze()
+            }
+        };
+        Self {
+            body,
+            e_tag,
+            version_id,
+            checksum_validation,
+        }
+    }
+
+    /// Returns the body of the object
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Consumes the output, returning the body of the object
+    pub fn into_body(self) -> Bytes {
+        self.body
+    }
+
+    /// Returns the entity tag of the object
+    pub fn e_tag(&self) -> Option<&str> {
+        self.e_tag.as_deref()
+    }
+
+    /// Returns the version ID of the object if the bucket is versioned
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    /// Returns the checksums that were validated
+    pub fn checksum_validation(&self) -> &ChecksumValidationReport {
+        &self.checksum_validation
+    }
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    HeadObject,
+    GetObject { part_number: Option<i32> },
+    ChecksumMismatch { part_number: Option<i32> },
+}
+
+/// Error returned when a [`Download`] fails
+#[derive(Debug)]
+pub struct DownloadError {
+    kind: ErrorKind,
+    source: BoxError,
+}
+
+impl DownloadError {
+    fn new(kind: ErrorKind, source: impl Into<BoxError>) -> Self {
+        Self {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    /// Returns true if the download failed because a checksum didn't match the downloaded data
+    pub fn is_checksum_mismatch(&self) -> bool {
+        matches!(self.kind, ErrorKind::ChecksumMismatch { .. })
+    }
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::HeadObject => write!(f, "failed to look up the object"),
+            ErrorKind::GetObject { part_number: None } => {
+                write!(f, "failed to download the object")
+            }
+            ErrorKind::GetObject {
+                part_number: Some(part_number),
+            } => write!(f, "failed to download part {part_number}"),
+            ErrorKind::ChecksumMismatch { part_number: None } => {
+                write!(f, "the checksum of the object doesn't match")
+            }
+            ErrorKind::ChecksumMismatch {
+                part_number: Some(part_number),
+            } => write!(f, "the checksum of part {part_number} doesn't match"),
+        }
+    }
+}
+
+impl Error for DownloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{multipart_part_count, split_composite};
+
+    #[test]
+    fn composite_checksums_are_split() {
+        assert_eq!(("wpn7tg==", Some(2)), split_composite("wpn7tg==-2"));
+        assert_eq!(("wpn7tg==", None), split_composite("wpn7tg=="));
+        assert_eq!(("wpn7tg==-", None), split_composite("wpn7tg==-"));
+        assert_eq!(("wpn7tg==-AA", None), split_composite("wpn7tg==-AA"));
+    }
+
+    #[test]
+    fn part_count_from_e_tag() {
+        assert_eq!(
+            Some(14),
+            multipart_part_count("\"c9a5a6878d97b48cc965c1e41859f034-14\"")
+        );
+        assert_eq!(
+            None,
+            multipart_part_count("\"c9a5a6878d97b48cc965c1e41859f034\"")
+        );
+        assert_eq!(None, multipart_part_count("\"-0\""));
+    }
+}
@@ -48,9 +48,12 @@ pub(crate) mod auth {
 pub(crate) mod identity_cache {
     use aws_credential_types::Credentials;
     use aws_smithy_async::time::SharedTimeSource;
+    use aws_smithy_observability::global::get_telemetry_provider;
+    use aws_smithy_observability::instruments::MonotonicCounter;
     use aws_smithy_runtime::expiring_cache::ExpiringCache;
     use aws_smithy_runtime_api::box_error::BoxError;
     use aws_smithy_runtime_api::client::identity::Identity;
+    use aws_smithy_types::config_bag::{Storable, StoreReplace};
     use aws_smithy_types::DateTime;
     use fastrand::Rng;
     use hmac::{digest::FixedOutput, Hmac, Mac};
@@ -60,25 +63,199 @@ pub(crate) mod identity_cache {
     use std::future::Future;
     use std::hash::Hash;
     use std::num::NonZeroUsize;
-    use std::sync::Mutex;
+    use std::sync::{Arc, Mutex};
     use std::time::{Duration, SystemTime};
 
     pub(crate) const DEFAULT_MAX_CACHE_CAPACITY: usize = 100;
     pub(crate) const DEFAULT_BUFFER_TIME: Duration = Duration::from_secs(10);
 
+    const METER_SCOPE: &str = "aws-sdk-s3";
+
+    /// Length of the hex-encoded HMAC-SHA256 digest that prefixes the bucket name in a [`CacheKey`]
+    const KEY_DIGEST_LEN: usize = 64;
+
+    /// How the S3 Express session credentials cache chooses the session to evict when it is full
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    pub enum S3ExpressSessionEvictionPolicy {
+        /// Evicts the session that was used least recently
+        #[default]
+        LeastRecentlyUsed,
+        /// Evicts the session that was created first, regardless of how recently it was used
+        FirstInFirstOut,
+    }
+
+    /// Configuration of the cache of S3 Express session credentials
+    ///
+    /// The default S3 Express identity provider creates a session for each directory bucket and
+    /// caches its credentials, per bucket and base credentials, until they are about to expire.
+    /// Workloads that spread requests across more directory buckets than the cache holds will
+    /// create sessions over and over, which can be avoided by raising [`max_entries`](Self::with_max_entries).
+    ///
+    /// Cache hits, misses, and evictions are recorded with the `aws.s3.express.session_cache.hits`,
+    /// `aws.s3.express.session_cache.misses`, and `aws.s3.express.session_cache.evictions` counters
+    /// of the meter provider of the global telemetry provider.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use aws_sdk_s3::config::{S3ExpressSessionCacheConfig, S3ExpressSessionEvictionPolicy};
+    /// use std::time::Duration;
+    ///
+    /// let cache_config = S3ExpressSessionCacheConfig::new()
+    ///     .with_max_entries(1000)
+    ///     .with_eviction_policy(S3ExpressSessionEvictionPolicy::FirstInFirstOut)
+    ///     .with_refresh_ahead(Duration::from_secs(60));
+    /// let config = aws_sdk_s3::Config::builder()
+    ///     .s3_express_session_cache_config(cache_config)
+    ///     .build();
+    /// ```
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct S3ExpressSessionCacheConfig {
+        max_entries: NonZeroUsize,
+        eviction_policy: S3ExpressSessionEvictionPolicy,
+        refresh_ahead: Duration,
+    }
+
+    impl Default for S3ExpressSessionCacheConfig {
+        fn default() -> Self {
+            Self {
+                max_entries: NonZeroUsize::new(DEFAULT_MAX_CACHE_CAPACITY)
+                    .expect("default capacity is not zero"),
+                eviction_policy: S3ExpressSessionEvictionPolicy::default(),
+                refresh_ahead: DEFAULT_BUFFER_TIME,
+            }
+        }
+    }
+
+    impl S3ExpressSessionCacheConfig {
+        /// Creates a cache config with the default settings
+        ///
+        /// The cache holds up to 100 sessions, evicts the least recently used session when it is
+        /// full, and refreshes sessions 10 seconds before they expire.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Sets the maximum number of sessions held by the cache
+        ///
+        /// The cache always holds at least one session, so a `max_entries` of zero is treated as one.
+        pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+            self.max_entries = NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::MIN);
+            self
+        }
+
+        /// Sets how the cache chooses the session to evict when it is full
+        pub fn with_eviction_policy(
+            mut self,
+            eviction_policy: S3ExpressSessionEvictionPolicy,
+        ) -> Self {
+            self.eviction_policy = eviction_policy;
+            self
+        }
+
+        /// Sets how long before their expiration cached sessions are replaced by new sessions
+        pub fn with_refresh_ahead(mut self, refresh_ahead: Duration) -> Self {
+            self.refresh_ahead = refresh_ahead;
+            self
+        }
+
+        /// Returns the maximum number of sessions held by the cache
+        pub fn max_entries(&self) -> usize {
+            self.max_entries.get()
+        }
+
+        /// Returns how the cache chooses the session to evict when it is full
+        pub fn eviction_policy(&self) -> S3ExpressSessionEvictionPolicy {
+            self.eviction_policy
+        }
+
+        /// Returns how long before their expiration cached sessions are replaced by new sessions
+        pub fn refresh_ahead(&self) -> Duration {
+            self.refresh_ahead
+        }
+    }
+
+    impl Storable for S3ExpressSessionCacheConfig {
+        type Storer = StoreReplace<Self>;
+    }
+
+    /// Counters of the S3 Express identity cache
+    #[derive(Clone, Debug)]
+    struct CacheMetrics {
+        hits: Arc<dyn MonotonicCounter>,
+        misses: Arc<dyn MonotonicCounter>,
+        evictions: Arc<dyn MonotonicCounter>,
+    }
+
+    impl CacheMetrics {
+        fn new() -> Option<Self> {
+            let meter = get_telemetry_provider()
+                .ok()?
+                .meter_provider()
+                .get_meter(METER_SCOPE, None);
+            Some(Self {
+                hits: meter
+                    .create_monotonic_counter("aws.s3.express.session_cache.hits")
+                    .set_description("Number of S3 Express session credentials served from the cache")
+                    .build(),
+                misses: meter
+                    .create_monotonic_counter("aws.s3.express.session_cache.misses")
+                    .set_description("Number of S3 Express sessions created because no valid session credentials were cached")
+                    .build(),
+                evictions: meter
+                    .create_monotonic_counter("aws.s3.express.session_cache.evictions")
+                    .set_description("Number of S3 Express sessions evicted from the cache because it was full")
+                    .build(),
+            })
+        }
+    }
+
     #[derive(Clone, Eq, PartialEq, Hash)]
     pub(crate) struct CacheKey(String);
 
+    impl CacheKey {
+        fn bucket_name(&self) -> &str {
+            &self.0[KEY_DIGEST_LEN..]
+        }
+    }
+
     /// The caching implementation for S3 Express identity.
     ///
-    /// While customers can either disable S3 Express itself or provide a custom S3 Express identity
-    /// provider, configuring S3 Express identity cache is not supported. Thus, this is _the_
-    /// implementation of S3 Express identity cache.
+    /// Customers can either disable S3 Express itself or provide a custom S3 Express identity
+    /// provider. Otherwise, this is _the_ implementation of S3 Express identity cache, configured
+    /// with [`S3ExpressSessionCacheConfig`].
     pub(crate) struct S3ExpressIdentityCache {
         inner: Mutex<LruCache<CacheKey, ExpiringCache<Identity, BoxError>>>,
         time_source: SharedTimeSource,
         buffer_time: Duration,
+        eviction_policy: S3ExpressSessionEvictionPolicy,
         random_bytes: [u8; 64],
+        metrics: Option<CacheMetrics>,
+    }
+
+    /// [`S3ExpressIdentityCache`] shared by the default S3 Express identity provider of a client
+    ///
+    /// The S3 Express runtime plugin stores it in the config bag, so that operations can invalidate
+    /// sessions that S3 rejected.
+    #[derive(Clone, Debug)]
+    pub(crate) struct SharedS3ExpressIdentityCache(Arc<S3ExpressIdentityCache>);
+
+    impl SharedS3ExpressIdentityCache {
+        pub(crate) fn new(cache: S3ExpressIdentityCache) -> Self {
+            Self(Arc::new(cache))
+        }
+    }
+
+    impl std::ops::Deref for SharedS3ExpressIdentityCache {
+        type Target = S3ExpressIdentityCache;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl Storable for SharedS3ExpressIdentityCache {
+        type Storer = StoreReplace<Self>;
     }
 
     impl fmt::Debug for S3ExpressIdentityCache {
@@ -89,8 +266,8 @@ pub(crate) mod identity_cache {
             };
             write!(
                 f,
-                "S3ExpressIdentityCache {{ time_source: {:?}, buffer_time: {:?} }}, with size/capacity: {}/{}",
-                self.time_source, &self.buffer_time, size, capacity,
+                "S3ExpressIdentityCache {{ time_source: {:?}, buffer_time: {:?}, eviction_policy: {:?} }}, with size/capacity: {}/{}",
+                self.time_source, &self.buffer_time, self.eviction_policy, size, capacity,
             )
         }
     }
@@ -100,6 +277,18 @@ pub(crate) mod identity_cache {
             capacity: usize,
             time_source: SharedTimeSource,
             buffer_time: Duration,
+        ) -> Self {
+            Self::from_config(
+                &S3ExpressSessionCacheConfig::new()
+                    .with_max_entries(capacity)
+                    .with_refresh_ahead(buffer_time),
+                time_source,
+            )
+        }
+
+        pub(crate) fn from_config(
+            config: &S3ExpressSessionCacheConfig,
+            time_source: SharedTimeSource,
         ) -> Self {
             // It'd be nice to use a cryptographically secure random generator but not necessary.
             // The cache is memory only and randomization here is mostly to obfuscate the key and
@@ -108,10 +297,12 @@ pub(crate) mod identity_cache {
             let mut random_bytes = [0u8; 64];
             rng.fill(&mut random_bytes);
             Self {
-                inner: Mutex::new(LruCache::new(NonZeroUsize::new(capacity).unwrap())),
+                inner: Mutex::new(LruCache::new(config.max_entries)),
                 time_source,
-                buffer_time,
+                buffer_time: config.refresh_ahead,
+                eviction_policy: config.eviction_policy,
                 random_bytes,
+                metrics: CacheMetrics::new(),
             }
         }
 
@@ -127,6 +318,45 @@ pub(crate) mod identity_cache {
             })
         }
 
+        /// Removes the cached sessions of `bucket_name`, regardless of their base credentials
+        ///
+        /// S3 may reject a session before its expiration, e.g. once it rotated the session. The
+        /// next request to the bucket then creates a new session instead of reusing the rejected one.
+        pub(crate) fn invalidate_bucket(&self, bucket_name: &str) {
+            let mut inner = self.inner.lock().unwrap();
+            let keys: Vec<CacheKey> = inner
+                .iter()
+                .map(|(key, _)| key)
+                .filter(|key| key.bucket_name() == bucket_name)
+                .cloned()
+                .collect();
+            for key in keys {
+                tracing::debug!(bucket_name, "invalidated S3 Express session");
+                inner.pop(&key);
+            }
+        }
+
+        /// Returns the cache entry for `key`, inserting an empty entry if there is none
+        fn entry(&self, key: CacheKey) -> ExpiringCache<Identity, BoxError> {
+            let mut inner = self.inner.lock().unwrap();
+            let existing = match self.eviction_policy {
+                // Looking an entry up promotes it to the most recently used entry.
+                S3ExpressSessionEvictionPolicy::LeastRecentlyUsed => inner.get(&key),
+                // Entries keep their insertion order, so that the oldest entry is evicted first.
+                S3ExpressSessionEvictionPolicy::FirstInFirstOut => inner.peek(&key),
+            };
+            if let Some(existing) = existing {
+                return existing.clone();
+            }
+            let entry = ExpiringCache::new(self.buffer_time);
+            if inner.push(key, entry.clone()).is_some() {
+                if let Some(metrics) = &self.metrics {
+                    metrics.evictions.add(1, None, None);
+                }
+            }
+            entry
+        }
+
         pub(crate) async fn get_or_load<F, Fut>(
             &self,
             key: CacheKey,
@@ -136,12 +366,7 @@ pub(crate) mod identity_cache {
             F: FnOnce() -> Fut,
             Fut: Future<Output = Result<(Identity, SystemTime), BoxError>>,
         {
-            let expiring_cache = {
-                let mut inner = self.inner.lock().unwrap();
-                inner
-                    .get_or_insert_mut(key, || ExpiringCache::new(self.buffer_time))
-                    .clone()
-            };
+            let expiring_cache = self.entry(key);
 
             let now = self.time_source.now();
 
@@ -153,9 +378,15 @@ pub(crate) mod identity_cache {
                         now=?now,
                         "loaded identity from cache"
                     );
+                    if let Some(metrics) = &self.metrics {
+                        metrics.hits.add(1, None, None);
+                    }
                     Ok(identity)
                 }
                 None => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.misses.add(1, None, None);
+                    }
                     let start_time = self.time_source.now();
                     let identity = expiring_cache.get_or_load(loader).await?;
                     let expiration = identity
@@ -437,13 +668,146 @@ pub(crate) mod identity_cache {
             })
             .await;
         }
+
+        #[tokio::test]
+        async fn identity_fetch_triggered_by_fifo_eviction() {
+            let time = ManualTimeSource::new(UNIX_EPOCH);
+            let runtime_components = RuntimeComponentsBuilder::for_tests()
+                .with_time_source(Some(time.clone()))
+                .with_sleep_impl(Some(TokioSleep::new()))
+                .build()
+                .unwrap();
+
+            let sut = S3ExpressIdentityCache::from_config(
+                &S3ExpressSessionCacheConfig::new()
+                    .with_max_entries(2)
+                    .with_eviction_policy(S3ExpressSessionEvictionPolicy::FirstInFirstOut),
+                time.into_shared(),
+            );
+
+            let identity_resolver = test_identity_resolver(vec![
+                Ok(identity_expiring_in(1000)),
+                Ok(identity_expiring_in(2000)),
+                Ok(identity_expiring_in(3000)),
+                Ok(identity_expiring_in(4000)),
+            ]);
+
+            let [key1, key2, key3] = [1, 2, 3].map(|i| {
+                sut.key(
+                    &format!("test-bucket-{i}--usw2-az1--x-s3"),
+                    &Credentials::for_tests_with_session_token(),
+                )
+            });
+
+            for (expiration, key) in [(1000, key1.clone()), (2000, key2.clone())] {
+                expect_identity(expiration, &sut, key, || {
+                    let identity_resolver = identity_resolver.clone();
+                    let runtime_components = runtime_components.clone();
+                    async move { load(identity_resolver, &runtime_components).await }
+                })
+                .await;
+            }
+            // Using `key1` doesn't keep it from being evicted first.
+            expect_identity(1000, &sut, key1.clone(), || async move {
+                panic!("new identity should not be loaded")
+            })
+            .await;
+
+            // This should populate a cache entry for `key3`, evicting the oldest entry for `key1`.
+            expect_identity(3000, &sut, key3, || {
+                let identity_resolver = identity_resolver.clone();
+                let runtime_components = runtime_components.clone();
+                async move { load(identity_resolver, &runtime_components).await }
+            })
+            .await;
+
+            expect_identity(2000, &sut, key2, || async move {
+                panic!("new identity should not be loaded")
+            })
+            .await;
+            expect_identity(4000, &sut, key1, || async move {
+                load(identity_resolver, &runtime_components).await
+            })
+            .await;
+        }
+
+        #[tokio::test]
+        async fn invalidate_bucket_removes_sessions_of_bucket_only() {
+            let time = ManualTimeSource::new(UNIX_EPOCH);
+            let runtime_components = RuntimeComponentsBuilder::for_tests()
+                .with_time_source(Some(time.clone()))
+                .with_sleep_impl(Some(TokioSleep::new()))
+                .build()
+                .unwrap();
+
+            let sut = S3ExpressIdentityCache::new(10, time.into_shared(), DEFAULT_BUFFER_TIME);
+
+            let identity_resolver = test_identity_resolver(vec![
+                Ok(identity_expiring_in(1000)),
+                Ok(identity_expiring_in(2000)),
+                Ok(identity_expiring_in(3000)),
+            ]);
+
+            let [key1, key2] = [1, 2].map(|i| {
+                sut.key(
+                    &format!("test-bucket-{i}--usw2-az1--x-s3"),
+                    &Credentials::for_tests_with_session_token(),
+                )
+            });
+
+            for (expiration, key) in [(1000, key1.clone()), (2000, key2.clone())] {
+                expect_identity(expiration, &sut, key, || {
+                    let identity_resolver = identity_resolver.clone();
+                    let runtime_components = runtime_components.clone();
+                    async move { load(identity_resolver, &runtime_components).await }
+                })
+                .await;
+            }
+
+            sut.invalidate_bucket("test-bucket-1--usw2-az1--x-s3");
+            // A bucket whose name has the invalidated name as a suffix keeps its session.
+            sut.invalidate_bucket("bucket-2--usw2-az1--x-s3");
+
+            // The session of the invalidated bucket is replaced by a new session.
+            expect_identity(3000, &sut, key1, || async move {
+                load(identity_resolver, &runtime_components).await
+            })
+            .await;
+            expect_identity(2000, &sut, key2, || async move {
+                panic!("new identity should not be loaded")
+            })
+            .await;
+        }
+
+        #[test]
+        fn session_cache_config_defaults() {
+            let config = S3ExpressSessionCacheConfig::new();
+            assert_eq!(DEFAULT_MAX_CACHE_CAPACITY, config.max_entries());
+            assert_eq!(DEFAULT_BUFFER_TIME, config.refresh_ahead());
+            assert_eq!(
+                S3ExpressSessionEvictionPolicy::LeastRecentlyUsed,
+                config.eviction_policy()
+            );
+        }
+
+        #[test]
+        fn session_cache_config_clamps_zero_entries() {
+            assert_eq!(
+                1,
+                S3ExpressSessionCacheConfig::new()
+                    .with_max_entries(0)
+                    .max_entries()
+            );
+        }
     }
 }
 /// Supporting code for S3 Express identity provider
 pub(crate) mod identity_provider {
-    use std::time::{Duration, SystemTime};
+    use std::time::SystemTime;
 
-    use crate::s3_express::identity_cache::S3ExpressIdentityCache;
+    use crate::s3_express::identity_cache::{
+        S3ExpressIdentityCache, S3ExpressSessionCacheConfig, SharedS3ExpressIdentityCache,
+    };
     use crate::types::SessionCredentials;
     use aws_credential_types::credential_feature::AwsCredentialFeature;
     use aws_credential_types::provider::error::CredentialsError;
@@ -461,12 +825,10 @@ pub(crate) mod identity_provider {
     use aws_smithy_runtime_api::shared::IntoShared;
     use aws_smithy_types::config_bag::ConfigBag;
 
-    use super::identity_cache::{DEFAULT_BUFFER_TIME, DEFAULT_MAX_CACHE_CAPACITY};
-
     #[derive(Debug)]
     pub(crate) struct DefaultS3ExpressIdentityProvider {
         behavior_version: crate::config::BehaviorVersion,
-        cache: S3ExpressIdentityCache,
+        cache: SharedS3ExpressIdentityCache,
     }
 
     impl TryFrom<SessionCredentials> for Credentials {
@@ -492,6 +854,11 @@ pub(crate) mod identity_provider {
             Builder::default()
         }
 
+        /// Returns the cache of the sessions created by this provider
+        pub(crate) fn session_cache(&self) -> SharedS3ExpressIdentityCache {
+            self.cache.clone()
+        }
+
         async fn identity<'a>(
             &'a self,
             runtime_components: &'a RuntimeComponents,
@@ -566,7 +933,7 @@ pub(crate) mod identity_provider {
     pub(crate) struct Builder {
         behavior_version: Option<crate::config::BehaviorVersion>,
         time_source: Option<SharedTimeSource>,
-        buffer_time: Option<Duration>,
+        session_cache_config: Option<S3ExpressSessionCacheConfig>,
     }
 
     impl Builder {
@@ -593,13 +960,18 @@ pub(crate) mod identity_provider {
             self
         }
         #[allow(dead_code)]
-        pub(crate) fn buffer_time(mut self, buffer_time: Duration) -> Self {
-            self.set_buffer_time(Some(buffer_time));
+        pub(crate) fn session_cache_config(
+            mut self,
+            session_cache_config: S3ExpressSessionCacheConfig,
+        ) -> Self {
+            self.set_session_cache_config(Some(session_cache_config));
             self
         }
-        #[allow(dead_code)]
-        pub(crate) fn set_buffer_time(&mut self, buffer_time: Option<Duration>) -> &mut Self {
-            self.buffer_time = buffer_time;
+        pub(crate) fn set_session_cache_config(
+            &mut self,
+            session_cache_config: Option<S3ExpressSessionCacheConfig>,
+        ) -> &mut Self {
+            self.session_cache_config = session_cache_config;
             self
         }
         pub(crate) fn build(self) -> DefaultS3ExpressIdentityProvider {
@@ -607,11 +979,10 @@ pub(crate) mod identity_provider {
                 behavior_version: self
                     .behavior_version
                     .expect("required field `behavior_version` should be set"),
-                cache: S3ExpressIdentityCache::new(
-                    DEFAULT_MAX_CACHE_CAPACITY,
+                cache: SharedS3ExpressIdentityCache::new(S3ExpressIdentityCache::from_config(
+                    &self.session_cache_config.unwrap_or_default(),
                     self.time_source.unwrap_or_default(),
-                    self.buffer_time.unwrap_or(DEFAULT_BUFFER_TIME),
-                ),
+                )),
             }
         }
     }
@@ -779,6 +1150,8 @@ pub(crate) mod identity_provider {
 pub(crate) mod runtime_plugin {
     use std::borrow::Cow;
 
+    use super::identity_cache::SharedS3ExpressIdentityCache;
+    use super::identity_provider::DefaultS3ExpressIdentityProvider;
     use aws_runtime::auth::SigV4SessionTokenNameOverride;
     use aws_sigv4::http_request::{SignatureLocation, SigningSettings};
     use aws_smithy_runtime_api::{
@@ -808,24 +1181,30 @@ pub(crate) mod runtime_plugin {
         }
 
         fn new_with(service_config: crate::config::Config, env: Env) -> Self {
+            let identity_provider = default_identity_provider(&service_config);
             Self {
                 config: config(
                     service_config
                         .config
                         .load::<crate::config::DisableS3ExpressSessionAuth>()
                         .cloned(),
+                    identity_provider
+                        .as_ref()
+                        .map(DefaultS3ExpressIdentityProvider::session_cache),
                     env,
                 ),
-                runtime_components_builder: runtime_components_builder(service_config),
+                runtime_components_builder: runtime_components_builder(identity_provider),
             }
         }
     }
 
     fn config(
         disable_s3_express_session_token: Option<crate::config::DisableS3ExpressSessionAuth>,
+        session_cache: Option<SharedS3ExpressIdentityCache>,
         env: Env,
     ) -> FrozenLayer {
         let mut layer = Layer::new("S3ExpressRuntimePlugin");
+        layer.store_or_unset(session_cache);
         if disable_s3_express_session_token.is_none() {
             match env.get(env::S3_DISABLE_EXPRESS_SESSION_AUTH) {
                 Ok(value)
@@ -876,9 +1255,11 @@ pub(crate) mod runtime_plugin {
         layer.freeze()
     }
 
-    fn runtime_components_builder(
-        service_config: crate::config::Config,
-    ) -> RuntimeComponentsBuilder {
+    /// Returns the default S3 Express identity provider, unless the identity provider is disabled
+    /// or replaced by `service_config`
+    fn default_identity_provider(
+        service_config: &crate::config::Config,
+    ) -> Option<DefaultS3ExpressIdentityProvider> {
         match (
             service_config
                 .runtime_components
@@ -887,25 +1268,39 @@ pub(crate) mod runtime_plugin {
                 .runtime_components
                 .identity_resolver(&aws_runtime::auth::sigv4::SCHEME_ID),
         ) {
-            (None, Some(_)) => RuntimeComponentsBuilder::new("S3ExpressRuntimePlugin")
-                .with_identity_resolver(
-                    super::auth::SCHEME_ID,
-                    super::identity_provider::DefaultS3ExpressIdentityProvider::builder()
-                        .time_source(
-                            service_config
-                                .runtime_components
-                                .time_source()
-                                .expect("should be set in `service_config`"),
-                        )
-                        .behavior_version(
-                            service_config
-                                .behavior_version
-                                .expect("should be set in `service_config`"),
-                        )
-                        .build(),
-                ),
-            _ => RuntimeComponentsBuilder::new("S3ExpressRuntimePlugin"),
+            (None, Some(_)) => {
+                let mut provider_builder = DefaultS3ExpressIdentityProvider::builder()
+                    .time_source(
+                        service_config
+                            .runtime_components
+                            .time_source()
+                            .expect("should be set in `service_config`"),
+                    )
+                    .behavior_version(
+                        service_config
+                            .behavior_version
+                            .expect("should be set in `service_config`"),
+                    );
+                provider_builder.set_session_cache_config(
+                    service_config
+                        .config
+                        .load::<crate::config::S3ExpressSessionCacheConfig>()
+                        .cloned(),
+                );
+                Some(provider_builder.build())
+            }
+            _ => None,
+        }
+    }
+
+    fn runtime_components_builder(
+        identity_provider: Option<DefaultS3ExpressIdentityProvider>,
+    ) -> RuntimeComponentsBuilder {
+        let mut builder = RuntimeComponentsBuilder::new("S3ExpressRuntimePlugin");
+        if let Some(identity_provider) = identity_provider {
+            builder.set_identity_resolver(super::auth::SCHEME_ID, identity_provider);
         }
+        builder
     }
 
     impl RuntimePlugin for S3ExpressRuntimePlugin {
@@ -936,6 +1331,7 @@ pub(crate) mod runtime_plugin {
             // but it will be overruled by what is in `layer`.
             let actual = config(
                 Some(disable_s3_express_session_token),
+                None,
                 Env::from_slice(&[(super::env::S3_DISABLE_EXPRESS_SESSION_AUTH, "false")]),
             );
 
@@ -950,6 +1346,7 @@ pub(crate) mod runtime_plugin {
         fn disable_option_set_from_env_should_take_the_second_highest_precedence() {
             // Disable option is set from environment variable.
             let actual = config(
+                None,
                 None,
                 Env::from_slice(&[(super::env::S3_DISABLE_EXPRESS_SESSION_AUTH, "true")]),
             );
@@ -972,7 +1369,7 @@ pub(crate) mod runtime_plugin {
         #[test]
         fn disable_option_should_be_unspecified_if_unset() {
             // Disable option is not set anywhere.
-            let actual = config(None, Env::from_slice(&[]));
+            let actual = config(None, None, Env::from_slice(&[]));
 
             // The config layer should not provide `DisableS3ExpressSessionAuth` when it's not configured.
             assert!(actual
@@ -989,7 +1386,7 @@ pub(crate) mod runtime_plugin {
                 .credentials_provider(Credentials::for_tests())
                 .build();
 
-            let actual = runtime_components_builder(config);
+            let actual = runtime_components_builder(default_identity_provider(&config));
             // The runtime plugin should provide a default S3 Express identity resolver.
             assert!(actual
                 .identity_resolver(&crate::s3_express::auth::SCHEME_ID)
@@ -1004,7 +1401,7 @@ pub(crate) mod runtime_plugin {
                 .time_source(aws_smithy_async::time::SystemTimeSource::new())
                 .build();
 
-            let actual = runtime_components_builder(config);
+            let actual = runtime_components_builder(default_identity_provider(&config));
             // The runtime plugin should not provide S3 Express identity resolver without SigV4 credentials.
             assert!(actual
                 .identity_resolver(&crate::s3_express::auth::SCHEME_ID)
@@ -1029,7 +1426,8 @@ pub(crate) mod runtime_plugin {
                 .build();
 
             // The runtime plugin should not override the user-provided identity resolver.
-            let runtime_components_builder = runtime_components_builder(config.clone());
+            let runtime_components_builder =
+                runtime_components_builder(default_identity_provider(&config));
             assert!(runtime_components_builder
                 .identity_resolver(&crate::s3_express::auth::SCHEME_ID)
                 .is_none());
@@ -1055,6 +1453,108 @@ pub(crate) mod runtime_plugin {
     }
 }
 
+/// Supporting code for replacing S3 Express sessions that S3 rejected
+pub(crate) mod session_refresh {
+    use super::identity_cache::SharedS3ExpressIdentityCache;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+    use aws_smithy_runtime_api::client::interceptors::context::FinalizerInterceptorContextRef;
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+    use aws_smithy_types::config_bag::ConfigBag;
+    use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+    use std::error::Error as StdError;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    /// Error codes of S3 rejecting an S3 Express session, e.g. because it rotated the session
+    /// before the session's expiration
+    const REJECTED_SESSION_ERRORS: &[&str] = &["ExpiredToken", "InvalidToken"];
+
+    /// Returns true if `error_code` is the code of S3 rejecting an S3 Express session
+    pub(crate) fn is_rejected_session_error(error_code: Option<&str>) -> bool {
+        error_code.is_some_and(|code| REJECTED_SESSION_ERRORS.contains(&code))
+    }
+
+    /// Removes the session of the bucket of an operation from the S3 Express identity cache
+    /// when S3 rejected the session
+    ///
+    /// Without this, the rejected session would be reused until its expiration. Removing it makes
+    /// the next request to the bucket, e.g. the next page of a `ListObjectsV2` paginator, create a
+    /// new session.
+    pub(crate) struct S3ExpressSessionRefreshInterceptor<E> {
+        _phantom: PhantomData<E>,
+    }
+
+    impl<E> fmt::Debug for S3ExpressSessionRefreshInterceptor<E> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("S3ExpressSessionRefreshInterceptor")
+        }
+    }
+
+    impl<E> S3ExpressSessionRefreshInterceptor<E> {
+        pub(crate) fn new() -> Self {
+            Self {
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<E> Intercept for S3ExpressSessionRefreshInterceptor<E>
+    where
+        E: StdError + ProvideErrorMetadata + Send + Sync + 'static,
+    {
+        fn name(&self) -> &'static str {
+            "S3ExpressSessionRefreshInterceptor"
+        }
+
+        fn read_after_attempt(
+            &self,
+            context: &FinalizerInterceptorContextRef<'_>,
+            _runtime_components: &RuntimeComponents,
+            cfg: &mut ConfigBag,
+        ) -> Result<(), BoxError> {
+            let error_code = match context.output_or_error() {
+                Some(Err(error)) => OrchestratorError::as_operation_error(error)
+                    .and_then(|error| error.downcast_ref::<E>())
+                    .and_then(|error| error.code()),
+                _ => None,
+            };
+            // An error response implies that the endpoint was resolved, which `for_s3_express`
+            // relies on.
+            if !is_rejected_session_error(error_code) || !super::utils::for_s3_express(cfg) {
+                return Ok(());
+            }
+            // Sessions are only cached by the default S3 Express identity provider.
+            let Some(session_cache) = cfg.load::<SharedS3ExpressIdentityCache>() else {
+                return Ok(());
+            };
+            let bucket = cfg
+                .load::<EndpointResolverParams>()
+                .and_then(|params| params.get::<crate::config::endpoint::Params>())
+                .and_then(|params| params.bucket());
+            if let Some(bucket) = bucket {
+                session_cache.invalidate_bucket(bucket);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::is_rejected_session_error;
+
+        #[test]
+        fn rejected_session_errors() {
+            assert!(is_rejected_session_error(Some("ExpiredToken")));
+            assert!(is_rejected_session_error(Some("InvalidToken")));
+            assert!(!is_rejected_session_error(Some("AccessDenied")));
+            assert!(!is_rejected_session_error(None));
+        }
+    }
+}
+
 pub(crate) mod checksum {
     use crate::http_request_checksum::DefaultRequestChecksumOverride;
     use aws_smithy_checksums::ChecksumAlgorithm;
@@ -0,0 +1,150 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Pagination of directory buckets that tolerates S3 Express session rotation.
+//!
+//! Requests to directory buckets are authorized with S3 Express sessions, which the SDK creates
+//! and caches until they are about to expire. S3 may reject a cached session earlier, e.g. once it
+//! rotated the session. The SDK then creates a new session for the next request to the bucket, but
+//! the rejected request fails with an `ExpiredToken` or `InvalidToken` error.
+//!
+//! [`ListObjectsV2SessionTolerantPaginator`] resends pages that failed with these errors, so that
+//! long listings of directory buckets don't surface session rotation to the caller.
+
+use crate::error::ProvideErrorMetadata;
+use crate::operation::list_objects_v2::builders::ListObjectsV2FluentBuilder;
+use crate::operation::list_objects_v2::{ListObjectsV2Error, ListObjectsV2Output};
+use aws_smithy_async::future::pagination_stream::fn_stream::FnStream;
+use aws_smithy_async::future::pagination_stream::PaginationStream;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+
+/// Suffix of the names of directory buckets
+const DIRECTORY_BUCKET_SUFFIX: &str = "--x-s3";
+
+/// Pages of a `ListObjectsV2` request
+pub type ListObjectsV2Pages =
+    PaginationStream<Result<ListObjectsV2Output, SdkError<ListObjectsV2Error, HttpResponse>>>;
+
+/// Paginator for `ListObjectsV2` that resends pages rejected because of S3 Express session rotation
+///
+/// Unlike the paginator returned by `into_paginator`, a page that fails because S3 rejected the
+/// S3 Express session of a directory bucket is sent again with a new session, instead of ending the
+/// stream with the error. Other errors end the stream as usual. Requests to general purpose buckets
+/// are paginated like with `into_paginator`.
+///
+/// # Examples
+/// ```no_run
+/// # async fn example(client: aws_sdk_s3::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use aws_sdk_s3::express_pagination::ListObjectsV2SessionTolerantPaginator;
+///
+/// let mut pages = ListObjectsV2SessionTolerantPaginator::new(
+///     client
+///         .list_objects_v2()
+///         .bucket("my-bucket--usw2-az1--x-s3"),
+/// )
+/// .send();
+/// while let Some(page) = pages.next().await {
+///     for object in page?.contents() {
+///         println!("{:?}", object.key());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ListObjectsV2SessionTolerantPaginator {
+    request: ListObjectsV2FluentBuilder,
+    max_session_retries: u32,
+}
+
+impl ListObjectsV2SessionTolerantPaginator {
+    /// Creates a paginator for `request`
+    ///
+    /// By default, each page is sent again at most once after S3 rejected its session.
+    pub fn new(request: ListObjectsV2FluentBuilder) -> Self {
+        Self {
+            request,
+            max_session_retries: 1,
+        }
+    }
+
+    /// Sets how many times a page is sent again after S3 rejected its session
+    pub fn max_session_retries(mut self, max_session_retries: u32) -> Self {
+        self.max_session_retries = max_session_retries;
+        self
+    }
+
+    /// Creates the pagination stream
+    ///
+    /// _Note:_ No requests are sent until the stream is used
+    /// (e.g. with the [`.next().await`](aws_smithy_async::future::pagination_stream::PaginationStream::next) method).
+    pub fn send(self) -> ListObjectsV2Pages {
+        let Self {
+            request,
+            max_session_retries,
+        } = self;
+        let max_session_retries = if request
+            .get_bucket()
+            .as_deref()
+            .is_some_and(|bucket| bucket.ends_with(DIRECTORY_BUCKET_SUFFIX))
+        {
+            max_session_retries
+        } else {
+            0
+        };
+        PaginationStream::new(FnStream::new(move |tx| {
+            Box::pin(async move {
+                let mut continuation_token = request.get_continuation_token().clone();
+                loop {
+                    let mut session_retries = 0;
+                    let page = loop {
+                        let page = request
+                            .clone()
+                            .set_continuation_token(continuation_token.clone())
+                            .send()
+                            .await;
+                        match &page {
+                            Err(err)
+                                if session_retries < max_session_retries
+                                    && is_rejected_session(err) =>
+                            {
+                                tracing::debug!(
+                                    "S3 rejected the S3 Express session of a page; sending the page again"
+                                );
+                                session_retries += 1;
+                            }
+                            _ => break page,
+                        }
+                    };
+                    let done = match &page {
+                        Ok(output) => {
+                            let next_token = output.next_continuation_token().map(str::to_owned);
+                            let done = output.is_truncated() == Some(false)
+                                || next_token.is_none()
+                                || next_token == continuation_token;
+                            continuation_token = next_token;
+                            done
+                        }
+                        Err(_) => true,
+                    };
+                    if tx.send(page).await.is_err() {
+                        // receiving end was dropped
+                        return;
+                    }
+                    if done {
+                        return;
+                    }
+                }
+            })
+        }))
+    }
+}
+
+fn is_rejected_session(err: &SdkError<ListObjectsV2Error, HttpResponse>) -> bool {
+    crate::s3_express::session_refresh::is_rejected_session_error(
+        err.as_service_error().and_then(|err| err.code()),
+    )
+}
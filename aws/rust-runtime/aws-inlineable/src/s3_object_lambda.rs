@@ -0,0 +1,172 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Helpers for S3 Object Lambda functions.
+//!
+//! An Object Lambda function receives a `getObjectContext` with an output route and token, and
+//! returns the transformed object with `WriteGetObjectResponse`. [`ObjectLambdaResponse`] streams
+//! the transformed object while it is produced, so that functions don't have to buffer whole
+//! objects in memory.
+
+use crate::operation::write_get_object_response::builders::WriteGetObjectResponseFluentBuilder;
+use crate::primitives::ByteStream;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use futures_util::stream::{Stream, StreamExt};
+use http::HeaderMap;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+type BoxStream = Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send + Sync>>;
+
+/// Response of an S3 Object Lambda function to a `GetObject` request
+///
+/// # Examples
+/// ```no_run
+/// # async fn example(
+/// #     client: aws_sdk_s3::Client,
+/// #     output_route: String,
+/// #     output_token: String,
+/// #     transformed: impl futures_util::Stream<Item = Result<bytes::Bytes, std::io::Error>> + Send + Sync + 'static,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use aws_sdk_s3::object_lambda::ObjectLambdaResponse;
+///
+/// // `output_route` and `output_token` are the `outputRoute` and `outputToken` of the
+/// // `getObjectContext` of the event that invoked the function
+/// ObjectLambdaResponse::new(client, output_route, output_token)
+///     .stream(transformed)
+///     .content_type("text/plain")
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ObjectLambdaResponse {
+    client: crate::Client,
+    request_route: String,
+    request_token: String,
+}
+
+impl ObjectLambdaResponse {
+    /// Creates a response for the request with the given output route and token
+    pub fn new(
+        client: crate::Client,
+        request_route: impl Into<String>,
+        request_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            request_route: request_route.into(),
+            request_token: request_token.into(),
+        }
+    }
+
+    fn request(&self) -> WriteGetObjectResponseFluentBuilder {
+        self.client
+            .write_get_object_response()
+            .request_route(&self.request_route)
+            .request_token(&self.request_token)
+    }
+
+    /// Returns a request that streams `body` as the transformed object with a `200 OK` status
+    ///
+    /// The body is sent with chunked transfer encoding as the stream produces data, so no
+    /// `Content-Length` is sent. Headers of the object, e.g. its content type, can be set on the
+    /// returned request before it is sent.
+    pub fn stream<S, E>(&self, body: S) -> WriteGetObjectResponseFluentBuilder
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + Sync + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        self.request().status_code(200).body(streaming_body(body))
+    }
+
+    /// Returns a request that fails the `GetObject` request with an S3 error
+    ///
+    /// `error_code` and `error_message` are returned to the caller as the code and message of the
+    /// error, e.g. `AccessDenied` with a status code of 403.
+    pub fn error(
+        &self,
+        status_code: i32,
+        error_code: impl Into<String>,
+        error_message: impl Into<String>,
+    ) -> WriteGetObjectResponseFluentBuilder {
+        self.request()
+            .status_code(status_code)
+            .error_code(error_code)
+            .error_message(error_message)
+    }
+}
+
+/// Creates a body of unknown length from a stream of chunks
+///
+/// Bodies without a known length are sent with chunked transfer encoding. Since they can't be
+/// replayed, requests with these bodies aren't retried.
+pub fn streaming_body<S, E>(stream: S) -> ByteStream
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + Sync + 'static,
+    E: Into<BoxError> + 'static,
+{
+    let stream: BoxStream = Box::pin(stream.map(|chunk| chunk.map_err(Into::into)));
+    ByteStream::new(SdkBody::from_body_0_4(StreamBody { stream }))
+}
+
+/// HTTP body that yields the chunks of a stream
+struct StreamBody {
+    stream: BoxStream,
+}
+
+impl fmt::Debug for StreamBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamBody").finish_non_exhaustive()
+    }
+}
+
+impl http_body::Body for StreamBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        loop {
+            match self.stream.as_mut().poll_next(cx) {
+                // Empty chunks would end a chunked body early
+                Poll::Ready(Some(Ok(chunk))) if chunk.is_empty() => continue,
+                other => return other,
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::streaming_body;
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    #[tokio::test]
+    async fn streaming_body_skips_empty_chunks() {
+        let chunks = ["hello", "", " ", "", "world"]
+            .map(|chunk| Ok::<_, std::io::Error>(Bytes::from_static(chunk.as_bytes())));
+        let body = streaming_body(stream::iter(chunks));
+        assert_eq!(None, body.size_hint().1);
+        assert_eq!(
+            &b"hello world"[..],
+            &body.collect().await.unwrap().into_bytes()[..]
+        );
+    }
+}
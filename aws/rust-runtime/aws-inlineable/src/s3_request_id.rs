@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_runtime_api::client::pagination::PageMetadata;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::http::{Headers, Response};
 use aws_smithy_types::error::metadata::{Builder as ErrorMetadataBuilder, ErrorMetadata};
@@ -39,6 +40,12 @@ impl<B> RequestIdExt for Response<B> {
     }
 }
 
+impl RequestIdExt for PageMetadata {
+    fn extended_request_id(&self) -> Option<&str> {
+        self.headers().extended_request_id()
+    }
+}
+
 impl RequestIdExt for Headers {
     fn extended_request_id(&self) -> Option<&str> {
         self.get("x-amz-id-2")
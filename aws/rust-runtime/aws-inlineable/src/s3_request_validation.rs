@@ -0,0 +1,534 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client-side validation of S3 requests before they are sent.
+//!
+//! S3 rejects many malformed requests only after they were signed and sent, often with errors
+//! that don't name the offending part of the request. With a [`RequestValidation`] set on the
+//! client, bucket names, object keys, and user-defined metadata are checked before a request is
+//! serialized, and requests that S3 would reject fail with a [`RequestValidationError`] instead.
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeSerializationInterceptorContextRef, Input,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Maximum length of an object key in bytes
+const MAX_KEY_LENGTH: usize = 1024;
+/// Maximum size of the user-defined metadata of an object in bytes
+const MAX_METADATA_SIZE: usize = 2048;
+/// Suffix of the names of directory buckets
+const DIRECTORY_BUCKET_SUFFIX: &str = "--x-s3";
+
+const RESERVED_PREFIXES: &[&str] = &["xn--", "sthree-", "amzn-s3-demo-"];
+const RESERVED_SUFFIXES: &[&str] = &["-s3alias", "--ol-s3", ".mrap", "--table-s3"];
+
+/// Checks that S3 requests are valid before they are sent
+///
+/// All checks are enabled by default:
+/// - Bucket names follow the naming rules of general purpose and directory buckets. Access point,
+///   Object Lambda, and Outposts ARNs aren't checked.
+/// - Buckets can be addressed with virtual-hosted style requests when the client requires it, e.g.
+///   with S3 Transfer Acceleration.
+/// - Object keys are at most 1024 bytes long, and don't contain control characters or `.` and `..`
+///   path segments, which some HTTP tools rewrite.
+/// - User-defined metadata is at most 2 KB, and its names and values are printable ASCII.
+///
+/// Buckets created in `us-east-1` before March 1, 2018 may have names that don't follow the
+/// current naming rules. Disable [`bucket_names`](Self::bucket_names) to use these buckets.
+///
+/// # Examples
+/// ```no_run
+/// use aws_sdk_s3::request_validation::RequestValidation;
+///
+/// let config = aws_sdk_s3::Config::builder()
+///     .request_validation(RequestValidation::new())
+///     .build();
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestValidation {
+    bucket_names: bool,
+    virtual_host_eligibility: bool,
+    keys: bool,
+    metadata: bool,
+}
+
+impl Default for RequestValidation {
+    fn default() -> Self {
+        Self {
+            bucket_names: true,
+            virtual_host_eligibility: true,
+            keys: true,
+            metadata: true,
+        }
+    }
+}
+
+impl RequestValidation {
+    /// Creates a validation with all checks enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether bucket names are checked against the bucket naming rules
+    pub fn bucket_names(mut self, enabled: bool) -> Self {
+        self.bucket_names = enabled;
+        self
+    }
+
+    /// Whether buckets are checked to be addressable with virtual-hosted style requests when the
+    /// client requires it
+    pub fn virtual_host_eligibility(mut self, enabled: bool) -> Self {
+        self.virtual_host_eligibility = enabled;
+        self
+    }
+
+    /// Whether object keys are checked
+    pub fn keys(mut self, enabled: bool) -> Self {
+        self.keys = enabled;
+        self
+    }
+
+    /// Whether user-defined metadata is checked
+    pub fn metadata(mut self, enabled: bool) -> Self {
+        self.metadata = enabled;
+        self
+    }
+
+    fn validate(&self, request: &RequestParts<'_>) -> Result<(), RequestValidationError> {
+        if let Some(bucket) = request.bucket.filter(|bucket| !bucket.starts_with("arn:")) {
+            if self.bucket_names {
+                validate_bucket_name(bucket)?;
+            }
+            if self.virtual_host_eligibility && request.accelerate {
+                validate_virtual_host_eligibility(bucket)?;
+            }
+        }
+        if let (true, Some(key)) = (self.keys, request.key) {
+            validate_key(key)?;
+        }
+        if let (true, Some(metadata)) = (self.metadata, request.metadata) {
+            validate_metadata(metadata)?;
+        }
+        Ok(())
+    }
+}
+
+impl Storable for RequestValidation {
+    type Storer = StoreReplace<Self>;
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    InvalidBucketName { bucket: String, reason: String },
+    NotVirtualHostable { bucket: String },
+    InvalidKey { key: String, reason: String },
+    InvalidMetadata { reason: String },
+}
+
+/// Error returned for a request that failed a [`RequestValidation`] check
+#[derive(Debug)]
+pub struct RequestValidationError {
+    kind: ErrorKind,
+}
+
+impl RequestValidationError {
+    fn invalid_bucket_name(bucket: &str, reason: impl Into<String>) -> Self {
+        Self {
+            kind: ErrorKind::InvalidBucketName {
+                bucket: bucket.to_owned(),
+                reason: reason.into(),
+            },
+        }
+    }
+
+    fn invalid_key(key: &str, reason: impl Into<String>) -> Self {
+        Self {
+            kind: ErrorKind::InvalidKey {
+                key: key.to_owned(),
+                reason: reason.into(),
+            },
+        }
+    }
+
+    fn invalid_metadata(reason: impl Into<String>) -> Self {
+        Self {
+            kind: ErrorKind::InvalidMetadata {
+                reason: reason.into(),
+            },
+        }
+    }
+
+    /// Returns true if the bucket name doesn't follow the bucket naming rules, or the bucket can't
+    /// be addressed as the client requires
+    pub fn is_invalid_bucket(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::InvalidBucketName { .. } | ErrorKind::NotVirtualHostable { .. }
+        )
+    }
+
+    /// Returns true if the object key is invalid
+    pub fn is_invalid_key(&self) -> bool {
+        matches!(self.kind, ErrorKind::InvalidKey { .. })
+    }
+
+    /// Returns true if the user-defined metadata is invalid
+    pub fn is_invalid_metadata(&self) -> bool {
+        matches!(self.kind, ErrorKind::InvalidMetadata { .. })
+    }
+}
+
+impl fmt::Display for RequestValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::InvalidBucketName { bucket, reason } => {
+                write!(f, "invalid bucket name `{bucket}`: {reason}")
+            }
+            ErrorKind::NotVirtualHostable { bucket } => write!(
+                f,
+                "bucket `{bucket}` can't be addressed with virtual-hosted style requests, which S3 Transfer Acceleration requires"
+            ),
+            ErrorKind::InvalidKey { key, reason } => {
+                write!(f, "invalid object key `{}`: {reason}", key.escape_debug())
+            }
+            ErrorKind::InvalidMetadata { reason } => {
+                write!(f, "invalid user-defined metadata: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for RequestValidationError {}
+
+fn validate_bucket_name(bucket: &str) -> Result<(), RequestValidationError> {
+    let invalid = |reason: &str| Err(RequestValidationError::invalid_bucket_name(bucket, reason));
+    // Directory bucket names have the form `base-name--zone-id--x-s3`
+    let name = match bucket.strip_suffix(DIRECTORY_BUCKET_SUFFIX) {
+        Some(name) => match name.rsplit_once("--") {
+            Some((base_name, zone_id)) if !zone_id.is_empty() => base_name,
+            _ => {
+                return invalid(
+                    "directory bucket names must have the form `base-name--zone-id--x-s3`",
+                )
+            }
+        },
+        None => bucket,
+    };
+    if !(3..=63).contains(&bucket.len()) {
+        return invalid("bucket names must be between 3 and 63 characters long");
+    }
+    if let Some(c) = bucket
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '.' || *c == '-'))
+    {
+        return invalid(&format!(
+            "bucket names may only contain lowercase letters, digits, dots, and hyphens, but found `{}`",
+            c.escape_debug()
+        ));
+    }
+    let starts_and_ends_with_alphanumeric = [name.bytes().next(), name.bytes().last()]
+        .into_iter()
+        .all(|b| b.is_some_and(|b| b.is_ascii_alphanumeric()));
+    if !starts_and_ends_with_alphanumeric {
+        return invalid("bucket names must begin and end with a letter or digit");
+    }
+    if bucket.contains("..") {
+        return invalid("bucket names must not contain two adjacent dots");
+    }
+    if bucket != name && bucket.contains('.') {
+        return invalid("directory bucket names must not contain dots");
+    }
+    if name.split('.').count() == 4 && name.split('.').all(|part| part.parse::<u8>().is_ok()) {
+        return invalid("bucket names must not be formatted as an IP address");
+    }
+    if let Some(prefix) = RESERVED_PREFIXES.iter().find(|p| bucket.starts_with(*p)) {
+        return invalid(&format!("the prefix `{prefix}` is reserved"));
+    }
+    if let Some(suffix) = RESERVED_SUFFIXES.iter().find(|s| bucket.ends_with(*s)) {
+        return invalid(&format!("the suffix `{suffix}` is reserved"));
+    }
+    Ok(())
+}
+
+fn validate_virtual_host_eligibility(bucket: &str) -> Result<(), RequestValidationError> {
+    // Dots in a host name don't match the wildcard TLS certificates of S3
+    let eligible = !bucket.contains('.')
+        && bucket
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+    if eligible {
+        Ok(())
+    } else {
+        Err(RequestValidationError {
+            kind: ErrorKind::NotVirtualHostable {
+                bucket: bucket.to_owned(),
+            },
+        })
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), RequestValidationError> {
+    if key.is_empty() {
+        return Err(RequestValidationError::invalid_key(
+            key,
+            "object keys must not be empty",
+        ));
+    }
+    if key.len() > MAX_KEY_LENGTH {
+        return Err(RequestValidationError::invalid_key(
+            key,
+            format!(
+                "object keys must be at most {MAX_KEY_LENGTH} bytes long when encoded as UTF-8, but the key is {} bytes long",
+                key.len()
+            ),
+        ));
+    }
+    if let Some(c) = key.chars().find(|c| c.is_control()) {
+        return Err(RequestValidationError::invalid_key(
+            key,
+            format!(
+                "object keys must not contain control characters, which can't be represented in XML responses, but found `{}`",
+                c.escape_unicode()
+            ),
+        ));
+    }
+    if key
+        .split('/')
+        .any(|segment| segment == "." || segment == "..")
+    {
+        return Err(RequestValidationError::invalid_key(
+            key,
+            "object keys must not contain `.` or `..` path segments, which HTTP clients and proxies may rewrite",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_metadata(metadata: &HashMap<String, String>) -> Result<(), RequestValidationError> {
+    let printable = |s: &str| s.bytes().all(|b| b.is_ascii_graphic() || b == b' ');
+    let mut size = 0;
+    for (name, value) in metadata {
+        if name.is_empty() || !name.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(RequestValidationError::invalid_metadata(format!(
+                "metadata names must be non-empty and only contain visible ASCII characters, but found `{}`",
+                name.escape_debug()
+            )));
+        }
+        if !printable(value) {
+            return Err(RequestValidationError::invalid_metadata(format!(
+                "the value of `{name}` must only contain printable ASCII characters; encode other values, e.g. as defined by RFC 2047"
+            )));
+        }
+        size += name.len() + value.len();
+    }
+    if size > MAX_METADATA_SIZE {
+        return Err(RequestValidationError::invalid_metadata(format!(
+            "user-defined metadata must be at most {MAX_METADATA_SIZE} bytes, but is {size} bytes"
+        )));
+    }
+    Ok(())
+}
+
+/// Parts of a request that are validated
+#[derive(Debug, Default)]
+struct RequestParts<'a> {
+    bucket: Option<&'a str>,
+    accelerate: bool,
+    key: Option<&'a str>,
+    metadata: Option<&'a HashMap<String, String>>,
+}
+
+/// Returns the object key and the user-defined metadata of the input of an S3 operation
+fn object_parts(input: &Input) -> (Option<&str>, Option<&HashMap<String, String>>) {
+    use crate::operation::*;
+
+    macro_rules! object_parts {
+        ($($input:ty $(=> $metadata:ident)?),+ $(,)?) => {
+            $(
+                if let Some(input) = input.downcast_ref::<$input>() {
+                    #[allow(unused_mut)]
+                    let mut metadata = None;
+                    $(metadata = input.$metadata.as_ref();)?
+                    return (input.key.as_deref(), metadata);
+                }
+            )+
+        };
+    }
+
+    object_parts!(
+        put_object::PutObjectInput => metadata,
+        copy_object::CopyObjectInput => metadata,
+        create_multipart_upload::CreateMultipartUploadInput => metadata,
+        get_object::GetObjectInput,
+        head_object::HeadObjectInput,
+        delete_object::DeleteObjectInput,
+        get_object_attributes::GetObjectAttributesInput,
+        upload_part::UploadPartInput,
+        upload_part_copy::UploadPartCopyInput,
+        complete_multipart_upload::CompleteMultipartUploadInput,
+        abort_multipart_upload::AbortMultipartUploadInput,
+        list_parts::ListPartsInput,
+        get_object_tagging::GetObjectTaggingInput,
+        put_object_tagging::PutObjectTaggingInput,
+        delete_object_tagging::DeleteObjectTaggingInput,
+        get_object_acl::GetObjectAclInput,
+        put_object_acl::PutObjectAclInput,
+        restore_object::RestoreObjectInput,
+    );
+    (None, None)
+}
+
+/// Interceptor that validates requests with the [`RequestValidation`] of the config
+#[derive(Debug, Default)]
+pub(crate) struct RequestValidationInterceptor;
+
+impl RequestValidationInterceptor {
+    pub(crate) fn new() -> Self {
+        Self
+    }
+}
+
+impl Intercept for RequestValidationInterceptor {
+    fn name(&self) -> &'static str {
+        "RequestValidationInterceptor"
+    }
+
+    fn read_before_serialization(
+        &self,
+        context: &BeforeSerializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(validation) = cfg.load::<RequestValidation>() else {
+            return Ok(());
+        };
+        let params = cfg
+            .load::<EndpointResolverParams>()
+            .and_then(|params| params.get::<crate::config::endpoint::Params>());
+        let (key, metadata) = object_parts(context.input());
+        let request = RequestParts {
+            bucket: params.and_then(|params| params.bucket()),
+            accelerate: params.and_then(|params| params.accelerate()) == Some(true),
+            key,
+            metadata,
+        };
+        validation.validate(&request)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        validate_bucket_name, validate_key, validate_metadata, RequestParts, RequestValidation,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn valid_bucket_names() {
+        for bucket in [
+            "my-bucket",
+            "abc",
+            "my.dotted.bucket",
+            "123-bucket",
+            "my-bucket--usw2-az1--x-s3",
+        ] {
+            assert!(validate_bucket_name(bucket).is_ok(), "{bucket}");
+        }
+    }
+
+    #[test]
+    fn invalid_bucket_names() {
+        for (bucket, reason) in [
+            ("ab", "between 3 and 63 characters"),
+            ("My-Bucket", "found `M`"),
+            ("my_bucket", "found `_`"),
+            ("-bucket", "begin and end"),
+            ("bucket-", "begin and end"),
+            ("my..bucket", "adjacent dots"),
+            ("192.168.5.4", "IP address"),
+            ("xn--bucket", "prefix `xn--`"),
+            ("bucket-s3alias", "suffix `-s3alias`"),
+            ("my.bucket--usw2-az1--x-s3", "must not contain dots"),
+            ("my-bucket--x-s3", "base-name--zone-id--x-s3"),
+        ] {
+            let err = validate_bucket_name(bucket).expect_err(bucket);
+            assert!(err.is_invalid_bucket());
+            assert!(err.to_string().contains(reason), "{bucket}: {err}");
+        }
+    }
+
+    #[test]
+    fn invalid_keys() {
+        let long_key = "a".repeat(1025);
+        for (key, reason) in [
+            ("", "must not be empty"),
+            (long_key.as_str(), "at most 1024 bytes"),
+            ("line\nbreak", "control characters"),
+            ("a/../b", "path segments"),
+            ("./a", "path segments"),
+        ] {
+            let err = validate_key(key).expect_err(key);
+            assert!(err.is_invalid_key());
+            assert!(err.to_string().contains(reason), "{key}: {err}");
+        }
+        assert!(validate_key("photos/2024/..hidden.jpg").is_ok());
+        assert!(validate_key(&"é".repeat(512)).is_ok());
+    }
+
+    #[test]
+    fn invalid_metadata() {
+        let metadata =
+            |name: &str, value: &str| HashMap::from([(name.to_owned(), value.to_owned())]);
+        assert!(validate_metadata(&metadata("author", "Jane Doe")).is_ok());
+        for (metadata, reason) in [
+            (metadata("author", "Zoë"), "printable ASCII"),
+            (metadata("my name", "value"), "visible ASCII"),
+            (metadata("big", &"a".repeat(2046)), "at most 2048 bytes"),
+        ] {
+            let err = validate_metadata(&metadata).expect_err(reason);
+            assert!(err.is_invalid_metadata());
+            assert!(err.to_string().contains(reason), "{err}");
+        }
+    }
+
+    #[test]
+    fn disabled_checks_are_skipped() {
+        let request = RequestParts {
+            bucket: Some("Legacy_Bucket"),
+            key: Some(""),
+            ..Default::default()
+        };
+        assert!(RequestValidation::new().validate(&request).is_err());
+        assert!(RequestValidation::new()
+            .bucket_names(false)
+            .keys(false)
+            .validate(&request)
+            .is_ok());
+    }
+
+    #[test]
+    fn accelerated_requests_require_virtual_hosted_buckets() {
+        let request = RequestParts {
+            bucket: Some("my.dotted.bucket"),
+            accelerate: true,
+            ..Default::default()
+        };
+        let err = RequestValidation::new().validate(&request).unwrap_err();
+        assert!(err.to_string().contains("Transfer Acceleration"), "{err}");
+        assert!(RequestValidation::new()
+            .virtual_host_eligibility(false)
+            .validate(&request)
+            .is_ok());
+    }
+}
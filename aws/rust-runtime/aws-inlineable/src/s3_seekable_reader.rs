@@ -0,0 +1,316 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Random access to S3 objects through ranged `GetObject` requests.
+//!
+//! [`SeekableReader`] implements [`AsyncRead`] and [`AsyncSeek`] over an object, so that readers of
+//! formats that only need parts of a file, e.g. Parquet or zip, can be used without downloading the
+//! whole object.
+
+use crate::config::http::HttpResponse;
+use crate::error::SdkError;
+use crate::operation::head_object::HeadObjectError;
+use bytes::Bytes;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::io::{self, SeekFrom};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+const DEFAULT_BLOCK_SIZE: u64 = 8 * 1024 * 1024;
+const DEFAULT_READ_AHEAD: u64 = 1;
+
+type BlockFuture = Pin<Box<dyn Future<Output = io::Result<Bytes>> + Send>>;
+
+/// Builder for [`SeekableReader`]
+#[derive(Debug)]
+pub struct SeekableReaderBuilder {
+    client: crate::Client,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    block_size: u64,
+    read_ahead: u64,
+}
+
+impl SeekableReaderBuilder {
+    /// Reads the given version of the object instead of its current version
+    pub fn version_id(mut self, version_id: impl Into<String>) -> Self {
+        self.version_id = Some(version_id.into());
+        self
+    }
+
+    /// Sets the number of bytes requested with each `GetObject` request
+    ///
+    /// Defaults to 8 MiB. Smaller blocks download less data that isn't read, and larger blocks take
+    /// fewer requests to read the same data. [`build`](Self::build) fails if `block_size` is zero.
+    pub fn block_size(mut self, block_size: u64) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Sets the number of blocks after the block being read that are requested ahead of time
+    ///
+    /// Defaults to 1. Blocks are only requested ahead while the reader is polled. Zero disables
+    /// read-ahead, which avoids downloading blocks that aren't read by readers that mostly seek.
+    pub fn read_ahead(mut self, blocks: u64) -> Self {
+        self.read_ahead = blocks;
+        self
+    }
+
+    /// Looks up the size of the object and creates the reader
+    ///
+    /// The object is pinned to its current entity tag, so reads fail instead of mixing the data of
+    /// different versions of the object if it is replaced while being read.
+    ///
+    /// Fails with a construction failure, without making a request, if the block size is zero.
+    pub async fn build(self) -> Result<SeekableReader, SdkError<HeadObjectError, HttpResponse>> {
+        if self.block_size == 0 {
+            return Err(SdkError::construction_failure(
+                "the block size must not be zero",
+            ));
+        }
+        let head = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .set_version_id(self.version_id.clone())
+            .send()
+            .await?;
+        Ok(SeekableReader {
+            len: head.content_length().unwrap_or_default().max(0) as u64,
+            e_tag: head.e_tag().map(str::to_owned),
+            client: self.client,
+            bucket: self.bucket,
+            key: self.key,
+            version_id: self.version_id,
+            block_size: self.block_size,
+            read_ahead: self.read_ahead,
+            position: 0,
+            seek_position: None,
+            current: None,
+            pending: VecDeque::new(),
+        })
+    }
+}
+
+/// Reader of an S3 object that downloads the parts of the object that are read with ranged `GetObject` requests
+///
+/// The object is split into blocks of [`block_size`](SeekableReaderBuilder::block_size) bytes.
+/// Reading requests the block at the current position along with the
+/// [`read_ahead`](SeekableReaderBuilder::read_ahead) blocks after it, and seeking discards requests
+/// for blocks that are no longer needed. The block being read is kept in memory, so reads within a
+/// block don't make requests.
+///
+/// # Examples
+/// ```no_run
+/// # async fn example(client: aws_sdk_s3::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use aws_sdk_s3::seekable_reader::SeekableReader;
+/// use std::io::SeekFrom;
+/// use tokio::io::{AsyncReadExt, AsyncSeekExt};
+///
+/// let mut reader = SeekableReader::builder(client, "my-bucket", "data.parquet")
+///     .block_size(1024 * 1024)
+///     .build()
+///     .await?;
+/// // Read the Parquet footer length and magic bytes
+/// let mut footer = [0; 8];
+/// reader.seek(SeekFrom::End(-8)).await?;
+/// reader.read_exact(&mut footer).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SeekableReader {
+    client: crate::Client,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    e_tag: Option<String>,
+    len: u64,
+    block_size: u64,
+    read_ahead: u64,
+    position: u64,
+    seek_position: Option<u64>,
+    current: Option<(u64, Bytes)>,
+    // Requests for blocks in ascending order
+    pending: VecDeque<(u64, BlockState)>,
+}
+
+enum BlockState {
+    Loading(BlockFuture),
+    Done(io::Result<Bytes>),
+}
+
+impl fmt::Debug for SeekableReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeekableReader")
+            .field("bucket", &self.bucket)
+            .field("key", &self.key)
+            .field("version_id", &self.version_id)
+            .field("len", &self.len)
+            .field("block_size", &self.block_size)
+            .field("read_ahead", &self.read_ahead)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+impl SeekableReader {
+    /// Creates a builder for a reader of the object `key` in `bucket`
+    pub fn builder(
+        client: crate::Client,
+        bucket: impl Into<String>,
+        key: impl Into<String>,
+    ) -> SeekableReaderBuilder {
+        SeekableReaderBuilder {
+            client,
+            bucket: bucket.into(),
+            key: key.into(),
+            version_id: None,
+            block_size: DEFAULT_BLOCK_SIZE,
+            read_ahead: DEFAULT_READ_AHEAD,
+        }
+    }
+
+    /// Returns the size of the object in bytes
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the object is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the current position of the reader
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    fn request_block(&self, block: u64) -> BlockFuture {
+        let start = block * self.block_size;
+        let end = (start + self.block_size).min(self.len) - 1;
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .set_version_id(self.version_id.clone())
+            .set_if_match(self.e_tag.clone())
+            .range(format!("bytes={start}-{end}"));
+        Box::pin(async move {
+            let output = request.send().await.map_err(io::Error::other)?;
+            let data = output
+                .body
+                .collect()
+                .await
+                .map_err(io::Error::other)?
+                .into_bytes();
+            if data.len() as u64 != end - start + 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "expected {} bytes for range {start}-{end}, but received {}",
+                        end - start + 1,
+                        data.len()
+                    ),
+                ));
+            }
+            Ok(data)
+        })
+    }
+
+    /// Makes sure that `block` and the blocks read ahead after it are requested, and polls all requests
+    fn poll_block(&mut self, cx: &mut Context<'_>, block: u64) -> Poll<io::Result<Bytes>> {
+        let last_block = (self.len - 1) / self.block_size;
+        let wanted = block..=(block.saturating_add(self.read_ahead)).min(last_block);
+        self.pending.retain(|(b, _)| wanted.contains(b));
+        for b in wanted {
+            if !self.pending.iter().any(|(pending, _)| *pending == b) {
+                let future = self.request_block(b);
+                self.pending.push_back((b, BlockState::Loading(future)));
+            }
+        }
+        for (_, state) in self.pending.iter_mut() {
+            if let BlockState::Loading(future) = state {
+                if let Poll::Ready(result) = future.as_mut().poll(cx) {
+                    *state = BlockState::Done(result);
+                }
+            }
+        }
+        match self.pending.front() {
+            Some((b, BlockState::Done(_))) if *b == block => match self.pending.pop_front() {
+                Some((_, BlockState::Done(result))) => Poll::Ready(result),
+                _ => unreachable!("just checked"),
+            },
+            _ => Poll::Pending,
+        }
+    }
+}
+
+impl AsyncRead for SeekableReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.position >= this.len || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            let block = this.position / this.block_size;
+            if let Some((current, data)) = &this.current {
+                if *current == block {
+                    let offset = (this.position - block * this.block_size) as usize;
+                    let n = buf.remaining().min(data.len() - offset);
+                    buf.put_slice(&data[offset..offset + n]);
+                    this.position += n as u64;
+                    return Poll::Ready(Ok(()));
+                }
+            }
+            match this.poll_block(cx, block) {
+                Poll::Ready(Ok(data)) => this.current = Some((block, data)),
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl AsyncSeek for SeekableReader {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+        let (base, offset) = match position {
+            SeekFrom::Start(position) => {
+                this.seek_position = Some(position);
+                return Ok(());
+            }
+            SeekFrom::End(offset) => (this.len, offset),
+            SeekFrom::Current(offset) => (this.position, offset),
+        };
+        match base.checked_add_signed(offset) {
+            Some(position) => {
+                this.seek_position = Some(position);
+                Ok(())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        if let Some(position) = this.seek_position.take() {
+            this.position = position;
+        }
+        Poll::Ready(Ok(this.position))
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client-level defaults for SSE-KMS server-side encryption of new objects.
+
+use crate::types::ServerSideEncryption;
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeSerializationInterceptorContextMut, Input,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::collections::BTreeMap;
+
+/// SSE-KMS settings applied to the objects written by `PutObject`, `CreateMultipartUpload`, and
+/// `CopyObject` requests of a client
+///
+/// The settings only apply to requests that don't choose a server-side encryption on their own.
+/// A request that sets its server-side encryption, KMS key ID, or SSE-C customer algorithm is sent
+/// as is. To send the requests of a single operation without the defaults, unset them with
+/// `set_server_side_encryption_defaults(None)` in a config override.
+///
+/// # Examples
+/// ```no_run
+/// use aws_sdk_s3::config::ServerSideEncryptionDefaults;
+///
+/// let defaults = ServerSideEncryptionDefaults::aws_kms()
+///     .kms_key_id("arn:aws:kms:us-west-2:123456789012:key/my-key")
+///     .bucket_key_enabled(true)
+///     .encryption_context("department", "finance");
+/// let config = aws_sdk_s3::Config::builder()
+///     .server_side_encryption_defaults(defaults)
+///     .build();
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ServerSideEncryptionDefaults {
+    kms_key_id: Option<String>,
+    bucket_key_enabled: Option<bool>,
+    encryption_context: BTreeMap<String, String>,
+}
+
+impl ServerSideEncryptionDefaults {
+    /// Encrypts objects with SSE-KMS, using the AWS managed key of S3 unless a key ID is set
+    pub fn aws_kms() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID, ARN, or alias of the KMS key that encrypts objects
+    pub fn kms_key_id(mut self, kms_key_id: impl Into<String>) -> Self {
+        self.kms_key_id = Some(kms_key_id.into());
+        self
+    }
+
+    /// Sets whether objects are encrypted with an S3 Bucket Key
+    pub fn bucket_key_enabled(mut self, bucket_key_enabled: bool) -> Self {
+        self.bucket_key_enabled = Some(bucket_key_enabled);
+        self
+    }
+
+    /// Adds a pair to the encryption context of objects
+    pub fn encryption_context(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.encryption_context.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the ID, ARN, or alias of the KMS key that encrypts objects
+    pub fn get_kms_key_id(&self) -> Option<&str> {
+        self.kms_key_id.as_deref()
+    }
+
+    /// Returns whether objects are encrypted with an S3 Bucket Key
+    pub fn get_bucket_key_enabled(&self) -> Option<bool> {
+        self.bucket_key_enabled
+    }
+
+    /// Returns the encryption context of objects
+    pub fn get_encryption_context(&self) -> &BTreeMap<String, String> {
+        &self.encryption_context
+    }
+
+    /// Returns the encryption context in the format of the `x-amz-server-side-encryption-context`
+    /// header, a base64-encoded JSON object
+    fn encoded_encryption_context(&self) -> Option<String> {
+        if self.encryption_context.is_empty() {
+            return None;
+        }
+        let mut json = String::new();
+        let mut object = JsonObjectWriter::new(&mut json);
+        for (key, value) in &self.encryption_context {
+            object.key(key).string(value);
+        }
+        object.finish();
+        Some(aws_smithy_types::base64::encode(json))
+    }
+}
+
+impl Storable for ServerSideEncryptionDefaults {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Applies `defaults` to the input of a request that writes an object, unless the request chooses
+/// a server-side encryption on its own
+fn apply_defaults(input: &mut Input, defaults: &ServerSideEncryptionDefaults) {
+    use crate::operation::*;
+
+    macro_rules! apply_defaults {
+        ($($input:ty),+ $(,)?) => {
+            $(
+                if let Some(input) = input.downcast_mut::<$input>() {
+                    if input.server_side_encryption.is_some()
+                        || input.ssekms_key_id.is_some()
+                        || input.sse_customer_algorithm.is_some()
+                    {
+                        return;
+                    }
+                    input.server_side_encryption = Some(ServerSideEncryption::AwsKms);
+                    input.ssekms_key_id = defaults.kms_key_id.clone();
+                    input.ssekms_encryption_context = input
+                        .ssekms_encryption_context
+                        .take()
+                        .or_else(|| defaults.encoded_encryption_context());
+                    input.bucket_key_enabled =
+                        input.bucket_key_enabled.or(defaults.bucket_key_enabled);
+                    return;
+                }
+            )+
+        };
+    }
+
+    apply_defaults!(
+        put_object::PutObjectInput,
+        create_multipart_upload::CreateMultipartUploadInput,
+        copy_object::CopyObjectInput,
+    );
+}
+
+/// Interceptor that applies the [`ServerSideEncryptionDefaults`] of the config to requests
+#[derive(Debug, Default)]
+pub(crate) struct ServerSideEncryptionDefaultsInterceptor;
+
+impl ServerSideEncryptionDefaultsInterceptor {
+    pub(crate) fn new() -> Self {
+        Self
+    }
+}
+
+impl Intercept for ServerSideEncryptionDefaultsInterceptor {
+    fn name(&self) -> &'static str {
+        "ServerSideEncryptionDefaultsInterceptor"
+    }
+
+    fn modify_before_serialization(
+        &self,
+        context: &mut BeforeSerializationInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if let Some(defaults) = cfg.load::<ServerSideEncryptionDefaults>() {
+            apply_defaults(context.input_mut(), defaults);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::operation::put_object::PutObjectInput;
+
+    fn apply(input: PutObjectInput, defaults: &ServerSideEncryptionDefaults) -> PutObjectInput {
+        let mut input = Input::new(input);
+        apply_defaults(&mut input, defaults);
+        *input.downcast::<PutObjectInput>().unwrap()
+    }
+
+    #[test]
+    fn defaults_apply_to_requests_without_encryption() {
+        let defaults = ServerSideEncryptionDefaults::aws_kms()
+            .kms_key_id("my-key")
+            .bucket_key_enabled(true)
+            .encryption_context("b", "2")
+            .encryption_context("a", "1");
+        let input = apply(
+            PutObjectInput::builder()
+                .bucket("bucket")
+                .key("key")
+                .build()
+                .unwrap(),
+            &defaults,
+        );
+        assert_eq!(
+            Some(&ServerSideEncryption::AwsKms),
+            input.server_side_encryption()
+        );
+        assert_eq!(Some("my-key"), input.ssekms_key_id());
+        assert_eq!(Some(true), input.bucket_key_enabled());
+        // Base64 of `{"a":"1","b":"2"}`
+        assert_eq!(
+            Some("eyJhIjoiMSIsImIiOiIyIn0="),
+            input.ssekms_encryption_context()
+        );
+    }
+
+    #[test]
+    fn requests_that_choose_encryption_are_not_changed() {
+        let defaults = ServerSideEncryptionDefaults::aws_kms()
+            .kms_key_id("my-key")
+            .bucket_key_enabled(true);
+        let input = apply(
+            PutObjectInput::builder()
+                .bucket("bucket")
+                .key("key")
+                .server_side_encryption(ServerSideEncryption::Aes256)
+                .build()
+                .unwrap(),
+            &defaults,
+        );
+        assert_eq!(
+            Some(&ServerSideEncryption::Aes256),
+            input.server_side_encryption()
+        );
+        assert_eq!(None, input.ssekms_key_id());
+        assert_eq!(None, input.bucket_key_enabled());
+    }
+
+    #[test]
+    fn aws_managed_key_has_no_key_id_or_context() {
+        let input = apply(
+            PutObjectInput::builder()
+                .bucket("bucket")
+                .key("key")
+                .build()
+                .unwrap(),
+            &ServerSideEncryptionDefaults::aws_kms(),
+        );
+        assert_eq!(
+            Some(&ServerSideEncryption::AwsKms),
+            input.server_side_encryption()
+        );
+        assert_eq!(None, input.ssekms_key_id());
+        assert_eq!(None, input.ssekms_encryption_context());
+    }
+}
@@ -0,0 +1,373 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Uploads that switch to multipart uploads for large bodies.
+//!
+//! [`Upload`] sends bodies up to a threshold with a single `PutObject` request. Larger bodies are
+//! split into parts that are uploaded concurrently with `UploadPart` requests, and the multipart
+//! upload is aborted if any part fails.
+
+use crate::primitives::ByteStream;
+use crate::types::{ChecksumAlgorithm, CompletedMultipartUpload, CompletedPart};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use std::error::Error;
+use std::fmt;
+
+const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+const DEFAULT_MULTIPART_THRESHOLD: u64 = 16 * 1024 * 1024;
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Builder for an [`Upload`]
+#[derive(Debug)]
+pub struct UploadBuilder {
+    client: crate::Client,
+    bucket: String,
+    key: String,
+    body: ByteStream,
+    content_type: Option<String>,
+    multipart_threshold: u64,
+    part_size: u64,
+    concurrency: usize,
+}
+
+impl UploadBuilder {
+    /// Sets the `Content-Type` of the object
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Sets the size above which bodies are uploaded with a multipart upload
+    ///
+    /// Defaults to 16 MiB. Up to this many bytes of the body are buffered to decide how it is uploaded.
+    pub fn multipart_threshold(mut self, bytes: u64) -> Self {
+        self.multipart_threshold = bytes;
+        self
+    }
+
+    /// Sets the size of the parts of multipart uploads
+    ///
+    /// Defaults to 8 MiB. S3 requires parts other than the last part to be at least 5 MiB, so smaller
+    /// sizes are raised to 5 MiB. Objects can have at most 10,000 parts, which limits objects with the
+    /// default part size to about 78 GiB.
+    pub fn part_size(mut self, bytes: u64) -> Self {
+        self.part_size = bytes.max(MIN_PART_SIZE);
+        self
+    }
+
+    /// Sets the maximum number of parts that are uploaded at the same time
+    ///
+    /// Defaults to 8. Each part being uploaded is buffered in memory.
+    ///
+    /// # Panics
+    /// Panics if `concurrency` is zero.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        assert!(
+            concurrency > 0,
+            "at least one part must be uploaded at a time"
+        );
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Creates the upload
+    pub fn build(self) -> Upload {
+        Upload { inner: self }
+    }
+}
+
+/// Upload of an object that uses a multipart upload for large bodies
+///
+/// # Examples
+/// ```no_run
+/// # async fn example(client: aws_sdk_s3::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use aws_sdk_s3::primitives::ByteStream;
+/// use aws_sdk_s3::upload::Upload;
+///
+/// let body = ByteStream::from_path("large-file.bin").await?;
+/// let output = Upload::builder(client, "my-bucket", "large-file.bin", body)
+///     .concurrency(16)
+///     .build()
+///     .send()
+///     .await?;
+/// println!("uploaded {} parts", output.parts());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Upload {
+    inner: UploadBuilder,
+}
+
+impl Upload {
+    /// Creates a builder for an upload of `body` to the object `key` in `bucket`
+    pub fn builder(
+        client: crate::Client,
+        bucket: impl Into<String>,
+        key: impl Into<String>,
+        body: ByteStream,
+    ) -> UploadBuilder {
+        UploadBuilder {
+            client,
+            bucket: bucket.into(),
+            key: key.into(),
+            body,
+            content_type: None,
+            multipart_threshold: DEFAULT_MULTIPART_THRESHOLD,
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Uploads the body
+    ///
+    /// A failed multipart upload is aborted, so that its parts aren't stored.
+    pub async fn send(self) -> Result<UploadOutput, UploadError> {
+        let UploadBuilder {
+            client,
+            bucket,
+            key,
+            body,
+            content_type,
+            multipart_threshold,
+            part_size,
+            concurrency,
+        } = self.inner;
+        let mut parts = PartReader {
+            body,
+            buffer: BytesMut::new(),
+            part_size,
+        };
+
+        if !parts.fill(multipart_threshold.saturating_add(1)).await? {
+            let output = client
+                .put_object()
+                .bucket(bucket)
+                .key(key)
+                .set_content_type(content_type)
+                .body(ByteStream::from(parts.buffer.freeze()))
+                .send()
+                .await
+                .map_err(|err| UploadError::new(ErrorKind::PutObject, err))?;
+            return Ok(UploadOutput {
+                e_tag: output.e_tag,
+                version_id: output.version_id,
+                parts: 0,
+            });
+        }
+
+        let upload_id = client
+            .create_multipart_upload()
+            .bucket(&bucket)
+            .key(&key)
+            .set_content_type(content_type)
+            .checksum_algorithm(ChecksumAlgorithm::Crc32)
+            .send()
+            .await
+            .map_err(|err| UploadError::new(ErrorKind::CreateMultipartUpload, err))?
+            .upload_id
+            .ok_or_else(|| {
+                UploadError::new(
+                    ErrorKind::CreateMultipartUpload,
+                    "no upload ID in the response",
+                )
+            })?;
+
+        let result = upload_parts(&client, &bucket, &key, &upload_id, parts, concurrency).await;
+        let result = match result {
+            Ok(completed_parts) => {
+                let part_count = completed_parts.len();
+                client
+                    .complete_multipart_upload()
+                    .bucket(&bucket)
+                    .key(&key)
+                    .upload_id(&upload_id)
+                    .multipart_upload(
+                        CompletedMultipartUpload::builder()
+                            .set_parts(Some(completed_parts))
+                            .build(),
+                    )
+                    .send()
+                    .await
+                    .map(|output| UploadOutput {
+                        e_tag: output.e_tag,
+                        version_id: output.version_id,
+                        parts: part_count,
+                    })
+                    .map_err(|err| UploadError::new(ErrorKind::CompleteMultipartUpload, err))
+            }
+            Err(err) => Err(err),
+        };
+        if result.is_err() {
+            if let Err(abort_err) = client
+                .abort_multipart_upload()
+                .bucket(&bucket)
+                .key(&key)
+                .upload_id(&upload_id)
+                .send()
+                .await
+            {
+                tracing::warn!(
+                    upload_id = %upload_id,
+                    error = %DisplayErrorContext(&abort_err),
+                    "failed to abort multipart upload"
+                );
+            }
+        }
+        result
+    }
+}
+
+/// Uploads the parts read from `parts`, returning the completed parts in order
+async fn upload_parts(
+    client: &crate::Client,
+    bucket: &str,
+    key: &str,
+    upload_id: &str,
+    parts: PartReader,
+    concurrency: usize,
+) -> Result<Vec<CompletedPart>, UploadError> {
+    let mut completed_parts =
+        stream::try_unfold((parts, 1), |(mut parts, part_number)| async move {
+            let part = parts.next_part().await?;
+            Ok::<_, UploadError>(part.map(|part| ((part_number, part), (parts, part_number + 1))))
+        })
+        .map_ok(|(part_number, part): (i32, Bytes)| async move {
+            let output = client
+                .upload_part()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .checksum_algorithm(ChecksumAlgorithm::Crc32)
+                .body(ByteStream::from(part))
+                .send()
+                .await
+                .map_err(|err| UploadError::new(ErrorKind::UploadPart { part_number }, err))?;
+            Ok::<_, UploadError>(
+                CompletedPart::builder()
+                    .part_number(part_number)
+                    .set_e_tag(output.e_tag)
+                    .set_checksum_crc32(output.checksum_crc32)
+                    .build(),
+            )
+        })
+        .try_buffer_unordered(concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
+    completed_parts.sort_by_key(|part| part.part_number());
+    Ok(completed_parts)
+}
+
+/// Splits a body into parts
+struct PartReader {
+    body: ByteStream,
+    buffer: BytesMut,
+    part_size: u64,
+}
+
+impl PartReader {
+    /// Buffers the body until at least `len` bytes are buffered, returning false if the body ended before
+    async fn fill(&mut self, len: u64) -> Result<bool, UploadError> {
+        while (self.buffer.len() as u64) < len {
+            match self
+                .body
+                .try_next()
+                .await
+                .map_err(|err| UploadError::new(ErrorKind::ReadBody, err))?
+            {
+                Some(data) => self.buffer.extend_from_slice(&data),
+                None => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the next part, or `None` once the whole body has been returned
+    async fn next_part(&mut self) -> Result<Option<Bytes>, UploadError> {
+        self.fill(self.part_size).await?;
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        let len = self.buffer.len().min(self.part_size as usize);
+        Ok(Some(self.buffer.split_to(len).freeze()))
+    }
+}
+
+/// Output of an [`Upload`]
+#[derive(Clone, Debug)]
+pub struct UploadOutput {
+    e_tag: Option<String>,
+    version_id: Option<String>,
+    parts: usize,
+}
+
+impl UploadOutput {
+    /// Returns the entity tag of the object
+    pub fn e_tag(&self) -> Option<&str> {
+        self.e_tag.as_deref()
+    }
+
+    /// Returns the version ID of the object if the bucket is versioned
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    /// Returns the number of parts of the object, or zero if it was uploaded with a single `PutObject` request
+    pub fn parts(&self) -> usize {
+        self.parts
+    }
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    ReadBody,
+    PutObject,
+    CreateMultipartUpload,
+    UploadPart { part_number: i32 },
+    CompleteMultipartUpload,
+}
+
+/// Error returned when an [`Upload`] fails
+#[derive(Debug)]
+pub struct UploadError {
+    kind: ErrorKind,
+    source: BoxError,
+}
+
+impl UploadError {
+    fn new(kind: ErrorKind, source: impl Into<BoxError>) -> Self {
+        Self {
+            kind,
+            source: source.into(),
+        }
+    }
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::ReadBody => write!(f, "failed to read the body of the upload"),
+            ErrorKind::PutObject => write!(f, "failed to upload the object"),
+            ErrorKind::CreateMultipartUpload => write!(f, "failed to create the multipart upload"),
+            ErrorKind::UploadPart { part_number } => {
+                write!(f, "failed to upload part {part_number}")
+            }
+            ErrorKind::CompleteMultipartUpload => {
+                write!(f, "failed to complete the multipart upload")
+            }
+        }
+    }
+}
+
+impl Error for UploadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
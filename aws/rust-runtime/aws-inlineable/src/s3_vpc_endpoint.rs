@@ -0,0 +1,41 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+/// Returns the endpoint URL for a DNS name of an S3 interface VPC endpoint
+///
+/// The VPC console lists DNS names such as `*.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com`.
+/// The wildcard stands for the bucket name, which the endpoint rules prepend for virtual-hosted
+/// style requests, so it's removed. DNS names without a scheme use HTTPS.
+pub(crate) fn vpc_endpoint_url(dns_name: &str) -> String {
+    let dns_name = dns_name.trim().trim_end_matches('/');
+    let (scheme, host) = dns_name.split_once("://").unwrap_or(("https", dns_name));
+    let host = host.strip_prefix("*.").unwrap_or(host);
+    format!("{scheme}://{host}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::vpc_endpoint_url;
+
+    #[test]
+    fn endpoint_url_from_dns_name() {
+        for dns_name in [
+            "*.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com",
+            "vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com",
+            "https://vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com/",
+            " https://*.vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com ",
+        ] {
+            assert_eq!(
+                "https://vpce-1a2b3c4d-5e6f.s3.us-east-1.vpce.amazonaws.com",
+                vpc_endpoint_url(dns_name),
+                "{dns_name}"
+            );
+        }
+        assert_eq!(
+            "http://vpce.example.com",
+            vpc_endpoint_url("http://vpce.example.com")
+        );
+    }
+}
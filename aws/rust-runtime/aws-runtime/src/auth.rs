@@ -24,10 +24,16 @@ use std::time::Duration;
 /// Auth implementations for SigV4.
 pub mod sigv4;
 
+pub mod bearer;
+
 #[cfg(feature = "sigv4a")]
 /// Auth implementations for SigV4a.
 pub mod sigv4a;
 
+pub mod signing_debug;
+
+pub mod signing_properties;
+
 /// Type of SigV4 signature.
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum HttpSignatureType {
@@ -0,0 +1,403 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Auth implementations for services that use Smithy's `@httpBearerAuth` auth scheme.
+//!
+//! [`BearerAuthScheme`] signs requests with the `Authorization: Bearer` header, and
+//! [`TokenResolver`] resolves the tokens it signs with from a [`ProvideToken`] implementation,
+//! caching them and refreshing them before they expire.
+
+use aws_credential_types::provider::error::TokenError;
+use aws_credential_types::provider::token::{ProvideToken, SharedTokenProvider};
+use aws_credential_types::Token;
+use aws_smithy_runtime::expiring_cache::ExpiringCache;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::http::HTTP_BEARER_AUTH_SCHEME_ID;
+use aws_smithy_runtime_api::client::auth::{
+    AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, Sign,
+};
+use aws_smithy_runtime_api::client::identity::{
+    Identity, IdentityCacheLocation, IdentityFuture, ResolveIdentity, SharedIdentityResolver,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::runtime_components::{GetIdentityResolver, RuntimeComponents};
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_REFRESH_BUFFER: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_TTL_WITHOUT_EXPIRATION: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Auth scheme for `@httpBearerAuth` that sends the resolved token in the `Authorization` header
+#[derive(Debug, Default)]
+pub struct BearerAuthScheme {
+    signer: BearerTokenSigner,
+}
+
+impl BearerAuthScheme {
+    /// Creates a new `BearerAuthScheme`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl AuthScheme for BearerAuthScheme {
+    fn scheme_id(&self) -> AuthSchemeId {
+        HTTP_BEARER_AUTH_SCHEME_ID
+    }
+
+    fn identity_resolver(
+        &self,
+        identity_resolvers: &dyn GetIdentityResolver,
+    ) -> Option<SharedIdentityResolver> {
+        identity_resolvers.identity_resolver(self.scheme_id())
+    }
+
+    fn signer(&self) -> &dyn Sign {
+        &self.signer
+    }
+}
+
+/// Signer that sets the `Authorization: Bearer <token>` header
+///
+/// The header value is marked as sensitive so that it's redacted from logs.
+#[derive(Debug, Default)]
+pub struct BearerTokenSigner;
+
+impl Sign for BearerTokenSigner {
+    fn sign_http_request(
+        &self,
+        request: &mut HttpRequest,
+        identity: &Identity,
+        _auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+        _runtime_components: &RuntimeComponents,
+        _config_bag: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        let token = identity
+            .data::<Token>()
+            .ok_or("bearer auth requires a `Token` identity")?;
+        if token.token().is_empty() {
+            return Err("the bearer token is empty".into());
+        }
+        let mut value = http_02x::HeaderValue::from_str(&format!("Bearer {}", token.token()))
+            .map_err(|_| "the bearer token contains characters that can't be sent in a header")?;
+        value.set_sensitive(true);
+        request
+            .headers_mut()
+            .insert(http_02x::header::AUTHORIZATION, value);
+        Ok(())
+    }
+}
+
+/// Identity resolver that caches the tokens of a [`ProvideToken`] implementation
+///
+/// Tokens are reused until they are within the refresh buffer of their expiration, at which point
+/// a new token is loaded. Concurrent requests share a single load. If loading a new token fails
+/// while the cached token hasn't expired yet, the cached token keeps being used, and loading is
+/// retried after a delay rather than on every request. Tokens without an expiration, such as API
+/// keys loaded from the environment, are reloaded periodically so that rotated keys are picked up.
+///
+/// The resolver manages its own cache, so the client's identity cache doesn't cache its tokens.
+#[derive(Debug)]
+pub struct TokenResolver {
+    provider: SharedTokenProvider,
+    cache: ExpiringCache<Token, TokenError>,
+    refresh_buffer: Duration,
+    ttl_without_expiration: Duration,
+    retry_delay: Duration,
+}
+
+impl TokenResolver {
+    /// Creates a `TokenResolver` that loads tokens from `provider`
+    pub fn new(provider: impl ProvideToken + 'static) -> Self {
+        Self::builder().build(provider)
+    }
+
+    /// Returns a builder for `TokenResolver`
+    pub fn builder() -> TokenResolverBuilder {
+        TokenResolverBuilder::default()
+    }
+
+    async fn resolve(&self, now: SystemTime) -> Result<Token, TokenError> {
+        let previous = match self.cache.yield_or_take_if_expired(now).await {
+            Ok(token) => return Ok(token),
+            Err(previous) => previous,
+        };
+        self.cache
+            .get_or_load(|| async move {
+                match self.provider.provide_token().await {
+                    Ok(token) => {
+                        let refresh_at = match token.expiration() {
+                            Some(expiration) => expiration,
+                            None => now + self.refresh_buffer + self.ttl_without_expiration,
+                        };
+                        Ok((token, refresh_at))
+                    }
+                    Err(err) => match previous {
+                        Some((token, _)) if token.expiration().is_some_and(|exp| now < exp) => {
+                            tracing::warn!(err = %err, "failed to refresh the bearer token, using the cached token until it expires");
+                            let expiration = token.expiration().expect("checked above");
+                            // Hold on to the cached token until the next retry, without serving it
+                            // past its expiration
+                            let retry_at = (now + self.refresh_buffer + self.retry_delay)
+                                .min(expiration + self.refresh_buffer);
+                            Ok((token, retry_at))
+                        }
+                        _ => Err(err),
+                    },
+                }
+            })
+            .await
+    }
+}
+
+impl ResolveIdentity for TokenResolver {
+    fn resolve_identity<'a>(
+        &'a self,
+        runtime_components: &'a RuntimeComponents,
+        _config_bag: &'a ConfigBag,
+    ) -> IdentityFuture<'a> {
+        let now = runtime_components.time_source().unwrap_or_default().now();
+        IdentityFuture::new(async move {
+            let token = self.resolve(now).await?;
+            Ok(token.into())
+        })
+    }
+
+    fn cache_location(&self) -> IdentityCacheLocation {
+        IdentityCacheLocation::IdentityResolver
+    }
+}
+
+/// Builder for [`TokenResolver`]
+#[derive(Debug, Default)]
+pub struct TokenResolverBuilder {
+    refresh_buffer: Option<Duration>,
+    ttl_without_expiration: Option<Duration>,
+    retry_delay: Option<Duration>,
+}
+
+impl TokenResolverBuilder {
+    /// Sets how long before its expiration a token is refreshed
+    ///
+    /// Defaults to 5 minutes.
+    pub fn refresh_buffer(mut self, refresh_buffer: Duration) -> Self {
+        self.refresh_buffer = Some(refresh_buffer);
+        self
+    }
+
+    /// Sets how long a token without an expiration is cached before it is loaded again
+    ///
+    /// Defaults to 15 minutes.
+    pub fn ttl_without_expiration(mut self, ttl: Duration) -> Self {
+        self.ttl_without_expiration = Some(ttl);
+        self
+    }
+
+    /// Sets how long to keep using a cached token after refreshing it failed before trying again
+    ///
+    /// Defaults to 10 seconds.
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = Some(retry_delay);
+        self
+    }
+
+    /// Creates a `TokenResolver` that loads tokens from `provider`
+    pub fn build(self, provider: impl ProvideToken + 'static) -> TokenResolver {
+        let refresh_buffer = self.refresh_buffer.unwrap_or(DEFAULT_REFRESH_BUFFER);
+        TokenResolver {
+            provider: provider.into_shared(),
+            cache: ExpiringCache::new(refresh_buffer),
+            refresh_buffer,
+            ttl_without_expiration: self
+                .ttl_without_expiration
+                .unwrap_or(DEFAULT_TTL_WITHOUT_EXPIRATION),
+            retry_delay: self.retry_delay.unwrap_or(DEFAULT_RETRY_DELAY),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_credential_types::token_fn::provide_token_fn;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_runtime_api::client::auth::AuthSchemeEndpointConfig;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::UNIX_EPOCH;
+
+    fn epoch_secs(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn runtime_components(time_source: &ManualTimeSource) -> RuntimeComponents {
+        RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time_source.clone()))
+            .build()
+            .unwrap()
+    }
+
+    /// A provider that returns the queued results in order, counting the calls
+    fn queued_provider(
+        results: Vec<Result<Token, TokenError>>,
+    ) -> (impl ProvideToken, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let results = Arc::new(Mutex::new(results.into_iter()));
+        let provider = provide_token_fn({
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let result = results.lock().unwrap().next().expect("no more tokens");
+                async move { result }
+            }
+        });
+        (provider, calls)
+    }
+
+    async fn resolve(resolver: &TokenResolver, components: &RuntimeComponents) -> String {
+        let identity = resolver
+            .resolve_identity(components, &ConfigBag::base())
+            .await
+            .expect("token is resolved");
+        identity.data::<Token>().unwrap().token().to_owned()
+    }
+
+    #[test]
+    fn signs_with_bearer_header() {
+        let mut request = HttpRequest::new(SdkBody::empty());
+        let identity = Identity::from(Token::new("my-token", None));
+        BearerTokenSigner
+            .sign_http_request(
+                &mut request,
+                &identity,
+                AuthSchemeEndpointConfig::empty(),
+                &RuntimeComponentsBuilder::for_tests().build().unwrap(),
+                &ConfigBag::base(),
+            )
+            .unwrap();
+        assert_eq!(
+            Some("Bearer my-token"),
+            request.headers().get("authorization")
+        );
+
+        let empty = Identity::from(Token::new("", None));
+        assert!(BearerTokenSigner
+            .sign_http_request(
+                &mut HttpRequest::new(SdkBody::empty()),
+                &empty,
+                AuthSchemeEndpointConfig::empty(),
+                &RuntimeComponentsBuilder::for_tests().build().unwrap(),
+                &ConfigBag::base(),
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn caches_and_refreshes_before_expiration() {
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let components = runtime_components(&time_source);
+        let (provider, calls) = queued_provider(vec![
+            Ok(Token::new("first", Some(epoch_secs(1000 + 3600)))),
+            Ok(Token::new("second", Some(epoch_secs(1000 + 7200)))),
+        ]);
+        let resolver = TokenResolver::new(provider);
+
+        assert_eq!("first", resolve(&resolver, &components).await);
+        time_source.advance(Duration::from_secs(3000));
+        assert_eq!("first", resolve(&resolver, &components).await);
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+
+        // Within the 5 minute refresh buffer
+        time_source.advance(Duration::from_secs(400));
+        assert_eq!("second", resolve(&resolver, &components).await);
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn keeps_cached_token_when_refresh_fails() {
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let components = runtime_components(&time_source);
+        let (provider, calls) = queued_provider(vec![
+            Ok(Token::new("first", Some(epoch_secs(1000 + 600)))),
+            Err(TokenError::provider_error("service unavailable")),
+            Ok(Token::new("second", Some(epoch_secs(1000 + 3600)))),
+        ]);
+        let resolver = TokenResolver::new(provider);
+        assert_eq!("first", resolve(&resolver, &components).await);
+
+        // The refresh fails, but the cached token is still valid
+        time_source.advance(Duration::from_secs(400));
+        assert_eq!("first", resolve(&resolver, &components).await);
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+        // The refresh isn't retried on every request
+        assert_eq!("first", resolve(&resolver, &components).await);
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+
+        time_source.advance(DEFAULT_RETRY_DELAY);
+        assert_eq!("second", resolve(&resolver, &components).await);
+        assert_eq!(3, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn fails_when_refresh_fails_after_expiration() {
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let components = runtime_components(&time_source);
+        let (provider, _calls) = queued_provider(vec![
+            Ok(Token::new("first", Some(epoch_secs(1000 + 600)))),
+            Err(TokenError::provider_error("service unavailable")),
+        ]);
+        let resolver = TokenResolver::new(provider);
+        assert_eq!("first", resolve(&resolver, &components).await);
+
+        time_source.advance(Duration::from_secs(600));
+        assert!(resolver
+            .resolve_identity(&components, &ConfigBag::base())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn reloads_tokens_without_expiration_periodically() {
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let components = runtime_components(&time_source);
+        let (provider, calls) = queued_provider(vec![
+            Ok(Token::new("first", None)),
+            Ok(Token::new("rotated", None)),
+        ]);
+        let resolver = TokenResolver::builder()
+            .ttl_without_expiration(Duration::from_secs(60))
+            .build(provider);
+
+        assert_eq!("first", resolve(&resolver, &components).await);
+        time_source.advance(Duration::from_secs(59));
+        assert_eq!("first", resolve(&resolver, &components).await);
+        time_source.advance(Duration::from_secs(1));
+        assert_eq!("rotated", resolve(&resolver, &components).await);
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn concurrent_resolves_share_one_load() {
+        let time_source = ManualTimeSource::new(epoch_secs(1000));
+        let components = runtime_components(&time_source);
+        let (provider, calls) =
+            queued_provider(vec![Ok(Token::new("first", Some(epoch_secs(1000 + 3600))))]);
+        let resolver = TokenResolver::new(provider);
+        let tokens = tokio::join!(
+            resolve(&resolver, &components),
+            resolve(&resolver, &components),
+            resolve(&resolver, &components),
+        );
+        assert_eq!(
+            ("first", "first", "first"),
+            (&*tokens.0, &*tokens.1, &*tokens.2)
+        );
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+    }
+}
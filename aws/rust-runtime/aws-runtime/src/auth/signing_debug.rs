@@ -0,0 +1,207 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Debugging support for signatures rejected by a service.
+//!
+//! When [`SigningDebugInterceptor`] is added to a client, the SigV4 and SigV4a signers capture
+//! the canonical request, string-to-sign, and signed headers of every signature they compute.
+//! If the service then rejects the signature (with `SignatureDoesNotMatch` or
+//! `InvalidSignatureException`), the captured values are logged and passed to an optional hook.
+
+use aws_sigv4::http_request::{SigningDebugInfo, SigningInstructions};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeTransmitInterceptorContextRef, FinalizerInterceptorContextRef,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+const SIGNATURE_MISMATCH_ERRORS: &[&str] = &["SignatureDoesNotMatch", "InvalidSignatureException"];
+
+/// Slot the signers write captured debug info into for the current attempt.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SigningDebugSink(Arc<Mutex<Option<SigningDebugInfo>>>);
+
+impl Storable for SigningDebugSink {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Returns true if the signers should capture debug info for the current attempt.
+pub(crate) fn capture_enabled(config_bag: &ConfigBag) -> bool {
+    config_bag.load::<SigningDebugSink>().is_some()
+}
+
+/// Records the debug info captured in `instructions`, if any.
+pub(crate) fn record(config_bag: &ConfigBag, instructions: &SigningInstructions) {
+    if let (Some(sink), Some(debug_info)) = (
+        config_bag.load::<SigningDebugSink>(),
+        instructions.debug_info(),
+    ) {
+        *sink.0.lock().unwrap() = Some(debug_info.clone());
+    }
+}
+
+type SignatureMismatchHook = Arc<dyn Fn(&SigningDebugInfo) + Send + Sync>;
+
+/// Interceptor that reports the signing details of requests whose signature was rejected.
+///
+/// Adding this interceptor enables debug capture in the SigV4 and SigV4a signers, which costs a few
+/// extra allocations per request. It is intended to be enabled while investigating signature
+/// errors rather than left on in production.
+///
+/// # Examples
+/// ```no_run
+/// use aws_runtime::auth::signing_debug::SigningDebugInterceptor;
+///
+/// let interceptor = SigningDebugInterceptor::new().on_signature_mismatch(|debug_info| {
+///     eprintln!("canonical request:\n{}", debug_info.canonical_request());
+///     eprintln!("string to sign:\n{}", debug_info.string_to_sign());
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct SigningDebugInterceptor {
+    on_signature_mismatch: Option<SignatureMismatchHook>,
+}
+
+impl SigningDebugInterceptor {
+    /// Creates a new `SigningDebugInterceptor` that logs rejected signatures at the `warn` level.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `hook` with the signing details whenever the service rejects a signature.
+    pub fn on_signature_mismatch(
+        mut self,
+        hook: impl Fn(&SigningDebugInfo) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_signature_mismatch = Some(Arc::new(hook));
+        self
+    }
+}
+
+impl fmt::Debug for SigningDebugInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningDebugInterceptor")
+            .field(
+                "on_signature_mismatch",
+                &self.on_signature_mismatch.as_ref().map(|_| "** hook **"),
+            )
+            .finish()
+    }
+}
+
+fn is_signature_mismatch(response: &HttpResponse) -> bool {
+    if !matches!(response.status().as_u16(), 400 | 403) {
+        return false;
+    }
+    if let Some(error_type) = response.headers().get("x-amzn-errortype") {
+        if SIGNATURE_MISMATCH_ERRORS
+            .iter()
+            .any(|code| error_type.starts_with(code))
+        {
+            return true;
+        }
+    }
+    let body = response.body().bytes().unwrap_or_default();
+    let body = String::from_utf8_lossy(body);
+    SIGNATURE_MISMATCH_ERRORS
+        .iter()
+        .any(|code| body.contains(code))
+}
+
+impl Intercept for SigningDebugInterceptor {
+    fn name(&self) -> &'static str {
+        "SigningDebugInterceptor"
+    }
+
+    fn read_before_attempt(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        // Each attempt is signed again, so use a fresh sink for every attempt
+        cfg.interceptor_state()
+            .store_put(SigningDebugSink::default());
+        Ok(())
+    }
+
+    fn read_after_attempt(
+        &self,
+        context: &FinalizerInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(response) = context.response() else {
+            return Ok(());
+        };
+        if !is_signature_mismatch(response) {
+            return Ok(());
+        }
+        let Some(debug_info) = cfg
+            .load::<SigningDebugSink>()
+            .and_then(|sink| sink.0.lock().unwrap().take())
+        else {
+            return Ok(());
+        };
+        tracing::warn!(
+            canonical_request = %debug_info.canonical_request(),
+            string_to_sign = %debug_info.string_to_sign(),
+            signed_headers = %debug_info.signed_headers(),
+            "the service rejected the request signature"
+        );
+        if let Some(hook) = &self.on_signature_mismatch {
+            hook(&debug_info);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_signature_mismatch;
+    use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_runtime_api::http::StatusCode;
+    use aws_smithy_types::body::SdkBody;
+
+    fn response(status: u16, error_type: Option<&'static str>, body: &'static str) -> HttpResponse {
+        let mut response =
+            HttpResponse::new(StatusCode::try_from(status).unwrap(), SdkBody::from(body));
+        if let Some(error_type) = error_type {
+            response
+                .headers_mut()
+                .insert("x-amzn-errortype", error_type);
+        }
+        response
+    }
+
+    #[test]
+    fn detects_signature_mismatch() {
+        assert!(is_signature_mismatch(&response(
+            403,
+            None,
+            "<Error><Code>SignatureDoesNotMatch</Code></Error>"
+        )));
+        assert!(is_signature_mismatch(&response(
+            400,
+            Some("InvalidSignatureException:http://internal.amazon.com/"),
+            "{}"
+        )));
+        assert!(!is_signature_mismatch(&response(
+            403,
+            None,
+            "<Error><Code>AccessDenied</Code></Error>"
+        )));
+        assert!(!is_signature_mismatch(&response(
+            500,
+            None,
+            "SignatureDoesNotMatch"
+        )));
+    }
+}
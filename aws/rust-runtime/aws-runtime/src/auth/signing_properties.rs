@@ -0,0 +1,230 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Signing properties resolved for a request.
+//!
+//! The endpoint resolved for a request can override the signing region and signing name
+//! configured on the client, so the values a request was actually signed with aren't always
+//! the ones that were configured. When [`ResolvedSigningPropertiesInterceptor`] is added to a
+//! client, the SigV4 and SigV4a signers record the values they used, and the interceptor attaches
+//! them to the raw response. They can then be read with [`SigningProperties`] from the outputs of
+//! successful requests and from the [`SdkError`] of failed ones.
+
+use crate::auth::SigV4OperationSigningConfig;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::AuthSchemeId;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeDeserializationInterceptorContextMut, BeforeTransmitInterceptorContextRef,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::Response;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_types::region::{SigningRegion, SigningRegionSet};
+use aws_types::SigningName;
+use std::sync::{Arc, Mutex};
+
+/// The signing region, signing name, and auth scheme a request was signed with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolvedSigningProperties {
+    auth_scheme_id: AuthSchemeId,
+    signing_region: Option<SigningRegion>,
+    signing_region_set: Option<SigningRegionSet>,
+    signing_name: Option<SigningName>,
+}
+
+impl ResolvedSigningProperties {
+    pub(crate) fn new(auth_scheme_id: AuthSchemeId, config: &SigV4OperationSigningConfig) -> Self {
+        Self {
+            auth_scheme_id,
+            signing_region: config.region.clone(),
+            signing_region_set: config.region_set.clone(),
+            signing_name: config.name.clone(),
+        }
+    }
+
+    /// Returns the ID of the auth scheme the request was signed with.
+    pub fn auth_scheme_id(&self) -> &AuthSchemeId {
+        &self.auth_scheme_id
+    }
+
+    /// Returns the region the request was signed for, if the request was signed with SigV4.
+    pub fn signing_region(&self) -> Option<&SigningRegion> {
+        self.signing_region.as_ref()
+    }
+
+    /// Returns the region set the request was signed for, if the request was signed with SigV4a.
+    pub fn signing_region_set(&self) -> Option<&SigningRegionSet> {
+        self.signing_region_set.as_ref()
+    }
+
+    /// Returns the service name the request was signed for.
+    pub fn signing_name(&self) -> Option<&SigningName> {
+        self.signing_name.as_ref()
+    }
+}
+
+/// Slot the signers write the resolved signing properties into for the current attempt.
+#[derive(Clone, Debug, Default)]
+struct ResolvedSigningPropertiesSink(Arc<Mutex<Option<ResolvedSigningProperties>>>);
+
+impl Storable for ResolvedSigningPropertiesSink {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Records the signing properties of the current attempt, if they're being collected.
+pub(crate) fn record(
+    config_bag: &ConfigBag,
+    auth_scheme_id: AuthSchemeId,
+    config: &SigV4OperationSigningConfig,
+) {
+    if let Some(sink) = config_bag.load::<ResolvedSigningPropertiesSink>() {
+        *sink.0.lock().unwrap() = Some(ResolvedSigningProperties::new(auth_scheme_id, config));
+    }
+}
+
+/// Interceptor that attaches the [`ResolvedSigningProperties`] of each attempt to its response.
+///
+/// Signing properties are only collected for clients this interceptor was added to.
+///
+/// # Examples
+/// ```ignore
+/// use aws_sdk_s3::config::interceptors::ResolvedSigningPropertiesInterceptor;
+/// use aws_sdk_s3::operation::SigningProperties;
+///
+/// let config = aws_sdk_s3::config::Builder::from(&sdk_config)
+///     .interceptor(ResolvedSigningPropertiesInterceptor::new())
+///     .build();
+/// let client = aws_sdk_s3::Client::from_conf(config);
+/// let output = client.head_bucket().bucket("my-bucket").send().await?;
+/// println!("signed for {:?}", output.signing_properties().and_then(|p| p.signing_region()));
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default)]
+pub struct ResolvedSigningPropertiesInterceptor;
+
+impl ResolvedSigningPropertiesInterceptor {
+    /// Creates a new `ResolvedSigningPropertiesInterceptor`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Intercept for ResolvedSigningPropertiesInterceptor {
+    fn name(&self) -> &'static str {
+        "ResolvedSigningPropertiesInterceptor"
+    }
+
+    fn read_before_attempt(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        // Each attempt is signed again, so use a fresh sink for every attempt
+        cfg.interceptor_state()
+            .store_put(ResolvedSigningPropertiesSink::default());
+        Ok(())
+    }
+
+    fn modify_before_deserialization(
+        &self,
+        context: &mut BeforeDeserializationInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if let Some(properties) = cfg
+            .load::<ResolvedSigningPropertiesSink>()
+            .and_then(|sink| sink.0.lock().unwrap().take())
+        {
+            context.response_mut().add_extension(properties);
+        }
+        Ok(())
+    }
+}
+
+/// Implementers return the [`ResolvedSigningProperties`] a request was signed with.
+pub trait SigningProperties {
+    /// Returns the resolved signing properties, or `None` if they weren't collected, the request
+    /// wasn't signed with SigV4 or SigV4a, or no response was received.
+    fn signing_properties(&self) -> Option<&ResolvedSigningProperties>;
+}
+
+impl<B> SigningProperties for Response<B> {
+    fn signing_properties(&self) -> Option<&ResolvedSigningProperties> {
+        self.extension::<ResolvedSigningProperties>()
+    }
+}
+
+impl<E, B> SigningProperties for SdkError<E, Response<B>> {
+    fn signing_properties(&self) -> Option<&ResolvedSigningProperties> {
+        self.raw_response()
+            .and_then(|response| response.signing_properties())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        record, ResolvedSigningPropertiesInterceptor, ResolvedSigningPropertiesSink,
+        SigningProperties,
+    };
+    use crate::auth::sigv4::SCHEME_ID;
+    use crate::auth::SigV4OperationSigningConfig;
+    use aws_smithy_runtime_api::client::interceptors::context::{
+        BeforeDeserializationInterceptorContextMut, Input, InterceptorContext,
+    };
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_runtime_api::client::result::SdkError;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_runtime_api::http::StatusCode;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::ConfigBag;
+    use aws_types::region::SigningRegion;
+    use aws_types::SigningName;
+
+    #[test]
+    fn attaches_signing_properties_to_response() {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::base();
+        cfg.interceptor_state()
+            .store_put(ResolvedSigningPropertiesSink::default());
+
+        let signing_config = SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-west-2")),
+            name: Some(SigningName::from_static("s3")),
+            ..Default::default()
+        };
+        record(&cfg, SCHEME_ID, &signing_config);
+
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.set_response(HttpResponse::new(
+            StatusCode::try_from(403).unwrap(),
+            SdkBody::empty(),
+        ));
+        let mut ctx: BeforeDeserializationInterceptorContextMut<'_> = (&mut context).into();
+        ResolvedSigningPropertiesInterceptor::new()
+            .modify_before_deserialization(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+
+        let response = std::mem::replace(
+            context.response_mut().unwrap(),
+            HttpResponse::new(StatusCode::try_from(200).unwrap(), SdkBody::empty()),
+        );
+        let properties = response.signing_properties().unwrap().clone();
+        assert_eq!(&SCHEME_ID, properties.auth_scheme_id());
+        assert_eq!(
+            Some("us-west-2"),
+            properties.signing_region().map(|r| r.as_ref())
+        );
+        assert_eq!(None, properties.signing_region_set());
+        assert_eq!(Some("s3"), properties.signing_name().map(|n| n.as_ref()));
+
+        let error = SdkError::<(), _>::response_error("test", response);
+        assert_eq!(Some(&properties), error.signing_properties());
+    }
+}
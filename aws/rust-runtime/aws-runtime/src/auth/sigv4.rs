@@ -5,12 +5,13 @@
 
 use crate::auth::{
     self, extract_endpoint_auth_scheme_signing_name, extract_endpoint_auth_scheme_signing_options,
-    extract_endpoint_auth_scheme_signing_region, PayloadSigningOverride,
-    SigV4OperationSigningConfig, SigV4SessionTokenNameOverride, SigV4SigningError,
+    extract_endpoint_auth_scheme_signing_region, signing_debug, signing_properties,
+    HttpSignatureType, PayloadSigningOverride, SigV4OperationSigningConfig,
+    SigV4SessionTokenNameOverride, SigV4SigningError, SigningOptions,
 };
 use aws_credential_types::Credentials;
 use aws_sigv4::http_request::{
-    sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
+    presign, sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
 };
 use aws_sigv4::sign::v4;
 use aws_smithy_runtime_api::box_error::BoxError;
@@ -24,7 +25,7 @@ use aws_smithy_types::config_bag::ConfigBag;
 use aws_types::region::SigningRegion;
 use aws_types::SigningName;
 use std::borrow::Cow;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 const EXPIRATION_WARNING: &str = "Presigned request will expire before the given \
         `expires_in` duration because the credentials used to sign it will expire first.";
@@ -169,7 +170,7 @@ impl Sign for SigV4Signer {
             Self::extract_operation_config(auth_scheme_endpoint_config, config_bag)?;
         let request_time = runtime_components.time_source().unwrap_or_default().now();
 
-        let settings = if let Some(session_token_name_override) =
+        let mut settings = if let Some(session_token_name_override) =
             config_bag.load::<SigV4SessionTokenNameOverride>()
         {
             let mut settings = Self::settings(&operation_config);
@@ -179,6 +180,7 @@ impl Sign for SigV4Signer {
         } else {
             Self::settings(&operation_config)
         };
+        settings.capture_debug_info = signing_debug::capture_enabled(config_bag);
 
         let signing_params =
             Self::signing_params(settings, identity, &operation_config, request_time)?;
@@ -224,38 +226,202 @@ impl Sign for SigV4Signer {
         #[cfg(feature = "event-stream")]
         {
             use aws_smithy_eventstream::frame::DeferredSignerSender;
-            use event_stream::SigV4MessageSigner;
+            use event_stream::{IdentityRefresh, SigV4MessageSigner};
 
             if let Some(signer_sender) = config_bag.load::<DeferredSignerSender>() {
                 let time_source = runtime_components.time_source().unwrap_or_default();
                 let region = operation_config.region.clone().unwrap();
                 let name = operation_config.name.clone().unwrap();
+                let mut signer = SigV4MessageSigner::new(
+                    _signature,
+                    identity.clone(),
+                    region,
+                    name,
+                    time_source,
+                );
+                if let Some(resolver) = runtime_components.identity_resolver(SCHEME_ID) {
+                    signer = signer.with_identity_refresh(IdentityRefresh::new(
+                        resolver,
+                        runtime_components.clone(),
+                        config_bag.without_interceptor_state(),
+                    ));
+                }
                 signer_sender
-                    .send(Box::new(SigV4MessageSigner::new(
-                        _signature,
-                        identity.clone(),
-                        region,
-                        name,
-                        time_source,
-                    )) as _)
+                    .send(Box::new(signer) as _)
                     .expect("failed to send deferred signer");
             }
         }
+        signing_debug::record(config_bag, &signing_instructions);
+        signing_properties::record(config_bag, SCHEME_ID, &operation_config);
         auth::apply_signing_instructions(signing_instructions, request)?;
         Ok(())
     }
 }
 
+/// Presigns an arbitrary HTTP request with SigV4 by adding the signature to its query string.
+///
+/// This can be used to create presigned URLs for services that don't have generated presigning
+/// support, or for custom APIs fronted by IAM auth. A body that is already in memory is included
+/// in the signature. Any other body is signed as `UNSIGNED-PAYLOAD`.
+///
+/// `expires_in` must be greater than zero and at most one week. A warning is logged if the
+/// credentials expire before the presigned request does.
+pub fn presign_http_request(
+    request: &mut HttpRequest,
+    credentials: &Credentials,
+    region: &SigningRegion,
+    name: &SigningName,
+    time: SystemTime,
+    expires_in: Duration,
+) -> Result<(), BoxError> {
+    let operation_config = SigV4OperationSigningConfig {
+        region: Some(region.clone()),
+        name: Some(name.clone()),
+        signing_options: SigningOptions {
+            signature_type: HttpSignatureType::HttpRequestQueryParams,
+            expires_in: Some(expires_in),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let identity = Identity::from(credentials.clone());
+    let settings = SigV4Signer::settings(&operation_config);
+    let signing_params = SigV4Signer::signing_params(settings, &identity, &operation_config, time)?;
+
+    let (signing_instructions, _signature) = {
+        let signable_body = request
+            .body()
+            .bytes()
+            .map(SignableBody::Bytes)
+            .unwrap_or(SignableBody::UnsignedPayload);
+        let signable_request = SignableRequest::new(
+            request.method(),
+            request.uri(),
+            request.headers().iter(),
+            signable_body,
+        )?;
+        presign(
+            signable_request,
+            SigningParams::V4(signing_params),
+            expires_in,
+        )?
+    }
+    .into_parts();
+    auth::apply_signing_instructions(signing_instructions, request)
+}
+
 #[cfg(feature = "event-stream")]
 mod event_stream {
     use aws_sigv4::event_stream::{sign_empty_message, sign_message};
     use aws_sigv4::sign::v4;
     use aws_smithy_async::time::SharedTimeSource;
     use aws_smithy_eventstream::frame::{SignMessage, SignMessageError};
-    use aws_smithy_runtime_api::client::identity::Identity;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::identity::{
+        Identity, ResolveCachedIdentity, SharedIdentityResolver,
+    };
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+    use aws_smithy_types::config_bag::ConfigBag;
     use aws_smithy_types::event_stream::Message;
     use aws_types::region::SigningRegion;
     use aws_types::SigningName;
+    use std::fmt;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{ready, Context, Poll};
+    use std::time::{Duration, SystemTime};
+
+    /// How long before the signing identity expires that it gets refreshed.
+    ///
+    /// This matches the default buffer time of the identity cache so that the refresh resolves
+    /// a new identity rather than the cached one that is about to expire.
+    const IDENTITY_REFRESH_BUFFER: Duration = Duration::from_secs(10);
+
+    /// The minimum time between two attempts to refresh the signing identity.
+    ///
+    /// This keeps a failing credentials provider, or one that returns short-lived credentials,
+    /// from being called for every message.
+    const IDENTITY_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+    type RefreshFuture = Pin<Box<dyn Future<Output = Result<Identity, BoxError>> + Send>>;
+
+    /// Resolves a new identity for an event stream whose signing identity is about to expire.
+    ///
+    /// Event stream messages are signed for as long as the stream stays open, which can be longer
+    /// than the credentials that signed the initial request are valid for. The event stream
+    /// signing spec allows the signing key to change between messages since every message
+    /// signature is chained to the signature of the previous message rather than the key.
+    pub(super) struct IdentityRefresh {
+        resolver: SharedIdentityResolver,
+        runtime_components: RuntimeComponents,
+        // The configuration of the operation that opened the stream
+        config_bag: Arc<ConfigBag>,
+        last_attempt: Option<SystemTime>,
+        // The future isn't `Sync`, but it is only polled through `&mut self`
+        in_flight: Option<Mutex<RefreshFuture>>,
+    }
+
+    impl IdentityRefresh {
+        pub(super) fn new(
+            resolver: SharedIdentityResolver,
+            runtime_components: RuntimeComponents,
+            config_bag: ConfigBag,
+        ) -> Self {
+            Self {
+                resolver,
+                runtime_components,
+                config_bag: Arc::new(config_bag),
+                last_attempt: None,
+                in_flight: None,
+            }
+        }
+
+        /// Starts a refresh unless the last one was started less than
+        /// [`IDENTITY_REFRESH_INTERVAL`] before `now`. Returns whether a refresh is in flight.
+        fn try_start(&mut self, now: SystemTime) -> bool {
+            if self.in_flight.is_some() {
+                return true;
+            }
+            if matches!(self.last_attempt, Some(last) if now < last + IDENTITY_REFRESH_INTERVAL) {
+                return false;
+            }
+            self.last_attempt = Some(now);
+            let (resolver, runtime_components, config_bag) = (
+                self.resolver.clone(),
+                self.runtime_components.clone(),
+                self.config_bag.clone(),
+            );
+            self.in_flight = Some(Mutex::new(Box::pin(async move {
+                let identity_cache = runtime_components.identity_cache();
+                let identity = identity_cache
+                    .resolve_cached_identity(resolver, &runtime_components, &config_bag)
+                    .await?;
+                Ok(identity)
+            })));
+            true
+        }
+
+        fn poll_refresh(&mut self, cx: &mut Context<'_>) -> Poll<Result<Identity, BoxError>> {
+            let in_flight = self
+                .in_flight
+                .as_mut()
+                .expect("a refresh was started with `try_start`");
+            let result = ready!(in_flight.get_mut().unwrap().as_mut().poll(cx));
+            self.in_flight = None;
+            Poll::Ready(result)
+        }
+    }
+
+    impl fmt::Debug for IdentityRefresh {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("IdentityRefresh")
+                .field("resolver", &self.resolver)
+                .field("last_attempt", &self.last_attempt)
+                .field("in_flight", &self.in_flight.is_some())
+                .finish()
+        }
+    }
 
     /// Event Stream SigV4 signing implementation.
     #[derive(Debug)]
@@ -265,6 +431,7 @@ mod event_stream {
         signing_region: SigningRegion,
         signing_name: SigningName,
         time: SharedTimeSource,
+        identity_refresh: Option<IdentityRefresh>,
     }
 
     impl SigV4MessageSigner {
@@ -281,9 +448,32 @@ mod event_stream {
                 signing_region,
                 signing_name,
                 time,
+                identity_refresh: None,
             }
         }
 
+        /// Refreshes the signing identity with `identity_refresh` when it is about to expire.
+        ///
+        /// If a refresh fails, messages are signed with the current identity until it expires.
+        pub(super) fn with_identity_refresh(mut self, identity_refresh: IdentityRefresh) -> Self {
+            self.identity_refresh = Some(identity_refresh);
+            self
+        }
+
+        fn identity_expires_soon(&self) -> bool {
+            self.identity
+                .expiration()
+                .map(|expiration| self.time.now() + IDENTITY_REFRESH_BUFFER >= expiration)
+                .unwrap_or(false)
+        }
+
+        fn identity_expired(&self) -> bool {
+            self.identity
+                .expiration()
+                .map(|expiration| self.time.now() >= expiration)
+                .unwrap_or(false)
+        }
+
         fn signing_params(&self) -> v4::SigningParams<'_, ()> {
             let builder = v4::SigningParams::builder()
                 .identity(&self.identity)
@@ -315,20 +505,63 @@ mod event_stream {
             self.last_signature = signature;
             Some(Ok(signed_message))
         }
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SignMessageError>> {
+            let now = self.time.now();
+            let expires_soon = self.identity_expires_soon();
+            let Some(identity_refresh) = self.identity_refresh.as_mut() else {
+                return Poll::Ready(Ok(()));
+            };
+            let refreshing = identity_refresh.in_flight.is_some();
+            if !refreshing && (!expires_soon || !identity_refresh.try_start(now)) {
+                return Poll::Ready(Ok(()));
+            }
+            match ready!(identity_refresh.poll_refresh(cx)) {
+                Ok(identity) => {
+                    tracing::debug!(
+                        expiration = ?identity.expiration(),
+                        "refreshed the identity used to sign event stream messages"
+                    );
+                    self.identity = identity;
+                }
+                Err(err) if !self.identity_expired() => {
+                    tracing::warn!(
+                        error = %err,
+                        "failed to refresh the identity used to sign event stream messages; \
+                         the current identity is used until it expires"
+                    );
+                }
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+            Poll::Ready(Ok(()))
+        }
     }
 
     #[cfg(test)]
     mod tests {
-        use crate::auth::sigv4::event_stream::SigV4MessageSigner;
+        use crate::auth::sigv4::event_stream::{IdentityRefresh, SigV4MessageSigner};
+        use crate::auth::sigv4::SCHEME_ID;
+        use aws_credential_types::credential_fn::provide_credentials_fn;
+        use aws_credential_types::provider::error::CredentialsError;
+        use aws_credential_types::provider::SharedCredentialsProvider;
         use aws_credential_types::Credentials;
+        use aws_smithy_async::test_util::ManualTimeSource;
         use aws_smithy_async::time::SharedTimeSource;
         use aws_smithy_eventstream::frame::SignMessage;
+        use aws_smithy_runtime::client::identity::IdentityCache;
+        use aws_smithy_runtime_api::client::identity::{IdentityFuture, ResolveIdentity};
+        use aws_smithy_runtime_api::client::runtime_components::{
+            GetIdentityResolver, RuntimeComponents, RuntimeComponentsBuilder,
+        };
+        use aws_smithy_types::config_bag::{ConfigBag, Layer, Storable, StoreReplace};
         use aws_smithy_types::event_stream::{HeaderValue, Message};
 
         use aws_types::region::Region;
         use aws_types::region::SigningRegion;
         use aws_types::SigningName;
-        use std::time::{Duration, UNIX_EPOCH};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
         fn check_send_sync<T: Send + Sync>(value: T) -> T {
             value
@@ -365,6 +598,153 @@ mod event_stream {
                 assert_ne!(signatures[i - 1], signatures[i]);
             }
         }
+
+        fn now() -> SystemTime {
+            UNIX_EPOCH + Duration::from_secs(1611160427)
+        }
+
+        fn credentials(access_key_id: &str, expires_in: u64) -> Credentials {
+            Credentials::new(
+                access_key_id,
+                "secret",
+                None,
+                Some(now() + Duration::from_secs(expires_in)),
+                "test",
+            )
+        }
+
+        fn signer_with_refresh(
+            resolver: impl ResolveIdentity + 'static,
+            config_bag: ConfigBag,
+            time: SharedTimeSource,
+        ) -> SigV4MessageSigner {
+            let runtime_components = RuntimeComponentsBuilder::for_tests()
+                .with_identity_cache(Some(IdentityCache::no_cache()))
+                .with_identity_resolver(SCHEME_ID, resolver)
+                .build()
+                .unwrap();
+            let identity_refresh = IdentityRefresh::new(
+                runtime_components.identity_resolver(SCHEME_ID).unwrap(),
+                runtime_components,
+                config_bag,
+            );
+            SigV4MessageSigner::new(
+                "initial-signature".into(),
+                credentials("expiring", 5).into(),
+                SigningRegion::from(Region::new("us-east-1")),
+                SigningName::from_static("transcribe"),
+                time,
+            )
+            .with_identity_refresh(identity_refresh)
+        }
+
+        fn access_key_id(signer: &SigV4MessageSigner) -> String {
+            signer
+                .identity
+                .data::<Credentials>()
+                .unwrap()
+                .access_key_id()
+                .to_string()
+        }
+
+        #[tokio::test]
+        async fn refresh_expiring_identity() {
+            let mut signer = signer_with_refresh(
+                SharedCredentialsProvider::new(credentials("refreshed", 3600)),
+                ConfigBag::base(),
+                SharedTimeSource::new(now()),
+            );
+
+            std::future::poll_fn(|cx| signer.poll_ready(cx))
+                .await
+                .unwrap();
+            assert_eq!("refreshed", access_key_id(&signer));
+            signer.sign(Message::new(&b"message"[..])).unwrap();
+
+            // The refreshed identity doesn't need to be refreshed again
+            assert!(!signer.identity_expires_soon());
+            std::future::poll_fn(|cx| signer.poll_ready(cx))
+                .await
+                .unwrap();
+            assert_eq!("refreshed", access_key_id(&signer));
+        }
+
+        #[tokio::test]
+        async fn refresh_resolves_with_the_operation_config() {
+            #[derive(Debug)]
+            struct AccessKeyId(&'static str);
+            impl Storable for AccessKeyId {
+                type Storer = StoreReplace<Self>;
+            }
+
+            #[derive(Debug)]
+            struct AccessKeyIdFromConfig;
+            impl ResolveIdentity for AccessKeyIdFromConfig {
+                fn resolve_identity<'a>(
+                    &'a self,
+                    _runtime_components: &'a RuntimeComponents,
+                    config_bag: &'a ConfigBag,
+                ) -> IdentityFuture<'a> {
+                    let access_key_id = config_bag.load::<AccessKeyId>().expect("configured").0;
+                    IdentityFuture::ready(Ok(credentials(access_key_id, 3600).into()))
+                }
+            }
+
+            let mut layer = Layer::new("operation");
+            layer.store_put(AccessKeyId("from-operation-config"));
+            let mut signer = signer_with_refresh(
+                AccessKeyIdFromConfig,
+                ConfigBag::of_layers(vec![layer]),
+                SharedTimeSource::new(now()),
+            );
+            std::future::poll_fn(|cx| signer.poll_ready(cx))
+                .await
+                .unwrap();
+            assert_eq!("from-operation-config", access_key_id(&signer));
+        }
+
+        #[tokio::test]
+        async fn failed_refreshes_keep_the_identity_until_it_expires() {
+            let attempts = Arc::new(AtomicUsize::new(0));
+            let provider = provide_credentials_fn({
+                let attempts = attempts.clone();
+                move || {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    async { Err(CredentialsError::not_loaded("unavailable")) }
+                }
+            });
+            let time = ManualTimeSource::new(now());
+            let mut signer = signer_with_refresh(
+                SharedCredentialsProvider::new(provider),
+                ConfigBag::base(),
+                SharedTimeSource::new(time.clone()),
+            );
+
+            std::future::poll_fn(|cx| signer.poll_ready(cx))
+                .await
+                .unwrap();
+            assert_eq!("expiring", access_key_id(&signer));
+            assert_eq!(1, attempts.load(Ordering::SeqCst));
+            signer.sign(Message::new(&b"message"[..])).unwrap();
+
+            // Refreshes are rate limited
+            std::future::poll_fn(|cx| signer.poll_ready(cx))
+                .await
+                .unwrap();
+            assert_eq!(1, attempts.load(Ordering::SeqCst));
+            time.advance(Duration::from_secs(1));
+            std::future::poll_fn(|cx| signer.poll_ready(cx))
+                .await
+                .unwrap();
+            assert_eq!(2, attempts.load(Ordering::SeqCst));
+
+            // Once the identity has expired, failing to refresh it is an error
+            time.advance(Duration::from_secs(4));
+            std::future::poll_fn(|cx| signer.poll_ready(cx))
+                .await
+                .expect_err("the identity expired");
+            assert_eq!(3, attempts.load(Ordering::SeqCst));
+        }
     }
 }
 
@@ -458,6 +838,52 @@ mod tests {
         assert!(matches!(result, Cow::Owned(_)));
     }
 
+    #[test]
+    fn presign_arbitrary_http_request() {
+        let mut request =
+            HttpRequest::get("https://abc123.execute-api.us-east-1.amazonaws.com/prod/items?id=5")
+                .unwrap();
+        presign_http_request(
+            &mut request,
+            &Credentials::for_tests_with_session_token(),
+            &SigningRegion::from_static("us-east-1"),
+            &SigningName::from_static("execute-api"),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+            Duration::from_secs(600),
+        )
+        .unwrap();
+
+        let uri = request.uri();
+        assert!(
+            uri.starts_with("https://abc123.execute-api.us-east-1.amazonaws.com/prod/items?id=5&"),
+            "{uri}"
+        );
+        for param in [
+            "X-Amz-Algorithm=AWS4-HMAC-SHA256",
+            "X-Amz-Date=20200913T122640Z",
+            "X-Amz-Expires=600",
+            "X-Amz-Signature=",
+            "X-Amz-Security-Token=",
+        ] {
+            assert!(uri.contains(param), "missing `{param}` in {uri}");
+        }
+        assert!(request.headers().get("authorization").is_none());
+    }
+
+    #[test]
+    fn presign_rejects_excessive_expiration() {
+        let mut request = HttpRequest::get("https://example.amazonaws.com/").unwrap();
+        let result = presign_http_request(
+            &mut request,
+            &Credentials::for_tests(),
+            &SigningRegion::from_static("us-east-1"),
+            &SigningName::from_static("execute-api"),
+            SystemTime::UNIX_EPOCH,
+            Duration::from_secs(8 * 24 * 60 * 60),
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn endpoint_config_supports_fallback_when_region_or_service_are_unset() {
         let mut layer = Layer::new("test");
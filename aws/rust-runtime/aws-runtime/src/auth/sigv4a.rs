@@ -5,10 +5,13 @@
 
 use crate::auth::{
     apply_signing_instructions, extract_endpoint_auth_scheme_signing_name,
-    extract_endpoint_auth_scheme_signing_options, SigV4OperationSigningConfig, SigV4SigningError,
+    extract_endpoint_auth_scheme_signing_options, signing_debug, signing_properties,
+    HttpSignatureType, SigV4OperationSigningConfig, SigV4SigningError, SigningOptions,
 };
 use aws_credential_types::Credentials;
-use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::http_request::{
+    presign, sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
+};
 use aws_sigv4::sign::v4a;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::{
@@ -21,7 +24,7 @@ use aws_smithy_types::config_bag::ConfigBag;
 use aws_types::region::SigningRegionSet;
 use aws_types::SigningName;
 use std::borrow::Cow;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 const EXPIRATION_WARNING: &str = "Presigned request will expire before the given \
         `expires_in` duration because the credentials used to sign it will expire first.";
@@ -122,9 +125,11 @@ impl SigV4aSigner {
         let name = extract_endpoint_auth_scheme_signing_name(&auth_scheme_endpoint_config)?
             .or(config_bag.load::<SigningName>().cloned());
 
-        let region_set =
-            extract_endpoint_auth_scheme_signing_region_set(&auth_scheme_endpoint_config)?
-                .or(config_bag.load::<SigningRegionSet>().cloned());
+        // A region set configured by the user takes precedence over the one from the endpoint
+        let region_set = match config_bag.load::<SigningRegionSet>() {
+            Some(region_set) => Some(region_set.clone()),
+            None => extract_endpoint_auth_scheme_signing_region_set(&auth_scheme_endpoint_config)?,
+        };
 
         let signing_options = extract_endpoint_auth_scheme_signing_options(
             &auth_scheme_endpoint_config,
@@ -183,7 +188,8 @@ impl Sign for SigV4aSigner {
             return Err(SigV4SigningError::WrongIdentityType(identity.clone()).into());
         }
 
-        let settings = Self::settings(&operation_config);
+        let mut settings = Self::settings(&operation_config);
+        settings.capture_debug_info = signing_debug::capture_enabled(config_bag);
         let signing_params =
             Self::signing_params(settings, identity, &operation_config, request_time)?;
 
@@ -215,20 +221,80 @@ impl Sign for SigV4aSigner {
         }
         .into_parts();
 
+        signing_debug::record(config_bag, &signing_instructions);
+        signing_properties::record(config_bag, SCHEME_ID, &operation_config);
         apply_signing_instructions(signing_instructions, request)?;
         Ok(())
     }
 }
 
+/// Presigns an arbitrary HTTP request with SigV4a by adding the signature to its query string.
+///
+/// SigV4a signatures are valid in every region of `region_set`, which makes this suitable for
+/// presigning requests to multi-region endpoints such as S3 Multi-Region Access Points, where
+/// `region_set` is `*`. A body that is already in memory is included in the signature. Any other
+/// body is signed as `UNSIGNED-PAYLOAD`.
+///
+/// `expires_in` must be greater than zero and at most one week. A warning is logged if the
+/// credentials expire before the presigned request does.
+pub fn presign_http_request(
+    request: &mut HttpRequest,
+    credentials: &Credentials,
+    region_set: &SigningRegionSet,
+    name: &SigningName,
+    time: SystemTime,
+    expires_in: Duration,
+) -> Result<(), BoxError> {
+    let operation_config = SigV4OperationSigningConfig {
+        region_set: Some(region_set.clone()),
+        name: Some(name.clone()),
+        signing_options: SigningOptions {
+            signature_type: HttpSignatureType::HttpRequestQueryParams,
+            expires_in: Some(expires_in),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let identity = Identity::from(credentials.clone());
+    let settings = SigV4aSigner::settings(&operation_config);
+    let signing_params =
+        SigV4aSigner::signing_params(settings, &identity, &operation_config, time)?;
+
+    let (signing_instructions, _signature) = {
+        let signable_body = request
+            .body()
+            .bytes()
+            .map(SignableBody::Bytes)
+            .unwrap_or(SignableBody::UnsignedPayload);
+        let signable_request = SignableRequest::new(
+            request.method(),
+            request.uri(),
+            request.headers().iter(),
+            signable_body,
+        )?;
+        presign(
+            signable_request,
+            SigningParams::V4a(signing_params),
+            expires_in,
+        )?
+    }
+    .into_parts();
+    apply_signing_instructions(signing_instructions, request)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{SigV4OperationSigningConfig, SigV4aSigner, EXPIRATION_WARNING};
+    use super::{
+        presign_http_request, SigV4OperationSigningConfig, SigV4aSigner, EXPIRATION_WARNING,
+    };
     use crate::auth::{HttpSignatureType, SigningOptions};
     use aws_credential_types::Credentials;
     use aws_sigv4::http_request::SigningSettings;
     use aws_smithy_runtime_api::client::auth::AuthSchemeEndpointConfig;
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
     use aws_smithy_types::config_bag::{ConfigBag, Layer};
     use aws_smithy_types::Document;
+    use aws_types::region::SigningRegionSet;
     use aws_types::SigningName;
     use std::borrow::Cow;
     use std::collections::HashMap;
@@ -305,6 +371,71 @@ mod tests {
         assert!(matches!(result, Cow::Owned(_)));
     }
 
+    #[test]
+    fn configured_region_set_overrides_endpoint_config() {
+        let mut layer = Layer::new("test");
+        layer.store_put(SigV4OperationSigningConfig {
+            region_set: Some("test".into()),
+            name: Some(SigningName::from_static("s3")),
+            ..Default::default()
+        });
+        layer.store_put(SigningRegionSet::from("us-west-2,us-east-1"));
+        let config = Document::Object({
+            let mut out = HashMap::new();
+            out.insert("name".to_owned(), "sigv4a".to_owned().into());
+            out.insert(
+                "signingRegionSet".to_string(),
+                Document::Array(vec!["*".to_string().into()]),
+            );
+            out
+        });
+        let config = AuthSchemeEndpointConfig::from(Some(&config));
+
+        let cfg = ConfigBag::of_layers(vec![layer]);
+        let result = SigV4aSigner::extract_operation_config(config, &cfg).expect("success");
+
+        assert_eq!(result.region_set, Some("us-west-2,us-east-1".into()));
+        assert_eq!(result.name, Some(SigningName::from_static("s3")));
+    }
+
+    #[test]
+    fn presign_multi_region_request() {
+        let mut request = HttpRequest::get(
+            "https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com/key?x-id=GetObject",
+        )
+        .unwrap();
+        presign_http_request(
+            &mut request,
+            &Credentials::for_tests_with_session_token(),
+            &SigningRegionSet::from("*"),
+            &SigningName::from_static("s3"),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+            Duration::from_secs(600),
+        )
+        .unwrap();
+
+        let uri = request.uri();
+        assert!(
+            uri.starts_with("https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com/key?x-id=GetObject&"),
+            "{uri}"
+        );
+        for param in [
+            "X-Amz-Algorithm=AWS4-ECDSA-P256-SHA256",
+            // the credential scope of SigV4a doesn't include a region
+            "X-Amz-Credential=ANOTREAL%2F20200913%2Fs3%2Faws4_request",
+            "X-Amz-Date=20200913T122640Z",
+            "X-Amz-Expires=600",
+            "X-Amz-Region-Set=%2A",
+            "X-Amz-SignedHeaders=host",
+            "X-Amz-Signature=",
+            "X-Amz-Security-Token=",
+        ] {
+            assert!(uri.contains(param), "missing `{param}` in {uri}");
+        }
+        assert!(request.headers().get("authorization").is_none());
+        assert!(request.headers().get("x-amz-region-set").is_none());
+    }
+
     #[test]
     fn endpoint_config_supports_fallback_when_region_or_service_are_unset() {
         let mut layer = Layer::new("test");
@@ -3,7 +3,10 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeSerializationInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 use bytes::{Bytes, BytesMut};
 use http_02x::{HeaderMap, HeaderValue};
 use http_body_04x::{Body, SizeHint};
@@ -36,6 +39,9 @@ pub struct AwsChunkedBodyOptions {
     /// Whether the aws-chunked encoding is disabled. This could occur, for instance,
     /// if a user specifies a custom checksum, rendering aws-chunked encoding unnecessary.
     disabled: bool,
+    /// Trailers to send in addition to the trailers of the inner body, such as user-provided
+    /// metadata. Their lengths are included in `trailer_lengths`.
+    additional_trailers: HeaderMap,
 }
 
 impl Storable for AwsChunkedBodyOptions {
@@ -49,6 +55,7 @@ impl AwsChunkedBodyOptions {
             stream_length,
             trailer_lengths,
             disabled: false,
+            additional_trailers: HeaderMap::new(),
         }
     }
 
@@ -70,6 +77,19 @@ impl AwsChunkedBodyOptions {
         self
     }
 
+    /// Append trailers to send after the trailers of the inner body
+    ///
+    /// The length of each trailer is accounted for automatically, so there is no need to call
+    /// [`AwsChunkedBodyOptions::with_trailer_len`] for these.
+    pub fn with_trailers(mut self, trailers: HeaderMap) -> Self {
+        for (name, value) in trailers.iter() {
+            self.trailer_lengths
+                .push((name.as_str().len() + TRAILER_SEPARATOR.len() + value.len()) as u64);
+        }
+        self.additional_trailers.extend(trailers);
+        self
+    }
+
     /// Create a new [`AwsChunkedBodyOptions`] with aws-chunked encoding disabled.
     ///
     /// When the option is disabled, the body must not be wrapped in an `AwsChunkedBody`.
@@ -107,6 +127,78 @@ impl AwsChunkedBodyOptions {
     }
 }
 
+/// Trailers to send with an aws-chunked encoded request body, in addition to the checksum trailer
+///
+/// When this is present in the config bag of a streaming operation that uses aws-chunked encoding,
+/// each trailer is appended to the encoded body and its name is added to the `x-amz-trailer`
+/// header. Since `x-amz-trailer` is signed along with the other request headers, the service
+/// rejects requests whose trailer names don't match the signed declaration. Trailer values are
+/// not covered by the signature, because signed chunks are not supported.
+///
+/// Use [`AwsChunkedTrailersInterceptor`] to add trailers to a single operation.
+#[derive(Clone, Debug, Default)]
+pub struct AwsChunkedTrailers(HeaderMap);
+
+impl AwsChunkedTrailers {
+    /// Creates a new `AwsChunkedTrailers` from the given trailers
+    pub fn new(trailers: HeaderMap) -> Self {
+        Self(trailers)
+    }
+
+    /// Returns the trailers
+    pub fn trailers(&self) -> &HeaderMap {
+        &self.0
+    }
+}
+
+impl Storable for AwsChunkedTrailers {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Interceptor that sends the given trailers with an aws-chunked encoded request body
+///
+/// This is intended to be added to individual operations that upload streaming bodies with
+/// aws-chunked encoding, for example with `.customize().interceptor(...)`. The operation fails if
+/// its request body isn't aws-chunked encoded, since trailers can't be sent otherwise.
+///
+/// # Examples
+/// ```no_run
+/// use aws_runtime::content_encoding::AwsChunkedTrailersInterceptor;
+/// use http_02x::{HeaderMap, HeaderValue};
+///
+/// let mut trailers = HeaderMap::new();
+/// trailers.insert("x-amz-meta-sha1", HeaderValue::from_static("2fd4e1c67a2d28fced849ee1bb76e7391b93eb12"));
+/// let interceptor = AwsChunkedTrailersInterceptor::new(trailers);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AwsChunkedTrailersInterceptor {
+    trailers: AwsChunkedTrailers,
+}
+
+impl AwsChunkedTrailersInterceptor {
+    /// Creates a new `AwsChunkedTrailersInterceptor` that sends `trailers`
+    pub fn new(trailers: HeaderMap) -> Self {
+        Self {
+            trailers: AwsChunkedTrailers::new(trailers),
+        }
+    }
+}
+
+impl Intercept for AwsChunkedTrailersInterceptor {
+    fn name(&self) -> &'static str {
+        "AwsChunkedTrailersInterceptor"
+    }
+
+    fn read_before_execution(
+        &self,
+        _context: &BeforeSerializationInterceptorContextRef<'_>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        cfg.interceptor_state().store_put(self.trailers.clone());
+        Ok(())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 enum AwsChunkedBodyState {
     /// Write out the size of the chunk that will follow. Then, transition into the
@@ -207,6 +299,18 @@ fn trailers_as_aws_chunked_bytes(
     }
 }
 
+/// Appends `additional` to the trailers of the inner body, keeping the inner trailers first.
+fn merge_trailers(trailers: Option<HeaderMap>, additional: HeaderMap) -> Option<HeaderMap> {
+    if additional.is_empty() {
+        return trailers;
+    }
+    let mut trailers = trailers.unwrap_or_default();
+    for (name, value) in additional.iter() {
+        trailers.append(name.clone(), value.clone());
+    }
+    Some(trailers)
+}
+
 /// Given an optional `HeaderMap`, calculate the total number of bytes required to represent the
 /// `HeaderMap`. If no `HeaderMap` is given as input, return 0.
 ///
@@ -288,6 +392,10 @@ where
                 return match this.inner.poll_trailers(cx) {
                     Poll::Ready(Ok(trailers)) => {
                         *this.state = AwsChunkedBodyState::Closed;
+                        let trailers = merge_trailers(
+                            trailers,
+                            std::mem::take(&mut this.options.additional_trailers),
+                        );
                         let expected_length = total_rendered_length_of_trailers(trailers.as_ref());
                         let actual_length = this.options.total_trailer_length();
 
@@ -501,6 +609,94 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_aws_chunked_encoding_with_additional_trailers() {
+        let input_str = "Hello world";
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-amz-meta-a", HeaderValue::from_static("1"));
+        trailers.append("x-amz-meta-b", HeaderValue::from_static("two"));
+        let opts =
+            AwsChunkedBodyOptions::new(input_str.len() as u64, Vec::new()).with_trailers(trailers);
+        let expected_length = opts.encoded_length();
+        let mut body = AwsChunkedBody::new(SdkBody::from(input_str), opts);
+
+        let mut output = SegmentedBuf::new();
+        while let Some(buf) = body.data().await {
+            output.push(buf.unwrap());
+        }
+
+        let mut actual_output = String::new();
+        output
+            .reader()
+            .read_to_string(&mut actual_output)
+            .expect("Doesn't cause IO errors");
+
+        let expected_output = "B\r\nHello world\r\n0\r\nx-amz-meta-a:1\r\nx-amz-meta-b:two\r\n\r\n";
+        assert_eq!(expected_output, actual_output);
+        assert_eq!(expected_length, actual_output.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_aws_chunked_encoding_merges_additional_trailers_with_body_trailers() {
+        struct BodyWithTrailers {
+            data: Option<Bytes>,
+            trailers: Option<HeaderMap>,
+        }
+
+        impl Body for BodyWithTrailers {
+            type Data = Bytes;
+            type Error = aws_smithy_types::body::Error;
+
+            fn poll_data(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+                Poll::Ready(self.data.take().map(Ok))
+            }
+
+            fn poll_trailers(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Result<Option<HeaderMap<HeaderValue>>, Self::Error>> {
+                Poll::Ready(Ok(self.trailers.take()))
+            }
+        }
+
+        let input_str = "Hello world";
+        let mut body_trailers = HeaderMap::new();
+        body_trailers.insert("x-amz-checksum-crc32", HeaderValue::from_static("i9aeUg=="));
+        let mut additional_trailers = HeaderMap::new();
+        additional_trailers.insert("x-amz-meta-a", HeaderValue::from_static("1"));
+        let opts = AwsChunkedBodyOptions::new(input_str.len() as u64, Vec::new())
+            .with_trailer_len("x-amz-checksum-crc32:i9aeUg==".len() as u64)
+            .with_trailers(additional_trailers);
+        let expected_length = opts.encoded_length();
+        let mut body = AwsChunkedBody::new(
+            BodyWithTrailers {
+                data: Some(Bytes::from_static(input_str.as_bytes())),
+                trailers: Some(body_trailers),
+            },
+            opts,
+        );
+
+        let mut output = SegmentedBuf::new();
+        while let Some(buf) = body.data().await {
+            output.push(buf.unwrap());
+        }
+
+        let mut actual_output = String::new();
+        output
+            .reader()
+            .read_to_string(&mut actual_output)
+            .expect("Doesn't cause IO errors");
+
+        // The body's own trailers come first, followed by the additional trailers
+        let expected_output =
+            "B\r\nHello world\r\n0\r\nx-amz-checksum-crc32:i9aeUg==\r\nx-amz-meta-a:1\r\n\r\n";
+        assert_eq!(expected_output, actual_output);
+        assert_eq!(expected_length, actual_output.len() as u64);
+    }
+
     #[tokio::test]
     async fn test_aws_chunked_encoding_sputtering_body() {
         let test_fut = async {
@@ -0,0 +1,983 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Endpoint discovery for services modeled with `@clientEndpointDiscovery`
+//!
+//! These services have an operation, usually `DescribeEndpoints`, that returns the endpoints
+//! requests should be sent to and how long they can be cached for. [`EndpointDiscoveryResolver`]
+//! caches the discovered endpoints per [`DiscoveryKey`], and [`ReloadEndpoint`] refreshes them
+//! in the background before they expire. Operations that don't use endpoint discovery, or whose
+//! discovery is optional and failed, are resolved by the regional fallback resolver.
+//!
+//! Concurrent requests for the same key share a single discovery. When discovery fails, it isn't
+//! attempted again for that key until [`FAILURE_BACKOFF`] has passed. At most
+//! [`MAX_CACHED_KEYS`] keys are cached, evicting the least recently used, and only keys that were
+//! used within [`IDLE_TIMEOUT`] are refreshed in the background.
+
+use aws_smithy_async::future::BoxFuture;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::{
+    EndpointFuture, EndpointResolverParams, ResolveEndpoint, SharedEndpointResolver,
+};
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeSerializationInterceptorContextMut, Input,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::endpoint::Endpoint;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime};
+
+/// Cached endpoints are refreshed when they expire within this buffer
+const EXPIRY_BUFFER: Duration = Duration::from_secs(120);
+/// How often [`ReloadEndpoint::reload_task`] checks for expiring endpoints
+const RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+/// How long discovery isn't attempted again for a key after it failed
+pub const FAILURE_BACKOFF: Duration = Duration::from_secs(60);
+/// How many keys the endpoints are cached for before the least recently used are evicted
+pub const MAX_CACHED_KEYS: usize = 1000;
+/// Keys that haven't been used for this long aren't refreshed, and are evicted once their
+/// endpoints expire
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Identifies the endpoints discovered for a request
+///
+/// Operations with members marked `@clientEndpointDiscoveryId` discover endpoints for their
+/// operation name and the values of those members. All other operations share the endpoints
+/// discovered for the default key.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DiscoveryKey {
+    operation: Option<String>,
+    identifiers: BTreeMap<String, String>,
+}
+
+impl DiscoveryKey {
+    /// Creates a key for the endpoints of `operation`
+    pub fn for_operation(operation: impl Into<String>) -> Self {
+        Self {
+            operation: Some(operation.into()),
+            identifiers: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an endpoint discovery identifier to this key
+    pub fn with_identifier(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.identifiers.insert(name.into(), value.into());
+        self
+    }
+
+    /// The operation endpoints are discovered for, if it has endpoint discovery identifiers
+    pub fn operation(&self) -> Option<&str> {
+        self.operation.as_deref()
+    }
+
+    /// The endpoint discovery identifiers, sorted by name
+    pub fn identifiers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.identifiers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// How an operation uses endpoint discovery
+///
+/// This is set as a property of the [`EndpointResolverParams`] by
+/// [`EndpointDiscoveryInterceptor`]. Requests without it are resolved by the fallback resolver.
+#[derive(Clone, Debug)]
+pub struct DiscoveryRequest {
+    key: DiscoveryKey,
+    required: bool,
+}
+
+impl DiscoveryRequest {
+    /// Creates a `DiscoveryRequest` for the endpoints identified by `key`
+    ///
+    /// If `required` is true, requests fail when endpoint discovery fails. Otherwise, they fall
+    /// back to the regional endpoint.
+    pub fn new(key: DiscoveryKey, required: bool) -> Self {
+        Self { key, required }
+    }
+
+    /// The key identifying the endpoints to use
+    pub fn key(&self) -> &DiscoveryKey {
+        &self.key
+    }
+
+    /// Returns true if the operation can't be called without a discovered endpoint
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+/// An endpoint returned by a service's endpoint discovery operation
+#[derive(Clone, Debug)]
+pub struct DiscoveredEndpoint {
+    endpoint: Endpoint,
+    cache_period: Duration,
+}
+
+impl DiscoveredEndpoint {
+    /// Creates a `DiscoveredEndpoint` that can be cached for `cache_period`
+    pub fn new(endpoint: Endpoint, cache_period: Duration) -> Self {
+        Self {
+            endpoint,
+            cache_period,
+        }
+    }
+
+    /// Creates a `DiscoveredEndpoint` from the `Address` and `CachePeriodInMinutes` returned by
+    /// the endpoint discovery operation
+    ///
+    /// Addresses without a scheme use `https`.
+    pub fn from_address(address: &str, cache_period_in_minutes: i64) -> Self {
+        let url = if address.contains("://") {
+            address.to_string()
+        } else {
+            format!("https://{address}")
+        };
+        Self::new(
+            Endpoint::builder().url(url).build(),
+            Duration::from_secs(cache_period_in_minutes.max(0) as u64 * 60),
+        )
+    }
+
+    /// The discovered endpoint
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// How long the endpoint can be cached for
+    pub fn cache_period(&self) -> Duration {
+        self.cache_period
+    }
+}
+
+type DiscoveryKeyFn = Arc<dyn Fn(&Input) -> DiscoveryKey + Send + Sync>;
+
+/// Interceptor that marks the requests of an operation as using endpoint discovery
+pub struct EndpointDiscoveryInterceptor {
+    key: DiscoveryKeyFn,
+    required: bool,
+}
+
+impl fmt::Debug for EndpointDiscoveryInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointDiscoveryInterceptor")
+            .field("required", &self.required)
+            .finish()
+    }
+}
+
+impl EndpointDiscoveryInterceptor {
+    /// Creates an interceptor for an operation whose endpoints are identified by `key`
+    pub fn new(key: DiscoveryKey, required: bool) -> Self {
+        Self {
+            key: Arc::new(move |_| key.clone()),
+            required,
+        }
+    }
+
+    /// Creates an interceptor for an operation whose endpoints are identified by the
+    /// `@clientEndpointDiscoveryId` members of its input
+    ///
+    /// `key` is called with the operation input of each request. Requests whose input isn't an
+    /// `I` use the default key.
+    pub fn from_input<I, F>(key: F, required: bool) -> Self
+    where
+        I: fmt::Debug + Send + Sync + 'static,
+        F: Fn(&I) -> DiscoveryKey + Send + Sync + 'static,
+    {
+        Self {
+            key: Arc::new(move |input| input.downcast_ref::<I>().map(&key).unwrap_or_default()),
+            required,
+        }
+    }
+}
+
+impl Intercept for EndpointDiscoveryInterceptor {
+    fn name(&self) -> &'static str {
+        "EndpointDiscoveryInterceptor"
+    }
+
+    fn modify_before_serialization(
+        &self,
+        context: &mut BeforeSerializationInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let request = DiscoveryRequest::new((self.key)(context.input()), self.required);
+        let params = cfg
+            .get_mut_from_interceptor_state::<EndpointResolverParams>()
+            .ok_or("endpoint resolver params must be set before serialization")?;
+        params.set_property(request);
+        Ok(())
+    }
+}
+
+type Discover =
+    Arc<dyn Fn(DiscoveryKey) -> BoxFuture<'static, DiscoveredEndpoint, BoxError> + Send + Sync>;
+
+#[derive(Debug)]
+struct ExpiringEndpoint {
+    endpoint: Endpoint,
+    expiry: SystemTime,
+}
+
+impl ExpiringEndpoint {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expiry <= now
+    }
+
+    fn expires_soon(&self, now: SystemTime) -> bool {
+        match self.expiry.duration_since(now) {
+            Err(_) => true,
+            Ok(remaining) => remaining < EXPIRY_BUFFER,
+        }
+    }
+}
+
+/// The error of a failed endpoint discovery, shared by the requests made during its backoff
+#[derive(Clone, Debug)]
+struct DiscoveryError(Arc<dyn Error + Send + Sync>);
+
+impl fmt::Display for DiscoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for DiscoveryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+type CachedResult = Option<Result<Endpoint, DiscoveryError>>;
+
+#[derive(Debug)]
+struct Failure {
+    error: DiscoveryError,
+    retry_after: SystemTime,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    endpoint: Option<ExpiringEndpoint>,
+    failure: Option<Failure>,
+    last_used: SystemTime,
+    /// Held while endpoints are discovered for the key, so that concurrent discoveries are deduplicated
+    discovering: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl CacheEntry {
+    fn new(now: SystemTime) -> Self {
+        Self {
+            endpoint: None,
+            failure: None,
+            last_used: now,
+            discovering: Default::default(),
+        }
+    }
+
+    /// The cached endpoint or recent failure, if discovery doesn't need to be attempted
+    fn cached(&self, now: SystemTime) -> CachedResult {
+        if let Some(cached) = self
+            .endpoint
+            .as_ref()
+            .filter(|cached| !cached.is_expired(now))
+        {
+            return Some(Ok(cached.endpoint.clone()));
+        }
+        self.failure
+            .as_ref()
+            .filter(|failure| failure.retry_after > now)
+            .map(|failure| Err(failure.error.clone()))
+    }
+
+    fn is_idle(&self, now: SystemTime) -> bool {
+        now.duration_since(self.last_used)
+            .is_ok_and(|idle| idle >= IDLE_TIMEOUT)
+    }
+}
+
+struct Inner {
+    discover: Discover,
+    fallback_resolver: Option<SharedEndpointResolver>,
+    time_source: SharedTimeSource,
+    cache: Mutex<HashMap<DiscoveryKey, CacheEntry>>,
+}
+
+impl Inner {
+    /// Marks `key` as used, and returns its cached endpoint or recent failure, along with the lock
+    /// to hold while discovering its endpoints
+    fn use_key(&self, key: &DiscoveryKey) -> (CachedResult, Arc<tokio::sync::Mutex<()>>) {
+        let now = self.time_source.now();
+        let mut cache = self.cache.lock().unwrap();
+        if !cache.contains_key(key) && cache.len() >= MAX_CACHED_KEYS {
+            let least_recently_used = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(evicted) = least_recently_used {
+                tracing::debug!(key = ?evicted, "evicting least recently used discovered endpoint");
+                cache.remove(&evicted);
+            }
+        }
+        let entry = cache
+            .entry(key.clone())
+            .or_insert_with(|| CacheEntry::new(now));
+        entry.last_used = now;
+        (entry.cached(now), entry.discovering.clone())
+    }
+
+    /// Returns the cached endpoint for `key`, discovering it if it isn't cached
+    async fn cached_or_discover(&self, key: &DiscoveryKey) -> Result<Endpoint, BoxError> {
+        let (cached, discovering) = self.use_key(key);
+        if let Some(cached) = cached {
+            return cached.map_err(Into::into);
+        }
+        let _guard = discovering.lock().await;
+        // another request may have discovered the endpoints while the lock was held
+        let now = self.time_source.now();
+        let cached = self
+            .cache
+            .lock()
+            .unwrap()
+            .get(key)
+            .and_then(|entry| entry.cached(now));
+        match cached {
+            Some(cached) => cached.map_err(Into::into),
+            None => self.discover_locked(key).await,
+        }
+    }
+
+    async fn discover(&self, key: &DiscoveryKey) -> Result<Endpoint, BoxError> {
+        let (_, discovering) = self.use_key(key);
+        let _guard = discovering.lock().await;
+        self.discover_locked(key).await
+    }
+
+    /// Discovers the endpoints for `key`. The caller must hold the key's `discovering` lock.
+    async fn discover_locked(&self, key: &DiscoveryKey) -> Result<Endpoint, BoxError> {
+        let result = (self.discover)(key.clone()).await;
+        let now = self.time_source.now();
+        let mut cache = self.cache.lock().unwrap();
+        let entry = cache
+            .entry(key.clone())
+            .or_insert_with(|| CacheEntry::new(now));
+        match result {
+            Ok(discovered) => {
+                let expiry = now + discovered.cache_period;
+                tracing::debug!(key = ?key, endpoint = ?discovered.endpoint, expiry = ?expiry, "caching discovered endpoint");
+                entry.endpoint = Some(ExpiringEndpoint {
+                    endpoint: discovered.endpoint.clone(),
+                    expiry,
+                });
+                entry.failure = None;
+                Ok(discovered.endpoint)
+            }
+            Err(err) => {
+                let error = DiscoveryError(Arc::from(err));
+                let retry_after = now + FAILURE_BACKOFF;
+                tracing::debug!(key = ?key, retry_after = ?retry_after, "endpoint discovery failed");
+                entry.failure = Some(Failure {
+                    error: error.clone(),
+                    retry_after,
+                });
+                Err(error.into())
+            }
+        }
+    }
+
+    async fn refresh(&self, only_expiring: bool) {
+        let now = self.time_source.now();
+        let keys: Vec<_> = {
+            let mut cache = self.cache.lock().unwrap();
+            cache.retain(|_, entry| {
+                !entry.is_idle(now)
+                    || entry
+                        .endpoint
+                        .as_ref()
+                        .is_some_and(|cached| !cached.is_expired(now))
+            });
+            cache
+                .iter()
+                .filter(|(_, entry)| !entry.is_idle(now))
+                .filter(|(_, entry)| {
+                    entry
+                        .failure
+                        .as_ref()
+                        .is_none_or(|failure| failure.retry_after <= now)
+                })
+                .filter(|(_, entry)| {
+                    !only_expiring
+                        || entry
+                            .endpoint
+                            .as_ref()
+                            .is_some_and(|cached| cached.expires_soon(now))
+                })
+                .map(|(key, entry)| (key.clone(), entry.discovering.clone()))
+                .collect()
+        };
+        for (key, discovering) in keys {
+            let _guard = discovering.lock().await;
+            tracing::debug!(key = ?key, "refreshing discovered endpoint");
+            if let Err(err) = self.discover_locked(&key).await {
+                tracing::warn!(key = ?key, err = %DisplayErrorContext(err.as_ref()), "failed to refresh discovered endpoint");
+            }
+        }
+    }
+
+    async fn resolve(
+        &self,
+        params: &EndpointResolverParams,
+        cfg: Option<&ConfigBag>,
+    ) -> Result<Endpoint, BoxError> {
+        let Some(request) = params.get_property::<DiscoveryRequest>() else {
+            return self.resolve_fallback(params, cfg).await;
+        };
+        match self.cached_or_discover(request.key()).await {
+            Ok(endpoint) => Ok(endpoint),
+            Err(err) if request.is_required() => Err(err),
+            Err(err) => {
+                tracing::debug!(err = %DisplayErrorContext(err.as_ref()), "endpoint discovery failed, using the regional endpoint");
+                self.resolve_fallback(params, cfg).await
+            }
+        }
+    }
+
+    async fn resolve_fallback(
+        &self,
+        params: &EndpointResolverParams,
+        cfg: Option<&ConfigBag>,
+    ) -> Result<Endpoint, BoxError> {
+        match (&self.fallback_resolver, cfg) {
+            (Some(resolver), Some(cfg)) => resolver.resolve_endpoint_with_config(params, cfg).await,
+            (Some(resolver), None) => resolver.resolve_endpoint(params).await,
+            (None, _) => Err("endpoint discovery isn't used for this request, and no fallback endpoint resolver was configured".into()),
+        }
+    }
+}
+
+/// Endpoint resolver that sends requests to discovered endpoints
+///
+/// Discovered endpoints are cached until their cache period ends. When a request needs an
+/// endpoint that isn't cached, it's discovered before the request is sent, and requests made
+/// while it's being discovered wait for the same discovery. Use
+/// [`EndpointDiscoveryResolver::reloader`] to refresh cached endpoints in the background instead.
+#[derive(Clone)]
+pub struct EndpointDiscoveryResolver {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for EndpointDiscoveryResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointDiscoveryResolver")
+            .field("fallback_resolver", &self.inner.fallback_resolver)
+            .field("cache", &self.inner.cache)
+            .finish()
+    }
+}
+
+impl EndpointDiscoveryResolver {
+    /// Returns a builder for `EndpointDiscoveryResolver`
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Discover and cache the endpoints for `key`
+    ///
+    /// This is useful to check that endpoint discovery works before any requests are sent.
+    pub async fn discover(&self, key: &DiscoveryKey) -> Result<Endpoint, BoxError> {
+        self.inner.discover(key).await
+    }
+
+    /// Returns a [`ReloadEndpoint`] that refreshes the endpoints cached by this resolver
+    pub fn reloader(&self, sleep_impl: SharedAsyncSleep) -> ReloadEndpoint {
+        ReloadEndpoint {
+            inner: Arc::downgrade(&self.inner),
+            sleep_impl,
+        }
+    }
+}
+
+impl ResolveEndpoint for EndpointDiscoveryResolver {
+    fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+        EndpointFuture::new(self.inner.resolve(params, None))
+    }
+
+    fn resolve_endpoint_with_config<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        cfg: &'a ConfigBag,
+    ) -> EndpointFuture<'a> {
+        EndpointFuture::new(self.inner.resolve(params, Some(cfg)))
+    }
+
+    fn finalize_params<'a>(
+        &'a self,
+        params: &'a mut EndpointResolverParams,
+    ) -> Result<(), BoxError> {
+        match &self.inner.fallback_resolver {
+            Some(resolver) => resolver.finalize_params(params),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builder for [`EndpointDiscoveryResolver`]
+#[derive(Default)]
+pub struct Builder {
+    discover: Option<Discover>,
+    fallback_resolver: Option<SharedEndpointResolver>,
+    time_source: Option<SharedTimeSource>,
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("fallback_resolver", &self.fallback_resolver)
+            .field("time_source", &self.time_source)
+            .finish()
+    }
+}
+
+impl Builder {
+    /// Set the function that calls the service's endpoint discovery operation
+    ///
+    /// This is required.
+    pub fn discover<F, Fut>(mut self, discover: F) -> Self
+    where
+        F: Fn(DiscoveryKey) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<DiscoveredEndpoint, BoxError>> + Send + 'static,
+    {
+        self.discover = Some(Arc::new(move |key| Box::pin(discover(key))));
+        self
+    }
+
+    /// Set the resolver for requests that don't use endpoint discovery
+    ///
+    /// This is also used when optional endpoint discovery fails.
+    pub fn fallback_resolver(mut self, resolver: impl ResolveEndpoint + 'static) -> Self {
+        self.set_fallback_resolver(Some(resolver.into_shared()));
+        self
+    }
+
+    /// Set the resolver for requests that don't use endpoint discovery
+    ///
+    /// This is also used when optional endpoint discovery fails.
+    pub fn set_fallback_resolver(&mut self, resolver: Option<SharedEndpointResolver>) -> &mut Self {
+        self.fallback_resolver = resolver;
+        self
+    }
+
+    /// Set the time source used to expire cached endpoints
+    ///
+    /// Defaults to the system time.
+    pub fn time_source(mut self, time_source: SharedTimeSource) -> Self {
+        self.time_source = Some(time_source);
+        self
+    }
+
+    /// Build the [`EndpointDiscoveryResolver`]
+    ///
+    /// # Panics
+    /// This will panic if [`Builder::discover`] wasn't called.
+    pub fn build(self) -> EndpointDiscoveryResolver {
+        EndpointDiscoveryResolver {
+            inner: Arc::new(Inner {
+                discover: self.discover.expect("discover is required"),
+                fallback_resolver: self.fallback_resolver,
+                time_source: self.time_source.unwrap_or_default(),
+                cache: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+/// Refreshes the endpoints cached by an [`EndpointDiscoveryResolver`]
+#[must_use]
+pub struct ReloadEndpoint {
+    inner: Weak<Inner>,
+    sleep_impl: SharedAsyncSleep,
+}
+
+impl fmt::Debug for ReloadEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadEndpoint").finish()
+    }
+}
+
+impl ReloadEndpoint {
+    /// Refresh the endpoints of every recently used key once
+    pub async fn reload_once(&self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.refresh(false).await;
+        }
+    }
+
+    /// An infinite loop task that refreshes the endpoints of recently used keys before they expire
+    ///
+    /// This task will terminate when the corresponding [`EndpointDiscoveryResolver`], and every
+    /// client using it, is dropped.
+    pub async fn reload_task(self) {
+        loop {
+            let Some(inner) = self.inner.upgrade() else {
+                break;
+            };
+            inner.refresh(true).await;
+            drop(inner);
+            self.sleep_impl.sleep(RELOAD_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_smithy_async::test_util::{controlled_time_and_sleep, ManualTimeSource};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::UNIX_EPOCH;
+
+    #[derive(Debug)]
+    struct RegionalResolver;
+
+    impl ResolveEndpoint for RegionalResolver {
+        fn resolve_endpoint<'a>(&'a self, _: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+            EndpointFuture::ready(Ok(Endpoint::builder()
+                .url("https://regional.example.com")
+                .build()))
+        }
+    }
+
+    fn counting_resolver(time_source: SharedTimeSource) -> EndpointDiscoveryResolver {
+        let count = Arc::new(AtomicUsize::new(0));
+        EndpointDiscoveryResolver::builder()
+            .discover(move |key| {
+                let count = count.fetch_add(1, Ordering::SeqCst) + 1;
+                async move {
+                    // let concurrent requests run while the endpoint is being discovered
+                    tokio::task::yield_now().await;
+                    let address = match key.operation() {
+                        Some(operation) => format!("{operation}-{count}.example.com"),
+                        None => format!("discovered-{count}.example.com"),
+                    };
+                    Ok(DiscoveredEndpoint::from_address(&address, 10))
+                }
+            })
+            .fallback_resolver(RegionalResolver)
+            .time_source(time_source)
+            .build()
+    }
+
+    fn params(request: Option<DiscoveryRequest>) -> EndpointResolverParams {
+        let mut params = EndpointResolverParams::new(());
+        if let Some(request) = request {
+            params.set_property(request);
+        }
+        params
+    }
+
+    async fn resolve(
+        resolver: &EndpointDiscoveryResolver,
+        params: &EndpointResolverParams,
+    ) -> String {
+        resolver
+            .resolve_endpoint(params)
+            .await
+            .expect("resolved")
+            .url()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn cache_endpoints_until_expired() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH + Duration::from_secs(1234567890));
+        let resolver = counting_resolver(SharedTimeSource::new(time_source.clone()));
+        let params = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), true)));
+
+        assert_eq!(
+            "https://discovered-1.example.com",
+            resolve(&resolver, &params).await
+        );
+        time_source.advance(Duration::from_secs(9 * 60));
+        assert_eq!(
+            "https://discovered-1.example.com",
+            resolve(&resolver, &params).await
+        );
+        time_source.advance(Duration::from_secs(60));
+        assert_eq!(
+            "https://discovered-2.example.com",
+            resolve(&resolver, &params).await
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_endpoints_per_key() {
+        let resolver = counting_resolver(SharedTimeSource::default());
+        let key = DiscoveryKey::for_operation("GetItem").with_identifier("TableName", "a");
+        let with_key = params(Some(DiscoveryRequest::new(key, false)));
+        let default_key = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), false)));
+
+        assert_eq!(
+            "https://GetItem-1.example.com",
+            resolve(&resolver, &with_key).await
+        );
+        assert_eq!(
+            "https://discovered-2.example.com",
+            resolve(&resolver, &default_key).await
+        );
+        assert_eq!(
+            "https://GetItem-1.example.com",
+            resolve(&resolver, &with_key).await
+        );
+    }
+
+    #[test]
+    fn interceptor_keys_requests_by_input() {
+        #[derive(Debug)]
+        struct GetItemInput {
+            table_name: Option<String>,
+        }
+
+        let interceptor = EndpointDiscoveryInterceptor::from_input(
+            |input: &GetItemInput| {
+                let mut key = DiscoveryKey::for_operation("GetItem");
+                if let Some(table_name) = &input.table_name {
+                    key = key.with_identifier("TableName", table_name.clone());
+                }
+                key
+            },
+            true,
+        );
+        let key = (interceptor.key)(&Input::erase(GetItemInput {
+            table_name: Some("a".into()),
+        }));
+        assert_eq!(Some("GetItem"), key.operation());
+        assert_eq!(
+            vec![("TableName", "a")],
+            key.identifiers().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            DiscoveryKey::for_operation("GetItem"),
+            (interceptor.key)(&Input::erase(GetItemInput { table_name: None }))
+        );
+        // Inputs of another type use the default key
+        assert_eq!(
+            DiscoveryKey::default(),
+            (interceptor.key)(&Input::erase(()))
+        );
+    }
+
+    #[tokio::test]
+    async fn fall_back_when_discovery_is_not_used_or_fails() {
+        let resolver = EndpointDiscoveryResolver::builder()
+            .discover(|_| async { Err::<DiscoveredEndpoint, BoxError>("unavailable".into()) })
+            .fallback_resolver(RegionalResolver)
+            .build();
+
+        assert_eq!(
+            "https://regional.example.com",
+            resolve(&resolver, &params(None)).await
+        );
+        let optional = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), false)));
+        assert_eq!(
+            "https://regional.example.com",
+            resolve(&resolver, &optional).await
+        );
+
+        let required = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), true)));
+        let err = resolver
+            .resolve_endpoint(&required)
+            .await
+            .expect_err("discovery is required");
+        assert_eq!("unavailable", err.to_string());
+    }
+
+    #[tokio::test]
+    async fn reload_task_refreshes_expiring_endpoints() {
+        let start = UNIX_EPOCH + Duration::from_secs(1234567890);
+        let (time, sleep, mut gate) = controlled_time_and_sleep(start);
+        let resolver = counting_resolver(SharedTimeSource::new(time));
+        let params = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), true)));
+        resolver
+            .discover(&DiscoveryKey::default())
+            .await
+            .expect("discovered");
+
+        let reload_task = tokio::spawn(
+            resolver
+                .reloader(SharedAsyncSleep::new(sleep))
+                .reload_task(),
+        );
+        // the endpoint expires after 10 minutes, and is refreshed less than 2 minutes before that
+        for _ in 0..9 {
+            let sleep = gate.expect_sleep().await;
+            assert_eq!(Duration::from_secs(60), sleep.duration());
+            assert_eq!(
+                "https://discovered-1.example.com",
+                resolve(&resolver, &params).await
+            );
+            sleep.allow_progress();
+        }
+        let sleep = gate.expect_sleep().await;
+        assert_eq!(
+            "https://discovered-2.example.com",
+            resolve(&resolver, &params).await
+        );
+
+        // the task stops once the resolver is dropped
+        drop(resolver);
+        sleep.allow_progress();
+        tokio::time::timeout(Duration::from_secs(1), reload_task)
+            .await
+            .expect("task finishes")
+            .expect("task doesn't panic");
+    }
+
+    #[tokio::test]
+    async fn deduplicate_concurrent_discoveries() {
+        let resolver = counting_resolver(SharedTimeSource::default());
+        let params = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), true)));
+
+        let (a, b, c) = tokio::join!(
+            resolve(&resolver, &params),
+            resolve(&resolver, &params),
+            resolve(&resolver, &params)
+        );
+        assert_eq!(vec!["https://discovered-1.example.com"; 3], vec![a, b, c]);
+    }
+
+    #[tokio::test]
+    async fn back_off_after_failed_discovery() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH + Duration::from_secs(1234567890));
+        let count = Arc::new(AtomicUsize::new(0));
+        let resolver = EndpointDiscoveryResolver::builder()
+            .discover({
+                let count = count.clone();
+                move |_| {
+                    let count = count.fetch_add(1, Ordering::SeqCst) + 1;
+                    async move {
+                        match count {
+                            1 => Err::<DiscoveredEndpoint, BoxError>("unavailable".into()),
+                            _ => Ok(DiscoveredEndpoint::from_address(
+                                "discovered.example.com",
+                                10,
+                            )),
+                        }
+                    }
+                }
+            })
+            .fallback_resolver(RegionalResolver)
+            .time_source(SharedTimeSource::new(time_source.clone()))
+            .build();
+        let optional = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), false)));
+        let required = params(Some(DiscoveryRequest::new(DiscoveryKey::default(), true)));
+
+        assert_eq!(
+            "https://regional.example.com",
+            resolve(&resolver, &optional).await
+        );
+        // discovery isn't attempted again until the backoff has passed
+        assert_eq!(
+            "https://regional.example.com",
+            resolve(&resolver, &optional).await
+        );
+        let err = resolver
+            .resolve_endpoint(&required)
+            .await
+            .expect_err("discovery failed recently");
+        assert_eq!("unavailable", err.to_string());
+        assert_eq!(1, count.load(Ordering::SeqCst));
+
+        time_source.advance(FAILURE_BACKOFF);
+        assert_eq!(
+            "https://discovered.example.com",
+            resolve(&resolver, &required).await
+        );
+        assert_eq!(2, count.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn evict_least_recently_used_keys() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH + Duration::from_secs(1234567890));
+        let resolver = counting_resolver(SharedTimeSource::new(time_source.clone()));
+        let key_params = |i: usize| {
+            let key =
+                DiscoveryKey::for_operation("GetItem").with_identifier("TableName", i.to_string());
+            params(Some(DiscoveryRequest::new(key, true)))
+        };
+
+        for i in 0..MAX_CACHED_KEYS {
+            resolve(&resolver, &key_params(i)).await;
+            time_source.advance(Duration::from_millis(1));
+        }
+        // using the first key makes the second the least recently used
+        assert_eq!(
+            "https://GetItem-1.example.com",
+            resolve(&resolver, &key_params(0)).await
+        );
+        time_source.advance(Duration::from_millis(1));
+        resolve(&resolver, &key_params(MAX_CACHED_KEYS)).await;
+        assert_eq!(MAX_CACHED_KEYS, resolver.inner.cache.lock().unwrap().len());
+
+        assert_eq!(
+            "https://GetItem-1.example.com",
+            resolve(&resolver, &key_params(0)).await
+        );
+        assert_eq!(
+            format!("https://GetItem-{}.example.com", MAX_CACHED_KEYS + 2),
+            resolve(&resolver, &key_params(1)).await
+        );
+    }
+
+    #[tokio::test]
+    async fn only_refresh_recently_used_keys() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH + Duration::from_secs(1234567890));
+        let count = Arc::new(AtomicUsize::new(0));
+        let resolver = EndpointDiscoveryResolver::builder()
+            .discover({
+                let count = count.clone();
+                move |_| {
+                    count.fetch_add(1, Ordering::SeqCst);
+                    async {
+                        Ok(DiscoveredEndpoint::from_address(
+                            "discovered.example.com",
+                            60,
+                        ))
+                    }
+                }
+            })
+            .time_source(SharedTimeSource::new(time_source.clone()))
+            .build();
+        let idle = DiscoveryKey::for_operation("Idle");
+        let used = DiscoveryKey::for_operation("Used");
+        resolver.discover(&idle).await.expect("discovered");
+        resolver.discover(&used).await.expect("discovered");
+        let reloader = resolver.reloader(SharedAsyncSleep::new(
+            aws_smithy_async::rt::sleep::TokioSleep::new(),
+        ));
+
+        time_source.advance(IDLE_TIMEOUT - Duration::from_secs(1));
+        resolve(&resolver, &params(Some(DiscoveryRequest::new(used, true)))).await;
+        time_source.advance(Duration::from_secs(1));
+        reloader.reload_once().await;
+        assert_eq!(3, count.load(Ordering::SeqCst));
+
+        // idle keys are evicted once their endpoints expire
+        time_source.advance(Duration::from_secs(60 * 60));
+        reloader.reload_once().await;
+        assert!(!resolver.inner.cache.lock().unwrap().contains_key(&idle));
+    }
+}
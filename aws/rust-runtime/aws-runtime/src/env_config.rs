@@ -12,6 +12,7 @@ use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
 
+pub mod edit;
 pub mod error;
 pub mod file;
 mod normalize;
@@ -30,11 +31,13 @@ pub fn get_service_env_config<'a, T, E>(
 where
     E: Error + Send + Sync + 'static,
 {
-    EnvConfigValue::default()
-        .env(key.env())
+    let mut value = EnvConfigValue::default()
         .profile(key.profile())
-        .service_id(key.service_id())
-        .validate(env, shared_config_sections, validator)
+        .service_id(key.service_id());
+    if let Some(env) = key.env() {
+        value = value.env(env);
+    }
+    value.validate(env, shared_config_sections, validator)
 }
 
 #[derive(Debug)]
@@ -0,0 +1,541 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Programmatic editing of config and credentials files
+//!
+//! [`EnvConfigFileEditor`] edits a single config or credentials file in place, the way
+//! `aws configure` does. Only the lines of the properties and sections being changed are
+//! rewritten: comments, blank lines, ordering, and any property the editor doesn't touch are
+//! preserved exactly as they were.
+
+use crate::env_config::file::EnvConfigFileKind;
+use crate::env_config::parse::{COMMENT, WHITESPACE};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+/// A section of a config file that can be edited
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EditableSection<'a> {
+    /// A profile, e.g. `[profile dev]` in the config file or `[dev]` in the credentials file
+    Profile(&'a str),
+    /// An SSO session, e.g. `[sso-session my-sso]`. These are only valid in the config file.
+    SsoSession(&'a str),
+}
+
+impl EditableSection<'_> {
+    fn name(&self) -> &str {
+        match self {
+            Self::Profile(name) | Self::SsoSession(name) => name,
+        }
+    }
+
+    fn header(&self, kind: EnvConfigFileKind) -> String {
+        match (kind, self) {
+            (EnvConfigFileKind::Config, Self::Profile("default")) => "[default]".to_string(),
+            (EnvConfigFileKind::Config, Self::Profile(name)) => format!("[profile {name}]"),
+            (_, Self::SsoSession(name)) => format!("[sso-session {name}]"),
+            (EnvConfigFileKind::Credentials, Self::Profile(name)) => format!("[{name}]"),
+        }
+    }
+
+    fn matches_header(&self, kind: EnvConfigFileKind, header: &str) -> bool {
+        let (prefix, name) = match header.split_once(WHITESPACE) {
+            Some((prefix, name)) => (Some(prefix), name.trim_matches(WHITESPACE)),
+            None => (None, header),
+        };
+        match (kind, self, prefix) {
+            (EnvConfigFileKind::Config, Self::Profile(profile), Some("profile")) => {
+                name == *profile
+            }
+            (EnvConfigFileKind::Config, Self::Profile("default"), None) => name == "default",
+            (EnvConfigFileKind::Config, Self::SsoSession(session), Some("sso-session")) => {
+                name == *session
+            }
+            (EnvConfigFileKind::Credentials, Self::Profile(profile), None) => name == *profile,
+            _ => false,
+        }
+    }
+}
+
+/// An invalid edit was made with an [`EnvConfigFileEditor`]
+#[derive(Debug)]
+pub struct EnvConfigEditError {
+    message: String,
+}
+
+impl EnvConfigEditError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for EnvConfigEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config file edit: {}", self.message)
+    }
+}
+
+impl Error for EnvConfigEditError {}
+
+/// Editor for a single config or credentials file
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_runtime::env_config::edit::{EditableSection, EnvConfigFileEditor};
+/// use aws_runtime::env_config::file::EnvConfigFileKind;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = "/home/me/.aws/config";
+/// let mut editor = EnvConfigFileEditor::load(EnvConfigFileKind::Config, path)?;
+/// editor.set(EditableSection::SsoSession("my-sso"), "sso_start_url", "https://example.awsapps.com/start")?;
+/// editor.set(EditableSection::SsoSession("my-sso"), "sso_region", "us-east-1")?;
+/// editor.set(EditableSection::Profile("dev"), "sso_session", "my-sso")?;
+/// editor.set(EditableSection::Profile("dev"), "region", "us-west-2")?;
+/// editor.write(path)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct EnvConfigFileEditor {
+    kind: EnvConfigFileKind,
+    lines: Vec<String>,
+}
+
+impl EnvConfigFileEditor {
+    /// Creates an editor for an empty file of the given kind
+    pub fn new(kind: EnvConfigFileKind) -> Self {
+        Self {
+            kind,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Creates an editor for a file of the given kind with the given contents
+    pub fn parse(kind: EnvConfigFileKind, contents: &str) -> Self {
+        Self {
+            kind,
+            lines: contents.lines().map(str::to_string).collect(),
+        }
+    }
+
+    /// Creates an editor for the file at `path`
+    ///
+    /// If the file doesn't exist, the editor starts out empty and the file is created when the
+    /// editor is [written](Self::write).
+    pub fn load(kind: EnvConfigFileKind, path: impl AsRef<Path>) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(Self::parse(kind, &contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::new(kind)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the value of `key` in `section`, if it is set
+    pub fn get(&self, section: EditableSection<'_>, key: &str) -> Option<&str> {
+        let span = self.section_spans(section).pop()?;
+        let line = self.find_property(span, key)?;
+        self.lines[line]
+            .split_once('=')
+            .map(|(_, value)| value.trim_matches(WHITESPACE))
+    }
+
+    /// Sets `key` to `value` in `section`
+    ///
+    /// The section is appended to the end of the file if it doesn't exist yet. If the key already
+    /// exists, its line (along with any sub-properties) is replaced. Otherwise, the key is added
+    /// after the last property of the section.
+    pub fn set(
+        &mut self,
+        section: EditableSection<'_>,
+        key: &str,
+        value: &str,
+    ) -> Result<&mut Self, EnvConfigEditError> {
+        self.validate_section(section)?;
+        validate_key(key)?;
+        if value.contains(['\n', '\r']) {
+            return Err(EnvConfigEditError::new(format!(
+                "the value for `{key}` must not contain line breaks"
+            )));
+        }
+        let property = if value.is_empty() {
+            format!("{key} =")
+        } else {
+            format!("{key} = {value}")
+        };
+        match self.section_spans(section).pop() {
+            Some(span) => match self.find_property(span.clone(), key) {
+                Some(line) => {
+                    let end = self.property_end(line);
+                    self.lines.drain(line + 1..end);
+                    self.lines[line] = property;
+                }
+                None => {
+                    let insert_at = self.lines[span.clone()]
+                        .iter()
+                        .rposition(|line| !is_empty_line(line) && !is_comment_line(line))
+                        .map(|offset| span.start + offset + 1)
+                        .unwrap_or(span.start + 1);
+                    self.lines.insert(insert_at, property);
+                }
+            },
+            None => {
+                if self.lines.last().is_some_and(|line| !is_empty_line(line)) {
+                    self.lines.push(String::new());
+                }
+                self.lines.push(section.header(self.kind));
+                self.lines.push(property);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Removes `key` (along with any sub-properties) from `section`
+    ///
+    /// Returns `true` if the key was set.
+    pub fn remove(&mut self, section: EditableSection<'_>, key: &str) -> bool {
+        let mut removed = false;
+        for span in self.section_spans(section).into_iter().rev() {
+            if let Some(line) = self.find_property(span, key) {
+                let end = self.property_end(line);
+                self.lines.drain(line..end);
+                removed = true;
+            }
+        }
+        removed
+    }
+
+    /// Removes `section` and all of its properties
+    ///
+    /// Returns `true` if the section existed.
+    pub fn remove_section(&mut self, section: EditableSection<'_>) -> bool {
+        let spans = self.section_spans(section);
+        for span in spans.iter().rev() {
+            self.lines.drain(span.clone());
+        }
+        !spans.is_empty()
+    }
+
+    /// Returns the contents of the edited file
+    pub fn contents(&self) -> String {
+        let mut contents = self.lines.join("\n");
+        if !contents.is_empty() {
+            contents.push('\n');
+        }
+        contents
+    }
+
+    /// Atomically writes the edited file to `path`
+    ///
+    /// The contents are written to a temporary file next to `path`, which then replaces `path`.
+    /// If `path` already exists, its permissions are preserved. Otherwise, on Unix, the file is
+    /// created readable and writable only by its owner since it may contain secrets.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        use std::io::Write;
+
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path must name a file"))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        if !directory.as_os_str().is_empty() {
+            std::fs::create_dir_all(directory)?;
+        }
+        let mut temp_name = std::ffi::OsString::from(".");
+        temp_name.push(file_name);
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = directory.join(temp_name);
+
+        let result = (|| {
+            let mut file = std::fs::File::create(&temp_path)?;
+            match std::fs::metadata(path) {
+                Ok(metadata) => file.set_permissions(metadata.permissions())?,
+                #[cfg(unix)]
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    use std::os::unix::fs::PermissionsExt;
+                    file.set_permissions(std::fs::Permissions::from_mode(0o600))?
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+            file.write_all(self.contents().as_bytes())?;
+            file.sync_all()?;
+            std::fs::rename(&temp_path, path)
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    fn validate_section(&self, section: EditableSection<'_>) -> Result<(), EnvConfigEditError> {
+        if matches!(
+            (self.kind, section),
+            (
+                EnvConfigFileKind::Credentials,
+                EditableSection::SsoSession(_)
+            )
+        ) {
+            return Err(EnvConfigEditError::new(
+                "sso-session sections can only be written to the config file",
+            ));
+        }
+        let name = section.name();
+        if name.is_empty() || name.contains(WHITESPACE) || name.contains(['[', ']', '\n', '\r']) {
+            return Err(EnvConfigEditError::new(format!(
+                "`{name}` is not a valid section name"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the line ranges of every section matching `section`, in file order
+    fn section_spans(&self, section: EditableSection<'_>) -> Vec<Range<usize>> {
+        let mut spans = Vec::new();
+        let mut current: Option<usize> = None;
+        for (index, line) in self.lines.iter().enumerate() {
+            if let Some(header) = parse_header(line) {
+                if let Some(start) = current.take() {
+                    spans.push(start..index);
+                }
+                if section.matches_header(self.kind, header) {
+                    current = Some(index);
+                }
+            }
+        }
+        if let Some(start) = current {
+            spans.push(start..self.lines.len());
+        }
+        spans
+    }
+
+    /// Returns the index of the last line in `span` that sets `key`
+    fn find_property(&self, span: Range<usize>, key: &str) -> Option<usize> {
+        span.skip(1).rev().find(|&index| {
+            let line = &self.lines[index];
+            !line.starts_with(WHITESPACE)
+                && !is_comment_line(line)
+                && line.split_once('=').is_some_and(|(name, _)| {
+                    name.trim_matches(WHITESPACE).eq_ignore_ascii_case(key)
+                })
+        })
+    }
+
+    /// Returns the index one past the last continuation line of the property at `line`
+    fn property_end(&self, line: usize) -> usize {
+        let continuation_lines = self.lines[line + 1..]
+            .iter()
+            .take_while(|line| line.starts_with(WHITESPACE) && !is_empty_line(line))
+            .count();
+        line + 1 + continuation_lines
+    }
+}
+
+impl fmt::Display for EnvConfigFileEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.contents())
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), EnvConfigEditError> {
+    if key.is_empty()
+        || key.contains(WHITESPACE)
+        || key.contains(['=', '\n', '\r'])
+        || key.starts_with(['['])
+        || key.starts_with(COMMENT)
+    {
+        return Err(EnvConfigEditError::new(format!(
+            "`{key}` is not a valid property name"
+        )));
+    }
+    Ok(())
+}
+
+fn parse_header(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix('[')?;
+    let end = rest.find(']')?;
+    Some(rest[..end].trim_matches(WHITESPACE))
+}
+
+fn is_empty_line(line: &str) -> bool {
+    line.trim_matches(WHITESPACE).is_empty()
+}
+
+fn is_comment_line(line: &str) -> bool {
+    line.starts_with(COMMENT)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EditableSection, EnvConfigFileEditor};
+    use crate::env_config::file::EnvConfigFileKind;
+
+    const CONFIG: &str = "\
+# my config
+[default]
+region = us-east-1 # inline
+; keep me
+
+[profile dev]
+region = us-west-2
+s3 =
+  addressing_style = path
+
+[services dev]
+s3 =
+  endpoint_url = http://localhost:4566
+";
+
+    #[test]
+    fn updates_existing_properties_in_place() {
+        let mut editor = EnvConfigFileEditor::parse(EnvConfigFileKind::Config, CONFIG);
+        editor
+            .set(EditableSection::Profile("default"), "region", "eu-west-1")
+            .unwrap()
+            .set(EditableSection::Profile("dev"), "s3", "")
+            .unwrap();
+        assert_eq!(
+            Some("eu-west-1"),
+            editor.get(EditableSection::Profile("default"), "REGION")
+        );
+        assert_eq!(
+            "\
+# my config
+[default]
+region = eu-west-1
+; keep me
+
+[profile dev]
+region = us-west-2
+s3 =
+
+[services dev]
+s3 =
+  endpoint_url = http://localhost:4566
+",
+            editor.contents()
+        );
+    }
+
+    #[test]
+    fn adds_properties_and_sections() {
+        let mut editor = EnvConfigFileEditor::parse(EnvConfigFileKind::Config, CONFIG);
+        editor
+            .set(EditableSection::Profile("default"), "output", "json")
+            .unwrap()
+            .set(
+                EditableSection::SsoSession("my-sso"),
+                "sso_region",
+                "us-east-1",
+            )
+            .unwrap()
+            .set(EditableSection::Profile("sso"), "sso_session", "my-sso")
+            .unwrap();
+        assert_eq!(
+            "\
+# my config
+[default]
+region = us-east-1 # inline
+output = json
+; keep me
+
+[profile dev]
+region = us-west-2
+s3 =
+  addressing_style = path
+
+[services dev]
+s3 =
+  endpoint_url = http://localhost:4566
+
+[sso-session my-sso]
+sso_region = us-east-1
+
+[profile sso]
+sso_session = my-sso
+",
+            editor.contents()
+        );
+    }
+
+    #[test]
+    fn removes_properties_and_sections() {
+        let mut editor = EnvConfigFileEditor::parse(EnvConfigFileKind::Config, CONFIG);
+        assert!(editor.remove(EditableSection::Profile("dev"), "s3"));
+        assert!(!editor.remove(EditableSection::Profile("dev"), "s3"));
+        assert!(editor.remove_section(EditableSection::Profile("default")));
+        assert!(!editor.remove_section(EditableSection::Profile("default")));
+        assert_eq!(
+            "\
+# my config
+[profile dev]
+region = us-west-2
+
+[services dev]
+s3 =
+  endpoint_url = http://localhost:4566
+",
+            editor.contents()
+        );
+    }
+
+    #[test]
+    fn credentials_file_sections() {
+        let mut editor = EnvConfigFileEditor::new(EnvConfigFileKind::Credentials);
+        editor
+            .set(EditableSection::Profile("dev"), "aws_access_key_id", "AKID")
+            .unwrap();
+        assert!(editor
+            .set(
+                EditableSection::SsoSession("my-sso"),
+                "sso_region",
+                "us-east-1"
+            )
+            .is_err());
+        assert_eq!("[dev]\naws_access_key_id = AKID\n", editor.contents());
+    }
+
+    #[test]
+    fn rejects_invalid_edits() {
+        let mut editor = EnvConfigFileEditor::new(EnvConfigFileKind::Config);
+        let profile = EditableSection::Profile("dev");
+        assert!(editor.set(profile, "", "value").is_err());
+        assert!(editor.set(profile, "a key", "value").is_err());
+        assert!(editor.set(profile, "key", "multi\nline").is_err());
+        assert!(editor
+            .set(EditableSection::Profile("bad name"), "key", "value")
+            .is_err());
+        assert_eq!("", editor.contents());
+    }
+
+    #[test]
+    fn writes_atomically() {
+        let directory =
+            std::env::temp_dir().join(format!("env-config-editor-test-{}", std::process::id()));
+        let path = directory.join("config");
+        let mut editor = EnvConfigFileEditor::load(EnvConfigFileKind::Config, &path).unwrap();
+        editor
+            .set(EditableSection::Profile("default"), "region", "us-east-1")
+            .unwrap();
+        editor.write(&path).unwrap();
+        assert_eq!(
+            "[default]\nregion = us-east-1\n",
+            std::fs::read_to_string(&path).unwrap()
+        );
+        let editor = EnvConfigFileEditor::load(EnvConfigFileKind::Config, &path).unwrap();
+        assert_eq!(
+            Some("us-east-1"),
+            editor.get(EditableSection::Profile("default"), "region")
+        );
+        assert_eq!(1, std::fs::read_dir(&directory).unwrap().count());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
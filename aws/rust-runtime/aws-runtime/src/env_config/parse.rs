@@ -26,7 +26,7 @@ pub(super) type RawProfileSet<'a> = HashMap<&'a str, HashMap<Cow<'a, str>, Cow<'
 /// Profile parsing is actually quite strict about what is and is not whitespace, so use this instead
 /// of `.is_whitespace()` / `.trim()`
 pub(crate) const WHITESPACE: &[char] = &[' ', '\t'];
-const COMMENT: &[char] = &['#', ';'];
+pub(crate) const COMMENT: &[char] = &['#', ';'];
 
 /// Location for use during error reporting
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -51,3 +51,6 @@ pub mod fs_util;
 /// Supporting code for parsing AWS config values set in a user's environment or
 /// in a shared config file.
 pub mod env_config;
+
+/// Endpoint discovery for services that require or support it.
+pub mod endpoint_discovery;
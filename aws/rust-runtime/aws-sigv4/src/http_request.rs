@@ -54,9 +54,46 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Example: Presigning an arbitrary HTTP request
+//!
+//! ```rust
+//! # use aws_credential_types::Credentials;
+//! #[cfg(feature = "http1")]
+//! fn test() -> Result<(), aws_sigv4::http_request::SigningError> {
+//! use aws_sigv4::http_request::{presign_http1x, SignableBody, SigningSettings};
+//! use aws_sigv4::sign::v4;
+//! use std::time::{Duration, SystemTime};
+//!
+//! let identity = Credentials::new(
+//!     "AKIDEXAMPLE",
+//!     "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+//!     None,
+//!     None,
+//!     "hardcoded-credentials"
+//! ).into();
+//! let signing_params = v4::SigningParams::builder()
+//!     .identity(&identity)
+//!     .region("us-east-1")
+//!     .name("execute-api")
+//!     .time(SystemTime::now())
+//!     .settings(SigningSettings::default())
+//!     .build()
+//!     .unwrap()
+//!     .into();
+//!
+//! let mut my_req = http::Request::get("https://abc123.execute-api.us-east-1.amazonaws.com/prod/items")
+//!     .body(())
+//!     .unwrap();
+//! presign_http1x(&mut my_req, SignableBody::empty(), signing_params, Duration::from_secs(900))?;
+//! let presigned_url = my_req.uri().to_string();
+//! # Ok(())
+//! # }
+//! ```
 
 mod canonical_request;
 mod error;
+mod presign;
 mod settings;
 mod sign;
 mod uri_path_normalization;
@@ -71,11 +108,14 @@ use crate::sign::v4a;
 use crate::SignatureVersion;
 use aws_credential_types::Credentials;
 pub use error::SigningError;
+pub use presign::{presign, MAX_PRESIGNED_REQUEST_EXPIRATION};
+#[cfg(feature = "http1")]
+pub use presign::{presign_http1x, sign_http1x};
 pub use settings::{
     PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignatureLocation, SigningSettings,
     UriPathNormalizationMode,
 };
-pub use sign::{sign, SignableBody, SignableRequest, SigningInstructions};
+pub use sign::{sign, SignableBody, SignableRequest, SigningDebugInfo, SigningInstructions};
 use std::time::SystemTime;
 
 // Individual Debug impls are responsible for redacting sensitive fields.
@@ -7,11 +7,14 @@ use http0::header::{InvalidHeaderName, InvalidHeaderValue};
 use http0::uri::InvalidUri;
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 
 #[derive(Debug)]
 enum SigningErrorKind {
     FailedToCreateCanonicalRequest { source: CanonicalRequestError },
     UnsupportedIdentityType,
+    InvalidExpiration { expires_in: Duration },
+    InvalidHeaderValue { name: String },
 }
 
 /// Error signing request
@@ -26,17 +29,42 @@ impl SigningError {
             kind: SigningErrorKind::UnsupportedIdentityType,
         }
     }
+
+    pub(crate) fn invalid_expiration(expires_in: Duration) -> Self {
+        Self {
+            kind: SigningErrorKind::InvalidExpiration { expires_in },
+        }
+    }
+
+    #[allow(dead_code)] // Unused when compiling without certain features
+    pub(crate) fn invalid_header_value(name: impl Into<String>) -> Self {
+        Self {
+            kind: SigningErrorKind::InvalidHeaderValue { name: name.into() },
+        }
+    }
 }
 
 impl fmt::Display for SigningError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.kind {
+        match &self.kind {
             SigningErrorKind::FailedToCreateCanonicalRequest { .. } => {
                 write!(f, "failed to create canonical request")
             }
             SigningErrorKind::UnsupportedIdentityType => {
                 write!(f, "only 'AWS credentials' are supported for signing")
             }
+            SigningErrorKind::InvalidExpiration { expires_in } => {
+                write!(
+                    f,
+                    "presigned request expiration must be greater than zero and at most one week, but was {expires_in:?}"
+                )
+            }
+            SigningErrorKind::InvalidHeaderValue { name } => {
+                write!(
+                    f,
+                    "the value of header `{name}` is not valid UTF-8 and cannot be signed"
+                )
+            }
         }
     }
 }
@@ -45,7 +73,9 @@ impl Error for SigningError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match &self.kind {
             SigningErrorKind::FailedToCreateCanonicalRequest { source } => Some(source),
-            SigningErrorKind::UnsupportedIdentityType => None,
+            SigningErrorKind::UnsupportedIdentityType
+            | SigningErrorKind::InvalidExpiration { .. }
+            | SigningErrorKind::InvalidHeaderValue { .. } => None,
         }
     }
 }
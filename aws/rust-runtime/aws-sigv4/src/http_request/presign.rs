@@ -0,0 +1,249 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use super::error::SigningError;
+use super::sign::{sign, SignableBody, SignableRequest, SigningInstructions};
+use super::{SignatureLocation, SigningParams};
+use crate::SigningOutput;
+use std::time::Duration;
+
+/// The longest amount of time that a SigV4 presigned request may be valid for (one week).
+pub const MAX_PRESIGNED_REQUEST_EXPIRATION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Presigns the given `request`, producing instructions that add the signature to the request's
+/// query string.
+///
+/// This is useful for producing presigned URLs for services (or custom APIs fronted by IAM auth)
+/// that don't have generated presigning support. The `signature_location` and `expires_in` of the
+/// given `params` are overridden. `expires_in` must be greater than zero and no longer than
+/// [`MAX_PRESIGNED_REQUEST_EXPIRATION`].
+pub fn presign<'a>(
+    request: SignableRequest<'a>,
+    mut params: SigningParams<'a>,
+    expires_in: Duration,
+) -> Result<SigningOutput<SigningInstructions>, SigningError> {
+    if expires_in.is_zero() || expires_in > MAX_PRESIGNED_REQUEST_EXPIRATION {
+        return Err(SigningError::invalid_expiration(expires_in));
+    }
+    let settings = params.settings_mut();
+    settings.signature_location = SignatureLocation::QueryParams;
+    settings.expires_in = Some(expires_in);
+    sign(request, &params)
+}
+
+/// Presigns the given HTTP request in place by adding the signing query params to its URI.
+///
+/// The `body` is the payload that will be signed. Presigned requests typically don't know their
+/// payload ahead of time, in which case [`SignableBody::UnsignedPayload`] should be used for
+/// services that support it (such as S3), and [`SignableBody::empty`] otherwise.
+///
+/// Returns the calculated signature.
+#[cfg(feature = "http1")]
+pub fn presign_http1x<B>(
+    request: &mut http::Request<B>,
+    body: SignableBody<'_>,
+    params: SigningParams<'_>,
+    expires_in: Duration,
+) -> Result<String, SigningError> {
+    let (instructions, signature) = {
+        let signable_request = signable_request_http1x(request, body)?;
+        presign(signable_request, params, expires_in)?.into_parts()
+    };
+    instructions.apply_to_request_http1x(request);
+    Ok(signature)
+}
+
+/// Signs the given HTTP request in place by adding the signing headers to it.
+///
+/// Returns the calculated signature.
+#[cfg(feature = "http1")]
+pub fn sign_http1x<B>(
+    request: &mut http::Request<B>,
+    body: SignableBody<'_>,
+    mut params: SigningParams<'_>,
+) -> Result<String, SigningError> {
+    params.settings_mut().signature_location = SignatureLocation::Headers;
+    let (instructions, signature) = {
+        let signable_request = signable_request_http1x(request, body)?;
+        sign(signable_request, &params)?.into_parts()
+    };
+    instructions.apply_to_request_http1x(request);
+    Ok(signature)
+}
+
+#[cfg(feature = "http1")]
+fn signable_request_http1x<'a, B>(
+    request: &'a http::Request<B>,
+    body: SignableBody<'a>,
+) -> Result<SignableRequest<'a>, SigningError> {
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            value
+                .to_str()
+                .map(|value| (name.as_str(), value))
+                .map_err(|_| SigningError::invalid_header_value(name.as_str()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    SignableRequest::new(
+        request.method().as_str(),
+        request.uri().to_string(),
+        headers.into_iter(),
+        body,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date_time::test_parsers::parse_date_time;
+    use crate::http_request::{sign, SigningSettings};
+    use crate::sign::v4;
+    use aws_credential_types::Credentials;
+    use aws_smithy_runtime_api::client::identity::Identity;
+
+    fn params(identity: &Identity) -> SigningParams<'_> {
+        v4::SigningParams::builder()
+            .identity(identity)
+            .region("us-east-1")
+            .name("execute-api")
+            .time(parse_date_time("20150830T123600Z").unwrap())
+            .settings(SigningSettings::default())
+            .build()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn presign_matches_query_param_signing() {
+        let identity = Credentials::for_tests_with_session_token().into();
+        let request = || {
+            SignableRequest::new(
+                "GET",
+                "https://abc123.execute-api.us-east-1.amazonaws.com/prod/items?id=5",
+                std::iter::empty(),
+                SignableBody::empty(),
+            )
+            .unwrap()
+        };
+
+        let mut expected_params = params(&identity);
+        expected_params.settings_mut().signature_location = SignatureLocation::QueryParams;
+        expected_params.settings_mut().expires_in = Some(Duration::from_secs(300));
+        let expected = sign(request(), &expected_params).unwrap();
+
+        let presigned = presign(request(), params(&identity), Duration::from_secs(300)).unwrap();
+        assert_eq!(expected.signature(), presigned.signature());
+        assert!(presigned.output().headers().next().is_none());
+        let names: Vec<_> = presigned
+            .output()
+            .params()
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(
+            vec![
+                "X-Amz-Algorithm",
+                "X-Amz-Credential",
+                "X-Amz-Date",
+                "X-Amz-Expires",
+                "X-Amz-SignedHeaders",
+                "X-Amz-Signature",
+                "X-Amz-Security-Token",
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn presign_rejects_invalid_expiration() {
+        let identity = Credentials::for_tests().into();
+        let request = || {
+            SignableRequest::new(
+                "GET",
+                "https://example.amazonaws.com/",
+                std::iter::empty(),
+                SignableBody::empty(),
+            )
+            .unwrap()
+        };
+        assert!(presign(request(), params(&identity), Duration::ZERO).is_err());
+        assert!(presign(
+            request(),
+            params(&identity),
+            MAX_PRESIGNED_REQUEST_EXPIRATION + Duration::from_secs(1)
+        )
+        .is_err());
+        assert!(presign(
+            request(),
+            params(&identity),
+            MAX_PRESIGNED_REQUEST_EXPIRATION
+        )
+        .is_ok());
+    }
+
+    #[cfg(feature = "http1")]
+    #[test]
+    fn presign_http1x_request() {
+        let identity = Credentials::for_tests().into();
+        let mut request = http::Request::builder()
+            .uri("https://abc123.execute-api.us-east-1.amazonaws.com/prod/items?id=5")
+            .header("x-custom", "value")
+            .body(())
+            .unwrap();
+        let signature = presign_http1x(
+            &mut request,
+            SignableBody::empty(),
+            params(&identity),
+            Duration::from_secs(900),
+        )
+        .unwrap();
+
+        let query = request.uri().query().unwrap();
+        assert!(query.starts_with("id=5&"), "{query}");
+        assert!(query.contains("X-Amz-Expires=900"), "{query}");
+        assert!(
+            query.contains("X-Amz-SignedHeaders=host%3Bx-custom"),
+            "{query}"
+        );
+        assert!(
+            query.contains(&format!("X-Amz-Signature={signature}")),
+            "{query}"
+        );
+        assert!(request.headers().get("authorization").is_none());
+    }
+
+    #[cfg(feature = "http1")]
+    #[test]
+    fn sign_http1x_request() {
+        let identity = Credentials::for_tests().into();
+        let mut request = http::Request::builder()
+            .uri("https://abc123.execute-api.us-east-1.amazonaws.com/prod/items")
+            .body(())
+            .unwrap();
+        let signature =
+            sign_http1x(&mut request, SignableBody::empty(), params(&identity)).unwrap();
+
+        let authorization = request.headers()["authorization"].to_str().unwrap();
+        assert!(authorization.ends_with(&format!("Signature={signature}")));
+        assert!(request.headers().contains_key("x-amz-date"));
+        assert!(request.uri().query().is_none());
+    }
+
+    #[cfg(feature = "http1")]
+    #[test]
+    fn non_utf8_header_values_are_rejected() {
+        let identity = Credentials::for_tests().into();
+        let mut request = http::Request::builder()
+            .uri("https://example.amazonaws.com/")
+            .header("x-custom", http::HeaderValue::from_bytes(&[0xff]).unwrap())
+            .body(())
+            .unwrap();
+        let err = sign_http1x(&mut request, SignableBody::empty(), params(&identity))
+            .expect_err("header value is not UTF-8");
+        assert!(err.to_string().contains("x-custom"), "{err}");
+    }
+}
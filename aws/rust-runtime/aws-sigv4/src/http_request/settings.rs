@@ -41,6 +41,12 @@ pub struct SigningSettings {
     /// Some services require an alternative session token header or query param instead of
     /// `x-amz-security-token` or `X-Amz-Security-Token`.
     pub session_token_name_override: Option<&'static str>,
+
+    /// Whether to capture the canonical request, string-to-sign, and signed headers in the
+    /// returned [`SigningInstructions`](super::SigningInstructions) for debugging.
+    ///
+    /// This is disabled by default since it requires additional allocations for every signature.
+    pub capture_debug_info: bool,
 }
 
 /// HTTP payload checksum type
@@ -140,6 +146,7 @@ impl Default for SigningSettings {
             uri_path_normalization_mode: UriPathNormalizationMode::Enabled,
             session_token_mode: SessionTokenMode::Include,
             session_token_name_override: None,
+            capture_debug_info: false,
         }
     }
 }
@@ -133,6 +133,59 @@ impl SignableBody<'_> {
 pub struct SigningInstructions {
     headers: Vec<Header>,
     params: Vec<(&'static str, Cow<'static, str>)>,
+    debug_info: Option<SigningDebugInfo>,
+}
+
+/// Intermediate values calculated while signing a request
+///
+/// These are only captured when [`SigningSettings::capture_debug_info`](super::SigningSettings::capture_debug_info)
+/// is enabled. Comparing them against the canonical request and string-to-sign reported by a
+/// service in a `SignatureDoesNotMatch` error is usually the quickest way to find out why a
+/// signature was rejected.
+///
+/// The session token is replaced with `** REDACTED **` in the captured canonical request, both
+/// as it appears in the `x-amz-security-token` header and percent-encoded in the query string.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SigningDebugInfo {
+    canonical_request: String,
+    string_to_sign: String,
+    signed_headers: String,
+}
+
+impl SigningDebugInfo {
+    fn new(
+        creq: &CanonicalRequest<'_>,
+        string_to_sign: String,
+        session_token: Option<&str>,
+    ) -> Self {
+        let mut canonical_request = creq.to_string();
+        if let Some(session_token) = session_token.filter(|token| !token.is_empty()) {
+            let encoded_session_token = aws_smithy_http::query::fmt_string(session_token);
+            canonical_request = canonical_request
+                .replace(session_token, "** REDACTED **")
+                .replace(encoded_session_token.as_str(), "** REDACTED **");
+        }
+        Self {
+            canonical_request,
+            string_to_sign,
+            signed_headers: creq.values.signed_headers().as_str().to_string(),
+        }
+    }
+
+    /// The canonical request that was signed
+    pub fn canonical_request(&self) -> &str {
+        &self.canonical_request
+    }
+
+    /// The string-to-sign derived from the canonical request
+    pub fn string_to_sign(&self) -> &str {
+        &self.string_to_sign
+    }
+
+    /// The semicolon-separated list of headers that were included in the signature
+    pub fn signed_headers(&self) -> &str {
+        &self.signed_headers
+    }
 }
 
 /// Header representation for use in [`SigningInstructions`]
@@ -175,7 +228,23 @@ impl Header {
 
 impl SigningInstructions {
     fn new(headers: Vec<Header>, params: Vec<(&'static str, Cow<'static, str>)>) -> Self {
-        Self { headers, params }
+        Self {
+            headers,
+            params,
+            debug_info: None,
+        }
+    }
+
+    fn with_debug_info(mut self, debug_info: Option<SigningDebugInfo>) -> Self {
+        self.debug_info = debug_info;
+        self
+    }
+
+    /// Returns the intermediate signing values, if they were captured
+    ///
+    /// See [`SigningDebugInfo`] for more details.
+    pub fn debug_info(&self) -> Option<&SigningDebugInfo> {
+        self.debug_info.as_ref()
     }
 
     /// Returns the headers and query params that should be applied to this request
@@ -252,17 +321,17 @@ pub fn sign<'a>(
     tracing::trace!(request = ?request, params = ?params, "signing request");
     match params.settings().signature_location {
         SignatureLocation::Headers => {
-            let (signing_headers, signature) =
-                calculate_signing_headers(&request, params)?.into_parts();
+            let (signing_headers, signature, debug_info) =
+                calculate_signing_headers(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(signing_headers, vec![]),
+                SigningInstructions::new(signing_headers, vec![]).with_debug_info(debug_info),
                 signature,
             ))
         }
         SignatureLocation::QueryParams => {
-            let (params, signature) = calculate_signing_params(&request, params)?;
+            let (params, signature, debug_info) = calculate_signing_params(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(vec![], params),
+                SigningInstructions::new(vec![], params).with_debug_info(debug_info),
                 signature,
             ))
         }
@@ -274,7 +343,7 @@ type CalculatedParams = Vec<(&'static str, Cow<'static, str>)>;
 fn calculate_signing_params<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<(CalculatedParams, String), SigningError> {
+) -> Result<(CalculatedParams, String, Option<SigningDebugInfo>), SigningError> {
     let creds = params.credentials()?;
     let creq = CanonicalRequest::from(request, params)?;
     let encoded_creq = &v4::sha256_hex_string(creq.to_string().as_bytes());
@@ -306,6 +375,10 @@ fn calculate_signing_params<'a>(
         }
     };
     tracing::trace!(canonical_request = %creq, string_to_sign = %string_to_sign, "calculated signing parameters");
+    let debug_info = params
+        .settings()
+        .capture_debug_info
+        .then(|| SigningDebugInfo::new(&creq, string_to_sign, creds.session_token()));
 
     let values = creq.values.into_query_params().expect("signing with query");
     let mut signing_params = vec![
@@ -340,7 +413,7 @@ fn calculate_signing_params<'a>(
         ));
     }
 
-    Ok((signing_params, signature))
+    Ok((signing_params, signature, debug_info))
 }
 
 /// Calculates the signature headers that need to get added to the given `request`.
@@ -352,7 +425,7 @@ fn calculate_signing_params<'a>(
 fn calculate_signing_headers<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<SigningOutput<Vec<Header>>, SigningError> {
+) -> Result<(Vec<Header>, String, Option<SigningDebugInfo>), SigningError> {
     let creds = params.credentials()?;
 
     // Step 1: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-create-canonical-request.html.
@@ -361,6 +434,7 @@ fn calculate_signing_headers<'a>(
     let encoded_creq = v4::sha256_hex_string(creq.to_string().as_bytes());
     tracing::trace!(canonical_request = %creq);
     let mut headers = vec![];
+    let mut debug_info = None;
 
     let signature = match params {
         SigningParams::V4(params) => {
@@ -378,7 +452,15 @@ fn calculate_signing_headers<'a>(
                 params.region,
                 params.name,
             );
-            let signature = v4::calculate_signature(signing_key, sts.to_string().as_bytes());
+            let string_to_sign = sts.to_string();
+            let signature = v4::calculate_signature(signing_key, string_to_sign.as_bytes());
+            if params.settings.capture_debug_info {
+                debug_info = Some(SigningDebugInfo::new(
+                    &creq,
+                    string_to_sign,
+                    creds.session_token(),
+                ));
+            }
 
             // Step 4: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-add-signature-to-request.html
             let values = creq.values.as_headers().expect("signing with headers");
@@ -427,7 +509,15 @@ fn calculate_signing_headers<'a>(
 
             let signing_key =
                 v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
-            let signature = v4a::calculate_signature(&signing_key, sts.to_string().as_bytes());
+            let string_to_sign = sts.to_string();
+            let signature = v4a::calculate_signature(&signing_key, string_to_sign.as_bytes());
+            if params.settings.capture_debug_info {
+                debug_info = Some(SigningDebugInfo::new(
+                    &creq,
+                    string_to_sign,
+                    creds.session_token(),
+                ));
+            }
 
             let values = creq.values.as_headers().expect("signing with headers");
             add_header(&mut headers, header::X_AMZ_DATE, &values.date_time, false);
@@ -470,7 +560,7 @@ fn calculate_signing_headers<'a>(
         }
     };
 
-    Ok(SigningOutput::new(headers, signature))
+    Ok((headers, signature, debug_info))
 }
 
 fn add_header(map: &mut Vec<Header>, key: &'static str, value: &str, sensitive: bool) {
@@ -519,6 +609,7 @@ mod tests {
     use proptest::proptest;
     use std::borrow::Cow;
     use std::iter;
+    use std::time::Duration;
 
     macro_rules! assert_req_eq {
         (http: $expected:expr, $actual:expr) => {
@@ -774,6 +865,115 @@ mod tests {
         assert_req_eq!(expected, signed);
     }
 
+    #[test]
+    fn test_sign_captures_debug_info() {
+        let settings = SigningSettings {
+            capture_debug_info: true,
+            ..Default::default()
+        };
+        let identity = &Credentials::for_tests_with_session_token().into();
+        let params = v4::SigningParams {
+            identity,
+            region: "us-east-1",
+            name: "service",
+            time: parse_date_time("20150830T123600Z").unwrap(),
+            settings,
+        }
+        .into();
+
+        let original = http0::Request::builder()
+            .uri("https://some-endpoint.some-region.amazonaws.com")
+            .body("")
+            .unwrap()
+            .into();
+        let signable = SignableRequest::from(&original);
+        let out = sign(signable, &params).unwrap();
+        let debug_info = out.output.debug_info().expect("debug info was captured");
+        assert_eq!(
+            "host;x-amz-date;x-amz-security-token",
+            debug_info.signed_headers()
+        );
+        assert!(debug_info
+            .string_to_sign()
+            .starts_with("AWS4-HMAC-SHA256\n20150830T123600Z\n"));
+        assert!(debug_info
+            .canonical_request()
+            .contains("x-amz-security-token:** REDACTED **\n"));
+        assert!(!debug_info
+            .canonical_request()
+            .contains("notarealsessiontoken"));
+
+        // Debug info is not captured by default
+        let params = v4::SigningParams {
+            identity,
+            region: "us-east-1",
+            name: "service",
+            time: parse_date_time("20150830T123600Z").unwrap(),
+            settings: SigningSettings::default(),
+        }
+        .into();
+        let signable = SignableRequest::from(&original);
+        let out = sign(signable, &params).unwrap();
+        assert!(out.output.debug_info().is_none());
+    }
+
+    #[test]
+    fn test_sign_redacts_encoded_session_token_in_debug_info() {
+        let session_token = "session/token+with=reserved==";
+        let encoded_session_token = aws_smithy_http::query::fmt_string(session_token);
+        assert_ne!(session_token, encoded_session_token);
+        let identity = &Credentials::new(
+            "ANOTREAL",
+            "notrealrnrELgWzOk3IfjzDKtFBhDby",
+            Some(session_token.to_string()),
+            None,
+            "test",
+        )
+        .into();
+
+        for signature_location in [SignatureLocation::Headers, SignatureLocation::QueryParams] {
+            let settings = SigningSettings {
+                capture_debug_info: true,
+                signature_location,
+                expires_in: Some(Duration::from_secs(30)),
+                ..Default::default()
+            };
+            let params = v4::SigningParams {
+                identity,
+                region: "us-east-1",
+                name: "service",
+                time: parse_date_time("20150830T123600Z").unwrap(),
+                settings,
+            }
+            .into();
+
+            let original = http0::Request::builder()
+                .uri("https://some-endpoint.some-region.amazonaws.com")
+                .body("")
+                .unwrap()
+                .into();
+            let signable = SignableRequest::from(&original);
+            let out = sign(signable, &params).unwrap();
+            let canonical_request = out
+                .output
+                .debug_info()
+                .expect("debug info was captured")
+                .canonical_request();
+            assert!(
+                canonical_request.contains("** REDACTED **"),
+                "{signature_location:?}: {canonical_request}"
+            );
+            assert!(
+                !canonical_request.contains(session_token),
+                "{signature_location:?}: {canonical_request}"
+            );
+            assert!(
+                !canonical_request.contains(encoded_session_token.as_str()),
+                "{signature_location:?}: {canonical_request}"
+            );
+        }
+    }
+
     #[test]
     fn test_sign_headers_utf8() {
         let settings = SigningSettings::default();
@@ -0,0 +1,121 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Defaults modes select a bundle of default settings tuned for the environment the SDK runs in.
+
+use std::fmt;
+use std::str::FromStr;
+
+const LEGACY: &str = "legacy";
+const STANDARD: &str = "standard";
+const IN_REGION: &str = "in-region";
+const CROSS_REGION: &str = "cross-region";
+const MOBILE: &str = "mobile";
+const AUTO: &str = "auto";
+
+/// A bundle of default settings tuned for an execution environment.
+///
+/// The defaults mode only changes settings that haven't been configured explicitly, e.g. with
+/// environment variables, profile files, or in code.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DefaultsMode {
+    /// Use the defaults of previous SDK versions.
+    #[default]
+    Legacy,
+    /// Use defaults that are safe for most scenarios.
+    Standard,
+    /// Use defaults tuned for applications calling services in the same region they run in.
+    InRegion,
+    /// Use defaults tuned for applications calling services in a different region.
+    CrossRegion,
+    /// Use defaults tuned for mobile applications, which may have high latency network connections.
+    Mobile,
+    /// Detect the execution environment and use the defaults of the matching mode.
+    ///
+    /// Detection may call the EC2 instance metadata service to determine the region the
+    /// application runs in.
+    Auto,
+}
+
+impl DefaultsMode {
+    fn all_variants() -> [DefaultsMode; 6] {
+        use DefaultsMode::*;
+        [Legacy, Standard, InRegion, CrossRegion, Mobile, Auto]
+    }
+
+    /// Returns the string representation of this mode, as used in profile files and environment
+    /// variables.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DefaultsMode::Legacy => LEGACY,
+            DefaultsMode::Standard => STANDARD,
+            DefaultsMode::InRegion => IN_REGION,
+            DefaultsMode::CrossRegion => CROSS_REGION,
+            DefaultsMode::Mobile => MOBILE,
+            DefaultsMode::Auto => AUTO,
+        }
+    }
+}
+
+impl fmt::Display for DefaultsMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DefaultsMode {
+    type Err = DefaultsModeParseError;
+
+    fn from_str(mode_str: &str) -> Result<Self, Self::Err> {
+        let mode_str = mode_str.trim();
+        DefaultsMode::all_variants()
+            .into_iter()
+            .find(|mode| mode_str.eq_ignore_ascii_case(mode.as_str()))
+            .ok_or_else(|| DefaultsModeParseError::new(mode_str))
+    }
+}
+
+/// Error encountered when failing to parse a string into [`DefaultsMode`].
+#[derive(Debug)]
+pub struct DefaultsModeParseError {
+    mode_string: String,
+}
+
+impl DefaultsModeParseError {
+    fn new(mode_string: impl Into<String>) -> Self {
+        Self {
+            mode_string: mode_string.into(),
+        }
+    }
+}
+
+impl fmt::Display for DefaultsModeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error parsing string `{}` as `DefaultsMode`, valid options are: {:#?}",
+            self.mode_string,
+            DefaultsMode::all_variants().map(|mode| mode.as_str())
+        )
+    }
+}
+
+impl std::error::Error for DefaultsModeParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::DefaultsMode;
+
+    #[test]
+    fn parse_defaults_mode() {
+        for mode in DefaultsMode::all_variants() {
+            assert_eq!(mode, mode.to_string().parse().unwrap());
+        }
+        assert_eq!(DefaultsMode::InRegion, "In-Region".parse().unwrap());
+        assert_eq!(DefaultsMode::Auto, " auto ".parse().unwrap());
+        assert!("in_region".parse::<DefaultsMode>().is_err());
+    }
+}
@@ -33,6 +33,85 @@ impl Storable for EndpointUrl {
     type Storer = StoreReplace<EndpointUrl>;
 }
 
+impl EndpointUrl {
+    /// Expands the `{service}` and `{region}` placeholders in this endpoint URL
+    ///
+    /// This allows a single endpoint URL, e.g. `https://{service}.{region}.mycorp.internal`, to route
+    /// requests for every service and region to a different host. URLs without placeholders are
+    /// returned unchanged.
+    pub fn expand(
+        &self,
+        service: &str,
+        region: Option<&str>,
+    ) -> Result<String, EndpointUrlTemplateError> {
+        let mut expanded = String::with_capacity(self.0.len());
+        let mut rest = self.0.as_str();
+        while let Some(start) = rest.find('{') {
+            expanded.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| EndpointUrlTemplateError::new(&self.0, ErrorKind::Unclosed))?;
+            match &rest[start + 1..start + end] {
+                "service" => expanded.push_str(service),
+                "region" => expanded.push_str(region.ok_or_else(|| {
+                    EndpointUrlTemplateError::new(&self.0, ErrorKind::MissingRegion)
+                })?),
+                other => {
+                    return Err(EndpointUrlTemplateError::new(
+                        &self.0,
+                        ErrorKind::UnknownPlaceholder(other.to_string()),
+                    ))
+                }
+            }
+            rest = &rest[start + end + 1..];
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Unclosed,
+    MissingRegion,
+    UnknownPlaceholder(String),
+}
+
+/// Error encountered when failing to expand the placeholders of an [`EndpointUrl`].
+#[derive(Debug)]
+pub struct EndpointUrlTemplateError {
+    url: String,
+    kind: ErrorKind,
+}
+
+impl EndpointUrlTemplateError {
+    fn new(url: impl Into<String>, kind: ErrorKind) -> Self {
+        Self {
+            url: url.into(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for EndpointUrlTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid endpoint URL `{}`: ", self.url)?;
+        match &self.kind {
+            ErrorKind::Unclosed => write!(f, "a placeholder is missing its closing `}}`"),
+            ErrorKind::MissingRegion => write!(
+                f,
+                "the URL contains a `{{region}}` placeholder, but no region is configured"
+            ),
+            ErrorKind::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder `{{{name}}}`, valid placeholders are `{{service}}` and `{{region}}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EndpointUrlTemplateError {}
+
 const PREFERRED: &str = "preferred";
 const DISABLED: &str = "disabled";
 const REQUIRED: &str = "required";
@@ -139,6 +218,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn expand_endpoint_url() {
+        let url = EndpointUrl("https://{service}.{region}.mycorp.internal/{service}".into());
+        assert_eq!(
+            "https://s3.us-west-2.mycorp.internal/s3",
+            url.expand("s3", Some("us-west-2")).unwrap()
+        );
+        assert_eq!(
+            "http://localhost:8000",
+            EndpointUrl("http://localhost:8000".into())
+                .expand("s3", None)
+                .unwrap()
+        );
+
+        let err = url.expand("s3", None).unwrap_err();
+        assert!(
+            format!("{err}").contains("no region is configured"),
+            "{err}"
+        );
+        let err = EndpointUrl("https://{partition}.mycorp.internal".into())
+            .expand("s3", Some("us-west-2"))
+            .unwrap_err();
+        assert!(
+            format!("{err}").contains("unknown placeholder `{partition}`"),
+            "{err}"
+        );
+        let err = EndpointUrl("https://{service.mycorp.internal".into())
+            .expand("s3", Some("us-west-2"))
+            .unwrap_err();
+        assert!(format!("{err}").contains("closing `}`"), "{err}");
+    }
+
     #[test]
     fn parse_err_account_id_endpoint_mode() {
         let err = AccountIdEndpointMode::from_str("invalid").err().unwrap();
@@ -19,6 +19,7 @@
 
 pub mod app_name;
 pub mod build_metadata;
+pub mod defaults_mode;
 pub mod endpoint_config;
 pub mod origin;
 pub mod os_shim_internal;
@@ -233,9 +233,57 @@ impl Env {
         match &self.0 {
             Inner::Real => std::env::var(k),
             Inner::Fake(map) => map.get(k).cloned().ok_or(VarError::NotPresent),
+            Inner::Prefixed { prefix, env } => {
+                if CREDENTIAL_VARIABLES.contains(&k) {
+                    // Credentials are resolved as a set so that a prefixed access key is never
+                    // combined with an unprefixed secret key or session token
+                    let prefixed_credentials_set = PREFIXED_CREDENTIAL_KEYS
+                        .iter()
+                        .any(|key| env.get(&format!("{prefix}{key}")).is_ok());
+                    return match (prefixed_credentials_set, k.starts_with(AWS_PREFIX)) {
+                        (true, true) => env.get(&format!("{prefix}{k}")),
+                        (true, false) => Err(VarError::NotPresent),
+                        (false, _) => env.get(k),
+                    };
+                }
+                if k.starts_with(AWS_PREFIX) {
+                    if let Ok(value) = env.get(&format!("{prefix}{k}")) {
+                        return Ok(value);
+                    }
+                }
+                env.get(k)
+            }
         }
     }
 
+    /// Look up `AWS_*` variables with `prefix` prepended before falling back to the variable itself
+    ///
+    /// This scopes SDK configuration to a component of a process without changing the `AWS_*`
+    /// variables other components see. Variables that don't start with `AWS_` are not affected.
+    ///
+    /// Static credentials are looked up as a set: if a prefixed access key ID or secret access key
+    /// is set, all of the credential variables are read with the prefix, and none of them fall
+    /// back to the unprefixed variables.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use aws_types::os_shim_internal::Env;
+    /// let env = Env::from_slice(&[
+    ///     ("AWS_REGION", "us-west-2"),
+    ///     ("MYAPP_AWS_REGION", "eu-west-1"),
+    ///     ("AWS_PROFILE", "default"),
+    /// ])
+    /// .with_prefix("MYAPP_");
+    /// assert_eq!(env.get("AWS_REGION").unwrap(), "eu-west-1");
+    /// assert_eq!(env.get("AWS_PROFILE").unwrap(), "default");
+    /// ```
+    pub fn with_prefix(self, prefix: impl Into<String>) -> Self {
+        Self(env::Inner::Prefixed {
+            prefix: prefix.into().into(),
+            env: Arc::new(self),
+        })
+    }
+
     /// Create a fake process environment from a slice of tuples.
     ///
     /// # Examples
@@ -269,6 +317,20 @@ impl From<HashMap<String, String>> for Env {
     }
 }
 
+const AWS_PREFIX: &str = "AWS_";
+
+/// Variables that make up static credentials, including the legacy `SECRET_ACCESS_KEY`
+const CREDENTIAL_VARIABLES: &[&str] = &[
+    "AWS_ACCESS_KEY_ID",
+    "AWS_SECRET_ACCESS_KEY",
+    "AWS_SESSION_TOKEN",
+    "AWS_ACCOUNT_ID",
+    "SECRET_ACCESS_KEY",
+];
+
+/// Variables that select the prefixed credential variables when they're set with the prefix
+const PREFIXED_CREDENTIAL_KEYS: &[&str] = &["AWS_ACCESS_KEY_ID", "AWS_SECRET_ACCESS_KEY"];
+
 mod env {
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -277,6 +339,10 @@ mod env {
     pub(super) enum Inner {
         Real,
         Fake(Arc<HashMap<String, String>>),
+        Prefixed {
+            prefix: Arc<str>,
+            env: Arc<super::Env>,
+        },
     }
 }
 
@@ -296,6 +362,54 @@ mod test {
         )
     }
 
+    #[test]
+    fn prefixed_env_overrides_aws_variables() {
+        let env = Env::from_slice(&[
+            ("AWS_REGION", "us-west-2"),
+            ("MYAPP_AWS_REGION", "eu-west-1"),
+            ("AWS_PROFILE", "default"),
+            ("HOME", "/home/me"),
+            ("MYAPP_HOME", "/home/myapp"),
+        ])
+        .with_prefix("MYAPP_");
+        assert_eq!(env.get("AWS_REGION").unwrap(), "eu-west-1");
+        assert_eq!(env.get("AWS_PROFILE").unwrap(), "default");
+        assert_eq!(env.get("HOME").unwrap(), "/home/me");
+        assert_eq!(
+            env.get("AWS_OTHER").expect_err("not present"),
+            VarError::NotPresent
+        );
+    }
+
+    #[test]
+    fn prefixed_credentials_are_resolved_as_a_set() {
+        let unprefixed = [
+            ("AWS_ACCESS_KEY_ID", "akid"),
+            ("AWS_SECRET_ACCESS_KEY", "secret"),
+            ("AWS_SESSION_TOKEN", "token"),
+            ("SECRET_ACCESS_KEY", "legacy-secret"),
+        ];
+
+        let mut vars = unprefixed.to_vec();
+        vars.push(("MYAPP_AWS_ACCESS_KEY_ID", "myapp-akid"));
+        let env = Env::from_slice(&vars).with_prefix("MYAPP_");
+        assert_eq!(env.get("AWS_ACCESS_KEY_ID").unwrap(), "myapp-akid");
+        for key in [
+            "AWS_SECRET_ACCESS_KEY",
+            "AWS_SESSION_TOKEN",
+            "SECRET_ACCESS_KEY",
+        ] {
+            assert_eq!(env.get(key).expect_err(key), VarError::NotPresent);
+        }
+
+        // Without a prefixed access key or secret key, a prefixed session token alone is ignored
+        let mut vars = unprefixed.to_vec();
+        vars.push(("MYAPP_AWS_SESSION_TOKEN", "myapp-token"));
+        let env = Env::from_slice(&vars).with_prefix("MYAPP_");
+        assert_eq!(env.get("AWS_ACCESS_KEY_ID").unwrap(), "akid");
+        assert_eq!(env.get("AWS_SESSION_TOKEN").unwrap(), "token");
+    }
+
     #[tokio::test]
     async fn fs_from_test_dir_works() {
         let fs = Fs::from_test_dir(".", "/users/test-data");
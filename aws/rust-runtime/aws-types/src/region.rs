@@ -99,6 +99,12 @@ impl From<Region> for SigningRegionSet {
     }
 }
 
+impl From<String> for SigningRegionSet {
+    fn from(region_set: String) -> Self {
+        SigningRegionSet(Cow::Owned(region_set))
+    }
+}
+
 impl From<&'static str> for SigningRegionSet {
     fn from(region: &'static str) -> Self {
         SigningRegionSet(Cow::Borrowed(region))
@@ -5,6 +5,7 @@
 
 //! AWS-specific request ID support
 
+use aws_smithy_runtime_api::client::pagination::PageMetadata;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::http::Headers;
 use aws_smithy_runtime_api::http::Response;
@@ -41,6 +42,12 @@ impl<B> RequestId for Response<B> {
     }
 }
 
+impl RequestId for PageMetadata {
+    fn request_id(&self) -> Option<&str> {
+        self.headers().request_id()
+    }
+}
+
 impl RequestId for Headers {
     fn request_id(&self) -> Option<&str> {
         self.get("x-amzn-requestid")
@@ -74,11 +81,13 @@ pub fn apply_request_id(builder: ErrorMetadataBuilder, headers: &Headers) -> Err
 mod tests {
     use crate::request_id::{apply_request_id, RequestId, AWS_REQUEST_ID};
     use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_runtime_api::client::pagination::PageMetadata;
     use aws_smithy_runtime_api::client::result::SdkError;
     use aws_smithy_runtime_api::http::Headers;
     use aws_smithy_types::body::SdkBody;
     use aws_smithy_types::error::ErrorMetadata;
     use http::{HeaderValue, Response};
+    use std::time::Duration;
 
     #[test]
     fn test_request_id_sdk_error() {
@@ -162,4 +171,24 @@ mod tests {
             .build();
         assert_eq!(Some("some-request-id"), err.request_id());
     }
+
+    #[test]
+    fn test_page_metadata_request_id_impl() {
+        let response = HttpResponse::try_from(
+            Response::builder()
+                .header(
+                    "x-amzn-requestid",
+                    HeaderValue::from_static("some-request-id"),
+                )
+                .body(SdkBody::empty())
+                .unwrap(),
+        )
+        .unwrap();
+        let metadata = PageMetadata::new(1, Duration::from_millis(100));
+        assert_eq!(None, metadata.request_id());
+        assert_eq!(
+            Some("some-request-id"),
+            metadata.with_response(&response).request_id()
+        );
+    }
 }
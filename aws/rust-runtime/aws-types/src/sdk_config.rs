@@ -10,17 +10,18 @@
 //! This module contains a shared configuration representation that is agnostic from a specific service.
 
 use crate::app_name::AppName;
+use crate::defaults_mode::DefaultsMode;
 use crate::docs_for;
 use crate::endpoint_config::AccountIdEndpointMode;
 use crate::origin::Origin;
-use crate::region::Region;
+use crate::region::{Region, SigningRegionSet};
 use crate::service_config::LoadServiceConfig;
 use aws_credential_types::provider::token::SharedTokenProvider;
 pub use aws_credential_types::provider::SharedCredentialsProvider;
 use aws_smithy_async::rt::sleep::AsyncSleep;
 pub use aws_smithy_async::rt::sleep::SharedAsyncSleep;
 pub use aws_smithy_async::time::{SharedTimeSource, TimeSource};
-use aws_smithy_runtime_api::client::auth::AuthSchemePreference;
+use aws_smithy_runtime_api::client::auth::{AuthSchemePreference, DisabledAuthSchemes};
 use aws_smithy_runtime_api::client::behavior_version::BehaviorVersion;
 use aws_smithy_runtime_api::client::http::HttpClient;
 pub use aws_smithy_runtime_api::client::http::SharedHttpClient;
@@ -52,6 +53,18 @@ If no dual-stack endpoint is available the request MAY return an error.
 
 **Note**: Some services do not offer dual-stack as a configurable parameter (e.g. Code Catalyst). For
 these services, this setting has no effect"
+        };
+        (defaults_mode) => {
+"The defaults mode used to select default settings that haven't been configured explicitly.
+
+With `auto`, the execution environment is detected when the config is loaded, and the
+defaults of the detected mode are used."
+        };
+        (sigv4a_signing_region_set) => {
+"The set of regions a SigV4a signature is valid in, e.g. `us-east-1,us-west-2` or `*`.
+
+This takes precedence over the region set determined by endpoint resolution, e.g. for S3
+Multi-Region Access Points. Services that don't use SigV4a ignore this setting."
         };
         (time_source) => {
 "The time source use to use for this client.
@@ -93,6 +106,13 @@ can reorder the schemes resolved by the auth scheme resolver.
 
 The preference list is intended as a hint rather than a strict override.
 Any schemes not present in the originally resolved auth schemes will be ignored.
+" };
+        (disabled_auth_schemes) => {
+"Set the auth schemes that must never be used to sign a request.
+
+Unlike the auth scheme preference, this is a strict restriction. Disabled schemes
+are removed from the auth schemes resolved for an operation, and the request fails
+with an error listing the disabled schemes if no other scheme is available.
 " };
     }
 }
@@ -102,12 +122,14 @@ Any schemes not present in the originally resolved auth schemes will be ignored.
 pub struct SdkConfig {
     app_name: Option<AppName>,
     auth_scheme_preference: Option<AuthSchemePreference>,
+    disabled_auth_schemes: Option<DisabledAuthSchemes>,
     identity_cache: Option<SharedIdentityCache>,
     credentials_provider: Option<SharedCredentialsProvider>,
     token_provider: Option<SharedTokenProvider>,
     region: Option<Region>,
     account_id_endpoint_mode: Option<AccountIdEndpointMode>,
     endpoint_url: Option<String>,
+    service_endpoint_urls: HashMap<String, String>,
     retry_config: Option<RetryConfig>,
     sleep_impl: Option<SharedAsyncSleep>,
     time_source: Option<SharedTimeSource>,
@@ -116,6 +138,8 @@ pub struct SdkConfig {
     http_client: Option<SharedHttpClient>,
     use_fips: Option<bool>,
     use_dual_stack: Option<bool>,
+    defaults_mode: Option<DefaultsMode>,
+    sigv4a_signing_region_set: Option<SigningRegionSet>,
     behavior_version: Option<BehaviorVersion>,
     service_config: Option<Arc<dyn LoadServiceConfig>>,
     config_origins: HashMap<&'static str, Origin>,
@@ -134,12 +158,14 @@ pub struct SdkConfig {
 pub struct Builder {
     app_name: Option<AppName>,
     auth_scheme_preference: Option<AuthSchemePreference>,
+    disabled_auth_schemes: Option<DisabledAuthSchemes>,
     identity_cache: Option<SharedIdentityCache>,
     credentials_provider: Option<SharedCredentialsProvider>,
     token_provider: Option<SharedTokenProvider>,
     region: Option<Region>,
     account_id_endpoint_mode: Option<AccountIdEndpointMode>,
     endpoint_url: Option<String>,
+    service_endpoint_urls: HashMap<String, String>,
     retry_config: Option<RetryConfig>,
     sleep_impl: Option<SharedAsyncSleep>,
     time_source: Option<SharedTimeSource>,
@@ -148,6 +174,8 @@ pub struct Builder {
     http_client: Option<SharedHttpClient>,
     use_fips: Option<bool>,
     use_dual_stack: Option<bool>,
+    defaults_mode: Option<DefaultsMode>,
+    sigv4a_signing_region_set: Option<SigningRegionSet>,
     behavior_version: Option<BehaviorVersion>,
     service_config: Option<Arc<dyn LoadServiceConfig>>,
     config_origins: HashMap<&'static str, Origin>,
@@ -227,6 +255,51 @@ impl Builder {
         self
     }
 
+    /// Set the endpoint URL to use when making requests to the service with the given service ID.
+    ///
+    /// This is the programmatic equivalent of the `AWS_ENDPOINT_URL_<SERVICE>` environment
+    /// variables, and takes precedence over them and over [`endpoint_url`](Self::endpoint_url).
+    /// Service IDs are matched case-insensitively, and spaces, dashes, and underscores are
+    /// interchangeable, e.g. `"DynamoDB"`, `"dynamodb"`, and `"Elastic Beanstalk"`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aws_types::SdkConfig;
+    /// let config = SdkConfig::builder()
+    ///     .endpoint_url_for("s3", "http://localhost:9000")
+    ///     .endpoint_url_for("DynamoDB", "http://localhost:8000")
+    ///     .build();
+    /// assert_eq!(Some("http://localhost:9000"), config.endpoint_url_for("S3"));
+    /// ```
+    pub fn endpoint_url_for(
+        mut self,
+        service_id: impl AsRef<str>,
+        endpoint_url: impl Into<String>,
+    ) -> Self {
+        self.set_endpoint_url_for(service_id, Some(endpoint_url.into()));
+        self
+    }
+
+    /// Set the endpoint URL to use when making requests to the service with the given service ID.
+    ///
+    /// Passing `None` removes a previously set endpoint URL for that service.
+    pub fn set_endpoint_url_for(
+        &mut self,
+        service_id: impl AsRef<str>,
+        endpoint_url: Option<String>,
+    ) -> &mut Self {
+        let service_id = normalize_service_id(service_id.as_ref());
+        match endpoint_url {
+            Some(endpoint_url) => {
+                self.service_endpoint_urls.insert(service_id, endpoint_url);
+            }
+            None => {
+                self.service_endpoint_urls.remove(&service_id);
+            }
+        }
+        self
+    }
+
     /// Set the checksum calculation strategy to use when making requests.
     /// # Examples
     /// ```
@@ -711,6 +784,44 @@ impl Builder {
         self
     }
 
+    #[doc = docs_for!(defaults_mode)]
+    pub fn defaults_mode(mut self, defaults_mode: DefaultsMode) -> Self {
+        self.set_defaults_mode(Some(defaults_mode));
+        self
+    }
+
+    #[doc = docs_for!(defaults_mode)]
+    pub fn set_defaults_mode(&mut self, defaults_mode: Option<DefaultsMode>) -> &mut Self {
+        self.defaults_mode = defaults_mode;
+        self
+    }
+
+    #[doc = docs_for!(sigv4a_signing_region_set)]
+    ///
+    /// # Examples
+    /// ```
+    /// use aws_types::SdkConfig;
+    /// let config = SdkConfig::builder()
+    ///     .sigv4a_signing_region_set("us-east-1,us-west-2")
+    ///     .build();
+    /// ```
+    pub fn sigv4a_signing_region_set(
+        mut self,
+        sigv4a_signing_region_set: impl Into<SigningRegionSet>,
+    ) -> Self {
+        self.set_sigv4a_signing_region_set(Some(sigv4a_signing_region_set.into()));
+        self
+    }
+
+    #[doc = docs_for!(sigv4a_signing_region_set)]
+    pub fn set_sigv4a_signing_region_set(
+        &mut self,
+        sigv4a_signing_region_set: Option<SigningRegionSet>,
+    ) -> &mut Self {
+        self.sigv4a_signing_region_set = sigv4a_signing_region_set;
+        self
+    }
+
     #[doc = docs_for!(time_source)]
     pub fn time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
         self.set_time_source(Some(SharedTimeSource::new(time_source)));
@@ -809,6 +920,24 @@ impl Builder {
         self
     }
 
+    #[doc = docs_for!(disabled_auth_schemes)]
+    pub fn disabled_auth_schemes(
+        mut self,
+        disabled_auth_schemes: impl Into<DisabledAuthSchemes>,
+    ) -> Self {
+        self.set_disabled_auth_schemes(Some(disabled_auth_schemes));
+        self
+    }
+
+    #[doc = docs_for!(disabled_auth_schemes)]
+    pub fn set_disabled_auth_schemes(
+        &mut self,
+        disabled_auth_schemes: Option<impl Into<DisabledAuthSchemes>>,
+    ) -> &mut Self {
+        self.disabled_auth_schemes = disabled_auth_schemes.map(|disabled| disabled.into());
+        self
+    }
+
     /// Set the origin of a setting.
     ///
     /// This is used internally to understand how to merge config structs while
@@ -822,18 +951,22 @@ impl Builder {
         SdkConfig {
             app_name: self.app_name,
             auth_scheme_preference: self.auth_scheme_preference,
+            disabled_auth_schemes: self.disabled_auth_schemes,
             identity_cache: self.identity_cache,
             credentials_provider: self.credentials_provider,
             token_provider: self.token_provider,
             region: self.region,
             account_id_endpoint_mode: self.account_id_endpoint_mode,
             endpoint_url: self.endpoint_url,
+            service_endpoint_urls: self.service_endpoint_urls,
             retry_config: self.retry_config,
             sleep_impl: self.sleep_impl,
             timeout_config: self.timeout_config,
             http_client: self.http_client,
             use_fips: self.use_fips,
             use_dual_stack: self.use_dual_stack,
+            defaults_mode: self.defaults_mode,
+            sigv4a_signing_region_set: self.sigv4a_signing_region_set,
             time_source: self.time_source,
             behavior_version: self.behavior_version,
             stalled_stream_protection_config: self.stalled_stream_protection_config,
@@ -934,11 +1067,25 @@ impl SdkConfig {
         self.auth_scheme_preference.as_ref()
     }
 
+    /// Configured disabled auth schemes
+    pub fn disabled_auth_schemes(&self) -> Option<&DisabledAuthSchemes> {
+        self.disabled_auth_schemes.as_ref()
+    }
+
     /// Configured endpoint URL
     pub fn endpoint_url(&self) -> Option<&str> {
         self.endpoint_url.as_deref()
     }
 
+    /// Endpoint URL configured for the service with the given service ID
+    ///
+    /// See [`Builder::endpoint_url_for`] for how service IDs are matched.
+    pub fn endpoint_url_for(&self, service_id: &str) -> Option<&str> {
+        self.service_endpoint_urls
+            .get(&normalize_service_id(service_id))
+            .map(String::as_str)
+    }
+
     /// Configured retry config
     pub fn retry_config(&self) -> Option<&RetryConfig> {
         self.retry_config.as_ref()
@@ -994,6 +1141,19 @@ impl SdkConfig {
         self.use_dual_stack
     }
 
+    /// The defaults mode used to select default settings
+    ///
+    /// When the config was loaded with [`DefaultsMode::Auto`], this is the mode that was detected
+    /// for the execution environment.
+    pub fn defaults_mode(&self) -> Option<DefaultsMode> {
+        self.defaults_mode
+    }
+
+    /// Configured SigV4a signing region set
+    pub fn sigv4a_signing_region_set(&self) -> Option<&SigningRegionSet> {
+        self.sigv4a_signing_region_set.as_ref()
+    }
+
     /// When true, request compression is disabled.
     pub fn disable_request_compression(&self) -> Option<bool> {
         self.disable_request_compression
@@ -1059,12 +1219,14 @@ impl SdkConfig {
         Builder {
             app_name: self.app_name,
             auth_scheme_preference: self.auth_scheme_preference,
+            disabled_auth_schemes: self.disabled_auth_schemes,
             identity_cache: self.identity_cache,
             credentials_provider: self.credentials_provider,
             token_provider: self.token_provider,
             region: self.region,
             account_id_endpoint_mode: self.account_id_endpoint_mode,
             endpoint_url: self.endpoint_url,
+            service_endpoint_urls: self.service_endpoint_urls,
             retry_config: self.retry_config,
             sleep_impl: self.sleep_impl,
             time_source: self.time_source,
@@ -1072,6 +1234,8 @@ impl SdkConfig {
             http_client: self.http_client,
             use_fips: self.use_fips,
             use_dual_stack: self.use_dual_stack,
+            defaults_mode: self.defaults_mode,
+            sigv4a_signing_region_set: self.sigv4a_signing_region_set,
             behavior_version: self.behavior_version,
             stalled_stream_protection_config: self.stalled_stream_protection_config,
             service_config: self.service_config,
@@ -1083,3 +1247,14 @@ impl SdkConfig {
         }
     }
 }
+
+fn normalize_service_id(service_id: &str) -> String {
+    service_id
+        .trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
@@ -5,7 +5,9 @@
 
 //! Code for extracting service config from the user's environment.
 
+use std::error::Error as StdError;
 use std::fmt;
+use std::str::FromStr;
 
 /// A struct used with the [`LoadServiceConfig`] trait to extract service config from the user's environment.
 // [profile active-profile]
@@ -18,7 +20,7 @@ use std::fmt;
 pub struct ServiceConfigKey<'a> {
     service_id: &'a str,
     profile: &'a str,
-    env: &'a str,
+    env: Option<&'a str>,
 }
 
 impl<'a> ServiceConfigKey<'a> {
@@ -35,7 +37,9 @@ impl<'a> ServiceConfigKey<'a> {
         self.profile
     }
     /// Get the environment key.
-    pub fn env(&self) -> &'a str {
+    ///
+    /// Returns `None` for settings that can only be set in a profile.
+    pub fn env(&self) -> Option<&'a str> {
         self.env
     }
 }
@@ -68,6 +72,8 @@ pub mod builder {
         }
 
         /// Set the environment key.
+        ///
+        /// This is optional, since some settings can only be set in a profile.
         pub fn env(mut self, env: &'a str) -> Self {
             self.env = Some(env);
             self
@@ -75,12 +81,12 @@ pub mod builder {
 
         /// Build the [`ServiceConfigKey`].
         ///
-        /// Returns an error if any of the required fields are missing.
+        /// Returns an error if the service ID or the profile key are missing.
         pub fn build(self) -> Result<ServiceConfigKey<'a>, Error> {
             Ok(ServiceConfigKey {
                 service_id: self.service_id.ok_or_else(Error::missing_service_id)?,
                 profile: self.profile.ok_or_else(Error::missing_profile)?,
-                env: self.env.ok_or_else(Error::missing_env)?,
+                env: self.env,
             })
         }
     }
@@ -144,3 +150,167 @@ pub trait LoadServiceConfig: fmt::Debug + Send + Sync {
     /// Given a [`ServiceConfigKey`], return the value associated with it.
     fn load_config(&self, key: ServiceConfigKey<'_>) -> Option<String>;
 }
+
+impl dyn LoadServiceConfig + '_ {
+    /// Load the value associated with `key` and parse it as a `T`.
+    ///
+    /// Returns `Ok(None)` when the value isn't set, and an error when it is set but can't be parsed.
+    pub fn load_parsed<T>(
+        &self,
+        key: ServiceConfigKey<'_>,
+    ) -> Result<Option<T>, InvalidServiceConfigValue>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        self.load_value(key, |value| value.parse::<T>().map_err(Into::into))
+    }
+
+    /// Load the value associated with `key` as a boolean.
+    ///
+    /// `true` and `false` are accepted in any case.
+    pub fn load_bool(
+        &self,
+        key: ServiceConfigKey<'_>,
+    ) -> Result<Option<bool>, InvalidServiceConfigValue> {
+        self.load_value(key, |value| {
+            if value.eq_ignore_ascii_case("true") {
+                Ok(true)
+            } else if value.eq_ignore_ascii_case("false") {
+                Ok(false)
+            } else {
+                Err("expected `true` or `false`".into())
+            }
+        })
+    }
+
+    fn load_value<T>(
+        &self,
+        key: ServiceConfigKey<'_>,
+        parse: impl FnOnce(&str) -> Result<T, Box<dyn StdError + Send + Sync>>,
+    ) -> Result<Option<T>, InvalidServiceConfigValue> {
+        let Some(value) = self.load_config(key.clone()) else {
+            return Ok(None);
+        };
+        parse(value.trim())
+            .map(Some)
+            .map_err(|source| InvalidServiceConfigValue {
+                service_id: key.service_id().to_owned(),
+                profile_key: key.profile().to_owned(),
+                env_key: key.env().map(str::to_owned),
+                value,
+                source,
+            })
+    }
+}
+
+/// Error returned when a service config value is set but can't be parsed.
+#[derive(Debug)]
+pub struct InvalidServiceConfigValue {
+    service_id: String,
+    profile_key: String,
+    env_key: Option<String>,
+    value: String,
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+impl InvalidServiceConfigValue {
+    /// Get the service ID the value was loaded for.
+    pub fn service_id(&self) -> &str {
+        &self.service_id
+    }
+    /// Get the profile key the value was loaded from.
+    pub fn profile_key(&self) -> &str {
+        &self.profile_key
+    }
+    /// Get the environment key the value was loaded from, if the setting has one.
+    pub fn env_key(&self) -> Option<&str> {
+        self.env_key.as_deref()
+    }
+    /// Get the value that couldn't be parsed.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for InvalidServiceConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid value `{}` for `{}`",
+            self.value, self.profile_key
+        )?;
+        if let Some(env_key) = &self.env_key {
+            write!(f, " (env: `{env_key}`)")?;
+        }
+        write!(f, " of service `{}`", self.service_id)
+    }
+}
+
+impl StdError for InvalidServiceConfigValue {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.source.as_ref() as _)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LoadServiceConfig, ServiceConfigKey};
+
+    #[derive(Debug)]
+    struct StaticConfig(Option<&'static str>);
+
+    impl LoadServiceConfig for StaticConfig {
+        fn load_config(&self, _key: ServiceConfigKey<'_>) -> Option<String> {
+            self.0.map(str::to_owned)
+        }
+    }
+
+    fn key() -> ServiceConfigKey<'static> {
+        ServiceConfigKey::builder()
+            .service_id("s3")
+            .profile("use_accelerate_endpoint")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn typed_values() {
+        let unset: &dyn LoadServiceConfig = &StaticConfig(None);
+        assert_eq!(None, unset.load_bool(key()).unwrap());
+        assert_eq!(None, unset.load_parsed::<u32>(key()).unwrap());
+
+        let set: &dyn LoadServiceConfig = &StaticConfig(Some(" TRUE "));
+        assert_eq!(Some(true), set.load_bool(key()).unwrap());
+
+        let number: &dyn LoadServiceConfig = &StaticConfig(Some("42"));
+        assert_eq!(Some(42), number.load_parsed::<u32>(key()).unwrap());
+
+        let invalid: &dyn LoadServiceConfig = &StaticConfig(Some("yes"));
+        let err = invalid.load_bool(key()).unwrap_err();
+        assert_eq!("yes", err.value());
+        assert_eq!("use_accelerate_endpoint", err.profile_key());
+        assert_eq!(None, err.env_key());
+        assert_eq!(
+            "invalid value `yes` for `use_accelerate_endpoint` of service `s3`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_values_name_the_env_key() {
+        let key = ServiceConfigKey::builder()
+            .service_id("s3")
+            .env("AWS_S3_USE_ARN_REGION")
+            .profile("s3_use_arn_region")
+            .build()
+            .unwrap();
+        let invalid: &dyn LoadServiceConfig = &StaticConfig(Some("yes"));
+        let err = invalid.load_bool(key).unwrap_err();
+        assert_eq!(Some("AWS_S3_USE_ARN_REGION"), err.env_key());
+        assert_eq!(
+            "invalid value `yes` for `s3_use_arn_region` (env: `AWS_S3_USE_ARN_REGION`) of service `s3`",
+            err.to_string()
+        );
+    }
+}
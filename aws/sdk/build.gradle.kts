@@ -60,6 +60,7 @@ val crateVersioner by lazy { aws.sdk.CrateVersioner.defaultFor(rootProject, prop
 fun getRustMSRV(): String = properties.get("rust.msrv") ?: throw Exception("Rust MSRV missing")
 fun getPreviousReleaseVersionManifestPath(): String? = properties.get("aws.sdk.previous.release.versions.manifest")
 fun getNullabilityCheckMode(): String = properties.get("nullability.check.mode") ?: "CLIENT_CAREFUL"
+fun getOperationFeatureGates(): Boolean = properties.get("aws.sdk.operation.feature.gates").toBoolean()
 
 fun loadServiceMembership(): Membership {
     val membershipOverride = properties.get("aws.services")?.let { parseMembership(it) }
@@ -102,7 +103,8 @@ fun generateSmithyBuild(services: AwsServices): String {
                             "renameErrors": false,
                             "debugMode": $debugMode,
                             "enableUserConfigurableRuntimePlugins": false,
-                            "nullabilityCheckMode": "${getNullabilityCheckMode()}"
+                            "nullabilityCheckMode": "${getNullabilityCheckMode()}",
+                            "operationFeatureGates": ${getOperationFeatureGates()}
                         },
                         "service": "${service.service}",
                         "module": "$moduleName",
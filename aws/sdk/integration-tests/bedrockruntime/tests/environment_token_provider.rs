@@ -24,11 +24,13 @@ struct TestEnv {
 
 impl LoadServiceConfig for TestEnv {
     fn load_config(&self, key: ServiceConfigKey<'_>) -> Option<String> {
-        let (value, _source) = EnvConfigValue::new()
-            .env(key.env())
+        let mut value = EnvConfigValue::new()
             .profile(key.profile())
-            .service_id(key.service_id())
-            .load(&self.env, None)?;
+            .service_id(key.service_id());
+        if let Some(env) = key.env() {
+            value = value.env(env);
+        }
+        let (value, _source) = value.load(&self.env, None)?;
 
         Some(value.to_string())
     }
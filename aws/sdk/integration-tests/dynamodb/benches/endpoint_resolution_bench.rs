@@ -0,0 +1,41 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_dynamodb::config::endpoint::{
+    DefaultResolver, Params, ResolveEndpoint, ResolvedEndpointCache,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use futures_util::FutureExt;
+
+fn do_bench(resolver: &DefaultResolver, params: &Params) {
+    let endpoint = resolver
+        .resolve_endpoint(black_box(params))
+        .now_or_never()
+        .expect("the default resolver is synchronous")
+        .expect("valid params");
+    assert_eq!("https://dynamodb.us-east-1.amazonaws.com", endpoint.url());
+}
+
+fn bench_group(c: &mut Criterion) {
+    let params = Params::builder()
+        .region("us-east-1")
+        .use_fips(false)
+        .use_dual_stack(false)
+        .build()
+        .expect("valid params");
+
+    let cached = DefaultResolver::new();
+    c.bench_function("endpoint_resolution_cached", |b| {
+        b.iter(|| do_bench(&cached, &params))
+    });
+
+    let uncached = DefaultResolver::new().with_cache(ResolvedEndpointCache::disabled());
+    c.bench_function("endpoint_resolution_uncached", |b| {
+        b.iter(|| do_bench(&uncached, &params))
+    });
+}
+
+criterion_group!(benches, bench_group);
+criterion_main!(benches);
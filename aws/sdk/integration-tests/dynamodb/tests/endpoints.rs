@@ -40,6 +40,18 @@ async fn endpoints_can_be_overridden_globally() {
     expect_uri(conf, "http://localhost:8000", |b| b).await;
 }
 
+#[tokio::test]
+async fn endpoints_can_be_overridden_per_service() {
+    let conf = aws_types::SdkConfig::builder()
+        .region(Region::new("us-east-4"))
+        .endpoint_url("http://localhost:1234")
+        .endpoint_url_for("s3", "http://localhost:9000")
+        .endpoint_url_for("DynamoDB", "http://localhost:8000")
+        .build();
+
+    expect_uri(conf, "http://localhost:8000", |b| b).await;
+}
+
 #[tokio::test]
 async fn endpoints_can_be_overridden_locally() {
     let shared_config = aws_types::SdkConfig::builder()
@@ -52,6 +64,16 @@ async fn endpoints_can_be_overridden_locally() {
     .await;
 }
 
+#[tokio::test]
+async fn endpoint_url_placeholders_are_expanded() {
+    let conf = aws_types::SdkConfig::builder()
+        .region(Region::new("us-east-4"))
+        .endpoint_url("https://{service}.{region}.mycorp.internal")
+        .build();
+
+    expect_uri(conf, "https://dynamodb.us-east-4.mycorp.internal", |b| b).await;
+}
+
 #[tokio::test]
 async fn dual_stack_endpoints() {
     let shared_config = SdkConfig::builder()
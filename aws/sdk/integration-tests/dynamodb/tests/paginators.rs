@@ -5,14 +5,22 @@
 
 use std::collections::HashMap;
 use std::iter::FromIterator;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
 
 use aws_credential_types::Credentials;
+use aws_sdk_dynamodb::operation::RequestId;
 use aws_sdk_dynamodb::types::AttributeValue;
 use aws_sdk_dynamodb::{Client, Config};
+use aws_smithy_async::future::pagination_stream::error_policy::ItemErrorPolicy;
+use aws_smithy_async::future::pagination_stream::rate_limit::PageRateLimiter;
+use aws_smithy_async::test_util::instant_time_and_sleep;
 use aws_smithy_http_client::test_util::{capture_request, ReplayEvent, StaticReplayClient};
 use aws_smithy_protocol_test::{assert_ok, validate_body, MediaType};
 use aws_smithy_runtime_api::client::http::HttpClient;
 use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::retry::RetryConfig;
 use aws_types::region::Region;
 
 fn stub_config(http_client: impl HttpClient + 'static) -> Config {
@@ -135,6 +143,188 @@ async fn paginators_loop_until_completion() {
     http_client.assert_requests_match(&[]);
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn paginators_prefetch_pages() {
+    let http_client = StaticReplayClient::new(vec![
+        ReplayEvent::new(
+            mk_request(r#"{"TableName":"test-table"}"#),
+            mk_response(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "joe@example.com" } }],
+                            "LastEvaluatedKey": { "PostedBy": { "S": "joe@example.com" } }
+                        }"#,
+            ),
+        ),
+        ReplayEvent::new(
+            mk_request(
+                r#"{"TableName":"test-table","ExclusiveStartKey":{"PostedBy":{"S":"joe@example.com"}}}"#,
+            ),
+            mk_response(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "jack@example.com" } }]
+                        }"#,
+            ),
+        ),
+    ]);
+    let client = Client::from_conf(stub_config(http_client.clone()));
+    let mut paginator = client
+        .scan()
+        .table_name("test-table")
+        .into_paginator()
+        .prefetch(1)
+        .send();
+    assert_eq!(http_client.actual_requests().count(), 0);
+    let first_page = paginator
+        .try_next()
+        .await
+        .expect("success")
+        .expect("page exists");
+    assert_eq!(
+        &AttributeValue::S("joe@example.com".to_string()),
+        &first_page.items()[0]["PostedBy"]
+    );
+    // the second page was requested before the first page was handed out
+    assert_eq!(http_client.actual_requests().count(), 2);
+    let second_page = paginator
+        .try_next()
+        .await
+        .expect("success")
+        .expect("page exists");
+    assert_eq!(
+        &AttributeValue::S("jack@example.com".to_string()),
+        &second_page.items()[0]["PostedBy"]
+    );
+    assert!(
+        paginator.next().await.is_none(),
+        "no more pages should exist"
+    );
+    assert_eq!(http_client.actual_requests().count(), 2);
+    http_client.assert_requests_match(&[]);
+}
+
+#[tokio::test]
+async fn paginators_yield_page_metadata() {
+    let mk_response_with_request_id = |body: &'static str, request_id: &'static str| {
+        http_1x::Response::builder()
+            .header("x-amzn-requestid", request_id)
+            .body(SdkBody::from(body))
+            .unwrap()
+    };
+    let http_client = StaticReplayClient::new(vec![
+        ReplayEvent::new(
+            mk_request(r#"{"TableName":"test-table"}"#),
+            mk_response_with_request_id(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "joe@example.com" } }],
+                            "LastEvaluatedKey": { "PostedBy": { "S": "joe@example.com" } }
+                        }"#,
+                "request-1",
+            ),
+        ),
+        ReplayEvent::new(
+            mk_request(
+                r#"{"TableName":"test-table","ExclusiveStartKey":{"PostedBy":{"S":"joe@example.com"}}}"#,
+            ),
+            mk_response_with_request_id(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "jack@example.com" } }]
+                        }"#,
+                "request-2",
+            ),
+        ),
+    ]);
+    let client = Client::from_conf(stub_config(http_client.clone()));
+    let pages = client
+        .scan()
+        .table_name("test-table")
+        .into_paginator()
+        .send_with_metadata()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .expect("success");
+    assert_eq!(2, pages.len());
+    for (index, (page, metadata)) in pages.iter().enumerate() {
+        assert_eq!(index + 1, metadata.page_number());
+        assert_eq!(Some(200), metadata.status().map(u16::from));
+        assert_eq!(page.request_id(), metadata.request_id());
+    }
+    assert_eq!(Some("request-1"), pages[0].1.request_id());
+    assert_eq!(Some("request-2"), pages[1].1.request_id());
+    assert_eq!(
+        &AttributeValue::S("jack@example.com".to_string()),
+        &pages[1].0.items()[0]["PostedBy"]
+    );
+    http_client.assert_requests_match(&[]);
+}
+
+#[tokio::test]
+async fn paginators_share_rate_limiter() {
+    let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+    let http_client = StaticReplayClient::new(vec![
+        ReplayEvent::new(
+            mk_request(r#"{"TableName":"test-table"}"#),
+            mk_response(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "joe@example.com" } }],
+                            "LastEvaluatedKey": { "PostedBy": { "S": "joe@example.com" } }
+                        }"#,
+            ),
+        ),
+        ReplayEvent::new(
+            mk_request(
+                r#"{"TableName":"test-table","ExclusiveStartKey":{"PostedBy":{"S":"joe@example.com"}}}"#,
+            ),
+            mk_response(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "jack@example.com" } }]
+                        }"#,
+            ),
+        ),
+        ReplayEvent::new(
+            mk_request(r#"{"TableName":"other-table"}"#),
+            mk_response(r#"{"Count": 0, "Items": []}"#),
+        ),
+    ]);
+    let config = stub_config(http_client.clone())
+        .to_builder()
+        .time_source(time_source)
+        .sleep_impl(sleep_impl.clone())
+        .build();
+    let client = Client::from_conf(config);
+    let limiter = PageRateLimiter::new(2.0);
+    let pages = client
+        .scan()
+        .table_name("test-table")
+        .into_paginator()
+        .rate_limiter(limiter.clone())
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .expect("success");
+    assert_eq!(2, pages.len());
+    assert_eq!(vec![Duration::from_millis(500)], sleep_impl.logs());
+
+    // the second paginator waits for the budget that the first one used up
+    let pages = client
+        .scan()
+        .table_name("other-table")
+        .into_paginator()
+        .rate_limiter(limiter)
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .expect("success");
+    assert_eq!(1, pages.len());
+    assert_eq!(vec![Duration::from_millis(500); 2], sleep_impl.logs());
+    http_client.assert_requests_match(&[]);
+}
+
 #[tokio::test]
 async fn paginators_handle_errors() {
     // LastEvaluatedKey is set but there is only one response in the test connection
@@ -174,6 +364,136 @@ async fn paginators_handle_errors() {
     assert_eq!(rows.try_next().await.expect("ok"), None);
 }
 
+#[tokio::test]
+async fn items_stop_with_callback_on_failed_page() {
+    // LastEvaluatedKey is set but there is only one response in the test connection
+    let http_client = StaticReplayClient::new(vec![ReplayEvent::new(
+        mk_request(r#"{"TableName":"test-table"}"#),
+        mk_response(
+            r#"{
+                   "Count": 1,
+                   "Items": [{ "PostedBy": { "S": "joe@example.com" } }],
+                   "LastEvaluatedKey": { "PostedBy": { "S": "joe@example.com" } }
+               }"#,
+        ),
+    )]);
+    let client = Client::from_conf(stub_config(http_client.clone()));
+    let stopped = Arc::new(AtomicUsize::new(0));
+    let rows = client
+        .scan()
+        .table_name("test-table")
+        .into_paginator()
+        .items()
+        .error_policy(ItemErrorPolicy::stop_with_callback({
+            let stopped = stopped.clone();
+            move |_err| {
+                stopped.fetch_add(1, Ordering::SeqCst);
+            }
+        }))
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .expect("the error is passed to the callback");
+    assert_eq!(1, rows.len());
+    assert_eq!(1, stopped.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn items_retry_failed_page() {
+    let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+    let second_page_request = || {
+        mk_request(
+            r#"{"TableName":"test-table","ExclusiveStartKey":{"PostedBy":{"S":"joe@example.com"}}}"#,
+        )
+    };
+    let http_client = StaticReplayClient::new(vec![
+        ReplayEvent::new(
+            mk_request(r#"{"TableName":"test-table"}"#),
+            mk_response(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "joe@example.com" } }],
+                            "LastEvaluatedKey": { "PostedBy": { "S": "joe@example.com" } }
+                        }"#,
+            ),
+        ),
+        ReplayEvent::new(
+            second_page_request(),
+            http_1x::Response::builder()
+                .status(500)
+                .body(SdkBody::from(
+                    r#"{"__type":"com.amazonaws.dynamodb.v20120810#InternalServerError","message":"Internal server error"}"#,
+                ))
+                .unwrap(),
+        ),
+        ReplayEvent::new(
+            second_page_request(),
+            mk_response(
+                r#"{
+                            "Count": 1,
+                            "Items": [{ "PostedBy": { "S": "jack@example.com" } }]
+                        }"#,
+            ),
+        ),
+    ]);
+    let config = stub_config(http_client.clone())
+        .to_builder()
+        .retry_config(RetryConfig::disabled())
+        .time_source(time_source)
+        .sleep_impl(sleep_impl.clone())
+        .build();
+    let client = Client::from_conf(config);
+    let rows = client
+        .scan()
+        .table_name("test-table")
+        .into_paginator()
+        .items()
+        .error_policy(ItemErrorPolicy::retry_page(1, Duration::from_secs(5)))
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .expect("the failed page is retried");
+    assert_eq!(2, rows.len());
+    assert_eq!(
+        Some(&AttributeValue::S("jack@example.com".to_string())),
+        rows[1].get("PostedBy")
+    );
+    assert_eq!(vec![Duration::from_secs(5)], sleep_impl.logs());
+    http_client.assert_requests_match(&[]);
+}
+
+#[tokio::test]
+async fn items_do_not_retry_non_retryable_errors() {
+    let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+    let http_client = StaticReplayClient::new(vec![ReplayEvent::new(
+        mk_request(r#"{"TableName":"test-table"}"#),
+        http_1x::Response::builder()
+            .status(400)
+            .body(SdkBody::from(
+                r#"{"__type":"com.amazonaws.dynamodb.v20120810#ResourceNotFoundException","message":"Requested resource not found"}"#,
+            ))
+            .unwrap(),
+    )]);
+    let config = stub_config(http_client.clone())
+        .to_builder()
+        .time_source(time_source)
+        .sleep_impl(sleep_impl.clone())
+        .build();
+    let client = Client::from_conf(config);
+    client
+        .scan()
+        .table_name("test-table")
+        .into_paginator()
+        .items()
+        .error_policy(ItemErrorPolicy::retry_page(1, Duration::from_secs(5)))
+        .send()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .expect_err("the error isn't retryable");
+    assert!(sleep_impl.logs().is_empty());
+    http_client.assert_requests_match(&[]);
+}
+
 #[tokio::test]
 async fn paginators_stop_on_duplicate_token_by_default() {
     let response = r#"{
@@ -11,7 +11,9 @@ use aws_smithy_async::test_util::tick_advance_sleep::{
 use aws_smithy_http_client::test_util::dvr::ReplayingClient;
 use aws_smithy_runtime::test_util::capture_test_logs::show_test_logs;
 use aws_smithy_runtime_api::client::waiters::error::WaiterError;
+use aws_smithy_runtime_api::client::waiters::{AcceptorState, WaitOptions};
 use aws_smithy_types::retry::RetryConfig;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 async fn prerequisites() -> (Client, ReplayingClient, TickAdvanceTime) {
@@ -56,6 +58,43 @@ async fn waiters_success() {
     http_client.full_validate("application/xml").await.unwrap();
 }
 
+#[tokio::test]
+async fn waiters_report_progress() {
+    let _logs = show_test_logs();
+
+    let (ec2, _, time_source) = prerequisites().await;
+
+    ec2.start_instances()
+        .instance_ids("i-09fb4224219ac6902")
+        .send()
+        .await
+        .unwrap();
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let options = WaitOptions::new(Duration::from_secs(300)).on_progress({
+        let progress = progress.clone();
+        move |p| progress.lock().unwrap().push(p.clone())
+    });
+    let waiter_task = tokio::spawn(
+        ec2.wait_until_instance_status_ok()
+            .instance_ids("i-09fb4224219ac6902")
+            .wait_with_options(options),
+    );
+
+    time_source.tick(Duration::from_secs(305)).await;
+    waiter_task.await.unwrap().unwrap();
+
+    let progress = progress.lock().unwrap();
+    assert!(progress.len() > 1, "the waiter polls a few times");
+    for (index, p) in progress.iter().enumerate() {
+        assert_eq!(index as u32 + 1, p.attempt());
+    }
+    let (last, retries) = progress.split_last().unwrap();
+    assert!(retries.iter().all(|p| p.next_delay().is_some()));
+    assert_eq!(AcceptorState::Success, last.last_state());
+    assert_eq!(None, last.next_delay());
+}
+
 #[tokio::test]
 async fn waiters_exceed_max_wait_time() {
     let _logs = show_test_logs();
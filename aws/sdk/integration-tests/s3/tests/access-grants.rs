@@ -0,0 +1,126 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::access_grants::{data_access_fn, AccessGrantsConfig, DataAccess, Permission};
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::SdkBody;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::infallible_client_fn;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+
+type Requests = Arc<Mutex<Vec<(String, Permission)>>>;
+
+/// Creates a client whose access grants provider vends credentials for `s3://bucket/granted/*`
+///
+/// Returns the client, the access key IDs that requests were signed with, and the requests to the provider.
+fn test_client(fallback: bool) -> (Client, Arc<Mutex<Vec<String>>>, Requests) {
+    let signed_with = Arc::new(Mutex::new(Vec::new()));
+    let http_client = infallible_client_fn({
+        let signed_with = signed_with.clone();
+        move |req| {
+            let authorization = req.headers().get("authorization").unwrap_or_default();
+            let access_key_id = authorization
+                .split("Credential=")
+                .nth(1)
+                .and_then(|credential| credential.split('/').next())
+                .unwrap_or_default();
+            signed_with.lock().unwrap().push(access_key_id.to_owned());
+            http_1x::Response::new(SdkBody::from(
+                "<ListAllMyBucketsResult></ListAllMyBucketsResult>",
+            ))
+        }
+    });
+
+    let data_access_requests = Requests::default();
+    let provider = data_access_fn({
+        let data_access_requests = data_access_requests.clone();
+        move |request| {
+            data_access_requests
+                .lock()
+                .unwrap()
+                .push((request.target().to_owned(), request.permission()));
+            let granted = request.target().starts_with("s3://bucket/granted/");
+            async move {
+                if !granted {
+                    return Err("access denied".into());
+                }
+                Ok(DataAccess::new(
+                    Credentials::new(
+                        "GRANTED",
+                        "secret",
+                        Some("token".into()),
+                        // The test time source is fixed at 1234567890 seconds after the epoch
+                        Some(UNIX_EPOCH + Duration::from_secs(1234567890 + 3600)),
+                        "test",
+                    ),
+                    "s3://bucket/granted/*",
+                ))
+            }
+        }
+    });
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-east-1"))
+        .http_client(http_client)
+        .with_test_defaults()
+        .access_grants(AccessGrantsConfig::new(provider).fallback_to_default_credentials(fallback))
+        .build();
+    (Client::from_conf(config), signed_with, data_access_requests)
+}
+
+#[tokio::test]
+async fn object_requests_are_signed_with_cached_grant_credentials() {
+    let (client, signed_with, data_access_requests) = test_client(false);
+    for key in ["granted/a", "granted/b"] {
+        client
+            .get_object()
+            .bucket("bucket")
+            .key(key)
+            .send()
+            .await
+            .unwrap();
+    }
+    client.list_buckets().send().await.unwrap();
+
+    assert_eq!(
+        vec!["GRANTED", "GRANTED", "ANOTREAL"],
+        *signed_with.lock().unwrap()
+    );
+    assert_eq!(
+        vec![("s3://bucket/granted/a".to_owned(), Permission::Read)],
+        *data_access_requests.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn requests_without_a_grant_fail() {
+    let (client, signed_with, _) = test_client(false);
+    client
+        .put_object()
+        .bucket("bucket")
+        .key("other")
+        .send()
+        .await
+        .expect_err("no grant covers the object");
+    assert!(signed_with.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn requests_without_a_grant_can_fall_back_to_the_client_credentials() {
+    let (client, signed_with, data_access_requests) = test_client(true);
+    client
+        .put_object()
+        .bucket("bucket")
+        .key("other")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(vec!["ANOTREAL"], *signed_with.lock().unwrap());
+    assert_eq!(
+        vec![("s3://bucket/other".to_owned(), Permission::Write)],
+        *data_access_requests.lock().unwrap()
+    );
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::batch_delete::BatchDelete;
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::SdkBody;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::infallible_client_fn;
+use std::sync::{Arc, Mutex};
+
+/// Creates a client that records the number of keys of each `DeleteObjects` request
+///
+/// Deleting `locked` fails with `AccessDenied`, deleting `reported-twice` fails with two errors, and
+/// batches containing `forbidden` fail entirely.
+fn test_client() -> (Client, Arc<Mutex<Vec<usize>>>) {
+    let batch_sizes = Arc::new(Mutex::new(Vec::new()));
+    let http_client = infallible_client_fn({
+        let batch_sizes = batch_sizes.clone();
+        move |req| {
+            assert_eq!(Some("delete"), req.uri().query());
+            let body = String::from_utf8(req.body().bytes().unwrap().to_vec()).unwrap();
+            assert!(body.contains("<Quiet>true</Quiet>"));
+            batch_sizes
+                .lock()
+                .unwrap()
+                .push(body.matches("<Key>").count());
+            if body.contains("<Key>forbidden</Key>") {
+                return http_1x::Response::builder()
+                    .status(403)
+                    .body(SdkBody::from("<Error><Code>AccessDenied</Code></Error>"))
+                    .unwrap();
+            }
+            let errors = if body.contains("<Key>locked</Key>") {
+                "<Error><Key>locked</Key><Code>AccessDenied</Code><Message>Access Denied</Message></Error>"
+            } else if body.contains("<Key>reported-twice</Key>") {
+                "<Error><Key>reported-twice</Key><Code>InternalError</Code></Error>\
+                 <Error><Key>reported-twice</Key><Code>InternalError</Code></Error>"
+            } else {
+                ""
+            };
+            http_1x::Response::new(SdkBody::from(format!(
+                "<DeleteResult>{errors}</DeleteResult>"
+            )))
+        }
+    });
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-west-2"))
+        .http_client(http_client)
+        .with_test_defaults()
+        .build();
+    (Client::from_conf(config), batch_sizes)
+}
+
+#[tokio::test]
+async fn keys_are_deleted_in_batches_of_1000() {
+    let (client, batch_sizes) = test_client();
+    let keys = (0..2500).map(|i| format!("key-{i}"));
+    let output = BatchDelete::builder(client, "test-bucket", keys)
+        .concurrency(2)
+        .build()
+        .send()
+        .await;
+
+    assert!(output.is_complete());
+    assert_eq!(2500, output.deleted_count());
+    let mut batch_sizes = batch_sizes.lock().unwrap().clone();
+    batch_sizes.sort();
+    assert_eq!(vec![500, 1000, 1000], batch_sizes);
+}
+
+#[tokio::test]
+async fn failures_of_all_batches_are_collected() {
+    let (client, _) = test_client();
+    let keys = (0..1000)
+        .map(|i| format!("key-{i}"))
+        .chain(["locked".to_owned()])
+        .chain((0..999).map(|i| format!("other-key-{i}")))
+        .chain(["forbidden".to_owned(), "key-after-forbidden".to_owned()]);
+    let output = BatchDelete::builder(client, "test-bucket", keys)
+        .build()
+        .send()
+        .await;
+
+    assert!(!output.is_complete());
+    // The second batch holds `locked` and 999 other keys.
+    assert_eq!(1999, output.deleted_count());
+    assert_eq!(1, output.errors().len());
+    assert_eq!(Some("locked"), output.errors()[0].key());
+    assert_eq!(Some("AccessDenied"), output.errors()[0].code());
+
+    assert_eq!(1, output.failed_batches().len());
+    let failed = &output.failed_batches()[0];
+    assert_eq!(&["forbidden", "key-after-forbidden"], failed.keys());
+    assert_eq!(
+        Some("AccessDenied"),
+        failed
+            .error()
+            .as_service_error()
+            .and_then(|err| err.meta().code())
+    );
+}
+
+#[tokio::test]
+async fn more_errors_than_keys_dont_underflow() {
+    let (client, batch_sizes) = test_client();
+    let output = BatchDelete::builder(client, "test-bucket", ["reported-twice"])
+        .concurrency(0)
+        .build()
+        .send()
+        .await;
+
+    assert_eq!(vec![1], *batch_sizes.lock().unwrap());
+    assert_eq!(0, output.deleted_count());
+    assert_eq!(2, output.errors().len());
+}
+
+#[tokio::test]
+async fn no_requests_are_sent_without_keys() {
+    let (client, batch_sizes) = test_client();
+    let output = BatchDelete::builder(client, "test-bucket", Vec::<String>::new())
+        .build()
+        .send()
+        .await;
+
+    assert!(output.is_complete());
+    assert_eq!(0, output.deleted_count());
+    assert!(batch_sizes.lock().unwrap().is_empty());
+}
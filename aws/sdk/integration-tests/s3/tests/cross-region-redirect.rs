@@ -0,0 +1,116 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_credential_types::Credentials;
+use aws_sdk_s3::config::retry::RetryConfig;
+use aws_sdk_s3::config::{BehaviorVersion, Region};
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::infallible_client_fn;
+use aws_smithy_types::body::SdkBody;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct SentRequest {
+    uri: String,
+    authorization: String,
+}
+
+/// Creates a client for `us-east-1` that talks to a bucket in `eu-west-1`
+///
+/// Retries are disabled, since redirects don't depend on them.
+fn test_client(cross_region_redirects: bool) -> (Client, Arc<Mutex<Vec<SentRequest>>>) {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let http_client = infallible_client_fn({
+        let requests = requests.clone();
+        move |req| {
+            let uri = req.uri().to_string();
+            requests.lock().unwrap().push(SentRequest {
+                uri: uri.clone(),
+                authorization: req.headers()["authorization"].to_str().unwrap().to_owned(),
+            });
+            if uri.contains(".s3.eu-west-1.amazonaws.com") {
+                http_1x::Response::new(SdkBody::empty())
+            } else {
+                http_1x::Response::builder()
+                    .status(301)
+                    .header("x-amz-bucket-region", "eu-west-1")
+                    .body(SdkBody::empty())
+                    .unwrap()
+            }
+        }
+    });
+    let config = Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-east-1"))
+        .retry_config(RetryConfig::disabled())
+        .http_client(http_client)
+        .cross_region_redirects(cross_region_redirects)
+        .build();
+    (Client::from_conf(config), requests)
+}
+
+#[tokio::test]
+async fn redirect_to_bucket_region() {
+    let (client, requests) = test_client(true);
+    client
+        .head_object()
+        .bucket("test-bucket")
+        .key("test-key")
+        .send()
+        .await
+        .expect("request is redirected to eu-west-1");
+
+    {
+        let requests = requests.lock().unwrap();
+        assert_eq!(2, requests.len(), "{requests:?}");
+        assert_eq!(
+            "https://test-bucket.s3.us-east-1.amazonaws.com/test-key",
+            requests[0].uri
+        );
+        assert_eq!(
+            "https://test-bucket.s3.eu-west-1.amazonaws.com/test-key",
+            requests[1].uri
+        );
+        // The redirected request is signed for the region of the bucket
+        assert!(
+            requests[1]
+                .authorization
+                .contains("/eu-west-1/s3/aws4_request"),
+            "{}",
+            requests[1].authorization
+        );
+    }
+
+    // The region of the bucket is cached
+    client
+        .head_object()
+        .bucket("test-bucket")
+        .key("test-key")
+        .send()
+        .await
+        .expect("request is sent to eu-west-1");
+    let requests = requests.lock().unwrap();
+    assert_eq!(3, requests.len(), "{requests:?}");
+    assert_eq!(
+        "https://test-bucket.s3.eu-west-1.amazonaws.com/test-key",
+        requests[2].uri
+    );
+}
+
+#[tokio::test]
+async fn redirects_are_not_followed_when_disabled() {
+    let (client, requests) = test_client(false);
+    client
+        .head_object()
+        .bucket("test-bucket")
+        .key("test-key")
+        .send()
+        .await
+        .expect_err("request is not redirected");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(1, requests.len(), "{requests:?}");
+}
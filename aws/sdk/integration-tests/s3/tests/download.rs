@@ -0,0 +1,110 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::download::{Download, ValidationStatus};
+use aws_sdk_s3::primitives::SdkBody;
+use aws_sdk_s3::types::ChecksumAlgorithm;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::infallible_client_fn;
+
+// CRC32 checksums of "hello", "world", their composite checksum, and the checksum of "helloworld"
+const HELLO_CRC32: &str = "NhCmhg==";
+const WORLD_CRC32: &str = "OncRQw==";
+const COMPOSITE_CRC32: &str = "wpn7tg==-2";
+const FULL_OBJECT_CRC32: &str = "+esgrQ==";
+
+/// Creates a client for an object with the given entity tag and object checksum
+///
+/// Objects whose entity tag has a `-2` suffix consist of the parts "hello" and "world".
+fn test_client(e_tag: &'static str, object_checksum: &'static str) -> Client {
+    let http_client = infallible_client_fn(move |req| {
+        let part_number = req
+            .uri()
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|param| param.strip_prefix("partNumber="))
+            .map(str::to_owned);
+        let response = http_1x::Response::builder().header("etag", e_tag);
+        if req.method() == "HEAD" {
+            return response
+                .header("content-length", "10")
+                .header("x-amz-checksum-crc32", object_checksum)
+                .body(SdkBody::empty())
+                .unwrap();
+        }
+        let (body, checksum) = match part_number.as_deref() {
+            Some("1") => ("hello", HELLO_CRC32),
+            Some("2") => ("world", WORLD_CRC32),
+            _ => ("helloworld", object_checksum),
+        };
+        response
+            .header("x-amz-checksum-crc32", checksum)
+            .body(SdkBody::from(body))
+            .unwrap()
+    });
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-east-1"))
+        .http_client(http_client)
+        .with_test_defaults()
+        .build();
+    Client::from_conf(config)
+}
+
+#[tokio::test]
+async fn composite_checksums_are_validated_per_part() {
+    let client = test_client("\"3e25960a79dbc69b674cd4ec67a72c62-2\"", COMPOSITE_CRC32);
+    let output = Download::builder(client, "bucket", "key")
+        .build()
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(&b"helloworld"[..], &output.body()[..]);
+
+    let report = output.checksum_validation();
+    assert_eq!(Some(&ChecksumAlgorithm::Crc32), report.algorithm());
+    assert_eq!(ValidationStatus::Validated, report.full_object());
+    let parts = report
+        .parts()
+        .iter()
+        .map(|part| (part.part_number(), part.size(), part.status()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (1, 5, ValidationStatus::Validated),
+            (2, 5, ValidationStatus::Validated)
+        ],
+        parts
+    );
+}
+
+#[tokio::test]
+async fn composite_checksum_mismatch_fails_the_download() {
+    let client = test_client("\"3e25960a79dbc69b674cd4ec67a72c62-2\"", "AAAAAA==-2");
+    let err = Download::builder(client, "bucket", "key")
+        .build()
+        .send()
+        .await
+        .expect_err("the composite checksum doesn't match");
+    assert!(err.is_checksum_mismatch(), "{err:?}");
+    assert_eq!("the checksum of the object doesn't match", err.to_string());
+}
+
+#[tokio::test]
+async fn full_object_checksums_are_validated() {
+    let client = test_client("\"3e25960a79dbc69b674cd4ec67a72c62\"", FULL_OBJECT_CRC32);
+    let output = Download::builder(client, "bucket", "key")
+        .build()
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(&b"helloworld"[..], &output.body()[..]);
+
+    let report = output.checksum_validation();
+    assert_eq!(ValidationStatus::Validated, report.full_object());
+    assert!(report.parts().is_empty());
+}
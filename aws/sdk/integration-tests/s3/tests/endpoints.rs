@@ -6,7 +6,7 @@
 #![cfg(feature = "test-util")]
 
 use aws_credential_types::provider::SharedCredentialsProvider;
-use aws_sdk_s3::config::Builder;
+use aws_sdk_s3::config::{AddressingStyle, AddressingStyleOverrides, Builder};
 use aws_sdk_s3::config::{Credentials, Region};
 use aws_sdk_s3::{Client, Config};
 use aws_smithy_http_client::test_util::{capture_request, CaptureRequestReceiver};
@@ -164,3 +164,167 @@ async fn write_get_object_response() {
         "https://req-route.s3-object-lambda.us-west-4.amazonaws.com/WriteGetObjectResponse"
     );
 }
+
+const VPC_ENDPOINT: &str = "*.vpce-1a2b3c4d-5e6f.s3.us-west-4.vpce.amazonaws.com";
+
+#[tokio::test]
+async fn vpc_endpoint_virtual_hosted_buckets() {
+    let (captured_request, client) = test_client(|b| b.vpc_endpoint(VPC_ENDPOINT));
+    let _ = client.list_objects_v2().bucket("test-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "https://test-bucket.vpce-1a2b3c4d-5e6f.s3.us-west-4.vpce.amazonaws.com/?list-type=2"
+    );
+}
+
+#[tokio::test]
+async fn vpc_endpoint_force_path_style() {
+    let (captured_request, client) =
+        test_client(|b| b.vpc_endpoint(VPC_ENDPOINT).force_path_style(true));
+    let _ = client.list_objects_v2().bucket("test-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "https://vpce-1a2b3c4d-5e6f.s3.us-west-4.vpce.amazonaws.com/test-bucket/?list-type=2"
+    );
+}
+
+#[tokio::test]
+async fn vpc_endpoint_presigning() {
+    use aws_sdk_s3::presigning::PresigningConfig;
+    use std::time::Duration;
+
+    let (_, client) = test_client(|b| b.vpc_endpoint(VPC_ENDPOINT));
+    let presigned = client
+        .get_object()
+        .bucket("test-bucket")
+        .key("test-key")
+        .presigned(PresigningConfig::expires_in(Duration::from_secs(30)).unwrap())
+        .await
+        .expect("success");
+    assert!(
+        presigned.uri().starts_with(
+            "https://test-bucket.vpce-1a2b3c4d-5e6f.s3.us-west-4.vpce.amazonaws.com/test-key?"
+        ),
+        "{}",
+        presigned.uri()
+    );
+}
+
+#[tokio::test]
+async fn endpoint_url_passthrough() {
+    let (captured_request, client) = test_client(|b| {
+        b.endpoint_url("http://localhost:9000/storage")
+            .endpoint_url_passthrough(true)
+    });
+    let _ = client.list_objects_v2().bucket("test-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "http://localhost:9000/storage/test-bucket/?list-type=2"
+    );
+}
+
+#[tokio::test]
+async fn endpoint_url_passthrough_skips_host_prefix() {
+    let (captured_request, client) = test_client(|b| {
+        b.endpoint_url("https://objects.mycorp.internal")
+            .endpoint_url_passthrough(true)
+    });
+    let _ = client
+        .write_get_object_response()
+        .request_route("req-route")
+        .request_token("token")
+        .status_code(200)
+        .body(vec![1, 2, 3].into())
+        .send()
+        .await;
+    let captured_request = captured_request.expect_request();
+    assert!(
+        captured_request
+            .uri()
+            .starts_with("https://objects.mycorp.internal/WriteGetObjectResponse"),
+        "{}",
+        captured_request.uri()
+    );
+}
+
+#[tokio::test]
+async fn endpoint_url_passthrough_disabled() {
+    let (captured_request, client) = test_client(|b| {
+        b.endpoint_url("https://objects.mycorp.internal")
+            .endpoint_url_passthrough(true)
+            .endpoint_url_passthrough(false)
+    });
+    let _ = client.list_objects_v2().bucket("test-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "https://test-bucket.objects.mycorp.internal/?list-type=2"
+    );
+}
+
+#[tokio::test]
+async fn endpoint_url_passthrough_requires_endpoint_url() {
+    let (_, client) = test_client(|b| b.endpoint_url_passthrough(true));
+    let err = client
+        .list_objects_v2()
+        .bucket("test-bucket")
+        .send()
+        .await
+        .expect_err("no endpoint URL is configured");
+    assert!(
+        format!(
+            "{}",
+            aws_smithy_types::error::display::DisplayErrorContext(&err)
+        )
+        .contains("no endpoint URL is configured"),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn addressing_style_overrides() {
+    fn overrides(b: Builder) -> Builder {
+        b.addressing_style_overrides(
+            AddressingStyleOverrides::new().with_override("local-*", AddressingStyle::Path),
+        )
+    }
+    let (captured_request, client) = test_client(overrides);
+    let _ = client.list_objects_v2().bucket("local-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "https://s3.us-west-4.amazonaws.com/local-bucket/?list-type=2"
+    );
+
+    let (captured_request, client) = test_client(overrides);
+    let _ = client.list_objects_v2().bucket("test-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "https://test-bucket.s3.us-west-4.amazonaws.com/?list-type=2"
+    );
+}
+
+#[tokio::test]
+async fn addressing_style_overrides_take_precedence_over_force_path_style() {
+    let (captured_request, client) = test_client(|b| {
+        b.force_path_style(true).addressing_style_overrides(
+            AddressingStyleOverrides::new().with_override("aws-*", AddressingStyle::VirtualHosted),
+        )
+    });
+    let _ = client.list_objects_v2().bucket("aws-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "https://aws-bucket.s3.us-west-4.amazonaws.com/?list-type=2"
+    );
+}
+
+#[tokio::test]
+async fn addressing_style_overrides_use_path_style_for_dns_incompatible_buckets() {
+    let (captured_request, client) = test_client(|b| {
+        b.endpoint_url("http://localhost:9000")
+            .addressing_style_overrides(AddressingStyleOverrides::new())
+    });
+    let _ = client.list_objects_v2().bucket("test.bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "http://localhost:9000/test.bucket/?list-type=2"
+    );
+}
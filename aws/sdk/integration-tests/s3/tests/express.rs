@@ -3,6 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use aws_config::timeout::TimeoutConfig;
@@ -11,13 +13,18 @@ use aws_runtime::user_agent::test_util::{
     assert_ua_contains_metric_values, assert_ua_does_not_contain_metric_values,
 };
 use aws_sdk_s3::config::endpoint::{EndpointFuture, Params, ResolveEndpoint};
-use aws_sdk_s3::config::{Builder, Credentials};
+use aws_sdk_s3::config::{
+    Builder, Credentials, S3ExpressSessionCacheConfig, S3ExpressSessionEvictionPolicy,
+};
+use aws_sdk_s3::express_pagination::ListObjectsV2SessionTolerantPaginator;
 use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::SdkBody;
 use aws_sdk_s3::types::ChecksumAlgorithm;
 use aws_sdk_s3::{Client, Config};
 use aws_smithy_http_client::test_util::dvr::ReplayingClient;
-use aws_smithy_http_client::test_util::{capture_request, ReplayEvent, StaticReplayClient};
+use aws_smithy_http_client::test_util::{
+    capture_request, infallible_client_fn, ReplayEvent, StaticReplayClient,
+};
 use aws_smithy_runtime::test_util::capture_test_logs::capture_test_logs;
 use aws_smithy_types::endpoint::Endpoint;
 use http_1x::Uri;
@@ -499,3 +506,156 @@ async fn s3_express_request_contains_metric_j() {
 
     assert_ua_contains_metric_values(user_agent, &["J"]);
 }
+
+#[tokio::test]
+async fn session_cache_config_limits_cached_sessions() {
+    let create_session_count = Arc::new(AtomicUsize::new(0));
+    let http_client = infallible_client_fn({
+        let create_session_count = create_session_count.clone();
+        move |req| {
+            if req.uri().query() == Some("session") {
+                create_session_count.fetch_add(1, Ordering::SeqCst);
+                create_session_response()
+            } else {
+                response_ok()
+            }
+        }
+    });
+    let client = test_client(|b| {
+        b.http_client(http_client.clone())
+            .s3_express_session_cache_config(
+                S3ExpressSessionCacheConfig::new()
+                    .with_max_entries(1)
+                    .with_eviction_policy(S3ExpressSessionEvictionPolicy::FirstInFirstOut),
+            )
+    })
+    .await;
+
+    for bucket in [
+        "s3express-test-bucket--usw2-az1--x-s3",
+        "s3express-test-bucket--usw2-az1--x-s3",
+        "s3express-test-bucket-2--usw2-az1--x-s3",
+        "s3express-test-bucket--usw2-az1--x-s3",
+    ] {
+        let _ = client.list_objects_v2().bucket(bucket).send().await;
+    }
+
+    // The cache only holds one session, so switching buckets creates a new session each time.
+    assert_eq!(3, create_session_count.load(Ordering::SeqCst));
+}
+
+fn list_objects_response(key: &str, next_continuation_token: Option<&str>) -> String {
+    let truncation = match next_continuation_token {
+        Some(token) => format!(
+            "<IsTruncated>true</IsTruncated><NextContinuationToken>{token}</NextContinuationToken>"
+        ),
+        None => "<IsTruncated>false</IsTruncated>".to_owned(),
+    };
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+            <Name>s3express-test-bucket--usw2-az1--x-s3</Name>
+            <Contents><Key>{key}</Key></Contents>
+            {truncation}
+        </ListBucketResult>
+        "#
+    )
+}
+
+#[tokio::test]
+async fn rotated_session_is_replaced_during_pagination() {
+    let create_session_count = Arc::new(AtomicUsize::new(0));
+    let rejected_session = Arc::new(AtomicUsize::new(0));
+    let http_client = infallible_client_fn({
+        let create_session_count = create_session_count.clone();
+        let rejected_session = rejected_session.clone();
+        move |req| {
+            let query = req.uri().query().unwrap_or_default().to_owned();
+            if query == "session" {
+                create_session_count.fetch_add(1, Ordering::SeqCst);
+                return create_session_response();
+            }
+            let body = if !query.contains("continuation-token=page-2") {
+                list_objects_response("a", Some("page-2"))
+            } else if rejected_session.fetch_add(1, Ordering::SeqCst) == 0 {
+                // S3 rotates the session while the listing is in progress.
+                return http_1x::Response::builder()
+                    .status(400)
+                    .body(SdkBody::from(
+                        "<Error><Code>ExpiredToken</Code><Message>The provided token has expired.</Message></Error>",
+                    ))
+                    .unwrap();
+            } else {
+                list_objects_response("b", None)
+            };
+            http_1x::Response::builder()
+                .status(200)
+                .body(SdkBody::from(body))
+                .unwrap()
+        }
+    });
+    let client = test_client(|b| b.http_client(http_client.clone())).await;
+
+    let pages = ListObjectsV2SessionTolerantPaginator::new(
+        client
+            .list_objects_v2()
+            .bucket("s3express-test-bucket--usw2-az1--x-s3"),
+    )
+    .send()
+    .collect::<Result<Vec<_>, _>>()
+    .await
+    .expect("the rejected page should be sent again with a new session");
+
+    let keys: Vec<_> = pages
+        .iter()
+        .flat_map(|page| page.contents())
+        .filter_map(|object| object.key())
+        .collect();
+    assert_eq!(vec!["a", "b"], keys);
+    // The rejected session is replaced by a new session instead of being reused.
+    assert_eq!(2, create_session_count.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn rotated_session_is_not_reused_by_next_request() {
+    let create_session_count = Arc::new(AtomicUsize::new(0));
+    let list_count = Arc::new(AtomicUsize::new(0));
+    let http_client = infallible_client_fn({
+        let create_session_count = create_session_count.clone();
+        let list_count = list_count.clone();
+        move |req| {
+            if req.uri().query() == Some("session") {
+                create_session_count.fetch_add(1, Ordering::SeqCst);
+                return create_session_response();
+            }
+            if list_count.fetch_add(1, Ordering::SeqCst) == 0 {
+                http_1x::Response::builder()
+                    .status(400)
+                    .body(SdkBody::from(
+                        "<Error><Code>InvalidToken</Code><Message>The provided token is malformed or otherwise invalid.</Message></Error>",
+                    ))
+                    .unwrap()
+            } else {
+                response_ok()
+            }
+        }
+    });
+    let client = test_client(|b| b.http_client(http_client.clone())).await;
+
+    let bucket = "s3express-test-bucket--usw2-az1--x-s3";
+    let err = client
+        .list_objects_v2()
+        .bucket(bucket)
+        .send()
+        .await
+        .expect_err("the session was rejected");
+    assert_eq!(Some("InvalidToken"), err.into_service_error().meta().code());
+    client
+        .list_objects_v2()
+        .bucket(bucket)
+        .send()
+        .await
+        .expect("a new session should be created");
+
+    assert_eq!(2, create_session_count.load(Ordering::SeqCst));
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::object_lambda::ObjectLambdaResponse;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::{capture_request, CaptureRequestReceiver};
+use bytes::Bytes;
+
+fn test_client() -> (CaptureRequestReceiver, Client) {
+    let (http_client, captured_request) = capture_request(None);
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-west-2"))
+        .http_client(http_client)
+        .with_test_defaults()
+        .build();
+    (captured_request, Client::from_conf(config))
+}
+
+#[tokio::test]
+async fn get_object_through_object_lambda_access_point() {
+    let (captured_request, client) = test_client();
+    let _ = client
+        .head_object()
+        .bucket("arn:aws:s3-object-lambda:us-west-2:123412341234:accesspoint/myolap")
+        .key("s3.txt")
+        .send()
+        .await;
+    let req = captured_request.expect_request();
+    assert_eq!(
+        "https://myolap-123412341234.s3-object-lambda.us-west-2.amazonaws.com/s3.txt",
+        req.uri()
+    );
+    assert!(req.headers().get("authorization").unwrap().starts_with(
+        "AWS4-HMAC-SHA256 Credential=ANOTREAL/20090213/us-west-2/s3-object-lambda/aws4_request"
+    ));
+}
+
+#[tokio::test]
+async fn transformed_objects_are_streamed() {
+    let (captured_request, client) = test_client();
+    let chunks = ["transformed ", "", "data"]
+        .map(|chunk| Ok::<_, std::io::Error>(Bytes::from_static(chunk.as_bytes())));
+    let _ = ObjectLambdaResponse::new(client, "route", "token")
+        .stream(futures_util::stream::iter(chunks))
+        .content_type("text/plain")
+        .send()
+        .await;
+
+    let req = captured_request.expect_request();
+    assert_eq!(
+        "https://route.s3-object-lambda.us-west-2.amazonaws.com/WriteGetObjectResponse",
+        req.uri().split('?').next().unwrap()
+    );
+    let headers = req.headers();
+    assert_eq!(Some("route"), headers.get("x-amz-request-route"));
+    assert_eq!(Some("token"), headers.get("x-amz-request-token"));
+    assert_eq!(Some("200"), headers.get("x-amz-fwd-status"));
+    assert_eq!(
+        Some("text/plain"),
+        headers.get("x-amz-fwd-header-Content-Type")
+    );
+    assert_eq!(
+        Some("UNSIGNED-PAYLOAD"),
+        headers.get("x-amz-content-sha256")
+    );
+    assert_eq!(None, headers.get("content-length"));
+    assert!(headers
+        .get("authorization")
+        .unwrap()
+        .contains("/s3-object-lambda/aws4_request"));
+
+    let body = ByteStream::new(req.into_body()).collect().await.unwrap();
+    assert_eq!(&b"transformed data"[..], &body.into_bytes()[..]);
+}
+
+#[tokio::test]
+async fn errors_are_forwarded() {
+    let (captured_request, client) = test_client();
+    let _ = ObjectLambdaResponse::new(client, "route", "token")
+        .error(403, "AccessDenied", "not allowed")
+        .send()
+        .await;
+
+    let req = captured_request.expect_request();
+    let headers = req.headers();
+    assert_eq!(Some("403"), headers.get("x-amz-fwd-status"));
+    assert_eq!(Some("AccessDenied"), headers.get("x-amz-fwd-error-code"));
+    assert_eq!(Some("not allowed"), headers.get("x-amz-fwd-error-message"));
+}
@@ -11,11 +11,14 @@ use futures_util::Future;
 use http_1x::header::{CONTENT_LENGTH, CONTENT_TYPE};
 use http_1x::Uri;
 use s3::config::{Credentials, Region};
+use s3::operation::complete_multipart_upload::builders::CompleteMultipartUploadFluentBuilder;
+use s3::operation::create_multipart_upload::builders::CreateMultipartUploadFluentBuilder;
 use s3::operation::get_object::builders::GetObjectFluentBuilder;
 use s3::operation::head_object::builders::HeadObjectFluentBuilder;
 use s3::operation::put_object::builders::PutObjectFluentBuilder;
 use s3::operation::upload_part::builders::UploadPartFluentBuilder;
 use s3::presigning::{PresignedRequest, PresigningConfig};
+use s3::types::{ChecksumAlgorithm, ChecksumType, CompletedMultipartUpload, CompletedPart};
 use std::pin::Pin;
 use std::time::{Duration, SystemTime};
 
@@ -43,6 +46,8 @@ rig_operation!(GetObjectFluentBuilder);
 rig_operation!(PutObjectFluentBuilder);
 rig_operation!(UploadPartFluentBuilder);
 rig_operation!(HeadObjectFluentBuilder);
+rig_operation!(CreateMultipartUploadFluentBuilder);
+rig_operation!(CompleteMultipartUploadFluentBuilder);
 
 /// Generates a `PresignedRequest` from the given input.
 /// Assumes that that input has a `presigned` method on it.
@@ -180,6 +185,123 @@ async fn test_presigned_upload_part() {
     );
 }
 
+/// Returns the value of the `X-Amz-SignedHeaders` query param of a presigned request
+fn signed_headers(presigned: &PresignedRequest) -> String {
+    let uri = presigned.uri().parse::<Uri>().unwrap();
+    uri.query()
+        .unwrap()
+        .split('&')
+        .find_map(|param| param.strip_prefix("X-Amz-SignedHeaders="))
+        .expect("signed headers are set")
+        .replace("%3B", ";")
+}
+
+#[tokio::test]
+async fn test_presigned_upload_part_with_checksum() {
+    let presigned = presign(|client| {
+        client
+            .upload_part()
+            .content_length(12345)
+            .bucket("bucket")
+            .key("key")
+            .part_number(1)
+            .upload_id("upload-id")
+            .checksum_algorithm(ChecksumAlgorithm::Crc32)
+            .checksum_crc32("NotARealChecksum")
+    })
+    .await;
+
+    assert_eq!("PUT", presigned.method());
+    assert_eq!(
+        "content-length;host;x-amz-checksum-crc32;x-amz-sdk-checksum-algorithm",
+        signed_headers(&presigned)
+    );
+    // The checksum headers must be sent along with the presigned request
+    let headers = presigned.headers().collect::<HashMap<_, _>>();
+    assert_eq!(
+        headers.get("x-amz-checksum-crc32"),
+        Some(&"NotARealChecksum")
+    );
+    assert_eq!(headers.get("x-amz-sdk-checksum-algorithm"), Some(&"CRC32"));
+}
+
+#[tokio::test]
+async fn test_presigned_create_multipart_upload() {
+    let presigned = presign(|client| {
+        client
+            .create_multipart_upload()
+            .bucket("bucket")
+            .key("key")
+            .checksum_algorithm(ChecksumAlgorithm::Crc32)
+            .checksum_type(ChecksumType::FullObject)
+    })
+    .await;
+
+    assert_eq!("POST", presigned.method());
+    let uri = presigned.uri().parse::<Uri>().unwrap();
+    assert_eq!("/key", uri.path());
+    assert!(uri.query().unwrap().starts_with("uploads&"));
+    assert_eq!(
+        "host;x-amz-checksum-algorithm;x-amz-checksum-type",
+        signed_headers(&presigned)
+    );
+    let headers = presigned.headers().collect::<HashMap<_, _>>();
+    assert_eq!(headers.get("x-amz-checksum-algorithm"), Some(&"CRC32"));
+    assert_eq!(headers.get("x-amz-checksum-type"), Some(&"FULL_OBJECT"));
+}
+
+#[tokio::test]
+async fn test_presigned_complete_multipart_upload() {
+    let presigned = presign(|client| {
+        client
+            .complete_multipart_upload()
+            .bucket("bucket")
+            .key("key")
+            .upload_id("upload-id")
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .parts(
+                        CompletedPart::builder()
+                            .part_number(1)
+                            .e_tag("etag")
+                            .checksum_crc32("NotARealPartChecksum")
+                            .build(),
+                    )
+                    .build(),
+            )
+            .checksum_type(ChecksumType::FullObject)
+            .checksum_crc32("NotARealChecksum")
+            .mpu_object_size(12345)
+    })
+    .await;
+
+    assert_eq!("POST", presigned.method());
+    let uri = presigned.uri().parse::<Uri>().unwrap();
+    assert_eq!("/key", uri.path());
+    assert!(uri.query().unwrap().starts_with("uploadId=upload-id&"));
+    assert_eq!(
+        "host;x-amz-checksum-crc32;x-amz-checksum-type;x-amz-mp-object-size",
+        signed_headers(&presigned)
+    );
+    let headers = presigned.headers().collect::<HashMap<_, _>>();
+    assert_eq!(
+        headers.get("x-amz-checksum-crc32"),
+        Some(&"NotARealChecksum")
+    );
+    assert_eq!(headers.get("x-amz-checksum-type"), Some(&"FULL_OBJECT"));
+    assert_eq!(headers.get("x-amz-mp-object-size"), Some(&"12345"));
+    // The parts must be sent in the body of the presigned request
+    let body = std::str::from_utf8(presigned.body()).unwrap();
+    assert!(body.starts_with("<CompleteMultipartUpload"), "{body}");
+    for part in [
+        "<ETag>etag</ETag>",
+        "<ChecksumCRC32>NotARealPartChecksum</ChecksumCRC32>",
+        "<PartNumber>1</PartNumber>",
+    ] {
+        assert!(body.contains(part), "{body}");
+    }
+}
+
 #[tokio::test]
 async fn test_presigning_object_lambda() {
     let presigned = presign(|client| {
@@ -196,6 +318,49 @@ async fn test_presigning_object_lambda() {
     );
 }
 
+#[tokio::test]
+async fn test_presigning_multi_region_access_point() {
+    let presigned = presign(|client| {
+        client
+            .get_object()
+            .bucket("arn:aws:s3::123456789012:accesspoint/mfzwi23gnjvgw.mrap")
+            .key("test-key")
+    })
+    .await;
+    let uri = presigned.uri().parse::<Uri>().unwrap();
+
+    let pq = uri.path_and_query().unwrap();
+    let query = pq.query().unwrap();
+    // SigV4a signatures aren't deterministic, so the signature itself can't be compared
+    let mut query_params: Vec<&str> = query
+        .split('&')
+        .filter(|param| !param.starts_with("X-Amz-Signature="))
+        .collect();
+    query_params.sort();
+
+    pretty_assertions::assert_eq!(
+        "mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com",
+        uri.authority().unwrap()
+    );
+    assert_eq!("GET", presigned.method());
+    assert_eq!("/test-key", pq.path());
+    pretty_assertions::assert_eq!(
+        &[
+            "X-Amz-Algorithm=AWS4-ECDSA-P256-SHA256",
+            "X-Amz-Credential=ANOTREAL%2F20090213%2Fs3%2Faws4_request",
+            "X-Amz-Date=20090213T233131Z",
+            "X-Amz-Expires=30",
+            "X-Amz-Region-Set=%2A",
+            "X-Amz-Security-Token=notarealsessiontoken",
+            "X-Amz-SignedHeaders=host",
+            "x-id=GetObject"
+        ][..],
+        &query_params
+    );
+    assert!(query.contains("X-Amz-Signature="));
+    assert_eq!(presigned.headers().count(), 0);
+}
+
 #[tokio::test]
 async fn test_presigned_head_object() {
     let presigned = presign(|client| client.head_object().bucket("bucket").key("key")).await;
@@ -0,0 +1,91 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::request_validation::{RequestValidation, RequestValidationError};
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::{capture_request, CaptureRequestReceiver};
+use std::error::Error;
+
+fn test_client(validation: Option<RequestValidation>) -> (CaptureRequestReceiver, Client) {
+    let (http_client, captured_request) = capture_request(None);
+    let mut config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-west-2"))
+        .http_client(http_client)
+        .with_test_defaults();
+    config.set_request_validation(validation);
+    (captured_request, Client::from_conf(config.build()))
+}
+
+/// Returns the `RequestValidationError` in the sources of `err`
+fn validation_error<'a>(err: &'a (dyn Error + 'static)) -> &'a RequestValidationError {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<RequestValidationError>() {
+            return err;
+        }
+        source = err.source();
+    }
+    panic!("no `RequestValidationError` in {err:?}")
+}
+
+#[tokio::test]
+async fn invalid_bucket_names_fail_before_sending() {
+    let (captured_request, client) = test_client(Some(RequestValidation::new()));
+    let err = client
+        .get_object()
+        .bucket("My_Bucket")
+        .key("key")
+        .send()
+        .await
+        .expect_err("the bucket name is invalid");
+
+    let err = validation_error(&err);
+    assert!(err.is_invalid_bucket());
+    assert!(err.to_string().contains("`My_Bucket`"), "{err}");
+    captured_request.expect_no_request();
+}
+
+#[tokio::test]
+async fn invalid_metadata_fails_before_sending() {
+    let (captured_request, client) = test_client(Some(RequestValidation::new()));
+    let err = client
+        .put_object()
+        .bucket("my-bucket")
+        .key("key")
+        .metadata("author", "Zoë")
+        .send()
+        .await
+        .expect_err("the metadata isn't ASCII");
+
+    assert!(validation_error(&err).is_invalid_metadata());
+    captured_request.expect_no_request();
+}
+
+#[tokio::test]
+async fn valid_requests_are_sent() {
+    let (captured_request, client) = test_client(Some(RequestValidation::new()));
+    let _ = client
+        .put_object()
+        .bucket("my-bucket")
+        .key("photos/2024/cat.jpg")
+        .metadata("author", "Jane Doe")
+        .send()
+        .await;
+    captured_request.expect_request();
+}
+
+#[tokio::test]
+async fn requests_are_not_validated_by_default() {
+    let (captured_request, client) = test_client(None);
+    let _ = client
+        .get_object()
+        .bucket("my-bucket")
+        .key("a/../b")
+        .send()
+        .await;
+    captured_request.expect_request();
+}
@@ -0,0 +1,121 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::SdkBody;
+use aws_sdk_s3::seekable_reader::SeekableReader;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::infallible_client_fn;
+use std::io::SeekFrom;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+const OBJECT: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Creates a client that serves ranges of `OBJECT` and records the requested ranges
+fn test_client() -> (Client, Arc<Mutex<Vec<String>>>) {
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let http_client = infallible_client_fn({
+        let ranges = ranges.clone();
+        move |req| {
+            if req.method() == "GET" {
+                assert_eq!(req.headers()["if-match"], "\"etag\"");
+            }
+            let response = http_1x::Response::builder().header("etag", "\"etag\"");
+            match req.headers().get("range") {
+                None => response
+                    .header("content-length", OBJECT.len().to_string())
+                    .body(SdkBody::empty())
+                    .unwrap(),
+                Some(range) => {
+                    let range = range.to_str().unwrap().to_owned();
+                    let (start, end) = range
+                        .strip_prefix("bytes=")
+                        .and_then(|range| range.split_once('-'))
+                        .unwrap();
+                    let (start, end): (usize, usize) =
+                        (start.parse().unwrap(), end.parse().unwrap());
+                    ranges.lock().unwrap().push(range);
+                    response
+                        .status(206)
+                        .body(SdkBody::from(&OBJECT[start..=end]))
+                        .unwrap()
+                }
+            }
+        }
+    });
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-east-1"))
+        .http_client(http_client)
+        .with_test_defaults()
+        .build();
+    (Client::from_conf(config), ranges)
+}
+
+#[tokio::test]
+async fn read_whole_object() {
+    let (client, ranges) = test_client();
+    let mut reader = SeekableReader::builder(client, "bucket", "key")
+        .block_size(10)
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(OBJECT.len() as u64, reader.len());
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).await.unwrap();
+    assert_eq!(OBJECT, data);
+    assert_eq!(
+        vec!["bytes=0-9", "bytes=10-19", "bytes=20-29", "bytes=30-35"],
+        *ranges.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn zero_block_size_is_an_error() {
+    let (client, ranges) = test_client();
+    let err = SeekableReader::builder(client, "bucket", "key")
+        .block_size(0)
+        .build()
+        .await
+        .expect_err("zero block size");
+    assert!(
+        format!("{err:?}").contains("the block size must not be zero"),
+        "{err:?}"
+    );
+    assert!(ranges.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn seek_only_requests_blocks_that_are_read() {
+    let (client, ranges) = test_client();
+    let mut reader = SeekableReader::builder(client, "bucket", "key")
+        .block_size(10)
+        .read_ahead(0)
+        .build()
+        .await
+        .unwrap();
+
+    let mut data = [0; 4];
+    reader.seek(SeekFrom::End(-4)).await.unwrap();
+    reader.read_exact(&mut data).await.unwrap();
+    assert_eq!(b"wxyz", &data);
+
+    reader.seek(SeekFrom::Start(8)).await.unwrap();
+    reader.read_exact(&mut data).await.unwrap();
+    assert_eq!(b"89ab", &data);
+
+    // Reading within the current block doesn't make requests
+    reader.seek(SeekFrom::Current(-2)).await.unwrap();
+    reader.read_exact(&mut data).await.unwrap();
+    assert_eq!(b"abcd", &data);
+    assert_eq!(
+        vec!["bytes=30-35", "bytes=0-9", "bytes=10-19"],
+        *ranges.lock().unwrap()
+    );
+
+    assert!(reader.seek(SeekFrom::Current(-100)).await.is_err());
+}
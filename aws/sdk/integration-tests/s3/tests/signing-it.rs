@@ -6,7 +6,9 @@
 #![cfg(feature = "test-util")]
 
 use aws_credential_types::provider::SharedCredentialsProvider;
-use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::config::interceptors::ResolvedSigningPropertiesInterceptor;
+use aws_sdk_s3::config::{Credentials, Region, RequestChecksumCalculation};
+use aws_sdk_s3::operation::SigningProperties;
 use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::{Client, Config};
 use aws_smithy_http_client::test_util::{capture_request, ReplayEvent, StaticReplayClient};
@@ -118,3 +120,71 @@ async fn disable_payload_signing_works_with_checksums() {
     // The checksum interceptor sets this.
     assert_eq!("STREAMING-UNSIGNED-PAYLOAD-TRAILER", x_amz_content_sha256);
 }
+
+// Disabling payload signing doesn't add a body checksum when request checksums are only calculated
+// when required.
+#[tokio::test]
+async fn disable_payload_signing_honors_checksum_when_required() {
+    let (http_client, request) = capture_request(None);
+    let conf = aws_sdk_s3::Config::builder()
+        .with_test_defaults()
+        .behavior_version_latest()
+        .region(Region::new("us-east-1"))
+        .http_client(http_client)
+        .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+        .build();
+    let client = aws_sdk_s3::Client::from_conf(conf);
+    let _ = client
+        .put_object()
+        .bucket("XXXXXXXXXXX")
+        .key("test-key")
+        .body(ByteStream::from_static(b"Hello, world!"))
+        .customize()
+        .disable_payload_signing()
+        .send()
+        .await;
+
+    let request = request.expect_request();
+    assert_eq!(
+        Some("UNSIGNED-PAYLOAD"),
+        request.headers().get("x-amz-content-sha256")
+    );
+    assert!(
+        request.headers().get("x-amz-checksum-crc32").is_none(),
+        "no body checksum is sent: {:?}",
+        request.headers()
+    );
+}
+
+#[tokio::test]
+async fn signing_properties_are_attached_to_outputs_when_collected() {
+    async fn put_object(collect: bool) -> aws_sdk_s3::operation::put_object::PutObjectOutput {
+        let (http_client, _request) = capture_request(None);
+        let mut conf = Config::builder()
+            .with_test_defaults()
+            .behavior_version_latest()
+            .region(Region::new("us-west-2"))
+            .http_client(http_client);
+        if collect {
+            conf = conf.interceptor(ResolvedSigningPropertiesInterceptor::new());
+        }
+        Client::from_conf(conf.build())
+            .put_object()
+            .bucket("test-bucket")
+            .key("test-key")
+            .body(ByteStream::from_static(b"Hello, world!"))
+            .send()
+            .await
+            .expect("success")
+    }
+
+    let output = put_object(true).await;
+    let properties = output.signing_properties().expect("collected");
+    assert_eq!(
+        Some("us-west-2"),
+        properties.signing_region().map(|r| r.as_ref())
+    );
+    assert_eq!(Some("s3"), properties.signing_name().map(|n| n.as_ref()));
+
+    assert_eq!(None, put_object(false).await.signing_properties());
+}
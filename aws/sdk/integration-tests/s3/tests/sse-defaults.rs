@@ -0,0 +1,113 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::config::{Credentials, Region, ServerSideEncryptionDefaults};
+use aws_sdk_s3::types::ServerSideEncryption;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::{capture_request, CaptureRequestReceiver};
+
+fn test_client() -> (CaptureRequestReceiver, Client) {
+    let (http_client, captured_request) = capture_request(None);
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-west-2"))
+        .http_client(http_client)
+        .server_side_encryption_defaults(
+            ServerSideEncryptionDefaults::aws_kms()
+                .kms_key_id("my-key")
+                .bucket_key_enabled(true)
+                .encryption_context("department", "finance"),
+        )
+        .with_test_defaults()
+        .build();
+    (captured_request, Client::from_conf(config))
+}
+
+#[tokio::test]
+async fn defaults_apply_to_object_writes() {
+    let (captured_request, client) = test_client();
+    let _ = client
+        .create_multipart_upload()
+        .bucket("test-bucket")
+        .key("test-key")
+        .send()
+        .await;
+
+    let req = captured_request.expect_request();
+    let headers = req.headers();
+    assert_eq!(Some("aws:kms"), headers.get("x-amz-server-side-encryption"));
+    assert_eq!(
+        Some("my-key"),
+        headers.get("x-amz-server-side-encryption-aws-kms-key-id")
+    );
+    assert_eq!(
+        Some("true"),
+        headers.get("x-amz-server-side-encryption-bucket-key-enabled")
+    );
+    // Base64 of `{"department":"finance"}`
+    assert_eq!(
+        Some("eyJkZXBhcnRtZW50IjoiZmluYW5jZSJ9"),
+        headers.get("x-amz-server-side-encryption-context")
+    );
+}
+
+#[tokio::test]
+async fn requests_override_defaults() {
+    let (captured_request, client) = test_client();
+    let _ = client
+        .put_object()
+        .bucket("test-bucket")
+        .key("test-key")
+        .server_side_encryption(ServerSideEncryption::Aes256)
+        .send()
+        .await;
+
+    let req = captured_request.expect_request();
+    let headers = req.headers();
+    assert_eq!(Some("AES256"), headers.get("x-amz-server-side-encryption"));
+    assert_eq!(
+        None,
+        headers.get("x-amz-server-side-encryption-aws-kms-key-id")
+    );
+    assert_eq!(
+        None,
+        headers.get("x-amz-server-side-encryption-bucket-key-enabled")
+    );
+}
+
+#[tokio::test]
+async fn config_override_unsets_defaults() {
+    let (captured_request, client) = test_client();
+    let mut config_override = Config::builder();
+    config_override.set_server_side_encryption_defaults(None);
+    let _ = client
+        .copy_object()
+        .bucket("test-bucket")
+        .key("test-key")
+        .copy_source("source-bucket/source-key")
+        .customize()
+        .config_override(config_override)
+        .send()
+        .await;
+
+    let req = captured_request.expect_request();
+    assert_eq!(None, req.headers().get("x-amz-server-side-encryption"));
+}
+
+#[tokio::test]
+async fn defaults_do_not_apply_to_other_operations() {
+    let (captured_request, client) = test_client();
+    let _ = client
+        .upload_part()
+        .bucket("test-bucket")
+        .key("test-key")
+        .upload_id("upload-id")
+        .part_number(1)
+        .send()
+        .await;
+
+    let req = captured_request.expect_request();
+    assert_eq!(None, req.headers().get("x-amz-server-side-encryption"));
+}
@@ -0,0 +1,159 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::{ByteStream, SdkBody};
+use aws_sdk_s3::upload::Upload;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_http_client::test_util::infallible_client_fn;
+use std::sync::{Arc, Mutex};
+
+const MIB: usize = 1024 * 1024;
+
+#[derive(Debug)]
+struct SentRequest {
+    method: String,
+    query: String,
+    body: Vec<u8>,
+}
+
+/// Creates a client that records requests and fails uploads of `failing_part`
+fn test_client(failing_part: Option<u32>) -> (Client, Arc<Mutex<Vec<SentRequest>>>) {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let http_client = infallible_client_fn({
+        let requests = requests.clone();
+        move |req| {
+            let method = req.method().to_string();
+            let query = req.uri().query().unwrap_or_default().to_owned();
+            let body = req.body().bytes().unwrap_or_default().to_vec();
+            let response = if method == "POST" && query.starts_with("uploads") {
+                http_1x::Response::new(SdkBody::from(
+                    "<InitiateMultipartUploadResult><UploadId>upload-id</UploadId></InitiateMultipartUploadResult>",
+                ))
+            } else if method == "POST" {
+                http_1x::Response::new(SdkBody::from(
+                    "<CompleteMultipartUploadResult><ETag>\"object-etag\"</ETag></CompleteMultipartUploadResult>",
+                ))
+            } else if failing_part.is_some_and(|part| {
+                query
+                    .split('&')
+                    .any(|param| param == format!("partNumber={part}"))
+            }) {
+                http_1x::Response::builder()
+                    .status(400)
+                    .body(SdkBody::from("<Error><Code>InvalidRequest</Code></Error>"))
+                    .unwrap()
+            } else {
+                let part_number = query
+                    .split('&')
+                    .find_map(|param| param.strip_prefix("partNumber="))
+                    .unwrap_or_default()
+                    .to_owned();
+                http_1x::Response::builder()
+                    .header("etag", format!("\"etag-{part_number}\""))
+                    .header("x-amz-checksum-crc32", format!("crc32-{part_number}"))
+                    .body(SdkBody::empty())
+                    .unwrap()
+            };
+            requests.lock().unwrap().push(SentRequest {
+                method,
+                query,
+                body,
+            });
+            response
+        }
+    });
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-east-1"))
+        .http_client(http_client)
+        .with_test_defaults()
+        .build();
+    (Client::from_conf(config), requests)
+}
+
+#[tokio::test]
+async fn small_bodies_are_uploaded_with_put_object() {
+    let (client, requests) = test_client(None);
+    let output = Upload::builder(client, "bucket", "key", ByteStream::from_static(b"hello"))
+        .build()
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(0, output.parts());
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(1, requests.len(), "{requests:?}");
+    assert_eq!("PUT", requests[0].method);
+    assert!(!requests[0].query.contains("uploadId"));
+}
+
+#[tokio::test]
+async fn large_bodies_are_uploaded_in_parts() {
+    let (client, requests) = test_client(None);
+    let body = vec![7; 12 * MIB];
+    let output = Upload::builder(client, "bucket", "key", ByteStream::from(body))
+        .multipart_threshold(6 * MIB as u64)
+        .part_size(5 * MIB as u64)
+        .concurrency(2)
+        .build()
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(3, output.parts());
+    assert_eq!(Some("\"object-etag\""), output.e_tag());
+
+    let requests = requests.lock().unwrap();
+    let mut part_sizes = requests
+        .iter()
+        .filter(|req| req.method == "PUT")
+        .map(|req| req.body.len())
+        .collect::<Vec<_>>();
+    part_sizes.sort();
+    assert_eq!(vec![2 * MIB, 5 * MIB, 5 * MIB], part_sizes);
+
+    let complete = requests.last().unwrap();
+    assert_eq!("POST", complete.method);
+    let complete_body = std::str::from_utf8(&complete.body).unwrap();
+    // Parts are completed in order, with the ETag and checksum that their upload responded with
+    let parts = complete_body.split("<Part>").skip(1).collect::<Vec<_>>();
+    assert_eq!(3, parts.len(), "{complete_body}");
+    for (part, part_xml) in (1..).zip(parts) {
+        assert!(
+            part_xml.contains(&format!("<PartNumber>{part}</PartNumber>")),
+            "{part_xml}"
+        );
+        assert!(
+            part_xml.contains(&format!("<ETag>&quot;etag-{part}&quot;</ETag>")),
+            "{part_xml}"
+        );
+        assert!(
+            part_xml.contains(&format!("<ChecksumCRC32>crc32-{part}</ChecksumCRC32>")),
+            "{part_xml}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn failed_multipart_uploads_are_aborted() {
+    let (client, requests) = test_client(Some(2));
+    let body = vec![7; 12 * MIB];
+    let err = Upload::builder(client, "bucket", "key", ByteStream::from(body))
+        .part_size(5 * MIB as u64)
+        .multipart_threshold(6 * MIB as u64)
+        .build()
+        .send()
+        .await
+        .expect_err("part 2 fails");
+    assert_eq!("failed to upload part 2", err.to_string());
+
+    let requests = requests.lock().unwrap();
+    let abort = requests.last().unwrap();
+    assert_eq!("DELETE", abort.method);
+    assert!(abort.query.contains("uploadId=upload-id"), "{abort:?}");
+    assert!(!requests
+        .iter()
+        .any(|req| req.method == "POST" && req.query.contains("uploadId")));
+}
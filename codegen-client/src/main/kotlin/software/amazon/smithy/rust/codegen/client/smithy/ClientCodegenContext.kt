@@ -6,10 +6,25 @@
 package software.amazon.smithy.rust.codegen.client.smithy
 
 import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.ServiceShape
+import software.amazon.smithy.model.shapes.Shape
 import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.ALL_OPERATIONS_FEATURE
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.OperationUsageIndex
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.operationFeatureName
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.owningOperation
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.generators.ClientBuilderInstantiator
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.all
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.allow
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.any
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.cfg
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.cfgAttr
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.feature
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.not
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenTarget
 import software.amazon.smithy.rust.codegen.core.smithy.ModuleDocProvider
@@ -42,4 +57,63 @@ data class ClientCodegenContext(
     override fun builderInstantiator(): BuilderInstantiator {
         return ClientBuilderInstantiator(this)
     }
+
+    private val requiredOperations by lazy { rootDecorator.requiredOperations(this).toSet() }
+    private val operationUsageIndex by lazy { OperationUsageIndex(model, serviceShape) }
+
+    private fun isFeatureGated(operation: OperationShape) =
+        settings.codegenConfig.operationFeatureGates && operation.id !in requiredOperations
+
+    override fun operationFeatureGate(shape: Shape): Attribute? =
+        shape.owningOperation(model)?.takeIf { isFeatureGated(it) }?.let {
+            Attribute.featureGate(operationFeatureName(it, serviceShape))
+        }
+
+    /**
+     * Returns an attribute that gates code calling all of [operations] behind their cargo features, or `null` if none
+     * of them is feature gated.
+     */
+    fun operationsFeatureGate(vararg operations: ShapeId): Attribute? {
+        if (!settings.codegenConfig.operationFeatureGates) {
+            return null
+        }
+        val features =
+            operations.map { model.expectShape(it, OperationShape::class.java) }.filter { isFeatureGated(it) }
+                .map { feature(operationFeatureName(it, serviceShape)) }
+        return when (features.size) {
+            0 -> null
+            1 -> Attribute(cfg(features.single()))
+            else -> Attribute(cfg(all(*features.toTypedArray())))
+        }
+    }
+
+    override fun serDeFeatureGates(shape: Shape): List<Attribute> {
+        if (!settings.codegenConfig.operationFeatureGates) {
+            return listOf()
+        }
+        // Serializers and deserializers of members are generated into the module of their container
+        val container = if (shape is MemberShape) model.expectShape(shape.container) else shape
+        operationFeatureGate(container)?.also { return listOf(it) }
+        if (container.owningOperation(model) != null) {
+            return listOf()
+        }
+
+        val serializing = operationUsageIndex.serializingOperations(container)
+        val deserializing = operationUsageIndex.deserializingOperations(container)
+        val operations = serializing + deserializing
+        if (operations.isEmpty() || operations.any { !isFeatureGated(it) }) {
+            return listOf()
+        }
+        val features = operations.map { operationFeatureName(it, serviceShape) }.sorted()
+        return listOfNotNull(
+            Attribute(cfg(any(*features.map { feature(it) }.toTypedArray()))),
+            // The serializers and deserializers share a module, so one of them is unused when only operations that
+            // use the shape in the other direction are enabled
+            if (serializing.isNotEmpty() && deserializing.isNotEmpty() && serializing != deserializing) {
+                Attribute(cfgAttr(not(feature(ALL_OPERATIONS_FEATURE)), allow("dead_code")))
+            } else {
+                null
+            },
+        )
+    }
 }
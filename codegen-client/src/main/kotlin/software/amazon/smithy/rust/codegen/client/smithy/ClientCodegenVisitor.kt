@@ -31,9 +31,13 @@ import software.amazon.smithy.rust.codegen.client.smithy.transformers.DisableSta
 import software.amazon.smithy.rust.codegen.core.rustlang.EscapeFor
 import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
 import software.amazon.smithy.rust.codegen.core.rustlang.RustReservedWords
+import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.implBlock
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.smithy.DirectedWalker
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProviderConfig
@@ -44,6 +48,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.generators.UnionGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.ProtocolGeneratorFactory
 import software.amazon.smithy.rust.codegen.core.smithy.transformers.EventStreamNormalizer
 import software.amazon.smithy.rust.codegen.core.smithy.transformers.OperationNormalizer
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
 import software.amazon.smithy.rust.codegen.core.smithy.transformers.RecursiveShapeBoxer
 import software.amazon.smithy.rust.codegen.core.util.CommandError
 import software.amazon.smithy.rust.codegen.core.util.getTrait
@@ -222,6 +227,35 @@ class ClientCodegenVisitor(
         // Add the underscore to avoid colliding with public module names
         "_" + RustReservedWords.escapeIfNeeded(name.toSnakeCase(), EscapeFor.ModuleName)
 
+    /**
+     * Renders `latest_initial_response` on event stream outputs whose non-event stream members are deserialized from
+     * the `initial-response` message, so that the members sent on a reconnected stream can be read.
+     */
+    private fun RustWriter.renderLatestInitialResponseMethod(shape: StructureShape) {
+        val operationId = shape.getTrait<SyntheticOutputTrait>()?.operation ?: return
+        val operation = model.expectShape(operationId, OperationShape::class.java)
+        val parser = codegenContext.protocolImpl?.structuredDataParser()?.operationParser(operation) ?: return
+        val eventStreamMember = shape.members().first { it.isEventStream(model) }
+        rustTemplate(
+            """
+            /// Deserializes the non-event stream members of the output from the `initial-response` message that the
+            /// service sent on the most recent connection of the event stream.
+            ///
+            /// The members of this output are deserialized from the `initial-response` message of the first
+            /// connection. If the event stream was reconnected, the service sent a new `initial-response` message,
+            /// whose members are returned here in a builder. Returns `Ok(None)` if the service didn't send an
+            /// `initial-response` message.
+            pub fn latest_initial_response(&self) -> #{Result}<#{Option}<#{Builder}>, #{BoxError}> {
+                self.${symbolProvider.toMemberName(eventStreamMember)}.parse_initial_response(#{Builder}::default(), #{parser})
+            }
+            """,
+            *preludeScope,
+            "Builder" to symbolProvider.symbolForBuilder(shape),
+            "BoxError" to RuntimeType.boxError(codegenContext.runtimeConfig),
+            "parser" to parser,
+        )
+    }
+
     /**
      * Structure Shape Visitor
      *
@@ -252,6 +286,7 @@ class ClientCodegenVisitor(
                                 ) == true
                             ) {
                                 BuilderGenerator.renderIntoBuilderMethod(this, symbolProvider, shape)
+                                renderLatestInitialResponseMethod(shape)
                             }
                         }
                     }
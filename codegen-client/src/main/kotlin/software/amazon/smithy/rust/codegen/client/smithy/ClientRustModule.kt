@@ -16,8 +16,10 @@ import software.amazon.smithy.model.shapes.StructureShape
 import software.amazon.smithy.model.shapes.UnionShape
 import software.amazon.smithy.model.traits.EnumTrait
 import software.amazon.smithy.model.traits.ErrorTrait
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.operationFeatureName
 import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientDocs
 import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientGenerator
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.EscapeFor
 import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
@@ -269,10 +271,16 @@ object ClientModuleProvider : ModuleProvider {
         val contextName = operationShape.contextName(context.serviceShape)
         val operationModuleName =
             RustReservedWords.escapeIfNeeded(contextName.toSnakeCase(), EscapeFor.ModuleName)
+        val featureGated = (context.settings as? ClientRustSettings)?.codegenConfig?.operationFeatureGates == true
         return RustModule.public(
             operationModuleName,
             parent = ClientRustModule.Operation,
             documentationOverride = "Types for the `$contextName` operation.",
+            additionalAttributes =
+                listOfNotNull(
+                    Attribute.featureGate(operationFeatureName(operationShape, context.serviceShape))
+                        .takeIf { featureGated },
+                ),
             // TODO(https://github.com/tokio-rs/tokio/issues/5683): Uncomment the NoImplicitPrelude attribute once this Tokio issue is resolved
             // // Disable the Rust prelude since every prelude type should be referenced with its
             // // fully qualified name to avoid name collisions with the generated operation shapes.
@@ -12,6 +12,7 @@ import software.amazon.smithy.model.shapes.ShapeId
 import software.amazon.smithy.rust.codegen.core.smithy.CODEGEN_SETTINGS
 import software.amazon.smithy.rust.codegen.core.smithy.CoreCodegenConfig
 import software.amazon.smithy.rust.codegen.core.smithy.CoreRustSettings
+import software.amazon.smithy.rust.codegen.core.smithy.NonFiniteFloatHandling
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import java.util.Optional
 
@@ -83,11 +84,24 @@ data class ClientRustSettings(
  * [includeFluentClient]: Generate a `client` module in the generated SDK (currently the AWS SDK sets this to `false`
  *   and generates its own client)
  * [addMessageToErrors]: Adds a `message` field automatically to all error shapes
+ * [validateInputConstraints]: Validate the `@length`, `@range`, and `@pattern` constraints of operation input members
+ *   when their builders are built, rather than leaving validation to the service. Only the top-level members of an
+ *   input are validated, and patterns that the `regex` crate can't compile are skipped.
+ * [operationFeatureGates]: Gate each operation behind its own cargo feature so that consumers can compile only the
+ *   operations they use. All operations are enabled by the default `all-operations` feature.
+ * [operationFeatureGroups]: Additional cargo features, keyed by group name, that each enable a group of operations.
+ *   Only used when [operationFeatureGates] is enabled.
+ * [cowStringInputs]: Store the string members of operation inputs as `aws_smithy_types::cow_str::CowStr` so that
+ *   input builders accept `&'static str` values without allocating
+ * [endpointRulesTrace]: Record which endpoint rules matched and which conditions weren't met while resolving endpoints,
+ *   and add `resolve_endpoint_with_trace` to the default endpoint resolver. Without it, the resolver doesn't record
+ *   any trace.
  */
 data class ClientCodegenConfig(
     override val formatTimeoutSeconds: Int = DEFAULT_FORMAT_TIMEOUT_SECONDS,
     override val debugMode: Boolean = DEFAULT_DEBUG_MODE,
     override val flattenCollectionAccessors: Boolean = DEFAULT_FLATTEN_ACCESSORS,
+    override val nonFiniteFloats: NonFiniteFloatHandling = DEFAULT_NON_FINITE_FLOATS,
     val nullabilityCheckMode: NullableIndex.CheckMode = NullableIndex.CheckMode.CLIENT,
     val renameExceptions: Boolean = DEFAULT_RENAME_EXCEPTIONS,
     val includeFluentClient: Boolean = DEFAULT_INCLUDE_FLUENT_CLIENT,
@@ -95,8 +109,13 @@ data class ClientCodegenConfig(
     /** If true, adds `endpoint_url`/`set_endpoint_url` methods to the service config */
     val includeEndpointUrlConfig: Boolean = DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG,
     val enableUserConfigurableRuntimePlugins: Boolean = DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS,
+    val validateInputConstraints: Boolean = DEFAULT_VALIDATE_INPUT_CONSTRAINTS,
+    val operationFeatureGates: Boolean = DEFAULT_OPERATION_FEATURE_GATES,
+    val operationFeatureGroups: Map<String, List<String>> = emptyMap(),
+    val cowStringInputs: Boolean = DEFAULT_COW_STRING_INPUTS,
+    val endpointRulesTrace: Boolean = DEFAULT_ENDPOINT_RULES_TRACE,
 ) : CoreCodegenConfig(
-        formatTimeoutSeconds, debugMode, DEFAULT_FLATTEN_ACCESSORS,
+        formatTimeoutSeconds, debugMode, DEFAULT_FLATTEN_ACCESSORS, nonFiniteFloats,
     ) {
     companion object {
         private const val DEFAULT_RENAME_EXCEPTIONS = true
@@ -105,6 +124,10 @@ data class ClientCodegenConfig(
         private const val DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG = true
         private const val DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS = true
         private const val DEFAULT_NULLABILITY_CHECK_MODE = "CLIENT"
+        private const val DEFAULT_VALIDATE_INPUT_CONSTRAINTS = false
+        private const val DEFAULT_OPERATION_FEATURE_GATES = false
+        private const val DEFAULT_COW_STRING_INPUTS = false
+        private const val DEFAULT_ENDPOINT_RULES_TRACE = false
 
         // Note: only clients default to true, servers default to false
         private const val DEFAULT_FLATTEN_ACCESSORS = true
@@ -119,6 +142,7 @@ data class ClientCodegenConfig(
                     node.get()
                         .getBooleanMemberOrDefault("flattenCollectionAccessors", DEFAULT_FLATTEN_ACCESSORS),
                 debugMode = coreCodegenConfig.debugMode,
+                nonFiniteFloats = coreCodegenConfig.nonFiniteFloats,
                 renameExceptions = node.get().getBooleanMemberOrDefault("renameErrors", DEFAULT_RENAME_EXCEPTIONS),
                 includeFluentClient =
                     node.get()
@@ -138,11 +162,30 @@ data class ClientCodegenConfig(
                     NullableIndex.CheckMode.valueOf(
                         node.get().getStringMemberOrDefault("nullabilityCheckMode", DEFAULT_NULLABILITY_CHECK_MODE),
                     ),
+                validateInputConstraints =
+                    node.get()
+                        .getBooleanMemberOrDefault("validateInputConstraints", DEFAULT_VALIDATE_INPUT_CONSTRAINTS),
+                operationFeatureGates =
+                    node.get()
+                        .getBooleanMemberOrDefault("operationFeatureGates", DEFAULT_OPERATION_FEATURE_GATES),
+                operationFeatureGroups =
+                    node.get().getObjectMember("operationFeatureGroups").map { groups ->
+                        groups.stringMap.mapValues { (_, operations) ->
+                            operations.expectArrayNode().map { it.expectStringNode().value }
+                        }
+                    }.orElse(emptyMap()),
+                cowStringInputs =
+                    node.get()
+                        .getBooleanMemberOrDefault("cowStringInputs", DEFAULT_COW_STRING_INPUTS),
+                endpointRulesTrace =
+                    node.get()
+                        .getBooleanMemberOrDefault("endpointRulesTrace", DEFAULT_ENDPOINT_RULES_TRACE),
             )
         } else {
             ClientCodegenConfig(
                 formatTimeoutSeconds = coreCodegenConfig.formatTimeoutSeconds,
                 debugMode = coreCodegenConfig.debugMode,
+                nonFiniteFloats = coreCodegenConfig.nonFiniteFloats,
                 nullabilityCheckMode = NullableIndex.CheckMode.valueOf(DEFAULT_NULLABILITY_CHECK_MODE),
             )
         }
@@ -0,0 +1,78 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy
+
+import software.amazon.smithy.codegen.core.Symbol
+import software.amazon.smithy.model.knowledge.PaginatedIndex
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.ServiceShape
+import software.amazon.smithy.model.shapes.Shape
+import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.model.traits.EnumTrait
+import software.amazon.smithy.model.traits.IdempotencyTokenTrait
+import software.amazon.smithy.rulesengine.traits.ContextIndex
+import software.amazon.smithy.rulesengine.traits.ContextParamTrait
+import software.amazon.smithy.rust.codegen.core.rustlang.RustType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
+import software.amazon.smithy.rust.codegen.core.smithy.WrappingSymbolProvider
+import software.amazon.smithy.rust.codegen.core.smithy.mapRustType
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrait
+import software.amazon.smithy.rust.codegen.core.util.getTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.orNull
+
+/**
+ * Wrapping symbol provider to change `String` to `aws_smithy_types::cow_str::CowStr` for the string members of
+ * operation inputs when the `cowStringInputs` codegen setting is enabled.
+ *
+ * `CowStr` can hold a `&'static str`, so string literals and other static strings can be set on input builders
+ * without allocating. Borrowing for any shorter lifetime isn't possible because the input is type erased, which
+ * requires it to be `'static`.
+ *
+ * Members that generated code assigns a `String` to keep their `String` type: idempotency tokens, paginator input
+ * tokens, and members that are bound to endpoint parameters.
+ */
+class CowStringInputSymbolProvider(
+    private val base: RustSymbolProvider,
+    private val serviceShape: ServiceShape,
+) : WrappingSymbolProvider(base) {
+    private val paginatedIndex = PaginatedIndex.of(model)
+    private val contextIndex = ContextIndex.of(model)
+
+    override fun toSymbol(shape: Shape): Symbol {
+        val initial = base.toSymbol(shape)
+        if (shape !is MemberShape || !usesCowStr(shape)) {
+            return initial
+        }
+        return initial.mapRustType(RuntimeType.cowStr(config.runtimeConfig)) { type ->
+            when (type) {
+                is RustType.Option -> RustType.Option(cowStr())
+                else -> cowStr()
+            }
+        }
+    }
+
+    private fun cowStr(): RustType =
+        RuntimeType.cowStr(config.runtimeConfig).let { RustType.Opaque(it.name, it.namespace, deref = RustType.Opaque("str")) }
+
+    private fun usesCowStr(member: MemberShape): Boolean {
+        val operationId = model.expectShape(member.container).getTrait<SyntheticInputTrait>()?.operation
+        val target = model.expectShape(member.target)
+        if (operationId == null || target !is StringShape || target.hasTrait<EnumTrait>()) {
+            return false
+        }
+        val operation = model.expectShape(operationId, OperationShape::class.java)
+        val paginationInfo = paginatedIndex.getPaginationInfo(serviceShape, operation).orNull()
+        return !(
+            member.hasTrait<IdempotencyTokenTrait>() ||
+                member.hasTrait<ContextParamTrait>() ||
+                contextIndex.getOperationContextParams(operation).isPresent ||
+                paginationInfo?.inputTokenMember?.memberName == member.memberName
+        )
+    }
+}
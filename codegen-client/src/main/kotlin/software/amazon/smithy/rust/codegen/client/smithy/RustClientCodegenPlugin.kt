@@ -14,7 +14,9 @@ import software.amazon.smithy.rust.codegen.client.smithy.customizations.ClientCu
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.HttpAuthDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.HttpConnectorConfigDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.IdempotencyTokenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.InputConstraintValidationDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.NoAuthDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.OperationFeatureGatesDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.SensitiveOutputDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.StaticSdkFeatureTrackerDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
@@ -74,6 +76,8 @@ class RustClientCodegenPlugin : ClientDecoratableBuildPlugin() {
                 IdempotencyTokenDecorator(),
                 StalledStreamProtectionDecorator(),
                 StaticSdkFeatureTrackerDecorator(),
+                InputConstraintValidationDecorator(),
+                OperationFeatureGatesDecorator(),
                 *decorator,
             )
 
@@ -99,6 +103,10 @@ class RustClientCodegenPlugin : ClientDecoratableBuildPlugin() {
             .let { EventStreamSymbolProvider(rustSymbolProviderConfig.runtimeConfig, it, CodegenTarget.CLIENT) }
             // Generate `ByteStream` instead of `Blob` for streaming binary shapes (e.g. S3 GetObject)
             .let { StreamingShapeSymbolProvider(it) }
+            // Generate `CowStr` instead of `String` for the string members of operation inputs, if enabled
+            .let {
+                if (settings.codegenConfig.cowStringInputs) CowStringInputSymbolProvider(it, serviceShape) else it
+            }
             // Add Rust attributes (like `#[derive(PartialEq)]`) to generated shapes
             .let { BaseSymbolMetadataProvider(it, additionalAttributes = listOf(NonExhaustive)) }
             // Streaming shapes need different derives (e.g. they cannot derive `PartialEq`)
@@ -133,7 +133,9 @@ private class AuthDecoratorConfigCustomizations(private val codegenContext: Clie
                         /// _Important:_ When introducing a custom auth scheme, ensure you override either
                         /// [`Self::auth_scheme_resolver`] or [`Self::set_auth_scheme_resolver`]
                         /// so that the custom auth scheme is included in the list of resolved auth scheme options.
-                        /// [The default auth scheme resolver](crate::config::auth::DefaultAuthSchemeResolver) will not recognize your custom auth scheme.
+                        /// [The default auth scheme resolver](crate::config::auth::DefaultAuthSchemeResolver) will not recognize your custom auth scheme
+                        /// unless it is added with [`with_preferred_auth_scheme`](crate::config::auth::DefaultAuthSchemeResolver::with_preferred_auth_scheme),
+                        /// e.g. `.auth_scheme_resolver(DefaultAuthSchemeResolver::default().with_preferred_auth_scheme(AuthSchemeId::new("custom")))`.
                         ///
                         /// ## Examples
                         /// ```no_run
@@ -315,6 +317,9 @@ private class AuthSchemePreferenceConfigCustomization(codegenContext: ClientCode
             "AuthSchemePreference" to
                 RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
                     .resolve("client::auth::AuthSchemePreference"),
+            "DisabledAuthSchemes" to
+                RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+                    .resolve("client::auth::DisabledAuthSchemes"),
         )
     private val moduleUseName = codegenContext.moduleUseName()
 
@@ -328,6 +333,11 @@ private class AuthSchemePreferenceConfigCustomization(codegenContext: ClientCode
                         pub fn auth_scheme_preference(&self) -> #{Option}<&#{AuthSchemePreference}> {
                             self.config.load::<#{AuthSchemePreference}>()
                         }
+
+                        /// Returns the configured disabled auth schemes
+                        pub fn disabled_auth_schemes(&self) -> #{Option}<&#{DisabledAuthSchemes}> {
+                            self.config.load::<#{DisabledAuthSchemes}>()
+                        }
                         """,
                         *codegenScope,
                     )
@@ -372,11 +382,49 @@ private class AuthSchemePreferenceConfigCustomization(codegenContext: ClientCode
                         """,
                         *codegenScope,
                     )
+
+                    val disabledDocs = """
+                        /// Set the auth schemes that must never be used to sign a request.
+                        ///
+                        /// Unlike the auth scheme preference, this is a strict restriction. Disabled schemes
+                        /// are removed from the auth schemes resolved for an operation, and the request fails
+                        /// with an error listing the disabled schemes if no other scheme is available.
+                        ///
+                        /// ## Examples
+                        ///
+                        /// ```no_run
+                        /// ## use aws_smithy_runtime_api::client::auth::AuthSchemeId;
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     .disabled_auth_schemes([AuthSchemeId::from("httpBearerAuth")])
+                        ///     // ...
+                        ///     .build();
+                        /// let client = $moduleUseName::Client::from_conf(config);
+                        /// ```
+                    """
+                    rustTemplate(
+                        """
+                        $disabledDocs
+                        pub fn disabled_auth_schemes(mut self, disabled: impl #{Into}<#{DisabledAuthSchemes}>) -> Self {
+                            self.set_disabled_auth_schemes(#{Some}(disabled.into()));
+                            self
+                        }
+
+                        $disabledDocs
+                        pub fn set_disabled_auth_schemes(&mut self, disabled: #{Option}<#{DisabledAuthSchemes}>) -> &mut Self {
+                            self.config.store_or_unset(disabled);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
                 }
 
                 is ServiceConfig.BuilderFromConfigBag ->
                     rustTemplate(
-                        "${section.builder}.set_auth_scheme_preference(${section.configBag}.load::<#{AuthSchemePreference}>().cloned());",
+                        """
+                        ${section.builder}.set_auth_scheme_preference(${section.configBag}.load::<#{AuthSchemePreference}>().cloned());
+                        ${section.builder}.set_disabled_auth_schemes(${section.configBag}.load::<#{DisabledAuthSchemes}>().cloned());
+                        """,
                         *codegenScope,
                     )
 
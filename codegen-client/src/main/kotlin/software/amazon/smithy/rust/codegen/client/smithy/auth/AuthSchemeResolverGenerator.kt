@@ -25,6 +25,7 @@ import software.amazon.smithy.rust.codegen.core.util.serviceNameOrDefault
  * pub struct DefaultAuthSchemeResolver {
  *     service_defaults: Vec<AuthSchemeOption>,
  *     operation_overrides: HashMap<&'static str, Vec<AuthSchemeOption>>,
+ *     preferred_auth_options: Vec<AuthSchemeOption>,
  * }
  *
  * impl crate::config::auth::ResolveEndpoint for DefaultAuthSchemeResolver {
@@ -78,6 +79,7 @@ class AuthSchemeResolverGenerator(
                 pub struct DefaultAuthSchemeResolver {
                     service_defaults: Vec<#{AuthSchemeOption}>,
                     operation_overrides: #{HashMap}<&'static str, Vec<#{AuthSchemeOption}>>,
+                    preferred_auth_options: Vec<#{AuthSchemeOption}>,
                 }
 
                 // TODO(https://github.com/smithy-lang/smithy-rs/issues/4177): Remove `allow(...)` once the issue is addressed.
@@ -90,10 +92,24 @@ class AuthSchemeResolverGenerator(
                         Self {
                             service_defaults: vec![#{service_defaults:W}],
                             operation_overrides: #{operation_overrides:W},
+                            preferred_auth_options: vec![],
                         }
                     }
                 }
 
+                impl DefaultAuthSchemeResolver {
+                    /// Returns this resolver with `auth_scheme_option` tried before the auth schemes modeled for each operation.
+                    ///
+                    /// This is the simplest way to have a custom auth scheme, registered with
+                    /// [`push_auth_scheme`](crate::config::Builder::push_auth_scheme), selected for every operation
+                    /// while still falling back to the modeled auth schemes when its identity cannot be resolved.
+                    /// Options added by repeated calls are tried in the order they were added.
+                    pub fn with_preferred_auth_scheme(mut self, auth_scheme_option: impl #{Into}<#{AuthSchemeOption}>) -> Self {
+                        self.preferred_auth_options.push(auth_scheme_option.into());
+                        self
+                    }
+                }
+
                 impl #{ServiceSpecificResolveAuthScheme} for DefaultAuthSchemeResolver {
                     fn resolve_auth_scheme<'a>(
                         &'a self,
@@ -108,7 +124,13 @@ class AuthSchemeResolverGenerator(
                             None => &self.service_defaults,
                         };
 
-                        let _fut = #{AuthSchemeOptionsFuture}::ready(Ok(modeled_auth_options.clone()));
+                        let _fut = if self.preferred_auth_options.is_empty() {
+                            #{AuthSchemeOptionsFuture}::ready(Ok(modeled_auth_options.clone()))
+                        } else {
+                            let mut auth_options = self.preferred_auth_options.clone();
+                            auth_options.extend(modeled_auth_options.iter().cloned());
+                            #{AuthSchemeOptionsFuture}::ready(Ok(auth_options))
+                        };
 
                         #{additional_impl:W}
 
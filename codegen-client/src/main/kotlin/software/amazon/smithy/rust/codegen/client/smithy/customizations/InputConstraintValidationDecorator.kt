@@ -0,0 +1,251 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.model.shapes.BlobShape
+import software.amazon.smithy.model.shapes.ByteShape
+import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.DoubleShape
+import software.amazon.smithy.model.shapes.FloatShape
+import software.amazon.smithy.model.shapes.IntegerShape
+import software.amazon.smithy.model.shapes.LongShape
+import software.amazon.smithy.model.shapes.MapShape
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.Shape
+import software.amazon.smithy.model.shapes.ShortShape
+import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.model.traits.EnumTrait
+import software.amazon.smithy.model.traits.LengthTrait
+import software.amazon.smithy.model.traits.PatternTrait
+import software.amazon.smithy.model.traits.RangeTrait
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustBlockTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderSection
+import software.amazon.smithy.rust.codegen.core.smithy.generators.operationBuildError
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrait
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.extendIf
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.isStreaming
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
+import java.math.BigDecimal
+import java.util.logging.Logger
+import kotlin.jvm.optionals.getOrNull
+
+/**
+ * Validates the `@length`, `@range`, and `@pattern` constraints of operation input members when the input's builder
+ * is built, so that invalid input is rejected before a request is sent.
+ *
+ * This is opt-in with the `validateInputConstraints` codegen setting. Only the top-level members of an input are
+ * validated; constraints on nested structures, and on the members of collections, are left to the service.
+ *
+ * Patterns are matched with the `regex` crate, which supports Unicode classes such as `\p{L}`. Patterns that use
+ * constructs the `regex` crate doesn't support, such as lookarounds and backreferences, are skipped at codegen time
+ * and left to the service as well.
+ */
+class InputConstraintValidationDecorator : ClientCodegenDecorator {
+    override val name: String = "InputConstraintValidation"
+    override val order: Byte = 0
+
+    override fun builderCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<BuilderCustomization>,
+    ): List<BuilderCustomization> =
+        baseCustomizations.extendIf(codegenContext.settings.codegenConfig.validateInputConstraints) {
+            InputConstraintValidationCustomization(codegenContext)
+        }
+}
+
+private class InputConstraintValidationCustomization(
+    private val codegenContext: ClientCodegenContext,
+) : BuilderCustomization() {
+    private val model = codegenContext.model
+    private val symbolProvider = codegenContext.symbolProvider
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "BuildError" to codegenContext.runtimeConfig.operationBuildError(),
+            "Constraint" to
+                RuntimeType.smithyTypes(codegenContext.runtimeConfig).resolve("error::operation::Constraint"),
+            "ConstraintViolation" to
+                RuntimeType.smithyTypes(codegenContext.runtimeConfig)
+                    .resolve("error::operation::ConstraintViolation"),
+            "Regex" to CargoDependency.Regex.toType(),
+        )
+    private val logger = Logger.getLogger(javaClass.name)
+
+    override fun section(section: BuilderSection): Writable =
+        writable {
+            if (section is BuilderSection.BeforeBuild && section.shape.hasTrait<SyntheticInputTrait>()) {
+                val constrainedMembers = section.shape.members().filter { it.isValidated() }
+                if (constrainedMembers.isEmpty()) {
+                    return@writable
+                }
+
+                rustTemplate("let mut violations = #{Vec}::new();", *codegenScope)
+                constrainedMembers.forEach { member -> renderMemberValidation(member) }
+                rustTemplate(
+                    """
+                    if !violations.is_empty() {
+                        return #{Err}(#{BuildError}::constraint_violations(violations));
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
+
+    private fun MemberShape.isValidated(): Boolean {
+        if (isStreaming(model)) {
+            return false
+        }
+        val target = model.expectShape(this.target)
+        return lengthTrait(target) != null || rangeTrait(target) != null || patternTrait(target) != null
+    }
+
+    private fun MemberShape.lengthTrait(target: Shape): LengthTrait? =
+        getMemberTrait(model, LengthTrait::class.java).getOrNull().takeIf {
+            target.isPlainString() || target is BlobShape || target is CollectionShape || target is MapShape
+        }
+
+    private fun MemberShape.rangeTrait(target: Shape): RangeTrait? =
+        getMemberTrait(model, RangeTrait::class.java).getOrNull().takeIf {
+            target.isInteger() || target is FloatShape || target is DoubleShape
+        }
+
+    private fun MemberShape.patternTrait(target: Shape): PatternTrait? =
+        getMemberTrait(model, PatternTrait::class.java).getOrNull()
+            ?.takeIf { target.isPlainString() }
+            ?.takeIf { pattern ->
+                pattern.isSupportedByRegexCrate().also { supported ->
+                    if (!supported) {
+                        logger.warning(
+                            "Not validating the `@pattern` of $this on the client: `${pattern.value}` uses a construct " +
+                                "that the `regex` crate doesn't support",
+                        )
+                    }
+                }
+            }
+
+    // Lookarounds, backreferences, possessive quantifiers, and atomic groups are valid in the ECMA 262 dialect that
+    // Smithy patterns use, but can't be compiled by the `regex` crate
+    private fun PatternTrait.isSupportedByRegexCrate(): Boolean =
+        !Regex("""\(\?<?[=!]|\(\?>|\\[1-9]|\\k<|(?<!\\)[*+?}]\+""").containsMatchIn(value)
+
+    // Enums are validated by the type system instead
+    private fun Shape.isPlainString() = this is StringShape && !hasTrait<EnumTrait>()
+
+    private fun Shape.isInteger() = this is ByteShape || this is ShortShape || this is IntegerShape || this is LongShape
+
+    private fun integerBounds(shape: Shape): Pair<BigDecimal?, BigDecimal?> =
+        when (shape) {
+            is ByteShape -> BigDecimal(Byte.MIN_VALUE.toLong()) to BigDecimal(Byte.MAX_VALUE.toLong())
+            is ShortShape -> BigDecimal(Short.MIN_VALUE.toLong()) to BigDecimal(Short.MAX_VALUE.toLong())
+            is IntegerShape -> BigDecimal(Int.MIN_VALUE.toLong()) to BigDecimal(Int.MAX_VALUE.toLong())
+            is LongShape -> BigDecimal(Long.MIN_VALUE) to BigDecimal(Long.MAX_VALUE)
+            else -> null to null
+        }
+
+    private fun RustWriter.renderMemberValidation(member: MemberShape) {
+        val memberName = symbolProvider.toMemberName(member)
+        val target = model.expectShape(member.target)
+        val redact = member.shouldRedact(model)
+
+        rustBlockTemplate("if let #{Some}(value) = &self.$memberName", *codegenScope) {
+            member.lengthTrait(target)?.also { length ->
+                val len =
+                    when (target) {
+                        is StringShape -> "value.chars().count()"
+                        is BlobShape -> "value.as_ref().len()"
+                        else -> "value.len()"
+                    }
+                rust("let length = $len;")
+                renderBoundsCheck(
+                    memberName,
+                    "Length",
+                    "length",
+                    "length",
+                    // Lengths can't be negative, so a minimum of zero is always satisfied
+                    length.min.getOrNull()?.takeIf { it > 0 }?.toString(),
+                    length.max.getOrNull()?.toString(),
+                )
+            }
+            member.rangeTrait(target)?.also { range ->
+                val isInteger = target.isInteger()
+                rust(if (isInteger) "let value = *value;" else "let value = *value as f64;")
+                val bound = { b: BigDecimal -> if (isInteger) b.toBigInteger().toString() else b.toDouble().toString() }
+                // Bounds that every value of the integer type satisfies can't be checked without an absurd comparison
+                val (typeMin, typeMax) = integerBounds(target)
+                renderBoundsCheck(
+                    memberName,
+                    "Range",
+                    "value",
+                    // Don't include sensitive values in the error
+                    if (redact) null else "value",
+                    range.min.getOrNull()?.takeUnless { typeMin != null && it <= typeMin }?.let(bound),
+                    range.max.getOrNull()?.takeUnless { typeMax != null && it >= typeMax }?.let(bound),
+                )
+            }
+            member.patternTrait(target)?.also { patternTrait ->
+                val pattern = patternTrait.value.replace("#", "##")
+                // Patterns that the `regex` crate can't compile are skipped above, but building input must never
+                // panic, so a pattern that still fails to compile isn't validated
+                rustTemplate(
+                    """
+                    static REGEX: std::sync::LazyLock<#{Option}<#{Regex}::Regex>> = std::sync::LazyLock::new(|| #{Regex}::Regex::new(r##"$pattern"##).ok());
+                    if let #{Some}(regex) = &*REGEX {
+                        if !regex.is_match(value) {
+                            violations.push(#{ConstraintViolation}::new(${memberName.dq()}, #{Constraint}::Pattern, r##"value doesn't match the pattern `$pattern`"##));
+                        }
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
+    }
+
+    /**
+     * Renders a check that [valueName] is within [min] and [max], both inclusive, pushing a violation of [constraint]
+     * otherwise. [describedAs] is used to print the value in the violation's details, if it's not `null`.
+     */
+    private fun RustWriter.renderBoundsCheck(
+        memberName: String,
+        constraint: String,
+        valueName: String,
+        describedAs: String?,
+        min: String?,
+        max: String?,
+    ) {
+        val subject = describedAs?.let { "$it {$valueName}" } ?: "the value"
+        val checks =
+            listOfNotNull(
+                min?.let { "$valueName < $it" to "$subject is less than $it" },
+                max?.let { "$valueName > $it" to "$subject is greater than $it" },
+            )
+        checks.forEach { (condition, details) ->
+            val detailsExpr = if (describedAs != null) "::std::format!(${details.dq()})" else details.dq()
+            rustTemplate(
+                """
+                if $condition {
+                    violations.push(#{ConstraintViolation}::new(${memberName.dq()}, #{Constraint}::$constraint, $detailsExpr));
+                }
+                """,
+                *codegenScope,
+            )
+        }
+    }
+}
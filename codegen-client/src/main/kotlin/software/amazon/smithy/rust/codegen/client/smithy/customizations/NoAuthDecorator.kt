@@ -12,10 +12,16 @@ import software.amazon.smithy.model.traits.Trait
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.auth.AuthSchemeOption
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.CustomizableOperationSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
 
 /**
  * Synthetic auth trait representing `noAuth`.
@@ -50,6 +56,89 @@ class NoAuthDecorator : ClientCodegenDecorator {
         codegenContext: ClientCodegenContext,
         baseAuthSchemeOptions: List<AuthSchemeOption>,
     ): List<AuthSchemeOption> = baseAuthSchemeOptions + NoAuthSchemeOption()
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + ForceNoAuthConfigCustomization(codegenContext)
+
+    override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> =
+        listOf(
+            adhocCustomization<CustomizableOperationSection.CustomizableOperationImpl> {
+                rustTemplate(
+                    """
+                    /// Sends this request without authentication.
+                    ///
+                    /// The "no auth" scheme is used regardless of the auth schemes supported by the operation,
+                    /// so the request is not signed and no identity (such as credentials or a token) is resolved.
+                    /// This is useful for unauthenticated endpoints, such as public S3 buckets.
+                    pub fn no_auth(self) -> Self {
+                        self.runtime_plugin(#{ForceNoAuthRuntimePlugin}::new())
+                    }
+                    """,
+                    "ForceNoAuthRuntimePlugin" to noAuthModule(codegenContext).resolve("ForceNoAuthRuntimePlugin"),
+                )
+            },
+        )
+}
+
+private class ForceNoAuthConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "ForceNoAuth" to noAuthModule(codegenContext).resolve("ForceNoAuth"),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                is ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns `true` if requests are sent without authentication
+                        pub fn no_auth(&self) -> bool {
+                            self.config.load::<#{ForceNoAuth}>().map(|f| f.0).unwrap_or_default()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.BuilderImpl -> {
+                    val docs = """
+                        /// Send requests without authentication.
+                        ///
+                        /// When enabled, the "no auth" scheme is used regardless of the auth schemes supported
+                        /// by an operation, so requests are not signed and no identity is resolved. To send a
+                        /// single request without authentication, use `customize().no_auth()` instead.
+                    """
+                    rustTemplate(
+                        """
+                        $docs
+                        pub fn no_auth(mut self, no_auth: bool) -> Self {
+                            self.set_no_auth(#{Some}(no_auth));
+                            self
+                        }
+
+                        $docs
+                        pub fn set_no_auth(&mut self, no_auth: #{Option}<bool>) -> &mut Self {
+                            self.config.store_or_unset(no_auth.map(#{ForceNoAuth}));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.BuilderFromConfigBag ->
+                    rustTemplate(
+                        "${section.builder}.set_no_auth(${section.configBag}.load::<#{ForceNoAuth}>().map(|f| f.0));",
+                        *codegenScope,
+                    )
+
+                else -> emptySection
+            }
+        }
 }
 
 class NoAuthSchemeOption : AuthSchemeOption {
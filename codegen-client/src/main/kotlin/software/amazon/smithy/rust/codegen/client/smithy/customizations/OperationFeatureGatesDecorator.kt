@@ -0,0 +1,143 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.codegen.core.CodegenException
+import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.knowledge.TopDownIndex
+import software.amazon.smithy.model.neighbor.Walker
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.ServiceShape
+import software.amazon.smithy.model.shapes.Shape
+import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.Feature
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.contextName
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrait
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
+import software.amazon.smithy.rust.codegen.core.util.getTrait
+import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
+
+/** The default cargo feature that enables every operation when operations are feature gated. */
+const val ALL_OPERATIONS_FEATURE = "all-operations"
+
+/** Cargo features of generated clients that operation feature groups can't be named after. */
+private val RESERVED_FEATURES =
+    setOf(
+        "default",
+        "rt-tokio",
+        "behavior-version-latest",
+        "test-util",
+        "rustls",
+        "default-https-client",
+        "unredacted-debug",
+        ALL_OPERATIONS_FEATURE,
+    )
+
+/** Returns the name of the cargo feature that gates [operation], e.g. `operation-get-object`. */
+fun operationFeatureName(
+    operation: OperationShape,
+    serviceShape: ServiceShape?,
+): String = "operation-" + operation.contextName(serviceShape).toSnakeCase().replace('_', '-')
+
+/**
+ * Returns the operation that [this] shape is specific to: the operation itself, its synthetic input or output
+ * structure, or a member of either. Returns `null` for shapes that can be shared between operations.
+ */
+fun Shape.owningOperation(model: Model): OperationShape? =
+    when (this) {
+        is OperationShape -> this
+        is MemberShape -> model.expectShape(container).owningOperation(model)
+        else ->
+            (getTrait<SyntheticInputTrait>()?.operation ?: getTrait<SyntheticOutputTrait>()?.operation)
+                ?.let { model.expectShape(it, OperationShape::class.java) }
+    }
+
+/**
+ * Index of the operations that serialize (as part of their input) or deserialize (as part of their output or errors)
+ * each shape of [service], including the shapes nested in those.
+ */
+class OperationUsageIndex(model: Model, service: ServiceShape) {
+    private val serializingOperations = mutableMapOf<ShapeId, MutableSet<OperationShape>>()
+    private val deserializingOperations = mutableMapOf<ShapeId, MutableSet<OperationShape>>()
+
+    init {
+        val walker = Walker(model)
+        TopDownIndex.of(model).getContainedOperations(service).forEach { operation ->
+            walker.walkShapes(model.expectShape(operation.inputShape)).forEach { shape ->
+                serializingOperations.getOrPut(shape.id) { mutableSetOf() }.add(operation)
+            }
+            (listOf(operation.outputShape) + operation.getErrors(service)).forEach { root ->
+                walker.walkShapes(model.expectShape(root)).forEach { shape ->
+                    deserializingOperations.getOrPut(shape.id) { mutableSetOf() }.add(operation)
+                }
+            }
+        }
+    }
+
+    /** Operations whose input contains [shape] */
+    fun serializingOperations(shape: Shape): Set<OperationShape> = serializingOperations[shape.id].orEmpty()
+
+    /** Operations whose output or errors contain [shape] */
+    fun deserializingOperations(shape: Shape): Set<OperationShape> = deserializingOperations[shape.id].orEmpty()
+}
+
+/**
+ * Gates each operation of the generated crate behind its own cargo feature when the `operationFeatureGates` codegen
+ * setting is enabled, so that consumers of very large services can compile only the operations they use.
+ *
+ * Every operation is enabled by the default `all-operations` feature. To select operations, depend on the crate with
+ * `default-features = false` and enable the `operation-*` features (or the feature of an operation group configured
+ * with `operationFeatureGroups`) for the operations that are used.
+ *
+ * The gating itself is applied by [ClientCodegenContext.operationFeatureGate] wherever operation specific code
+ * is generated, and by [ClientCodegenContext.serDeFeatureGates] to the serializers and deserializers of shapes that
+ * operations share. Operations that customizations call regardless of the enabled features (see
+ * [ClientCodegenDecorator.requiredOperations]) are never gated.
+ */
+class OperationFeatureGatesDecorator : ClientCodegenDecorator {
+    override val name: String = "OperationFeatureGates"
+    override val order: Byte = 0
+
+    private fun applies(codegenContext: ClientCodegenContext) =
+        codegenContext.settings.codegenConfig.operationFeatureGates
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        if (!applies(codegenContext)) {
+            return
+        }
+        val operations =
+            TopDownIndex.of(codegenContext.model).getContainedOperations(codegenContext.serviceShape)
+                .sortedBy { it.id }
+        val operationFeatures =
+            operations.associateBy { it.contextName(codegenContext.serviceShape) }
+                .mapValues { (_, operation) -> operationFeatureName(operation, codegenContext.serviceShape) }
+
+        operationFeatures.values.forEach { rustCrate.mergeFeature(Feature(it, default = false, deps = listOf())) }
+        rustCrate.mergeFeature(Feature(ALL_OPERATIONS_FEATURE, default = true, deps = operationFeatures.values.toList()))
+
+        codegenContext.settings.codegenConfig.operationFeatureGroups.forEach { (group, operationNames) ->
+            if (group in RESERVED_FEATURES || operationFeatures.containsValue(group)) {
+                throw CodegenException("Operation feature group `$group` conflicts with a generated cargo feature")
+            }
+            val deps =
+                operationNames.map { operationName ->
+                    operationFeatures[operationName]
+                        ?: throw CodegenException(
+                            "Operation feature group `$group` references `$operationName`, " +
+                                "which is not an operation of the service",
+                        )
+                }
+            rustCrate.mergeFeature(Feature(group, default = false, deps = deps))
+        }
+    }
+}
@@ -86,6 +86,12 @@ interface ClientCodegenDecorator : CoreCodegenDecorator<ClientCodegenContext, Cl
         codegenContext: ClientCodegenContext,
         baseCustomizations: List<ServiceRuntimePluginCustomization>,
     ): List<ServiceRuntimePluginCustomization> = baseCustomizations
+
+    /**
+     * Operations that generated customizations call regardless of the enabled cargo features. These operations are
+     * exempt from operation feature gates.
+     */
+    fun requiredOperations(codegenContext: ClientCodegenContext): List<ShapeId> = listOf()
 }
 
 /**
@@ -168,6 +174,9 @@ open class CombinedClientCodegenDecorator(decorators: List<ClientCodegenDecorato
             decorator.serviceRuntimePluginCustomizations(codegenContext, customizations)
         }
 
+    override fun requiredOperations(codegenContext: ClientCodegenContext): List<ShapeId> =
+        addCustomizations { decorator -> decorator.requiredOperations(codegenContext) }
+
     companion object {
         fun fromClasspath(
             context: PluginContext,
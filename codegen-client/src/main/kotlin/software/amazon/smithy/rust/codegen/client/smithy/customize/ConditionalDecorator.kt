@@ -170,6 +170,11 @@ open class ConditionalDecorator(
             delegateTo.serviceRuntimePluginCustomizations(codegenContext, baseCustomizations)
         }
 
+    final override fun requiredOperations(codegenContext: ClientCodegenContext): List<ShapeId> =
+        listOf<ShapeId>().maybeApply(codegenContext) {
+            delegateTo.requiredOperations(codegenContext)
+        }
+
     final override fun protocolTestGenerator(
         codegenContext: ClientCodegenContext,
         baseGenerator: ProtocolTestGenerator,
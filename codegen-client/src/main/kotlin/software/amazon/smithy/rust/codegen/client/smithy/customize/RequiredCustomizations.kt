@@ -91,6 +91,9 @@ class RequiredCustomizations : ClientCodegenDecorator {
 
         rustCrate.mergeFeature(TestUtilFeature)
 
+        // Reveal `@sensitive` data in `Debug` output for local debugging
+        rustCrate.mergeFeature(Feature("unredacted-debug", false, listOf("aws-smithy-types/unredacted-debug")))
+
         // Re-export resiliency types
         ResiliencyReExportCustomization(codegenContext).extras(rustCrate)
 
@@ -7,6 +7,7 @@ package software.amazon.smithy.rust.codegen.client.smithy.endpoint
 
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.endpointParamsModifier
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.serviceSpecificEndpointResolver
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
@@ -38,6 +39,7 @@ internal class EndpointConfigCustomization(
             "StaticUriEndpointResolver" to epRuntimeModule.resolve("StaticUriEndpointResolver"),
             "ServiceSpecificResolver" to codegenContext.serviceSpecificEndpointResolver(),
             "IntoShared" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("shared::IntoShared"),
+            "EndpointParamsModifier" to endpointParamsModifier(codegenContext),
         )
 
     override fun section(section: ServiceConfig): Writable {
@@ -128,6 +130,33 @@ internal class EndpointConfigCustomization(
                         /// let config = $moduleUseName::Config::builder().endpoint_resolver(resolver).build();
                         /// let client = $moduleUseName::Client::from_conf(config);
                         /// ```
+                        ///
+                        /// Endpoints can also be resolved asynchronously, e.g. by looking them up in a service registry. Implement
+                        /// `resolve_endpoint_with_config` to access the config of the operation while resolving the endpoint.
+                        /// ```no_run
+                        /// use $endpointModule::{ResolveEndpoint, EndpointFuture, Params, Endpoint};
+                        /// use $moduleUseName::config::ConfigBag;
+                        /// ##[derive(Debug)]
+                        /// struct RegistryResolver;
+                        /// impl RegistryResolver {
+                        ///     async fn lookup(&self, _params: &Params) -> String {
+                        ///         // call the service registry
+                        ///         unimplemented!()
+                        ///     }
+                        /// }
+                        /// impl ResolveEndpoint for RegistryResolver {
+                        ///     fn resolve_endpoint<'a>(&'a self, _params: &'a Params) -> EndpointFuture<'a> {
+                        ///         EndpointFuture::ready(Err("the config of the operation is required".into()))
+                        ///     }
+                        ///     fn resolve_endpoint_with_config<'a>(&'a self, params: &'a Params, _cfg: &'a ConfigBag) -> EndpointFuture<'a> {
+                        ///         EndpointFuture::new(async move {
+                        ///             let url = self.lookup(params).await;
+                        ///             Ok(Endpoint::builder().url(url).build())
+                        ///         })
+                        ///     }
+                        /// }
+                        /// let config = $moduleUseName::Config::builder().endpoint_resolver(RegistryResolver).build();
+                        /// ```
                         pub fn endpoint_resolver(mut self, endpoint_resolver: impl #{ServiceSpecificResolver} + 'static) -> Self {
                             self.set_endpoint_resolver(#{Some}(endpoint_resolver.into_shared_resolver()));
                             self
@@ -146,6 +175,37 @@ internal class EndpointConfigCustomization(
                             self.runtime_components.set_endpoint_resolver(endpoint_resolver);
                             self
                         }
+
+                        /// Adds a modifier that changes endpoint parameters based on the input of each operation.
+                        ///
+                        /// Modifiers run in the order they were added, after the endpoint parameters have been set from
+                        /// the config and the operation input. This makes it possible to route requests by their content,
+                        /// e.g. sending each request to the region that owns its partition key:
+                        /// ```ignore
+                        /// use $endpointModule::EndpointParamsModifier;
+                        /// use $moduleUseName::operation::get_item::GetItemInput;
+                        ///
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     .endpoint_params_modifier(EndpointParamsModifier::new(|input, params| {
+                        ///         match input.downcast_ref::<GetItemInput>().and_then(|input| input.partition_key()) {
+                        ///             Some(key) => params.region(region_for_partition(key)),
+                        ///             None => params,
+                        ///         }
+                        ///     }))
+                        ///     .build();
+                        /// ```
+                        pub fn endpoint_params_modifier(mut self, modifier: #{EndpointParamsModifier}) -> Self {
+                            self.push_endpoint_params_modifier(modifier);
+                            self
+                        }
+
+                        /// Adds a modifier that changes endpoint parameters based on the input of each operation.
+                        ///
+                        /// See [`Self::endpoint_params_modifier`] for more information.
+                        pub fn push_endpoint_params_modifier(&mut self, modifier: #{EndpointParamsModifier}) -> &mut Self {
+                            self.config.store_append(modifier);
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
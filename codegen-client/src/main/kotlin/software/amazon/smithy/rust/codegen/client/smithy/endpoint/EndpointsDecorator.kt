@@ -11,17 +11,22 @@ import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.CustomRuntimeFunction
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.endpointParamsOverride
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.endpointTestsModule
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.serviceSpecificEndpointResolver
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rulesgen.SmithyEndpointsStdLib
 import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.CustomizableOperationSection
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
 import software.amazon.smithy.rust.codegen.core.rustlang.Feature
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
 
 /**
  * BuiltInResolver enables potentially external codegen stages to provide sources for `builtIn` parameters.
@@ -203,6 +208,37 @@ class EndpointsDecorator : ClientCodegenDecorator {
             }
     }
 
+    override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> =
+        listOf(
+            adhocCustomization<CustomizableOperationSection.CustomizableOperationImpl> {
+                rustTemplate(
+                    """
+                    /// Overrides endpoint parameters for this operation invocation.
+                    ///
+                    /// `f` is called with the endpoint parameters resolved from the client config and the operation
+                    /// input, and can change any of them before the endpoint is resolved. For instance, a FIPS endpoint
+                    /// can be used for a single request without creating a second client:
+                    /// ```ignore
+                    /// let result = client.some_operation()
+                    ///     .customize()
+                    ///     .endpoint_params(|params| params.use_fips(true))
+                    ///     .send()
+                    ///     .await;
+                    /// ```
+                    pub fn endpoint_params<F>(self, f: F) -> Self
+                    where
+                        F: #{Fn}(#{ParamsBuilder}) -> #{ParamsBuilder} + #{Send} + #{Sync} + 'static,
+                    {
+                        self.runtime_plugin(#{EndpointParamsOverride}::new(f).into_runtime_plugin())
+                    }
+                    """,
+                    *preludeScope,
+                    "EndpointParamsOverride" to endpointParamsOverride(codegenContext),
+                    "ParamsBuilder" to EndpointTypesGenerator.fromContext(codegenContext).paramsBuilder(),
+                )
+            },
+        )
+
     override fun extras(
         codegenContext: ClientCodegenContext,
         rustCrate: RustCrate,
@@ -212,6 +248,16 @@ class EndpointsDecorator : ClientCodegenDecorator {
             withInlineModule(endpointTestsModule(), rustCrate.moduleDocProvider) {
                 generator.testGenerator()(this)
             }
+            if (generator.defaultResolver() != null && codegenContext.settings.codegenConfig.endpointRulesTrace) {
+                rustTemplate(
+                    """
+                    pub use #{EndpointRulesTrace};
+                    pub use #{RuleEvaluation};
+                    """,
+                    "EndpointRulesTrace" to EndpointsLib.EndpointRulesTrace,
+                    "RuleEvaluation" to EndpointsLib.RuleEvaluation,
+                )
+            }
         }
         rustCrate.mergeFeature(
             Feature(
@@ -46,6 +46,8 @@ fun Identifier.rustName(): String {
  */
 object EndpointsLib {
     val DiagnosticCollector = endpointsLib("diagnostic").toType().resolve("DiagnosticCollector")
+    val EndpointRulesTrace = endpointsLib("diagnostic").toType().resolve("EndpointRulesTrace")
+    val RuleEvaluation = endpointsLib("diagnostic").toType().resolve("RuleEvaluation")
 
     fun partitionResolver(runtimeConfig: RuntimeConfig) =
         endpointsLib("partition", CargoDependency.smithyJson(runtimeConfig), CargoDependency.RegexLite).toType()
@@ -188,7 +188,7 @@ class EndpointParamsGenerator(
         // Ensure that fields can be added in the future
         Attribute.NonExhaustive.render(writer)
         // Automatically implement standard Rust functionality
-        Attribute(derive(RuntimeType.Debug, RuntimeType.PartialEq, RuntimeType.Clone)).render(writer)
+        Attribute(derive(RuntimeType.Debug, RuntimeType.PartialEq, RuntimeType.Eq, RuntimeType.Hash, RuntimeType.Clone)).render(writer)
         // Generate the struct block:
         //    pub struct Params {
         //        ... members: pub(crate) field
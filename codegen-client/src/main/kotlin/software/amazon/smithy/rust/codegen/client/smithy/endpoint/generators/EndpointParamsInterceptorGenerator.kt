@@ -15,6 +15,7 @@ import software.amazon.smithy.model.traits.EndpointTrait
 import software.amazon.smithy.rulesengine.language.syntax.parameters.Parameters
 import software.amazon.smithy.rulesengine.traits.ContextIndex
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.ClientContextConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointTypesGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rustName
@@ -28,6 +29,7 @@ import software.amazon.smithy.rust.codegen.client.smithy.generators.waiters.Trav
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustType
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.asRef
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
@@ -73,6 +75,8 @@ class EndpointParamsInterceptorGenerator(
                 "Error" to interceptors.resolve("context::Error"),
                 "InterceptorError" to interceptors.resolve("error::InterceptorError"),
                 "Params" to endpointTypesGenerator.paramsStruct(),
+                "EndpointParamsModifier" to endpointParamsModifier(codegenContext),
+                "EndpointParamsOverride" to endpointParamsOverride(codegenContext),
             )
         }
 
@@ -104,8 +108,19 @@ class EndpointParamsInterceptorGenerator(
 
                     #{endpoint_prefix:W}
 
-                    let params = #{Params}::builder()
-                        #{param_setters}
+                    let params_builder = #{Params}::builder()
+                        #{param_setters};
+                    // modifiers are loaded most recently added first, but apply in the order they were added
+                    let modifiers = cfg.load::<#{EndpointParamsModifier}>().collect::<#{Vec}<_>>();
+                    let params_builder = modifiers
+                        .into_iter()
+                        .rev()
+                        .fold(params_builder, |params_builder, modifier| modifier.apply(context.input(), params_builder));
+                    let params_builder = match cfg.load::<#{EndpointParamsOverride}>() {
+                        #{Some}(params_override) => params_override.apply(params_builder),
+                        #{None} => params_builder,
+                    };
+                    let params = params_builder
                         .build()
                         .map_err(|err| #{ContextAttachedError}::new("endpoint params could not be built", err))?;
                     cfg.interceptor_state().store_put(#{EndpointResolverParams}::new(params));
@@ -271,3 +286,139 @@ class EndpointParamsInterceptorGenerator(
             }
         }
 }
+
+/**
+ * Modifies the endpoint parameters of every operation based on the operation input, added with
+ * `endpoint_params_modifier(...)` on the config builder
+ */
+internal fun endpointParamsModifier(codegenContext: ClientCodegenContext): RuntimeType {
+    val rc = codegenContext.runtimeConfig
+    return RuntimeType.forInlineFun("EndpointParamsModifier", ClientRustModule.Config.endpoint) {
+        rustTemplate(
+            """
+            /// Modifies endpoint parameters based on the input of the operation being invoked
+            ///
+            /// Modifiers run after the endpoint parameters have been set from the client config and the operation
+            /// input, and before any override set with `customize().endpoint_params(...)`. They're added to the config
+            /// with `endpoint_params_modifier`, or can be appended to the config bag by an interceptor that runs in
+            /// `read_before_execution` ahead of endpoint parameter resolution.
+            """,
+        )
+        renderEndpointParamsFn(
+            codegenContext,
+            name = "EndpointParamsModifier",
+            visibility = Visibility.PUBLIC,
+            fnArgs = "&#{Input}, ",
+            storer = RuntimeType.smithyTypes(rc).resolve("config_bag::StoreAppend"),
+            fnArgsScope =
+                listOf("Input" to RuntimeType.smithyRuntimeApiClient(rc).resolve("client::interceptors::context::Input")),
+        ) {
+            rustTemplate(
+                """
+                /// Creates a modifier from a function of the operation input and the endpoint parameters
+                ///
+                /// The input is type-erased; downcast it to the input type of the operations the modifier applies to.
+                pub fn new(f: impl #{Fn}(&#{Input}, #{ParamsBuilder}) -> #{ParamsBuilder} + #{Send} + #{Sync} + 'static) -> Self {
+                    Self(#{Arc}::new(f))
+                }
+
+                /// Applies this modifier to `params` for an operation invoked with `input`
+                pub fn apply(&self, input: &#{Input}, params: #{ParamsBuilder}) -> #{ParamsBuilder} {
+                    (self.0)(input, params)
+                }
+                """,
+                *preludeScope,
+                "Arc" to RuntimeType.Arc,
+                "Input" to RuntimeType.smithyRuntimeApiClient(rc).resolve("client::interceptors::context::Input"),
+                "ParamsBuilder" to EndpointTypesGenerator.fromContext(codegenContext).paramsBuilder(),
+            )
+        }
+    }
+}
+
+/**
+ * Overrides the endpoint parameters of a single operation invocation, set with `customize().endpoint_params(...)`
+ */
+internal fun endpointParamsOverride(codegenContext: ClientCodegenContext): RuntimeType {
+    val rc = codegenContext.runtimeConfig
+    return RuntimeType.forInlineFun("EndpointParamsOverride", ClientRustModule.Config.endpoint) {
+        rust("/// Overrides the endpoint parameters of a single operation invocation")
+        renderEndpointParamsFn(
+            codegenContext,
+            name = "EndpointParamsOverride",
+            visibility = Visibility.PUBCRATE,
+            fnArgs = "",
+            storer = RuntimeType.smithyTypes(rc).resolve("config_bag::StoreReplace"),
+        ) {
+            rustTemplate(
+                """
+                pub(crate) fn new(f: impl #{Fn}(#{ParamsBuilder}) -> #{ParamsBuilder} + #{Send} + #{Sync} + 'static) -> Self {
+                    Self(#{Arc}::new(f))
+                }
+
+                pub(crate) fn apply(&self, params: #{ParamsBuilder}) -> #{ParamsBuilder} {
+                    (self.0)(params)
+                }
+
+                ##[allow(dead_code)] // unused when a service does not provide any operations
+                pub(crate) fn into_runtime_plugin(self) -> #{StaticRuntimePlugin} {
+                    let mut layer = #{Layer}::new("EndpointParamsOverride");
+                    layer.store_put(self);
+                    #{StaticRuntimePlugin}::new().with_config(layer.freeze())
+                }
+                """,
+                *preludeScope,
+                "Arc" to RuntimeType.Arc,
+                "Layer" to RuntimeType.smithyTypes(rc).resolve("config_bag::Layer"),
+                "ParamsBuilder" to EndpointTypesGenerator.fromContext(codegenContext).paramsBuilder(),
+                "StaticRuntimePlugin" to
+                    RuntimeType.smithyRuntimeApiClient(rc).resolve("client::runtime_plugin::StaticRuntimePlugin"),
+            )
+        }
+    }
+}
+
+/**
+ * Renders [name] as a cloneable wrapper of a function that changes the endpoint parameters builder, along with the
+ * `Debug` and `Storable` impls needed to store it in the config bag. The function takes [fnArgs] before the params
+ * builder, and [impl] renders the methods of the wrapper. [fnArgsScope] is the template scope of [fnArgs].
+ */
+private fun RustWriter.renderEndpointParamsFn(
+    codegenContext: ClientCodegenContext,
+    name: String,
+    visibility: Visibility,
+    fnArgs: String,
+    storer: RuntimeType,
+    fnArgsScope: List<Pair<String, Any>> = listOf(),
+    impl: Writable,
+) {
+    val rc = codegenContext.runtimeConfig
+    rustTemplate(
+        """
+        ##[derive(Clone)]
+        ${visibility.toRustQualifier()} struct $name(#{Arc}<dyn #{Fn}($fnArgs#{ParamsBuilder}) -> #{ParamsBuilder} + #{Send} + #{Sync}>);
+
+        impl $name {
+            #{impl}
+        }
+
+        impl #{Debug} for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(${name.dq()})
+            }
+        }
+
+        impl #{Storable} for $name {
+            type Storer = #{Storer}<Self>;
+        }
+        """,
+        *preludeScope,
+        *fnArgsScope.toTypedArray(),
+        "Arc" to RuntimeType.Arc,
+        "Debug" to RuntimeType.Debug,
+        "impl" to impl,
+        "ParamsBuilder" to EndpointTypesGenerator.fromContext(codegenContext).paramsBuilder(),
+        "Storable" to RuntimeType.smithyTypes(rc).resolve("config_bag::Storable"),
+        "Storer" to storer,
+    )
+}
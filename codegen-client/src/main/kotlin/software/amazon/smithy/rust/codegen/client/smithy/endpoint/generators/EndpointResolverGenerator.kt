@@ -129,6 +129,7 @@ internal class EndpointResolverGenerator(
     stdlib: List<CustomRuntimeFunction>,
 ) {
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val traceRules = codegenContext.settings.codegenConfig.endpointRulesTrace
     private val registry: FunctionRegistry = FunctionRegistry(stdlib)
     private val types = Types(runtimeConfig)
     private val codegenScope =
@@ -186,20 +187,41 @@ internal class EndpointResolverGenerator(
             rustTemplate(
                 """
                 /// The default endpoint resolver
+                ///
+                /// Resolved endpoints are cached by their [`Params`](#{Params}), so the endpoint rules are only evaluated
+                /// when a request uses parameters that haven't been seen recently.
                 ##[derive(Debug, Default)]
                 pub struct DefaultResolver {
+                    cache: #{ResolvedEndpointCache}<#{Params}>,
                     #{custom_fields:W}
                 }
 
                 impl DefaultResolver {
                     /// Create a new endpoint resolver with default settings
                     pub fn new() -> Self {
-                        Self { #{custom_fields_init:W} }
+                        Self { cache: #{Default}::default(), #{custom_fields_init:W} }
+                    }
+
+                    /// Cache resolved endpoints in `cache`
+                    ///
+                    /// Use this to change the capacity or TTL of the cache, or pass
+                    /// [`ResolvedEndpointCache::disabled`](#{ResolvedEndpointCache}::disabled) to evaluate the endpoint
+                    /// rules for every request.
+                    pub fn with_cache(mut self, cache: #{ResolvedEndpointCache}<#{Params}>) -> Self {
+                        self.cache = cache;
+                        self
                     }
 
+                    #{resolve_endpoint_with_trace:W}
+
                     fn resolve_endpoint(&self, params: &#{Params}) -> #{Result}<#{SmithyEndpoint}, #{BoxError}> {
-                        let mut diagnostic_collector = #{DiagnosticCollector}::new();
-                        Ok(#{resolver_fn}(params, &mut diagnostic_collector, #{additional_args})
+                        self.cache.get_or_resolve(params, || {
+                            #{evaluate:W}
+                        })
+                    }
+
+                    fn evaluate_rules(&self, params: &#{Params}, diagnostic_collector: &mut #{DiagnosticCollector}) -> #{Result}<#{SmithyEndpoint}, #{BoxError}> {
+                        Ok(#{resolver_fn}(params, diagnostic_collector, #{additional_args})
                             .map_err(|err|err.with_source(diagnostic_collector.take_last_error()))?)
                     }
                 }
@@ -215,6 +237,52 @@ internal class EndpointResolverGenerator(
                 "Params" to EndpointParamsGenerator(codegenContext, endpointRuleSet.parameters).paramsStruct(),
                 "additional_args" to fnsUsed.mapNotNull { it.additionalArgsInvocation("self") }.join(","),
                 "resolver_fn" to resolverFn(endpointRuleSet, fnsUsed),
+                "ResolvedEndpointCache" to
+                    RuntimeType.smithyRuntime(runtimeConfig).resolve("client::endpoint::ResolvedEndpointCache"),
+                "resolve_endpoint_with_trace" to
+                    writable {
+                        if (traceRules) {
+                            rustTemplate(
+                                """
+                                /// Resolve the endpoint for `params`, recording how the endpoint rules were evaluated
+                                ///
+                                /// Use this to find out why `params` resolve to a particular endpoint or error. The cache is
+                                /// bypassed, so the rules are always evaluated. The same trace is also emitted as a `TRACE`
+                                /// level event whenever the resolver evaluates the endpoint rules while `TRACE` events are
+                                /// enabled.
+                                pub fn resolve_endpoint_with_trace(&self, params: &#{Params}) -> (#{Result}<#{SmithyEndpoint}, #{BoxError}>, #{EndpointRulesTrace}) {
+                                    let mut diagnostic_collector = #{DiagnosticCollector}::with_trace();
+                                    let result = self.evaluate_rules(params, &mut diagnostic_collector);
+                                    let trace = diagnostic_collector.take_trace(&result).expect("trace was enabled");
+                                    (result, trace)
+                                }
+                                """,
+                                "Params" to EndpointParamsGenerator(codegenContext, endpointRuleSet.parameters).paramsStruct(),
+                                "EndpointRulesTrace" to EndpointsLib.EndpointRulesTrace,
+                                *codegenScope,
+                            )
+                        }
+                    },
+                "evaluate" to
+                    writable {
+                        if (traceRules) {
+                            rustTemplate(
+                                """
+                                if #{tracing}::enabled!(#{tracing}::Level::TRACE) {
+                                    let (result, trace) = self.resolve_endpoint_with_trace(params);
+                                    #{tracing}::trace!(params = ?params, trace = %trace, "evaluated endpoint rules");
+                                    result
+                                } else {
+                                    self.evaluate_rules(params, &mut #{DiagnosticCollector}::new())
+                                }
+                                """,
+                                "tracing" to RuntimeType.Tracing,
+                                *codegenScope,
+                            )
+                        } else {
+                            rustTemplate("self.evaluate_rules(params, &mut #{DiagnosticCollector}::new())", *codegenScope)
+                        }
+                    },
                 *codegenScope,
             )
         }
@@ -250,21 +318,28 @@ internal class EndpointResolverGenerator(
             generateRulesList(endpointRuleSet.rules)(this)
         }
 
-    private fun generateRulesList(rules: List<Rule>) =
-        writable {
-            rules.forEach { rule ->
-                rule.documentation.orNull()?.also { comment(escape(it)) }
-                generateRule(rule)(this)
-            }
-            if (!isExhaustive(rules.last())) {
-                // it's hard to figure out if these are always needed or not
-                Attribute.AllowUnreachableCode.render(this)
-                rustTemplate(
-                    """return Err(#{EndpointError}::message(format!("No rules matched these parameters. This is a bug. {$PARAMS_NAME:?}")));""",
-                    *codegenScope,
-                )
-            }
+    /**
+     * Generates a list of rules. [parentId] is the position of the enclosing tree rule, if any. Rules are identified
+     * by their 1-based position in the rule set, e.g. `3.2` for the second rule of the third top-level rule.
+     */
+    private fun generateRulesList(
+        rules: List<Rule>,
+        parentId: String? = null,
+    ) = writable {
+        rules.forEachIndexed { index, rule ->
+            rule.documentation.orNull()?.also { comment(escape(it)) }
+            val ruleId = parentId?.let { "$it.${index + 1}" } ?: "${index + 1}"
+            generateRule(rule, ruleId)(this)
+        }
+        if (!isExhaustive(rules.last())) {
+            // it's hard to figure out if these are always needed or not
+            Attribute.AllowUnreachableCode.render(this)
+            rustTemplate(
+                """return Err(#{EndpointError}::message(format!("No rules matched these parameters. This is a bug. {$PARAMS_NAME:?}")));""",
+                *codegenScope,
+            )
         }
+    }
 
     private fun isExhaustive(rule: Rule): Boolean =
         rule.conditions.isEmpty() ||
@@ -276,8 +351,11 @@ internal class EndpointResolverGenerator(
                 }
             }
 
-    private fun generateRule(rule: Rule): Writable {
-        return generateRuleInternal(rule, rule.conditions)
+    private fun generateRule(
+        rule: Rule,
+        ruleId: String,
+    ): Writable {
+        return generateRuleInternal(rule, ruleId, rule.conditions)
     }
 
     /**
@@ -297,10 +375,11 @@ internal class EndpointResolverGenerator(
      */
     private fun generateRuleInternal(
         rule: Rule,
+        ruleId: String,
         conditions: List<Condition>,
     ): Writable {
         if (conditions.isEmpty()) {
-            return rule.accept(RuleVisitor())
+            return rule.accept(RuleVisitor(ruleId))
         } else {
             val condition = conditions.first()
             val rest = conditions.drop(1)
@@ -315,14 +394,24 @@ internal class EndpointResolverGenerator(
                 val resultName =
                     (condition.result.orNull() ?: (fn as? Reference)?.name)?.rustName() ?: "_"
                 val target = generator.generate(fn)
-                val next = generateRuleInternal(rule, rest)
+                val next = generateRuleInternal(rule, ruleId, rest)
+                // records the failed condition when the endpoint rules trace is enabled
+                val notMet =
+                    writable {
+                        if (traceRules) {
+                            rust(
+                                "else { $DIAGNOSTIC_COLLECTOR.condition_not_met(${ruleId.dq()}, ${escape(condition.toString().dq())}); }",
+                            )
+                        }
+                    }
                 when {
                     fn.type() is OptionalType -> {
                         Attribute.AllowUnusedVariables.render(this)
                         rustTemplate(
-                            "if let Some($resultName) = #{target:W} { #{next:W} }",
+                            "if let Some($resultName) = #{target:W} { #{next:W} } #{not_met:W}",
                             "target" to target,
                             "next" to next,
+                            "not_met" to notMet,
                         )
                     }
 
@@ -331,10 +420,11 @@ internal class EndpointResolverGenerator(
                             """
                             if #{target:W} {#{binding}
                                 #{next:W}
-                            }
+                            } #{not_met:W}
                             """,
                             "target" to target,
                             "next" to next,
+                            "not_met" to notMet,
                             // handle the rare but possible case where we bound the name of a variable to a boolean condition
                             "binding" to
                                 writable {
@@ -353,7 +443,7 @@ internal class EndpointResolverGenerator(
                             #{next:W}
                             """,
                             "target" to generator.generate(fn),
-                            "next" to generateRuleInternal(rule, rest),
+                            "next" to generateRuleInternal(rule, ruleId, rest),
                         )
                     }
                 }
@@ -361,11 +451,14 @@ internal class EndpointResolverGenerator(
         }
     }
 
-    inner class RuleVisitor : RuleValueVisitor<Writable> {
-        override fun visitTreeRule(rules: List<Rule>) = generateRulesList(rules)
+    inner class RuleVisitor(private val ruleId: String) : RuleValueVisitor<Writable> {
+        override fun visitTreeRule(rules: List<Rule>) = generateRulesList(rules, ruleId)
 
         override fun visitErrorRule(error: Expression) =
             writable {
+                if (traceRules) {
+                    rust("$DIAGNOSTIC_COLLECTOR.error_rule_matched(${ruleId.dq()});")
+                }
                 rustTemplate(
                     "return Err(#{EndpointError}::message(#{message:W}));",
                     *codegenScope,
@@ -375,6 +468,9 @@ internal class EndpointResolverGenerator(
 
         override fun visitEndpointRule(endpoint: Endpoint): Writable =
             writable {
+                if (traceRules) {
+                    rust("$DIAGNOSTIC_COLLECTOR.endpoint_rule_matched(${ruleId.dq()});")
+                }
                 rust("return Ok(#W);", generateEndpoint(endpoint))
             }
     }
@@ -408,6 +504,7 @@ fun ClientCodegenContext.serviceSpecificEndpointResolver(): RuntimeType {
                 "Params" to generator.paramsStruct(),
                 *Types(runtimeConfig).toArray(),
                 "Debug" to RuntimeType.Debug,
+                "ConfigBag" to RuntimeType.configBag(runtimeConfig),
             )
         val paramsFinalizers =
             endpointCustomizations.mapNotNull {
@@ -420,6 +517,15 @@ fun ClientCodegenContext.serviceSpecificEndpointResolver(): RuntimeType {
                 /// Resolve an endpoint with the given parameters
                 fn resolve_endpoint<'a>(&'a self, params: &'a #{Params}) -> #{EndpointFuture}<'a>;
 
+                /// Resolve an endpoint with the given parameters and the config of the operation
+                ///
+                /// This is called for every request. By default, it calls [`ResolveEndpoint::resolve_endpoint`]. Override it
+                /// to resolve endpoints asynchronously with config that isn't part of the endpoint parameters, e.g. to look up
+                /// an endpoint in a service registry.
+                fn resolve_endpoint_with_config<'a>(&'a self, params: &'a #{Params}, _cfg: &'a #{ConfigBag}) -> #{EndpointFuture}<'a> {
+                    self.resolve_endpoint(params)
+                }
+
                 /// Convert this service-specific resolver into a `SharedEndpointResolver`
                 ///
                 /// The resulting resolver will downcast `EndpointResolverParams` into `#{Params}`.
@@ -444,6 +550,12 @@ fun ClientCodegenContext.serviceSpecificEndpointResolver(): RuntimeType {
                     };
                     ep
                 }
+                fn resolve_endpoint_with_config<'a>(&'a self, params: &'a #{EndpointResolverParams}, cfg: &'a #{ConfigBag}) -> #{EndpointFuture}<'a> {
+                    match params.get::<#{Params}>() {
+                        Some(params) => self.0.resolve_endpoint_with_config(params, cfg),
+                        None => #{EndpointFuture}::ready(Err("params of expected type was not present".into())),
+                    }
+                }
                 #{finalize_endpoint_params:W}
             }
 
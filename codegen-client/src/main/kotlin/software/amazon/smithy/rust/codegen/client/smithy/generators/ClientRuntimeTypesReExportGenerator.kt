@@ -40,8 +40,14 @@ class ClientRuntimeTypesReExportGenerator(
                 pub use #{SharedEndpointResolver};
                 pub use #{EndpointFuture};
                 pub use #{Endpoint};
+                pub use #{ResolvedEndpointCache};
+                pub use #{FailoverEndpoints};
                 """,
                 *Types(rc).toArray(),
+                "FailoverEndpoints" to
+                    RuntimeType.smithyRuntime(rc).resolve("client::endpoint::FailoverEndpoints"),
+                "ResolvedEndpointCache" to
+                    RuntimeType.smithyRuntime(rc).resolve("client::endpoint::ResolvedEndpointCache"),
             )
         }
         rustCrate.withModule(ClientRustModule.Config.retry) {
@@ -92,10 +92,11 @@ private fun ClientCodegenContext.errorCorrectedDefault(member: MemberShape): Wri
                     "Some(#{unknown})", *preludeScope,
                     "unknown" to
                         writable {
+                            val unknown = RuntimeType.unknownUnionVariant(runtimeConfig)
                             if (memberSymbol.isRustBoxed()) {
-                                rust("Box::new(#T::Unknown)", targetSymbol)
+                                rust("Box::new(#T::Unknown(#T::default()))", targetSymbol, unknown)
                             } else {
-                                rust("#T::Unknown", targetSymbol)
+                                rust("#T::Unknown(#T::default())", targetSymbol, unknown)
                             }
                         },
                 )
@@ -123,6 +124,7 @@ fun ClientCodegenContext.correctErrors(shape: StructureShape): RuntimeType? {
     }
 
     return RuntimeType.forInlineFun(name, RustModule.private("serde_util")) {
+        serDeFeatureGates(shape).forEach { it.render(this) }
         rustTemplate(
             """
             pub(crate) fn $name(mut builder: #{Builder}) -> #{Builder} {
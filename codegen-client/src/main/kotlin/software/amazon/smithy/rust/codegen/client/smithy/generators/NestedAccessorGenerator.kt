@@ -37,6 +37,7 @@ class NestedAccessorGenerator(private val codegenContext: CodegenContext) {
         val baseType = symbolProvider.toSymbol(path.last())
         val fnName = symbolProvider.nestedAccessorName(codegenContext.serviceShape, "", root, path)
         return RuntimeType.forInlineFun(fnName, module) {
+            codegenContext.operationFeatureGate(root)?.render(this)
             rustTemplate(
                 """
                 pub(crate) fn $fnName(input: #{Input}) -> #{Output} {
@@ -62,6 +63,7 @@ class NestedAccessorGenerator(private val codegenContext: CodegenContext) {
         val fnName = symbolProvider.nestedAccessorName(codegenContext.serviceShape, "ref", root, path)
         val referencedType = baseType.mapRustType { (it as RustType.Option).referenced(lifetime = null) }
         return RuntimeType.forInlineFun(fnName, module) {
+            codegenContext.operationFeatureGate(root)?.render(this)
             rustTemplate(
                 """
                 pub(crate) fn $fnName(input: &#{Input}) -> #{Output} {
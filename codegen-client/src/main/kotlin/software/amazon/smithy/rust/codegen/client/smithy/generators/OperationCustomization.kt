@@ -28,6 +28,19 @@ sealed class OperationSection(name: String) : Section(name) {
         val responseHeadersName: String,
     ) : OperationSection("MutateOutput")
 
+    /**
+     * Hook to mutate the output of a successful response after it was parsed, with access to the whole response
+     * (e.g. its extensions) rather than only its headers.
+     */
+    data class MutateOutputFromResponse(
+        override val customizations: List<OperationCustomization>,
+        val operationShape: OperationShape,
+        /** Name of the response (for referring to it in Rust code) */
+        val responseName: String,
+        /** Name of the mutable output (for referring to it in Rust code) */
+        val outputName: String,
+    ) : OperationSection("MutateOutputFromResponse")
+
     /**
      * Allows for adding additional properties to the `extras` field on the
      * `aws_smithy_types::error::ErrorMetadata`.
@@ -7,7 +7,15 @@ package software.amazon.smithy.rust.codegen.client.smithy.generators
 
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.knowledge.PaginatedIndex
+import software.amazon.smithy.model.shapes.BooleanShape
+import software.amazon.smithy.model.shapes.ByteShape
+import software.amazon.smithy.model.shapes.IntegerShape
+import software.amazon.smithy.model.shapes.LongShape
+import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.ShortShape
+import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.model.traits.EnumTrait
 import software.amazon.smithy.model.traits.IdempotencyTokenTrait
 import software.amazon.smithy.model.traits.PaginatedTrait
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
@@ -25,12 +33,14 @@ import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.pre
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
 import software.amazon.smithy.rust.codegen.core.util.PANIC
+import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.findMemberWithTrait
 import software.amazon.smithy.rust.codegen.core.util.getTrait
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.inputShape
 import software.amazon.smithy.rust.codegen.core.util.orNull
 import software.amazon.smithy.rust.codegen.core.util.outputShape
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.core.util.toPascalCase
 
 // TODO(https://github.com/smithy-lang/smithy-rs/issues/1013) Support pagination when the idempotency trait is present
@@ -58,6 +68,7 @@ class PaginatorGenerator private constructor(
     private val model = codegenContext.model
     private val symbolProvider = codegenContext.symbolProvider
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val operationName = operation.id.name
     private val paginatorName = "${operation.id.name.toPascalCase()}Paginator"
     private val idx = PaginatedIndex.of(model)
     private val paginationInfo =
@@ -70,7 +81,8 @@ class PaginatorGenerator private constructor(
             documentationOverride = "Paginator for this operation",
         )
 
-    private val inputType = symbolProvider.toSymbol(operation.inputShape(model))
+    private val inputShape = operation.inputShape(model)
+    private val inputType = symbolProvider.toSymbol(inputShape)
     private val outputShape = operation.outputShape(model)
     private val outputType = symbolProvider.toSymbol(outputShape)
     private val errorType = symbolProvider.symbolForOperationError(operation)
@@ -105,6 +117,15 @@ class PaginatorGenerator private constructor(
                     .resolve("client::orchestrator::HttpResponse"),
             "SdkError" to RuntimeType.sdkError(runtimeConfig),
             "pagination_stream" to RuntimeType.smithyAsync(runtimeConfig).resolve("future::pagination_stream"),
+            "ResumeToken" to RuntimeType.smithyTypes(runtimeConfig).resolve("pagination::ResumeToken"),
+            "ResumeTokenError" to RuntimeType.smithyTypes(runtimeConfig).resolve("pagination::ResumeTokenError"),
+            "PageMetadata" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::pagination::PageMetadata"),
+            "StopPoint" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::orchestrator::StopPoint"),
+            "PageRateLimiter" to
+                RuntimeType.smithyAsync(runtimeConfig).resolve("future::pagination_stream::rate_limit::PageRateLimiter"),
+            "default_async_sleep" to RuntimeType.smithyAsync(runtimeConfig).resolve("rt::sleep::default_async_sleep"),
+            "ItemErrorPolicy" to
+                RuntimeType.smithyAsync(runtimeConfig).resolve("future::pagination_stream::error_policy::ItemErrorPolicy"),
             // External Types
             "Stream" to RuntimeType.TokioStream.resolve("Stream"),
         )
@@ -117,14 +138,18 @@ class PaginatorGenerator private constructor(
                     outputShape,
                     paginationInfo.outputTokenMemberPath,
                 )
-            val inputTokenMember = symbolProvider.toMemberName(paginationInfo.inputTokenMember)
             rustTemplate(
                 """
                 /// Paginator for #{operation:D}
+                #{resume_note:W}
+                ##[derive(::std::clone::Clone)]
                 pub struct $paginatorName {
                     handle: std::sync::Arc<crate::client::Handle>,
                     builder: #{Builder},
                     stop_on_duplicate_token: bool,
+                    prefetch: usize,
+                    rate_limiter: #{Option}<#{PageRateLimiter}>,
+                    error_policy: #{ItemErrorPolicy}<#{SdkError}<#{Error}, #{HttpResponse}>>,
                 }
 
                 impl $paginatorName {
@@ -134,6 +159,9 @@ class PaginatorGenerator private constructor(
                             handle,
                             builder,
                             stop_on_duplicate_token: true,
+                            prefetch: 0,
+                            rate_limiter: #{None},
+                            error_policy: #{ItemErrorPolicy}::FailFast,
                         }
                     }
 
@@ -141,6 +169,8 @@ class PaginatorGenerator private constructor(
 
                     #{items_fn:W}
 
+                    #{resume_fns:W}
+
                     /// Stop paginating when the service returns the same pagination token twice in a row.
                     ///
                     /// Defaults to true.
@@ -153,63 +183,240 @@ class PaginatorGenerator private constructor(
                         self
                     }
 
+                    /// Fetch up to `pages` pages ahead of the page that is currently being processed.
+                    ///
+                    /// Defaults to 0, which only requests the next page once it is asked for.
+                    ///
+                    /// Prefetching hides the latency of each request on long listings. The prefetched pages
+                    /// are held in memory until they are consumed, so `pages` bounds how many pages are buffered.
+                    ///
+                    /// Once the pagination stream is first polled, pages are fetched in a task spawned on the current
+                    /// Tokio runtime, so they arrive while the current page is being processed. Without the `rt-tokio`
+                    /// feature, or outside of a Tokio runtime, pages are only fetched ahead while the stream is polled.
+                    pub fn prefetch(mut self, pages: usize) -> Self {
+                        self.prefetch = pages;
+                        self
+                    }
+
+                    /// Fetch at most `pages_per_second` pages per second.
+                    ///
+                    /// This is a shorthand for [`rate_limiter`](Self::rate_limiter) with a limiter that is only used
+                    /// by this paginator.
+                    ///
+                    /// # Panics
+                    ///
+                    /// Panics if `pages_per_second` isn't a positive, finite number.
+                    pub fn max_pages_per_second(self, pages_per_second: f64) -> Self {
+                        self.rate_limiter(#{PageRateLimiter}::new(pages_per_second))
+                    }
+
+                    /// Limit the rate at which pages are fetched with the given [`PageRateLimiter`](#{PageRateLimiter}).
+                    ///
+                    /// Clones of a limiter share their budget, so giving clones of the same limiter to several
+                    /// paginators caps their combined page-fetch rate, which keeps exhaustive listings that run
+                    /// concurrently from being throttled by the service.
+                    ///
+                    /// Waiting for the limiter uses the client's sleep implementation. If no sleep implementation
+                    /// is available, the pagination stream yields a construction failure instead of any pages.
+                    pub fn rate_limiter(mut self, limiter: #{PageRateLimiter}) -> Self {
+                        self.rate_limiter = #{Some}(limiter);
+                        self
+                    }
+
                     /// Create the pagination stream
                     ///
                     /// _Note:_ No requests will be dispatched until the stream is used
                     /// (e.g. with the [`.next().await`](aws_smithy_async::future::pagination_stream::PaginationStream::next) method).
                     pub fn send(self) -> #{pagination_stream}::PaginationStream<#{item_type}> {
-                        // Move individual fields out of self for the borrow checker
-                        let builder = self.builder;
-                        let handle = self.handle;
-                        #{runtime_plugin_init}
-                        #{pagination_stream}::PaginationStream::new(#{pagination_stream}::fn_stream::FnStream::new(move |tx| #{Box}::pin(async move {
-                            // Build the input for the first time. If required fields are missing, this is where we'll produce an early error.
-                            let mut input = match builder.build().map_err(#{SdkError}::construction_failure) {
-                                #{Ok}(input) => input,
-                                #{Err}(e) => { let _ = tx.send(#{Err}(e)).await; return; }
-                            };
-                            loop {
-                                let resp = #{orchestrate};
-                                // If the input member is None or it was an error
-                                let done = match resp {
-                                    #{Ok}(ref resp) => {
-                                        let new_token = #{output_token}(resp);
-                                        #{is_empty_setter:W}
-                                        if !is_empty && new_token == input.$inputTokenMember.as_ref() && self.stop_on_duplicate_token {
-                                            true
-                                        } else {
-                                            input.$inputTokenMember = new_token.cloned();
-                                            is_empty
-                                        }
-                                    },
-                                    #{Err}(_) => true,
-                                };
-                                if tx.send(resp).await.is_err() {
-                                    // receiving end was dropped
-                                    return
-                                }
-                                if done {
-                                    return
-                                }
-                            }
-                        })))
+                        #{page_stream:W}
+                    }
+
+                    /// Create a pagination stream that yields each page along with metadata about its response
+                    ///
+                    /// The [`PageMetadata`](#{PageMetadata}) holds the page number, how long the page took to fetch,
+                    /// and the status code and headers of the HTTP response, which is useful for auditing and debugging
+                    /// long list operations.
+                    ///
+                    /// _Note:_ No requests will be dispatched until the stream is used
+                    /// (e.g. with the [`.next().await`](aws_smithy_async::future::pagination_stream::PaginationStream::next) method).
+                    pub fn send_with_metadata(self) -> #{pagination_stream}::PaginationStream<#{item_with_metadata_type}> {
+                        #{page_stream_with_metadata:W}
                     }
                 }
                 """,
                 *codegenScope,
                 "items_fn" to itemsFn(),
-                "output_token" to outputTokenLens,
+                "resume_fns" to resumeFns(outputTokenLens),
+                "resume_note" to
+                    writable {
+                        if (!hasStringTokens()) {
+                            rustTemplate(
+                                """
+                                ///
+                                /// This paginator can't be checkpointed with a [`ResumeToken`](#{ResumeToken}), because its
+                                /// pagination token isn't a string.
+                                """,
+                                *codegenScope,
+                            )
+                        }
+                    },
                 "item_type" to
                     writable {
                         rustTemplate("#{Result}<#{Output}, #{SdkError}<#{Error}, #{HttpResponse}>>", *codegenScope)
                     },
-                "orchestrate" to
+                "item_with_metadata_type" to
                     writable {
                         rustTemplate(
-                            "#{operation}::orchestrate(&runtime_plugins, input.clone()).await",
+                            "#{Result}<(#{Output}, #{PageMetadata}), #{SdkError}<#{Error}, #{HttpResponse}>>",
                             *codegenScope,
                         )
                     },
+                "page_stream" to pageStream(outputTokenLens, withMetadata = false),
+                "page_stream_with_metadata" to pageStream(outputTokenLens, withMetadata = true),
+            )
+        }
+
+    /**
+     * Generate the body of a method that creates the pagination stream. With [withMetadata], each page is
+     * yielded along with a `PageMetadata` for its response.
+     */
+    private fun pageStream(
+        outputTokenLens: RuntimeType,
+        withMetadata: Boolean,
+    ): Writable =
+        writable {
+            val inputTokenMember = symbolProvider.toMemberName(paginationInfo.inputTokenMember)
+            rustTemplate(
+                """
+                // Move individual fields out of self for the borrow checker
+                let builder = self.builder;
+                let handle = self.handle;
+                let prefetch = self.prefetch;
+                let rate_limiter = self.rate_limiter;
+                let error_policy = self.error_policy;
+                #{runtime_plugin_init}
+                let time_source = handle.conf.time_source().unwrap_or_default();
+                let sleep_impl = handle.conf.sleep_impl().or_else(#{default_async_sleep});
+                #{pagination_stream}::PaginationStream::new(#{pagination_stream}::fn_stream::FnStream::new(move |tx| #{Box}::pin(async move {
+                    // Build the input for the first time. If required fields are missing, this is where we'll produce an early error.
+                    let mut input = match builder.build().map_err(#{SdkError}::construction_failure) {
+                        #{Ok}(input) => input,
+                        #{Err}(e) => { let _ = tx.send(#{Err}(e)).await; return; }
+                    };
+                    let needs_sleep = rate_limiter.is_some() || error_policy.retry_delay().is_some_and(|delay| !delay.is_zero());
+                    if needs_sleep && sleep_impl.is_none() {
+                        let e = #{SdkError}::construction_failure("a sleep implementation is required to rate limit pagination or to retry pages with a delay");
+                        let _ = tx.send(#{Err}(e)).await;
+                        return;
+                    }
+                    #{page_number_init:W}
+                    #{page_retries_init:W}
+                    loop {
+                        if let (#{Some}(limiter), #{Some}(sleep_impl)) = (&rate_limiter, &sleep_impl) {
+                            limiter.acquire(&time_source, sleep_impl).await;
+                        }
+                        #{orchestrate:W}
+                        #{apply_error_policy:W}
+                        // If the input member is None or it was an error
+                        let done = match resp {
+                            #{Ok}(${if (withMetadata) "(ref resp, _)" else "ref resp"}) => {
+                                let new_token = #{output_token}(resp);
+                                #{is_empty_setter:W}
+                                if !is_empty && new_token == input.$inputTokenMember.as_ref() && self.stop_on_duplicate_token {
+                                    true
+                                } else {
+                                    input.$inputTokenMember = new_token.cloned();
+                                    is_empty
+                                }
+                            },
+                            #{Err}(_) => true,
+                        };
+                        if tx.send(resp).await.is_err() {
+                            // receiving end was dropped
+                            return
+                        }
+                        if done {
+                            return
+                        }
+                    }
+                }))).prefetch(prefetch)
+                """,
+                *codegenScope,
+                "output_token" to outputTokenLens,
+                "page_retries_init" to
+                    writable {
+                        if (!withMetadata) {
+                            rust("let mut page_retries = 0;")
+                        }
+                    },
+                "apply_error_policy" to
+                    writable {
+                        if (!withMetadata) {
+                            rustTemplate(
+                                """
+                                let retry_delay = match (&resp, &error_policy) {
+                                    (#{Ok}(_), _) => {
+                                        page_retries = 0;
+                                        #{None}
+                                    }
+                                    (#{Err}(err), #{ItemErrorPolicy}::RetryPage { max_attempts, delay })
+                                        if page_retries < *max_attempts && #{is_retryable_page_error}(err) => #{Some}(*delay),
+                                    (#{Err}(err), #{ItemErrorPolicy}::StopWithCallback(on_error)) => {
+                                        on_error(err);
+                                        return;
+                                    }
+                                    _ => #{None},
+                                };
+                                if let #{Some}(delay) = retry_delay {
+                                    // Request the same page again, since the input token wasn't advanced
+                                    page_retries += 1;
+                                    if let (#{Some}(sleep_impl), false) = (&sleep_impl, delay.is_zero()) {
+                                        #{AsyncSleep}::sleep(sleep_impl, delay).await;
+                                    }
+                                    continue;
+                                }
+                                """,
+                                *codegenScope,
+                                "AsyncSleep" to RuntimeType.smithyAsync(runtimeConfig).resolve("rt::sleep::AsyncSleep"),
+                                "is_retryable_page_error" to isRetryablePageError(),
+                            )
+                        }
+                    },
+                "page_number_init" to
+                    writable {
+                        if (withMetadata) {
+                            rustTemplate("let mut page_number = 0;")
+                        }
+                    },
+                "orchestrate" to
+                    writable {
+                        if (withMetadata) {
+                            rustTemplate(
+                                """
+                                page_number += 1;
+                                let start = time_source.now();
+                                let resp = #{operation}::orchestrate_with_stop_point(&runtime_plugins, input.clone(), #{StopPoint}::None)
+                                    .await
+                                    .and_then(|context| {
+                                        let latency = time_source.now().duration_since(start).unwrap_or_default();
+                                        let mut metadata = #{PageMetadata}::new(page_number, latency);
+                                        if let #{Some}(response) = context.response() {
+                                            metadata = metadata.with_response(response);
+                                        }
+                                        let output = context.finalize()?.downcast::<#{Output}>().expect("correct output type");
+                                        #{Ok}((output, metadata))
+                                    })
+                                    .map_err(|err| err.map_service_error(|err| err.downcast::<#{Error}>().expect("correct error type")));
+                                """,
+                                *codegenScope,
+                            )
+                        } else {
+                            rustTemplate(
+                                "let resp = #{operation}::orchestrate(&runtime_plugins, input.clone()).await;",
+                                *codegenScope,
+                            )
+                        }
+                    },
                 "runtime_plugin_init" to
                     writable {
                         rustTemplate(
@@ -232,6 +439,161 @@ class PaginatorGenerator private constructor(
             )
         }
 
+    /**
+     * Whether the input and output pagination tokens are plain strings that can be put into a `ResumeToken`.
+     *
+     * Structured tokens, like the `ExclusiveStartKey` map of DynamoDB's `Scan`, aren't supported: a `ResumeToken`
+     * only holds strings, and the runtime crates have no encoding for arbitrary modeled types. Paginators with
+     * structured tokens don't get `resume_token()` and `resume_from()`.
+     */
+    private fun hasStringTokens(): Boolean =
+        listOf(paginationInfo.inputTokenMember, paginationInfo.outputTokenMemberPath.last()).all { member ->
+            symbolProvider.toSymbol(member).rustType().stripOuter<RustType.Option>() == RustType.String
+        }
+
+    /**
+     * Top-level input members that are snapshotted into a `ResumeToken`. Only scalar members that can be
+     * round-tripped through a string are included, and sensitive members are never written into a token.
+     * Other members (floats, timestamps, blobs, documents, lists, maps, structures, and unions) are left out,
+     * so a resumed paginator keeps the values that were set on it before `resume_from()`.
+     */
+    private fun resumableInputMembers(): List<MemberShape> =
+        inputShape.members().filter { member ->
+            val target = model.expectShape(member.target)
+            member != paginationInfo.inputTokenMember &&
+                !member.shouldRedact(model) &&
+                (
+                    target is StringShape || target is BooleanShape || target is ByteShape ||
+                        target is ShortShape || target is IntegerShape || target is LongShape
+                )
+        }
+
+    /** Generate `resume_token()` and `resume_from()` to checkpoint and resume pagination */
+    private fun resumeFns(outputTokenLens: RuntimeType): Writable =
+        writable {
+            if (!hasStringTokens()) {
+                return@writable
+            }
+            val tokenBinding = if (resumableInputMembers().isEmpty()) "token" else "mut token"
+            val snapshot =
+                writable {
+                    resumableInputMembers().forEach { member ->
+                        val memberName = symbolProvider.toMemberName(member)
+                        val memberType = symbolProvider.toSymbol(member).rustType().stripOuter<RustType.Option>()
+                        val value =
+                            when {
+                                model.expectShape(member.target) !is StringShape -> "value.to_string()"
+                                memberType == RustType.String -> "value.clone()"
+                                else -> "value.as_str()"
+                            }
+                        rustTemplate(
+                            """
+                            if let #{Some}(value) = &self.builder.$memberName {
+                                token = token.with_input(${member.memberName.dq()}, $value);
+                            }
+                            """,
+                            *preludeScope,
+                        )
+                    }
+                }
+            val restore =
+                writable {
+                    resumableInputMembers().forEach { member ->
+                        val memberName = symbolProvider.toMemberName(member)
+                        val memberType = symbolProvider.toSymbol(member).rustType().stripOuter<RustType.Option>()
+                        val parsed =
+                            when {
+                                model.expectShape(member.target) !is StringShape ->
+                                    "token.parse_input::<${memberType.render(true)}>(${member.memberName.dq()})?"
+                                // The resumed paginator outlives the token, so the string can't be borrowed from it
+                                !model.expectShape(member.target).hasTrait<EnumTrait>() ->
+                                    "token.input(${member.memberName.dq()}).map(|value| value.to_owned().into())"
+                                else ->
+                                    "token.input(${member.memberName.dq()}).map(${memberType.render(true)}::from)"
+                            }
+                        rustTemplate(
+                            """
+                            if let #{Some}(value) = $parsed {
+                                self.builder.$memberName = #{Some}(value);
+                            }
+                            """,
+                            *preludeScope,
+                        )
+                    }
+                }
+            val inputTokenMember = symbolProvider.toMemberName(paginationInfo.inputTokenMember)
+            rustTemplate(
+                """
+                /// Returns a token that can be saved to resume pagination after `page`, or `None` if `page`
+                /// is the last page.
+                ///
+                /// The token holds the pagination token for the next page along with a snapshot of the
+                /// scalar, non-sensitive members of the input. Serialize it with `to_string()` and read it
+                /// back with `parse()`.
+                pub fn resume_token(&self, page: &#{Output}) -> #{Option}<#{ResumeToken}> {
+                    let resp = page;
+                    let new_token = #{output_token}(resp);
+                    #{is_empty_setter:W}
+                    if is_empty {
+                        return #{None};
+                    }
+                    let $tokenBinding = #{ResumeToken}::new(${operationName.dq()}, new_token?.clone());
+                    #{snapshot:W}
+                    #{Some}(token)
+                }
+
+                /// Resume pagination from a token that was returned by [`resume_token`](Self::resume_token).
+                ///
+                /// The input members that were snapshotted into the token replace the values set on this
+                /// paginator. Only scalar, non-sensitive members are snapshotted, so other members, such as
+                /// lists, maps, structures, timestamps, and sensitive values, must be set on this paginator the
+                /// same way as on the paginator that created the token.
+                ///
+                /// Fails if the token was created for a different operation, or if it holds an invalid input value.
+                pub fn resume_from(mut self, token: &#{ResumeToken}) -> #{Result}<Self, #{ResumeTokenError}> {
+                    token.check_operation(${operationName.dq()})?;
+                    #{restore:W}
+                    self.builder.$inputTokenMember = #{Some}(token.next_token().to_owned());
+                    #{Ok}(self)
+                }
+                """,
+                *codegenScope,
+                "output_token" to outputTokenLens,
+                "is_empty_setter" to isEmptySetter(),
+                "snapshot" to snapshot,
+                "restore" to restore,
+            )
+        }
+
+    /**
+     * Generate a function that tells whether requesting a page that failed again might succeed, so that the
+     * `RetryPage` error policy doesn't retry errors that will keep failing. It mirrors the default retry classifiers.
+     */
+    private fun isRetryablePageError(): RuntimeType =
+        RuntimeType.forInlineFun("is_retryable_page_error", RustModule.private("pagination_util")) {
+            rustTemplate(
+                """
+                /// Returns true if requesting a page that failed with `err` again might succeed.
+                ///
+                /// Timeouts, I/O failures, responses that couldn't be read, server errors, and errors that
+                /// are modeled as retryable are retryable.
+                pub(crate) fn is_retryable_page_error<E: #{ProvideErrorKind}>(err: &#{SdkError}<E, #{HttpResponse}>) -> bool {
+                    match err {
+                        #{SdkError}::TimeoutError(_) | #{SdkError}::ResponseError(_) => true,
+                        #{SdkError}::DispatchFailure(failure) => failure.is_timeout() || failure.is_io(),
+                        #{SdkError}::ServiceError(context) => {
+                            context.err().retryable_error_kind().is_some()
+                                || matches!(context.raw().status().as_u16(), 500 | 502 | 503 | 504)
+                        }
+                        _ => false,
+                    }
+                }
+                """,
+                *codegenScope,
+                "ProvideErrorKind" to RuntimeType.provideErrorKind(runtimeConfig),
+            )
+        }
+
     /** Generate code to calculate the value of is_empty. For most paginators this
      * is indicated by the next token being the empty string. But for paginators
      * with the isTruncatedPaginator trait the next token is not necessarily empty.
@@ -306,6 +668,19 @@ class PaginatorGenerator private constructor(
                     pub struct ${paginatorName}Items($paginatorName);
 
                     impl ${paginatorName}Items {
+                        /// Set how a page that fails to be fetched is handled.
+                        ///
+                        /// Defaults to [`ItemErrorPolicy::FailFast`](#{ItemErrorPolicy}::FailFast), which returns the error
+                        /// from the stream and ends it. Long-running enumeration jobs can instead retry a page that failed with
+                        /// a retryable error, or report the error to a callback and end the stream without it.
+                        ///
+                        /// Retrying with a delay uses the client's sleep implementation. If no sleep implementation is
+                        /// available, the pagination stream yields a construction failure instead of any items.
+                        pub fn error_policy(mut self, policy: #{ItemErrorPolicy}<#{SdkError}<#{Error}, #{HttpResponse}>>) -> Self {
+                            self.0.error_policy = policy;
+                            self
+                        }
+
                         /// Create the pagination stream
                         ///
                         /// _Note_: No requests will be dispatched until the stream is used
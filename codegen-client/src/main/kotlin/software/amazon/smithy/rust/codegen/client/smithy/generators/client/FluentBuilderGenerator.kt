@@ -4,11 +4,17 @@
  */
 package software.amazon.smithy.rust.codegen.client.smithy.generators.client
 
+import software.amazon.smithy.model.knowledge.PaginatedIndex
+import software.amazon.smithy.model.shapes.ListShape
 import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.shapes.UnionShape
+import software.amazon.smithy.model.traits.SparseTrait
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
 import software.amazon.smithy.rust.codegen.client.smithy.generators.PaginatorGenerator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.isPaginated
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.InlineDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustType
@@ -31,10 +37,17 @@ import software.amazon.smithy.rust.codegen.core.smithy.customize.writeCustomizat
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
 import software.amazon.smithy.rust.codegen.core.smithy.generators.getterName
 import software.amazon.smithy.rust.codegen.core.smithy.generators.setterName
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpLocation
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.parse.JsonParserGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.hasStreamingMember
 import software.amazon.smithy.rust.codegen.core.util.inputShape
+import software.amazon.smithy.rust.codegen.core.util.isInputEventStream
 import software.amazon.smithy.rust.codegen.core.util.isEventStream
 import software.amazon.smithy.rust.codegen.core.util.isOutputEventStream
+import software.amazon.smithy.rust.codegen.core.util.orNull
 import software.amazon.smithy.rust.codegen.core.util.outputShape
 
 /**
@@ -101,6 +114,7 @@ class FluentBuilderGenerator(
             "InputBuilder" to inputBuilderType,
             "Operation" to operationType,
             "OperationError" to errorType,
+            "OperationInput" to symbolProvider.toSymbol(inputShape),
             "OperationOutput" to outputType,
             "RuntimePlugins" to RuntimeType.runtimePlugins(runtimeConfig),
             "SdkBody" to RuntimeType.sdkBody(runtimeConfig),
@@ -112,6 +126,7 @@ class FluentBuilderGenerator(
                 ClientRustModule.Client.customize.toType()
                     .resolve("internal::SendResult"),
             "event_receiver" to forInlineDependency(InlineDependency.eventReceiver(runtimeConfig)),
+            "ReconnectPolicy" to RuntimeType.eventStreamReconnectPolicy(runtimeConfig),
         )
 
     fun render(writer: RustWriter) {
@@ -123,6 +138,9 @@ class FluentBuilderGenerator(
             writer.renderTraitImpls()
         }
         writer.renderImpl()
+        if (!config.sendOverridden() && itemStreamMember != null) {
+            writer.rust("pub use #T;", itemStream)
+        }
     }
 
     private fun defaultDocumentBuilder(): Writable =
@@ -163,6 +181,10 @@ class FluentBuilderGenerator(
                     #{epilogue:W}
                 }
 
+                #{send_with_reconnect:W}
+
+                #{send_with_item_stream:W}
+
                 /// Consumes this builder, creating a customizable operation that can be modified before being sent.
                 pub fn customize(
                     self,
@@ -172,14 +194,203 @@ class FluentBuilderGenerator(
                 """,
                 *scope,
                 "epilogue" to (
-                    handleEventStreamInitialResponse() ?: writable {
+                    handleEventStreamInitialResponse("self.handle.conf.sleep_impl()") ?: writable {
                         rustTemplate("#{Operation}::orchestrate(&runtime_plugins, input).await", *scope)
                     }
                 ),
+                "send_with_reconnect" to sendWithReconnect(),
+                "send_with_item_stream" to sendWithItemStream(),
+            )
+        }
+
+    private val itemStream by lazy {
+        forInlineDependency(InlineDependency.jsonItemStream(runtimeConfig)).resolve("ItemStream")
+    }
+
+    /**
+     * The items member of paginated JSON operations whose items are a top-level list of structures or unions, and
+     * whose output is entirely bound to the response body. Their items can be deserialized as the body is received.
+     */
+    private val itemStreamMember: MemberShape? by lazy {
+        val protocol = codegenContext.protocolImpl ?: return@lazy null
+        if (protocol.structuredDataParser() !is JsonParserGenerator ||
+            !operation.isPaginated(model) || outputShape.hasStreamingMember(model) ||
+            protocol.httpBindingResolver.responseBindings(operation).any { it.location != HttpLocation.DOCUMENT }
+        ) {
+            return@lazy null
+        }
+        val itemsMember =
+            PaginatedIndex.of(model).getPaginationInfo(codegenContext.serviceShape, operation).orNull()
+                ?.itemsMemberPath?.singleOrNull() ?: return@lazy null
+        val itemsShape = model.expectShape(itemsMember.target) as? ListShape ?: return@lazy null
+        val itemShape = model.expectShape(itemsShape.member.target)
+        itemsMember.takeIf {
+            !itemsShape.hasTrait<SparseTrait>() && (itemShape is StructureShape || itemShape is UnionShape)
+        }
+    }
+
+    /**
+     * Renders `send_with_item_stream` for operations with an [itemStreamMember], which deserializes the items as the
+     * response body is received rather than after it was buffered.
+     *
+     * The response is deserialized by replacing the operation's response deserializer with one that hands the
+     * response body to an `ItemStream`.
+     */
+    private fun sendWithItemStream(): Writable {
+        val itemsMember = itemStreamMember ?: return writable {}
+        val parser = codegenContext.protocolImpl!!.structuredDataParser() as JsonParserGenerator
+        val outputParser = parser.operationParser(operation) ?: return writable {}
+        val itemsShape = model.expectShape(itemsMember.target, ListShape::class.java)
+        val itemShape = model.expectShape(itemsShape.member.target)
+        val itemsMemberName = symbolProvider.toMemberName(itemsMember)
+        val runtimeApi = RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+        return writable {
+            rustTemplate(
+                """
+                /// Sends the request, and deserializes the `$itemsMemberName` of the response as they're received.
+                ///
+                /// Rather than buffering the whole response before deserializing it, each item is deserialized as soon
+                /// as it has been received with [`ItemStream::next`](#{ItemStream}::next). Once all items have been
+                /// received, [`ItemStream::into_output`](#{ItemStream}::into_output) deserializes the other members
+                /// of the output, whose `$itemsMemberName` are empty.
+                ///
+                /// Error responses are returned as usual. Since a successful response is returned before its body was
+                /// received, it isn't retried if receiving the body fails, and interceptors don't have access to the
+                /// output.
+                pub async fn send_with_item_stream(
+                    self,
+                ) -> #{Result}<#{ItemStream}<#{Item}, #{OperationOutput}>, #{SdkError}<#{OperationError}, #{HttpResponse}>> {
+                    ##[derive(Debug)]
+                    struct ItemStreamResponseDeserializer;
+                    impl #{DeserializeResponse} for ItemStreamResponseDeserializer {
+                        fn deserialize_streaming(&self, response: &mut #{HttpResponse}) -> #{Option}<#{OutputOrError}> {
+                            // Error responses are deserialized as usual once their body was received
+                            if !response.status().is_success() {
+                                return #{None};
+                            }
+                            let body = ::std::mem::replace(response.body_mut(), #{SdkBody}::taken());
+                            #{Some}(#{Ok}(#{Output}::erase(#{ItemStream}::new(
+                                body,
+                                ${parser.fieldName(itemsMember).dq()},
+                                #{parse_item},
+                                parse_output,
+                            ))))
+                        }
+
+                        fn deserialize_nonstreaming(&self, response: &#{HttpResponse}) -> #{OutputOrError} {
+                            ${operationType.namespace}::${operationType.name}ResponseDeserializer.deserialize_nonstreaming(response)
+                        }
+                    }
+
+                    fn parse_output(body: &[u8]) -> #{Result}<#{OperationOutput}, #{BoxError}> {
+                        let builder = #{parse_output}(body, #{OutputBuilder}::default())?;
+                        #{Ok}(#{build_output})
+                    }
+
+                    let input = self.inner.build().map_err(#{SdkError}::construction_failure)?;
+                    let runtime_plugins = #{Operation}::operation_runtime_plugins(
+                        self.handle.runtime_plugins.clone(),
+                        &self.handle.conf,
+                        self.config_override,
+                    )
+                    .with_operation_plugin(#{StaticRuntimePlugin}::new().with_config({
+                        let mut layer = #{Layer}::new("${operationType.name}ItemStream");
+                        layer.store_put(#{SharedResponseDeserializer}::new(ItemStreamResponseDeserializer));
+                        layer.freeze()
+                    }));
+                    let map_err = |err: #{SdkError}<#{Error}, #{HttpResponse}>| {
+                        err.map_service_error(|err| {
+                            err.downcast::<#{OperationError}>().expect("correct error type")
+                        })
+                    };
+                    let context = #{Operation}::orchestrate_with_stop_point(&runtime_plugins, input, #{StopPoint}::None)
+                        .await
+                        .map_err(map_err)?;
+                    let output = context.finalize().map_err(map_err)?;
+                    #{Ok}(output.downcast::<#{ItemStream}<#{Item}, #{OperationOutput}>>().expect("correct output type"))
+                }
+                """,
+                *scope,
+                "build_output" to
+                    codegenContext.builderInstantiator().finalizeBuilder(
+                        "builder",
+                        outputShape,
+                        writable { rustTemplate("#{BoxError}::from", *scope) },
+                    ),
+                "DeserializeResponse" to runtimeApi.resolve("client::ser_de::DeserializeResponse"),
+                "Error" to runtimeApi.resolve("client::interceptors::context::Error"),
+                "Item" to symbolProvider.toSymbol(itemShape),
+                "ItemStream" to itemStream,
+                "Layer" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::Layer"),
+                "Output" to runtimeApi.resolve("client::interceptors::context::Output"),
+                "OutputBuilder" to symbolProvider.symbolForBuilder(outputShape),
+                "OutputOrError" to runtimeApi.resolve("client::interceptors::context::OutputOrError"),
+                "SharedResponseDeserializer" to runtimeApi.resolve("client::ser_de::SharedResponseDeserializer"),
+                "StaticRuntimePlugin" to runtimeApi.resolve("client::runtime_plugin::StaticRuntimePlugin"),
+                "StopPoint" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::orchestrator::StopPoint"),
+                "parse_item" to parser.payloadParser(itemsShape.member),
+                "parse_output" to outputParser,
             )
         }
+    }
 
-    private fun handleEventStreamInitialResponse(): Writable? {
+    /**
+     * Renders `send_with_reconnect` for operations with an output event stream, which reconnects the event stream
+     * according to a `ReconnectPolicy` when its connection is interrupted. Reconnecting re-sends the request, so the
+     * input must be cloneable, which rules out operations with an input event stream or another streaming member.
+     */
+    private fun sendWithReconnect(): Writable {
+        if (!operation.isOutputEventStream(model) || operation.isInputEventStream(model) ||
+            inputShape.hasStreamingMember(model)
+        ) {
+            return writable {}
+        }
+        val eventStreamMemberName =
+            symbolProvider.toMemberName(outputShape.members().first { it.isEventStream(model) })
+        return writable {
+            rustTemplate(
+                """
+                /// Sends the request and returns the response, reconnecting the `$eventStreamMemberName` event stream
+                /// according to the given policy when its connection is interrupted.
+                ///
+                /// To reconnect, the request is sent again with the input of this builder, after it has been adjusted
+                /// by the policy's `adjust_input` callback, and events are received from the new event stream.
+                /// The other members of the output of a reconnected request are discarded.
+                pub async fn send_with_reconnect(
+                    self,
+                    policy: #{ReconnectPolicy}<#{OperationInput}>,
+                ) -> #{Result}<#{OperationOutput}, #{SdkError}<#{OperationError}, #{HttpResponse}>> {
+                    let input = self.inner.clone().build().map_err(#{SdkError}::construction_failure)?;
+                    let handle = self.handle.clone();
+                    let config_override = self.config_override.clone();
+                    let mut output = self.send().await?;
+                    output.$eventStreamMemberName = output.$eventStreamMemberName.with_reconnect(policy, input, move |input| {
+                        let runtime_plugins = #{Operation}::operation_runtime_plugins(
+                            handle.runtime_plugins.clone(),
+                            &handle.conf,
+                            config_override.clone(),
+                        );
+                        let sleep_impl = handle.conf.sleep_impl();
+                        #{Box}::pin(async move {
+                            let output: #{Result}<#{OperationOutput}, #{SdkError}<#{OperationError}, #{HttpResponse}>> =
+                                async { #{epilogue:W} }.await;
+                            #{Ok}(output?.$eventStreamMemberName)
+                        })
+                    });
+                    #{Ok}(output)
+                }
+                """,
+                *scope,
+                "epilogue" to handleEventStreamInitialResponse("sleep_impl")!!,
+            )
+        }
+    }
+
+    /**
+     * Renders the code that sends the request of an operation with an output event stream. [sleepImpl] is the
+     * expression of the sleep implementation that enforces the idle timeout of the event stream.
+     */
+    private fun handleEventStreamInitialResponse(sleepImpl: String): Writable? {
         if (!operation.isOutputEventStream(model)) {
             return null
         }
@@ -202,6 +413,7 @@ class FluentBuilderGenerator(
                         input,
                     )
                     .await?;
+                output.$eventStreamMemberName.set_sleep_impl($sleepImpl);
 
                 // Converts any error encountered beyond this point into an `SdkError` response error
                 // with an `HttpResponse`. However, since we have already exited the `orchestrate`
@@ -23,6 +23,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
 import software.amazon.smithy.rust.codegen.core.rustlang.RustReservedWords
 import software.amazon.smithy.rust.codegen.core.rustlang.RustType
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
 import software.amazon.smithy.rust.codegen.core.rustlang.asArgumentType
 import software.amazon.smithy.rust.codegen.core.rustlang.deprecatedShape
 import software.amazon.smithy.rust.codegen.core.rustlang.docLink
@@ -182,7 +183,13 @@ class FluentClientGenerator(
             val fnName = clientOperationFnName(operation, symbolProvider)
             val moduleName = clientOperationModuleName(operation, symbolProvider)
 
-            val privateModule = RustModule.private(moduleName, parent = ClientRustModule.client)
+            val privateModule =
+                RustModule.new(
+                    moduleName,
+                    Visibility.PRIVATE,
+                    parent = ClientRustModule.client,
+                    additionalAttributes = listOfNotNull(codegenContext.operationFeatureGate(operation)),
+                )
             crate.withModule(privateModule) {
                 rustBlock("impl super::Client") {
                     val fullPath = operation.fullyQualifiedFluentBuilder(symbolProvider)
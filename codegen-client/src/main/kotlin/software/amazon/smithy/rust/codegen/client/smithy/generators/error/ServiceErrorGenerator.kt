@@ -76,7 +76,11 @@ class ServiceErrorGenerator(
             // Every operation error can be converted into service::Error
             operations.forEach { operationShape ->
                 // operation errors
-                renderImplFrom(symbolProvider.symbolForOperationError(operationShape), operationShape.errors)
+                renderImplFrom(
+                    symbolProvider.symbolForOperationError(operationShape),
+                    operationShape.errors,
+                    codegenContext.operationFeatureGate(operationShape),
+                )
             }
             // Every waiter error can be converted into service::Error
             if (operations.any { it.hasTrait<WaitableTrait>() }) {
@@ -169,8 +173,10 @@ class ServiceErrorGenerator(
     private fun RustWriter.renderImplFrom(
         errorSymbol: Symbol,
         errors: List<ShapeId>,
+        featureGate: Attribute? = null,
     ) {
         val operationErrors = errors.map { model.expectShape(it) }
+        featureGate?.render(this)
         rustBlock(
             "impl<R> From<#T<#T, R>> for Error where R: Send + Sync + std::fmt::Debug + 'static",
             sdkError,
@@ -199,6 +205,7 @@ class ServiceErrorGenerator(
             }
         }
 
+        featureGate?.render(this)
         rustBlock("impl From<#T> for Error", errorSymbol) {
             rustBlock("fn from(err: #T) -> Self", errorSymbol) {
                 rustBlock("match err") {
@@ -11,6 +11,8 @@ import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCus
 import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.isEmpty
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
@@ -108,11 +110,12 @@ class ResponseDeserializerGenerator(
                 if (!response.status().is_success() && response.status().as_u16() != $successCode) || force_error {
                     return #{None};
                 }
-                #{Some}(#{type_erase_result}(#{parse_streaming_response}(response)))
+                #{Some}(#{type_erase_result}(#{parse_streaming_response}(response)#{mutate_output}))
             }
             """,
             *codegenScope,
             "parse_streaming_response" to parserGenerator.parseStreamingResponseFn(operationShape, customizations),
+            "mutate_output" to mutateOutputFromResponse(operationShape, customizations),
             "BeforeParseResponse" to
                 writable {
                     writeCustomizations(customizations, OperationSection.BeforeParseResponse(customizations, "response", "force_error", body = null))
@@ -151,13 +154,14 @@ class ResponseDeserializerGenerator(
             let parse_result = if !success && status != $successCode || force_error {
                 #{parse_error}(status, headers, body)
             } else {
-                #{parse_response}(status, headers, body)
+                #{parse_response}(status, headers, body)#{mutate_output}
             };
             #{type_erase_result}(parse_result)
             """,
             *codegenScope,
             "parse_error" to parserGenerator.parseErrorFn(operationShape, customizations),
             "parse_response" to parserGenerator.parseResponseFn(operationShape, customizations),
+            "mutate_output" to mutateOutputFromResponse(operationShape, customizations),
             "BeforeParseResponse" to
                 writable {
                     writeCustomizations(customizations, OperationSection.BeforeParseResponse(customizations, "response", "force_error", "body"))
@@ -165,6 +169,24 @@ class ResponseDeserializerGenerator(
         )
     }
 
+    private fun mutateOutputFromResponse(
+        operationShape: OperationShape,
+        customizations: List<OperationCustomization>,
+    ): Writable {
+        val mutations =
+            writable {
+                writeCustomizations(
+                    customizations,
+                    OperationSection.MutateOutputFromResponse(customizations, operationShape, "response", "output"),
+                )
+            }
+        return writable {
+            if (!mutations.isEmpty()) {
+                rustTemplate(".map(|mut output| { #{mutations} output })", "mutations" to mutations)
+            }
+        }
+    }
+
     private fun typeEraseResult(): RuntimeType =
         ProtocolFunctions.crossOperationFn("type_erase_result") { fnName ->
             rustTemplate(
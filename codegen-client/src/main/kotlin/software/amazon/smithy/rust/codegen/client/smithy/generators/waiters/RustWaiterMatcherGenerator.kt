@@ -84,6 +84,7 @@ class RustWaiterMatcherGenerator(
                     else -> ""
                 }
             docs("Matcher union: " + Node.printJson(matcher.toNode()))
+            codegenContext.operationFeatureGate(inputShape)?.render(this)
             rustBlockTemplate(
                 "pub(crate) fn $fnName(${inputArg}_result: #{Result}<&#{Output}, &#{Error}>) -> bool",
                 *scope,
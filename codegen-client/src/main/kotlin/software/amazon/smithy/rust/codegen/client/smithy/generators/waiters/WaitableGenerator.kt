@@ -88,6 +88,7 @@ class WaitableGenerator(
                     for (spec in op.waiters) {
                         val waiterDocs = spec.waiter.documentation.orNull() ?: "Wait for `${spec.waiterName.toSnakeCase()}`"
                         docs(waiterDocs)
+                        codegenContext.operationFeatureGate(op.shape)?.render(this)
                         renderWaiterFnDeclaration(spec)
                         rust(";")
                     }
@@ -97,6 +98,7 @@ class WaitableGenerator(
             rustBlockTemplate("impl Waiters for Client") {
                 for (op in operations) {
                     for (spec in op.waiters) {
+                        codegenContext.operationFeatureGate(op.shape)?.render(this)
                         renderWaiterFnDeclaration(spec)
                         rustTemplate(
                             "{ #{FluentBuilder}::new(self.handle.clone()) }",
@@ -116,11 +118,15 @@ class WaitableGenerator(
         )
     }
 
-    private fun waiterModule(waiterName: String): RustModule =
+    private fun waiterModule(
+        waiterName: String,
+        operation: OperationShape,
+    ): RustModule =
         RustModule.public(
             RustReservedWords.escapeIfNeeded(waiterName.toSnakeCase(), EscapeFor.ModuleName),
             ClientRustModule.waiters,
             documentationOverride = "Supporting types for the `${waiterName.toSnakeCase()}` waiter.",
+            additionalAttributes = listOfNotNull(codegenContext.operationFeatureGate(operation)),
         )
 
     private fun waiterFluentBuilder(
@@ -129,7 +135,7 @@ class WaitableGenerator(
         operation: OperationShape,
     ): RuntimeType {
         val builderName = "${waiterName.toPascalCase()}FluentBuilder"
-        val waiterModule = waiterModule(waiterName)
+        val waiterModule = waiterModule(waiterName, operation)
         return RuntimeType.forInlineFun(builderName, waiterModule) {
             FluentBuilderGenerator(
                 codegenContext,
@@ -170,6 +176,7 @@ private class WaiterFluentBuilderConfig(
                 RuntimeType.smithyRuntimeApiClient(runtimeConfig)
                     .resolve("client::waiters::error::WaiterError"),
             "WaiterOrchestrator" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::waiters::WaiterOrchestrator"),
+            "WaitOptions" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::waiters::WaitOptions"),
             "attach_waiter_tracing_span" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::waiters::attach_waiter_tracing_span"),
         )
 
@@ -201,6 +208,12 @@ private class WaiterFluentBuilderConfig(
             rustTemplate(
                 """
                 pub async fn wait(self, max_wait: #{Duration}) -> #{Result}<#{FinalPollAlias}, #{WaiterErrorAlias}> {
+                    self.wait_with_options(#{WaitOptions}::new(max_wait)).await
+                }
+
+                /// Wait with the given options, which can override the modeled minimum and maximum delay
+                /// between polls and the backoff strategy, and can observe the waiter's progress after every poll.
+                pub async fn wait_with_options(self, options: #{WaitOptions}) -> #{Result}<#{FinalPollAlias}, #{WaiterErrorAlias}> {
                     let input = self.inner.build()
                         .map_err(#{WaiterError}::construction_failure)?;
                     let runtime_plugins = #{Operation}::operation_runtime_plugins(
@@ -224,9 +237,11 @@ private class WaiterFluentBuilderConfig(
                         }
                     };
                     let orchestrator = #{WaiterOrchestrator}::builder()
-                        .min_delay(#{Duration}::from_secs(${waiter.minDelay}))
-                        .max_delay(#{Duration}::from_secs(${waiter.maxDelay}))
-                        .max_wait(max_wait)
+                        .min_delay(options.get_min_delay().unwrap_or(#{Duration}::from_secs(${waiter.minDelay})))
+                        .max_delay(options.get_max_delay().unwrap_or(#{Duration}::from_secs(${waiter.maxDelay})))
+                        .max_wait(options.get_max_wait())
+                        .backoff(options.get_backoff().clone())
+                        .progress_callback(options.get_progress_callback().cloned())
                         .time_source(time_source)
                         .sleep_impl(sleep_impl)
                         .acceptor(acceptor)
@@ -0,0 +1,249 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy
+
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.params.ParameterizedTest
+import org.junit.jupiter.params.provider.ValueSource
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.ClientAdditionalSettings
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+
+class CowStringInputSymbolProviderTest {
+    private val model =
+        """
+        namespace com.example
+        use aws.protocols#restJson1
+
+        @restJson1
+        service HelloService {
+            operations: [SayHello, SendNote],
+            version: "1"
+        }
+
+        @http(method: "POST", uri: "/hello/{name}")
+        @optionalAuth
+        @paginated(inputToken: "nextToken", outputToken: "nextToken")
+        operation SayHello {
+            input := {
+                @required
+                @httpLabel
+                name: String
+
+                @httpQuery("lang")
+                language: String
+
+                @httpHeader("x-mood")
+                mood: Mood
+
+                @httpQuery("nextToken")
+                nextToken: String
+
+                @idempotencyToken
+                @httpHeader("x-token")
+                token: String
+
+                message: String
+            }
+            output := {
+                nextToken: String
+            }
+        }
+
+        @http(method: "PUT", uri: "/note")
+        @optionalAuth
+        operation SendNote {
+            input := {
+                @httpPayload
+                note: String
+            }
+        }
+
+        enum Mood {
+            HAPPY = "happy"
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `input string members accept static strings without allocating`() {
+        val params =
+            IntegrationTestParams(
+                additionalSettings = ClientAdditionalSettings.builder().cowStringInputs().toObjectNode(),
+            )
+        clientIntegrationTest(model, params) { codegenContext, rustCrate ->
+            val rc = codegenContext.runtimeConfig
+            rustCrate.testModule {
+                unitTest("cow_string_members") {
+                    rustTemplate(
+                        """
+                        use crate::operation::say_hello::SayHelloInput;
+
+                        let input = SayHelloInput::builder()
+                            .name("world")
+                            .language(#{String}::from("en"))
+                            .message("hello")
+                            .next_token(#{String}::from("token"))
+                            .build()
+                            .unwrap();
+                        let name: &#{Option}<#{CowStr}> = &input.name;
+                        assert!(name.as_ref().unwrap().is_borrowed());
+                        assert!(!input.language.as_ref().unwrap().is_borrowed());
+                        assert_eq!(#{Some}("hello"), input.message());
+
+                        // Members that generated code assigns a `String` to are unchanged
+                        let _: &#{Option}<#{String}> = &input.next_token;
+                        let _: &#{Option}<#{String}> = &input.token;
+                        """,
+                        *RuntimeType.preludeScope,
+                        "CowStr" to RuntimeType.cowStr(rc),
+                    )
+                }
+                unitTest("cow_string_members_are_restored_from_resume_tokens") {
+                    rustTemplate(
+                        """
+                        use crate::operation::say_hello::SayHelloOutput;
+
+                        let config = crate::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(#{StaticReplayClient}::new(vec![]))
+                            .build();
+                        let client = crate::Client::from_conf(config);
+                        let page = SayHelloOutput::builder().next_token("next").build();
+                        let token = client
+                            .say_hello()
+                            .language("en")
+                            .message(#{String}::from("hello"))
+                            .into_paginator()
+                            .resume_token(&page)
+                            .expect("more pages");
+                        let token: #{ResumeToken} = token.to_string().parse().unwrap();
+                        let resumed = client.say_hello().into_paginator().resume_from(&token).expect("valid token");
+                        // The resumed paginator doesn't borrow from the token
+                        let expected = token.clone();
+                        drop(token);
+                        assert_eq!(#{Some}(expected), resumed.resume_token(&page));
+                        """,
+                        *RuntimeType.preludeScope,
+                        "ResumeToken" to RuntimeType.smithyTypes(rc).resolve("pagination::ResumeToken"),
+                        "StaticReplayClient" to
+                            CargoDependency.smithyRuntime(rc)
+                                .toDevDependency().withFeature("test-util").toType()
+                                .resolve("client::http::test_util::StaticReplayClient"),
+                    )
+                }
+                tokioTest("cow_string_members_are_serialized") {
+                    rustTemplate(
+                        """
+                        let (http_client, rx) = #{capture_request}(#{None});
+                        let config = crate::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        let client = crate::Client::from_conf(config);
+                        let _ = client
+                            .say_hello()
+                            .name("world")
+                            .language("en")
+                            .message(#{String}::from("hello"))
+                            .send()
+                            .await;
+                        let request = rx.expect_request();
+                        assert_eq!("http://localhost:1234/hello/world?lang=en", request.uri());
+                        assert_eq!(br##"{"message":"hello"}"##, request.body().bytes().unwrap());
+                        """,
+                        *RuntimeType.preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(rc),
+                    )
+                }
+                tokioTest("cow_string_payload_is_serialized") {
+                    rustTemplate(
+                        """
+                        let (http_client, rx) = #{capture_request}(#{None});
+                        let config = crate::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        let client = crate::Client::from_conf(config);
+                        let _ = client.send_note().note("a raw note").send().await;
+                        let request = rx.expect_request();
+                        assert_eq!(b"a raw note", request.body().bytes().unwrap());
+                        """,
+                        *RuntimeType.preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(rc),
+                    )
+                }
+            }
+        }
+    }
+
+    @ParameterizedTest
+    @ValueSource(strings = ["aws.protocols#awsJson1_0", "aws.protocols#awsQuery", "aws.protocols#restXml", "smithy.protocols#rpcv2Cbor"])
+    fun `cow string members are serialized by other protocols`(protocol: String) {
+        val protocolName = protocol.substringAfter("#")
+        val httpTrait = if (protocolName == "restXml") "@http(method: \"POST\", uri: \"/hello\")" else ""
+        val model =
+            """
+            namespace com.example
+            use $protocol
+
+            @$protocolName
+            @xmlNamespace(uri: "http://example.com")
+            service HelloService {
+                operations: [SayHello],
+                version: "1"
+            }
+
+            $httpTrait
+            @optionalAuth
+            operation SayHello {
+                input := {
+                    message: String
+                    note: String
+                }
+            }
+            """.asSmithyModel(smithyVersion = "2")
+        val params =
+            IntegrationTestParams(
+                additionalSettings = ClientAdditionalSettings.builder().cowStringInputs().toObjectNode(),
+            )
+        clientIntegrationTest(model, params) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                tokioTest("cow_string_members_are_serialized") {
+                    rustTemplate(
+                        """
+                        let (http_client, rx) = #{capture_request}(#{None});
+                        let config = crate::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        let client = crate::Client::from_conf(config);
+                        let _ = client
+                            .say_hello()
+                            .message("static message")
+                            .note(#{String}::from("owned note"))
+                            .send()
+                            .await;
+                        let request = rx.expect_request();
+                        let body = request.body().bytes().unwrap();
+                        let contains = |value: &[u8]| body.windows(value.len()).any(|window| window == value);
+                        assert!(contains(b"static"), "{body:?}");
+                        assert!(contains(b"owned"), "{body:?}");
+                        """,
+                        *RuntimeType.preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(codegenContext.runtimeConfig),
+                    )
+                }
+            }
+        }
+    }
+}
@@ -333,6 +333,39 @@ class AuthDecoratorTest {
                     """,
                     *codegenScope(codegenContext.runtimeConfig),
                 )
+
+                Attribute.TokioTest.render(this)
+                rustTemplate(
+                    """
+                    async fn register_custom_auth_scheme_with_default_resolver() {
+                        let http_client = #{StaticReplayClient}::new(
+                            vec![#{ReplayEvent}::new(
+                                http::Request::builder()
+                                    .uri("http://localhost:1234/SomeOperation/customidentitydata")
+                                    .body(#{SdkBody}::empty())
+                                    .unwrap(),
+                                http::Response::builder().status(200).body(#{SdkBody}::empty()).unwrap(),
+                            )],
+                        );
+                        let config = $moduleName::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .push_auth_scheme(CustomAuthScheme::default())
+                            .auth_scheme_resolver(
+                                $moduleName::config::auth::DefaultAuthSchemeResolver::default()
+                                    .with_preferred_auth_scheme(AuthSchemeId::new("custom")),
+                            )
+                            .http_client(http_client.clone())
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+                        let _ = client.some_operation()
+                            .send()
+                            .await
+                            .expect("success");
+                        http_client.assert_requests_match(&[]);
+                    }
+                    """,
+                    *codegenScope(codegenContext.runtimeConfig),
+                )
             }
         }
     }
@@ -0,0 +1,180 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.testutil.ClientAdditionalSettings
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+import software.amazon.smithy.rust.codegen.core.util.runCommand
+
+class InputConstraintValidationDecoratorTest {
+    private val model =
+        """
+        namespace com.example
+        use aws.protocols#awsJson1_0
+
+        @awsJson1_0
+        service HelloService {
+            operations: [SayHello],
+            version: "1"
+        }
+
+        @optionalAuth
+        operation SayHello { input: TestInput }
+
+        @length(min: 1, max: 3)
+        list Names {
+            member: String
+        }
+
+        @sensitive
+        @range(max: 100)
+        integer Secret
+
+        structure TestInput {
+            @length(min: 2, max: 8)
+            @pattern("^[a-z]+$")
+            name: String,
+
+            @range(min: 1, max: 10)
+            count: Integer,
+
+            @range(min: 0.5)
+            ratio: Double,
+
+            names: Names,
+
+            secret: Secret,
+
+            @length(max: 4)
+            data: Blob,
+
+            // Bounds that every value satisfies aren't checked
+            @length(min: 0, max: 2)
+            tags: Names,
+
+            @range(min: -128, max: 127)
+            small: Byte,
+
+            @range(min: 0, max: 2147483647)
+            nonNegative: Integer,
+
+            @pattern("^\\p{L}+$")
+            label: String,
+
+            // Lookarounds can't be compiled by the `regex` crate, so this pattern is left to the service
+            @pattern("^(?!admin).*$")
+            user: String,
+        }
+        """.asSmithyModel()
+
+    private val validationEnabled =
+        IntegrationTestParams(
+            command = { path ->
+                "cargo test --features behavior-version-latest".runCommand(path)
+                // Bounds that can't be violated must not be rendered as absurd comparisons
+                "cargo clippy --all-targets --features behavior-version-latest -- -D warnings".runCommand(path)
+            },
+            additionalSettings = ClientAdditionalSettings.builder().validateInputConstraints().toObjectNode(),
+        )
+
+    @Test
+    fun `input builders report every constraint violation`() {
+        clientIntegrationTest(model, validationEnabled) { codegenContext, rustCrate ->
+            val moduleName = codegenContext.moduleUseName()
+            rustCrate.integrationTest("input_constraint_validation") {
+                rust(
+                    """
+                    use $moduleName::operation::say_hello::SayHelloInput;
+                    use aws_smithy_types::error::operation::Constraint;
+
+                    ##[test]
+                    fn valid_input_builds() {
+                        SayHelloInput::builder()
+                            .name("hello")
+                            .count(10)
+                            .ratio(0.5)
+                            .names("a")
+                            .secret(100)
+                            .data($moduleName::primitives::Blob::new("abcd"))
+                            .build()
+                            .expect("valid");
+                        // Unset members aren't validated
+                        SayHelloInput::builder().build().expect("valid");
+                        SayHelloInput::builder()
+                            .tags("a")
+                            .small(i8::MIN)
+                            .non_negative(i32::MAX)
+                            .label("héllo")
+                            .user("admin")
+                            .build()
+                            .expect("valid");
+                    }
+
+                    ##[test]
+                    fn invalid_input_lists_all_violations() {
+                        let err = SayHelloInput::builder()
+                            .name("Ã")
+                            .count(11)
+                            .ratio(0.25)
+                            .set_names(Some(vec![]))
+                            .secret(101)
+                            .data($moduleName::primitives::Blob::new("abcde"))
+                            .non_negative(-1)
+                            .label("a1")
+                            .build()
+                            .expect_err("invalid");
+                        let violations: Vec<_> = err
+                            .violations()
+                            .iter()
+                            .map(|v| (v.field(), v.constraint(), v.details()))
+                            .collect();
+                        assert_eq!(
+                            vec![
+                                ("name", Constraint::Length, "length 1 is less than 2"),
+                                ("name", Constraint::Pattern, "value doesn't match the pattern `^[a-z]+$`"),
+                                ("count", Constraint::Range, "value 11 is greater than 10"),
+                                ("ratio", Constraint::Range, "value 0.25 is less than 0.5"),
+                                ("names", Constraint::Length, "length 0 is less than 1"),
+                                ("secret", Constraint::Range, "the value is greater than 100"),
+                                ("data", Constraint::Length, "length 5 is greater than 4"),
+                                ("non_negative", Constraint::Range, "value -1 is less than 0"),
+                                ("label", Constraint::Pattern, "value doesn't match the pattern `^\\p{L}+$`"),
+                            ],
+                            violations
+                        );
+                    }
+                    """,
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `input builders are not validated by default`() {
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            val moduleName = codegenContext.moduleUseName()
+            rustCrate.integrationTest("input_constraints_not_validated") {
+                rust(
+                    """
+                    ##[test]
+                    fn invalid_input_builds() {
+                        $moduleName::operation::say_hello::SayHelloInput::builder()
+                            .name("")
+                            .count(11)
+                            .build()
+                            .expect("not validated");
+                    }
+                    """,
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import com.moandjiezana.toml.Toml
+import io.kotest.matchers.collections.shouldContain
+import io.kotest.matchers.shouldBe
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.assertThrows
+import software.amazon.smithy.codegen.core.CodegenException
+import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.testutil.ClientAdditionalSettings
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+
+class OperationFeatureGatesDecoratorTest {
+    private val model =
+        """
+        namespace com.example
+        use aws.protocols#restJson1
+        use smithy.waiters#waitable
+
+        @restJson1
+        service HelloService {
+            operations: [SayHello, ListGreetings],
+            version: "1"
+        }
+
+        @http(method: "POST", uri: "/hello")
+        @optionalAuth
+        operation SayHello {
+            input := {
+                greeting: Greeting
+                signature: Signature
+            }
+            output := { greeting: Greeting }
+            errors: [Rejected]
+        }
+
+        @http(method: "GET", uri: "/greetings")
+        @readonly
+        @optionalAuth
+        @paginated(inputToken: "nextToken", outputToken: "nextToken", items: "greetings")
+        @waitable(
+            GreetingsExist: {
+                acceptors: [
+                    {
+                        state: "success"
+                        matcher: { output: { path: "length(greetings) > `0`", expected: "true", comparator: "booleanEquals" } }
+                    }
+                ]
+            }
+        )
+        operation ListGreetings {
+            input := {
+                @httpQuery("nextToken")
+                nextToken: String
+            }
+            output := {
+                nextToken: String
+                greetings: Greetings
+                // Serialized by `SayHello`, but only deserialized by `ListGreetings`
+                signature: Signature
+                // Required output members are error corrected by the deserializer
+                @required
+                count: Integer
+            }
+            errors: [Rejected]
+        }
+
+        list Greetings {
+            member: Greeting
+        }
+
+        structure Greeting {
+            message: String
+        }
+
+        structure Signature {
+            name: String
+        }
+
+        @error("client")
+        structure Rejected {
+            message: String
+        }
+        """.asSmithyModel()
+
+    private fun params(
+        features: String,
+        groups: Map<String, List<String>> = emptyMap(),
+    ) = IntegrationTestParams(
+        cargoCommand = "cargo test --no-default-features --features behavior-version-latest,$features",
+        additionalSettings =
+            ClientAdditionalSettings.builder().operationFeatureGates(groups = groups).toObjectNode(),
+    )
+
+    @Test
+    fun `all operations are enabled by default`() {
+        clientIntegrationTest(model, params(ALL_OPERATIONS_FEATURE)) { codegenContext, rustCrate ->
+            val moduleName = codegenContext.moduleUseName()
+            rustCrate.integrationTest("all_operations") {
+                rust(
+                    """
+                    use $moduleName::client::Waiters;
+
+                    ##[allow(dead_code)]
+                    fn builds(client: $moduleName::Client) {
+                        let _ = client.say_hello();
+                        let _ = client.list_greetings().into_paginator();
+                        let _ = client.wait_until_greetings_exist();
+                    }
+
+                    ##[test]
+                    fn error_conversions() {
+                        let err = $moduleName::operation::say_hello::SayHelloError::unhandled("boom");
+                        let _: $moduleName::Error = err.into();
+                    }
+                    """,
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `operations can be compiled individually`() {
+        val testDir =
+            clientIntegrationTest(model, params("operation-say-hello")) { codegenContext, rustCrate ->
+                val moduleName = codegenContext.moduleUseName()
+                rustCrate.integrationTest("single_operation") {
+                    rust(
+                        """
+                        ##[allow(dead_code)]
+                        fn builds(client: $moduleName::Client) {
+                            let _ = client.say_hello();
+                        }
+                        """,
+                    )
+                }
+            }
+
+        val features = Toml().read(testDir.resolve("Cargo.toml").toFile()).getTable("features").toMap()
+        features["operation-say-hello"] shouldBe emptyList<String>()
+        features["operation-list-greetings"] shouldBe emptyList<String>()
+        features[ALL_OPERATIONS_FEATURE] shouldBe listOf("operation-list-greetings", "operation-say-hello")
+        (features["default"] as List<*>) shouldContain ALL_OPERATIONS_FEATURE
+    }
+
+    @Test
+    fun `operation groups enable their operations`() {
+        val groups = mapOf("greetings" to listOf("ListGreetings"))
+        clientIntegrationTest(model, params("greetings", groups)) { codegenContext, rustCrate ->
+            val moduleName = codegenContext.moduleUseName()
+            rustCrate.integrationTest("operation_group") {
+                rust(
+                    """
+                    use $moduleName::client::Waiters;
+
+                    ##[allow(dead_code)]
+                    fn builds(client: $moduleName::Client) {
+                        let _ = client.list_greetings().into_paginator();
+                        let _ = client.wait_until_greetings_exist();
+                    }
+                    """,
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `operations required by customizations are not gated`() {
+        val decorator =
+            object : ClientCodegenDecorator {
+                override val name: String = "RequiresSayHello"
+                override val order: Byte = 0
+
+                override fun requiredOperations(codegenContext: ClientCodegenContext): List<ShapeId> =
+                    listOf(ShapeId.from("com.example#SayHello"))
+            }
+        clientIntegrationTest(
+            model,
+            params("operation-list-greetings"),
+            additionalDecorators = listOf(decorator),
+        ) { codegenContext, rustCrate ->
+            val moduleName = codegenContext.moduleUseName()
+            rustCrate.integrationTest("required_operation") {
+                rust(
+                    """
+                    ##[allow(dead_code)]
+                    fn builds(client: $moduleName::Client) {
+                        let _ = client.say_hello();
+                        let _ = client.list_greetings();
+                    }
+                    """,
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `operation groups must reference operations of the service`() {
+        val groups = mapOf("greetings" to listOf("SayGoodbye"))
+        val exception =
+            assertThrows<CodegenException> {
+                clientIntegrationTest(model, params("greetings", groups))
+            }
+        assert(exception.message!!.contains("SayGoodbye"))
+    }
+
+    @Test
+    fun `operation groups can't be named after existing cargo features`() {
+        listOf("default", "rt-tokio", "behavior-version-latest", ALL_OPERATIONS_FEATURE, "operation-say-hello")
+            .forEach { group ->
+                val exception =
+                    assertThrows<CodegenException> {
+                        clientIntegrationTest(model, params(group, mapOf(group to listOf("SayHello"))))
+                    }
+                assert(exception.message!!.contains("`$group`"))
+            }
+    }
+}
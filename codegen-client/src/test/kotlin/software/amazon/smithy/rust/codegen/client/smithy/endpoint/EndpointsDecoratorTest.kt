@@ -12,6 +12,8 @@ import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.testutil.ClientAdditionalSettings
 import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
@@ -157,7 +159,10 @@ class EndpointsDecoratorTest {
             clientIntegrationTest(
                 model,
                 // Just run integration tests.
-                IntegrationTestParams(command = { "cargo test --all-features --test *".runCommand(it) }),
+                IntegrationTestParams(
+                    command = { "cargo test --all-features --test *".runCommand(it) },
+                    additionalSettings = ClientAdditionalSettings.builder().endpointRulesTrace().toObjectNode(),
+                ),
             ) { clientCodegenContext, rustCrate ->
                 rustCrate.integrationTest("endpoint_params_test") {
                     val moduleName = clientCodegenContext.moduleUseName()
@@ -314,6 +319,145 @@ class EndpointsDecoratorTest {
                                 .withFeature("rt-tokio").toType().resolve("rt::sleep::TokioSleep"),
                     )
                 }
+                rustCrate.integrationTest("endpoint_params_override_test") {
+                    val moduleName = clientCodegenContext.moduleUseName()
+                    captureEndpoint(moduleName)(this)
+                    Attribute.TokioTest.render(this)
+                    rustTemplate(
+                        """
+                        async fn override_endpoint_params_for_one_operation() {
+                            use #{NeverClient};
+                            use $moduleName::{Client, Config};
+
+                            let capture = CaptureEndpoint::default();
+                            let config = Config::builder()
+                                .behavior_version_latest()
+                                .http_client(NeverClient::new())
+                                .interceptor(capture.clone())
+                                .a_bool_param(false)
+                                .build();
+                            let client = Client::from_conf(config);
+
+                            let _ = client.test_operation().bucket("bucket-name").send().await;
+                            assert_eq!(
+                                Some("https://www.us-east-2.example.com"),
+                                capture.0.lock().unwrap().as_deref()
+                            );
+
+                            let _ = client
+                                .test_operation()
+                                .bucket("bucket-name")
+                                .customize()
+                                .endpoint_params(|params| params.region("us-west-2"))
+                                .send()
+                                .await;
+                            assert_eq!(
+                                Some("https://www.us-west-2.example.com"),
+                                capture.0.lock().unwrap().as_deref()
+                            );
+                        }
+                        """,
+                        "NeverClient" to
+                            CargoDependency.smithyHttpClientTestUtil(clientCodegenContext.runtimeConfig)
+                                .toType().resolve("test_util::NeverClient"),
+                    )
+                }
+                rustCrate.integrationTest("endpoint_params_modifier_test") {
+                    val moduleName = clientCodegenContext.moduleUseName()
+                    captureEndpoint(moduleName)(this)
+                    Attribute.TokioTest.render(this)
+                    rustTemplate(
+                        """
+                        async fn modify_endpoint_params_based_on_input() {
+                            use #{NeverClient};
+                            use $moduleName::{
+                                config::endpoint::EndpointParamsModifier, operation::test_operation::TestOperationInput,
+                                Client, Config,
+                            };
+
+                            let capture = CaptureEndpoint::default();
+                            let config = Config::builder()
+                                .behavior_version_latest()
+                                .http_client(NeverClient::new())
+                                .interceptor(capture.clone())
+                                .a_bool_param(false)
+                                .endpoint_params_modifier(EndpointParamsModifier::new(|input, params| {
+                                    let bucket = input
+                                        .downcast_ref::<TestOperationInput>()
+                                        .and_then(|input| input.bucket());
+                                    match bucket {
+                                        Some(bucket) if bucket.starts_with("west-") => params.region("us-west-2"),
+                                        _ => params,
+                                    }
+                                }))
+                                .build();
+                            let client = Client::from_conf(config);
+
+                            let _ = client.test_operation().bucket("east-bucket").send().await;
+                            assert_eq!(
+                                Some("https://www.us-east-2.example.com"),
+                                capture.0.lock().unwrap().as_deref()
+                            );
+
+                            let _ = client.test_operation().bucket("west-bucket").send().await;
+                            assert_eq!(
+                                Some("https://www.us-west-2.example.com"),
+                                capture.0.lock().unwrap().as_deref()
+                            );
+
+                            // per-operation overrides take precedence over modifiers
+                            let _ = client
+                                .test_operation()
+                                .bucket("west-bucket")
+                                .customize()
+                                .endpoint_params(|params| params.region("eu-west-1"))
+                                .send()
+                                .await;
+                            assert_eq!(
+                                Some("https://www.eu-west-1.example.com"),
+                                capture.0.lock().unwrap().as_deref()
+                            );
+                        }
+                        """,
+                        "NeverClient" to
+                            CargoDependency.smithyHttpClientTestUtil(clientCodegenContext.runtimeConfig)
+                                .toType().resolve("test_util::NeverClient"),
+                    )
+                }
+                rustCrate.integrationTest("endpoint_rules_trace_test") {
+                    val moduleName = clientCodegenContext.moduleUseName()
+                    Attribute.Test.render(this)
+                    rustTemplate(
+                        """
+                        fn trace_endpoint_rules() {
+                            use $moduleName::config::endpoint::{DefaultResolver, Params, RuleEvaluation};
+
+                            let resolver = DefaultResolver::new();
+                            let params = Params::builder().region("us-east-2").a_bool_param(true).build().unwrap();
+                            let (result, trace) = resolver.resolve_endpoint_with_trace(&params);
+                            assert!(result.is_err());
+                            assert!(trace.error().is_some());
+                            match trace.evaluations() {
+                                [RuleEvaluation::ConditionNotMet { rule, condition }] => {
+                                    assert_eq!("1", *rule);
+                                    assert!(condition.contains("booleanEquals"), "{condition}");
+                                }
+                                other => panic!("unexpected evaluations: {other:?}"),
+                            }
+                            assert!(trace.to_string().contains("rule 1: condition not met"), "{trace}");
+
+                            let params = Params::builder().region("us-east-2").a_bool_param(false).build().unwrap();
+                            let (result, trace) = resolver.resolve_endpoint_with_trace(&params);
+                            assert_eq!("https://www.us-east-2.example.com", result.unwrap().url());
+                            assert_eq!(
+                                &[RuleEvaluation::EndpointRuleMatched { rule: "1" }],
+                                trace.evaluations()
+                            );
+                            assert_eq!("https://www.us-east-2.example.com", trace.endpoint().unwrap().url());
+                        }
+                        """,
+                    )
+                }
             }
         // the model has an intentionally failing test—ensure it fails
         val failure = shouldThrow<CommandError> { "cargo test".runWithWarnings(testDir) }
@@ -321,4 +465,38 @@ class EndpointsDecoratorTest {
         failure.output shouldContain "https://failingtest.com"
         "cargo clippy".runWithWarnings(testDir)
     }
+
+    /** Interceptor that records the URL of the resolved endpoint and stops the request before it's sent */
+    private fun captureEndpoint(moduleName: String) =
+        writable {
+            rustTemplate(
+                """
+                use aws_smithy_runtime_api::box_error::BoxError;
+                use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+                use aws_smithy_types::config_bag::ConfigBag;
+                use aws_smithy_types::endpoint::Endpoint;
+                use std::sync::{Arc, Mutex};
+                use $moduleName::config::{interceptors::BeforeTransmitInterceptorContextRef, Intercept};
+
+                ##[derive(Clone, Debug, Default)]
+                struct CaptureEndpoint(Arc<Mutex<Option<String>>>);
+                impl Intercept for CaptureEndpoint {
+                    fn name(&self) -> &'static str {
+                        "CaptureEndpoint"
+                    }
+
+                    fn read_before_transmit(
+                        &self,
+                        _context: &BeforeTransmitInterceptorContextRef<'_>,
+                        _runtime_components: &RuntimeComponents,
+                        cfg: &mut ConfigBag,
+                    ) -> Result<(), BoxError> {
+                        let endpoint = cfg.load::<Endpoint>().expect("endpoint set in config");
+                        *self.0.lock().unwrap() = Some(endpoint.url().to_string());
+                        Err("don't send the request".into())
+                    }
+                }
+                """,
+            )
+        }
 }
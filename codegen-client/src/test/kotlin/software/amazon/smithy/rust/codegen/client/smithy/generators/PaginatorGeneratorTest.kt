@@ -12,7 +12,10 @@ import software.amazon.smithy.model.transform.ModelTransformer
 import software.amazon.smithy.rust.codegen.client.smithy.traits.IsTruncatedPaginatorTrait
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
 import software.amazon.smithy.rust.codegen.core.util.letIf
@@ -103,4 +106,143 @@ internal class PaginatorGeneratorTest {
             }
         }
     }
+
+    @Test
+    fun `paginators can be resumed from a token`() {
+        clientIntegrationTest(model) { clientCodegenContext, rustCrate ->
+            val moduleName = clientCodegenContext.moduleUseName()
+            rustCrate.integrationTest("paginators_resume") {
+                rustTemplate(
+                    """
+                    use $moduleName::operation::paginated_list::PaginatedListOutput;
+                    use $moduleName::types::Inner;
+
+                    ##[test]
+                    fn resume_token_round_trip() {
+                        let config = $moduleName::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(#{StaticReplayClient}::new(vec![]))
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+                        let paginator = client.paginated_list().max_results(10).into_paginator();
+                        let page = |token: &str| {
+                            PaginatedListOutput::builder()
+                                .inner(
+                                    Inner::builder()
+                                        .token(token)
+                                        .set_items(Some(vec![]))
+                                        .set_map_items(Some(Default::default()))
+                                        .build()
+                                        .unwrap(),
+                                )
+                                .build()
+                        };
+                        assert!(paginator.resume_token(&page("")).is_none(), "last page");
+
+                        let token = paginator.resume_token(&page("next")).expect("more pages");
+                        assert_eq!("PaginatedList", token.operation());
+                        assert_eq!("next", token.next_token());
+                        assert_eq!(Some("10"), token.input("maxResults"));
+
+                        let token: #{ResumeToken} = token.to_string().parse().unwrap();
+                        // The input snapshot is restored onto the resumed paginator
+                        let resumed = client.paginated_list().into_paginator().resume_from(&token).expect("valid token");
+                        assert_eq!(Some(token.clone()), resumed.resume_token(&page("next")));
+
+                        let err = client.paginated_map().into_paginator().resume_from(&token).err().expect("wrong operation");
+                        assert_eq!("resume token for `PaginatedList` can't be used to paginate `PaginatedMap`", err.to_string());
+                    }
+                    """,
+                    "ResumeToken" to RuntimeType.smithyTypes(clientCodegenContext.runtimeConfig).resolve("pagination::ResumeToken"),
+                    "StaticReplayClient" to
+                        CargoDependency.smithyRuntime(clientCodegenContext.runtimeConfig)
+                            .toDevDependency().withFeature("test-util").toType()
+                            .resolve("client::http::test_util::StaticReplayClient"),
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `paginators yield page metadata`() {
+        clientIntegrationTest(model) { clientCodegenContext, rustCrate ->
+            val moduleName = clientCodegenContext.moduleUseName()
+            val runtimeConfig = clientCodegenContext.runtimeConfig
+            rustCrate.integrationTest("paginators_page_metadata") {
+                rustTemplate(
+                    """
+                    use $moduleName::operation::paginated_list::paginator::PaginatedListPaginator;
+                    use $moduleName::operation::paginated_list::{PaginatedListError, PaginatedListOutput};
+
+                    ##[allow(dead_code)]
+                    fn pages_with_metadata(
+                        paginator: PaginatedListPaginator,
+                    ) -> #{PaginationStream}<Result<(PaginatedListOutput, #{PageMetadata}), #{SdkError}<PaginatedListError, #{HttpResponse}>>> {
+                        paginator.send_with_metadata()
+                    }
+                    """,
+                    "PaginationStream" to
+                        RuntimeType.smithyAsync(runtimeConfig).resolve("future::pagination_stream::PaginationStream"),
+                    "PageMetadata" to
+                        RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::pagination::PageMetadata"),
+                    "SdkError" to RuntimeType.sdkError(runtimeConfig),
+                    "HttpResponse" to
+                        RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::orchestrator::HttpResponse"),
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `paginators can share a rate limiter`() {
+        clientIntegrationTest(model) { clientCodegenContext, rustCrate ->
+            val moduleName = clientCodegenContext.moduleUseName()
+            rustCrate.integrationTest("paginators_rate_limit") {
+                rustTemplate(
+                    """
+                    use $moduleName::operation::paginated_list::paginator::PaginatedListPaginator;
+                    use $moduleName::operation::paginated_map::paginator::PaginatedMapPaginator;
+
+                    ##[allow(dead_code)]
+                    fn rate_limited(
+                        list: PaginatedListPaginator,
+                        map: PaginatedMapPaginator,
+                    ) -> (PaginatedListPaginator, PaginatedMapPaginator) {
+                        let limiter = #{PageRateLimiter}::new(5.0).with_burst(10);
+                        (list.rate_limiter(limiter.clone()), map.rate_limiter(limiter))
+                    }
+                    """,
+                    "PageRateLimiter" to
+                        RuntimeType.smithyAsync(clientCodegenContext.runtimeConfig)
+                            .resolve("future::pagination_stream::rate_limit::PageRateLimiter"),
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `item paginators accept an error policy`() {
+        clientIntegrationTest(model) { clientCodegenContext, rustCrate ->
+            val moduleName = clientCodegenContext.moduleUseName()
+            rustCrate.integrationTest("paginators_error_policy") {
+                rustTemplate(
+                    """
+                    use $moduleName::operation::paginated_list::paginator::{PaginatedListPaginator, PaginatedListPaginatorItems};
+
+                    ##[allow(dead_code)]
+                    fn with_error_policies(paginator: PaginatedListPaginator) -> [PaginatedListPaginatorItems; 3] {
+                        [
+                            paginator.clone().items().error_policy(#{ItemErrorPolicy}::fail_fast()),
+                            paginator.clone().items().error_policy(#{ItemErrorPolicy}::retry_page(3, std::time::Duration::from_secs(1))),
+                            paginator.items().error_policy(#{ItemErrorPolicy}::stop_with_callback(|_err| {})),
+                        ]
+                    }
+                    """,
+                    "ItemErrorPolicy" to
+                        RuntimeType.smithyAsync(clientCodegenContext.runtimeConfig)
+                            .resolve("future::pagination_stream::error_policy::ItemErrorPolicy"),
+                )
+            }
+        }
+    }
 }
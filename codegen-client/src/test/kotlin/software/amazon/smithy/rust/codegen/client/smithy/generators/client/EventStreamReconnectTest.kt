@@ -0,0 +1,148 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.generators.client
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+
+class EventStreamReconnectTest {
+    private val model =
+        """
+        namespace com.example
+        use aws.protocols#restJson1
+
+        @restJson1
+        service HelloService {
+            operations: [Subscribe],
+            version: "1"
+        }
+
+        @http(method: "GET", uri: "/subscribe")
+        @optionalAuth
+        operation Subscribe {
+            input := {
+                @httpQuery("position")
+                position: Integer
+            }
+            output := {
+                @httpPayload
+                events: Events
+            }
+        }
+
+        @streaming
+        union Events {
+            Greeting: Greeting
+        }
+
+        structure Greeting {
+            message: String
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `interrupted event streams are reconnected with the adjusted input`() {
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            val rc = codegenContext.runtimeConfig
+            val moduleName = codegenContext.moduleUseName()
+            rustCrate.integrationTest("event_stream_reconnect") {
+                Attribute.TokioTest.render(this)
+                rustTemplate(
+                    """
+                    async fn reconnects_interrupted_event_stream() {
+                        use $moduleName::operation::subscribe::SubscribeInput;
+                        use $moduleName::primitives::event_stream::{Header, HeaderValue, Message, ReconnectPolicy};
+                        use $moduleName::types::Events;
+                        use std::sync::atomic::{AtomicI32, Ordering};
+                        use std::sync::Arc;
+
+                        fn event(message: &str) -> Vec<u8> {
+                            let message = Message::new(format!("{{\"message\":\"{}\"}}", message))
+                                .add_header(Header::new(":message-type", HeaderValue::String("event".into())))
+                                .add_header(Header::new(":event-type", HeaderValue::String("Greeting".into())))
+                                .add_header(Header::new(":content-type", HeaderValue::String("application/json".into())));
+                            let mut buffer = Vec::new();
+                            #{write_message_to}(&message, &mut buffer).unwrap();
+                            buffer
+                        }
+
+                        fn replay(body: Vec<u8>) -> #{ReplayEvent} {
+                            #{ReplayEvent}::new(
+                                http::Request::builder()
+                                    .uri("http://localhost:1234/subscribe")
+                                    .body(#{SdkBody}::empty())
+                                    .unwrap(),
+                                http::Response::builder().status(200).body(#{SdkBody}::from(body)).unwrap(),
+                            )
+                        }
+
+                        // The first stream is interrupted in the middle of its second event
+                        let mut interrupted = event("one");
+                        interrupted.extend_from_slice(&event("two")[..10]);
+                        let http_client = #{StaticReplayClient}::new(vec![replay(interrupted), replay(event("two"))]);
+                        let config = $moduleName::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(http_client.clone())
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+
+                        let received = Arc::new(AtomicI32::new(0));
+                        let policy = ReconnectPolicy::<SubscribeInput>::new(1)
+                            .initial_backoff(std::time::Duration::from_millis(1))
+                            .adjust_input({
+                                let received = received.clone();
+                                move |input, attempt| {
+                                    assert_eq!(1, attempt.attempt());
+                                    input.position = Some(received.load(Ordering::SeqCst));
+                                }
+                            });
+                        let mut output = client
+                            .subscribe()
+                            .position(0)
+                            .send_with_reconnect(policy)
+                            .await
+                            .expect("success");
+
+                        let mut messages = Vec::new();
+                        while let Some(event) = output.events.recv().await.expect("no errors") {
+                            match event {
+                                Events::Greeting(greeting) => messages.push(greeting.message.unwrap()),
+                                _ => panic!("unexpected event"),
+                            }
+                            received.fetch_add(1, Ordering::SeqCst);
+                        }
+                        assert_eq!(vec!["one".to_string(), "two".to_string()], messages);
+
+                        let uris: Vec<_> = http_client.actual_requests().map(|req| req.uri().to_string()).collect();
+                        assert_eq!(
+                            vec![
+                                "http://localhost:1234/subscribe?position=0".to_string(),
+                                "http://localhost:1234/subscribe?position=1".to_string(),
+                            ],
+                            uris
+                        );
+                    }
+                    """,
+                    "ReplayEvent" to
+                        CargoDependency.smithyHttpClientTestUtil(rc).toType()
+                            .resolve("test_util::ReplayEvent"),
+                    "StaticReplayClient" to
+                        CargoDependency.smithyHttpClientTestUtil(rc).toType()
+                            .resolve("test_util::StaticReplayClient"),
+                    "SdkBody" to RuntimeType.sdkBody(rc),
+                    "write_message_to" to
+                        CargoDependency.smithyEventStream(rc).toType().resolve("frame::write_message_to"),
+                )
+            }
+        }
+    }
+}
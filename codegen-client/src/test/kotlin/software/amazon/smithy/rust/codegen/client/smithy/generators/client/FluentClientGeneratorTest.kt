@@ -10,8 +10,10 @@ import org.junit.jupiter.api.Test
 import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.client.testutil.testSymbolProvider
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
 import software.amazon.smithy.rust.codegen.core.util.lookup
@@ -123,6 +125,90 @@ class FluentClientGeneratorTest {
         }
     }
 
+    @Test
+    fun `paginated JSON operations deserialize items as they're received`() {
+        val model =
+            """
+            namespace com.example
+            use aws.protocols#awsJson1_0
+
+            @awsJson1_0
+            service HelloService {
+                operations: [ListFoos],
+                version: "1"
+            }
+
+            @readonly
+            @optionalAuth
+            @paginated(inputToken: "nextToken", outputToken: "nextToken", items: "foos")
+            operation ListFoos {
+                input := { nextToken: String }
+                output := {
+                    foos: Foos
+                    nextToken: String
+                }
+            }
+
+            list Foos {
+                member: Foo
+            }
+
+            structure Foo {
+                name: String
+            }
+            """.asSmithyModel(smithyVersion = "2")
+
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            rustCrate.integrationTest("send_with_item_stream") {
+                val moduleName = codegenContext.moduleUseName()
+                val runtimeConfig = codegenContext.runtimeConfig
+                Attribute.TokioTest.render(this)
+                rustTemplate(
+                    """
+                    async fn items_are_deserialized_before_the_output() {
+                        let http_client = #{StaticReplayClient}::new(vec![#{ReplayEvent}::new(
+                            http::Request::builder()
+                                .uri("http://localhost:1234/")
+                                .body(#{SdkBody}::empty())
+                                .unwrap(),
+                            http::Response::builder()
+                                .status(200)
+                                .body(#{SdkBody}::from(r##"{"foos":[{"name":"a"},{"name":"b"}],"nextToken":"next"}"##))
+                                .unwrap(),
+                        )]);
+                        let config = $moduleName::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(http_client)
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+
+                        let mut items: $moduleName::operation::list_foos::builders::ItemStream<_, _> =
+                            client.list_foos().send_with_item_stream().await.unwrap();
+                        let mut names = Vec::new();
+                        while let Some(foo) = items.next().await.unwrap() {
+                            names.push(foo.name.unwrap());
+                        }
+                        assert_eq!(vec!["a", "b"], names);
+
+                        let output = items.into_output().await.unwrap();
+                        assert_eq!(Some("next"), output.next_token());
+                        assert!(output.foos().is_empty());
+                    }
+                    """,
+                    "ReplayEvent" to
+                        CargoDependency.smithyRuntime(runtimeConfig)
+                            .toDevDependency().withFeature("test-util").toType()
+                            .resolve("client::http::test_util::ReplayEvent"),
+                    "SdkBody" to RuntimeType.sdkBody(runtimeConfig),
+                    "StaticReplayClient" to
+                        CargoDependency.smithyRuntime(runtimeConfig)
+                            .toDevDependency().withFeature("test-util").toType()
+                            .resolve("client::http::test_util::StaticReplayClient"),
+                )
+            }
+        }
+    }
+
     @Test
     fun `dead-code warning should not be issued when a service has no operations`() {
         val model =
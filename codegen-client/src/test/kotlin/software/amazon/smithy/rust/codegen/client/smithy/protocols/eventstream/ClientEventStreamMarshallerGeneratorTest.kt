@@ -26,6 +26,7 @@ import software.amazon.smithy.rust.codegen.core.testutil.EventStreamUnmarshallTe
 import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
 import software.amazon.smithy.rust.codegen.core.testutil.testModule
 import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.lookup
 import java.util.stream.Stream
@@ -37,6 +38,32 @@ class ClientEventStreamMarshallerGeneratorTest {
         clientIntegrationTest(testCase.model) { codegenContext, rustCrate ->
             rustCrate.testModule {
                 writeMarshallTestCases(codegenContext, testCase, optionalBuilderInputs = false)
+
+                unitTest("unknown_message") {
+                    rust(
+                        """
+                        use aws_smithy_types::{UnknownUnionValue, UnknownUnionVariant};
+
+                        // unknown events are re-sent as the message they were received in
+                        let received = Message::new(&b"hello, world!"[..])
+                            .add_header(Header::new(":message-type", str_header("event")))
+                            .add_header(Header::new(":event-type", str_header("NewUnmodeledMessageType")))
+                            .add_header(Header::new(":content-type", str_header("application/octet-stream")))
+                            .add_header(Header::new("custom-header", HeaderValue::Int32(5)));
+                        let event = TestStream::Unknown(UnknownUnionVariant::new(
+                            "NewUnmodeledMessageType",
+                            UnknownUnionValue::EventStreamMessage(received.clone()),
+                        ));
+                        let message = crate::event_stream_serde::TestStreamMarshaller::new()
+                            .marshall(event)
+                            .expect("unknown events with their message can be marshalled");
+                        assert_eq!(received, message);
+
+                        let event = TestStream::Unknown(UnknownUnionVariant::without_value("NewUnmodeledMessageType"));
+                        assert!(crate::event_stream_serde::TestStreamMarshaller::new().marshall(event).is_err());
+                        """,
+                    )
+                }
             }
         }
     }
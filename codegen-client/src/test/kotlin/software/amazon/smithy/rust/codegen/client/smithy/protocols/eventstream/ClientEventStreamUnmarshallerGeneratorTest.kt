@@ -46,7 +46,14 @@ class ClientEventStreamUnmarshallerGeneratorTest {
                     let message = msg("event", "NewUnmodeledMessageType", "application/octet-stream", b"hello, world!");
                     let result = $generator::new().unmarshall(&message);
                     assert!(result.is_ok(), "expected ok, got: {:?}", result);
-                    assert!(expect_event(result.unwrap()).is_unknown());
+                    let event = expect_event(result.unwrap());
+                    let unknown = event.as_unknown().expect("unknown event");
+                    assert_eq!("NewUnmodeledMessageType", unknown.name());
+                    // the message is retained with all of its headers
+                    assert_eq!(
+                        Some(&message),
+                        unknown.value().and_then(|value| value.as_event_stream_message())
+                    );
                     """,
                 )
 
@@ -71,6 +78,71 @@ class ClientEventStreamUnmarshallerGeneratorTest {
                     }
                     """,
                 )
+
+                tokioTest("initial_response_is_available") {
+                    val initialResponsePayload =
+                        if (testCase.eventStreamMessageContentType == "application/cbor") {
+                            EventStreamTestModels.base64EncodeJson("{}")
+                        } else {
+                            "{}"
+                        }
+                    rustTemplate(
+                        """
+                        use aws_smithy_eventstream::frame::{MarshallMessage, write_message_to};
+                        use aws_smithy_http::event_stream::EventStreamSender;
+                        use aws_smithy_types::body::SdkBody;
+
+                        let initial_response = msg(
+                            "event",
+                            "initial-response",
+                            ${testCase.eventStreamMessageContentType.dq()},
+                            ${testCase.generateRustPayloadInitializer(initialResponsePayload)},
+                        );
+                        let event = TestStream::MessageWithString(
+                            MessageWithString::builder().data("hello, world!").build(),
+                        );
+                        let mut body = vec![];
+                        write_message_to(&initial_response, &mut body).unwrap();
+                        let event_message = crate::event_stream_serde::TestStreamMarshaller::new()
+                            .marshall(event.clone())
+                            .unwrap();
+                        write_message_to(&event_message, &mut body).unwrap();
+
+                        let (http_client, _r) = #{capture_request}(Some(
+                            #{http_1x}::Response::builder()
+                                .status(200)
+                                .body(SdkBody::from(body))
+                                .unwrap(),
+                        ));
+                        let conf = crate::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(http_client)
+                            .behavior_version_latest()
+                            .build();
+                        let client = crate::Client::from_conf(conf);
+                        let stream = #{futures_util}::stream::iter(vec![Ok(event)]);
+                        let mut output = client
+                            .test_stream_op()
+                            .value(EventStreamSender::from(stream))
+                            .send()
+                            .await
+                            .unwrap();
+
+                        // The initial response is available regardless of whether the protocol deserializes
+                        // output members from it, and it is never delivered as an event
+                        assert_eq!(Some(&initial_response), output.value.initial_response());
+                        match output.value.recv().await.unwrap() {
+                            Some(TestStream::MessageWithString(message)) => {
+                                assert_eq!("hello, world!", message.data().unwrap())
+                            }
+                            otherwise => panic!("matched on unexpected event {otherwise:?}"),
+                        }
+                        """,
+                        "capture_request" to RuntimeType.captureRequest(codegenContext.runtimeConfig),
+                        "futures_util" to CargoDependency.FuturesUtil.toType(),
+                        "http_1x" to CargoDependency.Http1x.toType(),
+                    )
+                }
             }
         }
     }
@@ -203,7 +275,17 @@ class ClientEventStreamUnmarshallerGeneratorTest {
                                     },
                                 initialResponseAssertion =
                                     writable {
-                                        rust("assert_eq!(${rpcEventStreamTestCase.expectedInInitialResponse}, response.test_string());")
+                                        rust(
+                                            """
+                                            assert_eq!(${rpcEventStreamTestCase.expectedInInitialResponse}, response.test_string());
+                                            assert!(response.value.initial_response().is_some());
+                                            let latest = response.latest_initial_response().unwrap().expect("initial response was sent");
+                                            assert_eq!(
+                                                latest.get_test_string().as_deref(),
+                                                ::std::option::Option::from(response.test_string()),
+                                            );
+                                            """,
+                                        )
                                     },
                             )
                         }
@@ -276,7 +358,12 @@ class ClientEventStreamUnmarshallerGeneratorTest {
                                     },
                                 initialResponseAssertion =
                                     writable {
-                                        rust("assert!(response.test_string().is_none());")
+                                        rust(
+                                            """
+                                            assert!(response.test_string().is_none());
+                                            assert!(response.value.initial_response().is_none());
+                                            """,
+                                        )
                                     },
                             )
                         }
@@ -107,9 +107,19 @@ class InlineDependency(
         fun eventReceiver(runtimeConfig: RuntimeConfig) =
             forInlineableRustFile(
                 "event_receiver",
+                CargoDependency.smithyAsync(runtimeConfig),
                 CargoDependency.smithyHttp(runtimeConfig),
                 CargoDependency.smithyRuntimeApi(runtimeConfig),
                 CargoDependency.smithyTypes(runtimeConfig),
+                CargoDependency.Tracing,
+            )
+
+        fun jsonItemStream(runtimeConfig: RuntimeConfig) =
+            forInlineableRustFile(
+                "json_item_stream",
+                CargoDependency.smithyJson(runtimeConfig),
+                CargoDependency.smithyRuntimeApi(runtimeConfig),
+                CargoDependency.smithyTypes(runtimeConfig),
             )
 
         fun jsonErrors(runtimeConfig: RuntimeConfig) =
@@ -387,6 +397,8 @@ data class CargoDependency(
 
         fun smithyJson(runtimeConfig: RuntimeConfig) = runtimeConfig.smithyRuntimeCrate("smithy-json")
 
+        fun smithyObservability(runtimeConfig: RuntimeConfig) = runtimeConfig.smithyRuntimeCrate("smithy-observability")
+
         fun smithyProtocolTestHelpers(runtimeConfig: RuntimeConfig) =
             runtimeConfig.smithyRuntimeCrate("smithy-protocol-test", scope = DependencyScope.Dev)
 
@@ -174,7 +174,17 @@ sealed class RustType {
         override val namespace = runtimeType.namespace
     }
 
-    data class Opaque(override val name: kotlin.String, override val namespace: kotlin.String? = null) : RustType()
+    /**
+     * A type that isn't known to the code generator.
+     *
+     * [deref] is the type an owned type dereferences to when the type is used like a `String`: it's accepted as
+     * `impl Into<T>` by setters and returned as `&deref` by getters.
+     */
+    data class Opaque(
+        override val name: kotlin.String,
+        override val namespace: kotlin.String? = null,
+        val deref: RustType? = null,
+    ) : RustType()
 
     /**
      * Represents application of a Rust type with the given arguments.
@@ -209,6 +219,7 @@ fun RustType.implInto(fullyQualified: Boolean = true): String {
 fun RustType.asArgumentType(fullyQualified: Boolean = true): String {
     return when (this) {
         is RustType.String, is RustType.Box -> this.implInto(fullyQualified)
+        is RustType.Opaque -> if (deref != null) this.implInto(fullyQualified) else this.render(fullyQualified)
         else -> this.render(fullyQualified)
     }
 }
@@ -217,6 +228,7 @@ fun RustType.asArgumentType(fullyQualified: Boolean = true): String {
 fun RustType.asArgumentValue(name: String) =
     when (this) {
         is RustType.String, is RustType.Box -> "$name.into()"
+        is RustType.Opaque -> if (deref != null) "$name.into()" else name
         else -> name
     }
 
@@ -348,6 +360,7 @@ fun RustType.asDeref(): RustType =
         is RustType.Box -> RustType.Reference(null, member)
         is RustType.String -> RustType.Opaque("str")
         is RustType.Vec -> RustType.Slice(member)
+        is RustType.Opaque -> deref ?: this
         else -> this
     }
 
@@ -357,6 +370,7 @@ fun RustType.isDeref(): Boolean =
         is RustType.Box -> true
         is RustType.String -> true
         is RustType.Vec -> true
+        is RustType.Opaque -> deref != null
         else -> false
     }
 
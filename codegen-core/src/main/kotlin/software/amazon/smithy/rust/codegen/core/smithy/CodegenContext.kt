@@ -10,6 +10,7 @@ import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.shapes.ServiceShape
 import software.amazon.smithy.model.shapes.Shape
 import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderInstantiator
 import software.amazon.smithy.rust.codegen.core.smithy.generators.StructSettings
 
@@ -93,4 +94,18 @@ abstract class CodegenContext(
     fun structSettings() = StructSettings(settings.codegenConfig.flattenCollectionAccessors)
 
     abstract fun builderInstantiator(): BuilderInstantiator
+
+    /**
+     * Returns an attribute that gates code specific to the operation [shape] belongs to behind a cargo feature, or
+     * `null` if operations aren't feature gated.
+     *
+     * [shape] may be an operation, its synthetic input or output structure, or a member of either of those.
+     */
+    open fun operationFeatureGate(shape: Shape): Attribute? = null
+
+    /**
+     * Returns the attributes that gate the serializers and deserializers of [shape] behind the cargo features of the
+     * operations that use them. Unlike [operationFeatureGate], [shape] may be shared between operations.
+     */
+    open fun serDeFeatureGates(shape: Shape): List<Attribute> = listOfNotNull(operationFeatureGate(shape))
 }
@@ -38,16 +38,19 @@ const val CODEGEN_SETTINGS = "codegen"
  *
  * [formatTimeoutSeconds]: Timeout for running cargo fmt at the end of code generation
  * [debugMode]: Generate comments in the generated code indicating where code was generated from
+ * [nonFiniteFloats]: How the serializers and parsers of document bodies handle NaN and infinities
  */
 open class CoreCodegenConfig(
     open val formatTimeoutSeconds: Int = DEFAULT_FORMAT_TIMEOUT_SECONDS,
     open val debugMode: Boolean = DEFAULT_DEBUG_MODE,
     open val flattenCollectionAccessors: Boolean = DEFAULT_FLATTEN_MODE,
+    open val nonFiniteFloats: NonFiniteFloatHandling = DEFAULT_NON_FINITE_FLOATS,
 ) {
     companion object {
         const val DEFAULT_FORMAT_TIMEOUT_SECONDS = 20
         const val DEFAULT_DEBUG_MODE = false
         const val DEFAULT_FLATTEN_MODE = false
+        val DEFAULT_NON_FINITE_FLOATS = NonFiniteFloatHandling.ENCODE
 
         fun fromNode(node: Optional<ObjectNode>): CoreCodegenConfig =
             if (node.isPresent) {
@@ -59,6 +62,10 @@ open class CoreCodegenConfig(
                     flattenCollectionAccessors =
                         node.get()
                             .getBooleanMemberOrDefault("flattenCollectionAccessors", DEFAULT_FLATTEN_MODE),
+                    nonFiniteFloats =
+                        node.get().getStringMember("nonFiniteFloats")
+                            .map { NonFiniteFloatHandling.fromValue(it.value) }
+                            .orElse(DEFAULT_NON_FINITE_FLOATS),
                 )
             } else {
                 CoreCodegenConfig(
@@ -69,6 +76,34 @@ open class CoreCodegenConfig(
     }
 }
 
+/**
+ * How generated serializers and parsers handle NaN and infinities, mirroring
+ * `aws_smithy_types::primitive::NonFiniteFloatHandling`.
+ *
+ * [ENCODE] is the default behavior of the runtime crates, so the generated code doesn't refer to the handling.
+ */
+enum class NonFiniteFloatHandling(val value: String, private val variant: String) {
+    ENCODE("encode", "Encode"),
+    ERROR("error", "Error"),
+    CLAMP("clamp", "Clamp"),
+    ;
+
+    /** The Rust value of this handling, or `null` for [ENCODE] */
+    fun rustValue(runtimeConfig: RuntimeConfig): RuntimeType? =
+        when (this) {
+            ENCODE -> null
+            else -> RuntimeType.smithyTypes(runtimeConfig).resolve("primitive::NonFiniteFloatHandling::$variant")
+        }
+
+    companion object {
+        fun fromValue(value: String): NonFiniteFloatHandling =
+            values().find { it.value == value }
+                ?: throw CodegenException(
+                    "`nonFiniteFloats` must be one of ${values().joinToString { "`${it.value}`" }}, found `$value`",
+                )
+    }
+}
+
 /**
  * [CoreRustSettings] contains crate settings that are _common to all_  smithy-rs plugins.
  *
@@ -420,14 +420,25 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
 
         fun byteStream(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("byte_stream::ByteStream")
 
+        fun cowStr(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("cow_str::CowStr")
+
         fun dateTime(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("DateTime")
 
         fun document(runtimeConfig: RuntimeConfig): RuntimeType = smithyTypes(runtimeConfig).resolve("Document")
 
         fun format(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("date_time::Format")
 
+        fun unknownUnionVariant(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("UnknownUnionVariant")
+
+        fun unknownUnionValue(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("UnknownUnionValue")
+
         fun retryErrorKind(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("retry::ErrorKind")
 
+        fun sensitive(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("sensitive::Sensitive")
+
+        fun sensitiveIsRevealed(runtimeConfig: RuntimeConfig) =
+            smithyTypes(runtimeConfig).resolve("sensitive::is_revealed")
+
         fun eventStreamReceiver(runtimeConfig: RuntimeConfig): RuntimeType =
             smithyHttp(runtimeConfig).resolve("event_stream::Receiver")
 
@@ -435,6 +446,14 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
             forInlineDependency(InlineDependency.eventReceiver(runtimeConfig))
                 .resolve("EventReceiver")
 
+        fun eventStreamReconnectPolicy(runtimeConfig: RuntimeConfig) =
+            forInlineDependency(InlineDependency.eventReceiver(runtimeConfig))
+                .resolve("ReconnectPolicy")
+
+        fun eventStreamReconnectAttempt(runtimeConfig: RuntimeConfig) =
+            forInlineDependency(InlineDependency.eventReceiver(runtimeConfig))
+                .resolve("ReconnectAttempt")
+
         fun eventStreamSender(runtimeConfig: RuntimeConfig): RuntimeType =
             smithyHttp(runtimeConfig).resolve("event_stream::EventStreamSender")
 
@@ -118,12 +118,16 @@ fun pubUseSmithyPrimitivesEventStream(
                 pub use #{Header};
                 pub use #{HeaderValue};
                 pub use #{Message};
+                pub use #{ReconnectAttempt};
+                pub use #{ReconnectPolicy};
                 pub use #{StrBytes};
                 """,
                 "EventReceiver" to eventReceiver(rc),
                 "Header" to RuntimeType.smithyTypes(rc).resolve("event_stream::Header"),
                 "HeaderValue" to RuntimeType.smithyTypes(rc).resolve("event_stream::HeaderValue"),
                 "Message" to RuntimeType.smithyTypes(rc).resolve("event_stream::Message"),
+                "ReconnectAttempt" to RuntimeType.eventStreamReconnectAttempt(rc),
+                "ReconnectPolicy" to RuntimeType.eventStreamReconnectPolicy(rc),
                 "StrBytes" to RuntimeType.smithyTypes(rc).resolve("str_bytes::StrBytes"),
             )
         }
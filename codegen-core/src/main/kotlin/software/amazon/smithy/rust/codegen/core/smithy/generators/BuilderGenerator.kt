@@ -46,11 +46,9 @@ import software.amazon.smithy.rust.codegen.core.smithy.isOptional
 import software.amazon.smithy.rust.codegen.core.smithy.makeOptional
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrait
-import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.letIf
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
 import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 
@@ -67,6 +65,9 @@ sealed class BuilderSection(name: String) : Section(name) {
     /** Hook to add additional methods to the builder */
     data class AdditionalMethods(override val shape: StructureShape) : BuilderSection("AdditionalMethods")
 
+    /** Hook to add code at the start of the `build()` method, e.g. to validate the builder's fields */
+    data class BeforeBuild(override val shape: StructureShape) : BuilderSection("BeforeBuild")
+
     /** Hook to add additional fields to the `build()` method */
     data class AdditionalFieldsInBuild(override val shape: StructureShape) : BuilderSection("AdditionalFieldsInBuild")
 
@@ -251,6 +252,7 @@ class BuilderGenerator(
             }
         }
         implBlockWriter.rustBlockTemplate("pub fn build(self) -> $returnType", *preludeScope) {
+            writeCustomizations(customizations, BuilderSection.BeforeBuild(shape))
             conditionalBlockTemplate("#{Ok}(", ")", conditional = fallibleBuilder, *preludeScope) {
                 // If a wrapper is specified, use the `::new` associated function to construct the wrapper
                 coreBuilder(this)
@@ -382,19 +384,14 @@ class BuilderGenerator(
                 members.forEach { member ->
                     val memberName = symbolProvider.toMemberName(member)
                     // If the struct is marked sensitive all fields get redacted, otherwise each field is determined on its own
-                    val fieldValue =
-                        if (shape.shouldRedact(model)) {
-                            REDACTION
-                        } else {
-                            member.redactIfNecessary(
-                                model,
-                                "self.$memberName",
-                            )
-                        }
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
-                    )
+                    if (shape.shouldRedact(model) || member.shouldRedact(model)) {
+                        rustTemplate(
+                            "formatter.field(${memberName.dq()}, &#{Sensitive}(&self.$memberName));",
+                            "Sensitive" to RuntimeType.sensitive(symbolProvider.config.runtimeConfig),
+                        )
+                    } else {
+                        rust("formatter.field(${memberName.dq()}, &self.$memberName);")
+                    }
                 }
                 writeCustomizations(customizations, BuilderSection.AdditionalDebugFields(shape, "formatter"))
                 rust("formatter.finish()")
@@ -330,19 +330,25 @@ open class EnumGenerator(
     /**
      * Manually implement the `Debug` trait for the enum if marked as sensitive.
      *
-     * It prints the redacted text regardless of the variant it is asked to print.
+     * It prints the redacted text regardless of the variant it is asked to print, unless sensitive data
+     * is revealed at runtime, in which case it prints the string value of the variant.
      */
     private fun RustWriter.renderDebugImplForSensitiveEnum() {
         rustTemplate(
             """
             impl #{Debug} for ${context.enumName} {
                 fn fmt(&self, f: &mut #{StdFmt}::Formatter<'_>) -> #{StdFmt}::Result {
-                    ::std::write!(f, $REDACTION)
+                    if #{is_revealed}() {
+                        #{Debug}::fmt(self.as_str(), f)
+                    } else {
+                        ::std::write!(f, $REDACTION)
+                    }
                 }
             }
             """,
             "Debug" to RuntimeType.Debug,
             "StdFmt" to RuntimeType.stdFmt,
+            "is_revealed" to RuntimeType.sensitiveIsRevealed(symbolProvider.config.runtimeConfig),
         )
     }
 }
@@ -24,6 +24,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.isDeref
 import software.amazon.smithy.rust.codegen.core.rustlang.render
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustBlock
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.stripOuter
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
@@ -33,10 +34,8 @@ import software.amazon.smithy.rust.codegen.core.smithy.customize.writeCustomizat
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
 import software.amazon.smithy.rust.codegen.core.smithy.renamedFrom
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
-import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.getTrait
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
 import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 
 /** StructureGenerator customization sections */
@@ -108,19 +107,14 @@ open class StructureGenerator(
                 members.forEach { member ->
                     val memberName = symbolProvider.toMemberName(member)
                     // If the struct is marked sensitive all fields get redacted, otherwise each field is determined on its own
-                    val fieldValue =
-                        if (shape.shouldRedact(model)) {
-                            REDACTION
-                        } else {
-                            member.redactIfNecessary(
-                                model,
-                                "self.$memberName",
-                            )
-                        }
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
-                    )
+                    if (shape.shouldRedact(model) || member.shouldRedact(model)) {
+                        rustTemplate(
+                            "formatter.field(${memberName.dq()}, &#{Sensitive}(&self.$memberName));",
+                            "Sensitive" to RuntimeType.sensitive(symbolProvider.config.runtimeConfig),
+                        )
+                    } else {
+                        rust("formatter.field(${memberName.dq()}, &self.$memberName);")
+                    }
                 }
                 writeCustomizations(customizations, StructureSection.AdditionalDebugFields(shape, "formatter"))
                 rust("formatter.finish()")
@@ -24,6 +24,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenTarget
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
 import software.amazon.smithy.rust.codegen.core.smithy.renamedFrom
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
@@ -55,13 +56,14 @@ fun CodegenTarget.renderUnknownVariant() =
  */
 open class UnionGenerator(
     val model: Model,
-    private val symbolProvider: SymbolProvider,
+    private val symbolProvider: RustSymbolProvider,
     private val writer: RustWriter,
     private val shape: UnionShape,
     private val renderUnknownVariant: Boolean = true,
 ) {
     private val sortedMembers: List<MemberShape> = shape.allMembers.values.sortedBy { symbolProvider.toMemberName(it) }
     private val unionSymbol = symbolProvider.toSymbol(shape)
+    private val unknownUnionVariant = RuntimeType.unknownUnionVariant(symbolProvider.config.runtimeConfig)
 
     open fun render() {
         writer.documentShape(shape, model)
@@ -100,9 +102,10 @@ open class UnionGenerator(
                 rust("/// The `Unknown` variant represents cases where the server sent a value that wasn't recognized")
                 rust("/// by the client. This can happen when the server adds new functionality, but the client has not been updated.")
                 rust("/// To investigate this, consider turning on debug logging to print the raw HTTP response.")
-                // at some point in the future, we may start actually putting things like the raw data in here.
+                rust("///")
+                rust("/// The name of the variant and its value as it was received are retained in the [`UnknownUnionVariant`](#T).", unknownUnionVariant)
                 Attribute.NonExhaustive.render(this)
-                rust("Unknown,")
+                rust("$UNKNOWN_VARIANT_NAME(#T),", unknownUnionVariant)
             }
         }
     }
@@ -126,46 +129,77 @@ open class UnionGenerator(
             if (renderUnknownVariant) {
                 rust("/// Returns true if the enum instance is the `Unknown` variant.")
                 rustBlock("pub fn is_unknown(&self) -> bool") {
-                    rust("matches!(self, Self::Unknown)")
+                    rust("matches!(self, Self::$UNKNOWN_VARIANT_NAME(_))")
+                }
+                rust("/// Tries to convert the enum instance into the `Unknown` variant, extracting the name and raw value of the variant.")
+                rust("/// Returns `Err(&Self)` if it can't be converted.")
+                rustBlockTemplate(
+                    "pub fn as_unknown(&self) -> #{Result}<&#{UnknownUnionVariant}, &Self>",
+                    *preludeScope,
+                    "UnknownUnionVariant" to unknownUnionVariant,
+                ) {
+                    rustTemplate(
+                        "if let Self::$UNKNOWN_VARIANT_NAME(val) = &self { #{Ok}(val) } else { #{Err}(self) }",
+                        *preludeScope,
+                    )
                 }
             }
         }
     }
 
     private fun renderFullyRedactedDebugImpl() {
-        writer.rustTemplate(
-            """
-            impl #{Debug} for ${unionSymbol.name} {
-                fn fmt(&self, f: &mut #{StdFmt}::Formatter<'_>) -> #{StdFmt}::Result {
-                    ::std::write!(f, $REDACTION)
-                }
+        writer.rustBlock("impl #T for ${unionSymbol.name}", RuntimeType.Debug) {
+            writer.rustBlock("fn fmt(&self, f: &mut #1T::Formatter<'_>) -> #1T::Result", RuntimeType.stdFmt) {
+                rustTemplate(
+                    """
+                    if !#{is_revealed}() {
+                        return ::std::write!(f, $REDACTION);
+                    }
+                    """,
+                    "is_revealed" to RuntimeType.sensitiveIsRevealed(symbolProvider.config.runtimeConfig),
+                )
+                renderDebugMatch(redactSensitiveMembers = false)
             }
-            """,
-            "Debug" to RuntimeType.Debug,
-            "StdFmt" to RuntimeType.stdFmt,
-        )
+        }
     }
 
     private fun renderDebugImpl() {
         writer.rustBlock("impl #T for ${unionSymbol.name}", RuntimeType.Debug) {
             writer.rustBlock("fn fmt(&self, f: &mut #1T::Formatter<'_>) -> #1T::Result", RuntimeType.stdFmt) {
-                rustBlock("match self") {
-                    sortedMembers.forEach { member ->
-                        val memberName = symbolProvider.toMemberName(member)
-                        val shouldRedact = member.shouldRedact(model)
-                        val isTargetUnit = member.isTargetUnit()
-                        when {
-                            !shouldRedact && isTargetUnit -> rust("${unionSymbol.name}::$memberName => f.debug_tuple(${memberName.dq()}).finish(),")
-                            !shouldRedact && !isTargetUnit -> rust("${unionSymbol.name}::$memberName(val) => f.debug_tuple(${memberName.dq()}).field(&val).finish(),")
-                            // We can always render (_) because the Unit target in a Union cannot be marked as sensitive separately.
-                            else -> rust("${unionSymbol.name}::$memberName(_) => f.debug_tuple($REDACTION).finish(),")
-                        }
-                    }
-                    if (renderUnknownVariant) {
-                        rust("${unionSymbol.name}::$UNKNOWN_VARIANT_NAME => f.debug_tuple(${UNKNOWN_VARIANT_NAME.dq()}).finish(),")
-                    }
+                renderDebugMatch(redactSensitiveMembers = true)
+            }
+        }
+    }
+
+    private fun RustWriter.renderDebugMatch(redactSensitiveMembers: Boolean) {
+        rustBlock("match self") {
+            sortedMembers.forEach { member ->
+                val memberName = symbolProvider.toMemberName(member)
+                val shouldRedact = redactSensitiveMembers && member.shouldRedact(model)
+                val isTargetUnit = member.isTargetUnit()
+                when {
+                    !shouldRedact && isTargetUnit -> rust("${unionSymbol.name}::$memberName => f.debug_tuple(${memberName.dq()}).finish(),")
+                    !shouldRedact && !isTargetUnit -> rust("${unionSymbol.name}::$memberName(val) => f.debug_tuple(${memberName.dq()}).field(&val).finish(),")
+                    // The Unit target in a Union cannot be marked as sensitive separately, so there's always a value.
+                    else ->
+                        rustTemplate(
+                            """
+                            ${unionSymbol.name}::$memberName(val) => if #{is_revealed}() {
+                                f.debug_tuple(${memberName.dq()}).field(&val).finish()
+                            } else {
+                                f.debug_tuple($REDACTION).finish()
+                            },
+                            """,
+                            "is_revealed" to RuntimeType.sensitiveIsRevealed(symbolProvider.config.runtimeConfig),
+                        )
                 }
             }
+            if (renderUnknownVariant) {
+                // The raw value of an unknown variant may contain sensitive data, so only its name is printed
+                rust(
+                    "${unionSymbol.name}::$UNKNOWN_VARIANT_NAME(val) => f.debug_tuple(${UNKNOWN_VARIANT_NAME.dq()}).field(&val.name()).finish(),",
+                )
+            }
         }
     }
 
@@ -176,7 +210,7 @@ open class UnionGenerator(
 
 fun unknownVariantError(union: String) =
     "Cannot serialize `$union::${UnionGenerator.UNKNOWN_VARIANT_NAME}` for the request. " +
-        "The `Unknown` variant is intended for responses only. " +
+        "The `Unknown` variant can only be serialized with the raw value it was received with, in the same protocol. " +
         "It occurs when an outdated client is used after a new enum variant was added on the server side."
 
 private fun RustWriter.renderVariant(
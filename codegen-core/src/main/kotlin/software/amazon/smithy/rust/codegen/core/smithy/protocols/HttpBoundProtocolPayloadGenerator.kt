@@ -18,6 +18,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustBlockTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.withBlock
 import software.amazon.smithy.rust.codegen.core.rustlang.withBlockTemplate
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
@@ -362,8 +363,8 @@ class HttpBoundProtocolPayloadGenerator(
                     // Convert an enum to `&str` then to `&[u8]` then to `Vec<u8>`.
                     rust("$payloadName.as_str().as_bytes().to_vec()")
                 } else {
-                    // Convert a `String` to `Vec<u8>`.
-                    rust("$payloadName.into_bytes()")
+                    // Convert a `String`, or a string type that converts into one, to `Vec<u8>`.
+                    rustTemplate("#{String}::from($payloadName).into_bytes()", *RuntimeType.preludeScope)
                 }
             }
 
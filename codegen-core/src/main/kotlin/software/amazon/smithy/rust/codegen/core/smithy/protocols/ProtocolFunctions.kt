@@ -110,7 +110,7 @@ class ProtocolFunctions(
                     FnType.Serialize -> "ser_$fnBaseName$suffix"
                 },
             )
-        return serDeFn(moduleName, fnName, parentModule, block)
+        return serDeFn(moduleName, fnName, parentModule, block, codegenContext.serDeFeatureGates(shape))
     }
 
     private fun serDeFn(
@@ -118,6 +118,7 @@ class ProtocolFunctions(
         fnName: String,
         parentModule: RustModule.LeafModule,
         block: ProtocolFnWritable,
+        featureGates: List<Attribute>,
     ): RuntimeType {
         val additionalAttributes =
             when {
@@ -125,7 +126,7 @@ class ProtocolFunctions(
                 // and the Rust compiler warning doesn't like multiple adjacent underscores.
                 moduleName.contains("__") || fnName.contains("__") -> listOf(Attribute.AllowNonSnakeCase)
                 else -> emptyList()
-            }
+            } + featureGates
         return RuntimeType.forInlineFun(
             fnName,
             RustModule.pubCrate(moduleName, parent = parentModule, additionalAttributes = additionalAttributes),
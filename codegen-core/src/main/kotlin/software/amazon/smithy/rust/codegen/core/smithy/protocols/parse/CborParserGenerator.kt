@@ -24,6 +24,7 @@ import software.amazon.smithy.model.shapes.StructureShape
 import software.amazon.smithy.model.shapes.TimestampShape
 import software.amazon.smithy.model.shapes.UnionShape
 import software.amazon.smithy.model.traits.EnumTrait
+import software.amazon.smithy.model.neighbor.Walker
 import software.amazon.smithy.model.traits.SparseTrait
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
@@ -110,6 +111,7 @@ class CborParserGenerator(
     private val model = codegenContext.model
     private val symbolProvider = codegenContext.symbolProvider
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val nonFiniteFloats = codegenContext.settings.codegenConfig.nonFiniteFloats.rustValue(runtimeConfig)
     private val codegenTarget = codegenContext.target
     private val smithyCbor = CargoDependency.smithyCbor(runtimeConfig).toType()
     private val protocolFunctions = ProtocolFunctions(codegenContext)
@@ -118,9 +120,13 @@ class CborParserGenerator(
         arrayOf(
             *preludeScope,
             "SmithyCbor" to smithyCbor,
+            "Bytes" to RuntimeType.Bytes,
             "Decoder" to smithyCbor.resolve("Decoder"),
+            "DecodeOptions" to smithyCbor.resolve("decode::DecodeOptions"),
             "Error" to smithyCbor.resolve("decode::DeserializeError"),
             "HashMap" to RuntimeType.HashMap,
+            "UnknownUnionVariant" to RuntimeType.unknownUnionVariant(runtimeConfig),
+            "UnknownUnionValue" to RuntimeType.unknownUnionValue(runtimeConfig),
             *preludeScope,
         )
 
@@ -379,9 +385,11 @@ class CborParserGenerator(
                         true ->
                             rustTemplate(
                                 """
-                                _ => {
-                                  decoder.skip()?;
-                                  #{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}
+                                variant => {
+                                  #{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(#{UnknownUnionVariant}::new(
+                                      variant,
+                                      #{UnknownUnionValue}::Bytes(decoder.raw_element()?),
+                                  ))
                                 }
                                 """,
                                 "Union" to returnSymbolToParse.symbol,
@@ -465,6 +473,28 @@ class CborParserGenerator(
         )
     }
 
+    /**
+     * Creates `decoder` over `value`.
+     *
+     * When [members] can hold blobs, `value` is copied into a single buffer that the decoded blobs share, rather
+     * than copying each blob out of `value`.
+     */
+    private fun newDecoderWritable(members: List<MemberShape>) =
+        writable {
+            val hasBlobs = members.any { member -> Walker(model).walkShapes(member).any { it is BlobShape } }
+            if (hasBlobs) {
+                rustTemplate(
+                    """
+                    let value = #{Bytes}::copy_from_slice(value);
+                    let decoder = &mut #{Decoder}::from_bytes(&value);
+                    """,
+                    *codegenScope,
+                )
+            } else {
+                rustTemplate("let decoder = &mut #{Decoder}::new(value);", *codegenScope)
+            }
+        }
+
     private fun decodeStructureMapLoopWritable() = decodeCollectionLoopWritable(CollectionKind.Map, "builder", "pair")
 
     private fun decodeMapLoopWritable() = decodeCollectionLoopWritable(CollectionKind.Map, "map", "pair")
@@ -476,12 +506,16 @@ class CborParserGenerator(
      * operation, error and structure shapes.
      * We still generate the parser symbol even if there are no included members because the server
      * generation requires parsers for all input structures.
+     *
+     * When [validateInput] is set, the input is rejected before it's decoded if it nests too deeply, has maps with
+     * duplicate keys, or has tags Smithy doesn't use. Servers set it, since their input comes from untrusted clients.
      */
     private fun structureParser(
         shape: Shape,
         builderSymbol: Symbol,
         includedMembers: List<MemberShape>,
         fnNameSuffix: String? = null,
+        validateInput: Boolean = false,
     ): RuntimeType {
         return protocolFunctions.deserializeFn(shape, fnNameSuffix) { fnName ->
             rustTemplate(
@@ -489,7 +523,8 @@ class CborParserGenerator(
                 pub(crate) fn $fnName(value: &[u8], mut builder: #{Builder}) -> #{Result}<#{Builder}, #{Error}> {
                     #{StructurePairParserFn:W}
 
-                    let decoder = &mut #{Decoder}::new(value);
+                    #{NewDecoder:W}
+                    #{ValidateInput:W}
 
                     #{DecodeStructureMapLoop:W}
 
@@ -503,6 +538,13 @@ class CborParserGenerator(
                 "Builder" to builderSymbol,
                 "StructurePairParserFn" to structurePairParserFnWritable(builderSymbol, includedMembers),
                 "DecodeStructureMapLoop" to decodeStructureMapLoopWritable(),
+                "NewDecoder" to newDecoderWritable(includedMembers),
+                "ValidateInput" to
+                    writable {
+                        if (validateInput) {
+                            rustTemplate("decoder.validate(&#{DecodeOptions}::new().strict(true))?;", *codegenScope)
+                        }
+                    },
                 *codegenScope,
             )
         }
@@ -518,12 +560,13 @@ class CborParserGenerator(
             rustTemplate(
                 """
                 pub(crate) fn $fnName(value: &[u8]) -> #{Result}<#{ReturnType}, #{Error}> {
-                    let decoder = &mut #{Decoder}::new(value);
+                    #{NewDecoder:W}
                     #{DeserializeMember}
                 }
                 """,
                 "ReturnType" to returnSymbol.symbol,
                 "DeserializeMember" to deserializeMember(member),
+                "NewDecoder" to newDecoderWritable(listOf(member)),
                 *codegenScope,
             )
         }
@@ -557,7 +600,12 @@ class CborParserGenerator(
             return null
         }
         val inputShape = operationShape.inputShape(model)
-        return structureParser(operationShape, symbolProvider.symbolForBuilder(inputShape), includedMembers)
+        return structureParser(
+            operationShape,
+            symbolProvider.symbolForBuilder(inputShape),
+            includedMembers,
+            validateInput = true,
+        )
     }
 
     private fun deserializeMember(memberShape: MemberShape) =
@@ -574,8 +622,14 @@ class CborParserGenerator(
                 is IntegerShape -> rust("decoder.integer()")
                 is LongShape -> rust("decoder.long()")
 
-                is FloatShape -> rust("decoder.float()")
-                is DoubleShape -> rust("decoder.double()")
+                is FloatShape, is DoubleShape -> {
+                    val method = if (target is FloatShape) "float" else "double"
+                    if (nonFiniteFloats != null) {
+                        rust("decoder.${method}_with(#T)", nonFiniteFloats)
+                    } else {
+                        rust("decoder.$method()")
+                    }
+                }
 
                 is TimestampShape -> rust("decoder.timestamp()")
 
@@ -82,6 +82,8 @@ class EventStreamUnmarshallerGenerator(
             "tracing" to RuntimeType.Tracing,
             "UnmarshalledMessage" to smithyEventStream.resolve("frame::UnmarshalledMessage"),
             "UnmarshallMessage" to smithyEventStream.resolve("frame::UnmarshallMessage"),
+            "UnknownUnionVariant" to RuntimeType.unknownUnionVariant(runtimeConfig),
+            "UnknownUnionValue" to RuntimeType.unknownUnionValue(runtimeConfig),
         )
 
     fun render(): RuntimeType {
@@ -160,18 +162,23 @@ class EventStreamUnmarshallerGenerator(
                     renderUnmarshallUnionMember(member, target)
                 }
             }
-            rustBlock("_unknown_variant => ") {
+            rustBlock("unknown_variant => ") {
                 when (codegenTarget.renderUnknownVariant()) {
                     true ->
                         rustTemplate(
-                            "Ok(#{UnmarshalledMessage}::Event(#{Output}::${UnionGenerator.UNKNOWN_VARIANT_NAME}))",
+                            """
+                            Ok(#{UnmarshalledMessage}::Event(#{Output}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(#{UnknownUnionVariant}::new(
+                                unknown_variant,
+                                #{UnknownUnionValue}::EventStreamMessage(message.clone()),
+                            ))))
+                            """,
                             "Output" to unionSymbol,
                             *codegenScope,
                         )
 
                     false ->
                         rustTemplate(
-                            "return Err(#{Error}::unmarshalling(format!(\"unrecognized :event-type: {_unknown_variant}\")));",
+                            "return Err(#{Error}::unmarshalling(format!(\"unrecognized :event-type: {unknown_variant}\")));",
                             *codegenScope,
                         )
                 }
@@ -290,7 +297,7 @@ class EventStreamUnmarshallerGenerator(
             conditionalBlock("Some(", ")", member.isOptional) {
                 when (target) {
                     is BlobShape -> {
-                        rustTemplate("#{Blob}::new(message.payload().as_ref())", *codegenScope)
+                        rustTemplate("#{Blob}::from(message.payload().clone())", *codegenScope)
                     }
 
                     is StringShape -> {
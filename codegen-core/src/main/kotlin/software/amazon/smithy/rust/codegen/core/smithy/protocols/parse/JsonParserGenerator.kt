@@ -107,6 +107,7 @@ class JsonParserGenerator(
     private val model = codegenContext.model
     private val symbolProvider = codegenContext.symbolProvider
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val nonFiniteFloats = codegenContext.settings.codegenConfig.nonFiniteFloats.rustValue(runtimeConfig)
     private val codegenTarget = codegenContext.target
     private val smithyJson = CargoDependency.smithyJson(runtimeConfig).toType()
     private val protocolFunctions = ProtocolFunctions(codegenContext)
@@ -117,6 +118,7 @@ class JsonParserGenerator(
             "expect_blob_or_null" to smithyJson.resolve("deserialize::token::expect_blob_or_null"),
             "expect_bool_or_null" to smithyJson.resolve("deserialize::token::expect_bool_or_null"),
             "expect_document" to smithyJson.resolve("deserialize::token::expect_document"),
+            "expect_document_or_skip" to smithyJson.resolve("deserialize::token::expect_document_or_skip"),
             "expect_number_or_null" to smithyJson.resolve("deserialize::token::expect_number_or_null"),
             "expect_start_array" to smithyJson.resolve("deserialize::token::expect_start_array"),
             "expect_start_object" to smithyJson.resolve("deserialize::token::expect_start_object"),
@@ -127,6 +129,8 @@ class JsonParserGenerator(
             "skip_value" to smithyJson.resolve("deserialize::token::skip_value"),
             "skip_to_end" to smithyJson.resolve("deserialize::token::skip_to_end"),
             "Token" to smithyJson.resolve("deserialize::Token"),
+            "UnknownUnionVariant" to RuntimeType.unknownUnionVariant(runtimeConfig),
+            "UnknownUnionValue" to RuntimeType.unknownUnionValue(runtimeConfig),
             "or_empty" to orEmptyJson(),
             *preludeScope,
         )
@@ -165,6 +169,9 @@ class JsonParserGenerator(
         }
     }
 
+    /** Returns the JSON field name [member] is deserialized from. */
+    fun fieldName(member: MemberShape): String = jsonName(member)
+
     override fun payloadParser(member: MemberShape): RuntimeType {
         val shape = model.expectShape(member.target)
         val returnSymbolToParse = returnSymbolToParse(shape)
@@ -357,7 +364,27 @@ class JsonParserGenerator(
     }
 
     private fun RustWriter.deserializeNumber(target: NumberShape) {
-        if (target.isFloatShape) {
+        if (nonFiniteFloats != null && target.isFloatShape) {
+            // The handling is applied after the conversion to `f32`, so that infinities are clamped to `f32::MAX`
+            rustTemplate(
+                """
+                #{expect_number_or_null}(tokens.next())?
+                    .map(|v| #{handling}.apply_f32(v.to_f32_lossy()))
+                    .transpose()
+                    .map_err(|err| #{Error}::custom_source("invalid float", err))?
+                """,
+                "handling" to nonFiniteFloats,
+                *codegenScope,
+            )
+        } else if (nonFiniteFloats != null && target.isDoubleShape) {
+            rustTemplate(
+                "#{expect_number_or_null_with}(tokens.next(), #{handling})?.map(|v| v.to_f64_lossy())",
+                "expect_number_or_null_with" to
+                    smithyJson.resolve("deserialize::token::expect_number_or_null_with"),
+                "handling" to nonFiniteFloats,
+                *codegenScope,
+            )
+        } else if (target.isFloatShape) {
             rustTemplate("#{expect_number_or_null}(tokens.next())?.map(|v| v.to_f32_lossy())", *codegenScope)
         } else if (target.isDoubleShape) {
             rustTemplate("#{expect_number_or_null}(tokens.next())?.map(|v| v.to_f64_lossy())", *codegenScope)
@@ -617,12 +644,17 @@ class JsonParserGenerator(
                                     }
                                     when (codegenTarget.renderUnknownVariant()) {
                                         // In client mode, resolve an unknown union variant to the unknown variant.
+                                        // Values that can't be represented as a document are skipped rather than
+                                        // failing the response, so that unknown variants stay forward compatible.
                                         true ->
                                             rustTemplate(
                                                 """
-                                                _ => {
-                                                  #{skip_value}(tokens)?;
-                                                  Some(#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME})
+                                                variant => {
+                                                  let unknown = match #{expect_document_or_skip}(tokens)? {
+                                                      #{Some}(document) => #{UnknownUnionVariant}::new(variant, #{UnknownUnionValue}::Document(document)),
+                                                      #{None} => #{UnknownUnionVariant}::without_value(variant),
+                                                  };
+                                                  Some(#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(unknown))
                                                 }
                                                 """,
                                                 "Union" to returnSymbolToParse.symbol,
@@ -13,6 +13,7 @@ import software.amazon.smithy.model.knowledge.HttpBindingIndex
 import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.DocumentShape
 import software.amazon.smithy.model.shapes.MapShape
 import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.model.shapes.NumberShape
@@ -97,6 +98,7 @@ class XmlBindingTraitParserGenerator(
 
     private val scopedDecoder = smithyXml.resolve("decode::ScopedDecoder")
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val nonFiniteFloats = codegenContext.settings.codegenConfig.nonFiniteFloats.rustValue(runtimeConfig)
     private val protocolFunctions = ProtocolFunctions(codegenContext)
     private val codegenTarget = codegenContext.target
     private val builderInstantiator = codegenContext.builderInstantiator()
@@ -109,8 +111,13 @@ class XmlBindingTraitParserGenerator(
             "XmlDecodeError" to xmlDecodeError,
             "next_start_element" to smithyXml.resolve("decode::next_start_element"),
             "try_data" to smithyXml.resolve("decode::try_data"),
+            "decode_document" to
+                CargoDependency.smithyXml(codegenContext.runtimeConfig).withFeature("document").toType()
+                    .resolve("document::decode_document"),
             "ScopedDecoder" to scopedDecoder,
             "aws_smithy_types" to CargoDependency.smithyTypes(runtimeConfig).toType(),
+            "UnknownUnionVariant" to RuntimeType.unknownUnionVariant(runtimeConfig),
+            "UnknownUnionValue" to RuntimeType.unknownUnionValue(runtimeConfig),
             *RuntimeType.preludeScope,
         )
     private val model = codegenContext.model
@@ -372,7 +379,7 @@ class XmlBindingTraitParserGenerator(
         conditionalBlock("Some(", ")", forceOptional || symbol.isOptional()) {
             conditionalBlock("Box::new(", ")", symbol.isRustBoxed()) {
                 when (target) {
-                    is StringShape, is BooleanShape, is NumberShape, is TimestampShape, is BlobShape ->
+                    is StringShape, is BooleanShape, is NumberShape, is TimestampShape, is BlobShape, is DocumentShape ->
                         parsePrimitiveInner(memberShape) {
                             rustTemplate("#{try_data}(&mut ${ctx.tag})?.as_ref()", *codegenScope)
                         }
@@ -465,7 +472,20 @@ class XmlBindingTraitParserGenerator(
                             }
                         }
                         when (target.renderUnknownVariant()) {
-                            true -> rust("_unknown => base = Some(#T::${UnionGenerator.UNKNOWN_VARIANT_NAME}),", symbol)
+                            true ->
+                                rustTemplate(
+                                    """
+                                    unknown => {
+                                        let variant = unknown.local().to_owned();
+                                        let raw = tag.into_raw()?;
+                                        base = Some(#{Shape}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(#{UnknownUnionVariant}::new(
+                                            variant,
+                                            #{UnknownUnionValue}::Bytes(#{Blob}::new(raw.into_owned())),
+                                        )));
+                                    }
+                                    """,
+                                    *codegenScope, "Shape" to symbol,
+                                )
                             false ->
                                 rustTemplate(
                                     """variant => return Err(#{XmlDecodeError}::custom(format!("unexpected union variant: {variant:?}")))""",
@@ -686,6 +706,14 @@ class XmlBindingTraitParserGenerator(
                         """.map_err(|_|#{XmlDecodeError}::custom("expected ${escape(shape.toString())}"))""",
                         *codegenScope,
                     )
+                    if (nonFiniteFloats != null && (shape.isFloatShape || shape.isDoubleShape)) {
+                        val apply = if (shape.isFloatShape) "apply_f32" else "apply_f64"
+                        rustTemplate(
+                            ".and_then(|v| #{handling}.$apply(v).map_err(#{XmlDecodeError}::unhandled))",
+                            "handling" to nonFiniteFloats,
+                            *codegenScope,
+                        )
+                    }
                 }
             }
 
@@ -718,6 +746,13 @@ class XmlBindingTraitParserGenerator(
                 )
             }
 
+            // XML has no native document representation, so documents are embedded as JSON text
+            is DocumentShape -> {
+                withBlockTemplate("#{decode_document}(", ")", *codegenScope) {
+                    provider()
+                }
+            }
+
             else -> PANIC("unexpected shape: $shape")
         }
     }
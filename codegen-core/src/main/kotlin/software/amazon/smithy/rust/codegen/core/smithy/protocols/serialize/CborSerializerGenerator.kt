@@ -211,6 +211,7 @@ class CborSerializerGenerator(
     private val symbolProvider = codegenContext.symbolProvider
     private val codegenTarget = codegenContext.target
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val nonFiniteFloats = codegenContext.settings.codegenConfig.nonFiniteFloats.rustValue(runtimeConfig)
     private val protocolFunctions = ProtocolFunctions(codegenContext)
 
     private val codegenScope =
@@ -488,8 +489,14 @@ class CborSerializerGenerator(
             is IntegerShape -> rust("$encoder.integer(${value.asValue()});")
             is LongShape -> rust("$encoder.long(${value.asValue()});")
 
-            is FloatShape -> rust("$encoder.float(${value.asValue()});")
-            is DoubleShape -> rust("$encoder.double(${value.asValue()});")
+            is FloatShape, is DoubleShape -> {
+                val method = if (target is FloatShape) "float" else "double"
+                if (nonFiniteFloats != null) {
+                    rust("$encoder.${method}_with(${value.asValue()}, #T)?;", nonFiniteFloats)
+                } else {
+                    rust("$encoder.$method(${value.asValue()});")
+                }
+            }
 
             is TimestampShape -> rust("$encoder.timestamp(${value.asRef()});")
 
@@ -590,8 +597,16 @@ class CborSerializerGenerator(
                             }
                         }
                         if (codegenTarget.renderUnknownVariant()) {
+                            // Unknown variants are written with the data item they were received with
                             rustTemplate(
-                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => return #{Err}(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
+                                """
+                                #{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(unknown) => match unknown.value().and_then(|value| value.as_bytes()) {
+                                    #{Some}(raw) => {
+                                        encoder.str(unknown.name()).raw_element(raw);
+                                    }
+                                    #{None} => return #{Err}(#{Error}::unknown_variant(${unionSymbol.name.dq()})),
+                                },
+                                """,
                                 "Union" to unionSymbol,
                                 *codegenScope,
                             )
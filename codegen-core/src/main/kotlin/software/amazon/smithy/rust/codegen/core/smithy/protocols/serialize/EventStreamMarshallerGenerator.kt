@@ -69,6 +69,7 @@ open class EventStreamMarshallerGenerator(
             "HeaderValue" to smithyTypes.resolve("event_stream::HeaderValue"),
             "Error" to smithyEventStream.resolve("error::Error"),
             "SdkBody" to RuntimeType.sdkBody(runtimeConfig),
+            "UnknownUnionValue" to RuntimeType.unknownUnionValue(runtimeConfig),
         )
 
     open fun render(): RuntimeType {
@@ -176,11 +177,15 @@ open class EventStreamMarshallerGenerator(
                         }
                     }
                     if (target.renderUnknownVariant()) {
+                        // Unknown events are re-sent as the message they were received in, with all of its headers
                         rustTemplate(
                             """
-                            Self::Input::${UnionGenerator.UNKNOWN_VARIANT_NAME} => return Err(
-                                #{Error}::marshalling(${unknownVariantError(unionSymbol.rustType().name).dq()}.to_owned())
-                            )
+                            Self::Input::${UnionGenerator.UNKNOWN_VARIANT_NAME}(unknown) => match unknown.into_parts().1 {
+                                #{Some}(#{UnknownUnionValue}::EventStreamMessage(message)) => return #{Ok}(message),
+                                _ => return Err(
+                                    #{Error}::marshalling(${unknownVariantError(unionSymbol.rustType().name).dq()}.to_owned())
+                                ),
+                            }
                             """,
                             *codegenScope,
                         )
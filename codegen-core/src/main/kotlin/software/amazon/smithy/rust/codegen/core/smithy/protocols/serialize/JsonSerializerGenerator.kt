@@ -181,6 +181,7 @@ class JsonSerializerGenerator(
     private val symbolProvider = codegenContext.symbolProvider
     private val codegenTarget = codegenContext.target
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val nonFiniteFloats = codegenContext.settings.codegenConfig.nonFiniteFloats.rustValue(runtimeConfig)
     private val protocolFunctions = ProtocolFunctions(codegenContext)
     private val codegenScope =
         arrayOf(
@@ -429,10 +430,25 @@ class JsonSerializerGenerator(
                         is DoubleShape, is FloatShape -> "Float"
                         else -> throw IllegalStateException("unreachable")
                     }
-                rust(
-                    "$writer.number(##[allow(clippy::useless_conversion)]#T::$numberType((${value.asValue()}).into()));",
-                    RuntimeType.smithyTypes(runtimeConfig).resolve("Number"),
-                )
+                val number = RuntimeType.smithyTypes(runtimeConfig).resolve("Number")
+                when {
+                    nonFiniteFloats != null && target is DoubleShape ->
+                        rust("$writer.number_with(#T::Float(${value.asValue()}), #T)?;", number, nonFiniteFloats)
+
+                    // The handling is applied before the conversion to `f64`, so that infinities are clamped to `f32::MAX`
+                    nonFiniteFloats != null && target is FloatShape ->
+                        rust(
+                            "$writer.number(#T::Float(#T.apply_f32(${value.asValue()})?.into()));",
+                            number,
+                            nonFiniteFloats,
+                        )
+
+                    else ->
+                        rust(
+                            "$writer.number(##[allow(clippy::useless_conversion)]#T::$numberType((${value.asValue()}).into()));",
+                            number,
+                        )
+                }
             }
 
             is BlobShape ->
@@ -566,8 +582,14 @@ class JsonSerializerGenerator(
                             }
                         }
                         if (codegenTarget.renderUnknownVariant()) {
+                            // Unknown variants are written with the document they were received with
                             rustTemplate(
-                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
+                                """
+                                #{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(unknown) => match unknown.value().and_then(|value| value.as_document()) {
+                                    Some(value) => ${context.writerExpression}.key(unknown.name()).document(value),
+                                    None => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()})),
+                                },
+                                """,
                                 "Union" to unionSymbol,
                                 *codegenScope,
                             )
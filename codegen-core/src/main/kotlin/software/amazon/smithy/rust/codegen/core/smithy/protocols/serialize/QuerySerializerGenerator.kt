@@ -89,6 +89,7 @@ abstract class QuerySerializerGenerator(private val codegenContext: CodegenConte
     protected val model = codegenContext.model
     protected val symbolProvider = codegenContext.symbolProvider
     protected val runtimeConfig = codegenContext.runtimeConfig
+    private val nonFiniteFloats = codegenContext.settings.codegenConfig.nonFiniteFloats.rustValue(runtimeConfig)
     private val target = codegenContext.target
     private val serviceShape = codegenContext.serviceShape
     private val serializerError = runtimeConfig.serializationError()
@@ -239,10 +240,22 @@ abstract class QuerySerializerGenerator(private val codegenContext: CodegenConte
                         is RustType.Integer -> "NegInt"
                         else -> throw IllegalStateException("unreachable")
                     }
-                rust(
-                    "$writer.number(##[allow(clippy::useless_conversion)]#T::$numberType((${value.asValue()}).into()));",
-                    smithyTypes.resolve("Number"),
-                )
+                when {
+                    nonFiniteFloats != null && (target.isFloatShape || target.isDoubleShape) -> {
+                        val apply = if (target.isFloatShape) "apply_f32" else "apply_f64"
+                        rust(
+                            "$writer.number(##[allow(clippy::useless_conversion)]#T::Float(#T.$apply(${value.asValue()})?.into()));",
+                            smithyTypes.resolve("Number"),
+                            nonFiniteFloats,
+                        )
+                    }
+
+                    else ->
+                        rust(
+                            "$writer.number(##[allow(clippy::useless_conversion)]#T::$numberType((${value.asValue()}).into()));",
+                            smithyTypes.resolve("Number"),
+                        )
+                }
             }
             is BlobShape ->
                 rust(
@@ -397,7 +410,7 @@ abstract class QuerySerializerGenerator(private val codegenContext: CodegenConte
                         }
                         if (target.renderUnknownVariant()) {
                             rustTemplate(
-                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
+                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(_) => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
                                 "Union" to unionSymbol,
                                 *codegenScope,
                             )
@@ -9,6 +9,7 @@ import software.amazon.smithy.codegen.core.CodegenException
 import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.DocumentShape
 import software.amazon.smithy.model.shapes.MapShape
 import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.model.shapes.NumberShape
@@ -23,6 +24,7 @@ import software.amazon.smithy.model.traits.TimestampFormatTrait
 import software.amazon.smithy.model.traits.XmlFlattenedTrait
 import software.amazon.smithy.model.traits.XmlNamespaceTrait
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustType
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.autoDeref
@@ -59,6 +61,7 @@ class XmlBindingTraitSerializerGenerator(
 ) : StructuredDataSerializerGenerator {
     private val symbolProvider = codegenContext.symbolProvider
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val nonFiniteFloats = codegenContext.settings.codegenConfig.nonFiniteFloats.rustValue(runtimeConfig)
     private val model = codegenContext.model
     private val codegenTarget = codegenContext.target
     private val protocolFunctions = ProtocolFunctions(codegenContext)
@@ -327,7 +330,7 @@ class XmlBindingTraitSerializerGenerator(
         member: MemberShape,
         input: String,
     ) {
-        when (model.expectShape(member.target)) {
+        when (val target = model.expectShape(member.target)) {
             is StringShape -> {
                 // The `input` expression always evaluates to a reference type at this point, but if
                 // it does so because
@@ -341,10 +344,18 @@ class XmlBindingTraitSerializerGenerator(
                 rust("$dereferenced.as_str()")
             }
             is BooleanShape, is NumberShape -> {
-                rust(
-                    "#T::from(${autoDeref(input)}).encode()",
-                    RuntimeType.smithyTypes(runtimeConfig).resolve("primitive::Encoder"),
-                )
+                if (nonFiniteFloats != null && (target.isFloatShape || target.isDoubleShape)) {
+                    rust(
+                        "#T::from(${autoDeref(input)}).encode_with(#T)?",
+                        RuntimeType.smithyTypes(runtimeConfig).resolve("primitive::Encoder"),
+                        nonFiniteFloats,
+                    )
+                } else {
+                    rust(
+                        "#T::from(${autoDeref(input)}).encode()",
+                        RuntimeType.smithyTypes(runtimeConfig).resolve("primitive::Encoder"),
+                    )
+                }
             }
             is BlobShape ->
                 rust("#T($input.as_ref()).as_ref()", RuntimeType.base64Encode(runtimeConfig))
@@ -364,6 +375,13 @@ class XmlBindingTraitSerializerGenerator(
                     )
                 rust("$input.fmt(#T)?.as_ref()", timestampFormatType)
             }
+            // XML has no native document representation, so documents are embedded as JSON text
+            is DocumentShape ->
+                rust(
+                    "#T($input).as_str()",
+                    CargoDependency.smithyXml(runtimeConfig).withFeature("document").toType()
+                        .resolve("document::encode_document"),
+                )
             else -> TODO(member.toString())
         }
     }
@@ -384,6 +402,7 @@ class XmlBindingTraitSerializerGenerator(
                 is NumberShape,
                 is TimestampShape,
                 is BlobShape,
+                is DocumentShape,
                 -> {
                     rust(
                         "let mut inner_writer = ${ctx.scopeWriter}.start_el(${xmlName.dq()})$ns.finish();",
@@ -516,8 +535,16 @@ class XmlBindingTraitSerializerGenerator(
                         }
 
                         if (codegenTarget.renderUnknownVariant()) {
+                            // Unknown variants are written with the element they were received with
                             rustTemplate(
-                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
+                                """
+                                #{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(unknown) => {
+                                    match unknown.value().and_then(|value| value.as_bytes()).and_then(|raw| std::str::from_utf8(raw).ok()) {
+                                        Some(raw) => scope_writer.raw(raw),
+                                        None => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()})),
+                                    }
+                                }
+                                """,
                                 "Union" to unionSymbol,
                                 *codegenScope,
                             )
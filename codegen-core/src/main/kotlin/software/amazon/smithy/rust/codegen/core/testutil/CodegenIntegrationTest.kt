@@ -7,7 +7,9 @@ package software.amazon.smithy.rust.codegen.core.testutil
 
 import software.amazon.smithy.build.PluginContext
 import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.node.ArrayNode
 import software.amazon.smithy.model.node.ObjectNode
+import software.amazon.smithy.model.node.StringNode
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.util.runCommand
 import java.io.File
@@ -87,10 +89,71 @@ sealed class AdditionalSettings {
 class ClientAdditionalSettings private constructor(settings: List<AdditionalSettings>) :
     AdditionalSettings.CoreAdditionalSettings(settings) {
         class Builder : CoreAdditionalSettings.Builder<ClientAdditionalSettings>() {
+            fun validateInputConstraints(enabled: Boolean = true): Builder {
+                settings.add(ValidateInputConstraints(enabled))
+                return this
+            }
+
+            fun operationFeatureGates(
+                enabled: Boolean = true,
+                groups: Map<String, List<String>> = emptyMap(),
+            ): Builder {
+                settings.add(OperationFeatureGates(enabled, groups))
+                return this
+            }
+
+            fun cowStringInputs(enabled: Boolean = true): Builder {
+                settings.add(CowStringInputs(enabled))
+                return this
+            }
+
+            fun endpointRulesTrace(enabled: Boolean = true): Builder {
+                settings.add(EndpointRulesTrace(enabled))
+                return this
+            }
+
             override fun build(): ClientAdditionalSettings = ClientAdditionalSettings(settings)
         }
 
         // Additional settings that are specific to client generation should be defined here.
+        private data class ValidateInputConstraints(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("validateInputConstraints", enabled)
+                    .build()
+        }
+
+        private data class OperationFeatureGates(
+            val enabled: Boolean,
+            val groups: Map<String, List<String>>,
+        ) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("operationFeatureGates", enabled)
+                    .withMember(
+                        "operationFeatureGroups",
+                        ObjectNode.objectNode(
+                            groups.map { (group, operations) ->
+                                StringNode.from(group) to ArrayNode.fromStrings(operations)
+                            }.toMap(),
+                        ),
+                    )
+                    .build()
+        }
+
+        private data class CowStringInputs(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("cowStringInputs", enabled)
+                    .build()
+        }
+
+        private data class EndpointRulesTrace(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("endpointRulesTrace", enabled)
+                    .build()
+        }
 
         companion object {
             fun builder() = Builder()
@@ -411,6 +411,10 @@ class EnumGeneratorTest {
                     """
                     assert_eq!(format!("{:?}", SomeEnum::Foo), $REDACTION);
                     assert_eq!(format!("{:?}", SomeEnum::Bar), $REDACTION);
+                    {
+                        let _guard = ::aws_smithy_types::sensitive::reveal_on_current_thread();
+                        assert_eq!(format!("{:?}", SomeEnum::Foo), "\"Foo\"");
+                    }
                     """,
                 )
             }
@@ -218,6 +218,27 @@ class StructureGeneratorTest {
         }.compileAndTest()
     }
 
+    @Test
+    fun `custom debug implementation reveals sensitive fields while the reveal guard is held`() {
+        val provider = testSymbolProvider(model, rustReservedWordConfig = rustReservedWordConfig)
+        TestWorkspace.testProject().unitTest {
+            structureGenerator(model, provider, this, secretStructure).render()
+
+            rust(
+                """
+                let secret_structure = SecretStructure {
+                    secret_field: Some("secret".to_owned()),
+                };
+                {
+                    let _guard = ::aws_smithy_types::sensitive::reveal_on_current_thread();
+                    assert_eq!(format!("{:?}", secret_structure), "SecretStructure { secret_field: Some(\"secret\") }");
+                }
+                assert_eq!(format!("{:?}", secret_structure), "SecretStructure { secret_field: \"*** Sensitive Data Redacted ***\" }");
+                """,
+            )
+        }.compileAndTest()
+    }
+
     @Test
     fun `generate a custom debug implementation when the sensitive trait is applied to an inner struct`() {
         val provider = testSymbolProvider(model, rustReservedWordConfig = rustReservedWordConfig)
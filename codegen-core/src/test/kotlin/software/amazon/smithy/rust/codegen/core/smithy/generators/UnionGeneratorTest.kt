@@ -58,7 +58,7 @@ class UnionGeneratorTest {
         writer.compileAndTest(
             """
             let var_a = MyUnion::UnknownValue("abc".to_string());
-            let var_b = MyUnion::Unknown;
+            let var_b = MyUnion::Unknown(aws_smithy_types::UnknownUnionVariant::default());
             assert_ne!(var_a, var_b);
             assert_eq!(var_a, var_a);
             """,
@@ -116,8 +116,15 @@ class UnionGeneratorTest {
         val writer = generateUnion("union MyUnion { a: String, b: String }", unknownVariant = true)
         writer.compileAndTest(
             """
-            let union = MyUnion::Unknown;
+            let union = MyUnion::Unknown(aws_smithy_types::UnknownUnionVariant::new(
+                "c",
+                aws_smithy_types::UnknownUnionValue::Document("value".into()),
+            ));
             assert!(union.is_unknown());
+            assert_eq!("c", union.as_unknown().unwrap().name());
+            assert!(MyUnion::A("a".to_string()).as_unknown().is_err());
+            // the raw value isn't printed since it may be sensitive
+            assert_eq!(format!("{union:?}"), "Unknown(\"c\")");
 
             """,
         )
@@ -216,6 +223,39 @@ class UnionGeneratorTest {
         )
     }
 
+    @Test
+    fun `impl debug for union should reveal sensitive text while the reveal guard is held`() {
+        val writer =
+            generateUnion(
+                """
+                @sensitive
+                string Bar
+
+                union MyUnion {
+                    foo: PrimitiveInteger,
+                    bar: Bar,
+                }
+
+                @sensitive
+                union SecretUnion {
+                    foo: PrimitiveInteger,
+                }
+                """,
+            )
+
+        writer.compileAndTest(
+            """
+            {
+                let _guard = ::aws_smithy_types::sensitive::reveal_on_current_thread();
+                assert_eq!(format!("{:?}", MyUnion::Bar("bar".to_owned())), "Bar(\"bar\")");
+                assert_eq!(format!("{:?}", SecretUnion::Foo(3)), "Foo(3)");
+            }
+            assert_eq!(format!("{:?}", MyUnion::Bar("bar".to_owned())), $REDACTION);
+            assert_eq!(format!("{:?}", SecretUnion::Foo(3)), $REDACTION);
+            """,
+        )
+    }
+
     @Test
     fun `impl debug for union with unit target should redact text for sensitive member target`() {
         val writer =
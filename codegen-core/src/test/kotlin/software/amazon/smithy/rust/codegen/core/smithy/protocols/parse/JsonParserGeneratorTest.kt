@@ -6,9 +6,13 @@
 package software.amazon.smithy.rust.codegen.core.smithy.protocols.parse
 
 import org.junit.jupiter.api.Test
+import org.junit.jupiter.params.ParameterizedTest
+import org.junit.jupiter.params.provider.CsvSource
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.StringShape
 import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.rust.codegen.core.smithy.CoreCodegenConfig
+import software.amazon.smithy.rust.codegen.core.smithy.NonFiniteFloatHandling
 import software.amazon.smithy.rust.codegen.core.smithy.generators.EnumGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.generators.TestEnumType
 import software.amazon.smithy.rust.codegen.core.smithy.generators.UnionGenerator
@@ -22,6 +26,7 @@ import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
 import software.amazon.smithy.rust.codegen.core.testutil.renderWithModelBuilder
 import software.amazon.smithy.rust.codegen.core.testutil.testCodegenContext
+import software.amazon.smithy.rust.codegen.core.testutil.testRustSettings
 import software.amazon.smithy.rust.codegen.core.testutil.testSymbolProvider
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.lookup
@@ -168,7 +173,21 @@ class JsonParserGeneratorTest {
                 // unknown variant
                 let input = br#"{ "top": { "choice": { "somenewvariant": "data" } } }"#;
                 let output = ${format(operationGenerator)}(input, test_output::OpOutput::builder()).unwrap().build();
-                assert!(output.top.unwrap().choice.is_unknown());
+                let choice = output.top.unwrap().choice;
+                let unknown = choice.as_unknown().unwrap();
+                assert_eq!("somenewvariant", unknown.name());
+                assert_eq!(
+                    Some(&aws_smithy_types::Document::from("data")),
+                    unknown.value().and_then(|value| value.as_document())
+                );
+
+                // unknown variants whose value can't be a document are skipped instead of failing
+                let input = br#"{ "top": { "choice": { "somenewvariant": { "a": 18446744073709551616 } }, "field": "after" } }"#;
+                let top = ${format(operationGenerator)}(input, test_output::OpOutput::builder()).unwrap().build().top.unwrap();
+                let unknown = top.choice.as_unknown().unwrap();
+                assert_eq!("somenewvariant", unknown.name());
+                assert_eq!(None, unknown.value());
+                assert_eq!(Some("after".to_string()), top.field);
                 """,
             )
 
@@ -238,4 +257,72 @@ class JsonParserGeneratorTest {
         }
         project.compileAndTest()
     }
+
+    @ParameterizedTest
+    @CsvSource("ERROR", "CLAMP")
+    fun `parsers apply the non-finite float handling`(handling: NonFiniteFloatHandling) {
+        val model =
+            OperationNormalizer.transform(
+                """
+                namespace test
+                use aws.protocols#restJson1
+
+                @http(uri: "/floats", method: "POST")
+                operation Op {
+                    output := {
+                        double: Double,
+                        float: Float,
+                    }
+                }
+                """.asSmithyModel(smithyVersion = "2"),
+            )
+        val codegenContext =
+            testCodegenContext(
+                model,
+                settings = testRustSettings(codegenConfig = CoreCodegenConfig(nonFiniteFloats = handling)),
+            )
+        val parser =
+            JsonParserGenerator(
+                codegenContext,
+                HttpTraitHttpBindingResolver(model, ProtocolContentTypes.consistent("application/json")),
+                ::restJsonFieldName,
+            ).operationParser(model.lookup("test#Op"))!!
+
+        val project = TestWorkspace.testProject(codegenContext.symbolProvider)
+        project.lib {
+            unitTest(
+                "non_finite_floats",
+                """
+                let parse = |json: &str| {
+                    ${format(parser)}(json.as_bytes(), test_output::OpOutput::builder()).map(|builder| builder.build())
+                };
+                let output = parse(r#"{"double": 1.5, "float": -2.5}"#).unwrap();
+                assert_eq!((Some(1.5), Some(-2.5)), (output.double, output.float));
+                assert!(parse(r#"{"double": "NaN"}"#).is_err());
+                assert!(parse(r#"{"float": "NaN"}"#).is_err());
+                """,
+            )
+            unitTest(
+                "infinities",
+                if (handling == NonFiniteFloatHandling.CLAMP) {
+                    """
+                    let output = ${format(parser)}(
+                        br#"{"double": "Infinity", "float": "-Infinity"}"#,
+                        test_output::OpOutput::builder(),
+                    ).unwrap().build();
+                    assert_eq!((Some(f64::MAX), Some(f32::MIN)), (output.double, output.float));
+                    """
+                } else {
+                    """
+                    ${format(parser)}(br#"{"float": "Infinity"}"#, test_output::OpOutput::builder())
+                        .expect_err("infinity is rejected");
+                    """
+                },
+            )
+        }
+        model.lookup<OperationShape>("test#Op").outputShape(model).also { output ->
+            output.renderWithModelBuilder(model, codegenContext.symbolProvider, project)
+        }
+        project.compileAndTest()
+    }
 }
@@ -189,17 +189,37 @@ internal class XmlBindingTraitParserGeneratorTest {
                 test = """
                     let xml = br#"<Top>
                         <choice>
-                            <NewVariantName>
-                                <Name>some key</Name>
-                                <Setting>
-                                    <s>hello</s>
-                                </Setting>
-                            </NewVariantName>
+                            <NewVariantName><Name>some key</Name><Setting><s>hello</s></Setting></NewVariantName>
                         </choice>
                     </Top>
                     "#;
                     let output = ${format(operationParser)}(xml, test_output::OpOutput::builder()).unwrap().build();
-                    assert!(output.choice.unwrap().is_unknown());
+                    let choice = output.choice.unwrap();
+                    let unknown = choice.as_unknown().unwrap();
+                    assert_eq!("NewVariantName", unknown.name());
+                    assert_eq!(
+                        Some(&b"<NewVariantName><Name>some key</Name><Setting><s>hello</s></Setting></NewVariantName>"[..]),
+                        unknown.value().and_then(|value| value.as_bytes())
+                    );
+                """,
+            )
+            unitTest(
+                name = "unknown_union_variant_declares_namespaces",
+                test = """
+                    let xml = br#"<Top xmlns="https://example.com/" xmlns:ext="urn:ext">
+                        <choice>
+                            <NewVariantName><ext:Name>some key</ext:Name></NewVariantName>
+                        </choice>
+                    </Top>
+                    "#;
+                    let output = ${format(operationParser)}(xml, test_output::OpOutput::builder()).unwrap().build();
+                    let choice = output.choice.unwrap();
+                    let unknown = choice.as_unknown().unwrap();
+                    // the raw value can be written into another document as-is
+                    assert_eq!(
+                        Some(&br#"<NewVariantName xmlns="https://example.com/" xmlns:ext="urn:ext"><ext:Name>some key</ext:Name></NewVariantName>"#[..]),
+                        unknown.value().and_then(|value| value.as_bytes())
+                    );
                 """,
             )
         }
@@ -218,4 +238,88 @@ internal class XmlBindingTraitParserGeneratorTest {
         }
         project.compileAndTest()
     }
+
+    @Test
+    fun `parses documents from embedded json`() {
+        val model =
+            RecursiveShapeBoxer().transform(
+                OperationNormalizer.transform(
+                    """
+                    namespace test
+                    use aws.protocols#restXml
+
+                    document Doc
+
+                    union Choice {
+                        doc: Doc,
+                        s: String,
+                    }
+
+                    structure Top {
+                        doc: Doc,
+                        choice: Choice,
+                    }
+
+                    @http(uri: "/top", method: "POST")
+                    operation Op {
+                        input: Top,
+                        output: Top
+                    }
+                    """.asSmithyModel(),
+                ),
+            )
+        val codegenContext = testCodegenContext(model)
+        val symbolProvider = codegenContext.symbolProvider
+        val parserGenerator =
+            XmlBindingTraitParserGenerator(
+                codegenContext,
+                RuntimeType.wrappedXmlErrors(TestRuntimeConfig),
+            ) { _, inner -> inner("decoder") }
+        val operationParser = parserGenerator.operationParser(model.lookup("test#Op"))!!
+
+        val choiceShape = model.lookup<UnionShape>("test#Choice")
+        val project = TestWorkspace.testProject(symbolProvider)
+        project.lib {
+            unitTest(name = "parse_documents") {
+                rustTemplate(
+                    """
+                    use aws_smithy_types::{Document, Number};
+                    let xml = br##"<Top>
+                        <doc>{&quot;list&quot;: [1, &quot;&lt;a&amp;b&gt;&quot;]}</doc>
+                        <choice><doc>null</doc></choice>
+                    </Top>
+                    "##;
+                    let output = ${format(operationParser)}(xml, test_output::OpOutput::builder()).unwrap().build();
+                    let expected = Document::Object(
+                        [(
+                            "list".to_string(),
+                            Document::Array(vec![Document::Number(Number::PosInt(1)), Document::String("<a&b>".into())]),
+                        )]
+                        .into(),
+                    );
+                    assert_eq!(output.doc, Some(expected));
+                    assert_eq!(output.choice, Some(#{Choice}::Doc(Document::Null)));
+                    """,
+                    "Choice" to symbolProvider.toSymbol(choiceShape),
+                )
+            }
+            unitTest(
+                name = "invalid_document",
+                test = """
+                    let xml = br#"<Top><doc>{not json}</doc></Top>"#;
+                    ${format(operationParser)}(xml, test_output::OpOutput::builder()).expect_err("invalid document");
+                """,
+            )
+        }
+        model.lookup<StructureShape>("test#Top").also { top ->
+            top.renderWithModelBuilder(model, symbolProvider, project)
+            project.moduleFor(top) {
+                UnionGenerator(model, symbolProvider, this, choiceShape).render()
+            }
+        }
+        model.lookup<OperationShape>("test#Op").outputShape(model).also { out ->
+            out.renderWithModelBuilder(model, symbolProvider, project)
+        }
+        project.compileAndTest()
+    }
 }
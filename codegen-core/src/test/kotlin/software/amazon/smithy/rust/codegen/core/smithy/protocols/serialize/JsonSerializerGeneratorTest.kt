@@ -11,6 +11,8 @@ import software.amazon.smithy.model.knowledge.NullableIndex
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.StringShape
 import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.rust.codegen.core.smithy.CoreCodegenConfig
+import software.amazon.smithy.rust.codegen.core.smithy.NonFiniteFloatHandling
 import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.generators.EnumGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.generators.TestEnumType
@@ -25,6 +27,7 @@ import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
 import software.amazon.smithy.rust.codegen.core.testutil.renderWithModelBuilder
 import software.amazon.smithy.rust.codegen.core.testutil.testCodegenContext
+import software.amazon.smithy.rust.codegen.core.testutil.testRustSettings
 import software.amazon.smithy.rust.codegen.core.testutil.testSymbolProvider
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.inputShape
@@ -147,10 +150,24 @@ class JsonSerializerGeneratorTest {
 
                 let input = crate::test_input::OpInput::builder().top(
                     Top::builder()
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown(aws_smithy_types::UnknownUnionVariant::default()))
                         .build()
                 ).build().unwrap();
                 ${format(operationGenerator)}(&input).expect_err("cannot serialize unknown variant");
+
+                // unknown variants that retain their value are written as they were received
+                let unknown = aws_smithy_types::UnknownUnionVariant::new(
+                    "newVariant",
+                    aws_smithy_types::UnknownUnionValue::Document(aws_smithy_types::Document::from("value")),
+                );
+                let input = crate::test_input::OpInput::builder().top(
+                    Top::builder()
+                        .choice(Choice::Unknown(unknown))
+                        .build()
+                ).build().unwrap();
+                let serialized = ${format(operationGenerator)}(&input).unwrap();
+                let output = std::str::from_utf8(serialized.bytes().unwrap()).unwrap();
+                assert_eq!(output, r#"{"top":{"choice":{"newVariant":"value"}}}"#);
                 """,
             )
         }
@@ -319,7 +336,7 @@ class JsonSerializerGeneratorTest {
                 let input = crate::test_input::OpInput::builder().top(
                     Top::builder()
                         .field("Hello")
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown(aws_smithy_types::UnknownUnionVariant::default()))
                         .extra(45)
                         .build()
                         $maybeUnwrap
@@ -342,4 +359,78 @@ class JsonSerializerGeneratorTest {
         }
         project.compileAndTest()
     }
+
+    @ParameterizedTest
+    @CsvSource("ERROR", "CLAMP")
+    fun `serializers apply the non-finite float handling`(handling: NonFiniteFloatHandling) {
+        val model =
+            OperationNormalizer.transform(
+                """
+                namespace test
+                use aws.protocols#restJson1
+
+                @http(uri: "/floats", method: "POST")
+                operation Op {
+                    input := {
+                        double: Double,
+                        float: Float,
+                    }
+                }
+                """.asSmithyModel(smithyVersion = "2"),
+            )
+        val codegenContext =
+            testCodegenContext(
+                model,
+                settings = testRustSettings(codegenConfig = CoreCodegenConfig(nonFiniteFloats = handling)),
+            )
+        val serializer =
+            JsonSerializerGenerator(
+                codegenContext,
+                HttpTraitHttpBindingResolver(model, ProtocolContentTypes.consistent("application/json")),
+                ::restJsonFieldName,
+            ).operationInputSerializer(model.lookup("test#Op"))!!
+
+        val project = TestWorkspace.testProject(codegenContext.symbolProvider)
+        project.lib {
+            unitTest(
+                "non_finite_floats",
+                """
+                let serialize = |double: f64, float: f32| {
+                    let input = crate::test_input::OpInput::builder().double(double).float(float).build().unwrap();
+                    ${format(serializer)}(&input)
+                        .map(|body| std::str::from_utf8(body.bytes().unwrap()).unwrap().to_owned())
+                };
+                assert_eq!(r#"{"double":1.5,"float":-2.5}"#, serialize(1.5, -2.5).unwrap());
+                assert!(serialize(f64::NAN, 0.0).is_err());
+                assert!(serialize(0.0, f32::NAN).is_err());
+                """,
+            )
+            unitTest(
+                "infinities",
+                if (handling == NonFiniteFloatHandling.CLAMP) {
+                    """
+                    let input = crate::test_input::OpInput::builder()
+                        .double(f64::INFINITY)
+                        .float(f32::NEG_INFINITY)
+                        .build()
+                        .unwrap();
+                    let body = ${format(serializer)}(&input).unwrap();
+                    assert_eq!(
+                        r#"{"double":1.7976931348623157e308,"float":-3.4028234663852886e38}"#,
+                        std::str::from_utf8(body.bytes().unwrap()).unwrap()
+                    );
+                    """
+                } else {
+                    """
+                    let input = crate::test_input::OpInput::builder().double(f64::INFINITY).build().unwrap();
+                    ${format(serializer)}(&input).expect_err("infinity is rejected");
+                    """
+                },
+            )
+        }
+        model.lookup<OperationShape>("test#Op").inputShape(model).also { input ->
+            input.renderWithModelBuilder(model, codegenContext.symbolProvider, project)
+        }
+        project.compileAndTest()
+    }
 }
@@ -5,6 +5,7 @@
 
 package software.amazon.smithy.rust.codegen.core.smithy.protocols.serialize
 
+import org.junit.jupiter.api.Test
 import org.junit.jupiter.params.ParameterizedTest
 import org.junit.jupiter.params.provider.CsvSource
 import software.amazon.smithy.model.knowledge.NullableIndex
@@ -12,6 +13,8 @@ import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.StringShape
 import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.rust.codegen.core.smithy.CoreCodegenConfig
+import software.amazon.smithy.rust.codegen.core.smithy.NonFiniteFloatHandling
 import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.generators.EnumGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.generators.TestEnumType
@@ -26,6 +29,7 @@ import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
 import software.amazon.smithy.rust.codegen.core.testutil.renderWithModelBuilder
 import software.amazon.smithy.rust.codegen.core.testutil.testCodegenContext
+import software.amazon.smithy.rust.codegen.core.testutil.testRustSettings
 import software.amazon.smithy.rust.codegen.core.testutil.testSymbolProvider
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.inputShape
@@ -152,10 +156,24 @@ internal class XmlBindingTraitSerializerGeneratorTest {
                 use test_model::{Top, Choice};
                 let input = crate::test_input::OpInput::builder().payload(
                     Top::builder()
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown(aws_smithy_types::UnknownUnionVariant::default()))
                         .build()
                 ).build().unwrap();
                 ${format(operationSerializer)}(&input.payload.unwrap()).expect_err("cannot serialize unknown variant");
+
+                // unknown variants that retain their element are written as they were received
+                let unknown = aws_smithy_types::UnknownUnionVariant::new(
+                    "NewVariant",
+                    aws_smithy_types::UnknownUnionValue::Bytes("<NewVariant><a>1</a></NewVariant>".as_bytes().into()),
+                );
+                let input = crate::test_input::OpInput::builder().payload(
+                    Top::builder()
+                        .choice(Choice::Unknown(unknown))
+                        .build()
+                ).build().unwrap();
+                let serialized = ${format(operationSerializer)}(&input.payload.unwrap()).unwrap();
+                let output = std::str::from_utf8(&serialized).unwrap();
+                assert_eq!(output, "<Top><choice><NewVariant><a>1</a></NewVariant></choice></Top>");
                 """,
             )
         }
@@ -320,7 +338,7 @@ internal class XmlBindingTraitSerializerGeneratorTest {
                 let input = crate::test_input::OpInput::builder().payload(
                     Top::builder()
                         .field("Hello")
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown(aws_smithy_types::UnknownUnionVariant::default()))
                         .extra(45)
                         .build()
                         $maybeUnwrap
@@ -342,4 +360,151 @@ internal class XmlBindingTraitSerializerGeneratorTest {
         }
         project.compileAndTest()
     }
+
+    @Test
+    fun `serializes documents as embedded json`() {
+        val model =
+            RecursiveShapeBoxer().transform(
+                OperationNormalizer.transform(
+                    """
+                    namespace test
+                    use aws.protocols#restXml
+
+                    document Doc
+
+                    union Choice {
+                        doc: Doc,
+                        s: String,
+                    }
+
+                    structure Top {
+                        doc: Doc,
+                        choice: Choice,
+                    }
+
+                    structure OpInput {
+                        @httpPayload
+                        payload: Top
+                    }
+
+                    @http(uri: "/top", method: "POST")
+                    operation Op {
+                        input: OpInput,
+                    }
+                    """.asSmithyModel(),
+                ),
+            )
+        val codegenContext = testCodegenContext(model)
+        val symbolProvider = codegenContext.symbolProvider
+        val serializerGenerator =
+            XmlBindingTraitSerializerGenerator(
+                codegenContext,
+                HttpTraitHttpBindingResolver(model, ProtocolContentTypes.consistent("application/xml")),
+            )
+        val operationSerializer = serializerGenerator.payloadSerializer(model.lookup("test#OpInput\$payload"))
+
+        val project = TestWorkspace.testProject(symbolProvider)
+        project.lib {
+            unitTest(
+                "serialize_documents",
+                """
+                use aws_smithy_types::{Document, Number};
+                use test_model::{Choice, Top};
+                let input = crate::test_input::OpInput::builder().payload(
+                    Top::builder()
+                        .doc(Document::Array(vec![Document::Number(Number::PosInt(1)), Document::String("<a&b>".into())]))
+                        .choice(Choice::Doc(Document::Null))
+                        .build()
+                ).build().unwrap();
+                let serialized = ${format(operationSerializer)}(&input.payload.unwrap()).unwrap();
+                let output = std::str::from_utf8(&serialized).unwrap();
+                assert_eq!(output, "<Top><doc>[1,&quot;&lt;a&amp;b&gt;&quot;]</doc><choice><doc>null</doc></choice></Top>");
+                """,
+            )
+        }
+        model.lookup<StructureShape>("test#Top").also { top ->
+            top.renderWithModelBuilder(model, symbolProvider, project)
+            project.moduleFor(top) {
+                UnionGenerator(model, symbolProvider, this, model.lookup("test#Choice")).render()
+            }
+        }
+        model.lookup<OperationShape>("test#Op").inputShape(model).also { input ->
+            input.renderWithModelBuilder(model, symbolProvider, project)
+        }
+        project.compileAndTest()
+    }
+
+    @ParameterizedTest
+    @CsvSource("ERROR", "CLAMP")
+    fun `serializers apply the non-finite float handling`(handling: NonFiniteFloatHandling) {
+        val model =
+            OperationNormalizer.transform(
+                """
+                namespace test
+                use aws.protocols#restXml
+
+                @http(uri: "/floats", method: "POST")
+                operation Op {
+                    input := {
+                        double: Double,
+                        @xmlAttribute
+                        float: Float,
+                    }
+                }
+                """.asSmithyModel(smithyVersion = "2"),
+            )
+        val codegenContext =
+            testCodegenContext(
+                model,
+                settings = testRustSettings(codegenConfig = CoreCodegenConfig(nonFiniteFloats = handling)),
+            )
+        val serializer =
+            XmlBindingTraitSerializerGenerator(
+                codegenContext,
+                HttpTraitHttpBindingResolver(model, ProtocolContentTypes.consistent("application/xml")),
+            ).operationInputSerializer(model.lookup("test#Op"))!!
+
+        val project = TestWorkspace.testProject(codegenContext.symbolProvider)
+        project.lib {
+            unitTest(
+                "non_finite_floats",
+                """
+                let serialize = |double: f64, float: f32| {
+                    let input = crate::test_input::OpInput::builder().double(double).float(float).build().unwrap();
+                    ${format(serializer)}(&input)
+                        .map(|body| std::str::from_utf8(body.bytes().unwrap()).unwrap().to_owned())
+                };
+                assert_eq!(r#"<OpInput float="-2.5"><double>1.5</double></OpInput>"#, serialize(1.5, -2.5).unwrap());
+                assert!(serialize(f64::NAN, 0.0).is_err());
+                assert!(serialize(0.0, f32::NAN).is_err());
+                """,
+            )
+            unitTest(
+                "infinities",
+                if (handling == NonFiniteFloatHandling.CLAMP) {
+                    """
+                    let input = crate::test_input::OpInput::builder()
+                        .double(f64::INFINITY)
+                        .float(f32::NEG_INFINITY)
+                        .build()
+                        .unwrap();
+                    let body = ${format(serializer)}(&input).unwrap();
+                    assert_eq!(
+                        r#"<OpInput float="-3.4028235e38"><double>1.7976931348623157e308</double></OpInput>"#,
+                        std::str::from_utf8(body.bytes().unwrap()).unwrap()
+                    );
+                    """
+                } else {
+                    """
+                    let input = crate::test_input::OpInput::builder().float(f32::INFINITY).build().unwrap();
+                    ${format(serializer)}(&input).expect_err("infinity is rejected");
+                    """
+                },
+            )
+        }
+        model.lookup<OperationShape>("test#Op").inputShape(model).also { input ->
+            input.renderWithModelBuilder(model, codegenContext.symbolProvider, project)
+        }
+        project.compileAndTest()
+    }
 }
@@ -0,0 +1,665 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.serde
+
+import software.amazon.smithy.codegen.core.Symbol
+import software.amazon.smithy.model.knowledge.TopDownIndex
+import software.amazon.smithy.model.shapes.BlobShape
+import software.amazon.smithy.model.shapes.BooleanShape
+import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.DocumentShape
+import software.amazon.smithy.model.shapes.DoubleShape
+import software.amazon.smithy.model.shapes.FloatShape
+import software.amazon.smithy.model.shapes.MapShape
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.NumberShape
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.ServiceShape
+import software.amazon.smithy.model.shapes.Shape
+import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.shapes.TimestampShape
+import software.amazon.smithy.model.shapes.UnionShape
+import software.amazon.smithy.model.traits.EnumTrait
+import software.amazon.smithy.model.traits.SensitiveTrait
+import software.amazon.smithy.model.traits.SparseTrait
+import software.amazon.smithy.model.traits.StreamingTrait
+import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.RustType
+import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.join
+import software.amazon.smithy.rust.codegen.core.rustlang.render
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustBlock
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.stripOuter
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.SimpleShapes
+import software.amazon.smithy.rust.codegen.core.smithy.contextName
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderGenerator
+import software.amazon.smithy.rust.codegen.core.smithy.generators.setterName
+import software.amazon.smithy.rust.codegen.core.smithy.mapRustType
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.shapeFunctionName
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.shapeModuleName
+import software.amazon.smithy.rust.codegen.core.smithy.symbolBuilder
+import software.amazon.smithy.rust.codegen.core.util.PANIC
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.isEventStream
+import software.amazon.smithy.rust.codegen.core.util.isTargetUnit
+
+/**
+ * Generates `serde::Deserialize` implementations that read the format written by [SerializeImplGenerator].
+ *
+ * Structures, unions, and enums implement `Deserialize` directly. All other types (including collections
+ * of model types) are deserialized through the `Deserialized` wrapper, since the Rust types of these shapes
+ * are defined outside the generated crate. Deserialization is lenient:
+ * - Floats are read from numbers as well as from the strings written for out-of-range floats.
+ * - Blobs are read from base64 strings for human-readable formats and from bytes otherwise.
+ *
+ * Values that were redacted during serialization can't be deserialized. Redacted structures, collections, and
+ * other non-string values fail to deserialize on their own, and the placeholder is rejected explicitly wherever
+ * a `@sensitive` string, enum, or document is read.
+ *
+ * This is only supported for clients, since structures are built with their client builders.
+ */
+class DeserializeImplGenerator(private val codegenContext: CodegenContext) {
+    private val model = codegenContext.model
+    private val symbolProvider = codegenContext.symbolProvider
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val topIndex = TopDownIndex.of(model)
+    private val codegenScope =
+        arrayOf(
+            *RuntimeType.preludeScope,
+            *SupportStructures.codegenScope,
+            "Deserialized" to SupportStructures.deserialized(),
+            "fmt" to RuntimeType.stdFmt,
+        )
+
+    fun generateRootDeserializerForShape(shape: Shape): Writable = deserializerFn(shape)
+
+    /**
+     * Returns a writable that adds the deserializers required for `shape` as dependencies.
+     */
+    private fun deserializerFn(shape: Shape): Writable {
+        if (shape is ServiceShape) {
+            return topIndex.getContainedOperations(shape).map {
+                deserializerFn(it)
+            }.join("\n")
+        } else if (shape is OperationShape) {
+            if (shape.isEventStream(model)) {
+                // Don't generate deserializers for event streams
+                return writable { }
+            }
+            return writable {
+                deserializerFn(model.expectShape(shape.inputShape))(this)
+                deserializerFn(model.expectShape(shape.outputShape))(this)
+            }
+        }
+        val name = symbolProvider.shapeFunctionName(codegenContext.serviceShape, shape) + "_deserialize"
+        val deps =
+            when (shape) {
+                is StructureShape -> RuntimeType.forInlineFun(name, serdeSubmodule(shape), structDeserializeImpl(shape))
+                is UnionShape -> RuntimeType.forInlineFun(name, serdeSubmodule(shape), unionDeserializeImpl(shape))
+                is StringShape ->
+                    if (shape.hasTrait<EnumTrait>()) {
+                        RuntimeType.forInlineFun(name, serdeSubmodule(shape), enumDeserializeImpl(shape))
+                    } else {
+                        deserializeDirectly(shape)
+                    }
+
+                is TimestampShape -> deserializeDateTime()
+                is BlobShape ->
+                    if (shape.hasTrait<StreamingTrait>()) {
+                        deserializeByteStream()
+                    } else {
+                        deserializeBlob()
+                    }
+
+                is FloatShape, is DoubleShape -> deserializeFloat(shape as NumberShape)
+                is NumberShape, is BooleanShape -> deserializeDirectly(shape)
+                is DocumentShape -> deserializeDocument()
+                is MapShape, is CollectionShape -> null
+                else -> PANIC("No deserializer supported for $shape")
+            }
+
+        return writable {
+            when (shape) {
+                is MapShape -> {
+                    deserializerFn(model.expectShape(shape.key.target))(this)
+                    deserializerFn(model.expectShape(shape.value.target))(this)
+                }
+
+                is CollectionShape -> deserializerFn(model.expectShape(shape.member.target))(this)
+                else -> deps?.toSymbol()?.also { addDependency(it) }
+            }
+        }
+    }
+
+    private fun serdeSubmodule(shape: Shape) =
+        RustModule.pubCrate(
+            symbolProvider.shapeModuleName(codegenContext.serviceShape, shape),
+            parent = SerdeModule,
+        )
+
+    /**
+     * Deserialize a type that already implements `Deserialize`
+     */
+    private fun deserializeDirectly(shape: Shape): RuntimeType {
+        val type = SimpleShapes.getValue(shape::class)
+        return RuntimeType.forInlineFun("Deserialize${type.name}", SerializeImplGenerator.PrimitiveShapesModule) {
+            implDeserializeWrapped(symbolBuilder(shape, type).build()) {
+                rustTemplate("<${type.render()} as #{serde}::Deserialize>::deserialize(deserializer).map(#{Deserialized})", *codegenScope)
+            }
+        }
+    }
+
+    private fun deserializeFloat(shape: NumberShape): RuntimeType {
+        val type = SimpleShapes.getValue(shape::class).name
+        return RuntimeType.forInlineFun("Deserialize$type", SerializeImplGenerator.PrimitiveShapesModule) {
+            implDeserializeWrapped(symbolBuilder(shape, SimpleShapes.getValue(shape::class)).build()) {
+                rustTemplate(
+                    """
+                    struct Visitor;
+                    impl #{serde}::de::Visitor<'_> for Visitor {
+                        type Value = $type;
+                        fn expecting(&self, formatter: &mut #{fmt}::Formatter<'_>) -> #{fmt}::Result {
+                            formatter.write_str("a number, `NaN`, `Infinity`, or `-Infinity`")
+                        }
+                        fn visit_f64<E: #{serde}::de::Error>(self, value: f64) -> #{Result}<Self::Value, E> {
+                            #{Ok}(value as $type)
+                        }
+                        fn visit_i64<E: #{serde}::de::Error>(self, value: i64) -> #{Result}<Self::Value, E> {
+                            #{Ok}(value as $type)
+                        }
+                        fn visit_u64<E: #{serde}::de::Error>(self, value: u64) -> #{Result}<Self::Value, E> {
+                            #{Ok}(value as $type)
+                        }
+                        fn visit_str<E: #{serde}::de::Error>(self, value: &str) -> #{Result}<Self::Value, E> {
+                            match value {
+                                "NaN" => #{Ok}($type::NAN),
+                                "Infinity" => #{Ok}($type::INFINITY),
+                                "-Infinity" => #{Ok}($type::NEG_INFINITY),
+                                _ => #{Err}(E::invalid_value(#{serde}::de::Unexpected::Str(value), &self)),
+                            }
+                        }
+                    }
+                    // Out-of-range floats are written as strings, which requires a self-describing format
+                    let value = if deserializer.is_human_readable() {
+                        deserializer.deserialize_any(Visitor)?
+                    } else {
+                        deserializer.deserialize_$type(Visitor)?
+                    };
+                    #{Ok}(#{Deserialized}(value))
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
+    }
+
+    private fun deserializeDateTime(): RuntimeType =
+        RuntimeType.forInlineFun("DeserializeDateTime", SerializeImplGenerator.PrimitiveShapesModule) {
+            implDeserializeWrapped(RuntimeType.dateTime(runtimeConfig).toSymbol()) {
+                rustTemplate(
+                    """
+                    let value = <#{String} as #{serde}::Deserialize>::deserialize(deserializer)?;
+                    // Dates that are out of range for RFC-3339 are written as epoch seconds
+                    #{DateTime}::from_str(&value, #{Format}::DateTime)
+                        .or_else(|_| #{DateTime}::from_str(&value, #{Format}::EpochSeconds))
+                        .map(#{Deserialized})
+                        .map_err(#{serde}::de::Error::custom)
+                    """,
+                    *codegenScope,
+                    "DateTime" to RuntimeType.dateTime(runtimeConfig),
+                    "Format" to RuntimeType.smithyTypes(runtimeConfig).resolve("date_time::Format"),
+                )
+            }
+        }
+
+    private fun deserializeBlob(): RuntimeType =
+        RuntimeType.forInlineFun("DeserializeBlob", SerializeImplGenerator.PrimitiveShapesModule) {
+            implDeserializeWrapped(RuntimeType.blob(runtimeConfig).toSymbol()) {
+                rustTemplate(
+                    "#{deserialize_bytes}(deserializer).map(|bytes| #{Deserialized}(#{Blob}::new(bytes)))",
+                    *codegenScope,
+                    "Blob" to RuntimeType.blob(runtimeConfig),
+                    "deserialize_bytes" to deserializeBytes(),
+                )
+            }
+        }
+
+    private fun deserializeByteStream(): RuntimeType =
+        RuntimeType.forInlineFun("DeserializeByteStream", SerializeImplGenerator.PrimitiveShapesModule) {
+            implDeserializeWrapped(RuntimeType.byteStream(runtimeConfig).toSymbol()) {
+                rustTemplate(
+                    "#{deserialize_bytes}(deserializer).map(|bytes| #{Deserialized}(#{ByteStream}::from(bytes)))",
+                    *codegenScope,
+                    "ByteStream" to RuntimeType.byteStream(runtimeConfig),
+                    "deserialize_bytes" to deserializeBytes(),
+                )
+            }
+        }
+
+    /**
+     * Reads the bytes of blobs and byte streams, which are written as base64 strings for human-readable formats
+     */
+    private fun deserializeBytes(): RuntimeType =
+        RuntimeType.forInlineFun("deserialize_bytes", SerializeImplGenerator.PrimitiveShapesModule) {
+            rustTemplate(
+                """
+                pub(crate) fn deserialize_bytes<'de, D>(deserializer: D) -> #{Result}<#{Vec}<u8>, D::Error>
+                where
+                    D: #{serde}::Deserializer<'de>,
+                {
+                    struct Visitor;
+                    impl<'de> #{serde}::de::Visitor<'de> for Visitor {
+                        type Value = #{Vec}<u8>;
+                        fn expecting(&self, formatter: &mut #{fmt}::Formatter<'_>) -> #{fmt}::Result {
+                            formatter.write_str("bytes or a base64-encoded string")
+                        }
+                        fn visit_str<E: #{serde}::de::Error>(self, value: &str) -> #{Result}<Self::Value, E> {
+                            #{base64_decode}(value).map_err(E::custom)
+                        }
+                        fn visit_bytes<E: #{serde}::de::Error>(self, value: &[u8]) -> #{Result}<Self::Value, E> {
+                            #{Ok}(value.to_vec())
+                        }
+                        fn visit_byte_buf<E: #{serde}::de::Error>(self, value: #{Vec}<u8>) -> #{Result}<Self::Value, E> {
+                            #{Ok}(value)
+                        }
+                        fn visit_seq<A>(self, mut seq: A) -> #{Result}<Self::Value, A::Error>
+                        where
+                            A: #{serde}::de::SeqAccess<'de>,
+                        {
+                            let mut bytes = #{Vec}::with_capacity(seq.size_hint().unwrap_or_default());
+                            while let #{Some}(byte) = seq.next_element()? {
+                                bytes.push(byte);
+                            }
+                            #{Ok}(bytes)
+                        }
+                    }
+                    if deserializer.is_human_readable() {
+                        deserializer.deserialize_str(Visitor)
+                    } else {
+                        deserializer.deserialize_byte_buf(Visitor)
+                    }
+                }
+                """,
+                *codegenScope,
+                "base64_decode" to RuntimeType.base64Decode(runtimeConfig),
+            )
+        }
+
+    private fun deserializeDocument(): RuntimeType =
+        RuntimeType.forInlineFun("DeserializeDocument", SerializeImplGenerator.PrimitiveShapesModule) {
+            implDeserializeWrapped(RuntimeType.document(runtimeConfig).toSymbol()) {
+                rustTemplate(
+                    """
+                    struct Visitor;
+                    impl<'de> #{serde}::de::Visitor<'de> for Visitor {
+                        type Value = #{Document};
+                        fn expecting(&self, formatter: &mut #{fmt}::Formatter<'_>) -> #{fmt}::Result {
+                            formatter.write_str("a document")
+                        }
+                        fn visit_bool<E: #{serde}::de::Error>(self, value: bool) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::Bool(value))
+                        }
+                        fn visit_i64<E: #{serde}::de::Error>(self, value: i64) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::Number(if value < 0 {
+                                #{Number}::NegInt(value)
+                            } else {
+                                #{Number}::PosInt(value as u64)
+                            }))
+                        }
+                        fn visit_u64<E: #{serde}::de::Error>(self, value: u64) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::Number(#{Number}::PosInt(value)))
+                        }
+                        fn visit_f64<E: #{serde}::de::Error>(self, value: f64) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::Number(#{Number}::Float(value)))
+                        }
+                        fn visit_str<E: #{serde}::de::Error>(self, value: &str) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::String(value.into()))
+                        }
+                        fn visit_string<E: #{serde}::de::Error>(self, value: #{String}) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::String(value))
+                        }
+                        fn visit_unit<E: #{serde}::de::Error>(self) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::Null)
+                        }
+                        fn visit_none<E: #{serde}::de::Error>(self) -> #{Result}<Self::Value, E> {
+                            #{Ok}(#{Document}::Null)
+                        }
+                        fn visit_some<D>(self, deserializer: D) -> #{Result}<Self::Value, D::Error>
+                        where
+                            D: #{serde}::Deserializer<'de>,
+                        {
+                            deserializer.deserialize_any(self)
+                        }
+                        fn visit_seq<A>(self, mut seq: A) -> #{Result}<Self::Value, A::Error>
+                        where
+                            A: #{serde}::de::SeqAccess<'de>,
+                        {
+                            let mut values = #{Vec}::with_capacity(seq.size_hint().unwrap_or_default());
+                            while let #{Some}(#{Deserialized}(value)) = seq.next_element()? {
+                                values.push(value);
+                            }
+                            #{Ok}(#{Document}::Array(values))
+                        }
+                        fn visit_map<A>(self, mut map: A) -> #{Result}<Self::Value, A::Error>
+                        where
+                            A: #{serde}::de::MapAccess<'de>,
+                        {
+                            let mut values = ::std::collections::HashMap::with_capacity(map.size_hint().unwrap_or_default());
+                            while let #{Some}((key, #{Deserialized}(value))) = map.next_entry::<#{String}, _>()? {
+                                values.insert(key, value);
+                            }
+                            #{Ok}(#{Document}::Object(values))
+                        }
+                    }
+                    deserializer.deserialize_any(Visitor).map(#{Deserialized})
+                    """,
+                    *codegenScope,
+                    "Document" to RuntimeType.document(runtimeConfig),
+                    "Number" to RuntimeType.smithyTypes(runtimeConfig).resolve("Number"),
+                )
+            }
+        }
+
+    /**
+     * Deserialize an enum from its string value. Unknown values are preserved in the `Unknown` variant.
+     */
+    private fun enumDeserializeImpl(shape: StringShape): Writable =
+        writable {
+            implDeserialize(symbolProvider.toSymbol(shape)) {
+                rustTemplate(
+                    """
+                    let value = <#{String} as #{serde}::Deserialize>::deserialize(deserializer)?;
+                    #{Ok}(Self::from(value.as_str()))
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
+
+    private fun structDeserializeImpl(shape: StructureShape): Writable {
+        val shapeSymbol = symbolProvider.toSymbol(shape)
+        val structName = shape.contextName(codegenContext.serviceShape)
+        return writable {
+            implDeserialize(shapeSymbol) {
+                val readFields =
+                    writable {
+                        rustBlock("match key.as_str()") {
+                            for (member in shape.members()) {
+                                val target = model.expectShape(member.target)
+                                deserializerFn(target)(this)
+                                // Builder setters take the member type without its `Option`
+                                val valueType =
+                                    symbolProvider.toSymbol(member).mapRustType { it.stripOuter<RustType.Option>() }
+                                rustTemplate(
+                                    """
+                                    ${member.memberName.dq()} => {
+                                        let #{Deserialized}(value) = map.next_value::<#{Deserialized}<#{Value}>>()?;
+                                        #{reject_redacted}
+                                        builder = builder.${member.setterName()}(#{Some}(value));
+                                    }
+                                    """,
+                                    *codegenScope,
+                                    "Value" to valueType,
+                                    "reject_redacted" to rejectRedacted(member),
+                                )
+                            }
+                            rustTemplate(
+                                """
+                                _ => {
+                                    map.next_value::<#{serde}::de::IgnoredAny>()?;
+                                }
+                                """,
+                                *codegenScope,
+                            )
+                        }
+                    }
+                val build =
+                    writable {
+                        if (BuilderGenerator.hasFallibleBuilder(shape, symbolProvider)) {
+                            rustTemplate("builder.build().map_err(#{serde}::de::Error::custom)", *codegenScope)
+                        } else {
+                            rustTemplate("#{Ok}(builder.build())", *codegenScope)
+                        }
+                    }
+                rustTemplate(
+                    """
+                    struct Visitor;
+                    impl<'de> #{serde}::de::Visitor<'de> for Visitor {
+                        type Value = #{Shape};
+                        fn expecting(&self, formatter: &mut #{fmt}::Formatter<'_>) -> #{fmt}::Result {
+                            formatter.write_str(${"struct $structName".dq()})
+                        }
+                        ##[allow(unused_mut, clippy::match_single_binding)]
+                        fn visit_map<A>(self, mut map: A) -> #{Result}<Self::Value, A::Error>
+                        where
+                            A: #{serde}::de::MapAccess<'de>,
+                        {
+                            let mut builder = #{Shape}::builder();
+                            while let #{Some}(key) = map.next_key::<#{String}>()? {
+                                #{read_fields}
+                            }
+                            #{build}
+                        }
+                    }
+                    const FIELDS: &[&str] = &[${shape.members().joinToString { it.memberName.dq() }}];
+                    deserializer.deserialize_struct(${structName.dq()}, FIELDS, Visitor)
+                    """,
+                    *codegenScope,
+                    "Shape" to shapeSymbol,
+                    "read_fields" to readFields,
+                    "build" to build,
+                )
+            }
+        }
+    }
+
+    private fun unionDeserializeImpl(shape: UnionShape): Writable {
+        val unionSymbol = symbolProvider.toSymbol(shape)
+        val unionName = shape.contextName(codegenContext.serviceShape)
+        return writable {
+            implDeserialize(unionSymbol) {
+                val readVariant =
+                    writable {
+                        rustBlock("match variant.as_str()") {
+                            for (member in shape.members()) {
+                                val variantName = symbolProvider.toMemberName(member)
+                                if (member.isTargetUnit()) {
+                                    rustTemplate(
+                                        "${member.memberName.dq()} => access.unit_variant().map(|_| #{Union}::$variantName),",
+                                        "Union" to unionSymbol,
+                                    )
+                                } else if (redactedCheck(model.expectShape(member.target), "value") != null) {
+                                    deserializerFn(model.expectShape(member.target))(this)
+                                    rustTemplate(
+                                        """
+                                        ${member.memberName.dq()} => {
+                                            let #{Deserialized}(value) = access.newtype_variant::<#{Deserialized}<#{Value}>>()?;
+                                            #{reject_redacted}
+                                            #{Ok}(#{Union}::$variantName(value))
+                                        }
+                                        """,
+                                        *codegenScope,
+                                        "Union" to unionSymbol,
+                                        "Value" to symbolProvider.toSymbol(member),
+                                        "reject_redacted" to rejectRedacted(member),
+                                    )
+                                } else {
+                                    deserializerFn(model.expectShape(member.target))(this)
+                                    rustTemplate(
+                                        """
+                                        ${member.memberName.dq()} => access
+                                            .newtype_variant::<#{Deserialized}<#{Value}>>()
+                                            .map(|value| #{Union}::$variantName(value.0)),
+                                        """,
+                                        *codegenScope,
+                                        "Union" to unionSymbol,
+                                        "Value" to symbolProvider.toSymbol(member),
+                                    )
+                                }
+                            }
+                            rustTemplate(
+                                "_ => #{Err}(#{serde}::de::Error::unknown_variant(&variant, VARIANTS)),",
+                                *codegenScope,
+                            )
+                        }
+                    }
+                rustTemplate(
+                    """
+                    const VARIANTS: &[&str] = &[${shape.members().joinToString { it.memberName.dq() }}];
+                    struct Visitor;
+                    impl<'de> #{serde}::de::Visitor<'de> for Visitor {
+                        type Value = #{Union};
+                        fn expecting(&self, formatter: &mut #{fmt}::Formatter<'_>) -> #{fmt}::Result {
+                            formatter.write_str(${"union $unionName".dq()})
+                        }
+                        fn visit_enum<A>(self, data: A) -> #{Result}<Self::Value, A::Error>
+                        where
+                            A: #{serde}::de::EnumAccess<'de>,
+                        {
+                            use #{serde}::de::VariantAccess;
+                            let (variant, access) = data.variant::<#{String}>()?;
+                            #{read_variant}
+                        }
+                    }
+                    deserializer.deserialize_enum(${unionName.dq()}, VARIANTS, Visitor)
+                    """,
+                    *codegenScope,
+                    "Union" to unionSymbol,
+                    "read_variant" to readVariant,
+                )
+            }
+        }
+    }
+
+    /**
+     * Returns an error if `value`, the deserialized value of `member`, holds the placeholder that is written in
+     * place of redacted sensitive values, since it would otherwise be read as a literal string.
+     */
+    private fun rejectRedacted(member: MemberShape): Writable =
+        writable {
+            val check = redactedCheck(model.expectShape(member.target), "value") ?: return@writable
+            rustTemplate(
+                """
+                if #{check} {
+                    return #{Err}(#{serde}::de::Error::custom(
+                        ${"`${member.memberName}` was redacted when it was serialized and can't be deserialized".dq()},
+                    ));
+                }
+                """,
+                *codegenScope,
+                "check" to check,
+            )
+        }
+
+    /**
+     * Returns a boolean expression that checks whether `expr`, a value of `shape` or a reference to one, holds a redacted
+     * placeholder, or `null` if it can't. Structures and unions check their own members when they're deserialized.
+     */
+    private fun redactedCheck(
+        shape: Shape,
+        expr: String,
+    ): Writable? {
+        fun elements(
+            iter: String,
+            sparse: Boolean,
+            target: Shape,
+        ): Writable? {
+            val check = redactedCheck(target, "value") ?: return null
+            val flatten = if (sparse) ".flatten()" else ""
+            return writable { rustTemplate("$expr.$iter()$flatten.any(|value| #{check})", "check" to check) }
+        }
+        return when (shape) {
+            is StringShape ->
+                if (shape.hasTrait<SensitiveTrait>()) {
+                    writable { rust("$expr.as_str() == ${SupportStructures.REDACTED.dq()}") }
+                } else {
+                    null
+                }
+
+            is DocumentShape ->
+                if (shape.hasTrait<SensitiveTrait>()) {
+                    writable {
+                        rustTemplate(
+                            "matches!(&$expr, #{Document}::String(value) if value == ${SupportStructures.REDACTED.dq()})",
+                            "Document" to RuntimeType.document(runtimeConfig),
+                        )
+                    }
+                } else {
+                    null
+                }
+
+            is CollectionShape -> elements("iter", shape.hasTrait<SparseTrait>(), model.expectShape(shape.member.target))
+            is MapShape -> {
+                val keys = elements("keys", false, model.expectShape(shape.key.target))
+                val values = elements("values", shape.hasTrait<SparseTrait>(), model.expectShape(shape.value.target))
+                listOfNotNull(keys, values).takeIf { it.isNotEmpty() }?.join(" || ")
+            }
+
+            else -> null
+        }
+    }
+
+    /**
+     * Implements `Deserialize` for a model type, and for the `Deserialized` wrapper of the type so that
+     * collections of the type can be deserialized.
+     */
+    private fun RustWriter.implDeserialize(
+        shape: Symbol,
+        block: Writable,
+    ) {
+        rustTemplate(
+            """
+            impl<'de> #{serde}::Deserialize<'de> for #{Shape} {
+                fn deserialize<D>(deserializer: D) -> #{Result}<Self, D::Error>
+                where
+                    D: #{serde}::Deserializer<'de>,
+                {
+                    #{body}
+                }
+            }
+            """,
+            *codegenScope,
+            "Shape" to shape,
+            "body" to block,
+        )
+        implDeserializeWrapped(shape) {
+            rustTemplate(
+                "<#{Shape} as #{serde}::Deserialize>::deserialize(deserializer).map(#{Deserialized})",
+                *codegenScope,
+                "Shape" to shape,
+            )
+        }
+    }
+
+    private fun RustWriter.implDeserializeWrapped(
+        shape: Symbol,
+        block: Writable,
+    ) {
+        rustTemplate(
+            """
+            impl<'de> #{serde}::Deserialize<'de> for #{Deserialized}<#{Shape}> {
+                fn deserialize<D>(deserializer: D) -> #{Result}<Self, D::Error>
+                where
+                    D: #{serde}::Deserializer<'de>,
+                {
+                    #{body}
+                }
+            }
+            """,
+            *codegenScope,
+            "Shape" to shape,
+            "body" to block,
+        )
+    }
+}
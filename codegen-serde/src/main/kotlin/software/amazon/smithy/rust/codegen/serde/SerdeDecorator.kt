@@ -5,6 +5,7 @@
 
 package software.amazon.smithy.rust.codegen.serde
 
+import software.amazon.smithy.build.SmithyBuildException
 import software.amazon.smithy.model.neighbor.Walker
 import software.amazon.smithy.model.shapes.Shape
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
@@ -14,7 +15,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.Feature
 import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
-import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.getTrait
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.customize.ServerCodegenDecorator
 
@@ -43,7 +44,12 @@ class ServerSerdeDecorator : ServerCodegenDecorator {
     override fun extras(
         codegenContext: ServerCodegenContext,
         rustCrate: RustCrate,
-    ) = extrasCommon(codegenContext, rustCrate)
+    ) {
+        if (deserializationRoots(codegenContext).isNotEmpty()) {
+            throw SmithyBuildException("deserialize is only supported for clients.")
+        }
+        extrasCommon(codegenContext, rustCrate)
+    }
 }
 
 // Just a common function to keep things DRY.
@@ -63,13 +69,35 @@ private fun extrasCommon(
             addDependency(SupportStructures.serializeUnredacted().toSymbol())
         }
     }
+    val deserializationRoots = deserializationRoots(codegenContext)
+    if (deserializationRoots.isNotEmpty()) {
+        rustCrate.mergeFeature(SerdeFeature)
+        val generator = DeserializeImplGenerator(codegenContext)
+        rustCrate.withModule(SerdeModule) {
+            deserializationRoots.forEach {
+                generator.generateRootDeserializerForShape(it)(this)
+            }
+        }
+    }
 }
 
 /**
  * All entry points for serialization in the service closure.
  */
-fun serializationRoots(ctx: CodegenContext): List<Shape> {
+fun serializationRoots(ctx: CodegenContext): List<Shape> = serdeRoots(ctx) { it.serialize }
+
+/**
+ * All entry points for deserialization in the service closure.
+ */
+fun deserializationRoots(ctx: CodegenContext): List<Shape> = serdeRoots(ctx) { it.deserialize }
+
+private fun serdeRoots(
+    ctx: CodegenContext,
+    predicate: (SerdeTrait) -> Boolean,
+): List<Shape> {
     val serviceShape = ctx.serviceShape
     val walker = Walker(ctx.model)
-    return walker.walkShapes(serviceShape).filter { it.hasTrait<SerdeTrait>() }
+    return walker.walkShapes(serviceShape).filter { shape ->
+        shape.getTrait<SerdeTrait>()?.let(predicate) ?: false
+    }
 }
@@ -445,7 +445,7 @@ class SerializeImplGenerator(private val codegenContext: CodegenContext) {
                     }
                     if (codegenContext.target.renderUnknownVariant()) {
                         rustTemplate(
-                            "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => serializer.serialize_str(\"unknown variant!\")",
+                            "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}(_) => serializer.serialize_str(\"unknown variant!\")",
                             "Union" to unionSymbol,
                         )
                     }
@@ -585,6 +585,6 @@ class SerializeImplGenerator(private val codegenContext: CodegenContext) {
     }
 
     companion object {
-        private val PrimitiveShapesModule = RustModule.pubCrate("primitives", parent = SerdeModule)
+        internal val PrimitiveShapesModule = RustModule.pubCrate("primitives", parent = SerdeModule)
     }
 }
@@ -13,6 +13,9 @@ import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 object SupportStructures {
     private val supportModule = SerdeModule
 
+    /** Placeholder that is written in place of sensitive values when they are redacted */
+    const val REDACTED = "<redacted>"
+
     private val serde =
         CargoDependency.Serde.copy(
             scope = DependencyScope.Compile,
@@ -81,6 +84,97 @@ object SupportStructures {
             )
         }
 
+    /**
+     * Wrapper for deserializing types that don't implement `Deserialize` on their own, e.g. `Blob` and collections
+     * of model types. The `Deserialize` implementations for concrete types are generated alongside the model types.
+     */
+    fun deserialized(): RuntimeType =
+        RuntimeType.forInlineFun("Deserialized", supportModule) {
+            rustTemplate(
+                """
+                /// Wrapper that deserializes the contained type in the format written by `SerializeConfigured`
+                pub(crate) struct Deserialized<T>(pub(crate) T);
+
+                impl<'de, T> #{serde}::Deserialize<'de> for Deserialized<#{Option}<T>>
+                where
+                    Deserialized<T>: #{serde}::Deserialize<'de>,
+                {
+                    fn deserialize<D>(deserializer: D) -> #{Result}<Self, D::Error>
+                    where
+                        D: #{serde}::Deserializer<'de>,
+                    {
+                        let value = <#{Option}<Deserialized<T>> as #{serde}::Deserialize>::deserialize(deserializer)?;
+                        #{Ok}(Deserialized(value.map(|value| value.0)))
+                    }
+                }
+
+                impl<'de, T> #{serde}::Deserialize<'de> for Deserialized<#{Box}<T>>
+                where
+                    Deserialized<T>: #{serde}::Deserialize<'de>,
+                {
+                    fn deserialize<D>(deserializer: D) -> #{Result}<Self, D::Error>
+                    where
+                        D: #{serde}::Deserializer<'de>,
+                    {
+                        let value = <Deserialized<T> as #{serde}::Deserialize>::deserialize(deserializer)?;
+                        #{Ok}(Deserialized(#{Box}::new(value.0)))
+                    }
+                }
+
+                impl<'de, T> #{serde}::Deserialize<'de> for Deserialized<#{Vec}<T>>
+                where
+                    Deserialized<T>: #{serde}::Deserialize<'de>,
+                {
+                    fn deserialize<D>(deserializer: D) -> #{Result}<Self, D::Error>
+                    where
+                        D: #{serde}::Deserializer<'de>,
+                    {
+                        let value = <#{Vec}<Deserialized<T>> as #{serde}::Deserialize>::deserialize(deserializer)?;
+                        #{Ok}(Deserialized(value.into_iter().map(|value| value.0).collect()))
+                    }
+                }
+
+                impl<'de, K, V> #{serde}::Deserialize<'de> for Deserialized<::std::collections::HashMap<K, V>>
+                where
+                    K: ::std::cmp::Eq + ::std::hash::Hash,
+                    Deserialized<K>: #{serde}::Deserialize<'de>,
+                    Deserialized<V>: #{serde}::Deserialize<'de>,
+                {
+                    fn deserialize<D>(deserializer: D) -> #{Result}<Self, D::Error>
+                    where
+                        D: #{serde}::Deserializer<'de>,
+                    {
+                        struct Visitor<K, V>(::std::marker::PhantomData<(K, V)>);
+                        impl<'de, K, V> #{serde}::de::Visitor<'de> for Visitor<K, V>
+                        where
+                            K: ::std::cmp::Eq + ::std::hash::Hash,
+                            Deserialized<K>: #{serde}::Deserialize<'de>,
+                            Deserialized<V>: #{serde}::Deserialize<'de>,
+                        {
+                            type Value = ::std::collections::HashMap<K, V>;
+                            fn expecting(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                                formatter.write_str("a map")
+                            }
+                            fn visit_map<A>(self, mut map: A) -> #{Result}<Self::Value, A::Error>
+                            where
+                                A: #{serde}::de::MapAccess<'de>,
+                            {
+                                let mut values = ::std::collections::HashMap::with_capacity(map.size_hint().unwrap_or_default());
+                                while let #{Some}((Deserialized(key), Deserialized(value))) = map.next_entry()? {
+                                    values.insert(key, value);
+                                }
+                                #{Ok}(values)
+                            }
+                        }
+                        deserializer.deserialize_map(Visitor(::std::marker::PhantomData)).map(Deserialized)
+                    }
+                }
+                """,
+                "serde" to serde,
+                *RuntimeType.preludeScope,
+            )
+        }
+
     private fun serializeConfigured(): RuntimeType =
         RuntimeType.forInlineFun("SerializeConfigured", supportModule) {
             rustTemplate(
@@ -142,7 +236,7 @@ object SupportStructures {
                     S: serde::Serializer,
                     {
                         match self.settings.redact_sensitive_fields {
-                            true => serializer.serialize_str("<redacted>"),
+                            true => serializer.serialize_str("$REDACTED"),
                             false => self.value.0.serialize(serializer),
                         }
                     }
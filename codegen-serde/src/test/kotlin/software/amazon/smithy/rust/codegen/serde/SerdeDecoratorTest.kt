@@ -6,6 +6,8 @@
 package software.amazon.smithy.rust.codegen.serde
 
 import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.assertThrows
+import software.amazon.smithy.build.SmithyBuildException
 import software.amazon.smithy.model.node.Node
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
@@ -460,6 +462,191 @@ class SerdeDecoratorTest {
 
     private val expectedStreaming = """{"data":"MTIz"}"""
 
+    private val deserializeModel =
+        """
+        namespace com.example
+        use smithy.rust#serde
+        use aws.protocols#awsJson1_0
+        @awsJson1_0
+        service HelloService {
+            operations: [SayHello, Streaming],
+            version: "1"
+        }
+
+        @serde(deserialize: true)
+        operation SayHello {
+            input: TestInput
+        }
+
+        @serde(deserialize: true)
+        operation Streaming {
+            input: StreamingInput
+        }
+
+        structure StreamingInput {
+            @required
+            data: StreamingBlob
+        }
+
+        @streaming
+        blob StreamingBlob
+
+        structure TestInput {
+           foo: SensitiveString,
+           e: TestEnum,
+           nested: Nested,
+           union: U,
+           document: Document,
+           blob: Blob,
+           recursive: Recursive,
+           float: Float,
+           double: Double,
+           secrets: SensitiveList
+        }
+
+        list SensitiveList {
+            member: SensitiveString
+        }
+
+        structure Recursive {
+            inner: RecursiveList
+        }
+
+        list RecursiveList {
+            member: Recursive
+        }
+
+        @sensitive
+        string SensitiveString
+
+        enum TestEnum {
+            A,
+            B
+        }
+
+        union U {
+            nested: Nested,
+            enum: TestEnum,
+            other: Unit
+        }
+
+        structure Nested {
+            @required
+            int: Integer,
+            timestamps: Timestamps,
+            sparse: SparseList
+        }
+
+        map Timestamps {
+            key: String
+            value: Timestamp
+        }
+
+        @sparse
+        list SparseList {
+            member: TestEnum
+        }
+        """.asSmithyModel(smithyVersion = "2")
+
+    @Test
+    fun generateDeserializersThatWorkClient() {
+        val path =
+            clientIntegrationTest(deserializeModel, params = params) { ctx, crate ->
+                val codegenScope =
+                    arrayOf(
+                        "crate" to RustType.Opaque(ctx.moduleUseName()),
+                        "serde_json" to CargoDependency("serde_json", CratesIo("1")).toDevDependency().toType(),
+                    )
+
+                crate.integrationTest("test_serde_round_trip") {
+                    unitTest("input_round_trip") {
+                        rustTemplate(
+                            """
+                            use #{crate}::types::{Nested, Recursive, TestEnum, U};
+                            use #{crate}::operation::say_hello::SayHelloInput;
+                            use #{crate}::serde::*;
+                            use std::time::UNIX_EPOCH;
+                            use aws_smithy_types::{DateTime, Document, Blob};
+                            let input = SayHelloInput::builder()
+                                .foo("foo-value")
+                                .e(TestEnum::A)
+                                .document(Document::Object([("a".to_string(), Document::Array(vec![Document::Null, Document::Bool(true)]))].into()))
+                                .blob(Blob::new("hello"))
+                                .float(f32::INFINITY)
+                                .double(1.5)
+                                .nested(Nested::builder()
+                                    .int(5)
+                                    .timestamps("a", DateTime::from(UNIX_EPOCH))
+                                    .sparse(None).sparse(Some(TestEnum::B)).sparse(Some("unknown".into()))
+                                    .build().unwrap()
+                                )
+                                .union(U::Other)
+                                .recursive(Recursive::builder().inner(Recursive::builder().build()).build())
+                                .build()
+                                .unwrap();
+                            let mut settings = SerializationSettings::leak_sensitive_fields();
+                            settings.out_of_range_floats_as_strings = true;
+                            let serialized = #{serde_json}::to_string(&input.serialize_ref(&settings)).expect("failed to serialize");
+                            let deserialized: SayHelloInput = #{serde_json}::from_str(&serialized).expect("failed to deserialize");
+                            assert_eq!(input, deserialized);
+                            """,
+                            *codegenScope,
+                        )
+                    }
+
+                    unitTest("union_and_required_members") {
+                        rustTemplate(
+                            """
+                            use #{crate}::types::{Nested, U};
+                            let union: U = #{serde_json}::from_str(r##"{"nested":{"int":1}}"##).expect("valid union");
+                            assert_eq!(U::Nested(Nested::builder().int(1).build().unwrap()), union);
+                            #{serde_json}::from_str::<U>(r##"{"other":{}}"##).expect_err("unit variants have no value");
+                            #{serde_json}::from_str::<Nested>(r##"{}"##).expect_err("int is required");
+                            #{serde_json}::from_str::<Nested>(r##"{"int":1,"extra":true}"##).expect("unknown fields are ignored");
+                            """,
+                            *codegenScope,
+                        )
+                    }
+
+                    unitTest("redacted_values_are_rejected") {
+                        rustTemplate(
+                            """
+                            use #{crate}::operation::say_hello::SayHelloInput;
+                            use #{crate}::serde::*;
+                            let input = SayHelloInput::builder().foo("foo-value").secrets("secret").build().unwrap();
+                            let serialized = #{serde_json}::to_string(&input.serialize_ref(&SerializationSettings::redact_sensitive_fields())).unwrap();
+                            let err = #{serde_json}::from_str::<SayHelloInput>(&serialized).expect_err("redacted values can't be deserialized");
+                            assert!(err.to_string().contains("`foo` was redacted"), "{err}");
+                            let err = #{serde_json}::from_str::<SayHelloInput>(r##"{"secrets":["a","<redacted>"]}"##).expect_err("redacted list element");
+                            assert!(err.to_string().contains("`secrets` was redacted"), "{err}");
+                            #{serde_json}::from_str::<SayHelloInput>(r##"{"foo":"<redacted>-ish","secrets":["a"]}"##).expect("not redacted");
+                            """,
+                            *codegenScope,
+                        )
+                    }
+
+                    unitTest("bytestream_round_trip") {
+                        rustTemplate(
+                            """
+                            use #{crate}::operation::streaming::StreamingInput;
+                            let input: StreamingInput = #{serde_json}::from_str(${expectedStreaming.dq()}).expect("failed to deserialize");
+                            assert_eq!(Some(&b"123"[..]), input.data.bytes());
+                            """,
+                            *codegenScope,
+                        )
+                    }
+                }
+            }
+        "cargo clippy --all-features".runCommand(path)
+    }
+
+    @Test
+    fun `deserialize is not supported for servers`() {
+        assertThrows<SmithyBuildException> {
+            serverIntegrationTest(deserializeModel, params = params) { _, _ -> }
+        }
+    }
+
     @Test
     fun generateSerializersThatWorkClient() {
         val path =
@@ -11,6 +11,7 @@ import software.amazon.smithy.model.shapes.ShapeId
 import software.amazon.smithy.rust.codegen.core.smithy.CODEGEN_SETTINGS
 import software.amazon.smithy.rust.codegen.core.smithy.CoreCodegenConfig
 import software.amazon.smithy.rust.codegen.core.smithy.CoreRustSettings
+import software.amazon.smithy.rust.codegen.core.smithy.NonFiniteFloatHandling
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import java.util.Optional
 
@@ -87,6 +88,7 @@ data class ServerRustSettings(
 data class ServerCodegenConfig(
     override val formatTimeoutSeconds: Int = DEFAULT_FORMAT_TIMEOUT_SECONDS,
     override val debugMode: Boolean = DEFAULT_DEBUG_MODE,
+    override val nonFiniteFloats: NonFiniteFloatHandling = DEFAULT_NON_FINITE_FLOATS,
     val publicConstrainedTypes: Boolean = DEFAULT_PUBLIC_CONSTRAINED_TYPES,
     val ignoreUnsupportedConstraints: Boolean = DEFAULT_IGNORE_UNSUPPORTED_CONSTRAINTS,
     /**
@@ -99,7 +101,7 @@ data class ServerCodegenConfig(
     val addValidationExceptionToConstrainedOperations: Boolean = DEFAULT_ADD_VALIDATION_EXCEPTION_TO_CONSTRAINED_OPERATIONS,
     val alwaysSendEventStreamInitialResponse: Boolean = DEFAULT_SEND_EVENT_STREAM_INITIAL_RESPONSE,
 ) : CoreCodegenConfig(
-        formatTimeoutSeconds, debugMode,
+        formatTimeoutSeconds, debugMode, nonFiniteFloats = nonFiniteFloats,
     ) {
     companion object {
         private const val DEFAULT_PUBLIC_CONSTRAINED_TYPES = true
@@ -115,6 +117,7 @@ data class ServerCodegenConfig(
             ServerCodegenConfig(
                 formatTimeoutSeconds = coreCodegenConfig.formatTimeoutSeconds,
                 debugMode = coreCodegenConfig.debugMode,
+                nonFiniteFloats = coreCodegenConfig.nonFiniteFloats,
                 publicConstrainedTypes =
                     node.get()
                         .getBooleanMemberOrDefault("publicConstrainedTypes", DEFAULT_PUBLIC_CONSTRAINED_TYPES),
@@ -144,6 +147,7 @@ data class ServerCodegenConfig(
             ServerCodegenConfig(
                 formatTimeoutSeconds = coreCodegenConfig.formatTimeoutSeconds,
                 debugMode = coreCodegenConfig.debugMode,
+                nonFiniteFloats = coreCodegenConfig.nonFiniteFloats,
             )
         }
     }
@@ -66,6 +66,9 @@ class ServerRequiredCustomizations : ServerCodegenDecorator {
             ),
         )
 
+        // Reveal `@sensitive` data in `Debug` output for local debugging
+        rustCrate.mergeFeature(Feature("unredacted-debug", false, listOf("aws-smithy-types/unredacted-debug")))
+
         rustCrate.withModule(ServerRustModule.Types) {
             pubUseSmithyPrimitives(codegenContext, codegenContext.model, rustCrate)(this)
             rustTemplate(
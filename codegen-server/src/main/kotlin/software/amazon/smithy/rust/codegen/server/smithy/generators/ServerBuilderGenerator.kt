@@ -44,7 +44,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrai
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.letIf
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.canReachConstrainedShape
@@ -460,11 +460,14 @@ class ServerBuilderGenerator(
                 rust("""let mut formatter = f.debug_struct("Builder");""")
                 members.forEach { member ->
                     val memberName = symbolProvider.toMemberName(member)
-                    val fieldValue = member.redactIfNecessary(model, "self.$memberName")
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
-                    )
+                    if (member.shouldRedact(model)) {
+                        rustTemplate(
+                            "formatter.field(${memberName.dq()}, &#{Sensitive}(&self.$memberName));",
+                            "Sensitive" to RuntimeType.sensitive(runtimeConfig),
+                        )
+                    } else {
+                        rust("formatter.field(${memberName.dq()}, &self.$memberName);")
+                    }
                 }
                 rust("formatter.finish()")
             }
@@ -65,6 +65,7 @@ import software.amazon.smithy.rust.codegen.core.util.hasStreamingMember
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.inputShape
 import software.amazon.smithy.rust.codegen.core.util.isEventStream
+import software.amazon.smithy.rust.codegen.core.util.isInputEventStream
 import software.amazon.smithy.rust.codegen.core.util.isStreaming
 import software.amazon.smithy.rust.codegen.core.util.outputShape
 import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
@@ -750,6 +751,13 @@ class ServerHttpBoundProtocolTraitImplGenerator(
             "let mut input = #T::default();",
             inputShape.serverBuilderSymbol(codegenContext),
         )
+        if (operationShape.isInputEventStream(model)) {
+            // Limits on the incoming event stream frames can be set by inserting them into the request extensions
+            rustTemplate(
+                "let event_stream_decoder_limits = request.extensions().get::<#{DecoderLimits}>().copied();",
+                "DecoderLimits" to RuntimeType.smithyHttp(runtimeConfig).resolve("event_stream::DecoderLimits"),
+            )
+        }
         Attribute.AllowUnusedVariables.render(this)
         rustTemplate(
             """
@@ -919,6 +927,9 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                                 """
                                 {
                                     let mut receiver = #{Deserializer}(&mut body.into().into_inner())?;
+                                    if let Some(limits) = event_stream_decoder_limits {
+                                        receiver.set_decoder_limits(limits);
+                                    }
                                     if let Some(_initial_event) = receiver
                                         .try_recv_initial(#{InitialMessageType}::Request)
                                         .await
@@ -0,0 +1,110 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+package software.amazon.smithy.rust.codegen.server.smithy.protocols.serialize
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+
+class CborServerInputValidationTest {
+    val model =
+        """
+        namespace test
+
+        use smithy.protocols#rpcv2Cbor
+        use smithy.framework#ValidationException
+
+        @rpcv2Cbor
+        service SampleService {
+            operations: [SampleOperation],
+        }
+        operation SampleOperation {
+            input:= { x: String }
+            output:= { y: String }
+        }
+        """.asSmithyModel(smithyVersion = "2")
+
+    private val codegenScope =
+        arrayOf(
+            "Hyper" to RuntimeType.Hyper,
+            "Http" to RuntimeType.Http,
+            "Tower" to RuntimeType.Tower,
+            *RuntimeType.preludeScope,
+        )
+
+    @Test
+    fun `untrusted input is validated before it's decoded`() {
+        serverIntegrationTest(model) { _, rustCrate ->
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    async fn handler(_input: crate::input::SampleOperationInput) -> crate::output::SampleOperationOutput {
+                        crate::output::SampleOperationOutput { y: None }
+                    }
+
+                    async fn status(body: #{Vec}<u8>) -> #{Http}::StatusCode {
+                        let config = crate::SampleServiceConfig::builder().build();
+                        let service = crate::SampleService::builder(config)
+                            .sample_operation(handler)
+                            .build()
+                            .expect("could not build service");
+                        let request = #{Http}::Request::builder()
+                            .uri("/service/SampleService/operation/SampleOperation")
+                            .method("POST")
+                            .header("content-type", "application/cbor")
+                            .header("Smithy-Protocol", "rpc-v2-cbor")
+                            .body(#{Hyper}::Body::from(body))
+                            .expect("failed to build request");
+                        #{Tower}::ServiceExt::oneshot(service, request)
+                            .await
+                            .expect("failed to call service")
+                            .status()
+                    }
+                    """,
+                    *codegenScope,
+                )
+
+                tokioTest("valid_input_is_accepted") {
+                    rustTemplate(
+                        """
+                        // {"x": "a"}
+                        let body = vec![0xa1, 0x61, b'x', 0x61, b'a'];
+                        assert!(status(body).await.is_success());
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                tokioTest("duplicate_keys_are_rejected") {
+                    rustTemplate(
+                        """
+                        // {"x": "a", "x": "b"}
+                        let body = vec![0xa2, 0x61, b'x', 0x61, b'a', 0x61, b'x', 0x61, b'b'];
+                        assert_eq!(#{Http}::StatusCode::BAD_REQUEST, status(body).await);
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                tokioTest("deeply_nested_input_is_rejected") {
+                    rustTemplate(
+                        """
+                        // {"x": [[[...0...]]]}, nested deeper than the default limit
+                        let mut body = vec![0xa1, 0x61, b'x'];
+                        body.extend(std::iter::repeat(0x81).take(1000));
+                        body.push(0x00);
+                        assert_eq!(#{Http}::StatusCode::BAD_REQUEST, status(body).await);
+                        """,
+                        *codegenScope,
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+/// This example demonstrates how to register a custom auth scheme, consisting of an identity
+/// resolver and a signer, and have the client select it for every operation. The signer adds an
+/// HMAC-SHA256 signature of the request to the `Authorization` header.
+///
+/// The example assumes that the Pokémon service is running on the localhost on TCP port 13734.
+/// Refer to the [README.md](https://github.com/smithy-lang/smithy-rs/tree/main/examples/pokemon-service-client-usage/README.md)
+/// file for instructions on how to launch the service locally.
+///
+/// The example can be run using `cargo run --example custom-auth-scheme`.
+///
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aws_smithy_runtime_api::client::auth::{
+    AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, Sign,
+};
+use aws_smithy_runtime_api::client::identity::{
+    Identity, IdentityFuture, ResolveIdentity, SharedIdentityResolver,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::runtime_components::{GetIdentityResolver, RuntimeComponents};
+use aws_smithy_runtime_api::shared::IntoShared;
+use hmac::{Hmac, Mac};
+use pokemon_service_client::config::auth::DefaultAuthSchemeResolver;
+use pokemon_service_client::config::ConfigBag;
+use pokemon_service_client::error::BoxError;
+use pokemon_service_client::Client as PokemonClient;
+use pokemon_service_client_usage::{setup_tracing_subscriber, POKEMON_SERVICE_URL};
+use sha2::Sha256;
+
+/// Auth scheme ID for the custom HMAC auth scheme.
+const HMAC_SCHEME_ID: AuthSchemeId = AuthSchemeId::new("example#hmac");
+
+/// The identity used by the HMAC auth scheme: a key ID and its shared secret.
+#[derive(Debug)]
+struct HmacKey {
+    key_id: String,
+    secret: Vec<u8>,
+}
+
+/// Resolves the `HmacKey` for each request.
+///
+/// A real implementation would load the key from a secure location, and may set an expiration
+/// on the returned `Identity` so that it is refreshed by the identity cache.
+#[derive(Debug)]
+struct HmacKeyResolver {
+    key_id: String,
+    secret: String,
+}
+
+impl ResolveIdentity for HmacKeyResolver {
+    fn resolve_identity<'a>(
+        &'a self,
+        _runtime_components: &'a RuntimeComponents,
+        _config_bag: &'a ConfigBag,
+    ) -> IdentityFuture<'a> {
+        IdentityFuture::ready(Ok(Identity::new(
+            HmacKey {
+                key_id: self.key_id.clone(),
+                secret: self.secret.clone().into_bytes(),
+            },
+            None,
+        )))
+    }
+}
+
+/// Signs a request by computing an HMAC-SHA256 over its method, path, and timestamp.
+#[derive(Debug)]
+struct HmacSigner;
+
+impl Sign for HmacSigner {
+    fn sign_http_request(
+        &self,
+        request: &mut HttpRequest,
+        identity: &Identity,
+        _auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+        _runtime_components: &RuntimeComponents,
+        _config_bag: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        let key = identity
+            .data::<HmacKey>()
+            .ok_or("the HMAC auth scheme requires an `HmacKey` identity")?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let path = http::Uri::try_from(request.uri())?.path().to_owned();
+        let string_to_sign = format!("{}\n{}\n{}", request.method(), path, timestamp);
+
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&key.secret).expect("HMAC accepts keys of any size");
+        mac.update(string_to_sign.as_bytes());
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        let headers = request.headers_mut();
+        headers.insert("x-example-timestamp", timestamp.to_string());
+        headers.insert(
+            "authorization",
+            format!(
+                "EXAMPLE-HMAC-SHA256 KeyId={}, Signature={}",
+                key.key_id, signature
+            ),
+        );
+        Ok(())
+    }
+}
+
+/// The custom auth scheme ties together the scheme ID, identity resolver, and signer.
+#[derive(Debug)]
+struct HmacAuthScheme {
+    identity_resolver: SharedIdentityResolver,
+    signer: HmacSigner,
+}
+
+impl HmacAuthScheme {
+    fn new(key_id: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            identity_resolver: HmacKeyResolver {
+                key_id: key_id.into(),
+                secret: secret.into(),
+            }
+            .into_shared(),
+            signer: HmacSigner,
+        }
+    }
+}
+
+impl AuthScheme for HmacAuthScheme {
+    fn scheme_id(&self) -> AuthSchemeId {
+        HMAC_SCHEME_ID
+    }
+
+    fn identity_resolver(
+        &self,
+        _identity_resolvers: &dyn GetIdentityResolver,
+    ) -> Option<SharedIdentityResolver> {
+        Some(self.identity_resolver.clone())
+    }
+
+    fn signer(&self) -> &dyn Sign {
+        &self.signer
+    }
+}
+
+/// Creates a new `smithy-rs` client that signs every request with the HMAC auth scheme.
+///
+/// # Examples
+///
+/// Basic usage:
+/// ```
+/// let client = create_client();
+/// ```
+fn create_client() -> PokemonClient {
+    let config = pokemon_service_client::Config::builder()
+        .endpoint_url(POKEMON_SERVICE_URL)
+        // Register the auth scheme, which makes it available to the orchestrator.
+        .push_auth_scheme(HmacAuthScheme::new("example-key-id", "example-secret"))
+        // The default auth scheme resolver only returns the auth schemes in the service model,
+        // so it needs to be told to try the custom auth scheme first.
+        .auth_scheme_resolver(
+            DefaultAuthSchemeResolver::default().with_preferred_auth_scheme(HMAC_SCHEME_ID),
+        )
+        .build();
+
+    // Instantiate a client by applying the configuration.
+    PokemonClient::from_conf(config)
+}
+
+#[tokio::main]
+async fn main() {
+    setup_tracing_subscriber();
+
+    // Create a configured `smithy-rs` client.
+    let client = create_client();
+
+    // Call an operation `get_server_statistics` on the Pokémon service.
+    let response = client
+        .get_server_statistics()
+        .send()
+        .await
+        .expect("operation failed");
+
+    tracing::info!(?response, "Response received");
+}
@@ -11,8 +11,12 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 pub mod collect;
+pub mod error_policy;
 pub mod fn_stream;
+mod prefetch;
+pub mod rate_limit;
 use fn_stream::FnStream;
+use prefetch::Prefetch;
 
 /// Stream specifically made to support paginators.
 ///
@@ -49,27 +53,63 @@ use fn_stream::FnStream;
 /// [`PaginationStream`] is implemented in terms of [`FnStream`], but the latter is meant to be
 /// used internally and not by external users.
 #[derive(Debug)]
-pub struct PaginationStream<Item>(FnStream<Item>);
+pub struct PaginationStream<Item>(Inner<Item>);
+
+#[derive(Debug)]
+enum Inner<Item> {
+    Fn(FnStream<Item>),
+    Prefetch(Box<Prefetch<Item>>),
+}
 
 impl<Item> PaginationStream<Item> {
     /// Creates a `PaginationStream` from the given [`FnStream`].
     pub fn new(stream: FnStream<Item>) -> Self {
-        Self(stream)
+        Self(Inner::Fn(stream))
+    }
+
+    /// Returns a stream that reads up to `capacity` items ahead of the consumer into a buffer.
+    ///
+    /// For paginators, this means that the requests for the next pages are dispatched while the
+    /// current page is still being processed, which hides the latency of each request on long
+    /// listings. A `capacity` of zero returns this stream unchanged.
+    ///
+    /// Nothing is read until the returned stream is first polled. From then on, items are read
+    /// ahead in a task spawned on the current Tokio runtime, so reading ahead continues while the
+    /// consumer is busy. This requires the `rt-tokio` feature. Without it, or outside of a Tokio
+    /// runtime, items are only read ahead while the returned stream is being polled.
+    pub fn prefetch(self, capacity: usize) -> Self
+    where
+        Item: Send + 'static,
+    {
+        if capacity == 0 {
+            self
+        } else {
+            Self(Inner::Prefetch(Box::new(Prefetch::new(self, capacity))))
+        }
     }
 
     /// Consumes and returns the next `Item` from this stream.
     pub async fn next(&mut self) -> Option<Item> {
-        self.0.next().await
+        std::future::poll_fn(|cx| self.poll_next(cx)).await
     }
 
     /// Poll an item from the stream
     pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Item>> {
-        Pin::new(&mut self.0).poll_next(cx)
+        match &mut self.0 {
+            Inner::Fn(stream) => Pin::new(stream).poll_next(cx),
+            Inner::Prefetch(stream) => stream.poll_next(cx),
+        }
     }
 
     /// Consumes this stream and gathers elements into a collection.
-    pub async fn collect<T: Collectable<Item>>(self) -> T {
-        self.0.collect().await
+    pub async fn collect<T: Collectable<Item>>(mut self) -> T {
+        let mut collection = T::initialize();
+        while let Some(item) = self.next().await {
+            if !T::extend(&mut collection, item) {
+                break;
+            }
+        }
+        T::finalize(collection)
     }
 }
 
@@ -134,6 +174,7 @@ impl<Page, Err> TryFlatMap<Page, Err> {
 mod test {
     use crate::future::pagination_stream::{FnStream, PaginationStream, TryFlatMap};
     use std::sync::{Arc, Mutex};
+    use std::task::Poll;
     use std::time::Duration;
 
     /// basic test of FnStream functionality
@@ -230,6 +271,113 @@ mod test {
         assert_eq!(4, *progress.lock().unwrap());
     }
 
+    /// Tests that a prefetching stream keeps fetching pages in the background while the reader is
+    /// busy with the current page
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn prefetch_reads_ahead() {
+        let progress = Arc::new(Mutex::new(0));
+        let mut stream = PaginationStream::new(FnStream::new(|tx| {
+            let progress = progress.clone();
+            Box::pin(async move {
+                for i in 1..=5 {
+                    // Each page takes a while to arrive
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    *progress.lock().unwrap() = i;
+                    if tx.send(i).await.is_err() {
+                        return;
+                    }
+                }
+            })
+        }))
+        .prefetch(2);
+        // Nothing is fetched until the stream is used
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(*progress.lock().unwrap(), 0);
+
+        assert_eq!(Some(1), stream.next().await);
+        // Process the first page without polling the stream
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        // The next two pages were fetched in the meantime, but no more than that
+        assert_eq!(*progress.lock().unwrap(), 3);
+
+        assert_eq!(vec![2, 3, 4, 5], stream.collect::<Vec<_>>().await);
+        assert_eq!(*progress.lock().unwrap(), 5);
+    }
+
+    /// Tests that a prefetching stream outside of a runtime reads ahead whenever it is polled
+    #[test]
+    fn prefetch_reads_ahead_when_polled() {
+        let progress = Arc::new(Mutex::new(0));
+        let stream = PaginationStream::new(FnStream::new(|tx| {
+            let progress = progress.clone();
+            Box::pin(async move {
+                for i in 1..=5 {
+                    *progress.lock().unwrap() = i;
+                    if tx.send(i).await.is_err() {
+                        return;
+                    }
+                }
+            })
+        }))
+        .prefetch(2);
+        let mut stream = tokio_test::task::spawn(stream);
+        // The rendezvous channel needs a few polls to hand over an item
+        let mut next = || {
+            (0..10)
+                .find_map(|_| match stream.enter(|cx, s| s.get_mut().poll_next(cx)) {
+                    Poll::Ready(item) => Some(item),
+                    Poll::Pending => None,
+                })
+                .expect("stream stays pending")
+        };
+        assert_eq!(Some(1), next());
+        // The generator runs ahead of the reader while the first item is being processed
+        assert_eq!(*progress.lock().unwrap(), 3);
+
+        for i in 2..=5 {
+            assert_eq!(Some(i), next());
+        }
+        assert_eq!(None, next());
+        assert_eq!(*progress.lock().unwrap(), 5);
+    }
+
+    /// Tests that dropping a prefetching stream stops fetching pages
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn prefetch_stops_when_dropped() {
+        let progress = Arc::new(Mutex::new(0));
+        let mut stream = PaginationStream::new(FnStream::new(|tx| {
+            let progress = progress.clone();
+            Box::pin(async move {
+                for i in 1.. {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    *progress.lock().unwrap() = i;
+                    if tx.send(i).await.is_err() {
+                        return;
+                    }
+                }
+            })
+        }))
+        .prefetch(1);
+        assert_eq!(Some(1), stream.next().await);
+        drop(stream);
+        let fetched = *progress.lock().unwrap();
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(fetched, *progress.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn prefetch_preserves_errors() {
+        let stream = PaginationStream::new(FnStream::new(|tx| {
+            Box::pin(async move {
+                tx.send(Ok(1)).await.unwrap();
+                tx.send(Err("err")).await.unwrap();
+            })
+        }));
+        assert_eq!(Err("err"), stream.prefetch(4).try_collect().await);
+    }
+
     #[tokio::test]
     async fn generator_with_errors() {
         let mut stream = FnStream::new(|tx| {
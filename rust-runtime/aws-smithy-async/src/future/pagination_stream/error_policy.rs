@@ -0,0 +1,170 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Module to define how flattened paginators handle pages that fail to be fetched.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Policy for handling a page that fails to be fetched while a paginator's items are streamed.
+///
+/// Generated item paginators (created with `paginator.items()`) accept an `ItemErrorPolicy` with
+/// their `error_policy()` method, so that long-running enumeration jobs can choose robustness over
+/// strictness. The policy applies after the client's retry strategy has already given up on the
+/// request for a page.
+///
+/// ```no_run
+/// use aws_smithy_async::future::pagination_stream::error_policy::ItemErrorPolicy;
+/// use std::time::Duration;
+///
+/// # struct Error;
+/// // Request a page that failed up to 3 more times, waiting 5 seconds before each attempt.
+/// let policy = ItemErrorPolicy::<Error>::retry_page(3, Duration::from_secs(5));
+/// ```
+#[non_exhaustive]
+pub enum ItemErrorPolicy<E> {
+    /// Return the error from the stream, and end it.
+    ///
+    /// This is the default.
+    FailFast,
+
+    /// Request the page that failed again, up to `max_attempts` more times, waiting `delay`
+    /// before each attempt. If the page still fails, the error is returned from the stream.
+    ///
+    /// Only errors that might go away when the page is requested again are retried, such as
+    /// timeouts, server errors, and errors that are modeled as retryable. Other errors are
+    /// returned from the stream right away.
+    RetryPage {
+        /// The number of times a page that failed is requested again.
+        max_attempts: u32,
+        /// How long to wait before requesting a page that failed again.
+        delay: Duration,
+    },
+
+    /// Pass the error to a callback instead of returning it from the stream, and end the stream.
+    ///
+    /// Items that were already streamed are kept, so the stream ends without an error. Since the
+    /// token for the next page is part of the response that failed, the pages after a failed page
+    /// can't be fetched.
+    StopWithCallback(Arc<dyn Fn(&E) + Send + Sync>),
+}
+
+impl<E> ItemErrorPolicy<E> {
+    /// Creates a policy that returns the error from the stream, and ends it.
+    pub fn fail_fast() -> Self {
+        Self::FailFast
+    }
+
+    /// Creates a policy that requests a page that failed with a retryable error again, up to
+    /// `max_attempts` more times, waiting `delay` before each attempt.
+    pub fn retry_page(max_attempts: u32, delay: Duration) -> Self {
+        Self::RetryPage {
+            max_attempts,
+            delay,
+        }
+    }
+
+    /// Creates a policy that passes the error to `on_error` instead of returning it from the
+    /// stream, and ends the stream.
+    pub fn stop_with_callback(on_error: impl Fn(&E) + Send + Sync + 'static) -> Self {
+        Self::StopWithCallback(Arc::new(on_error))
+    }
+
+    /// Returns how long to wait before requesting a page that failed again, if this policy
+    /// retries pages.
+    pub fn retry_delay(&self) -> Option<Duration> {
+        match self {
+            Self::RetryPage { delay, .. } => Some(*delay),
+            _ => None,
+        }
+    }
+}
+
+impl<E> Default for ItemErrorPolicy<E> {
+    fn default() -> Self {
+        Self::FailFast
+    }
+}
+
+impl<E> Clone for ItemErrorPolicy<E> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::FailFast => Self::FailFast,
+            Self::RetryPage {
+                max_attempts,
+                delay,
+            } => Self::RetryPage {
+                max_attempts: *max_attempts,
+                delay: *delay,
+            },
+            Self::StopWithCallback(on_error) => Self::StopWithCallback(on_error.clone()),
+        }
+    }
+}
+
+impl<E> fmt::Debug for ItemErrorPolicy<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FailFast => f.write_str("FailFast"),
+            Self::RetryPage {
+                max_attempts,
+                delay,
+            } => f
+                .debug_struct("RetryPage")
+                .field("max_attempts", max_attempts)
+                .field("delay", delay)
+                .finish(),
+            Self::StopWithCallback(_) => f
+                .debug_tuple("StopWithCallback")
+                .field(&"** callback **")
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ItemErrorPolicy;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn retry_delay() {
+        assert_eq!(None, ItemErrorPolicy::<()>::default().retry_delay());
+        assert_eq!(
+            None,
+            ItemErrorPolicy::<()>::stop_with_callback(|_| {}).retry_delay()
+        );
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            ItemErrorPolicy::<()>::retry_page(2, Duration::from_secs(1)).retry_delay()
+        );
+    }
+
+    #[test]
+    fn clones_share_callback() {
+        let stopped = Arc::new(AtomicUsize::new(0));
+        let policy = ItemErrorPolicy::stop_with_callback({
+            let stopped = stopped.clone();
+            move |_: &&str| {
+                stopped.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        let cloned = policy.clone();
+        for policy in [policy, cloned] {
+            match policy {
+                ItemErrorPolicy::StopWithCallback(on_error) => on_error(&"err"),
+                other => panic!("unexpected policy: {other:?}"),
+            }
+        }
+        assert_eq!(2, stopped.load(Ordering::SeqCst));
+        assert_eq!(
+            "StopWithCallback(\"** callback **\")",
+            format!("{:?}", ItemErrorPolicy::<()>::stop_with_callback(|_| {}))
+        );
+    }
+}
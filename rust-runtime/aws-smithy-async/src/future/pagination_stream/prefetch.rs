@@ -0,0 +1,164 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Module to define a wrapper that reads ahead from a pagination stream into a bounded buffer.
+
+use crate::future::pagination_stream::PaginationStream;
+use std::collections::VecDeque;
+use std::fmt;
+use std::task::{Context, Poll};
+
+/// Reads up to `capacity` items ahead of the consumer from the wrapped stream.
+///
+/// The first time the stream is polled, the wrapped stream is moved into a task on the current
+/// Tokio runtime (when the `rt-tokio` feature is enabled and a runtime is available). That task
+/// keeps reading from the wrapped stream into a channel of `capacity` items, so the requests for
+/// the next pages make progress while the consumer is busy processing the current item. The task
+/// is aborted when the stream is dropped.
+///
+/// Without a runtime to spawn onto, the wrapped stream is instead polled until the buffer is full
+/// every time this stream is polled, so read-ahead only makes progress while the consumer polls.
+pub(crate) struct Prefetch<Item> {
+    state: State<Item>,
+    capacity: usize,
+    // Captures the `Send + 'static` bounds needed to spawn the task, which `poll_next` doesn't have
+    start: fn(PaginationStream<Item>, usize) -> State<Item>,
+}
+
+enum State<Item> {
+    /// The stream hasn't been polled yet
+    Idle(PaginationStream<Item>),
+    /// The wrapped stream is driven by a spawned task
+    #[cfg(feature = "rt-tokio")]
+    Spawned {
+        rx: tokio::sync::mpsc::Receiver<Item>,
+        task: tokio::task::JoinHandle<()>,
+    },
+    /// The wrapped stream is driven by polling this stream
+    Polled(Polled<Item>),
+    /// Transitional state while the wrapped stream is being moved
+    Taken,
+}
+
+impl<Item: fmt::Debug> fmt::Debug for Prefetch<Item> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("Prefetch");
+        match &self.state {
+            State::Idle(inner) => f.field("inner", inner),
+            #[cfg(feature = "rt-tokio")]
+            State::Spawned { rx, .. } => f.field("buffered", &rx.len()),
+            State::Polled(polled) => f
+                .field("inner", &polled.inner)
+                .field("buffered", &polled.buffer.len())
+                .field("inner_done", &polled.inner_done),
+            State::Taken => f.field("inner", &"taken"),
+        };
+        f.field("capacity", &self.capacity).finish()
+    }
+}
+
+impl<Item> Prefetch<Item> {
+    pub(crate) fn new(inner: PaginationStream<Item>, capacity: usize) -> Self
+    where
+        Item: Send + 'static,
+    {
+        Self {
+            state: State::Idle(inner),
+            capacity,
+            start: start::<Item>,
+        }
+    }
+
+    pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Item>> {
+        if let State::Idle(_) = self.state {
+            match std::mem::replace(&mut self.state, State::Taken) {
+                State::Idle(inner) => self.state = (self.start)(inner, self.capacity),
+                _ => unreachable!("the state was just checked"),
+            }
+        }
+        match &mut self.state {
+            #[cfg(feature = "rt-tokio")]
+            State::Spawned { rx, .. } => rx.poll_recv(cx),
+            State::Polled(polled) => polled.poll_next(cx),
+            State::Idle(_) | State::Taken => unreachable!("the stream is started above"),
+        }
+    }
+}
+
+/// Starts reading ahead from `inner`, in a spawned task if a runtime is available.
+fn start<Item: Send + 'static>(inner: PaginationStream<Item>, capacity: usize) -> State<Item> {
+    #[cfg(feature = "rt-tokio")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
+        let task = handle.spawn(read_ahead(inner, tx));
+        return State::Spawned { rx, task };
+    }
+    State::Polled(Polled::new(inner, capacity))
+}
+
+impl<Item> Drop for Prefetch<Item> {
+    fn drop(&mut self) {
+        #[cfg(feature = "rt-tokio")]
+        if let State::Spawned { task, .. } = &self.state {
+            // Stop fetching pages that will never be consumed
+            task.abort();
+        }
+    }
+}
+
+/// Reads items from `inner` into `tx` until `inner` is exhausted or the receiver is dropped.
+#[cfg(feature = "rt-tokio")]
+async fn read_ahead<Item>(mut inner: PaginationStream<Item>, tx: tokio::sync::mpsc::Sender<Item>) {
+    // Reserve a slot before reading the next item so that at most `capacity` items are read ahead
+    while let Ok(permit) = tx.reserve().await {
+        match inner.next().await {
+            Some(item) => permit.send(item),
+            None => return,
+        }
+    }
+}
+
+/// Reads ahead from the wrapped stream whenever it is polled.
+struct Polled<Item> {
+    inner: PaginationStream<Item>,
+    buffer: VecDeque<Item>,
+    capacity: usize,
+    inner_done: bool,
+}
+
+impl<Item> Polled<Item> {
+    fn new(inner: PaginationStream<Item>, capacity: usize) -> Self {
+        Self {
+            inner,
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            inner_done: false,
+        }
+    }
+
+    /// Polls the wrapped stream until the buffer is full, it is exhausted, or it is pending.
+    fn fill(&mut self, cx: &mut Context<'_>) {
+        while !self.inner_done && self.buffer.len() < self.capacity {
+            match self.inner.poll_next(cx) {
+                Poll::Ready(Some(item)) => self.buffer.push_back(item),
+                Poll::Ready(None) => self.inner_done = true,
+                Poll::Pending => break,
+            }
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Item>> {
+        self.fill(cx);
+        match self.buffer.pop_front() {
+            Some(item) => {
+                // Start fetching into the slot that was just freed up
+                self.fill(cx);
+                Poll::Ready(Some(item))
+            }
+            None if self.inner_done => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,188 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Module to define a token bucket that limits how fast pages are fetched by paginators.
+
+use crate::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use crate::time::SharedTimeSource;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Limits the rate at which paginators fetch pages.
+///
+/// `PageRateLimiter` is a token bucket that is refilled at `pages_per_second`, and holds up to
+/// `burst` tokens. Each page request takes a token, and waits for the bucket to refill if it is
+/// empty, so exhaustive listings don't run into service throttling.
+///
+/// Clones of a `PageRateLimiter` share the same bucket, so a single limiter can be given to
+/// several paginators that run concurrently to cap their combined page-fetch rate.
+///
+/// ```no_run
+/// use aws_smithy_async::future::pagination_stream::rate_limit::PageRateLimiter;
+///
+/// // Allow up to 5 pages per second across all paginators that use this limiter,
+/// // with bursts of up to 10 pages after a pause.
+/// let limiter = PageRateLimiter::new(5.0).with_burst(10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PageRateLimiter {
+    pages_per_second: f64,
+    burst: f64,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    tokens: f64,
+    last_refill: Option<SystemTime>,
+}
+
+impl PageRateLimiter {
+    /// Creates a limiter that allows `pages_per_second` pages to be fetched per second, with
+    /// bursts of a single page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pages_per_second` isn't a positive, finite number.
+    pub fn new(pages_per_second: f64) -> Self {
+        assert!(
+            pages_per_second.is_finite() && pages_per_second > 0.0,
+            "pages_per_second must be a positive, finite number"
+        );
+        Self {
+            pages_per_second,
+            burst: 1.0,
+            state: Default::default(),
+        }
+    }
+
+    /// Sets how many pages can be fetched back to back once the limiter has been idle.
+    ///
+    /// Defaults to 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "burst must be at least 1");
+        self.burst = burst as f64;
+        self
+    }
+
+    /// Returns the number of pages that are allowed to be fetched per second.
+    pub fn pages_per_second(&self) -> f64 {
+        self.pages_per_second
+    }
+
+    /// Returns the number of pages that can be fetched back to back.
+    pub fn burst(&self) -> u32 {
+        self.burst as u32
+    }
+
+    /// Waits until another page can be fetched, and takes a token for it.
+    ///
+    /// Tokens are handed out in the order they are asked for: when the bucket is empty, the token
+    /// is reserved right away, and this waits until the bucket has refilled far enough to cover it.
+    pub async fn acquire(&self, time_source: &SharedTimeSource, sleep_impl: &SharedAsyncSleep) {
+        let wait = self.reserve(time_source.now());
+        if !wait.is_zero() {
+            sleep_impl.sleep(wait).await;
+        }
+    }
+
+    /// Takes a token at `now`, and returns how long to wait until it is available.
+    fn reserve(&self, now: SystemTime) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        state.tokens = match state.last_refill {
+            Some(last_refill) => {
+                let elapsed = now.duration_since(last_refill).unwrap_or_default();
+                (state.tokens + elapsed.as_secs_f64() * self.pages_per_second).min(self.burst)
+            }
+            None => self.burst,
+        };
+        state.last_refill = Some(now);
+        state.tokens -= 1.0;
+        if state.tokens < 0.0 {
+            Duration::from_secs_f64(-state.tokens / self.pages_per_second)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod test {
+    use super::PageRateLimiter;
+    use crate::rt::sleep::SharedAsyncSleep;
+    use crate::test_util::instant_time_and_sleep;
+    use crate::time::SharedTimeSource;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[tokio::test]
+    async fn waits_for_tokens() {
+        let (time_source, sleep) = instant_time_and_sleep(UNIX_EPOCH);
+        let (shared_time, shared_sleep) = (
+            SharedTimeSource::new(time_source.clone()),
+            SharedAsyncSleep::new(sleep.clone()),
+        );
+        let limiter = PageRateLimiter::new(2.0);
+        for _ in 0..4 {
+            limiter.acquire(&shared_time, &shared_sleep).await;
+        }
+        assert_eq!(vec![Duration::from_millis(500); 3], sleep.logs());
+
+        // after a long pause, only `burst` pages are fetched without waiting
+        time_source.advance(Duration::from_secs(60));
+        limiter.acquire(&shared_time, &shared_sleep).await;
+        limiter.acquire(&shared_time, &shared_sleep).await;
+        // the manual time source logs the pause alongside the sleeps
+        let mut expected = vec![Duration::from_millis(500); 3];
+        expected.extend([Duration::from_secs(60), Duration::from_millis(500)]);
+        assert_eq!(expected, sleep.logs());
+    }
+
+    #[tokio::test]
+    async fn allows_bursts() {
+        let (time_source, sleep) = instant_time_and_sleep(UNIX_EPOCH);
+        let (shared_time, shared_sleep) = (
+            SharedTimeSource::new(time_source),
+            SharedAsyncSleep::new(sleep.clone()),
+        );
+        let limiter = PageRateLimiter::new(10.0).with_burst(3);
+        for _ in 0..4 {
+            limiter.acquire(&shared_time, &shared_sleep).await;
+        }
+        assert_eq!(vec![Duration::from_millis(100)], sleep.logs());
+    }
+
+    #[tokio::test]
+    async fn clones_share_tokens() {
+        let (time_source, sleep) = instant_time_and_sleep(UNIX_EPOCH);
+        let (shared_time, shared_sleep) = (
+            SharedTimeSource::new(time_source),
+            SharedAsyncSleep::new(sleep.clone()),
+        );
+        let limiter = PageRateLimiter::new(1.0);
+        let other = limiter.clone();
+        limiter.acquire(&shared_time, &shared_sleep).await;
+        other.acquire(&shared_time, &shared_sleep).await;
+        limiter.acquire(&shared_time, &shared_sleep).await;
+        assert_eq!(vec![Duration::from_secs(1); 2], sleep.logs());
+    }
+
+    #[test]
+    fn concurrent_reservations_queue_up() {
+        let limiter = PageRateLimiter::new(4.0);
+        assert_eq!(Duration::ZERO, limiter.reserve(UNIX_EPOCH));
+        assert_eq!(Duration::from_millis(250), limiter.reserve(UNIX_EPOCH));
+        assert_eq!(Duration::from_millis(500), limiter.reserve(UNIX_EPOCH));
+    }
+
+    #[test]
+    #[should_panic(expected = "pages_per_second must be a positive, finite number")]
+    fn rejects_invalid_rate() {
+        PageRateLimiter::new(0.0);
+    }
+}
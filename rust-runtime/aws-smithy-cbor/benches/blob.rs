@@ -4,6 +4,8 @@
  */
 
 use aws_smithy_cbor::decode::Decoder;
+use aws_smithy_cbor::Encoder;
+use bytes::Bytes;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 pub fn blob_benchmark(c: &mut Criterion) {
@@ -22,5 +24,29 @@ pub fn blob_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, blob_benchmark);
+pub fn large_blob_benchmark(c: &mut Criterion) {
+    // A list of 1000 definite length blobs of 4 KiB each, similar to a large response body.
+    let mut encoder = Encoder::new(Vec::new());
+    encoder.array(1000);
+    for i in 0..1000 {
+        encoder.blob(&vec![(i % 256) as u8; 4096].into());
+    }
+    let input = Bytes::from(encoder.into_writer());
+
+    let decode_all = |mut decoder: Decoder<'_>| {
+        let len = decoder.list().unwrap().unwrap();
+        for _ in 0..len {
+            let _ = black_box(decoder.blob().unwrap());
+        }
+    };
+
+    c.bench_function("large_blob_list_copied", |b| {
+        b.iter(|| decode_all(Decoder::new(&input)))
+    });
+    c.bench_function("large_blob_list_shared", |b| {
+        b.iter(|| decode_all(Decoder::from_bytes(&input)))
+    });
+}
+
+criterion_group!(benches, blob_benchmark, large_blob_benchmark);
 criterion_main!(benches);
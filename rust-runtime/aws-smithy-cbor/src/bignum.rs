@@ -0,0 +1,163 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Conversions between decimal literals and the big-endian byte strings used by CBOR bignums
+//! (tags 2 and 3) and decimal fractions (tag 4).
+//! <https://www.rfc-editor.org/rfc/rfc8949.html#name-bignums>
+
+/// The maximum length in bytes of a decoded bignum magnitude, excluding leading zeros.
+///
+/// Converting a bignum into decimal digits takes time quadratic in its length, so larger
+/// bignums are rejected to bound the cost of decoding untrusted input. 512 bytes hold integers
+/// of more than 1,200 decimal digits.
+pub(crate) const MAX_BIGNUM_LEN: usize = 512;
+
+/// Converts a big-endian unsigned magnitude into its decimal digits.
+pub(crate) fn bytes_to_decimal(bytes: &[u8]) -> String {
+    // Little-endian limbs in base 10^9 so each limb fits in a `u32`
+    const BASE: u64 = 1_000_000_000;
+    let mut limbs: Vec<u32> = vec![0];
+    for &byte in bytes {
+        let mut carry = byte as u64;
+        for limb in limbs.iter_mut() {
+            let value = (*limb as u64) * 256 + carry;
+            *limb = (value % BASE) as u32;
+            carry = value / BASE;
+        }
+        while carry > 0 {
+            limbs.push((carry % BASE) as u32);
+            carry /= BASE;
+        }
+    }
+    let mut digits = limbs.last().expect("at least one limb").to_string();
+    for limb in limbs.iter().rev().skip(1) {
+        digits.push_str(&format!("{limb:09}"));
+    }
+    digits
+}
+
+/// Converts decimal digits into a big-endian unsigned magnitude with no leading zero bytes.
+pub(crate) fn decimal_to_bytes(digits: &str) -> Vec<u8> {
+    // Accumulate little-endian so that carries are pushed rather than inserted at the front
+    let mut bytes: Vec<u8> = Vec::new();
+    for digit in digits.bytes() {
+        let mut carry = (digit - b'0') as u32;
+        for byte in bytes.iter_mut() {
+            let value = (*byte as u32) * 10 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    bytes.reverse();
+    bytes
+}
+
+/// Adds one to a big-endian unsigned magnitude.
+pub(crate) fn increment(bytes: &mut Vec<u8>) {
+    for byte in bytes.iter_mut().rev() {
+        let (value, overflow) = byte.overflowing_add(1);
+        *byte = value;
+        if !overflow {
+            return;
+        }
+    }
+    bytes.insert(0, 1);
+}
+
+/// Subtracts one from a non-zero big-endian unsigned magnitude.
+pub(crate) fn decrement(bytes: &mut Vec<u8>) {
+    for byte in bytes.iter_mut().rev() {
+        let (value, underflow) = byte.overflowing_sub(1);
+        *byte = value;
+        if !underflow {
+            break;
+        }
+    }
+    while bytes.first() == Some(&0) {
+        bytes.remove(0);
+    }
+}
+
+/// Splits a valid number literal into `(negative, digits, exponent)` such that its value is
+/// `(-1)^negative * digits * 10^exponent`, with leading zeros removed from `digits`.
+pub(crate) fn decompose(literal: &str) -> (bool, String, i64) {
+    let (negative, unsigned) = match literal.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, literal),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(idx) => (
+            &unsigned[..idx],
+            unsigned[idx + 1..]
+                .trim_start_matches('+')
+                .parse::<i64>()
+                .unwrap_or_else(|_| {
+                    if unsigned[idx + 1..].starts_with('-') {
+                        i64::MIN
+                    } else {
+                        i64::MAX
+                    }
+                }),
+        ),
+        None => (unsigned, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{integer}{fraction}")
+        .trim_start_matches('0')
+        .to_string();
+    (
+        negative,
+        digits,
+        exponent.saturating_sub(fraction.len() as i64),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decimal_byte_round_trip() {
+        for digits in [
+            "1",
+            "255",
+            "256",
+            "18446744073709551616",
+            "340282366920938463463374607431768211456",
+            "123456789012345678901234567890123456789012345678901234567890",
+        ] {
+            assert_eq!(digits, bytes_to_decimal(&decimal_to_bytes(digits)));
+        }
+        assert_eq!(
+            vec![1, 0, 0, 0, 0, 0, 0, 0, 0],
+            decimal_to_bytes("18446744073709551616")
+        );
+        assert_eq!("0", bytes_to_decimal(&[]));
+    }
+
+    #[test]
+    fn increment_and_decrement() {
+        let mut bytes = vec![0xff, 0xff];
+        increment(&mut bytes);
+        assert_eq!(vec![1, 0, 0], bytes);
+        decrement(&mut bytes);
+        assert_eq!(vec![0xff, 0xff], bytes);
+    }
+
+    #[test]
+    fn decompose_literals() {
+        assert_eq!((false, "15".to_string(), -1), decompose("1.5"));
+        assert_eq!((true, "12345".to_string(), -2), decompose("-0.012345e+4"));
+        assert_eq!((false, "".to_string(), 0), decompose("0"));
+        assert_eq!(
+            (false, "1".to_string(), i64::MAX),
+            decompose("1e99999999999999999999")
+        );
+    }
+}
@@ -4,10 +4,14 @@
  */
 
 use std::borrow::Cow;
+use std::collections::HashSet;
 
-use aws_smithy_types::{Blob, DateTime};
+use aws_smithy_types::primitive::NonFiniteFloatHandling;
+use aws_smithy_types::{BigNumber, Blob, DateTime};
+use bytes::Bytes;
 use minicbor::decode::Error;
 
+use crate::bignum;
 use crate::data::Type;
 
 /// Provides functions for decoding a CBOR object with a known schema.
@@ -17,9 +21,55 @@ use crate::data::Type;
 /// position by calling `str` method, and call the relevant function based on the predetermined schema
 /// for that key. If an unexpected key is encountered, the caller can use the `skip` method to skip
 /// over the element.
+///
+/// A decoder created with [`Decoder::from_bytes`] returns blobs that share the input buffer
+/// instead of copying it.
 #[derive(Debug, Clone)]
 pub struct Decoder<'b> {
     decoder: minicbor::Decoder<'b>,
+    source: Option<&'b Bytes>,
+}
+
+/// Checks that can be applied to untrusted input with [`Decoder::validate`] before it's decoded.
+///
+/// By default, strict mode is disabled and nesting is limited to
+/// [`DEFAULT_MAX_DEPTH`](Self::DEFAULT_MAX_DEPTH) levels.
+#[derive(Debug, Clone)]
+pub struct DecodeOptions {
+    strict: bool,
+    max_depth: usize,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// Default maximum nesting depth of arrays, maps, and tags.
+    pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+    /// Creates options with strict mode disabled and the default maximum nesting depth.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects maps with duplicate keys, and tags other than timestamps (tag 1), bignums
+    /// (tags 2 and 3), and decimal fractions (tag 4).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Rejects input with arrays, maps, and tags nested more than `max_depth` levels deep.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
 }
 
 /// When any of the decode methods are called they look for that particular data type at the current
@@ -123,6 +173,16 @@ impl<'b> Decoder<'b> {
     pub fn new(bytes: &'b [u8]) -> Self {
         Self {
             decoder: minicbor::Decoder::new(bytes),
+            source: None,
+        }
+    }
+
+    /// Creates a decoder over `bytes` whose definite-length blobs borrow from `bytes` rather than
+    /// being copied out of it.
+    pub fn from_bytes(bytes: &'b Bytes) -> Self {
+        Self {
+            decoder: minicbor::Decoder::new(bytes),
+            source: Some(bytes),
         }
     }
 
@@ -158,6 +218,30 @@ impl<'b> Decoder<'b> {
         map => map(Option<u64>);
     }
 
+    /// Reads a float at the current position, applying `handling` to non-finite values.
+    pub fn float_with(
+        &mut self,
+        handling: NonFiniteFloatHandling,
+    ) -> Result<f32, DeserializeError> {
+        let at = self.position();
+        let value = self.float()?;
+        handling
+            .apply_f32(value)
+            .map_err(|err| DeserializeError::custom(err.to_string(), at))
+    }
+
+    /// Reads a double at the current position, applying `handling` to non-finite values.
+    pub fn double_with(
+        &mut self,
+        handling: NonFiniteFloatHandling,
+    ) -> Result<f64, DeserializeError> {
+        let at = self.position();
+        let value = self.double()?;
+        handling
+            .apply_f64(value)
+            .map_err(|err| DeserializeError::custom(err.to_string(), at))
+    }
+
     /// Returns the current position of the buffer, which will be decoded when any of the methods is called.
     pub fn position(&self) -> usize {
         self.decoder.position()
@@ -215,12 +299,28 @@ impl<'b> Decoder<'b> {
             .map_err(DeserializeError::new)?;
 
         Ok(if parts.len() == 1 {
-            Blob::new(parts[0]) // Directly convert &[u8] to Blob if there's only one part.
+            match self.source {
+                // Share the input buffer when the blob is a single contiguous slice of it.
+                Some(source) => Blob::from(source.slice_ref(parts[0])),
+                None => Blob::new(parts[0]), // Directly convert &[u8] to Blob if there's only one part.
+            }
         } else {
             Blob::new(parts.concat()) // Concatenate all parts into a single Blob.
         })
     }
 
+    /// Skips the element at the current position and returns its encoded bytes, e.g. to retain
+    /// the value of an unknown union variant.
+    pub fn raw_element(&mut self) -> Result<Blob, DeserializeError> {
+        let start = self.decoder.position();
+        self.decoder.skip().map_err(DeserializeError::new)?;
+        let raw = &self.decoder.input()[start..self.decoder.position()];
+        Ok(match self.source {
+            Some(source) => Blob::from(source.slice_ref(raw)),
+            None => Blob::new(raw),
+        })
+    }
+
     /// Returns a `DateTime` if the element at the current position in the buffer is a `timestamp`. Otherwise,
     /// a `DeserializeError` error is returned.
     pub fn timestamp(&mut self) -> Result<DateTime, DeserializeError> {
@@ -247,6 +347,201 @@ impl<'b> Decoder<'b> {
             Ok(result)
         }
     }
+
+    /// Checks that the element at the current position satisfies `options`, without advancing
+    /// the decoder. Servers exposed to untrusted clients can use this to reject input before
+    /// decoding it. Generated servers validate request bodies in strict mode with the default
+    /// maximum depth.
+    pub fn validate(&self, options: &DecodeOptions) -> Result<(), DeserializeError> {
+        validate_element(&mut self.decoder.clone(), options, 0)
+    }
+
+    /// Returns a `BigNumber` if the element at the current position in the buffer is an integer,
+    /// a finite float, a bignum (tags 2 and 3), or a decimal fraction (tag 4). Otherwise, a
+    /// `DeserializeError` error is returned.
+    ///
+    /// Integers and bignums are decoded without loss of precision, regardless of their size.
+    pub fn big_number(&mut self) -> Result<BigNumber, DeserializeError> {
+        let position = self.decoder.position();
+        let literal = match self.decoder.datatype().map_err(DeserializeError::new)? {
+            minicbor::data::Type::F16 | minicbor::data::Type::F32 | minicbor::data::Type::F64 => {
+                let value = self.decoder.f64().map_err(DeserializeError::new)?;
+                return BigNumber::try_from(value)
+                    .map_err(|err| DeserializeError::custom(err.to_string(), position));
+            }
+            minicbor::data::Type::Tag => {
+                let decimal_tag = minicbor::data::Tag::from(minicbor::data::IanaTag::Decimal);
+                let bookmark = self.decoder.position();
+                if self.decoder.tag().map_err(DeserializeError::new)? == decimal_tag {
+                    if self.decoder.array().map_err(DeserializeError::new)? != Some(2) {
+                        return Err(DeserializeError::custom(
+                            "expected decimal fraction to be an array of two elements",
+                            position,
+                        ));
+                    }
+                    let exponent = self.decoder.i64().map_err(DeserializeError::new)?;
+                    let mantissa = self.integer_literal()?;
+                    format!("{mantissa}e{exponent}")
+                } else {
+                    self.decoder.set_position(bookmark);
+                    self.integer_literal()?
+                }
+            }
+            _ => self.integer_literal()?,
+        };
+        literal
+            .parse::<BigNumber>()
+            .map_err(|err| DeserializeError::custom(err.to_string(), position))
+    }
+
+    /// Reads an integer or a bignum (tags 2 and 3) at the current position as a decimal literal.
+    fn integer_literal(&mut self) -> Result<String, DeserializeError> {
+        let position = self.decoder.position();
+        if self.decoder.datatype().map_err(DeserializeError::new)? != minicbor::data::Type::Tag {
+            let value = self.decoder.int().map_err(DeserializeError::new)?;
+            return Ok(i128::from(value).to_string());
+        }
+
+        let tag = self.decoder.tag().map_err(DeserializeError::new)?;
+        let negative = if tag == minicbor::data::Tag::from(minicbor::data::IanaTag::PosBignum) {
+            false
+        } else if tag == minicbor::data::Tag::from(minicbor::data::IanaTag::NegBignum) {
+            true
+        } else {
+            return Err(DeserializeError::custom(
+                "expected integer, bignum, or decimal fraction",
+                position,
+            ));
+        };
+        let magnitude = self.blob()?;
+        let magnitude = magnitude.as_ref();
+        let leading_zeros = magnitude.iter().take_while(|byte| **byte == 0).count();
+        let mut magnitude = magnitude[leading_zeros..].to_vec();
+        if magnitude.len() > bignum::MAX_BIGNUM_LEN {
+            return Err(DeserializeError::custom(
+                format!(
+                    "bignum is larger than the maximum of {} bytes",
+                    bignum::MAX_BIGNUM_LEN
+                ),
+                position,
+            ));
+        }
+        if negative {
+            // Negative bignums encode `-1 - n`
+            bignum::increment(&mut magnitude);
+            Ok(format!("-{}", bignum::bytes_to_decimal(&magnitude)))
+        } else {
+            Ok(bignum::bytes_to_decimal(&magnitude))
+        }
+    }
+}
+
+fn validate_element(
+    decoder: &mut minicbor::Decoder<'_>,
+    options: &DecodeOptions,
+    depth: usize,
+) -> Result<(), DeserializeError> {
+    use minicbor::data::{IanaTag, Tag, Type};
+
+    let position = decoder.position();
+    let check_depth = || {
+        if depth + 1 > options.max_depth {
+            Err(DeserializeError::custom(
+                format!("exceeded maximum nesting depth of {}", options.max_depth),
+                position,
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    match decoder.datatype().map_err(DeserializeError::new)? {
+        Type::Array | Type::ArrayIndef => {
+            check_depth()?;
+            let mut remaining = decoder.array().map_err(DeserializeError::new)?;
+            while has_next(decoder, &mut remaining)? {
+                validate_element(decoder, options, depth + 1)?;
+            }
+        }
+        Type::Map | Type::MapIndef => {
+            check_depth()?;
+            let mut remaining = decoder.map().map_err(DeserializeError::new)?;
+            let mut keys = HashSet::new();
+            while has_next(decoder, &mut remaining)? {
+                let key_position = decoder.position();
+                let key = map_key(decoder, options, depth + 1)?;
+                if options.strict && !keys.insert(key) {
+                    return Err(DeserializeError::custom("duplicate map key", key_position));
+                }
+                validate_element(decoder, options, depth + 1)?;
+            }
+        }
+        Type::Tag => {
+            check_depth()?;
+            let tag = decoder.tag().map_err(DeserializeError::new)?;
+            let supported = [
+                IanaTag::Timestamp,
+                IanaTag::PosBignum,
+                IanaTag::NegBignum,
+                IanaTag::Decimal,
+            ];
+            if options.strict && !supported.into_iter().any(|t| Tag::from(t) == tag) {
+                return Err(DeserializeError::custom("unsupported tag", position));
+            }
+            validate_element(decoder, options, depth + 1)?;
+        }
+        _ => decoder.skip().map_err(DeserializeError::new)?,
+    }
+    Ok(())
+}
+
+/// Returns `true` if the array or map being validated has another element, consuming the break
+/// that ends an indefinite-length array or map.
+fn has_next(
+    decoder: &mut minicbor::Decoder<'_>,
+    remaining: &mut Option<u64>,
+) -> Result<bool, DeserializeError> {
+    match remaining {
+        Some(0) => Ok(false),
+        Some(n) => {
+            *n -= 1;
+            Ok(true)
+        }
+        None => {
+            if decoder.datatype().map_err(DeserializeError::new)? == minicbor::data::Type::Break {
+                // A break is a single byte
+                decoder.set_position(decoder.position() + 1);
+                Ok(false)
+            } else {
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// Validates a map key and returns a representation of it for detecting duplicates. Text keys are
+/// compared by their contents so that definite and indefinite-length encodings of the same key
+/// are considered equal.
+fn map_key(
+    decoder: &mut minicbor::Decoder<'_>,
+    options: &DecodeOptions,
+    depth: usize,
+) -> Result<Vec<u8>, DeserializeError> {
+    let start = decoder.position();
+    match decoder.datatype().map_err(DeserializeError::new)? {
+        minicbor::data::Type::String | minicbor::data::Type::StringIndef => {
+            // Prefix with the major type of text strings, so that text keys can't be equal to
+            // the encoding of a key of another type.
+            let mut key = vec![0x60];
+            for chunk in decoder.str_iter().map_err(DeserializeError::new)? {
+                key.extend_from_slice(chunk.map_err(DeserializeError::new)?.as_bytes());
+            }
+            Ok(key)
+        }
+        _ => {
+            validate_element(decoder, options, depth)?;
+            Ok(decoder.input()[start..decoder.position()].to_vec())
+        }
+    }
 }
 
 #[allow(dead_code)] // to avoid `never constructed` warning
@@ -300,8 +595,52 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::Decoder;
+    use crate::decode::DecodeOptions;
+    use crate::{Decoder, Encoder};
     use aws_smithy_types::date_time::Format;
+    use aws_smithy_types::BigNumber;
+
+    #[test]
+    fn non_finite_float_handling() {
+        use aws_smithy_types::primitive::NonFiniteFloatHandling::{Clamp, Encode, Error};
+
+        let encoded = |f: fn(&mut Encoder)| {
+            let mut encoder = Encoder::new(Vec::new());
+            f(&mut encoder);
+            encoder.into_writer()
+        };
+        let finite = encoded(|e| {
+            e.double(1.5).float(-2.0);
+        });
+        for handling in [Encode, Error, Clamp] {
+            let mut decoder = Decoder::new(&finite);
+            assert_eq!(1.5, decoder.double_with(handling).unwrap());
+            assert_eq!(-2.0, decoder.float_with(handling).unwrap());
+        }
+
+        let non_finite = encoded(|e| {
+            e.double(f64::INFINITY)
+                .float(f32::NEG_INFINITY)
+                .double(f64::NAN);
+        });
+        let mut decoder = Decoder::new(&non_finite);
+        assert_eq!(f64::INFINITY, decoder.double_with(Encode).unwrap());
+        assert_eq!(f32::NEG_INFINITY, decoder.float_with(Encode).unwrap());
+        assert!(decoder.double_with(Encode).unwrap().is_nan());
+
+        let mut decoder = Decoder::new(&non_finite);
+        assert_eq!(f64::MAX, decoder.double_with(Clamp).unwrap());
+        assert_eq!(f32::MIN, decoder.float_with(Clamp).unwrap());
+        assert!(decoder.double_with(Clamp).is_err());
+
+        let mut decoder = Decoder::new(&non_finite);
+        let err = decoder.double_with(Error).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("non-finite float `Infinity` is not allowed"),
+            "{err}"
+        );
+    }
 
     #[test]
     fn test_definite_str_is_cow_borrowed() {
@@ -344,6 +683,19 @@ mod tests {
         assert_eq!(member, aws_smithy_types::Blob::new([]));
     }
 
+    #[test]
+    fn test_blob_from_bytes_shares_input() {
+        // `h'010203'` followed by `h'04'`, to check that the position is advanced
+        let input = bytes::Bytes::from_static(&[0x43, 0x01, 0x02, 0x03, 0x41, 0x04]);
+        let mut decoder = Decoder::from_bytes(&input);
+        let first = decoder.blob().expect("could not decode blob");
+        assert_eq!(first.as_ref(), &[1, 2, 3]);
+        assert_eq!(input[1..].as_ptr(), first.as_ref().as_ptr());
+        let second = decoder.blob().expect("could not decode blob");
+        assert_eq!(second.as_ref(), &[4]);
+        assert_eq!(input[5..].as_ptr(), second.as_ref().as_ptr());
+    }
+
     #[test]
     fn test_indefinite_length_blob() {
         // Indefinite length blob containing bytes corresponding to `indefinite-byte, chunked, on each comma`.
@@ -380,4 +732,230 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn test_big_number_round_trip() {
+        let round_trip = |literal: &str| -> String {
+            let mut encoder = Encoder::new(Vec::new());
+            encoder.big_number(&literal.parse().unwrap());
+            let bytes = encoder.into_writer();
+            Decoder::new(&bytes)
+                .big_number()
+                .expect("should decode big number")
+                .into_string()
+        };
+
+        assert_eq!("0", round_trip("0"));
+        assert_eq!("-5", round_trip("-5"));
+        assert_eq!("18446744073709551615", round_trip("18446744073709551615"));
+        assert_eq!("-18446744073709551616", round_trip("-18446744073709551616"));
+        assert_eq!("18446744073709551616", round_trip("18446744073709551616"));
+        assert_eq!("-18446744073709551617", round_trip("-18446744073709551617"));
+        assert_eq!(
+            "123456789012345678901234567890123456789012345678901234567890",
+            round_trip("123456789012345678901234567890123456789012345678901234567890")
+        );
+        assert_eq!("27315e-2", round_trip("273.15"));
+        assert_eq!(
+            "-100000000000000000000001e-24",
+            round_trip("-0.100000000000000000000001")
+        );
+        assert_eq!("15e3", round_trip("1.5e4"));
+    }
+
+    #[test]
+    fn test_big_number_decodes_rfc_examples() {
+        // https://www.rfc-editor.org/rfc/rfc8949.html#name-examples-of-encoded-cbor-da
+        let decode = |bytes: &[u8]| Decoder::new(bytes).big_number().unwrap();
+        assert_eq!(
+            "18446744073709551616",
+            decode(&[0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]).as_str()
+        );
+        assert_eq!(
+            "-18446744073709551617",
+            decode(&[0xc3, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]).as_str()
+        );
+        assert_eq!(
+            "-18446744073709551616",
+            decode(&[0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).as_str()
+        );
+        // 273.15 as a decimal fraction: https://www.rfc-editor.org/rfc/rfc8949.html#name-decimal-fractions-and-bigfl
+        assert_eq!(
+            "27315e-2",
+            decode(&[0xc4, 0x82, 0x21, 0x19, 0x6a, 0xb3]).as_str()
+        );
+        assert_eq!("1.5", decode(&[0xf9, 0x3e, 0x00]).as_str());
+        assert_eq!(
+            Some(BigNumber::from(u64::MAX)),
+            Some(decode(&[
+                0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+            ]))
+        );
+
+        assert!(Decoder::new(&[0xf9, 0x7c, 0x00]).big_number().is_err());
+        assert!(Decoder::new(&[0x61, 0x31]).big_number().is_err());
+    }
+
+    #[test]
+    fn test_big_number_limits_bignum_size() {
+        let bignum = |magnitude: &[u8]| {
+            let mut encoder = minicbor::Encoder::new(Vec::new());
+            encoder
+                .tag(minicbor::data::Tag::from(
+                    minicbor::data::IanaTag::PosBignum,
+                ))
+                .unwrap()
+                .bytes(magnitude)
+                .unwrap();
+            encoder.into_writer()
+        };
+
+        let mut magnitude = vec![0xff; crate::bignum::MAX_BIGNUM_LEN];
+        let largest = bignum(&magnitude);
+        let decoded = Decoder::new(&largest)
+            .big_number()
+            .expect("within the limit");
+        assert_eq!(crate::bignum::decimal_to_bytes(decoded.as_str()), magnitude);
+
+        // Leading zeros don't count towards the limit
+        let mut padded = vec![0; 4096];
+        padded.push(1);
+        assert_eq!(
+            "1",
+            Decoder::new(&bignum(&padded))
+                .big_number()
+                .unwrap()
+                .as_str()
+        );
+
+        magnitude.push(0xff);
+        let err = Decoder::new(&bignum(&magnitude))
+            .big_number()
+            .expect_err("bignum is too large");
+        assert!(
+            err.to_string()
+                .contains("bignum is larger than the maximum"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_validate_duplicate_map_keys() {
+        // `{"a": 1, "a": 2}`
+        let duplicate = [0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02];
+        // `{"a": 1, _ "a": 2}`, with the second key as an indefinite-length string
+        let duplicate_indefinite = [0xa2, 0x61, 0x61, 0x01, 0x7f, 0x61, 0x61, 0xff, 0x02];
+        // `{_ "a": {"a": 1}, "b": 2}`, which has no duplicates at any one level
+        let nested = [
+            0xbf, 0x61, 0x61, 0xa1, 0x61, 0x61, 0x01, 0x61, 0x62, 0x02, 0xff,
+        ];
+
+        let strict = DecodeOptions::new().strict(true);
+        for input in [&duplicate[..], &duplicate_indefinite[..]] {
+            assert!(Decoder::new(input).validate(&DecodeOptions::new()).is_ok());
+            let err = Decoder::new(input).validate(&strict).unwrap_err();
+            assert!(err.to_string().contains("duplicate map key"), "{err}");
+        }
+        assert!(Decoder::new(&nested).validate(&strict).is_ok());
+    }
+
+    #[test]
+    fn test_validate_unsupported_tags() {
+        // Tag 32 (URI) wrapping `"a"`
+        let uri = [0xd8, 0x20, 0x61, 0x61];
+        let strict = DecodeOptions::new().strict(true);
+        assert!(Decoder::new(&uri).validate(&DecodeOptions::new()).is_ok());
+        let err = Decoder::new(&uri).validate(&strict).unwrap_err();
+        assert!(err.to_string().contains("unsupported tag"), "{err}");
+
+        // Timestamps are always supported
+        let timestamp = [0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0];
+        assert!(Decoder::new(&timestamp).validate(&strict).is_ok());
+    }
+
+    #[test]
+    fn test_validate_max_depth() {
+        // `[[[1]]]` and `{"a": [{}]}`
+        let arrays = [0x83, 0x81, 0x81, 0x01, 0x02, 0x03];
+        let mixed = [0xa1, 0x61, 0x61, 0x9f, 0xa0, 0xff];
+        for input in [&arrays[..], &mixed[..]] {
+            assert!(Decoder::new(input)
+                .validate(&DecodeOptions::new().max_depth(3))
+                .is_ok());
+            let err = Decoder::new(input)
+                .validate(&DecodeOptions::new().max_depth(2))
+                .unwrap_err();
+            assert!(err.to_string().contains("maximum nesting depth"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_validate_max_depth_counts_tags() {
+        // Tag 32 (URI) wrapping tag 32 wrapping `"a"`
+        let tags = [0xd8, 0x20, 0xd8, 0x20, 0x61, 0x61];
+        assert!(Decoder::new(&tags)
+            .validate(&DecodeOptions::new().max_depth(2))
+            .is_ok());
+        let err = Decoder::new(&tags)
+            .validate(&DecodeOptions::new().max_depth(1))
+            .unwrap_err();
+        assert!(err.to_string().contains("maximum nesting depth"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_default_max_depth() {
+        let nested = |depth: usize, header: u8| {
+            let mut bytes = vec![header; depth];
+            bytes.push(0x01);
+            bytes
+        };
+        let max = DecodeOptions::DEFAULT_MAX_DEPTH;
+        // Arrays of one element and timestamp tags
+        for header in [0x81, 0xc1] {
+            assert!(Decoder::new(&nested(max, header))
+                .validate(&DecodeOptions::new())
+                .is_ok());
+            let err = Decoder::new(&nested(max + 1, header))
+                .validate(&DecodeOptions::new())
+                .unwrap_err();
+            assert!(err.to_string().contains("maximum nesting depth"), "{err}");
+        }
+        // Deeply nested tags can't overflow the stack
+        assert!(Decoder::new(&nested(1_000_000, 0xc1))
+            .validate(&DecodeOptions::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_does_not_advance() {
+        let bytes = [0x82, 0x01, 0x02];
+        let mut decoder = Decoder::new(&bytes);
+        decoder
+            .validate(&DecodeOptions::new().strict(true))
+            .unwrap();
+        assert_eq!(0, decoder.position());
+        assert_eq!(Some(2), decoder.list().unwrap());
+    }
+
+    #[test]
+    fn test_raw_element() {
+        // `{"unknown": [1, "a"], "next": 2}`
+        let bytes = bytes::Bytes::from_static(&[
+            0xa2, 0x67, 0x75, 0x6e, 0x6b, 0x6e, 0x6f, 0x77, 0x6e, 0x82, 0x01, 0x61, 0x61, 0x64,
+            0x6e, 0x65, 0x78, 0x74, 0x02,
+        ]);
+        for mut decoder in [Decoder::new(&bytes), Decoder::from_bytes(&bytes)] {
+            assert_eq!(Some(2), decoder.map().unwrap());
+            assert_eq!("unknown", decoder.str().unwrap());
+            let raw = decoder.raw_element().unwrap();
+            assert_eq!(&[0x82, 0x01, 0x61, 0x61], raw.as_ref());
+            assert_eq!("next", decoder.str().unwrap());
+            assert_eq!(2, decoder.integer().unwrap());
+        }
+        let shared = Decoder::from_bytes(&bytes)
+            .raw_element()
+            .unwrap()
+            .into_bytes();
+        assert_eq!(bytes.as_ptr(), shared.as_ptr());
+    }
 }
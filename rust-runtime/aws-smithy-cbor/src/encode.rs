@@ -3,7 +3,10 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use aws_smithy_types::{Blob, DateTime};
+use aws_smithy_types::primitive::{NonFiniteFloatError, NonFiniteFloatHandling};
+use aws_smithy_types::{BigNumber, Blob, DateTime};
+
+use crate::bignum;
 
 /// Macro for delegating method calls to the encoder.
 ///
@@ -34,6 +37,7 @@ macro_rules! delegate_method {
 #[derive(Debug, Clone)]
 pub struct Encoder {
     encoder: minicbor::Encoder<Vec<u8>>,
+    compact_floats: bool,
 }
 
 /// We always write to a `Vec<u8>`, which is infallible in `minicbor`.
@@ -44,9 +48,19 @@ impl Encoder {
     pub fn new(writer: Vec<u8>) -> Self {
         Self {
             encoder: minicbor::Encoder::new(writer),
+            compact_floats: false,
         }
     }
 
+    /// When enabled, floating point values are written with the smallest of the half, single, and
+    /// double precision encodings that represents them exactly, i.e. the CBOR "preferred
+    /// serialization" of floats. Disabled by default.
+    /// <https://www.rfc-editor.org/rfc/rfc8949.html#name-preferred-serialization>
+    pub fn with_compact_floats(mut self, compact_floats: bool) -> Self {
+        self.compact_floats = compact_floats;
+        self
+    }
+
     delegate_method! {
         /// Used when it's not cheap to calculate the size, i.e. when the struct has one or more
         /// `Option`al members.
@@ -63,21 +77,62 @@ impl Encoder {
         integer => i32(x: i32);
         /// Writes an long value.
         long => i64(x: i64);
-        /// Writes an float value.
-        float => f32(x: f32);
-        /// Writes an double value.
-        double => f64(x: f64);
         /// Writes a null tag.
         null => null();
         /// Writes an end tag.
         end => end();
     }
 
+    /// Writes an float value.
+    pub fn float(&mut self, x: f32) -> &mut Self {
+        if self.compact_floats && fits_in_f16(x) {
+            self.encoder.f16(x).expect(INFALLIBLE_WRITE);
+        } else {
+            self.encoder.f32(x).expect(INFALLIBLE_WRITE);
+        }
+        self
+    }
+
+    /// Writes an double value.
+    pub fn double(&mut self, x: f64) -> &mut Self {
+        if self.compact_floats && (x.is_nan() || (x as f32) as f64 == x) {
+            return self.float(x as f32);
+        }
+        self.encoder.f64(x).expect(INFALLIBLE_WRITE);
+        self
+    }
+
+    /// Writes a float value, applying `handling` if it's non-finite.
+    pub fn float_with(
+        &mut self,
+        x: f32,
+        handling: NonFiniteFloatHandling,
+    ) -> Result<&mut Self, NonFiniteFloatError> {
+        Ok(self.float(handling.apply_f32(x)?))
+    }
+
+    /// Writes a double value, applying `handling` if it's non-finite.
+    pub fn double_with(
+        &mut self,
+        x: f64,
+        handling: NonFiniteFloatHandling,
+    ) -> Result<&mut Self, NonFiniteFloatError> {
+        Ok(self.double(handling.apply_f64(x)?))
+    }
+
     pub fn blob(&mut self, x: &Blob) -> &mut Self {
         self.encoder.bytes(x.as_ref()).expect(INFALLIBLE_WRITE);
         self
     }
 
+    /// Writes `x`, which must be a complete encoded CBOR data item, as-is. This is used to write
+    /// values that were retained without being decoded, such as the value of an unknown union
+    /// variant read with [`Decoder::raw_element`](crate::Decoder::raw_element).
+    pub fn raw_element(&mut self, x: &[u8]) -> &mut Self {
+        self.encoder.writer_mut().extend_from_slice(x);
+        self
+    }
+
     /// Writes a fixed length array of given length.
     pub fn array(&mut self, len: usize) -> &mut Self {
         self.encoder
@@ -111,7 +166,167 @@ impl Encoder {
         self
     }
 
+    /// Writes an arbitrary-precision number without loss of precision.
+    ///
+    /// Integers are written as CBOR integers when they fit, and as bignums (tags 2 and 3)
+    /// otherwise. Numbers written with a fraction or exponent are written as decimal fractions
+    /// (tag 4). The value is preserved exactly, but not necessarily its lexical form.
+    pub fn big_number(&mut self, x: &BigNumber) -> &mut Self {
+        let (negative, digits, exponent) = bignum::decompose(x.as_str());
+        if exponent != 0 {
+            self.encoder
+                .tag(minicbor::data::Tag::from(minicbor::data::IanaTag::Decimal))
+                .expect(INFALLIBLE_WRITE)
+                .array(2)
+                .expect(INFALLIBLE_WRITE)
+                .i64(exponent)
+                .expect(INFALLIBLE_WRITE);
+        }
+        self.big_integer(negative, &digits)
+    }
+
+    /// Writes the integer with the given sign and decimal `digits` as a CBOR integer or bignum.
+    fn big_integer(&mut self, negative: bool, digits: &str) -> &mut Self {
+        let value = if digits.is_empty() {
+            Some(0)
+        } else {
+            digits
+                .parse::<i128>()
+                .ok()
+                .map(|magnitude| if negative { -magnitude } else { magnitude })
+        };
+        match value.and_then(|value| minicbor::data::Int::try_from(value).ok()) {
+            Some(value) => {
+                self.encoder.int(value).expect(INFALLIBLE_WRITE);
+            }
+            None => {
+                let mut magnitude = bignum::decimal_to_bytes(digits);
+                let tag = if negative {
+                    // Negative bignums encode `-1 - n`
+                    bignum::decrement(&mut magnitude);
+                    minicbor::data::IanaTag::NegBignum
+                } else {
+                    minicbor::data::IanaTag::PosBignum
+                };
+                self.encoder
+                    .tag(minicbor::data::Tag::from(tag))
+                    .expect(INFALLIBLE_WRITE)
+                    .bytes(&magnitude)
+                    .expect(INFALLIBLE_WRITE);
+            }
+        }
+        self
+    }
+
     pub fn into_writer(self) -> Vec<u8> {
         self.encoder.into_writer()
     }
 }
+
+/// Returns `true` if `x` can be represented exactly as a half precision float.
+fn fits_in_f16(x: f32) -> bool {
+    if x.is_nan() || x.is_infinite() || x == 0.0 {
+        return true;
+    }
+    let bits = x.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    let mantissa = bits & 0x7f_ffff;
+    if (-14..=15).contains(&exponent) {
+        // Normal half precision floats have 10 bits of mantissa, compared to 23 bits for `f32`.
+        mantissa & 0x1fff == 0
+    } else if (-24..-14).contains(&exponent) {
+        // Subnormal half precision floats lose a bit of precision for every step below the
+        // minimum exponent.
+        let shift = (-1 - exponent) as u32;
+        (mantissa | 0x80_0000) & ((1 << shift) - 1) == 0
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Encoder;
+
+    fn encode(compact_floats: bool, f: impl FnOnce(&mut Encoder)) -> Vec<u8> {
+        let mut encoder = Encoder::new(Vec::new()).with_compact_floats(compact_floats);
+        f(&mut encoder);
+        encoder.into_writer()
+    }
+
+    #[test]
+    fn floats_are_not_compacted_by_default() {
+        assert_eq!(
+            vec![0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0],
+            encode(false, |e| {
+                e.double(1.5);
+            })
+        );
+        assert_eq!(
+            vec![0xfa, 0x3f, 0xc0, 0, 0],
+            encode(false, |e| {
+                e.float(1.5);
+            })
+        );
+    }
+
+    #[test]
+    fn compact_floats_use_the_smallest_exact_encoding() {
+        // Examples from https://www.rfc-editor.org/rfc/rfc8949.html#name-examples-of-encoded-cbor-da
+        let double = |x: f64| {
+            encode(true, |e| {
+                e.double(x);
+            })
+        };
+        assert_eq!(vec![0xf9, 0x3e, 0x00], double(1.5));
+        assert_eq!(vec![0xf9, 0x7b, 0xff], double(65504.0));
+        assert_eq!(vec![0xf9, 0x00, 0x01], double(5.960464477539063e-8));
+        assert_eq!(vec![0xf9, 0x04, 0x00], double(0.00006103515625));
+        assert_eq!(vec![0xf9, 0xc4, 0x00], double(-4.0));
+        assert_eq!(vec![0xf9, 0x7c, 0x00], double(f64::INFINITY));
+        assert_eq!(vec![0xfa, 0x47, 0xc3, 0x50, 0x00], double(100000.0));
+        assert_eq!(vec![0xfa, 0x7f, 0x7f, 0xff, 0xff], double(f32::MAX as f64));
+        assert_eq!(
+            vec![0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a],
+            double(1.1)
+        );
+        assert_eq!(
+            vec![0xfb, 0x7e, 0x37, 0xe4, 0x3c, 0x88, 0x00, 0x75, 0x9c],
+            double(1.0e+300)
+        );
+    }
+
+    #[test]
+    fn raw_element_round_trips() {
+        // `{"unknown": [1, "a"]}`
+        let input = [
+            0xa1, 0x67, 0x75, 0x6e, 0x6b, 0x6e, 0x6f, 0x77, 0x6e, 0x82, 0x01, 0x61, 0x61,
+        ];
+        let mut decoder = crate::Decoder::new(&input);
+        decoder.map().unwrap();
+        let name = decoder.str().unwrap();
+        let raw = decoder.raw_element().unwrap();
+        let output = encode(false, |encoder| {
+            encoder.map(1).str(&name).raw_element(raw.as_ref());
+        });
+        assert_eq!(&input[..], &output[..]);
+    }
+
+    #[test]
+    fn non_finite_float_handling() {
+        use aws_smithy_types::primitive::NonFiniteFloatHandling::{Clamp, Encode, Error};
+
+        let mut encoder = Encoder::new(Vec::new());
+        encoder.double_with(1.5, Error).unwrap();
+        encoder.double_with(f64::INFINITY, Encode).unwrap();
+        encoder.float_with(f32::NEG_INFINITY, Clamp).unwrap();
+        assert!(encoder.double_with(f64::NAN, Clamp).is_err());
+        assert!(encoder.float_with(f32::INFINITY, Error).is_err());
+
+        let expected = encode(false, |e| {
+            e.double(1.5).double(f64::INFINITY).float(f32::MIN);
+        });
+        // Rejected values aren't written
+        assert_eq!(expected, encoder.into_writer());
+    }
+}
@@ -9,6 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 /* End of automatically managed default lints */
 
+mod bignum;
 pub mod data;
 pub mod decode;
 pub mod encode;
@@ -17,6 +17,11 @@ pub(crate) enum ErrorKind {
     InvalidHeadersLength,
     InvalidMessageLength,
     InvalidUtf8String,
+    LimitExceeded {
+        limit: DecoderLimit,
+        actual: usize,
+        max: usize,
+    },
     MessageChecksumMismatch(u32, u32),
     MessageTooLong,
     PayloadTooLong,
@@ -26,6 +31,31 @@ pub(crate) enum ErrorKind {
     Unmarshalling(String),
 }
 
+/// A limit enforced by the [`MessageFrameDecoder`](crate::frame::MessageFrameDecoder) on incoming frames.
+///
+/// See [`DecoderLimits`](crate::frame::DecoderLimits) for how to configure these.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecoderLimit {
+    /// The maximum total length of a frame, as declared in its prelude.
+    FrameLen,
+    /// The maximum length of a frame's encoded headers, as declared in its prelude.
+    HeadersLen,
+    /// The maximum number of headers in a frame.
+    HeaderCount,
+}
+
+impl fmt::Display for DecoderLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecoderLimit::FrameLen => write!(f, "frame length"),
+            DecoderLimit::HeadersLen => write!(f, "headers length"),
+            DecoderLimit::HeaderCount => write!(f, "header count"),
+        }
+    }
+}
+
+/// An error that occurred while encoding, decoding, marshalling, or unmarshalling an Event Stream message.
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
@@ -65,6 +95,24 @@ impl Error {
                 | Marshalling(_)
         )
     }
+
+    /// Returns true if the error is a prelude or message checksum mismatch, which indicates
+    /// that the frame was corrupted.
+    pub fn is_checksum_mismatch(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::PreludeChecksumMismatch(..) | ErrorKind::MessageChecksumMismatch(..)
+        )
+    }
+
+    /// Returns the decoder limit that was exceeded if this error was caused by an incoming
+    /// frame exceeding one of the configured [`DecoderLimits`](crate::frame::DecoderLimits).
+    pub fn exceeded_limit(&self) -> Option<DecoderLimit> {
+        match self.kind {
+            ErrorKind::LimitExceeded { limit, .. } => Some(limit),
+            _ => None,
+        }
+    }
 }
 
 impl From<ErrorKind> for Error {
@@ -87,6 +135,10 @@ impl fmt::Display for Error {
             InvalidHeadersLength => write!(f, "invalid headers length"),
             InvalidMessageLength => write!(f, "invalid message length"),
             InvalidUtf8String => write!(f, "encountered invalid UTF-8 string"),
+            LimitExceeded { limit, actual, max } => write!(
+                f,
+                "event stream frame {limit} of {actual} exceeds the configured maximum of {max}"
+            ),
             MessageChecksumMismatch(expected, actual) => write!(
                 f,
                 "message checksum 0x{actual:X} didn't match expected checksum 0x{expected:X}"
@@ -7,9 +7,8 @@
 
 use crate::buf::count::CountBuf;
 use crate::buf::crc::{CrcBuf, CrcBufMut};
-use crate::error::{Error, ErrorKind};
+use crate::error::{DecoderLimit, Error, ErrorKind};
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
-use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
 use aws_smithy_types::str_bytes::StrBytes;
 use aws_smithy_types::DateTime;
 use bytes::{Buf, BufMut};
@@ -17,6 +16,10 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::mem::size_of;
 use std::sync::{mpsc, Mutex};
+use std::task::{Context, Poll};
+
+/// Event Stream message, header, and header value types that are encoded and decoded by this module.
+pub use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
 
 const PRELUDE_LENGTH_BYTES: u32 = 3 * size_of::<u32>() as u32;
 const PRELUDE_LENGTH_BYTES_USIZE: usize = PRELUDE_LENGTH_BYTES as usize;
@@ -35,10 +38,12 @@ pub(crate) const TYPE_STRING: u8 = 7;
 pub(crate) const TYPE_TIMESTAMP: u8 = 8;
 pub(crate) const TYPE_UUID: u8 = 9;
 
+/// Error returned by a [`SignMessage`] implementation.
 pub type SignMessageError = Box<dyn StdError + Send + Sync + 'static>;
 
 /// Signs an Event Stream message.
 pub trait SignMessage: fmt::Debug {
+    /// Signs the given `message`, returning the signed message that should be sent instead.
     fn sign(&mut self, message: Message) -> Result<Message, SignMessageError>;
 
     /// SigV4 requires an empty last signed message to be sent.
@@ -46,6 +51,16 @@ pub trait SignMessage: fmt::Debug {
     /// Return `Some(_)` to send a signed last empty message, before completing the stream.
     /// Return `None` to not send one and terminate the stream immediately.
     fn sign_empty(&mut self) -> Option<Result<Message, SignMessageError>>;
+
+    /// Prepares the signer to sign the next message.
+    ///
+    /// This is polled to completion before every message is signed, including the empty last
+    /// message, so that signers can do asynchronous work such as refreshing the credentials they
+    /// sign with before those credentials expire. The default implementation is always ready.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SignMessageError>> {
+        let _ = cx;
+        Poll::Ready(Ok(()))
+    }
 }
 
 /// A sender that gets placed in the request config to wire up an event stream signer after signing.
@@ -94,6 +109,7 @@ pub struct DeferredSigner {
 }
 
 impl DeferredSigner {
+    /// Creates a new `DeferredSigner` along with the sender that is used to provide it a signer.
     pub fn new() -> (Self, DeferredSignerSender) {
         let (tx, rx) = mpsc::channel();
         (
@@ -139,8 +155,13 @@ impl SignMessage for DeferredSigner {
     fn sign_empty(&mut self) -> Option<Result<Message, SignMessageError>> {
         self.acquire().sign_empty()
     }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SignMessageError>> {
+        self.acquire().poll_ready(cx)
+    }
 }
 
+/// Signer that leaves messages unsigned and doesn't send an empty last message.
 #[derive(Debug)]
 pub struct NoOpSigner {}
 impl SignMessage for NoOpSigner {
@@ -158,13 +179,16 @@ pub trait MarshallMessage: fmt::Debug {
     /// Smithy modeled input type to convert from.
     type Input;
 
+    /// Converts the given `input` into a [`Message`].
     fn marshall(&self, input: Self::Input) -> Result<Message, Error>;
 }
 
 /// A successfully unmarshalled message that is either an `Event` or an `Error`.
 #[derive(Debug)]
 pub enum UnmarshalledMessage<T, E> {
+    /// The message was a modeled event.
     Event(T),
+    /// The message was a modeled error.
     Error(E),
 }
 
@@ -175,6 +199,7 @@ pub trait UnmarshallMessage: fmt::Debug {
     /// Smithy modeled error to convert into.
     type Error;
 
+    /// Converts the given `message` into either an event or an error.
     fn unmarshall(
         &self,
         message: &Message,
@@ -321,39 +346,97 @@ pub fn write_headers_to<B: BufMut>(headers: &[Header], mut buffer: B) -> Result<
     Ok(())
 }
 
-// Returns (total_len, header_len)
-fn read_prelude_from<B: Buf>(mut buffer: B) -> Result<(u32, u32), Error> {
-    let mut crc_buffer = CrcBuf::new(&mut buffer);
+/// The prelude at the start of every Event Stream message frame.
+///
+/// A frame is laid out as the prelude, followed by the encoded headers, the payload, and finally
+/// a CRC32 checksum of everything that came before it. The prelude holds the total length of the
+/// frame, the length of the encoded headers, and a CRC32 checksum of those two lengths.
+///
+/// Reading only the prelude is useful when frames need to be split out of a byte stream without
+/// decoding them, such as when proxying or recording Event Stream traffic:
+///
+/// ```rust
+/// use aws_smithy_eventstream::frame::{write_message_to, Message, Prelude};
+///
+/// let mut frame = Vec::new();
+/// write_message_to(&Message::new(&b"payload"[..]), &mut frame).unwrap();
+///
+/// let prelude = Prelude::read_from(&frame[..]).unwrap();
+/// assert_eq!(frame.len(), prelude.total_len() as usize);
+/// assert_eq!(0, prelude.headers_len());
+/// assert_eq!(7, prelude.payload_len());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Prelude {
+    total_len: u32,
+    headers_len: u32,
+}
 
-    // If the buffer doesn't have the entire, then error
-    let total_len = crc_buffer.get_u32();
-    if crc_buffer.remaining() + size_of::<u32>() < total_len as usize {
-        return Err(ErrorKind::InvalidMessageLength.into());
+impl Prelude {
+    /// Length of an encoded prelude in bytes, including its checksum.
+    pub const LENGTH: usize = PRELUDE_LENGTH_BYTES_USIZE;
+
+    /// Reads and validates a prelude from the given `buffer`.
+    ///
+    /// Exactly [`Prelude::LENGTH`] bytes are consumed from the buffer. The rest of the frame
+    /// doesn't need to be in the buffer yet. An error is returned if the prelude checksum
+    /// doesn't match or if the lengths it holds can't describe a valid frame.
+    pub fn read_from<B: Buf>(mut buffer: B) -> Result<Prelude, Error> {
+        if buffer.remaining() < PRELUDE_LENGTH_BYTES_USIZE {
+            return Err(ErrorKind::InvalidMessageLength.into());
+        }
+
+        let mut crc_buffer = CrcBuf::new(&mut buffer);
+        let total_len = crc_buffer.get_u32();
+        let headers_len = crc_buffer.get_u32();
+        let (expected_crc, prelude_crc) = (crc_buffer.into_crc(), buffer.get_u32());
+        if expected_crc != prelude_crc {
+            return Err(ErrorKind::PreludeChecksumMismatch(expected_crc, prelude_crc).into());
+        }
+        // The header length can be 0 or >= 2, but must fit within the frame size
+        if headers_len == 1 || headers_len > max_header_len(total_len)? {
+            return Err(ErrorKind::InvalidHeadersLength.into());
+        }
+        Ok(Prelude {
+            total_len,
+            headers_len,
+        })
     }
 
-    // Validate the prelude
-    let header_len = crc_buffer.get_u32();
-    let (expected_crc, prelude_crc) = (crc_buffer.into_crc(), buffer.get_u32());
-    if expected_crc != prelude_crc {
-        return Err(ErrorKind::PreludeChecksumMismatch(expected_crc, prelude_crc).into());
+    /// Returns the total length of the frame in bytes, including the prelude and message checksum.
+    pub fn total_len(&self) -> u32 {
+        self.total_len
     }
-    // The header length can be 0 or >= 2, but must fit within the frame size
-    if header_len == 1 || header_len > max_header_len(total_len)? {
-        return Err(ErrorKind::InvalidHeadersLength.into());
+
+    /// Returns the length of the encoded headers in bytes.
+    pub fn headers_len(&self) -> u32 {
+        self.headers_len
+    }
+
+    /// Returns the length of the payload in bytes.
+    pub fn payload_len(&self) -> u32 {
+        payload_len(self.total_len, self.headers_len).expect("validated in Prelude::read_from")
     }
-    Ok((total_len, header_len))
 }
 
 /// Reads a message from the given `buffer`. For streaming use cases, use
 /// the [`MessageFrameDecoder`] instead of this.
-pub fn read_message_from<B: Buf>(mut buffer: B) -> Result<Message, Error> {
+pub fn read_message_from<B: Buf>(buffer: B) -> Result<Message, Error> {
+    read_message_with_header_count_limit(buffer, None)
+}
+
+fn read_message_with_header_count_limit<B: Buf>(
+    mut buffer: B,
+    max_header_count: Option<usize>,
+) -> Result<Message, Error> {
     if buffer.remaining() < PRELUDE_LENGTH_BYTES_USIZE {
         return Err(ErrorKind::InvalidMessageLength.into());
     }
 
     // Calculate a CRC as we go and read the prelude
     let mut crc_buffer = CrcBuf::new(&mut buffer);
-    let (total_len, header_len) = read_prelude_from(&mut crc_buffer)?;
+    let prelude = Prelude::read_from(&mut crc_buffer)?;
+    let (total_len, header_len) = (prelude.total_len, prelude.headers_len);
 
     // Verify we have the full frame before continuing
     let remaining_len = total_len
@@ -373,6 +456,9 @@ pub fn read_message_from<B: Buf>(mut buffer: B) -> Result<Message, Error> {
             return Err(ErrorKind::InvalidHeaderValue.into());
         }
         headers.push(header);
+        if let Some(max) = max_header_count {
+            check_limit(DecoderLimit::HeaderCount, headers.len(), max)?;
+        }
     }
 
     // Read payload
@@ -388,7 +474,24 @@ pub fn read_message_from<B: Buf>(mut buffer: B) -> Result<Message, Error> {
     Ok(Message::new_from_parts(headers, payload))
 }
 
-/// Writes the `message` to the given `buffer`.
+/// Writes the `message` to the given `buffer` as a single frame.
+///
+/// The prelude and message checksums are calculated as the frame is written. An error is
+/// returned if the message is too large to fit in a frame.
+///
+/// ```rust
+/// use aws_smithy_eventstream::frame::{
+///     read_message_from, write_message_to, Header, HeaderValue, Message,
+/// };
+///
+/// let message = Message::new(&b"{}"[..])
+///     .add_header(Header::new(":message-type", HeaderValue::String("event".into())))
+///     .add_header(Header::new(":event-type", HeaderValue::String("Greeting".into())));
+/// let mut frame = Vec::new();
+/// write_message_to(&message, &mut frame).unwrap();
+///
+/// assert_eq!(message, read_message_from(&frame[..]).unwrap());
+/// ```
 pub fn write_message_to(message: &Message, buffer: &mut dyn BufMut) -> Result<(), Error> {
     let mut headers = Vec::new();
     for header in message.headers() {
@@ -443,7 +546,7 @@ fn payload_len(total_len: u32, header_len: u32) -> Result<u32, Error> {
 mod message_tests {
     use super::read_message_from;
     use crate::error::ErrorKind;
-    use crate::frame::{write_message_to, Header, HeaderValue, Message};
+    use crate::frame::{write_message_to, Header, HeaderValue, Message, Prelude};
     use aws_smithy_types::DateTime;
     use bytes::Bytes;
 
@@ -604,6 +707,49 @@ mod message_tests {
         assert_eq!(message.headers(), result.headers());
         assert_eq!(message.payload().as_ref(), result.payload().as_ref());
     }
+
+    #[test]
+    fn read_prelude() {
+        let message = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+        let mut buffer = &message[..];
+        let prelude = Prelude::read_from(&mut buffer).unwrap();
+        assert_eq!(message.len() - Prelude::LENGTH, buffer.len());
+        assert_eq!(message.len(), prelude.total_len() as usize);
+
+        let parsed = read_message_from(&message[..]).unwrap();
+        let mut headers = Vec::new();
+        crate::frame::write_headers_to(parsed.headers(), &mut headers).unwrap();
+        assert_eq!(headers.len(), prelude.headers_len() as usize);
+        assert_eq!(parsed.payload().len(), prelude.payload_len() as usize);
+
+        // Only the prelude needs to be available
+        let prelude_only = Prelude::read_from(&message[..Prelude::LENGTH]).unwrap();
+        assert_eq!(prelude, prelude_only);
+    }
+
+    #[test]
+    fn read_invalid_prelude() {
+        let message = include_bytes!("../test_data/invalid_prelude_checksum");
+        let err = Prelude::read_from(&message[..]).unwrap_err();
+        assert!(err.is_checksum_mismatch());
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::PreludeChecksumMismatch(0x8BB495FB, 0xDEADBEEF)
+        ));
+
+        let message = include_bytes!("../test_data/valid_no_headers");
+        let err = Prelude::read_from(&message[..Prelude::LENGTH - 1]).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidMessageLength));
+        assert!(!err.is_checksum_mismatch());
+    }
+
+    #[test]
+    fn message_checksum_mismatch() {
+        let message = include_bytes!("../test_data/invalid_message_checksum");
+        assert!(read_message_from(&message[..])
+            .unwrap_err()
+            .is_checksum_mismatch());
+    }
 }
 
 /// Return value from [`MessageFrameDecoder`].
@@ -615,20 +761,139 @@ pub enum DecodedFrame {
     Complete(Message),
 }
 
+/// Limits enforced by the [`MessageFrameDecoder`] on incoming frames.
+///
+/// Frame and header lengths are checked against these limits as soon as a frame's prelude
+/// has been read, before any of the rest of the frame is buffered, so that a malicious prelude
+/// can't cause the consumer of an untrusted stream to buffer an arbitrary amount of data.
+/// Frames that exceed a limit fail to decode with an [`Error`] whose
+/// [`exceeded_limit`](Error::exceeded_limit) returns the limit that was exceeded.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecoderLimits {
+    max_frame_len: usize,
+    max_headers_len: usize,
+    max_header_count: Option<usize>,
+}
+
+impl Default for DecoderLimits {
+    fn default() -> Self {
+        Self {
+            max_frame_len: Self::DEFAULT_MAX_FRAME_LEN,
+            max_headers_len: Self::DEFAULT_MAX_HEADERS_LEN,
+            max_header_count: None,
+        }
+    }
+}
+
+impl DecoderLimits {
+    /// Default maximum total frame length (16 MiB), which is the largest frame allowed by the
+    /// Event Stream specification.
+    pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+    /// Default maximum length of a frame's encoded headers (128 KiB), which is the largest
+    /// headers length allowed by the Event Stream specification.
+    pub const DEFAULT_MAX_HEADERS_LEN: usize = 128 * 1024;
+
+    /// Returns the default decoder limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum total length of a frame in bytes, including its prelude and checksum.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Sets the maximum length of a frame's encoded headers in bytes.
+    pub fn with_max_headers_len(mut self, max_headers_len: usize) -> Self {
+        self.max_headers_len = max_headers_len;
+        self
+    }
+
+    /// Sets the maximum number of headers in a frame. There is no limit by default, since the
+    /// header count is already bounded by the maximum headers length.
+    pub fn with_max_header_count(mut self, max_header_count: Option<usize>) -> Self {
+        self.max_header_count = max_header_count;
+        self
+    }
+
+    /// Returns the maximum total length of a frame in bytes.
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Returns the maximum length of a frame's encoded headers in bytes.
+    pub fn max_headers_len(&self) -> usize {
+        self.max_headers_len
+    }
+
+    /// Returns the maximum number of headers in a frame, if there is one.
+    pub fn max_header_count(&self) -> Option<usize> {
+        self.max_header_count
+    }
+
+    fn check_prelude(&self, prelude: &Prelude) -> Result<(), Error> {
+        check_limit(
+            DecoderLimit::FrameLen,
+            prelude.total_len as usize,
+            self.max_frame_len,
+        )?;
+        check_limit(
+            DecoderLimit::HeadersLen,
+            prelude.headers_len as usize,
+            self.max_headers_len,
+        )
+    }
+}
+
+fn check_limit(limit: DecoderLimit, actual: usize, max: usize) -> Result<(), Error> {
+    if actual > max {
+        return Err(ErrorKind::LimitExceeded { limit, actual, max }.into());
+    }
+    Ok(())
+}
+
 /// Streaming decoder for decoding a [`Message`] from a stream.
 #[non_exhaustive]
 #[derive(Default, Debug)]
 pub struct MessageFrameDecoder {
     prelude: [u8; PRELUDE_LENGTH_BYTES_USIZE],
     prelude_read: bool,
+    limits: DecoderLimits,
 }
 
 impl MessageFrameDecoder {
-    /// Returns a new `MessageFrameDecoder`.
+    /// Returns a new `MessageFrameDecoder` with the default [`DecoderLimits`].
     pub fn new() -> Self {
         Default::default()
     }
 
+    /// Returns a new `MessageFrameDecoder` that enforces the given `limits`.
+    pub fn with_limits(limits: DecoderLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the limits enforced on frames that haven't started decoding yet.
+    pub fn set_limits(&mut self, limits: DecoderLimits) -> &mut Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the limits enforced by this decoder.
+    pub fn limits(&self) -> &DecoderLimits {
+        &self.limits
+    }
+
+    /// Returns the total length of the frame currently being decoded, if its prelude has been read.
+    pub fn pending_frame_len(&self) -> Option<u32> {
+        self.prelude_read.then(|| (&self.prelude[..]).get_u32())
+    }
+
     /// Determines if the `buffer` has enough data in it to read a full frame.
     /// Returns `Ok(None)` if there's not enough data, or `Some(remaining)` where
     /// `remaining` is the number of bytes after the prelude that belong to the
@@ -663,15 +928,35 @@ impl MessageFrameDecoder {
     /// position. The state from the reading of the prelude is stored in the decoder so that
     /// the next call will be able to decode the entire message, even though the prelude
     /// is no longer available in the `Buf`.
-    pub fn decode_frame<B: Buf>(&mut self, mut buffer: B) -> Result<DecodedFrame, Error> {
+    ///
+    /// The prelude is validated against the decoder's [`DecoderLimits`] as soon as it is read,
+    /// so a frame that exceeds them fails before the rest of it needs to be buffered.
+    ///
+    /// If decoding fails, the decoder is reset so that it doesn't hold on to the state of the
+    /// failed frame.
+    pub fn decode_frame<B: Buf>(&mut self, buffer: B) -> Result<DecodedFrame, Error> {
+        let result = self.try_decode_frame(buffer);
+        if result.is_err() {
+            self.reset();
+        }
+        result
+    }
+
+    fn try_decode_frame<B: Buf>(&mut self, mut buffer: B) -> Result<DecodedFrame, Error> {
         if !self.prelude_read && buffer.remaining() >= PRELUDE_LENGTH_BYTES_USIZE {
             buffer.copy_to_slice(&mut self.prelude);
+            let prelude = Prelude::read_from(&self.prelude[..])?;
+            self.limits.check_prelude(&prelude)?;
             self.prelude_read = true;
         }
 
         if let Some(remaining_len) = self.remaining_bytes_if_frame_available(&buffer)? {
             let mut message_buf = (&self.prelude[..]).chain(buffer.take(remaining_len));
-            let result = read_message_from(&mut message_buf).map(DecodedFrame::Complete);
+            let result = read_message_with_header_count_limit(
+                &mut message_buf,
+                self.limits.max_header_count,
+            )
+            .map(DecodedFrame::Complete);
             self.reset();
             return result;
         }
@@ -682,9 +967,10 @@ impl MessageFrameDecoder {
 
 #[cfg(test)]
 mod message_frame_decoder_tests {
-    use super::{DecodedFrame, MessageFrameDecoder};
+    use super::{DecodedFrame, DecoderLimits, MessageFrameDecoder};
+    use crate::error::DecoderLimit;
     use crate::frame::read_message_from;
-    use bytes::Bytes;
+    use bytes::{BufMut, Bytes};
     use bytes_utils::SegmentedBuf;
 
     #[test]
@@ -747,6 +1033,84 @@ mod message_frame_decoder_tests {
             multiple_streaming_messages_chunk_size(chunk_size);
         }
     }
+
+    fn prelude(total_len: u32, headers_len: u32) -> Vec<u8> {
+        let mut prelude = Vec::new();
+        prelude.put_u32(total_len);
+        prelude.put_u32(headers_len);
+        prelude.put_u32(crc32fast::hash(&prelude));
+        prelude
+    }
+
+    #[test]
+    fn oversized_prelude_is_rejected_before_buffering() {
+        // Only the prelude is available, but it claims a frame of nearly 4 GiB
+        let mut buffer = Bytes::from(prelude(u32::MAX, 0));
+        let err = MessageFrameDecoder::new()
+            .decode_frame(&mut buffer)
+            .expect_err("frame exceeds the default max frame length");
+        assert_eq!(Some(DecoderLimit::FrameLen), err.exceeded_limit());
+
+        let mut buffer = Bytes::from(prelude(1024 * 1024, 512 * 1024));
+        let err = MessageFrameDecoder::new()
+            .decode_frame(&mut buffer)
+            .expect_err("headers exceed the default max headers length");
+        assert_eq!(Some(DecoderLimit::HeadersLen), err.exceeded_limit());
+    }
+
+    #[test]
+    fn custom_limits() {
+        let message = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+        let decode = |limits: DecoderLimits| {
+            MessageFrameDecoder::with_limits(limits).decode_frame(&mut Bytes::from_static(message))
+        };
+
+        assert!(matches!(
+            decode(DecoderLimits::new()),
+            Ok(DecodedFrame::Complete(_))
+        ));
+        let err = decode(DecoderLimits::new().with_max_frame_len(message.len() - 1)).unwrap_err();
+        assert_eq!(Some(DecoderLimit::FrameLen), err.exceeded_limit());
+        let err = decode(DecoderLimits::new().with_max_headers_len(2)).unwrap_err();
+        assert_eq!(Some(DecoderLimit::HeadersLen), err.exceeded_limit());
+        let err = decode(DecoderLimits::new().with_max_header_count(Some(1))).unwrap_err();
+        assert_eq!(Some(DecoderLimit::HeaderCount), err.exceeded_limit());
+        assert_eq!(
+            "event stream frame header count of 2 exceeds the configured maximum of 1",
+            format!("{err}")
+        );
+    }
+
+    #[test]
+    fn decoder_is_reset_after_an_error() {
+        let message = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+        let mut decoder =
+            MessageFrameDecoder::with_limits(DecoderLimits::new().with_max_header_count(Some(1)));
+        let mut segmented = SegmentedBuf::new();
+        segmented.push(&message[..20]);
+        assert!(matches!(
+            decoder.decode_frame(&mut segmented),
+            Ok(DecodedFrame::Incomplete)
+        ));
+        assert_eq!(Some(message.len() as u32), decoder.pending_frame_len());
+        segmented.push(&message[20..]);
+        let err = decoder.decode_frame(&mut segmented).unwrap_err();
+        assert_eq!(Some(DecoderLimit::HeaderCount), err.exceeded_limit());
+        assert_eq!(None, decoder.pending_frame_len());
+
+        let err = decoder
+            .decode_frame(&mut Bytes::from(prelude(u32::MAX, 0)))
+            .unwrap_err();
+        assert_eq!(Some(DecoderLimit::FrameLen), err.exceeded_limit());
+        assert_eq!(None, decoder.pending_frame_len());
+
+        // The next frame is decoded from scratch
+        decoder.set_limits(DecoderLimits::new());
+        assert!(matches!(
+            decoder.decode_frame(&mut Bytes::from_static(message)),
+            Ok(DecodedFrame::Complete(_))
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -15,6 +15,42 @@
 )]
 
 //! AWS Event Stream frame serialization/deserialization implementation.
+//!
+//! This crate implements the `application/vnd.amazon.eventstream` framing that is used by
+//! generated clients for event stream operations. The [`frame`] module can also be used on its
+//! own by tools that need to record, proxy, or replay Event Stream traffic:
+//!
+//! - [`frame::Message`], [`frame::Header`], and [`frame::HeaderValue`] describe a message.
+//! - [`frame::write_message_to`] encodes a message into a frame, calculating its checksums.
+//! - [`frame::read_message_from`] decodes a single frame that is entirely in memory.
+//! - [`frame::MessageFrameDecoder`] decodes frames from a stream of bytes that arrive in chunks.
+//! - [`frame::Prelude`] reads and validates just the start of a frame to find out how long it is.
+//!
+//! ```rust
+//! use aws_smithy_eventstream::frame::{
+//!     write_message_to, DecodedFrame, Header, HeaderValue, Message, MessageFrameDecoder,
+//! };
+//!
+//! let message = Message::new(&b"hello"[..])
+//!     .add_header(Header::new(":message-type", HeaderValue::String("event".into())));
+//! let mut frame = Vec::new();
+//! write_message_to(&message, &mut frame).unwrap();
+//!
+//! // Feed the frame to the decoder a few bytes at a time, as it would arrive over the network
+//! let mut decoder = MessageFrameDecoder::new();
+//! let mut buffer = Vec::new();
+//! let mut decoded = Vec::new();
+//! for chunk in frame.chunks(5) {
+//!     buffer.extend_from_slice(chunk);
+//!     let mut unread = &buffer[..];
+//!     if let DecodedFrame::Complete(message) = decoder.decode_frame(&mut unread).unwrap() {
+//!         decoded.push(message);
+//!     }
+//!     let consumed = buffer.len() - unread.len();
+//!     buffer.drain(..consumed);
+//! }
+//! assert_eq!(vec![message], decoded);
+//! ```
 
 #[cfg(feature = "derive-arbitrary")]
 pub mod arbitrary;
@@ -4,6 +4,7 @@
  */
 use crate::cfg::{cfg_rustls, cfg_s2n_tls};
 use crate::HttpClientError;
+use aws_smithy_runtime_api::box_error::BoxError;
 
 /// Choice of underlying cryptography library
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -26,6 +27,8 @@ pub enum Provider {
 pub struct TlsContext {
     #[allow(unused)]
     trust_store: TrustStore,
+    #[allow(unused)]
+    client_identity: Option<ClientIdentity>,
 }
 
 impl TlsContext {
@@ -45,12 +48,14 @@ impl Default for TlsContext {
 #[derive(Debug)]
 pub struct TlsContextBuilder {
     trust_store: TrustStore,
+    client_identity: Option<ClientIdentity>,
 }
 
 impl TlsContextBuilder {
     fn new() -> Self {
         TlsContextBuilder {
             trust_store: TrustStore::default(),
+            client_identity: None,
         }
     }
 
@@ -60,10 +65,23 @@ impl TlsContextBuilder {
         self
     }
 
+    /// Configure the identity the client presents when the server requests client authentication (mutual TLS)
+    pub fn with_client_identity(mut self, client_identity: ClientIdentity) -> Self {
+        self.client_identity = Some(client_identity);
+        self
+    }
+
     /// Build a new [TlsContext]
+    ///
+    /// Returns an error if the certificate chain or private key of the client identity can't be
+    /// loaded by the enabled TLS providers, or if they don't match each other.
     pub fn build(self) -> Result<TlsContext, HttpClientError> {
+        if let Some(client_identity) = &self.client_identity {
+            client_identity.validate().map_err(HttpClientError::new)?;
+        }
         Ok(TlsContext {
             trust_store: self.trust_store,
+            client_identity: self.client_identity,
         })
     }
 }
@@ -79,6 +97,71 @@ impl From<&[u8]> for CertificatePEM {
     }
 }
 
+/// PEM encoded private key
+#[allow(unused)]
+#[derive(Clone)]
+struct PrivateKeyPEM(Vec<u8>);
+
+/// Certificate chain and private key used to authenticate the client with mutual TLS (mTLS)
+///
+/// NOTE: PEM contents are validated when the [TlsContext] is built.
+#[derive(Clone)]
+pub struct ClientIdentity {
+    #[allow(unused)]
+    cert_chain: CertificatePEM,
+    #[allow(unused)]
+    private_key: PrivateKeyPEM,
+}
+
+impl ClientIdentity {
+    /// Create a client identity from a PEM encoded certificate chain and a PEM encoded private key
+    ///
+    /// The certificate chain starts with the client certificate, optionally followed by
+    /// intermediate certificates. The private key may be PKCS#1, PKCS#8, or SEC1 encoded.
+    pub fn from_pem(
+        cert_chain_pem: impl Into<Vec<u8>>,
+        private_key_pem: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            cert_chain: CertificatePEM(cert_chain_pem.into()),
+            private_key: PrivateKeyPEM(private_key_pem.into()),
+        }
+    }
+
+    fn validate(&self) -> Result<(), BoxError> {
+        use rustls_pki_types::pem::PemObject;
+        use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+
+        let cert_chain = CertificateDer::pem_slice_iter(&self.cert_chain.0)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("invalid PEM client certificate chain: {err}"))?;
+        if cert_chain.is_empty() {
+            return Err("the client certificate chain contains no PEM certificates".into());
+        }
+        PrivateKeyDer::from_pem_slice(&self.private_key.0)
+            .map_err(|err| format!("invalid PEM client private key: {err}"))?;
+
+        #[cfg(any(
+            feature = "rustls-aws-lc",
+            feature = "rustls-aws-lc-fips",
+            feature = "rustls-ring"
+        ))]
+        rustls_provider::build_connector::validate_client_identity(self)?;
+        #[cfg(feature = "s2n-tls")]
+        s2n_tls_provider::build_connector::validate_client_identity(self)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for ClientIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientIdentity")
+            .field("cert_chain", &self.cert_chain)
+            .field("private_key", &"** redacted **")
+            .finish()
+    }
+}
+
 /// Container for root certificates able to provide a root-of-trust for connection authentication
 ///
 /// Platform native root certificates are enabled by default. To start with a clean trust
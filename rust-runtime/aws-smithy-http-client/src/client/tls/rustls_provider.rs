@@ -52,13 +52,14 @@ impl Provider {
 
 pub(crate) mod build_connector {
     use crate::client::tls::rustls_provider::CryptoMode;
-    use crate::tls::TlsContext;
+    use crate::tls::{ClientIdentity, TlsContext};
+    use aws_smithy_runtime_api::box_error::BoxError;
     use client::connect::HttpConnector;
     use hyper_util::client::legacy as client;
     use rustls::crypto::CryptoProvider;
     use rustls_native_certs::CertificateResult;
     use rustls_pki_types::pem::PemObject;
-    use rustls_pki_types::CertificateDer;
+    use rustls_pki_types::{CertificateDer, PrivateKeyDer};
     use std::sync::Arc;
     use std::sync::LazyLock;
 
@@ -129,6 +130,37 @@ pub(crate) mod build_connector {
         }
     }
 
+    fn client_auth_cert(
+        identity: &ClientIdentity,
+    ) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), BoxError> {
+        let cert_chain = CertificateDer::pem_slice_iter(&identity.cert_chain.0)
+            .collect::<Result<Vec<_>, _>>()?;
+        let private_key = PrivateKeyDer::from_pem_slice(&identity.private_key.0)?;
+        Ok((cert_chain, private_key))
+    }
+
+    /// Check that every enabled crypto mode can load the client identity, so that building the
+    /// connector for it can't fail
+    pub(crate) fn validate_client_identity(identity: &ClientIdentity) -> Result<(), BoxError> {
+        let providers: &[fn() -> CryptoProvider] = &[
+            #[cfg(feature = "rustls-aws-lc")]
+            rustls::crypto::aws_lc_rs::default_provider,
+            #[cfg(feature = "rustls-ring")]
+            rustls::crypto::ring::default_provider,
+            #[cfg(feature = "rustls-aws-lc-fips")]
+            rustls::crypto::default_fips_provider,
+        ];
+        for provider in providers {
+            let (cert_chain, private_key) = client_auth_cert(identity)?;
+            rustls::ClientConfig::builder_with_provider(Arc::new(provider()))
+                .with_safe_default_protocol_versions()?
+                .with_root_certificates(rustls::RootCertStore::empty())
+                .with_client_auth_cert(cert_chain, private_key)
+                .map_err(|err| format!("invalid client certificate or private key: {err}"))?;
+        }
+        Ok(())
+    }
+
     /// Create a rustls ClientConfig with smithy-rs defaults
     ///
     /// This centralizes the rustls ClientConfig creation logic to ensure
@@ -138,11 +170,20 @@ pub(crate) mod build_connector {
         tls_context: &TlsContext,
     ) -> rustls::ClientConfig {
         let root_certs = tls_context.rustls_root_certs();
-        rustls::ClientConfig::builder_with_provider(Arc::new(restrict_ciphers(crypto_mode.provider())))
+        let builder = rustls::ClientConfig::builder_with_provider(Arc::new(restrict_ciphers(crypto_mode.provider())))
             .with_safe_default_protocol_versions()
             .expect("Error with the TLS configuration. Please file a bug report under https://github.com/smithy-lang/smithy-rs/issues.")
-            .with_root_certificates(root_certs)
-            .with_no_client_auth()
+            .with_root_certificates(root_certs);
+        match &tls_context.client_identity {
+            Some(identity) => {
+                let (cert_chain, private_key) = client_auth_cert(identity)
+                    .expect("client identity is validated when the TlsContext is built");
+                builder
+                    .with_client_auth_cert(cert_chain, private_key)
+                    .expect("client identity is validated when the TlsContext is built")
+            }
+            None => builder.with_no_client_auth(),
+        }
     }
 
     pub(crate) fn wrap_connector<R>(
@@ -4,9 +4,11 @@
  */
 
 pub(crate) mod build_connector {
-    use crate::tls::TlsContext;
+    use crate::tls::{ClientIdentity, TlsContext};
+    use aws_smithy_runtime_api::box_error::BoxError;
     use client::connect::HttpConnector;
     use hyper_util::client::legacy as client;
+    use s2n_tls::enums::ClientAuthType;
     use s2n_tls::security::Policy;
     use std::sync::LazyLock;
 
@@ -32,11 +34,23 @@ pub(crate) mod build_connector {
         config.build().expect("valid s2n config")
     });
 
+    /// Check that s2n-tls can load the client identity, so that building the connector for it
+    /// can't fail
+    pub(crate) fn validate_client_identity(identity: &ClientIdentity) -> Result<(), BoxError> {
+        s2n_tls::config::Config::builder()
+            .load_pem(&identity.cert_chain.0, &identity.private_key.0)
+            .map_err(|err| format!("invalid client certificate or private key: {err}"))?;
+        Ok(())
+    }
+
     impl TlsContext {
         fn s2n_config(&self) -> s2n_tls::config::Config {
             // TODO(s2n-tls): s2n does not support turning a config back into a builder or a way to load a trust store and re-use it
             // instead if we are only using the defaults then use a cached config, otherwise pay the cost to build a new one
-            if self.trust_store.enable_native_roots && self.trust_store.custom_certs.is_empty() {
+            if self.trust_store.enable_native_roots
+                && self.trust_store.custom_certs.is_empty()
+                && self.client_identity.is_none()
+            {
                 CACHED_CONFIG.clone()
             } else {
                 let mut config = base_config();
@@ -48,6 +62,14 @@ pub(crate) mod build_connector {
                         .trust_pem(pem_cert.0.as_slice())
                         .expect("valid certificate");
                 }
+                if let Some(identity) = &self.client_identity {
+                    config
+                        .set_client_auth_type(ClientAuthType::Optional)
+                        .expect("valid client auth type");
+                    config
+                        .load_pem(&identity.cert_chain.0, &identity.private_key.0)
+                        .expect("client identity is validated when the TlsContext is built");
+                }
                 config.build().expect("valid s2n config")
             }
         }
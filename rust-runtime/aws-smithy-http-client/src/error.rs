@@ -13,6 +13,15 @@ pub struct HttpClientError {
     source: Option<BoxError>,
 }
 
+impl HttpClientError {
+    #[cfg(feature = "default-client")]
+    pub(crate) fn new(source: impl Into<BoxError>) -> Self {
+        Self {
+            source: Some(source.into()),
+        }
+    }
+}
+
 impl fmt::Display for HttpClientError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "unknown HTTP client error")
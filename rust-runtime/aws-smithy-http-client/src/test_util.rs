@@ -19,7 +19,9 @@
 //!   can accomplish this, and the recorded traffic can be saved to JSON and checked in. Note: if
 //!   the traffic recording has sensitive information in it, such as signatures or authorization,
 //!   you will need to manually scrub this out if you intend to store the recording alongside
-//!   your tests.
+//!   your tests. Alternatively, [`CassetteClient`](dvr::CassetteClient) records the traffic the
+//!   first time a test runs, scrubs credentials out of it, and replays it on every later run while
+//!   checking that the requests haven't drifted from the recording.
 //! - [`StaticReplayClient`]: If you want to have a set list of requests and their responses in a test,
 //!   then the static replay client will be useful. On construction, it takes a list of request/response
 //!   pairs that represent each expected request and the response for that test. At the end of the test,
@@ -18,9 +18,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
+mod cassette;
 mod record;
 mod replay;
 
+pub use cassette::{CassetteClient, Sanitizer};
 pub use record::RecordingClient;
 pub use replay::ReplayingClient;
 
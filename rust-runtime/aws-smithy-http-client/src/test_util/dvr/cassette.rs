@@ -0,0 +1,925 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use super::{Action, BodyData, ConnectionId, NetworkTraffic, RecordingClient, ReplayingClient};
+use aws_smithy_runtime_api::client::connector_metadata::ConnectorMetadata;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::base64;
+use bytes::Bytes;
+use indexmap::IndexMap;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Value that redacted headers and query parameters are replaced with.
+const REDACTED: &str = "REDACTED";
+
+/// Headers that carry credentials, and are redacted by default.
+const DEFAULT_REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-amz-security-token",
+    // session token of S3 Express One Zone requests
+    "x-amz-s3session-token",
+    "cookie",
+    "set-cookie",
+];
+
+/// Query parameters of presigned requests that carry credentials, and are redacted by default.
+const DEFAULT_REDACTED_QUERY_PARAMS: &[&str] = &[
+    "X-Amz-Credential",
+    "X-Amz-Signature",
+    "X-Amz-Security-Token",
+    "X-Amz-S3session-Token",
+];
+
+/// Fields of credential responses that hold secrets, and are redacted from bodies by default.
+///
+/// These cover STS (`SecretAccessKey`, `SessionToken`), SSO (`secretAccessKey`, `sessionToken`,
+/// `accessToken`, `refreshToken`), IMDS and container credentials (`Token`), and Cognito
+/// (`SecretKey`) responses.
+const DEFAULT_REDACTED_BODY_FIELDS: &[&str] = &[
+    "SecretAccessKey",
+    "SessionToken",
+    "Token",
+    "SecretKey",
+    "AccessToken",
+    "RefreshToken",
+];
+
+/// Headers that change from one run to the next, and are ignored when checking for drift by default.
+const DEFAULT_VOLATILE_HEADERS: &[&str] = &[
+    "x-amz-date",
+    "x-amz-user-agent",
+    "user-agent",
+    "amz-sdk-invocation-id",
+    "amz-sdk-request",
+    "x-amzn-trace-id",
+];
+
+/// Removes credentials from recorded traffic.
+///
+/// By default, the `Authorization`, `Proxy-Authorization`, `X-Amz-Security-Token`,
+/// `X-Amz-S3session-Token`, `Cookie`, and `Set-Cookie` headers, and the credential, signature, and
+/// session token query parameters of presigned requests, are replaced with `REDACTED`. The secret
+/// key and session token fields of credential responses from STS, SSO, IMDS, container
+/// credential endpoints, and Cognito are masked in JSON and XML bodies, so that recordings that
+/// include credential lookups don't persist live credentials. Additional secrets, such as the keys
+/// of the credentials that the traffic was recorded with, can be registered with
+/// [`redact_secret`](Sanitizer::redact_secret), and are masked wherever they appear in URIs,
+/// headers, and bodies.
+#[derive(Clone)]
+pub struct Sanitizer {
+    headers: Vec<String>,
+    query_params: Vec<String>,
+    body_fields: Vec<String>,
+    secrets: Vec<String>,
+}
+
+// Secrets are deliberately left out of the debug output.
+impl fmt::Debug for Sanitizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sanitizer")
+            .field("headers", &self.headers)
+            .field("query_params", &self.query_params)
+            .field("body_fields", &self.body_fields)
+            .field("secrets", &format!("** {} redacted **", self.secrets.len()))
+            .finish()
+    }
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self {
+            headers: DEFAULT_REDACTED_HEADERS
+                .iter()
+                .map(|h| h.to_string())
+                .collect(),
+            query_params: DEFAULT_REDACTED_QUERY_PARAMS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            body_fields: DEFAULT_REDACTED_BODY_FIELDS
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+            secrets: Vec::new(),
+        }
+    }
+}
+
+impl Sanitizer {
+    /// Creates a sanitizer that redacts the default credential headers, query parameters, and body
+    /// fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redacts the values of the header `name`.
+    pub fn redact_header(mut self, name: impl Into<String>) -> Self {
+        self.headers.push(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Redacts the values of the query parameter `name`.
+    pub fn redact_query_param(mut self, name: impl Into<String>) -> Self {
+        self.query_params.push(name.into());
+        self
+    }
+
+    /// Masks the string value of the JSON field or the text of the XML element `name` in bodies.
+    ///
+    /// Field names are matched case-insensitively. The value is replaced with as many `*` as it has
+    /// bytes, so that the length of bodies doesn't change. Fields that are split across several data
+    /// frames of a body aren't masked.
+    pub fn redact_body_field(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        if !name.is_empty() {
+            self.body_fields.push(name);
+        }
+        self
+    }
+
+    /// Masks `secret` wherever it appears in URIs, headers, and bodies.
+    ///
+    /// The secret is replaced with as many `*` as it has bytes, so that the length of bodies (and
+    /// their `Content-Length`) doesn't change. Secrets that are split across several data frames of
+    /// a body aren't masked.
+    pub fn redact_secret(mut self, secret: impl Into<String>) -> Self {
+        let secret = secret.into();
+        if !secret.is_empty() {
+            self.secrets.push(secret);
+        }
+        self
+    }
+
+    /// Removes credentials from `traffic`.
+    pub fn sanitize(&self, traffic: &mut NetworkTraffic) {
+        for event in &mut traffic.events {
+            match &mut event.action {
+                Action::Request { request } => {
+                    request.uri = self.sanitize_uri(&request.uri);
+                    self.sanitize_headers(&mut request.headers);
+                }
+                Action::Response {
+                    response: Ok(response),
+                } => self.sanitize_headers(&mut response.headers),
+                Action::Response {
+                    response: Err(error),
+                } => error.0 = self.mask_secrets_in_str(&error.0),
+                Action::Data { data, .. } => *data = self.sanitize_body_data(data),
+                Action::Eof { .. } => {}
+            }
+        }
+    }
+
+    fn is_redacted_header(&self, name: &str) -> bool {
+        self.headers.iter().any(|h| h.eq_ignore_ascii_case(name))
+    }
+
+    fn sanitize_uri(&self, uri: &str) -> String {
+        let uri = self.mask_secrets_in_str(uri);
+        let Some((base, query)) = uri.split_once('?') else {
+            return uri;
+        };
+        let query = query
+            .split('&')
+            .map(|param| match param.split_once('=') {
+                Some((name, _))
+                    if self
+                        .query_params
+                        .iter()
+                        .any(|p| p.eq_ignore_ascii_case(name)) =>
+                {
+                    format!("{name}={REDACTED}")
+                }
+                _ => param.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        format!("{base}?{query}")
+    }
+
+    fn sanitize_headers(&self, headers: &mut IndexMap<String, Vec<String>>) {
+        for (name, values) in headers.iter_mut() {
+            for value in values.iter_mut() {
+                *value = if self.is_redacted_header(name) {
+                    REDACTED.to_string()
+                } else {
+                    self.mask_secrets_in_str(value)
+                };
+            }
+        }
+    }
+
+    fn sanitize_body_data(&self, data: &BodyData) -> BodyData {
+        let mut bytes = data.copy_to_vec();
+        self.mask_body_fields(&mut bytes);
+        self.mask_secrets(&mut bytes);
+        match data {
+            // masking only replaces whole UTF-8 sequences with ASCII, so the data remains UTF-8
+            BodyData::Utf8(_) => BodyData::Utf8(String::from_utf8(bytes).expect("valid UTF-8")),
+            BodyData::Base64(_) => BodyData::Base64(base64::encode(bytes)),
+        }
+    }
+
+    fn mask_secrets_in_str(&self, value: &str) -> String {
+        let mut bytes = value.as_bytes().to_vec();
+        self.mask_secrets(&mut bytes);
+        String::from_utf8(bytes).expect("valid UTF-8")
+    }
+
+    /// Replaces the values of redacted JSON fields and XML elements in `bytes` with `*`, keeping the
+    /// length of `bytes`.
+    fn mask_body_fields(&self, bytes: &mut [u8]) {
+        for name in &self.body_fields {
+            // XML: `<name>value</name>`
+            let (open, close) = (format!("<{name}>"), format!("</{name}>"));
+            let mut start = 0;
+            while let Some(found) = find_ignore_case(bytes, open.as_bytes(), start) {
+                let value_start = found + open.len();
+                let Some(value_end) = find_ignore_case(bytes, close.as_bytes(), value_start) else {
+                    break;
+                };
+                bytes[value_start..value_end].fill(b'*');
+                start = value_end + close.len();
+            }
+
+            // JSON: `"name": "value"`
+            let key = format!("\"{name}\"");
+            let mut start = 0;
+            while let Some(found) = find_ignore_case(bytes, key.as_bytes(), start) {
+                start = found + key.len();
+                let mut pos = skip_whitespace(bytes, start);
+                if bytes.get(pos) != Some(&b':') {
+                    continue;
+                }
+                pos = skip_whitespace(bytes, pos + 1);
+                if bytes.get(pos) != Some(&b'"') {
+                    continue;
+                }
+                let value_start = pos + 1;
+                let mut value_end = value_start;
+                while value_end < bytes.len() && bytes[value_end] != b'"' {
+                    // skip over escaped characters, including escaped quotes
+                    value_end += if bytes[value_end] == b'\\' { 2 } else { 1 };
+                }
+                let value_end = value_end.min(bytes.len());
+                bytes[value_start..value_end].fill(b'*');
+                start = value_end;
+            }
+        }
+    }
+
+    /// Replaces every occurrence of a secret in `bytes` with `*`, keeping the length of `bytes`.
+    fn mask_secrets(&self, bytes: &mut [u8]) {
+        for secret in &self.secrets {
+            let secret = secret.as_bytes();
+            let mut start = 0;
+            while start + secret.len() <= bytes.len() {
+                if &bytes[start..start + secret.len()] == secret {
+                    bytes[start..start + secret.len()].fill(b'*');
+                    start += secret.len();
+                } else {
+                    start += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the position of the first occurrence of `needle` in `haystack` at or after `from`,
+/// ignoring ASCII case.
+fn find_ignore_case(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle))
+        .map(|position| from + position)
+}
+
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Record-once, replay-forever client
+///
+/// `CassetteClient` brings VCR-style testing to SDK clients. The first time a test runs, there is
+/// no recording at `path`, so requests are sent with a live connector, and the traffic is recorded
+/// with a [`RecordingClient`]. When the test calls [`finish`](CassetteClient::finish), the traffic
+/// is sanitized to remove credentials, and saved to `path`. Every later run replays the recording
+/// with a [`ReplayingClient`] instead, without network access or credentials, and `finish` checks
+/// that the requests the test sent haven't drifted from the recorded requests.
+///
+/// Delete the recording, or create the client with [`record`](CassetteClient::record), to record
+/// the traffic again.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use aws_smithy_http_client::test_util::dvr::{CassetteClient, Sanitizer};
+///
+/// #[tokio::test]
+/// async fn list_buckets() {
+///     let live = aws_smithy_http_client::Builder::new()
+///         .build_https()
+///         .http_connector(&Default::default(), &runtime_components);
+///     let http_client = CassetteClient::new("tests/data/list-buckets.json", live)
+///         .unwrap()
+///         .sanitizer(Sanitizer::new().redact_secret("my-account-id"));
+///
+///     // Only needed while recording
+///     let config = if http_client.is_recording() {
+///         aws_config::load_defaults(BehaviorVersion::latest()).await
+///     } else {
+///         // test credentials and region
+///     };
+///     let config = Config::from(&config).to_builder()
+///         .http_client(http_client.clone())
+///         .build();
+///
+///     let client = Client::from_conf(config);
+///     let _resp = client.list_buckets().send().await.unwrap();
+///
+///     // Saves the recording, or checks for drift from it.
+///     http_client.finish().await.unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CassetteClient {
+    path: PathBuf,
+    sanitizer: Sanitizer,
+    volatile_headers: Vec<String>,
+    mode: Mode,
+}
+
+#[derive(Clone)]
+enum Mode {
+    Record(RecordingClient),
+    Replay(ReplayingClient),
+}
+
+// Traffic is deliberately left out of the debug output, since it may contain credentials.
+impl fmt::Debug for CassetteClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CassetteClient")
+            .field("path", &self.path)
+            .field("recording", &self.is_recording())
+            .finish()
+    }
+}
+
+impl CassetteClient {
+    /// Creates a client that replays the traffic recorded at `path`, or that records traffic sent
+    /// with `live_connector` if there is no recording yet.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        live_connector: impl HttpConnector + 'static,
+    ) -> Result<Self, Box<dyn Error>> {
+        let path = path.into();
+        if !path.exists() {
+            return Ok(Self::record(path, live_connector));
+        }
+        let replay = ReplayingClient::from_file(&path)?;
+        Ok(Self::with_mode(path, Mode::Replay(replay)))
+    }
+
+    /// Creates a client that records traffic sent with `live_connector`, replacing any recording
+    /// at `path` when it finishes.
+    pub fn record(path: impl Into<PathBuf>, live_connector: impl HttpConnector + 'static) -> Self {
+        Self::with_mode(
+            path.into(),
+            Mode::Record(RecordingClient::new(live_connector)),
+        )
+    }
+
+    fn with_mode(path: PathBuf, mode: Mode) -> Self {
+        Self {
+            path,
+            sanitizer: Sanitizer::default(),
+            volatile_headers: DEFAULT_VOLATILE_HEADERS
+                .iter()
+                .map(|h| h.to_string())
+                .collect(),
+            mode,
+        }
+    }
+
+    /// Sets the [`Sanitizer`] that removes credentials from the traffic before it is saved.
+    ///
+    /// Sent requests are sanitized the same way before they are checked for drift.
+    pub fn sanitizer(mut self, sanitizer: Sanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
+    /// Ignores the header `name` when checking requests for drift.
+    ///
+    /// Headers that change between runs, such as `X-Amz-Date`, `User-Agent`, and
+    /// `Amz-Sdk-Invocation-Id`, are ignored by default, as are redacted headers.
+    pub fn ignore_header(mut self, name: impl Into<String>) -> Self {
+        self.volatile_headers.push(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Returns `true` if this client sends live requests and records them, and `false` if it
+    /// replays a recording.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.mode, Mode::Record(_))
+    }
+
+    /// Returns the path of the recording.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Saves the sanitized recording, or checks the sent requests for drift from the recording.
+    ///
+    /// When recording, response bodies need to be read to completion before this is called, or
+    /// the recording won't include them. When replaying, an error that lists the differences is
+    /// returned if the sent requests don't match the recorded requests.
+    pub async fn finish(self) -> Result<(), Box<dyn Error>> {
+        match &self.mode {
+            Mode::Record(recording) => {
+                let mut traffic = recording.network_traffic();
+                self.sanitizer.sanitize(&mut traffic);
+                traffic.docs = Some(
+                    "Recorded by CassetteClient. Delete this file to record the traffic again."
+                        .into(),
+                );
+                if let Some(parent) = self.path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                traffic.write_to_file(&self.path)
+            }
+            Mode::Replay(replay) => {
+                let drift = self.detect_drift(replay).await;
+                if drift.is_empty() {
+                    return Ok(());
+                }
+                Err(format!(
+                    "requests drifted from the recording at `{}` (delete it to record the traffic again):\n{}",
+                    self.path.display(),
+                    drift
+                        .iter()
+                        .map(|d| format!("  - {d}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                )
+                .into())
+            }
+        }
+    }
+
+    async fn detect_drift(&self, replay: &ReplayingClient) -> Vec<String> {
+        let (num_sent, num_recorded) = (replay.num_sent(), replay.num_recorded());
+        let mut drift = Vec::new();
+        for idx in 0..num_sent.max(num_recorded) {
+            let id = ConnectionId(idx);
+            let sent = replay.take_sent_request(id).await;
+            match (replay.recorded_request(id), sent) {
+                (Some(recorded), Some(sent)) => self.compare(idx, recorded, &sent, &mut drift),
+                (Some(_), None) if idx < num_sent => {
+                    drift.push(format!("request {idx} was sent, but didn't complete"))
+                }
+                (Some(_), None) => drift.push(format!("request {idx} was recorded, but not sent")),
+                (None, _) => drift.push(format!(
+                    "request {idx} was sent, but only {num_recorded} requests were recorded"
+                )),
+            }
+        }
+        drift
+    }
+
+    fn compare(
+        &self,
+        idx: usize,
+        recorded: &http_1x::Request<Bytes>,
+        sent: &http_1x::Request<Bytes>,
+        drift: &mut Vec<String>,
+    ) {
+        if recorded.method() != sent.method() {
+            drift.push(format!(
+                "request {idx}: method changed from `{}` to `{}`",
+                recorded.method(),
+                sent.method()
+            ));
+        }
+
+        let (recorded_uri, sent_uri) = (
+            recorded.uri().to_string(),
+            self.sanitizer.sanitize_uri(&sent.uri().to_string()),
+        );
+        if recorded_uri != sent_uri {
+            drift.push(format!(
+                "request {idx}: URI changed from `{recorded_uri}` to `{sent_uri}`"
+            ));
+        }
+
+        let mut names: Vec<&str> = recorded.headers().keys().map(|k| k.as_str()).collect();
+        for name in sent.headers().keys() {
+            if !names.contains(&name.as_str()) {
+                names.push(name.as_str());
+            }
+        }
+        for name in names {
+            if self.sanitizer.is_redacted_header(name)
+                || self
+                    .volatile_headers
+                    .iter()
+                    .any(|h| h.eq_ignore_ascii_case(name))
+            {
+                continue;
+            }
+            let recorded_values: Vec<String> = recorded
+                .headers()
+                .get_all(name)
+                .iter()
+                .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+                .collect();
+            let sent_values: Vec<String> = sent
+                .headers()
+                .get_all(name)
+                .iter()
+                .map(|v| {
+                    self.sanitizer
+                        .mask_secrets_in_str(&String::from_utf8_lossy(v.as_bytes()))
+                })
+                .collect();
+            if recorded_values != sent_values {
+                drift.push(format!(
+                    "request {idx}: header `{name}` changed from {recorded_values:?} to {sent_values:?}"
+                ));
+            }
+        }
+
+        // sanitize the sent body the same way the recorded body was sanitized
+        let mut sent_body = sent.body().to_vec();
+        self.sanitizer.mask_body_fields(&mut sent_body);
+        self.sanitizer.mask_secrets(&mut sent_body);
+        if recorded.body().as_ref() != sent_body.as_slice() {
+            drift.push(format!(
+                "request {idx}: body changed from {:?} to {:?}",
+                String::from_utf8_lossy(recorded.body()),
+                String::from_utf8_lossy(&sent_body)
+            ));
+        }
+    }
+}
+
+impl HttpConnector for CassetteClient {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        match &self.mode {
+            Mode::Record(recording) => recording.call(request),
+            Mode::Replay(replay) => replay.call(request),
+        }
+    }
+}
+
+impl HttpClient for CassetteClient {
+    fn http_connector(
+        &self,
+        _: &HttpConnectorSettings,
+        _: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        self.clone().into_shared()
+    }
+
+    fn connector_metadata(&self) -> Option<ConnectorMetadata> {
+        Some(ConnectorMetadata::new("cassette-client", None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CassetteClient, Sanitizer};
+    use crate::test_util::dvr::{Action, BodyData, NetworkTraffic};
+    use crate::test_util::{NeverClient, ReplayEvent, StaticReplayClient};
+    use aws_smithy_runtime_api::client::http::HttpConnector;
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use std::path::PathBuf;
+
+    const SECRET: &str = "wJalrXUtnFEMI/K7MDENG";
+
+    fn recording_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("cassette-{}", std::process::id()))
+            .join(format!("{name}.json"));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn request(key: &str, signature: &str) -> HttpRequest {
+        http_1x::Request::builder()
+            .method("PUT")
+            .uri(format!(
+                "https://bucket.s3.amazonaws.com/{key}?X-Amz-Signature={signature}&x-id=PutObject"
+            ))
+            .header(
+                "authorization",
+                format!("AWS4-HMAC-SHA256 Signature={signature}"),
+            )
+            .header("x-amz-date", "20090213T233130Z")
+            .header("content-type", "text/plain")
+            .body(SdkBody::from(format!("secret is {SECRET}")))
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    async fn send(client: &CassetteClient, request: HttpRequest) -> String {
+        let response = client.call(request).await.expect("response");
+        let body = ByteStream::new(response.into_body()).collect().await;
+        String::from_utf8(body.unwrap().to_vec()).unwrap()
+    }
+
+    fn live_connector() -> StaticReplayClient {
+        StaticReplayClient::new(vec![ReplayEvent::new(
+            request("key", "unused"),
+            http_1x::Response::builder()
+                .status(200)
+                .header("set-cookie", "session=1234")
+                .body(SdkBody::from(format!("<Secret>{SECRET}</Secret>")))
+                .unwrap(),
+        )])
+    }
+
+    #[tokio::test]
+    async fn records_sanitized_traffic_once_then_replays_it() {
+        let path = recording_path("record-replay");
+        let sanitizer = Sanitizer::new().redact_secret(SECRET);
+
+        let client = CassetteClient::new(&path, live_connector())
+            .unwrap()
+            .sanitizer(sanitizer.clone());
+        assert!(client.is_recording());
+        assert_eq!(
+            format!("<Secret>{SECRET}</Secret>"),
+            send(&client, request("key", "abcd1234")).await
+        );
+        client.finish().await.unwrap();
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        for credential in [SECRET, "abcd1234", "session=1234"] {
+            assert!(!recording.contains(credential), "{recording}");
+        }
+        let traffic = NetworkTraffic::from_file(&path).unwrap();
+        let Action::Request { request: recorded } = &traffic.events()[0].action else {
+            panic!("unexpected event: {:?}", traffic.events()[0]);
+        };
+        assert_eq!(
+            "https://bucket.s3.amazonaws.com/key?X-Amz-Signature=REDACTED&x-id=PutObject",
+            recorded.uri
+        );
+        assert_eq!(
+            Some(&vec!["REDACTED".to_string()]),
+            recorded.headers.get("authorization")
+        );
+        assert!(traffic.events().iter().any(|event| event.action
+            == Action::Data {
+                data: BodyData::Utf8(format!("<Secret>{}</Secret>", "*".repeat(SECRET.len()))),
+                direction: crate::test_util::dvr::Direction::Response,
+            }));
+
+        // the recording is replayed with different credentials, without sending live requests
+        let client = CassetteClient::new(&path, NeverClient::new())
+            .unwrap()
+            .sanitizer(sanitizer);
+        assert!(!client.is_recording());
+        assert_eq!(
+            format!("<Secret>{}</Secret>", "*".repeat(SECRET.len())),
+            send(&client, request("key", "efgh5678")).await
+        );
+        client.finish().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn redacts_s3_express_session_tokens() {
+        let path = recording_path("s3-express");
+        let request = || -> HttpRequest {
+            http_1x::Request::builder()
+                .uri("https://bucket--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/key?X-Amz-S3session-Token=query-session-token&x-id=GetObject")
+                .header("x-amz-s3session-token", "header-session-token")
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        };
+        let live = StaticReplayClient::new(vec![ReplayEvent::new(
+            request(),
+            http_1x::Response::builder()
+                .status(200)
+                .body(SdkBody::empty())
+                .unwrap(),
+        )]);
+        let client = CassetteClient::new(&path, live).unwrap();
+        send(&client, request()).await;
+        client.finish().await.unwrap();
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        for token in ["query-session-token", "header-session-token"] {
+            assert!(!recording.contains(token), "{recording}");
+        }
+        let traffic = NetworkTraffic::from_file(&path).unwrap();
+        let Action::Request { request: recorded } = &traffic.events()[0].action else {
+            panic!("unexpected event: {:?}", traffic.events()[0]);
+        };
+        assert!(
+            recorded.uri.contains("X-Amz-S3session-Token=REDACTED"),
+            "{}",
+            recorded.uri
+        );
+        assert_eq!(
+            Some(&vec!["REDACTED".to_string()]),
+            recorded.headers.get("x-amz-s3session-token")
+        );
+    }
+
+    #[tokio::test]
+    async fn redacts_credentials_in_response_bodies() {
+        let sts = "<AssumeRoleResponse><AssumeRoleResult><Credentials>\
+            <AccessKeyId>ASIAEXAMPLE</AccessKeyId>\
+            <SecretAccessKey>sts-secret-key</SecretAccessKey>\
+            <SessionToken>sts-session-token</SessionToken>\
+            <Expiration>2024-01-01T00:00:00Z</Expiration>\
+            </Credentials></AssumeRoleResult></AssumeRoleResponse>";
+        let sso = r#"{"roleCredentials":{"accessKeyId":"ASIAEXAMPLE","secretAccessKey":"sso-secret-key","sessionToken":"sso-session\"token","expiration":1704067200000}}"#;
+        let imds = r#"{ "Code" : "Success", "AccessKeyId" : "ASIAEXAMPLE", "SecretAccessKey" : "imds-secret-key", "Token" : "imds-session-token" }"#;
+
+        let path = recording_path("credentials");
+        let request = |uri: &str| -> HttpRequest {
+            http_1x::Request::builder()
+                .uri(uri)
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        };
+        let uris = [
+            "https://sts.amazonaws.com/",
+            "https://portal.sso.us-east-1.amazonaws.com/federation/credentials",
+            "http://169.254.169.254/latest/meta-data/iam/security-credentials/role",
+        ];
+        let live = StaticReplayClient::new(
+            uris.iter()
+                .zip([sts, sso, imds])
+                .map(|(uri, body)| {
+                    ReplayEvent::new(
+                        request(uri),
+                        http_1x::Response::builder()
+                            .status(200)
+                            .body(SdkBody::from(body))
+                            .unwrap(),
+                    )
+                })
+                .collect(),
+        );
+        let client = CassetteClient::new(&path, live).unwrap();
+        for uri in uris {
+            send(&client, request(uri)).await;
+        }
+        client.finish().await.unwrap();
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        for secret in [
+            "sts-secret-key",
+            "sts-session-token",
+            "sso-secret-key",
+            "sso-session",
+            "imds-secret-key",
+            "imds-session-token",
+        ] {
+            assert!(!recording.contains(secret), "{recording}");
+        }
+        // Access key IDs aren't secret, and the length of bodies is preserved
+        assert!(recording.contains("ASIAEXAMPLE"), "{recording}");
+        let traffic = NetworkTraffic::from_file(&path).unwrap();
+        let bodies: Vec<_> = traffic
+            .events()
+            .iter()
+            .filter_map(|event| match &event.action {
+                Action::Data {
+                    data: BodyData::Utf8(data),
+                    direction: crate::test_util::dvr::Direction::Response,
+                } => Some(data.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            vec![sts.len(), sso.len(), imds.len()],
+            bodies.iter().map(|body| body.len()).collect::<Vec<_>>()
+        );
+        assert!(bodies[0].contains("<SessionToken>*****************</SessionToken>"));
+        assert!(bodies[1].contains(r#""sessionToken":"******************""#));
+        assert!(bodies[2].contains(r#""Token" : "******************""#));
+    }
+
+    #[test]
+    fn redacts_custom_body_fields() {
+        let sanitizer = Sanitizer::new().redact_body_field("password");
+        assert_eq!(
+            BodyData::Utf8(r#"{"Password": "****", "user": "me"}"#.into()),
+            sanitizer.sanitize_body_data(&BodyData::Utf8(
+                r#"{"Password": "1234", "user": "me"}"#.into()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn detects_drift() {
+        let path = recording_path("drift");
+        let client = CassetteClient::new(&path, live_connector()).unwrap();
+        send(&client, request("key", "abcd1234")).await;
+        client.finish().await.unwrap();
+
+        let client = CassetteClient::new(&path, NeverClient::new()).unwrap();
+        send(&client, request("other-key", "abcd1234")).await;
+        assert!(client.call(request("key", "abcd1234")).await.is_err());
+        let err = client.finish().await.expect_err("requests drifted");
+        let err = err.to_string();
+        assert!(
+            err.contains("request 0: URI changed from `https://bucket.s3.amazonaws.com/key?X-Amz-Signature=REDACTED&x-id=PutObject` to `https://bucket.s3.amazonaws.com/other-key?X-Amz-Signature=REDACTED&x-id=PutObject`"),
+            "{err}"
+        );
+        assert!(
+            err.contains("request 1 was sent, but only 1 requests were recorded"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn redacted_request_body_fields_are_not_drift() {
+        let path = recording_path("redacted-request-body");
+        let request = |refresh_token: &str| -> HttpRequest {
+            http_1x::Request::builder()
+                .method("POST")
+                .uri("https://oidc.us-east-1.amazonaws.com/token")
+                .body(SdkBody::from(format!(
+                    r#"{{"grantType":"refresh_token","refreshToken":"{refresh_token}"}}"#
+                )))
+                .unwrap()
+                .try_into()
+                .unwrap()
+        };
+        let live = StaticReplayClient::new(vec![ReplayEvent::new(
+            request("live-refresh-token"),
+            http_1x::Response::builder()
+                .status(200)
+                .body(SdkBody::from("{}"))
+                .unwrap(),
+        )]);
+        let client = CassetteClient::new(&path, live).unwrap();
+        send(&client, request("live-refresh-token")).await;
+        client.finish().await.unwrap();
+        let recording = std::fs::read_to_string(&path).unwrap();
+        assert!(!recording.contains("live-refresh-token"), "{recording}");
+
+        let client = CassetteClient::new(&path, NeverClient::new()).unwrap();
+        send(&client, request("live-refresh-token")).await;
+        client.finish().await.expect("no drift");
+
+        let client = CassetteClient::new(&path, NeverClient::new()).unwrap();
+        send(&client, request("other-refresh-token")).await;
+        let err = client.finish().await.expect_err("body length changed");
+        assert!(err.to_string().contains("request 0: body changed"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn detects_unsent_requests() {
+        let path = recording_path("unsent");
+        let client = CassetteClient::new(&path, live_connector()).unwrap();
+        send(&client, request("key", "abcd1234")).await;
+        client.finish().await.unwrap();
+
+        let client = CassetteClient::new(&path, NeverClient::new()).unwrap();
+        let err = client.finish().await.expect_err("request wasn't sent");
+        assert!(
+            err.to_string()
+                .contains("request 0 was recorded, but not sent"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn sanitizes_base64_bodies() {
+        let sanitizer = Sanitizer::new().redact_secret("secret");
+        let data = BodyData::Base64(aws_smithy_types::base64::encode(b"\xff secret \xfe"));
+        assert_eq!(
+            BodyData::Base64(aws_smithy_types::base64::encode(b"\xff ****** \xfe")),
+            sanitizer.sanitize_body_data(&data)
+        );
+        assert!(format!("{sanitizer:?}").contains("** 1 redacted **"));
+    }
+}
@@ -233,6 +233,30 @@ impl ReplayingClient {
             .collect()
     }
 
+    /// Returns the number of requests that were sent to this client.
+    pub(super) fn num_sent(&self) -> usize {
+        self.num_events.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of requests in the replayed traffic.
+    pub(super) fn num_recorded(&self) -> usize {
+        self.verifiable_events.len()
+    }
+
+    /// Returns the request in the replayed traffic for connection `id`.
+    pub(super) fn recorded_request(&self, id: ConnectionId) -> Option<&http_1x::Request<Bytes>> {
+        self.verifiable_events.get(&id)
+    }
+
+    /// Takes the request that was sent for connection `id`, once its body has been read.
+    pub(super) async fn take_sent_request(
+        &self,
+        id: ConnectionId,
+    ) -> Option<http_1x::Request<Bytes>> {
+        let sent = self.recorded_requests.lock().unwrap().remove(&id)?;
+        Some(sent.take().await)
+    }
+
     /// Build a replay connection from a JSON file
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
         let events: NetworkTraffic =
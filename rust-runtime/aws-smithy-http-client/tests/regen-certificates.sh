@@ -12,6 +12,9 @@ set -xe
 # Files generated by this script:
 # server.rsa - private RSA server key
 # server.pem - certificate chain containing certificates [server, intermediate, CA]
+# client.rsa - private RSA client key, used for mutual TLS
+# client.pem - client certificate, used for mutual TLS
+# client-ca.pem - CA certificate that signed the client certificate
 
 # generate the root CA certificate and key
 openssl req -nodes \
@@ -69,4 +72,40 @@ openssl x509 -req \
             -extensions v3_end -extfile openssl.cnf
 
 cat server.cert inter.cert ca.cert > server.pem
+
+# generate the CA that signs client certificates
+openssl req -nodes \
+          -x509 \
+          -days 3650 \
+          -newkey rsa:2048 \
+          -keyout client-ca.key \
+          -out client-ca.pem \
+          -sha256 \
+          -batch \
+          -subj "/CN=SDK RSA client CA"
+
+# generate key and signing request for client
+openssl req -nodes \
+          -newkey rsa:2048 \
+          -keyout client.key \
+          -out client.req \
+          -sha256 \
+          -batch \
+          -subj "/CN=sdktest client"
+
+openssl rsa \
+          -in client.key \
+          -out client.rsa
+
+# sign client cert with the client CA
+openssl x509 -req \
+            -in client.req \
+            -out client.pem \
+            -CA client-ca.pem \
+            -CAkey client-ca.key \
+            -sha256 \
+            -days 3650 \
+            -set_serial 789 \
+            -extensions v3_client -extfile openssl.cnf
+
 rm *.key *.cert *.req
@@ -12,7 +12,7 @@
 
 use aws_smithy_async::time::SystemTimeSource;
 use aws_smithy_http_client::tls;
-use aws_smithy_http_client::tls::{TlsContext, TrustStore};
+use aws_smithy_http_client::tls::{ClientIdentity, TlsContext, TrustStore};
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::http::{HttpClient, HttpConnector, HttpConnectorSettings};
 use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
@@ -24,7 +24,8 @@ use hyper::body::{Bytes, Incoming};
 use hyper::service::service_fn;
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use hyper_util::server::conn::auto::Builder;
-use rustls::ServerConfig;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
 use rustls_pki_types::{CertificateDer, PrivateKeyDer};
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -53,6 +54,12 @@ impl TestServer {
 }
 
 async fn server() -> Result<TestServer, BoxError> {
+    server_with_client_auth(None).await
+}
+
+/// Start a server that requires clients to authenticate with a certificate signed by the CA in
+/// `client_ca` (mutual TLS), or that doesn't authenticate clients if it is `None`
+async fn server_with_client_auth(client_ca: Option<&str>) -> Result<TestServer, BoxError> {
     // Set process wide crypto provider
     let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
 
@@ -68,8 +75,18 @@ async fn server() -> Result<TestServer, BoxError> {
     debug!("Starting to serve on https://{}", addr);
 
     // TLS config
-    let mut server_config = ServerConfig::builder()
-        .with_no_client_auth()
+    let server_config = match client_ca {
+        Some(client_ca) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(client_ca)? {
+                roots.add(cert)?;
+            }
+            ServerConfig::builder()
+                .with_client_cert_verifier(WebPkiClientVerifier::builder(Arc::new(roots)).build()?)
+        }
+        None => ServerConfig::builder().with_no_client_auth(),
+    };
+    let mut server_config = server_config
         .with_single_cert(certs, key)
         .map_err(|e| error(e.to_string()))?;
 
@@ -168,6 +185,66 @@ fn tls_context_from_pem(filename: &str) -> TlsContext {
         .unwrap()
 }
 
+fn tls_context_with_client_identity(cert_chain: &str, private_key: &str) -> TlsContext {
+    let trust_store =
+        TrustStore::empty().with_pem_certificate(fs::read("tests/server.pem").unwrap());
+    TlsContext::builder()
+        .with_trust_store(trust_store)
+        .with_client_identity(ClientIdentity::from_pem(
+            fs::read(cert_chain).unwrap(),
+            fs::read(private_key).unwrap(),
+        ))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_client_identity_invalid_pem() {
+    let err = TlsContext::builder()
+        .with_client_identity(ClientIdentity::from_pem(
+            "not a certificate",
+            fs::read("tests/client.rsa").unwrap(),
+        ))
+        .build()
+        .expect_err("the certificate chain is not PEM encoded");
+    assert!(
+        format!("{err:?}").contains("no PEM certificates"),
+        "{err:?}"
+    );
+
+    let err = TlsContext::builder()
+        .with_client_identity(ClientIdentity::from_pem(
+            fs::read("tests/client.pem").unwrap(),
+            "not a private key",
+        ))
+        .build()
+        .expect_err("the private key is not PEM encoded");
+    assert!(
+        format!("{err:?}").contains("invalid PEM client private key"),
+        "{err:?}"
+    );
+}
+
+#[cfg(any(
+    feature = "rustls-ring",
+    feature = "rustls-aws-lc",
+    feature = "rustls-aws-lc-fips",
+))]
+#[test]
+fn test_client_identity_mismatched_key() {
+    let err = TlsContext::builder()
+        .with_client_identity(ClientIdentity::from_pem(
+            fs::read("tests/client.pem").unwrap(),
+            fs::read("tests/server.rsa").unwrap(),
+        ))
+        .build()
+        .expect_err("the private key doesn't belong to the certificate");
+    assert!(
+        format!("{err:?}").contains("invalid client certificate or private key"),
+        "{err:?}"
+    );
+}
+
 #[cfg(feature = "rustls-aws-lc")]
 #[should_panic(expected = "InvalidCertificate(UnknownIssuer)")]
 #[tokio::test]
@@ -264,6 +341,67 @@ async fn test_rustls_ring_custom_ca() {
     run_tls_test(&client).await.unwrap()
 }
 
+#[cfg(feature = "rustls-ring")]
+#[tokio::test]
+async fn test_rustls_ring_client_identity() {
+    let client = aws_smithy_http_client::Builder::new()
+        .tls_provider(tls::Provider::Rustls(
+            tls::rustls_provider::CryptoMode::Ring,
+        ))
+        .tls_context(tls_context_with_client_identity(
+            "tests/client.pem",
+            "tests/client.rsa",
+        ))
+        .build_https();
+
+    run_mtls_test(&client).await.unwrap()
+}
+
+#[cfg(feature = "rustls-ring")]
+#[tokio::test]
+async fn test_rustls_ring_missing_client_identity() {
+    let client = aws_smithy_http_client::Builder::new()
+        .tls_provider(tls::Provider::Rustls(
+            tls::rustls_provider::CryptoMode::Ring,
+        ))
+        .tls_context(tls_context_from_pem("tests/server.pem"))
+        .build_https();
+
+    run_mtls_test(&client)
+        .await
+        .expect_err("the server requires a client certificate");
+}
+
+#[cfg(feature = "rustls-aws-lc")]
+#[tokio::test]
+async fn test_rustls_aws_lc_client_identity() {
+    let client = aws_smithy_http_client::Builder::new()
+        .tls_provider(tls::Provider::Rustls(
+            tls::rustls_provider::CryptoMode::AwsLc,
+        ))
+        .tls_context(tls_context_with_client_identity(
+            "tests/client.pem",
+            "tests/client.rsa",
+        ))
+        .build_https();
+
+    run_mtls_test(&client).await.unwrap()
+}
+
+#[cfg(feature = "s2n-tls")]
+#[tokio::test]
+async fn test_s2n_tls_client_identity() {
+    let client = aws_smithy_http_client::Builder::new()
+        .tls_provider(tls::Provider::S2nTls)
+        .tls_context(tls_context_with_client_identity(
+            "tests/client.pem",
+            "tests/client.rsa",
+        ))
+        .build_https();
+
+    run_mtls_test(&client).await.unwrap()
+}
+
 #[cfg(feature = "s2n-tls")]
 #[should_panic(expected = "Certificate is untrusted")]
 #[tokio::test]
@@ -289,11 +427,24 @@ async fn run_tls_test(client: &dyn HttpClient) -> Result<(), BoxError> {
     run_tls_test_with_idle_timeout(client, None).await
 }
 
+async fn run_mtls_test(client: &dyn HttpClient) -> Result<(), BoxError> {
+    let server = server_with_client_auth(Some("tests/client-ca.pem")).await?;
+    run_tls_test_against(client, server, None).await
+}
+
 async fn run_tls_test_with_idle_timeout(
     client: &dyn HttpClient,
     pool_timeout: Option<Duration>,
 ) -> Result<(), BoxError> {
     let server = server().await?;
+    run_tls_test_against(client, server, pool_timeout).await
+}
+
+async fn run_tls_test_against(
+    client: &dyn HttpClient,
+    server: TestServer,
+    pool_timeout: Option<Duration>,
+) -> Result<(), BoxError> {
     let start = tokio::time::Instant::now();
     assert_eq!(server.conn_count(), 0); // calibrate conn_count
     let endpoint = format!("https://localhost:{}/", server.listen_addr.port());
@@ -7,6 +7,7 @@
 
 use std::error::Error as StdError;
 
+mod frame_trace;
 mod receiver;
 mod sender;
 
@@ -18,3 +19,6 @@ pub use sender::{EventStreamSender, MessageStreamAdapter, MessageStreamError};
 
 #[doc(inline)]
 pub use receiver::{InitialMessageType, Receiver, ReceiverError};
+
+#[doc(inline)]
+pub use aws_smithy_eventstream::frame::DecoderLimits;
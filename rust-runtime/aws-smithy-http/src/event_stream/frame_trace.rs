@@ -0,0 +1,70 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Frame-level tracing for event streams.
+//!
+//! Every message frame that is sent or received is encoded or decoded within a span, and an event
+//! that describes the frame is emitted once it is complete. Both are emitted at the `TRACE` level
+//! under the [`FRAME_TRACE_TARGET`] target so that they can be enabled separately from the rest
+//! of the logs, for example with `RUST_LOG=aws_smithy_http::event_stream::frame=trace`. The span
+//! duration is the time it took to encode or decode the frame, and the sequence number of each
+//! frame shows how frames were interleaved in bidirectional streams.
+
+use aws_smithy_eventstream::message_size_hint::MessageSizeHint;
+use aws_smithy_types::event_stream::Message;
+use tracing::Span;
+
+/// Target of the spans and events that are emitted for every event stream message frame.
+pub(crate) const FRAME_TRACE_TARGET: &str = "aws_smithy_http::event_stream::frame";
+
+/// Whether a frame was sent or received.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Direction {
+    Sent,
+    Received,
+}
+
+impl Direction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Direction::Sent => "sent",
+            Direction::Received => "received",
+        }
+    }
+}
+
+/// Returns the span to encode or decode the frame with the given `sequence` number within.
+pub(crate) fn frame_span(direction: Direction, sequence: u64) -> Span {
+    tracing::trace_span!(
+        target: FRAME_TRACE_TARGET,
+        "event_stream_frame",
+        direction = direction.as_str(),
+        sequence
+    )
+}
+
+/// Emits an event that describes the `message` of a frame that was encoded or decoded within `span`.
+pub(crate) fn trace_frame(span: &Span, message: &Message) {
+    tracing::trace!(
+        target: FRAME_TRACE_TARGET,
+        parent: span,
+        message_type = header(message, ":message-type"),
+        event_type = header(message, ":event-type"),
+        exception_type = header(message, ":exception-type"),
+        headers = ?message.headers(),
+        payload_len = message.payload().len(),
+        frame_len = message.size_hint(),
+        "event stream frame"
+    );
+}
+
+fn header<'a>(message: &'a Message, name: &str) -> Option<&'a str> {
+    message
+        .headers()
+        .iter()
+        .find(|h| h.name().as_str() == name)
+        .and_then(|h| h.value().as_string().ok())
+        .map(|value| value.as_str())
+}
@@ -3,8 +3,11 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::event_stream::frame_trace::{frame_span, trace_frame, Direction};
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
 use aws_smithy_eventstream::frame::{
-    DecodedFrame, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage,
+    DecodedFrame, DecoderLimits, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage,
 };
 use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
 use aws_smithy_types::body::SdkBody;
@@ -16,7 +19,14 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
-use tracing::trace;
+use std::time::Duration;
+use tracing::{trace, Span};
+
+/// Values of the `:message-type` header of heartbeat messages.
+///
+/// Heartbeat messages keep an otherwise idle event stream alive. They are skipped by the
+/// receiver rather than being unmarshalled.
+const HEARTBEAT_MESSAGE_TYPES: &[&str] = &["heartbeat", "ping"];
 
 /// Wrapper around SegmentedBuf that tracks the state of the stream.
 #[derive(Debug)]
@@ -90,6 +100,10 @@ impl RecvBuf {
 enum ReceiverErrorKind {
     /// The stream ended before a complete message frame was received.
     UnexpectedEndOfStream,
+    /// No data was received within the idle timeout.
+    IdleTimeout(Duration),
+    /// An incomplete message frame grew beyond the maximum number of buffered bytes.
+    BufferLimitExceeded(usize),
 }
 
 /// An error that occurs within an event stream receiver.
@@ -102,13 +116,37 @@ impl fmt::Display for ReceiverError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
             ReceiverErrorKind::UnexpectedEndOfStream => write!(f, "unexpected end of stream"),
+            ReceiverErrorKind::IdleTimeout(idle_timeout) => write!(
+                f,
+                "no data was received on the event stream within the idle timeout of {idle_timeout:?}"
+            ),
+            ReceiverErrorKind::BufferLimitExceeded(limit) => write!(
+                f,
+                "event stream message frame exceeded the maximum of {limit} buffered bytes"
+            ),
         }
     }
 }
 
 impl StdError for ReceiverError {}
 
+impl ReceiverError {
+    /// Returns true if the error was caused by a message frame that was too large to buffer.
+    ///
+    /// See [`Receiver::set_max_buffered_bytes`].
+    pub fn is_buffer_limit_exceeded(&self) -> bool {
+        matches!(self.kind, ReceiverErrorKind::BufferLimitExceeded(_))
+    }
+}
+
 /// Receives Smithy-modeled messages out of an Event Stream.
+///
+/// The receiver only reads from the response body when a message is requested with
+/// [`recv`](Self::recv), and it decodes every complete frame that has already been buffered before
+/// reading more. A consumer that is slower than the service therefore applies backpressure to the
+/// underlying connection through its flow control rather than causing messages to pile up in
+/// memory. The only data that is buffered is the incomplete frame that is currently being received,
+/// which can be bounded with [`set_max_buffered_bytes`](Self::set_max_buffered_bytes).
 #[derive(Debug)]
 pub struct Receiver<T, E> {
     unmarshaller: Box<dyn UnmarshallMessage<Output = T, Error = E> + Send + Sync>,
@@ -120,6 +158,13 @@ pub struct Receiver<T, E> {
     /// initial response, then the message will be stored in `buffered_message` so that it can
     /// be returned with the next call of `recv()`.
     buffered_message: Option<Message>,
+    idle_timeout: Option<Duration>,
+    sleep_impl: Option<SharedAsyncSleep>,
+    max_buffered_bytes: Option<usize>,
+    frames_received: u64,
+    /// Span of the frame that is currently being decoded. It's kept across partial decodes so
+    /// that there is exactly one span per frame.
+    frame_span: Option<Span>,
     _phantom: PhantomData<E>,
 }
 
@@ -152,10 +197,70 @@ impl<T, E> Receiver<T, E> {
             buffer: RecvBuf::Empty,
             body,
             buffered_message: None,
+            idle_timeout: None,
+            sleep_impl: None,
+            max_buffered_bytes: None,
+            frames_received: 0,
+            frame_span: None,
             _phantom: Default::default(),
         }
     }
 
+    /// Sets the idle timeout of this receiver.
+    ///
+    /// When no data, including heartbeat messages, is received on the stream within the idle timeout,
+    /// receiving fails with a retryable [`SdkError::TimeoutError`]. The idle timeout only takes effect
+    /// once a sleep implementation has been set with [`set_sleep_impl`](Self::set_sleep_impl).
+    /// By default, there is no idle timeout.
+    pub fn set_idle_timeout(&mut self, idle_timeout: Option<Duration>) -> &mut Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Returns the idle timeout of this receiver.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    /// Sets the sleep implementation that is used to enforce the idle timeout.
+    pub fn set_sleep_impl(&mut self, sleep_impl: Option<SharedAsyncSleep>) -> &mut Self {
+        self.sleep_impl = sleep_impl;
+        self
+    }
+
+    /// Sets the maximum number of bytes that may be buffered while waiting for a message frame to
+    /// be completed.
+    ///
+    /// Once a frame's prelude announces a length over this limit (or an incomplete frame reaches
+    /// this size before its prelude can be read), receiving fails with an
+    /// [`SdkError::ResponseError`] and the stream is terminated, so frames that are larger than the
+    /// limit can't be received. By default, there is no limit.
+    pub fn set_max_buffered_bytes(&mut self, max_buffered_bytes: Option<usize>) -> &mut Self {
+        self.max_buffered_bytes = max_buffered_bytes;
+        self
+    }
+
+    /// Returns the maximum number of bytes that may be buffered while waiting for a message frame to
+    /// be completed.
+    pub fn max_buffered_bytes(&self) -> Option<usize> {
+        self.max_buffered_bytes
+    }
+
+    /// Sets the limits that the frame decoder enforces on incoming frames.
+    ///
+    /// Frames that exceed these limits are rejected as soon as their prelude has been received,
+    /// and receiving fails with an [`SdkError::ResponseError`]. See [`DecoderLimits`] for the
+    /// defaults.
+    pub fn set_decoder_limits(&mut self, limits: DecoderLimits) -> &mut Self {
+        self.decoder.set_limits(limits);
+        self
+    }
+
+    /// Returns the limits that the frame decoder enforces on incoming frames.
+    pub fn decoder_limits(&self) -> &DecoderLimits {
+        self.decoder.limits()
+    }
+
     fn unmarshall(&self, message: Message) -> Result<Option<T>, SdkError<E, RawMessage>> {
         match self.unmarshaller.unmarshall(&message) {
             Ok(unmarshalled) => match unmarshalled {
@@ -172,10 +277,41 @@ impl<T, E> Receiver<T, E> {
         use http_body_04x::Body;
 
         if !self.buffer.is_eos() {
-            let next_chunk = self
-                .body
-                .data()
-                .await
+            if let Some(limit) = self.max_buffered_bytes {
+                // Everything that's still buffered belongs to an incomplete frame at this point.
+                // Once its prelude has been read, the frame's total length is known up front.
+                let buffered = if self.buffer.has_data() {
+                    self.buffer.buffered().remaining()
+                } else {
+                    0
+                };
+                let exceeded = match self.decoder.pending_frame_len() {
+                    Some(frame_len) => frame_len as usize > limit,
+                    None => buffered >= limit,
+                };
+                if exceeded {
+                    self.buffer = RecvBuf::Terminated;
+                    return Err(SdkError::response_error(
+                        ReceiverError {
+                            kind: ReceiverErrorKind::BufferLimitExceeded(limit),
+                        },
+                        RawMessage::Invalid(None),
+                    ));
+                }
+            }
+            let next_chunk = match (self.idle_timeout, &self.sleep_impl) {
+                (Some(idle_timeout), Some(sleep_impl)) => {
+                    Timeout::new(self.body.data(), sleep_impl.sleep(idle_timeout))
+                        .await
+                        .map_err(|_| {
+                            SdkError::timeout_error(ReceiverError {
+                                kind: ReceiverErrorKind::IdleTimeout(idle_timeout),
+                            })
+                        })?
+                }
+                _ => self.body.data().await,
+            };
+            let next_chunk = next_chunk
                 .transpose()
                 .map_err(|err| SdkError::dispatch_failure(ConnectorError::io(err)))?;
             let buffer = mem::replace(&mut self.buffer, RecvBuf::Empty);
@@ -191,17 +327,28 @@ impl<T, E> Receiver<T, E> {
     async fn next_message(&mut self) -> Result<Option<Message>, SdkError<E, RawMessage>> {
         while !self.buffer.is_eos() {
             if self.buffer.has_data() {
-                if let DecodedFrame::Complete(message) = self
-                    .decoder
-                    .decode_frame(self.buffer.buffered())
-                    .map_err(|err| {
-                        SdkError::response_error(
-                            err,
-                            // the buffer has been consumed
-                            RawMessage::Invalid(None),
-                        )
-                    })?
-                {
+                let frames_received = self.frames_received;
+                let span = self
+                    .frame_span
+                    .get_or_insert_with(|| frame_span(Direction::Received, frames_received + 1))
+                    .clone();
+                let decoded = span.in_scope(|| self.decoder.decode_frame(self.buffer.buffered()));
+                if !matches!(decoded, Ok(DecodedFrame::Incomplete)) {
+                    self.frame_span = None;
+                }
+                if let DecodedFrame::Complete(message) = decoded.map_err(|err| {
+                    SdkError::response_error(
+                        err,
+                        // the buffer has been consumed
+                        RawMessage::Invalid(None),
+                    )
+                })? {
+                    self.frames_received += 1;
+                    trace_frame(&span, &message);
+                    if is_heartbeat(&message) {
+                        trace!(message = ?message, "received event stream heartbeat");
+                        continue;
+                    }
                     trace!(message = ?message, "received complete event stream message");
                     return Ok(Some(message));
                 }
@@ -278,18 +425,34 @@ impl<T, E> Receiver<T, E> {
     }
 }
 
+/// Returns true if `message` is a heartbeat message, which is identified by its `:message-type` header.
+fn is_heartbeat(message: &Message) -> bool {
+    message
+        .headers()
+        .iter()
+        .find(|h| h.name().as_str() == ":message-type")
+        .and_then(|h| h.value().as_string().ok())
+        .map(|message_type| HEARTBEAT_MESSAGE_TYPES.contains(&message_type.as_str()))
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{InitialMessageType, Receiver, UnmarshallMessage};
-    use aws_smithy_eventstream::error::Error as EventStreamError;
-    use aws_smithy_eventstream::frame::{write_message_to, UnmarshalledMessage};
+    use super::{InitialMessageType, Receiver, ReceiverError, UnmarshallMessage};
+    use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
+    use aws_smithy_eventstream::error::{DecoderLimit, Error as EventStreamError};
+    use aws_smithy_eventstream::frame::{write_message_to, DecoderLimits, UnmarshalledMessage};
     use aws_smithy_runtime_api::client::result::SdkError;
     use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::error::display::DisplayErrorContext;
     use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
     use bytes::Bytes;
+    use futures_util::StreamExt;
     use hyper::body::Body;
     use std::error::Error as StdError;
     use std::io::{Error as IOError, ErrorKind};
+    use std::time::Duration;
+    use tracing_test::traced_test;
 
     fn encode_initial_response() -> Bytes {
         let mut buffer = Vec::new();
@@ -306,6 +469,16 @@ mod tests {
         buffer.into()
     }
 
+    fn encode_heartbeat() -> Bytes {
+        let mut buffer = Vec::new();
+        let message = Message::new(Bytes::new()).add_header(Header::new(
+            ":message-type",
+            HeaderValue::String("heartbeat".into()),
+        ));
+        write_message_to(&message, &mut buffer).unwrap();
+        buffer.into()
+    }
+
     fn encode_message(message: &str) -> Bytes {
         let mut buffer = Vec::new();
         let message = Message::new(Bytes::copy_from_slice(message.as_bytes()));
@@ -560,6 +733,190 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn heartbeats_are_skipped() {
+        let chunks: Vec<Result<_, IOError>> = vec![
+            Ok(encode_heartbeat()),
+            Ok(encode_initial_response()),
+            Ok(Bytes::from(
+                [
+                    encode_heartbeat(),
+                    encode_message("one"),
+                    encode_heartbeat(),
+                ]
+                .concat(),
+            )),
+            Ok(encode_message("two")),
+            Ok(encode_heartbeat()),
+        ];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        assert!(receiver
+            .try_recv_initial(InitialMessageType::Response)
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(
+            TestMessage("two".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn idle_timeout() {
+        let chunks: Vec<Result<_, IOError>> = vec![Ok(encode_message("one"))];
+        let chunk_stream =
+            futures_util::stream::iter(chunks).chain(futures_util::stream::pending());
+        let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        receiver
+            .set_idle_timeout(Some(Duration::from_millis(100)))
+            .set_sleep_impl(Some(SharedAsyncSleep::new(TokioSleep::new())));
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        let err = receiver.recv().await.expect_err("idle timeout");
+        assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+        assert!(
+            format!("{}", DisplayErrorContext(&err)).contains("idle timeout of 100ms"),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn max_buffered_bytes() {
+        // The second frame is 66 bytes long, which is over the limit
+        let frames = [encode_message("one"), encode_message(&"m".repeat(50))].concat();
+        let chunks: Vec<Result<_, IOError>> = frames
+            .chunks(10)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        receiver.set_max_buffered_bytes(Some(64));
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+
+        let err = receiver.recv().await.expect_err("frame is too large");
+        match &err {
+            SdkError::ResponseError(_) => assert!(err
+                .source()
+                .expect("error source")
+                .downcast_ref::<ReceiverError>()
+                .expect("receiver error")
+                .is_buffer_limit_exceeded()),
+            _ => panic!("expected a response error, got {err:?}"),
+        }
+        assert!(
+            format!("{}", DisplayErrorContext(&err)).contains("maximum of 64 buffered bytes"),
+            "{err:?}"
+        );
+        // The stream is terminated after the limit is exceeded
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn decoder_limits() {
+        let frames = [encode_message("one"), encode_message(&"l".repeat(100))].concat();
+        let chunk_stream = futures_util::stream::iter(vec![Ok::<_, IOError>(Bytes::from(frames))]);
+        let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        receiver.set_decoder_limits(DecoderLimits::new().with_max_frame_len(64));
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+
+        let err = receiver.recv().await.expect_err("frame is too large");
+        match &err {
+            SdkError::ResponseError(_) => assert_eq!(
+                Some(DecoderLimit::FrameLen),
+                err.source()
+                    .expect("error source")
+                    .downcast_ref::<EventStreamError>()
+                    .expect("event stream error")
+                    .exceeded_limit()
+            ),
+            _ => panic!("expected a response error, got {err:?}"),
+        }
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn frames_are_traced() {
+        let chunks: Vec<Result<_, IOError>> =
+            vec![Ok(encode_heartbeat()), Ok(encode_message("one"))];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert!(logs_contain("event_stream_frame"));
+        assert!(logs_contain("heartbeat"));
+        assert!(logs_contain("sequence=2"));
+        assert!(logs_contain("payload_len=3"));
+    }
+
+    #[tokio::test]
+    async fn one_span_per_frame() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        struct CountSpans(Arc<AtomicU64>);
+        impl tracing::Subscriber for CountSpans {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+                if attrs.metadata().name() == "event_stream_frame" {
+                    self.0.fetch_add(1, Ordering::SeqCst);
+                }
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let spans = Arc::new(AtomicU64::new(0));
+        let _guard = tracing::subscriber::set_default(CountSpans(spans.clone()));
+
+        let frames = [encode_message("one"), encode_message("two")].concat();
+        let chunks: Vec<Result<_, IOError>> = frames
+            .chunks(5)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(
+            TestMessage("two".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(None, receiver.recv().await.unwrap());
+        assert_eq!(2, spans.load(Ordering::SeqCst));
+    }
+
     fn assert_send_and_sync<T: Send + Sync>() {}
 
     #[tokio::test]
@@ -3,10 +3,12 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::event_stream::frame_trace::{frame_span, trace_frame, Direction};
 use aws_smithy_eventstream::frame::{write_message_to, MarshallMessage, SignMessage};
 use aws_smithy_eventstream::message_size_hint::MessageSizeHint;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_types::error::ErrorMetadata;
+use aws_smithy_types::event_stream::Message;
 use bytes::Bytes;
 use futures_core::Stream;
 use std::error::Error as StdError;
@@ -14,8 +16,8 @@ use std::fmt;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::pin::Pin;
-use std::task::{Context, Poll};
-use tracing::trace;
+use std::task::{ready, Context, Poll};
+use tracing::{trace, Span};
 
 /// Input type for Event Streams.
 pub struct EventStreamSender<T, E> {
@@ -123,7 +125,11 @@ pub struct MessageStreamAdapter<T, E: StdError + Send + Sync + 'static> {
     error_marshaller: Box<dyn MarshallMessage<Input = E> + Send + Sync>,
     signer: Box<dyn SignMessage + Send + Sync>,
     stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send>>,
+    /// A marshalled message that is waiting for the signer to be ready to sign it.
+    unsigned_message: Option<(Span, Message)>,
+    end_of_stream: bool,
     end_signal_sent: bool,
+    frames_sent: u64,
     _phantom: PhantomData<E>,
 }
 
@@ -142,7 +148,10 @@ impl<T, E: StdError + Send + Sync + 'static> MessageStreamAdapter<T, E> {
             error_marshaller: Box::new(error_marshaller),
             signer: Box::new(signer),
             stream,
+            unsigned_message: None,
+            end_of_stream: false,
             end_signal_sent: false,
+            frames_sent: 0,
             _phantom: Default::default(),
         }
     }
@@ -153,49 +162,59 @@ impl<T, E: StdError + Send + Sync + 'static> Stream for MessageStreamAdapter<T,
         Result<Bytes, SdkError<E, aws_smithy_runtime_api::client::orchestrator::HttpResponse>>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        match self.stream.as_mut().poll_next(cx) {
-            Poll::Ready(message_option) => {
-                if let Some(message_result) = message_option {
-                    let message = match message_result {
-                        Ok(message) => self
-                            .marshaller
-                            .marshall(message)
-                            .map_err(SdkError::construction_failure)?,
-                        Err(message) => self
-                            .error_marshaller
-                            .marshall(message)
-                            .map_err(SdkError::construction_failure)?,
-                    };
-
-                    trace!(unsigned_message = ?message, "signing event stream message");
-                    let message = self
-                        .signer
-                        .sign(message)
+        if self.end_signal_sent {
+            return Poll::Ready(None);
+        }
+        if self.unsigned_message.is_none() && !self.end_of_stream {
+            match ready!(self.stream.as_mut().poll_next(cx)) {
+                Some(message_result) => {
+                    self.frames_sent += 1;
+                    let span = frame_span(Direction::Sent, self.frames_sent);
+                    let message = span
+                        .in_scope(|| match message_result {
+                            Ok(message) => self.marshaller.marshall(message),
+                            Err(message) => self.error_marshaller.marshall(message),
+                        })
                         .map_err(SdkError::construction_failure)?;
+                    trace_frame(&span, &message);
+                    self.unsigned_message = Some((span, message));
+                }
+                None => self.end_of_stream = true,
+            }
+        }
 
+        // The signer may need to refresh its credentials before it can sign the next message
+        ready!(self.signer.poll_ready(cx)).map_err(SdkError::construction_failure)?;
+
+        if let Some((span, message)) = self.unsigned_message.take() {
+            let _entered = span.enter();
+            trace!(unsigned_message = ?message, "signing event stream message");
+            let message = self
+                .signer
+                .sign(message)
+                .map_err(SdkError::construction_failure)?;
+
+            let mut buffer = Vec::with_capacity(message.size_hint());
+            write_message_to(&message, &mut buffer).map_err(SdkError::construction_failure)?;
+            trace!(signed_message = ?buffer, "sending signed event stream message");
+            Poll::Ready(Some(Ok(Bytes::from(buffer))))
+        } else {
+            self.end_signal_sent = true;
+            match self.signer.sign_empty() {
+                Some(sign) => {
+                    self.frames_sent += 1;
+                    let span = frame_span(Direction::Sent, self.frames_sent);
+                    let _entered = span.enter();
+                    let message = sign.map_err(SdkError::construction_failure)?;
+                    trace_frame(&span, &message);
                     let mut buffer = Vec::with_capacity(message.size_hint());
                     write_message_to(&message, &mut buffer)
                         .map_err(SdkError::construction_failure)?;
-                    trace!(signed_message = ?buffer, "sending signed event stream message");
+                    trace!(signed_message = ?buffer, "sending signed empty message to terminate the event stream");
                     Poll::Ready(Some(Ok(Bytes::from(buffer))))
-                } else if !self.end_signal_sent {
-                    self.end_signal_sent = true;
-                    match self.signer.sign_empty() {
-                        Some(sign) => {
-                            let message = sign.map_err(SdkError::construction_failure)?;
-                            let mut buffer = Vec::with_capacity(message.size_hint());
-                            write_message_to(&message, &mut buffer)
-                                .map_err(SdkError::construction_failure)?;
-                            trace!(signed_message = ?buffer, "sending signed empty message to terminate the event stream");
-                            Poll::Ready(Some(Ok(Bytes::from(buffer))))
-                        }
-                        None => Poll::Ready(None),
-                    }
-                } else {
-                    Poll::Ready(None)
                 }
+                None => Poll::Ready(None),
             }
-            Poll::Pending => Poll::Pending,
         }
     }
 }
@@ -215,6 +234,7 @@ mod tests {
     use futures_core::Stream;
     use futures_util::stream::StreamExt;
     use std::error::Error as StdError;
+    use std::task::{Context, Poll};
 
     #[derive(Debug, Eq, PartialEq)]
     struct TestMessage(String);
@@ -263,6 +283,46 @@ mod tests {
         }
     }
 
+    /// Signer that has to asynchronously refresh before it can sign each message.
+    #[derive(Debug, Default)]
+    struct RefreshingSigner {
+        refreshing: bool,
+        refreshed: bool,
+        refreshes: i32,
+    }
+    impl RefreshingSigner {
+        fn signed(&mut self, message: Message) -> Message {
+            assert!(
+                std::mem::take(&mut self.refreshed),
+                "signer must be ready before signing"
+            );
+            message.add_header(Header::new("refreshes", HeaderValue::Int32(self.refreshes)))
+        }
+    }
+    impl SignMessage for RefreshingSigner {
+        fn sign(&mut self, message: Message) -> Result<Message, SignMessageError> {
+            Ok(self.signed(message))
+        }
+
+        fn sign_empty(&mut self) -> Option<Result<Message, SignMessageError>> {
+            Some(Ok(self.signed(Message::new(&b""[..]))))
+        }
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SignMessageError>> {
+            if !self.refreshed {
+                if !self.refreshing {
+                    self.refreshing = true;
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                self.refreshing = false;
+                self.refreshed = true;
+                self.refreshes += 1;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
     fn check_send_sync<T: Send + Sync>(value: T) -> T {
         value
     }
@@ -361,6 +421,35 @@ mod tests {
         assert!(adapter.next().await.is_none());
     }
 
+    #[tokio::test]
+    async fn message_stream_adapter_waits_for_signer() {
+        let stream = stream! {
+            yield Ok(TestMessage("one".into()));
+            yield Ok(TestMessage("two".into()));
+        };
+        let adapter = MessageStreamAdapter::<TestMessage, TestServiceError>::new(
+            Marshaller,
+            ErrorMarshaller,
+            RefreshingSigner::default(),
+            Box::pin(stream),
+        );
+
+        let sent: Vec<_> = adapter
+            .map(|bytes| read_message_from(&mut bytes.unwrap()).unwrap())
+            .collect()
+            .await;
+        let sent: Vec<_> = sent
+            .iter()
+            .map(|message| {
+                (
+                    std::str::from_utf8(message.payload()).unwrap(),
+                    message.headers()[0].value().as_int32().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(vec![("one", 1), ("two", 2), ("", 3)], sent);
+    }
+
     // Verify the developer experience for this compiles
     #[allow(unused)]
     fn event_stream_input_ergonomics() {
@@ -0,0 +1,59 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_json::deserialize::token::{expect_string_or_null, skip_value};
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::borrow::Cow;
+
+/// Builds a DynamoDB `Query` response with `count` items.
+fn query_response(count: usize) -> String {
+    let items = (0..count)
+        .map(|i| {
+            format!(
+                r#"{{"pk":{{"S":"customer#{i:08}"}},"sk":{{"S":"order#2024-01-01T00:00:00Z#{i}"}},"total":{{"N":"{i}.99"}},"note":{{"S":"line one\nline two"}}}}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(r#"{{"Count":{count},"Items":[{items}],"ScannedCount":{count}}}"#)
+}
+
+/// Reads every string attribute value with `read`, which decides whether data is copied.
+fn read_strings<'a, T>(input: &'a [u8], read: impl Fn(Cow<'a, str>) -> T) -> Vec<T> {
+    let mut values = Vec::new();
+    let mut tokens = json_token_iter(input).peekable();
+    while let Some(token) = tokens.next() {
+        match token.unwrap() {
+            Token::ObjectKey { key, .. } if key.as_escaped_str() == "S" => {
+                let value = expect_string_or_null(tokens.next()).unwrap().unwrap();
+                values.push(read(value.to_unescaped().unwrap()));
+            }
+            Token::ObjectKey { key, .. } if key.as_escaped_str() == "N" => {
+                skip_value(&mut tokens).unwrap();
+            }
+            _ => {}
+        }
+    }
+    values
+}
+
+pub fn query_benchmark(c: &mut Criterion) {
+    let input = query_response(1000);
+    let values = read_strings(input.as_bytes(), |value| value);
+    // Strings without escapes borrow from the response, while escaped strings are unescaped
+    assert!(matches!(values[0], Cow::Borrowed(_)));
+    assert!(matches!(values[2], Cow::Owned(_)));
+
+    c.bench_function("dynamodb_query_borrowed", |b| {
+        b.iter(|| black_box(read_strings(input.as_bytes(), |value| value)))
+    });
+    c.bench_function("dynamodb_query_owned", |b| {
+        b.iter(|| black_box(read_strings(input.as_bytes(), |value| value.into_owned())))
+    });
+}
+
+criterion_group!(benches, query_benchmark);
+criterion_main!(benches);
@@ -8,6 +8,7 @@ use aws_smithy_types::Number;
 use ErrorKind::*;
 
 pub mod error;
+pub mod stream;
 pub mod token;
 
 pub use token::{EscapeError, EscapedStr, Offset, Token};
@@ -318,33 +319,44 @@ impl<'a> JsonTokenIterator<'a> {
         debug_assert!(std::str::from_utf8(number_slice).is_ok());
         let number_str = unsafe { std::str::from_utf8_unchecked(number_slice) };
 
+        use std::num::IntErrorKind;
         use std::str::FromStr;
-        Ok(Token::ValueNumber {
+        // Numbers that don't fit in a `Number` are returned as their literal instead
+        let big_number = || Token::ValueBigNumber {
             offset,
-            value: if floating {
-                Number::Float(
-                    f64::from_str(number_str)
-                        .map_err(|_| self.error_at(start, InvalidNumber))
-                        .and_then(|f| {
-                            must_be_finite(f).map_err(|_| self.error_at(start, InvalidNumber))
-                        })?,
-                )
-            } else if negative {
+            literal: number_str,
+        };
+        let value = if floating {
+            let float =
+                f64::from_str(number_str).map_err(|_| self.error_at(start, InvalidNumber))?;
+            if must_be_finite(float).is_err() {
+                return Ok(big_number());
+            }
+            Number::Float(float)
+        } else {
+            let magnitude = if negative {
+                &number_str[1..]
+            } else {
+                number_str
+            };
+            let magnitude = match u64::from_str(magnitude) {
+                Ok(magnitude) => magnitude,
+                Err(err) if *err.kind() == IntErrorKind::PosOverflow => return Ok(big_number()),
+                Err(_) => return Err(self.error_at(start, InvalidNumber)),
+            };
+            if negative {
                 // If the negative value overflows, then stuff it into an f64
-                let positive = u64::from_str(&number_str[1..])
-                    .map_err(|_| self.error_at(start, InvalidNumber))?;
-                let negative = positive.wrapping_neg() as i64;
+                let negative = magnitude.wrapping_neg() as i64;
                 if negative > 0 {
-                    Number::Float(-(positive as f64))
+                    Number::Float(-(magnitude as f64))
                 } else {
                     Number::NegInt(negative)
                 }
             } else {
-                Number::PosInt(
-                    u64::from_str(number_str).map_err(|_| self.error_at(start, InvalidNumber))?,
-                )
-            },
-        })
+                Number::PosInt(magnitude)
+            }
+        };
+        Ok(Token::ValueNumber { offset, value })
     }
 
     /// Reads a value from the stream and returns the next token. For objects and arrays,
@@ -508,7 +520,7 @@ mod tests {
         end_array, end_object, object_key, start_array, start_object, value_bool, value_null,
         value_number, value_string,
     };
-    use crate::deserialize::{json_token_iter, EscapedStr, Token};
+    use crate::deserialize::{json_token_iter, EscapedStr, Offset, Token};
     use aws_smithy_types::Number;
     use proptest::prelude::*;
 
@@ -648,6 +660,41 @@ mod tests {
             Number::Float(-18446744073709551615.0),
             b"-18446744073709551615",
         );
+        expect(Number::PosInt(u64::MAX), b"18446744073709551615");
+    }
+
+    #[test]
+    fn big_numbers() {
+        // Numbers that a `Number` can't represent without loss are returned as their literal
+        for literal in [
+            "18446744073709551616",
+            "-18446744073709551616",
+            "123456789012345678901234567890",
+            "1e400",
+            "-1.5E+309",
+        ] {
+            let mut iter = json_token_iter(literal.as_bytes());
+            expect_token(
+                Some(Ok(Token::ValueBigNumber {
+                    offset: Offset(0),
+                    literal,
+                })),
+                iter.next(),
+            );
+            expect_token(None, iter.next());
+        }
+
+        let mut iter = json_token_iter(b"[18446744073709551616, 1]");
+        expect_token(start_array(0), iter.next());
+        expect_token(
+            Some(Ok(Token::ValueBigNumber {
+                offset: Offset(1),
+                literal: "18446744073709551616",
+            })),
+            iter.next(),
+        );
+        expect_token(value_number(23, Number::PosInt(1)), iter.next());
+        expect_token(end_array(24), iter.next());
     }
 
     // These cases actually shouldn't parse according to the spec, but it's easier
@@ -0,0 +1,303 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Incremental deserialization of JSON documents that are received in chunks.
+
+use crate::deserialize::error::DeserializeError as Error;
+use crate::escape::unescape_string;
+use std::collections::VecDeque;
+
+/// Splits the elements of an array member out of a JSON object as the object is received in
+/// chunks.
+///
+/// Some operations have outputs that are dominated by a single large list, such as query results.
+/// Rather than buffering the entire response and materializing every list member at once, the
+/// response body can be fed to a `JsonArrayStream` chunk by chunk with [`push`](Self::push), and
+/// each list member can be taken with [`next_element`](Self::next_element) as soon as it has been
+/// fully received. Each element is a complete JSON value that can be parsed with
+/// [`json_token_iter`](crate::deserialize::json_token_iter).
+///
+/// Once the entire body has been pushed and all elements have been taken,
+/// [`finish`](Self::finish) returns the rest of the document with the streamed array replaced by
+/// an empty array, so that the remaining members can be parsed as usual.
+///
+/// Only a member of the top-level object is streamed. The splitter only tracks enough of the JSON
+/// grammar to find element boundaries; the elements and the remainder are fully validated when
+/// they're parsed.
+///
+/// # Examples
+///
+/// ```
+/// use aws_smithy_json::deserialize::stream::JsonArrayStream;
+///
+/// let mut stream = JsonArrayStream::new("Items");
+/// let mut items = Vec::new();
+/// for chunk in [&br#"{"Count":2,"Items":[{"id":1},"#[..], br#"{"id":2}],"Next":null}"#] {
+///     stream.push(chunk);
+///     while let Some(item) = stream.next_element() {
+///         items.push(String::from_utf8(item).unwrap());
+///     }
+/// }
+/// assert_eq!(vec![r#"{"id":1}"#, r#"{"id":2}"#], items);
+/// assert_eq!(br#"{"Count":2,"Items":[],"Next":null}"#, &stream.finish().unwrap()[..]);
+/// ```
+#[derive(Debug)]
+pub struct JsonArrayStream {
+    member: String,
+    phase: Phase,
+    /// Container nesting depth of the document outside of the streamed array
+    depth: usize,
+    /// Container nesting depth within the element currently being received
+    element_depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// The last string seen at the top level of the root object, still escaped. It's a key if it's
+    /// followed by `:`.
+    last_string: Vec<u8>,
+    recording_string: bool,
+    /// Whether the next value is the value of the streamed member
+    awaiting_value: bool,
+    element: Vec<u8>,
+    elements: VecDeque<Vec<u8>>,
+    remainder: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Before,
+    Inside,
+    After,
+}
+
+impl JsonArrayStream {
+    /// Creates a stream that yields the elements of the array value of `member` in the
+    /// top-level JSON object.
+    pub fn new(member: impl Into<String>) -> Self {
+        Self {
+            member: member.into(),
+            phase: Phase::Before,
+            depth: 0,
+            element_depth: 0,
+            in_string: false,
+            escaped: false,
+            last_string: Vec::new(),
+            recording_string: false,
+            awaiting_value: false,
+            element: Vec::new(),
+            elements: VecDeque::new(),
+            remainder: Vec::new(),
+        }
+    }
+
+    /// Feeds the next chunk of the document into the stream.
+    pub fn push(&mut self, chunk: &[u8]) {
+        for &byte in chunk {
+            match self.phase {
+                Phase::Inside => self.push_element_byte(byte),
+                Phase::Before | Phase::After => self.push_document_byte(byte),
+            }
+        }
+    }
+
+    /// Takes the next complete element of the array, if one has been received.
+    pub fn next_element(&mut self) -> Option<Vec<u8>> {
+        self.elements.pop_front()
+    }
+
+    /// Returns `true` once the end of the streamed array has been received.
+    pub fn is_array_complete(&self) -> bool {
+        self.phase == Phase::After
+    }
+
+    /// Returns the document with the streamed array's elements removed.
+    ///
+    /// Elements that haven't been taken with [`next_element`](Self::next_element) are discarded.
+    /// An error is returned if the document is incomplete.
+    pub fn finish(self) -> Result<Vec<u8>, Error> {
+        if self.phase == Phase::Inside || self.depth != 0 || self.in_string {
+            return Err(Error::custom("JSON document ended before it was complete"));
+        }
+        Ok(self.remainder)
+    }
+
+    fn push_document_byte(&mut self, byte: u8) {
+        self.remainder.push(byte);
+        if self.in_string {
+            if self.advance_string(byte) && self.recording_string {
+                self.last_string.push(byte);
+            }
+            return;
+        }
+
+        let awaiting_value = std::mem::take(&mut self.awaiting_value);
+        match byte {
+            b' ' | b'\t' | b'\n' | b'\r' => self.awaiting_value = awaiting_value,
+            b'"' => {
+                self.in_string = true;
+                self.recording_string = self.depth == 1 && self.phase == Phase::Before;
+                self.last_string.clear();
+            }
+            b':' if self.depth == 1 => {
+                self.awaiting_value = self.phase == Phase::Before && self.last_string_is_member();
+            }
+            b'[' if awaiting_value => self.phase = Phase::Inside,
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    fn push_element_byte(&mut self, byte: u8) {
+        if self.in_string {
+            self.advance_string(byte);
+            self.element.push(byte);
+            return;
+        }
+
+        match byte {
+            b' ' | b'\t' | b'\n' | b'\r' if self.element_depth == 0 => {}
+            b',' if self.element_depth == 0 => self.finish_element(),
+            b']' if self.element_depth == 0 => {
+                self.finish_element();
+                self.remainder.push(byte);
+                self.phase = Phase::After;
+            }
+            _ => {
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'{' | b'[' => self.element_depth += 1,
+                    b'}' | b']' => self.element_depth = self.element_depth.saturating_sub(1),
+                    _ => {}
+                }
+                self.element.push(byte);
+            }
+        }
+    }
+
+    /// Advances the string state with a `byte` within a string. Returns `false` if the byte was
+    /// the closing quote.
+    fn advance_string(&mut self, byte: u8) -> bool {
+        if self.escaped {
+            self.escaped = false;
+        } else if byte == b'\\' {
+            self.escaped = true;
+        } else if byte == b'"' {
+            self.in_string = false;
+            return false;
+        }
+        true
+    }
+
+    /// Returns `true` if the last string is the streamed member's key once it's unescaped.
+    fn last_string_is_member(&self) -> bool {
+        std::str::from_utf8(&self.last_string)
+            .ok()
+            .and_then(|key| unescape_string(key).ok())
+            .is_some_and(|key| key == self.member)
+    }
+
+    fn finish_element(&mut self) {
+        if !self.element.is_empty() {
+            self.elements.push_back(std::mem::take(&mut self.element));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JsonArrayStream;
+    use crate::deserialize::json_token_iter;
+    use crate::deserialize::token::expect_document;
+
+    fn split(member: &str, input: &[u8], chunk_size: usize) -> (Vec<String>, String) {
+        let mut stream = JsonArrayStream::new(member);
+        let mut elements = Vec::new();
+        for chunk in input.chunks(chunk_size) {
+            stream.push(chunk);
+            while let Some(element) = stream.next_element() {
+                elements.push(String::from_utf8(element).unwrap());
+            }
+        }
+        (
+            elements,
+            String::from_utf8(stream.finish().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn splits_elements_regardless_of_chunk_size() {
+        let input = br#"{
+            "Count": 3,
+            "Items": [ {"a": {"S": "x]}"}, "b": [1, 2]}, "quote\"[", 12.5e3 ],
+            "LastEvaluatedKey": {"Items": [true]}
+        }"#;
+        for chunk_size in [1, 2, 7, input.len()] {
+            let (elements, remainder) = split("Items", input, chunk_size);
+            assert_eq!(
+                vec![
+                    r#"{"a": {"S": "x]}"}, "b": [1, 2]}"#,
+                    r#""quote\"[""#,
+                    "12.5e3"
+                ],
+                elements,
+                "chunk size {chunk_size}"
+            );
+            assert_eq!(
+                r#"{
+            "Count": 3,
+            "Items": [],
+            "LastEvaluatedKey": {"Items": [true]}
+        }"#,
+                remainder
+            );
+        }
+    }
+
+    #[test]
+    fn elements_and_remainder_are_valid_json() {
+        let input = br#"{"Items":[{"n":1},{"n":[2,3]}],"Next":"token"}"#;
+        let (elements, remainder) = split("Items", input, 3);
+        for json in elements.iter().chain(std::iter::once(&remainder)) {
+            expect_document(&mut json_token_iter(json.as_bytes()).peekable()).unwrap();
+        }
+    }
+
+    #[test]
+    fn member_must_be_a_top_level_key() {
+        let input = br#"{"Items":null,"Other":{"Items":[1,2]},"Value":"Items"}"#;
+        let (elements, remainder) = split("Items", input, 5);
+        assert!(elements.is_empty());
+        assert_eq!(std::str::from_utf8(input).unwrap(), remainder);
+    }
+
+    #[test]
+    fn escaped_member_key_matches() {
+        let input = br#"{"It\u0065ms":[1,2],"Next\"Items":[3]}"#;
+        let (elements, remainder) = split("Items", input, 4);
+        assert_eq!(vec!["1", "2"], elements);
+        assert_eq!(r#"{"It\u0065ms":[],"Next\"Items":[3]}"#, remainder);
+    }
+
+    #[test]
+    fn empty_array() {
+        let (elements, remainder) = split("Items", br#"{"Items":[ ]}"#, 1);
+        assert!(elements.is_empty());
+        assert_eq!(r#"{"Items":[]}"#, remainder);
+    }
+
+    #[test]
+    fn incomplete_document_is_an_error() {
+        let mut stream = JsonArrayStream::new("Items");
+        stream.push(br#"{"Items":[1,2"#);
+        assert_eq!(Some(b"1".to_vec()), stream.next_element());
+        assert!(!stream.is_array_complete());
+        assert!(stream.finish().is_err());
+
+        let mut stream = JsonArrayStream::new("Items");
+        stream.push(br#"{"Items":[1,2],"Other":{"#);
+        assert!(stream.is_array_complete());
+        assert!(stream.finish().is_err());
+    }
+}
@@ -8,8 +8,8 @@ use crate::deserialize::must_not_be_finite;
 use crate::escape::unescape_string;
 pub use crate::escape::EscapeError;
 use aws_smithy_types::date_time::Format;
-use aws_smithy_types::primitive::Parse;
-use aws_smithy_types::{base64, Blob, DateTime, Document, Number};
+use aws_smithy_types::primitive::{NonFiniteFloatHandling, Parse};
+use aws_smithy_types::{base64, BigNumber, Blob, DateTime, Document, Number};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::iter::Peekable;
@@ -78,6 +78,14 @@ pub enum Token<'a> {
         offset: Offset,
         value: Number,
     },
+    /// A number that can't be represented by [Number] without loss of precision: an integer
+    /// larger than `u64::MAX` or smaller than `-u64::MAX`, or a float too large for an `f64`.
+    ///
+    /// Use [expect_big_number_or_null] to read it.
+    ValueBigNumber {
+        offset: Offset,
+        literal: &'a str,
+    },
     ValueString {
         offset: Offset,
         value: EscapedStr<'a>,
@@ -96,6 +104,7 @@ impl Token<'_> {
             ValueBool { offset, .. } => offset,
             ValueNull { offset } => offset,
             ValueNumber { offset, .. } => offset,
+            ValueBigNumber { offset, .. } => offset,
             ValueString { offset, .. } => offset,
         }
     }
@@ -164,6 +173,7 @@ pub fn expect_number_or_null(
     match token.transpose()? {
         Some(Token::ValueNull { .. }) => Ok(None),
         Some(Token::ValueNumber { value, .. }) => Ok(Some(value)),
+        Some(Token::ValueBigNumber { offset, literal }) => Err(big_number_error(offset, literal)),
         Some(Token::ValueString { value, offset }) => match value.to_unescaped() {
             Err(err) => Err(Error::custom_source( "expected a valid string, escape was invalid", err).with_offset(offset.0)),
             Ok(v) => f64::parse_smithy_primitive(v.as_ref())
@@ -186,6 +196,81 @@ pub fn expect_number_or_null(
     }
 }
 
+/// Expects a [Token::ValueString], [Token::ValueNumber] or [Token::ValueNull] like
+/// [expect_number_or_null], and applies `handling` to non-finite floats.
+///
+/// Non-finite floats are encoded as the strings `"NaN"`, `"Infinity"`, and `"-Infinity"`.
+/// Number literals too large to be represented by an `f64`, e.g. `1e400`, are always rejected.
+pub fn expect_number_or_null_with(
+    token: Option<Result<Token<'_>, Error>>,
+    handling: NonFiniteFloatHandling,
+) -> Result<Option<Number>, Error> {
+    let offset = match &token {
+        Some(Ok(token)) => Some(token.offset().0),
+        _ => None,
+    };
+    match expect_number_or_null(token)? {
+        Some(Number::Float(value)) => handling
+            .apply_f64(value)
+            .map(|value| Some(Number::Float(value)))
+            .map_err(|err| {
+                let err = Error::custom_source("invalid float", err);
+                match offset {
+                    Some(offset) => err.with_offset(offset),
+                    None => err,
+                }
+            }),
+        other => Ok(other),
+    }
+}
+
+/// Expects a [Token::ValueNumber], [Token::ValueBigNumber], or [Token::ValueNull], and returns
+/// the number's exact literal as a [BigNumber] if it's not null.
+///
+/// Unlike [expect_number_or_null], this doesn't coerce the value into a `u64`, `i64`, or `f64`,
+/// so integers and decimals of any size and precision are preserved. The literal of a
+/// [Token::ValueNumber] is read from `input`, which must be the same buffer the token was
+/// produced from.
+pub fn expect_big_number_or_null(
+    token: Option<Result<Token<'_>, Error>>,
+    input: &[u8],
+) -> Result<Option<BigNumber>, Error> {
+    match token.transpose()? {
+        Some(Token::ValueNull { .. }) => Ok(None),
+        Some(Token::ValueNumber { offset, .. }) => {
+            let literal = input
+                .get(offset.0..)
+                .map(|rest| {
+                    let len = rest
+                        .iter()
+                        .take_while(|b| matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'))
+                        .count();
+                    &rest[..len]
+                })
+                .and_then(|literal| std::str::from_utf8(literal).ok())
+                .ok_or_else(|| offset.error("number offset is outside of the input".into()))?;
+            parse_big_number(offset, literal).map(Some)
+        }
+        Some(Token::ValueBigNumber { offset, literal }) => {
+            parse_big_number(offset, literal).map(Some)
+        }
+        _ => Err(Error::custom(
+            "expected ValueNumber, ValueBigNumber, or ValueNull",
+        )),
+    }
+}
+
+fn parse_big_number(offset: Offset, literal: &str) -> Result<BigNumber, Error> {
+    literal.parse().map_err(|err| {
+        Error::custom_source("expected a valid number literal", err).with_offset(offset.0)
+    })
+}
+
+fn big_number_error(offset: Offset, literal: &str) -> Error {
+    offset
+        .error(format!("number `{literal}` can't be represented without loss of precision").into())
+}
+
 /// Expects a [Token::ValueString] or [Token::ValueNull]. If the value is a string, it interprets it as a base64 encoded [Blob] value.
 pub fn expect_blob_or_null(token: Option<Result<Token<'_>, Error>>) -> Result<Option<Blob>, Error> {
     Ok(match expect_string_or_null(token)? {
@@ -249,6 +334,7 @@ where
         Some(Token::ValueNull { .. }) => Ok(Document::Null),
         Some(Token::ValueBool { value, .. }) => Ok(Document::Bool(value)),
         Some(Token::ValueNumber { value, .. }) => Ok(Document::Number(value)),
+        Some(Token::ValueBigNumber { offset, literal }) => Err(big_number_error(offset, literal)),
         Some(Token::ValueString { value, .. }) => {
             Ok(Document::String(value.to_unescaped()?.into_owned()))
         }
@@ -288,6 +374,84 @@ where
     }
 }
 
+/// Expects and parses a complete document value like [expect_document], but skips the value and
+/// returns `None` if it can't be represented as a [Document].
+///
+/// A value can't be represented when it contains a number that doesn't fit in a [Number] (see
+/// [Token::ValueBigNumber]) or when it's nested too deeply. The whole value is consumed either
+/// way, so parsing can continue after it.
+pub fn expect_document_or_skip<'a, I>(tokens: &mut Peekable<I>) -> Result<Option<Document>, Error>
+where
+    I: Iterator<Item = Result<Token<'a>, Error>>,
+{
+    expect_document_or_skip_inner(tokens, 0)
+}
+
+fn expect_document_or_skip_inner<'a, I>(
+    tokens: &mut Peekable<I>,
+    depth: usize,
+) -> Result<Option<Document>, Error>
+where
+    I: Iterator<Item = Result<Token<'a>, Error>>,
+{
+    if depth >= MAX_DOCUMENT_RECURSION {
+        skip_value(tokens)?;
+        return Ok(None);
+    }
+    match tokens.next().transpose()? {
+        Some(Token::ValueNull { .. }) => Ok(Some(Document::Null)),
+        Some(Token::ValueBool { value, .. }) => Ok(Some(Document::Bool(value))),
+        Some(Token::ValueNumber { value, .. }) => Ok(Some(Document::Number(value))),
+        Some(Token::ValueBigNumber { .. }) => Ok(None),
+        Some(Token::ValueString { value, .. }) => {
+            Ok(Some(Document::String(value.to_unescaped()?.into_owned())))
+        }
+        Some(Token::StartObject { .. }) => {
+            // Keep consuming members after one fails to convert so that the whole object is skipped
+            let mut object = Some(HashMap::new());
+            loop {
+                match tokens.next().transpose()? {
+                    Some(Token::EndObject { .. }) => break,
+                    Some(Token::ObjectKey { key, .. }) => {
+                        let key = key.to_unescaped()?.into_owned();
+                        let value = expect_document_or_skip_inner(tokens, depth + 1)?;
+                        object = object.zip(value).map(|(mut object, value)| {
+                            object.insert(key, value);
+                            object
+                        });
+                    }
+                    _ => return Err(Error::custom("expected object key or end object")),
+                }
+            }
+            Ok(object.map(Document::Object))
+        }
+        Some(Token::StartArray { .. }) => {
+            let mut array = Some(Vec::new());
+            loop {
+                match tokens.peek() {
+                    Some(Ok(Token::EndArray { .. })) => {
+                        tokens.next().transpose().unwrap();
+                        break;
+                    }
+                    _ => {
+                        let value = expect_document_or_skip_inner(tokens, depth + 1)?;
+                        array = array.zip(value).map(|(mut array, value)| {
+                            array.push(value);
+                            array
+                        });
+                    }
+                }
+            }
+            Ok(array.map(Document::Array))
+        }
+        Some(Token::EndObject { .. }) | Some(Token::ObjectKey { .. }) => {
+            unreachable!("end object and object key are handled in start object")
+        }
+        Some(Token::EndArray { .. }) => unreachable!("end array is handled in start array"),
+        None => Err(Error::custom("expected value")),
+    }
+}
+
 /// Skips an entire value in the token stream. Errors if it isn't a value.
 pub fn skip_value<'a>(
     tokens: &mut impl Iterator<Item = Result<Token<'a>, Error>>,
@@ -322,6 +486,7 @@ fn skip_inner<'a>(
             Some(Token::ValueNull { .. })
             | Some(Token::ValueBool { .. })
             | Some(Token::ValueNumber { .. })
+            | Some(Token::ValueBigNumber { .. })
             | Some(Token::ValueString { .. }) => {
                 if depth == 0 {
                     break;
@@ -453,6 +618,41 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn non_finite_float_handling() {
+        use aws_smithy_types::primitive::NonFiniteFloatHandling::{Clamp, Encode, Error};
+
+        let parse = |input: &'static str, handling| {
+            expect_number_or_null_with(json_token_iter(input.as_bytes()).next(), handling)
+        };
+        for handling in [Encode, Error, Clamp] {
+            assert_eq!(Some(Number::Float(1.5)), parse("1.5", handling).unwrap());
+            assert_eq!(Some(Number::PosInt(5)), parse("5", handling).unwrap());
+            assert_eq!(None, parse("null", handling).unwrap());
+        }
+
+        assert_eq!(
+            Some(Number::Float(f64::NEG_INFINITY)),
+            parse(r#""-Infinity""#, Encode).unwrap()
+        );
+        assert!(matches!(parse(r#""NaN""#, Encode), Ok(Some(Number::Float(v))) if v.is_nan()));
+
+        for input in [r#""NaN""#, r#""Infinity""#, r#""-Infinity""#] {
+            let err = parse(input, Error).expect_err(input);
+            assert_eq!(Some(0), err.offset, "{input}");
+        }
+
+        assert_eq!(
+            Some(Number::Float(f64::MAX)),
+            parse(r#""Infinity""#, Clamp).unwrap()
+        );
+        assert_eq!(
+            Some(Number::Float(f64::MIN)),
+            parse(r#""-Infinity""#, Clamp).unwrap()
+        );
+        assert!(parse(r#""NaN""#, Clamp).is_err());
+    }
+
     #[test]
     fn test_non_finite_floats() {
         let mut tokens = json_token_iter(b"inf");
@@ -569,6 +769,70 @@ pub mod test {
                 panic!("expected nan, found: {not_ok:?}")
             }
         }
+        // Numbers that would lose precision are rejected rather than coerced into a float
+        expect_err_custom(
+            "number `18446744073709551616` can't be represented without loss of precision",
+            Some(0),
+            expect_number_or_null(json_token_iter(b"18446744073709551616").next()),
+        );
+        expect_err_custom(
+            "number `1e400` can't be represented without loss of precision",
+            Some(0),
+            expect_number_or_null(json_token_iter(b"1e400").next()),
+        );
+    }
+
+    #[test]
+    fn test_expect_big_number_or_null() {
+        let input =
+            br#"[null, 123456789012345678901234567890, -0.100000000000000000000001e-5, "1"]"#;
+        let mut tokens = json_token_iter(input);
+        expect_start_array(tokens.next()).unwrap();
+        assert_eq!(
+            None,
+            expect_big_number_or_null(tokens.next(), input).unwrap()
+        );
+        assert_eq!(
+            "123456789012345678901234567890",
+            expect_big_number_or_null(tokens.next(), input)
+                .unwrap()
+                .unwrap()
+                .as_str()
+        );
+        assert_eq!(
+            "-0.100000000000000000000001e-5",
+            expect_big_number_or_null(tokens.next(), input)
+                .unwrap()
+                .unwrap()
+                .as_str()
+        );
+        expect_err_custom(
+            "expected ValueNumber, ValueBigNumber, or ValueNull",
+            None,
+            expect_big_number_or_null(tokens.next(), input),
+        );
+
+        // Numbers that don't fit in a `Number` are read without loss
+        let input = br#"[-18446744073709551616, 1e400, 18446744073709551616]"#;
+        let mut tokens = json_token_iter(input);
+        expect_start_array(tokens.next()).unwrap();
+        for expected in ["-18446744073709551616", "1e400", "18446744073709551616"] {
+            assert_eq!(
+                expected,
+                expect_big_number_or_null(tokens.next(), input)
+                    .unwrap()
+                    .unwrap()
+                    .as_str()
+            );
+        }
+
+        // The tokenizer leniently accepts some literals that aren't valid numbers
+        let input = b"2.";
+        expect_err_custom(
+            "expected a valid number literal",
+            Some(0),
+            expect_big_number_or_null(json_token_iter(input).next(), input),
+        );
     }
 
     #[test]
@@ -719,4 +983,48 @@ pub mod test {
             expect_document(&mut json_token_iter(value.as_bytes()).peekable()),
         );
     }
+
+    #[test]
+    fn test_expect_document_or_skip() {
+        let mut tokens = json_token_iter(br#"{"a": [1, {"b": null}], "c": "d"}"#).peekable();
+        assert_eq!(
+            Some(Document::Object(
+                vec![
+                    (
+                        "a".to_string(),
+                        Document::Array(vec![
+                            Document::Number(Number::PosInt(1)),
+                            Document::Object(
+                                vec![("b".to_string(), Document::Null)]
+                                    .into_iter()
+                                    .collect()
+                            ),
+                        ])
+                    ),
+                    ("c".to_string(), Document::String("d".to_string())),
+                ]
+                .into_iter()
+                .collect()
+            )),
+            expect_document_or_skip(&mut tokens).unwrap()
+        );
+        assert!(tokens.next().is_none());
+
+        // Values that can't be a `Document` are skipped entirely, leaving the tokens after them
+        let mut nested = String::new();
+        nested.extend(std::iter::repeat_n('[', 300));
+        nested.extend(std::iter::repeat_n(']', 300));
+        for value in [
+            "18446744073709551616",
+            r#"{"a": [1, 1e400, {"b": 2}], "c": 3}"#,
+            nested.as_str(),
+        ] {
+            let input = format!("[{value}, true]");
+            let mut tokens = json_token_iter(input.as_bytes()).peekable();
+            expect_start_array(tokens.next()).unwrap();
+            assert_eq!(None, expect_document_or_skip(&mut tokens).unwrap());
+            assert_eq!(Some(true), expect_bool_or_null(tokens.next()).unwrap());
+            assert!(matches!(tokens.next(), Some(Ok(Token::EndArray { .. }))));
+        }
+    }
 }
@@ -5,8 +5,8 @@
 
 use crate::escape::escape_string;
 use aws_smithy_types::date_time::{DateTimeFormatError, Format};
-use aws_smithy_types::primitive::Encoder;
-use aws_smithy_types::{DateTime, Document, Number};
+use aws_smithy_types::primitive::{Encoder, NonFiniteFloatError, NonFiniteFloatHandling};
+use aws_smithy_types::{BigNumber, DateTime, Document, Number};
 use std::borrow::Cow;
 
 pub struct JsonValueWriter<'a> {
@@ -94,6 +94,25 @@ impl<'a> JsonValueWriter<'a> {
         }
     }
 
+    /// Writes a number `value`, applying `handling` to non-finite floats.
+    pub fn number_with(
+        self,
+        value: Number,
+        handling: NonFiniteFloatHandling,
+    ) -> Result<(), NonFiniteFloatError> {
+        let value = match value {
+            Number::Float(value) => Number::Float(handling.apply_f64(value)?),
+            other => other,
+        };
+        self.number(value);
+        Ok(())
+    }
+
+    /// Writes an arbitrary-precision number `value` using its exact literal.
+    pub fn big_number(self, value: &BigNumber) {
+        self.output.push_str(value.as_str());
+    }
+
     /// Writes a date-time `value` with the given `format`.
     pub fn date_time(
         self,
@@ -255,6 +274,23 @@ mod tests {
         assert_eq!("[[5],[]]", &output);
     }
 
+    #[test]
+    fn big_number() {
+        let mut output = String::new();
+        let mut array = JsonArrayWriter::new(&mut output);
+        array
+            .value()
+            .big_number(&"123456789012345678901234567890".parse().unwrap());
+        array
+            .value()
+            .big_number(&"-1.000000000000000000001e-400".parse().unwrap());
+        array.finish();
+        assert_eq!(
+            "[123456789012345678901234567890,-1.000000000000000000001e-400]",
+            &output
+        );
+    }
+
     #[test]
     fn object() {
         let mut output = String::new();
@@ -426,6 +462,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn non_finite_float_handling() {
+        use aws_smithy_types::primitive::NonFiniteFloatHandling::{self, Clamp, Encode, Error};
+
+        let format = |number, handling: NonFiniteFloatHandling| {
+            let mut formatted = String::new();
+            JsonValueWriter::new(&mut formatted)
+                .number_with(number, handling)
+                .map(|_| formatted)
+        };
+        for handling in [Encode, Error, Clamp] {
+            assert_eq!("-1.2", format(Number::Float(-1.2), handling).unwrap());
+            assert_eq!("1", format(Number::PosInt(1), handling).unwrap());
+        }
+
+        assert_eq!("\"NaN\"", format(Number::Float(f64::NAN), Encode).unwrap());
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(format(Number::Float(value), Error).is_err());
+        }
+        assert_eq!(
+            "-1.7976931348623157e308",
+            format(Number::Float(f64::NEG_INFINITY), Clamp).unwrap()
+        );
+        assert!(format(Number::Float(f64::NAN), Clamp).is_err());
+    }
+
     proptest! {
         #[test]
         fn matches_serde_json_pos_int_format(value: u64) {
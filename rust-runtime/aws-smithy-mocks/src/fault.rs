@@ -0,0 +1,115 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A connection-level failure that a rule can simulate instead of returning a response.
+///
+/// The failure is returned by the mock HTTP client as a [`ConnectorError`], so the client's retry
+/// strategy classifies and retries it the same way it would a real connection failure.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionFailure {
+    /// The connection times out before a response is received.
+    Timeout,
+    /// The connection is reset before a response is received.
+    Reset,
+}
+
+impl ConnectionFailure {
+    pub(crate) fn into_connector_error(self) -> ConnectorError {
+        match self {
+            ConnectionFailure::Timeout => {
+                ConnectorError::timeout("mock connection timed out".into())
+            }
+            ConnectionFailure::Reset => {
+                ConnectorError::io(io::Error::from(io::ErrorKind::ConnectionReset).into())
+            }
+        }
+    }
+}
+
+/// Truncates the body of `response` to its first `len` bytes.
+///
+/// Reading the truncated body yields those bytes, and then fails as if the connection was closed
+/// before the rest of the body was received. The `Content-Length` header is set to the length of
+/// the full body, unless the response already has one.
+///
+/// # Panics
+///
+/// Panics if the body of `response` is streaming.
+pub(crate) fn truncate_body(mut response: HttpResponse, len: usize) -> HttpResponse {
+    let body = response
+        .body()
+        .bytes()
+        .expect("only in-memory response bodies can be truncated");
+    let received = Bytes::copy_from_slice(&body[..len.min(body.len())]);
+    if !response.headers().contains_key("content-length") {
+        let content_length = body.len().to_string();
+        response
+            .headers_mut()
+            .insert("content-length", content_length);
+    }
+    *response.body_mut() = SdkBody::from_body_1_x(TruncatedBody {
+        received: Some(received),
+    });
+    response
+}
+
+/// A response body that ends with an error after the bytes that were received.
+struct TruncatedBody {
+    received: Option<Bytes>,
+}
+
+impl http_body_1x::Body for TruncatedBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body_1x::Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(Some(match self.received.take() {
+            Some(received) if !received.is_empty() => Ok(http_body_1x::Frame::data(received)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed before the response body was complete",
+            )),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_body;
+    use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
+
+    #[tokio::test]
+    async fn truncated_body_fails_after_received_bytes() {
+        let response = HttpResponse::new(200.try_into().unwrap(), SdkBody::from("hello world"));
+        let mut response = truncate_body(response, 5);
+        assert_eq!(Some("11"), response.headers().get("content-length"));
+
+        let mut body = ByteStream::new(response.take_body());
+        assert_eq!(b"hello", &body.next().await.unwrap().unwrap()[..]);
+        let err = body.next().await.unwrap().expect_err("body is truncated");
+        assert!(
+            format!(
+                "{}",
+                aws_smithy_types::error::display::DisplayErrorContext(&err)
+            )
+            .contains("connection closed before the response body was complete"),
+            "{err}"
+        );
+    }
+}
@@ -3,26 +3,34 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::fault::ConnectionFailure;
+use crate::rule::{CapturedInput, MockAttempt, MockOutcome};
 use crate::{MockResponse, Rule, RuleMode};
-use aws_smithy_http_client::test_util::infallible_client_fn;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
 use aws_smithy_runtime_api::box_error::BoxError;
-use aws_smithy_runtime_api::client::http::SharedHttpClient;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
+    SharedHttpConnector,
+};
 use aws_smithy_runtime_api::client::interceptors::context::{
-    BeforeSerializationInterceptorContextMut, BeforeTransmitInterceptorContextMut, Error,
-    FinalizerInterceptorContextMut, Input, Output,
+    BeforeSerializationInterceptorContextMut, BeforeTransmitInterceptorContextMut,
+    FinalizerInterceptorContextMut, Input,
 };
 use aws_smithy_runtime_api::client::interceptors::Intercept;
-use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::ConnectorError;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::body::SdkBody;
 use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 use std::collections::VecDeque;
 use std::fmt;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 // Store active rule in config bag
 #[derive(Debug, Clone)]
-struct ActiveRule(Rule);
+struct ActiveRule(Rule, Option<CapturedInput>);
 
 impl Storable for ActiveRule {
     type Storer = StoreReplace<ActiveRule>;
@@ -33,7 +41,7 @@ pub struct MockResponseInterceptor {
     rules: Arc<Mutex<VecDeque<Rule>>>,
     rule_mode: RuleMode,
     must_match: bool,
-    active_response: Arc<Mutex<Option<MockResponse<Output, Error>>>>,
+    active_response: Arc<Mutex<Option<MockAttempt>>>,
 }
 
 impl fmt::Debug for MockResponseInterceptor {
@@ -103,6 +111,7 @@ impl Intercept for MockResponseInterceptor {
         // Find a matching rule and get its response
         let mut matching_rule = None;
         let mut matching_response = None;
+        let mut captured_input = None;
 
         match self.rule_mode {
             RuleMode::Sequential => {
@@ -123,13 +132,21 @@ impl Intercept for MockResponseInterceptor {
                     }
 
                     // Check if the rule matches
-                    if !(rule.matcher)(input) {
+                    if !rule.matches(input) {
                         // Rule doesn't match, this is an error in sequential mode
-                        panic!("In order matching was enforced but rule did not match {input:?}");
+                        panic!(
+                            "In order matching was enforced but rule did not match {input:?}\n{}",
+                            rule.mismatches(input)
+                                .iter()
+                                .map(|mismatch| format!("  - {mismatch}"))
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        );
                     }
 
                     // Rule matches and is not exhausted, get the response
-                    if let Some(response) = rule.next_response(input) {
+                    captured_input = rule.capture_input(input);
+                    if let Some(response) = rule.next_response(input, captured_input.clone()) {
                         matching_rule = Some(rule.clone());
                         matching_response = Some(response);
                     } else {
@@ -150,8 +167,9 @@ impl Intercept for MockResponseInterceptor {
                         continue;
                     }
 
-                    if (rule.matcher)(input) {
-                        if let Some(response) = rule.next_response(input) {
+                    if rule.matches(input) {
+                        captured_input = rule.capture_input(input);
+                        if let Some(response) = rule.next_response(input, captured_input.clone()) {
                             matching_rule = Some(rule.clone());
                             matching_response = Some(response);
                             break;
@@ -164,7 +182,8 @@ impl Intercept for MockResponseInterceptor {
         match (matching_rule, matching_response) {
             (Some(rule), Some(response)) => {
                 // Store the rule in the config bag
-                cfg.interceptor_state().store_put(ActiveRule(rule));
+                cfg.interceptor_state()
+                    .store_put(ActiveRule(rule, captured_input));
                 // store the response on the interceptor (because going
                 // through interceptor context requires the type to impl Clone)
                 let mut active_resp = self.active_response.lock().unwrap();
@@ -174,7 +193,8 @@ impl Intercept for MockResponseInterceptor {
                 // No matching rule or no response
                 if self.must_match {
                     panic!(
-                        "must_match was enabled but no rules matched or all rules were exhausted for {input:?}"
+                        "must_match was enabled but no rules matched or all rules were exhausted for {input:?}\n{}",
+                        unmatched_request_report(&rules, input)
                     );
                 }
             }
@@ -197,22 +217,33 @@ impl Intercept for MockResponseInterceptor {
                 // During retries, input is not available in modify_before_transmit.
                 // For HTTP status responses that don't use the input, we can use a dummy input.
                 let dummy_input = Input::doesnt_matter();
-                let next_resp = active_rule.0.next_response(&dummy_input);
+                // The calls of retries are recorded with the input captured for the first attempt.
+                let next_resp = active_rule
+                    .0
+                    .next_response(&dummy_input, active_rule.1.clone());
                 active_response = next_resp;
             }
         }
 
-        if let Some(resp) = active_response {
-            match resp {
+        if let Some(attempt) = active_response {
+            let request = context.request_mut();
+            // the HTTP client waits out the delay before it responds or fails
+            request.add_extension(MockDelay(attempt.delay));
+            match attempt.outcome {
                 // place the http response into the extensions and let the HTTP client return it
-                MockResponse::Http(http_resp) => {
-                    context
-                        .request_mut()
-                        .add_extension(MockHttpResponse(Arc::new(http_resp)));
+                MockOutcome::Response(MockResponse::Http(http_resp)) => {
+                    request.add_extension(MockHttpResponse(Arc::new(http_resp)));
+                }
+                // let the HTTP client fail to connect
+                MockOutcome::ConnectionFailure(failure) => {
+                    request.add_extension(MockConnectionFailure(failure));
                 }
-                _ => {
+                outcome => {
                     // put it back for modeled output/errors
-                    let _ = (*state).replace(resp);
+                    let _ = (*state).replace(MockAttempt {
+                        outcome,
+                        delay: attempt.delay,
+                    });
                 }
             }
         }
@@ -229,7 +260,7 @@ impl Intercept for MockResponseInterceptor {
         // Handle modeled responses
         let mut state = self.active_response.lock().unwrap();
         let active_response = (*state).take();
-        if let Some(resp) = active_response {
+        if let Some(MockOutcome::Response(resp)) = active_response.map(|attempt| attempt.outcome) {
             match resp {
                 MockResponse::Output(output) => {
                     context.inner_mut().set_output_or_error(Ok(output));
@@ -249,33 +280,109 @@ impl Intercept for MockResponseInterceptor {
     }
 }
 
+/// Describes why each of `rules` didn't match `input`.
+fn unmatched_request_report(rules: &VecDeque<Rule>, input: &Input) -> String {
+    if rules.is_empty() {
+        return "no rules remain".into();
+    }
+    let mut report = String::from("unmatched request report:");
+    for (idx, rule) in rules.iter().enumerate() {
+        report.push_str(&format!("\n  rule {idx}:"));
+        if rule.is_exhausted() {
+            report.push_str(" exhausted");
+            continue;
+        }
+        for mismatch in rule.mismatches(input) {
+            report.push_str(&format!("\n    - {mismatch}"));
+        }
+    }
+    report
+}
+
 /// Extension for storing mock HTTP responses in request extensions
 #[derive(Clone)]
 struct MockHttpResponse(Arc<HttpResponse>);
 
-/// Create a mock HTTP client that works with the interceptor using existing utilities
+/// Extension for storing how long the mock HTTP client waits before it responds
+#[derive(Clone, Copy)]
+struct MockDelay(Duration);
+
+/// Extension for storing a connection failure that the mock HTTP client returns
+#[derive(Clone, Copy)]
+struct MockConnectionFailure(ConnectionFailure);
+
+/// Create a mock HTTP client that works with the interceptor
+///
+/// The client returns the HTTP responses and connection failures of the matched rules. Response
+/// delays are waited out with the sleep implementation of the client that the mock HTTP client is
+/// used with.
 pub fn create_mock_http_client() -> SharedHttpClient {
-    infallible_client_fn(|mut req| {
-        // Try to get the mock HTTP response generator from the extensions
-        if let Some(mock_response) = req.extensions_mut().remove::<MockHttpResponse>() {
-            let http_resp =
-                Arc::try_unwrap(mock_response.0).expect("mock HTTP response has single reference");
-            return http_resp.try_into_http1x().unwrap();
+    MockHttpClient.into_shared()
+}
+
+#[derive(Debug)]
+struct MockHttpClient;
+
+impl HttpClient for MockHttpClient {
+    fn http_connector(
+        &self,
+        _settings: &HttpConnectorSettings,
+        components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        MockHttpConnector {
+            sleep_impl: components.sleep_impl(),
         }
+        .into_shared()
+    }
+}
+
+#[derive(Debug)]
+struct MockHttpConnector {
+    sleep_impl: Option<SharedAsyncSleep>,
+}
+
+impl HttpConnector for MockHttpConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let mut request = request.try_into_http1x().unwrap();
+        let extensions = request.extensions_mut();
+        let delay = extensions.remove::<MockDelay>();
+        let failure = extensions.remove::<MockConnectionFailure>();
+        let mock_response = extensions.remove::<MockHttpResponse>();
+        let sleep_impl = self.sleep_impl.clone();
+        HttpConnectorFuture::new(async move {
+            if let Some(MockDelay(delay)) = delay.filter(|delay| !delay.0.is_zero()) {
+                let sleep_impl = sleep_impl.ok_or_else(|| {
+                    ConnectorError::other(
+                        "a sleep implementation is required to delay mock responses".into(),
+                        None,
+                    )
+                })?;
+                sleep_impl.sleep(delay).await;
+            }
+            if let Some(MockConnectionFailure(failure)) = failure {
+                return Err(failure.into_connector_error());
+            }
+            if let Some(mock_response) = mock_response {
+                return Ok(Arc::try_unwrap(mock_response.0)
+                    .expect("mock HTTP response has single reference"));
+            }
 
-        // Default dummy response if no mock response is defined
-        http::Response::builder()
-            .status(418)
-            .body(SdkBody::from("Mock HTTP client dummy response"))
-            .unwrap()
-    })
+            // Default dummy response if no mock response is defined
+            Ok(HttpResponse::new(
+                418.try_into().unwrap(),
+                SdkBody::from("Mock HTTP client dummy response"),
+            ))
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
     use aws_smithy_runtime::client::orchestrator::operation::Operation;
-    use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+    use aws_smithy_runtime::client::retries::classifiers::{
+        HttpStatusCodeClassifier, TransientErrorClassifier,
+    };
     use aws_smithy_runtime_api::client::orchestrator::{
         HttpRequest, HttpResponse, OrchestratorError,
     };
@@ -285,11 +392,15 @@ mod tests {
     use aws_smithy_types::retry::RetryConfig;
     use aws_smithy_types::timeout::TimeoutConfig;
 
-    use crate::{create_mock_http_client, MockResponseInterceptor, RuleBuilder, RuleMode};
+    use crate::matchers::{any, eq, regex};
+    use crate::{
+        create_mock_http_client, verify_call_order, ConnectionFailure, MockResponseInterceptor,
+        RuleBuilder, RuleMode,
+    };
     use std::time::Duration;
 
     // Simple test input and output types
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq)]
     struct TestInput {
         bucket: String,
         key: String,
@@ -396,6 +507,7 @@ mod tests {
 
             builder
                 .retry_classifier(HttpStatusCodeClassifier::default())
+                .retry_classifier(TransientErrorClassifier::<TestError>::new())
                 .standard_retry(&retry_config)
                 .build()
         } else {
@@ -941,4 +1053,315 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(rule.num_calls(), 2);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_delayed_responses() {
+        let rule = create_rule_builder()
+            .delay(Duration::from_secs(5))
+            .sequence()
+            .output(|| TestOutput::new("rule delay"))
+            .output(|| TestOutput::new("step delay"))
+            .delay(Duration::from_secs(30))
+            .build();
+
+        let interceptor = MockResponseInterceptor::new()
+            .rule_mode(RuleMode::Sequential)
+            .with_rule(&rule);
+        let operation = create_test_operation(interceptor, false);
+
+        let start = tokio::time::Instant::now();
+        let result = operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await;
+        assert_eq!(result.unwrap(), TestOutput::new("rule delay"));
+        assert_eq!(Duration::from_secs(5), start.elapsed());
+
+        let start = tokio::time::Instant::now();
+        let result = operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await;
+        assert_eq!(result.unwrap(), TestOutput::new("step delay"));
+        assert_eq!(Duration::from_secs(30), start.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_connection_failures_are_retried() {
+        let rule = create_rule_builder()
+            .sequence()
+            .connection_failure(ConnectionFailure::Timeout)
+            .connection_failure(ConnectionFailure::Reset)
+            .output(|| TestOutput::new("success after connection failures"))
+            .build();
+
+        let interceptor = MockResponseInterceptor::new()
+            .rule_mode(RuleMode::Sequential)
+            .with_rule(&rule);
+        let operation = create_test_operation(interceptor, true);
+
+        let result = operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await;
+        assert_eq!(
+            result.unwrap(),
+            TestOutput::new("success after connection failures")
+        );
+        assert_eq!(rule.num_calls(), 3);
+        assert!(rule.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_connection_failure_without_retries() {
+        let rule = create_rule_builder().then_connection_failure(ConnectionFailure::Timeout);
+
+        let interceptor = MockResponseInterceptor::new().with_rule(&rule);
+        let operation = create_test_operation(interceptor, false);
+
+        let err = operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await
+            .expect_err("connection failed");
+        match err {
+            SdkError::DispatchFailure(failure) => assert!(failure.is_timeout()),
+            err => panic!("expected a dispatch failure, got: {err:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_truncated_body() {
+        let rule = create_rule_builder()
+            .sequence()
+            .http_response(|| {
+                HttpResponse::new(
+                    StatusCode::try_from(200).unwrap(),
+                    SdkBody::from("complete response"),
+                )
+            })
+            .truncate_body(8)
+            .build();
+
+        let interceptor = MockResponseInterceptor::new().with_rule(&rule);
+        let operation = create_test_operation(interceptor, false);
+
+        let err = operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await
+            .expect_err("body was truncated");
+        assert!(
+            matches!(err, SdkError::ResponseError(_)),
+            "expected a response error, got: {err:?}"
+        );
+    }
+
+    #[should_panic(expected = "truncate_body() must follow an HTTP response in the sequence")]
+    #[test]
+    fn test_truncate_body_validation() {
+        let _rule = create_rule_builder()
+            .sequence()
+            .output(|| TestOutput::new("response"))
+            .truncate_body(1)
+            .build();
+    }
+
+    #[tokio::test]
+    async fn test_verify_call_counts_and_inputs() {
+        let rule = create_rule_builder()
+            .capture_inputs()
+            .sequence()
+            .http_status(503, None)
+            .times(2)
+            .output(|| TestOutput::new("success after retries"))
+            .build();
+        let unused_rule = create_rule_builder()
+            .match_requests(|input| input.key == "unused-key")
+            .then_output(|| TestOutput::new("unused"));
+
+        let interceptor = MockResponseInterceptor::new()
+            .with_rule(&rule)
+            .with_rule(&unused_rule);
+        let operation = create_test_operation(interceptor, true);
+
+        operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await
+            .expect("success after retries");
+
+        rule.verify().times(3).at_least(1).at_most(3);
+        unused_rule.verify().never();
+
+        // retried attempts are recorded with the input of the operation
+        assert_eq!(
+            vec![TestInput::new("test-bucket", "test-key"); 3],
+            rule.inputs::<TestInput>()
+        );
+        assert!(unused_rule.inputs::<TestInput>().is_empty());
+        // inputs of another type aren't returned
+        assert!(rule.inputs::<String>().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inputs_are_not_captured_by_default() {
+        // rules can be built for inputs that can't be cloned, like streaming inputs
+        #[derive(Debug)]
+        struct UncloneableInput;
+        let _ = RuleBuilder::<UncloneableInput, TestOutput, TestError>::new()
+            .then_output(|| TestOutput::new("response"));
+
+        let rule = create_rule_builder().then_output(|| TestOutput::new("response"));
+        let interceptor = MockResponseInterceptor::new().with_rule(&rule);
+        let operation = create_test_operation(interceptor, false);
+
+        operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await
+            .expect("success");
+
+        rule.verify().times(1);
+        assert!(rule.inputs::<TestInput>().is_empty());
+    }
+
+    #[should_panic(expected = "expected the rule to be called 2 times, but it was called 1 times")]
+    #[tokio::test]
+    async fn test_verify_times_mismatch() {
+        let rule = create_rule_builder().then_output(|| TestOutput::new("response"));
+        let interceptor = MockResponseInterceptor::new().with_rule(&rule);
+        let operation = create_test_operation(interceptor, false);
+
+        operation
+            .invoke(TestInput::new("test-bucket", "test-key"))
+            .await
+            .unwrap();
+        rule.verify().times(2);
+    }
+
+    #[tokio::test]
+    async fn test_verify_call_order() {
+        let first = create_rule_builder()
+            .match_requests(|input| input.key == "first")
+            .then_output(|| TestOutput::new("first"));
+        let second = create_rule_builder()
+            .match_requests(|input| input.key == "second")
+            .then_output(|| TestOutput::new("second"));
+
+        let interceptor = MockResponseInterceptor::new()
+            .with_rule(&first)
+            .with_rule(&second);
+        let operation = create_test_operation(interceptor, false);
+
+        for key in ["first", "second", "second", "first"] {
+            operation
+                .invoke(TestInput::new("test-bucket", key))
+                .await
+                .unwrap();
+        }
+
+        verify_call_order(&[&first, &second, &second, &first]);
+    }
+
+    #[should_panic(
+        expected = "expected the rules to be called in the order [0, 0, 2], but they were called in the order [0, 2, 0]"
+    )]
+    #[tokio::test]
+    async fn test_verify_call_order_mismatch() {
+        let first = create_rule_builder()
+            .match_requests(|input| input.key == "first")
+            .then_output(|| TestOutput::new("first"));
+        let second = create_rule_builder()
+            .match_requests(|input| input.key == "second")
+            .then_output(|| TestOutput::new("second"));
+
+        let interceptor = MockResponseInterceptor::new()
+            .with_rule(&first)
+            .with_rule(&second);
+        let operation = create_test_operation(interceptor, false);
+
+        for key in ["first", "second", "first"] {
+            operation
+                .invoke(TestInput::new("test-bucket", key))
+                .await
+                .unwrap();
+        }
+
+        verify_call_order(&[&first, &first, &second]);
+    }
+
+    #[tokio::test]
+    async fn test_match_fields() {
+        let logs_rule = create_rule_builder()
+            .match_fields(|input, m| {
+                m.field("bucket", input.bucket.as_str(), eq("test-bucket"))
+                    .field("key", input.key.as_str(), regex("^logs/.*\\.json$"));
+            })
+            .then_output(|| TestOutput::new("logs"));
+        let fallback_rule = create_rule_builder()
+            .match_fields(|input, m| {
+                m.field("bucket", input.bucket.as_str(), any());
+            })
+            .then_output(|| TestOutput::new("fallback"));
+
+        let interceptor = MockResponseInterceptor::new()
+            .with_rule(&logs_rule)
+            .with_rule(&fallback_rule);
+        let operation = create_test_operation(interceptor, false);
+
+        let result = operation
+            .invoke(TestInput::new("test-bucket", "logs/2024/01.json"))
+            .await;
+        assert_eq!(result.unwrap(), TestOutput::new("logs"));
+
+        let result = operation
+            .invoke(TestInput::new("test-bucket", "data/01.json"))
+            .await;
+        assert_eq!(result.unwrap(), TestOutput::new("fallback"));
+
+        let result = operation
+            .invoke(TestInput::new("other-bucket", "logs/01.json"))
+            .await;
+        assert_eq!(result.unwrap(), TestOutput::new("fallback"));
+    }
+
+    #[should_panic(
+        expected = "unmatched request report:\n  rule 0:\n    - field `key`: expected a string matching regex `^logs/`, got \"data/01.json\"\n  rule 1:\n    - the `match_requests` predicate returned false"
+    )]
+    #[tokio::test]
+    async fn test_unmatched_request_report() {
+        let logs_rule = create_rule_builder()
+            .match_fields(|input, m| {
+                m.field("bucket", input.bucket.as_str(), eq("test-bucket"))
+                    .field("key", input.key.as_str(), regex("^logs/"));
+            })
+            .then_output(|| TestOutput::new("logs"));
+        let other_rule = create_rule_builder()
+            .match_requests(|input| input.bucket == "other-bucket")
+            .then_output(|| TestOutput::new("other"));
+
+        let interceptor = MockResponseInterceptor::new()
+            .with_rule(&logs_rule)
+            .with_rule(&other_rule);
+        let operation = create_test_operation(interceptor, false);
+
+        let _ = operation
+            .invoke(TestInput::new("test-bucket", "data/01.json"))
+            .await;
+    }
+
+    #[should_panic(
+        expected = "})\n  - field `key`: expected equal to \"logs/01.json\", got \"data/01.json\""
+    )]
+    #[tokio::test]
+    async fn test_sequential_mismatch_report() {
+        let rule = create_rule_builder()
+            .match_fields(|input, m| {
+                m.field("key", input.key.as_str(), eq("logs/01.json"));
+            })
+            .then_output(|| TestOutput::new("logs"));
+
+        let interceptor = MockResponseInterceptor::new()
+            .rule_mode(RuleMode::Sequential)
+            .with_rule(&rule);
+        let operation = create_test_operation(interceptor, false);
+
+        let _ = operation
+            .invoke(TestInput::new("test-bucket", "data/01.json"))
+            .await;
+    }
 }
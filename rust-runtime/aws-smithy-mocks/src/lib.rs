@@ -14,11 +14,16 @@
     rust_2018_idioms
 )]
 
+mod fault;
 mod interceptor;
+pub mod matchers;
 mod rule;
+mod verify;
 
+pub use fault::ConnectionFailure;
 pub use interceptor::{create_mock_http_client, MockResponseInterceptor};
 pub use rule::{MockResponse, Rule, RuleBuilder, RuleMode};
+pub use verify::{verify_call_order, RuleVerifier};
 
 // why do we need a macro for this?
 // We want customers to be able to provide an ergonomic way to say the method they're looking for,
@@ -0,0 +1,326 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Matchers for the fields of operation inputs.
+//!
+//! Matchers are used with [`RuleBuilder::match_fields`](crate::RuleBuilder::match_fields) to match
+//! requests on some of their fields, without hand-writing a predicate over the whole input. Each
+//! matcher also describes itself, so that requests that match no rule can be reported with the
+//! fields that didn't match.
+//!
+//! ```rust,ignore
+//! use aws_smithy_mocks::matchers::{any, eq, regex, some};
+//!
+//! let rule = mock!(Client::get_object)
+//!     .match_fields(|req, m| {
+//!         m.field("bucket", req.bucket(), eq(Some("test-bucket")))
+//!             .field("key", req.key(), regex("^logs/.*\\.json$"))
+//!             .field("range", req.range(), any())
+//!             .field("version_id", req.version_id(), some(any()));
+//!     })
+//!     .then_output(|| GetObjectOutput::builder().build());
+//! ```
+
+use regex_lite::Regex;
+use std::fmt;
+
+/// A predicate over the value of an input field.
+pub trait ValueMatcher<V> {
+    /// Tests if `value` matches.
+    fn matches(&self, value: &V) -> bool;
+
+    /// Describes the values that match, to report mismatches. For example, `equal to "value"`.
+    fn describe(&self) -> String;
+}
+
+impl<V, M: ValueMatcher<V> + ?Sized> ValueMatcher<V> for &M {
+    fn matches(&self, value: &V) -> bool {
+        (**self).matches(value)
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+/// The fields that are matched by a rule, passed to the closure of
+/// [`RuleBuilder::match_fields`](crate::RuleBuilder::match_fields).
+#[derive(Debug, Default)]
+pub struct FieldMatches {
+    mismatches: Vec<String>,
+}
+
+impl FieldMatches {
+    /// Matches the field `name`, which has the given `value`, with `matcher`.
+    ///
+    /// The request only matches if all of its fields match.
+    pub fn field<V: fmt::Debug>(
+        &mut self,
+        name: &str,
+        value: V,
+        matcher: impl ValueMatcher<V>,
+    ) -> &mut Self {
+        if !matcher.matches(&value) {
+            self.mismatches.push(format!(
+                "field `{name}`: expected {}, got {value:?}",
+                matcher.describe()
+            ));
+        }
+        self
+    }
+
+    /// Tests if all the fields matched.
+    pub(crate) fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Returns a description of each field that didn't match.
+    pub(crate) fn into_mismatches(self) -> Vec<String> {
+        self.mismatches
+    }
+}
+
+/// Matcher that matches any value. Created with [`any`].
+#[derive(Debug, Clone, Copy)]
+pub struct AnyValue;
+
+/// Matches any value.
+///
+/// This is a placeholder for fields that are listed to document a rule, but that don't need to
+/// have a particular value.
+pub fn any() -> AnyValue {
+    AnyValue
+}
+
+impl<V> ValueMatcher<V> for AnyValue {
+    fn matches(&self, _value: &V) -> bool {
+        true
+    }
+
+    fn describe(&self) -> String {
+        "any value".into()
+    }
+}
+
+/// Matcher that matches values equal to an expected value. Created with [`eq`].
+#[derive(Debug, Clone)]
+pub struct EqualTo<T>(T);
+
+/// Matches values that are equal to `expected`.
+///
+/// Optional fields are matched with `eq(Some(expected))`, or with [`some`]`(eq(expected))`.
+pub fn eq<T>(expected: T) -> EqualTo<T> {
+    EqualTo(expected)
+}
+
+impl<T: fmt::Debug, V: PartialEq<T>> ValueMatcher<V> for EqualTo<T> {
+    fn matches(&self, value: &V) -> bool {
+        *value == self.0
+    }
+
+    fn describe(&self) -> String {
+        format!("equal to {:?}", self.0)
+    }
+}
+
+/// Matcher that matches strings with a regular expression. Created with [`regex`].
+#[derive(Debug, Clone)]
+pub struct MatchesRegex(Regex);
+
+/// Matches strings that contain a match of the regular expression `pattern`.
+///
+/// Use `^` and `$` to match the whole string. Optional strings that are unset don't match.
+///
+/// The syntax of `pattern` is the syntax of the [`regex-lite`](https://docs.rs/regex-lite) crate.
+///
+/// # Panics
+///
+/// Panics if `pattern` isn't a valid regular expression.
+pub fn regex(pattern: &str) -> MatchesRegex {
+    MatchesRegex(
+        Regex::new(pattern).unwrap_or_else(|err| panic!("invalid regex `{pattern}`: {err}")),
+    )
+}
+
+impl MatchesRegex {
+    fn describe_regex(&self) -> String {
+        format!("a string matching regex `{}`", self.0.as_str())
+    }
+}
+
+impl ValueMatcher<&str> for MatchesRegex {
+    fn matches(&self, value: &&str) -> bool {
+        self.0.is_match(value)
+    }
+
+    fn describe(&self) -> String {
+        self.describe_regex()
+    }
+}
+
+impl ValueMatcher<String> for MatchesRegex {
+    fn matches(&self, value: &String) -> bool {
+        self.0.is_match(value)
+    }
+
+    fn describe(&self) -> String {
+        self.describe_regex()
+    }
+}
+
+impl ValueMatcher<Option<&str>> for MatchesRegex {
+    fn matches(&self, value: &Option<&str>) -> bool {
+        value.is_some_and(|value| self.0.is_match(value))
+    }
+
+    fn describe(&self) -> String {
+        self.describe_regex()
+    }
+}
+
+impl ValueMatcher<Option<String>> for MatchesRegex {
+    fn matches(&self, value: &Option<String>) -> bool {
+        value.as_deref().is_some_and(|value| self.0.is_match(value))
+    }
+
+    fn describe(&self) -> String {
+        self.describe_regex()
+    }
+}
+
+/// Matcher that matches optional values that are set. Created with [`some`].
+#[derive(Debug, Clone)]
+pub struct IsSome<M>(M);
+
+/// Matches optional values that are set, and whose value matches `inner`.
+///
+/// Use `some(any())` to match fields that are set to any value.
+pub fn some<M>(inner: M) -> IsSome<M> {
+    IsSome(inner)
+}
+
+impl<V, M: ValueMatcher<V>> ValueMatcher<Option<V>> for IsSome<M> {
+    fn matches(&self, value: &Option<V>) -> bool {
+        value.as_ref().is_some_and(|value| self.0.matches(value))
+    }
+
+    fn describe(&self) -> String {
+        format!("Some({})", self.0.describe())
+    }
+}
+
+/// Matcher that matches optional values that are unset. Created with [`none`].
+#[derive(Debug, Clone, Copy)]
+pub struct IsNone;
+
+/// Matches optional values that are unset.
+pub fn none() -> IsNone {
+    IsNone
+}
+
+impl<V> ValueMatcher<Option<V>> for IsNone {
+    fn matches(&self, value: &Option<V>) -> bool {
+        value.is_none()
+    }
+
+    fn describe(&self) -> String {
+        "None".into()
+    }
+}
+
+/// Matcher that matches values with a custom predicate. Created with [`predicate`].
+#[derive(Clone)]
+pub struct Predicate<F> {
+    description: String,
+    predicate: F,
+}
+
+impl<F> fmt::Debug for Predicate<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Predicate")
+            .field("description", &self.description)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Matches values for which `predicate` returns `true`.
+///
+/// `description` describes the values that match, to report mismatches.
+///
+/// ```rust,ignore
+/// m.field("max_keys", req.max_keys(), predicate("at most 100", |max| max.unwrap_or(0) <= 100));
+/// ```
+pub fn predicate<F>(description: impl Into<String>, predicate: F) -> Predicate<F> {
+    Predicate {
+        description: description.into(),
+        predicate,
+    }
+}
+
+impl<V, F: Fn(&V) -> bool> ValueMatcher<V> for Predicate<F> {
+    fn matches(&self, value: &V) -> bool {
+        (self.predicate)(value)
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{any, eq, none, predicate, regex, some, FieldMatches, ValueMatcher};
+
+    #[test]
+    fn matchers() {
+        assert!(any().matches(&Some("value")));
+        assert!(any().matches(&None::<i32>));
+
+        assert!(eq("value").matches(&"value"));
+        assert!(eq(Some("value")).matches(&Some("value")));
+        assert!(!eq(Some("value")).matches(&None));
+        assert!(some(eq(5)).matches(&Some(5)));
+        assert!(!some(eq(5)).matches(&Some(6)));
+        assert!(some(any()).matches(&Some("value")));
+        assert!(!some(any()).matches(&None::<&str>));
+        assert!(none().matches(&None::<&str>));
+        assert!(!none().matches(&Some("value")));
+
+        let logs = regex("^logs/.*\\.json$");
+        assert!(logs.matches(&"logs/2024/01.json"));
+        assert!(logs.matches(&Some("logs/01.json")));
+        assert!(logs.matches(&Some("logs/01.json".to_string())));
+        assert!(!logs.matches(&"data/01.json"));
+        assert!(!logs.matches(&None::<&str>));
+
+        let small = predicate("at most 10", |value: &Option<i32>| value.unwrap_or(0) <= 10);
+        assert!(small.matches(&Some(10)));
+        assert!(!small.matches(&Some(11)));
+    }
+
+    #[test]
+    fn reports_mismatched_fields() {
+        let key_matcher = regex("^logs/");
+        let mut matches = FieldMatches::default();
+        matches
+            .field("bucket", Some("test-bucket"), eq(Some("test-bucket")))
+            .field("key", Some("data/01.json"), &key_matcher)
+            .field("version_id", None::<&str>, some(any()));
+        assert!(!matches.is_match());
+        assert_eq!(
+            vec![
+                "field `key`: expected a string matching regex `^logs/`, got Some(\"data/01.json\")",
+                "field `version_id`: expected Some(any value), got None",
+            ],
+            matches.into_mismatches()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid regex `(`")]
+    fn rejects_invalid_regex() {
+        regex("(");
+    }
+}
@@ -3,15 +3,20 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::fault::{truncate_body, ConnectionFailure};
+use crate::matchers::FieldMatches;
+use crate::verify::RuleVerifier;
 use aws_smithy_runtime_api::client::interceptors::context::{Error, Input, Output};
 use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::http::StatusCode;
 use aws_smithy_types::body::SdkBody;
+use std::any::Any;
 use std::fmt;
 use std::future::Future;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// A mock response that can be returned by a rule.
 ///
@@ -31,9 +36,44 @@ pub enum MockResponse<O, E> {
     Http(HttpResponse),
 }
 
-/// A function that matches requests.
-type MatchFn = Arc<dyn Fn(&Input) -> bool + Send + Sync>;
-type ServeFn = Arc<dyn Fn(usize, &Input) -> Option<MockResponse<Output, Error>> + Send + Sync>;
+/// The outcome of a request attempt that is served by a rule.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub(crate) enum MockOutcome<O, E> {
+    /// The rule responds with a mock response.
+    Response(MockResponse<O, E>),
+    /// The connection fails before a response is received.
+    ConnectionFailure(ConnectionFailure),
+}
+
+/// A request attempt that is served by a rule.
+#[derive(Debug)]
+pub(crate) struct MockAttempt<O = Output, E = Error> {
+    /// What the attempt results in.
+    pub(crate) outcome: MockOutcome<O, E>,
+    /// How long the mock HTTP client waits before producing the outcome.
+    pub(crate) delay: Duration,
+}
+
+/// A function that matches requests, and describes why requests don't match.
+type MatchFn = Arc<dyn Fn(&Input) -> Result<(), Vec<String>> + Send + Sync>;
+type ServeFn = Arc<dyn Fn(usize, &Input) -> Option<MockAttempt> + Send + Sync>;
+
+/// A typed input that was captured when a rule was called.
+pub(crate) type CapturedInput = Arc<dyn Any + Send + Sync>;
+/// A function that captures typed inputs.
+type CaptureFn = Arc<dyn Fn(&Input) -> Option<CapturedInput> + Send + Sync>;
+
+/// Sequence number of the next call of any rule, used to verify the order of calls across rules.
+static NEXT_CALL: AtomicU64 = AtomicU64::new(0);
+
+/// A call of a rule.
+struct Call {
+    /// Sequence number of the call across all rules.
+    seq: u64,
+    /// The input of the operation that the call was made for.
+    input: Option<CapturedInput>,
+}
 
 /// A rule for matching requests and providing mock responses.
 ///
@@ -42,14 +82,20 @@ type ServeFn = Arc<dyn Fn(usize, &Input) -> Option<MockResponse<Output, Error>>
 #[derive(Clone)]
 pub struct Rule {
     /// Function that determines if this rule matches a request.
-    pub(crate) matcher: MatchFn,
+    matcher: MatchFn,
 
     /// Handler function that generates responses.
     response_handler: ServeFn,
 
+    /// Function that captures the typed input of a request.
+    input_capture: CaptureFn,
+
     /// Number of times this rule has been called.
     call_count: Arc<AtomicUsize>,
 
+    /// The calls of this rule, in the order they were made.
+    calls: Arc<Mutex<Vec<Call>>>,
+
     /// Maximum number of responses this rule will provide.
     pub(crate) max_responses: usize,
 
@@ -71,7 +117,8 @@ impl Rule {
     #[allow(clippy::type_complexity)]
     pub(crate) fn new<O, E>(
         matcher: MatchFn,
-        response_handler: Arc<dyn Fn(usize, &Input) -> Option<MockResponse<O, E>> + Send + Sync>,
+        input_capture: CaptureFn,
+        response_handler: Arc<dyn Fn(usize, &Input) -> Option<MockAttempt<O, E>> + Send + Sync>,
         max_responses: usize,
         is_simple: bool,
     ) -> Self
@@ -83,29 +130,64 @@ impl Rule {
             matcher,
             response_handler: Arc::new(move |idx: usize, input: &Input| {
                 if idx < max_responses {
-                    response_handler(idx, input).map(|resp| match resp {
-                        MockResponse::Output(o) => MockResponse::Output(Output::erase(o)),
-                        MockResponse::Error(e) => MockResponse::Error(Error::erase(e)),
-                        MockResponse::Http(http_resp) => MockResponse::Http(http_resp),
+                    response_handler(idx, input).map(|attempt| MockAttempt {
+                        outcome: match attempt.outcome {
+                            MockOutcome::Response(resp) => MockOutcome::Response(match resp {
+                                MockResponse::Output(o) => MockResponse::Output(Output::erase(o)),
+                                MockResponse::Error(e) => MockResponse::Error(Error::erase(e)),
+                                MockResponse::Http(http_resp) => MockResponse::Http(http_resp),
+                            }),
+                            MockOutcome::ConnectionFailure(failure) => {
+                                MockOutcome::ConnectionFailure(failure)
+                            }
+                        },
+                        delay: attempt.delay,
                     })
                 } else {
                     None
                 }
             }),
+            input_capture,
             call_count: Arc::new(AtomicUsize::new(0)),
+            calls: Default::default(),
             max_responses,
             is_simple,
         }
     }
 
+    /// Tests if this rule matches a request.
+    pub(crate) fn matches(&self, input: &Input) -> bool {
+        (self.matcher)(input).is_ok()
+    }
+
+    /// Describes why this rule doesn't match a request. Returns an empty list if it matches.
+    pub(crate) fn mismatches(&self, input: &Input) -> Vec<String> {
+        (self.matcher)(input).err().unwrap_or_default()
+    }
+
     /// Test if this is a "simple" rule (non-sequenced)
     pub(crate) fn is_simple(&self) -> bool {
         self.is_simple
     }
 
-    /// Gets the next response.
-    pub(crate) fn next_response(&self, input: &Input) -> Option<MockResponse<Output, Error>> {
+    /// Captures the typed input of a request so that it can be recorded with the calls of this rule.
+    pub(crate) fn capture_input(&self, input: &Input) -> Option<CapturedInput> {
+        (self.input_capture)(input)
+    }
+
+    /// Gets the next response, and records the call along with the `captured` input of the operation.
+    pub(crate) fn next_response(
+        &self,
+        input: &Input,
+        captured: Option<CapturedInput>,
+    ) -> Option<MockAttempt> {
+        let mut calls = self.calls.lock().unwrap();
         let idx = self.call_count.fetch_add(1, Ordering::SeqCst);
+        calls.push(Call {
+            seq: NEXT_CALL.fetch_add(1, Ordering::SeqCst),
+            input: captured,
+        });
+        drop(calls);
         (self.response_handler)(idx, input)
     }
 
@@ -114,6 +196,57 @@ impl Rule {
         self.call_count.load(Ordering::SeqCst)
     }
 
+    /// Returns the inputs of the operations that this rule was called for, in the order of the calls.
+    ///
+    /// Each attempt of an operation is a separate call, so operations that are retried appear once
+    /// per attempt. `I` must be the input type of the operation that the rule was created for.
+    /// Inputs are only recorded for rules built with [`RuleBuilder::capture_inputs`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let rule = mock!(Client::get_object)
+    ///     .capture_inputs()
+    ///     .then_output(|| GetObjectOutput::builder().build());
+    /// // ... exercise the client
+    /// let inputs = rule.inputs::<GetObjectInput>();
+    /// assert_eq!(Some("test-key"), inputs[0].key());
+    /// ```
+    pub fn inputs<I: Clone + 'static>(&self) -> Vec<I> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|call| call.input.as_ref()?.downcast_ref::<I>().cloned())
+            .collect()
+    }
+
+    /// Returns a [`RuleVerifier`] to assert how many times this rule was called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// rule.verify().times(3);
+    /// ```
+    pub fn verify(&self) -> RuleVerifier<'_> {
+        RuleVerifier::new(self)
+    }
+
+    /// Returns the sequence numbers of the calls of this rule.
+    pub(crate) fn call_sequence(&self) -> Vec<u64> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|call| call.seq)
+            .collect()
+    }
+
+    /// Tests if `other` is this rule, or a clone of it.
+    pub(crate) fn is_same_rule(&self, other: &Rule) -> bool {
+        Arc::ptr_eq(&self.calls, &other.calls)
+    }
+
     /// Checks if this rule is exhausted (has provided all its responses).
     pub fn is_exhausted(&self) -> bool {
         self.num_calls() >= self.max_responses
@@ -142,6 +275,12 @@ pub struct RuleBuilder<I, O, E> {
     /// Function that determines if this rule matches a request.
     pub(crate) input_filter: MatchFn,
 
+    /// Function that captures the typed input of a request.
+    pub(crate) input_capture: CaptureFn,
+
+    /// Delay before each response of this rule.
+    pub(crate) delay: Duration,
+
     /// Phantom data for the input type.
     pub(crate) _ty: std::marker::PhantomData<(I, O, E)>,
 }
@@ -156,7 +295,9 @@ where
     #[doc(hidden)]
     pub fn new() -> Self {
         RuleBuilder {
-            input_filter: Arc::new(|i: &Input| i.downcast_ref::<I>().is_some()),
+            input_filter: Self::match_input_type(),
+            input_capture: Arc::new(|_: &Input| None),
+            delay: Duration::ZERO,
             _ty: std::marker::PhantomData,
         }
     }
@@ -168,23 +309,120 @@ where
         F: Future<Output = Result<O, SdkError<E, R>>>,
     {
         Self {
-            input_filter: Arc::new(|i: &Input| i.downcast_ref::<I>().is_some()),
+            input_filter: Self::match_input_type(),
+            input_capture: Arc::new(|_: &Input| None),
+            delay: Duration::ZERO,
             _ty: Default::default(),
         }
     }
 
+    fn match_input_type() -> MatchFn {
+        Arc::new(|i: &Input| match i.downcast_ref::<I>() {
+            Some(_) => Ok(()),
+            _ => Err(vec![Self::input_type_mismatch()]),
+        })
+    }
+
+    /// Records a clone of the input of every call of this rule, so that it can be inspected with
+    /// [`Rule::inputs`].
+    ///
+    /// Inputs aren't recorded by default, since the inputs of some operations, such as those with
+    /// streaming bodies, can't be cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let rule = mock!(Client::get_object)
+    ///     .capture_inputs()
+    ///     .then_output(|| GetObjectOutput::builder().build());
+    /// ```
+    pub fn capture_inputs(mut self) -> Self
+    where
+        I: Clone,
+    {
+        self.input_capture = Arc::new(|i: &Input| {
+            i.downcast_ref::<I>()
+                .map(|typed_input| Arc::new(typed_input.clone()) as CapturedInput)
+        });
+        self
+    }
+
     /// Sets the function that determines if this rule matches a request.
     pub fn match_requests<F>(mut self, filter: F) -> Self
     where
         F: Fn(&I) -> bool + Send + Sync + 'static,
     {
         self.input_filter = Arc::new(move |i: &Input| match i.downcast_ref::<I>() {
-            Some(typed_input) => filter(typed_input),
-            _ => false,
+            Some(typed_input) if filter(typed_input) => Ok(()),
+            Some(_) => Err(vec!["the `match_requests` predicate returned false".into()]),
+            _ => Err(vec![Self::input_type_mismatch()]),
         });
         self
     }
 
+    /// Sets the fields that requests must match for this rule to apply.
+    ///
+    /// `fields` is called with the input of each request, and matches its fields with the
+    /// [matchers](crate::matchers). The rule only applies if every field matches. Fields that aren't
+    /// listed can have any value. This replaces any predicate set with
+    /// [`match_requests`](Self::match_requests).
+    ///
+    /// Requests that match no rule panic with a report of the fields that didn't match each rule.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use aws_smithy_mocks::matchers::{eq, regex};
+    ///
+    /// let rule = mock!(Client::get_object)
+    ///     .match_fields(|req, m| {
+    ///         m.field("bucket", req.bucket(), eq(Some("test-bucket")))
+    ///             .field("key", req.key(), regex("^logs/"));
+    ///     })
+    ///     .then_output(|| GetObjectOutput::builder().build());
+    /// ```
+    pub fn match_fields<F>(mut self, fields: F) -> Self
+    where
+        F: Fn(&I, &mut FieldMatches) + Send + Sync + 'static,
+    {
+        self.input_filter = Arc::new(move |i: &Input| match i.downcast_ref::<I>() {
+            Some(typed_input) => {
+                let mut matches = FieldMatches::default();
+                fields(typed_input, &mut matches);
+                if matches.is_match() {
+                    Ok(())
+                } else {
+                    Err(matches.into_mismatches())
+                }
+            }
+            _ => Err(vec![Self::input_type_mismatch()]),
+        });
+        self
+    }
+
+    fn input_type_mismatch() -> String {
+        format!("the input is not a `{}`", std::any::type_name::<I>())
+    }
+
+    /// Delays every response of this rule by `delay`.
+    ///
+    /// The mock HTTP client waits out the delay with the client's sleep implementation, so tests
+    /// that use a mocked clock (for example, `instant_time_and_sleep` from `aws-smithy-async`, or a
+    /// paused Tokio runtime) can exercise timeouts deterministically. Responses in a
+    /// [sequence](Self::sequence) can override the delay for each response with `delay()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let slow_rule = mock!(Client::get_object)
+    ///     .delay(Duration::from_secs(10))
+    ///     .then_output(|| GetObjectOutput::builder().build());
+    /// ```
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
     /// Start building a response sequence
     ///
     /// A sequence allows a single rule to generate multiple responses which can
@@ -203,7 +441,7 @@ where
     ///     .build();
     /// ```
     pub fn sequence(self) -> ResponseSequenceBuilder<I, O, E> {
-        ResponseSequenceBuilder::new(self.input_filter)
+        ResponseSequenceBuilder::new(self.input_filter, self.input_capture, self.delay)
     }
 
     /// Creates a rule that returns a modeled output.
@@ -230,6 +468,11 @@ where
         self.sequence().http_response(response_fn).build_simple()
     }
 
+    /// Creates a rule that fails to connect with the given `failure`.
+    pub fn then_connection_failure(self, failure: ConnectionFailure) -> Rule {
+        self.sequence().connection_failure(failure).build_simple()
+    }
+
     /// Creates a rule that computes an output based on the input.
     ///
     /// This allows generating responses based on the input request.
@@ -282,14 +525,42 @@ where
 
 type SequenceGeneratorFn<O, E> = Arc<dyn Fn(&Input) -> MockResponse<O, E> + Send + Sync>;
 
+/// A step of a response sequence.
+struct SequenceStep<O, E> {
+    /// What the step results in.
+    kind: StepKind<O, E>,
+
+    /// Number of times this step is repeated.
+    repeat_count: usize,
+
+    /// Delay before the response, if it overrides the delay of the rule.
+    delay: Option<Duration>,
+}
+
+enum StepKind<O, E> {
+    /// Respond with a generated response. `is_http` is set for steps that generate HTTP responses.
+    Respond {
+        generator: SequenceGeneratorFn<O, E>,
+        is_http: bool,
+    },
+    /// Fail to connect.
+    ConnectionFailure(ConnectionFailure),
+}
+
 /// A builder for creating response sequences
 pub struct ResponseSequenceBuilder<I, O, E> {
-    /// The response generators in the sequence
-    generators: Vec<(SequenceGeneratorFn<O, E>, usize)>,
+    /// The steps in the sequence
+    steps: Vec<SequenceStep<O, E>>,
 
     /// Function that determines if this rule matches a request
     input_filter: MatchFn,
 
+    /// Function that captures the typed input of a request
+    input_capture: CaptureFn,
+
+    /// Delay before each response that doesn't override it
+    default_delay: Duration,
+
     /// flag indicating this is a "simple" rule
     is_simple: bool,
 
@@ -309,15 +580,33 @@ where
     E: fmt::Debug + Send + Sync + std::error::Error + 'static,
 {
     /// Create a new response sequence builder
-    pub(crate) fn new(input_filter: MatchFn) -> Self {
+    pub(crate) fn new(
+        input_filter: MatchFn,
+        input_capture: CaptureFn,
+        default_delay: Duration,
+    ) -> Self {
         Self {
-            generators: Vec::new(),
+            steps: Vec::new(),
             input_filter,
+            input_capture,
+            default_delay,
             is_simple: false,
             _marker: std::marker::PhantomData,
         }
     }
 
+    fn push_response(&mut self, generator: SequenceGeneratorFn<O, E>, is_http: bool) {
+        self.push_step(StepKind::Respond { generator, is_http });
+    }
+
+    fn push_step(&mut self, kind: StepKind<O, E>) {
+        self.steps.push(SequenceStep {
+            kind,
+            repeat_count: 1,
+            delay: None,
+        });
+    }
+
     /// Add a modeled output response to the sequence
     ///
     /// # Examples
@@ -333,7 +622,7 @@ where
         F: Fn() -> O + Send + Sync + 'static,
     {
         let generator = Arc::new(move |_input: &Input| MockResponse::Output(output_fn()));
-        self.generators.push((generator, 1));
+        self.push_response(generator, false);
         self
     }
 
@@ -343,7 +632,7 @@ where
         F: Fn() -> E + Send + Sync + 'static,
     {
         let generator = Arc::new(move |_input: &Input| MockResponse::Error(error_fn()));
-        self.generators.push((generator, 1));
+        self.push_response(generator, false);
         self
     }
 
@@ -360,7 +649,7 @@ where
             }),
         };
 
-        self.generators.push((generator, 1));
+        self.push_response(generator, true);
         self
     }
 
@@ -370,7 +659,7 @@ where
         F: Fn() -> HttpResponse + Send + Sync + 'static,
     {
         let generator = Arc::new(move |_input: &Input| MockResponse::Http(response_fn()));
-        self.generators.push((generator, 1));
+        self.push_response(generator, true);
         self
     }
 
@@ -389,7 +678,7 @@ where
                 panic!("Input type mismatch in compute_output")
             }
         });
-        self.generators.push((generator, 1));
+        self.push_response(generator, false);
         self
     }
 
@@ -405,7 +694,91 @@ where
                 panic!("Input type mismatch in compute_response")
             }
         });
-        self.generators.push((generator, 1));
+        self.push_response(generator, false);
+        self
+    }
+
+    /// Add a connection failure to the sequence
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// // Create a rule where the connection is reset twice, then succeeds
+    /// let rule = mock!(Client::get_object)
+    ///     .sequence()
+    ///     .connection_failure(ConnectionFailure::Reset)
+    ///     .times(2)
+    ///     .output(|| GetObjectOutput::builder().build())
+    ///     .build();
+    /// ```
+    pub fn connection_failure(mut self, failure: ConnectionFailure) -> Self {
+        self.push_step(StepKind::ConnectionFailure(failure));
+        self
+    }
+
+    /// Delay the last added response by `delay`.
+    ///
+    /// This overrides the delay that was set for the whole rule with [`RuleBuilder::delay`].
+    /// The mock HTTP client waits out the delay with the client's sleep implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// // Create a rule that responds after the attempt timeout once, then responds immediately
+    /// let rule = mock!(Client::get_object)
+    ///     .sequence()
+    ///     .output(|| GetObjectOutput::builder().build())
+    ///     .delay(Duration::from_secs(30))
+    ///     .output(|| GetObjectOutput::builder().build())
+    ///     .build();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before adding any responses to the sequence.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        let last_step = self
+            .steps
+            .last_mut()
+            .expect("delay() called before adding a response to the sequence");
+        last_step.delay = Some(delay);
+        self
+    }
+
+    /// Truncate the body of the last added HTTP response to its first `len` bytes.
+    ///
+    /// Reading the body yields those bytes, and then fails as if the connection was closed before
+    /// the rest of the body was received. This is useful to test how partial responses are handled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let rule = mock!(Client::get_object)
+    ///     .sequence()
+    ///     .http_status(200, Some("12345-abcde".to_string()))
+    ///     .truncate_body(5)
+    ///     .build();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last added response isn't an HTTP response with an in-memory body.
+    pub fn truncate_body(mut self, len: usize) -> Self {
+        match self.steps.last_mut().map(|step| &mut step.kind) {
+            Some(StepKind::Respond {
+                generator,
+                is_http: true,
+            }) => {
+                let inner = generator.clone();
+                *generator = Arc::new(move |input: &Input| match inner(input) {
+                    MockResponse::Http(response) => {
+                        MockResponse::Http(truncate_body(response, len))
+                    }
+                    other => other,
+                });
+            }
+            _ => panic!("truncate_body() must follow an HTTP response in the sequence"),
+        }
         self
     }
 
@@ -433,7 +806,7 @@ where
     /// - Called with a count of 0
     /// - Called before adding any responses to the sequence
     pub fn times(mut self, count: usize) -> Self {
-        if self.generators.is_empty() {
+        if self.steps.is_empty() {
             panic!("times(n) called before adding a response to the sequence");
         }
         match count {
@@ -444,9 +817,9 @@ where
             _ => {}
         }
 
-        // update the repeat count of the last generator
-        if let Some(last_generator) = self.generators.last_mut() {
-            last_generator.1 = count;
+        // update the repeat count of the last step
+        if let Some(last_step) = self.steps.last_mut() {
+            last_step.repeat_count = count;
         }
         self
     }
@@ -475,7 +848,7 @@ where
     ///
     /// Panics if called before adding any responses to the sequence.
     pub fn repeatedly(self) -> FinalizedResponseSequenceBuilder<I, O, E> {
-        if self.generators.is_empty() {
+        if self.steps.is_empty() {
             panic!("repeatedly() called before adding a response to the sequence");
         }
         let inner = self.times(usize::MAX);
@@ -490,25 +863,38 @@ where
 
     /// Build the rule with this response sequence
     pub fn build(self) -> Rule {
-        let generators = self.generators;
+        let steps = self.steps;
+        let default_delay = self.default_delay;
         let is_simple = self.is_simple;
 
         // calculate total responses (sum of all repetitions)
-        let total_responses: usize = generators
+        let total_responses: usize = steps
             .iter()
-            .map(|(_, count)| *count)
+            .map(|step| step.repeat_count)
             .fold(0, |acc, count| acc.saturating_add(count));
 
         Rule::new(
             self.input_filter,
+            self.input_capture,
             Arc::new(move |idx, input| {
-                // find which generator to use
+                // find which step to use
                 let mut current_idx = idx;
-                for (generator, repeat_count) in &generators {
-                    if current_idx < *repeat_count {
-                        return Some(generator(input));
+                for step in &steps {
+                    if current_idx < step.repeat_count {
+                        let outcome = match &step.kind {
+                            StepKind::Respond { generator, .. } => {
+                                MockOutcome::Response(generator(input))
+                            }
+                            StepKind::ConnectionFailure(failure) => {
+                                MockOutcome::ConnectionFailure(*failure)
+                            }
+                        };
+                        return Some(MockAttempt {
+                            outcome,
+                            delay: step.delay.unwrap_or(default_delay),
+                        });
                     }
-                    current_idx -= repeat_count;
+                    current_idx -= step.repeat_count;
                 }
                 None
             }),
@@ -0,0 +1,122 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::Rule;
+
+/// Asserts how many times a rule was called.
+///
+/// Created with [`Rule::verify`]. Each attempt of an operation is a separate call, so an operation
+/// that is retried twice calls its rule three times. Every assertion panics with a description of
+/// the mismatch, and returns the verifier so that assertions can be chained.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let rule = mock!(Client::get_object)
+///     .sequence()
+///     .http_status(503, None)
+///     .times(2)
+///     .output(|| GetObjectOutput::builder().build())
+///     .build();
+/// // ... exercise the client
+/// rule.verify().times(3);
+/// ```
+#[derive(Debug)]
+pub struct RuleVerifier<'a> {
+    rule: &'a Rule,
+}
+
+impl<'a> RuleVerifier<'a> {
+    pub(crate) fn new(rule: &'a Rule) -> Self {
+        Self { rule }
+    }
+
+    /// Asserts that the rule was called exactly `expected` times.
+    #[track_caller]
+    pub fn times(self, expected: usize) -> Self {
+        let actual = self.rule.num_calls();
+        assert_eq!(
+            expected, actual,
+            "expected the rule to be called {expected} times, but it was called {actual} times"
+        );
+        self
+    }
+
+    /// Asserts that the rule was never called.
+    #[track_caller]
+    pub fn never(self) -> Self {
+        self.times(0)
+    }
+
+    /// Asserts that the rule was called at least `min` times.
+    #[track_caller]
+    pub fn at_least(self, min: usize) -> Self {
+        let actual = self.rule.num_calls();
+        assert!(
+            actual >= min,
+            "expected the rule to be called at least {min} times, but it was called {actual} times"
+        );
+        self
+    }
+
+    /// Asserts that the rule was called at most `max` times.
+    #[track_caller]
+    pub fn at_most(self, max: usize) -> Self {
+        let actual = self.rule.num_calls();
+        assert!(
+            actual <= max,
+            "expected the rule to be called at most {max} times, but it was called {actual} times"
+        );
+        self
+    }
+}
+
+/// Asserts that `rules` were called in exactly the given order.
+///
+/// The calls of all the given rules are merged in the order they were made, and compared with
+/// `rules`. A rule that is expected to be called several times is listed once for each call, and
+/// every call of the given rules must be listed.
+///
+/// # Panics
+///
+/// Panics if the calls were made in a different order. Rules are identified in the panic message by
+/// the position at which they first appear in `rules`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let create = mock!(Client::create_bucket).then_output(|| CreateBucketOutput::builder().build());
+/// let put = mock!(Client::put_object).then_output(|| PutObjectOutput::builder().build());
+/// // ... exercise the client
+/// verify_call_order(&[&create, &put, &put]);
+/// ```
+#[track_caller]
+pub fn verify_call_order(rules: &[&Rule]) {
+    // identify each rule by the position it first appears at
+    let ids: Vec<usize> = rules
+        .iter()
+        .map(|rule| {
+            rules
+                .iter()
+                .position(|other| other.is_same_rule(rule))
+                .expect("rule is in the list")
+        })
+        .collect();
+
+    let mut calls: Vec<(u64, usize)> = Vec::new();
+    for (idx, rule) in rules.iter().enumerate() {
+        // rules listed several times contribute their calls once
+        if ids[idx] == idx {
+            calls.extend(rule.call_sequence().into_iter().map(|seq| (seq, idx)));
+        }
+    }
+    calls.sort_unstable();
+    let actual: Vec<usize> = calls.into_iter().map(|(_, id)| id).collect();
+
+    assert_eq!(
+        ids, actual,
+        "expected the rules to be called in the order {ids:?}, but they were called in the order {actual:?}"
+    );
+}
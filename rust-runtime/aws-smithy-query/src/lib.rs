@@ -8,26 +8,59 @@
 /* End of automatically managed default lints */
 #![allow(clippy::derive_partial_eq_without_eq)]
 #![warn(
-    // missing_docs,
+    missing_docs,
     rustdoc::missing_crate_level_docs,
     unreachable_pub,
     rust_2018_idioms
 )]
 
 //! Abstractions for the Smithy AWS Query protocol
+//!
+//! The writers in this crate serialize values into the `application/x-www-form-urlencoded`
+//! format used by the AWS Query and EC2 Query protocols. Generated clients use them to build
+//! request bodies, and they can also be used directly to build Query payloads by hand.
+//!
+//! # Examples
+//!
+//! ```
+//! use aws_smithy_query::QueryWriter;
+//!
+//! let mut out = String::new();
+//! let mut writer = QueryWriter::new(&mut out, "SendMessageBatch", "2012-11-05");
+//! writer.prefix("QueueUrl").string("https://example.com/queue");
+//! let mut entries = writer
+//!     .prefix("SendMessageBatchRequestEntry")
+//!     .start_list(true, None);
+//! let mut entry = entries.entry();
+//! entry.prefix("Id").string("msg-1");
+//! entry.prefix("MessageBody").string("hello world");
+//! entries.finish();
+//! writer.finish();
+//!
+//! assert_eq!(
+//!     "Action=SendMessageBatch&Version=2012-11-05\
+//!     &QueueUrl=https%3A%2F%2Fexample.com%2Fqueue\
+//!     &SendMessageBatchRequestEntry.1.Id=msg-1\
+//!     &SendMessageBatchRequestEntry.1.MessageBody=hello%20world",
+//!     out
+//! );
+//! ```
 
 use aws_smithy_types::date_time::{DateTimeFormatError, Format};
 use aws_smithy_types::primitive::Encoder;
-use aws_smithy_types::{DateTime, Number};
+use aws_smithy_types::{base64, Blob, DateTime, Number};
 use std::borrow::Cow;
 use std::fmt::Write;
 use urlencoding::encode;
 
+/// Writes the top-level parameters of a Query document.
 pub struct QueryWriter<'a> {
     output: &'a mut String,
 }
 
 impl<'a> QueryWriter<'a> {
+    /// Creates a writer for a request to `action` of the service API `version`, and writes the
+    /// `Action` and `Version` parameters to `output`.
     pub fn new(output: &'a mut String, action: &str, version: &str) -> Self {
         output.push_str("Action=");
         output.push_str(&encode(action));
@@ -36,15 +69,37 @@ impl<'a> QueryWriter<'a> {
         QueryWriter { output }
     }
 
+    /// Creates a writer that appends parameters to `output` without writing the `Action` and
+    /// `Version` parameters, e.g. to build a fragment of a larger Query document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aws_smithy_query::QueryWriter;
+    ///
+    /// let mut out = String::new();
+    /// let mut writer = QueryWriter::without_action(&mut out);
+    /// writer.prefix("Id").string("1");
+    /// writer.prefix("Body").string("a b");
+    /// writer.finish();
+    /// assert_eq!("Id=1&Body=a%20b", out);
+    /// ```
+    pub fn without_action(output: &'a mut String) -> Self {
+        QueryWriter { output }
+    }
+
+    /// Starts writing the parameter named `prefix`.
     pub fn prefix(&mut self, prefix: &'a str) -> QueryValueWriter<'_> {
         QueryValueWriter::new(self.output, Cow::Borrowed(prefix))
     }
 
+    /// Finishes writing the document.
     pub fn finish(self) {
         // Calling this drops self
     }
 }
 
+/// Writes the entries of a map parameter.
 #[must_use]
 pub struct QueryMapWriter<'a> {
     output: &'a mut String,
@@ -73,11 +128,13 @@ impl<'a> QueryMapWriter<'a> {
         }
     }
 
+    /// Writes the `key` of the next entry and returns a writer for its value.
     pub fn entry(&mut self, key: &str) -> QueryValueWriter<'_> {
         let entry = if self.flatten { "" } else { ".entry" };
+        write_separator(self.output);
         write!(
             &mut self.output,
-            "&{}{}.{}.{}={}",
+            "{}{}.{}.{}={}",
             self.prefix,
             entry,
             self.next_index,
@@ -96,11 +153,13 @@ impl<'a> QueryMapWriter<'a> {
         QueryValueWriter::new(self.output, Cow::Owned(value_name))
     }
 
+    /// Finishes writing the map.
     pub fn finish(self) {
         // Calling this drops self
     }
 }
 
+/// Writes the members of a list parameter.
 #[must_use]
 pub struct QueryListWriter<'a> {
     output: &'a mut String,
@@ -126,6 +185,7 @@ impl<'a> QueryListWriter<'a> {
         }
     }
 
+    /// Returns a writer for the next member of the list.
     pub fn entry(&mut self) -> QueryValueWriter<'_> {
         let value_name = if self.flatten {
             format!("{}.{}", self.prefix, self.next_index)
@@ -144,6 +204,7 @@ impl<'a> QueryListWriter<'a> {
         QueryValueWriter::new(self.output, Cow::Owned(value_name))
     }
 
+    /// Finishes writing the list. An empty list is written as a parameter with an empty value.
     pub fn finish(self) {
         // https://github.com/awslabs/smithy/commit/715b1d94ab14764ad43496b016b0c2e85bcf1d1f
         // If the list was empty, just serialize the parameter name
@@ -153,6 +214,7 @@ impl<'a> QueryListWriter<'a> {
     }
 }
 
+/// Writes the value of a parameter.
 #[must_use]
 pub struct QueryValueWriter<'a> {
     output: &'a mut String,
@@ -160,6 +222,7 @@ pub struct QueryValueWriter<'a> {
 }
 
 impl<'a> QueryValueWriter<'a> {
+    /// Creates a writer for the value of the parameter named `prefix`, which appends to `output`.
     pub fn new(output: &'a mut String, prefix: Cow<'a, str>) -> QueryValueWriter<'a> {
         QueryValueWriter { output, prefix }
     }
@@ -187,6 +250,11 @@ impl<'a> QueryValueWriter<'a> {
         self.output.push_str(&encode(value));
     }
 
+    /// Writes a blob `value` as a base64-encoded string.
+    pub fn blob(self, value: &Blob) {
+        self.string(&base64::encode(value))
+    }
+
     /// Writes a number `value`.
     pub fn number(self, value: Number) {
         match value {
@@ -227,17 +295,24 @@ impl<'a> QueryValueWriter<'a> {
     }
 
     fn write_param_name(&mut self) {
-        self.output.push('&');
+        write_separator(self.output);
         self.output.push_str(&self.prefix);
         self.output.push('=');
     }
 }
 
+/// Writes the `&` that separates parameters, unless `output` is empty.
+fn write_separator(output: &mut String) {
+    if !output.is_empty() {
+        output.push('&');
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::QueryWriter;
     use aws_smithy_types::date_time::Format;
-    use aws_smithy_types::{DateTime, Number};
+    use aws_smithy_types::{Blob, DateTime, Number};
 
     #[test]
     fn no_params() {
@@ -449,4 +524,35 @@ mod tests {
         QueryWriter::new(&mut out, "Some Action", "1 2").finish();
         assert_eq!("Action=Some%20Action&Version=1%202", out);
     }
+
+    #[test]
+    fn blobs() {
+        let mut out = String::new();
+        let mut writer = QueryWriter::new(&mut out, "SomeAction", "1.0");
+        writer.prefix("Blob").blob(&Blob::new("hello?"));
+        writer.finish();
+        assert_eq!("Action=SomeAction&Version=1.0&Blob=aGVsbG8%2F", out);
+    }
+
+    #[test]
+    fn without_action() {
+        let mut out = String::new();
+        let mut writer = QueryWriter::without_action(&mut out);
+        let mut map = writer.prefix("Attributes").start_map(true, "Name", "Value");
+        map.entry("DelaySeconds").string("10");
+        map.finish();
+        writer.prefix("Id").string("1");
+        writer.finish();
+        assert_eq!(
+            "Attributes.1.Name=DelaySeconds&Attributes.1.Value=10&Id=1",
+            out
+        );
+
+        let mut out = String::new();
+        QueryWriter::without_action(&mut out)
+            .prefix("Empty")
+            .start_list(false, None)
+            .finish();
+        assert_eq!("Empty=", out);
+    }
 }
@@ -25,6 +25,9 @@ pub mod http;
 /// Static auth scheme option resolver.
 pub mod static_resolver;
 
+/// Auth schemes backed by a user-provided signer.
+pub mod custom;
+
 /// The output type from the [`ResolveAuthSchemeOptions::resolve_auth_scheme_options_v2`]
 ///
 /// The resolver returns a list of these, in the order the auth scheme resolver wishes to use them.
@@ -411,6 +414,38 @@ pub trait Sign: Send + Sync + fmt::Debug {
     ) -> Result<(), BoxError>;
 }
 
+/// Container for a shared signer implementation.
+#[derive(Clone, Debug)]
+pub struct SharedSigner(Arc<dyn Sign>);
+
+impl SharedSigner {
+    /// Creates a new [`SharedSigner`] from the given signer.
+    pub fn new(signer: impl Sign + 'static) -> Self {
+        Self(Arc::new(signer))
+    }
+}
+
+impl Sign for SharedSigner {
+    fn sign_http_request(
+        &self,
+        request: &mut HttpRequest,
+        identity: &Identity,
+        auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+        runtime_components: &RuntimeComponents,
+        config_bag: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        self.0.sign_http_request(
+            request,
+            identity,
+            auth_scheme_endpoint_config,
+            runtime_components,
+            config_bag,
+        )
+    }
+}
+
+impl_shared_conversions!(convert SharedSigner from Sign using SharedSigner::new);
+
 /// Endpoint configuration for the selected auth scheme.
 ///
 /// The configuration held by this struct originates from the endpoint rule set in the service model.
@@ -478,6 +513,48 @@ where
     }
 }
 
+/// A list of [AuthSchemeId]s that must never be selected
+///
+/// Unlike [`AuthSchemePreference`], this is a strict restriction: any scheme in this list is
+/// removed from the auth schemes resolved by an auth scheme resolver before one is selected.
+/// If no resolved scheme remains, the request fails with an error explaining which schemes
+/// were disabled.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DisabledAuthSchemes {
+    disabled: Vec<AuthSchemeId>,
+}
+
+impl DisabledAuthSchemes {
+    /// Returns `true` if `scheme_id` is disabled
+    pub fn contains(&self, scheme_id: &AuthSchemeId) -> bool {
+        self.disabled.contains(scheme_id)
+    }
+}
+
+impl Storable for DisabledAuthSchemes {
+    type Storer = StoreReplace<Self>;
+}
+
+impl IntoIterator for DisabledAuthSchemes {
+    type Item = AuthSchemeId;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.disabled.into_iter()
+    }
+}
+
+impl<T> From<T> for DisabledAuthSchemes
+where
+    T: AsRef<[AuthSchemeId]>,
+{
+    fn from(slice: T) -> Self {
+        DisabledAuthSchemes {
+            disabled: slice.as_ref().to_vec(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
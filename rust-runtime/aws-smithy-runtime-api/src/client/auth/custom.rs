@@ -0,0 +1,176 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::client::auth::{AuthScheme, AuthSchemeId, SharedSigner, Sign};
+use crate::client::identity::{ResolveIdentity, SharedIdentityResolver};
+use crate::client::runtime_components::GetIdentityResolver;
+use crate::shared::IntoShared;
+
+/// An [`AuthScheme`] that signs requests with a user-provided [`Sign`] implementation.
+///
+/// This makes it possible to plug signing schemes that aren't modeled in Smithy (for example, an
+/// HMAC header, a JWT assertion, or a proprietary enterprise scheme) into the auth orchestration
+/// of a generated client without implementing [`AuthScheme`] by hand.
+///
+/// The signer receives the resolved [`Identity`](crate::client::identity::Identity), the
+/// [`AuthSchemeEndpointConfig`](crate::client::auth::AuthSchemeEndpointConfig) for the resolved
+/// endpoint, the runtime components, and the config bag. By default, the identity is resolved with
+/// the identity resolver registered for the scheme ID in the client config. A dedicated resolver
+/// can be set with [`CustomAuthScheme::with_identity_resolver`] instead.
+///
+/// For the scheme to be selected, its ID must be returned by the client's auth scheme option
+/// resolver.
+///
+/// # Examples
+/// ```no_run
+/// use aws_smithy_runtime_api::box_error::BoxError;
+/// use aws_smithy_runtime_api::client::auth::custom::CustomAuthScheme;
+/// use aws_smithy_runtime_api::client::auth::{AuthSchemeEndpointConfig, AuthSchemeId, Sign};
+/// use aws_smithy_runtime_api::client::identity::Identity;
+/// use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+/// use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+/// use aws_smithy_types::config_bag::ConfigBag;
+///
+/// #[derive(Debug)]
+/// struct JwtAssertion(String);
+///
+/// #[derive(Debug)]
+/// struct JwtAssertionSigner;
+///
+/// impl Sign for JwtAssertionSigner {
+///     fn sign_http_request(
+///         &self,
+///         request: &mut HttpRequest,
+///         identity: &Identity,
+///         _auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+///         _runtime_components: &RuntimeComponents,
+///         _config_bag: &ConfigBag,
+///     ) -> Result<(), BoxError> {
+///         let assertion = identity
+///             .data::<JwtAssertion>()
+///             .ok_or("JWT assertion signing requires a `JwtAssertion` identity")?;
+///         request
+///             .headers_mut()
+///             .insert("x-jwt-assertion", assertion.0.clone());
+///         Ok(())
+///     }
+/// }
+///
+/// let auth_scheme = CustomAuthScheme::new(AuthSchemeId::new("jwtAssertion"), JwtAssertionSigner);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CustomAuthScheme {
+    scheme_id: AuthSchemeId,
+    signer: SharedSigner,
+    identity_resolver: Option<SharedIdentityResolver>,
+}
+
+impl CustomAuthScheme {
+    /// Creates a new `CustomAuthScheme` with the given ID that signs requests with `signer`.
+    pub fn new(scheme_id: AuthSchemeId, signer: impl Sign + 'static) -> Self {
+        Self {
+            scheme_id,
+            signer: signer.into_shared(),
+            identity_resolver: None,
+        }
+    }
+
+    /// Resolves identities for this scheme with `identity_resolver` rather than the identity
+    /// resolver registered for the scheme ID in the client config.
+    pub fn with_identity_resolver(
+        mut self,
+        identity_resolver: impl ResolveIdentity + 'static,
+    ) -> Self {
+        self.identity_resolver = Some(identity_resolver.into_shared());
+        self
+    }
+}
+
+impl AuthScheme for CustomAuthScheme {
+    fn scheme_id(&self) -> AuthSchemeId {
+        self.scheme_id.clone()
+    }
+
+    fn identity_resolver(
+        &self,
+        identity_resolvers: &dyn GetIdentityResolver,
+    ) -> Option<SharedIdentityResolver> {
+        self.identity_resolver
+            .clone()
+            .or_else(|| identity_resolvers.identity_resolver(self.scheme_id()))
+    }
+
+    fn signer(&self) -> &dyn Sign {
+        &self.signer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CustomAuthScheme;
+    use crate::box_error::BoxError;
+    use crate::client::auth::{AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, Sign};
+    use crate::client::identity::{
+        Identity, IdentityFuture, ResolveIdentity, SharedIdentityResolver,
+    };
+    use crate::client::orchestrator::HttpRequest;
+    use crate::client::runtime_components::{GetIdentityResolver, RuntimeComponents};
+    use aws_smithy_types::config_bag::ConfigBag;
+
+    const SCHEME_ID: AuthSchemeId = AuthSchemeId::new("custom");
+
+    #[derive(Debug)]
+    struct TestSigner;
+    impl Sign for TestSigner {
+        fn sign_http_request(
+            &self,
+            _: &mut HttpRequest,
+            _: &Identity,
+            _: AuthSchemeEndpointConfig<'_>,
+            _: &RuntimeComponents,
+            _: &ConfigBag,
+        ) -> Result<(), BoxError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestIdentityResolver(&'static str);
+    impl ResolveIdentity for TestIdentityResolver {
+        fn resolve_identity<'a>(
+            &'a self,
+            _: &'a RuntimeComponents,
+            _: &'a ConfigBag,
+        ) -> IdentityFuture<'a> {
+            IdentityFuture::ready(Ok(Identity::new(self.0, None)))
+        }
+    }
+
+    struct TestIdentityResolvers(SharedIdentityResolver);
+    impl GetIdentityResolver for TestIdentityResolvers {
+        fn identity_resolver(&self, scheme_id: AuthSchemeId) -> Option<SharedIdentityResolver> {
+            (scheme_id == SCHEME_ID).then(|| self.0.clone())
+        }
+    }
+
+    #[test]
+    fn identity_resolver_selection() {
+        let configured =
+            TestIdentityResolvers(SharedIdentityResolver::new(TestIdentityResolver("config")));
+
+        let scheme = CustomAuthScheme::new(SCHEME_ID, TestSigner);
+        assert_eq!(SCHEME_ID, scheme.scheme_id());
+        let resolver = scheme.identity_resolver(&configured).unwrap();
+        assert!(format!("{resolver:?}").contains("config"));
+
+        let scheme = CustomAuthScheme::new(SCHEME_ID, TestSigner)
+            .with_identity_resolver(TestIdentityResolver("dedicated"));
+        let resolver = scheme.identity_resolver(&configured).unwrap();
+        assert!(format!("{resolver:?}").contains("dedicated"));
+
+        let other = CustomAuthScheme::new(AuthSchemeId::new("other"), TestSigner);
+        assert!(other.identity_resolver(&configured).is_none());
+    }
+}
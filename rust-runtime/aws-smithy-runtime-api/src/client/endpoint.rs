@@ -8,7 +8,7 @@
 use crate::box_error::BoxError;
 use crate::client::runtime_components::sealed::ValidateConfig;
 use crate::impl_shared_conversions;
-use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 use aws_smithy_types::endpoint::Endpoint;
 use aws_smithy_types::type_erasure::TypeErasedBox;
 use error::InvalidEndpointError;
@@ -88,6 +88,19 @@ pub trait ResolveEndpoint: Send + Sync + fmt::Debug {
     /// Asynchronously resolves an endpoint to use from the given endpoint parameters.
     fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a>;
 
+    /// Asynchronously resolves an endpoint from the given endpoint parameters and the config of the operation.
+    ///
+    /// This is what the orchestrator calls. The default implementation ignores `_cfg` and calls
+    /// [`ResolveEndpoint::resolve_endpoint`]. Override it when resolving an endpoint depends on
+    /// config that isn't part of the endpoint parameters.
+    fn resolve_endpoint_with_config<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        _cfg: &'a ConfigBag,
+    ) -> EndpointFuture<'a> {
+        self.resolve_endpoint(params)
+    }
+
     /// Finalize the service-specific concrete parameters in `_params`.
     ///
     /// The `EndpointResolverParams` may need to include additional data at a later point,
@@ -123,6 +136,14 @@ impl ResolveEndpoint for SharedEndpointResolver {
         self.0.resolve_endpoint(params)
     }
 
+    fn resolve_endpoint_with_config<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        cfg: &'a ConfigBag,
+    ) -> EndpointFuture<'a> {
+        self.0.resolve_endpoint_with_config(params, cfg)
+    }
+
     fn finalize_params<'a>(
         &'a self,
         params: &'a mut EndpointResolverParams,
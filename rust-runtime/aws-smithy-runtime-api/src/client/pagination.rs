@@ -0,0 +1,87 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Smithy support-code for code generated paginators.
+
+use crate::client::orchestrator::HttpResponse;
+use crate::http::{Headers, StatusCode};
+use std::time::Duration;
+
+/// Metadata about the response for a single page of a paginated operation.
+///
+/// This is yielded alongside each page by the `send_with_metadata()` method of generated
+/// paginators, for auditing and debugging long list operations. AWS SDKs implement their
+/// `RequestId` trait for this type, so the request ID of each page can be read with `request_id()`.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct PageMetadata {
+    page_number: usize,
+    latency: Duration,
+    status: Option<StatusCode>,
+    headers: Headers,
+}
+
+impl PageMetadata {
+    /// Creates metadata for the page with the given (one-based) `page_number`, which took `latency`
+    /// to fetch.
+    pub fn new(page_number: usize, latency: Duration) -> Self {
+        Self {
+            page_number,
+            latency,
+            status: None,
+            headers: Headers::new(),
+        }
+    }
+
+    /// Records the status code and headers of the HTTP response for the page.
+    pub fn with_response(mut self, response: &HttpResponse) -> Self {
+        self.status = Some(response.status());
+        self.headers = response.headers().clone();
+        self
+    }
+
+    /// Returns the one-based number of the page within the pagination stream.
+    pub fn page_number(&self) -> usize {
+        self.page_number
+    }
+
+    /// Returns how long it took to fetch the page, including any retries.
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// Returns the status code of the HTTP response for the page, if there was one.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+
+    /// Returns the headers of the HTTP response for the page.
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PageMetadata;
+    use crate::client::orchestrator::HttpResponse;
+    use aws_smithy_types::body::SdkBody;
+    use std::time::Duration;
+
+    #[test]
+    fn records_response() {
+        let metadata = PageMetadata::new(2, Duration::from_millis(150));
+        assert_eq!(None, metadata.status());
+        assert!(metadata.headers().is_empty());
+
+        let mut response = HttpResponse::new(200.try_into().unwrap(), SdkBody::empty());
+        response.headers_mut().insert("x-request-id", "abc-123");
+        let metadata = metadata.with_response(&response);
+        assert_eq!(2, metadata.page_number());
+        assert_eq!(Duration::from_millis(150), metadata.latency());
+        assert_eq!(Some(200), metadata.status().map(u16::from));
+        assert_eq!(Some("abc-123"), metadata.headers().get("x-request-id"));
+    }
+}
@@ -3,6 +3,12 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub mod combinators;
+
 /// Error types for waiters.
 pub mod error {
     use crate::client::{
@@ -230,3 +236,218 @@ impl<O, E> FinalPoll<O, E> {
         FinalPoll::new(self.result.map_err(mapper))
     }
 }
+
+/// Waiter acceptor state
+///
+/// This enum (vaguely) matches the [acceptor state] from the Smithy spec.
+/// It has an additional `NoAcceptorsMatched` variant to indicate the case where
+/// none of the modeled waiters matched the response, which the spec mentions but
+/// doesn't consider an official part of the acceptor state enum. An `Option<AcceptorState>`
+/// could have been used instead, but this seemed cleaner.
+///
+/// [acceptor state]: https://smithy.io/2.0/additional-specs/waiters.html#acceptorstate-enum
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AcceptorState {
+    /// None of the modeled acceptors matched the response.
+    NoAcceptorsMatched,
+    /// A `success` acceptor matched the response.
+    Success,
+    /// A `failure` acceptor matched the response.
+    Failure,
+    /// A `retry` acceptor matched the response.
+    Retry,
+}
+
+/// Progress of a waiter, reported to the [`WaiterProgressCallback`] after every poll.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaiterProgress {
+    attempt: u32,
+    last_state: AcceptorState,
+    elapsed: Duration,
+    next_delay: Option<Duration>,
+}
+
+impl WaiterProgress {
+    /// Creates a new `WaiterProgress`.
+    pub fn new(
+        attempt: u32,
+        last_state: AcceptorState,
+        elapsed: Duration,
+        next_delay: Option<Duration>,
+    ) -> Self {
+        Self {
+            attempt,
+            last_state,
+            elapsed,
+            next_delay,
+        }
+    }
+
+    /// Returns the number of the poll that was just made, starting at 1.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Returns the state that the acceptors observed for the poll that was just made.
+    pub fn last_state(&self) -> AcceptorState {
+        self.last_state
+    }
+
+    /// Returns how much time has elapsed since the waiter started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns how long the waiter will sleep before polling again, or `None` if it won't poll again.
+    pub fn next_delay(&self) -> Option<Duration> {
+        self.next_delay
+    }
+}
+
+/// Callback that is invoked with the [`WaiterProgress`] after every poll a waiter makes.
+#[derive(Clone)]
+pub struct WaiterProgressCallback(Arc<dyn Fn(&WaiterProgress) + Send + Sync>);
+
+impl WaiterProgressCallback {
+    /// Creates a new `WaiterProgressCallback` from the given function.
+    pub fn new(callback: impl Fn(&WaiterProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// Invokes the callback.
+    pub fn call(&self, progress: &WaiterProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for WaiterProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WaiterProgressCallback")
+    }
+}
+
+/// Strategy used by a waiter to calculate the delay between polls.
+///
+/// Delays are always kept between the waiter's minimum and maximum delay, and are shortened
+/// so that the final poll happens before the maximum wait time is exceeded.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub enum BackoffStrategy {
+    /// Jittered exponential backoff, as described by the [Smithy waiter spec].
+    ///
+    /// Delays are calculated in whole seconds, so waiters with a minimum delay of less than one
+    /// second poll at a fixed interval of the minimum delay instead, as with [`Fixed`](Self::Fixed).
+    ///
+    /// [Smithy waiter spec]: https://smithy.io/2.0/additional-specs/waiters.html#waiter-retries
+    #[default]
+    JitteredExponential,
+    /// Poll at a fixed interval of the minimum delay.
+    Fixed,
+    /// Calculate the delay before the next poll from the number of polls made so far.
+    Custom(CustomBackoff),
+}
+
+impl BackoffStrategy {
+    /// Creates a custom backoff strategy from a function that receives the number of polls made
+    /// so far and returns the delay before the next poll.
+    pub fn custom(delay: impl Fn(u32) -> Duration + Send + Sync + 'static) -> Self {
+        Self::Custom(CustomBackoff(Arc::new(delay)))
+    }
+}
+
+/// Delay function for [`BackoffStrategy::Custom`].
+#[derive(Clone)]
+pub struct CustomBackoff(Arc<dyn Fn(u32) -> Duration + Send + Sync>);
+
+impl CustomBackoff {
+    /// Returns the delay before the next poll after `attempt` polls have been made.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        (self.0)(attempt)
+    }
+}
+
+impl fmt::Debug for CustomBackoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomBackoff")
+    }
+}
+
+/// Options for a single call to a waiter.
+///
+/// The minimum and maximum delays default to the values modeled for the waiter.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct WaitOptions {
+    max_wait: Duration,
+    min_delay: Option<Duration>,
+    max_delay: Option<Duration>,
+    backoff: BackoffStrategy,
+    progress_callback: Option<WaiterProgressCallback>,
+}
+
+impl WaitOptions {
+    /// Creates `WaitOptions` that wait for at most `max_wait`.
+    pub fn new(max_wait: Duration) -> Self {
+        Self {
+            max_wait,
+            min_delay: None,
+            max_delay: None,
+            backoff: BackoffStrategy::default(),
+            progress_callback: None,
+        }
+    }
+
+    /// Overrides the modeled minimum delay between polls.
+    pub fn min_delay(mut self, min_delay: Duration) -> Self {
+        self.min_delay = Some(min_delay);
+        self
+    }
+
+    /// Overrides the modeled maximum delay between polls.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Sets the strategy used to calculate the delay between polls.
+    pub fn backoff(mut self, backoff: BackoffStrategy) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets a callback that is invoked with the waiter's progress after every poll.
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(&WaiterProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress_callback = Some(WaiterProgressCallback::new(callback));
+        self
+    }
+
+    /// Returns the maximum total time to wait.
+    pub fn get_max_wait(&self) -> Duration {
+        self.max_wait
+    }
+
+    /// Returns the minimum delay between polls, if it was overridden.
+    pub fn get_min_delay(&self) -> Option<Duration> {
+        self.min_delay
+    }
+
+    /// Returns the maximum delay between polls, if it was overridden.
+    pub fn get_max_delay(&self) -> Option<Duration> {
+        self.max_delay
+    }
+
+    /// Returns the strategy used to calculate the delay between polls.
+    pub fn get_backoff(&self) -> &BackoffStrategy {
+        &self.backoff
+    }
+
+    /// Returns the progress callback, if one was set.
+    pub fn get_progress_callback(&self) -> Option<&WaiterProgressCallback> {
+        self.progress_callback.as_ref()
+    }
+}
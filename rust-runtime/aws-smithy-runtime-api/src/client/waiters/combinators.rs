@@ -0,0 +1,636 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Combinators for waiting on several waiters at once.
+//!
+//! [`all_of`] waits until every waiter in a group succeeds, and [`first_of`] waits until any
+//! of them does. The waiters in a group run concurrently and share a single maximum wait time.
+//! The group is raced against a sleep for that time, so the group as a whole never waits longer
+//! than that, even if one of its waiters doesn't enforce the maximum wait time itself. Waiters
+//! from different clients and for different operations can be combined:
+//!
+//! ```no_run
+//! # async fn example(sleep_impl: aws_smithy_async::rt::sleep::SharedAsyncSleep) -> Result<(), aws_smithy_runtime_api::client::waiters::combinators::WaiterGroupError> {
+//! # async fn wait_until_instance_running(_: std::time::Duration) -> Result<(), std::io::Error> { Ok(()) }
+//! # async fn wait_until_instance_status_ok(_: std::time::Duration) -> Result<(), std::io::Error> { Ok(()) }
+//! use aws_smithy_runtime_api::client::waiters::combinators::all_of;
+//! use std::time::Duration;
+//!
+//! // With an SDK client, each waiter would be a closure like:
+//! // `|max_wait| client.wait_until_instance_running().instance_ids(id).wait(max_wait)`
+//! // and the sleep implementation would be `client.config().sleep_impl()`.
+//! let (running, status_ok) = all_of(
+//!     sleep_impl,
+//!     Duration::from_secs(300),
+//!     (wait_until_instance_running, wait_until_instance_status_ok),
+//! )
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::box_error::BoxError;
+use aws_smithy_async::rt::sleep::AsyncSleep;
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use sealed::Slot;
+
+/// A waiter that can be combined with other waiters using [`all_of`] and [`first_of`].
+///
+/// This is implemented for any function that takes the maximum wait time and returns a waiter
+/// future, such as `|max_wait| client.wait_until_bucket_exists().bucket("my-bucket").wait(max_wait)`.
+pub trait Waiter {
+    /// The output of the waiter when it succeeds.
+    type Output;
+    /// The error returned by the waiter when it fails.
+    type Error: Into<BoxError>;
+    /// The future that waits.
+    type Future: Future<Output = Result<Self::Output, Self::Error>>;
+
+    /// Starts waiting for at most `max_wait`.
+    fn wait(self, max_wait: Duration) -> Self::Future;
+}
+
+impl<F, Fut, T, E> Waiter for F
+where
+    F: FnOnce(Duration) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Into<BoxError>,
+{
+    type Output = T;
+    type Error = E;
+    type Future = Fut;
+
+    fn wait(self, max_wait: Duration) -> Self::Future {
+        self(max_wait)
+    }
+}
+
+/// A group of [`Waiter`]s that can be waited on with [`all_of`].
+///
+/// This is implemented for tuples of up to eight waiters, and for a `Vec` of waiters of the same type.
+pub trait AllOf: sealed::Sealed {
+    /// The outputs of every waiter in the group.
+    type Outputs;
+
+    #[doc(hidden)]
+    type Slots;
+
+    #[doc(hidden)]
+    fn start(self, max_wait: Duration) -> Self::Slots;
+
+    #[doc(hidden)]
+    fn poll_all(
+        slots: &mut Self::Slots,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Self::Outputs, WaiterGroupError>>;
+}
+
+/// A group of [`Waiter`]s with the same output that can be waited on with [`first_of`].
+///
+/// This is implemented for tuples of up to eight waiters, and for a `Vec` of waiters of the same type.
+/// When combining waiters with different outputs, map their outputs to a common type first.
+pub trait FirstOf<T>: sealed::Sealed {
+    #[doc(hidden)]
+    type Slots;
+
+    #[doc(hidden)]
+    fn start(self, max_wait: Duration) -> Self::Slots;
+
+    #[doc(hidden)]
+    fn poll_first(
+        slots: &mut Self::Slots,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(usize, T), WaiterGroupError>>;
+}
+
+/// Waits until every waiter in the group succeeds.
+///
+/// All of the waiters are started at once with the same `max_wait`. As soon as one of them
+/// fails, the others are dropped and its error is returned. Otherwise, the outputs of
+/// every waiter are returned in the same order as the group.
+///
+/// If the waiters haven't all succeeded once `sleep_impl` has slept for `max_wait`, they are
+/// dropped and an error is returned.
+pub async fn all_of<G: AllOf>(
+    sleep_impl: impl AsyncSleep,
+    max_wait: Duration,
+    group: G,
+) -> Result<G::Outputs, WaiterGroupError> {
+    let mut slots = group.start(max_wait);
+    let mut deadline = pin!(sleep_impl.sleep(max_wait));
+    poll_fn(|cx| match G::poll_all(&mut slots, cx) {
+        Poll::Ready(result) => Poll::Ready(result),
+        Poll::Pending => deadline
+            .as_mut()
+            .poll(cx)
+            .map(|()| Err(WaiterGroupError::exceeded_max_wait(max_wait))),
+    })
+    .await
+}
+
+/// Waits until any waiter in the group succeeds.
+///
+/// All of the waiters are started at once with the same `max_wait`. As soon as one of them
+/// succeeds, the others are dropped and its index in the group is returned with its output.
+/// Waiters that fail don't stop the others from waiting; if every waiter fails, the error of
+/// the last waiter to fail is returned.
+///
+/// If none of the waiters has succeeded once `sleep_impl` has slept for `max_wait`, they are
+/// dropped and an error is returned.
+///
+/// # Panics
+///
+/// Panics if the group is empty.
+pub async fn first_of<T, G: FirstOf<T>>(
+    sleep_impl: impl AsyncSleep,
+    max_wait: Duration,
+    group: G,
+) -> Result<(usize, T), WaiterGroupError> {
+    let mut slots = group.start(max_wait);
+    let mut deadline = pin!(sleep_impl.sleep(max_wait));
+    poll_fn(|cx| match G::poll_first(&mut slots, cx) {
+        Poll::Ready(result) => Poll::Ready(result),
+        Poll::Pending => deadline
+            .as_mut()
+            .poll(cx)
+            .map(|()| Err(WaiterGroupError::exceeded_max_wait(max_wait))),
+    })
+    .await
+}
+
+#[derive(Debug)]
+enum WaiterGroupErrorKind {
+    WaiterFailed { index: usize, source: BoxError },
+    ExceededMaxWait { max_wait: Duration },
+}
+
+/// Failure of a group of waiters passed to [`all_of`] or [`first_of`].
+///
+/// Either one of the waiters failed, or the group exceeded its maximum wait time.
+#[derive(Debug)]
+pub struct WaiterGroupError {
+    kind: WaiterGroupErrorKind,
+}
+
+impl WaiterGroupError {
+    fn new(index: usize, source: BoxError) -> Self {
+        Self {
+            kind: WaiterGroupErrorKind::WaiterFailed { index, source },
+        }
+    }
+
+    fn exceeded_max_wait(max_wait: Duration) -> Self {
+        Self {
+            kind: WaiterGroupErrorKind::ExceededMaxWait { max_wait },
+        }
+    }
+
+    /// Returns the index of the waiter that failed within its group, or `None` if the group
+    /// exceeded its maximum wait time.
+    pub fn index(&self) -> Option<usize> {
+        match &self.kind {
+            WaiterGroupErrorKind::WaiterFailed { index, .. } => Some(*index),
+            WaiterGroupErrorKind::ExceededMaxWait { .. } => None,
+        }
+    }
+
+    /// Returns true if the group exceeded its maximum wait time.
+    pub fn is_exceeded_max_wait(&self) -> bool {
+        matches!(self.kind, WaiterGroupErrorKind::ExceededMaxWait { .. })
+    }
+
+    /// Grants ownership of the error of the waiter that failed, or returns `None` if the group
+    /// exceeded its maximum wait time.
+    ///
+    /// For generated waiters, this can be downcast to a
+    /// [`WaiterError`](crate::client::waiters::error::WaiterError).
+    pub fn into_source(self) -> Option<BoxError> {
+        match self.kind {
+            WaiterGroupErrorKind::WaiterFailed { source, .. } => Some(source),
+            WaiterGroupErrorKind::ExceededMaxWait { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for WaiterGroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            WaiterGroupErrorKind::WaiterFailed { index, .. } => {
+                write!(f, "waiter {index} in the group failed")
+            }
+            WaiterGroupErrorKind::ExceededMaxWait { max_wait } => write!(
+                f,
+                "the waiter group exceeded its max wait time of {max_wait:?}"
+            ),
+        }
+    }
+}
+
+impl StdError for WaiterGroupError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.kind {
+            WaiterGroupErrorKind::WaiterFailed { source, .. } => Some(source.as_ref()),
+            WaiterGroupErrorKind::ExceededMaxWait { .. } => None,
+        }
+    }
+}
+
+trait PollSlot {
+    fn is_waiting(&self) -> bool;
+
+    /// Polls a waiting slot, returning `Ready` once its waiter has finished.
+    fn poll_waiter(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>>;
+}
+
+impl<W: Waiter> PollSlot for Slot<W> {
+    fn is_waiting(&self) -> bool {
+        matches!(self, Self::Waiting(_))
+    }
+
+    fn poll_waiter(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        let Self::Waiting(future) = self else {
+            unreachable!("only waiting slots are polled")
+        };
+        match future.as_mut().poll(cx) {
+            Poll::Ready(Ok(output)) => {
+                *self = Self::Succeeded(output);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(err)) => {
+                *self = Self::Failed;
+                Poll::Ready(Err(err.into()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+fn poll_all(
+    slots: &mut [&mut dyn PollSlot],
+    cx: &mut Context<'_>,
+) -> Poll<Result<(), WaiterGroupError>> {
+    let mut done = true;
+    for (index, slot) in slots.iter_mut().enumerate() {
+        if !slot.is_waiting() {
+            continue;
+        }
+        match slot.poll_waiter(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(WaiterGroupError::new(index, err))),
+            Poll::Pending => done = false,
+        }
+    }
+    if done {
+        Poll::Ready(Ok(()))
+    } else {
+        Poll::Pending
+    }
+}
+
+fn poll_first(
+    slots: &mut [&mut dyn PollSlot],
+    cx: &mut Context<'_>,
+) -> Poll<Result<usize, WaiterGroupError>> {
+    assert!(!slots.is_empty(), "`first_of` requires at least one waiter");
+    let mut last_failure = None;
+    for (index, slot) in slots.iter_mut().enumerate() {
+        if !slot.is_waiting() {
+            continue;
+        }
+        match slot.poll_waiter(cx) {
+            Poll::Ready(Ok(())) => return Poll::Ready(Ok(index)),
+            Poll::Ready(Err(err)) => last_failure = Some(WaiterGroupError::new(index, err)),
+            Poll::Pending => {}
+        }
+    }
+    if slots.iter().any(|slot| slot.is_waiting()) {
+        return Poll::Pending;
+    }
+    match last_failure {
+        Some(err) => Poll::Ready(Err(err)),
+        None => unreachable!("failures are reported by the poll that observes them"),
+    }
+}
+
+mod sealed {
+    use super::Waiter;
+    use std::pin::Pin;
+    use std::time::Duration;
+
+    pub trait Sealed {}
+
+    pub enum Slot<W: Waiter> {
+        Waiting(Pin<Box<W::Future>>),
+        Succeeded(W::Output),
+        Failed,
+        Taken,
+    }
+
+    impl<W: Waiter> Slot<W> {
+        pub(super) fn new(waiter: W, max_wait: Duration) -> Self {
+            Self::Waiting(Box::pin(waiter.wait(max_wait)))
+        }
+
+        pub(super) fn take(&mut self) -> W::Output {
+            match std::mem::replace(self, Self::Taken) {
+                Self::Succeeded(output) => output,
+                _ => unreachable!("only successful waiters have an output"),
+            }
+        }
+    }
+}
+
+macro_rules! impl_waiter_group {
+    ($($waiter:ident $index:tt),+) => {
+        impl<$($waiter: Waiter),+> sealed::Sealed for ($($waiter,)+) {}
+
+        impl<$($waiter: Waiter),+> AllOf for ($($waiter,)+) {
+            type Outputs = ($($waiter::Output,)+);
+            type Slots = ($(Slot<$waiter>,)+);
+
+            fn start(self, max_wait: Duration) -> Self::Slots {
+                ($(Slot::new(self.$index, max_wait),)+)
+            }
+
+            fn poll_all(
+                slots: &mut Self::Slots,
+                cx: &mut Context<'_>,
+            ) -> Poll<Result<Self::Outputs, WaiterGroupError>> {
+                let ready = poll_all(&mut [$(&mut slots.$index as &mut dyn PollSlot),+], cx);
+                ready.map_ok(|()| ($(slots.$index.take(),)+))
+            }
+        }
+
+        impl<T, $($waiter: Waiter<Output = T>),+> FirstOf<T> for ($($waiter,)+) {
+            type Slots = ($(Slot<$waiter>,)+);
+
+            fn start(self, max_wait: Duration) -> Self::Slots {
+                ($(Slot::new(self.$index, max_wait),)+)
+            }
+
+            fn poll_first(
+                slots: &mut Self::Slots,
+                cx: &mut Context<'_>,
+            ) -> Poll<Result<(usize, T), WaiterGroupError>> {
+                let ready = poll_first(&mut [$(&mut slots.$index as &mut dyn PollSlot),+], cx);
+                ready.map_ok(|index| match index {
+                    $($index => (index, slots.$index.take()),)+
+                    _ => unreachable!("index is within the group"),
+                })
+            }
+        }
+    };
+}
+
+impl_waiter_group!(A 0);
+impl_waiter_group!(A 0, B 1);
+impl_waiter_group!(A 0, B 1, C 2);
+impl_waiter_group!(A 0, B 1, C 2, D 3);
+impl_waiter_group!(A 0, B 1, C 2, D 3, E 4);
+impl_waiter_group!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_waiter_group!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_waiter_group!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<W: Waiter> sealed::Sealed for Vec<W> {}
+
+impl<W: Waiter> AllOf for Vec<W> {
+    type Outputs = Vec<W::Output>;
+    type Slots = Vec<Slot<W>>;
+
+    fn start(self, max_wait: Duration) -> Self::Slots {
+        self.into_iter()
+            .map(|waiter| Slot::new(waiter, max_wait))
+            .collect()
+    }
+
+    fn poll_all(
+        slots: &mut Self::Slots,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Self::Outputs, WaiterGroupError>> {
+        let mut pollable: Vec<&mut dyn PollSlot> = slots
+            .iter_mut()
+            .map(|slot| slot as &mut dyn PollSlot)
+            .collect();
+        let ready = poll_all(&mut pollable, cx);
+        ready.map_ok(|()| slots.iter_mut().map(Slot::take).collect())
+    }
+}
+
+impl<W: Waiter> FirstOf<W::Output> for Vec<W> {
+    type Slots = Vec<Slot<W>>;
+
+    fn start(self, max_wait: Duration) -> Self::Slots {
+        AllOf::start(self, max_wait)
+    }
+
+    fn poll_first(
+        slots: &mut Self::Slots,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(usize, W::Output), WaiterGroupError>> {
+        let mut pollable: Vec<&mut dyn PollSlot> = slots
+            .iter_mut()
+            .map(|slot| slot as &mut dyn PollSlot)
+            .collect();
+        let ready = poll_first(&mut pollable, cx);
+        ready.map_ok(|index| (index, slots[index].take()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::rt::sleep::Sleep;
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::oneshot;
+
+    /// A sleep that never finishes.
+    #[derive(Debug)]
+    struct NeverSleep;
+
+    impl AsyncSleep for NeverSleep {
+        fn sleep(&self, _duration: Duration) -> Sleep {
+            Sleep::new(std::future::pending())
+        }
+    }
+
+    /// A sleep that finishes once its sender is dropped, and records how long it was asked to sleep.
+    #[derive(Debug)]
+    struct ManualSleep {
+        done: Mutex<Option<oneshot::Receiver<()>>>,
+        duration: Arc<Mutex<Option<Duration>>>,
+    }
+
+    impl ManualSleep {
+        fn new() -> (oneshot::Sender<()>, Self) {
+            let (tx, rx) = oneshot::channel();
+            let sleep = Self {
+                done: Mutex::new(Some(rx)),
+                duration: Default::default(),
+            };
+            (tx, sleep)
+        }
+    }
+
+    impl AsyncSleep for ManualSleep {
+        fn sleep(&self, duration: Duration) -> Sleep {
+            *self.duration.lock().unwrap() = Some(duration);
+            let done = self.done.lock().unwrap().take().expect("only sleeps once");
+            Sleep::new(async move {
+                let _ = done.await;
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn all_of_waits_for_every_waiter() {
+        let (tx_a, rx_a) = oneshot::channel::<Result<u32, String>>();
+        let (tx_b, rx_b) = oneshot::channel::<Result<&'static str, String>>();
+        let group = tokio::spawn(all_of(
+            NeverSleep,
+            Duration::from_secs(10),
+            (
+                move |_| async move { rx_a.await.unwrap() },
+                move |_| async move { rx_b.await.unwrap() },
+            ),
+        ));
+
+        tx_b.send(Ok("b")).unwrap();
+        tx_a.send(Ok(1)).unwrap();
+        assert_eq!((1, "b"), group.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn all_of_fails_fast() {
+        let (tx_a, rx_a) = oneshot::channel::<Result<(), String>>();
+        let (tx_b, rx_b) = oneshot::channel::<Result<(), String>>();
+        let group = tokio::spawn(all_of(
+            NeverSleep,
+            Duration::from_secs(10),
+            (
+                move |_| async move { rx_a.await.unwrap() },
+                move |_| async move { rx_b.await.unwrap() },
+            ),
+        ));
+
+        tx_b.send(Err("failure state".into())).unwrap();
+        let err = group.await.unwrap().unwrap_err();
+        assert_eq!(Some(1), err.index());
+        assert_eq!("waiter 1 in the group failed", err.to_string());
+        assert_eq!("failure state", err.into_source().unwrap().to_string());
+        // the waiter that was still waiting was dropped
+        assert!(tx_a.is_closed());
+    }
+
+    #[tokio::test]
+    async fn waiters_share_the_max_wait() {
+        let group: Vec<_> = (0..3)
+            .map(|_| |max_wait| async move { Ok::<_, String>(max_wait) })
+            .collect();
+        let outputs = all_of(NeverSleep, Duration::from_secs(30), group)
+            .await
+            .unwrap();
+        assert_eq!(vec![Duration::from_secs(30); 3], outputs);
+    }
+
+    #[tokio::test]
+    async fn first_of_returns_the_first_success() {
+        let (mut senders, receivers): (Vec<_>, Vec<_>) = (0..3)
+            .map(|_| oneshot::channel::<Result<&'static str, String>>())
+            .unzip();
+        let group = tokio::spawn(first_of(
+            NeverSleep,
+            Duration::from_secs(10),
+            receivers
+                .into_iter()
+                .map(|rx| move |_| async move { rx.await.unwrap() })
+                .collect::<Vec<_>>(),
+        ));
+
+        senders.pop().unwrap().send(Ok("third")).unwrap();
+        assert_eq!((2, "third"), group.await.unwrap().unwrap());
+        assert!(senders[0].is_closed());
+        assert!(senders[1].is_closed());
+    }
+
+    #[tokio::test]
+    async fn first_of_waits_past_failures() {
+        let result = first_of(
+            NeverSleep,
+            Duration::from_secs(10),
+            (
+                |_| async { Err::<u32, _>("failure state") },
+                |_| async { Ok::<_, &str>(2) },
+            ),
+        )
+        .await;
+        assert_eq!((1, 2), result.unwrap());
+
+        let err = first_of(
+            NeverSleep,
+            Duration::from_secs(10),
+            (
+                |_| async { Err::<u32, _>("first failure") },
+                |_| async { Err::<u32, _>("second failure") },
+            ),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(Some(1), err.index());
+        assert_eq!("second failure", err.into_source().unwrap().to_string());
+    }
+
+    #[tokio::test]
+    async fn groups_are_raced_against_the_max_wait() {
+        // The waiters don't enforce the max wait time themselves
+        let (tx_a, rx_a) = oneshot::channel::<Result<(), String>>();
+        let (tx_b, rx_b) = oneshot::channel::<Result<(), String>>();
+        let (elapse, sleep_impl) = ManualSleep::new();
+        let sleep_duration = sleep_impl.duration.clone();
+        let group = tokio::spawn(all_of(
+            sleep_impl,
+            Duration::from_secs(10),
+            (
+                move |_| async move { rx_a.await.unwrap() },
+                move |_| async move { rx_b.await.unwrap() },
+            ),
+        ));
+        tx_a.send(Ok(())).unwrap();
+        drop(elapse);
+
+        let err = group.await.unwrap().unwrap_err();
+        assert!(err.is_exceeded_max_wait());
+        assert_eq!(None, err.index());
+        assert_eq!(
+            "the waiter group exceeded its max wait time of 10s",
+            err.to_string()
+        );
+        assert_eq!(
+            Some(Duration::from_secs(10)),
+            *sleep_duration.lock().unwrap()
+        );
+        // the waiter that was still waiting was dropped
+        assert!(tx_b.is_closed());
+
+        let (tx, rx) = oneshot::channel::<Result<u32, String>>();
+        let (elapse, sleep_impl) = ManualSleep::new();
+        let group = tokio::spawn(first_of(
+            sleep_impl,
+            Duration::from_secs(10),
+            (
+                |_| async { Err::<u32, _>("failure state".to_owned()) },
+                move |_| async move { rx.await.unwrap() },
+            ),
+        ));
+        drop(elapse);
+        assert!(group.await.unwrap().unwrap_err().is_exceeded_max_wait());
+        assert!(tx.is_closed());
+    }
+}
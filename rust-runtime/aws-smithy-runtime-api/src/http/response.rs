@@ -193,6 +193,11 @@ impl<B> Response<B> {
     pub fn add_extension<T: Send + Sync + Clone + 'static>(&mut self, extension: T) {
         self.extensions.insert(extension);
     }
+
+    /// Returns a reference to the response extension of type `T`, if one has been added
+    pub fn extension<T: Send + Sync + Clone + 'static>(&self) -> Option<&T> {
+        self.extensions.get::<T>()
+    }
 }
 
 impl Response<SdkBody> {
@@ -267,6 +272,14 @@ mod test {
         assert_eq!(200, http0.status().as_u16());
     }
 
+    #[test]
+    fn extensions_can_be_read() {
+        let mut rsp = Response::new(StatusCode::try_from(200).unwrap(), SdkBody::empty());
+        assert_eq!(None, rsp.extension::<u32>());
+        rsp.add_extension(5_u32);
+        assert_eq!(Some(&5_u32), rsp.extension::<u32>());
+    }
+
     macro_rules! resp_eq {
         ($a: expr, $b: expr) => {{
             assert_eq!($a.status(), $b.status(), "status code mismatch");
@@ -16,7 +16,7 @@ use aws_smithy_runtime_api::client::runtime_components::{
     GetIdentityResolver, RuntimeComponents, RuntimeComponentsBuilder,
 };
 use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
-use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::config_bag::{ConfigBag, FrozenLayer, Layer, Storable, StoreReplace};
 use std::borrow::Cow;
 
 /// Auth scheme ID for "no auth".
@@ -59,6 +59,66 @@ impl RuntimePlugin for NoAuthRuntimePlugin {
     }
 }
 
+/// Config bag value that forces the orchestrator to use the "no auth" scheme.
+///
+/// When set to `true`, the auth scheme options resolved for an operation are replaced with
+/// [`NO_AUTH_SCHEME_ID`] so that the request is sent without being signed. This is useful for
+/// calling unauthenticated endpoints, such as public S3 buckets, without configuring fake
+/// credentials.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ForceNoAuth(pub bool);
+
+impl Storable for ForceNoAuth {
+    type Storer = StoreReplace<Self>;
+}
+
+/// A [`RuntimePlugin`] that forces requests to be sent without authentication.
+///
+/// In addition to storing [`ForceNoAuth`] in the config bag, this plugin registers the "no auth"
+/// identity resolver and auth scheme, so it works for services whose model doesn't allow
+/// unauthenticated requests.
+#[derive(Debug)]
+pub struct ForceNoAuthRuntimePlugin {
+    config: FrozenLayer,
+    runtime_components: RuntimeComponentsBuilder,
+}
+
+impl Default for ForceNoAuthRuntimePlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ForceNoAuthRuntimePlugin {
+    /// Creates a new `ForceNoAuthRuntimePlugin`.
+    pub fn new() -> Self {
+        let mut layer = Layer::new("ForceNoAuthRuntimePlugin");
+        layer.store_put(ForceNoAuth(true));
+        Self {
+            config: layer.freeze(),
+            runtime_components: RuntimeComponentsBuilder::new("ForceNoAuthRuntimePlugin")
+                .with_identity_resolver(
+                    NO_AUTH_SCHEME_ID,
+                    SharedIdentityResolver::new(NoAuthIdentityResolver::new()),
+                )
+                .with_auth_scheme(SharedAuthScheme::new(NoAuthScheme::new())),
+        }
+    }
+}
+
+impl RuntimePlugin for ForceNoAuthRuntimePlugin {
+    fn config(&self) -> Option<FrozenLayer> {
+        Some(self.config.clone())
+    }
+
+    fn runtime_components(
+        &self,
+        _: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        Cow::Borrowed(&self.runtime_components)
+    }
+}
+
 /// The "no auth" auth scheme.
 ///
 /// The orchestrator requires an auth scheme, so Smithy's `@optionalAuth` trait is implemented
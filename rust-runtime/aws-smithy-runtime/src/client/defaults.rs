@@ -65,6 +65,20 @@ pub fn default_http_client_plugin() -> Option<SharedRuntimePlugin> {
 pub fn default_http_client_plugin_v2(
     behavior_version: BehaviorVersion,
 ) -> Option<SharedRuntimePlugin> {
+    default_http_client(behavior_version).map(|default| {
+        default_plugin("default_http_client_plugin", |components| {
+            components.with_http_client(Some(default))
+        })
+        .into_shared()
+    })
+}
+
+/// Creates the default HTTPS client for the given behavior version.
+///
+/// This is the client [`default_http_client_plugin_v2`] provides. It can be used to share one
+/// client, and its connection pool, between several clients. Returns `None` if no default HTTP
+/// client is enabled with crate features.
+pub fn default_http_client(behavior_version: BehaviorVersion) -> Option<SharedHttpClient> {
     let mut _default: Option<SharedHttpClient> = None;
 
     #[allow(deprecated)]
@@ -97,12 +111,28 @@ pub fn default_http_client_plugin_v2(
         }
     }
 
-    _default.map(|default| {
-        default_plugin("default_http_client_plugin", |components| {
-            components.with_http_client(Some(default))
-        })
-        .into_shared()
-    })
+    _default
+}
+
+/// Creates the default HTTPS client for the given behavior version, sending requests through
+/// `proxy_config`.
+///
+/// This is the client [`default_http_client`] creates, with the same TLS provider, except that
+/// `proxy_config` is used instead of the proxy settings from the environment. Returns `None` if
+/// the default client for `behavior_version` doesn't support proxies.
+#[cfg(feature = "default-https-client")]
+pub fn default_http_client_with_proxy(
+    behavior_version: BehaviorVersion,
+    proxy_config: aws_smithy_http_client::proxy::ProxyConfig,
+) -> Option<SharedHttpClient> {
+    #[allow(deprecated)]
+    if !behavior_version.is_at_least(BehaviorVersion::v2025_01_17()) {
+        return None;
+    }
+    let opts = crate::client::http::DefaultClientOptions::default()
+        .with_behavior_version(behavior_version)
+        .with_proxy_config(proxy_config);
+    crate::client::http::default_https_client(opts)
 }
 
 /// Runtime plugin that provides a default async sleep implementation.
@@ -353,6 +383,30 @@ mod tests {
         config
     }
 
+    #[test]
+    #[cfg(feature = "default-https-client")]
+    #[allow(deprecated)]
+    fn default_http_client_with_proxy() {
+        use aws_smithy_http_client::proxy::ProxyConfig;
+        use aws_smithy_runtime_api::client::http::HttpClient;
+
+        let proxy_config = || ProxyConfig::all("http://proxy.example.com:8080").unwrap();
+        let client =
+            super::default_http_client_with_proxy(BehaviorVersion::latest(), proxy_config())
+                .expect("the latest default client supports proxies");
+        assert_eq!(
+            default_http_client(BehaviorVersion::latest())
+                .unwrap()
+                .connector_metadata(),
+            client.connector_metadata()
+        );
+        assert!(super::default_http_client_with_proxy(
+            BehaviorVersion::v2024_03_28(),
+            proxy_config()
+        )
+        .is_none());
+    }
+
     #[test]
     #[allow(deprecated)]
     fn v2024_03_28_stalled_stream_protection_difference() {
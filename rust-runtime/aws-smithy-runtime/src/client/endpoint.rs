@@ -5,6 +5,12 @@
 
 //! Code for applying endpoints to a request.
 
+mod cache;
+pub use cache::ResolvedEndpointCache;
+
+mod failover;
+pub use failover::{FailoverEndpoints, FailoverEndpointsBuilder};
+
 use aws_smithy_runtime_api::client::endpoint::{error::InvalidEndpointError, EndpointPrefix};
 use std::borrow::Cow;
 use std::result::Result as StdResult;
@@ -0,0 +1,308 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A cache for resolved endpoints.
+
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::endpoint::Endpoint;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_CAPACITY: usize = 128;
+
+/// Bounded cache of endpoints keyed by the parameters they were resolved from
+///
+/// Endpoint rules are a pure function of the endpoint parameters, and the parameters rarely change
+/// between requests made by the same client. Memoizing the result avoids evaluating the rules again
+/// for every request.
+///
+/// Once the cache holds `capacity` endpoints, expired entries are removed first and then the
+/// oldest entries. Failed resolutions are never cached. Clones of the cache share the same entries.
+pub struct ResolvedEndpointCache<K> {
+    capacity: usize,
+    ttl: Option<Duration>,
+    time_source: SharedTimeSource,
+    entries: Arc<Mutex<Entries<K>>>,
+}
+
+struct Entries<K> {
+    endpoints: HashMap<K, CachedEndpoint>,
+    insertion_order: VecDeque<K>,
+}
+
+struct CachedEndpoint {
+    endpoint: Endpoint,
+    expires_at: Option<SystemTime>,
+}
+
+impl CachedEndpoint {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at.map(|at| at <= now).unwrap_or_default()
+    }
+}
+
+impl<K> ResolvedEndpointCache<K> {
+    /// Creates a cache that holds up to `capacity` endpoints
+    ///
+    /// Cached endpoints don't expire unless a TTL is set with [`ResolvedEndpointCache::with_ttl`].
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ttl: None,
+            time_source: SharedTimeSource::default(),
+            entries: Arc::new(Mutex::new(Entries {
+                endpoints: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Creates a cache that never stores endpoints, so every request resolves its endpoint
+    pub fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    /// Expire cached endpoints `ttl` after they were resolved
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Use `time_source` to determine when cached endpoints expire
+    pub fn with_time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = SharedTimeSource::new(time_source);
+        self
+    }
+
+    /// The maximum number of endpoints held by this cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// How long endpoints are cached for, if they expire
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+}
+
+impl<K> ResolvedEndpointCache<K>
+where
+    K: Clone + Eq + Hash,
+{
+    /// Returns the cached endpoint for `key`, or resolves it with `resolve` and caches the result
+    ///
+    /// The lock on the cache isn't held while `resolve` runs.
+    pub fn get_or_resolve(
+        &self,
+        key: &K,
+        resolve: impl FnOnce() -> Result<Endpoint, BoxError>,
+    ) -> Result<Endpoint, BoxError> {
+        if self.capacity == 0 {
+            return resolve();
+        }
+        let now = self.time_source.now();
+        if let Some(cached) = self.entries.lock().unwrap().endpoints.get(key) {
+            if !cached.is_expired(now) {
+                return Ok(cached.endpoint.clone());
+            }
+        }
+
+        let endpoint = resolve()?;
+        let cached = CachedEndpoint {
+            endpoint: endpoint.clone(),
+            expires_at: self.ttl.map(|ttl| now + ttl),
+        };
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(existing) = entries.endpoints.get_mut(key) {
+            *existing = cached;
+            return Ok(endpoint);
+        }
+        if entries.endpoints.len() >= self.capacity {
+            entries.evict(now, self.capacity - 1);
+        }
+        entries.endpoints.insert(key.clone(), cached);
+        entries.insertion_order.push_back(key.clone());
+        Ok(endpoint)
+    }
+
+    /// Removes all cached endpoints
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.endpoints.clear();
+        entries.insertion_order.clear();
+    }
+
+    /// The number of endpoints currently cached, including expired ones that haven't been evicted yet
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().endpoints.len()
+    }
+
+    /// Returns true if no endpoints are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K> Entries<K>
+where
+    K: Eq + Hash,
+{
+    /// Evict expired endpoints, and then the oldest ones, until at most `max_len` remain
+    fn evict(&mut self, now: SystemTime, max_len: usize) {
+        let endpoints = &mut self.endpoints;
+        endpoints.retain(|_, cached| !cached.is_expired(now));
+        self.insertion_order
+            .retain(|key| endpoints.contains_key(key));
+        while self.endpoints.len() > max_len {
+            match self.insertion_order.pop_front() {
+                Some(oldest) => {
+                    self.endpoints.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl<K> Clone for ResolvedEndpointCache<K> {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            ttl: self.ttl,
+            time_source: self.time_source.clone(),
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K> Default for ResolvedEndpointCache<K> {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl<K> fmt::Debug for ResolvedEndpointCache<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedEndpointCache")
+            .field("capacity", &self.capacity)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ResolvedEndpointCache;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_types::endpoint::Endpoint;
+    use std::cell::Cell;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn endpoint(url: &str) -> Endpoint {
+        Endpoint::builder().url(url.to_owned()).build()
+    }
+
+    #[test]
+    fn resolve_once_per_key() {
+        let cache = ResolvedEndpointCache::new(10);
+        let resolutions = Cell::new(0);
+        let resolve = |url: &'static str| {
+            let resolutions = &resolutions;
+            move || {
+                resolutions.set(resolutions.get() + 1);
+                Ok(endpoint(url))
+            }
+        };
+
+        for _ in 0..3 {
+            let resolved = cache
+                .get_or_resolve(&"a", resolve("https://a.example.com"))
+                .unwrap();
+            assert_eq!("https://a.example.com", resolved.url());
+        }
+        let resolved = cache
+            .get_or_resolve(&"b", resolve("https://b.example.com"))
+            .unwrap();
+        assert_eq!("https://b.example.com", resolved.url());
+        assert_eq!(2, resolutions.get());
+        assert_eq!(2, cache.len());
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let cache = ResolvedEndpointCache::new(10);
+        cache
+            .get_or_resolve(&"a", || Err("invalid params".into()))
+            .expect_err("resolution failed");
+        assert!(cache.is_empty());
+        let resolved = cache
+            .get_or_resolve(&"a", || Ok(endpoint("https://a.example.com")))
+            .unwrap();
+        assert_eq!("https://a.example.com", resolved.url());
+    }
+
+    #[test]
+    fn evict_oldest_entries() {
+        let cache = ResolvedEndpointCache::new(2);
+        for key in ["a", "b", "c"] {
+            cache
+                .get_or_resolve(&key, || Ok(endpoint(&format!("https://{key}.example.com"))))
+                .unwrap();
+        }
+        assert_eq!(2, cache.len());
+        let resolved = cache
+            .get_or_resolve(&"a", || Ok(endpoint("https://new-a.example.com")))
+            .unwrap();
+        assert_eq!("https://new-a.example.com", resolved.url());
+        let resolved = cache
+            .get_or_resolve(&"c", || panic!("`c` should still be cached"))
+            .unwrap();
+        assert_eq!("https://c.example.com", resolved.url());
+    }
+
+    #[test]
+    fn expire_after_ttl() {
+        let time = ManualTimeSource::new(UNIX_EPOCH);
+        let cache = ResolvedEndpointCache::new(10)
+            .with_ttl(Duration::from_secs(60))
+            .with_time_source(time.clone());
+        cache
+            .get_or_resolve(&"a", || Ok(endpoint("https://a.example.com")))
+            .unwrap();
+
+        time.advance(Duration::from_secs(30));
+        let resolved = cache
+            .get_or_resolve(&"a", || panic!("endpoint should still be cached"))
+            .unwrap();
+        assert_eq!("https://a.example.com", resolved.url());
+
+        time.advance(Duration::from_secs(30));
+        let resolved = cache
+            .get_or_resolve(&"a", || Ok(endpoint("https://new-a.example.com")))
+            .unwrap();
+        assert_eq!("https://new-a.example.com", resolved.url());
+        assert_eq!(1, cache.len());
+    }
+
+    #[test]
+    fn disabled_cache_always_resolves() {
+        let cache = ResolvedEndpointCache::disabled();
+        let resolutions = Cell::new(0);
+        for _ in 0..3 {
+            cache
+                .get_or_resolve(&"a", || {
+                    resolutions.set(resolutions.get() + 1);
+                    Ok(endpoint("https://a.example.com"))
+                })
+                .unwrap();
+        }
+        assert_eq!(3, resolutions.get());
+        assert!(cache.is_empty());
+    }
+}
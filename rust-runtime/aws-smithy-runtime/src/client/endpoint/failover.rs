@@ -0,0 +1,538 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Failover between an ordered list of endpoints with passive health checking.
+
+use crate::client::retries::classifiers::run_classifiers_on_ctx;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeTransmitInterceptorContextMut, FinalizerInterceptorContextRef, InterceptorContext,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::retries::classifiers::{RetryAction, RetryReason};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::retry::ErrorKind;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+const DEFAULT_RECOVERY_PERIOD: Duration = Duration::from_secs(30);
+
+/// Sends requests to the first healthy endpoint of an ordered list of endpoints
+///
+/// This is an interceptor that redirects requests meant for the primary (first) endpoint to a
+/// secondary endpoint while the primary is unhealthy. Endpoint resolution is unchanged, so the
+/// client must be configured to send requests to the primary endpoint, e.g. with `endpoint_url`.
+/// Since the host of a request is replaced, endpoints that use virtual-hosted style addressing
+/// (`bucket.primary.example.com`) fail over to `bucket.secondary.example.com`.
+///
+/// Health is checked passively: an endpoint is considered unhealthy once `failure_threshold`
+/// consecutive attempts failed with a connection error, a timeout, or a `5xx` response. Responses
+/// that the client's retry classifiers consider throttling errors, like S3's `503 SlowDown`, mean
+/// that the endpoint is up but busy, so they don't count as failures. Unhealthy endpoints are
+/// skipped for `recovery_period`, after which requests are sent to them again, so
+/// that the client fails back once the primary endpoint recovers. A successful attempt resets the
+/// health of an endpoint. If all endpoints are unhealthy, the one that recovers first is used.
+///
+/// Health is shared between clones, so the same `FailoverEndpoints` should be used for all
+/// operations of a client.
+///
+/// # Examples
+/// ```no_run
+/// use aws_smithy_runtime::client::endpoint::FailoverEndpoints;
+/// use std::time::Duration;
+///
+/// let failover = FailoverEndpoints::builder()
+///     .endpoint_url("https://storage-a.example.com")
+///     .endpoint_url("https://storage-b.example.com")
+///     .recovery_period(Duration::from_secs(60))
+///     .build()
+///     .expect("valid endpoint URLs");
+/// // then configure the client with `.endpoint_url("https://storage-a.example.com")`
+/// // and `.interceptor(failover)`
+/// ```
+#[derive(Clone)]
+pub struct FailoverEndpoints {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    endpoints: Vec<EndpointUrl>,
+    failure_threshold: u32,
+    recovery_period: Duration,
+    health: Mutex<Vec<Health>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct EndpointUrl {
+    scheme: String,
+    authority: String,
+}
+
+impl EndpointUrl {
+    fn parse(url: &str) -> Result<Self, BoxError> {
+        let uri: http_1x::Uri = url.parse()?;
+        match (uri.scheme_str(), uri.authority()) {
+            (Some(scheme), Some(authority)) => Ok(Self {
+                scheme: scheme.to_string(),
+                authority: authority.to_string(),
+            }),
+            _ => Err(format!("failover endpoint `{url}` must have a scheme and a host").into()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    unhealthy_until: Option<SystemTime>,
+}
+
+impl Health {
+    fn is_unhealthy(&self, now: SystemTime) -> bool {
+        self.unhealthy_until.is_some_and(|until| until > now)
+    }
+}
+
+/// How an attempt reflects on the health of the endpoint it was sent to
+#[derive(Debug, PartialEq, Eq)]
+enum AttemptHealth {
+    Healthy,
+    Unhealthy,
+    Unknown,
+}
+
+impl AttemptHealth {
+    fn of(context: &InterceptorContext, runtime_components: &RuntimeComponents) -> Self {
+        // Throttled endpoints are up, and failing over wouldn't reduce the load they're under
+        let throttled = matches!(
+            run_classifiers_on_ctx(runtime_components.retry_classifiers(), context),
+            RetryAction::RetryIndicated(RetryReason::RetryableError {
+                kind: ErrorKind::ThrottlingError,
+                ..
+            })
+        );
+        match context.response() {
+            _ if throttled => AttemptHealth::Unknown,
+            Some(response) if response.status().is_server_error() => AttemptHealth::Unhealthy,
+            Some(_) => AttemptHealth::Healthy,
+            None if context.output_or_error().is_some_and(|result| {
+                result.is_err_and(|err| err.is_connector_error() || err.is_timeout_error())
+            }) =>
+            {
+                AttemptHealth::Unhealthy
+            }
+            None => AttemptHealth::Unknown,
+        }
+    }
+}
+
+/// The endpoint that the current attempt was sent to
+#[derive(Clone, Debug)]
+struct SelectedEndpoint(usize);
+
+impl Storable for SelectedEndpoint {
+    type Storer = StoreReplace<Self>;
+}
+
+impl FailoverEndpoints {
+    /// Returns a builder for `FailoverEndpoints`
+    pub fn builder() -> FailoverEndpointsBuilder {
+        FailoverEndpointsBuilder::default()
+    }
+
+    /// Returns the index of the endpoint that the next attempt should be sent to
+    fn select(&self, now: SystemTime) -> usize {
+        let health = self.inner.health.lock().unwrap();
+        health
+            .iter()
+            .position(|health| !health.is_unhealthy(now))
+            .or_else(|| {
+                health
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, health)| health.unhealthy_until)
+                    .map(|(idx, _)| idx)
+            })
+            .unwrap_or_default()
+    }
+
+    fn report_success(&self, idx: usize) {
+        let mut health = self.inner.health.lock().unwrap();
+        health[idx] = Health::default();
+    }
+
+    fn report_failure(&self, idx: usize, now: SystemTime) {
+        let mut health = self.inner.health.lock().unwrap();
+        let health = &mut health[idx];
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= self.inner.failure_threshold {
+            tracing::debug!(
+                endpoint = %self.inner.endpoints[idx].authority,
+                "marking endpoint as unhealthy"
+            );
+            health.unhealthy_until = Some(now + self.inner.recovery_period);
+        }
+    }
+
+    /// Replaces the primary endpoint in `uri` with the endpoint at `idx`
+    ///
+    /// Returns `None` if `uri` wasn't sent to the primary endpoint.
+    fn redirect(&self, uri: &str, idx: usize) -> Option<String> {
+        let primary = &self.inner.endpoints[0];
+        let target = &self.inner.endpoints[idx];
+        let (_scheme, rest) = uri.split_once("://")?;
+        let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+        let (authority, path_and_query) = rest.split_at(authority_end);
+        let subdomain = if authority == primary.authority {
+            ""
+        } else {
+            authority
+                .strip_suffix(primary.authority.as_str())
+                .filter(|subdomain| subdomain.ends_with('.'))?
+        };
+        Some(format!(
+            "{}://{subdomain}{}{path_and_query}",
+            target.scheme, target.authority
+        ))
+    }
+}
+
+impl fmt::Debug for FailoverEndpoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FailoverEndpoints")
+            .field("endpoints", &self.inner.endpoints)
+            .field("failure_threshold", &self.inner.failure_threshold)
+            .field("recovery_period", &self.inner.recovery_period)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Intercept for FailoverEndpoints {
+    fn name(&self) -> &'static str {
+        "FailoverEndpoints"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let now = runtime_components.time_source().unwrap_or_default().now();
+        let idx = self.select(now);
+        let request = context.request_mut();
+        let redirected = match self.redirect(request.uri(), idx) {
+            Some(redirected) => redirected,
+            None => {
+                // the request isn't sent to the primary endpoint, so its health can't be tracked
+                cfg.interceptor_state().unset::<SelectedEndpoint>();
+                return Ok(());
+            }
+        };
+        if idx != 0 {
+            tracing::debug!(uri = %redirected, "failing over to a secondary endpoint");
+            request.set_uri(redirected)?;
+        }
+        cfg.interceptor_state().store_put(SelectedEndpoint(idx));
+        Ok(())
+    }
+
+    fn read_after_attempt(
+        &self,
+        context: &FinalizerInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let idx = match cfg.load::<SelectedEndpoint>() {
+            Some(selected) => selected.0,
+            None => return Ok(()),
+        };
+        cfg.interceptor_state().unset::<SelectedEndpoint>();
+
+        match AttemptHealth::of(context.inner(), runtime_components) {
+            AttemptHealth::Healthy => self.report_success(idx),
+            AttemptHealth::Unhealthy => {
+                let now = runtime_components.time_source().unwrap_or_default().now();
+                self.report_failure(idx, now);
+            }
+            AttemptHealth::Unknown => {}
+        }
+        Ok(())
+    }
+}
+
+/// Builder for [`FailoverEndpoints`]
+#[derive(Debug)]
+pub struct FailoverEndpointsBuilder {
+    endpoint_urls: Vec<String>,
+    failure_threshold: u32,
+    recovery_period: Duration,
+}
+
+impl Default for FailoverEndpointsBuilder {
+    fn default() -> Self {
+        Self {
+            endpoint_urls: Vec::new(),
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            recovery_period: DEFAULT_RECOVERY_PERIOD,
+        }
+    }
+}
+
+impl FailoverEndpointsBuilder {
+    /// Adds an endpoint URL, e.g. `https://storage-a.example.com`
+    ///
+    /// The first endpoint URL is the primary endpoint; the others are tried in the order they're
+    /// added while the endpoints before them are unhealthy.
+    pub fn endpoint_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoint_urls.push(url.into());
+        self
+    }
+
+    /// The number of consecutive failed attempts after which an endpoint is considered unhealthy
+    ///
+    /// Defaults to 3.
+    pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    /// How long an unhealthy endpoint is skipped before requests are sent to it again
+    ///
+    /// Defaults to 30 seconds.
+    pub fn recovery_period(mut self, recovery_period: Duration) -> Self {
+        self.recovery_period = recovery_period;
+        self
+    }
+
+    /// Builds `FailoverEndpoints`
+    ///
+    /// Fails if no endpoint URLs were added, or if one of them doesn't have a scheme and a host.
+    pub fn build(self) -> Result<FailoverEndpoints, BoxError> {
+        if self.endpoint_urls.is_empty() {
+            return Err("at least one failover endpoint URL is required".into());
+        }
+        let endpoints = self
+            .endpoint_urls
+            .iter()
+            .map(|url| EndpointUrl::parse(url))
+            .collect::<Result<Vec<_>, _>>()?;
+        let health = endpoints.iter().map(|_| Health::default()).collect();
+        Ok(FailoverEndpoints {
+            inner: Arc::new(Inner {
+                endpoints,
+                failure_threshold: self.failure_threshold,
+                recovery_period: self.recovery_period,
+                health: Mutex::new(health),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FailoverEndpoints;
+    use crate::client::retries::classifiers::HttpStatusCodeClassifier;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_runtime_api::client::interceptors::context::{
+        BeforeTransmitInterceptorContextMut, FinalizerInterceptorContextRef, Input,
+        InterceptorContext,
+    };
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::retries::classifiers::{ClassifyRetry, RetryAction};
+    use aws_smithy_runtime_api::client::runtime_components::{
+        RuntimeComponents, RuntimeComponentsBuilder,
+    };
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::ConfigBag;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn failover() -> FailoverEndpoints {
+        FailoverEndpoints::builder()
+            .endpoint_url("https://storage-a.example.com")
+            .endpoint_url("http://storage-b.example.com:8080")
+            .failure_threshold(2)
+            .recovery_period(Duration::from_secs(30))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn redirect_to_secondary_endpoint() {
+        let failover = failover();
+        assert_eq!(
+            Some("http://storage-b.example.com:8080/bucket/key?x-id=GetObject"),
+            failover
+                .redirect("https://storage-a.example.com/bucket/key?x-id=GetObject", 1)
+                .as_deref()
+        );
+        assert_eq!(
+            Some("http://bucket.storage-b.example.com:8080/key"),
+            failover
+                .redirect("https://bucket.storage-a.example.com/key", 1)
+                .as_deref()
+        );
+        assert_eq!(
+            Some("https://storage-a.example.com?list-type=2"),
+            failover
+                .redirect("https://storage-a.example.com?list-type=2", 0)
+                .as_deref()
+        );
+        assert_eq!(
+            None,
+            failover.redirect("https://other-storage-a.example.com/key", 1)
+        );
+        assert_eq!(None, failover.redirect("https://example.com/key", 1));
+    }
+
+    #[test]
+    fn fail_over_and_back() {
+        let failover = failover();
+        let start = UNIX_EPOCH;
+        assert_eq!(0, failover.select(start));
+
+        failover.report_failure(0, start);
+        assert_eq!(0, failover.select(start), "below the failure threshold");
+        failover.report_failure(0, start);
+        assert_eq!(1, failover.select(start));
+        assert_eq!(1, failover.select(start + Duration::from_secs(29)));
+
+        // fail back once the recovery period has passed
+        let recovered = start + Duration::from_secs(30);
+        assert_eq!(0, failover.select(recovered));
+        // a single failure after recovery marks the endpoint as unhealthy again
+        failover.report_failure(0, recovered);
+        assert_eq!(1, failover.select(recovered));
+
+        let recovered = recovered + Duration::from_secs(30);
+        assert_eq!(0, failover.select(recovered));
+        failover.report_success(0);
+        failover.report_failure(0, recovered);
+        assert_eq!(0, failover.select(recovered));
+    }
+
+    #[test]
+    fn use_endpoint_that_recovers_first_when_all_are_unhealthy() {
+        let failover = failover();
+        failover.report_failure(1, UNIX_EPOCH);
+        failover.report_failure(1, UNIX_EPOCH);
+        let later = UNIX_EPOCH + Duration::from_secs(10);
+        failover.report_failure(0, later);
+        failover.report_failure(0, later);
+        assert_eq!(1, failover.select(later));
+    }
+
+    #[test]
+    fn invalid_endpoint_urls() {
+        assert!(FailoverEndpoints::builder().build().is_err());
+        assert!(FailoverEndpoints::builder()
+            .endpoint_url("storage-a.example.com")
+            .build()
+            .is_err());
+    }
+
+    /// Classifies `503 SlowDown` responses as throttling errors, like the AWS SDK's classifiers
+    #[derive(Debug)]
+    struct SlowDownClassifier;
+
+    impl ClassifyRetry for SlowDownClassifier {
+        fn classify_retry(&self, ctx: &InterceptorContext) -> RetryAction {
+            match ctx.response() {
+                Some(response)
+                    if response.headers().get("x-amz-error-code") == Some("SlowDown") =>
+                {
+                    RetryAction::throttling_error()
+                }
+                _ => RetryAction::NoActionIndicated,
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "SlowDown"
+        }
+    }
+
+    /// Sends an attempt through the interceptor, and returns the URI it was sent to
+    fn attempt(
+        failover: &FailoverEndpoints,
+        runtime_components: &RuntimeComponents,
+        response: http_1x::Response<SdkBody>,
+    ) -> String {
+        let mut cfg = ConfigBag::base();
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let _ = ctx.take_input();
+        ctx.set_request(
+            http_1x::Request::builder()
+                .uri("https://storage-a.example.com/bucket/key")
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        ctx.enter_before_transmit_phase();
+        failover
+            .modify_before_signing(
+                &mut BeforeTransmitInterceptorContextMut::from(&mut ctx),
+                runtime_components,
+                &mut cfg,
+            )
+            .unwrap();
+        let uri = ctx.request().unwrap().uri().to_string();
+        ctx.enter_transmit_phase();
+        ctx.set_response(response.try_into().unwrap());
+        failover
+            .read_after_attempt(
+                &FinalizerInterceptorContextRef::from(&ctx),
+                runtime_components,
+                &mut cfg,
+            )
+            .unwrap();
+        uri
+    }
+
+    fn response(status: u16, error_code: Option<&str>) -> http_1x::Response<SdkBody> {
+        let mut response = http_1x::Response::builder().status(status);
+        if let Some(error_code) = error_code {
+            response = response.header("x-amz-error-code", error_code);
+        }
+        response.body(SdkBody::empty()).unwrap()
+    }
+
+    #[test]
+    fn throttling_responses_do_not_fail_over() {
+        let failover = failover();
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(ManualTimeSource::new(UNIX_EPOCH)))
+            .with_retry_classifier(HttpStatusCodeClassifier::default())
+            .with_retry_classifier(SlowDownClassifier)
+            .build()
+            .unwrap();
+
+        let primary = "https://storage-a.example.com/bucket/key";
+        let secondary = "http://storage-b.example.com:8080/bucket/key";
+        for _ in 0..3 {
+            let slow_down = response(503, Some("SlowDown"));
+            assert_eq!(primary, attempt(&failover, &runtime_components, slow_down));
+        }
+
+        // other server errors make the primary endpoint unhealthy
+        assert_eq!(
+            primary,
+            attempt(&failover, &runtime_components, response(503, None))
+        );
+        assert_eq!(
+            primary,
+            attempt(&failover, &runtime_components, response(500, None))
+        );
+        assert_eq!(
+            secondary,
+            attempt(&failover, &runtime_components, response(200, None))
+        );
+    }
+}
@@ -61,12 +61,16 @@ pub mod body;
 #[derive(Debug, Clone)]
 pub(crate) struct DefaultClientOptions {
     behavior_version: BehaviorVersion,
+    #[cfg(feature = "default-https-client")]
+    proxy_config: Option<aws_smithy_http_client::proxy::ProxyConfig>,
 }
 
 impl Default for DefaultClientOptions {
     fn default() -> Self {
         DefaultClientOptions {
             behavior_version: BehaviorVersion::latest(),
+            #[cfg(feature = "default-https-client")]
+            proxy_config: None,
         }
     }
 }
@@ -78,6 +82,16 @@ impl DefaultClientOptions {
         self.behavior_version = behavior_version;
         self
     }
+
+    /// Set the proxy configuration to use instead of the proxy settings from the environment
+    #[cfg(feature = "default-https-client")]
+    pub(crate) fn with_proxy_config(
+        mut self,
+        proxy_config: aws_smithy_http_client::proxy::ProxyConfig,
+    ) -> Self {
+        self.proxy_config = Some(proxy_config);
+        self
+    }
 }
 
 /// Creates an HTTPS client using the default TLS provider
@@ -97,7 +111,9 @@ pub(crate) fn default_https_client(options: DefaultClientOptions) -> Option<Shar
             conn_builder.set_sleep_impl(components.sleep_impl());
         }
 
-        if options
+        if let Some(proxy_config) = &options.proxy_config {
+            conn_builder.set_proxy_config(Some(proxy_config.clone()));
+        } else if options
             .behavior_version
             .is_at_least(BehaviorVersion::v2025_08_07())
         {
@@ -25,7 +25,8 @@ use tracing::Instrument;
 const DEFAULT_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_EXPIRATION: Duration = Duration::from_secs(15 * 60);
 const DEFAULT_BUFFER_TIME: Duration = Duration::from_secs(10);
-const DEFAULT_BUFFER_TIME_JITTER_FRACTION: fn() -> f64 = || fastrand::f64() * 0.5;
+const DEFAULT_BUFFER_TIME_JITTER_FRACTION: fn() -> f64 = fastrand::f64;
+const DEFAULT_MAX_BUFFER_TIME_JITTER_FRACTION: f64 = 0.5;
 
 /// Builder for lazy identity caching.
 #[derive(Default, Debug)]
@@ -35,6 +36,8 @@ pub struct LazyCacheBuilder {
     load_timeout: Option<Duration>,
     buffer_time: Option<Duration>,
     buffer_time_jitter_fraction: Option<fn() -> f64>,
+    max_buffer_time_jitter_fraction: Option<f64>,
+    serve_stale_identity: Option<bool>,
     default_expiration: Option<Duration>,
 }
 
@@ -87,7 +90,7 @@ impl LazyCacheBuilder {
     /// For example, if the identity are expiring in 15 minutes, and the buffer time is 10 seconds,
     /// then any requests made after 14 minutes and 50 seconds will load a new identity.
     ///
-    /// Note: random jitter value between [0.0, 0.5] is multiplied to this buffer time.
+    /// Note: a random jitter is subtracted from this buffer time. See [`buffer_time_jitter`](Self::buffer_time_jitter).
     ///
     /// Defaults to 10 seconds.
     pub fn buffer_time(mut self, buffer_time: Duration) -> Self {
@@ -100,7 +103,7 @@ impl LazyCacheBuilder {
     /// For example, if the identity are expiring in 15 minutes, and the buffer time is 10 seconds,
     /// then any requests made after 14 minutes and 50 seconds will load a new identity.
     ///
-    /// Note: random jitter value between [0.0, 0.5] is multiplied to this buffer time.
+    /// Note: a random jitter is subtracted from this buffer time. See [`buffer_time_jitter`](Self::buffer_time_jitter).
     ///
     /// Defaults to 10 seconds.
     pub fn set_buffer_time(&mut self, buffer_time: Option<Duration>) -> &mut Self {
@@ -108,13 +111,79 @@ impl LazyCacheBuilder {
         self
     }
 
-    /// A random percentage by which buffer time is jittered for randomization.
+    /// Maximum fraction of the buffer time that is randomly subtracted from it for each loaded identity.
+    ///
+    /// Jitter spreads out identity refreshes so that a fleet of clients that loaded their identities
+    /// at the same time don't all refresh them at the same time. For example, if the buffer time is
+    /// 5 minutes and the maximum jitter fraction is 0.2, then each identity is refreshed somewhere
+    /// between 5 minutes and 4 minutes before it expires.
+    ///
+    /// Setting this to `0.0` disables jitter.
+    ///
+    /// Defaults to 0.5.
+    ///
+    /// # Panics
+    ///
+    /// [`build`](Self::build) will panic if the fraction isn't between 0.0 and 1.0.
+    pub fn buffer_time_jitter(mut self, max_fraction: f64) -> Self {
+        self.set_buffer_time_jitter(Some(max_fraction));
+        self
+    }
+
+    /// Maximum fraction of the buffer time that is randomly subtracted from it for each loaded identity.
+    ///
+    /// Jitter spreads out identity refreshes so that a fleet of clients that loaded their identities
+    /// at the same time don't all refresh them at the same time. For example, if the buffer time is
+    /// 5 minutes and the maximum jitter fraction is 0.2, then each identity is refreshed somewhere
+    /// between 5 minutes and 4 minutes before it expires.
+    ///
+    /// Setting this to `0.0` disables jitter.
+    ///
+    /// Defaults to 0.5.
+    ///
+    /// # Panics
+    ///
+    /// [`build`](Self::build) will panic if the fraction isn't between 0.0 and 1.0.
+    pub fn set_buffer_time_jitter(&mut self, max_fraction: Option<f64>) -> &mut Self {
+        self.max_buffer_time_jitter_fraction = max_fraction;
+        self
+    }
+
+    /// Whether to keep serving the cached identity if refreshing it fails.
+    ///
+    /// When enabled and the identity resolver fails (or times out) while refreshing an identity
+    /// that is within its buffer time but hasn't actually expired yet, the cached identity is
+    /// returned instead of the error, and a refresh is attempted again on the next request.
+    /// This allows requests to continue while the identity source is temporarily unavailable.
+    /// Identities that have actually expired are never served.
+    ///
+    /// Defaults to `false`.
+    pub fn serve_stale_identity(mut self, serve_stale_identity: bool) -> Self {
+        self.set_serve_stale_identity(Some(serve_stale_identity));
+        self
+    }
+
+    /// Whether to keep serving the cached identity if refreshing it fails.
+    ///
+    /// When enabled and the identity resolver fails (or times out) while refreshing an identity
+    /// that is within its buffer time but hasn't actually expired yet, the cached identity is
+    /// returned instead of the error, and a refresh is attempted again on the next request.
+    /// This allows requests to continue while the identity source is temporarily unavailable.
+    /// Identities that have actually expired are never served.
+    ///
+    /// Defaults to `false`.
+    pub fn set_serve_stale_identity(&mut self, serve_stale_identity: Option<bool>) -> &mut Self {
+        self.serve_stale_identity = serve_stale_identity;
+        self
+    }
+
+    /// A random value between 0.0 and 1.0 that is multiplied with the maximum buffer time jitter fraction.
     ///
     /// For example, if the identity is expiring in 15 minutes, the buffer time is 10 seconds,
-    /// and buffer time jitter fraction is 0.2, then buffer time is adjusted to 8 seconds.
-    /// Therefore, any requests made after 14 minutes and 52 seconds will load a new identity.
+    /// the maximum jitter fraction is 0.5, and this returns 0.4, then buffer time is adjusted to
+    /// 8 seconds. Therefore, any requests made after 14 minutes and 52 seconds will load a new identity.
     ///
-    /// Defaults to a randomly generated value between 0.0 and 0.5. This setter is for testing only.
+    /// Defaults to a randomly generated value between 0.0 and 1.0. This setter is for testing only.
     #[allow(unused)]
     #[cfg(test)]
     fn buffer_time_jitter_fraction(mut self, buffer_time_jitter_fraction: fn() -> f64) -> Self {
@@ -122,13 +191,13 @@ impl LazyCacheBuilder {
         self
     }
 
-    /// A random percentage by which buffer time is jittered for randomization.
+    /// A random value between 0.0 and 1.0 that is multiplied with the maximum buffer time jitter fraction.
     ///
     /// For example, if the identity is expiring in 15 minutes, the buffer time is 10 seconds,
-    /// and buffer time jitter fraction is 0.2, then buffer time is adjusted to 8 seconds.
-    /// Therefore, any requests made after 14 minutes and 52 seconds will load a new identity.
+    /// the maximum jitter fraction is 0.5, and this returns 0.4, then buffer time is adjusted to
+    /// 8 seconds. Therefore, any requests made after 14 minutes and 52 seconds will load a new identity.
     ///
-    /// Defaults to a randomly generated value between 0.0 and 0.5. This setter is for testing only.
+    /// Defaults to a randomly generated value between 0.0 and 1.0. This setter is for testing only.
     #[allow(unused)]
     #[cfg(test)]
     fn set_buffer_time_jitter_fraction(
@@ -172,6 +241,13 @@ impl LazyCacheBuilder {
             default_expiration >= DEFAULT_EXPIRATION,
             "default_expiration must be at least 15 minutes"
         );
+        let max_buffer_time_jitter_fraction = self
+            .max_buffer_time_jitter_fraction
+            .unwrap_or(DEFAULT_MAX_BUFFER_TIME_JITTER_FRACTION);
+        assert!(
+            (0.0..=1.0).contains(&max_buffer_time_jitter_fraction),
+            "buffer_time_jitter must be between 0.0 and 1.0"
+        );
         LazyCache::new(
             self.load_timeout.unwrap_or(DEFAULT_LOAD_TIMEOUT),
             self.buffer_time.unwrap_or(DEFAULT_BUFFER_TIME),
@@ -179,6 +255,8 @@ impl LazyCacheBuilder {
                 .unwrap_or(DEFAULT_BUFFER_TIME_JITTER_FRACTION),
             default_expiration,
         )
+        .with_max_buffer_time_jitter_fraction(max_buffer_time_jitter_fraction)
+        .with_serve_stale_identity(self.serve_stale_identity.unwrap_or_default())
         .into_shared()
     }
 }
@@ -222,6 +300,8 @@ struct LazyCache {
     load_timeout: Duration,
     buffer_time: Duration,
     buffer_time_jitter_fraction: fn() -> f64,
+    max_buffer_time_jitter_fraction: f64,
+    serve_stale_identity: bool,
     default_expiration: Duration,
 }
 
@@ -237,9 +317,21 @@ impl LazyCache {
             load_timeout,
             buffer_time,
             buffer_time_jitter_fraction,
+            max_buffer_time_jitter_fraction: DEFAULT_MAX_BUFFER_TIME_JITTER_FRACTION,
+            serve_stale_identity: false,
             default_expiration,
         }
     }
+
+    fn with_max_buffer_time_jitter_fraction(mut self, max_fraction: f64) -> Self {
+        self.max_buffer_time_jitter_fraction = max_fraction;
+        self
+    }
+
+    fn with_serve_stale_identity(mut self, serve_stale_identity: bool) -> Self {
+        self.serve_stale_identity = serve_stale_identity;
+        self
+    }
 }
 
 macro_rules! required_err {
@@ -310,67 +402,89 @@ impl ResolveCachedIdentity for LazyCache {
 
         IdentityFuture::new(async move {
             // Attempt to get cached identity, or clear the cache if they're expired
-            if let Some(identity) = cache.yield_or_clear_if_expired(now).await {
-                tracing::debug!(
-                    buffer_time=?self.buffer_time,
-                    cached_expiration=?identity.expiration(),
-                    now=?now,
-                    "loaded identity from cache"
-                );
-                Ok(identity)
-            } else {
-                // If we didn't get identity from the cache, then we need to try and load.
-                // There may be other threads also loading simultaneously, but this is OK
-                // since the futures are not eagerly executed, and the cache will only run one
-                // of them.
-                let start_time = time_source.now();
-                let result = cache
-                    .get_or_load(|| {
-                        let span = tracing::debug_span!("lazy_load_identity");
-                        async move {
-                            let fut = Timeout::new(
-                                resolver.resolve_identity(runtime_components, config_bag),
-                                timeout_future,
-                            );
-                            let identity = match fut.await {
-                                Ok(result) => result?,
-                                Err(_err) => match resolver.fallback_on_interrupt() {
-                                    Some(identity) => identity,
-                                    None => {
-                                        return Err(BoxError::from(TimedOutError(load_timeout)))
-                                    }
-                                },
-                            };
-                            // If the identity don't have an expiration time, then create a default one
-                            let expiration =
-                                identity.expiration().unwrap_or(now + default_expiration);
-
-                            let jitter = self
-                                .buffer_time
-                                .mul_f64((self.buffer_time_jitter_fraction)());
-
-                            // Logging for cache miss should be emitted here as opposed to after the call to
-                            // `cache.get_or_load` above. In the case of multiple threads concurrently executing
-                            // `cache.get_or_load`, logging inside `cache.get_or_load` ensures that it is emitted
-                            // only once for the first thread that succeeds in populating a cache value.
-                            let printable = DateTime::from(expiration);
-                            tracing::debug!(
-                                new_expiration=%printable,
-                                valid_for=?expiration.duration_since(time_source.now()).unwrap_or_default(),
-                                partition=?partition,
-                                "identity cache miss occurred; added new identity (took {:?})",
-                                time_source.now().duration_since(start_time).unwrap_or_default()
-                            );
-
-                            Ok((identity, expiration + jitter))
-                        }
-                        // Only instrument the the actual load future so that no span
-                        // is opened if the cache decides not to execute it.
-                        .instrument(span)
-                    })
-                    .await;
-                tracing::debug!("loaded identity");
-                result
+            let stale = match cache.yield_or_take_if_expired(now).await {
+                Ok(identity) => {
+                    tracing::debug!(
+                        buffer_time=?self.buffer_time,
+                        cached_expiration=?identity.expiration(),
+                        now=?now,
+                        "loaded identity from cache"
+                    );
+                    return Ok(identity);
+                }
+                Err(stale) => stale,
+            };
+            // If we didn't get identity from the cache, then we need to try and load.
+            // There may be other threads also loading simultaneously, but this is OK
+            // since the futures are not eagerly executed, and the cache will only run one
+            // of them.
+            let start_time = time_source.now();
+            let result = cache
+                .get_or_load(|| {
+                    let span = tracing::debug_span!("lazy_load_identity");
+                    async move {
+                        let fut = Timeout::new(
+                            resolver.resolve_identity(runtime_components, config_bag),
+                            timeout_future,
+                        );
+                        let identity = match fut.await {
+                            Ok(result) => result?,
+                            Err(_err) => match resolver.fallback_on_interrupt() {
+                                Some(identity) => identity,
+                                None => {
+                                    return Err(BoxError::from(TimedOutError(load_timeout)))
+                                }
+                            },
+                        };
+                        // If the identity don't have an expiration time, then create a default one
+                        let expiration =
+                            identity.expiration().unwrap_or(now + default_expiration);
+
+                        let jitter = self.buffer_time.mul_f64(
+                            (self.buffer_time_jitter_fraction)()
+                                * self.max_buffer_time_jitter_fraction,
+                        );
+
+                        // Logging for cache miss should be emitted here as opposed to after the call to
+                        // `cache.get_or_load` above. In the case of multiple threads concurrently executing
+                        // `cache.get_or_load`, logging inside `cache.get_or_load` ensures that it is emitted
+                        // only once for the first thread that succeeds in populating a cache value.
+                        let printable = DateTime::from(expiration);
+                        tracing::debug!(
+                            new_expiration=%printable,
+                            valid_for=?expiration.duration_since(time_source.now()).unwrap_or_default(),
+                            partition=?partition,
+                            "identity cache miss occurred; added new identity (took {:?})",
+                            time_source.now().duration_since(start_time).unwrap_or_default()
+                        );
+
+                        Ok((identity, expiration + jitter))
+                    }
+                    // Only instrument the the actual load future so that no span
+                    // is opened if the cache decides not to execute it.
+                    .instrument(span)
+                })
+                .await;
+            match (result, stale) {
+                (Err(err), Some((identity, cache_expiration)))
+                    if self.serve_stale_identity
+                        && !matches!(identity.expiration(), Some(exp) if exp <= now) =>
+                {
+                    tracing::warn!(
+                        error = %err,
+                        cached_expiration = ?identity.expiration(),
+                        "failed to refresh identity; continuing to use the cached identity until it expires"
+                    );
+                    // Put the identity back so that concurrent and subsequent requests
+                    // can also use it while the next request retries the refresh.
+                    cache
+                        .get_or_load(|| async move { Ok((identity, cache_expiration)) })
+                        .await
+                }
+                (result, _) => {
+                    tracing::debug!("loaded identity");
+                    result
+                }
             }
         })
     }
@@ -663,15 +777,16 @@ mod tests {
             .with_sleep_impl(Some(TokioSleep::new()))
             .build()
             .unwrap();
-        let buffer_time_jitter_fraction = || 0.5_f64;
+        let buffer_time_jitter_fraction = || 1.0_f64;
         let (cache, resolver) = test_cache(
             buffer_time_jitter_fraction,
             vec![Ok(test_identity(1000)), Ok(test_identity(2000))],
         );
 
         expect_identity(1000, &cache, &components, resolver.clone()).await;
-        let buffer_time_with_jitter =
-            (DEFAULT_BUFFER_TIME.as_secs_f64() * buffer_time_jitter_fraction()) as u64;
+        let buffer_time_with_jitter = (DEFAULT_BUFFER_TIME.as_secs_f64()
+            * buffer_time_jitter_fraction()
+            * DEFAULT_MAX_BUFFER_TIME_JITTER_FRACTION) as u64;
         assert_eq!(buffer_time_with_jitter, 5);
         // Advance time to the point where the first test_identity are about to expire (but haven't).
         let almost_expired_secs = 1000 - buffer_time_with_jitter - 1;
@@ -685,6 +800,94 @@ mod tests {
         expect_identity(2000, &cache, &components, resolver.clone()).await;
     }
 
+    #[tokio::test]
+    async fn configurable_buffer_time_jitter() {
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let (cache, resolver) = test_cache(
+            || 1.0_f64,
+            vec![Ok(test_identity(1000)), Ok(test_identity(2000))],
+        );
+        // A max jitter of 0.2 takes at most 2 seconds off of the 10 second buffer time
+        let cache = cache.with_max_buffer_time_jitter_fraction(0.2);
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(1000 - 8 - 1));
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(1000 - 8));
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer_time_jitter must be between 0.0 and 1.0")]
+    fn invalid_buffer_time_jitter() {
+        LazyCacheBuilder::new().buffer_time_jitter(1.5).build();
+    }
+
+    #[tokio::test]
+    async fn refresh_failure_without_stale_identity() {
+        let config_bag = ConfigBag::base();
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let (cache, resolver) = test_cache(
+            BUFFER_TIME_NO_JITTER,
+            vec![Ok(test_identity(1000)), Err("failed".into())],
+        );
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        // Within the buffer time, but not yet expired
+        time.set_time(epoch_secs(995));
+        assert!(cache
+            .resolve_cached_identity(resolver.clone(), &components, &config_bag)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn serve_stale_identity_on_refresh_failure() {
+        let config_bag = ConfigBag::base();
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let (cache, resolver) = test_cache(
+            BUFFER_TIME_NO_JITTER,
+            vec![
+                Ok(test_identity(1000)),
+                Err("failed".into()),
+                Err("failed".into()),
+                Err("failed".into()),
+                Ok(test_identity(2000)),
+            ],
+        );
+        let cache = cache.with_serve_stale_identity(true);
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        // Refreshes within the buffer time fail, so the cached identity is served
+        time.set_time(epoch_secs(995));
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(999));
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        // Once the identity has actually expired, the error is returned
+        time.set_time(epoch_secs(1000));
+        assert!(cache
+            .resolve_cached_identity(resolver.clone(), &components, &config_bag)
+            .await
+            .is_err());
+        // And a later successful refresh replaces it
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+    }
+
     #[tokio::test]
     async fn cache_partitioning() {
         let time = ManualTimeSource::new(epoch_secs(0));
@@ -3,12 +3,13 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::client::auth::no_auth::NO_AUTH_SCHEME_ID;
+use crate::client::auth::no_auth::{ForceNoAuth, NO_AUTH_SCHEME_ID};
 use crate::client::identity::IdentityCache;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::{
     AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, AuthSchemeOption,
-    AuthSchemeOptionResolverParams, AuthSchemePreference, ResolveAuthSchemeOptions,
+    AuthSchemeOptionResolverParams, AuthSchemePreference, DisabledAuthSchemes,
+    ResolveAuthSchemeOptions,
 };
 use aws_smithy_runtime_api::client::endpoint::{EndpointResolverParams, ResolveEndpoint};
 use aws_smithy_runtime_api::client::identity::{Identity, ResolveIdentity};
@@ -51,6 +52,7 @@ impl fmt::Display for NoMatchingAuthSchemeError {
 
         let mut try_add_identity = false;
         let mut likely_bug = false;
+        let mut disabled = false;
         f.write_str("failed to select an auth scheme to sign the request with.")?;
         for item in explored.items() {
             write!(
@@ -72,6 +74,10 @@ impl fmt::Display for NoMatchingAuthSchemeError {
                     "there is auth config in the endpoint config, but this scheme wasn't listed in it \
                     (see https://github.com/smithy-lang/smithy-rs/discussions/3281 for more details)."
                 }
+                ExploreResult::Disabled => {
+                    disabled = true;
+                    "it was disabled by the `DisabledAuthSchemes` configuration."
+                }
                 ExploreResult::NotExplored => {
                     debug_assert!(false, "this should be unreachable");
                     "<unknown>"
@@ -82,6 +88,8 @@ impl fmt::Display for NoMatchingAuthSchemeError {
             f.write_str(" Be sure to set an identity, such as credentials, auth token, or other identity type that is required for this service.")?;
         } else if likely_bug {
             f.write_str(" This is likely a bug.")?;
+        } else if disabled {
+            f.write_str(" Remove at least one of these schemes from the disabled auth schemes to allow the request to be signed.")?;
         }
         if explored.truncated {
             f.write_str(" Note: there were other auth schemes that were evaluated that weren't listed here.")?;
@@ -126,8 +134,12 @@ pub(super) async fn resolve_identity(
     let options = option_resolver
         .resolve_auth_scheme_options_v2(params, cfg, runtime_components)
         .await?;
-    let options =
-        reprioritize_with_auth_scheme_preference(options, cfg.load::<AuthSchemePreference>()).await;
+    let options = if cfg.load::<ForceNoAuth>().map(|f| f.0).unwrap_or_default() {
+        trace!("`ForceNoAuth` is set, ignoring resolved auth scheme options");
+        vec![AuthSchemeOption::from(NO_AUTH_SCHEME_ID)]
+    } else {
+        reprioritize_with_auth_scheme_preference(options, cfg.load::<AuthSchemePreference>()).await
+    };
 
     trace!(
         auth_scheme_option_resolver_params = ?params,
@@ -135,11 +147,17 @@ pub(super) async fn resolve_identity(
         "orchestrating auth",
     );
 
+    let disabled_auth_schemes = cfg.load::<DisabledAuthSchemes>();
     let mut explored = ExploredList::default();
 
     // Iterate over IDs of possibly-supported auth schemes
     for auth_scheme_option in &options {
         let scheme_id = auth_scheme_option.scheme_id();
+        if disabled_auth_schemes.is_some_and(|disabled| disabled.contains(scheme_id)) {
+            trace!(scheme_id = ?scheme_id, "skipping disabled auth scheme");
+            explored.push(scheme_id.clone(), ExploreResult::Disabled);
+            continue;
+        }
         // For each ID, try to resolve the corresponding auth scheme.
         if let Some(auth_scheme) = runtime_components.auth_scheme(scheme_id) {
             // Use the resolved auth scheme to resolve an identity
@@ -362,6 +380,7 @@ enum ExploreResult {
     NoAuthScheme,
     NoIdentityResolver,
     MissingEndpointConfig,
+    Disabled,
 }
 
 /// Information about an evaluated auth option.
@@ -912,6 +931,18 @@ mod tests {
             err.to_string()
         );
 
+        // Disabled schemes should be explained
+        let mut list = ExploredList::default();
+        list.push(AuthSchemeId::new("httpBearerAuth"), ExploreResult::Disabled);
+        let err = NoMatchingAuthSchemeError(list);
+        assert_eq!(
+            "failed to select an auth scheme to sign the request with. \
+            \"httpBearerAuth\" wasn't a valid option because it was disabled by the \
+            `DisabledAuthSchemes` configuration. Remove at least one of these schemes from the \
+            disabled auth schemes to allow the request to be signed.",
+            err.to_string()
+        );
+
         // Truncation should be indicated
         let mut list = ExploredList::default();
         for _ in 0..=MAX_EXPLORED_LIST_LEN {
@@ -1137,4 +1168,121 @@ mod tests {
             assert_eq!(test_case.expected_resolved_auths, actual);
         }
     }
+
+    #[tokio::test]
+    async fn force_no_auth() {
+        use crate::client::auth::no_auth::NoAuthScheme;
+        use crate::client::identity::no_auth::NoAuthIdentityResolver;
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_auth_scheme(SharedAuthScheme::new(NoAuthScheme::new()))
+            .with_auth_scheme_option_resolver(Some(SharedAuthSchemeOptionResolver::new(
+                StaticAuthSchemeOptionResolver::new(vec![AuthSchemeId::new("test-scheme")]),
+            )))
+            .with_identity_resolver(
+                NO_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(NoAuthIdentityResolver::new()),
+            )
+            .with_endpoint_resolver(Some(SharedEndpointResolver::new(
+                StaticUriEndpointResolver::http_localhost(8080),
+            )))
+            .build()
+            .unwrap();
+
+        let config_bag = |force_no_auth: Option<ForceNoAuth>| {
+            let mut layer = Layer::new("test");
+            layer.store_put(AuthSchemeAndEndpointOrchestrationV2);
+            layer.store_put(AuthSchemeOptionResolverParams::new("doesntmatter"));
+            layer.store_put(AuthSchemePreference::from([AuthSchemeId::new(
+                "test-scheme",
+            )]));
+            layer.store_or_unset(force_no_auth);
+            ConfigBag::of_layers(vec![layer])
+        };
+
+        // The resolved options don't include "no auth", so without `ForceNoAuth` this fails
+        for force_no_auth in [None, Some(ForceNoAuth(false))] {
+            resolve_identity(&runtime_components, &mut config_bag(force_no_auth))
+                .await
+                .expect_err("test-scheme isn't registered");
+        }
+
+        let (scheme_id, _, _) = resolve_identity(
+            &runtime_components,
+            &mut config_bag(Some(ForceNoAuth(true))),
+        )
+        .await
+        .expect("success");
+        assert_eq!(NO_AUTH_SCHEME_ID, scheme_id);
+    }
+
+    #[cfg(feature = "http-auth")]
+    #[tokio::test]
+    async fn disabled_auth_schemes() {
+        use crate::client::auth::http::{ApiKeyAuthScheme, ApiKeyLocation, BasicAuthScheme};
+        use aws_smithy_runtime_api::client::auth::http::{
+            HTTP_API_KEY_AUTH_SCHEME_ID, HTTP_BASIC_AUTH_SCHEME_ID,
+        };
+        use aws_smithy_runtime_api::client::identity::http::Token;
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_auth_scheme(SharedAuthScheme::new(BasicAuthScheme::new()))
+            .with_auth_scheme(SharedAuthScheme::new(ApiKeyAuthScheme::new(
+                "result:",
+                ApiKeyLocation::Header,
+                "Authorization",
+            )))
+            .with_auth_scheme_option_resolver(Some(SharedAuthSchemeOptionResolver::new(
+                StaticAuthSchemeOptionResolver::new(vec![
+                    HTTP_BASIC_AUTH_SCHEME_ID,
+                    HTTP_API_KEY_AUTH_SCHEME_ID,
+                ]),
+            )))
+            .with_identity_resolver(
+                HTTP_BASIC_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(Token::new("basic", None)),
+            )
+            .with_identity_resolver(
+                HTTP_API_KEY_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(Token::new("api-key", None)),
+            )
+            .with_endpoint_resolver(Some(SharedEndpointResolver::new(
+                StaticUriEndpointResolver::http_localhost(8080),
+            )))
+            .build()
+            .unwrap();
+
+        let config_bag = |disabled: &[AuthSchemeId]| {
+            let mut layer = Layer::new("test");
+            layer.store_put(AuthSchemeAndEndpointOrchestrationV2);
+            layer.store_put(AuthSchemeOptionResolverParams::new("doesntmatter"));
+            layer.store_put(DisabledAuthSchemes::from(disabled));
+            ConfigBag::of_layers(vec![layer])
+        };
+
+        let (scheme_id, _, _) = resolve_identity(&runtime_components, &mut config_bag(&[]))
+            .await
+            .expect("success");
+        assert_eq!(HTTP_BASIC_AUTH_SCHEME_ID, scheme_id);
+
+        let (scheme_id, _, _) = resolve_identity(
+            &runtime_components,
+            &mut config_bag(&[HTTP_BASIC_AUTH_SCHEME_ID]),
+        )
+        .await
+        .expect("success");
+        assert_eq!(HTTP_API_KEY_AUTH_SCHEME_ID, scheme_id);
+
+        let err = resolve_identity(
+            &runtime_components,
+            &mut config_bag(&[HTTP_BASIC_AUTH_SCHEME_ID, HTTP_API_KEY_AUTH_SCHEME_ID]),
+        )
+        .await
+        .expect_err("all schemes are disabled");
+        assert!(
+            err.to_string()
+                .contains("disabled by the `DisabledAuthSchemes` configuration"),
+            "{err}"
+        );
+    }
 }
@@ -85,8 +85,13 @@ pub(super) async fn orchestrate_endpoint(
 
     endpoint_resolver.finalize_params(params)?;
 
+    let params = cfg
+        .load::<EndpointResolverParams>()
+        .expect("set in the interceptor state above");
     tracing::debug!(endpoint_params = ?params, "resolving endpoint");
-    let endpoint = endpoint_resolver.resolve_endpoint(params).await?;
+    let endpoint = endpoint_resolver
+        .resolve_endpoint_with_config(params, cfg)
+        .await?;
 
     apply_endpoint(&endpoint, ctx, cfg)?;
 
@@ -160,8 +165,16 @@ fn apply_endpoint_to_request(
 
 #[cfg(test)]
 mod test {
-    use aws_smithy_runtime_api::client::endpoint::EndpointPrefix;
+    use super::orchestrate_endpoint;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::endpoint::{
+        EndpointFuture, EndpointPrefix, EndpointResolverParams, ResolveEndpoint,
+    };
+    use aws_smithy_runtime_api::client::identity::Identity;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
     use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::{ConfigBag, Layer, Storable, StoreReplace};
     use aws_smithy_types::endpoint::Endpoint;
 
     #[test]
@@ -177,4 +190,65 @@ mod test {
             "https://prefix.subdomain.s3.amazon.com/foo?bar=1"
         );
     }
+
+    #[tokio::test]
+    async fn resolve_endpoint_with_config() {
+        #[derive(Clone, Debug)]
+        struct Stage(&'static str);
+        impl Storable for Stage {
+            type Storer = StoreReplace<Self>;
+        }
+
+        #[derive(Debug)]
+        struct StageResolver;
+        impl ResolveEndpoint for StageResolver {
+            fn resolve_endpoint<'a>(&'a self, _: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+                EndpointFuture::ready(Err("the config bag is required".into()))
+            }
+
+            fn resolve_endpoint_with_config<'a>(
+                &'a self,
+                _: &'a EndpointResolverParams,
+                cfg: &'a ConfigBag,
+            ) -> EndpointFuture<'a> {
+                EndpointFuture::new(async move {
+                    let stage = cfg.load::<Stage>().ok_or("the stage must be set")?;
+                    Ok::<_, BoxError>(
+                        Endpoint::builder()
+                            .url(format!("https://{}.example.com", stage.0))
+                            .build(),
+                    )
+                })
+            }
+        }
+
+        let mut layer = Layer::new("test");
+        layer.store_put(Stage("beta"));
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        cfg.interceptor_state()
+            .store_put(EndpointResolverParams::new(()));
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(StageResolver))
+            .build()
+            .unwrap();
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let _ = ctx.take_input();
+        let mut request = HttpRequest::empty();
+        request.set_uri("/foo").unwrap();
+        ctx.set_request(request);
+
+        orchestrate_endpoint(
+            Identity::new((), None),
+            &mut ctx,
+            &runtime_components,
+            &mut cfg,
+        )
+        .await
+        .expect("resolved");
+        assert_eq!(
+            "https://beta.example.com/foo",
+            ctx.request().expect("set").uri()
+        );
+    }
 }
@@ -8,38 +8,21 @@ use aws_smithy_async::{
     rt::sleep::{AsyncSleep, SharedAsyncSleep},
     time::SharedTimeSource,
 };
-use aws_smithy_runtime_api::client::waiters::FinalPoll;
+use aws_smithy_runtime_api::client::waiters::{
+    BackoffStrategy, FinalPoll, WaiterProgress, WaiterProgressCallback,
+};
 use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
 use aws_smithy_runtime_api::client::{
     result::CreateUnhandledError,
     waiters::error::{ExceededMaxWait, FailureState, OperationFailed, WaiterError},
 };
 use std::future::Future;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 mod backoff;
 
-/// Waiter acceptor state
-///
-/// This enum (vaguely) matches the [acceptor state] from the Smithy spec.
-/// It has an additional `NoAcceptorsMatched` variant to indicate the case where
-/// none of the modeled waiters matched the response, which the spec mentions but
-/// doesn't consider an official part of the acceptor state enum. An `Option<AcceptorState>`
-/// could have been used instead, but this seemed cleaner.
-///
-/// [acceptor state]: https://smithy.io/2.0/additional-specs/waiters.html#acceptorstate-enum
-#[non_exhaustive]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub enum AcceptorState {
-    /// None of the modeled acceptors matched the response.
-    NoAcceptorsMatched,
-    /// A `success` acceptor matched the response.
-    Success,
-    /// A `failure` acceptor matched the response.
-    Failure,
-    /// A `retry` acceptor matched the response.
-    Retry,
-}
+#[doc(inline)]
+pub use aws_smithy_runtime_api::client::waiters::AcceptorState;
 
 /// Orchestrates waiting via polling with jittered exponential backoff.
 ///
@@ -47,6 +30,7 @@ pub enum AcceptorState {
 /// waiter functionality.
 pub struct WaiterOrchestrator<AcceptorFn, OperationFn> {
     backoff: Backoff,
+    progress_callback: Option<WaiterProgressCallback>,
     time_source: SharedTimeSource,
     sleep_impl: SharedAsyncSleep,
     acceptor_fn: AcceptorFn,
@@ -63,6 +47,7 @@ impl WaiterOrchestrator<(), ()> {
 impl<AcceptorFn, OperationFn> WaiterOrchestrator<AcceptorFn, OperationFn> {
     fn new(
         backoff: Backoff,
+        progress_callback: Option<WaiterProgressCallback>,
         time_source: SharedTimeSource,
         sleep_impl: SharedAsyncSleep,
         acceptor_fn: AcceptorFn,
@@ -70,12 +55,30 @@ impl<AcceptorFn, OperationFn> WaiterOrchestrator<AcceptorFn, OperationFn> {
     ) -> Self {
         WaiterOrchestrator {
             backoff,
+            progress_callback,
             time_source,
             sleep_impl,
             acceptor_fn,
             operation_fn,
         }
     }
+
+    fn report_progress(
+        &self,
+        attempt: u32,
+        state: AcceptorState,
+        start_time: SystemTime,
+        next_delay: Option<Duration>,
+    ) {
+        if let Some(callback) = &self.progress_callback {
+            let elapsed = self
+                .time_source
+                .now()
+                .duration_since(start_time)
+                .unwrap_or_default();
+            callback.call(&WaiterProgress::new(attempt, state, elapsed, next_delay));
+        }
+    }
 }
 
 impl<AcceptorFn, OperationFn, O, E, Fut> WaiterOrchestrator<AcceptorFn, OperationFn>
@@ -89,6 +92,9 @@ where
     pub async fn orchestrate(
         self,
     ) -> Result<FinalPoll<O, SdkError<E, HttpResponse>>, WaiterError<O, E>> {
+        self.backoff
+            .validate()
+            .map_err(WaiterError::construction_failure)?;
         let start_time = self.time_source.now();
         let mut attempt = 0;
         let mut done_retrying = false;
@@ -111,20 +117,27 @@ where
             };
 
             tracing::debug!("waiter acceptor state: {acceptor_state:?}");
+            let poll_number = attempt + 1;
             match acceptor_state {
-                AcceptorState::Success => return Ok(FinalPoll::new(result)),
+                AcceptorState::Success => {
+                    self.report_progress(poll_number, acceptor_state, start_time, None);
+                    return Ok(FinalPoll::new(result));
+                }
                 AcceptorState::Failure => {
+                    self.report_progress(poll_number, acceptor_state, start_time, None);
                     return Err(WaiterError::FailureState(FailureState::new(
                         FinalPoll::new(result.map_err(|err| err.into_service_error())),
-                    )))
+                    )));
                 }
                 // This occurs when there was a modeled error response, but none of the acceptors matched it
                 AcceptorState::NoAcceptorsMatched if error => {
+                    self.report_progress(poll_number, acceptor_state, start_time, None);
                     return Err(WaiterError::OperationFailed(OperationFailed::new(
                         result.err().expect("checked above"),
-                    )))
+                    )));
                 }
-                AcceptorState::Retry | AcceptorState::NoAcceptorsMatched => {
+                // `AcceptorState` is non-exhaustive, and any other state is treated as a retry
+                _ => {
                     attempt += 1;
 
                     let now = self.time_source.now();
@@ -136,6 +149,7 @@ where
                         // from max_time. If we didn't detect this and stop polling, then we could
                         // slam the server at the very end of the wait period for servers that are
                         // really fast (for example, a few milliseconds total round-trip latency).
+                        self.report_progress(poll_number, acceptor_state, start_time, Some(delay));
                         if delay.is_zero() {
                             tracing::debug!(
                                 "delay calculated for attempt #{attempt}; elapsed ({elapsed:?}); waiter is close to max time; will immediately poll one last time"
@@ -148,6 +162,7 @@ where
                             self.sleep_impl.sleep(delay).await;
                         }
                     } else {
+                        self.report_progress(poll_number, acceptor_state, start_time, None);
                         tracing::debug!(
                             "waiter exceeded max wait time of {:?}",
                             self.backoff.max_wait()
@@ -170,6 +185,8 @@ pub struct WaiterOrchestratorBuilder<AcceptorFn = (), OperationFn = ()> {
     min_delay: Option<Duration>,
     max_delay: Option<Duration>,
     max_wait: Option<Duration>,
+    backoff: BackoffStrategy,
+    progress_callback: Option<WaiterProgressCallback>,
     time_source: Option<SharedTimeSource>,
     sleep_impl: Option<SharedAsyncSleep>,
     random_fn: RandomImpl,
@@ -196,6 +213,20 @@ impl<AcceptorFn, OperationFn> WaiterOrchestratorBuilder<AcceptorFn, OperationFn>
         self
     }
 
+    /// Set the strategy used to calculate the delay between polls.
+    ///
+    /// Defaults to [`BackoffStrategy::JitteredExponential`].
+    pub fn backoff(mut self, backoff: BackoffStrategy) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set a callback that is invoked with the waiter's progress after every poll.
+    pub fn progress_callback(mut self, progress_callback: Option<WaiterProgressCallback>) -> Self {
+        self.progress_callback = progress_callback;
+        self
+    }
+
     #[cfg(all(test, any(feature = "test-util", feature = "legacy-test-util")))]
     fn random(mut self, random_fn: impl Fn(u64, u64) -> u64 + Send + Sync + 'static) -> Self {
         self.random_fn = RandomImpl::Override(Box::new(random_fn));
@@ -221,8 +252,10 @@ impl<AcceptorFn, OperationFn> WaiterOrchestratorBuilder<AcceptorFn, OperationFn>
                 self.min_delay.expect("min delay is required"),
                 self.max_delay.expect("max delay is required"),
                 self.max_wait.expect("max wait is required"),
+                self.backoff,
                 self.random_fn,
             ),
+            self.progress_callback,
             self.time_source.expect("time source required"),
             self.sleep_impl.expect("sleep impl required"),
             self.acceptor_fn.expect("acceptor fn required"),
@@ -241,6 +274,8 @@ impl<OperationFn> WaiterOrchestratorBuilder<(), OperationFn> {
             min_delay: self.min_delay,
             max_delay: self.max_delay,
             max_wait: self.max_wait,
+            backoff: self.backoff,
+            progress_callback: self.progress_callback,
             time_source: self.time_source,
             sleep_impl: self.sleep_impl,
             random_fn: self.random_fn,
@@ -260,6 +295,8 @@ impl<AcceptorFn> WaiterOrchestratorBuilder<AcceptorFn, ()> {
             min_delay: self.min_delay,
             max_delay: self.max_delay,
             max_wait: self.max_wait,
+            backoff: self.backoff,
+            progress_callback: self.progress_callback,
             time_source: self.time_source,
             sleep_impl: self.sleep_impl,
             random_fn: self.random_fn,
@@ -526,4 +563,74 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().as_result().is_err());
     }
+
+    #[tokio::test]
+    async fn fixed_backoff_reports_progress() {
+        let _logs = show_test_logs();
+        let (time_source, sleep_impl) = tick_advance_time_and_sleep();
+
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let attempt = Arc::new(AtomicUsize::new(1));
+        let orchestrator = WaiterOrchestrator::builder()
+            .min_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1))
+            .max_wait(Duration::from_secs(10))
+            .backoff(BackoffStrategy::Fixed)
+            .progress_callback(Some(WaiterProgressCallback::new({
+                let progress = progress.clone();
+                move |p: &WaiterProgress| progress.lock().unwrap().push(p.clone())
+            })))
+            .sleep_impl(sleep_impl.into_shared())
+            .time_source(time_source.clone().into_shared())
+            .acceptor(|result: Result<&usize, &TestError>| match result {
+                Ok(3) => AcceptorState::Success,
+                _ => AcceptorState::Retry,
+            })
+            .operation(move || {
+                let attempt = attempt.clone();
+                async move {
+                    Result::<_, SdkError<TestError, HttpResponse>>::Ok(
+                        attempt.fetch_add(1, Ordering::SeqCst),
+                    )
+                }
+            })
+            .build();
+
+        let task = tokio::spawn(orchestrator.orchestrate());
+        tokio::task::yield_now().await;
+        time_source.tick(Duration::from_secs(1)).await;
+        let result = task.await.unwrap();
+
+        assert_eq!(3, *result.unwrap().as_result().unwrap());
+        let delay = Duration::from_millis(100);
+        assert_eq!(
+            vec![
+                WaiterProgress::new(1, AcceptorState::Retry, Duration::ZERO, Some(delay)),
+                WaiterProgress::new(2, AcceptorState::Retry, delay, Some(delay)),
+                WaiterProgress::new(3, AcceptorState::Success, delay * 2, None),
+            ],
+            *progress.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_delays_are_a_construction_failure() {
+        let _logs = show_test_logs();
+        let (time_source, sleep_impl) = tick_advance_time_and_sleep();
+        let orchestrator = test_orchestrator(sleep_impl, time_source)
+            .min_delay(Duration::from_millis(100))
+            .acceptor(|_result: Result<&usize, &TestError>| unreachable!())
+            .operation(|| async {
+                Result::<usize, SdkError<TestError, HttpResponse>>::Err(SdkError::timeout_error(
+                    "no polls should be made",
+                ))
+            })
+            .build();
+
+        let result = orchestrator.orchestrate().await;
+        assert!(
+            matches!(result, Err(WaiterError::ConstructionFailure(_))),
+            "expected construction failure, got: {result:?}"
+        );
+    }
 }
@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_runtime_api::client::waiters::BackoffStrategy;
 use std::{fmt, time::Duration};
 
 #[derive(Debug)]
@@ -11,6 +12,7 @@ pub(super) struct Backoff {
     max_delay: Duration,
     max_wait: Duration,
     attempt_ceiling: u32,
+    strategy: BackoffStrategy,
     random: RandomImpl,
 }
 
@@ -19,8 +21,17 @@ impl Backoff {
         min_delay: Duration,
         max_delay: Duration,
         max_wait: Duration,
+        strategy: BackoffStrategy,
         random: RandomImpl,
     ) -> Self {
+        // Jittered exponential delays are calculated in whole seconds, so sub-second minimum
+        // delays poll at a fixed interval instead.
+        let strategy = match strategy {
+            BackoffStrategy::JitteredExponential if min_delay < Duration::from_secs(1) => {
+                BackoffStrategy::Fixed
+            }
+            strategy => strategy,
+        };
         Self {
             min_delay,
             max_delay,
@@ -29,18 +40,30 @@ impl Backoff {
             attempt_ceiling: (((max_delay.as_secs_f64() / min_delay.as_secs_f64()).ln()
                 / 2f64.ln())
                 + 1.0) as u32,
+            strategy,
             random,
         }
     }
 
-    // Calculates backoff delay time according to the Smithy spec: https://smithy.io/2.0/additional-specs/waiters.html#waiter-retries
+    /// Returns an error message if the delays can't be used with the backoff strategy.
+    pub(super) fn validate(&self) -> Result<(), String> {
+        if self.min_delay > self.max_delay {
+            return Err(format!(
+                "the minimum delay ({:?}) must not be greater than the maximum delay ({:?})",
+                self.min_delay, self.max_delay
+            ));
+        }
+        Ok(())
+    }
+
     pub(super) fn delay(&self, attempt: u32, elapsed: Duration) -> Duration {
-        let delay = if attempt > self.attempt_ceiling {
-            self.max_delay.as_secs()
-        } else {
-            self.min_delay.as_secs() * 2u64.pow(attempt - 1)
+        let mut delay = match &self.strategy {
+            BackoffStrategy::Fixed => self.min_delay,
+            BackoffStrategy::Custom(custom) => {
+                custom.delay(attempt).clamp(self.min_delay, self.max_delay)
+            }
+            _ => self.jittered_exponential_delay(attempt),
         };
-        let mut delay = Duration::from_secs(self.random.random(self.min_delay.as_secs(), delay));
 
         let remaining_time = self.max_wait.saturating_sub(elapsed);
         if remaining_time.saturating_sub(delay) <= self.min_delay {
@@ -50,6 +73,16 @@ impl Backoff {
         delay
     }
 
+    // Calculates backoff delay time according to the Smithy spec: https://smithy.io/2.0/additional-specs/waiters.html#waiter-retries
+    fn jittered_exponential_delay(&self, attempt: u32) -> Duration {
+        let delay = if attempt > self.attempt_ceiling {
+            self.max_delay.as_secs()
+        } else {
+            self.min_delay.as_secs() * 2u64.pow(attempt - 1)
+        };
+        Duration::from_secs(self.random.random(self.min_delay.as_secs(), delay))
+    }
+
     #[inline]
     pub(super) fn max_wait(&self) -> Duration {
         self.max_wait
@@ -99,6 +132,7 @@ mod tests {
             Duration::from_secs(min_delay),
             Duration::from_secs(max_delay),
             Duration::from_secs(300),
+            BackoffStrategy::JitteredExponential,
             RandomImpl::Override(Box::new(test_random)),
         ));
 
@@ -170,4 +204,90 @@ mod tests {
         ];
         test_backoff(15, 120, test_random, attempt_delays);
     }
+
+    #[test]
+    fn fixed_backoff() {
+        let backoff = Backoff::new(
+            Duration::from_millis(500),
+            Duration::from_secs(120),
+            Duration::from_secs(300),
+            BackoffStrategy::Fixed,
+            RandomImpl::Default,
+        );
+        backoff.validate().unwrap();
+        assert_eq!(Duration::from_millis(500), backoff.delay(1, Duration::ZERO));
+        assert_eq!(
+            Duration::from_millis(500),
+            backoff.delay(50, Duration::from_secs(100))
+        );
+        // The last delay is stretched to poll right at the max wait time
+        assert_eq!(
+            Duration::from_millis(800),
+            backoff.delay(100, Duration::from_millis(299_200))
+        );
+    }
+
+    #[test]
+    fn custom_backoff_is_clamped() {
+        let backoff = Backoff::new(
+            Duration::from_millis(200),
+            Duration::from_secs(1),
+            Duration::from_secs(300),
+            BackoffStrategy::custom(|attempt| Duration::from_millis(100 * attempt as u64)),
+            RandomImpl::Default,
+        );
+        assert_eq!(Duration::from_millis(200), backoff.delay(1, Duration::ZERO));
+        assert_eq!(Duration::from_millis(500), backoff.delay(5, Duration::ZERO));
+        assert_eq!(Duration::from_secs(1), backoff.delay(20, Duration::ZERO));
+    }
+
+    #[test]
+    fn validate_delays() {
+        let backoff = |min_delay, max_delay, strategy| {
+            Backoff::new(
+                min_delay,
+                max_delay,
+                Duration::from_secs(300),
+                strategy,
+                RandomImpl::Default,
+            )
+        };
+        let sub_second = Duration::from_millis(100);
+        assert!(
+            backoff(sub_second, Duration::from_secs(1), BackoffStrategy::Fixed)
+                .validate()
+                .is_ok()
+        );
+        assert!(backoff(
+            sub_second,
+            Duration::from_secs(1),
+            BackoffStrategy::JitteredExponential
+        )
+        .validate()
+        .is_ok());
+        assert!(backoff(
+            Duration::from_secs(10),
+            Duration::from_secs(5),
+            BackoffStrategy::Fixed
+        )
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn sub_second_min_delay_falls_back_to_fixed() {
+        let backoff = Backoff::new(
+            Duration::from_millis(200),
+            Duration::from_secs(10),
+            Duration::from_secs(300),
+            BackoffStrategy::JitteredExponential,
+            RandomImpl::Override(Box::new(|_, max| max)),
+        );
+        for attempt in 1..10 {
+            assert_eq!(
+                Duration::from_millis(200),
+                backoff.delay(attempt, Duration::ZERO)
+            );
+        }
+    }
 }
@@ -76,10 +76,21 @@ where
 
     /// If the value is expired, clears the cache. Otherwise, yields the current value.
     pub async fn yield_or_clear_if_expired(&self, now: SystemTime) -> Option<T> {
+        self.yield_or_take_if_expired(now).await.ok()
+    }
+
+    /// If the value is expired, clears the cache and returns the cleared value with its expiration
+    /// as an `Err`. Otherwise, yields the current value.
+    ///
+    /// `Err(None)` is returned if there was no value, or if another thread cleared it first.
+    pub async fn yield_or_take_if_expired(
+        &self,
+        now: SystemTime,
+    ) -> Result<T, Option<(T, SystemTime)>> {
         // Short-circuit if the value is not expired
         if let Some((value, expiry)) = self.value.read().await.get() {
             if !expired(*expiry, self.buffer_time, now) {
-                return Some(value.clone());
+                return Ok(value.clone());
             } else {
                 tracing::debug!(expiry = ?expiry, delta= ?now.duration_since(*expiry), "An item existed but it expired.")
             }
@@ -93,10 +104,10 @@ where
             // Also check that we're clearing the expired value and not a value
             // that has been refreshed by another thread.
             if expired(*expiration, self.buffer_time, now) {
-                *lock = OnceCell::new();
+                return Err(std::mem::take(&mut *lock).into_inner());
             }
         }
-        None
+        Err(None)
     }
 }
 
@@ -163,4 +174,25 @@ mod tests {
             .is_none());
         assert!(cache.get().await.is_none());
     }
+
+    #[tokio::test]
+    async fn take_returns_expired_value() {
+        let cache = ExpiringCache::new(Duration::from_secs(10));
+        assert!(matches!(
+            cache.yield_or_take_if_expired(epoch_secs(100)).await,
+            Err(None)
+        ));
+
+        cache.get_or_load(|| async { identity(100) }).await.unwrap();
+        assert!(cache.yield_or_take_if_expired(epoch_secs(10)).await.is_ok());
+
+        let (taken, expiry) = cache
+            .yield_or_take_if_expired(epoch_secs(95))
+            .await
+            .expect_err("expired")
+            .expect("value is returned");
+        assert_eq!(epoch_secs(100), expiry);
+        assert_eq!(Some(epoch_secs(100)), taken.expiration());
+        assert!(cache.get().await.is_none());
+    }
 }
@@ -6,12 +6,15 @@
 //! Conversions from [`DateTime`] to the types in the
 //! [`time`](https://crates.io/crates/time) or
 //! [`chrono`](https://crates.io/crates/chrono)
-//! crates.
+//! crates, and creation of a [`DateTime`] from a `TimeSource`.
 
 use aws_smithy_types::DateTime;
 use std::error::Error as StdError;
 use std::fmt;
 
+#[cfg(feature = "convert-time-source")]
+use aws_smithy_async::time::TimeSource;
+
 #[derive(Debug)]
 enum ErrorKind {
     /// Conversion failed because the value being converted is out of range for its destination
@@ -115,6 +118,12 @@ pub trait DateTimeExt {
     #[cfg(feature = "convert-chrono")]
     fn from_chrono_fixed(time: chrono::DateTime<chrono::FixedOffset>) -> DateTime;
 
+    /// Converts a [`chrono::DateTime`] with any timezone, e.g. [`chrono::Local`], to a [`DateTime`].
+    #[cfg(feature = "convert-chrono")]
+    fn from_chrono<Tz: chrono::TimeZone>(time: chrono::DateTime<Tz>) -> DateTime {
+        Self::from_chrono_utc(time.with_timezone(&chrono::Utc))
+    }
+
     /// Converts a [`DateTime`] to a [`time::OffsetDateTime`].
     ///
     /// Returns an [`Error`] if the time is after
@@ -125,6 +134,15 @@ pub trait DateTimeExt {
     /// Converts a [`time::OffsetDateTime`] to a [`DateTime`].
     #[cfg(feature = "convert-time")]
     fn from_time(time: time::OffsetDateTime) -> DateTime;
+
+    /// Returns the current time of `time_source` as a [`DateTime`].
+    ///
+    /// Use the time source of a client config (e.g. `config.time_source()`) so that the time is
+    /// consistent with the time that requests are signed with, and can be controlled in tests.
+    #[cfg(feature = "convert-time-source")]
+    fn now(time_source: &dyn TimeSource) -> DateTime {
+        DateTime::from(time_source.now())
+    }
 }
 
 impl DateTimeExt for DateTime {
@@ -164,10 +182,18 @@ impl DateTimeExt for DateTime {
     }
 }
 
-#[cfg(all(test, any(feature = "convert-chrono", feature = "convert-time")))]
+#[cfg(all(
+    test,
+    any(
+        feature = "convert-chrono",
+        feature = "convert-time",
+        feature = "convert-time-source"
+    )
+))]
 mod test {
     use super::DateTimeExt;
     use aws_smithy_types::date_time::{DateTime, Format};
+    #[cfg(feature = "convert-chrono")]
     use chrono::Timelike;
 
     #[cfg(feature = "convert-time")]
@@ -202,6 +228,17 @@ mod test {
             .unwrap();
         let expected = DateTime::from_str("2039-07-08T11:03:11.123Z", Format::DateTime).unwrap();
         assert_eq!(expected, DateTime::from_chrono_fixed(chrono));
+        assert_eq!(expected, DateTime::from_chrono(chrono));
+        assert_eq!(expected, DateTime::from_chrono(chrono.with_timezone(&Utc)));
+    }
+
+    #[test]
+    #[cfg(feature = "convert-chrono")]
+    fn chrono_round_trip_preserves_nanoseconds() {
+        let date_time = DateTime::from_secs_and_nanos(1_700_000_000, 123_456_789);
+        let chrono = date_time.to_chrono_utc().unwrap();
+        assert_eq!(123_456_789, chrono.nanosecond());
+        assert_eq!(date_time, DateTime::from_chrono_utc(chrono));
     }
 
     #[test]
@@ -284,4 +321,26 @@ mod test {
             })
         ));
     }
+
+    #[test]
+    #[cfg(feature = "convert-time")]
+    fn time_round_trip_preserves_nanoseconds() {
+        let date_time = DateTime::from_secs_and_nanos(-1_700_000_000, 123_456_789);
+        let time = date_time.to_time().unwrap();
+        assert_eq!(123_456_789, time.nanosecond());
+        assert_eq!(date_time, DateTime::from_time(time));
+    }
+
+    #[test]
+    #[cfg(feature = "convert-time-source")]
+    fn now_reads_time_source() {
+        use aws_smithy_async::time::StaticTimeSource;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let time_source = StaticTimeSource::new(UNIX_EPOCH + Duration::new(5, 123_000_000));
+        assert_eq!(
+            DateTime::from_secs_and_nanos(5, 123_000_000),
+            DateTime::now(&time_source)
+        );
+    }
 }
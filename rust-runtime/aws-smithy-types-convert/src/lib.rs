@@ -16,7 +16,11 @@
     rust_2018_idioms
 )]
 
-#[cfg(any(feature = "convert-time", feature = "convert-chrono"))]
+#[cfg(any(
+    feature = "convert-time",
+    feature = "convert-chrono",
+    feature = "convert-time-source"
+))]
 pub mod date_time;
 
 #[cfg(feature = "convert-streams")]
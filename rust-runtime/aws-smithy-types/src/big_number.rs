@@ -0,0 +1,447 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An arbitrary-precision number that preserves its original lexical representation.
+
+use crate::error::{BigNumberParseError, TryFromNumberError, TryFromNumberErrorKind};
+use crate::Number;
+use std::fmt;
+use std::str::FromStr;
+
+/// An arbitrary-precision number that preserves the exact literal it was created from.
+///
+/// [`Number`] stores values as `u64`, `i64`, or `f64`, so integers beyond 64 bits and decimals
+/// with more significant digits than an `f64` can hold lose precision. `BigNumber` instead
+/// keeps the number's literal text (for example, `123456789012345678901234567890` or
+/// `0.10000000000000000000001`) so that it can be round-tripped through JSON or CBOR unchanged.
+///
+/// The literal follows the JSON number grammar, except that leading zeros are permitted:
+/// an optional `-`, one or more digits, an optional fraction (`.` followed by one or more
+/// digits), and an optional exponent (`e` or `E`, an optional sign, and one or more digits).
+///
+/// Equality is lexical: `1.0` and `1` are different `BigNumber`s.
+///
+/// `BigNumber` isn't used by [`Document`](crate::Document) or by generated code yet; it's read and
+/// written directly with the `aws-smithy-json` and `aws-smithy-cbor` APIs.
+///
+/// # Examples
+///
+/// ```
+/// use aws_smithy_types::BigNumber;
+///
+/// let number: BigNumber = "340282366920938463463374607431768211455".parse().unwrap();
+/// assert_eq!("340282366920938463463374607431768211455", number.as_str());
+/// assert_eq!(u128::MAX, u128::try_from(&number).unwrap());
+/// assert!(u64::try_from(&number).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigNumber {
+    literal: String,
+}
+
+impl BigNumber {
+    /// Returns the exact literal this number was created from.
+    pub fn as_str(&self) -> &str {
+        &self.literal
+    }
+
+    /// Converts this number into its literal.
+    pub fn into_string(self) -> String {
+        self.literal
+    }
+
+    /// Returns `true` if the number is negative. Negative zero (`-0`) is considered negative.
+    pub fn is_negative(&self) -> bool {
+        self.literal.starts_with('-')
+    }
+
+    /// Returns `true` if this number has an integral value, even if it is written with a
+    /// fraction or exponent (e.g. `1.50e1`).
+    pub fn is_integer(&self) -> bool {
+        Decimal::from_literal(&self.literal).exponent >= 0
+    }
+
+    /// Converts to an `f64` lossily.
+    ///
+    /// Numbers too large in magnitude to be represented become infinite.
+    /// Use `i64::try_from`, `u128::try_from`, etc. to convert without loss.
+    pub fn to_f64_lossy(&self) -> f64 {
+        f64::from_str(&self.literal).expect("BigNumber literals are valid f64 literals")
+    }
+
+    /// Returns the (negative, magnitude) of this number if it is an integer that fits in a `u128`.
+    fn to_integer_parts(&self) -> Result<(bool, u128), TryFromNumberError> {
+        let decimal = Decimal::from_literal(&self.literal);
+        if decimal.exponent < 0 {
+            return Err(TryFromNumberErrorKind::BigNumberToIntegerLossyConversion(
+                self.literal.clone(),
+            )
+            .into());
+        }
+        let out_of_range = || -> TryFromNumberError {
+            TryFromNumberErrorKind::BigNumberOutsideIntegerRange(self.literal.clone()).into()
+        };
+        let mut magnitude = 0u128;
+        for digit in decimal.digits.bytes() {
+            magnitude = magnitude
+                .checked_mul(10)
+                .and_then(|m| m.checked_add((digit - b'0') as u128))
+                .ok_or_else(out_of_range)?;
+        }
+        if magnitude != 0 {
+            for _ in 0..decimal.exponent {
+                magnitude = magnitude.checked_mul(10).ok_or_else(out_of_range)?;
+            }
+        }
+        Ok((decimal.negative && magnitude != 0, magnitude))
+    }
+}
+
+/// A number decomposed into `(-1)^negative * digits * 10^exponent`, with leading and trailing
+/// zeros removed from `digits` (zero is represented by empty `digits`).
+struct Decimal {
+    negative: bool,
+    digits: String,
+    exponent: i64,
+}
+
+impl Decimal {
+    /// Decomposes a literal that has already been validated by [`validate`].
+    fn from_literal(literal: &str) -> Self {
+        let (negative, unsigned) = match literal.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, literal),
+        };
+        let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+            Some(idx) => (&unsigned[..idx], parse_exponent(&unsigned[idx + 1..])),
+            None => (unsigned, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let all_digits = format!("{integer}{fraction}");
+        let significant = all_digits.trim_start_matches('0');
+        let digits = significant.trim_end_matches('0');
+        let exponent = if digits.is_empty() {
+            0
+        } else {
+            exponent
+                .saturating_sub(fraction.len() as i64)
+                .saturating_add((significant.len() - digits.len()) as i64)
+        };
+        Decimal {
+            negative,
+            digits: digits.to_string(),
+            exponent,
+        }
+    }
+}
+
+/// Parses a validated exponent, saturating rather than overflowing.
+fn parse_exponent(exponent: &str) -> i64 {
+    let (negative, digits) = match exponent.as_bytes().first() {
+        Some(b'-') => (true, &exponent[1..]),
+        Some(b'+') => (false, &exponent[1..]),
+        _ => (false, exponent),
+    };
+    let magnitude = digits.bytes().fold(0i64, |acc, digit| {
+        acc.saturating_mul(10).saturating_add((digit - b'0') as i64)
+    });
+    if negative {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Returns `true` if `literal` is a valid number literal.
+fn validate(literal: &str) -> bool {
+    fn digits(bytes: &[u8]) -> usize {
+        bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    let mut bytes = literal.as_bytes();
+    if let Some(b'-') = bytes.first() {
+        bytes = &bytes[1..];
+    }
+    let integer = digits(bytes);
+    if integer == 0 {
+        return false;
+    }
+    bytes = &bytes[integer..];
+    if let Some(b'.') = bytes.first() {
+        let fraction = digits(&bytes[1..]);
+        if fraction == 0 {
+            return false;
+        }
+        bytes = &bytes[1 + fraction..];
+    }
+    if let Some(b'e' | b'E') = bytes.first() {
+        bytes = &bytes[1..];
+        if let Some(b'-' | b'+') = bytes.first() {
+            bytes = &bytes[1..];
+        }
+        let exponent = digits(bytes);
+        if exponent == 0 {
+            return false;
+        }
+        bytes = &bytes[exponent..];
+    }
+    bytes.is_empty()
+}
+
+impl FromStr for BigNumber {
+    type Err = BigNumberParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if validate(s) {
+            Ok(BigNumber {
+                literal: s.to_string(),
+            })
+        } else {
+            Err(BigNumberParseError::new(s))
+        }
+    }
+}
+
+impl TryFrom<String> for BigNumber {
+    type Error = BigNumberParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if validate(&value) {
+            Ok(BigNumber { literal: value })
+        } else {
+            Err(BigNumberParseError::new(&value))
+        }
+    }
+}
+
+impl fmt::Display for BigNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.literal)
+    }
+}
+
+impl AsRef<str> for BigNumber {
+    fn as_ref(&self) -> &str {
+        &self.literal
+    }
+}
+
+impl From<BigNumber> for String {
+    fn from(value: BigNumber) -> Self {
+        value.literal
+    }
+}
+
+/// Converts a [`Number`] into a `BigNumber`. This conversion fails for infinite and `NaN` floats,
+/// since those have no numeric literal.
+impl TryFrom<Number> for BigNumber {
+    type Error = TryFromNumberError;
+
+    fn try_from(value: Number) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Number::PosInt(v) => v.into(),
+            Number::NegInt(v) => v.into(),
+            Number::Float(v) => v.try_into()?,
+        })
+    }
+}
+
+/// Converts an `f64` into a `BigNumber`. This conversion fails for infinite and `NaN` values.
+impl TryFrom<f64> for BigNumber {
+    type Error = TryFromNumberError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if value.is_finite() {
+            // `f64`'s `Debug` output always includes a fraction or exponent and is the shortest
+            // representation that round-trips.
+            Ok(BigNumber {
+                literal: format!("{value:?}"),
+            })
+        } else {
+            Err(TryFromNumberErrorKind::NonFiniteFloatToBigNumber(value).into())
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($typ:ident),+) => {
+        $(
+            impl From<$typ> for BigNumber {
+                fn from(value: $typ) -> Self {
+                    BigNumber {
+                        literal: value.to_string(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+from_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+macro_rules! to_integer {
+    ($($typ:ident),+) => {
+        $(
+            #[doc = concat!("Converts to a `", stringify!($typ), "`. This conversion fails if it is lossy.")]
+            impl TryFrom<&BigNumber> for $typ {
+                type Error = TryFromNumberError;
+
+                fn try_from(value: &BigNumber) -> Result<Self, Self::Error> {
+                    let (negative, magnitude) = value.to_integer_parts()?;
+                    let out_of_range = || -> TryFromNumberError {
+                        TryFromNumberErrorKind::BigNumberOutsideIntegerRange(value.literal.clone())
+                            .into()
+                    };
+                    if negative {
+                        // `i128::MIN` has a magnitude one greater than `i128::MAX`
+                        let signed = if magnitude == i128::MIN.unsigned_abs() {
+                            i128::MIN
+                        } else {
+                            -i128::try_from(magnitude).map_err(|_| out_of_range())?
+                        };
+                        $typ::try_from(signed).map_err(|_| out_of_range())
+                    } else {
+                        $typ::try_from(magnitude).map_err(|_| out_of_range())
+                    }
+                }
+            }
+        )+
+    };
+}
+
+to_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+#[cfg(test)]
+mod test {
+    use super::BigNumber;
+    use crate::Number;
+    use std::str::FromStr;
+
+    fn big(literal: &str) -> BigNumber {
+        BigNumber::from_str(literal).unwrap()
+    }
+
+    #[test]
+    fn parse_valid_literals() {
+        for literal in [
+            "0",
+            "-0",
+            "007",
+            "123456789012345678901234567890123456789012345678901234567890",
+            "-1.5",
+            "0.10000000000000000000001",
+            "1e5",
+            "1E+5",
+            "-2.5e-300",
+            "1e99999999999999999999999",
+        ] {
+            assert_eq!(literal, big(literal).as_str(), "{literal}");
+            assert_eq!(literal, big(literal).to_string(), "{literal}");
+        }
+    }
+
+    #[test]
+    fn parse_invalid_literals() {
+        for literal in [
+            "", "-", "+1", "1.", ".5", "1e", "1e+", "1.5.5", "0x10", "NaN", "Infinity", " 1", "1 ",
+            "1_000",
+        ] {
+            assert!(BigNumber::from_str(literal).is_err(), "{literal}");
+            assert!(
+                BigNumber::try_from(literal.to_string()).is_err(),
+                "{literal}"
+            );
+        }
+    }
+
+    #[test]
+    fn is_integer() {
+        assert!(big("10").is_integer());
+        assert!(big("1.50e1").is_integer());
+        assert!(big("1500e-2").is_integer());
+        assert!(big("0.000").is_integer());
+        assert!(!big("1.5").is_integer());
+        assert!(!big("15e-1").is_integer());
+        assert!(!big("1e-99999999999999999999999").is_integer());
+    }
+
+    #[test]
+    fn integer_conversions() {
+        assert_eq!(
+            u128::MAX,
+            u128::try_from(&big(&u128::MAX.to_string())).unwrap()
+        );
+        assert_eq!(
+            i128::MIN,
+            i128::try_from(&big(&i128::MIN.to_string())).unwrap()
+        );
+        assert_eq!(
+            i64::MIN,
+            i64::try_from(&big("-9223372036854775808")).unwrap()
+        );
+        assert_eq!(
+            u64::MAX,
+            u64::try_from(&big("18446744073709551615")).unwrap()
+        );
+        assert_eq!(1500, i32::try_from(&big("1.5e3")).unwrap());
+        assert_eq!(15, u8::try_from(&big("1500e-2")).unwrap());
+        assert_eq!(0, u32::try_from(&big("-0.0")).unwrap());
+        assert_eq!(0, u32::try_from(&big("0e99999999999999999999999")).unwrap());
+
+        assert!(u64::try_from(&big("18446744073709551616")).is_err());
+        assert!(i64::try_from(&big("-9223372036854775809")).is_err());
+        assert!(u128::try_from(&big("340282366920938463463374607431768211456")).is_err());
+        assert!(i128::try_from(&big(&u128::MAX.to_string())).is_err());
+        assert!(u32::try_from(&big("-1")).is_err());
+        assert!(i64::try_from(&big("1.5")).is_err());
+        assert!(i64::try_from(&big("1e99999999999999999999999")).is_err());
+    }
+
+    #[test]
+    fn conversion_errors_are_descriptive() {
+        assert_eq!(
+            "cannot convert 1.5 into an integer without losing its fractional part",
+            i64::try_from(&big("1.5")).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "18446744073709551616 is outside the range of the target integer type",
+            u64::try_from(&big("18446744073709551616"))
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn from_number() {
+        assert_eq!(
+            "5",
+            BigNumber::try_from(Number::PosInt(5)).unwrap().as_str()
+        );
+        assert_eq!(
+            "-5",
+            BigNumber::try_from(Number::NegInt(-5)).unwrap().as_str()
+        );
+        assert_eq!(
+            "0.1",
+            BigNumber::try_from(Number::Float(0.1)).unwrap().as_str()
+        );
+        assert_eq!(
+            "1e300",
+            BigNumber::try_from(Number::Float(1e300)).unwrap().as_str()
+        );
+        assert!(BigNumber::try_from(Number::Float(f64::NAN)).is_err());
+        assert!(BigNumber::try_from(Number::Float(f64::INFINITY)).is_err());
+    }
+
+    #[test]
+    fn to_f64_lossy() {
+        assert_eq!(0.1, big("0.10000000000000000000001").to_f64_lossy());
+        assert_eq!(1e30, big("1000000000000000000000000000000").to_f64_lossy());
+        assert_eq!(
+            f64::INFINITY,
+            big("1e99999999999999999999999").to_f64_lossy()
+        );
+    }
+}
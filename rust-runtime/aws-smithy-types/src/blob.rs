@@ -3,28 +3,50 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use bytes::Bytes;
+use std::fmt;
+
 /// Binary Blob Type
 ///
 /// Blobs represent protocol-agnostic binary content.
-#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+///
+/// A `Blob` is backed by [`Bytes`], so a blob created from `Bytes` (e.g. a slice of a response
+/// buffer) shares that buffer rather than copying it, and cloning a `Blob` is cheap.
+#[derive(Default, PartialEq, Eq, Hash, Clone)]
 pub struct Blob {
-    inner: Vec<u8>,
+    inner: Bytes,
 }
 
 impl Blob {
     /// Creates a new blob from the given `input`.
     pub fn new<T: Into<Vec<u8>>>(input: T) -> Self {
         Blob {
-            inner: input.into(),
+            inner: Bytes::from(input.into()),
         }
     }
 
     /// Consumes the `Blob` and returns a `Vec<u8>` with its contents.
+    ///
+    /// This doesn't copy if the blob is the only owner of a buffer that was created from a
+    /// `Vec<u8>`. Use [`Blob::into_bytes`] to avoid copying in all cases.
     pub fn into_inner(self) -> Vec<u8> {
+        self.inner.into()
+    }
+
+    /// Consumes the `Blob` and returns its contents as [`Bytes`] without copying.
+    pub fn into_bytes(self) -> Bytes {
         self.inner
     }
 }
 
+impl fmt::Debug for Blob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blob")
+            .field("inner", &self.inner.as_ref())
+            .finish()
+    }
+}
+
 impl AsRef<[u8]> for Blob {
     fn as_ref(&self) -> &[u8] {
         &self.inner
@@ -49,6 +71,18 @@ impl From<&[u8]> for Blob {
     }
 }
 
+impl From<Bytes> for Blob {
+    fn from(value: Bytes) -> Self {
+        Blob { inner: value }
+    }
+}
+
+impl From<Blob> for Bytes {
+    fn from(value: Blob) -> Self {
+        value.into_bytes()
+    }
+}
+
 #[cfg(all(aws_sdk_unstable, feature = "serde-serialize"))]
 mod serde_serialize {
     use super::*;
@@ -85,7 +119,7 @@ mod serde_deserialize {
             E: serde::de::Error,
         {
             match crate::base64::decode(v) {
-                Ok(inner) => Ok(Blob { inner }),
+                Ok(inner) => Ok(Blob::new(inner)),
                 Err(e) => Err(E::custom(e)),
             }
         }
@@ -102,7 +136,7 @@ mod serde_deserialize {
         where
             E: serde::de::Error,
         {
-            Ok(Blob { inner: v })
+            Ok(Blob::new(v))
         }
     }
 
@@ -123,6 +157,7 @@ mod serde_deserialize {
 #[cfg(test)]
 mod test {
     use crate::Blob;
+    use bytes::Bytes;
 
     #[test]
     fn blob_conversion() {
@@ -138,6 +173,25 @@ mod test {
         let vec2: Vec<u8> = blob2.into();
         assert_eq!(orig_vec, vec2);
     }
+
+    #[test]
+    fn blob_shares_bytes() {
+        let buffer = Bytes::from_static(b"hello, world!");
+        let blob = Blob::from(buffer.slice(7..12));
+        assert_eq!(b"world", blob.as_ref());
+        // The blob points into the original buffer rather than a copy of it
+        assert_eq!(buffer[7..].as_ptr(), blob.as_ref().as_ptr());
+        assert_eq!(Bytes::from_static(b"world"), Bytes::from(blob.clone()));
+        assert_eq!(b"world".to_vec(), blob.into_inner());
+    }
+
+    #[test]
+    fn blob_debug_lists_bytes() {
+        assert_eq!(
+            "Blob { inner: [1, 2, 3] }",
+            format!("{:?}", Blob::new([1, 2, 3]))
+        );
+    }
 }
 
 #[cfg(all(
@@ -159,9 +213,7 @@ mod test_serde {
     fn human_readable_blob() {
         let aws_in_base64 = r#"{"blob":"QVdT"}"#;
         let for_test = ForTest {
-            blob: Blob {
-                inner: vec![b'A', b'W', b'S'],
-            },
+            blob: Blob::new(vec![b'A', b'W', b'S']),
         };
         assert_eq!(for_test, serde_json::from_str(aws_in_base64).unwrap());
         assert_eq!(serde_json::to_string(&for_test).unwrap(), aws_in_base64);
@@ -172,9 +224,7 @@ mod test_serde {
         use std::ffi::CString;
 
         let for_test = ForTest {
-            blob: Blob {
-                inner: vec![b'A', b'W', b'S'],
-            },
+            blob: Blob::new(vec![b'A', b'W', b'S']),
         };
         let mut buf = vec![];
         let res = ciborium::ser::into_writer(&for_test, &mut buf);
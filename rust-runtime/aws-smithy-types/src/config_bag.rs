@@ -709,6 +709,18 @@ impl ConfigBag {
         }
     }
 
+    /// Returns a config bag that shares the configuration layers of this one.
+    ///
+    /// The interceptor state holds the state of a single request attempt, and isn't included. This
+    /// makes it possible to keep using an operation's configuration after the operation has
+    /// completed, for example from work that continues in the background.
+    pub fn without_interceptor_state(&self) -> ConfigBag {
+        ConfigBag {
+            interceptor_state: ConfigBag::base().interceptor_state,
+            tail: self.tail.clone(),
+        }
+    }
+
     /// Add a new layer with `name` after freezing the top layer so far
     pub fn add_layer(self, name: impl Into<Cow<'static, str>>) -> ConfigBag {
         self.with_fn(name, |_| {})
@@ -1069,4 +1081,28 @@ mod test {
                 .join(" ")
         );
     }
+
+    #[test]
+    fn without_interceptor_state() {
+        #[derive(Debug)]
+        struct TestStr(&'static str);
+        impl Storable for TestStr {
+            type Storer = StoreReplace<TestStr>;
+        }
+        #[derive(Debug)]
+        struct TestNum(u32);
+        impl Storable for TestNum {
+            type Storer = StoreReplace<TestNum>;
+        }
+
+        let mut layer = Layer::new("config");
+        layer.store_put(TestStr("configured"));
+        let mut bag = ConfigBag::of_layers(vec![layer]);
+        bag.interceptor_state().store_put(TestNum(1));
+
+        let copy = bag.without_interceptor_state();
+        assert_eq!("configured", copy.load::<TestStr>().unwrap().0);
+        assert!(copy.load::<TestNum>().is_none());
+        assert_eq!(1, bag.load::<TestNum>().unwrap().0);
+    }
 }
@@ -0,0 +1,210 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A string that is either borrowed for `'static` or owned.
+
+use std::borrow::{Borrow, Cow};
+use std::fmt;
+use std::ops::Deref;
+
+/// A string that is either a `&'static str` or an owned `String`.
+///
+/// Operation inputs generated with the `cowStringInputs` codegen setting store their string members as `CowStr`
+/// so that string literals and other `'static` strings can be set on an input builder without allocating.
+/// Owned strings are accepted as well and are moved into the input as is.
+///
+/// ```rust
+/// use aws_smithy_types::cow_str::CowStr;
+///
+/// let borrowed: CowStr = "example".into();
+/// assert!(borrowed.is_borrowed());
+///
+/// let owned: CowStr = String::from("example").into();
+/// assert!(!owned.is_borrowed());
+///
+/// assert_eq!(borrowed, owned);
+/// assert_eq!("example", borrowed.as_str());
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CowStr(Cow<'static, str>);
+
+impl CowStr {
+    /// Creates a `CowStr` that borrows a `&'static str`.
+    pub const fn from_static(value: &'static str) -> Self {
+        CowStr(Cow::Borrowed(value))
+    }
+
+    /// Returns the `CowStr` value as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if this string is borrowed rather than owned.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+
+    /// Converts this string into an owned `String`, allocating only if it is borrowed.
+    pub fn into_owned(self) -> String {
+        self.0.into_owned()
+    }
+
+    /// Returns the underlying `Cow`.
+    pub fn into_inner(self) -> Cow<'static, str> {
+        self.0
+    }
+}
+
+impl Deref for CowStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for CowStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for CowStr {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Borrow<str> for CowStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for CowStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for CowStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl From<&'static str> for CowStr {
+    fn from(value: &'static str) -> Self {
+        CowStr(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for CowStr {
+    fn from(value: String) -> Self {
+        CowStr(Cow::Owned(value))
+    }
+}
+
+impl From<&String> for CowStr {
+    fn from(value: &String) -> Self {
+        CowStr(Cow::Owned(value.clone()))
+    }
+}
+
+impl From<Cow<'static, str>> for CowStr {
+    fn from(value: Cow<'static, str>) -> Self {
+        CowStr(value)
+    }
+}
+
+impl From<CowStr> for String {
+    fn from(value: CowStr) -> Self {
+        value.into_owned()
+    }
+}
+
+impl From<CowStr> for Cow<'static, str> {
+    fn from(value: CowStr) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<str> for CowStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for CowStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for CowStr {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<CowStr> for String {
+    fn eq(&self, other: &CowStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<CowStr> for &str {
+    fn eq(&self, other: &CowStr) -> bool {
+        *self == other.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cow_str::CowStr;
+    use std::borrow::Cow;
+
+    #[test]
+    fn static_strings_are_not_copied() {
+        let literal = "example";
+        let value = CowStr::from(literal);
+        assert!(value.is_borrowed());
+        assert_eq!(literal.as_ptr(), value.as_str().as_ptr());
+        assert!(CowStr::from_static(literal).is_borrowed());
+    }
+
+    #[test]
+    fn owned_strings_are_moved() {
+        let owned = String::from("example");
+        let ptr = owned.as_ptr();
+        let value = CowStr::from(owned);
+        assert!(!value.is_borrowed());
+        assert_eq!(ptr, value.as_str().as_ptr());
+        let owned = String::from(value);
+        assert_eq!(ptr, owned.as_ptr());
+    }
+
+    #[test]
+    fn comparisons() {
+        let value = CowStr::from("example");
+        assert_eq!(value, CowStr::from(String::from("example")));
+        assert_eq!(value, "example");
+        assert_eq!(value, *"example");
+        assert_eq!(value, String::from("example"));
+        assert_eq!(String::from("example"), value);
+        assert_eq!(Cow::<'static, str>::from(value.clone()), "example");
+        let (a, b) = (CowStr::from("a"), CowStr::from(String::from("b")));
+        assert!(a < b);
+    }
+
+    #[test]
+    fn formatting() {
+        let value = CowStr::from("example");
+        assert_eq!("\"example\"", format!("{value:?}"));
+        assert_eq!("example", format!("{value}"));
+        assert_eq!(7, value.len());
+        assert_eq!(b"example", AsRef::<[u8]>::as_ref(&value));
+    }
+}
@@ -154,6 +154,141 @@ impl Document {
     pub fn is_null(&self) -> bool {
         matches!(self, Self::Null)
     }
+
+    /// Returns the number as an `i64` if this `Document` is a number that converts to an `i64`
+    /// without loss.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number()
+            .and_then(|number| i64::try_from(*number).ok())
+    }
+
+    /// Returns the number as an `u64` if this `Document` is a number that converts to an `u64`
+    /// without loss.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.as_number()? {
+            // `From<i64>` stores non-negative values as `NegInt` as well
+            Number::NegInt(value) => u64::try_from(*value).ok(),
+            number => u64::try_from(*number).ok(),
+        }
+    }
+
+    /// Returns the number as an `f64` if this `Document` is a number. Large integers may lose
+    /// precision.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_number().map(|number| number.to_f64_lossy())
+    }
+
+    /// Returns the value of `key` if this `Document` is an object that contains `key`.
+    pub fn get(&self, key: &str) -> Option<&Document> {
+        self.as_object()?.get(key)
+    }
+
+    /// Returns the mutable value of `key` if this `Document` is an object that contains `key`.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Document> {
+        self.as_object_mut()?.get_mut(key)
+    }
+
+    /// Returns the element at `index` if this `Document` is an array that is long enough.
+    pub fn get_index(&self, index: usize) -> Option<&Document> {
+        self.as_array()?.get(index)
+    }
+
+    /// Returns the mutable element at `index` if this `Document` is an array that is long enough.
+    pub fn get_index_mut(&mut self, index: usize) -> Option<&mut Document> {
+        self.as_array_mut()?.get_mut(index)
+    }
+
+    /// Returns the value at `path`, or `None` if there is no value at `path` or `path` is malformed.
+    ///
+    /// A path is a list of object keys separated by `.`, where each key may be followed by array
+    /// indexes in brackets, e.g. `a.b[2].c` or `items[0][1]`. A path may start with an index, e.g.
+    /// `[0].name`, and an empty path refers to the document itself. Keys that contain `.`, `[`, or
+    /// `]` can't be part of a path; use [`Document::get`] for these.
+    ///
+    /// # Examples
+    /// ```
+    /// use aws_smithy_types::Document;
+    /// use std::collections::HashMap;
+    ///
+    /// let document = Document::Object(HashMap::from([(
+    ///     "items".to_string(),
+    ///     Document::Array(vec![Document::Object(HashMap::from([(
+    ///         "name".to_string(),
+    ///         Document::from("first"),
+    ///     )]))]),
+    /// )]));
+    /// assert_eq!(
+    ///     Some("first"),
+    ///     document.path("items[0].name").and_then(Document::as_string)
+    /// );
+    /// assert_eq!(None, document.path("items[1].name"));
+    /// ```
+    pub fn path(&self, path: &str) -> Option<&Document> {
+        parse_path(path)?
+            .into_iter()
+            .try_fold(self, |document, segment| match segment {
+                PathSegment::Key(key) => document.get(key),
+                PathSegment::Index(index) => document.get_index(index),
+            })
+    }
+
+    /// Returns the mutable value at `path`, or `None` if there is no value at `path` or `path` is
+    /// malformed.
+    ///
+    /// See [`Document::path`] for the syntax of paths.
+    pub fn path_mut(&mut self, path: &str) -> Option<&mut Document> {
+        parse_path(path)?
+            .into_iter()
+            .try_fold(self, |document, segment| match segment {
+                PathSegment::Key(key) => document.get_mut(key),
+                PathSegment::Index(index) => document.get_index_mut(index),
+            })
+    }
+
+    /// Removes `key` and returns its value if this `Document` is an object that contains `key`.
+    pub fn remove(&mut self, key: &str) -> Option<Document> {
+        self.as_object_mut()?.remove(key)
+    }
+
+    /// Takes the value out of this `Document`, leaving `Document::Null` in its place.
+    pub fn take(&mut self) -> Document {
+        std::mem::take(self)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum PathSegment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Splits a path like `a.b[2].c` into its segments, or returns `None` if the path is malformed.
+fn parse_path(path: &str) -> Option<Vec<PathSegment<'_>>> {
+    let mut segments = Vec::new();
+    if path.is_empty() {
+        return Some(segments);
+    }
+    for (position, part) in path.split('.').enumerate() {
+        let (key, mut indexes) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if key.contains(']') {
+            return None;
+        }
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key));
+        } else if position > 0 || indexes.is_empty() {
+            // Only the first part of a path may start with an index
+            return None;
+        }
+        while !indexes.is_empty() {
+            let (index, rest) = indexes.strip_prefix('[')?.split_once(']')?;
+            if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            segments.push(PathSegment::Index(index.parse().ok()?));
+            indexes = rest;
+        }
+    }
+    Some(segments)
 }
 
 /// The default value is `Document::Null`.
@@ -231,8 +366,259 @@ impl From<Number> for Document {
 
 /* ANCHOR END: document */
 
+#[cfg(feature = "serde-json")]
+mod json {
+    use super::Document;
+    use crate::Number;
+    use serde_json::Value;
+
+    impl Document {
+        /// Parses a JSON string into a `Document`.
+        ///
+        /// Integers are parsed as [`Number::PosInt`] or [`Number::NegInt`] depending on their sign,
+        /// and all other numbers as [`Number::Float`].
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde-json")))]
+        pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+            serde_json::from_str::<Value>(json).map(Document::from)
+        }
+
+        /// Serializes this `Document` into a JSON string.
+        ///
+        /// Floats that can't be represented in JSON (NaN and infinities) are serialized as `null`.
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde-json")))]
+        pub fn to_json_string(&self) -> String {
+            to_json_value(self).to_string()
+        }
+    }
+
+    fn to_json_value(document: &Document) -> Value {
+        match document {
+            Document::Object(values) => Value::Object(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), to_json_value(value)))
+                    .collect(),
+            ),
+            Document::Array(values) => Value::Array(values.iter().map(to_json_value).collect()),
+            Document::Number(Number::PosInt(value)) => Value::from(*value),
+            Document::Number(Number::NegInt(value)) => Value::from(*value),
+            Document::Number(Number::Float(value)) => Value::from(*value),
+            Document::String(value) => Value::String(value.clone()),
+            Document::Bool(value) => Value::Bool(*value),
+            Document::Null => Value::Null,
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde-json")))]
+    impl From<Value> for Document {
+        fn from(value: Value) -> Self {
+            match value {
+                Value::Object(values) => Document::Object(
+                    values
+                        .into_iter()
+                        .map(|(key, value)| (key, Document::from(value)))
+                        .collect(),
+                ),
+                Value::Array(values) => {
+                    Document::Array(values.into_iter().map(Document::from).collect())
+                }
+                Value::Number(number) => Document::Number(if let Some(value) = number.as_u64() {
+                    Number::PosInt(value)
+                } else if let Some(value) = number.as_i64() {
+                    Number::NegInt(value)
+                } else {
+                    // Without the `arbitrary_precision` feature of `serde_json`, every number is an
+                    // `u64`, an `i64`, or a `f64`
+                    Number::Float(number.as_f64().unwrap_or(f64::NAN))
+                }),
+                Value::String(value) => Document::String(value),
+                Value::Bool(value) => Document::Bool(value),
+                Value::Null => Document::Null,
+            }
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde-json")))]
+    impl From<Document> for Value {
+        fn from(document: Document) -> Self {
+            to_json_value(&document)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use crate::{Document, Number};
+        use serde_json::{json, Value};
+        use std::collections::HashMap;
+
+        #[test]
+        fn json_value_round_trip() {
+            let json = json!({
+                "string": "hello",
+                "pos_int": 1,
+                "neg_int": -1,
+                "float": 0.5,
+                "bool": true,
+                "null": null,
+                "array": [1, "two", [3.5]],
+                "object": {"nested": {}},
+            });
+            let document = Document::from(json.clone());
+            let object = document.as_object().unwrap();
+            assert_eq!(Document::Number(Number::PosInt(1)), object["pos_int"]);
+            assert_eq!(Document::Number(Number::NegInt(-1)), object["neg_int"]);
+            assert_eq!(Document::Number(Number::Float(0.5)), object["float"]);
+            assert_eq!(
+                Document::Object(HashMap::new()),
+                object["object"].as_object().unwrap()["nested"]
+            );
+            assert_eq!(json, Value::from(document));
+        }
+
+        #[test]
+        fn json_string_round_trip() {
+            let json = include_str!("../test_data/serialize_document.json");
+            let document = Document::from_json_str(json).unwrap();
+            assert_eq!(
+                document,
+                Document::from_json_str(&document.to_json_string()).unwrap()
+            );
+            assert_eq!(
+                serde_json::from_str::<Value>(json).unwrap(),
+                serde_json::from_str::<Value>(&document.to_json_string()).unwrap()
+            );
+        }
+
+        #[test]
+        fn invalid_json_is_an_error() {
+            assert!(Document::from_json_str("{").is_err());
+        }
+
+        #[test]
+        fn non_finite_floats_become_null() {
+            assert_eq!("null", Document::from(f64::NAN).to_json_string());
+            assert_eq!(
+                "[null]",
+                Document::Array(vec![f64::INFINITY.into()]).to_json_string()
+            );
+        }
+
+        #[test]
+        fn positive_signed_integers_stay_integers() {
+            // `From<i64>` always creates a `Number::NegInt`
+            assert_eq!("5", Document::from(5i64).to_json_string());
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::{parse_path, PathSegment};
+    use crate::{Document, Number};
+    use std::collections::HashMap;
+
+    fn object<const N: usize>(entries: [(&str, Document); N]) -> Document {
+        Document::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    fn nested_document() -> Document {
+        object([(
+            "a",
+            object([(
+                "b",
+                Document::Array(vec![
+                    Document::Null,
+                    Document::Array(vec![Document::from("x"), Document::from("y")]),
+                    object([("c", Document::from(5u64))]),
+                ]),
+            )]),
+        )])
+    }
+
+    #[test]
+    fn parse_paths() {
+        use PathSegment::*;
+        assert_eq!(Some(vec![]), parse_path(""));
+        assert_eq!(
+            Some(vec![Key("a"), Key("b"), Index(2), Key("c")]),
+            parse_path("a.b[2].c")
+        );
+        assert_eq!(
+            Some(vec![Index(0), Index(1), Key("name")]),
+            parse_path("[0][1].name")
+        );
+        for malformed in [
+            "a..b", ".a", "a.", "a[", "a[]", "a[x]", "a[-1]", "a]", "a[0]b", "a.[0]",
+        ] {
+            assert_eq!(None, parse_path(malformed), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn path_traverses_objects_and_arrays() {
+        let document = nested_document();
+        assert_eq!(Some(&document), document.path(""));
+        assert_eq!(
+            Some(5),
+            document.path("a.b[2].c").and_then(Document::as_u64)
+        );
+        assert_eq!(
+            Some("y"),
+            document.path("a.b[1][1]").and_then(Document::as_string)
+        );
+        assert_eq!(Some(&Document::Null), document.path("a.b[0]"));
+        assert_eq!(None, document.path("a.b[3]"));
+        assert_eq!(None, document.path("a.missing"));
+        assert_eq!(None, document.path("a[0]"));
+        assert_eq!(None, document.path("a.b.c"));
+        assert_eq!(None, document.path("a.b[x]"));
+        assert_eq!(
+            Some("x"),
+            document
+                .path("a")
+                .and_then(|a| a.get("b"))
+                .and_then(|b| b.get_index(1))
+                .and_then(|b| b.get_index(0))
+                .and_then(Document::as_string)
+        );
+    }
+
+    #[test]
+    fn path_mut_modifies_nested_values() {
+        let mut document = nested_document();
+        *document.path_mut("a.b[2].c").unwrap() = Document::from("replaced");
+        assert_eq!(
+            Some("replaced"),
+            document.path("a.b[2].c").and_then(Document::as_string)
+        );
+        let taken = document.path_mut("a.b[1]").unwrap().take();
+        assert!(taken.is_array());
+        assert_eq!(Some(&Document::Null), document.path("a.b[1]"));
+        let removed = document.path_mut("a.b[2]").unwrap().remove("c");
+        assert_eq!(Some(Document::from("replaced")), removed);
+        assert_eq!(Some(&object([])), document.path("a.b[2]"));
+        assert_eq!(None, document.path_mut("a.missing"));
+    }
+
+    #[test]
+    fn typed_number_getters() {
+        assert_eq!(Some(5), Document::from(5i64).as_u64());
+        assert_eq!(Some(5), Document::from(5u64).as_i64());
+        assert_eq!(None, Document::from(-5i64).as_u64());
+        assert_eq!(None, Document::from(u64::MAX).as_i64());
+        assert_eq!(Some(2), Document::from(2.0).as_i64());
+        assert_eq!(None, Document::from(2.5).as_i64());
+        assert_eq!(Some(2.5), Document::from(2.5).as_f64());
+        assert_eq!(Some(-1.0), Document::Number(Number::NegInt(-1)).as_f64());
+        assert_eq!(None, Document::from("5").as_i64());
+        assert_eq!(None, Document::Object(HashMap::new()).get("a"));
+    }
+
     /// checks if a) serialization of json suceeds and b) it is compatible with serde_json
     #[test]
     #[cfg(all(
@@ -241,9 +627,6 @@ mod test {
         feature = "serde-deserialize"
     ))]
     fn serialize_json() {
-        use crate::Document;
-        use crate::Number;
-        use std::collections::HashMap;
         let mut map: HashMap<String, Document> = HashMap::new();
         // string
         map.insert("hello".into(), "world".to_string().into());
@@ -28,6 +28,13 @@ pub(super) enum TryFromNumberErrorKind {
     FloatToIntegerLossyConversion(f64),
     /// Used when attempting to convert a negative [`Number`](crate::Number) into an unsigned integer type.
     NegativeToUnsignedLossyConversion(i64),
+    /// Used when attempting to convert a [`BigNumber`](crate::BigNumber) with a fractional part
+    /// into an integer type.
+    BigNumberToIntegerLossyConversion(String),
+    /// Used when a [`BigNumber`](crate::BigNumber) doesn't fit into the target integer type.
+    BigNumberOutsideIntegerRange(String),
+    /// Used when attempting to convert an infinite or `NaN` float into a [`BigNumber`](crate::BigNumber).
+    NonFiniteFloatToBigNumber(f64),
 }
 
 /// The error type returned when conversion into an integer type or floating point type is lossy.
@@ -39,7 +46,7 @@ pub struct TryFromNumberError {
 impl fmt::Display for TryFromNumberError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use TryFromNumberErrorKind::*;
-        match self.kind {
+        match &self.kind {
             OutsideIntegerRange(_) => write!(f, "integer too large"),
             FloatToIntegerLossyConversion(v) => write!(
                 f,
@@ -64,6 +71,16 @@ impl fmt::Display for TryFromNumberError {
             F64ToF32LossyConversion(v) => {
                 write!(f, "will not attempt to convert {v}f64 into a f32")
             }
+            BigNumberToIntegerLossyConversion(v) => write!(
+                f,
+                "cannot convert {v} into an integer without losing its fractional part"
+            ),
+            BigNumberOutsideIntegerRange(v) => {
+                write!(f, "{v} is outside the range of the target integer type")
+            }
+            NonFiniteFloatToBigNumber(v) => {
+                write!(f, "cannot convert {v} into an arbitrary-precision number")
+            }
         }
     }
 }
@@ -77,7 +94,10 @@ impl std::error::Error for TryFromNumberError {
             | NegativeToUnsignedLossyConversion(_)
             | U64ToFloatLossyConversion(_)
             | I64ToFloatLossyConversion(_)
-            | F64ToF32LossyConversion(_) => None,
+            | F64ToF32LossyConversion(_)
+            | BigNumberToIntegerLossyConversion(_)
+            | BigNumberOutsideIntegerRange(_)
+            | NonFiniteFloatToBigNumber(_) => None,
         }
     }
 }
@@ -95,3 +115,25 @@ impl From<TryFromNumberErrorKind> for TryFromNumberError {
         Self { kind }
     }
 }
+
+/// The error type returned when a string is not a valid [`BigNumber`](crate::BigNumber) literal.
+#[derive(Debug)]
+pub struct BigNumberParseError {
+    input: String,
+}
+
+impl BigNumberParseError {
+    pub(super) fn new(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for BigNumberParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a valid number literal", self.input)
+    }
+}
+
+impl std::error::Error for BigNumberParseError {}
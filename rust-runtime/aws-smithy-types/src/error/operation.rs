@@ -6,6 +6,7 @@
 //! Errors for operations
 
 use crate::date_time::DateTimeFormatError;
+use crate::primitive::NonFiniteFloatError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
@@ -13,6 +14,7 @@ use std::fmt::{Display, Formatter};
 enum SerializationErrorKind {
     CannotSerializeUnknownVariant { union: &'static str },
     DateTimeFormatError { cause: DateTimeFormatError },
+    NonFiniteFloat { cause: NonFiniteFloatError },
 }
 
 /// An error that occurs when serialization of an operation fails.
@@ -35,13 +37,17 @@ impl Display for SerializationError {
         match self.kind {
             SerializationErrorKind::CannotSerializeUnknownVariant { union } => write!(
                 f,
-                "Cannot serialize `{union}::Unknown`. Unknown union variants cannot be serialized. \
-                This can occur when round-tripping a response from the server that was not \
-                recognized by the SDK. Consider upgrading to the latest version of the SDK.",
+                "Cannot serialize `{union}::Unknown`. Unknown union variants can only be \
+                serialized with the protocol they were received with. This can occur when \
+                round-tripping a response from the server that was not recognized by the SDK. \
+                Consider upgrading to the latest version of the SDK.",
             ),
             SerializationErrorKind::DateTimeFormatError { .. } => {
                 write!(f, "failed to serialize timestamp")
             }
+            SerializationErrorKind::NonFiniteFloat { .. } => {
+                write!(f, "failed to serialize float")
+            }
         }
     }
 }
@@ -51,6 +57,7 @@ impl Error for SerializationError {
         match &self.kind {
             SerializationErrorKind::CannotSerializeUnknownVariant { .. } => None,
             SerializationErrorKind::DateTimeFormatError { cause } => Some(cause as _),
+            SerializationErrorKind::NonFiniteFloat { cause } => Some(cause as _),
         }
     }
 }
@@ -63,6 +70,80 @@ impl From<DateTimeFormatError> for SerializationError {
     }
 }
 
+impl From<NonFiniteFloatError> for SerializationError {
+    fn from(err: NonFiniteFloatError) -> SerializationError {
+        Self {
+            kind: SerializationErrorKind::NonFiniteFloat { cause: err },
+        }
+    }
+}
+
+/// A modeled constraint that an input value can violate.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// The `@length` constraint on strings, blobs, lists, and maps
+    Length,
+    /// The `@range` constraint on numbers
+    Range,
+    /// The `@pattern` constraint on strings
+    Pattern,
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constraint::Length => write!(f, "@length"),
+            Constraint::Range => write!(f, "@range"),
+            Constraint::Pattern => write!(f, "@pattern"),
+        }
+    }
+}
+
+/// A field of an input that doesn't satisfy one of its modeled constraints
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    field: &'static str,
+    constraint: Constraint,
+    details: String,
+}
+
+impl ConstraintViolation {
+    /// Creates a violation of `constraint` by `field`
+    pub fn new(field: &'static str, constraint: Constraint, details: impl Into<String>) -> Self {
+        Self {
+            field,
+            constraint,
+            details: details.into(),
+        }
+    }
+
+    /// The name of the field that violates the constraint
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// The constraint that was violated
+    pub fn constraint(&self) -> Constraint {
+        self.constraint
+    }
+
+    /// A description of how the value violates the constraint
+    pub fn details(&self) -> &str {
+        &self.details
+    }
+}
+
+impl Display for ConstraintViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} violates {}: {}",
+            self.field, self.constraint, self.details
+        )
+    }
+}
+
 #[derive(Debug)]
 enum BuildErrorKind {
     /// A field contained an invalid value
@@ -75,6 +156,8 @@ enum BuildErrorKind {
         field: &'static str,
         details: &'static str,
     },
+    /// One or more fields violated their modeled constraints
+    ConstraintViolations(Vec<ConstraintViolation>),
     /// The serializer could not serialize the input
     SerializationError(SerializationError),
 
@@ -110,6 +193,21 @@ impl BuildError {
         }
     }
 
+    /// Construct a build error listing every field that violates its modeled constraints
+    pub fn constraint_violations(violations: Vec<ConstraintViolation>) -> Self {
+        Self {
+            kind: BuildErrorKind::ConstraintViolations(violations),
+        }
+    }
+
+    /// Returns the constraint violations of the input, if this error was caused by them
+    pub fn violations(&self) -> &[ConstraintViolation] {
+        match &self.kind {
+            BuildErrorKind::ConstraintViolations(violations) => violations,
+            _ => &[],
+        }
+    }
+
     /// Construct a build error from another underlying error
     pub fn other(source: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
         Self {
@@ -141,6 +239,14 @@ impl Display for BuildError {
             BuildErrorKind::MissingField { field, details } => {
                 write!(f, "{field} was missing: {details}")
             }
+            BuildErrorKind::ConstraintViolations(violations) => {
+                write!(f, "input violates its modeled constraints")?;
+                for (i, violation) in violations.iter().enumerate() {
+                    let separator = if i == 0 { ": " } else { "; " };
+                    write!(f, "{separator}{violation}")?;
+                }
+                Ok(())
+            }
             BuildErrorKind::SerializationError(_) => {
                 write!(f, "failed to serialize input")
             }
@@ -156,7 +262,34 @@ impl Error for BuildError {
         match &self.kind {
             BuildErrorKind::SerializationError(source) => Some(source as _),
             BuildErrorKind::Other(source) => Some(source.as_ref()),
-            BuildErrorKind::InvalidField { .. } | BuildErrorKind::MissingField { .. } => None,
+            BuildErrorKind::InvalidField { .. }
+            | BuildErrorKind::MissingField { .. }
+            | BuildErrorKind::ConstraintViolations(_) => None,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{BuildError, Constraint, ConstraintViolation};
+
+    #[test]
+    fn constraint_violations() {
+        let err = BuildError::constraint_violations(vec![
+            ConstraintViolation::new("name", Constraint::Length, "length 0 is less than 1"),
+            ConstraintViolation::new("count", Constraint::Range, "value 11 is greater than 10"),
+        ]);
+        assert_eq!(2, err.violations().len());
+        assert_eq!("count", err.violations()[1].field());
+        assert_eq!(Constraint::Range, err.violations()[1].constraint());
+        assert_eq!(
+            "input violates its modeled constraints: name violates @length: length 0 is less \
+            than 1; count violates @range: value 11 is greater than 10",
+            err.to_string()
+        );
+
+        assert!(BuildError::missing_field("name", "required")
+            .violations()
+            .is_empty());
+    }
+}
@@ -23,23 +23,30 @@ pub mod byte_stream;
 pub mod checksum_config;
 /// A typemap for storing configuration.
 pub mod config_bag;
+pub mod cow_str;
 pub mod date_time;
 pub mod endpoint;
 pub mod error;
 pub mod event_stream;
+pub mod pagination;
 pub mod primitive;
 pub mod retry;
+pub mod sensitive;
 pub mod timeout;
 
 /// Utilities for type erasure.
 pub mod type_erasure;
 
+mod big_number;
 mod blob;
 mod document;
 mod number;
 pub mod str_bytes;
+mod unknown_union;
 
+pub use big_number::BigNumber;
 pub use blob::Blob;
 pub use date_time::DateTime;
 pub use document::Document;
 pub use number::Number;
+pub use unknown_union::{UnknownUnionValue, UnknownUnionVariant};
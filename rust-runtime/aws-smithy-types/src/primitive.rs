@@ -30,6 +30,18 @@
 //! assert_eq!("Infinity", Encoder::from(f64::INFINITY).encode());
 //! assert_eq!("true", Encoder::from(true).encode());
 //! ```
+//!
+//! ## Non-finite floats
+//! Serializers and deserializers can be configured with [`NonFiniteFloatHandling`] to reject or
+//! clamp non-finite floats rather than encoding them:
+//! ```
+//! use aws_smithy_types::primitive::{Encoder, NonFiniteFloatHandling};
+//! assert!(Encoder::from(f64::NAN).encode_with(NonFiniteFloatHandling::Error).is_err());
+//! assert_eq!(
+//!     "1.7976931348623157e308",
+//!     Encoder::from(f64::INFINITY).encode_with(NonFiniteFloatHandling::Clamp).unwrap()
+//! );
+//! ```
 use crate::primitive::private::Sealed;
 use std::error::Error;
 use std::fmt;
@@ -95,6 +107,75 @@ impl Parse for f64 {
     }
 }
 
+/// How serializers and deserializers handle non-finite floating point values: NaN, infinity, and
+/// negative infinity.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonFiniteFloatHandling {
+    /// Non-finite values are encoded in the protocol's representation of them. Text based protocols,
+    /// i.e. JSON, XML, and query, use the strings `NaN`, `Infinity`, and `-Infinity`, while CBOR
+    /// uses its native representation.
+    #[default]
+    Encode,
+    /// Non-finite values are rejected with a [`NonFiniteFloatError`].
+    Error,
+    /// Infinities are replaced with the largest finite value of the same sign, e.g. [`f64::MAX`].
+    /// NaN has no sensible finite replacement, so it's rejected with a [`NonFiniteFloatError`].
+    Clamp,
+}
+
+impl NonFiniteFloatHandling {
+    /// Applies the handling to `value`, returning the value that should be encoded or decoded.
+    pub fn apply_f64(self, value: f64) -> Result<f64, NonFiniteFloatError> {
+        match self {
+            _ if value.is_finite() => Ok(value),
+            Self::Encode => Ok(value),
+            Self::Clamp if value == f64::INFINITY => Ok(f64::MAX),
+            Self::Clamp if value == f64::NEG_INFINITY => Ok(f64::MIN),
+            Self::Error | Self::Clamp => Err(NonFiniteFloatError::new(value)),
+        }
+    }
+
+    /// Applies the handling to `value`, returning the value that should be encoded or decoded.
+    pub fn apply_f32(self, value: f32) -> Result<f32, NonFiniteFloatError> {
+        match self {
+            _ if value.is_finite() => Ok(value),
+            Self::Encode => Ok(value),
+            Self::Clamp if value == f32::INFINITY => Ok(f32::MAX),
+            Self::Clamp if value == f32::NEG_INFINITY => Ok(f32::MIN),
+            Self::Error | Self::Clamp => Err(NonFiniteFloatError::new(value as f64)),
+        }
+    }
+}
+
+/// A non-finite float was rejected by [`NonFiniteFloatHandling`]
+#[derive(Debug)]
+pub struct NonFiniteFloatError {
+    value: &'static str,
+}
+
+impl NonFiniteFloatError {
+    fn new(value: f64) -> Self {
+        Self {
+            value: if value.is_nan() {
+                float::NAN
+            } else if value.is_sign_positive() {
+                float::INFINITY
+            } else {
+                float::NEG_INFINITY
+            },
+        }
+    }
+}
+
+impl fmt::Display for NonFiniteFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "non-finite float `{}` is not allowed", self.value)
+    }
+}
+
+impl Error for NonFiniteFloatError {}
+
 enum Inner {
     /// Boolean
     Bool(bool),
@@ -174,6 +255,19 @@ impl Encoder {
             }
         }
     }
+
+    /// Encodes a Smithy primitive as a string, applying `handling` to non-finite floats.
+    pub fn encode_with(
+        &mut self,
+        handling: NonFiniteFloatHandling,
+    ) -> Result<&str, NonFiniteFloatError> {
+        match &mut self.0 {
+            Inner::F32(v, _) => *v = handling.apply_f32(*v)?,
+            Inner::F64(v, _) => *v = handling.apply_f64(*v)?,
+            _ => {}
+        }
+        Ok(self.encode())
+    }
 }
 
 impl From<bool> for Encoder {
@@ -226,6 +320,7 @@ impl From<f64> for Encoder {
 
 mod float {
     use std::num::ParseFloatError;
+    use std::str::FromStr;
 
     /// Smithy encoded value for `f64::INFINITY`
     pub(crate) const INFINITY: &str = "Infinity";
@@ -237,29 +332,38 @@ mod float {
     pub(crate) const NAN: &str = "NaN";
 
     /// Parses a Smithy encoded primitive string into an `f32`.
-    pub(crate) fn parse_f32(data: &str) -> Result<f32, ParseFloatError> {
+    pub(crate) fn parse_f32(data: &str) -> Result<f32, ()> {
         match data {
             INFINITY => Ok(f32::INFINITY),
             NEG_INFINITY => Ok(f32::NEG_INFINITY),
             NAN => Ok(f32::NAN),
-            other => other.parse::<f32>(),
+            other => parse_number(other),
         }
     }
 
     /// Parses a Smithy encoded primitive string into an `f64`.
-    pub(crate) fn parse_f64(data: &str) -> Result<f64, ParseFloatError> {
+    pub(crate) fn parse_f64(data: &str) -> Result<f64, ()> {
         match data {
             INFINITY => Ok(f64::INFINITY),
             NEG_INFINITY => Ok(f64::NEG_INFINITY),
             NAN => Ok(f64::NAN),
-            other => other.parse::<f64>(),
+            other => parse_number(other),
+        }
+    }
+
+    /// Parses a float literal. Rust also accepts spellings of non-finite values, e.g. `inf` or
+    /// `nan`, which aren't valid in Smithy protocols, so only inputs containing digits are accepted.
+    fn parse_number<T: FromStr<Err = ParseFloatError>>(data: &str) -> Result<T, ()> {
+        if !data.bytes().any(|b| b.is_ascii_digit()) {
+            return Err(());
         }
+        data.parse::<T>().map_err(|_| ())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::primitive::{Encoder, Parse};
+    use crate::primitive::{Encoder, NonFiniteFloatHandling, Parse};
 
     #[test]
     fn bool_format() {
@@ -305,4 +409,51 @@ mod test {
             f32::NEG_INFINITY
         );
     }
+
+    #[test]
+    fn float_parse_rejects_rust_spellings_of_non_finite_values() {
+        for input in ["inf", "-inf", "infinity", "nan", "NAN", "+Infinity"] {
+            assert!(f64::parse_smithy_primitive(input).is_err(), "{input}");
+            assert!(f32::parse_smithy_primitive(input).is_err(), "{input}");
+        }
+        assert_eq!(f64::parse_smithy_primitive("1e3").unwrap(), 1000.0);
+    }
+
+    #[test]
+    fn non_finite_float_handling() {
+        use NonFiniteFloatHandling::*;
+        assert_eq!(Encode, NonFiniteFloatHandling::default());
+
+        for handling in [Encode, Error, Clamp] {
+            assert_eq!(Encoder::from(1.5_f64).encode_with(handling).unwrap(), "1.5");
+            assert_eq!(Encoder::from(-2_f32).encode_with(handling).unwrap(), "-2.0");
+            assert_eq!(Encoder::from(5_i32).encode_with(handling).unwrap(), "5");
+        }
+
+        assert_eq!(Encoder::from(f64::NAN).encode_with(Encode).unwrap(), "NaN");
+        assert_eq!(
+            Encoder::from(f32::NEG_INFINITY)
+                .encode_with(Encode)
+                .unwrap(),
+            "-Infinity"
+        );
+
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(Encoder::from(value).encode_with(Error).is_err());
+            assert!(Encoder::from(value as f32).encode_with(Error).is_err());
+        }
+        assert_eq!(
+            Error.apply_f64(f64::NEG_INFINITY).unwrap_err().to_string(),
+            "non-finite float `-Infinity` is not allowed"
+        );
+
+        assert_eq!(Clamp.apply_f64(f64::INFINITY).unwrap(), f64::MAX);
+        assert_eq!(Clamp.apply_f64(f64::NEG_INFINITY).unwrap(), f64::MIN);
+        assert_eq!(Clamp.apply_f32(f32::INFINITY).unwrap(), f32::MAX);
+        assert_eq!(Clamp.apply_f32(f32::NEG_INFINITY).unwrap(), f32::MIN);
+        assert_eq!(
+            Clamp.apply_f32(f32::NAN).unwrap_err().to_string(),
+            "non-finite float `NaN` is not allowed"
+        );
+    }
 }
@@ -0,0 +1,152 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Redaction of data modeled with the `@sensitive` trait.
+//!
+//! Generated `Debug` implementations redact sensitive members by default. Redaction can be lifted
+//! for local debugging in two ways:
+//!
+//! - Enabling the `unredacted-debug` feature of this crate (generated crates forward a feature of
+//!   the same name) reveals sensitive data everywhere.
+//! - Holding the guard returned by [`reveal_on_current_thread`] reveals sensitive data formatted on
+//!   the current thread.
+//!
+//! Both only take effect in builds with `debug_assertions` enabled. Cargo unifies features across
+//! the dependency graph, so any crate could turn the feature on; requiring `debug_assertions` means
+//! neither switch can lift redaction in release builds.
+
+use std::cell::Cell;
+use std::fmt::{Debug, Error, Formatter};
+use std::marker::PhantomData;
+
+/// The text that takes the place of redacted data.
+pub const REDACTED: &str = "*** Sensitive Data Redacted ***";
+
+thread_local! {
+    static REVEALED_ON_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns `true` if sensitive data should be printed rather than redacted.
+pub fn is_revealed() -> bool {
+    cfg!(debug_assertions)
+        && (cfg!(feature = "unredacted-debug") || REVEALED_ON_THREAD.with(Cell::get))
+}
+
+/// Reveals sensitive data formatted on the current thread until the returned guard is dropped.
+///
+/// This is intended for tests and local debugging. It has no effect unless `debug_assertions` are
+/// enabled.
+///
+/// # Example
+///
+/// ```
+/// use aws_smithy_types::sensitive::{reveal_on_current_thread, Sensitive};
+///
+/// let password = "hunter2";
+/// {
+///     let _guard = reveal_on_current_thread();
+///     # #[cfg(debug_assertions)]
+///     assert_eq!("\"hunter2\"", format!("{:?}", Sensitive(&password)));
+/// }
+/// # #[cfg(not(all(feature = "unredacted-debug", debug_assertions)))]
+/// assert_eq!(
+///     "\"*** Sensitive Data Redacted ***\"",
+///     format!("{:?}", Sensitive(&password))
+/// );
+/// ```
+pub fn reveal_on_current_thread() -> RevealGuard {
+    let previous = REVEALED_ON_THREAD.with(|revealed| revealed.replace(true));
+    RevealGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// Guard returned by [`reveal_on_current_thread`] that restores redaction when dropped.
+#[derive(Debug)]
+#[must_use = "sensitive data is only revealed while the guard is held"]
+pub struct RevealGuard {
+    previous: bool,
+    // The guard modifies thread-local state, so it must be dropped on the thread that created it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for RevealGuard {
+    fn drop(&mut self) {
+        REVEALED_ON_THREAD.with(|revealed| revealed.set(self.previous));
+    }
+}
+
+/// A wrapper whose [`Debug`] implementation defers to `T` when sensitive data is
+/// [revealed](is_revealed), and prints [`REDACTED`] otherwise.
+pub struct Sensitive<T>(pub T);
+
+impl<T> Debug for Sensitive<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        if is_revealed() {
+            self.0.fmt(f)
+        } else {
+            Debug::fmt(&REDACTED, f)
+        }
+    }
+}
+
+#[cfg(all(test, not(all(feature = "unredacted-debug", debug_assertions))))]
+mod test {
+    use super::{is_revealed, reveal_on_current_thread, Sensitive};
+
+    #[test]
+    fn redacted_by_default() {
+        assert!(!is_revealed());
+        assert_eq!(
+            "\"*** Sensitive Data Redacted ***\"",
+            format!("{:?}", Sensitive(Some("secret")))
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn guard_reveals_until_dropped() {
+        {
+            let _outer = reveal_on_current_thread();
+            {
+                let _inner = reveal_on_current_thread();
+                assert_eq!(
+                    "Some(\"secret\")",
+                    format!("{:?}", Sensitive(Some("secret")))
+                );
+            }
+            // Dropping a nested guard keeps the data revealed for the outer guard
+            assert!(is_revealed());
+        }
+        assert!(!is_revealed());
+        assert_eq!(
+            "\"*** Sensitive Data Redacted ***\"",
+            format!("{:?}", Sensitive("secret"))
+        );
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn never_revealed_in_release_builds() {
+        let _guard = reveal_on_current_thread();
+        assert!(!is_revealed());
+        assert_eq!(
+            "\"*** Sensitive Data Redacted ***\"",
+            format!("{:?}", Sensitive("secret"))
+        );
+    }
+
+    #[test]
+    fn guard_is_thread_local() {
+        let _guard = reveal_on_current_thread();
+        std::thread::spawn(|| assert!(!is_revealed()))
+            .join()
+            .unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::event_stream::Message;
+use crate::sensitive::Sensitive;
+use crate::{Blob, Document};
+use std::fmt;
+
+/// The data of a union variant that wasn't recognized during deserialization.
+///
+/// Clients deserialize union variants that were added to a service after the client was generated
+/// into the union's `Unknown` variant. `UnknownUnionVariant` retains the name of the variant and
+/// its value as it was received, so that it can be logged, inspected, or passed along.
+///
+/// The value is `None` when the variant wasn't read from a serialized response, for example when
+/// a required union member is missing from a response and is filled in by error correction. It's
+/// also `None` when the value couldn't be retained, e.g. a JSON value containing a number too large
+/// for a [`Document`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UnknownUnionVariant {
+    name: String,
+    value: Option<UnknownUnionValue>,
+}
+
+impl UnknownUnionVariant {
+    /// Creates an `UnknownUnionVariant` for the variant `name` with the value it was received with.
+    pub fn new(name: impl Into<String>, value: UnknownUnionValue) -> Self {
+        Self {
+            name: name.into(),
+            value: Some(value),
+        }
+    }
+
+    /// Creates an `UnknownUnionVariant` for the variant `name` whose value couldn't be retained.
+    pub fn without_value(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: None,
+        }
+    }
+
+    /// The name of the variant as it appeared on the wire, e.g. the JSON key or the XML element name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The value of the variant as it was received, if it's available.
+    pub fn value(&self) -> Option<&UnknownUnionValue> {
+        self.value.as_ref()
+    }
+
+    /// Consumes the `UnknownUnionVariant` and returns its name and value.
+    pub fn into_parts(self) -> (String, Option<UnknownUnionValue>) {
+        (self.name, self.value)
+    }
+}
+
+/// The value of an unrecognized union variant, in the form the protocol provides it.
+///
+/// The value may contain sensitive data, so its `Debug` implementation redacts it unless sensitive
+/// data is [revealed](crate::sensitive::is_revealed).
+#[non_exhaustive]
+#[derive(Clone, PartialEq)]
+pub enum UnknownUnionValue {
+    /// The value of a variant received in a JSON-based protocol.
+    Document(Document),
+    /// The serialized bytes of a variant, e.g. a CBOR data item, or an XML element (including its
+    /// start and end tags).
+    Bytes(Blob),
+    /// The event stream message that an unrecognized event was received in, with all of its
+    /// headers.
+    EventStreamMessage(Message),
+}
+
+impl UnknownUnionValue {
+    /// Returns the value as a [`Document`] if it was received in a JSON-based protocol.
+    pub fn as_document(&self) -> Option<&Document> {
+        match self {
+            Self::Document(document) => Some(document),
+            _ => None,
+        }
+    }
+
+    /// Returns the serialized bytes of the value if it was received in a binary or XML protocol,
+    /// or the payload of the message if it's an unrecognized event.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes.as_ref()),
+            Self::EventStreamMessage(message) => Some(message.payload().as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Returns the event stream message that an unrecognized event was received in.
+    pub fn as_event_stream_message(&self) -> Option<&Message> {
+        match self {
+            Self::EventStreamMessage(message) => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for UnknownUnionValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Document(document) => f
+                .debug_tuple("Document")
+                .field(&Sensitive(document))
+                .finish(),
+            Self::Bytes(bytes) => f.debug_tuple("Bytes").field(&Sensitive(bytes)).finish(),
+            Self::EventStreamMessage(message) => f
+                .debug_tuple("EventStreamMessage")
+                .field(&Sensitive(message))
+                .finish(),
+        }
+    }
+}
+
+impl From<Document> for UnknownUnionValue {
+    fn from(value: Document) -> Self {
+        Self::Document(value)
+    }
+}
+
+impl From<Blob> for UnknownUnionValue {
+    fn from(value: Blob) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{UnknownUnionValue, UnknownUnionVariant};
+    use crate::event_stream::{Header, HeaderValue, Message};
+    use crate::{Blob, Document};
+
+    #[test]
+    fn accessors() {
+        let variant = UnknownUnionVariant::new("NewVariant", Document::from("value").into());
+        assert_eq!("NewVariant", variant.name());
+        assert_eq!(
+            Some(&Document::from("value")),
+            variant.value().and_then(UnknownUnionValue::as_document)
+        );
+        assert_eq!(None, variant.value().and_then(UnknownUnionValue::as_bytes));
+
+        let (name, value) =
+            UnknownUnionVariant::new("Raw", Blob::new(vec![0xa0]).into()).into_parts();
+        assert_eq!("Raw", name);
+        assert_eq!(Some(&[0xa0][..]), value.as_ref().and_then(|v| v.as_bytes()));
+
+        let message = Message::new(&b"payload"[..])
+            .add_header(Header::new(":event-type", HeaderValue::String("NewEvent".into())));
+        let event = UnknownUnionValue::EventStreamMessage(message.clone());
+        assert_eq!(Some(&message), event.as_event_stream_message());
+        assert_eq!(Some(&b"payload"[..]), event.as_bytes());
+
+        let unretained = UnknownUnionVariant::without_value("Unretained");
+        assert_eq!("Unretained", unretained.name());
+        assert!(unretained.value().is_none());
+
+        let empty = UnknownUnionVariant::default();
+        assert_eq!("", empty.name());
+        assert!(empty.value().is_none());
+    }
+
+    #[cfg(not(feature = "unredacted-debug"))]
+    #[test]
+    fn debug_redacts_value() {
+        let variant = UnknownUnionVariant::new("NewVariant", Document::from("secret").into());
+        let debug = format!("{variant:?}");
+        assert!(debug.contains("NewVariant"), "{debug}");
+        assert!(!debug.contains("secret"), "{debug}");
+        assert_eq!(
+            "Bytes(\"*** Sensitive Data Redacted ***\")",
+            format!(
+                "{:?}",
+                UnknownUnionValue::from(Blob::new(b"secret".to_vec()))
+            )
+        );
+    }
+}
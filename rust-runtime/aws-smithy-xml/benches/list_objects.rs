@@ -0,0 +1,73 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_xml::decode::{try_data, Document, ScopedDecoder};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::borrow::Cow;
+
+/// Builds an S3 `ListObjectsV2` response listing `count` objects.
+fn list_objects_response(count: usize) -> String {
+    let mut out = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix></Prefix><KeyCount>1000</KeyCount><MaxKeys>1000</MaxKeys><IsTruncated>false</IsTruncated>"#,
+    );
+    for i in 0..count {
+        out.push_str(&format!(
+            "<Contents><Key>photos/2024/01/{i:08}/IMG_{i}.jpg</Key><LastModified>2024-01-01T00:00:00.000Z</LastModified>\
+             <ETag>&quot;d41d8cd98f00b204e9800998ecf8427e&quot;</ETag><Size>{i}</Size><StorageClass>STANDARD</StorageClass></Contents>"
+        ));
+    }
+    out.push_str("</ListBucketResult>");
+    out
+}
+
+/// Reads the `Key` and `ETag` of every object with `read`, which decides whether data is copied.
+fn read_keys<'inp, T>(
+    input: &'inp str,
+    read: impl Fn(Cow<'inp, str>) -> T,
+) -> Vec<(Option<T>, Option<T>)> {
+    let mut doc = Document::new(input);
+    let mut root = doc.root_element().unwrap();
+    let mut objects = Vec::new();
+    while let Some(mut tag) = root.next_tag() {
+        if tag.start_el().matches("Contents") {
+            objects.push(read_object(&mut tag, &read));
+        }
+    }
+    objects
+}
+
+fn read_object<'inp, T>(
+    decoder: &mut ScopedDecoder<'inp, '_>,
+    read: &impl Fn(Cow<'inp, str>) -> T,
+) -> (Option<T>, Option<T>) {
+    let (mut key, mut etag) = (None, None);
+    while let Some(mut tag) = decoder.next_tag() {
+        if tag.start_el().matches("Key") {
+            key = Some(read(try_data(&mut tag).unwrap()));
+        } else if tag.start_el().matches("ETag") {
+            etag = Some(read(try_data(&mut tag).unwrap()));
+        }
+    }
+    (key, etag)
+}
+
+pub fn list_objects_benchmark(c: &mut Criterion) {
+    let input = list_objects_response(1000);
+    let objects = read_keys(&input, |value| value);
+    assert!(matches!(objects[0].0, Some(Cow::Borrowed(_))));
+    assert!(matches!(objects[0].1, Some(Cow::Owned(_))));
+
+    // Strings that don't need unescaping, like `Key`, borrow from the response. `ETag` is escaped,
+    // so it's always unescaped into an owned string.
+    c.bench_function("list_objects_borrowed", |b| {
+        b.iter(|| black_box(read_keys(&input, |value| value)))
+    });
+    c.bench_function("list_objects_owned", |b| {
+        b.iter(|| black_box(read_keys(&input, |value| value.into_owned())))
+    });
+}
+
+criterion_group!(benches, list_objects_benchmark);
+criterion_main!(benches);
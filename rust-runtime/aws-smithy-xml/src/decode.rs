@@ -186,8 +186,24 @@ impl<'a> StartEl<'a> {
 /// This document wraps a lazy tokenizer with depth tracking.
 /// Constructing a document is essentially free.
 pub struct Document<'a> {
+    input: &'a str,
     tokenizer: Tokenizer<'a>,
     depth: Depth,
+    /// Offset of the `<` of the most recent start element
+    element_start: usize,
+    /// Offset just past the `>` of the most recent end element
+    element_end: usize,
+    /// The namespace declarations in scope, e.g. `xmlns:a="uri"`, with the depth of the elements
+    /// they're declared on (plus one)
+    namespaces: Vec<(Depth, Namespace<'a>)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Namespace<'a> {
+    /// The declared prefix, or empty for the default namespace
+    prefix: &'a str,
+    /// The declaration as it appears in the document
+    declaration: &'a str,
 }
 
 impl<'a> TryFrom<&'a [u8]> for Document<'a> {
@@ -203,8 +219,12 @@ impl<'a> TryFrom<&'a [u8]> for Document<'a> {
 impl<'inp> Document<'inp> {
     pub fn new(doc: &'inp str) -> Self {
         Document {
+            input: doc,
             tokenizer: Tokenizer::from(doc),
             depth: 0,
+            element_start: 0,
+            element_end: 0,
+            namespaces: Vec::new(),
         }
     }
 
@@ -233,6 +253,7 @@ impl<'inp> Document<'inp> {
             .next_start_element()
             .ok_or_else(|| XmlDecodeError::custom("no root element"))?;
         Ok(ScopedDecoder {
+            start: self.element_start,
             doc: self,
             start_el,
             terminated: false,
@@ -246,6 +267,7 @@ impl<'inp> Document<'inp> {
     /// to a field owned by the current function
     pub fn scoped_to<'a>(&'a mut self, start_el: StartEl<'inp>) -> ScopedDecoder<'inp, 'a> {
         ScopedDecoder {
+            start: self.element_start,
             doc: self,
             start_el,
             terminated: false,
@@ -279,20 +301,39 @@ impl<'inp> Iterator for Document<'inp> {
         // depth bookkeeping
         match tok {
             Token::ElementEnd {
-                end: ElementEnd::Close(_, _),
-                ..
+                end: ElementEnd::Close(_, _) | ElementEnd::Empty,
+                span,
             } => {
                 self.depth -= 1;
+                self.element_end = span.end();
+                let depth = self.depth;
+                self.namespaces.retain(|(declared, _)| *declared <= depth);
             }
-            Token::ElementEnd {
-                end: ElementEnd::Empty,
+            Token::Attribute {
+                prefix,
+                local,
+                span,
                 ..
-            } => self.depth -= 1,
-            t @ Token::ElementStart { .. } => {
+            } if prefix.as_str() == "xmlns" || (prefix.is_empty() && local.as_str() == "xmlns") => {
+                let prefix = if prefix.is_empty() {
+                    ""
+                } else {
+                    local.as_str()
+                };
+                self.namespaces.push((
+                    self.depth,
+                    Namespace {
+                        prefix,
+                        declaration: span.as_str(),
+                    },
+                ));
+            }
+            Token::ElementStart { span, .. } => {
+                self.element_start = span.start();
                 self.depth += 1;
                 // We want the startel and endel to have the same depth, but after the opener,
                 // the parser will be at depth 1. Return the previous depth:
-                return Some(Ok((XmlToken(t), self.depth - 1)));
+                return Some(Ok((XmlToken(tok), self.depth - 1)));
             }
             _ => {}
         }
@@ -307,6 +348,8 @@ impl<'inp> Iterator for Document<'inp> {
 pub struct ScopedDecoder<'inp, 'a> {
     doc: &'a mut Document<'inp>,
     start_el: StartEl<'inp>,
+    /// Offset of the `<` of this scope's start element
+    start: usize,
     terminated: bool,
 }
 
@@ -343,8 +386,65 @@ impl<'inp> ScopedDecoder<'inp, '_> {
         Some(self.nested_decoder(next_tag))
     }
 
+    /// Reads the rest of this scope and returns the raw text of the entire element, from its
+    /// start tag through its end tag.
+    ///
+    /// This is used to retain elements that aren't modeled, such as unknown union variants.
+    /// Namespaces declared on the element's ancestors are declared again on the element, so that
+    /// the returned text can be written into another document as-is.
+    ///
+    /// ```xml
+    /// <Response xmlns:a="uri">
+    ///    <a:A><Nested/></a:A> <-- `into_raw()` on the decoder for `A` returns
+    ///                             `<a:A xmlns:a="uri"><Nested/></a:A>`
+    /// </Response>
+    /// ```
+    pub fn into_raw(mut self) -> Result<Cow<'inp, str>, XmlDecodeError> {
+        // The element's own declarations take precedence over its ancestors' declarations of the
+        // same prefix, as do the declarations of inner ancestors over outer ones
+        let declares = |prefix: &str| {
+            self.start_el.attributes.iter().any(|attr| match prefix {
+                "" => attr.name.prefix.is_empty() && attr.name.local == "xmlns",
+                prefix => attr.name.prefix == "xmlns" && attr.name.local == prefix,
+            })
+        };
+        let mut inherited: Vec<Namespace<'inp>> = Vec::new();
+        for (declared, namespace) in self.doc.namespaces.iter().rev() {
+            if *declared <= self.start_el.depth
+                && !declares(namespace.prefix)
+                && !inherited.iter().any(|ns| ns.prefix == namespace.prefix)
+            {
+                inherited.push(*namespace);
+            }
+        }
+        let name_len = match self.start_el.prefix() {
+            "" => self.start_el.local().len(),
+            prefix => prefix.len() + 1 + self.start_el.local().len(),
+        };
+
+        for token in &mut self {
+            token?;
+        }
+        let raw = &self.doc.input[self.start..self.doc.element_end];
+        if inherited.is_empty() {
+            return Ok(Cow::Borrowed(raw));
+        }
+        // insert the declarations after the element's name in its start tag
+        let (start_tag_name, rest) = raw.split_at(1 + name_len);
+        let declarations_len: usize = inherited.iter().map(|ns| ns.declaration.len() + 1).sum();
+        let mut out = String::with_capacity(raw.len() + declarations_len);
+        out.push_str(start_tag_name);
+        for namespace in inherited.iter().rev() {
+            out.push(' ');
+            out.push_str(namespace.declaration);
+        }
+        out.push_str(rest);
+        Ok(Cow::Owned(out))
+    }
+
     fn nested_decoder<'a>(&'a mut self, start_el: StartEl<'inp>) -> ScopedDecoder<'inp, 'a> {
         ScopedDecoder {
+            start: self.doc.element_start,
             doc: self.doc,
             start_el,
             terminated: false,
@@ -513,6 +613,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn into_raw() {
+        let xml = r#"<Response><A x="1"><B>hi</B><C/></A> <D/><E>&amp;</E></Response>"#;
+        let mut doc = Document::new(xml);
+        let mut root = doc.root_element().unwrap();
+        let mut a = root.next_tag().unwrap();
+        // a partially read element is read to its end
+        assert_eq!(a.next_tag().unwrap().start_el().local(), "B");
+        assert_eq!(a.into_raw().unwrap(), r#"<A x="1"><B>hi</B><C/></A>"#);
+        assert_eq!(root.next_tag().unwrap().into_raw().unwrap(), "<D/>");
+        assert_eq!(root.next_tag().unwrap().into_raw().unwrap(), "<E>&amp;</E>");
+        assert!(root.next_tag().is_none());
+    }
+
+    #[test]
+    fn into_raw_declares_inherited_namespaces() {
+        let xml = r#"<Response xmlns="default" xmlns:a="outer" xmlns:b="b"><Inner xmlns:a="inner"><a:A x="1"><b:B/></a:A><C xmlns="own"/></Inner><D/></Response>"#;
+        let mut doc = Document::new(xml);
+        let mut root = doc.root_element().unwrap();
+        let mut inner = root.next_tag().unwrap();
+        // the innermost declaration of each prefix is used
+        assert_eq!(
+            inner.next_tag().unwrap().into_raw().unwrap(),
+            r#"<a:A xmlns="default" xmlns:b="b" xmlns:a="inner" x="1"><b:B/></a:A>"#
+        );
+        // the element's own declarations aren't repeated
+        assert_eq!(
+            inner.next_tag().unwrap().into_raw().unwrap(),
+            r#"<C xmlns:b="b" xmlns:a="inner" xmlns="own"/>"#
+        );
+        drop(inner);
+        // declarations go out of scope with their element
+        assert_eq!(
+            root.next_tag().unwrap().into_raw().unwrap(),
+            r#"<D xmlns="default" xmlns:a="outer" xmlns:b="b"/>"#
+        );
+    }
+
     #[test]
     fn read_data_invalid() {
         let xml = r#"<Response><A></A></Response>"#;
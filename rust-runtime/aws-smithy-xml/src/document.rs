@@ -0,0 +1,95 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Support for document shapes in XML protocols.
+//!
+//! XML has no native representation of an untyped document, so a document is embedded in the text
+//! of its element as a JSON string, e.g. `<Doc>{"foo":[1,true,null]}</Doc>`. The JSON is escaped
+//! like any other element text.
+
+use crate::decode::XmlDecodeError;
+use aws_smithy_json::deserialize::json_token_iter;
+use aws_smithy_json::deserialize::token::expect_document;
+use aws_smithy_json::serialize::JsonValueWriter;
+use aws_smithy_types::Document;
+
+/// Encodes `document` as the JSON text of an XML element.
+pub fn encode_document(document: &Document) -> String {
+    let mut out = String::new();
+    JsonValueWriter::new(&mut out).document(document);
+    out
+}
+
+/// Decodes a document from the (already unescaped) JSON text of an XML element.
+pub fn decode_document(data: &str) -> Result<Document, XmlDecodeError> {
+    let mut tokens = json_token_iter(data.as_bytes()).peekable();
+    let document = expect_document(&mut tokens).map_err(XmlDecodeError::unhandled)?;
+    if tokens.next().is_some() {
+        return Err(XmlDecodeError::custom(
+            "found more JSON tokens after completing parsing of the document",
+        ));
+    }
+    Ok(document)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_document, encode_document};
+    use crate::decode::{try_data, Document as XmlDocument};
+    use crate::encode::XmlWriter;
+    use aws_smithy_types::{Document, Number};
+    use std::collections::HashMap;
+
+    fn document() -> Document {
+        Document::Object(HashMap::from([
+            (
+                "list".to_string(),
+                Document::Array(vec![
+                    Document::Number(Number::PosInt(1)),
+                    Document::Number(Number::Float(2.5)),
+                    Document::Bool(true),
+                    Document::Null,
+                ]),
+            ),
+            (
+                "escaped".to_string(),
+                Document::String("<a href=\"x\">&amp;</a>".to_string()),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn round_trip_through_element_text() {
+        let mut out = String::new();
+        let mut writer = XmlWriter::new(&mut out);
+        let mut el = writer.start_el("Doc").finish();
+        el.data(&encode_document(&document()));
+        el.finish();
+        assert!(!out.contains("<a"), "document JSON must be escaped: {out}");
+
+        let mut doc = XmlDocument::new(&out);
+        let mut root = doc.root_element().unwrap();
+        let data = try_data(&mut root).unwrap();
+        assert_eq!(document(), decode_document(&data).unwrap());
+    }
+
+    #[test]
+    fn scalar_documents() {
+        assert_eq!(Document::Null, decode_document("null").unwrap());
+        assert_eq!(
+            Document::String("hello".into()),
+            decode_document(r#""hello""#).unwrap()
+        );
+        assert_eq!("false", encode_document(&Document::Bool(false)));
+    }
+
+    #[test]
+    fn invalid_documents() {
+        decode_document("").expect_err("empty");
+        decode_document("{").expect_err("incomplete");
+        decode_document("[1] [2]").expect_err("trailing tokens");
+        decode_document("<Doc/>").expect_err("not JSON");
+    }
+}
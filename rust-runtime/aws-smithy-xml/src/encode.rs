@@ -9,11 +9,16 @@
 use crate::escape::escape;
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter, Write};
+use std::io;
 
-// currently there's actually no way that encoding can fail but give it time :-)
+/// Error returned when the output of an [`XmlWriter`] fails.
+///
+/// Writing to a `String` never fails.
 #[non_exhaustive]
 #[derive(Debug)]
-pub struct XmlEncodeError {}
+pub struct XmlEncodeError {
+    source: fmt::Error,
+}
 
 impl Display for XmlEncodeError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -21,7 +26,11 @@ impl Display for XmlEncodeError {
     }
 }
 
-impl StdError for XmlEncodeError {}
+impl StdError for XmlEncodeError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.source)
+    }
+}
 
 /// XmlWriter Abstraction
 ///
@@ -49,31 +58,79 @@ impl StdError for XmlEncodeError {}
 /// assert_eq!(s, "<Root xmlns=\"http://example.com\">hello</Root>");
 /// ```
 ///
+/// The document is written to a `String` by default, but any [`fmt::Write`] can be used as the
+/// output. To write a document into an [`io::Write`], wrap the destination in an [`IoOutput`].
+/// To indent the document for debugging, wrap the output in a [`PrettyOutput`].
+///
+/// If the output fails, nothing more is written to it, and [`XmlWriter::finish`] returns the error.
+///
 /// See `tests/handwritten_serializers.rs` for more usage examples.
-pub struct XmlWriter<'a> {
-    doc: &'a mut String,
+pub struct XmlWriter<'a, W: Write = String> {
+    doc: &'a mut W,
+    result: fmt::Result,
 }
 
-impl<'a> XmlWriter<'a> {
-    pub fn new(doc: &'a mut String) -> Self {
-        Self { doc }
+impl<'a, W: Write> XmlWriter<'a, W> {
+    pub fn new(doc: &'a mut W) -> Self {
+        Self {
+            doc,
+            result: Ok(()),
+        }
     }
 }
 
-impl XmlWriter<'_> {
-    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b> {
-        write!(self.doc, "<{tag}").unwrap();
-        ElWriter::new(self.doc, tag)
+impl<W: Write> XmlWriter<'_, W> {
+    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b, W> {
+        let mut doc = Output {
+            doc: self.doc,
+            result: &mut self.result,
+        };
+        write!(doc, "<{tag}");
+        ElWriter::new(doc, tag)
+    }
+
+    /// Returns an error if writing any part of the document to the output failed.
+    pub fn finish(self) -> Result<(), XmlEncodeError> {
+        self.result.map_err(|source| XmlEncodeError { source })
     }
 }
 
-pub struct ElWriter<'a, 'b> {
+/// The output of a document, and the result of writing to it so far
+///
+/// Once a write fails, the error is kept and nothing more is written.
+struct Output<'a, W> {
+    doc: &'a mut W,
+    result: &'a mut fmt::Result,
+}
+
+impl<W: Write> Output<'_, W> {
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) {
+        if self.result.is_ok() {
+            *self.result = self.doc.write_fmt(args);
+        }
+    }
+
+    fn write_str(&mut self, s: &str) {
+        if self.result.is_ok() {
+            *self.result = self.doc.write_str(s);
+        }
+    }
+
+    fn reborrow(&mut self) -> Output<'_, W> {
+        Output {
+            doc: self.doc,
+            result: self.result,
+        }
+    }
+}
+
+pub struct ElWriter<'a, 'b, W: Write = String> {
     start: &'b str,
-    doc: Option<&'a mut String>,
+    doc: Option<Output<'a, W>>,
 }
 
-impl<'a, 'b> ElWriter<'a, 'b> {
-    fn new(doc: &'a mut String, start: &'b str) -> ElWriter<'a, 'b> {
+impl<'a, 'b, W: Write> ElWriter<'a, 'b, W> {
+    fn new(doc: Output<'a, W>, start: &'b str) -> ElWriter<'a, 'b, W> {
         ElWriter {
             start,
             doc: Some(doc),
@@ -81,25 +138,23 @@ impl<'a, 'b> ElWriter<'a, 'b> {
     }
 
     pub fn write_attribute(&mut self, key: &str, value: &str) -> &mut Self {
-        write!(self.doc(), " {}=\"{}\"", key, escape(value)).unwrap();
+        write!(self.doc(), " {}=\"{}\"", key, escape(value));
         self
     }
 
     pub fn write_ns(mut self, namespace: &str, prefix: Option<&str>) -> Self {
         match prefix {
-            Some(prefix) => {
-                write!(self.doc(), " xmlns:{}=\"{}\"", prefix, escape(namespace)).unwrap()
-            }
-            None => write!(self.doc(), " xmlns=\"{}\"", escape(namespace)).unwrap(),
+            Some(prefix) => write!(self.doc(), " xmlns:{}=\"{}\"", prefix, escape(namespace)),
+            None => write!(self.doc(), " xmlns=\"{}\"", escape(namespace)),
         }
         self
     }
 
-    fn write_end(doc: &mut String) {
-        write!(doc, ">").unwrap();
+    fn write_end(doc: &mut Output<'_, W>) {
+        doc.write_str(">");
     }
 
-    fn doc<'c>(&'c mut self) -> &'c mut String
+    fn doc<'c>(&'c mut self) -> &'c mut Output<'a, W>
     where
         'a: 'c,
     {
@@ -116,9 +171,9 @@ impl<'a, 'b> ElWriter<'a, 'b> {
         self.doc.as_mut().unwrap()
     }
 
-    pub fn finish(mut self) -> ScopeWriter<'a, 'b> {
-        let doc = self.doc.take().unwrap();
-        Self::write_end(doc);
+    pub fn finish(mut self) -> ScopeWriter<'a, 'b, W> {
+        let mut doc = self.doc.take().unwrap();
+        Self::write_end(&mut doc);
         ScopeWriter {
             doc,
             start: self.start,
@@ -126,48 +181,239 @@ impl<'a, 'b> ElWriter<'a, 'b> {
     }
 }
 
-impl Drop for ElWriter<'_, '_> {
+impl<W: Write> Drop for ElWriter<'_, '_, W> {
     fn drop(&mut self) {
-        if let Some(doc) = self.doc.take() {
+        if let Some(mut doc) = self.doc.take() {
             // Calls to write_end() are always preceded by self.doc.take(). The value in self.doc
             // is set to Some initially, and is never reset to Some after being taken. Since this
             // transition to None happens only once, we will never double-close the XML element.
-            Self::write_end(doc);
+            Self::write_end(&mut doc);
         }
     }
 }
 
 /// Wrap the construction of a tag pair `<a></a>`
-pub struct ScopeWriter<'a, 'b> {
-    doc: &'a mut String,
+pub struct ScopeWriter<'a, 'b, W: Write = String> {
+    doc: Output<'a, W>,
     start: &'b str,
 }
 
-impl Drop for ScopeWriter<'_, '_> {
+impl<W: Write> Drop for ScopeWriter<'_, '_, W> {
     fn drop(&mut self) {
-        write!(self.doc, "</{}>", self.start).unwrap();
+        write!(self.doc, "</{}>", self.start);
     }
 }
 
-impl ScopeWriter<'_, '_> {
+impl<W: Write> ScopeWriter<'_, '_, W> {
     pub fn data(&mut self, data: &str) {
-        self.doc.write_str(escape(data).as_ref()).unwrap();
+        self.doc.write_str(escape(data).as_ref());
+    }
+
+    /// Writes `xml` into this element as-is, without escaping it.
+    ///
+    /// `xml` must be well-formed element content that declares the namespaces it uses. This is
+    /// used to write elements that were retained without being parsed, such as the value of an
+    /// unknown union variant, which declares the namespaces it was received in.
+    pub fn raw(&mut self, xml: &str) {
+        self.doc.write_str(xml);
     }
 
     pub fn finish(self) {
         // drop will be called which writes the closer to the document
     }
 
-    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b> {
-        write!(self.doc, "<{tag}").unwrap();
-        ElWriter::new(self.doc, tag)
+    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b, W> {
+        let mut doc = self.doc.reborrow();
+        write!(doc, "<{tag}");
+        ElWriter::new(doc, tag)
+    }
+}
+
+/// Output that writes an XML document into an [`io::Write`].
+///
+/// Output is buffered, and flushed to the underlying writer by [`IoOutput::into_inner`], which
+/// returns the first I/O error. When the underlying writer fails before that, the [`XmlWriter`]
+/// stops writing and [`XmlWriter::finish`] returns an error.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_xml::encode::{IoOutput, XmlWriter};
+/// let mut output = IoOutput::new(Vec::new());
+/// let mut doc = XmlWriter::new(&mut output);
+/// doc.start_el("Root").finish().data("hello");
+/// doc.finish().unwrap();
+/// assert_eq!(b"<Root>hello</Root>", &output.into_inner().unwrap()[..]);
+/// ```
+pub struct IoOutput<W: io::Write> {
+    inner: io::BufWriter<W>,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write + fmt::Debug> fmt::Debug for IoOutput<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoOutput")
+            .field("inner", &self.inner)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<W: io::Write> IoOutput<W> {
+    /// Creates an output that writes to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: io::BufWriter::new(inner),
+            error: None,
+        }
+    }
+
+    /// Flushes and returns the underlying writer, or the first error that occurred while writing.
+    pub fn into_inner(mut self) -> Result<W, io::Error> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let mut inner = self
+            .inner
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?;
+        inner.flush()?;
+        Ok(inner)
+    }
+}
+
+impl<W: io::Write> Write for IoOutput<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.error.is_some() {
+            return Err(fmt::Error);
+        }
+        io::Write::write_all(&mut self.inner, s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Output that indents an XML document as it's written, for debugging.
+///
+/// Each element starts on a new line, indented by two spaces per level of nesting. Elements that
+/// only contain data are kept on a single line. The indentation changes the document's data, so
+/// this shouldn't be used for requests that are sent over the wire.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_xml::encode::{PrettyOutput, XmlWriter};
+/// let mut output = PrettyOutput::new(String::new());
+/// let mut doc = XmlWriter::new(&mut output);
+/// let mut root = doc.start_el("Root").finish();
+/// root.start_el("A").finish().data("1");
+/// root.start_el("B").finish();
+/// root.finish();
+/// assert_eq!("<Root>\n  <A>1</A>\n  <B></B>\n</Root>", output.into_inner());
+/// ```
+#[derive(Debug)]
+pub struct PrettyOutput<W> {
+    inner: W,
+    depth: usize,
+    /// Whether a `<` has been seen whose tag kind (opening or closing) isn't known yet
+    pending_lt: bool,
+    in_tag: bool,
+    in_closing_tag: bool,
+    /// Whether the previous character in the current tag was a `/`, i.e. whether a `>` closes
+    /// an empty-element tag such as `<A/>`
+    slash_in_tag: bool,
+    last: Last,
+}
+
+/// The last part of the document that was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Last {
+    Nothing,
+    OpeningTag,
+    ClosingTag,
+    Data,
+}
+
+impl<W: Write> PrettyOutput<W> {
+    /// Creates an output that writes an indented document to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            depth: 0,
+            pending_lt: false,
+            in_tag: false,
+            in_closing_tag: false,
+            slash_in_tag: false,
+            last: Last::Nothing,
+        }
+    }
+
+    /// Returns the underlying output.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn new_line(&mut self) -> fmt::Result {
+        self.inner.write_char('\n')?;
+        for _ in 0..self.depth {
+            self.inner.write_str("  ")?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for PrettyOutput<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // Data and attribute values are always escaped, so `<` and `>` only occur in tags.
+        for c in s.chars() {
+            if self.pending_lt {
+                self.pending_lt = false;
+                self.in_tag = true;
+                self.in_closing_tag = c == '/';
+                self.slash_in_tag = false;
+                if self.in_closing_tag {
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.last == Last::ClosingTag {
+                        self.new_line()?;
+                    }
+                } else if self.last != Last::Nothing {
+                    self.new_line()?;
+                }
+                self.inner.write_char('<')?;
+                self.inner.write_char(c)?;
+                continue;
+            }
+            match c {
+                '<' if !self.in_tag => self.pending_lt = true,
+                '>' if self.in_tag => {
+                    self.in_tag = false;
+                    if self.in_closing_tag || self.slash_in_tag {
+                        self.last = Last::ClosingTag;
+                    } else {
+                        self.depth += 1;
+                        self.last = Last::OpeningTag;
+                    }
+                    self.inner.write_char(c)?;
+                }
+                _ => {
+                    if !self.in_tag {
+                        self.last = Last::Data;
+                    }
+                    self.inner.write_char(c)?;
+                }
+            }
+            if self.in_tag {
+                self.slash_in_tag = c == '/';
+            }
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::encode::XmlWriter;
+    use crate::encode::{IoOutput, PrettyOutput, XmlWriter};
     use aws_smithy_protocol_test::{assert_ok, validate_body, MediaType};
+    use std::fmt::Write;
 
     #[test]
     fn forgot_finish() {
@@ -244,4 +490,91 @@ mod test {
             r#"<Hello key="&lt;key=&quot;value&quot;&gt;">&#xA;&#xD;&amp;</Hello>"#
         )
     }
+
+    #[test]
+    fn io_output_streams_document() {
+        let mut output = IoOutput::new(Vec::new());
+        {
+            let mut doc_writer = XmlWriter::new(&mut output);
+            let mut start_el = doc_writer
+                .start_el("Hello")
+                .write_ns("http://example.com", None);
+            start_el.write_attribute("key", "foo");
+            let mut tag = start_el.finish();
+            tag.start_el("inner").finish().data("hello & goodbye");
+        }
+        assert_eq!(
+            r#"<Hello xmlns="http://example.com" key="foo"><inner>hello &amp; goodbye</inner></Hello>"#,
+            std::str::from_utf8(&output.into_inner().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn io_output_returns_first_error() {
+        #[derive(Debug)]
+        struct FailingWriter;
+        impl std::io::Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("boom"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut output = IoOutput::new(FailingWriter);
+        let mut doc_writer = XmlWriter::new(&mut output);
+        // more data than is buffered, so that the writer fails before the output is flushed
+        doc_writer
+            .start_el("Hello")
+            .finish()
+            .data(&"a".repeat(10_000));
+        doc_writer.start_el("Unwritten").finish();
+        assert!(doc_writer.finish().is_err());
+        assert_eq!("boom", output.into_inner().unwrap_err().to_string());
+    }
+
+    #[test]
+    fn pretty_output_indents_document() {
+        let mut output = PrettyOutput::new(String::new());
+        {
+            let mut doc_writer = XmlWriter::new(&mut output);
+            let mut start_el = doc_writer.start_el("Hello");
+            start_el.write_attribute("key", "<value>");
+            let mut tag = start_el.finish();
+            tag.start_el("inner").finish().data("a < b");
+            let mut nested = tag.start_el("nested").finish();
+            nested.start_el("empty").finish();
+            nested.finish();
+        }
+        assert_eq!(
+            "<Hello key=\"&lt;value&gt;\">\n  <inner>a &lt; b</inner>\n  <nested>\n    <empty></empty>\n  </nested>\n</Hello>",
+            output.into_inner()
+        );
+    }
+
+    #[test]
+    fn raw_content() {
+        let raw = r#"<New a="1"><Empty/><Data>x &amp; y</Data></New>"#;
+        let mut out = String::new();
+        {
+            let mut doc_writer = XmlWriter::new(&mut out);
+            let mut scope = doc_writer.start_el("Union").finish();
+            scope.raw(raw);
+        }
+        assert_eq!(format!("<Union>{raw}</Union>"), out);
+    }
+
+    #[test]
+    fn pretty_output_self_closing_tags() {
+        let mut output = PrettyOutput::new(String::new());
+        output
+            .write_str(r#"<A><B x="1/2"/><C/><D>d</D></A>"#)
+            .unwrap();
+        // self-closing tags don't increase the depth of the following elements
+        assert_eq!(
+            "<A>\n  <B x=\"1/2\"/>\n  <C/>\n  <D>d</D>\n</A>",
+            output.into_inner()
+        );
+    }
 }
@@ -18,6 +18,9 @@
 //! [XML Binding Traits](https://smithy.io/2.0/spec/protocol-traits.html#xml-bindings)
 
 pub mod decode;
+#[cfg(feature = "document")]
+#[cfg_attr(docsrs, doc(cfg(feature = "document")))]
+pub mod document;
 pub mod encode;
 mod escape;
 mod unescape;
@@ -0,0 +1,87 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Conformance tests for non-finite floats in XML, which the code generated by
+//! XmlBindingTraitSerializerGenerator.kt and XmlBindingTraitParserGenerator.kt encode and parse
+//! with `aws_smithy_types::primitive`.
+
+use aws_smithy_types::primitive::NonFiniteFloatHandling::{self, Clamp, Encode, Error};
+use aws_smithy_types::primitive::{Encoder, NonFiniteFloatError, Parse};
+use aws_smithy_xml::decode::{try_data, Document, XmlDecodeError};
+use aws_smithy_xml::encode::XmlWriter;
+
+fn serialize(value: f64, handling: NonFiniteFloatHandling) -> Result<String, NonFiniteFloatError> {
+    let mut out = String::new();
+    {
+        let mut writer = XmlWriter::new(&mut out);
+        let mut scope = writer.start_el("Value").finish();
+        scope.data(Encoder::from(value).encode_with(handling)?);
+    }
+    Ok(out)
+}
+
+fn parse(xml: &str, handling: NonFiniteFloatHandling) -> Result<f64, XmlDecodeError> {
+    let mut doc = Document::new(xml);
+    let mut root = doc.root_element()?;
+    let data = try_data(&mut root)?;
+    let value = f64::parse_smithy_primitive(&data).map_err(XmlDecodeError::unhandled)?;
+    handling.apply_f64(value).map_err(XmlDecodeError::unhandled)
+}
+
+#[test]
+fn finite_floats_are_unaffected() {
+    for handling in [Encode, Error, Clamp] {
+        assert_eq!("<Value>1.5</Value>", serialize(1.5, handling).unwrap());
+        assert_eq!(1.5, parse("<Value>1.5</Value>", handling).unwrap());
+    }
+}
+
+#[test]
+fn encode_as_strings() {
+    for (value, expected) in [
+        (f64::INFINITY, "<Value>Infinity</Value>"),
+        (f64::NEG_INFINITY, "<Value>-Infinity</Value>"),
+        (f64::NAN, "<Value>NaN</Value>"),
+    ] {
+        assert_eq!(expected, serialize(value, Encode).unwrap());
+    }
+    assert_eq!(
+        f64::NEG_INFINITY,
+        parse("<Value>-Infinity</Value>", Encode).unwrap()
+    );
+    assert!(parse("<Value>NaN</Value>", Encode).unwrap().is_nan());
+}
+
+#[test]
+fn rust_spellings_are_rejected() {
+    for xml in ["<Value>inf</Value>", "<Value>nan</Value>"] {
+        assert!(parse(xml, Encode).is_err(), "{xml}");
+    }
+}
+
+#[test]
+fn error() {
+    for value in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+        assert!(serialize(value, Error).is_err());
+    }
+    for xml in [
+        "<Value>Infinity</Value>",
+        "<Value>-Infinity</Value>",
+        "<Value>NaN</Value>",
+    ] {
+        assert!(parse(xml, Error).is_err(), "{xml}");
+    }
+}
+
+#[test]
+fn clamp() {
+    assert_eq!(
+        "<Value>1.7976931348623157e308</Value>",
+        serialize(f64::INFINITY, Clamp).unwrap()
+    );
+    assert_eq!(f64::MIN, parse("<Value>-Infinity</Value>", Clamp).unwrap());
+    assert!(serialize(f64::NAN, Clamp).is_err());
+    assert!(parse("<Value>NaN</Value>", Clamp).is_err());
+}
@@ -3,7 +3,9 @@
  *  SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_types::endpoint::Endpoint;
 use std::error::Error;
+use std::fmt;
 
 /// Diagnostic collector for endpoint resolution
 ///
@@ -12,6 +14,7 @@ use std::error::Error;
 #[derive(Debug, Default)]
 pub(crate) struct DiagnosticCollector {
     last_error: Option<Box<dyn Error + Send + Sync>>,
+    trace: Option<EndpointRulesTrace>,
 }
 
 impl DiagnosticCollector {
@@ -42,6 +45,186 @@ impl DiagnosticCollector {
 
     /// Create a new diagnostic collector
     pub(crate) fn new() -> Self {
-        Self { last_error: None }
+        Self {
+            last_error: None,
+            trace: None,
+        }
+    }
+
+    #[allow(unused)]
+    /// Create a new diagnostic collector that records how the endpoint rules are evaluated
+    pub(crate) fn with_trace() -> Self {
+        Self {
+            last_error: None,
+            trace: Some(EndpointRulesTrace::default()),
+        }
+    }
+
+    #[allow(unused)]
+    /// Record that `rule` was skipped because `condition` wasn't met
+    pub(crate) fn condition_not_met(&mut self, rule: &'static str, condition: &'static str) {
+        if let Some(trace) = &mut self.trace {
+            trace
+                .evaluations
+                .push(RuleEvaluation::ConditionNotMet { rule, condition });
+        }
+    }
+
+    #[allow(unused)]
+    /// Record that `rule` matched and resolved to an endpoint
+    pub(crate) fn endpoint_rule_matched(&mut self, rule: &'static str) {
+        if let Some(trace) = &mut self.trace {
+            trace
+                .evaluations
+                .push(RuleEvaluation::EndpointRuleMatched { rule });
+        }
+    }
+
+    #[allow(unused)]
+    /// Record that `rule` matched and resolved to an error
+    pub(crate) fn error_rule_matched(&mut self, rule: &'static str) {
+        if let Some(trace) = &mut self.trace {
+            trace
+                .evaluations
+                .push(RuleEvaluation::ErrorRuleMatched { rule });
+        }
+    }
+
+    #[allow(unused)]
+    /// Take the recorded trace, completing it with the `result` of endpoint resolution
+    pub(crate) fn take_trace(
+        &mut self,
+        result: &Result<Endpoint, impl fmt::Display>,
+    ) -> Option<EndpointRulesTrace> {
+        let mut trace = self.trace.take()?;
+        trace.result = Some(match result {
+            Ok(endpoint) => Ok(endpoint.clone()),
+            Err(err) => Err(err.to_string()),
+        });
+        Some(trace)
+    }
+}
+
+/// Record of how the endpoint rules were evaluated for a set of endpoint parameters
+///
+/// Rules are identified by their position in the endpoint rule set, e.g. `3.2` is the second
+/// rule of the tree rule that is the third top-level rule. Conditions are displayed the way they
+/// are written in the rule set.
+#[derive(Clone, Debug, Default)]
+pub struct EndpointRulesTrace {
+    evaluations: Vec<RuleEvaluation>,
+    result: Option<Result<Endpoint, String>>,
+}
+
+// only re-exported when the endpoint rules trace is enabled in codegen
+#[allow(unused)]
+impl EndpointRulesTrace {
+    /// The rule evaluations in the order they happened
+    pub fn evaluations(&self) -> &[RuleEvaluation] {
+        &self.evaluations
+    }
+
+    /// The resolved endpoint, including its auth scheme properties, if resolution succeeded
+    pub fn endpoint(&self) -> Option<&Endpoint> {
+        self.result.as_ref().and_then(|result| result.as_ref().ok())
+    }
+
+    /// The error message, if resolution failed
+    pub fn error(&self) -> Option<&str> {
+        self.result
+            .as_ref()
+            .and_then(|result| result.as_ref().err())
+            .map(String::as_str)
+    }
+}
+
+impl fmt::Display for EndpointRulesTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for evaluation in &self.evaluations {
+            writeln!(f, "{evaluation}")?;
+        }
+        match &self.result {
+            Some(Ok(endpoint)) => write!(f, "resolved endpoint: {endpoint:?}"),
+            Some(Err(err)) => write!(f, "resolution failed: {err}"),
+            None => write!(f, "resolution didn't complete"),
+        }
+    }
+}
+
+/// A step of endpoint rule evaluation
+#[allow(unused)]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleEvaluation {
+    /// The rule was skipped because one of its conditions wasn't met
+    ConditionNotMet {
+        /// Position of the rule in the rule set
+        rule: &'static str,
+        /// The condition that wasn't met
+        condition: &'static str,
+    },
+    /// All conditions of the rule were met, and it resolved to an endpoint
+    EndpointRuleMatched {
+        /// Position of the rule in the rule set
+        rule: &'static str,
+    },
+    /// All conditions of the rule were met, and it resolved to an error
+    ErrorRuleMatched {
+        /// Position of the rule in the rule set
+        rule: &'static str,
+    },
+}
+
+impl fmt::Display for RuleEvaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleEvaluation::ConditionNotMet { rule, condition } => {
+                write!(f, "rule {rule}: condition not met: {condition}")
+            }
+            RuleEvaluation::EndpointRuleMatched { rule } => {
+                write!(f, "rule {rule}: matched endpoint rule")
+            }
+            RuleEvaluation::ErrorRuleMatched { rule } => {
+                write!(f, "rule {rule}: matched error rule")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DiagnosticCollector, RuleEvaluation};
+    use aws_smithy_types::endpoint::Endpoint;
+
+    #[test]
+    fn trace_is_only_recorded_when_enabled() {
+        let mut collector = DiagnosticCollector::new();
+        collector.condition_not_met("1", "isSet(Endpoint)");
+        let result: Result<_, String> = Ok(Endpoint::builder().url("https://a.com").build());
+        assert!(collector.take_trace(&result).is_none());
+
+        let mut collector = DiagnosticCollector::with_trace();
+        collector.condition_not_met("1", "isSet(Endpoint)");
+        collector.endpoint_rule_matched("2.1");
+        let trace = collector.take_trace(&result).expect("trace enabled");
+        assert_eq!(
+            &[
+                RuleEvaluation::ConditionNotMet {
+                    rule: "1",
+                    condition: "isSet(Endpoint)"
+                },
+                RuleEvaluation::EndpointRuleMatched { rule: "2.1" }
+            ],
+            trace.evaluations()
+        );
+        assert_eq!("https://a.com", trace.endpoint().unwrap().url());
+        assert!(trace.error().is_none());
+        let display = trace.to_string();
+        assert!(
+            display.starts_with(
+                "rule 1: condition not met: isSet(Endpoint)\nrule 2.1: matched endpoint rule\n"
+            ),
+            "{display}"
+        );
     }
 }
@@ -3,19 +3,288 @@
  *  SPDX-License-Identifier: Apache-2.0
  */
 
-use aws_smithy_http::event_stream::{InitialMessageType, Receiver};
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_http::event_stream::{DecoderLimits, InitialMessageType, Receiver, ReceiverError};
+use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_types::event_stream::{Message, RawMessage};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+const DEFAULT_MAX_TOTAL_ATTEMPTS: u32 = 100;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(20);
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+type AdjustInput<I> = dyn Fn(&mut I, &ReconnectAttempt<'_>) + Send + Sync;
+type Connect<T, E> =
+    dyn Fn(&ReconnectAttempt<'_>) -> BoxFuture<Result<EventReceiver<T, E>, BoxError>> + Send + Sync;
+
+/// Policy for reconnecting an output event stream after its connection was interrupted.
+///
+/// When the connection of an event stream that was received with a reconnect policy is interrupted, the
+/// request that opened the stream is sent again, and events are received from the new stream. Before each
+/// reconnect attempt, the [`adjust_input`](Self::adjust_input) callback can modify the input of the request,
+/// for example to resume the stream from the sequence number of the last event that was received.
+///
+/// Only transport failures (including a stream that ends in the middle of a message) cause a reconnect.
+/// Service errors and a stream that ends normally are returned to the caller as usual.
+///
+/// Each reconnect attempt is delayed with exponential backoff, starting at
+/// [`initial_backoff`](Self::initial_backoff) and doubling up to [`max_backoff`](Self::max_backoff).
+/// The backoff requires the client to be configured with a sleep implementation, which is the case by
+/// default when the `rt-tokio` feature is enabled. Without one, the stream is not reconnected.
+pub struct ReconnectPolicy<I> {
+    max_attempts: u32,
+    max_total_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    adjust_input: Option<Arc<AdjustInput<I>>>,
+}
+
+impl<I> ReconnectPolicy<I> {
+    /// Creates a policy that makes up to `max_attempts` consecutive reconnect attempts after an interruption.
+    ///
+    /// The attempt count is reset whenever an event is received successfully. The total number of attempts
+    /// over the lifetime of the stream is capped separately by [`max_total_attempts`](Self::max_total_attempts).
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            max_total_attempts: DEFAULT_MAX_TOTAL_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            adjust_input: None,
+        }
+    }
+
+    /// Sets the maximum number of reconnect attempts over the lifetime of the stream, which isn't reset
+    /// when events are received. Defaults to 100.
+    pub fn max_total_attempts(mut self, max_total_attempts: u32) -> Self {
+        self.max_total_attempts = max_total_attempts;
+        self
+    }
+
+    /// Sets the delay before the first consecutive reconnect attempt. Defaults to 1 second.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay before a reconnect attempt. Defaults to 20 seconds.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets a callback that adjusts the input of the request before each reconnect attempt.
+    pub fn adjust_input(
+        mut self,
+        adjust_input: impl Fn(&mut I, &ReconnectAttempt<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.adjust_input = Some(Arc::new(adjust_input));
+        self
+    }
+
+    /// Returns the maximum number of consecutive reconnect attempts.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+}
+
+impl<I> Clone for ReconnectPolicy<I> {
+    fn clone(&self) -> Self {
+        Self {
+            max_attempts: self.max_attempts,
+            max_total_attempts: self.max_total_attempts,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            adjust_input: self.adjust_input.clone(),
+        }
+    }
+}
+
+impl<I> fmt::Debug for ReconnectPolicy<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("max_total_attempts", &self.max_total_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field(
+                "adjust_input",
+                &self.adjust_input.as_ref().map(|_| "** callback **"),
+            )
+            .finish()
+    }
+}
+
+/// Information about a reconnect attempt that is passed to [`ReconnectPolicy::adjust_input`].
 #[derive(Debug)]
+pub struct ReconnectAttempt<'a> {
+    attempt: u32,
+    interruption: &'a (dyn std::error::Error + Send + Sync + 'static),
+}
+
+impl ReconnectAttempt<'_> {
+    /// Returns the number of this attempt since the last successfully received event, starting at 1.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Returns the error that interrupted the event stream.
+    pub fn interruption(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self.interruption
+    }
+}
+
+/// Re-issues the request that opened an event stream.
+struct Reconnect<T, E> {
+    max_attempts: u32,
+    max_total_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    connect: Box<Connect<T, E>>,
+}
+
+impl<T, E> Reconnect<T, E> {
+    fn new<I, F>(policy: ReconnectPolicy<I>, input: I, send: F) -> Self
+    where
+        I: Clone + Send + 'static,
+        F: Fn(I) -> BoxFuture<Result<EventReceiver<T, E>, BoxError>> + Send + Sync + 'static,
+    {
+        let input = Mutex::new(input);
+        let adjust_input = policy.adjust_input;
+        Self {
+            max_attempts: policy.max_attempts,
+            max_total_attempts: policy.max_total_attempts,
+            initial_backoff: policy.initial_backoff,
+            max_backoff: policy.max_backoff,
+            connect: Box::new(move |attempt| {
+                let input = {
+                    let mut input = input.lock().unwrap();
+                    if let Some(adjust_input) = &adjust_input {
+                        adjust_input(&mut input, attempt);
+                    }
+                    input.clone()
+                };
+                send(input)
+            }),
+        }
+    }
+
+    /// Returns the delay before the given consecutive reconnect attempt, starting at 1.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Returns true if `err` was caused by the connection of the event stream being interrupted.
+fn is_interruption<E>(err: &SdkError<E, RawMessage>) -> bool
+where
+    E: std::error::Error + 'static,
+{
+    match err {
+        SdkError::DispatchFailure(_) | SdkError::TimeoutError(_) => true,
+        SdkError::ResponseError(_) => std::error::Error::source(err)
+            .and_then(|err| err.downcast_ref::<ReceiverError>())
+            .map(|err| !err.is_buffer_limit_exceeded())
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 /// Receives unmarshalled events at a time out of an Event Stream.
 pub struct EventReceiver<T, E> {
     inner: Receiver<T, E>,
+    initial_response: Option<Message>,
+    reconnect: Option<Reconnect<T, E>>,
+    reconnect_attempts: u32,
+    total_reconnect_attempts: u32,
+    sleep_impl: Option<SharedAsyncSleep>,
+}
+
+impl<T: fmt::Debug, E: fmt::Debug> fmt::Debug for EventReceiver<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventReceiver")
+            .field("inner", &self.inner)
+            .field("initial_response", &self.initial_response)
+            .field(
+                "reconnect",
+                &self.reconnect.as_ref().map(|r| r.max_attempts),
+            )
+            .finish()
+    }
 }
 
 impl<T, E> EventReceiver<T, E> {
     pub(crate) fn new(inner: Receiver<T, E>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            initial_response: None,
+            reconnect: None,
+            reconnect_attempts: 0,
+            total_reconnect_attempts: 0,
+            sleep_impl: None,
+        }
+    }
+
+    /// Reconnects this receiver according to `policy` when its connection is interrupted. `send` re-issues
+    /// the request that opened the event stream with the given input and returns the new event stream.
+    #[allow(dead_code)]
+    pub(crate) fn with_reconnect<I, F>(
+        mut self,
+        policy: ReconnectPolicy<I>,
+        input: I,
+        send: F,
+    ) -> Self
+    where
+        I: Clone + Send + 'static,
+        F: Fn(I) -> BoxFuture<Result<EventReceiver<T, E>, BoxError>> + Send + Sync + 'static,
+    {
+        self.reconnect = Some(Reconnect::new(policy, input, send));
+        self
+    }
+
+    /// Sets the duration after which [`recv`](Self::recv) fails with a retryable
+    /// `SdkError::TimeoutError` if no data arrives on the stream. Heartbeat messages sent by the
+    /// service count as data, but are never returned as events. By default, there is no idle timeout.
+    ///
+    /// The idle timeout requires the client to be configured with a sleep implementation, which is
+    /// the case by default when the `rt-tokio` feature is enabled.
+    pub fn set_idle_timeout(&mut self, idle_timeout: Option<Duration>) -> &mut Self {
+        self.inner.set_idle_timeout(idle_timeout);
+        self
+    }
+
+    /// Sets the maximum number of bytes that are buffered while waiting for an event's message frame to
+    /// be completed. When a frame grows beyond this limit, [`recv`](Self::recv) fails with an
+    /// `SdkError::ResponseError` and the stream ends. By default, there is no limit.
+    ///
+    /// Events are only read from the connection when `recv` is called, so a slow consumer applies
+    /// backpressure to the service through flow control instead of causing events to be buffered.
+    pub fn set_max_buffered_bytes(&mut self, max_buffered_bytes: Option<usize>) -> &mut Self {
+        self.inner.set_max_buffered_bytes(max_buffered_bytes);
+        self
+    }
+
+    /// Sets the limits on the frame length, headers length, and header count of received events.
+    /// Events that exceed them are rejected before the rest of their frame is read, and
+    /// [`recv`](Self::recv) fails with an `SdkError::ResponseError`.
+    pub fn set_decoder_limits(&mut self, limits: DecoderLimits) -> &mut Self {
+        self.inner.set_decoder_limits(limits);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn set_sleep_impl(&mut self, sleep_impl: Option<SharedAsyncSleep>) {
+        self.inner.set_sleep_impl(sleep_impl.clone());
+        self.sleep_impl = sleep_impl;
     }
 
     #[allow(dead_code)]
@@ -31,16 +300,396 @@ impl<T, E> EventReceiver<T, E> {
     pub(crate) async fn try_recv_initial_response(
         &mut self,
     ) -> Result<Option<Message>, SdkError<E, RawMessage>> {
-        self.inner
+        let message = self
+            .inner
             .try_recv_initial(InitialMessageType::Response)
-            .await
+            .await?;
+        self.initial_response = message.clone();
+        Ok(message)
+    }
+
+    /// Returns the `initial-response` message that the service sent before any events, if it sent one.
+    ///
+    /// For protocols that don't bind output members to HTTP, the non-event stream members of the operation
+    /// output are deserialized from this message, so they are usually more convenient to read from the output.
+    /// If the event stream was reconnected, this is the initial response of the most recent connection.
+    pub fn initial_response(&self) -> Option<&Message> {
+        self.initial_response.as_ref()
+    }
+
+    /// Deserializes the payload of the kept `initial-response` message into `builder` with `parse`.
+    ///
+    /// Returns `Ok(None)` if the service didn't send an `initial-response` message.
+    #[allow(dead_code)]
+    pub(crate) fn parse_initial_response<B, PE>(
+        &self,
+        builder: B,
+        parse: impl FnOnce(&[u8], B) -> Result<B, PE>,
+    ) -> Result<Option<B>, BoxError>
+    where
+        PE: Into<BoxError>,
+    {
+        self.initial_response
+            .as_ref()
+            .map(|message| parse(message.payload(), builder).map_err(Into::into))
+            .transpose()
     }
 
     /// Asynchronously tries to receive an event from the stream. If the stream has ended, it
     /// returns an `Ok(None)`. If there is a transport layer error, it will return
     /// `Err(SdkError::DispatchFailure)`. Service-modeled errors will be a part of the returned
     /// messages.
-    pub async fn recv(&mut self) -> Result<Option<T>, SdkError<E, RawMessage>> {
-        self.inner.recv().await
+    ///
+    /// If the stream was opened with a [`ReconnectPolicy`], transport layer errors are only returned
+    /// once the policy's reconnect attempts have been exhausted, or if no sleep implementation is
+    /// configured to back off between them.
+    pub async fn recv(&mut self) -> Result<Option<T>, SdkError<E, RawMessage>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        'receive: loop {
+            let interruption = match self.inner.recv().await {
+                Ok(event) => {
+                    self.reconnect_attempts = 0;
+                    return Ok(event);
+                }
+                Err(err) if is_interruption(&err) => err,
+                Err(err) => return Err(err),
+            };
+            let (reconnect, sleep_impl) = match (&self.reconnect, &self.sleep_impl) {
+                (Some(reconnect), Some(sleep_impl)) => (reconnect, sleep_impl.clone()),
+                (Some(_), None) => {
+                    tracing::debug!(
+                        interruption = %interruption,
+                        "not reconnecting event stream because no sleep implementation is configured"
+                    );
+                    return Err(interruption);
+                }
+                (None, _) => return Err(interruption),
+            };
+            while self.reconnect_attempts < reconnect.max_attempts
+                && self.total_reconnect_attempts < reconnect.max_total_attempts
+            {
+                self.reconnect_attempts += 1;
+                self.total_reconnect_attempts += 1;
+                let attempt = ReconnectAttempt {
+                    attempt: self.reconnect_attempts,
+                    interruption: &interruption,
+                };
+                let backoff = reconnect.backoff(attempt.attempt);
+                tracing::debug!(
+                    attempt = attempt.attempt,
+                    backoff = ?backoff,
+                    interruption = %interruption,
+                    "reconnecting event stream"
+                );
+                sleep_impl.sleep(backoff).await;
+                match (reconnect.connect)(&attempt).await {
+                    Ok(receiver) => {
+                        let idle_timeout = self.inner.idle_timeout();
+                        let max_buffered_bytes = self.inner.max_buffered_bytes();
+                        let decoder_limits = *self.inner.decoder_limits();
+                        self.inner = receiver.inner;
+                        self.initial_response = receiver.initial_response;
+                        self.inner
+                            .set_sleep_impl(Some(sleep_impl))
+                            .set_idle_timeout(idle_timeout)
+                            .set_max_buffered_bytes(max_buffered_bytes)
+                            .set_decoder_limits(decoder_limits);
+                        continue 'receive;
+                    }
+                    Err(err) => {
+                        tracing::debug!(
+                            attempt = attempt.attempt,
+                            error = %err,
+                            "failed to reconnect event stream"
+                        );
+                    }
+                }
+            }
+            return Err(interruption);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventReceiver, ReconnectPolicy};
+    use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
+    use aws_smithy_async::test_util::InstantSleep;
+    use aws_smithy_eventstream::error::Error as EventStreamError;
+    use aws_smithy_eventstream::frame::{write_message_to, UnmarshallMessage, UnmarshalledMessage};
+    use aws_smithy_http::event_stream::Receiver;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+    use bytes::Bytes;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct Unmarshaller;
+    impl UnmarshallMessage for Unmarshaller {
+        type Output = String;
+        type Error = EventStreamError;
+
+        fn unmarshall(
+            &self,
+            message: &Message,
+        ) -> Result<UnmarshalledMessage<Self::Output, Self::Error>, EventStreamError> {
+            Ok(UnmarshalledMessage::Event(
+                std::str::from_utf8(&message.payload()[..]).unwrap().into(),
+            ))
+        }
+    }
+
+    fn encode_message(message: &str) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        let message = Message::new(Bytes::copy_from_slice(message.as_bytes()));
+        write_message_to(&message, &mut buffer).unwrap();
+        buffer
+    }
+
+    fn event_receiver(body: Vec<u8>) -> EventReceiver<String, EventStreamError> {
+        EventReceiver::new(Receiver::new(Unmarshaller, SdkBody::from(body)))
+    }
+
+    /// Returns a receiver that receives `event` and is then interrupted in the middle of the next message.
+    fn interrupted_receiver(event: &str) -> EventReceiver<String, EventStreamError> {
+        let mut body = encode_message(event);
+        body.extend_from_slice(&encode_message("truncated")[..10]);
+        event_receiver(body)
+    }
+
+    /// Returns a sleep implementation that completes immediately and records the durations it slept for.
+    fn instant_sleep() -> (SharedAsyncSleep, Arc<Mutex<Vec<Duration>>>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        (SharedAsyncSleep::new(InstantSleep::new(log.clone())), log)
+    }
+
+    #[tokio::test]
+    async fn initial_response_is_kept() {
+        let initial_response = Message::new(&b"{}"[..])
+            .add_header(Header::new(
+                ":message-type",
+                HeaderValue::String("event".into()),
+            ))
+            .add_header(Header::new(
+                ":event-type",
+                HeaderValue::String("initial-response".into()),
+            ));
+        let mut body = Vec::new();
+        write_message_to(&initial_response, &mut body).unwrap();
+        body.extend(encode_message("one"));
+
+        let mut receiver = event_receiver(body);
+        assert!(receiver.initial_response().is_none());
+        let received = receiver.try_recv_initial_response().await.unwrap();
+        assert_eq!(Some(&initial_response), received.as_ref());
+        assert_eq!(Some(&initial_response), receiver.initial_response());
+        let parse = |payload: &[u8], mut builder: Vec<u8>| {
+            builder.extend_from_slice(payload);
+            Ok::<_, BoxError>(builder)
+        };
+        assert_eq!(
+            Some(b"{}".to_vec()),
+            receiver.parse_initial_response(Vec::new(), parse).unwrap()
+        );
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+
+        // Streams without an initial response deliver their first event as usual
+        let mut receiver = event_receiver(encode_message("one"));
+        assert!(receiver
+            .try_recv_initial_response()
+            .await
+            .unwrap()
+            .is_none());
+        assert!(receiver.initial_response().is_none());
+        assert!(receiver
+            .parse_initial_response(Vec::<u8>::new(), |_, builder| Ok::<_, BoxError>(builder))
+            .unwrap()
+            .is_none());
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn interruption_is_returned_without_policy() {
+        let mut receiver = interrupted_receiver("one");
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+        assert!(receiver.recv().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn reconnects_with_adjusted_input() {
+        let inputs = Arc::new(Mutex::new(Vec::new()));
+        let policy = ReconnectPolicy::new(1).adjust_input(|position: &mut u32, attempt| {
+            assert_eq!(1, attempt.attempt());
+            *position += 1;
+        });
+        let mut receiver = interrupted_receiver("one").with_reconnect(policy, 0, {
+            let inputs = inputs.clone();
+            move |position| {
+                inputs.lock().unwrap().push(position);
+                let receiver = if position == 1 {
+                    interrupted_receiver("two")
+                } else {
+                    event_receiver(encode_message("three"))
+                };
+                Box::pin(async move { Ok(receiver) })
+            }
+        });
+        let (sleep_impl, sleeps) = instant_sleep();
+        receiver.set_sleep_impl(Some(sleep_impl));
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+        assert_eq!(Some("two".to_string()), receiver.recv().await.unwrap());
+        // The attempt count was reset by the successfully received event
+        assert_eq!(Some("three".to_string()), receiver.recv().await.unwrap());
+        assert_eq!(None, receiver.recv().await.unwrap());
+        assert_eq!(vec![1, 2], *inputs.lock().unwrap());
+        assert_eq!(
+            vec![Duration::from_secs(1), Duration::from_secs(1)],
+            *sleeps.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn interruption_is_returned_when_attempts_are_exhausted() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let policy = ReconnectPolicy::new(5)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500));
+        let mut receiver = interrupted_receiver("one").with_reconnect(policy, (), {
+            let attempts = attempts.clone();
+            move |_| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async { Err("service unavailable".into()) })
+            }
+        });
+        let (sleep_impl, sleeps) = instant_sleep();
+        receiver.set_sleep_impl(Some(sleep_impl));
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+        let err = receiver.recv().await.expect_err("attempts are exhausted");
+        assert!(format!("{err:?}").contains("ResponseError"), "{err:?}");
+        assert_eq!(5, attempts.load(Ordering::SeqCst));
+        // The backoff doubles between consecutive attempts, up to the maximum
+        assert_eq!(
+            vec![100, 200, 400, 500, 500],
+            sleeps
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|d| d.as_millis())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn total_attempts_are_capped() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let policy = ReconnectPolicy::new(1).max_total_attempts(2);
+        let mut receiver = interrupted_receiver("one").with_reconnect(policy, (), {
+            let attempts = attempts.clone();
+            move |_| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                let receiver = interrupted_receiver("again");
+                Box::pin(async move { Ok(receiver) })
+            }
+        });
+        let (sleep_impl, _sleeps) = instant_sleep();
+        receiver.set_sleep_impl(Some(sleep_impl));
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+        // Every reconnected stream delivers an event, which resets the consecutive attempt count
+        assert_eq!(Some("again".to_string()), receiver.recv().await.unwrap());
+        assert_eq!(Some("again".to_string()), receiver.recv().await.unwrap());
+        receiver
+            .recv()
+            .await
+            .expect_err("total attempts are exhausted");
+        assert_eq!(2, attempts.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn interruption_is_returned_without_sleep_impl() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let mut receiver =
+            interrupted_receiver("one").with_reconnect(ReconnectPolicy::new(1), (), {
+                let attempts = attempts.clone();
+                move |_| {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async { Ok(event_receiver(encode_message("two"))) })
+                }
+            });
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+        assert!(receiver.recv().await.is_err());
+        assert_eq!(0, attempts.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn oversized_frames_are_not_reconnected() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let body = encode_message(&"x".repeat(100))[..80].to_vec();
+        let mut receiver = event_receiver(body).with_reconnect(ReconnectPolicy::new(1), (), {
+            let attempts = attempts.clone();
+            move |_| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async { Ok(event_receiver(encode_message("one"))) })
+            }
+        });
+        receiver.set_max_buffered_bytes(Some(32));
+        let err = receiver.recv().await.expect_err("frame is too large");
+        assert!(
+            format!("{err:?}").contains("BufferLimitExceeded"),
+            "{err:?}"
+        );
+        assert_eq!(0, attempts.load(Ordering::SeqCst));
+    }
+
+    /// A body that never produces any data.
+    struct PendingBody;
+    impl http_body::Body for PendingBody {
+        type Data = Bytes;
+        type Error = std::io::Error;
+
+        fn poll_data(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<Result<Self::Data, Self::Error>>> {
+            std::task::Poll::Pending
+        }
+
+        fn poll_trailers(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            std::task::Poll::Pending
+        }
+    }
+
+    #[tokio::test]
+    async fn idle_streams_are_reconnected() {
+        let sleep_impl = SharedAsyncSleep::new(TokioSleep::new());
+        let policy = ReconnectPolicy::new(1).initial_backoff(Duration::from_millis(10));
+        let mut receiver = EventReceiver::new(Receiver::new(
+            Unmarshaller,
+            SdkBody::from_body_0_4(PendingBody),
+        ))
+        .with_reconnect(policy, (), {
+            let sleep_impl = sleep_impl.clone();
+            move |_| {
+                let mut receiver = event_receiver(encode_message("one"));
+                receiver.set_sleep_impl(Some(sleep_impl.clone()));
+                Box::pin(async move { Ok(receiver) })
+            }
+        });
+        receiver.set_sleep_impl(Some(sleep_impl));
+        receiver.set_idle_timeout(Some(Duration::from_millis(50)));
+        assert_eq!(Some("one".to_string()), receiver.recv().await.unwrap());
+        // The idle timeout carries over to the reconnected stream
+        assert_eq!(
+            Some(Duration::from_millis(50)),
+            receiver.inner.idle_timeout()
+        );
+        assert_eq!(None, receiver.recv().await.unwrap());
     }
 }
@@ -0,0 +1,194 @@
+/*
+ *  Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ *  SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_json::deserialize::error::DeserializeError;
+use aws_smithy_json::deserialize::stream::JsonArrayStream;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use std::fmt;
+
+type ParseItem<T> = fn(&[u8]) -> Result<T, DeserializeError>;
+type ParseOutput<O> = fn(&[u8]) -> Result<O, BoxError>;
+
+/// Items of a list member of an operation output, deserialized as the response body is received.
+///
+/// Items are received with [`next`](Self::next). Once all items have been received,
+/// [`into_output`](Self::into_output) deserializes the other members of the output. The streamed
+/// list member of that output is empty.
+pub struct ItemStream<T, O> {
+    body: ByteStream,
+    splitter: JsonArrayStream,
+    body_complete: bool,
+    parse_item: ParseItem<T>,
+    parse_output: ParseOutput<O>,
+}
+
+impl<T, O> fmt::Debug for ItemStream<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ItemStream")
+            .field("body", &self.body)
+            .field("splitter", &self.splitter)
+            .field("body_complete", &self.body_complete)
+            .finish()
+    }
+}
+
+impl<T, O> ItemStream<T, O> {
+    pub(crate) fn new(
+        body: SdkBody,
+        member: &str,
+        parse_item: ParseItem<T>,
+        parse_output: ParseOutput<O>,
+    ) -> Self {
+        Self {
+            body: ByteStream::new(body),
+            splitter: JsonArrayStream::new(member),
+            body_complete: false,
+            parse_item,
+            parse_output,
+        }
+    }
+
+    /// Receives the next item. Returns `Ok(None)` once all items have been received.
+    pub async fn next(&mut self) -> Result<Option<T>, BoxError> {
+        loop {
+            if let Some(item) = self.splitter.next_element() {
+                return Ok(Some((self.parse_item)(&item)?));
+            }
+            if self.splitter.is_array_complete() || !self.receive().await? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Receives the rest of the response, and deserializes the members of the output other than
+    /// the streamed list member.
+    ///
+    /// Items that haven't been received with [`next`](Self::next) are discarded.
+    pub async fn into_output(mut self) -> Result<O, BoxError> {
+        while self.receive().await? {
+            // discard the items that weren't received
+            while self.splitter.next_element().is_some() {}
+        }
+        let remainder = self.splitter.finish()?;
+        (self.parse_output)(&remainder)
+    }
+
+    /// Feeds the next chunk of the body into the splitter. Returns `false` once the body is complete.
+    async fn receive(&mut self) -> Result<bool, BoxError> {
+        if self.body_complete {
+            return Ok(false);
+        }
+        match self.body.next().await {
+            Some(chunk) => {
+                self.splitter.push(&chunk?);
+                Ok(true)
+            }
+            None => {
+                self.body_complete = true;
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ItemStream;
+    use aws_smithy_json::deserialize::error::DeserializeError;
+    use aws_smithy_json::deserialize::json_token_iter;
+    use aws_smithy_json::deserialize::token::{expect_number_or_null, expect_start_object};
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_types::body::SdkBody;
+    use bytes::Bytes;
+    use std::collections::VecDeque;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    fn parse_item(item: &[u8]) -> Result<u64, DeserializeError> {
+        let mut tokens = json_token_iter(item).peekable();
+        let value = expect_number_or_null(tokens.next())?
+            .ok_or_else(|| DeserializeError::custom("expected a number"))?;
+        Ok(value.to_f64_lossy() as u64)
+    }
+
+    fn parse_output(output: &[u8]) -> Result<String, BoxError> {
+        let mut tokens = json_token_iter(output).peekable();
+        expect_start_object(tokens.next())?;
+        Ok(String::from_utf8(output.to_vec())?)
+    }
+
+    /// A body that produces each of its chunks separately.
+    struct ChunkedBody(VecDeque<Bytes>);
+    impl http_body::Body for ChunkedBody {
+        type Data = Bytes;
+        type Error = std::io::Error;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Ready(self.0.pop_front().map(Ok))
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn chunked_body(chunks: &[&'static str]) -> SdkBody {
+        SdkBody::from_body_0_4(ChunkedBody(
+            chunks
+                .iter()
+                .map(|chunk| Bytes::from_static(chunk.as_bytes()))
+                .collect(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn items_are_received_before_the_rest_of_the_output() {
+        let body = chunked_body(&[
+            r#"{"Count":3,"Items":[1,"#,
+            r#"2,3],"Next":"#,
+            r#""token"}"#,
+        ]);
+        let mut stream = ItemStream::new(body, "Items", parse_item, parse_output);
+        let mut items = Vec::new();
+        while let Some(item) = stream.next().await.unwrap() {
+            items.push(item);
+        }
+        assert_eq!(vec![1, 2, 3], items);
+        assert_eq!(
+            r#"{"Count":3,"Items":[],"Next":"token"}"#,
+            stream.into_output().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn items_that_were_not_received_are_discarded() {
+        let body = SdkBody::from(r#"{"Items":[1,2,3],"Next":null}"#);
+        let mut stream = ItemStream::new(body, "Items", parse_item, parse_output);
+        assert_eq!(Some(1), stream.next().await.unwrap());
+        assert_eq!(
+            r#"{"Items":[],"Next":null}"#,
+            stream.into_output().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_items_and_incomplete_bodies_are_errors() {
+        let body = SdkBody::from(r#"{"Items":[true]}"#);
+        let mut stream = ItemStream::new(body, "Items", parse_item, parse_output);
+        assert!(stream.next().await.is_err());
+
+        let body = SdkBody::from(r#"{"Items":[1"#);
+        let stream = ItemStream::new(body, "Items", parse_item, parse_output);
+        assert!(stream.into_output().await.is_err());
+    }
+}
@@ -25,6 +25,8 @@ mod idempotency_token;
 #[allow(dead_code)]
 mod json_errors;
 #[allow(unused)]
+mod json_item_stream;
+#[allow(unused)]
 mod rest_xml_unwrapped_errors;
 #[allow(unused)]
 mod rest_xml_wrapped_errors;